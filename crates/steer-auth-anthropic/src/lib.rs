@@ -9,10 +9,10 @@ use std::time::{Duration, SystemTime};
 
 use steer_auth_plugin::AuthPlugin;
 use steer_auth_plugin::{
-    AnthropicAuth, AuthDirective, AuthError, AuthErrorAction, AuthErrorContext, AuthHeaderContext,
-    AuthHeaderProvider, AuthMethod, AuthProgress, AuthStorage, AuthTokens, AuthenticationFlow,
-    Credential, CredentialType, DynAuthenticationFlow, HeaderPair, InstructionPolicy, ProviderId,
-    QueryParam, Result,
+    AccountInfo, AnthropicAuth, AuthDirective, AuthError, AuthErrorAction, AuthErrorContext,
+    AuthHeaderContext, AuthHeaderProvider, AuthMethod, AuthProgress, AuthStorage, AuthTokens,
+    AuthenticationFlow, Credential, CredentialType, DynAuthenticationFlow, HeaderPair,
+    InstructionPolicy, ProviderId, QueryParam, Result,
 };
 
 const PROVIDER_ID: &str = "anthropic";
@@ -21,6 +21,7 @@ const TOKEN_URL: &str = "https://console.anthropic.com/v1/oauth/token";
 const CLIENT_ID: &str = "9d1c250a-e61b-44d9-88ed-5944d1962f5e";
 const REDIRECT_URI: &str = "https://console.anthropic.com/oauth/code/callback";
 const SCOPES: &str = "org:create_api_key user:profile user:inference";
+const PROFILE_URL: &str = "https://api.anthropic.com/api/oauth/profile";
 
 #[derive(Debug)]
 pub struct PkceChallenge {
@@ -46,7 +47,7 @@ impl AnthropicOAuth {
         Self {
             client_id: CLIENT_ID.to_string(),
             redirect_uri: REDIRECT_URI.to_string(),
-            http_client: reqwest::Client::new(),
+            http_client: steer_auth_plugin::http::auth_client(),
         }
     }
 
@@ -228,6 +229,62 @@ impl AnthropicOAuth {
             id_token: None,
         })
     }
+
+    /// Look up the account and organization the token was issued for
+    /// (granted by the `user:profile` scope).
+    pub async fn fetch_account_info(&self, access_token: &str) -> Result<AccountInfo> {
+        #[derive(Deserialize)]
+        struct ProfileResponse {
+            #[serde(default)]
+            account: Option<Account>,
+            #[serde(default)]
+            organization: Option<Organization>,
+        }
+
+        #[derive(Deserialize)]
+        struct Account {
+            #[serde(default)]
+            uuid: Option<String>,
+            #[serde(default, alias = "email_address")]
+            email: Option<String>,
+        }
+
+        #[derive(Deserialize)]
+        struct Organization {
+            #[serde(default)]
+            name: Option<String>,
+        }
+
+        let mut request = self.http_client.get(PROFILE_URL);
+        for header in get_oauth_headers(access_token) {
+            request = request.header(header.name, header.value);
+        }
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+                return Err(AuthError::ReauthRequired);
+            }
+
+            let status = response.status();
+            return Err(AuthError::InvalidResponse(format!(
+                "Profile lookup failed with status {status}"
+            )));
+        }
+
+        let profile: ProfileResponse = response.json().await.map_err(|e| {
+            AuthError::InvalidResponse(format!("Failed to parse profile response: {e}"))
+        })?;
+        let (account_id, email) = profile
+            .account
+            .map_or((None, None), |account| (account.uuid, account.email));
+
+        Ok(AccountInfo {
+            account_id,
+            email,
+            organization: profile.organization.and_then(|org| org.name),
+        })
+    }
 }
 
 fn resolve_callback_input(input: &str, verifier: &str) -> Result<(String, String)> {
@@ -474,7 +531,16 @@ pub struct AnthropicAuthState {
 
 #[derive(Debug, Clone)]
 pub enum AnthropicAuthStateKind {
-    OAuthStarted { verifier: String, auth_url: String },
+    OAuthStarted {
+        verifier: String,
+        auth_url: String,
+    },
+    ExchangingCode {
+        code: String,
+        state: String,
+        verifier: String,
+    },
+    Refreshing,
 }
 
 pub struct AnthropicOAuthFlow {
@@ -489,6 +555,26 @@ impl AnthropicOAuthFlow {
             oauth_client: AnthropicOAuth::new(),
         }
     }
+
+    fn start_oauth(&self) -> AnthropicAuthStateKind {
+        let pkce = AnthropicOAuth::generate_pkce();
+        let auth_url = self.oauth_client.build_auth_url(&pkce);
+
+        AnthropicAuthStateKind::OAuthStarted {
+            verifier: pkce.verifier,
+            auth_url,
+        }
+    }
+
+    /// Expired OAuth tokens that can still be refreshed make a browser login unnecessary.
+    async fn has_refreshable_tokens(&self) -> Result<bool> {
+        Ok(matches!(
+            self.storage
+                .get_credential(PROVIDER_ID, CredentialType::OAuth2)
+                .await?,
+            Some(Credential::OAuth2(tokens)) if tokens_need_refresh(&tokens)
+        ))
+    }
 }
 
 #[async_trait]
@@ -502,17 +588,14 @@ impl AuthenticationFlow for AnthropicOAuthFlow {
     async fn start_auth(&self, method: AuthMethod) -> Result<Self::State> {
         match method {
             AuthMethod::OAuth => {
-                let pkce = AnthropicOAuth::generate_pkce();
-                let auth_url = self.oauth_client.build_auth_url(&pkce);
-
-                Ok(AnthropicAuthState {
-                    kind: AnthropicAuthStateKind::OAuthStarted {
-                        verifier: pkce.verifier,
-                        auth_url,
-                    },
-                })
+                let kind = if self.has_refreshable_tokens().await? {
+                    AnthropicAuthStateKind::Refreshing
+                } else {
+                    self.start_oauth()
+                };
+                Ok(AnthropicAuthState { kind })
             }
-            AuthMethod::ApiKey => Err(AuthError::UnsupportedMethod {
+            AuthMethod::ApiKey | AuthMethod::DeviceCode => Err(AuthError::UnsupportedMethod {
                 method: format!("{method:?}"),
                 provider: PROVIDER_ID.to_string(),
             }),
@@ -525,13 +608,16 @@ impl AuthenticationFlow for AnthropicOAuthFlow {
         method: AuthMethod,
     ) -> Result<AuthProgress> {
         match method {
-            AuthMethod::OAuth => {
-                let AnthropicAuthStateKind::OAuthStarted { auth_url, .. } = &state.kind;
-                Ok(AuthProgress::OAuthStarted {
-                    auth_url: auth_url.clone(),
-                })
-            }
-            AuthMethod::ApiKey => Err(AuthError::UnsupportedMethod {
+            AuthMethod::OAuth => match &state.kind {
+                AnthropicAuthStateKind::OAuthStarted { auth_url, .. } => {
+                    Ok(AuthProgress::OAuthStarted {
+                        auth_url: auth_url.clone(),
+                    })
+                }
+                AnthropicAuthStateKind::ExchangingCode { .. } => Ok(AuthProgress::ExchangingCode),
+                AnthropicAuthStateKind::Refreshing => Ok(AuthProgress::Refreshing),
+            },
+            AuthMethod::ApiKey | AuthMethod::DeviceCode => Err(AuthError::UnsupportedMethod {
                 method: format!("{method:?}"),
                 provider: PROVIDER_ID.to_string(),
             }),
@@ -549,9 +635,23 @@ impl AuthenticationFlow for AnthropicOAuthFlow {
 
                 let (code, state_param) = resolve_callback_input(input, verifier)?;
 
+                // The exchange runs on the next poll so clients can report it.
+                let verifier = std::mem::take(verifier);
+                state.kind = AnthropicAuthStateKind::ExchangingCode {
+                    code,
+                    state: state_param,
+                    verifier,
+                };
+                Ok(AuthProgress::ExchangingCode)
+            }
+            AnthropicAuthStateKind::ExchangingCode {
+                code,
+                state: state_param,
+                verifier,
+            } => {
                 let tokens = self
                     .oauth_client
-                    .exchange_code_for_tokens(&code, &state_param, verifier)
+                    .exchange_code_for_tokens(code, state_param, verifier)
                     .await?;
 
                 self.storage
@@ -560,6 +660,18 @@ impl AuthenticationFlow for AnthropicOAuthFlow {
 
                 Ok(AuthProgress::Complete)
             }
+            AnthropicAuthStateKind::Refreshing => {
+                if force_refresh(&self.storage, &self.oauth_client)
+                    .await
+                    .is_ok()
+                {
+                    return Ok(AuthProgress::Complete);
+                }
+
+                // Stored tokens could not be refreshed; fall back to a browser login.
+                state.kind = self.start_oauth();
+                self.get_initial_progress(state, AuthMethod::OAuth).await
+            }
         }
     }
 
@@ -677,6 +789,23 @@ impl AuthPlugin for AnthropicAuthPlugin {
 
         Ok(false)
     }
+
+    async fn account_info(&self, storage: Arc<dyn AuthStorage>) -> Result<Option<AccountInfo>> {
+        if storage
+            .get_credential(PROVIDER_ID, CredentialType::OAuth2)
+            .await?
+            .is_none()
+        {
+            return Ok(None);
+        }
+
+        let oauth = AnthropicOAuth::new();
+        let tokens = refresh_if_needed(&storage, &oauth).await?;
+        oauth
+            .fetch_account_info(&tokens.access_token)
+            .await
+            .map(Some)
+    }
 }
 
 #[cfg(test)]
@@ -33,6 +33,12 @@ impl CallbackServerHandle {
             )),
         }
     }
+
+    /// Stop the server and wait for its listener to be released.
+    pub async fn shutdown(&mut self) {
+        self.task.abort();
+        let _ = (&mut self.task).await;
+    }
 }
 
 impl Drop for CallbackServerHandle {
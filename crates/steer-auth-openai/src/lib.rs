@@ -5,15 +5,16 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::net::SocketAddr;
 use std::sync::Arc;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tracing::info;
 
 use steer_auth_plugin::AuthPlugin;
 use steer_auth_plugin::{
-    AuthDirective, AuthError, AuthErrorAction, AuthErrorContext, AuthHeaderContext,
+    AccountInfo, AuthDirective, AuthError, AuthErrorAction, AuthErrorContext, AuthHeaderContext,
     AuthHeaderProvider, AuthMethod, AuthProgress, AuthSource, AuthStorage, AuthTokens,
-    AuthenticationFlow, Credential, CredentialType, DynAuthenticationFlow, HeaderPair,
-    InstructionPolicy, ModelId, ModelVisibilityPolicy, OpenAiResponsesAuth, ProviderId, Result,
+    AuthenticationFlow, Credential, CredentialType, DeviceCodePoller, DevicePollAction,
+    DevicePollResponse, DynAuthenticationFlow, HeaderPair, InstructionPolicy, ModelId,
+    ModelVisibilityPolicy, OpenAiResponsesAuth, ProviderId, Result,
 };
 use steer_tools::tools::{
     AST_GREP_TOOL_NAME, BASH_TOOL_NAME, DISPATCH_AGENT_TOOL_NAME, EDIT_TOOL_NAME, FETCH_TOOL_NAME,
@@ -33,6 +34,12 @@ const SCOPES: &str = "openid profile email offline_access";
 const ORIGINATOR: &str = "codex_cli_rs";
 const CALLBACK_PATH: &str = "/auth/callback";
 const CALLBACK_PORT: u16 = 1455;
+const DEVICE_USER_CODE_URL: &str = "https://auth.openai.com/api/accounts/deviceauth/usercode";
+const DEVICE_TOKEN_URL: &str = "https://auth.openai.com/api/accounts/deviceauth/token";
+const DEVICE_VERIFICATION_URL: &str = "https://auth.openai.com/codex/device";
+const DEVICE_REDIRECT_URI: &str = "https://auth.openai.com/deviceauth/callback";
+const DEVICE_CODE_DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+const DEVICE_CODE_TIMEOUT: Duration = Duration::from_secs(15 * 60);
 
 const CODEX_BASE_URL: &str = "https://chatgpt.com/backend-api/codex/responses";
 const OPENAI_BETA: &str = "responses=experimental";
@@ -173,6 +180,24 @@ pub struct PkceChallenge {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ChatGptAccountId(pub String);
 
+/// A device code issued by the OpenAI device authorization endpoint.
+#[derive(Debug, Clone)]
+pub struct DeviceCodeGrant {
+    pub device_auth_id: String,
+    pub user_code: String,
+    pub interval: Duration,
+}
+
+/// Result of polling the device token endpoint.
+#[derive(Debug)]
+pub enum DeviceTokenPoll {
+    Waiting(DevicePollResponse),
+    Authorized {
+        authorization_code: String,
+        code_verifier: String,
+    },
+}
+
 #[derive(Clone)]
 pub struct OpenAIOAuth {
     client_id: String,
@@ -191,7 +216,7 @@ impl OpenAIOAuth {
         Self {
             client_id: CLIENT_ID.to_string(),
             redirect_uri: REDIRECT_URI.to_string(),
-            http_client: reqwest::Client::new(),
+            http_client: steer_auth_plugin::http::auth_client(),
         }
     }
 
@@ -230,6 +255,111 @@ impl OpenAIOAuth {
         &self,
         code: &str,
         pkce_verifier: &str,
+    ) -> Result<AuthTokens> {
+        self.exchange_code(code, pkce_verifier, &self.redirect_uri)
+            .await
+    }
+
+    /// Start a device authorization: the user enters the returned code at
+    /// the verification URL instead of being redirected to a local server.
+    pub async fn request_device_code(&self) -> Result<DeviceCodeGrant> {
+        #[derive(Serialize)]
+        struct UserCodeRequest<'a> {
+            client_id: &'a str,
+        }
+
+        #[derive(Deserialize)]
+        struct UserCodeResponse {
+            device_auth_id: String,
+            #[serde(alias = "usercode")]
+            user_code: String,
+            #[serde(default)]
+            interval: Option<serde_json::Value>,
+        }
+
+        let response = self
+            .http_client
+            .post(DEVICE_USER_CODE_URL)
+            .json(&UserCodeRequest {
+                client_id: &self.client_id,
+            })
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(AuthError::InvalidResponse(format!(
+                "Device code request failed with status {status}: {error_text}"
+            )));
+        }
+
+        let body: UserCodeResponse = response.json().await.map_err(|e| {
+            AuthError::InvalidResponse(format!("Failed to parse device code response: {e}"))
+        })?;
+
+        Ok(DeviceCodeGrant {
+            device_auth_id: body.device_auth_id,
+            user_code: body.user_code,
+            interval: parse_poll_interval(body.interval.as_ref()),
+        })
+    }
+
+    pub async fn poll_device_token(&self, grant: &DeviceCodeGrant) -> Result<DeviceTokenPoll> {
+        #[derive(Serialize)]
+        struct DeviceTokenRequest<'a> {
+            device_auth_id: &'a str,
+            user_code: &'a str,
+        }
+
+        #[derive(Deserialize)]
+        struct DeviceTokenResponse {
+            authorization_code: String,
+            code_verifier: String,
+        }
+
+        let response = self
+            .http_client
+            .post(DEVICE_TOKEN_URL)
+            .json(&DeviceTokenRequest {
+                device_auth_id: &grant.device_auth_id,
+                user_code: &grant.user_code,
+            })
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status.is_success() {
+            let body: DeviceTokenResponse = response.json().await.map_err(|e| {
+                AuthError::InvalidResponse(format!("Failed to parse device token response: {e}"))
+            })?;
+            return Ok(DeviceTokenPoll::Authorized {
+                authorization_code: body.authorization_code,
+                code_verifier: body.code_verifier,
+            });
+        }
+
+        let error_text = response.text().await.unwrap_or_default();
+        classify_device_poll_error(status, &error_text).map(DeviceTokenPoll::Waiting)
+    }
+
+    pub async fn exchange_device_code_for_tokens(
+        &self,
+        code: &str,
+        code_verifier: &str,
+    ) -> Result<AuthTokens> {
+        self.exchange_code(code, code_verifier, DEVICE_REDIRECT_URI)
+            .await
+    }
+
+    async fn exchange_code(
+        &self,
+        code: &str,
+        pkce_verifier: &str,
+        redirect_uri: &str,
     ) -> Result<AuthTokens> {
         #[derive(Serialize)]
         struct TokenRequest {
@@ -252,7 +382,7 @@ impl OpenAIOAuth {
             grant_type: "authorization_code".to_string(),
             client_id: self.client_id.clone(),
             code: code.to_string(),
-            redirect_uri: self.redirect_uri.clone(),
+            redirect_uri: redirect_uri.to_string(),
             code_verifier: pkce_verifier.to_string(),
         };
 
@@ -579,6 +709,56 @@ fn parse_callback_input(input: &str) -> Result<CallbackResponse> {
     ))
 }
 
+/// Interpret a non-success response from the device token endpoint.
+///
+/// OpenAI answers 403/404 while the user has not finished authorizing; RFC 8628
+/// error codes in the body are honoured as well.
+fn classify_device_poll_error(
+    status: reqwest::StatusCode,
+    body: &str,
+) -> Result<DevicePollResponse> {
+    let error_code = serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|value| {
+            let error = value.get("error")?;
+            error
+                .as_str()
+                .or_else(|| error.get("code").and_then(|code| code.as_str()))
+                .map(str::to_string)
+        });
+
+    match error_code.as_deref() {
+        Some("authorization_pending") => return Ok(DevicePollResponse::Pending),
+        Some("slow_down") => return Ok(DevicePollResponse::SlowDown),
+        Some("access_denied") => return Ok(DevicePollResponse::Denied),
+        Some("expired_token") => return Ok(DevicePollResponse::Expired),
+        _ => {}
+    }
+
+    match status {
+        reqwest::StatusCode::FORBIDDEN | reqwest::StatusCode::NOT_FOUND => {
+            Ok(DevicePollResponse::Pending)
+        }
+        reqwest::StatusCode::TOO_MANY_REQUESTS => Ok(DevicePollResponse::SlowDown),
+        reqwest::StatusCode::GONE => Ok(DevicePollResponse::Expired),
+        _ => Err(AuthError::InvalidResponse(format!(
+            "Device token poll failed with status {status}: {body}"
+        ))),
+    }
+}
+
+/// The interval may be sent as a number or a numeric string.
+fn parse_poll_interval(value: Option<&serde_json::Value>) -> Duration {
+    value
+        .and_then(|value| {
+            value
+                .as_u64()
+                .or_else(|| value.as_str().and_then(|s| s.trim().parse().ok()))
+        })
+        .filter(|secs| *secs > 0)
+        .map_or(DEVICE_CODE_DEFAULT_INTERVAL, Duration::from_secs)
+}
+
 fn generate_random_string(length: usize) -> String {
     const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-._~";
     let mut rng = rand::thread_rng();
@@ -613,7 +793,18 @@ pub enum OpenAIAuthStateKind {
         state: String,
         auth_url: String,
         callback_server: Option<CallbackServerHandle>,
+        started_at: Instant,
     },
+    ExchangingCode {
+        code: String,
+        verifier: String,
+    },
+    Refreshing,
+    DeviceCode {
+        grant: DeviceCodeGrant,
+        poller: DeviceCodePoller,
+    },
+    Cancelled,
 }
 
 pub struct OpenAIOAuthFlow {
@@ -628,6 +819,47 @@ impl OpenAIOAuthFlow {
             oauth_client: OpenAIOAuth::new(),
         }
     }
+
+    async fn start_oauth(&self) -> OpenAIAuthStateKind {
+        let pkce = OpenAIOAuth::generate_pkce();
+        let state = OpenAIOAuth::generate_state();
+        let auth_url = self.oauth_client.build_auth_url(&pkce, &state);
+
+        let callback_server = match spawn_callback_server(
+            state.clone(),
+            SocketAddr::from(([127, 0, 0, 1], CALLBACK_PORT)),
+            CALLBACK_PATH,
+        )
+        .await
+        {
+            Ok(handle) => Some(handle),
+            Err(err) => {
+                info!(
+                    "OpenAI OAuth callback server unavailable, falling back to manual paste: {}",
+                    err
+                );
+                None
+            }
+        };
+
+        OpenAIAuthStateKind::OAuthStarted {
+            verifier: pkce.verifier,
+            state,
+            auth_url,
+            callback_server,
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Expired OAuth tokens that can still be refreshed make a browser login unnecessary.
+    async fn has_refreshable_tokens(&self) -> Result<bool> {
+        Ok(matches!(
+            self.storage
+                .get_credential(PROVIDER_ID, CredentialType::OAuth2)
+                .await?,
+            Some(Credential::OAuth2(tokens)) if tokens_need_refresh(&tokens)
+        ))
+    }
 }
 
 #[async_trait]
@@ -635,42 +867,27 @@ impl AuthenticationFlow for OpenAIOAuthFlow {
     type State = OpenAIAuthState;
 
     fn available_methods(&self) -> Vec<AuthMethod> {
-        vec![AuthMethod::OAuth]
+        vec![AuthMethod::OAuth, AuthMethod::DeviceCode]
     }
 
     async fn start_auth(&self, method: AuthMethod) -> Result<Self::State> {
         match method {
-            AuthMethod::OAuth => {
-                let pkce = OpenAIOAuth::generate_pkce();
-                let state = OpenAIOAuth::generate_state();
-                let auth_url = self.oauth_client.build_auth_url(&pkce, &state);
-
-                let callback_server = match spawn_callback_server(
-                    state.clone(),
-                    SocketAddr::from(([127, 0, 0, 1], CALLBACK_PORT)),
-                    CALLBACK_PATH,
-                )
-                .await
-                {
-                    Ok(handle) => Some(handle),
-                    Err(err) => {
-                        info!(
-                            "OpenAI OAuth callback server unavailable, falling back to manual paste: {}",
-                            err
-                        );
-                        None
-                    }
-                };
-
+            AuthMethod::DeviceCode => {
+                let grant = self.oauth_client.request_device_code().await?;
+                let poller =
+                    DeviceCodePoller::new(grant.interval, DEVICE_CODE_TIMEOUT, Instant::now());
                 Ok(OpenAIAuthState {
-                    kind: OpenAIAuthStateKind::OAuthStarted {
-                        verifier: pkce.verifier,
-                        state,
-                        auth_url,
-                        callback_server,
-                    },
+                    kind: OpenAIAuthStateKind::DeviceCode { grant, poller },
                 })
             }
+            AuthMethod::OAuth => {
+                let kind = if self.has_refreshable_tokens().await? {
+                    OpenAIAuthStateKind::Refreshing
+                } else {
+                    self.start_oauth().await
+                };
+                Ok(OpenAIAuthState { kind })
+            }
             AuthMethod::ApiKey => Err(AuthError::UnsupportedMethod {
                 method: format!("{method:?}"),
                 provider: PROVIDER_ID.to_string(),
@@ -683,14 +900,18 @@ impl AuthenticationFlow for OpenAIOAuthFlow {
         state: &Self::State,
         method: AuthMethod,
     ) -> Result<AuthProgress> {
-        match method {
-            AuthMethod::OAuth => {
-                let OpenAIAuthStateKind::OAuthStarted { auth_url, .. } = &state.kind;
+        match (&state.kind, method) {
+            (OpenAIAuthStateKind::OAuthStarted { auth_url, .. }, AuthMethod::OAuth) => {
                 Ok(AuthProgress::OAuthStarted {
                     auth_url: auth_url.clone(),
                 })
             }
-            AuthMethod::ApiKey => Err(AuthError::UnsupportedMethod {
+            (OpenAIAuthStateKind::Refreshing, AuthMethod::OAuth) => Ok(AuthProgress::Refreshing),
+            (OpenAIAuthStateKind::DeviceCode { grant, .. }, AuthMethod::DeviceCode) => {
+                Ok(device_code_progress(grant))
+            }
+            (OpenAIAuthStateKind::Cancelled, _) => Err(AuthError::Cancelled),
+            _ => Err(AuthError::UnsupportedMethod {
                 method: format!("{method:?}"),
                 provider: PROVIDER_ID.to_string(),
             }),
@@ -703,6 +924,7 @@ impl AuthenticationFlow for OpenAIOAuthFlow {
                 verifier,
                 state: expected_state,
                 callback_server,
+                started_at,
                 ..
             } => {
                 let callback = if input.trim().is_empty() {
@@ -710,9 +932,9 @@ impl AuthenticationFlow for OpenAIOAuthFlow {
                         if let Some(result) = server.try_recv() {
                             result?
                         } else {
-                            return Ok(AuthProgress::InProgress(
-                                "Waiting for OAuth callback...".to_string(),
-                            ));
+                            return Ok(AuthProgress::WaitingForCallback {
+                                elapsed: started_at.elapsed(),
+                            });
                         }
                     } else {
                         return Ok(AuthProgress::NeedInput(
@@ -727,22 +949,96 @@ impl AuthenticationFlow for OpenAIOAuthFlow {
                     return Err(AuthError::StateMismatch);
                 }
 
+                // Replacing the state drops the callback server; the exchange runs on the next poll.
+                let verifier = std::mem::take(verifier);
+                state.kind = OpenAIAuthStateKind::ExchangingCode {
+                    code: callback.code,
+                    verifier,
+                };
+                Ok(AuthProgress::ExchangingCode)
+            }
+            OpenAIAuthStateKind::ExchangingCode { code, verifier } => {
                 let tokens = self
                     .oauth_client
-                    .exchange_code_for_tokens(&callback.code, verifier)
+                    .exchange_code_for_tokens(code, verifier)
                     .await?;
 
                 self.storage
                     .set_credential(PROVIDER_ID, Credential::OAuth2(tokens))
                     .await?;
 
-                if let Some(server) = callback_server.take() {
-                    drop(server);
+                Ok(AuthProgress::Complete)
+            }
+            OpenAIAuthStateKind::Refreshing => {
+                match force_refresh(&self.storage, &self.oauth_client).await {
+                    Ok(_) => Ok(AuthProgress::Complete),
+                    Err(err) => {
+                        info!(
+                            "OpenAI token refresh failed, starting browser login: {}",
+                            err
+                        );
+                        state.kind = self.start_oauth().await;
+                        self.get_initial_progress(state, AuthMethod::OAuth).await
+                    }
+                }
+            }
+            OpenAIAuthStateKind::DeviceCode { grant, poller } => {
+                match poller.next_action(Instant::now()) {
+                    DevicePollAction::Wait(_) => return Ok(device_code_progress(grant)),
+                    DevicePollAction::Expired => {
+                        return Ok(AuthProgress::Error(
+                            AuthError::DeviceCodeExpired.to_string(),
+                        ));
+                    }
+                    DevicePollAction::Poll => {}
+                }
+
+                let (response, authorization) =
+                    match self.oauth_client.poll_device_token(grant).await? {
+                        DeviceTokenPoll::Waiting(response) => (response, None),
+                        DeviceTokenPoll::Authorized {
+                            authorization_code,
+                            code_verifier,
+                        } => (
+                            DevicePollResponse::Authorized,
+                            Some((authorization_code, code_verifier)),
+                        ),
+                    };
+
+                match poller.record(response, Instant::now()) {
+                    Ok(true) => {}
+                    Ok(false) => return Ok(device_code_progress(grant)),
+                    Err(err) => return Ok(AuthProgress::Error(err.to_string())),
                 }
 
+                let (code, code_verifier) = authorization.ok_or_else(|| {
+                    AuthError::InvalidResponse("Missing device authorization code".to_string())
+                })?;
+                let tokens = self
+                    .oauth_client
+                    .exchange_device_code_for_tokens(&code, &code_verifier)
+                    .await?;
+
+                self.storage
+                    .set_credential(PROVIDER_ID, Credential::OAuth2(tokens))
+                    .await?;
+
                 Ok(AuthProgress::Complete)
             }
+            OpenAIAuthStateKind::Cancelled => Err(AuthError::Cancelled),
+        }
+    }
+
+    async fn cancel(&self, state: &mut Self::State) -> Result<()> {
+        let previous = std::mem::replace(&mut state.kind, OpenAIAuthStateKind::Cancelled);
+        if let OpenAIAuthStateKind::OAuthStarted {
+            callback_server: Some(mut server),
+            ..
+        } = previous
+        {
+            server.shutdown().await;
         }
+        Ok(())
     }
 
     async fn is_authenticated(&self) -> Result<bool> {
@@ -762,6 +1058,13 @@ impl AuthenticationFlow for OpenAIOAuthFlow {
     }
 }
 
+fn device_code_progress(grant: &DeviceCodeGrant) -> AuthProgress {
+    AuthProgress::DeviceCode {
+        verification_uri: DEVICE_VERIFICATION_URL.to_string(),
+        user_code: grant.user_code.clone(),
+    }
+}
+
 #[derive(Clone)]
 struct OpenAiHeaderProvider {
     storage: Arc<dyn AuthStorage>,
@@ -861,7 +1164,7 @@ impl AuthPlugin for OpenAiAuthPlugin {
     }
 
     fn supported_methods(&self) -> Vec<AuthMethod> {
-        vec![AuthMethod::OAuth]
+        vec![AuthMethod::OAuth, AuthMethod::DeviceCode]
     }
 
     fn create_flow(&self, storage: Arc<dyn AuthStorage>) -> Option<Box<dyn DynAuthenticationFlow>> {
@@ -899,6 +1202,30 @@ impl AuthPlugin for OpenAiAuthPlugin {
         Ok(false)
     }
 
+    async fn account_info(&self, storage: Arc<dyn AuthStorage>) -> Result<Option<AccountInfo>> {
+        let Some(Credential::OAuth2(tokens)) = storage
+            .get_credential(PROVIDER_ID, CredentialType::OAuth2)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let Some(id_token) = tokens.id_token.as_deref() else {
+            return Ok(None);
+        };
+
+        let account_id = extract_chatgpt_account_id(id_token)?;
+        let email = decode_jwt_payload(id_token)?
+            .get("email")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+
+        Ok(Some(AccountInfo {
+            account_id: Some(account_id.0),
+            email,
+            organization: None,
+        }))
+    }
+
     fn model_visibility(&self) -> Option<Box<dyn ModelVisibilityPolicy>> {
         Some(Box::new(OpenAiModelVisibility))
     }
@@ -907,7 +1234,42 @@ impl AuthPlugin for OpenAiAuthPlugin {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
     use steer_auth_plugin::{AuthMethod, AuthSource};
+    use tokio::sync::Mutex;
+
+    #[derive(Default)]
+    struct TestAuthStorage {
+        credentials: Mutex<HashMap<String, Credential>>,
+    }
+
+    #[async_trait]
+    impl AuthStorage for TestAuthStorage {
+        async fn get_credential(
+            &self,
+            provider: &str,
+            credential_type: CredentialType,
+        ) -> Result<Option<Credential>> {
+            let key = format!("{provider}-{credential_type}");
+            Ok(self.credentials.lock().await.get(&key).cloned())
+        }
+
+        async fn set_credential(&self, provider: &str, credential: Credential) -> Result<()> {
+            let key = format!("{}-{}", provider, credential.credential_type());
+            self.credentials.lock().await.insert(key, credential);
+            Ok(())
+        }
+
+        async fn remove_credential(
+            &self,
+            provider: &str,
+            credential_type: CredentialType,
+        ) -> Result<()> {
+            let key = format!("{provider}-{credential_type}");
+            self.credentials.lock().await.remove(&key);
+            Ok(())
+        }
+    }
 
     #[test]
     fn test_auth_url_building() {
@@ -962,6 +1324,37 @@ mod tests {
         assert_eq!(account_id.0, "acct_nested");
     }
 
+    #[tokio::test]
+    async fn test_account_info_from_stored_id_token() {
+        let storage: Arc<dyn AuthStorage> = Arc::new(TestAuthStorage::default());
+        let plugin = OpenAiAuthPlugin::new();
+        assert_eq!(plugin.account_info(storage.clone()).await.unwrap(), None);
+
+        let id_token = make_jwt(serde_json::json!({
+            CHATGPT_ACCOUNT_ID_NESTED_CLAIM: {
+                "chatgpt_account_id": "acct_stored"
+            },
+            "email": "dev@example.com",
+            "exp": 1_700_000_000u64
+        }));
+        storage
+            .set_credential(
+                PROVIDER_ID,
+                Credential::OAuth2(AuthTokens {
+                    access_token: "access".to_string(),
+                    refresh_token: "refresh".to_string(),
+                    expires_at: UNIX_EPOCH,
+                    id_token: Some(id_token),
+                }),
+            )
+            .await
+            .unwrap();
+
+        let info = plugin.account_info(storage).await.unwrap().unwrap();
+        assert_eq!(info.account_id.as_deref(), Some("acct_stored"));
+        assert_eq!(info.email.as_deref(), Some("dev@example.com"));
+    }
+
     #[test]
     fn test_resolve_expires_at_from_token() {
         let payload = serde_json::json!({
@@ -1011,6 +1404,162 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_classify_device_poll_error() {
+        use reqwest::StatusCode;
+
+        assert_eq!(
+            classify_device_poll_error(StatusCode::FORBIDDEN, "").unwrap(),
+            DevicePollResponse::Pending
+        );
+        assert_eq!(
+            classify_device_poll_error(StatusCode::NOT_FOUND, "not found").unwrap(),
+            DevicePollResponse::Pending
+        );
+        assert_eq!(
+            classify_device_poll_error(StatusCode::BAD_REQUEST, r#"{"error":"slow_down"}"#)
+                .unwrap(),
+            DevicePollResponse::SlowDown
+        );
+        assert_eq!(
+            classify_device_poll_error(
+                StatusCode::BAD_REQUEST,
+                r#"{"error":{"code":"expired_token"}}"#
+            )
+            .unwrap(),
+            DevicePollResponse::Expired
+        );
+        assert_eq!(
+            classify_device_poll_error(StatusCode::BAD_REQUEST, r#"{"error":"access_denied"}"#)
+                .unwrap(),
+            DevicePollResponse::Denied
+        );
+        assert!(classify_device_poll_error(StatusCode::INTERNAL_SERVER_ERROR, "boom").is_err());
+    }
+
+    #[test]
+    fn test_parse_poll_interval() {
+        assert_eq!(
+            parse_poll_interval(Some(&serde_json::json!("7"))),
+            Duration::from_secs(7)
+        );
+        assert_eq!(
+            parse_poll_interval(Some(&serde_json::json!(3))),
+            Duration::from_secs(3)
+        );
+        assert_eq!(parse_poll_interval(None), DEVICE_CODE_DEFAULT_INTERVAL);
+        assert_eq!(
+            parse_poll_interval(Some(&serde_json::json!(0))),
+            DEVICE_CODE_DEFAULT_INTERVAL
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oauth_flow_progress_transitions() {
+        let storage: Arc<dyn AuthStorage> = Arc::new(TestAuthStorage::default());
+        let flow = OpenAIOAuthFlow::new(storage);
+        let callback_server = spawn_callback_server(
+            "state-123".to_string(),
+            SocketAddr::from(([127, 0, 0, 1], 0)),
+            CALLBACK_PATH,
+        )
+        .await
+        .unwrap();
+        let mut state = OpenAIAuthState {
+            kind: OpenAIAuthStateKind::OAuthStarted {
+                verifier: "verifier-123".to_string(),
+                state: "state-123".to_string(),
+                auth_url: "https://auth.openai.com/oauth/authorize".to_string(),
+                callback_server: Some(callback_server),
+                started_at: Instant::now(),
+            },
+        };
+
+        let progress = flow
+            .get_initial_progress(&state, AuthMethod::OAuth)
+            .await
+            .unwrap();
+        assert!(matches!(progress, AuthProgress::OAuthStarted { .. }));
+
+        let progress = flow.handle_input(&mut state, "").await.unwrap();
+        assert!(matches!(progress, AuthProgress::WaitingForCallback { .. }));
+
+        let progress = flow
+            .handle_input(
+                &mut state,
+                "http://localhost:1455/auth/callback?code=abc123&state=state-123",
+            )
+            .await
+            .unwrap();
+        assert!(matches!(progress, AuthProgress::ExchangingCode));
+        match &state.kind {
+            OpenAIAuthStateKind::ExchangingCode { code, verifier } => {
+                assert_eq!(code, "abc123");
+                assert_eq!(verifier, "verifier-123");
+            }
+            other => panic!("Expected ExchangingCode state, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancel_releases_callback_port() {
+        let storage: Arc<dyn AuthStorage> = Arc::new(TestAuthStorage::default());
+        let flow = OpenAIOAuthFlow::new(storage);
+
+        let mut state = flow.start_auth(AuthMethod::OAuth).await.unwrap();
+        assert!(matches!(
+            state.kind,
+            OpenAIAuthStateKind::OAuthStarted {
+                callback_server: Some(_),
+                ..
+            }
+        ));
+
+        flow.cancel(&mut state).await.unwrap();
+        assert!(matches!(state.kind, OpenAIAuthStateKind::Cancelled));
+        assert!(matches!(
+            flow.handle_input(&mut state, "").await,
+            Err(AuthError::Cancelled)
+        ));
+
+        let mut restarted = flow.start_auth(AuthMethod::OAuth).await.unwrap();
+        assert!(matches!(
+            restarted.kind,
+            OpenAIAuthStateKind::OAuthStarted {
+                callback_server: Some(_),
+                ..
+            }
+        ));
+        flow.cancel(&mut restarted).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_oauth_start_refreshes_expired_tokens() {
+        let storage: Arc<dyn AuthStorage> = Arc::new(TestAuthStorage::default());
+        storage
+            .set_credential(
+                PROVIDER_ID,
+                Credential::OAuth2(AuthTokens {
+                    access_token: "access".to_string(),
+                    refresh_token: "refresh".to_string(),
+                    expires_at: UNIX_EPOCH,
+                    id_token: None,
+                }),
+            )
+            .await
+            .unwrap();
+        let flow = OpenAIOAuthFlow::new(storage);
+
+        let state = flow.start_auth(AuthMethod::OAuth).await.unwrap();
+        let progress = flow
+            .get_initial_progress(&state, AuthMethod::OAuth)
+            .await
+            .unwrap();
+
+        assert!(matches!(state.kind, OpenAIAuthStateKind::Refreshing));
+        assert!(matches!(progress, AuthProgress::Refreshing));
+    }
+
     fn make_jwt(payload: serde_json::Value) -> String {
         let header = base64_url_encode(b"{}");
         let payload = base64_url_encode(payload.to_string().as_bytes());
@@ -0,0 +1,159 @@
+use crate::error::{AuthError, Result};
+use std::time::{Duration, Instant};
+
+/// Extra delay added to the polling interval on every `slow_down` response (RFC 8628 §3.5).
+const SLOW_DOWN_INCREMENT: Duration = Duration::from_secs(5);
+
+/// How the token endpoint answered a device-code poll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DevicePollResponse {
+    /// The user has not finished authorizing yet.
+    Pending,
+    /// The client is polling too often.
+    SlowDown,
+    /// The user declined the authorization request.
+    Denied,
+    /// The device code is no longer valid.
+    Expired,
+    /// The user approved the request; tokens can be fetched.
+    Authorized,
+}
+
+/// What a device-code flow should do next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DevicePollAction {
+    /// Not due yet; poll again after the given delay.
+    Wait(Duration),
+    /// Poll the token endpoint now.
+    Poll,
+    /// The device code expired before the user authorized it.
+    Expired,
+}
+
+/// Paces polling of a device-code token endpoint.
+///
+/// Providers call [`next_action`](Self::next_action) whenever the client asks for progress
+/// and only hit the network when it returns [`DevicePollAction::Poll`], then feed the answer
+/// back through [`record`](Self::record).
+#[derive(Debug, Clone)]
+pub struct DeviceCodePoller {
+    interval: Duration,
+    next_poll_at: Instant,
+    expires_at: Instant,
+}
+
+impl DeviceCodePoller {
+    pub fn new(interval: Duration, expires_in: Duration, now: Instant) -> Self {
+        Self {
+            interval,
+            next_poll_at: now + interval,
+            expires_at: now + expires_in,
+        }
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn next_action(&self, now: Instant) -> DevicePollAction {
+        if now >= self.expires_at {
+            DevicePollAction::Expired
+        } else if now >= self.next_poll_at {
+            DevicePollAction::Poll
+        } else {
+            DevicePollAction::Wait(self.next_poll_at - now)
+        }
+    }
+
+    /// Record a poll response. Returns `Ok(true)` once the user has authorized the device.
+    pub fn record(&mut self, response: DevicePollResponse, now: Instant) -> Result<bool> {
+        match response {
+            DevicePollResponse::Pending => {
+                self.next_poll_at = now + self.interval;
+                Ok(false)
+            }
+            DevicePollResponse::SlowDown => {
+                self.interval += SLOW_DOWN_INCREMENT;
+                self.next_poll_at = now + self.interval;
+                Ok(false)
+            }
+            DevicePollResponse::Denied => Err(AuthError::Cancelled),
+            DevicePollResponse::Expired => Err(AuthError::DeviceCodeExpired),
+            DevicePollResponse::Authorized => Ok(true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_secs(5);
+    const EXPIRES_IN: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn waits_for_interval_before_first_poll() {
+        let start = Instant::now();
+        let poller = DeviceCodePoller::new(INTERVAL, EXPIRES_IN, start);
+
+        assert_eq!(poller.next_action(start), DevicePollAction::Wait(INTERVAL));
+        assert_eq!(
+            poller.next_action(start + Duration::from_secs(2)),
+            DevicePollAction::Wait(Duration::from_secs(3))
+        );
+        assert_eq!(poller.next_action(start + INTERVAL), DevicePollAction::Poll);
+    }
+
+    #[test]
+    fn pending_reschedules_and_slow_down_backs_off() {
+        let start = Instant::now();
+        let mut poller = DeviceCodePoller::new(INTERVAL, EXPIRES_IN, start);
+
+        let first = start + INTERVAL;
+        assert!(!poller.record(DevicePollResponse::Pending, first).unwrap());
+        assert_eq!(poller.next_action(first), DevicePollAction::Wait(INTERVAL));
+
+        let second = first + INTERVAL;
+        assert!(!poller.record(DevicePollResponse::SlowDown, second).unwrap());
+        assert_eq!(poller.interval(), Duration::from_secs(10));
+        assert_eq!(
+            poller.next_action(second + INTERVAL),
+            DevicePollAction::Wait(INTERVAL)
+        );
+        assert_eq!(
+            poller.next_action(second + Duration::from_secs(10)),
+            DevicePollAction::Poll
+        );
+    }
+
+    #[test]
+    fn authorized_completes() {
+        let start = Instant::now();
+        let mut poller = DeviceCodePoller::new(INTERVAL, EXPIRES_IN, start);
+
+        assert!(
+            poller
+                .record(DevicePollResponse::Authorized, start + INTERVAL)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn expiry_and_denial_are_terminal() {
+        let start = Instant::now();
+        let mut poller = DeviceCodePoller::new(INTERVAL, EXPIRES_IN, start);
+
+        assert_eq!(
+            poller.next_action(start + EXPIRES_IN),
+            DevicePollAction::Expired
+        );
+        assert!(matches!(
+            poller.record(DevicePollResponse::Expired, start),
+            Err(AuthError::DeviceCodeExpired)
+        ));
+        assert!(matches!(
+            poller.record(DevicePollResponse::Denied, start),
+            Err(AuthError::Cancelled)
+        ));
+    }
+}
@@ -3,7 +3,10 @@ use thiserror::Error;
 #[derive(Error, Debug)]
 pub enum AuthError {
     #[error("Network error: {0}")]
-    Network(#[from] reqwest::Error),
+    Network(reqwest::Error),
+
+    #[error("Auth request timed out")]
+    Timeout,
 
     #[error("Invalid authorization code")]
     InvalidCode,
@@ -23,6 +26,9 @@ pub enum AuthError {
     #[error("Failed to start callback server: {0}")]
     CallbackServer(String),
 
+    #[error("Device code expired before authorization completed")]
+    DeviceCodeExpired,
+
     #[error("OAuth state mismatch")]
     StateMismatch,
 
@@ -47,8 +53,21 @@ pub enum AuthError {
     #[error("Invalid credential: {0}")]
     InvalidCredential(String),
 
+    #[error("Invalid HTTP configuration: {0}")]
+    HttpConfig(String),
+
     #[error("Missing required input: {0}")]
     MissingInput(String),
 }
 
+impl From<reqwest::Error> for AuthError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            Self::Timeout
+        } else {
+            Self::Network(err)
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, AuthError>;
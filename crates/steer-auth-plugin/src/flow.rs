@@ -1,11 +1,15 @@
 use crate::error::{AuthError, Result};
 use async_trait::async_trait;
+use std::time::Duration;
 
 /// Available authentication methods for a provider
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AuthMethod {
     OAuth,
     ApiKey,
+    /// Device authorization grant: the user enters a code on another device
+    /// while the flow polls the token endpoint, so no local callback server is needed.
+    DeviceCode,
 }
 
 /// Progress status for authentication flows
@@ -21,6 +25,17 @@ pub enum AuthProgress {
     Error(String),
     /// OAuth flow started, contains the authorization URL
     OAuthStarted { auth_url: String },
+    /// Device-code flow started; the user should enter `user_code` at `verification_uri`
+    DeviceCode {
+        verification_uri: String,
+        user_code: String,
+    },
+    /// Waiting for the OAuth redirect to reach the local callback server
+    WaitingForCallback { elapsed: Duration },
+    /// Authorization code received; exchanging it for tokens
+    ExchangingCode,
+    /// Refreshing previously stored credentials
+    Refreshing,
 }
 
 /// Generic authentication flow trait that providers can implement
@@ -45,6 +60,12 @@ pub trait AuthenticationFlow: Send + Sync {
     /// Handle user input during authentication
     async fn handle_input(&self, state: &mut Self::State, input: &str) -> Result<AuthProgress>;
 
+    /// Abandon an in-progress flow, releasing any resources it holds
+    /// (such as a local callback server) and clearing transient state
+    async fn cancel(&self, _state: &mut Self::State) -> Result<()> {
+        Ok(())
+    }
+
     /// Check if the provider is already authenticated
     async fn is_authenticated(&self) -> Result<bool>;
 
@@ -75,6 +96,9 @@ pub trait DynAuthenticationFlow: Send + Sync {
         input: &str,
     ) -> Result<AuthProgress>;
 
+    /// Abandon an in-progress flow
+    async fn cancel(&self, state: &mut Box<dyn std::any::Any + Send + Sync>) -> Result<()>;
+
     /// Check if the provider is already authenticated
     async fn is_authenticated(&self) -> Result<bool>;
 
@@ -131,6 +155,13 @@ where
         self.inner.handle_input(concrete_state, input).await
     }
 
+    async fn cancel(&self, state: &mut Box<dyn std::any::Any + Send + Sync>) -> Result<()> {
+        let concrete_state = state
+            .downcast_mut::<T::State>()
+            .ok_or_else(|| AuthError::InvalidResponse("Invalid state type".to_string()))?;
+        self.inner.cancel(concrete_state).await
+    }
+
     async fn is_authenticated(&self) -> Result<bool> {
         self.inner.is_authenticated().await
     }
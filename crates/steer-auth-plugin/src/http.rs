@@ -0,0 +1,219 @@
+//! Shared outbound HTTP settings.
+//!
+//! Every `reqwest::Client` Steer builds (auth token exchange and refresh, model
+//! requests, the fetch tool) starts from [`client_builder`], so a proxy or custom
+//! CA bundle configured once at startup applies everywhere. Without an explicit
+//! proxy, reqwest honours `HTTPS_PROXY`, `HTTP_PROXY`, `ALL_PROXY` and `NO_PROXY`.
+//!
+//! Auth requests additionally get a whole-request timeout via [`auth_client`], so a
+//! stalled token endpoint surfaces as [`AuthError::Timeout`] instead of hanging login.
+//! Model clients only get the connect timeout, since their responses stream.
+
+use crate::error::{AuthError, Result};
+use std::path::PathBuf;
+use std::sync::{LazyLock, PoisonError, RwLock};
+use std::time::Duration;
+
+/// Request timeout used when [`HttpConfig::request_timeout`] is unset.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// User-facing proxy and TLS settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HttpConfig {
+    /// Proxy URL for all requests, e.g. `http://proxy.corp:3128`. Overrides the proxy env vars.
+    pub proxy: Option<String>,
+    /// Comma-separated hosts that bypass `proxy`, in `NO_PROXY` syntax.
+    pub no_proxy: Option<String>,
+    /// PEM bundle of extra root certificates to trust.
+    pub ca_bundle: Option<PathBuf>,
+    /// Timeout for auth requests and for establishing any connection.
+    /// Defaults to [`DEFAULT_REQUEST_TIMEOUT`].
+    pub request_timeout: Option<Duration>,
+}
+
+impl HttpConfig {
+    /// Validate the settings and load the CA bundle.
+    pub fn prepare(&self) -> Result<PreparedHttpConfig> {
+        let proxy = self
+            .proxy
+            .as_deref()
+            .map(|url| {
+                reqwest::Proxy::all(url)
+                    .map(|proxy| {
+                        proxy.no_proxy(
+                            self.no_proxy
+                                .as_deref()
+                                .and_then(reqwest::NoProxy::from_string),
+                        )
+                    })
+                    .map_err(|e| AuthError::HttpConfig(format!("invalid proxy URL {url}: {e}")))
+            })
+            .transpose()?;
+
+        let certificates = match &self.ca_bundle {
+            Some(path) => {
+                let pem = std::fs::read(path).map_err(|e| {
+                    AuthError::HttpConfig(format!(
+                        "failed to read CA bundle {}: {e}",
+                        path.display()
+                    ))
+                })?;
+                reqwest::Certificate::from_pem_bundle(&pem).map_err(|e| {
+                    AuthError::HttpConfig(format!("invalid CA bundle {}: {e}", path.display()))
+                })?
+            }
+            None => Vec::new(),
+        };
+
+        if self.request_timeout == Some(Duration::ZERO) {
+            return Err(AuthError::HttpConfig(
+                "request timeout must be greater than zero".to_string(),
+            ));
+        }
+
+        Ok(PreparedHttpConfig {
+            proxy,
+            certificates,
+            request_timeout: self.request_timeout,
+        })
+    }
+}
+
+/// [`HttpConfig`] with the proxy parsed and certificates loaded.
+#[derive(Debug, Clone, Default)]
+pub struct PreparedHttpConfig {
+    proxy: Option<reqwest::Proxy>,
+    certificates: Vec<reqwest::Certificate>,
+    request_timeout: Option<Duration>,
+}
+
+impl PreparedHttpConfig {
+    pub fn request_timeout(&self) -> Duration {
+        self.request_timeout.unwrap_or(DEFAULT_REQUEST_TIMEOUT)
+    }
+
+    pub fn apply(&self, mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        builder = builder.connect_timeout(self.request_timeout());
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(proxy.clone());
+        }
+        for certificate in &self.certificates {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+        builder
+    }
+}
+
+static HTTP_CONFIG: LazyLock<RwLock<PreparedHttpConfig>> =
+    LazyLock::new(|| RwLock::new(PreparedHttpConfig::default()));
+
+/// Install `config` for every client built afterwards via [`client_builder`].
+pub fn configure(config: &HttpConfig) -> Result<()> {
+    let prepared = config.prepare()?;
+    *HTTP_CONFIG.write().unwrap_or_else(PoisonError::into_inner) = prepared;
+    Ok(())
+}
+
+/// A `reqwest::ClientBuilder` with the configured proxy and CA bundle applied.
+pub fn client_builder() -> reqwest::ClientBuilder {
+    HTTP_CONFIG
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .apply(reqwest::Client::builder())
+}
+
+/// A client with default settings plus the configured proxy and CA bundle.
+pub fn client() -> reqwest::Client {
+    client_builder().build().unwrap_or_default()
+}
+
+/// A client for auth requests: [`client`] plus a whole-request timeout.
+pub fn auth_client() -> reqwest::Client {
+    let config = HTTP_CONFIG.read().unwrap_or_else(PoisonError::into_inner);
+    auth_client_with(&config)
+}
+
+fn auth_client_with(config: &PreparedHttpConfig) -> reqwest::Client {
+    config
+        .apply(reqwest::Client::builder())
+        .timeout(config.request_timeout())
+        .build()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configured_proxy_is_set_on_built_client() {
+        let config = HttpConfig {
+            proxy: Some("http://proxy.example.com:3128".to_string()),
+            no_proxy: Some("localhost,internal.example.com".to_string()),
+            ca_bundle: None,
+            request_timeout: None,
+        };
+
+        let client = config
+            .prepare()
+            .unwrap()
+            .apply(reqwest::Client::builder())
+            .build()
+            .unwrap();
+
+        assert!(format!("{client:?}").contains("proxy.example.com:3128"));
+    }
+
+    #[test]
+    fn invalid_proxy_and_missing_ca_bundle_are_rejected() {
+        let bad_proxy = HttpConfig {
+            proxy: Some("not a url".to_string()),
+            ..HttpConfig::default()
+        };
+        assert!(matches!(bad_proxy.prepare(), Err(AuthError::HttpConfig(_))));
+
+        let missing_bundle = HttpConfig {
+            ca_bundle: Some(PathBuf::from("/nonexistent/steer-ca.pem")),
+            ..HttpConfig::default()
+        };
+        assert!(matches!(
+            missing_bundle.prepare(),
+            Err(AuthError::HttpConfig(_))
+        ));
+
+        let zero_timeout = HttpConfig {
+            request_timeout: Some(Duration::ZERO),
+            ..HttpConfig::default()
+        };
+        assert!(matches!(
+            zero_timeout.prepare(),
+            Err(AuthError::HttpConfig(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn stalled_auth_request_times_out() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            // Accept the connection but never answer.
+            let (_socket, _) = listener.accept().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(10)).await;
+        });
+
+        let config = HttpConfig {
+            request_timeout: Some(Duration::from_millis(200)),
+            ..HttpConfig::default()
+        }
+        .prepare()
+        .unwrap();
+        let err = auth_client_with(&config)
+            .post(format!("http://{addr}/oauth/token"))
+            .send()
+            .await
+            .unwrap_err();
+
+        assert!(matches!(AuthError::from(err), AuthError::Timeout));
+        server.abort();
+    }
+}
@@ -1,11 +1,14 @@
+pub mod device_code;
 pub mod directive;
 pub mod error;
 pub mod flow;
+pub mod http;
 pub mod identifiers;
 pub mod plugin;
 pub mod storage;
 pub mod strategy;
 
+pub use device_code::{DeviceCodePoller, DevicePollAction, DevicePollResponse};
 pub use directive::{
     AnthropicAuth, AuthDirective, AuthErrorAction, AuthErrorContext, AuthHeaderContext,
     AuthHeaderProvider, HeaderPair, InstructionPolicy, OpenAiResponsesAuth, QueryParam,
@@ -16,6 +19,6 @@ pub use flow::{
     AuthFlowWrapper, AuthMethod, AuthProgress, AuthenticationFlow, DynAuthenticationFlow,
 };
 pub use identifiers::{ModelId, ProviderId};
-pub use plugin::{AuthPlugin, ModelVisibilityPolicy};
+pub use plugin::{AccountInfo, AuthPlugin, ModelVisibilityPolicy};
 pub use storage::{AuthStorage, AuthTokens, Credential, CredentialType, OAuth2Token};
 pub use strategy::{ApiKeyOrigin, AuthSource};
@@ -21,11 +21,27 @@ pub trait AuthPlugin: Send + Sync {
 
     async fn is_authenticated(&self, storage: Arc<dyn AuthStorage>) -> Result<bool, AuthError>;
 
+    /// Who the stored credentials belong to, for `steer auth whoami`.
+    /// `None` when not logged in or the provider can't tell.
+    async fn account_info(
+        &self,
+        _storage: Arc<dyn AuthStorage>,
+    ) -> Result<Option<AccountInfo>, AuthError> {
+        Ok(None)
+    }
+
     fn model_visibility(&self) -> Option<Box<dyn ModelVisibilityPolicy>> {
         None
     }
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountInfo {
+    pub account_id: Option<String>,
+    pub email: Option<String>,
+    pub organization: Option<String>,
+}
+
 pub trait ModelVisibilityPolicy: Send + Sync {
     fn allow_model(&self, model_id: &ModelId, auth_source: &AuthSource) -> bool;
 }
@@ -0,0 +1,55 @@
+//! Run the agent loop in-process, with no server.
+//!
+//! ```bash
+//! cargo run -p steer-core --example embedded_agent -- "Summarize the README"
+//! ```
+//!
+//! The model may read the current directory freely; any other tool call is
+//! denied.
+
+use std::io::Write;
+
+use futures::StreamExt;
+use steer_core::embedded::{Agent, AgentEvent, ApprovalDecision};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let prompt = std::env::args().skip(1).collect::<Vec<_>>().join(" ");
+    if prompt.is_empty() {
+        return Err("usage: embedded_agent <prompt>".into());
+    }
+
+    let agent = Agent::builder()
+        .model("sonnet")
+        .workspace_path(std::env::current_dir()?)
+        .build()
+        .await?;
+
+    let mut stdout = std::io::stdout();
+    let mut turn = agent.send_message(prompt).await?;
+    while let Some(event) = turn.next().await {
+        match event {
+            AgentEvent::AssistantMessage { text } => writeln!(stdout, "{text}")?,
+            AgentEvent::ToolCallStarted { name, .. } => writeln!(stdout, "> {name}")?,
+            AgentEvent::ApprovalRequested {
+                request_id,
+                tool_call,
+            } => {
+                writeln!(stdout, "denying {}", tool_call.name)?;
+                agent
+                    .approve_tool(
+                        request_id,
+                        ApprovalDecision::DeniedWithReason(
+                            "This agent only reads the workspace".to_string(),
+                        ),
+                    )
+                    .await?;
+            }
+            AgentEvent::Error { message } => writeln!(stdout, "error: {message}")?,
+            _ => {}
+        }
+    }
+
+    agent.shutdown().await;
+    Ok(())
+}
@@ -0,0 +1,205 @@
+//! On-disk cache of model responses, for re-running the same headless prompts
+//! without paying for the same completions again.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use steer_tools::ToolSchema;
+use tracing::warn;
+
+use crate::api::provider::CompletionResponse;
+use crate::app::SystemContext;
+use crate::app::conversation::{Message, MessageData};
+use crate::config::model::{ModelId, ModelParameters};
+
+const ENTRY_EXTENSION: &str = "json";
+
+/// Entry count and total size of a response cache directory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResponseCacheStats {
+    pub entries: u64,
+    pub bytes: u64,
+}
+
+/// Completed model responses stored one file per request under `dir`.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+}
+
+#[derive(Serialize)]
+struct KeyInput<'a> {
+    model: &'a ModelId,
+    messages: Vec<&'a MessageData>,
+    system: Option<String>,
+    tools: Vec<&'a ToolSchema>,
+    parameters: Option<ModelParameters>,
+}
+
+impl ResponseCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Hex SHA-256 identifying a request. Message ids and timestamps are left
+    /// out so a re-run of the same conversation hashes the same, and tools are
+    /// sorted by name so registration order does not matter.
+    pub fn key(
+        model: &ModelId,
+        messages: &[Message],
+        system: Option<&SystemContext>,
+        tools: &[ToolSchema],
+        parameters: Option<ModelParameters>,
+    ) -> String {
+        let mut tools: Vec<&ToolSchema> = tools.iter().collect();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        let input = KeyInput {
+            model,
+            messages: messages.iter().map(|message| &message.data).collect(),
+            system: system.and_then(SystemContext::render),
+            tools,
+            parameters,
+        };
+        // Serializing plain data to a string cannot fail.
+        let encoded = serde_json::to_string(&input).unwrap_or_default();
+        hex::encode(Sha256::digest(encoded.as_bytes()))
+    }
+
+    /// The stored response for `key`. Unreadable entries count as misses.
+    pub async fn get(&self, key: &str) -> Option<CompletionResponse> {
+        let path = self.entry_path(key);
+        let bytes = match tokio::fs::read(&path).await {
+            Ok(bytes) => bytes,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return None,
+            Err(error) => {
+                warn!(path = %path.display(), %error, "Failed to read cached response");
+                return None;
+            }
+        };
+        match serde_json::from_slice(&bytes) {
+            Ok(response) => Some(response),
+            Err(error) => {
+                warn!(path = %path.display(), %error, "Ignoring corrupt cached response");
+                None
+            }
+        }
+    }
+
+    /// Store `response` under `key`, replacing any earlier entry.
+    pub async fn put(&self, key: &str, response: &CompletionResponse) -> io::Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let bytes = serde_json::to_vec(response).map_err(io::Error::other)?;
+        let path = self.entry_path(key);
+        let tmp = path.with_extension("tmp");
+        tokio::fs::write(&tmp, bytes).await?;
+        tokio::fs::rename(&tmp, &path).await
+    }
+
+    pub async fn stats(&self) -> io::Result<ResponseCacheStats> {
+        let mut stats = ResponseCacheStats::default();
+        for path in self.entries().await? {
+            stats.entries += 1;
+            stats.bytes += tokio::fs::metadata(&path).await?.len();
+        }
+        Ok(stats)
+    }
+
+    /// Delete every entry, returning how many were removed.
+    pub async fn clear(&self) -> io::Result<u64> {
+        let mut removed = 0;
+        for path in self.entries().await? {
+            tokio::fs::remove_file(&path).await?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    async fn entries(&self) -> io::Result<Vec<PathBuf>> {
+        let mut read_dir = match tokio::fs::read_dir(&self.dir).await {
+            Ok(read_dir) => read_dir,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(error),
+        };
+        let mut entries = Vec::new();
+        while let Some(entry) = read_dir.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == ENTRY_EXTENSION) {
+                entries.push(path);
+            }
+        }
+        Ok(entries)
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(key).with_extension(ENTRY_EXTENSION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::provider::TokenUsage;
+    use crate::app::conversation::{AssistantContent, UserContent};
+    use crate::config::provider::ProviderId;
+
+    fn user_message(id: &str, timestamp: u64, text: &str) -> Message {
+        Message {
+            timestamp,
+            id: id.to_string(),
+            parent_message_id: None,
+            data: MessageData::User {
+                content: vec![UserContent::Text {
+                    text: text.to_string(),
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn key_ignores_message_ids_but_not_content_or_temperature() {
+        let model = ModelId::new(ProviderId("stub".to_string()), "model");
+        let first = ResponseCache::key(&model, &[user_message("a", 1, "hi")], None, &[], None);
+        let rerun = ResponseCache::key(&model, &[user_message("b", 2, "hi")], None, &[], None);
+        let other = ResponseCache::key(&model, &[user_message("a", 1, "bye")], None, &[], None);
+        let warmer = ResponseCache::key(
+            &model,
+            &[user_message("a", 1, "hi")],
+            None,
+            &[],
+            Some(ModelParameters {
+                temperature: Some(0.7),
+                ..Default::default()
+            }),
+        );
+
+        assert_eq!(first, rerun);
+        assert_ne!(first, other);
+        assert_ne!(first, warmer);
+    }
+
+    #[tokio::test]
+    async fn stores_counts_and_clears_responses() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ResponseCache::new(dir.path().join("cache"));
+        let response = CompletionResponse::new(vec![AssistantContent::Text {
+            text: "cached".to_string(),
+        }])
+        .with_usage(TokenUsage::new(3, 4, 7));
+
+        assert_eq!(cache.stats().await.unwrap(), ResponseCacheStats::default());
+        assert!(cache.get("abc").await.is_none());
+
+        cache.put("abc", &response).await.unwrap();
+        assert_eq!(cache.get("abc").await, Some(response));
+        assert_eq!(cache.stats().await.unwrap().entries, 1);
+
+        assert_eq!(cache.clear().await.unwrap(), 1);
+        assert!(cache.get("abc").await.is_none());
+    }
+}
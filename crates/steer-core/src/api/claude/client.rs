@@ -14,7 +14,8 @@ use crate::api::util::map_http_status_to_api_error;
 use crate::api::{CompletionResponse, Provider, error::ApiError};
 use crate::app::SystemContext;
 use crate::app::conversation::{
-    AssistantContent, ImageSource, Message as AppMessage, ThoughtContent, ToolResult, UserContent,
+    AssistantContent, AttachmentBody, ImageSource, Message as AppMessage, ThoughtContent,
+    ToolResult, UserContent,
 };
 use crate::auth::{
     AnthropicAuth, AuthErrorAction, AuthErrorContext, AuthHeaderContext, InstructionPolicy,
@@ -25,6 +26,11 @@ use crate::config::model::{ModelId, ModelParameters};
 use steer_tools::{InputSchema, ToolCall, ToolSchema};
 
 const API_URL: &str = "https://api.anthropic.com/v1/messages";
+const COUNT_TOKENS_URL: &str = "https://api.anthropic.com/v1/messages/count_tokens";
+/// Tool the model is forced to call to return structured output.
+const STRUCTURED_OUTPUT_TOOL: &str = "structured_output";
+/// Property holding non-object answers, since tool inputs must be objects.
+const STRUCTURED_OUTPUT_VALUE: &str = "value";
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Display)]
 pub enum ClaudeMessageRole {
@@ -136,6 +142,78 @@ struct CompletionRequest {
     thinking: Option<Thinking>,
 }
 
+#[derive(Debug, Serialize)]
+struct CountTokensRequest {
+    model: String,
+    messages: Vec<ClaudeMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<System>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<ClaudeTool>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CountTokensResponse {
+    input_tokens: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct ToolChoice {
+    #[serde(rename = "type")]
+    choice_type: String,
+    name: String,
+}
+
+#[derive(Debug, Serialize)]
+struct StructuredOutputRequest {
+    model: String,
+    messages: Vec<ClaudeMessage>,
+    max_tokens: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<System>,
+    tools: Vec<ClaudeTool>,
+    tool_choice: ToolChoice,
+}
+
+/// Build the forced tool for `schema`, wrapping non-object schemas in a
+/// single `value` property. Returns whether the schema was wrapped.
+fn structured_output_tool(schema: &Value) -> (ClaudeTool, bool) {
+    let is_object = schema.get("type").and_then(Value::as_str) == Some("object");
+    let input_schema = if is_object {
+        schema.clone()
+    } else {
+        serde_json::json!({
+            "type": "object",
+            "properties": { STRUCTURED_OUTPUT_VALUE: schema },
+            "required": [STRUCTURED_OUTPUT_VALUE],
+        })
+    };
+    let tool = ClaudeTool {
+        name: STRUCTURED_OUTPUT_TOOL.to_string(),
+        description: "Return the final answer. The input must match the schema exactly."
+            .to_string(),
+        input_schema: InputSchema::new(sanitize_for_claude(&input_schema, &input_schema)),
+    };
+    (tool, !is_object)
+}
+
+fn extract_structured_output(content: Vec<ClaudeContentBlock>, wrapped: bool) -> Option<Value> {
+    content.into_iter().find_map(|block| match block {
+        ClaudeContentBlock::ToolUse {
+            name, mut input, ..
+        } if name == STRUCTURED_OUTPUT_TOOL => {
+            if wrapped {
+                input
+                    .as_object_mut()
+                    .and_then(|object| object.remove(STRUCTURED_OUTPUT_VALUE))
+            } else {
+                Some(input)
+            }
+        }
+        _ => None,
+    })
+}
+
 #[derive(Debug, Serialize, Clone)]
 struct ClaudeTool {
     name: String,
@@ -800,6 +878,71 @@ mod tests {
         assert_eq!(map_claude_usage(&usage), TokenUsage::new(11, 7, 18));
     }
 
+    #[test]
+    fn count_tokens_request_omits_generation_settings() {
+        let message = AppMessage {
+            data: crate::app::conversation::MessageData::User {
+                content: vec![UserContent::Text {
+                    text: "hello".to_string(),
+                }],
+            },
+            timestamp: 0,
+            id: "u1".to_string(),
+            parent_message_id: None,
+        };
+        let request = CountTokensRequest {
+            model: "claude-test".to_string(),
+            messages: convert_messages(vec![message]).expect("convert messages"),
+            system: build_system_content(Some("be brief".to_string()), None),
+            tools: None,
+        };
+
+        let body = serde_json::to_value(&request).expect("serialize request");
+
+        assert_eq!(body["model"], "claude-test");
+        assert!(body.get("system").is_some());
+        assert!(body.get("max_tokens").is_none());
+        assert!(body.get("tools").is_none());
+        assert!(body.get("stream").is_none());
+
+        let response: CountTokensResponse =
+            serde_json::from_str(r#"{"input_tokens": 42}"#).expect("parse response");
+        assert_eq!(response.input_tokens, 42);
+    }
+
+    #[test]
+    fn structured_output_tool_wraps_non_object_schemas() {
+        let (tool, wrapped) = structured_output_tool(&json!({
+            "type": "object",
+            "properties": { "answer": { "type": "string" } },
+        }));
+        assert!(!wrapped);
+        assert_eq!(tool.name, STRUCTURED_OUTPUT_TOOL);
+        assert!(tool.input_schema.as_value()["properties"]["answer"].is_object());
+
+        let (tool, wrapped) = structured_output_tool(&json!({
+            "type": "array",
+            "items": { "type": "integer" },
+        }));
+        assert!(wrapped);
+        assert_eq!(
+            tool.input_schema.as_value()["properties"]["value"]["type"],
+            "array"
+        );
+
+        let content = vec![ClaudeContentBlock::ToolUse {
+            id: "toolu_1".to_string(),
+            name: STRUCTURED_OUTPUT_TOOL.to_string(),
+            input: json!({ "value": [1, 2, 3] }),
+            cache_control: None,
+            extra: Default::default(),
+        }];
+        assert_eq!(
+            extract_structured_output(content, wrapped),
+            Some(json!([1, 2, 3]))
+        );
+    }
+
     #[test]
     fn convert_claude_completion_includes_usage() {
         let claude_completion = ClaudeCompletionResponse {
@@ -895,6 +1038,33 @@ mod tests {
             Some(AssistantContent::Text { text }) if text == "Hello world"
         ));
     }
+
+    #[tokio::test]
+    async fn convert_claude_stream_reports_refusal_stop_reason() {
+        let events = vec![
+            Ok::<SseEvent, SseParseError>(SseEvent {
+                event_type: None,
+                data: r#"{"type":"message_delta","delta":{"stop_reason":"refusal"},"usage":{"input_tokens":10,"output_tokens":0}}"#.to_string(),
+                id: None,
+            }),
+            Ok(SseEvent {
+                event_type: None,
+                data: r#"{"type":"message_stop"}"#.to_string(),
+                id: None,
+            }),
+        ];
+
+        let sse_stream: SseStream = Box::pin(stream::iter(events));
+        let chunks: Vec<_> = convert_claude_stream(sse_stream, CancellationToken::new())
+            .collect()
+            .await;
+
+        assert!(matches!(
+            chunks.as_slice(),
+            [StreamChunk::Error(StreamError::Refused { provider, reason })]
+                if provider == "anthropic" && reason.contains("refusal")
+        ));
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -1000,7 +1170,6 @@ enum ClaudeStreamEvent {
     ContentBlockStop { index: usize },
     #[serde(rename = "message_delta")]
     MessageDelta {
-        #[expect(dead_code)]
         delta: ClaudeMessageDeltaData,
         #[serde(default)]
         usage: Option<ClaudeUsage>,
@@ -1050,7 +1219,6 @@ enum ClaudeDelta {
 
 #[derive(Debug, Deserialize)]
 struct ClaudeMessageDeltaData {
-    #[expect(dead_code)]
     #[serde(default)]
     stop_reason: Option<String>,
 }
@@ -1093,7 +1261,7 @@ impl AnthropicClient {
             header::HeaderValue::from_static("application/json"),
         );
 
-        reqwest::Client::builder()
+        crate::http::client_builder()
             .default_headers(headers)
             .build()
             .map_err(ApiError::Network)
@@ -1141,20 +1309,24 @@ impl AnthropicClient {
     }
 
     fn request_url(&self) -> Result<String, ApiError> {
+        self.endpoint_url(API_URL)
+    }
+
+    fn endpoint_url(&self, base: &str) -> Result<String, ApiError> {
         let AuthMode::Directive(directive) = &self.auth else {
-            return Ok(API_URL.to_string());
+            return Ok(base.to_string());
         };
 
         let Some(query_params) = &directive.query_params else {
-            return Ok(API_URL.to_string());
+            return Ok(base.to_string());
         };
 
         if query_params.is_empty() {
-            return Ok(API_URL.to_string());
+            return Ok(base.to_string());
         }
 
-        let mut url = url::Url::parse(API_URL)
-            .map_err(|e| ApiError::Configuration(format!("Invalid API_URL '{API_URL}': {e}")))?;
+        let mut url = url::Url::parse(base)
+            .map_err(|e| ApiError::Configuration(format!("Invalid API URL '{base}': {e}")))?;
         for param in query_params {
             url.query_pairs_mut().append_pair(&param.name, &param.value);
         }
@@ -1273,6 +1445,16 @@ fn convert_single_message(msg: AppMessage) -> Result<ClaudeMessage, ApiError> {
                     UserContent::Image { image } => {
                         claude_blocks.push(user_image_to_claude_block(image)?);
                     }
+                    UserContent::Attachment { attachment } => {
+                        claude_blocks.push(ClaudeContentBlock::Text {
+                            text: attachment.prompt_text(),
+                            cache_control: None,
+                            extra: Default::default(),
+                        });
+                        if let AttachmentBody::Image { image } = &attachment.body {
+                            claude_blocks.push(user_image_to_claude_block(image)?);
+                        }
+                    }
                     UserContent::CommandExecution {
                         command,
                         stdout,
@@ -1550,6 +1732,13 @@ fn map_claude_usage(usage: &ClaudeUsage) -> TokenUsage {
     )
 }
 
+/// Anthropic ends a response with `stop_reason: "refusal"` when its safety
+/// classifiers stop the model.
+fn refusal_reason(stop_reason: Option<&str>) -> Option<String> {
+    (stop_reason == Some("refusal"))
+        .then(|| "the model declined to respond (stop_reason: refusal)".to_string())
+}
+
 fn convert_claude_completion(claude_completion: ClaudeCompletionResponse) -> CompletionResponse {
     CompletionResponse {
         content: convert_claude_content(claude_completion.content),
@@ -1563,6 +1752,146 @@ impl Provider for AnthropicClient {
         "anthropic"
     }
 
+    fn supports_token_counting(&self) -> bool {
+        // Directive auth rewrites system prompts per request; only count
+        // requests we send verbatim.
+        matches!(&self.auth, AuthMode::ApiKey(_))
+    }
+
+    async fn count_tokens(
+        &self,
+        model_id: &ModelId,
+        messages: Vec<AppMessage>,
+        system: Option<SystemContext>,
+        tools: Option<Vec<ToolSchema>>,
+        token: CancellationToken,
+    ) -> Result<u32, ApiError> {
+        let request = CountTokensRequest {
+            model: model_id.id.clone(),
+            messages: convert_messages(messages)?,
+            system: build_system_content(apply_instruction_policy(system, None), None),
+            tools: tools.map(|tools| tools.into_iter().map(ClaudeTool::from).collect()),
+        };
+
+        let auth_headers = self
+            .auth_headers(auth_header_context(model_id, RequestKind::Complete))
+            .await?;
+        let mut request_builder = self
+            .http_client
+            .post(self.endpoint_url(COUNT_TOKENS_URL)?)
+            .json(&request);
+        for (name, value) in auth_headers {
+            request_builder = request_builder.header(&name, &value);
+        }
+
+        let response_text = tokio::select! {
+            biased;
+            () = token.cancelled() => {
+                return Err(ApiError::Cancelled { provider: self.name().to_string() });
+            }
+            res = async {
+                let response = request_builder.send().await?;
+                let status = response.status();
+                let text = response.text().await?;
+                Ok::<_, reqwest::Error>((status, text))
+            } => {
+                let (status, text) = res?;
+                if !status.is_success() {
+                    return Err(map_http_status_to_api_error(
+                        self.name(),
+                        status.as_u16(),
+                        text,
+                    ));
+                }
+                text
+            }
+        };
+
+        let counted: CountTokensResponse =
+            serde_json::from_str(&response_text).map_err(|e| ApiError::ResponseParsingError {
+                provider: self.name().to_string(),
+                details: format!("Error: {e}, Body: {response_text}"),
+            })?;
+        Ok(counted.input_tokens)
+    }
+
+    fn supports_structured_output(&self) -> bool {
+        // Same restriction as token counting: directive auth rewrites the
+        // request, so only API key requests get a forced tool call.
+        matches!(&self.auth, AuthMode::ApiKey(_))
+    }
+
+    async fn complete_structured(
+        &self,
+        model_id: &ModelId,
+        messages: Vec<AppMessage>,
+        system: Option<SystemContext>,
+        schema: &Value,
+        token: CancellationToken,
+    ) -> Result<Value, ApiError> {
+        let (tool, wrapped) = structured_output_tool(schema);
+        let request = StructuredOutputRequest {
+            model: model_id.id.clone(),
+            messages: convert_messages(messages)?,
+            max_tokens: 8000,
+            system: build_system_content(apply_instruction_policy(system, None), None),
+            tools: vec![tool],
+            tool_choice: ToolChoice {
+                choice_type: "tool".to_string(),
+                name: STRUCTURED_OUTPUT_TOOL.to_string(),
+            },
+        };
+
+        let auth_headers = self
+            .auth_headers(auth_header_context(model_id, RequestKind::Complete))
+            .await?;
+        let mut request_builder = self.http_client.post(self.request_url()?).json(&request);
+        for (name, value) in auth_headers {
+            request_builder = request_builder.header(&name, &value);
+        }
+
+        let response_text = tokio::select! {
+            biased;
+            () = token.cancelled() => {
+                return Err(ApiError::Cancelled { provider: self.name().to_string() });
+            }
+            res = async {
+                let response = request_builder.send().await?;
+                let status = response.status();
+                let text = response.text().await?;
+                Ok::<_, reqwest::Error>((status, text))
+            } => {
+                let (status, text) = res?;
+                if !status.is_success() {
+                    return Err(map_http_status_to_api_error(
+                        self.name(),
+                        status.as_u16(),
+                        text,
+                    ));
+                }
+                text
+            }
+        };
+
+        let completion: ClaudeCompletionResponse =
+            serde_json::from_str(&response_text).map_err(|e| ApiError::ResponseParsingError {
+                provider: self.name().to_string(),
+                details: format!("Error: {e}, Body: {response_text}"),
+            })?;
+        if let Some(reason) = refusal_reason(completion.stop_reason.as_deref()) {
+            return Err(ApiError::RequestBlocked {
+                provider: self.name().to_string(),
+                details: reason,
+            });
+        }
+        extract_structured_output(completion.content, wrapped).ok_or_else(|| {
+            ApiError::ResponseParsingError {
+                provider: self.name().to_string(),
+                details: format!("No {STRUCTURED_OUTPUT_TOOL} tool call in response"),
+            }
+        })
+    }
+
     async fn complete(
         &self,
         model_id: &ModelId,
@@ -1760,6 +2089,12 @@ impl Provider for AnthropicClient {
                     provider: self.name().to_string(),
                     details: format!("Error: {e}, Body: {response_text}"),
                 })?;
+            if let Some(reason) = refusal_reason(claude_completion.stop_reason.as_deref()) {
+                return Err(ApiError::RequestBlocked {
+                    provider: self.name().to_string(),
+                    details: reason,
+                });
+            }
             let completion = convert_claude_completion(claude_completion);
 
             return Ok(completion);
@@ -2266,10 +2601,17 @@ fn convert_claude_stream(
                         message: error.message,
                     });
                 }
-                ClaudeStreamEvent::MessageDelta { usage, .. } => {
+                ClaudeStreamEvent::MessageDelta { delta, usage } => {
                     if let Some(usage) = usage.as_ref() {
                         latest_usage = Some(map_claude_usage(usage));
                     }
+                    if let Some(reason) = refusal_reason(delta.stop_reason.as_deref()) {
+                        yield StreamChunk::Error(StreamError::Refused {
+                            provider: "anthropic".into(),
+                            reason,
+                        });
+                        break;
+                    }
                 }
                 ClaudeStreamEvent::MessageStart { .. } | ClaudeStreamEvent::Ping => {}
             }
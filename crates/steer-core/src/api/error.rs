@@ -100,6 +100,11 @@ pub enum StreamError {
         raw_error_type: Option<String>,
         message: String,
     },
+
+    /// The provider declined to answer or filtered the response, e.g. an
+    /// Anthropic `refusal` or OpenAI `content_filter` stop reason.
+    #[error("{provider} refused the request: {reason}")]
+    Refused { provider: String, reason: String },
 }
 
 #[derive(Error, Debug)]
@@ -1,7 +1,8 @@
 use crate::api::error::ApiError;
 use crate::api::provider::Provider;
 use crate::api::{
-    claude::AnthropicClient, gemini::GeminiClient, openai::OpenAIClient, xai::XAIClient,
+    claude::AnthropicClient, gemini::GeminiClient, mock::MockProvider, openai::OpenAIClient,
+    xai::XAIClient,
 };
 use crate::auth::{AuthDirective, Credential};
 use crate::config::provider::{ApiFormat, ProviderConfig};
@@ -71,6 +72,7 @@ pub fn create_provider(
                 };
                 Ok(Arc::new(client))
             }
+            ApiFormat::Mock => Ok(Arc::new(MockProvider::from_config(provider_cfg)?)),
         },
         Credential::OAuth2(_) => Err(ApiError::Configuration(
             "OAuth requires an AuthDirective, not a raw credential".to_string(),
@@ -14,8 +14,8 @@ use crate::api::sse::parse_sse_stream;
 use crate::api::util::map_http_status_to_api_error;
 use crate::app::SystemContext;
 use crate::app::conversation::{
-    AssistantContent, ImageSource, Message as AppMessage, ThoughtContent, ThoughtSignature,
-    ToolResult, UserContent,
+    AssistantContent, AttachmentBody, ImageSource, Message as AppMessage, ThoughtContent,
+    ThoughtSignature, ToolResult, UserContent,
 };
 use crate::config::model::{ModelId, ModelParameters};
 use steer_tools::ToolSchema;
@@ -53,7 +53,7 @@ impl GeminiClient {
     pub fn new(api_key: impl Into<String>) -> Self {
         Self {
             api_key: api_key.into(),
-            client: HttpClient::new(),
+            client: crate::http::client(),
         }
     }
 }
@@ -243,6 +243,8 @@ struct GeminiResponse {
 
 #[derive(Debug, Deserialize)]
 struct GeminiCandidate {
+    // Blocked candidates arrive without content.
+    #[serde(default)]
     content: GeminiContentResponse,
     #[serde(rename = "finishReason")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -263,6 +265,9 @@ enum GeminiFinishReason {
     MaxTokens,
     Safety,
     Recitation,
+    Blocklist,
+    ProhibitedContent,
+    Spii,
     Other,
     #[serde(rename = "TOOL_CODE_ERROR")]
     ToolCodeError,
@@ -379,10 +384,12 @@ struct GeminiExecutableCode {
     code: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 #[expect(dead_code)]
 struct GeminiContentResponse {
+    #[serde(default)]
     role: String,
+    #[serde(default)]
     parts: Vec<GeminiResponsePart>,
 }
 
@@ -497,6 +504,14 @@ fn convert_messages(messages: Vec<AppMessage>) -> Result<Vec<GeminiContent>, Api
                         UserContent::Image { image } => {
                             parts.push(user_image_to_request_part(image)?);
                         }
+                        UserContent::Attachment { attachment } => {
+                            parts.push(GeminiRequestPart::Text {
+                                text: attachment.prompt_text(),
+                            });
+                            if let AttachmentBody::Image { image } = &attachment.body {
+                                parts.push(user_image_to_request_part(image)?);
+                            }
+                        }
                         UserContent::CommandExecution {
                             command,
                             stdout,
@@ -1061,6 +1076,36 @@ fn convert_tools(tools: Vec<ToolSchema>) -> Vec<GeminiTool> {
     }]
 }
 
+/// Gemini stops a candidate with one of these finish reasons when its safety
+/// filters block the output.
+fn finish_refusal_reason(reason: &GeminiFinishReason) -> Option<String> {
+    let name = match reason {
+        GeminiFinishReason::Safety => "SAFETY",
+        GeminiFinishReason::Recitation => "RECITATION",
+        GeminiFinishReason::Blocklist => "BLOCKLIST",
+        GeminiFinishReason::ProhibitedContent => "PROHIBITED_CONTENT",
+        GeminiFinishReason::Spii => "SPII",
+        _ => return None,
+    };
+    Some(format!("the response was blocked (finishReason: {name})"))
+}
+
+fn response_refusal_reason(response: &GeminiResponse) -> Option<String> {
+    if let Some(reason) = response
+        .prompt_feedback
+        .as_ref()
+        .and_then(|feedback| feedback.block_reason.as_ref())
+    {
+        return Some(format!("the prompt was blocked (blockReason: {reason:?})"));
+    }
+    response
+        .candidates
+        .iter()
+        .flatten()
+        .filter_map(|candidate| candidate.finish_reason.as_ref())
+        .find_map(finish_refusal_reason)
+}
+
 fn convert_response(response: GeminiResponse) -> Result<CompletionResponse, ApiError> {
     // Log prompt feedback if present
     if let Some(feedback) = &response.prompt_feedback
@@ -1111,7 +1156,6 @@ fn convert_response(response: GeminiResponse) -> Result<CompletionResponse, ApiE
             }
             GeminiFinishReason::Safety => {
                 warn!(target: "gemini::convert_response", "Response stopped due to safety settings. Ratings: {:?}", candidate.safety_ratings);
-                // Consider returning an error or modifying the response based on safety ratings
             }
             GeminiFinishReason::Recitation => {
                 warn!(target: "gemini::convert_response", "Response stopped due to potential recitation. Citations: {:?}", candidate.citation_metadata);
@@ -1123,6 +1167,13 @@ fn convert_response(response: GeminiResponse) -> Result<CompletionResponse, ApiE
                 info!(target: "gemini::convert_response", "Response finished with reason: {:?}", reason);
             }
         }
+
+        if let Some(details) = finish_refusal_reason(reason) {
+            return Err(ApiError::RequestBlocked {
+                provider: "google".to_string(),
+                details,
+            });
+        }
     }
 
     // Log usage metadata if present
@@ -1455,6 +1506,14 @@ impl GeminiClient {
                         latest_usage = Some(mapped);
                     }
 
+                if let Some(reason) = response_refusal_reason(&chunk) {
+                    yield StreamChunk::Error(StreamError::Refused {
+                        provider: "google".to_string(),
+                        reason,
+                    });
+                    break;
+                }
+
                 if let Some(candidates) = chunk.candidates {
                     for candidate in candidates {
                         for part in candidate.content.parts {
@@ -1927,6 +1986,32 @@ mod tests {
         assert!(matches!(complete, StreamChunk::MessageComplete(_)));
     }
 
+    #[tokio::test]
+    async fn test_convert_gemini_stream_reports_safety_block() {
+        use crate::api::provider::StreamChunk;
+        use crate::api::sse::SseEvent;
+        use futures::StreamExt;
+        use futures::stream;
+        use tokio_util::sync::CancellationToken;
+
+        let events = vec![Ok(SseEvent {
+            event_type: None,
+            data: r#"{"candidates":[{"finishReason":"SAFETY"}]}"#.to_string(),
+            id: None,
+        })];
+
+        let sse_stream = stream::iter(events);
+        let chunks: Vec<_> =
+            GeminiClient::convert_gemini_stream(sse_stream, CancellationToken::new())
+                .collect()
+                .await;
+
+        assert!(matches!(
+            chunks.as_slice(),
+            [StreamChunk::Error(StreamError::Refused { reason, .. })] if reason.contains("SAFETY")
+        ));
+    }
+
     #[tokio::test]
     async fn test_convert_gemini_stream_captures_final_usage() {
         use crate::api::provider::StreamChunk;
@@ -0,0 +1,225 @@
+//! Scripted provider for tests and offline demos.
+//!
+//! The built-in `mock` provider needs no credentials or network. It replays a
+//! script of assistant turns, read from the file named by the provider's
+//! `file://` base URL or, failing that, by `STEER_MOCK_SCRIPT`:
+//!
+//! ```json
+//! [
+//!   { "text": "Let me look.", "tool_calls": [{ "name": "ls", "parameters": { "path": "." } }] },
+//!   { "text": "That's the project layout." }
+//! ]
+//! ```
+//!
+//! Turn `n` answers the request that follows the `n`th assistant message in the
+//! conversation. Without a script, or once it runs out, the provider echoes the
+//! latest user message.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use steer_tools::{ToolCall, ToolSchema};
+use tokio_util::sync::CancellationToken;
+
+use crate::api::error::ApiError;
+use crate::api::provider::{CompletionResponse, Provider};
+use crate::app::SystemContext;
+use crate::app::conversation::{AssistantContent, Message, MessageData};
+use crate::config::model::{ModelId, ModelParameters};
+use crate::config::provider::ProviderConfig;
+
+/// Environment variable naming the script when the provider has no base URL.
+pub const MOCK_SCRIPT_ENV: &str = "STEER_MOCK_SCRIPT";
+
+/// One scripted assistant turn.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MockTurn {
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub tool_calls: Vec<MockToolCall>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MockToolCall {
+    pub name: String,
+    #[serde(default)]
+    pub parameters: Value,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MockProvider {
+    turns: Vec<MockTurn>,
+}
+
+impl MockProvider {
+    pub fn new(turns: Vec<MockTurn>) -> Self {
+        Self { turns }
+    }
+
+    /// Builds the provider for `config`, loading its script if one is set.
+    pub fn from_config(config: &ProviderConfig) -> Result<Self, ApiError> {
+        match Self::script_path(config)? {
+            Some(path) => Self::from_file(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, ApiError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            ApiError::Configuration(format!(
+                "Failed to read mock script {}: {e}",
+                path.display()
+            ))
+        })?;
+        let turns = serde_json::from_str(&contents).map_err(|e| {
+            ApiError::Configuration(format!("Invalid mock script {}: {e}", path.display()))
+        })?;
+        Ok(Self::new(turns))
+    }
+
+    fn script_path(config: &ProviderConfig) -> Result<Option<PathBuf>, ApiError> {
+        match &config.base_url {
+            Some(url) => url.to_file_path().map(Some).map_err(|()| {
+                ApiError::Configuration(format!(
+                    "Mock provider {:?} needs a file:// base_url, got {url}",
+                    config.id
+                ))
+            }),
+            None => Ok(std::env::var_os(MOCK_SCRIPT_ENV).map(PathBuf::from)),
+        }
+    }
+
+    fn respond(&self, messages: &[Message]) -> CompletionResponse {
+        let turn_index = messages
+            .iter()
+            .filter(|message| matches!(message.data, MessageData::Assistant { .. }))
+            .count();
+
+        let Some(turn) = self.turns.get(turn_index) else {
+            let echo = messages
+                .iter()
+                .rev()
+                .find(|message| matches!(message.data, MessageData::User { .. }))
+                .map(Message::extract_text)
+                .unwrap_or_default();
+            return CompletionResponse::new(vec![AssistantContent::Text { text: echo }]);
+        };
+
+        let mut content = Vec::new();
+        if let Some(text) = &turn.text {
+            content.push(AssistantContent::Text { text: text.clone() });
+        }
+        for (i, call) in turn.tool_calls.iter().enumerate() {
+            content.push(AssistantContent::ToolCall {
+                tool_call: ToolCall {
+                    name: call.name.clone(),
+                    parameters: call.parameters.clone(),
+                    id: format!("mock-{turn_index}-{i}"),
+                },
+                thought_signature: None,
+            });
+        }
+        CompletionResponse::new(content)
+    }
+}
+
+#[async_trait]
+impl Provider for MockProvider {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn complete(
+        &self,
+        _model_id: &ModelId,
+        messages: Vec<Message>,
+        _system: Option<SystemContext>,
+        _tools: Option<Vec<ToolSchema>>,
+        _call_options: Option<ModelParameters>,
+        token: CancellationToken,
+    ) -> Result<CompletionResponse, ApiError> {
+        if token.is_cancelled() {
+            return Err(ApiError::Cancelled {
+                provider: self.name().to_string(),
+            });
+        }
+        Ok(self.respond(&messages))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::conversation::UserContent;
+    use crate::config::provider::{ApiFormat, ProviderId};
+
+    fn user(text: &str) -> Message {
+        Message {
+            timestamp: 0,
+            id: "u".to_string(),
+            parent_message_id: None,
+            data: MessageData::User {
+                content: vec![UserContent::Text {
+                    text: text.to_string(),
+                }],
+            },
+        }
+    }
+
+    fn assistant(text: &str) -> Message {
+        Message {
+            timestamp: 0,
+            id: "a".to_string(),
+            parent_message_id: None,
+            data: MessageData::Assistant {
+                content: vec![AssistantContent::Text {
+                    text: text.to_string(),
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn replays_turns_then_echoes() {
+        let turns: Vec<MockTurn> = serde_json::from_str(
+            r#"[{"text": "first", "tool_calls": [{"name": "ls", "parameters": {"path": "."}}]}]"#,
+        )
+        .unwrap();
+        let provider = MockProvider::new(turns);
+
+        let first = provider.respond(&[user("hi")]);
+        assert_eq!(first.extract_text(), "first");
+        let calls = first.extract_tool_calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].name, "ls");
+        assert_eq!(calls[0].id, "mock-0-0");
+
+        let echoed = provider.respond(&[user("hi"), assistant("first"), user("again")]);
+        assert_eq!(echoed.extract_text(), "again");
+        assert!(!echoed.has_tool_calls());
+    }
+
+    #[test]
+    fn base_url_must_be_a_file_url() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("script.json");
+        std::fs::write(&script, r#"[{"text": "scripted"}]"#).unwrap();
+
+        let mut config = ProviderConfig {
+            id: ProviderId("mock".to_string()),
+            name: "Mock".to_string(),
+            api_format: ApiFormat::Mock,
+            auth_schemes: vec![],
+            base_url: Some(url::Url::from_file_path(&script).unwrap()),
+        };
+        let provider = MockProvider::from_config(&config).unwrap();
+        assert_eq!(provider.respond(&[user("hi")]).extract_text(), "scripted");
+
+        config.base_url = Some("http://localhost:1234".parse().unwrap());
+        assert!(MockProvider::from_config(&config).is_err());
+    }
+}
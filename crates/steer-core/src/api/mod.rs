@@ -1,7 +1,9 @@
+pub mod cache;
 pub mod claude;
 pub mod error;
 pub mod factory;
 pub mod gemini;
+pub mod mock;
 pub mod openai;
 pub mod provider;
 pub mod sse;
@@ -11,10 +13,13 @@ pub mod xai;
 use crate::auth::storage::Credential;
 use crate::auth::{AuthSource, ProviderRegistry};
 use crate::config::model::{ModelId, ModelParameters};
-use crate::config::provider::ProviderId;
+use crate::config::provider::{ApiFormat, ProviderId};
 use crate::config::{LlmConfigProvider, ResolvedAuth};
 use crate::error::Result;
-use crate::model_registry::ModelRegistry;
+use crate::metrics;
+use crate::model_registry::SharedModelRegistry;
+use crate::profiling::{ProfilePhase, Profiler, time_phase};
+pub use cache::{ResponseCache, ResponseCacheStats};
 pub use error::{ApiError, ProviderStreamErrorKind, SseParseError, StreamError};
 pub use factory::{create_provider, create_provider_with_directive};
 use futures::StreamExt;
@@ -23,7 +28,7 @@ use rand::Rng;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::RwLock;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use steer_tools::ToolSchema;
 use tokio_util::sync::CancellationToken;
 use tracing::debug;
@@ -43,7 +48,9 @@ pub struct Client {
     provider_map: Arc<RwLock<HashMap<ProviderId, ProviderEntry>>>,
     config_provider: LlmConfigProvider,
     provider_registry: Arc<ProviderRegistry>,
-    model_registry: Arc<ModelRegistry>,
+    model_registry: SharedModelRegistry,
+    profiler: Option<Profiler>,
+    response_cache: Option<Arc<ResponseCache>>,
 }
 
 #[derive(Clone)]
@@ -103,7 +110,9 @@ impl Client {
         match error {
             StreamError::SseParse(SseParseError::Transport { .. }) => true,
             StreamError::Provider { kind, .. } => kind.is_retryable(),
-            StreamError::Cancelled | StreamError::SseParse(_) => false,
+            StreamError::Cancelled | StreamError::SseParse(_) | StreamError::Refused { .. } => {
+                false
+            }
         }
     }
 
@@ -237,6 +246,12 @@ impl Client {
                         provider: provider.clone(),
                     });
                 }
+                StreamChunk::Error(StreamError::Refused { provider, reason }) => {
+                    return Err(ApiError::RequestBlocked {
+                        provider,
+                        details: reason,
+                    });
+                }
                 StreamChunk::Error(error) => {
                     return Err(ApiError::StreamError {
                         provider: provider.clone(),
@@ -263,24 +278,65 @@ impl Client {
     pub fn new_with_deps(
         config_provider: LlmConfigProvider,
         provider_registry: Arc<ProviderRegistry>,
-        model_registry: Arc<ModelRegistry>,
+        model_registry: impl Into<SharedModelRegistry>,
     ) -> Self {
         Self {
             provider_map: Arc::new(RwLock::new(HashMap::new())),
             config_provider,
             provider_registry,
-            model_registry,
+            model_registry: model_registry.into(),
+            profiler: None,
+            response_cache: None,
         }
     }
 
+    pub fn with_profiler(mut self, profiler: Profiler) -> Self {
+        self.profiler = Some(profiler);
+        self
+    }
+
+    /// Serve repeated model requests from `cache`. Off unless set.
+    pub fn with_response_cache(mut self, cache: ResponseCache) -> Self {
+        self.response_cache = Some(Arc::new(cache));
+        self
+    }
+
+    pub fn response_cache(&self) -> Option<&Arc<ResponseCache>> {
+        self.response_cache.as_ref()
+    }
+
+    /// The response cache key for a request, using the parameters the model
+    /// would actually be called with.
+    pub fn response_cache_key(
+        &self,
+        model_id: &ModelId,
+        messages: &[Message],
+        system: Option<&SystemContext>,
+        tools: &[ToolSchema],
+    ) -> String {
+        let parameters = self
+            .model_registry
+            .snapshot()
+            .get(model_id)
+            .and_then(|config| config.effective_parameters(None));
+        ResponseCache::key(model_id, messages, system, tools, parameters)
+    }
+
+    /// The registry model lookups go through; reloading it affects later calls.
+    pub fn model_registry(&self) -> &SharedModelRegistry {
+        &self.model_registry
+    }
+
     pub fn model_context_window_tokens(&self, model_id: &ModelId) -> Option<u32> {
         self.model_registry
+            .snapshot()
             .get(model_id)
             .and_then(|model| model.context_window_tokens)
     }
 
     pub fn model_max_output_tokens(&self, model_id: &ModelId) -> Option<u32> {
         self.model_registry
+            .snapshot()
             .get(model_id)
             .and_then(|model| model.parameters)
             .and_then(|parameters| parameters.max_output_tokens)
@@ -307,6 +363,26 @@ impl Client {
         }
     }
 
+    /// Whether a provider has credentials and can be reached in offline mode, so
+    /// optional background calls can be skipped instead of failing.
+    pub async fn has_credentials(&self, provider_id: &ProviderId) -> bool {
+        match self.provider_registry.get(provider_id) {
+            Some(config) if crate::offline::unavailable_reason(config).is_some() => return false,
+            Some(config) if config.api_format == ApiFormat::Mock => return true,
+            _ => {}
+        }
+        let cached = self
+            .provider_map
+            .read()
+            .is_ok_and(|map| map.contains_key(provider_id));
+        cached
+            || self
+                .config_provider
+                .resolve_auth_for_provider(provider_id)
+                .await
+                .is_ok_and(|resolved| !matches!(resolved, ResolvedAuth::None))
+    }
+
     async fn get_or_create_provider_entry(&self, provider_id: ProviderId) -> Result<ProviderEntry> {
         // First check without holding the lock across await
         {
@@ -326,11 +402,31 @@ impl Client {
                 "No provider configuration found for {provider_id:?}"
             )))
         })?;
+        if let Some(reason) = crate::offline::unavailable_reason(provider_config) {
+            return Err(crate::error::Error::Api(ApiError::Configuration(reason)));
+        }
 
-        let resolved = self
-            .config_provider
-            .resolve_auth_for_provider(&provider_id)
-            .await?;
+        // The mock provider has no credentials to resolve.
+        if provider_config.api_format == ApiFormat::Mock {
+            let entry = ProviderEntry {
+                provider: Arc::new(mock::MockProvider::from_config(provider_config)?),
+                auth_source: AuthSource::None,
+            };
+            let mut map = self.provider_map.write().map_err(|_| {
+                crate::error::Error::Api(ApiError::Configuration(
+                    "Provider cache lock poisoned".to_string(),
+                ))
+            })?;
+            return Ok(map.entry(provider_id).or_insert(entry).clone());
+        }
+
+        let resolved = time_phase(
+            self.profiler.as_ref(),
+            ProfilePhase::AuthResolution,
+            || Some(provider_id.to_string()),
+            self.config_provider.resolve_auth_for_provider(&provider_id),
+        )
+        .await?;
 
         // Now acquire write lock and create provider
         let mut map = self.provider_map.write().map_err(|_| {
@@ -425,6 +521,63 @@ impl Client {
         Self::collect_completion_from_stream(stream, model_id.provider.to_string()).await
     }
 
+    /// Whether `model_id`'s provider can count tokens exactly.
+    pub async fn supports_token_counting(&self, model_id: &ModelId) -> bool {
+        self.get_or_create_provider_entry(model_id.provider.clone())
+            .await
+            .is_ok_and(|entry| entry.provider.supports_token_counting())
+    }
+
+    /// Count the input tokens a request would use with the provider's own
+    /// tokenizer. Returns `None` when the provider cannot count tokens.
+    pub async fn count_tokens(
+        &self,
+        model_id: &ModelId,
+        messages: Vec<Message>,
+        system: Option<SystemContext>,
+        tools: Option<Vec<ToolSchema>>,
+        token: CancellationToken,
+    ) -> std::result::Result<Option<u32>, ApiError> {
+        let entry = self
+            .get_or_create_provider_entry(model_id.provider.clone())
+            .await
+            .map_err(ApiError::from)?;
+        if !entry.provider.supports_token_counting() {
+            return Ok(None);
+        }
+
+        entry
+            .provider
+            .count_tokens(model_id, messages, system, tools, token)
+            .await
+            .map(Some)
+    }
+
+    /// Ask the provider for a JSON answer constrained to `schema`. Returns
+    /// `None` when the provider has no native structured output mode.
+    pub async fn complete_structured(
+        &self,
+        model_id: &ModelId,
+        messages: Vec<Message>,
+        system: Option<SystemContext>,
+        schema: &serde_json::Value,
+        token: CancellationToken,
+    ) -> std::result::Result<Option<serde_json::Value>, ApiError> {
+        let entry = self
+            .get_or_create_provider_entry(model_id.provider.clone())
+            .await
+            .map_err(ApiError::from)?;
+        if !entry.provider.supports_structured_output() {
+            return Ok(None);
+        }
+
+        entry
+            .provider
+            .complete_structured(model_id, messages, system, schema, token)
+            .await
+            .map(Some)
+    }
+
     pub async fn stream_complete(
         &self,
         model_id: &ModelId,
@@ -433,6 +586,40 @@ impl Client {
         tools: Option<Vec<ToolSchema>>,
         call_options: Option<crate::config::model::ModelParameters>,
         token: CancellationToken,
+    ) -> std::result::Result<CompletionStream, ApiError> {
+        let started = Instant::now();
+        let stream = match self
+            .open_stream(model_id, messages, system, tools, call_options, token)
+            .await
+        {
+            Ok(stream) => stream,
+            Err(err) => {
+                metrics::record_model_request(model_id, started, false, None);
+                return Err(err);
+            }
+        };
+
+        // Errors that reach the caller are terminal; retried ones never do.
+        let model_id = model_id.clone();
+        Ok(Box::pin(stream.inspect(move |chunk| match chunk {
+            StreamChunk::MessageComplete(response) => {
+                metrics::record_model_request(&model_id, started, true, response.usage.as_ref());
+            }
+            StreamChunk::Error(_) => {
+                metrics::record_model_request(&model_id, started, false, None);
+            }
+            _ => {}
+        })))
+    }
+
+    async fn open_stream(
+        &self,
+        model_id: &ModelId,
+        messages: Vec<Message>,
+        system: Option<SystemContext>,
+        tools: Option<Vec<ToolSchema>>,
+        call_options: Option<crate::config::model::ModelParameters>,
+        token: CancellationToken,
     ) -> std::result::Result<CompletionStream, ApiError> {
         let provider_id = model_id.provider.clone();
         let entry = self
@@ -447,7 +634,8 @@ impl Client {
             });
         }
 
-        let model_config = self.model_registry.get(model_id);
+        let model_registry = self.model_registry.snapshot();
+        let model_config = model_registry.get(model_id);
         let effective_params = match (model_config, &call_options) {
             (Some(config), Some(opts)) => config.effective_parameters(Some(opts)),
             (Some(config), None) => config.effective_parameters(None),
@@ -600,6 +788,35 @@ impl Client {
         tools: &Option<Vec<ToolSchema>>,
         token: CancellationToken,
         max_attempts: usize,
+    ) -> std::result::Result<CompletionResponse, ApiError> {
+        let started = Instant::now();
+        let result = self
+            .run_complete_with_fallback(
+                model_id,
+                messages,
+                system_prompt,
+                tools,
+                token,
+                max_attempts,
+            )
+            .await;
+        metrics::record_model_request(
+            model_id,
+            started,
+            result.is_ok(),
+            result.as_ref().ok().and_then(|r| r.usage.as_ref()),
+        );
+        result
+    }
+
+    async fn run_complete_with_fallback(
+        &self,
+        model_id: &ModelId,
+        messages: &[Message],
+        system_prompt: &Option<SystemContext>,
+        tools: &Option<Vec<ToolSchema>>,
+        token: CancellationToken,
+        max_attempts: usize,
     ) -> std::result::Result<CompletionResponse, ApiError> {
         let provider_id = model_id.provider.clone();
         let entry = self
@@ -607,7 +824,8 @@ impl Client {
             .await
             .map_err(ApiError::from)?;
 
-        let model_config = self.model_registry.get(model_id);
+        let model_registry = self.model_registry.snapshot();
+        let model_config = model_registry.get(model_id);
         debug!(
             target: "api::complete_with_retry",
             ?model_id,
@@ -680,6 +898,7 @@ mod tests {
     use crate::app::conversation::AssistantContent;
     use crate::auth::ApiKeyOrigin;
     use crate::config::provider::ProviderId;
+    use crate::model_registry::ModelRegistry;
     use async_trait::async_trait;
     use futures::StreamExt;
     use std::sync::atomic::{AtomicUsize, Ordering};
@@ -11,11 +11,13 @@ use crate::api::sse::parse_sse_stream;
 use crate::api::util::map_http_status_to_api_error;
 use crate::app::SystemContext;
 use crate::app::conversation::{
-    AssistantContent, ImageSource, Message as AppMessage, MessageData, ThoughtContent, UserContent,
+    AssistantContent, AttachmentBody, ImageSource, Message as AppMessage, MessageData,
+    ThoughtContent, UserContent,
 };
 use crate::config::model::{ModelId, ModelParameters};
 use steer_tools::ToolSchema;
 
+use super::CompletionInputs;
 use super::types::{OpenAIFunction, OpenAITool, ServiceTier, ToolChoice};
 
 const DEFAULT_API_URL: &str = "https://api.openai.com/v1/chat/completions";
@@ -69,7 +71,7 @@ impl Client {
             })?,
         );
 
-        let http_client = reqwest::Client::builder()
+        let http_client = crate::http::client_builder()
             .default_headers(headers)
             .timeout(std::time::Duration::from_secs(super::HTTP_TIMEOUT_SECS))
             .build()
@@ -92,6 +94,56 @@ impl Client {
         call_options: Option<ModelParameters>,
         token: CancellationToken,
     ) -> Result<CompletionResponse, ApiError> {
+        let inputs = CompletionInputs {
+            messages,
+            system,
+            tools,
+            call_options,
+        };
+        self.complete_with_format(model_id, inputs, None, token)
+            .await
+    }
+
+    /// Answer with JSON constrained to `schema` via `response_format`.
+    pub(super) async fn complete_structured(
+        &self,
+        model_id: &ModelId,
+        messages: Vec<AppMessage>,
+        system: Option<SystemContext>,
+        schema: &serde_json::Value,
+        token: CancellationToken,
+    ) -> Result<CompletionResponse, ApiError> {
+        let response_format = serde_json::json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": super::STRUCTURED_OUTPUT_NAME,
+                "schema": schema,
+                "strict": false,
+            },
+        });
+        let inputs = CompletionInputs {
+            messages,
+            system,
+            tools: None,
+            call_options: None,
+        };
+        self.complete_with_format(model_id, inputs, Some(response_format), token)
+            .await
+    }
+
+    async fn complete_with_format(
+        &self,
+        model_id: &ModelId,
+        inputs: CompletionInputs,
+        response_format: Option<serde_json::Value>,
+        token: CancellationToken,
+    ) -> Result<CompletionResponse, ApiError> {
+        let CompletionInputs {
+            messages,
+            system,
+            tools,
+            call_options,
+        } = inputs;
         let mut openai_messages = Vec::new();
 
         let system_text = system.and_then(|context| context.render());
@@ -161,7 +213,7 @@ impl Client {
             tools: openai_tools,
             tool_choice: None,
             parallel_tool_calls: None,
-            response_format: None,
+            response_format,
             reasoning_effort,
             audio: None,
             stream_options: None,
@@ -212,6 +264,12 @@ impl Client {
         })?;
 
         if let Some(choice) = parsed.choices.first() {
+            if let Some(reason) = refusal_reason(choice.finish_reason.as_deref()) {
+                return Err(ApiError::RequestBlocked {
+                    provider: super::PROVIDER_NAME.to_string(),
+                    details: reason,
+                });
+            }
             Ok(Self::convert_response_message(
                 &choice.message,
                 parsed.usage.as_ref(),
@@ -236,6 +294,14 @@ impl Client {
                         UserContent::Image { image } => {
                             content_parts.push(Self::user_image_part(&image)?);
                         }
+                        UserContent::Attachment { attachment } => {
+                            content_parts.push(OpenAIContentPart::Text {
+                                text: attachment.prompt_text(),
+                            });
+                            if let AttachmentBody::Image { image } = &attachment.body {
+                                content_parts.push(Self::user_image_part(image)?);
+                            }
+                        }
                         UserContent::CommandExecution {
                             command,
                             stdout,
@@ -628,6 +694,14 @@ impl Client {
                 }
 
                 if let Some(choice) = chunk.choices.first() {
+                    if let Some(reason) = refusal_reason(choice.finish_reason.as_deref()) {
+                        yield StreamChunk::Error(StreamError::Refused {
+                            provider: super::PROVIDER_NAME.to_string(),
+                            reason,
+                        });
+                        break;
+                    }
+
                     if let Some(text_delta) = &choice.delta.content {
                         if let Some(AssistantContent::Text { text }) = content.last_mut() { text.push_str(text_delta) } else {
                             content.push(AssistantContent::Text {
@@ -735,6 +809,14 @@ impl Client {
     }
 }
 
+/// OpenAI-compatible APIs report `finish_reason: "content_filter"` when the
+/// provider's moderation stops the response.
+fn refusal_reason(finish_reason: Option<&str>) -> Option<String> {
+    (finish_reason == Some("content_filter")).then(|| {
+        "the response was stopped by the content filter (finish_reason: content_filter)".to_string()
+    })
+}
+
 // OpenAI-specific message format
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "role", rename_all = "lowercase")]
@@ -934,7 +1016,6 @@ struct OpenAIStreamChoice {
     #[expect(dead_code)]
     index: u32,
     delta: OpenAIStreamDelta,
-    #[expect(dead_code)]
     finish_reason: Option<String>,
 }
 
@@ -1196,6 +1277,41 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn test_convert_openai_stream_reports_content_filter() {
+        let events = vec![
+            Ok(SseEvent {
+                event_type: None,
+                data: r#"{"id":"chatcmpl-123","object":"chat.completion.chunk","choices":[{"index":0,"delta":{"content":"I"},"finish_reason":null}]}"#.to_string(),
+                id: None,
+            }),
+            Ok(SseEvent {
+                event_type: None,
+                data: r#"{"id":"chatcmpl-123","object":"chat.completion.chunk","choices":[{"index":0,"delta":{},"finish_reason":"content_filter"}]}"#.to_string(),
+                id: None,
+            }),
+            Ok(SseEvent {
+                event_type: None,
+                data: "[DONE]".to_string(),
+                id: None,
+            }),
+        ];
+
+        let sse_stream = stream::iter(events);
+        let token = CancellationToken::new();
+        let chunks: Vec<_> = Client::convert_openai_stream(sse_stream, token)
+            .collect()
+            .await;
+
+        assert!(matches!(
+            chunks.as_slice(),
+            [
+                StreamChunk::TextDelta(_),
+                StreamChunk::Error(StreamError::Refused { reason, .. })
+            ] if reason.contains("content_filter")
+        ));
+    }
+
     #[test]
     fn test_convert_message_with_command_execution() {
         let message = Message {
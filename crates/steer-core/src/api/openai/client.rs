@@ -90,6 +90,44 @@ impl Provider for OpenAIClient {
         }
     }
 
+    fn supports_structured_output(&self) -> bool {
+        match self.default_mode {
+            OpenAIMode::Responses => self.responses_client.supports_complete(),
+            OpenAIMode::Chat => self.chat_client.is_some(),
+        }
+    }
+
+    async fn complete_structured(
+        &self,
+        model_id: &ModelId,
+        messages: Vec<Message>,
+        system: Option<SystemContext>,
+        schema: &serde_json::Value,
+        token: CancellationToken,
+    ) -> Result<serde_json::Value, ApiError> {
+        let response = match self.default_mode {
+            OpenAIMode::Responses => {
+                self.responses_client
+                    .complete_structured(model_id, messages, system, schema, token)
+                    .await?
+            }
+            OpenAIMode::Chat => {
+                let chat_client = self.chat_client.as_ref().ok_or_else(|| {
+                    ApiError::Configuration(
+                        "OpenAI chat mode is not available with OAuth authentication".to_string(),
+                    )
+                })?;
+                chat_client
+                    .complete_structured(model_id, messages, system, schema, token)
+                    .await?
+            }
+        };
+        serde_json::from_str(&response.extract_text()).map_err(|e| ApiError::ResponseParsingError {
+            provider: self.name().to_string(),
+            details: format!("Structured output is not valid JSON: {e}"),
+        })
+    }
+
     async fn stream_complete(
         &self,
         model_id: &ModelId,
@@ -1,3 +1,8 @@
+use crate::app::SystemContext;
+use crate::app::conversation::Message;
+use crate::config::model::ModelParameters;
+use steer_tools::ToolSchema;
+
 mod chat;
 mod client;
 mod responses;
@@ -10,6 +15,16 @@ pub use client::OpenAIClient;
 pub(crate) const PROVIDER_NAME: &str = "openai";
 /// Default HTTP timeout for OpenAI requests (30 minutes)
 pub(crate) const HTTP_TIMEOUT_SECS: u64 = 1800;
+/// Schema name sent with structured output requests
+pub(crate) const STRUCTURED_OUTPUT_NAME: &str = "structured_output";
+
+/// The conversation and settings of a non-streaming completion request.
+struct CompletionInputs {
+    messages: Vec<Message>,
+    system: Option<SystemContext>,
+    tools: Option<Vec<ToolSchema>>,
+    call_options: Option<ModelParameters>,
+}
 
 #[derive(Debug, Clone, Copy)]
 pub enum OpenAIMode {
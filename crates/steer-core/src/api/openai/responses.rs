@@ -5,18 +5,21 @@ use tokio_util::sync::CancellationToken;
 use tracing::{debug, error};
 
 use crate::api::error::{ApiError, ProviderStreamErrorKind, SseParseError, StreamError};
+use crate::api::openai::CompletionInputs;
 use crate::api::openai::responses_types::{
     ExtraValue, InputContentPart, InputItem, InputType, MessageContentPart, ReasoningConfig,
     ReasoningSummary, ReasoningSummaryPart, ResponseError, ResponseErrorEvent, ResponseFailedEvent,
-    ResponseOutputItem, ResponseUsage, ResponsesApiResponse, ResponsesFunctionTool,
-    ResponsesHttpErrorEnvelope, ResponsesRequest, ResponsesToolChoice,
+    ResponseIncompleteDetails, ResponseIncompleteEvent, ResponseOutputItem, ResponseUsage,
+    ResponsesApiResponse, ResponsesFunctionTool, ResponsesHttpErrorEnvelope, ResponsesRequest,
+    ResponsesToolChoice, TextConfig, TextFormat,
 };
 use crate::api::provider::{CompletionResponse, CompletionStream, StreamChunk, TokenUsage};
 use crate::api::sse::parse_sse_stream;
 use crate::api::util::map_http_status_to_api_error;
 use crate::app::SystemContext;
 use crate::app::conversation::{
-    AssistantContent, ImageSource, Message as AppMessage, MessageData, ThoughtContent, UserContent,
+    AssistantContent, AttachmentBody, ImageSource, Message as AppMessage, MessageData,
+    ThoughtContent, UserContent,
 };
 use crate::auth::{
     AuthErrorAction, AuthErrorContext, AuthHeaderContext, InstructionPolicy, OpenAiResponsesAuth,
@@ -89,7 +92,7 @@ impl Client {
         api_key: String,
         base_url: Option<String>,
     ) -> Result<Self, ApiError> {
-        let http = reqwest::Client::builder()
+        let http = crate::http::client_builder()
             .timeout(std::time::Duration::from_secs(super::HTTP_TIMEOUT_SECS))
             .build()
             .map_err(ApiError::Network)?;
@@ -108,7 +111,7 @@ impl Client {
         directive: OpenAiResponsesAuth,
         base_url: Option<String>,
     ) -> Result<Self, ApiError> {
-        let http = reqwest::Client::builder()
+        let http = crate::http::client_builder()
             .timeout(std::time::Duration::from_secs(super::HTTP_TIMEOUT_SECS))
             .build()
             .map_err(ApiError::Network)?;
@@ -434,6 +437,14 @@ impl Client {
                             UserContent::Image { image } => {
                                 content_parts.push(Self::user_image_part(image)?);
                             }
+                            UserContent::Attachment { attachment } => {
+                                content_parts.push(InputContentPart::InputText {
+                                    text: attachment.prompt_text(),
+                                });
+                                if let AttachmentBody::Image { image } = &attachment.body {
+                                    content_parts.push(Self::user_image_part(image)?);
+                                }
+                            }
                             UserContent::CommandExecution {
                                 command,
                                 stdout,
@@ -557,6 +568,14 @@ impl Client {
 }
 
 impl Client {
+    /// Whether this client may send non-streaming requests.
+    pub(super) fn supports_complete(&self) -> bool {
+        !self
+            .auth
+            .directive()
+            .is_some_and(|directive| directive.require_streaming.unwrap_or(false))
+    }
+
     pub(super) async fn complete(
         &self,
         model_id: &ModelId,
@@ -566,9 +585,56 @@ impl Client {
         call_options: Option<ModelParameters>,
         token: CancellationToken,
     ) -> Result<CompletionResponse, ApiError> {
-        if let Some(directive) = self.auth.directive()
-            && directive.require_streaming.unwrap_or(false)
-        {
+        let inputs = CompletionInputs {
+            messages,
+            system,
+            tools,
+            call_options,
+        };
+        self.complete_with_text(model_id, inputs, None, token).await
+    }
+
+    /// Answer with JSON constrained to `schema` via the `text.format` setting.
+    pub(super) async fn complete_structured(
+        &self,
+        model_id: &ModelId,
+        messages: Vec<AppMessage>,
+        system: Option<SystemContext>,
+        schema: &serde_json::Value,
+        token: CancellationToken,
+    ) -> Result<CompletionResponse, ApiError> {
+        let text = TextConfig {
+            format: Some(TextFormat::JsonSchema {
+                name: super::STRUCTURED_OUTPUT_NAME.to_string(),
+                schema: schema.clone(),
+                strict: false,
+            }),
+            style: None,
+        };
+        let inputs = CompletionInputs {
+            messages,
+            system,
+            tools: None,
+            call_options: None,
+        };
+        self.complete_with_text(model_id, inputs, Some(text), token)
+            .await
+    }
+
+    async fn complete_with_text(
+        &self,
+        model_id: &ModelId,
+        inputs: CompletionInputs,
+        text: Option<TextConfig>,
+        token: CancellationToken,
+    ) -> Result<CompletionResponse, ApiError> {
+        let CompletionInputs {
+            messages,
+            system,
+            tools,
+            call_options,
+        } = inputs;
+        if !self.supports_complete() {
             return Err(ApiError::Configuration(
                 "OpenAI OAuth requests require streaming responses".to_string(),
             ));
@@ -578,13 +644,14 @@ impl Client {
         let mut attempts = 0;
 
         loop {
-            let request = self.build_request(
+            let mut request = self.build_request(
                 model_id,
                 messages.clone(),
                 system.clone(),
                 tools.clone(),
                 call_options,
             )?;
+            request.text.clone_from(&text);
             log_request_payload(&request, false);
             let headers = self.auth_headers(auth_ctx.clone()).await?;
             let request_builder = self
@@ -696,6 +763,13 @@ impl Client {
                 }
             })?;
 
+            if let Some(reason) = refusal_reason(parsed.incomplete_details.as_ref()) {
+                return Err(ApiError::RequestBlocked {
+                    provider: "openai".to_string(),
+                    details: reason,
+                });
+            }
+
             return Ok(Self::convert_response(parsed));
         }
     }
@@ -891,8 +965,19 @@ impl Client {
                                         }
                                     }
                             }
+                            Some("response.output_item.done") => {
+                                // Reasoning that arrives only as encrypted content has no
+                                // summary to stream; show that the model was thinking.
+                                if let Ok(data) = serde_json::from_str::<serde_json::Value>(&event.data)
+                                    && let Some(item) = data.get("item")
+                                    && is_encrypted_only_reasoning(item) {
+                                        yield StreamChunk::ThinkingDelta(
+                                            ENCRYPTED_REASONING_INDICATOR.to_string(),
+                                        );
+                                    }
+                            }
                             Some("response.in_progress" | "response.reasoning_summary_part.added" |
-        "response.created" | "response.output_item.done" | "response.output_text.done"
+        "response.created" | "response.output_text.done"
         | "response.content_part.added" | "response.content_part.done" |
         "response.refusal.done") => {
                                 // No-op: informational events that don't affect streamed content.
@@ -1116,6 +1201,24 @@ impl Client {
                                     }
                                 }
                             }
+                            Some("response.incomplete") => {
+                                let reason = serde_json::from_str::<ResponseIncompleteEvent>(&event.data)
+                                    .ok()
+                                    .and_then(|incomplete| {
+                                        refusal_reason(incomplete.response.incomplete_details.as_ref())
+                                    });
+                                if let Some(reason) = reason {
+                                    yield StreamChunk::Error(StreamError::Refused {
+                                        provider: "openai".into(),
+                                        reason,
+                                    });
+                                    break;
+                                }
+                                debug!(
+                                    target: "openai::responses::stream",
+                                    "Response incomplete: {}", event.data
+                                );
+                            }
                             Some("response.failed") => {
                                 let parsed_event =
                                     serde_json::from_str::<ResponseFailedEvent>(&event.data);
@@ -1174,6 +1277,14 @@ impl Client {
     }
 }
 
+/// A response that stops with `incomplete_details.reason: "content_filter"`
+/// was cut off by OpenAI's moderation.
+fn refusal_reason(details: Option<&ResponseIncompleteDetails>) -> Option<String> {
+    (details.and_then(|details| details.reason.as_deref()) == Some("content_filter")).then(|| {
+        "the response was stopped by the content filter (reason: content_filter)".to_string()
+    })
+}
+
 fn extract_response_completed_usage(event_data: &str) -> Option<TokenUsage> {
     let payload = serde_json::from_str::<serde_json::Value>(event_data).ok()?;
     let usage = payload
@@ -1234,6 +1345,21 @@ fn resolve_tool_call_id_for_event(
     None
 }
 
+/// Streamed in place of a summary when a reasoning item is encrypted. It is
+/// not added to the final content, so it disappears once the turn completes.
+const ENCRYPTED_REASONING_INDICATOR: &str = "Thinking…";
+
+fn is_encrypted_only_reasoning(item: &serde_json::Value) -> bool {
+    if item.get("type").and_then(|value| value.as_str()) != Some("reasoning") {
+        return false;
+    }
+    let has_summary = item
+        .get("summary")
+        .and_then(|value| value.as_array())
+        .is_some_and(|parts| !parts.is_empty());
+    !has_summary && extract_non_empty_str(item, "encrypted_content").is_some()
+}
+
 fn summary_index_from_container(value: &serde_json::Value) -> Option<i64> {
     let summary_index = value.get("summary_index")?;
     summary_index.as_i64().or_else(|| {
@@ -2145,6 +2271,32 @@ mod tests {
         assert_eq!(request.max_output_tokens, None);
     }
 
+    #[test]
+    fn test_responses_api_json_schema_text_format() {
+        let text = TextConfig {
+            format: Some(TextFormat::JsonSchema {
+                name: "structured_output".to_string(),
+                schema: serde_json::json!({ "type": "object" }),
+                strict: false,
+            }),
+            style: None,
+        };
+
+        let body = serde_json::to_value(&text).expect("serialize text config");
+
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "format": {
+                    "type": "json_schema",
+                    "name": "structured_output",
+                    "schema": { "type": "object" },
+                    "strict": false,
+                }
+            })
+        );
+    }
+
     #[test]
     fn test_responses_api_reasoning_config() {
         let client = Client::new("test_key".to_string()).expect("openai responses client");
@@ -2535,6 +2687,96 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn test_convert_responses_stream_incomplete_content_filter_is_refusal() {
+        use crate::api::sse::SseEvent;
+        use futures::stream;
+        use std::pin::pin;
+
+        let events = vec![Ok(SseEvent {
+            event_type: Some("response.incomplete".to_string()),
+            data: r#"{"type":"response.incomplete","sequence_number":4,"response":{"id":"resp_3","object":"response","created_at":1,"status":"incomplete","incomplete_details":{"reason":"content_filter"}}}"#.to_string(),
+            id: None,
+        })];
+
+        let sse_stream = stream::iter(events);
+        let token = CancellationToken::new();
+        let mut stream = pin!(Client::convert_responses_stream(sse_stream, token));
+
+        let error_chunk = stream.next().await.unwrap();
+        assert!(matches!(
+            error_chunk,
+            StreamChunk::Error(StreamError::Refused { ref provider, ref reason })
+                if provider == "openai" && reason.contains("content_filter")
+        ));
+    }
+
+    async fn collect_recorded_stream(recording: &'static str) -> Vec<StreamChunk> {
+        use futures::stream;
+
+        let bytes = stream::once(async move {
+            Ok::<_, std::io::Error>(tokio_util::bytes::Bytes::from(recording))
+        });
+        let sse_stream = parse_sse_stream(bytes);
+        Client::convert_responses_stream(sse_stream, CancellationToken::new())
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_recorded_stream_streams_reasoning_summary_before_answer() {
+        let chunks =
+            collect_recorded_stream(include_str!("testdata/reasoning_summary_stream.sse")).await;
+
+        let deltas: Vec<String> = chunks
+            .iter()
+            .filter_map(|chunk| match chunk {
+                StreamChunk::ThinkingDelta(text) => Some(format!("thinking:{text}")),
+                StreamChunk::TextDelta(text) => Some(format!("text:{text}")),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            deltas,
+            vec![
+                "thinking:**Reading the layout**",
+                "thinking:\n\nThe user wants a greeting.",
+                "text:Hello",
+                "text: there!",
+            ]
+        );
+
+        let Some(StreamChunk::MessageComplete(response)) = chunks.last() else {
+            panic!("expected MessageComplete, got {:?}", chunks.last());
+        };
+        assert!(matches!(
+            &response.content[0],
+            AssistantContent::Thought {
+                thought: ThoughtContent::Simple { text },
+            } if text == "**Reading the layout**\n\nThe user wants a greeting."
+        ));
+        assert_eq!(response.extract_text(), "Hello there!");
+        assert_eq!(response.usage.map(|usage| usage.total_tokens), Some(72));
+    }
+
+    #[tokio::test]
+    async fn test_recorded_stream_with_encrypted_reasoning_only_shows_indicator() {
+        let chunks =
+            collect_recorded_stream(include_str!("testdata/encrypted_reasoning_stream.sse")).await;
+
+        assert!(matches!(
+            &chunks[0],
+            StreamChunk::ThinkingDelta(text) if text == ENCRYPTED_REASONING_INDICATOR
+        ));
+        assert!(matches!(&chunks[1], StreamChunk::TextDelta(text) if text == "Done."));
+
+        let Some(StreamChunk::MessageComplete(response)) = chunks.last() else {
+            panic!("expected MessageComplete, got {:?}", chunks.last());
+        };
+        assert_eq!(response.content.len(), 1);
+        assert_eq!(response.extract_text(), "Done.");
+    }
+
     #[tokio::test]
     async fn test_convert_responses_stream_failed_event_without_error_object() {
         use crate::api::sse::SseEvent;
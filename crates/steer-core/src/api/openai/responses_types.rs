@@ -467,7 +467,11 @@ pub enum TextFormat {
     #[serde(rename = "text")]
     Text,
     #[serde(rename = "json_schema")]
-    JsonSchema { json_schema: serde_json::Value },
+    JsonSchema {
+        name: String,
+        schema: serde_json::Value,
+        strict: bool,
+    },
 }
 
 /// Response-level error details returned by the Responses API.
@@ -528,6 +532,9 @@ pub struct ResponseFailedEvent {
     pub extra: HashMap<String, ExtraValue>,
 }
 
+/// `response.incomplete` SSE event payload; same envelope as `response.failed`.
+pub type ResponseIncompleteEvent = ResponseFailedEvent;
+
 /// Annotation for content parts
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Annotation {
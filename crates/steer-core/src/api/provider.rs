@@ -153,4 +153,49 @@ pub trait Provider: Send + Sync + 'static {
     ) -> Option<Box<dyn DynAuthenticationFlow>> {
         None
     }
+
+    /// Whether [`Provider::count_tokens`] can count a request exactly.
+    fn supports_token_counting(&self) -> bool {
+        false
+    }
+
+    /// Count the input tokens a request would use, without running it.
+    async fn count_tokens(
+        &self,
+        _model_id: &ModelId,
+        _messages: Vec<Message>,
+        _system: Option<SystemContext>,
+        _tools: Option<Vec<ToolSchema>>,
+        _token: CancellationToken,
+    ) -> Result<u32, ApiError> {
+        Err(ApiError::UnsupportedFeature {
+            provider: self.name().to_string(),
+            feature: "token counting".to_string(),
+            details: "this provider has no token counting endpoint".to_string(),
+        })
+    }
+
+    /// Whether [`Provider::complete_structured`] can constrain output to a
+    /// JSON schema natively.
+    fn supports_structured_output(&self) -> bool {
+        false
+    }
+
+    /// Answer the conversation with a JSON value the provider constrains to
+    /// `schema`. No tools are offered; callers should still validate the
+    /// result, since providers only support a subset of JSON Schema.
+    async fn complete_structured(
+        &self,
+        _model_id: &ModelId,
+        _messages: Vec<Message>,
+        _system: Option<SystemContext>,
+        _schema: &serde_json::Value,
+        _token: CancellationToken,
+    ) -> Result<serde_json::Value, ApiError> {
+        Err(ApiError::UnsupportedFeature {
+            provider: self.name().to_string(),
+            feature: "structured output".to_string(),
+            details: "this provider has no native JSON schema mode".to_string(),
+        })
+    }
 }
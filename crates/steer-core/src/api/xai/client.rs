@@ -14,7 +14,7 @@ use crate::api::sse::parse_sse_stream;
 use crate::api::util::{map_http_status_to_api_error, normalize_chat_url};
 use crate::app::SystemContext;
 use crate::app::conversation::{
-    AssistantContent, ImageSource, Message as AppMessage, ToolResult, UserContent,
+    AssistantContent, AttachmentBody, ImageSource, Message as AppMessage, ToolResult, UserContent,
 };
 use crate::config::model::{ModelId, ModelParameters};
 use steer_tools::ToolSchema;
@@ -318,7 +318,6 @@ struct XAIStreamChoice {
     #[expect(dead_code)]
     index: u32,
     delta: XAIStreamDelta,
-    #[expect(dead_code)]
     finish_reason: Option<String>,
 }
 
@@ -366,7 +365,7 @@ impl XAIClient {
             })?,
         );
 
-        let client = reqwest::Client::builder()
+        let client = crate::http::client_builder()
             .default_headers(headers)
             .timeout(std::time::Duration::from_secs(300)) // 5 minute timeout
             .build()
@@ -431,6 +430,14 @@ impl XAIClient {
                             UserContent::Image { image } => {
                                 content_parts.push(Self::user_image_part(image)?);
                             }
+                            UserContent::Attachment { attachment } => {
+                                content_parts.push(XAIUserContentPart::Text {
+                                    text: attachment.prompt_text(),
+                                });
+                                if let AttachmentBody::Image { image } = &attachment.body {
+                                    content_parts.push(Self::user_image_part(image)?);
+                                }
+                            }
                             UserContent::CommandExecution {
                                 command,
                                 stdout,
@@ -580,6 +587,14 @@ fn map_xai_usage(usage: &XAIUsage) -> TokenUsage {
     )
 }
 
+/// xAI reports `finish_reason: "content_filter"` when moderation stops the
+/// response.
+fn refusal_reason(finish_reason: Option<&str>) -> Option<String> {
+    (finish_reason == Some("content_filter")).then(|| {
+        "the response was stopped by the content filter (finish_reason: content_filter)".to_string()
+    })
+}
+
 fn convert_xai_completion_response(
     xai_response: XAICompletionResponse,
 ) -> Result<CompletionResponse, ApiError> {
@@ -591,6 +606,13 @@ fn convert_xai_completion_response(
         });
     };
 
+    if let Some(reason) = refusal_reason(choice.finish_reason.as_deref()) {
+        return Err(ApiError::RequestBlocked {
+            provider: "xai".to_string(),
+            details: reason,
+        });
+    }
+
     let mut content_blocks = Vec::new();
 
     // Add reasoning content (thinking) first if present
@@ -975,6 +997,14 @@ impl XAIClient {
                 }
 
                 if let Some(choice) = chunk.choices.first() {
+                    if let Some(reason) = refusal_reason(choice.finish_reason.as_deref()) {
+                        yield StreamChunk::Error(StreamError::Refused {
+                            provider: "xai".to_string(),
+                            reason,
+                        });
+                        break;
+                    }
+
                     if let Some(text_delta) = &choice.delta.content {
                         if let Some(AssistantContent::Text { text }) = content.last_mut() { text.push_str(text_delta) } else {
                             content.push(AssistantContent::Text {
@@ -1275,4 +1305,31 @@ mod tests {
             panic!("Expected MessageComplete");
         }
     }
+
+    #[tokio::test]
+    async fn test_convert_xai_stream_reports_content_filter() {
+        let events = vec![
+            Ok(SseEvent {
+                event_type: None,
+                data: r#"{"id":"chatcmpl-1","choices":[{"index":0,"delta":{},"finish_reason":"content_filter"}]}"#.to_string(),
+                id: None,
+            }),
+            Ok(SseEvent {
+                event_type: None,
+                data: "[DONE]".to_string(),
+                id: None,
+            }),
+        ];
+
+        let sse_stream = stream::iter(events);
+        let token = CancellationToken::new();
+        let chunks: Vec<_> = XAIClient::convert_xai_stream(sse_stream, token)
+            .collect()
+            .await;
+
+        assert!(matches!(
+            chunks.as_slice(),
+            [StreamChunk::Error(StreamError::Refused { provider, .. })] if provider == "xai"
+        ));
+    }
 }
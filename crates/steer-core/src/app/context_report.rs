@@ -0,0 +1,370 @@
+//! Token breakdown of the request the next model call would send, for the
+//! `/context` inspector.
+
+use crate::app::conversation::{Message, MessageData};
+use crate::app::domain::state::AppState;
+use crate::utils::tokens::{estimate_message_tokens, estimate_tokens};
+use steer_tools::ToolSchema;
+
+const MESSAGE_PREVIEW_CHARS: usize = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextSegmentKind {
+    SystemPrompt,
+    Environment,
+    Tools,
+    Messages,
+}
+
+impl ContextSegmentKind {
+    pub const ALL: [ContextSegmentKind; 4] = [
+        ContextSegmentKind::SystemPrompt,
+        ContextSegmentKind::Environment,
+        ContextSegmentKind::Tools,
+        ContextSegmentKind::Messages,
+    ];
+
+    /// Stable identifier used on the wire and to select a segment to drill into.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ContextSegmentKind::SystemPrompt => "system",
+            ContextSegmentKind::Environment => "environment",
+            ContextSegmentKind::Tools => "tools",
+            ContextSegmentKind::Messages => "messages",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ContextSegmentKind::SystemPrompt => "System prompt",
+            ContextSegmentKind::Environment => "Environment",
+            ContextSegmentKind::Tools => "Tool schemas",
+            ContextSegmentKind::Messages => "Messages",
+        }
+    }
+
+    /// Parses a segment identifier, accepting a few common spellings.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "system" | "system-prompt" | "prompt" => Some(ContextSegmentKind::SystemPrompt),
+            "environment" | "env" => Some(ContextSegmentKind::Environment),
+            "tools" | "tool" => Some(ContextSegmentKind::Tools),
+            "messages" | "message" | "conversation" => Some(ContextSegmentKind::Messages),
+            _ => None,
+        }
+    }
+}
+
+/// One entry within a segment: a prompt section, a tool, or a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextItem {
+    pub label: String,
+    pub tokens: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextSegment {
+    pub kind: ContextSegmentKind,
+    /// Sum of the item estimates.
+    pub tokens: u32,
+    pub items: Vec<ContextItem>,
+}
+
+impl ContextSegment {
+    fn new(kind: ContextSegmentKind, items: Vec<ContextItem>) -> Self {
+        let tokens = items
+            .iter()
+            .fold(0u32, |total, item| total.saturating_add(item.tokens));
+        Self {
+            kind,
+            tokens,
+            items,
+        }
+    }
+}
+
+/// Estimated size of each part of the next request, using the same estimator
+/// as auto-compaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextReport {
+    pub segments: Vec<ContextSegment>,
+    pub context_window_tokens: Option<u32>,
+    pub max_output_tokens: Option<u32>,
+}
+
+impl ContextReport {
+    pub fn from_state(
+        state: &AppState,
+        context_window_tokens: Option<u32>,
+        max_output_tokens: Option<u32>,
+    ) -> Self {
+        let system_context = state.cached_system_context.as_ref();
+        let prompt_items = system_context
+            .map(|context| prompt_sections(&context.prompt))
+            .unwrap_or_default();
+        let environment_items = system_context
+            .and_then(|context| context.environment.as_ref())
+            .map(|environment| environment_sections(&environment.as_context()))
+            .unwrap_or_default();
+        let message_items = state
+            .message_graph
+            .get_thread_messages()
+            .into_iter()
+            .enumerate()
+            .map(|(index, message)| ContextItem {
+                label: message_label(index + 1, message),
+                tokens: estimate_message_tokens(message),
+            })
+            .collect();
+
+        Self {
+            segments: vec![
+                ContextSegment::new(ContextSegmentKind::SystemPrompt, prompt_items),
+                ContextSegment::new(ContextSegmentKind::Environment, environment_items),
+                ContextSegment::new(ContextSegmentKind::Tools, tool_items(&state.tools)),
+                ContextSegment::new(ContextSegmentKind::Messages, message_items),
+            ],
+            context_window_tokens,
+            max_output_tokens,
+        }
+    }
+
+    pub fn total_tokens(&self) -> u32 {
+        self.segments
+            .iter()
+            .fold(0u32, |total, segment| total.saturating_add(segment.tokens))
+    }
+
+    pub fn segment(&self, kind: ContextSegmentKind) -> Option<&ContextSegment> {
+        self.segments.iter().find(|segment| segment.kind == kind)
+    }
+
+    /// Input tokens the model accepts once its maximum output is reserved.
+    pub fn input_budget_tokens(&self) -> Option<u32> {
+        self.context_window_tokens
+            .map(|window| window.saturating_sub(self.max_output_tokens.unwrap_or(0)))
+    }
+}
+
+/// Splits a prompt into sections at markdown headings.
+fn prompt_sections(prompt: &str) -> Vec<ContextItem> {
+    let mut sections: Vec<(String, String)> = Vec::new();
+    for line in prompt.trim_end().lines() {
+        let heading = line
+            .strip_prefix('#')
+            .map(|rest| rest.trim_start_matches('#').trim());
+        match heading {
+            Some(title) if !title.is_empty() => sections.push((title.to_string(), String::new())),
+            _ if sections.is_empty() => sections.push(("Preamble".to_string(), String::new())),
+            _ => {}
+        }
+        if let Some((_, body)) = sections.last_mut() {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+
+    sections
+        .into_iter()
+        .filter(|(_, body)| !body.trim().is_empty())
+        .map(|(label, body)| ContextItem {
+            label,
+            tokens: estimate_tokens(&body),
+        })
+        .collect()
+}
+
+/// Splits the rendered environment block at its top-level tags
+/// (`<env>`, `<file_structure>`, `<file name="README.md">`, ...).
+fn environment_sections(context: &str) -> Vec<ContextItem> {
+    let mut sections: Vec<(String, String)> = Vec::new();
+    for line in context.trim().lines() {
+        if let Some(tag) = opening_tag(line) {
+            let label = match tag {
+                "env" => "Environment info".to_string(),
+                "file_structure" => "File structure".to_string(),
+                "vcs_status" => "VCS status".to_string(),
+                other => other.to_string(),
+            };
+            // The introductory sentence belongs to the first block.
+            if sections.len() == 1 && sections[0].0.is_empty() {
+                sections[0].0 = label;
+            } else {
+                sections.push((label, String::new()));
+            }
+        } else if sections.is_empty() {
+            sections.push((String::new(), String::new()));
+        }
+        if let Some((_, body)) = sections.last_mut() {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+
+    sections
+        .into_iter()
+        .filter(|(_, body)| !body.trim().is_empty())
+        .map(|(label, body)| ContextItem {
+            label: if label.is_empty() {
+                "Environment info".to_string()
+            } else {
+                label
+            },
+            tokens: estimate_tokens(&body),
+        })
+        .collect()
+}
+
+/// Returns the tag name, or the `name` attribute of a `<file>` tag, when the
+/// line opens a top-level block.
+fn opening_tag(line: &str) -> Option<&str> {
+    let inner = line.strip_prefix('<')?.strip_suffix('>')?;
+    if inner.starts_with('/') {
+        return None;
+    }
+    if let Some(name) = inner
+        .strip_prefix("file name=\"")
+        .and_then(|rest| rest.strip_suffix('"'))
+    {
+        return Some(name);
+    }
+    inner
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+        .then_some(inner)
+}
+
+/// Tool schemas sorted largest first, counting what providers send: name,
+/// description and input schema.
+fn tool_items(tools: &[ToolSchema]) -> Vec<ContextItem> {
+    let mut items: Vec<ContextItem> = tools
+        .iter()
+        .map(|tool| ContextItem {
+            label: tool.name.clone(),
+            tokens: estimate_tokens(&tool.name)
+                .saturating_add(estimate_tokens(&tool.description))
+                .saturating_add(estimate_tokens(&tool.input_schema.as_value().to_string())),
+        })
+        .collect();
+    items.sort_by(|a, b| b.tokens.cmp(&a.tokens).then_with(|| a.label.cmp(&b.label)));
+    items
+}
+
+fn message_label(position: usize, message: &Message) -> String {
+    let (role, text) = match &message.data {
+        MessageData::User { .. } => ("user", message.extract_text()),
+        MessageData::Assistant { content } => {
+            let text = message.extract_text();
+            let tool_calls = content
+                .iter()
+                .filter_map(|block| match block {
+                    crate::app::conversation::AssistantContent::ToolCall { tool_call, .. } => {
+                        Some(tool_call.name.as_str())
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            if text.trim().is_empty() && !tool_calls.is_empty() {
+                ("assistant", format!("calls {}", tool_calls.join(", ")))
+            } else {
+                ("assistant", text)
+            }
+        }
+        MessageData::Tool { result, .. } => ("tool", result.llm_format()),
+    };
+
+    let first_line = text
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or("");
+    let mut preview: String = first_line
+        .trim()
+        .chars()
+        .take(MESSAGE_PREVIEW_CHARS)
+        .collect();
+    if first_line.trim().chars().count() > MESSAGE_PREVIEW_CHARS {
+        preview.push('…');
+    }
+    format!("#{position} {role}: {preview}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::SystemContext;
+    use crate::app::conversation::UserContent;
+    use crate::app::domain::types::SessionId;
+    use steer_tools::InputSchema;
+
+    #[test]
+    fn report_breaks_down_prompt_tools_and_messages() {
+        let mut state = AppState::new(SessionId::new());
+        state.cached_system_context = Some(SystemContext::new(
+            "You are helpful.\n\n# Tools\nUse tools wisely.\n".to_string(),
+        ));
+        state.tools = vec![ToolSchema {
+            name: "grep".to_string(),
+            display_name: "Grep".to_string(),
+            description: "Search file contents.".to_string(),
+            input_schema: InputSchema::new(serde_json::json!({"type": "object"})),
+        }];
+        state.message_graph.add_message(Message {
+            data: MessageData::User {
+                content: vec![UserContent::Text {
+                    text: "Find the bug in main.rs please".to_string(),
+                }],
+            },
+            timestamp: 0,
+            id: "user_1".to_string(),
+            parent_message_id: None,
+        });
+
+        let report = ContextReport::from_state(&state, Some(1_000), Some(200));
+
+        let prompt = report.segment(ContextSegmentKind::SystemPrompt).unwrap();
+        let labels: Vec<_> = prompt
+            .items
+            .iter()
+            .map(|item| item.label.as_str())
+            .collect();
+        assert_eq!(labels, vec!["Preamble", "Tools"]);
+
+        let tools = report.segment(ContextSegmentKind::Tools).unwrap();
+        assert_eq!(tools.items[0].label, "grep");
+        // "grep" (1) + description (6) + `{"type":"object"}` (5)
+        assert_eq!(tools.tokens, 12);
+
+        let messages = report.segment(ContextSegmentKind::Messages).unwrap();
+        assert_eq!(
+            messages.items[0].label,
+            "#1 user: Find the bug in main.rs please"
+        );
+        assert_eq!(messages.tokens, 8);
+
+        assert!(
+            report
+                .segment(ContextSegmentKind::Environment)
+                .unwrap()
+                .items
+                .is_empty()
+        );
+        assert_eq!(
+            report.total_tokens(),
+            prompt.tokens + tools.tokens + messages.tokens
+        );
+        assert_eq!(report.input_budget_tokens(), Some(800));
+    }
+
+    #[test]
+    fn environment_splits_at_top_level_tags() {
+        let context = "Here is useful information:\n<env>\nWorking directory: /tmp\n</env>\n\n<file_structure>\nsrc/\n</file_structure>\n<file name=\"README.md\">\nHello\n</file>";
+        let labels: Vec<_> = environment_sections(context)
+            .into_iter()
+            .map(|item| item.label)
+            .collect();
+        assert_eq!(
+            labels,
+            vec!["Environment info", "File structure", "README.md"]
+        );
+    }
+}
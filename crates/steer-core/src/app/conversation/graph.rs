@@ -1,8 +1,11 @@
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
 use tracing::debug;
 
 use super::message::{AssistantContent, Message, MessageData, UserContent};
+use crate::config::model::ModelId;
+use crate::utils::tokens::TokenCounter;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageGraph {
@@ -10,6 +13,45 @@ pub struct MessageGraph {
     pub active_message_id: Option<String>,
     #[serde(default)]
     pub compaction_summary_ids: HashSet<String>,
+    #[serde(skip)]
+    token_estimate: TokenEstimateCache,
+}
+
+/// The last thread token estimate, keyed by what it was computed from so
+/// direct writes to `messages` or `active_message_id` also invalidate it.
+#[derive(Debug, Default)]
+struct TokenEstimateCache(Mutex<Option<CachedTokenEstimate>>);
+
+#[derive(Debug, Clone, PartialEq)]
+struct CachedTokenEstimate {
+    counter: TokenCounter,
+    message_count: usize,
+    active_message_id: Option<String>,
+    tokens: u32,
+}
+
+impl TokenEstimateCache {
+    fn get(&self) -> Option<CachedTokenEstimate> {
+        self.0.lock().ok().and_then(|cached| cached.clone())
+    }
+
+    fn set(&self, estimate: CachedTokenEstimate) {
+        if let Ok(mut cached) = self.0.lock() {
+            *cached = Some(estimate);
+        }
+    }
+
+    fn invalidate(&self) {
+        if let Ok(mut cached) = self.0.lock() {
+            *cached = None;
+        }
+    }
+}
+
+impl Clone for TokenEstimateCache {
+    fn clone(&self) -> Self {
+        Self(Mutex::new(self.get()))
+    }
 }
 
 impl Default for MessageGraph {
@@ -24,16 +66,19 @@ impl MessageGraph {
             messages: Vec::new(),
             active_message_id: None,
             compaction_summary_ids: HashSet::new(),
+            token_estimate: TokenEstimateCache::default(),
         }
     }
 
     pub fn add_message(&mut self, message: Message) {
+        self.token_estimate.invalidate();
         self.active_message_id = Some(message.id().to_string());
         self.messages.push(message);
     }
 
     pub fn add_message_from_data(&mut self, message_data: MessageData) -> &Message {
         debug!(target: "message_graph::add_message", "Adding message: {:?}", message_data);
+        self.token_estimate.invalidate();
         self.messages.push(Message {
             data: message_data,
             id: Message::generate_id("", Message::current_timestamp()),
@@ -47,6 +92,7 @@ impl MessageGraph {
 
     pub fn clear(&mut self) {
         debug!(target:"message_graph::clear", "Clearing message graph");
+        self.token_estimate.invalidate();
         self.messages.clear();
         self.active_message_id = None;
     }
@@ -103,6 +149,7 @@ impl MessageGraph {
         stderr: String,
         exit_code: i32,
     ) -> Option<Message> {
+        self.token_estimate.invalidate();
         for message in &mut self.messages {
             if message.id() != message_id {
                 continue;
@@ -125,6 +172,7 @@ impl MessageGraph {
     }
 
     pub fn replace_message(&mut self, updated: Message) -> bool {
+        self.token_estimate.invalidate();
         for message in &mut self.messages {
             if message.id() == updated.id() {
                 *message = updated;
@@ -146,6 +194,7 @@ impl MessageGraph {
     }
 
     pub fn mark_compaction_summary(&mut self, id: String) {
+        self.token_estimate.invalidate();
         self.compaction_summary_ids.insert(id);
     }
 
@@ -200,6 +249,32 @@ impl MessageGraph {
     pub fn get_thread_messages(&self) -> Vec<&Message> {
         self.get_active_thread()
     }
+
+    /// Estimated tokens the active thread costs when sent to `model`.
+    pub fn token_estimate(&self, model: &ModelId) -> u32 {
+        let counter = TokenCounter::for_model(model);
+        if let Some(cached) = self.token_estimate.get()
+            && cached.counter == counter
+            && cached.message_count == self.messages.len()
+            && cached.active_message_id == self.active_message_id
+        {
+            return cached.tokens;
+        }
+
+        let tokens = self
+            .get_thread_messages()
+            .into_iter()
+            .fold(0u32, |total, message| {
+                total.saturating_add(counter.count_message(message))
+            });
+        self.token_estimate.set(CachedTokenEstimate {
+            counter,
+            message_count: self.messages.len(),
+            active_message_id: self.active_message_id.clone(),
+            tokens,
+        });
+        tokens
+    }
 }
 
 #[cfg(test)]
@@ -501,4 +576,32 @@ mod tests {
         graph.add_message(msg4);
         assert_eq!(graph.active_message_id, Some("msg4".to_string()));
     }
+    #[test]
+    fn test_token_estimate_grows_as_messages_are_added() {
+        let model = crate::config::model::builtin::claude_sonnet_4_5();
+        let mut graph = MessageGraph::new();
+        assert_eq!(graph.token_estimate(&model), 0);
+
+        let mut previous = 0;
+        let mut parent: Option<String> = None;
+        for i in 0..6 {
+            let id = format!("msg{i}");
+            let text = "a reasonably long line of conversation text ".repeat(i + 1);
+            let message = if i % 2 == 0 {
+                create_user_message(&id, parent.as_deref(), &text)
+            } else {
+                create_assistant_message(&id, parent.as_deref(), &text)
+            };
+            graph.add_message(message);
+            parent = Some(id);
+
+            let estimate = graph.token_estimate(&model);
+            assert!(
+                estimate > previous,
+                "estimate {estimate} did not grow past {previous}"
+            );
+            assert_eq!(graph.token_estimate(&model), estimate);
+            previous = estimate;
+        }
+    }
 }
@@ -41,6 +41,66 @@ pub struct ImageContent {
     pub sha256: Option<String>,
 }
 
+/// What an attached file carries to the model.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AttachmentBody {
+    /// The file's text, sent as a fenced block.
+    Text { text: String },
+    /// An image file, sent as an image block. Persisted like pasted images.
+    Image { image: ImageContent },
+}
+
+/// A file the user attached to a message, kept with its provenance.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AttachmentContent {
+    /// The path the user attached, as they gave it.
+    pub path: String,
+    pub mime_type: String,
+    /// Size of the original file.
+    pub bytes: u64,
+    pub body: AttachmentBody,
+}
+
+impl AttachmentContent {
+    /// File name of the attachment, for labels.
+    pub fn file_name(&self) -> &str {
+        std::path::Path::new(&self.path)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(&self.path)
+    }
+
+    /// One-line description by name and size, used where the contents are
+    /// left out.
+    pub fn summary(&self) -> String {
+        format!("{} ({}, {} bytes)", self.path, self.mime_type, self.bytes)
+    }
+
+    /// The attachment as prompt text: text files become a fenced block headed
+    /// by their path; images, which providers send as image blocks, get just
+    /// the heading.
+    pub fn prompt_text(&self) -> String {
+        match &self.body {
+            AttachmentBody::Text { text } => {
+                let language = std::path::Path::new(&self.path)
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .unwrap_or("");
+                // The fence has to be longer than any backtick run in the file.
+                let longest_run = text.split(|ch| ch != '`').map(str::len).max().unwrap_or(0);
+                let fence = "`".repeat(longest_run.max(2) + 1);
+                let newline = if text.ends_with('\n') { "" } else { "\n" };
+                format!(
+                    "Attached file `{}`:\n{fence}{language}\n{text}{newline}{fence}",
+                    self.path
+                )
+            }
+            AttachmentBody::Image { .. } => format!("Attached image `{}`", self.path),
+        }
+    }
+}
+
 /// Content that can be sent by a user
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -51,6 +111,9 @@ pub enum UserContent {
     Image {
         image: ImageContent,
     },
+    Attachment {
+        attachment: AttachmentContent,
+    },
     CommandExecution {
         command: String,
         stdout: String,
@@ -204,6 +267,9 @@ impl Message {
                 .map(|c| match c {
                     UserContent::Text { text } => text.clone(),
                     UserContent::Image { .. } => "[Image]".to_string(),
+                    UserContent::Attachment { attachment } => {
+                        format!("[Attachment: {}]", attachment.path)
+                    }
                     UserContent::CommandExecution { stdout, .. } => stdout.clone(),
                 })
                 .collect::<Vec<_>>()
@@ -234,6 +300,9 @@ impl Message {
                     UserContent::Image { image } => {
                         format!("[Image: {}]", image.mime_type)
                     }
+                    UserContent::Attachment { attachment } => {
+                        format!("[Attachment: {}]", attachment.summary())
+                    }
                     UserContent::CommandExecution {
                         command,
                         stdout,
@@ -281,6 +350,8 @@ impl Message {
                     ToolResult::TodoWrite(_) => "Todo Update",
                     ToolResult::Fetch(_) => "Fetch Result",
                     ToolResult::Agent(_) => "Agent Result",
+                    ToolResult::GitStatus(_) => "Git Status Result",
+                    ToolResult::Memory(_) => "Session Memory",
                     ToolResult::External(_) => "External Tool Result",
                     ToolResult::Error(_) => "Error",
                 };
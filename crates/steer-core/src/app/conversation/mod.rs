@@ -3,6 +3,6 @@ mod message;
 
 pub use graph::MessageGraph;
 pub use message::{
-    AssistantContent, ImageContent, ImageSource, Message, MessageData, Role, ThoughtContent,
-    ThoughtSignature, ToolResult, UserContent,
+    AssistantContent, AttachmentBody, AttachmentContent, ImageContent, ImageSource, Message,
+    MessageData, Role, ThoughtContent, ThoughtSignature, ToolResult, UserContent,
 };
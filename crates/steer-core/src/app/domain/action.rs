@@ -8,7 +8,7 @@ use steer_tools::result::ToolResult;
 use steer_tools::{ToolCall, ToolError, ToolSchema};
 use thiserror::Error;
 
-use super::event::SessionEvent;
+use super::event::{BestOfCandidate, SessionEvent};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ModelCallRequestErrorKind {
@@ -88,6 +88,10 @@ pub enum ModelCallError {
 
     #[error("Model stream ended without completion response")]
     MissingCompletionResponse,
+
+    /// The provider refused the request or filtered the response.
+    #[error("{provider} refused the request: {reason}")]
+    Refused { provider: String, reason: String },
 }
 
 #[derive(Debug, Clone, Error, PartialEq, Eq)]
@@ -96,6 +100,15 @@ pub enum SessionTitleGenerationError {
     ModelCall(#[from] ModelCallError),
 }
 
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum SessionSummaryError {
+    #[error(transparent)]
+    ModelCall(#[from] ModelCallError),
+
+    #[error("No credentials configured for provider {provider}")]
+    NotAuthenticated { provider: String },
+}
+
 #[derive(Debug, Clone)]
 pub enum Action {
     UserInput {
@@ -117,6 +130,45 @@ pub enum Action {
         timestamp: u64,
     },
 
+    /// Regenerate the response to the last user prompt on the active branch.
+    /// The replaced turn stays on a sibling branch.
+    RetryLastTurn {
+        session_id: SessionId,
+        op_id: OpId,
+        new_message_id: MessageId,
+        model: ModelId,
+        timestamp: u64,
+    },
+
+    /// Send `content` as a new prompt and draft one candidate answer per
+    /// entry in `candidate_op_ids` in parallel, for the user to pick from.
+    StartBestOf {
+        session_id: SessionId,
+        content: Vec<UserContent>,
+        op_id: OpId,
+        message_id: MessageId,
+        candidate_op_ids: Vec<OpId>,
+        model: ModelId,
+        timestamp: u64,
+    },
+
+    /// A best-of candidate finished. `side_effect_tool` names the tool that
+    /// stopped it when it needed one with side effects.
+    BestOfCandidateFinished {
+        session_id: SessionId,
+        op_id: OpId,
+        candidate: BestOfCandidate,
+        side_effect_tool: Option<String>,
+    },
+
+    /// Make a best-of candidate the answer; the others stay on sibling
+    /// branches.
+    PickBestOfCandidate {
+        session_id: SessionId,
+        index: u32,
+        timestamp: u64,
+    },
+
     ToolApprovalRequested {
         session_id: SessionId,
         request_id: RequestId,
@@ -160,6 +212,17 @@ pub enum Action {
         agent_id: String,
     },
 
+    /// Switch the model used by the active agent operation.
+    ///
+    /// `op_id` identifies the replacement operation when `mode` is
+    /// [`ModelSwitchMode::RestartOperation`]; it is unused otherwise.
+    SwitchModel {
+        session_id: SessionId,
+        model: crate::config::model::ModelId,
+        mode: ModelSwitchMode,
+        op_id: OpId,
+    },
+
     McpServerStateChanged {
         session_id: SessionId,
         server_name: String,
@@ -183,6 +246,13 @@ pub enum Action {
         error: String,
     },
 
+    /// The provider refused the request; see [`ModelCallError::Refused`].
+    ModelResponseRefused {
+        session_id: SessionId,
+        op_id: OpId,
+        reason: String,
+    },
+
     SessionTitleGenerated {
         session_id: SessionId,
         title: String,
@@ -193,11 +263,45 @@ pub enum Action {
         error: SessionTitleGenerationError,
     },
 
+    SessionSummaryGenerated {
+        session_id: SessionId,
+        summary: String,
+        message_count: usize,
+    },
+
+    SessionSummaryGenerationFailed {
+        session_id: SessionId,
+        error: SessionSummaryError,
+    },
+
+    /// The provider counted a request exactly; `estimated` is our own
+    /// estimate of the same request.
+    TokenCountCalibrated {
+        session_id: SessionId,
+        model: ModelId,
+        counted: u32,
+        estimated: u32,
+    },
+
     Cancel {
         session_id: SessionId,
         op_id: Option<OpId>,
     },
 
+    /// Cancel an operation that was in flight when the session's previous
+    /// actor stopped and cannot be resumed.
+    AbandonInterruptedOperation {
+        session_id: SessionId,
+        op_id: OpId,
+    },
+
+    /// Re-request the approval an operation was waiting on when the session's
+    /// previous actor stopped, under a new request id.
+    ReissuePendingApproval {
+        session_id: SessionId,
+        op_id: OpId,
+    },
+
     DirectBashCommand {
         session_id: SessionId,
         op_id: OpId,
@@ -252,10 +356,34 @@ pub enum Action {
     },
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ApprovalDecision {
     Approved,
     Denied,
+    /// Denied with a user-supplied reason that is passed back to the model.
+    DeniedWithReason(String),
+    /// Approved for only the listed hunks of an edit tool call (see
+    /// [`steer_tools::tools::edit::hunks`]); the rest are reverted before execution.
+    ApprovedHunks(Vec<usize>),
+}
+
+impl ApprovalDecision {
+    pub fn is_approved(&self) -> bool {
+        matches!(
+            self,
+            ApprovalDecision::Approved | ApprovalDecision::ApprovedHunks(_)
+        )
+    }
+}
+
+/// How a model switch applies to an agent operation that is already running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ModelSwitchMode {
+    /// Let the in-flight request finish; later requests in the operation use the new model.
+    #[default]
+    AfterCurrentTurn,
+    /// Cancel the operation and restart it from the current conversation with the new model.
+    RestartOperation,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -285,6 +413,10 @@ impl Action {
         match self {
             Action::UserInput { session_id, .. }
             | Action::UserEditedMessage { session_id, .. }
+            | Action::RetryLastTurn { session_id, .. }
+            | Action::StartBestOf { session_id, .. }
+            | Action::BestOfCandidateFinished { session_id, .. }
+            | Action::PickBestOfCandidate { session_id, .. }
             | Action::ToolApprovalRequested { session_id, .. }
             | Action::ToolApprovalDecided { session_id, .. }
             | Action::ToolExecutionStarted { session_id, .. }
@@ -292,12 +424,19 @@ impl Action {
             | Action::ToolSchemasAvailable { session_id, .. }
             | Action::ToolSchemasUpdated { session_id, .. }
             | Action::SwitchPrimaryAgent { session_id, .. }
+            | Action::SwitchModel { session_id, .. }
             | Action::McpServerStateChanged { session_id, .. }
             | Action::ModelResponseComplete { session_id, .. }
             | Action::ModelResponseError { session_id, .. }
+            | Action::ModelResponseRefused { session_id, .. }
             | Action::SessionTitleGenerated { session_id, .. }
             | Action::SessionTitleGenerationFailed { session_id, .. }
+            | Action::SessionSummaryGenerated { session_id, .. }
+            | Action::SessionSummaryGenerationFailed { session_id, .. }
+            | Action::TokenCountCalibrated { session_id, .. }
             | Action::Cancel { session_id, .. }
+            | Action::AbandonInterruptedOperation { session_id, .. }
+            | Action::ReissuePendingApproval { session_id, .. }
             | Action::DirectBashCommand { session_id, .. }
             | Action::DequeueQueuedItem { session_id, .. }
             | Action::DrainQueuedWork { session_id, .. }
@@ -314,12 +453,17 @@ impl Action {
         match self {
             Action::UserInput { op_id, .. }
             | Action::UserEditedMessage { op_id, .. }
+            | Action::RetryLastTurn { op_id, .. }
+            | Action::StartBestOf { op_id, .. }
+            | Action::BestOfCandidateFinished { op_id, .. }
             | Action::DirectBashCommand { op_id, .. }
             | Action::RequestCompaction { op_id, .. }
             | Action::ModelResponseComplete { op_id, .. }
             | Action::ModelResponseError { op_id, .. }
             | Action::CompactionComplete { op_id, .. }
-            | Action::CompactionFailed { op_id, .. } => Some(*op_id),
+            | Action::CompactionFailed { op_id, .. }
+            | Action::AbandonInterruptedOperation { op_id, .. }
+            | Action::ReissuePendingApproval { op_id, .. } => Some(*op_id),
             Action::Cancel { op_id, .. } => *op_id,
             _ => None,
         }
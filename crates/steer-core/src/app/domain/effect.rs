@@ -5,6 +5,7 @@ use crate::config::model::ModelId;
 use steer_tools::{ToolCall, ToolSchema};
 
 use super::event::SessionEvent;
+use super::session::ToolApprover;
 
 #[derive(Debug, Clone)]
 pub enum Effect {
@@ -23,6 +24,7 @@ pub enum Effect {
         session_id: SessionId,
         op_id: OpId,
         tool_call: ToolCall,
+        approved_by: ToolApprover,
     },
 
     CallModel {
@@ -34,6 +36,18 @@ pub enum Effect {
         tools: Vec<ToolSchema>,
     },
 
+    /// Draft one answer per candidate op id in parallel. Only read-only
+    /// tools run; a candidate needing any other tool stops the run.
+    RunBestOf {
+        session_id: SessionId,
+        op_id: OpId,
+        candidate_op_ids: Vec<OpId>,
+        model: ModelId,
+        messages: Vec<Message>,
+        system_context: Option<SystemContext>,
+        tools: Vec<ToolSchema>,
+    },
+
     GenerateSessionTitle {
         session_id: SessionId,
         op_id: OpId,
@@ -41,6 +55,13 @@ pub enum Effect {
         user_prompt: String,
     },
 
+    /// Summarize `messages` in the background; never tied to an operation.
+    GenerateSessionSummary {
+        session_id: SessionId,
+        model: ModelId,
+        messages: Vec<Message>,
+    },
+
     ListWorkspaceFiles {
         session_id: SessionId,
     },
@@ -85,7 +106,9 @@ impl Effect {
             | Effect::RequestUserApproval { session_id, .. }
             | Effect::ExecuteTool { session_id, .. }
             | Effect::CallModel { session_id, .. }
+            | Effect::RunBestOf { session_id, .. }
             | Effect::GenerateSessionTitle { session_id, .. }
+            | Effect::GenerateSessionSummary { session_id, .. }
             | Effect::ListWorkspaceFiles { session_id }
             | Effect::CancelOperation { session_id, .. }
             | Effect::ConnectMcpServer { session_id, .. }
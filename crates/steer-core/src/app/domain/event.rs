@@ -1,5 +1,5 @@
 use crate::api::provider::TokenUsage;
-use crate::app::conversation::Message;
+use crate::app::conversation::{AssistantContent, Message};
 use crate::app::domain::action::{ApprovalDecision, ApprovalMemory, McpServerState};
 use crate::app::domain::types::{
     CompactionRecord, MessageId, OpId, RequestId, SessionId, ToolCallId,
@@ -55,6 +55,14 @@ pub enum SessionEvent {
         context_window: Option<ContextWindowUsage>,
     },
 
+    /// Estimated tokens the conversation occupies as it is sent to the
+    /// model, against the context window when it is known.
+    ContextUsage {
+        used: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        limit: Option<u32>,
+    },
+
     MessageUpdated {
         message: Message,
     },
@@ -98,6 +106,10 @@ pub enum SessionEvent {
 
     OperationCompleted {
         op_id: OpId,
+        /// Where the operation's wall-clock time went. Filled in by the
+        /// session runtime; absent for operations it did not time.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timing: Option<OperationTiming>,
     },
 
     OperationCancelled {
@@ -124,10 +136,51 @@ pub enum SessionEvent {
         message: String,
     },
 
+    /// The provider refused the request or filtered the response, so the
+    /// model call produced no message.
+    Refused {
+        reason: String,
+    },
+
     McpServerStateChanged {
         server_name: String,
         state: McpServerState,
     },
+
+    /// A best-of turn started drafting one candidate answer to
+    /// `user_message_id` per entry in `candidate_op_ids`. Each candidate's
+    /// token usage is reported under its own op id.
+    BestOfStarted {
+        op_id: OpId,
+        user_message_id: MessageId,
+        model: ModelId,
+        candidate_op_ids: Vec<OpId>,
+    },
+
+    BestOfCandidateReady {
+        op_id: OpId,
+        candidate: BestOfCandidate,
+    },
+
+    /// The best-of turn ended. `picked` is the candidate that became the
+    /// answer, or `None` when the run was refused, failed or discarded.
+    BestOfResolved {
+        op_id: OpId,
+        picked: Option<u32>,
+    },
+}
+
+/// One drafted answer of a best-of turn.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BestOfCandidate {
+    pub index: u32,
+    pub op_id: OpId,
+    pub message_id: MessageId,
+    pub content: Vec<AssistantContent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -231,6 +284,61 @@ pub struct ContextWindowUsage {
     pub estimated: bool,
 }
 
+/// Latency breakdown of a finished operation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperationTiming {
+    /// From the operation starting to it completing; excludes queue wait.
+    pub total_ms: u64,
+    /// Spans in the order they finished. Tool spans may overlap when tools
+    /// run concurrently, and time between spans is not recorded.
+    pub spans: Vec<TimingSpan>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimingSpan {
+    pub kind: TimingSpanKind,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TimingSpanKind {
+    /// Waiting behind another operation before starting.
+    QueueWait,
+    /// From sending a model request until its first streamed output.
+    TimeToFirstToken,
+    /// From the first streamed output until the response completed.
+    Streaming,
+    ToolExecution {
+        tool_name: String,
+    },
+    /// Waiting for the user to approve or deny a tool call.
+    ApprovalWait,
+}
+
+impl TimingSpanKind {
+    pub fn label(&self) -> String {
+        match self {
+            Self::QueueWait => "queue wait".to_string(),
+            Self::TimeToFirstToken => "model: first token".to_string(),
+            Self::Streaming => "model: streaming".to_string(),
+            Self::ToolExecution { tool_name } => format!("tool: {tool_name}"),
+            Self::ApprovalWait => "approval wait".to_string(),
+        }
+    }
+
+    /// Stable metric label for the phase this span belongs to.
+    pub fn phase(&self) -> &'static str {
+        match self {
+            Self::QueueWait => "queue_wait",
+            Self::TimeToFirstToken => "time_to_first_token",
+            Self::Streaming => "streaming",
+            Self::ToolExecution { .. } => "tool_execution",
+            Self::ApprovalWait => "approval_wait",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CancellationInfo {
     pub pending_tool_calls: usize,
@@ -260,16 +368,21 @@ impl SessionEvent {
     pub fn is_error(&self) -> bool {
         matches!(
             self,
-            SessionEvent::Error { .. } | SessionEvent::ToolCallFailed { .. }
+            SessionEvent::Error { .. }
+                | SessionEvent::Refused { .. }
+                | SessionEvent::ToolCallFailed { .. }
         )
     }
 
     pub fn operation_id(&self) -> Option<OpId> {
         match self {
             SessionEvent::OperationStarted { op_id, .. }
-            | SessionEvent::OperationCompleted { op_id }
+            | SessionEvent::OperationCompleted { op_id, .. }
             | SessionEvent::OperationCancelled { op_id, .. }
-            | SessionEvent::LlmUsageUpdated { op_id, .. } => Some(*op_id),
+            | SessionEvent::LlmUsageUpdated { op_id, .. }
+            | SessionEvent::BestOfStarted { op_id, .. }
+            | SessionEvent::BestOfCandidateReady { op_id, .. }
+            | SessionEvent::BestOfResolved { op_id, .. } => Some(*op_id),
             _ => None,
         }
     }
@@ -6,12 +6,16 @@ pub mod reduce;
 pub mod runtime;
 pub mod session;
 pub mod state;
+pub mod tool_budget;
 pub mod types;
 
 #[cfg(test)]
 mod tests;
 
-pub use action::{Action, ApprovalDecision, ApprovalMemory, McpServerState, SchemaSource};
+pub use action::{
+    Action, ApprovalDecision, ApprovalMemory, McpServerState, ModelSwitchMode, SchemaSource,
+    SessionSummaryError,
+};
 pub use delta::{StreamDelta, ToolCallDelta};
 pub use effect::{Effect, McpServerConfig};
 pub use event::{CancellationInfo, OperationKind, SessionEvent};
@@ -2,30 +2,37 @@ use crate::agents::default_agent_spec_id;
 use crate::api::provider::TokenUsage;
 use crate::app::conversation::{AssistantContent, Message, MessageData, UserContent};
 
-use crate::app::domain::action::{Action, ApprovalDecision, ApprovalMemory, McpServerState};
+use crate::app::domain::action::{
+    Action, ApprovalDecision, ApprovalMemory, McpServerState, ModelSwitchMode, SessionSummaryError,
+};
 
 use crate::app::domain::effect::{Effect, McpServerConfig};
 use crate::app::domain::event::{
-    CancellationInfo, ContextWindowUsage, QueuedWorkItemSnapshot, QueuedWorkKind, SessionEvent,
+    BestOfCandidate, CancellationInfo, ContextWindowUsage, QueuedWorkItemSnapshot, QueuedWorkKind,
+    SessionEvent,
 };
+use crate::app::domain::session::ToolApprover;
 use crate::app::domain::state::{
-    AppState, OperationKind, PendingApproval, QueuedApproval, QueuedWorkItem,
+    AppState, BestOfRun, MAX_BEST_OF_CANDIDATES, OperationKind, OperationState, PendingApproval,
+    QueuedApproval, QueuedWorkItem,
 };
+use crate::app::domain::tool_budget;
 use crate::primary_agents::{
     default_primary_agent_id, primary_agent_spec, resolve_effective_config,
 };
-use crate::session::state::{BackendConfig, ToolDecision};
+use crate::session::state::{BackendConfig, ResolvedToolResultBudget, ToolDecision};
+use crate::utils::tokens::{TokenCalibration, calibrated, estimate_message_tokens};
 
 use crate::app::domain::event::CompactTrigger;
 use crate::tools::{DISPATCH_AGENT_TOOL_NAME, DispatchAgentParams, DispatchAgentTarget};
 use serde_json::Value;
 use steer_tools::ToolError;
-use steer_tools::result::ToolResult;
+use steer_tools::result::{SkippedEdit, ToolResult};
 use steer_tools::tools::BASH_TOOL_NAME;
+use steer_tools::tools::edit::hunks::{accept_hunks, edit_hunks};
 use thiserror::Error;
 
 const MIN_MESSAGES_FOR_COMPACT: usize = 3;
-const ESTIMATED_CHARS_PER_TOKEN: f64 = 4.0;
 const SESSION_TITLE_MAX_CHARS: usize = 80;
 const COMPACTION_CONTINUE_PROMPT: &str =
     "Continue from the compaction summary and resume the conversation.";
@@ -36,6 +43,8 @@ pub enum InvalidActionKind {
     MissingSessionConfig,
     UnknownPrimaryAgent,
     QueueEmpty,
+    NothingToRetry,
+    InvalidBestOf,
 }
 
 #[derive(Debug, Error)]
@@ -156,49 +165,88 @@ fn prune_assistant_tool_calls(content: &[AssistantContent]) -> Option<Vec<Assist
     }
 }
 
-fn estimated_additional_tokens_from_message(message: &Message) -> u32 {
-    let chars = match &message.data {
-        MessageData::Tool { result, .. } => result.llm_format().chars().count(),
-        MessageData::Assistant { content } => content
-            .iter()
-            .map(|block| match block {
-                AssistantContent::Text { text } => text.chars().count(),
-                AssistantContent::Image { image } => image.mime_type.chars().count(),
-                AssistantContent::ToolCall { tool_call, .. } => {
-                    tool_call.name.chars().count()
-                        + tool_call.parameters.to_string().chars().count()
-                }
-                AssistantContent::Thought { thought } => thought.display_text().chars().count(),
-            })
-            .sum(),
-        MessageData::User { content } => content
-            .iter()
-            .map(|block| match block {
-                UserContent::Text { text } => text.chars().count(),
-                UserContent::Image { image } => image.mime_type.chars().count(),
-                UserContent::CommandExecution {
-                    command,
-                    stdout,
-                    stderr,
-                    ..
-                } => command.chars().count() + stdout.chars().count() + stderr.chars().count(),
-            })
-            .sum(),
-    };
+fn estimated_additional_tokens_from_trailing_tool_messages(
+    state: &AppState,
+    model: &crate::config::model::ModelId,
+) -> u32 {
+    let estimate = state
+        .message_graph
+        .get_thread_messages()
+        .into_iter()
+        .rev()
+        .take_while(|message| matches!(&message.data, MessageData::Tool { .. }))
+        .fold(0u32, |total, message| {
+            total.saturating_add(estimate_message_tokens(message))
+        });
+    calibrated(estimate, state.token_calibrations.get(model))
+}
 
-    ((chars as f64) / ESTIMATED_CHARS_PER_TOKEN).ceil() as u32
+fn known_context_window_tokens(state: &AppState) -> Option<u32> {
+    state
+        .llm_usage_by_op
+        .values()
+        .filter_map(|snapshot| snapshot.context_window.as_ref())
+        .filter_map(|cw| cw.max_context_tokens)
+        .max()
 }
 
-fn estimated_additional_tokens_from_trailing_tool_messages(state: &AppState) -> u32 {
+fn tool_result_budget(state: &AppState) -> ResolvedToolResultBudget {
     state
+        .session_config
+        .as_ref()
+        .map(|config| config.tool_config.result_budget)
+        .unwrap_or_default()
+        .resolve(known_context_window_tokens(state))
+}
+
+/// Apply the per-turn cap to the tool results that follow the last assistant
+/// message, rewriting any that had to be cut.
+fn enforce_turn_tool_budget(
+    state: &mut AppState,
+    session_id: crate::app::domain::types::SessionId,
+    max_turn_bytes: usize,
+) -> Vec<Effect> {
+    let mut turn_messages: Vec<Message> = state
         .message_graph
         .get_thread_messages()
         .into_iter()
         .rev()
         .take_while(|message| matches!(&message.data, MessageData::Tool { .. }))
-        .fold(0u32, |total, message| {
-            total.saturating_add(estimated_additional_tokens_from_message(message))
+        .cloned()
+        .collect();
+    turn_messages.reverse();
+
+    let mut results: Vec<ToolResult> = turn_messages
+        .iter()
+        .filter_map(|message| match &message.data {
+            MessageData::Tool { result, .. } => Some(result.clone()),
+            _ => None,
+        })
+        .collect();
+
+    let truncated = tool_budget::enforce_turn_budget(&mut results, max_turn_bytes);
+    if !truncated.is_empty() {
+        tracing::debug!(
+            count = truncated.len(),
+            max_turn_bytes,
+            "Truncated tool results to fit the turn budget"
+        );
+    }
+
+    truncated
+        .into_iter()
+        .filter_map(|index| {
+            let mut message = turn_messages.get(index)?.clone();
+            if let MessageData::Tool { result, .. } = &mut message.data {
+                *result = results[index].clone();
+            }
+            state.message_graph.replace_message(message.clone());
+            Some(Effect::EmitEvent {
+                session_id,
+                event: SessionEvent::MessageUpdated { message },
+            })
         })
+        .collect()
 }
 
 fn latest_final_usage_total_tokens(state: &AppState) -> Option<u32> {
@@ -258,17 +306,13 @@ fn maybe_prepare_auto_compaction_from_tool_results(
     model: &crate::config::model::ModelId,
 ) -> Option<Vec<Effect>> {
     let latest_usage_total = latest_final_usage_total_tokens(state)?;
-    let projected_tool_growth = estimated_additional_tokens_from_trailing_tool_messages(state);
+    let projected_tool_growth =
+        estimated_additional_tokens_from_trailing_tool_messages(state, model);
     if projected_tool_growth == 0 {
         return None;
     }
     let projected_total_tokens = latest_usage_total.saturating_add(projected_tool_growth);
-    let context_window_tokens = state
-        .llm_usage_by_op
-        .values()
-        .filter_map(|snapshot| snapshot.context_window.as_ref())
-        .filter_map(|cw| cw.max_context_tokens)
-        .max();
+    let context_window_tokens = known_context_window_tokens(state);
 
     if !should_auto_compact_for_projected_context(
         state,
@@ -286,7 +330,10 @@ fn maybe_prepare_auto_compaction_from_tool_results(
 
     let mut effects = vec![Effect::EmitEvent {
         session_id,
-        event: SessionEvent::OperationCompleted { op_id },
+        event: SessionEvent::OperationCompleted {
+            op_id,
+            timing: None,
+        },
     }];
 
     let auto = maybe_auto_compact(
@@ -307,6 +354,44 @@ fn maybe_prepare_auto_compaction_from_tool_results(
 }
 
 pub fn reduce(state: &mut AppState, action: Action) -> Result<Vec<Effect>, ReduceError> {
+    let effects = reduce_action(state, action)?;
+    Ok(with_context_usage(state, effects))
+}
+
+/// Precede every model call with an estimate of how much of the context
+/// window the conversation being sent already occupies.
+fn with_context_usage(state: &AppState, effects: Vec<Effect>) -> Vec<Effect> {
+    if !effects
+        .iter()
+        .any(|effect| matches!(effect, Effect::CallModel { .. }))
+    {
+        return effects;
+    }
+
+    let limit = known_context_window_tokens(state);
+    let mut with_usage = Vec::with_capacity(effects.len() + 1);
+    for effect in effects {
+        if let Effect::CallModel {
+            session_id, model, ..
+        } = &effect
+        {
+            with_usage.push(Effect::EmitEvent {
+                session_id: *session_id,
+                event: SessionEvent::ContextUsage {
+                    used: calibrated(
+                        state.message_graph.token_estimate(model),
+                        state.token_calibrations.get(model),
+                    ),
+                    limit,
+                },
+            });
+        }
+        with_usage.push(effect);
+    }
+    with_usage
+}
+
+fn reduce_action(state: &mut AppState, action: Action) -> Result<Vec<Effect>, ReduceError> {
     match action {
         Action::UserInput {
             session_id,
@@ -340,6 +425,54 @@ pub fn reduce(state: &mut AppState, action: Action) -> Result<Vec<Effect>, Reduc
             },
         ),
 
+        Action::RetryLastTurn {
+            session_id,
+            op_id,
+            new_message_id,
+            model,
+            timestamp,
+        } => handle_retry_last_turn(state, session_id, op_id, new_message_id, model, timestamp),
+
+        Action::StartBestOf {
+            session_id,
+            content,
+            op_id,
+            message_id,
+            candidate_op_ids,
+            model,
+            timestamp,
+        } => handle_start_best_of(
+            state,
+            session_id,
+            StartBestOfParams {
+                content,
+                op_id,
+                message_id,
+                candidate_op_ids,
+                model,
+                timestamp,
+            },
+        ),
+
+        Action::BestOfCandidateFinished {
+            session_id,
+            op_id,
+            candidate,
+            side_effect_tool,
+        } => Ok(handle_best_of_candidate_finished(
+            state,
+            session_id,
+            op_id,
+            candidate,
+            side_effect_tool,
+        )),
+
+        Action::PickBestOfCandidate {
+            session_id,
+            index,
+            timestamp,
+        } => handle_pick_best_of_candidate(state, session_id, index, timestamp),
+
         Action::ToolApprovalRequested {
             session_id,
             request_id,
@@ -414,6 +547,14 @@ pub fn reduce(state: &mut AppState, action: Action) -> Result<Vec<Effect>, Reduc
             state, session_id, op_id, &error,
         )),
 
+        Action::ModelResponseRefused {
+            session_id,
+            op_id,
+            reason,
+        } => Ok(handle_model_response_refused(
+            state, session_id, op_id, reason,
+        )),
+
         Action::SessionTitleGenerated { session_id, title } => {
             Ok(handle_session_title_generated(state, session_id, title))
         }
@@ -427,8 +568,64 @@ pub fn reduce(state: &mut AppState, action: Action) -> Result<Vec<Effect>, Reduc
             Ok(vec![])
         }
 
+        Action::SessionSummaryGenerated {
+            session_id,
+            summary,
+            message_count,
+        } => Ok(handle_session_summary_generated(
+            state,
+            session_id,
+            summary,
+            message_count,
+        )),
+
+        Action::SessionSummaryGenerationFailed { session_id, error } => {
+            state.summary_in_flight = false;
+            if let SessionSummaryError::NotAuthenticated { .. } = error {
+                tracing::debug!(
+                    session_id = %session_id,
+                    error = %error,
+                    "Skipped session summary"
+                );
+            } else {
+                tracing::warn!(
+                    session_id = %session_id,
+                    error = %error,
+                    "Session summary generation failed"
+                );
+            }
+            Ok(vec![])
+        }
+
+        Action::TokenCountCalibrated {
+            model,
+            counted,
+            estimated,
+            ..
+        } => {
+            if let Some(calibration) = TokenCalibration::new(counted, estimated) {
+                tracing::debug!(
+                    %model,
+                    counted,
+                    estimated,
+                    ratio = calibration.ratio(),
+                    "Calibrated token estimates"
+                );
+                state.token_calibrations.insert(model, calibration);
+            }
+            Ok(vec![])
+        }
+
         Action::Cancel { session_id, op_id } => Ok(handle_cancel(state, session_id, op_id)),
 
+        Action::AbandonInterruptedOperation { session_id, op_id } => Ok(
+            handle_abandon_interrupted_operation(state, session_id, op_id),
+        ),
+
+        Action::ReissuePendingApproval { session_id, op_id } => {
+            Ok(handle_reissue_pending_approval(state, session_id, op_id))
+        }
+
         Action::DirectBashCommand {
             session_id,
             op_id,
@@ -475,6 +672,13 @@ pub fn reduce(state: &mut AppState, action: Action) -> Result<Vec<Effect>, Reduc
             agent_id,
         } => handle_switch_primary_agent(state, session_id, agent_id),
 
+        Action::SwitchModel {
+            session_id,
+            model,
+            mode,
+            op_id,
+        } => Ok(handle_switch_model(state, session_id, model, mode, op_id)),
+
         Action::McpServerStateChanged {
             session_id,
             server_name,
@@ -579,6 +783,8 @@ fn handle_user_input(
         return effects;
     }
 
+    effects.extend(discard_best_of(state, session_id));
+
     let parent_id = state.message_graph.active_message_id.clone();
 
     let message = Message {
@@ -667,6 +873,7 @@ fn handle_user_edited_message(
             "Cannot edit message while an operation is active.",
         ));
     }
+    effects.extend(discard_best_of(state, session_id));
 
     let parent_id = state
         .message_graph
@@ -722,101 +929,449 @@ fn handle_user_edited_message(
     Ok(effects)
 }
 
-fn handle_tool_approval_requested(
+/// Re-submit the last user prompt on the active branch unchanged, as an edit.
+/// The previous attempt, including any tool calls it made, stays on a sibling
+/// branch and is never sent to the model again.
+fn handle_retry_last_turn(
     state: &mut AppState,
     session_id: crate::app::domain::types::SessionId,
-    request_id: crate::app::domain::types::RequestId,
-    tool_call: steer_tools::ToolCall,
-) -> Vec<Effect> {
-    let mut effects = Vec::new();
-
-    if let Err(error) = validate_tool_call(state, &tool_call) {
-        let error_message = error.to_string();
-        return fail_tool_call_without_execution(
-            state,
-            session_id,
-            tool_call,
-            error,
-            error_message,
-            "invalid",
-            true,
-        );
+    op_id: crate::app::domain::types::OpId,
+    new_message_id: crate::app::domain::types::MessageId,
+    model: crate::config::model::ModelId,
+    timestamp: u64,
+) -> Result<Vec<Effect>, ReduceError> {
+    if state.has_active_operation() {
+        return Err(invalid_action(
+            InvalidActionKind::OperationInFlight,
+            "Cannot retry while an operation is active.",
+        ));
     }
 
-    let decision = get_tool_decision(state, &tool_call);
-
-    match decision {
-        ToolDecision::Allow => {
-            let Some(op_id) = state.current_operation.as_ref().map(|o| o.op_id) else {
-                return vec![Effect::EmitEvent {
-                    session_id,
-                    event: SessionEvent::Error {
-                        message: "Tool approval requested without active operation".to_string(),
-                    },
-                }];
-            };
-            state.add_pending_tool_call(crate::app::domain::types::ToolCallId::from_string(
-                &tool_call.id,
-            ));
-
-            effects.push(Effect::ExecuteTool {
-                session_id,
-                op_id,
-                tool_call,
-            });
-        }
-        ToolDecision::Deny => {
-            let error = ToolError::DeniedByPolicy(tool_call.name.clone());
-            let tool_name = tool_call.name.clone();
-            effects.extend(fail_tool_call_without_execution(
-                state,
-                session_id,
-                tool_call,
-                error,
-                format!("Tool '{tool_name}' denied by policy"),
-                "denied",
-                true,
-            ));
-        }
-        ToolDecision::Ask => {
-            if state.pending_approval.is_some() {
-                state.approval_queue.push_back(QueuedApproval { tool_call });
-                return effects;
+    let last_prompt = state
+        .message_graph
+        .get_thread_messages()
+        .into_iter()
+        .rev()
+        .find_map(|message| match &message.data {
+            MessageData::User { content }
+                if content
+                    .iter()
+                    .any(|block| !matches!(block, UserContent::CommandExecution { .. })) =>
+            {
+                Some((message.id().to_string(), content.clone()))
             }
+            _ => None,
+        });
+    let Some((message_id, content)) = last_prompt else {
+        return Err(invalid_action(
+            InvalidActionKind::NothingToRetry,
+            "No previous message to retry.",
+        ));
+    };
 
-            state.pending_approval = Some(PendingApproval {
-                request_id,
-                tool_call: tool_call.clone(),
-            });
-
-            effects.push(Effect::EmitEvent {
-                session_id,
-                event: SessionEvent::ApprovalRequested {
-                    request_id,
-                    tool_call: tool_call.clone(),
-                },
-            });
-
-            effects.push(Effect::RequestUserApproval {
-                session_id,
-                request_id,
-                tool_call,
-            });
-        }
-    }
+    handle_user_edited_message(
+        state,
+        session_id,
+        UserEditedMessageParams {
+            original_message_id: crate::app::domain::types::MessageId::from_string(message_id),
+            new_content: content,
+            op_id,
+            new_message_id,
+            model,
+            timestamp,
+        },
+    )
+}
 
-    effects
+struct StartBestOfParams {
+    content: Vec<UserContent>,
+    op_id: crate::app::domain::types::OpId,
+    message_id: crate::app::domain::types::MessageId,
+    candidate_op_ids: Vec<crate::app::domain::types::OpId>,
+    model: crate::config::model::ModelId,
+    timestamp: u64,
 }
 
-fn validate_tool_call(
-    state: &AppState,
-    tool_call: &steer_tools::ToolCall,
-) -> Result<(), ToolError> {
-    if tool_call.name.trim().is_empty() {
-        return Err(ToolError::invalid_params(
-            "unknown",
-            "Malformed tool call: missing tool name",
-        ));
+/// Add the prompt and draft its candidate answers in parallel. Candidates see
+/// every tool but may only run read-only ones, so a prompt that needs a tool
+/// with side effects stops the run instead of acting on the workspace.
+fn handle_start_best_of(
+    state: &mut AppState,
+    session_id: crate::app::domain::types::SessionId,
+    params: StartBestOfParams,
+) -> Result<Vec<Effect>, ReduceError> {
+    let StartBestOfParams {
+        content,
+        op_id,
+        message_id,
+        candidate_op_ids,
+        model,
+        timestamp,
+    } = params;
+
+    if state.has_active_operation() {
+        return Err(invalid_action(
+            InvalidActionKind::OperationInFlight,
+            "Cannot start best-of while an operation is active.",
+        ));
+    }
+    if !(2..=MAX_BEST_OF_CANDIDATES).contains(&candidate_op_ids.len()) {
+        return Err(invalid_action(
+            InvalidActionKind::InvalidBestOf,
+            format!("Best-of drafts between 2 and {MAX_BEST_OF_CANDIDATES} candidates."),
+        ));
+    }
+
+    let mut effects = discard_best_of(state, session_id);
+
+    let message = Message {
+        data: MessageData::User { content },
+        timestamp,
+        id: message_id.0.clone(),
+        parent_message_id: state.message_graph.active_message_id.clone(),
+    };
+    state.message_graph.add_message(message.clone());
+    state.message_graph.active_message_id = Some(message_id.0.clone());
+
+    state.start_operation(op_id, OperationKind::AgentLoop);
+    state.operation_models.insert(op_id, model.clone());
+    state.best_of = Some(BestOfRun {
+        op_id,
+        user_message_id: message_id.clone(),
+        model: model.clone(),
+        candidate_op_ids: candidate_op_ids.clone(),
+        candidates: Vec::new(),
+    });
+
+    effects.push(Effect::EmitEvent {
+        session_id,
+        event: SessionEvent::UserMessageAdded {
+            message: message.clone(),
+        },
+    });
+    effects.push(Effect::EmitEvent {
+        session_id,
+        event: SessionEvent::OperationStarted {
+            op_id,
+            kind: OperationKind::AgentLoop,
+        },
+    });
+    effects.push(Effect::EmitEvent {
+        session_id,
+        event: SessionEvent::BestOfStarted {
+            op_id,
+            user_message_id: message_id,
+            model: model.clone(),
+            candidate_op_ids: candidate_op_ids.clone(),
+        },
+    });
+    effects.push(Effect::RunBestOf {
+        session_id,
+        op_id,
+        candidate_op_ids,
+        model: model.clone(),
+        messages: state
+            .message_graph
+            .get_thread_messages()
+            .into_iter()
+            .cloned()
+            .collect(),
+        system_context: state.cached_system_context.clone(),
+        tools: state.tools.clone(),
+    });
+
+    if let Some(title_prompt) = first_user_title_input(&message)
+        && session_title_missing(state)
+    {
+        effects.push(Effect::GenerateSessionTitle {
+            session_id,
+            op_id,
+            model,
+            user_prompt: title_prompt,
+        });
+    }
+
+    Ok(effects)
+}
+
+/// Record a finished candidate and its usage under the candidate's own op id.
+/// The operation completes once every candidate is in; the run then waits for
+/// [`Action::PickBestOfCandidate`].
+fn handle_best_of_candidate_finished(
+    state: &mut AppState,
+    session_id: crate::app::domain::types::SessionId,
+    op_id: crate::app::domain::types::OpId,
+    candidate: BestOfCandidate,
+    side_effect_tool: Option<String>,
+) -> Vec<Effect> {
+    let Some(model) = state
+        .best_of
+        .as_ref()
+        .filter(|run| {
+            run.op_id == op_id
+                && run.candidate_op_ids.contains(&candidate.op_id)
+                && !run.candidates.iter().any(|c| c.op_id == candidate.op_id)
+        })
+        .map(|run| run.model.clone())
+    else {
+        return Vec::new();
+    };
+    let mut effects = Vec::new();
+
+    if let Some(usage) = candidate.usage {
+        state.record_llm_usage(candidate.op_id, model.clone(), usage, None);
+        effects.push(Effect::EmitEvent {
+            session_id,
+            event: SessionEvent::LlmUsageUpdated {
+                op_id: candidate.op_id,
+                model,
+                usage,
+                context_window: None,
+            },
+        });
+    }
+
+    if let Some(tool) = side_effect_tool {
+        effects.push(Effect::EmitEvent {
+            session_id,
+            event: SessionEvent::Error {
+                message: format!(
+                    "Best-of stopped: answering needs '{tool}', which has side effects. \
+                     Retry the prompt as a normal turn instead."
+                ),
+            },
+        });
+        effects.extend(discard_best_of(state, session_id));
+        effects.push(Effect::CancelOperation { session_id, op_id });
+        effects.extend(finish_best_of_operation(state, session_id, op_id));
+        return effects;
+    }
+
+    let Some(run) = state.best_of.as_mut() else {
+        return effects;
+    };
+    run.candidates.push(candidate.clone());
+    let complete = run.is_complete();
+    let all_failed = complete && run.candidates.iter().all(|c| c.error.is_some());
+    effects.push(Effect::EmitEvent {
+        session_id,
+        event: SessionEvent::BestOfCandidateReady { op_id, candidate },
+    });
+
+    if complete {
+        if all_failed {
+            effects.push(Effect::EmitEvent {
+                session_id,
+                event: SessionEvent::Error {
+                    message: "Every best-of candidate failed.".to_string(),
+                },
+            });
+            effects.extend(discard_best_of(state, session_id));
+        }
+        effects.extend(finish_best_of_operation(state, session_id, op_id));
+    }
+
+    effects
+}
+
+fn finish_best_of_operation(
+    state: &mut AppState,
+    session_id: crate::app::domain::types::SessionId,
+    op_id: crate::app::domain::types::OpId,
+) -> Vec<Effect> {
+    state.complete_operation(op_id);
+    let mut effects = vec![Effect::EmitEvent {
+        session_id,
+        event: SessionEvent::OperationCompleted {
+            op_id,
+            timing: None,
+        },
+    }];
+    effects.extend(maybe_start_queued_work(state, session_id));
+    effects
+}
+
+/// Drop a best-of run that is still waiting, without picking a candidate.
+fn discard_best_of(
+    state: &mut AppState,
+    session_id: crate::app::domain::types::SessionId,
+) -> Vec<Effect> {
+    let Some(run) = state.best_of.take() else {
+        return Vec::new();
+    };
+    vec![Effect::EmitEvent {
+        session_id,
+        event: SessionEvent::BestOfResolved {
+            op_id: run.op_id,
+            picked: None,
+        },
+    }]
+}
+
+/// Answer the best-of prompt with candidate `index`. The other candidates
+/// that succeeded are added first as sibling branches of the answer, so the
+/// picked one ends up on the active branch.
+fn handle_pick_best_of_candidate(
+    state: &mut AppState,
+    session_id: crate::app::domain::types::SessionId,
+    index: u32,
+    timestamp: u64,
+) -> Result<Vec<Effect>, ReduceError> {
+    let Some(run) = state.best_of.as_ref().filter(|run| run.is_complete()) else {
+        return Err(invalid_action(
+            InvalidActionKind::InvalidBestOf,
+            "No best-of candidates are waiting to be picked.",
+        ));
+    };
+    if !run
+        .candidates
+        .iter()
+        .any(|c| c.index == index && c.error.is_none())
+    {
+        return Err(invalid_action(
+            InvalidActionKind::InvalidBestOf,
+            format!("Best-of candidate {} is not available.", index + 1),
+        ));
+    }
+    let Some(run) = state.best_of.take() else {
+        return Ok(Vec::new());
+    };
+
+    let (mut kept, picked): (Vec<_>, Vec<_>) = run
+        .candidates
+        .into_iter()
+        .filter(|c| c.error.is_none())
+        .partition(|c| c.index != index);
+    kept.sort_by_key(|c| c.index);
+    kept.extend(picked);
+
+    let mut effects = Vec::new();
+    for candidate in kept {
+        let message = Message {
+            data: MessageData::Assistant {
+                content: candidate.content,
+            },
+            timestamp,
+            id: candidate.message_id.0,
+            parent_message_id: Some(run.user_message_id.0.clone()),
+        };
+        state.message_graph.add_message(message.clone());
+        state.message_graph.active_message_id = Some(message.id().to_string());
+        effects.push(Effect::EmitEvent {
+            session_id,
+            event: SessionEvent::AssistantMessageAdded {
+                message,
+                model: run.model.clone(),
+            },
+        });
+    }
+    effects.push(Effect::EmitEvent {
+        session_id,
+        event: SessionEvent::BestOfResolved {
+            op_id: run.op_id,
+            picked: Some(index),
+        },
+    });
+
+    Ok(effects)
+}
+
+fn handle_tool_approval_requested(
+    state: &mut AppState,
+    session_id: crate::app::domain::types::SessionId,
+    request_id: crate::app::domain::types::RequestId,
+    tool_call: steer_tools::ToolCall,
+) -> Vec<Effect> {
+    let mut effects = Vec::new();
+
+    if let Err(error) = validate_tool_call(state, &tool_call) {
+        let error_message = error.to_string();
+        return fail_tool_call_without_execution(
+            state,
+            session_id,
+            tool_call,
+            error,
+            error_message,
+            "invalid",
+            true,
+        );
+    }
+
+    let decision = get_tool_decision(state, &tool_call);
+
+    match decision {
+        ToolDecision::Allow => {
+            let Some(op_id) = state.current_operation.as_ref().map(|o| o.op_id) else {
+                return vec![Effect::EmitEvent {
+                    session_id,
+                    event: SessionEvent::Error {
+                        message: "Tool approval requested without active operation".to_string(),
+                    },
+                }];
+            };
+            state.add_pending_tool_call(crate::app::domain::types::ToolCallId::from_string(
+                &tool_call.id,
+            ));
+
+            effects.push(Effect::ExecuteTool {
+                session_id,
+                op_id,
+                tool_call,
+                approved_by: ToolApprover::Policy,
+            });
+        }
+        ToolDecision::Deny => {
+            let error = ToolError::DeniedByPolicy(tool_call.name.clone());
+            let tool_name = tool_call.name.clone();
+            effects.extend(fail_tool_call_without_execution(
+                state,
+                session_id,
+                tool_call,
+                error,
+                format!("Tool '{tool_name}' denied by policy"),
+                "denied",
+                true,
+            ));
+        }
+        ToolDecision::Ask => {
+            if state.pending_approval.is_some() {
+                state.approval_queue.push_back(QueuedApproval { tool_call });
+                return effects;
+            }
+
+            state.pending_approval = Some(PendingApproval {
+                request_id,
+                tool_call: tool_call.clone(),
+            });
+
+            effects.push(Effect::EmitEvent {
+                session_id,
+                event: SessionEvent::ApprovalRequested {
+                    request_id,
+                    tool_call: tool_call.clone(),
+                },
+            });
+
+            effects.push(Effect::RequestUserApproval {
+                session_id,
+                request_id,
+                tool_call,
+            });
+        }
+    }
+
+    effects
+}
+
+fn validate_tool_call(
+    state: &AppState,
+    tool_call: &steer_tools::ToolCall,
+) -> Result<(), ToolError> {
+    if tool_call.name.trim().is_empty() {
+        return Err(ToolError::invalid_params(
+            "unknown",
+            "Malformed tool call: missing tool name",
+        ));
     }
 
     if tool_call.id.trim().is_empty() {
@@ -963,6 +1518,8 @@ fn fail_tool_call_without_execution(
     let no_pending_approvals = state.pending_approval.is_none() && state.approval_queue.is_empty();
 
     if all_tools_complete && no_pending_approvals {
+        let max_turn_bytes = tool_result_budget(state).max_turn_bytes;
+        effects.extend(enforce_turn_tool_budget(state, session_id, max_turn_bytes));
         effects.push(Effect::CallModel {
             session_id,
             op_id,
@@ -998,11 +1555,36 @@ fn handle_tool_approval_decided(
         }
     };
 
-    let resolved_memory = if decision == ApprovalDecision::Approved {
-        match remember {
-            Some(ApprovalMemory::PendingTool) => {
-                Some(ApprovalMemory::Tool(pending.tool_call.name.clone()))
+    // A partial approval runs a rewritten call that keeps only the accepted
+    // hunks; accepting none of them is a denial.
+    let mut tool_call = pending.tool_call;
+    let mut rejected_hunks = Vec::new();
+    let (decision, remember) = match decision {
+        ApprovalDecision::ApprovedHunks(accepted) => match accept_hunks(&tool_call, &accepted) {
+            Some(filtered) => {
+                rejected_hunks = edit_hunks(&tool_call)
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(index, _)| !accepted.contains(index))
+                    .map(|(_, hunk)| hunk)
+                    .collect();
+                tool_call = filtered;
+                (ApprovalDecision::ApprovedHunks(accepted), None)
             }
+            None => (
+                ApprovalDecision::DeniedWithReason(
+                    "none of the proposed hunks were accepted".to_string(),
+                ),
+                None,
+            ),
+        },
+        other => (other, remember),
+    };
+
+    let approved = decision.is_approved();
+    let resolved_memory = if approved {
+        match remember {
+            Some(ApprovalMemory::PendingTool) => Some(ApprovalMemory::Tool(tool_call.name.clone())),
             Some(ApprovalMemory::Tool(name)) => Some(ApprovalMemory::Tool(name)),
             Some(ApprovalMemory::BashPattern(pattern)) => {
                 Some(ApprovalMemory::BashPattern(pattern))
@@ -1017,12 +1599,12 @@ fn handle_tool_approval_decided(
         session_id,
         event: SessionEvent::ApprovalDecided {
             request_id,
-            decision,
+            decision: decision.clone(),
             remember: resolved_memory.clone(),
         },
     });
 
-    if decision == ApprovalDecision::Approved {
+    if approved {
         if let Some(ref memory) = resolved_memory {
             match memory {
                 ApprovalMemory::Tool(name) => {
@@ -1044,24 +1626,43 @@ fn handle_tool_approval_decided(
             });
             return effects;
         };
-        state.add_pending_tool_call(crate::app::domain::types::ToolCallId::from_string(
-            &pending.tool_call.id,
-        ));
+        let tool_call_id = crate::app::domain::types::ToolCallId::from_string(&tool_call.id);
+        if !rejected_hunks.is_empty() {
+            state
+                .rejected_hunks
+                .insert(tool_call_id.clone(), rejected_hunks);
+        }
+        state.add_pending_tool_call(tool_call_id);
 
         effects.push(Effect::ExecuteTool {
             session_id,
             op_id,
-            tool_call: pending.tool_call,
+            tool_call,
+            approved_by: ToolApprover::User,
         });
     } else {
-        let tool_name = pending.tool_call.name.clone();
-        let error = ToolError::DeniedByUser(tool_name.clone());
+        let tool_name = tool_call.name.clone();
+        let (error, event_error) = match decision {
+            ApprovalDecision::DeniedWithReason(reason) => {
+                let event_error = format!("Tool '{tool_name}' denied by user: {reason}");
+                (
+                    ToolError::approval_denied(tool_name.clone(), Some(reason)),
+                    event_error,
+                )
+            }
+            ApprovalDecision::Approved
+            | ApprovalDecision::ApprovedHunks(_)
+            | ApprovalDecision::Denied => (
+                ToolError::approval_denied(tool_name.clone(), None),
+                format!("Tool '{tool_name}' denied by user"),
+            ),
+        };
         effects.extend(fail_tool_call_without_execution(
             state,
             session_id,
-            pending.tool_call,
+            tool_call,
             error,
-            format!("Tool '{tool_name}' denied by user"),
+            event_error,
             "denied",
             false,
         ));
@@ -1102,6 +1703,7 @@ fn process_next_queued_approval(
                     session_id,
                     op_id,
                     tool_call: queued.tool_call,
+                    approved_by: ToolApprover::Policy,
                 });
             }
             ToolDecision::Deny => {
@@ -1155,6 +1757,8 @@ fn process_next_queued_approval(
     {
         let op_id = op.op_id;
         if let Some(model) = state.operation_models.get(&op_id).cloned() {
+            let max_turn_bytes = tool_result_budget(state).max_turn_bytes;
+            effects.extend(enforce_turn_tool_budget(state, session_id, max_turn_bytes));
             effects.push(Effect::CallModel {
                 session_id,
                 op_id,
@@ -1297,11 +1901,24 @@ fn handle_tool_result(
 
     state.remove_pending_tool_call(&tool_call_id);
 
-    let tool_result = match result {
+    let mut tool_result = match result {
         Ok(r) => r,
         Err(e) => ToolResult::Error(e),
     };
 
+    if let Some(rejected) = state.rejected_hunks.remove(&tool_call_id)
+        && let ToolResult::Edit(edit) = &mut tool_result
+    {
+        edit.skipped_edits
+            .extend(rejected.into_iter().map(|hunk| SkippedEdit {
+                edit_index: hunk.edit_index + 1,
+                reason: format!(
+                    "the user rejected the change at line {} of old_string, so it was not applied",
+                    hunk.old_start
+                ),
+            }));
+    }
+
     let is_direct_bash = matches!(op.kind, OperationKind::DirectBash { .. });
 
     if is_direct_bash {
@@ -1360,7 +1977,10 @@ fn handle_tool_result(
 
         effects.push(Effect::EmitEvent {
             session_id,
-            event: SessionEvent::OperationCompleted { op_id },
+            event: SessionEvent::OperationCompleted {
+                op_id,
+                timing: None,
+            },
         });
 
         effects.extend(maybe_start_queued_work(state, session_id));
@@ -1380,6 +2000,15 @@ fn handle_tool_result(
         }
     };
 
+    let budget = tool_result_budget(state);
+    if tool_budget::truncate_tool_result(&mut tool_result, budget.max_result_bytes) {
+        tracing::debug!(
+            tool_call_id = %tool_call_id,
+            max_result_bytes = budget.max_result_bytes,
+            "Truncated tool result to fit the per-result budget"
+        );
+    }
+
     let event = match &tool_result {
         ToolResult::Error(e) => SessionEvent::ToolCallFailed {
             id: tool_call_id.clone(),
@@ -1423,6 +2052,12 @@ fn handle_tool_result(
     let no_pending_approvals = state.pending_approval.is_none() && state.approval_queue.is_empty();
 
     if all_tools_complete && no_pending_approvals {
+        effects.extend(enforce_turn_tool_budget(
+            state,
+            session_id,
+            budget.max_turn_bytes,
+        ));
+
         if let Some(compact_effects) =
             maybe_prepare_auto_compaction_from_tool_results(state, session_id, op_id, &model)
         {
@@ -1474,6 +2109,83 @@ fn handle_session_title_generated(
     let mut updated_config = config.clone();
     updated_config.title = Some(sanitize_session_title(title));
 
+    emit_session_config_update(state, session_id, updated_config)
+}
+
+/// Summarize the conversation in the background once the stored summary is
+/// `refresh_after_messages` or more messages behind.
+fn maybe_refresh_session_summary(
+    state: &mut AppState,
+    session_id: crate::app::domain::types::SessionId,
+) -> Vec<Effect> {
+    let Some(config) = state.session_config.as_ref() else {
+        return vec![];
+    };
+    if state.summary_in_flight {
+        return vec![];
+    }
+
+    let messages: Vec<Message> = state
+        .message_graph
+        .get_thread_messages()
+        .into_iter()
+        .cloned()
+        .collect();
+    if messages.is_empty() {
+        return vec![];
+    }
+    let refresh_after = config.auto_summary.refresh_after_messages.max(1) as usize;
+    let stale_by = messages
+        .len()
+        .saturating_sub(config.summary_message_count());
+    if !config.auto_summary.enabled || stale_by < refresh_after {
+        return vec![];
+    }
+
+    let model = config.summary_model();
+    state.summary_in_flight = true;
+    vec![Effect::GenerateSessionSummary {
+        session_id,
+        model,
+        messages,
+    }]
+}
+
+fn handle_session_summary_generated(
+    state: &mut AppState,
+    session_id: crate::app::domain::types::SessionId,
+    summary: String,
+    message_count: usize,
+) -> Vec<Effect> {
+    state.summary_in_flight = false;
+
+    let summary = summary.trim();
+    let Some(config) = state.session_config.as_ref() else {
+        return vec![];
+    };
+    if summary.is_empty() {
+        return vec![];
+    }
+
+    let mut updated_config = config.clone();
+    updated_config.metadata.insert(
+        crate::session::state::SUMMARY_METADATA_KEY.to_string(),
+        summary.to_string(),
+    );
+    updated_config.metadata.insert(
+        crate::session::state::SUMMARY_MESSAGE_COUNT_METADATA_KEY.to_string(),
+        message_count.to_string(),
+    );
+
+    emit_session_config_update(state, session_id, updated_config)
+}
+
+/// Apply `updated_config` and persist it, keeping the active primary agent.
+fn emit_session_config_update(
+    state: &mut AppState,
+    session_id: crate::app::domain::types::SessionId,
+    updated_config: crate::session::state::SessionConfig,
+) -> Vec<Effect> {
     let primary_agent_id = state.primary_agent_id.clone().unwrap_or_else(|| {
         updated_config
             .primary_agent_id
@@ -1522,6 +2234,9 @@ fn first_user_title_input(message: &Message) -> Option<String> {
             UserContent::Image { image } => {
                 parts.push(format!("Image ({})", image.mime_type));
             }
+            UserContent::Attachment { attachment } => {
+                parts.push(format!("Attachment: {}", attachment.path));
+            }
             UserContent::CommandExecution { command, .. } => {
                 let trimmed = command.trim();
                 if !trimmed.is_empty() {
@@ -1658,7 +2373,10 @@ fn handle_model_response_complete(
         state.approval_queue.clear();
         effects.push(Effect::EmitEvent {
             session_id,
-            event: SessionEvent::OperationCompleted { op_id },
+            event: SessionEvent::OperationCompleted {
+                op_id,
+                timing: None,
+            },
         });
 
         let auto = maybe_auto_compact(
@@ -1682,8 +2400,12 @@ fn handle_model_response_complete(
         state.complete_operation(op_id);
         effects.push(Effect::EmitEvent {
             session_id,
-            event: SessionEvent::OperationCompleted { op_id },
+            event: SessionEvent::OperationCompleted {
+                op_id,
+                timing: None,
+            },
         });
+        effects.extend(maybe_refresh_session_summary(state, session_id));
         // Try auto-compact first; if it doesn't fire, drain queued work.
         let auto = maybe_auto_compact(
             state,
@@ -1740,7 +2462,10 @@ fn handle_model_response_error(
     {
         let mut compact_effects = vec![Effect::EmitEvent {
             session_id,
-            event: SessionEvent::OperationCompleted { op_id },
+            event: SessionEvent::OperationCompleted {
+                op_id,
+                timing: None,
+            },
         }];
 
         let auto = maybe_force_auto_compact_after_context_overflow(state, session_id, &model);
@@ -1768,7 +2493,10 @@ fn handle_model_response_error(
 
     effects.push(Effect::EmitEvent {
         session_id,
-        event: SessionEvent::OperationCompleted { op_id },
+        event: SessionEvent::OperationCompleted {
+            op_id,
+            timing: None,
+        },
     });
 
     effects.extend(maybe_start_queued_work(state, session_id));
@@ -1776,6 +2504,35 @@ fn handle_model_response_error(
     effects
 }
 
+fn handle_model_response_refused(
+    state: &mut AppState,
+    session_id: crate::app::domain::types::SessionId,
+    op_id: crate::app::domain::types::OpId,
+    reason: String,
+) -> Vec<Effect> {
+    if state.cancelled_ops.contains(&op_id) {
+        return Vec::new();
+    }
+
+    state.complete_operation(op_id);
+
+    let mut effects = vec![
+        Effect::EmitEvent {
+            session_id,
+            event: SessionEvent::Refused { reason },
+        },
+        Effect::EmitEvent {
+            session_id,
+            event: SessionEvent::OperationCompleted {
+                op_id,
+                timing: None,
+            },
+        },
+    ];
+    effects.extend(maybe_start_queued_work(state, session_id));
+    effects
+}
+
 fn handle_direct_bash(
     state: &mut AppState,
     session_id: crate::app::domain::types::SessionId,
@@ -1849,10 +2606,12 @@ fn handle_direct_bash(
         parameters: serde_json::json!({ "command": command }),
     };
 
+    // The user typed the command themselves.
     effects.push(Effect::ExecuteTool {
         session_id,
         op_id,
         tool_call,
+        approved_by: ToolApprover::User,
     });
 
     effects
@@ -2020,7 +2779,12 @@ fn snapshot_queued_work_item(item: &QueuedWorkItem) -> QueuedWorkItemSnapshot {
             attachment_count: message
                 .content
                 .iter()
-                .filter(|item| matches!(item, UserContent::Image { .. }))
+                .filter(|item| {
+                    matches!(
+                        item,
+                        UserContent::Image { .. } | UserContent::Attachment { .. }
+                    )
+                })
                 .count() as u32,
         },
         QueuedWorkItem::DirectBash(command) => QueuedWorkItemSnapshot {
@@ -2083,16 +2847,91 @@ fn handle_cancel(
     state: &mut AppState,
     session_id: crate::app::domain::types::SessionId,
     target_op: Option<crate::app::domain::types::OpId>,
+) -> Vec<Effect> {
+    let op = match &state.current_operation {
+        Some(op) if target_op.is_none_or(|t| t == op.op_id) => op.clone(),
+        _ => return Vec::new(),
+    };
+
+    cancel_operation(state, session_id, op, true)
+}
+
+/// Cancel an operation restored by [`restore_interrupted_operation`]. Queued
+/// work stays queued and starts once the operation is cleared.
+fn handle_abandon_interrupted_operation(
+    state: &mut AppState,
+    session_id: crate::app::domain::types::SessionId,
+    op_id: crate::app::domain::types::OpId,
+) -> Vec<Effect> {
+    let op = match &state.current_operation {
+        Some(op) if op.op_id == op_id => op.clone(),
+        _ => return Vec::new(),
+    };
+
+    let mut effects = cancel_operation(state, session_id, op, false);
+    effects.extend(maybe_start_queued_work(state, session_id));
+    effects
+}
+
+/// Replace the approval restored by [`restore_interrupted_operation`] with a
+/// fresh request. The prompt clients saw before the session stopped is
+/// effectively cancelled: a decision on its request id no longer matches.
+fn handle_reissue_pending_approval(
+    state: &mut AppState,
+    session_id: crate::app::domain::types::SessionId,
+    op_id: crate::app::domain::types::OpId,
+) -> Vec<Effect> {
+    if state
+        .current_operation
+        .as_ref()
+        .is_none_or(|op| op.op_id != op_id)
+    {
+        return Vec::new();
+    }
+    let Some(pending) = state.pending_approval.as_mut() else {
+        return Vec::new();
+    };
+
+    let request_id = crate::app::domain::types::RequestId::new();
+    pending.request_id = request_id;
+    let tool_call = pending.tool_call.clone();
+
+    vec![
+        Effect::EmitEvent {
+            session_id,
+            event: SessionEvent::ApprovalRequested {
+                request_id,
+                tool_call: tool_call.clone(),
+            },
+        },
+        Effect::RequestUserApproval {
+            session_id,
+            request_id,
+            tool_call,
+        },
+    ]
+}
+
+/// Cancel `op`, failing its outstanding tool calls. When `release_queued_item`
+/// is set, the next queued item is handed back to the client rather than started.
+fn cancel_operation(
+    state: &mut AppState,
+    session_id: crate::app::domain::types::SessionId,
+    op: OperationState,
+    release_queued_item: bool,
 ) -> Vec<Effect> {
     let mut effects = Vec::new();
 
-    let op = match &state.current_operation {
-        Some(op) if target_op.is_none_or(|t| t == op.op_id) => op.clone(),
-        _ => return effects,
-    };
-
     state.record_cancelled_op(op.op_id);
 
+    if state
+        .best_of
+        .as_ref()
+        .is_some_and(|run| run.op_id == op.op_id)
+    {
+        effects.extend(discard_best_of(state, session_id));
+    }
+
     if let OperationKind::Compact { trigger } = op.kind {
         effects.push(Effect::EmitEvent {
             session_id,
@@ -2156,7 +2995,11 @@ fn handle_cancel(
     }
     state.active_streams.remove(&op.op_id);
 
-    let dequeued_item = state.pop_next_queued_work();
+    let dequeued_item = if release_queued_item {
+        state.pop_next_queued_work()
+    } else {
+        None
+    };
     let popped_queued_item = dequeued_item.as_ref().map(snapshot_queued_work_item);
 
     effects.push(Effect::EmitEvent {
@@ -2189,6 +3032,61 @@ fn handle_cancel(
     effects
 }
 
+/// Switch the model of the active agent loop.
+///
+/// Without an agent loop in flight there is nothing to redirect: the caller's
+/// next input carries the model it wants.
+fn handle_switch_model(
+    state: &mut AppState,
+    session_id: crate::app::domain::types::SessionId,
+    model: crate::config::model::ModelId,
+    mode: ModelSwitchMode,
+    restart_op_id: crate::app::domain::types::OpId,
+) -> Vec<Effect> {
+    let Some(op) = state
+        .current_operation
+        .clone()
+        .filter(|op| matches!(op.kind, OperationKind::AgentLoop))
+    else {
+        return Vec::new();
+    };
+
+    match mode {
+        ModelSwitchMode::AfterCurrentTurn => {
+            state.operation_models.insert(op.op_id, model);
+            Vec::new()
+        }
+        ModelSwitchMode::RestartOperation => {
+            let mut effects = cancel_operation(state, session_id, op, false);
+
+            state.start_operation(restart_op_id, OperationKind::AgentLoop);
+            state.operation_models.insert(restart_op_id, model.clone());
+
+            effects.push(Effect::EmitEvent {
+                session_id,
+                event: SessionEvent::OperationStarted {
+                    op_id: restart_op_id,
+                    kind: OperationKind::AgentLoop,
+                },
+            });
+            effects.push(Effect::CallModel {
+                session_id,
+                op_id: restart_op_id,
+                model,
+                messages: state
+                    .message_graph
+                    .get_thread_messages()
+                    .into_iter()
+                    .cloned()
+                    .collect(),
+                system_context: state.cached_system_context.clone(),
+                tools: state.tools.clone(),
+            });
+            effects
+        }
+    }
+}
+
 fn handle_hydrate(
     state: &mut AppState,
     session_id: crate::app::domain::types::SessionId,
@@ -2366,6 +3264,7 @@ fn re_evaluate_pending_approvals_after_agent_switch(
                     session_id,
                     op_id,
                     tool_call,
+                    approved_by: ToolApprover::Policy,
                 });
             }
             ToolDecision::Deny => {
@@ -2571,7 +3470,7 @@ pub fn apply_event_to_state(state: &mut AppState, event: &SessionEvent) {
         SessionEvent::ApprovalDecided {
             decision, remember, ..
         } => {
-            if *decision == ApprovalDecision::Approved
+            if decision.is_approved()
                 && let Some(memory) = remember
             {
                 match memory {
@@ -2586,9 +3485,31 @@ pub fn apply_event_to_state(state: &mut AppState, event: &SessionEvent) {
             }
             state.pending_approval = None;
         }
-        SessionEvent::OperationCompleted { op_id } => {
+        SessionEvent::OperationCompleted { op_id, .. } => {
             state.complete_operation(*op_id);
         }
+        SessionEvent::BestOfStarted {
+            op_id,
+            user_message_id,
+            model,
+            candidate_op_ids,
+        } => {
+            state.best_of = Some(BestOfRun {
+                op_id: *op_id,
+                user_message_id: user_message_id.clone(),
+                model: model.clone(),
+                candidate_op_ids: candidate_op_ids.clone(),
+                candidates: Vec::new(),
+            });
+        }
+        SessionEvent::BestOfCandidateReady { op_id, candidate } => {
+            if let Some(run) = state.best_of.as_mut().filter(|run| run.op_id == *op_id) {
+                run.candidates.push(candidate.clone());
+            }
+        }
+        SessionEvent::BestOfResolved { .. } => {
+            state.best_of = None;
+        }
         SessionEvent::OperationCancelled { op_id, .. } => {
             state.record_cancelled_op(*op_id);
             state.complete_operation(*op_id);
@@ -2676,6 +3597,129 @@ pub fn apply_event_to_state(state: &mut AppState, event: &SessionEvent) {
     state.event_sequence += 1;
 }
 
+/// An operation that was still running when its session's actor stopped,
+/// restored into [`AppState`] by [`restore_interrupted_operation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterruptedOperation {
+    /// The operation was waiting on a user approval and is waiting again.
+    AwaitingApproval {
+        op_id: crate::app::domain::types::OpId,
+    },
+    /// The operation was mid model call or tool execution. It can't be
+    /// resumed and should be abandoned with
+    /// [`Action::AbandonInterruptedOperation`].
+    Abandoned {
+        op_id: crate::app::domain::types::OpId,
+    },
+}
+
+/// Restore the operation left open at the end of `events`, if any.
+///
+/// Replay alone drops in-flight state, which leaves a half-finished turn.
+/// An agent loop that stopped on an approval prompt gets its pending approval
+/// and remaining tool calls back; anything else is restored with its
+/// unfinished tool calls so that cancelling it records a result for each.
+pub fn restore_interrupted_operation(
+    state: &mut AppState,
+    events: &[(u64, SessionEvent)],
+) -> Option<InterruptedOperation> {
+    let mut open: Option<(crate::app::domain::types::OpId, OperationKind)> = None;
+    let mut model = None;
+    let mut tool_calls: Vec<steer_tools::ToolCall> = Vec::new();
+    let mut resolved = std::collections::HashSet::new();
+    let mut started = std::collections::HashSet::new();
+    let mut approval: Option<PendingApproval> = None;
+
+    for (_, event) in events {
+        match event {
+            SessionEvent::OperationStarted { op_id, kind } => {
+                open = Some((*op_id, kind.clone()));
+                model = None;
+                tool_calls.clear();
+                resolved.clear();
+                started.clear();
+                approval = None;
+            }
+            SessionEvent::OperationCompleted { op_id, .. }
+            | SessionEvent::OperationCancelled { op_id, .. } => {
+                if open.as_ref().is_some_and(|(open_id, _)| open_id == op_id) {
+                    open = None;
+                }
+            }
+            SessionEvent::AssistantMessageAdded {
+                message,
+                model: message_model,
+            } if open.is_some() => {
+                model = Some(message_model.clone());
+                if let MessageData::Assistant { content } = &message.data {
+                    tool_calls.extend(content.iter().filter_map(|block| match block {
+                        AssistantContent::ToolCall { tool_call, .. } => Some(tool_call.clone()),
+                        _ => None,
+                    }));
+                }
+            }
+            SessionEvent::ToolMessageAdded { message } => {
+                if let MessageData::Tool { tool_use_id, .. } = &message.data {
+                    resolved.insert(tool_use_id.clone());
+                }
+            }
+            SessionEvent::ToolCallStarted { id, .. } => {
+                started.insert(id.as_str().to_string());
+            }
+            SessionEvent::ApprovalRequested {
+                request_id,
+                tool_call,
+            } => {
+                approval = Some(PendingApproval {
+                    request_id: *request_id,
+                    tool_call: tool_call.clone(),
+                });
+            }
+            SessionEvent::ApprovalDecided { request_id, .. } => {
+                if approval
+                    .as_ref()
+                    .is_some_and(|pending| pending.request_id == *request_id)
+                {
+                    approval = None;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let (op_id, kind) = open?;
+    let mut seen = std::collections::HashSet::new();
+    let unresolved: Vec<_> = tool_calls
+        .into_iter()
+        .filter(|call| !resolved.contains(&call.id) && seen.insert(call.id.clone()))
+        .collect();
+    let approval =
+        approval.filter(|pending| unresolved.iter().any(|c| c.id == pending.tool_call.id));
+
+    state.start_operation(op_id, kind.clone());
+    if let Some(model) = model.clone() {
+        state.operation_models.insert(op_id, model);
+    }
+
+    let resumable = matches!(kind, OperationKind::AgentLoop)
+        && model.is_some()
+        && !unresolved.iter().any(|call| started.contains(&call.id));
+    if let Some(pending) = approval.filter(|_| resumable) {
+        state.approval_queue = unresolved
+            .into_iter()
+            .filter(|call| call.id != pending.tool_call.id)
+            .map(|tool_call| QueuedApproval { tool_call })
+            .collect();
+        state.pending_approval = Some(pending);
+        return Some(InterruptedOperation::AwaitingApproval { op_id });
+    }
+
+    for call in &unresolved {
+        state.add_pending_tool_call(crate::app::domain::types::ToolCallId::from_string(&call.id));
+    }
+    Some(InterruptedOperation::Abandoned { op_id })
+}
+
 fn maybe_continue_after_compaction(
     state: &mut AppState,
     session_id: crate::app::domain::types::SessionId,
@@ -2830,7 +3874,10 @@ fn handle_compaction_complete(
         },
         Effect::EmitEvent {
             session_id,
-            event: SessionEvent::OperationCompleted { op_id },
+            event: SessionEvent::OperationCompleted {
+                op_id,
+                timing: None,
+            },
         },
     ];
 
@@ -2869,7 +3916,10 @@ fn handle_compaction_failed(
         },
         Effect::EmitEvent {
             session_id,
-            event: SessionEvent::OperationCompleted { op_id },
+            event: SessionEvent::OperationCompleted {
+                op_id,
+                timing: None,
+            },
         },
     ];
 
@@ -2914,86 +3964,457 @@ fn emit_mcp_connect_effects(
         }
     }
 
-    effects
-}
+    effects
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::provider::TokenUsage;
+    use crate::app::domain::event::{BestOfCandidate, ContextWindowUsage};
+    use crate::app::domain::state::{OperationState, PendingApproval};
+    use crate::app::domain::types::{MessageId, OpId, RequestId, SessionId, ToolCallId};
+    use crate::config::model::builtin;
+    use crate::primary_agents::resolve_effective_config;
+    use crate::session::state::{
+        ApprovalRules, AutoSummaryConfig, SessionConfig, SessionPolicyOverrides,
+        ToolApprovalPolicy, ToolResultBudget, ToolVisibility, UnapprovedBehavior,
+    };
+    use crate::tools::DISPATCH_AGENT_TOOL_NAME;
+    use crate::tools::builtin_tools::READ_ONLY_TOOL_NAMES;
+    use schemars::schema_for;
+    use serde_json::json;
+    use std::collections::HashSet;
+    use steer_tools::{InputSchema, ToolCall, ToolError, ToolSchema};
+
+    fn test_state() -> AppState {
+        AppState::new(SessionId::new())
+    }
+
+    fn test_schema(name: &str) -> ToolSchema {
+        ToolSchema {
+            name: name.to_string(),
+            display_name: name.to_string(),
+            description: String::new(),
+            input_schema: InputSchema::empty_object(),
+        }
+    }
+
+    fn base_session_config() -> SessionConfig {
+        let mut config = SessionConfig::read_only(builtin::claude_sonnet_4_5());
+        config.primary_agent_id = Some("normal".to_string());
+        config.policy_overrides = SessionPolicyOverrides::empty();
+        resolve_effective_config(&config)
+    }
+
+    fn reduce(state: &mut AppState, action: Action) -> Vec<Effect> {
+        super::reduce(state, action).expect("reduce failed")
+    }
+
+    #[test]
+    fn test_user_input_starts_operation_and_requests_session_title() {
+        let mut state = test_state();
+        let session_id = state.session_id;
+        let op_id = OpId::new();
+        let message_id = MessageId::new();
+        let model = builtin::claude_sonnet_4_5();
+
+        let effects = reduce(
+            &mut state,
+            Action::UserInput {
+                session_id,
+                content: vec![UserContent::Text {
+                    text: "Hello".to_string(),
+                }],
+                op_id,
+                message_id,
+                model,
+                timestamp: 1_234_567_890,
+            },
+        );
+
+        assert_eq!(state.message_graph.messages.len(), 1);
+        assert!(state.current_operation.is_some());
+        assert!(
+            effects
+                .iter()
+                .any(|e| matches!(e, Effect::CallModel { .. }))
+        );
+        assert!(effects.iter().any(|e| matches!(
+            e,
+            Effect::GenerateSessionTitle { op_id: title_op_id, .. } if *title_op_id == op_id
+        )));
+    }
+
+    #[test]
+    fn test_context_usage_applies_token_count_calibration() {
+        let mut state = test_state();
+        let session_id = state.session_id;
+        let model = builtin::claude_sonnet_4_5();
+
+        let effects = reduce(
+            &mut state,
+            Action::TokenCountCalibrated {
+                session_id,
+                model: model.clone(),
+                counted: 300,
+                estimated: 100,
+            },
+        );
+        assert!(effects.is_empty());
+
+        let effects = reduce(
+            &mut state,
+            Action::UserInput {
+                session_id,
+                content: vec![UserContent::Text {
+                    text: "x".repeat(40),
+                }],
+                op_id: OpId::new(),
+                message_id: MessageId::new(),
+                model: model.clone(),
+                timestamp: 1,
+            },
+        );
+
+        let estimate = state.message_graph.token_estimate(&model);
+        assert_eq!(estimate, 10);
+        let used = effects.iter().find_map(|effect| match effect {
+            Effect::EmitEvent {
+                event: SessionEvent::ContextUsage { used, .. },
+                ..
+            } => Some(*used),
+            _ => None,
+        });
+        assert_eq!(used, Some(30));
+    }
+
+    #[test]
+    fn test_retry_last_turn_branches_with_override_model() {
+        let mut state = test_state();
+        let session_id = state.session_id;
+        let mut events = Vec::new();
+        let mut record = |effects: &[Effect]| {
+            for effect in effects {
+                if let Effect::EmitEvent { event, .. } = effect {
+                    events.push((events.len() as u64, event.clone()));
+                }
+            }
+        };
+
+        let user_id = MessageId::new();
+        let first_op = OpId::new();
+        record(&reduce(
+            &mut state,
+            Action::UserInput {
+                session_id,
+                content: vec![UserContent::Text {
+                    text: "List the files".to_string(),
+                }],
+                op_id: first_op,
+                message_id: user_id.clone(),
+                model: builtin::claude_sonnet_4_5(),
+                timestamp: 1,
+            },
+        ));
+        let old_answer = MessageId::new();
+        record(&reduce(
+            &mut state,
+            Action::ModelResponseComplete {
+                session_id,
+                op_id: first_op,
+                message_id: old_answer.clone(),
+                content: vec![AssistantContent::ToolCall {
+                    tool_call: ToolCall {
+                        id: "tc_old".to_string(),
+                        name: "test_tool".to_string(),
+                        parameters: json!({}),
+                    },
+                    thought_signature: None,
+                }],
+                usage: None,
+                context_window_tokens: None,
+                configured_max_output_tokens: None,
+                timestamp: 2,
+            },
+        ));
+        record(&reduce(
+            &mut state,
+            Action::Cancel {
+                session_id,
+                op_id: None,
+            },
+        ));
+        assert!(state.current_operation.is_none());
+
+        let retry_op = OpId::new();
+        let retried_user_id = MessageId::new();
+        let effects = reduce(
+            &mut state,
+            Action::RetryLastTurn {
+                session_id,
+                op_id: retry_op,
+                new_message_id: retried_user_id.clone(),
+                model: builtin::claude_haiku_4_5(),
+                timestamp: 3,
+            },
+        );
+        record(&effects);
+
+        let Some(Effect::CallModel {
+            model, messages, ..
+        }) = effects
+            .iter()
+            .find(|e| matches!(e, Effect::CallModel { .. }))
+        else {
+            panic!("retry should call the model");
+        };
+        assert_eq!(*model, builtin::claude_haiku_4_5());
+        let sent: Vec<&str> = messages.iter().map(Message::id).collect();
+        assert_eq!(sent, vec![retried_user_id.0.as_str()]);
+        assert_eq!(
+            state.operation_models.get(&retry_op),
+            Some(&builtin::claude_haiku_4_5())
+        );
+        assert!(
+            state
+                .message_graph
+                .messages
+                .iter()
+                .any(|m| m.id() == old_answer.0),
+            "the replaced attempt stays on a side branch"
+        );
+
+        // Resuming mid-retry must not pick up the discarded attempt's tool call.
+        let mut resumed = test_state();
+        for (_, event) in &events {
+            apply_event_to_state(&mut resumed, event);
+        }
+        assert_eq!(
+            restore_interrupted_operation(&mut resumed, &events),
+            Some(InterruptedOperation::Abandoned { op_id: retry_op })
+        );
+        let effects = reduce(
+            &mut resumed,
+            Action::AbandonInterruptedOperation {
+                session_id,
+                op_id: retry_op,
+            },
+        );
+        assert!(!effects.iter().any(|e| matches!(
+            e,
+            Effect::ExecuteTool { .. }
+                | Effect::EmitEvent {
+                    event: SessionEvent::ToolCallFailed { .. },
+                    ..
+                }
+        )));
+    }
+
+    #[test]
+    fn test_retry_last_turn_without_prompt_is_rejected() {
+        let mut state = test_state();
+        let session_id = state.session_id;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::api::provider::TokenUsage;
-    use crate::app::domain::event::ContextWindowUsage;
-    use crate::app::domain::state::{OperationState, PendingApproval};
-    use crate::app::domain::types::{MessageId, OpId, RequestId, SessionId, ToolCallId};
-    use crate::config::model::builtin;
-    use crate::primary_agents::resolve_effective_config;
-    use crate::session::state::{
-        ApprovalRules, SessionConfig, SessionPolicyOverrides, ToolApprovalPolicy, ToolVisibility,
-        UnapprovedBehavior,
-    };
-    use crate::tools::DISPATCH_AGENT_TOOL_NAME;
-    use crate::tools::builtin_tools::READ_ONLY_TOOL_NAMES;
-    use schemars::schema_for;
-    use serde_json::json;
-    use std::collections::HashSet;
-    use steer_tools::{InputSchema, ToolCall, ToolError, ToolSchema};
+        let result = super::reduce(
+            &mut state,
+            Action::RetryLastTurn {
+                session_id,
+                op_id: OpId::new(),
+                new_message_id: MessageId::new(),
+                model: builtin::claude_sonnet_4_5(),
+                timestamp: 1,
+            },
+        );
 
-    fn test_state() -> AppState {
-        AppState::new(SessionId::new())
+        assert!(matches!(
+            result,
+            Err(ReduceError::InvalidAction {
+                kind: InvalidActionKind::NothingToRetry,
+                ..
+            })
+        ));
+        assert!(state.current_operation.is_none());
     }
 
-    fn test_schema(name: &str) -> ToolSchema {
-        ToolSchema {
-            name: name.to_string(),
-            display_name: name.to_string(),
-            description: String::new(),
-            input_schema: InputSchema::empty_object(),
-        }
+    fn start_best_of(state: &mut AppState, candidates: usize) -> (OpId, Vec<OpId>, MessageId) {
+        let session_id = state.session_id;
+        let op_id = OpId::new();
+        let candidate_op_ids: Vec<OpId> = (0..candidates).map(|_| OpId::new()).collect();
+        let user_id = MessageId::new();
+        let effects = reduce(
+            state,
+            Action::StartBestOf {
+                session_id,
+                content: vec![UserContent::Text {
+                    text: "Name this function".to_string(),
+                }],
+                op_id,
+                message_id: user_id.clone(),
+                candidate_op_ids: candidate_op_ids.clone(),
+                model: builtin::claude_sonnet_4_5(),
+                timestamp: 1,
+            },
+        );
+        assert!(effects.iter().any(|e| matches!(
+            e,
+            Effect::RunBestOf { candidate_op_ids: ids, .. } if *ids == candidate_op_ids
+        )));
+        (op_id, candidate_op_ids, user_id)
     }
 
-    fn base_session_config() -> SessionConfig {
-        let mut config = SessionConfig::read_only(builtin::claude_sonnet_4_5());
-        config.primary_agent_id = Some("normal".to_string());
-        config.policy_overrides = SessionPolicyOverrides::empty();
-        resolve_effective_config(&config)
+    fn best_of_candidate(index: u32, op_id: OpId, text: &str) -> BestOfCandidate {
+        BestOfCandidate {
+            index,
+            op_id,
+            message_id: MessageId::new(),
+            content: vec![AssistantContent::Text {
+                text: text.to_string(),
+            }],
+            usage: Some(TokenUsage::new(10, 5 + index, 15 + index)),
+            error: None,
+        }
     }
 
-    fn reduce(state: &mut AppState, action: Action) -> Vec<Effect> {
-        super::reduce(state, action).expect("reduce failed")
+    #[test]
+    fn test_best_of_attributes_usage_per_candidate_and_branches_on_pick() {
+        let mut state = test_state();
+        let session_id = state.session_id;
+        let (op_id, candidate_ops, user_id) = start_best_of(&mut state, 2);
+
+        let first = best_of_candidate(0, candidate_ops[0], "parse_header");
+        let second = best_of_candidate(1, candidate_ops[1], "read_header");
+        let effects = reduce(
+            &mut state,
+            Action::BestOfCandidateFinished {
+                session_id,
+                op_id,
+                candidate: first.clone(),
+                side_effect_tool: None,
+            },
+        );
+        assert!(!effects.iter().any(|e| matches!(
+            e,
+            Effect::EmitEvent {
+                event: SessionEvent::OperationCompleted { .. },
+                ..
+            }
+        )));
+        let effects = reduce(
+            &mut state,
+            Action::BestOfCandidateFinished {
+                session_id,
+                op_id,
+                candidate: second.clone(),
+                side_effect_tool: None,
+            },
+        );
+        assert!(effects.iter().any(|e| matches!(
+            e,
+            Effect::EmitEvent {
+                event: SessionEvent::OperationCompleted { op_id: done, .. },
+                ..
+            } if *done == op_id
+        )));
+        assert!(state.current_operation.is_none());
+        assert_eq!(
+            state.llm_usage_by_op[&candidate_ops[0]].usage,
+            TokenUsage::new(10, 5, 15)
+        );
+        assert_eq!(
+            state.llm_usage_by_op[&candidate_ops[1]].usage,
+            TokenUsage::new(10, 6, 16)
+        );
+        assert_eq!(state.llm_usage_totals.total_tokens, 31);
+
+        reduce(
+            &mut state,
+            Action::PickBestOfCandidate {
+                session_id,
+                index: 0,
+                timestamp: 2,
+            },
+        );
+        assert!(state.best_of.is_none());
+        assert_eq!(
+            state.message_graph.active_message_id.as_deref(),
+            Some(first.message_id.0.as_str())
+        );
+        let archived = state
+            .message_graph
+            .messages
+            .iter()
+            .find(|m| m.id() == second.message_id.0)
+            .expect("unpicked candidate should be kept on a branch");
+        assert_eq!(archived.parent_message_id(), Some(user_id.0.as_str()));
+        let thread: Vec<&str> = state
+            .message_graph
+            .get_thread_messages()
+            .into_iter()
+            .map(Message::id)
+            .collect();
+        assert_eq!(
+            thread,
+            vec![user_id.0.as_str(), first.message_id.0.as_str()]
+        );
     }
 
     #[test]
-    fn test_user_input_starts_operation_and_requests_session_title() {
+    fn test_best_of_refuses_side_effecting_tools() {
         let mut state = test_state();
         let session_id = state.session_id;
-        let op_id = OpId::new();
-        let message_id = MessageId::new();
-        let model = builtin::claude_sonnet_4_5();
+        let (op_id, candidate_ops, _) = start_best_of(&mut state, 2);
 
+        let mut candidate = best_of_candidate(1, candidate_ops[1], "");
+        candidate.content.clear();
         let effects = reduce(
             &mut state,
-            Action::UserInput {
+            Action::BestOfCandidateFinished {
                 session_id,
-                content: vec![UserContent::Text {
-                    text: "Hello".to_string(),
-                }],
                 op_id,
-                message_id,
-                model,
-                timestamp: 1_234_567_890,
+                candidate,
+                side_effect_tool: Some("edit_file".to_string()),
             },
         );
 
-        assert_eq!(state.message_graph.messages.len(), 1);
-        assert!(state.current_operation.is_some());
-        assert!(
-            effects
-                .iter()
-                .any(|e| matches!(e, Effect::CallModel { .. }))
-        );
         assert!(effects.iter().any(|e| matches!(
             e,
-            Effect::GenerateSessionTitle { op_id: title_op_id, .. } if *title_op_id == op_id
+            Effect::EmitEvent {
+                event: SessionEvent::Error { message },
+                ..
+            } if message.contains("edit_file")
         )));
+        assert!(effects.iter().any(
+            |e| matches!(e, Effect::CancelOperation { op_id: cancelled, .. } if *cancelled == op_id)
+        ));
+        assert!(state.best_of.is_none());
+        assert!(state.current_operation.is_none());
+        assert!(state.llm_usage_by_op.contains_key(&candidate_ops[1]));
+
+        let late = reduce(
+            &mut state,
+            Action::BestOfCandidateFinished {
+                session_id,
+                op_id,
+                candidate: best_of_candidate(0, candidate_ops[0], "late"),
+                side_effect_tool: None,
+            },
+        );
+        assert!(late.is_empty());
+        assert!(matches!(
+            super::reduce(
+                &mut state,
+                Action::PickBestOfCandidate {
+                    session_id,
+                    index: 0,
+                    timestamp: 3,
+                },
+            ),
+            Err(ReduceError::InvalidAction {
+                kind: InvalidActionKind::InvalidBestOf,
+                ..
+            })
+        ));
     }
 
     #[test]
@@ -3098,24 +4519,132 @@ mod tests {
     }
 
     #[test]
-    fn test_session_title_generation_failure_logs_without_effects() {
+    fn test_session_title_generation_failure_logs_without_effects() {
+        let mut state = test_state();
+        let session_id = state.session_id;
+
+        let effects = reduce(
+            &mut state,
+            Action::SessionTitleGenerationFailed {
+                session_id,
+                error: crate::app::domain::action::SessionTitleGenerationError::from(
+                    crate::app::domain::action::ModelCallError::RequestStartFailed {
+                        kind: crate::app::domain::action::ModelCallRequestErrorKind::Cancelled,
+                        message: "cancelled".to_string(),
+                    },
+                ),
+            },
+        );
+
+        assert!(effects.is_empty());
+    }
+
+    fn complete_text_turn(state: &mut AppState, text: &str) -> Vec<Effect> {
+        let session_id = state.session_id;
+        let op_id = OpId::new();
+        let _ = reduce(
+            state,
+            Action::UserInput {
+                session_id,
+                content: vec![UserContent::Text {
+                    text: text.to_string(),
+                }],
+                op_id,
+                message_id: MessageId::new(),
+                model: builtin::claude_sonnet_4_5(),
+                timestamp: 1,
+            },
+        );
+        reduce(
+            state,
+            Action::ModelResponseComplete {
+                session_id,
+                op_id,
+                message_id: MessageId::new(),
+                content: vec![AssistantContent::Text {
+                    text: format!("Done: {text}"),
+                }],
+                usage: None,
+                context_window_tokens: None,
+                configured_max_output_tokens: None,
+                timestamp: 2,
+            },
+        )
+    }
+
+    #[test]
+    fn test_completed_operation_summarizes_once_summary_is_stale() {
+        let mut state = test_state();
+        let session_id = state.session_id;
+        let mut config = base_session_config();
+        config.auto_summary.refresh_after_messages = 4;
+        apply_session_config_state(&mut state, &config, Some("normal".to_string()), true);
+
+        let effects = complete_text_turn(&mut state, "first");
+        assert!(
+            !effects
+                .iter()
+                .any(|e| matches!(e, Effect::GenerateSessionSummary { .. })),
+            "two messages are not enough to refresh"
+        );
+
+        let effects = complete_text_turn(&mut state, "second");
+        let Some(Effect::GenerateSessionSummary { messages, .. }) = effects
+            .iter()
+            .find(|e| matches!(e, Effect::GenerateSessionSummary { .. }))
+        else {
+            panic!("four new messages should trigger a summary");
+        };
+        assert_eq!(messages.len(), 4);
+        assert!(state.summary_in_flight);
+
+        let effects = reduce(
+            &mut state,
+            Action::SessionSummaryGenerated {
+                session_id,
+                summary: "Two small requests, both done.".to_string(),
+                message_count: 4,
+            },
+        );
+        assert!(!state.summary_in_flight);
+        let config = state.session_config.as_ref().expect("config");
+        assert_eq!(config.summary(), Some("Two small requests, both done."));
+        assert_eq!(config.summary_message_count(), 4);
+        assert!(effects.iter().any(|e| matches!(
+            e,
+            Effect::EmitEvent {
+                event: SessionEvent::SessionConfigUpdated { .. },
+                ..
+            }
+        )));
+
+        let effects = complete_text_turn(&mut state, "third");
+        assert!(
+            !effects
+                .iter()
+                .any(|e| matches!(e, Effect::GenerateSessionSummary { .. })),
+            "summary covers all but two messages"
+        );
+    }
+
+    #[test]
+    fn test_disabled_auto_summary_never_summarizes() {
         let mut state = test_state();
-        let session_id = state.session_id;
+        let mut config = base_session_config();
+        config.auto_summary = AutoSummaryConfig {
+            refresh_after_messages: 1,
+            ..AutoSummaryConfig::disabled()
+        };
+        apply_session_config_state(&mut state, &config, Some("normal".to_string()), true);
 
-        let effects = reduce(
-            &mut state,
-            Action::SessionTitleGenerationFailed {
-                session_id,
-                error: crate::app::domain::action::SessionTitleGenerationError::from(
-                    crate::app::domain::action::ModelCallError::RequestStartFailed {
-                        kind: crate::app::domain::action::ModelCallRequestErrorKind::Cancelled,
-                        message: "cancelled".to_string(),
-                    },
-                ),
-            },
-        );
+        let effects = complete_text_turn(&mut state, "hello");
 
-        assert!(effects.is_empty());
+        assert!(
+            !effects
+                .iter()
+                .any(|e| matches!(e, Effect::GenerateSessionSummary { .. }))
+        );
+        assert!(!state.summary_in_flight);
     }
 
     #[test]
@@ -3347,7 +4876,7 @@ mod tests {
                             remember,
                         },
                     ..
-                } => Some((*request_id, *decision, remember.clone())),
+                } => Some((*request_id, decision.clone(), remember.clone())),
                 _ => None,
             })
             .collect();
@@ -3435,7 +4964,7 @@ mod tests {
                             remember,
                         },
                     ..
-                } => Some((*request_id, *decision, remember.clone())),
+                } => Some((*request_id, decision.clone(), remember.clone())),
                 _ => None,
             })
             .collect();
@@ -3520,7 +5049,7 @@ mod tests {
                             remember,
                         },
                     ..
-                } => Some((*request_id, *decision, remember.clone())),
+                } => Some((*request_id, decision.clone(), remember.clone())),
                 _ => None,
             })
             .collect();
@@ -3537,44 +5066,350 @@ mod tests {
                     tool_call,
                 },
                 ..
-            } if *request_id == new_pending.request_id && tool_call.id == pending_tool_call.id
+            } if *request_id == new_pending.request_id && tool_call.id == pending_tool_call.id
+        )));
+        assert!(effects.iter().any(|effect| matches!(
+            effect,
+            Effect::RequestUserApproval {
+                request_id,
+                tool_call,
+                ..
+            } if *request_id == new_pending.request_id && tool_call.id == pending_tool_call.id
+        )));
+        assert!(
+            !effects
+                .iter()
+                .any(|effect| matches!(effect, Effect::ExecuteTool { .. }))
+        );
+    }
+
+    #[test]
+    fn test_late_result_ignored_after_cancel() {
+        let mut state = test_state();
+        let session_id = state.session_id;
+        let op_id = OpId::new();
+        let tool_call_id = ToolCallId::from_string("tc_1");
+
+        state.current_operation = Some(OperationState {
+            op_id,
+            kind: OperationKind::AgentLoop,
+            pending_tool_calls: [tool_call_id.clone()].into_iter().collect(),
+        });
+
+        let _ = reduce(
+            &mut state,
+            Action::Cancel {
+                session_id,
+                op_id: None,
+            },
+        );
+
+        state.current_operation = Some(OperationState {
+            op_id,
+            kind: OperationKind::AgentLoop,
+            pending_tool_calls: HashSet::new(),
+        });
+        state
+            .operation_models
+            .insert(op_id, builtin::claude_sonnet_4_5());
+        state
+            .operation_models
+            .insert(op_id, builtin::claude_sonnet_4_5());
+        state
+            .operation_models
+            .insert(op_id, builtin::claude_sonnet_4_5());
+
+        let effects = reduce(
+            &mut state,
+            Action::ToolResult {
+                session_id,
+                tool_call_id,
+                tool_name: "test".to_string(),
+                result: Ok(ToolResult::External(steer_tools::result::ExternalResult {
+                    tool_name: "test".to_string(),
+                    payload: "done".to_string(),
+                })),
+            },
+        );
+
+        assert!(effects.is_empty());
+    }
+
+    #[test]
+    fn test_pre_approved_tool_executes_immediately() {
+        let mut state = test_state();
+        let session_id = state.session_id;
+        let op_id = OpId::new();
+
+        state.approved_tools.insert("test_tool".to_string());
+        state.current_operation = Some(OperationState {
+            op_id,
+            kind: OperationKind::AgentLoop,
+            pending_tool_calls: HashSet::new(),
+        });
+        state
+            .operation_models
+            .insert(op_id, builtin::claude_sonnet_4_5());
+        state
+            .operation_models
+            .insert(op_id, builtin::claude_sonnet_4_5());
+        state
+            .operation_models
+            .insert(op_id, builtin::claude_sonnet_4_5());
+
+        let tool_call = steer_tools::ToolCall {
+            id: "tc_1".to_string(),
+            name: "test_tool".to_string(),
+            parameters: serde_json::json!({}),
+        };
+
+        let effects = reduce(
+            &mut state,
+            Action::ToolApprovalRequested {
+                session_id,
+                request_id: RequestId::new(),
+                tool_call,
+            },
+        );
+
+        assert!(
+            effects
+                .iter()
+                .any(|e| matches!(e, Effect::ExecuteTool { .. }))
+        );
+        assert!(state.pending_approval.is_none());
+    }
+
+    #[test]
+    fn test_denied_tool_request_emits_failure_message() {
+        let mut state = test_state();
+        let session_id = state.session_id;
+        let op_id = OpId::new();
+
+        state.current_operation = Some(OperationState {
+            op_id,
+            kind: OperationKind::AgentLoop,
+            pending_tool_calls: HashSet::new(),
+        });
+
+        state
+            .operation_models
+            .insert(op_id, builtin::claude_sonnet_4_5());
+
+        let mut config = SessionConfig::read_only(builtin::claude_sonnet_4_5());
+        config.tool_config.approval_policy = ToolApprovalPolicy {
+            default_behavior: UnapprovedBehavior::Deny,
+            preapproved: ApprovalRules::default(),
+        };
+        state.session_config = Some(config);
+
+        let tool_call = steer_tools::ToolCall {
+            id: "tc_1".to_string(),
+            name: "test_tool".to_string(),
+            parameters: serde_json::json!({}),
+        };
+
+        let effects = reduce(
+            &mut state,
+            Action::ToolApprovalRequested {
+                session_id,
+                request_id: RequestId::new(),
+                tool_call,
+            },
+        );
+
+        assert!(effects.iter().any(|e| matches!(
+            e,
+            Effect::EmitEvent {
+                event: SessionEvent::ToolCallFailed { .. },
+                ..
+            }
+        )));
+        assert!(effects.iter().any(|e| matches!(
+            e,
+            Effect::EmitEvent {
+                event: SessionEvent::ToolMessageAdded { .. },
+                ..
+            }
+        )));
+        assert!(
+            !effects
+                .iter()
+                .any(|e| matches!(e, Effect::ExecuteTool { .. }))
+        );
+        assert!(
+            !effects
+                .iter()
+                .any(|e| matches!(e, Effect::RequestUserApproval { .. }))
+        );
+        assert!(state.pending_approval.is_none());
+        assert!(state.approval_queue.is_empty());
+        assert_eq!(state.message_graph.messages.len(), 1);
+
+        match &state.message_graph.messages[0].data {
+            MessageData::Tool { result, .. } => match result {
+                ToolResult::Error(error) => {
+                    assert!(
+                        matches!(error, ToolError::DeniedByPolicy(name) if name == "test_tool")
+                    );
+                }
+                _ => panic!("expected denied tool error"),
+            },
+            _ => panic!("expected tool message"),
+        }
+    }
+
+    #[test]
+    fn test_user_denied_tool_request_emits_failure_message() {
+        let mut state = test_state();
+        let session_id = state.session_id;
+        let op_id = OpId::new();
+
+        state.current_operation = Some(OperationState {
+            op_id,
+            kind: OperationKind::AgentLoop,
+            pending_tool_calls: HashSet::new(),
+        });
+        state
+            .operation_models
+            .insert(op_id, builtin::claude_sonnet_4_5());
+
+        let tool_call = steer_tools::ToolCall {
+            id: "tc_1".to_string(),
+            name: "test_tool".to_string(),
+            parameters: serde_json::json!({}),
+        };
+        let request_id = RequestId::new();
+        state.pending_approval = Some(PendingApproval {
+            request_id,
+            tool_call: tool_call.clone(),
+        });
+
+        let effects = reduce(
+            &mut state,
+            Action::ToolApprovalDecided {
+                session_id,
+                request_id,
+                decision: ApprovalDecision::Denied,
+                remember: None,
+            },
+        );
+
+        assert!(effects.iter().any(|e| matches!(
+            e,
+            Effect::EmitEvent {
+                event: SessionEvent::ToolCallFailed { .. },
+                ..
+            }
         )));
-        assert!(effects.iter().any(|effect| matches!(
-            effect,
-            Effect::RequestUserApproval {
-                request_id,
-                tool_call,
+        assert!(effects.iter().any(|e| matches!(
+            e,
+            Effect::EmitEvent {
+                event: SessionEvent::ToolMessageAdded { .. },
                 ..
-            } if *request_id == new_pending.request_id && tool_call.id == pending_tool_call.id
+            }
         )));
         assert!(
             !effects
                 .iter()
-                .any(|effect| matches!(effect, Effect::ExecuteTool { .. }))
+                .any(|e| matches!(e, Effect::ExecuteTool { .. }))
         );
+        assert!(state.pending_approval.is_none());
+        assert!(state.approval_queue.is_empty());
+        assert_eq!(state.message_graph.messages.len(), 1);
+
+        match &state.message_graph.messages[0].data {
+            MessageData::Tool { result, .. } => {
+                match result {
+                    ToolResult::Error(error) => {
+                        assert!(matches!(
+                            error,
+                            ToolError::ApprovalDenied { tool_name, reason: None }
+                                if tool_name == "test_tool"
+                        ));
+                        assert!(result.llm_format().starts_with(
+                            "Error: Approval denied: the user declined to run test_tool"
+                        ));
+                    }
+                    _ => panic!("expected denied tool error"),
+                }
+            }
+            _ => panic!("expected tool message"),
+        }
     }
 
     #[test]
-    fn test_late_result_ignored_after_cancel() {
+    fn test_user_denied_with_reason_returns_reason_and_continues_loop() {
         let mut state = test_state();
         let session_id = state.session_id;
         let op_id = OpId::new();
-        let tool_call_id = ToolCallId::from_string("tc_1");
 
         state.current_operation = Some(OperationState {
             op_id,
             kind: OperationKind::AgentLoop,
-            pending_tool_calls: [tool_call_id.clone()].into_iter().collect(),
+            pending_tool_calls: HashSet::new(),
+        });
+        state
+            .operation_models
+            .insert(op_id, builtin::claude_sonnet_4_5());
+
+        let tool_call = steer_tools::ToolCall {
+            id: "tc_1".to_string(),
+            name: "test_tool".to_string(),
+            parameters: serde_json::json!({}),
+        };
+        let request_id = RequestId::new();
+        state.pending_approval = Some(PendingApproval {
+            request_id,
+            tool_call,
         });
 
-        let _ = reduce(
+        let effects = reduce(
             &mut state,
-            Action::Cancel {
+            Action::ToolApprovalDecided {
                 session_id,
-                op_id: None,
+                request_id,
+                decision: ApprovalDecision::DeniedWithReason("run the tests first".to_string()),
+                remember: None,
             },
         );
 
+        assert!(effects.iter().any(|e| matches!(
+            e,
+            Effect::EmitEvent {
+                event: SessionEvent::ToolCallFailed { error, .. },
+                ..
+            } if error == "Tool 'test_tool' denied by user: run the tests first"
+        )));
+        assert!(
+            effects
+                .iter()
+                .any(|e| matches!(e, Effect::CallModel { op_id: id, .. } if *id == op_id)),
+            "denying a single call should let the agent loop continue"
+        );
+        assert!(state.current_operation.is_some());
+
+        match &state.message_graph.messages[0].data {
+            MessageData::Tool {
+                result: ToolResult::Error(error),
+                ..
+            } => {
+                assert!(matches!(
+                    error,
+                    ToolError::ApprovalDenied { tool_name, reason: Some(reason) }
+                        if tool_name == "test_tool" && reason == "run the tests first"
+                ));
+            }
+            _ => panic!("expected denied tool error"),
+        }
+    }
+
+    #[test]
+    fn test_rejecting_a_whole_multi_edit_edit_keeps_edit_numbers() {
+        let mut state = test_state();
+        let session_id = state.session_id;
+        let op_id = OpId::new();
+
         state.current_operation = Some(OperationState {
             op_id,
             kind: OperationKind::AgentLoop,
@@ -3583,36 +5418,98 @@ mod tests {
         state
             .operation_models
             .insert(op_id, builtin::claude_sonnet_4_5());
-        state
-            .operation_models
-            .insert(op_id, builtin::claude_sonnet_4_5());
-        state
-            .operation_models
-            .insert(op_id, builtin::claude_sonnet_4_5());
 
+        let tool_call = steer_tools::ToolCall {
+            id: "tc_1".to_string(),
+            name: steer_tools::tools::MULTI_EDIT_TOOL_NAME.to_string(),
+            parameters: json!({
+                "file_path": "/tmp/lib.rs",
+                "edits": [
+                    { "old_string": "a\nb\nc\n", "new_string": "A\nb\nC\n" },
+                    { "old_string": "x\ny\nz\n", "new_string": "X\ny\nZ\n" },
+                ],
+            }),
+        };
+        let request_id = RequestId::new();
+        state.pending_approval = Some(PendingApproval {
+            request_id,
+            tool_call,
+        });
+
+        // Hunks 0 and 1 belong to the first edit; accept only the second edit's.
         let effects = reduce(
+            &mut state,
+            Action::ToolApprovalDecided {
+                session_id,
+                request_id,
+                decision: ApprovalDecision::ApprovedHunks(vec![2, 3]),
+                remember: None,
+            },
+        );
+
+        let executed = effects
+            .iter()
+            .find_map(|e| match e {
+                Effect::ExecuteTool { tool_call, .. } => Some(tool_call),
+                _ => None,
+            })
+            .expect("partially approved edit should execute");
+        let edits = executed.parameters["edits"]
+            .as_array()
+            .expect("multi_edit keeps its edits");
+        assert_eq!(edits.len(), 2, "rejected edits stay as no-ops");
+        assert_eq!(edits[0]["new_string"], "a\nb\nc\n");
+        assert_eq!(edits[1]["new_string"], "X\ny\nZ\n");
+
+        let _ = reduce(
             &mut state,
             Action::ToolResult {
                 session_id,
-                tool_call_id,
-                tool_name: "test".to_string(),
-                result: Ok(ToolResult::External(steer_tools::result::ExternalResult {
-                    tool_name: "test".to_string(),
-                    payload: "done".to_string(),
+                tool_call_id: ToolCallId::from_string("tc_1"),
+                tool_name: steer_tools::tools::MULTI_EDIT_TOOL_NAME.to_string(),
+                result: Ok(ToolResult::Edit(steer_tools::result::EditResult {
+                    file_path: "/tmp/lib.rs".to_string(),
+                    changes_made: 2,
+                    file_created: false,
+                    old_content: None,
+                    new_content: None,
+                    skipped_edits: Vec::new(),
                 })),
             },
         );
 
-        assert!(effects.is_empty());
+        match &state.message_graph.messages[0].data {
+            MessageData::Tool {
+                result: ToolResult::Edit(edit),
+                ..
+            } => {
+                assert_eq!(edit.skipped_edits.len(), 2);
+                assert!(
+                    edit.skipped_edits
+                        .iter()
+                        .all(|skipped| skipped.edit_index == 1)
+                );
+                assert!(
+                    edit.skipped_edits[0]
+                        .reason
+                        .contains("line 1 of old_string")
+                );
+                assert!(
+                    edit.skipped_edits[1]
+                        .reason
+                        .contains("line 3 of old_string")
+                );
+            }
+            _ => panic!("expected edit tool result"),
+        }
     }
 
     #[test]
-    fn test_pre_approved_tool_executes_immediately() {
+    fn test_approving_some_hunks_applies_only_those_and_reports_the_rest() {
         let mut state = test_state();
         let session_id = state.session_id;
         let op_id = OpId::new();
 
-        state.approved_tools.insert("test_tool".to_string());
         state.current_operation = Some(OperationState {
             op_id,
             kind: OperationKind::AgentLoop,
@@ -3621,183 +5518,324 @@ mod tests {
         state
             .operation_models
             .insert(op_id, builtin::claude_sonnet_4_5());
-        state
-            .operation_models
-            .insert(op_id, builtin::claude_sonnet_4_5());
-        state
-            .operation_models
-            .insert(op_id, builtin::claude_sonnet_4_5());
 
         let tool_call = steer_tools::ToolCall {
             id: "tc_1".to_string(),
-            name: "test_tool".to_string(),
-            parameters: serde_json::json!({}),
+            name: steer_tools::tools::EDIT_TOOL_NAME.to_string(),
+            parameters: json!({
+                "file_path": "/tmp/lib.rs",
+                "old_string": "one\ntwo\nthree\n",
+                "new_string": "ONE\ntwo\nTHREE\n",
+            }),
         };
+        let request_id = RequestId::new();
+        state.pending_approval = Some(PendingApproval {
+            request_id,
+            tool_call,
+        });
 
         let effects = reduce(
             &mut state,
-            Action::ToolApprovalRequested {
+            Action::ToolApprovalDecided {
                 session_id,
-                request_id: RequestId::new(),
-                tool_call,
+                request_id,
+                decision: ApprovalDecision::ApprovedHunks(vec![1]),
+                remember: Some(ApprovalMemory::PendingTool),
             },
         );
 
+        let executed = effects
+            .iter()
+            .find_map(|e| match e {
+                Effect::ExecuteTool { tool_call, .. } => Some(tool_call),
+                _ => None,
+            })
+            .expect("partially approved edit should execute");
+        assert_eq!(executed.parameters["new_string"], "one\ntwo\nTHREE\n");
         assert!(
-            effects
-                .iter()
-                .any(|e| matches!(e, Effect::ExecuteTool { .. }))
+            state.approved_tools.is_empty(),
+            "a partial approval should not be remembered"
         );
-        assert!(state.pending_approval.is_none());
+
+        let _ = reduce(
+            &mut state,
+            Action::ToolResult {
+                session_id,
+                tool_call_id: ToolCallId::from_string("tc_1"),
+                tool_name: steer_tools::tools::EDIT_TOOL_NAME.to_string(),
+                result: Ok(ToolResult::Edit(steer_tools::result::EditResult {
+                    file_path: "/tmp/lib.rs".to_string(),
+                    changes_made: 1,
+                    file_created: false,
+                    old_content: None,
+                    new_content: None,
+                    skipped_edits: Vec::new(),
+                })),
+            },
+        );
+
+        assert!(state.rejected_hunks.is_empty());
+        match &state.message_graph.messages[0].data {
+            MessageData::Tool { result, .. } => assert!(
+                result.llm_format().contains(
+                    "Skipped edit #1: the user rejected the change at line 1 of old_string"
+                ),
+                "rejected hunk should be reported to the model"
+            ),
+            _ => panic!("expected tool message"),
+        }
+    }
+
+    fn external_result(payload_len: usize) -> Result<ToolResult, ToolError> {
+        Ok(ToolResult::External(steer_tools::result::ExternalResult {
+            tool_name: "mcp__search".to_string(),
+            payload: "x".repeat(payload_len),
+        }))
     }
 
     #[test]
-    fn test_denied_tool_request_emits_failure_message() {
+    fn test_tool_results_are_truncated_to_the_configured_budget() {
         let mut state = test_state();
         let session_id = state.session_id;
         let op_id = OpId::new();
 
+        let mut config = base_session_config();
+        config.tool_config.result_budget = ToolResultBudget {
+            max_result_bytes: Some(4_000),
+            max_turn_bytes: Some(5_000),
+        };
+        state.session_config = Some(config);
         state.current_operation = Some(OperationState {
             op_id,
             kind: OperationKind::AgentLoop,
-            pending_tool_calls: HashSet::new(),
+            pending_tool_calls: [
+                ToolCallId::from_string("tc_1"),
+                ToolCallId::from_string("tc_2"),
+            ]
+            .into_iter()
+            .collect(),
         });
-
         state
             .operation_models
             .insert(op_id, builtin::claude_sonnet_4_5());
 
-        let mut config = SessionConfig::read_only(builtin::claude_sonnet_4_5());
-        config.tool_config.approval_policy = ToolApprovalPolicy {
-            default_behavior: UnapprovedBehavior::Deny,
-            preapproved: ApprovalRules::default(),
-        };
-        state.session_config = Some(config);
-
-        let tool_call = steer_tools::ToolCall {
-            id: "tc_1".to_string(),
-            name: "test_tool".to_string(),
-            parameters: serde_json::json!({}),
-        };
-
-        let effects = reduce(
+        let first = reduce(
             &mut state,
-            Action::ToolApprovalRequested {
+            Action::ToolResult {
                 session_id,
-                request_id: RequestId::new(),
-                tool_call,
+                tool_call_id: ToolCallId::from_string("tc_1"),
+                tool_name: "mcp__search".to_string(),
+                result: external_result(10_000),
             },
         );
-
-        assert!(effects.iter().any(|e| matches!(
-            e,
-            Effect::EmitEvent {
-                event: SessionEvent::ToolCallFailed { .. },
-                ..
-            }
-        )));
-        assert!(effects.iter().any(|e| matches!(
+        assert!(first.iter().any(|e| matches!(
             e,
             Effect::EmitEvent {
-                event: SessionEvent::ToolMessageAdded { .. },
+                event: SessionEvent::ToolCallCompleted { result, .. },
                 ..
-            }
+            } if result.llm_format().len() <= 4_000
+                && result.llm_format().contains("tool result budget")
         )));
-        assert!(
-            !effects
-                .iter()
-                .any(|e| matches!(e, Effect::ExecuteTool { .. }))
-        );
-        assert!(
-            !effects
-                .iter()
-                .any(|e| matches!(e, Effect::RequestUserApproval { .. }))
-        );
-        assert!(state.pending_approval.is_none());
-        assert!(state.approval_queue.is_empty());
-        assert_eq!(state.message_graph.messages.len(), 1);
 
-        match &state.message_graph.messages[0].data {
-            MessageData::Tool { result, .. } => match result {
-                ToolResult::Error(error) => {
-                    assert!(
-                        matches!(error, ToolError::DeniedByPolicy(name) if name == "test_tool")
-                    );
-                }
-                _ => panic!("expected denied tool error"),
+        let second = reduce(
+            &mut state,
+            Action::ToolResult {
+                session_id,
+                tool_call_id: ToolCallId::from_string("tc_2"),
+                tool_name: "mcp__search".to_string(),
+                result: external_result(3_000),
             },
-            _ => panic!("expected tool message"),
-        }
+        );
+
+        let updated = second
+            .iter()
+            .filter(|e| {
+                matches!(
+                    e,
+                    Effect::EmitEvent {
+                        event: SessionEvent::MessageUpdated { .. },
+                        ..
+                    }
+                )
+            })
+            .count();
+        assert_eq!(updated, 2, "both results exceed the turn's fair share");
+
+        let Some(Effect::CallModel { messages, .. }) = second
+            .iter()
+            .find(|e| matches!(e, Effect::CallModel { .. }))
+        else {
+            panic!("expected the agent loop to continue");
+        };
+        let total: usize = messages
+            .iter()
+            .filter_map(|message| match &message.data {
+                MessageData::Tool { result, .. } => Some(result.llm_format().len()),
+                _ => None,
+            })
+            .sum();
+        assert!(total <= 5_000, "turn total {total} exceeds the cap");
+    }
+
+    fn start_agent_loop(state: &mut AppState, op_id: OpId) {
+        let session_id = state.session_id;
+        let _ = reduce(
+            state,
+            Action::UserInput {
+                session_id,
+                content: vec![UserContent::Text {
+                    text: "Find the bug".to_string(),
+                }],
+                op_id,
+                message_id: MessageId::new(),
+                model: builtin::claude_sonnet_4_5(),
+                timestamp: 1,
+            },
+        );
     }
 
     #[test]
-    fn test_user_denied_tool_request_emits_failure_message() {
+    fn test_switch_model_after_current_turn_applies_to_next_request() {
         let mut state = test_state();
         let session_id = state.session_id;
         let op_id = OpId::new();
+        start_agent_loop(&mut state, op_id);
 
-        state.current_operation = Some(OperationState {
-            op_id,
-            kind: OperationKind::AgentLoop,
-            pending_tool_calls: HashSet::new(),
-        });
-        state
-            .operation_models
-            .insert(op_id, builtin::claude_sonnet_4_5());
+        let effects = reduce(
+            &mut state,
+            Action::SwitchModel {
+                session_id,
+                model: builtin::claude_haiku_4_5(),
+                mode: ModelSwitchMode::AfterCurrentTurn,
+                op_id: OpId::new(),
+            },
+        );
+        assert!(
+            effects.is_empty(),
+            "the in-flight request must not be interrupted"
+        );
+        assert_eq!(
+            state.current_operation.as_ref().map(|op| op.op_id),
+            Some(op_id)
+        );
 
-        let tool_call = steer_tools::ToolCall {
-            id: "tc_1".to_string(),
-            name: "test_tool".to_string(),
-            parameters: serde_json::json!({}),
-        };
-        let request_id = RequestId::new();
-        state.pending_approval = Some(PendingApproval {
-            request_id,
-            tool_call: tool_call.clone(),
-        });
+        if let Some(op) = state.current_operation.as_mut() {
+            op.pending_tool_calls
+                .insert(ToolCallId::from_string("tc_1"));
+        }
 
         let effects = reduce(
             &mut state,
-            Action::ToolApprovalDecided {
+            Action::ToolResult {
                 session_id,
-                request_id,
-                decision: ApprovalDecision::Denied,
-                remember: None,
+                tool_call_id: ToolCallId::from_string("tc_1"),
+                tool_name: "test_tool".to_string(),
+                result: external_result(10),
             },
         );
 
         assert!(effects.iter().any(|e| matches!(
             e,
-            Effect::EmitEvent {
-                event: SessionEvent::ToolCallFailed { .. },
-                ..
-            }
+            Effect::CallModel { op_id: id, model, .. }
+                if *id == op_id && *model == builtin::claude_haiku_4_5()
         )));
+    }
+
+    #[test]
+    fn test_switch_model_restart_cancels_and_reissues_request() {
+        let mut state = test_state();
+        let session_id = state.session_id;
+        let op_id = OpId::new();
+        start_agent_loop(&mut state, op_id);
+
+        let queued_op = OpId::new();
+        let _ = reduce(
+            &mut state,
+            Action::UserInput {
+                session_id,
+                content: vec![UserContent::Text {
+                    text: "And then write a test".to_string(),
+                }],
+                op_id: queued_op,
+                message_id: MessageId::new(),
+                model: builtin::claude_sonnet_4_5(),
+                timestamp: 2,
+            },
+        );
+
+        let restart_op = OpId::new();
+        let effects = reduce(
+            &mut state,
+            Action::SwitchModel {
+                session_id,
+                model: builtin::claude_haiku_4_5(),
+                mode: ModelSwitchMode::RestartOperation,
+                op_id: restart_op,
+            },
+        );
+
         assert!(effects.iter().any(|e| matches!(
             e,
-            Effect::EmitEvent {
-                event: SessionEvent::ToolMessageAdded { .. },
-                ..
-            }
+            Effect::CancelOperation { op_id: id, .. } if *id == op_id
         )));
+        let Some(Effect::CallModel {
+            op_id: call_op,
+            model,
+            messages,
+            ..
+        }) = effects
+            .iter()
+            .find(|e| matches!(e, Effect::CallModel { .. }))
+        else {
+            panic!("expected the operation to be restarted");
+        };
+        assert_eq!(*call_op, restart_op);
+        assert_eq!(*model, builtin::claude_haiku_4_5());
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            state.current_operation.as_ref().map(|op| op.op_id),
+            Some(restart_op)
+        );
+        assert_eq!(state.queued_work.len(), 1, "queued input stays queued");
+
+        let late = reduce(
+            &mut state,
+            Action::ModelResponseComplete {
+                session_id,
+                op_id,
+                message_id: MessageId::new(),
+                content: vec![AssistantContent::Text {
+                    text: "stale answer".to_string(),
+                }],
+                usage: None,
+                context_window_tokens: None,
+                configured_max_output_tokens: None,
+                timestamp: 3,
+            },
+        );
         assert!(
-            !effects
-                .iter()
-                .any(|e| matches!(e, Effect::ExecuteTool { .. }))
+            late.is_empty(),
+            "the cancelled request's response is dropped"
         );
-        assert!(state.pending_approval.is_none());
-        assert!(state.approval_queue.is_empty());
-        assert_eq!(state.message_graph.messages.len(), 1);
+    }
 
-        match &state.message_graph.messages[0].data {
-            MessageData::Tool { result, .. } => match result {
-                ToolResult::Error(error) => {
-                    assert!(matches!(error, ToolError::DeniedByUser(name) if name == "test_tool"));
-                }
-                _ => panic!("expected denied tool error"),
+    #[test]
+    fn test_switch_model_without_active_operation_is_noop() {
+        let mut state = test_state();
+        let session_id = state.session_id;
+
+        let effects = reduce(
+            &mut state,
+            Action::SwitchModel {
+                session_id,
+                model: builtin::claude_haiku_4_5(),
+                mode: ModelSwitchMode::RestartOperation,
+                op_id: OpId::new(),
             },
-            _ => panic!("expected tool message"),
-        }
+        );
+
+        assert!(effects.is_empty());
+        assert!(state.current_operation.is_none());
     }
 
     #[test]
@@ -3937,6 +5975,185 @@ mod tests {
         }
     }
 
+    fn interrupted_turn_events(op_id: OpId, tool_calls: &[ToolCall]) -> Vec<(u64, SessionEvent)> {
+        let events = vec![
+            SessionEvent::OperationStarted {
+                op_id,
+                kind: OperationKind::AgentLoop,
+            },
+            SessionEvent::AssistantMessageAdded {
+                message: Message {
+                    data: MessageData::Assistant {
+                        content: tool_calls
+                            .iter()
+                            .map(|tool_call| AssistantContent::ToolCall {
+                                tool_call: tool_call.clone(),
+                                thought_signature: None,
+                            })
+                            .collect(),
+                    },
+                    timestamp: 0,
+                    id: "msg_1".to_string(),
+                    parent_message_id: None,
+                },
+                model: builtin::claude_sonnet_4_5(),
+            },
+        ];
+        events
+            .into_iter()
+            .enumerate()
+            .map(|(i, e)| (i as u64, e))
+            .collect()
+    }
+
+    #[test]
+    fn test_restore_interrupted_operation_resumes_pending_approval() {
+        let mut state = test_state();
+        let session_id = state.session_id;
+        let op_id = OpId::new();
+        let request_id = RequestId::new();
+        let calls: Vec<ToolCall> = ["tc_1", "tc_2"]
+            .into_iter()
+            .map(|id| ToolCall {
+                id: id.to_string(),
+                name: "test_tool".to_string(),
+                parameters: json!({}),
+            })
+            .collect();
+        let mut events = interrupted_turn_events(op_id, &calls);
+        events.push((
+            2,
+            SessionEvent::ApprovalRequested {
+                request_id,
+                tool_call: calls[0].clone(),
+            },
+        ));
+        for (_, event) in &events {
+            apply_event_to_state(&mut state, event);
+        }
+
+        let interrupted = restore_interrupted_operation(&mut state, &events);
+
+        assert_eq!(
+            interrupted,
+            Some(InterruptedOperation::AwaitingApproval { op_id })
+        );
+        let pending = state.pending_approval.as_ref().expect("pending approval");
+        assert_eq!(pending.request_id, request_id);
+        assert_eq!(pending.tool_call.id, "tc_1");
+        assert_eq!(state.approval_queue.len(), 1);
+        assert_eq!(state.approval_queue[0].tool_call.id, "tc_2");
+        assert_eq!(
+            state.operation_models.get(&op_id),
+            Some(&builtin::claude_sonnet_4_5())
+        );
+
+        let effects = reduce(
+            &mut state,
+            Action::ToolApprovalDecided {
+                session_id,
+                request_id,
+                decision: ApprovalDecision::Approved,
+                remember: None,
+            },
+        );
+        assert!(effects.iter().any(|e| matches!(
+            e,
+            Effect::ExecuteTool { tool_call, .. } if tool_call.id == "tc_1"
+        )));
+    }
+
+    #[test]
+    fn test_restore_interrupted_operation_abandons_running_tool() {
+        let mut state = test_state();
+        let session_id = state.session_id;
+        let op_id = OpId::new();
+        let calls = vec![
+            ToolCall {
+                id: "tc_done".to_string(),
+                name: "test_tool".to_string(),
+                parameters: json!({}),
+            },
+            ToolCall {
+                id: "tc_running".to_string(),
+                name: "test_tool".to_string(),
+                parameters: json!({}),
+            },
+        ];
+        let mut events = interrupted_turn_events(op_id, &calls);
+        events.push((
+            2,
+            SessionEvent::ToolMessageAdded {
+                message: Message {
+                    data: MessageData::Tool {
+                        tool_use_id: "tc_done".to_string(),
+                        result: ToolResult::Error(ToolError::Cancelled("test_tool".to_string())),
+                    },
+                    timestamp: 0,
+                    id: "tool_done".to_string(),
+                    parent_message_id: Some("msg_1".to_string()),
+                },
+            },
+        ));
+        events.push((
+            3,
+            SessionEvent::ToolCallStarted {
+                id: ToolCallId::from_string("tc_running"),
+                name: "test_tool".to_string(),
+                parameters: json!({}),
+                model: builtin::claude_sonnet_4_5(),
+            },
+        ));
+        for (_, event) in &events {
+            apply_event_to_state(&mut state, event);
+        }
+
+        let interrupted = restore_interrupted_operation(&mut state, &events);
+        assert_eq!(interrupted, Some(InterruptedOperation::Abandoned { op_id }));
+
+        let effects = reduce(
+            &mut state,
+            Action::AbandonInterruptedOperation { session_id, op_id },
+        );
+
+        let failed: Vec<_> = effects
+            .iter()
+            .filter_map(|e| match e {
+                Effect::EmitEvent {
+                    event: SessionEvent::ToolCallFailed { id, .. },
+                    ..
+                } => Some(id.as_str().to_string()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(failed, vec!["tc_running".to_string()]);
+        assert!(effects.iter().any(|e| matches!(
+            e,
+            Effect::EmitEvent {
+                event: SessionEvent::OperationCancelled { op_id: id, .. },
+                ..
+            } if *id == op_id
+        )));
+        assert!(state.current_operation.is_none());
+    }
+
+    #[test]
+    fn test_restore_interrupted_operation_ignores_finished_operations() {
+        let mut state = test_state();
+        let op_id = OpId::new();
+        let mut events = interrupted_turn_events(op_id, &[]);
+        events.push((
+            2,
+            SessionEvent::OperationCompleted {
+                op_id,
+                timing: None,
+            },
+        ));
+
+        assert_eq!(restore_interrupted_operation(&mut state, &events), None);
+        assert!(state.current_operation.is_none());
+    }
+
     #[test]
     fn test_malformed_tool_call_auto_denies() {
         let mut state = test_state();
@@ -4318,7 +6535,7 @@ mod tests {
         assert!(effects.iter().any(|e| matches!(
             e,
             Effect::EmitEvent {
-                event: SessionEvent::OperationCompleted { op_id: completed_op_id },
+                event: SessionEvent::OperationCompleted { op_id: completed_op_id, .. },
                 ..
             } if *completed_op_id == op_id
         )));
@@ -4891,7 +7108,7 @@ mod tests {
         assert!(effects.iter().any(|e| matches!(
             e,
             Effect::EmitEvent {
-                event: SessionEvent::OperationCompleted { op_id },
+                event: SessionEvent::OperationCompleted { op_id, .. },
                 ..
             } if *op_id == op_b
         )));
@@ -5322,7 +7539,7 @@ mod tests {
         assert!(effects.iter().any(|e| matches!(
             e,
             Effect::EmitEvent {
-                event: SessionEvent::OperationCompleted { op_id: completed },
+                event: SessionEvent::OperationCompleted { op_id: completed, .. },
                 ..
             } if *completed == op_id
         )));
@@ -5453,7 +7670,7 @@ mod tests {
         assert!(effects.iter().any(|e| matches!(
             e,
             Effect::EmitEvent {
-                event: SessionEvent::OperationCompleted { op_id: completed },
+                event: SessionEvent::OperationCompleted { op_id: completed, .. },
                 ..
             } if *completed == op_id
         )));
@@ -5512,6 +7729,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_model_response_refused_emits_refused_and_completes_operation() {
+        let mut state = test_state();
+        let session_id = state.session_id;
+        let op_id = OpId::new();
+
+        state.current_operation = Some(OperationState {
+            op_id,
+            kind: OperationKind::AgentLoop,
+            pending_tool_calls: HashSet::new(),
+        });
+        state
+            .operation_models
+            .insert(op_id, builtin::claude_sonnet_4_5());
+
+        let effects = reduce(
+            &mut state,
+            Action::ModelResponseRefused {
+                session_id,
+                op_id,
+                reason: "anthropic refused the request".to_string(),
+            },
+        );
+
+        assert!(effects.iter().any(|e| matches!(
+            e,
+            Effect::EmitEvent {
+                event: SessionEvent::Refused { reason },
+                ..
+            } if reason == "anthropic refused the request"
+        )));
+        assert!(!effects.iter().any(|e| matches!(
+            e,
+            Effect::EmitEvent {
+                event: SessionEvent::Error { .. },
+                ..
+            }
+        )));
+        assert!(effects.iter().any(|e| matches!(
+            e,
+            Effect::EmitEvent {
+                event: SessionEvent::OperationCompleted { op_id: completed, .. },
+                ..
+            } if *completed == op_id
+        )));
+        assert!(state.current_operation.is_none());
+    }
+
     #[test]
     fn test_handle_compaction_failed_emits_compact_result() {
         use crate::app::domain::event::{CompactResult, CompactTrigger};
@@ -17,7 +17,7 @@ use crate::session::state::{
     SessionConfig, SessionPolicyOverrides, SessionToolConfig, ToolApprovalPolicyOverrides,
     ToolVisibility, WorkspaceConfig,
 };
-use crate::tools::{SessionMcpBackends, ToolExecutor};
+use crate::tools::{CommandDenylist, SessionMcpBackends, ToolEnv, ToolExecutor};
 
 use super::interpreter::EffectInterpreter;
 use super::stepper::{AgentConfig, AgentInput, AgentOutput, AgentState, AgentStepper};
@@ -58,6 +58,7 @@ pub struct AgentInterpreter {
     config: AgentInterpreterConfig,
     event_store: Arc<dyn EventStore>,
     effect_interpreter: EffectInterpreter,
+    tool_env: Arc<ToolEnv>,
 }
 
 impl AgentInterpreter {
@@ -82,6 +83,15 @@ impl AgentInterpreter {
         if session_config.parent_session_id.is_none() {
             session_config.parent_session_id = config.parent_session_id;
         }
+        let tool_env = Arc::new(
+            ToolEnv::resolve(&session_config.tool_config.env)
+                .with_denylist(
+                    CommandDenylist::new(&session_config.tool_config.bash_denylist)
+                        .map_err(|e| AgentInterpreterError::InvalidConfig(e.to_string()))?,
+                )
+                .with_network(session_config.tool_config.allow_network)
+                .with_outside_workspace(session_config.tool_config.allow_outside_workspace),
+        );
 
         let session_created_event = SessionEvent::SessionCreated {
             config: Box::new(session_config),
@@ -105,6 +115,7 @@ impl AgentInterpreter {
             config,
             event_store,
             effect_interpreter,
+            tool_env,
         })
     }
 
@@ -153,8 +164,11 @@ impl AgentInterpreter {
                 if stepper.is_terminal(&state) {
                     match state {
                         AgentState::Complete { final_message } => {
-                            self.emit_event(SessionEvent::OperationCompleted { op_id: self.op_id })
-                                .await?;
+                            self.emit_event(SessionEvent::OperationCompleted {
+                                op_id: self.op_id,
+                                timing: None,
+                            })
+                            .await?;
                             return Ok(final_message);
                         }
                         AgentState::Failed { error } => {
@@ -162,8 +176,11 @@ impl AgentInterpreter {
                                 message: error.clone(),
                             })
                             .await?;
-                            self.emit_event(SessionEvent::OperationCompleted { op_id: self.op_id })
-                                .await?;
+                            self.emit_event(SessionEvent::OperationCompleted {
+                                op_id: self.op_id,
+                                timing: None,
+                            })
+                            .await?;
                             return Err(AgentInterpreterError::Agent(error));
                         }
                         AgentState::Cancelled => {
@@ -287,6 +304,7 @@ impl AgentInterpreter {
                         .execute_tool(
                             tool_call.clone(),
                             Some(agent_config.model.clone()),
+                            self.tool_env.clone(),
                             cancel_token.clone(),
                         )
                         .await;
@@ -347,8 +365,11 @@ impl AgentInterpreter {
                 }
 
                 AgentOutput::Done { final_message } => {
-                    self.emit_event(SessionEvent::OperationCompleted { op_id: self.op_id })
-                        .await?;
+                    self.emit_event(SessionEvent::OperationCompleted {
+                        op_id: self.op_id,
+                        timing: None,
+                    })
+                    .await?;
                     return Ok(final_message);
                 }
 
@@ -357,8 +378,11 @@ impl AgentInterpreter {
                         message: error.clone(),
                     })
                     .await?;
-                    self.emit_event(SessionEvent::OperationCompleted { op_id: self.op_id })
-                        .await?;
+                    self.emit_event(SessionEvent::OperationCompleted {
+                        op_id: self.op_id,
+                        timing: None,
+                    })
+                    .await?;
                     return Err(AgentInterpreterError::Agent(error));
                 }
 
@@ -401,6 +425,11 @@ fn default_session_config(default_model: crate::config::model::ModelId) -> Sessi
             visibility: ToolVisibility::All,
             approval_policy: crate::session::state::ToolApprovalPolicy::default(),
             metadata: HashMap::new(),
+            result_budget: crate::session::state::ToolResultBudget::default(),
+            env: HashMap::new(),
+            bash_denylist: crate::session::state::BashDenylistConfig::default(),
+            allow_network: true,
+            allow_outside_workspace: false,
         },
         system_prompt: None,
         primary_agent_id: None,
@@ -413,6 +442,9 @@ fn default_session_config(default_model: crate::config::model::ModelId) -> Sessi
         metadata: HashMap::new(),
         default_model,
         auto_compaction: crate::session::state::AutoCompactionConfig::default(),
+        auto_summary: crate::session::state::AutoSummaryConfig::default(),
+        isolation: crate::isolation::IsolationMode::None,
+        isolated_workspace: None,
     }
 }
 
@@ -434,6 +466,9 @@ pub enum AgentInterpreterError {
     #[error("Event store error: {0}")]
     EventStore(String),
 
+    #[error("Invalid session config: {0}")]
+    InvalidConfig(String),
+
     #[error("Cancelled")]
     Cancelled,
 }
@@ -0,0 +1,166 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use steer_tools::result::ToolResult;
+use steer_tools::{ToolCall, ToolError};
+use tracing::warn;
+
+use crate::app::domain::session::{ToolApprover, ToolAuditEntry, ToolAuditStatus, ToolAuditStore};
+use crate::app::domain::types::SessionId;
+use crate::tools::ToolEnv;
+
+use super::event_log::redact_secrets;
+
+/// A tool execution headed for the audit log. Started just before the tool
+/// runs and written once its result is known.
+pub(super) struct PendingToolAudit {
+    store: Arc<dyn ToolAuditStore>,
+    session_id: SessionId,
+    tool_call_id: String,
+    tool_name: String,
+    parameters_hash: String,
+    parameters: Value,
+    workspace: Option<String>,
+    approved_by: ToolApprover,
+    started_at: DateTime<Utc>,
+    started: Instant,
+}
+
+impl PendingToolAudit {
+    pub(super) fn start(
+        store: Arc<dyn ToolAuditStore>,
+        session_id: SessionId,
+        tool_call: &ToolCall,
+        approved_by: ToolApprover,
+        workspace: Option<String>,
+        env: &ToolEnv,
+    ) -> Self {
+        Self {
+            store,
+            session_id,
+            tool_call_id: tool_call.id.clone(),
+            tool_name: tool_call.name.clone(),
+            parameters_hash: hash_parameters(&tool_call.parameters),
+            parameters: redact_parameters(&tool_call.parameters, env),
+            workspace,
+            approved_by,
+            started_at: Utc::now(),
+            started: Instant::now(),
+        }
+    }
+
+    /// Append the entry. A failed write is logged rather than failing the
+    /// tool call, whose result has already been produced.
+    pub(super) async fn finish(self, result: &Result<ToolResult, ToolError>) {
+        let status = match result {
+            Ok(ToolResult::Error(ToolError::Cancelled(_))) | Err(ToolError::Cancelled(_)) => {
+                ToolAuditStatus::Cancelled
+            }
+            Ok(ToolResult::Error(_)) | Err(_) => ToolAuditStatus::Failed,
+            Ok(_) => ToolAuditStatus::Succeeded,
+        };
+        let entry = ToolAuditEntry {
+            session_id: self.session_id,
+            tool_call_id: self.tool_call_id,
+            tool_name: self.tool_name,
+            parameters_hash: self.parameters_hash,
+            parameters: self.parameters,
+            workspace: self.workspace,
+            status,
+            duration_ms: u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX),
+            approved_by: self.approved_by,
+            started_at: self.started_at,
+            finished_at: Utc::now(),
+        };
+
+        if let Err(error) = self.store.record_tool_execution(&entry).await {
+            warn!(
+                session_id = %entry.session_id,
+                tool_name = %entry.tool_name,
+                "Failed to write tool audit entry: {error}"
+            );
+        }
+    }
+}
+
+fn hash_parameters(parameters: &Value) -> String {
+    hex::encode(Sha256::digest(parameters.to_string().as_bytes()))
+}
+
+/// Blank out secret-looking keys, bearer credentials and any value taken from
+/// the session's secret environment variables.
+fn redact_parameters(parameters: &Value, env: &ToolEnv) -> Value {
+    let mut redacted = parameters.clone();
+    redact_secrets(&mut redacted);
+    redact_env_secrets(&mut redacted, env);
+    redacted
+}
+
+fn redact_env_secrets(value: &mut Value, env: &ToolEnv) {
+    match value {
+        Value::String(s) => *s = env.redact(s),
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| redact_env_secrets(item, env)),
+        Value::Object(map) => map
+            .values_mut()
+            .for_each(|item| redact_env_secrets(item, env)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::domain::session::{InMemoryEventStore, ToolAuditFilter};
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn records_redacted_parameters_with_hash_of_original() {
+        let store = Arc::new(InMemoryEventStore::new());
+        let session_id = SessionId::new();
+        let env = ToolEnv::resolve(&HashMap::from([(
+            "DEPLOY_KEY".to_string(),
+            "${env:PATH}".to_string(),
+        )]));
+        let path = std::env::var("PATH").unwrap();
+        let tool_call = ToolCall {
+            id: "tc_1".to_string(),
+            name: "bash".to_string(),
+            parameters: serde_json::json!({
+                "command": format!("deploy --key {path}"),
+                "api_key": "sk-live",
+            }),
+        };
+
+        let audit = PendingToolAudit::start(
+            store.clone(),
+            session_id,
+            &tool_call,
+            ToolApprover::User,
+            Some("/repo".to_string()),
+            &env,
+        );
+        audit
+            .finish(&Err(ToolError::Cancelled("bash".to_string())))
+            .await;
+
+        let entries = store
+            .list_tool_executions(&ToolAuditFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        let entry = &entries[0];
+        assert_eq!(entry.status, ToolAuditStatus::Cancelled);
+        assert_eq!(entry.approved_by, ToolApprover::User);
+        assert_eq!(entry.parameters["command"], "deploy --key [REDACTED]");
+        assert_eq!(entry.parameters["api_key"], "[REDACTED]");
+        assert_eq!(
+            entry.parameters_hash,
+            hash_parameters(&tool_call.parameters)
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! Drafting candidate answers for best-of turns.
+//!
+//! A candidate runs its own small agent loop outside the session's message
+//! graph: the model sees every tool, but only read-only tools are executed. A
+//! call to any other tool stops the candidate and is reported back so the
+//! whole run can be refused.
+
+use std::sync::Arc;
+
+use steer_tools::result::ToolResult;
+use steer_tools::tools::{FORGET_TOOL_NAME, REMEMBER_TOOL_NAME, TODO_WRITE_TOOL_NAME};
+use steer_tools::{ToolCall, ToolSchema};
+use tokio_util::sync::CancellationToken;
+
+use crate::api::provider::TokenUsage;
+use crate::app::SystemContext;
+use crate::app::conversation::{AssistantContent, Message, MessageData};
+use crate::config::model::ModelId;
+use crate::tools::ToolEnv;
+use crate::tools::builtin_tools::READ_ONLY_TOOL_NAMES;
+
+use super::interpreter::EffectInterpreter;
+
+/// Model calls a candidate may make before it has to answer.
+const MAX_CANDIDATE_STEPS: usize = 8;
+
+pub(crate) struct CandidateRequest {
+    pub model: ModelId,
+    pub messages: Vec<Message>,
+    pub system_context: Option<SystemContext>,
+    pub tools: Vec<ToolSchema>,
+    pub env: Arc<ToolEnv>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct CandidateDraft {
+    pub content: Vec<AssistantContent>,
+    /// Usage summed over every model call the candidate made.
+    pub usage: Option<TokenUsage>,
+    pub error: Option<String>,
+    /// The tool with side effects the candidate asked for, if any.
+    pub side_effect_tool: Option<String>,
+}
+
+pub(crate) async fn draft_candidate(
+    interpreter: &EffectInterpreter,
+    request: CandidateRequest,
+    cancel_token: CancellationToken,
+) -> CandidateDraft {
+    let CandidateRequest {
+        model,
+        mut messages,
+        system_context,
+        tools,
+        env,
+    } = request;
+    let mut draft = CandidateDraft::default();
+
+    for _ in 0..MAX_CANDIDATE_STEPS {
+        let response = match interpreter
+            .call_model(
+                model.clone(),
+                messages.clone(),
+                system_context.clone(),
+                tools.clone(),
+                cancel_token.clone(),
+            )
+            .await
+        {
+            Ok(response) => response,
+            Err(error) => {
+                draft.error = Some(error.to_string());
+                return draft;
+            }
+        };
+        draft.usage = add_usage(draft.usage, response.usage);
+
+        let tool_calls: Vec<ToolCall> = response
+            .content
+            .iter()
+            .filter_map(|block| match block {
+                AssistantContent::ToolCall { tool_call, .. } => Some(tool_call.clone()),
+                _ => None,
+            })
+            .collect();
+        if tool_calls.is_empty() {
+            draft.content = response.content;
+            return draft;
+        }
+        if let Some(call) = tool_calls
+            .iter()
+            .find(|call| !is_candidate_tool(&call.name))
+        {
+            draft.side_effect_tool = Some(call.name.clone());
+            return draft;
+        }
+
+        let assistant = Message {
+            data: MessageData::Assistant {
+                content: response.content,
+            },
+            timestamp: Message::current_timestamp(),
+            id: Message::generate_id("assistant", Message::current_timestamp()),
+            parent_message_id: messages.last().map(|m| m.id().to_string()),
+        };
+        messages.push(assistant);
+
+        for tool_call in tool_calls {
+            let tool_use_id = tool_call.id.clone();
+            let result = interpreter
+                .execute_tool(
+                    tool_call,
+                    Some(model.clone()),
+                    env.clone(),
+                    cancel_token.clone(),
+                )
+                .await
+                .unwrap_or_else(ToolResult::Error);
+            messages.push(Message {
+                data: MessageData::Tool {
+                    tool_use_id,
+                    result,
+                },
+                timestamp: Message::current_timestamp(),
+                id: Message::generate_id("tool", Message::current_timestamp()),
+                parent_message_id: messages.last().map(|m| m.id().to_string()),
+            });
+        }
+    }
+
+    draft.error = Some(format!("No answer after {MAX_CANDIDATE_STEPS} model calls"));
+    draft
+}
+
+/// Read-only tools a candidate may run. The todo list and memory are session
+/// state, so a candidate may not write them even though those tools are
+/// otherwise auto-approved.
+fn is_candidate_tool(name: &str) -> bool {
+    ![TODO_WRITE_TOOL_NAME, REMEMBER_TOOL_NAME, FORGET_TOOL_NAME].contains(&name)
+        && READ_ONLY_TOOL_NAMES.contains(&name)
+}
+
+fn add_usage(total: Option<TokenUsage>, usage: Option<TokenUsage>) -> Option<TokenUsage> {
+    match (total, usage) {
+        (Some(a), Some(b)) => Some(TokenUsage::new(
+            a.input_tokens.saturating_add(b.input_tokens),
+            a.output_tokens.saturating_add(b.output_tokens),
+            a.total_tokens.saturating_add(b.total_tokens),
+        )),
+        (total, usage) => total.or(usage),
+    }
+}
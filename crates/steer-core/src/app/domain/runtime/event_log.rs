@@ -0,0 +1,439 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{Mutex, broadcast};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::app::domain::event::SessionEvent;
+use crate::app::domain::types::SessionId;
+
+use super::subscription::RuntimeEvent;
+use super::supervisor::RuntimeHandle;
+
+const REDACTED: &str = "[REDACTED]";
+/// Object keys whose string values are replaced with `[REDACTED]`.
+const SECRET_KEY_MARKERS: [&str; 8] = [
+    "api_key",
+    "apikey",
+    "authorization",
+    "cookie",
+    "passphrase",
+    "password",
+    "secret",
+    "token",
+];
+pub const DEFAULT_EVENT_LOG_MAX_FILES: usize = 5;
+
+/// Where logged events are written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventLogSink {
+    /// One JSON object per line on stdout.
+    Stdout,
+    /// Structured `tracing` lines under the `steer::events` target.
+    Tracing,
+    /// A JSONL file, rotated to `<path>.1`, `<path>.2`, … once it reaches `max_bytes`.
+    File {
+        path: PathBuf,
+        max_bytes: Option<u64>,
+        max_files: usize,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct EventLogConfig {
+    pub sink: EventLogSink,
+    /// Only sessions whose id matches this glob are logged.
+    pub sessions: Option<glob::Pattern>,
+    /// Event kinds to log, as snake_case variant names; empty means all.
+    pub kinds: Vec<String>,
+}
+
+impl EventLogConfig {
+    pub fn new(sink: EventLogSink) -> Self {
+        Self {
+            sink,
+            sessions: None,
+            kinds: Vec::new(),
+        }
+    }
+
+    pub fn wants(&self, session_id: SessionId, kind: &str) -> bool {
+        let session_matches = self
+            .sessions
+            .as_ref()
+            .is_none_or(|pattern| pattern.matches(&session_id.to_string()));
+        let kind_matches =
+            self.kinds.is_empty() || self.kinds.iter().any(|k| to_snake_case(k) == kind);
+        session_matches && kind_matches
+    }
+}
+
+/// One line of the event log.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EventLogRecord {
+    pub timestamp: DateTime<Utc>,
+    pub session_id: SessionId,
+    pub seq: u64,
+    pub kind: String,
+    pub event: Value,
+}
+
+impl EventLogRecord {
+    pub fn new(session_id: SessionId, seq: u64, event: &SessionEvent) -> serde_json::Result<Self> {
+        let mut event = serde_json::to_value(event)?;
+        redact_secrets(&mut event);
+        Ok(Self {
+            timestamp: Utc::now(),
+            session_id,
+            seq,
+            kind: event_kind(&event),
+            event,
+        })
+    }
+}
+
+/// Tees every session's events to a sink for debugging remote servers.
+/// Logging can be paused and resumed at runtime without resubscribing.
+pub struct EventLogger {
+    config: EventLogConfig,
+    enabled: AtomicBool,
+    file: Mutex<Option<RotatingFile>>,
+}
+
+impl EventLogger {
+    pub fn new(config: EventLogConfig) -> Self {
+        let file = match &config.sink {
+            EventLogSink::File {
+                path,
+                max_bytes,
+                max_files,
+            } => Some(RotatingFile::new(path.clone(), *max_bytes, *max_files)),
+            EventLogSink::Stdout | EventLogSink::Tracing => None,
+        };
+        Self {
+            config,
+            enabled: AtomicBool::new(true),
+            file: Mutex::new(file),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+        info!(enabled, "Session event logging toggled");
+    }
+
+    /// Write `event` if logging is enabled and it passes the filters.
+    pub async fn log(&self, event: &RuntimeEvent) -> std::io::Result<()> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+
+        let record =
+            EventLogRecord::new(event.session_id, event.envelope.seq, &event.envelope.event)?;
+        if !self.config.wants(record.session_id, &record.kind) {
+            return Ok(());
+        }
+
+        match &self.config.sink {
+            EventLogSink::Tracing => {
+                info!(
+                    target: "steer::events",
+                    session_id = %record.session_id,
+                    seq = record.seq,
+                    kind = %record.kind,
+                    event = %record.event,
+                );
+                Ok(())
+            }
+            EventLogSink::Stdout => {
+                let mut line = serde_json::to_vec(&record)?;
+                line.push(b'\n');
+                let mut stdout = tokio::io::stdout();
+                stdout.write_all(&line).await?;
+                stdout.flush().await
+            }
+            EventLogSink::File { .. } => {
+                let mut line = serde_json::to_vec(&record)?;
+                line.push(b'\n');
+                match self.file.lock().await.as_mut() {
+                    Some(file) => file.write_line(&line).await,
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+
+    /// Log events from every session on `runtime` until `shutdown` is cancelled.
+    pub fn spawn(
+        self: Arc<Self>,
+        runtime: &RuntimeHandle,
+        shutdown: CancellationToken,
+    ) -> JoinHandle<()> {
+        let mut events = runtime.subscribe_all_events();
+        tokio::spawn(async move {
+            loop {
+                let event = tokio::select! {
+                    () = shutdown.cancelled() => break,
+                    event = events.recv() => event,
+                };
+                match event {
+                    Ok(event) => {
+                        if let Err(e) = self.log(&event).await {
+                            warn!(error = %e, "Failed to write session event log");
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!(lagged = n, "Event logger lagged; some events were dropped");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+            debug!("Event logger stopped");
+        })
+    }
+}
+
+/// Append-only JSONL file that rotates by size.
+struct RotatingFile {
+    path: PathBuf,
+    max_bytes: Option<u64>,
+    max_files: usize,
+    file: Option<tokio::fs::File>,
+    written: u64,
+}
+
+impl RotatingFile {
+    fn new(path: PathBuf, max_bytes: Option<u64>, max_files: usize) -> Self {
+        Self {
+            path,
+            max_bytes,
+            max_files,
+            file: None,
+            written: 0,
+        }
+    }
+
+    async fn write_line(&mut self, line: &[u8]) -> std::io::Result<()> {
+        let len = u64::try_from(line.len()).unwrap_or(u64::MAX);
+        if self.file.is_none() {
+            self.open().await?;
+        }
+        if self
+            .max_bytes
+            .is_some_and(|max| self.written > 0 && self.written.saturating_add(len) > max)
+        {
+            self.rotate().await?;
+        }
+
+        let Some(file) = self.file.as_mut() else {
+            return Ok(());
+        };
+        file.write_all(line).await?;
+        file.flush().await?;
+        self.written = self.written.saturating_add(len);
+        Ok(())
+    }
+
+    async fn open(&mut self) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        self.written = file.metadata().await?.len();
+        self.file = Some(file);
+        Ok(())
+    }
+
+    /// Shift `<path>.N` to `<path>.N+1`, dropping the oldest, and start a fresh file.
+    async fn rotate(&mut self) -> std::io::Result<()> {
+        self.file = None;
+        if self.max_files == 0 {
+            tokio::fs::remove_file(&self.path).await?;
+        } else {
+            for index in (1..self.max_files).rev() {
+                let from = rotated_path(&self.path, index);
+                if tokio::fs::try_exists(&from).await? {
+                    tokio::fs::rename(&from, rotated_path(&self.path, index + 1)).await?;
+                }
+            }
+            tokio::fs::rename(&self.path, rotated_path(&self.path, 1)).await?;
+        }
+        self.open().await
+    }
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(format!(".{index}"));
+    PathBuf::from(name)
+}
+
+/// The snake_case variant name of a serialized `SessionEvent`.
+fn event_kind(event: &Value) -> String {
+    let name = match event {
+        Value::String(name) => name.as_str(),
+        Value::Object(map) => map.keys().next().map_or("unknown", String::as_str),
+        _ => "unknown",
+    };
+    to_snake_case(name)
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Blank out string values under secret-looking keys and bearer credentials anywhere.
+pub(super) fn redact_secrets(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let key = key.to_ascii_lowercase();
+                if value.is_string() && SECRET_KEY_MARKERS.iter().any(|m| key.contains(m)) {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact_secrets(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        Value::String(s) if s.trim_start().to_ascii_lowercase().starts_with("bearer ") => {
+            *s = REDACTED.to_string();
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::domain::runtime::SessionEventEnvelope;
+    use crate::app::domain::types::OpId;
+
+    fn runtime_event(session_id: SessionId, seq: u64, event: SessionEvent) -> RuntimeEvent {
+        RuntimeEvent {
+            session_id,
+            envelope: SessionEventEnvelope { seq, event },
+        }
+    }
+
+    #[test]
+    fn kinds_are_snake_case_variant_names() {
+        let record = EventLogRecord::new(
+            SessionId::new(),
+            3,
+            &SessionEvent::OperationCompleted {
+                op_id: OpId::new(),
+                timing: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(record.kind, "operation_completed");
+
+        let record =
+            EventLogRecord::new(SessionId::new(), 4, &SessionEvent::WorkspaceChanged).unwrap();
+        assert_eq!(record.kind, "workspace_changed");
+    }
+
+    #[test]
+    fn filters_by_session_glob_and_kind() {
+        let session_id = SessionId::new();
+        let id = session_id.to_string();
+        let config = EventLogConfig {
+            sessions: Some(glob::Pattern::new(&format!("{}*", &id[..id.len() - 4])).unwrap()),
+            kinds: vec!["OperationCompleted".to_string(), "error".to_string()],
+            ..EventLogConfig::new(EventLogSink::Stdout)
+        };
+
+        assert!(config.wants(session_id, "operation_completed"));
+        assert!(config.wants(session_id, "error"));
+        assert!(!config.wants(session_id, "tool_call_started"));
+        assert!(!config.wants(SessionId::new(), "error"));
+    }
+
+    #[test]
+    fn redacts_secret_keys_and_bearer_values() {
+        let mut value = serde_json::json!({
+            "env": { "GITHUB_TOKEN": "ghp_abc", "PATH": "/usr/bin" },
+            "headers": [{ "name": "x", "value": "Bearer sk-live" }],
+            "api_key": "sk-123",
+            "usage": { "input_tokens": 12 },
+        });
+        redact_secrets(&mut value);
+
+        assert_eq!(value["env"]["GITHUB_TOKEN"], REDACTED);
+        assert_eq!(value["env"]["PATH"], "/usr/bin");
+        assert_eq!(value["headers"][0]["value"], REDACTED);
+        assert_eq!(value["api_key"], REDACTED);
+        assert_eq!(value["usage"]["input_tokens"], 12);
+    }
+
+    #[tokio::test]
+    async fn file_sink_rotates_by_size_and_respects_toggle() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+        let logger = EventLogger::new(EventLogConfig::new(EventLogSink::File {
+            path: path.clone(),
+            max_bytes: Some(1),
+            max_files: 2,
+        }));
+        let session_id = SessionId::new();
+
+        for seq in 0..4 {
+            logger
+                .log(&runtime_event(
+                    session_id,
+                    seq,
+                    SessionEvent::WorkspaceChanged,
+                ))
+                .await
+                .unwrap();
+        }
+
+        let current = std::fs::read_to_string(&path).unwrap();
+        let record: Value = serde_json::from_str(current.trim()).unwrap();
+        assert_eq!(record["seq"], 3);
+        assert_eq!(record["kind"], "workspace_changed");
+        assert_eq!(record["event"], "WorkspaceChanged");
+        assert!(rotated_path(&path, 1).exists());
+        assert!(rotated_path(&path, 2).exists());
+        assert!(!rotated_path(&path, 3).exists());
+
+        logger.set_enabled(false);
+        logger
+            .log(&runtime_event(
+                session_id,
+                4,
+                SessionEvent::WorkspaceChanged,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), current);
+    }
+}
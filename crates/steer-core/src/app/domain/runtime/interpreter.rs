@@ -4,16 +4,18 @@ use futures_util::StreamExt;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
-use crate::api::Client as ApiClient;
 use crate::api::provider::{CompletionResponse, StreamChunk};
+use crate::api::{ApiError, Client as ApiClient, ResponseCache, StreamError};
 use crate::app::SystemContext;
-use crate::app::conversation::Message;
+use crate::app::conversation::{AssistantContent, Message, MessageData};
 use crate::app::domain::action::{ModelCallError, ModelCallRequestErrorKind};
 use crate::app::domain::delta::{StreamDelta, ToolCallDelta};
 use crate::app::domain::types::{MessageId, OpId, SessionId, ToolCallId};
 use crate::config::model::ModelId;
-use crate::tools::{SessionMcpBackends, ToolExecutor};
+use crate::profiling::{ProfilePhase, time_phase};
+use crate::tools::{SessionMcpBackends, ToolEnv, ToolExecutor};
 use steer_tools::{ToolCall, ToolError, ToolResult, ToolSchema};
+use tracing::{debug, warn};
 
 #[derive(Clone)]
 pub struct EffectInterpreter {
@@ -62,6 +64,31 @@ impl EffectInterpreter {
         self.api_client.model_max_output_tokens(model)
     }
 
+    /// Whether credentials are configured for `model`'s provider.
+    pub async fn has_credentials(&self, model: &ModelId) -> bool {
+        self.api_client.has_credentials(&model.provider).await
+    }
+
+    pub async fn supports_token_counting(&self, model: &ModelId) -> bool {
+        self.api_client.supports_token_counting(model).await
+    }
+
+    /// Count a request's input tokens with the provider's tokenizer, when the
+    /// provider offers one.
+    pub async fn count_tokens(
+        &self,
+        model: &ModelId,
+        messages: Vec<Message>,
+        system_context: Option<SystemContext>,
+        tools: Vec<ToolSchema>,
+        cancel_token: CancellationToken,
+    ) -> Result<Option<u32>, ApiError> {
+        let tools_option = if tools.is_empty() { None } else { Some(tools) };
+        self.api_client
+            .count_tokens(model, messages, system_context, tools_option, cancel_token)
+            .await
+    }
+
     pub async fn call_model(
         &self,
         model: ModelId,
@@ -83,6 +110,43 @@ impl EffectInterpreter {
         cancel_token: CancellationToken,
         delta_stream: Option<DeltaStreamContext>,
     ) -> Result<CompletionResponse, ModelCallError> {
+        let label = model.to_string();
+        time_phase(
+            self.tool_executor.profiler(),
+            ProfilePhase::ModelRequest,
+            || Some(label),
+            self.stream_model_response(
+                model,
+                messages,
+                system_context,
+                tools,
+                cancel_token,
+                delta_stream,
+            ),
+        )
+        .await
+    }
+
+    async fn stream_model_response(
+        &self,
+        model: ModelId,
+        messages: Vec<Message>,
+        system_context: Option<SystemContext>,
+        tools: Vec<ToolSchema>,
+        cancel_token: CancellationToken,
+        delta_stream: Option<DeltaStreamContext>,
+    ) -> Result<CompletionResponse, ModelCallError> {
+        let cache_key = self.response_cache_key(&model, &messages, system_context.as_ref(), &tools);
+        if let Some((cache, key)) = &cache_key
+            && let Some(response) = cache.get(key).await
+        {
+            debug!(target: "api::cache", %model, key, "Serving model response from cache");
+            if let Some(delta_stream) = &delta_stream {
+                replay_cached_response(delta_stream, &response).await;
+            }
+            return Ok(response);
+        }
+
         let tools_option = if tools.is_empty() { None } else { Some(tools) };
 
         let mut stream = self
@@ -96,9 +160,15 @@ impl EffectInterpreter {
                 cancel_token,
             )
             .await
-            .map_err(|error| ModelCallError::RequestStartFailed {
-                kind: ModelCallRequestErrorKind::from_api_error(&error),
-                message: error.to_string(),
+            .map_err(|error| match error {
+                ApiError::RequestBlocked { provider, details } => ModelCallError::Refused {
+                    provider,
+                    reason: details,
+                },
+                error => ModelCallError::RequestStartFailed {
+                    kind: ModelCallRequestErrorKind::from_api_error(&error),
+                    message: error.to_string(),
+                },
             })?;
 
         let mut final_response = None;
@@ -151,6 +221,9 @@ impl EffectInterpreter {
                 StreamChunk::MessageComplete(response) => {
                     final_response = Some(response);
                 }
+                StreamChunk::Error(StreamError::Refused { provider, reason }) => {
+                    return Err(ModelCallError::Refused { provider, reason });
+                }
                 StreamChunk::Error(err) => {
                     return Err(ModelCallError::StreamFailed(err));
                 }
@@ -158,13 +231,59 @@ impl EffectInterpreter {
             }
         }
 
-        final_response.ok_or(ModelCallError::MissingCompletionResponse)
+        let response = final_response.ok_or(ModelCallError::MissingCompletionResponse)?;
+        if let Some((cache, key)) = &cache_key
+            && let Err(error) = cache.put(key, &response).await
+        {
+            warn!(target: "api::cache", %error, "Failed to cache model response");
+        }
+        Ok(response)
+    }
+
+    /// The cache and key for this request, unless caching is off or a tool
+    /// with side effects has run since the last user message. After such a
+    /// tool the workspace may differ from the cached run even when the
+    /// conversation reads the same.
+    fn response_cache_key(
+        &self,
+        model: &ModelId,
+        messages: &[Message],
+        system_context: Option<&SystemContext>,
+        tools: &[ToolSchema],
+    ) -> Option<(Arc<ResponseCache>, String)> {
+        let cache = self.api_client.response_cache()?;
+        let operation_start = messages
+            .iter()
+            .rposition(|message| matches!(message.data, MessageData::User { .. }))
+            .map_or(0, |index| index + 1);
+        let side_effects = messages[operation_start..]
+            .iter()
+            .filter_map(|message| match &message.data {
+                MessageData::Assistant { content } => Some(content),
+                _ => None,
+            })
+            .flatten()
+            .any(|content| match content {
+                AssistantContent::ToolCall { tool_call, .. } => {
+                    !self.tool_executor.is_idempotent(&tool_call.name)
+                }
+                _ => false,
+            });
+        if side_effects {
+            return None;
+        }
+
+        let key = self
+            .api_client
+            .response_cache_key(model, messages, system_context, tools);
+        Some((cache.clone(), key))
     }
 
     pub async fn execute_tool(
         &self,
         tool_call: ToolCall,
         invoking_model: Option<ModelId>,
+        env: Arc<ToolEnv>,
         cancel_token: CancellationToken,
     ) -> Result<ToolResult, ToolError> {
         let resolver = self
@@ -178,6 +297,7 @@ impl EffectInterpreter {
                     &tool_call,
                     session_id,
                     invoking_model,
+                    env,
                     cancel_token,
                     resolver,
                 )
@@ -201,12 +321,33 @@ impl EffectInterpreter {
     }
 }
 
+/// Send a cached response's text and thinking as deltas, as if it had just
+/// been streamed.
+async fn replay_cached_response(delta_stream: &DeltaStreamContext, response: &CompletionResponse) {
+    let (op_id, message_id) = &delta_stream.context;
+    for content in &response.content {
+        let delta = match content {
+            AssistantContent::Text { text } => StreamDelta::TextChunk {
+                op_id: *op_id,
+                message_id: message_id.clone(),
+                delta: text.clone(),
+            },
+            AssistantContent::Thought { thought } => StreamDelta::ThinkingChunk {
+                op_id: *op_id,
+                message_id: message_id.clone(),
+                delta: thought.display_text(),
+            },
+            AssistantContent::ToolCall { .. } | AssistantContent::Image { .. } => continue,
+        };
+        let _ = delta_stream.tx.send(delta).await;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::api::error::{ApiError, StreamError};
     use crate::api::provider::{CompletionResponse, Provider, TokenUsage};
-    use crate::app::conversation::AssistantContent;
     use crate::app::validation::ValidatorRegistry;
     use crate::auth::ProviderRegistry;
     use crate::config::model::{ModelId, ModelParameters};
@@ -288,6 +429,33 @@ mod tests {
         }
     }
 
+    #[derive(Default)]
+    struct CountingProvider {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Provider for CountingProvider {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        async fn complete(
+            &self,
+            _model_id: &ModelId,
+            _messages: Vec<Message>,
+            _system: Option<SystemContext>,
+            _tools: Option<Vec<ToolSchema>>,
+            _call_options: Option<ModelParameters>,
+            _token: CancellationToken,
+        ) -> Result<CompletionResponse, ApiError> {
+            let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(CompletionResponse::new(vec![AssistantContent::Text {
+                text: format!("call {call}"),
+            }]))
+        }
+    }
+
     async fn create_test_deps() -> (Arc<ApiClient>, Arc<ToolExecutor>) {
         let model_registry = Arc::new(ModelRegistry::load(&[]).expect("model registry"));
         let provider_registry = Arc::new(ProviderRegistry::load(&[]).expect("provider registry"));
@@ -330,6 +498,69 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn call_model_reuses_cached_response_until_a_side_effect_runs() {
+        let (api_client, tool_executor) = create_test_deps().await;
+        let cache_dir = tempfile::tempdir().unwrap();
+        let api_client = Arc::new(
+            (*api_client)
+                .clone()
+                .with_response_cache(ResponseCache::new(cache_dir.path())),
+        );
+        let provider_id = ProviderId("counting".to_string());
+        let provider = Arc::new(CountingProvider::default());
+        api_client.insert_test_provider(provider_id.clone(), provider.clone());
+        let interpreter = EffectInterpreter::new(api_client, tool_executor);
+        let model = ModelId::new(provider_id, "counting-model");
+
+        let user = |id: &str| Message {
+            timestamp: 0,
+            id: id.to_string(),
+            parent_message_id: None,
+            data: MessageData::User {
+                content: vec![crate::app::conversation::UserContent::Text {
+                    text: "run the checks".to_string(),
+                }],
+            },
+        };
+        let call = |messages: Vec<Message>| {
+            interpreter.call_model(
+                model.clone(),
+                messages,
+                None,
+                vec![],
+                CancellationToken::new(),
+            )
+        };
+
+        let first = call(vec![user("first-run")]).await.unwrap();
+        let rerun = call(vec![user("second-run")]).await.unwrap();
+        assert_eq!(first, rerun);
+        assert_eq!(provider.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        let after_bash = vec![
+            user("first-run"),
+            Message {
+                timestamp: 0,
+                id: "assistant".to_string(),
+                parent_message_id: None,
+                data: MessageData::Assistant {
+                    content: vec![AssistantContent::ToolCall {
+                        tool_call: ToolCall {
+                            id: "call-1".to_string(),
+                            name: "bash".to_string(),
+                            parameters: serde_json::json!({"command": "make"}),
+                        },
+                        thought_signature: None,
+                    }],
+                },
+            },
+        ];
+        call(after_bash.clone()).await.unwrap();
+        call(after_bash).await.unwrap();
+        assert_eq!(provider.calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
     #[tokio::test]
     async fn call_model_returns_typed_stream_error() {
         let (api_client, tool_executor) = create_test_deps().await;
@@ -1,12 +1,24 @@
 mod agent_interpreter;
+mod audit;
+mod best_of;
+mod event_log;
 mod interpreter;
 mod session_actor;
 mod stepper;
 mod subscription;
 mod supervisor;
+mod timing;
+mod webhooks;
 
 pub use agent_interpreter::{AgentInterpreter, AgentInterpreterConfig, AgentInterpreterError};
+pub use event_log::{
+    DEFAULT_EVENT_LOG_MAX_FILES, EventLogConfig, EventLogRecord, EventLogSink, EventLogger,
+};
 pub use interpreter::EffectInterpreter;
+pub use session_actor::{ActiveOperation, OperationPhase};
 pub use stepper::{AgentConfig, AgentInput, AgentOutput, AgentState, AgentStepper};
-pub use subscription::{SessionEventEnvelope, SessionEventSubscription};
-pub use supervisor::{RuntimeError, RuntimeHandle, RuntimeService};
+pub use subscription::{RuntimeEvent, SessionEventEnvelope, SessionEventSubscription};
+pub use supervisor::{RuntimeConfig, RuntimeError, RuntimeHandle, RuntimeService};
+pub use webhooks::{
+    WEBHOOK_SIGNATURE_HEADER, WebhookConfig, WebhookEventKind, WebhookNotifier, WebhookPayload,
+};
@@ -1,29 +1,50 @@
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use chrono::{DateTime, Utc};
 use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio::task::{AbortHandle, JoinHandle};
+use tokio::time::Instant;
 use tokio_util::sync::CancellationToken;
 
 use crate::api::Client as ApiClient;
 use crate::api::provider::CompletionResponse;
+use crate::app::SystemContext;
+use crate::app::conversation::{AssistantContent, Message, MessageData, UserContent};
 use crate::app::domain::action::{
-    Action, McpServerState, ModelCallError, SchemaSource, SessionTitleGenerationError,
+    Action, McpServerState, ModelCallError, SchemaSource, SessionSummaryError,
+    SessionTitleGenerationError,
 };
 use crate::app::domain::delta::StreamDelta;
 use crate::app::domain::effect::{Effect, McpServerConfig};
-use crate::app::domain::event::SessionEvent;
+use crate::app::domain::event::{BestOfCandidate, SessionEvent, TimingSpanKind};
 use crate::app::domain::reduce::{InvalidActionKind, ReduceError, reduce};
 use crate::app::domain::session::{EventStore, EventStoreError};
-use crate::app::domain::state::AppState;
+use crate::app::domain::state::{AppState, OperationKind};
 use crate::app::domain::types::{MessageId, OpId, SessionId};
-use crate::tools::{McpBackend, SessionMcpBackends, ToolBackend, ToolExecutor};
+use crate::config::model::ModelId;
+use crate::metrics;
+use crate::tools::{
+    CommandDenylist, McpBackend, SessionMcpBackends, ToolBackend, ToolEnv, ToolExecutor,
+};
+use crate::utils::tokens::{TokenCalibration, TokenCounter, estimate_request_tokens};
 
+use super::audit::PendingToolAudit;
+use super::best_of;
 use super::interpreter::{DeltaStreamContext, EffectInterpreter};
-use super::subscription::{SessionEventEnvelope, SessionEventSubscription, UnsubscribeSignal};
+use super::subscription::{
+    RuntimeEvent, SessionEventEnvelope, SessionEventSubscription, UnsubscribeSignal,
+};
+use super::timing::OperationTimers;
 
 const EVENT_BROADCAST_CAPACITY: usize = 256;
 const DELTA_BROADCAST_CAPACITY: usize = 1024;
+/// Longest transcript sent for summarization; older messages are dropped first.
+const SUMMARY_TRANSCRIPT_MAX_CHARS: usize = 24_000;
+/// Longest excerpt of a single message in the summarization transcript.
+const SUMMARY_MESSAGE_MAX_CHARS: usize = 2_000;
 
 pub(crate) enum SessionCmd {
     Dispatch {
@@ -42,9 +63,44 @@ pub(crate) enum SessionCmd {
     Suspend {
         reply: oneshot::Sender<()>,
     },
+    Drain {
+        reply: oneshot::Sender<()>,
+    },
+    GetActiveOperation {
+        reply: oneshot::Sender<Option<ActiveOperation>>,
+    },
+    GetIdleSince {
+        reply: oneshot::Sender<Option<Instant>>,
+    },
+    CancelOperation {
+        op_id: Option<OpId>,
+        force: bool,
+        reply: oneshot::Sender<Result<bool, SessionError>>,
+    },
     Shutdown,
 }
 
+/// What a session's in-flight operation is doing, for admin listings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveOperation {
+    pub session_id: SessionId,
+    pub op_id: OpId,
+    pub phase: OperationPhase,
+    /// `None` for an operation restored from a previous server run.
+    pub started_at: Option<DateTime<Utc>>,
+    pub current_tool: Option<String>,
+    pub model: Option<ModelId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationPhase {
+    CallingModel,
+    RunningTool,
+    AwaitingApproval,
+    Compacting,
+    RunningBash,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum SessionError {
     #[error("Event store error: {0}")]
@@ -66,6 +122,7 @@ pub enum SessionError {
     ChannelClosed,
 }
 
+#[derive(Clone)]
 pub(crate) struct SessionActorHandle {
     pub cmd_tx: mpsc::Sender<SessionCmd>,
 }
@@ -119,6 +176,57 @@ impl SessionActorHandle {
         reply_rx.await.map_err(|_| SessionError::ChannelClosed)
     }
 
+    /// Cancels the running operation, records the cancellation, and stops
+    /// the actor. Resolves once the cancellation is persisted.
+    pub async fn drain(&self) -> Result<(), SessionError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.cmd_tx
+            .send(SessionCmd::Drain { reply: reply_tx })
+            .await
+            .map_err(|_| SessionError::ChannelClosed)?;
+        reply_rx.await.map_err(|_| SessionError::ChannelClosed)
+    }
+
+    pub async fn active_operation(&self) -> Result<Option<ActiveOperation>, SessionError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.cmd_tx
+            .send(SessionCmd::GetActiveOperation { reply: reply_tx })
+            .await
+            .map_err(|_| SessionError::ChannelClosed)?;
+        reply_rx.await.map_err(|_| SessionError::ChannelClosed)
+    }
+
+    /// When the session last saw activity, or `None` while it has subscribers
+    /// or an operation that is doing work. An operation waiting on an approval
+    /// does not keep the session busy.
+    pub async fn idle_since(&self) -> Result<Option<Instant>, SessionError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.cmd_tx
+            .send(SessionCmd::GetIdleSince { reply: reply_tx })
+            .await
+            .map_err(|_| SessionError::ChannelClosed)?;
+        reply_rx.await.map_err(|_| SessionError::ChannelClosed)
+    }
+
+    /// Cancels the running operation (or `op_id`, if it is the running one).
+    /// Returns whether anything was cancelled.
+    pub async fn cancel_operation(
+        &self,
+        op_id: Option<OpId>,
+        force: bool,
+    ) -> Result<bool, SessionError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.cmd_tx
+            .send(SessionCmd::CancelOperation {
+                op_id,
+                force,
+                reply: reply_tx,
+            })
+            .await
+            .map_err(|_| SessionError::ChannelClosed)?;
+        reply_rx.await.map_err(|_| SessionError::ChannelClosed)?
+    }
+
     pub fn shutdown(&self) {
         let _ = self.cmd_tx.try_send(SessionCmd::Shutdown);
     }
@@ -131,14 +239,26 @@ struct SessionActor {
     interpreter: EffectInterpreter,
     tool_executor: Arc<ToolExecutor>,
     active_operations: HashMap<OpId, CancellationToken>,
+    /// Tasks spawned for each operation, aborted by a forced cancel.
+    operation_tasks: HashMap<OpId, Vec<AbortHandle>>,
+    operation_started_at: HashMap<OpId, DateTime<Utc>>,
+    operation_timers: OperationTimers,
     event_broadcast: broadcast::Sender<SessionEventEnvelope>,
+    runtime_events: Option<broadcast::Sender<RuntimeEvent>>,
     delta_broadcast: broadcast::Sender<StreamDelta>,
     subscriber_count: usize,
+    last_activity: Instant,
     unsubscribe_rx: mpsc::UnboundedReceiver<UnsubscribeSignal>,
     unsubscribe_tx: mpsc::UnboundedSender<UnsubscribeSignal>,
     internal_action_tx: mpsc::Sender<Action>,
     internal_action_rx: mpsc::Receiver<Action>,
     session_mcp_backends: Arc<SessionMcpBackends>,
+    /// Set while a provider token count is running, so model calls never
+    /// queue up more than one.
+    token_count_in_flight: Arc<AtomicBool>,
+    /// Cancelled when the actor stops; background model calls that belong to
+    /// no operation run on a child of it.
+    shutdown_token: CancellationToken,
 }
 
 impl SessionActor {
@@ -165,14 +285,21 @@ impl SessionActor {
             interpreter,
             tool_executor,
             active_operations: HashMap::new(),
+            operation_tasks: HashMap::new(),
+            operation_started_at: HashMap::new(),
+            operation_timers: OperationTimers::default(),
             event_broadcast,
+            runtime_events: None,
             delta_broadcast,
             subscriber_count: 0,
+            last_activity: Instant::now(),
             unsubscribe_rx,
             unsubscribe_tx,
             internal_action_tx,
             internal_action_rx,
             session_mcp_backends,
+            token_count_in_flight: Arc::new(AtomicBool::new(false)),
+            shutdown_token: CancellationToken::new(),
         }
     }
 
@@ -187,14 +314,17 @@ impl SessionActor {
                 Some(cmd) = cmd_rx.recv() => {
                     match cmd {
                         SessionCmd::Dispatch { action, reply } => {
+                            self.last_activity = Instant::now();
                             let result = self.handle_action(*action).await;
                             let _ = reply.send(result);
                         }
                         SessionCmd::Subscribe { reply } => {
+                            self.last_activity = Instant::now();
                             let subscription = self.create_subscription();
                             let _ = reply.send(subscription);
                         }
                         SessionCmd::SubscribeDeltas { reply } => {
+                            self.last_activity = Instant::now();
                             let rx = self.delta_broadcast.subscribe();
                             let _ = reply.send(rx);
                         }
@@ -206,6 +336,23 @@ impl SessionActor {
                             let _ = reply.send(());
                             break;
                         }
+                        SessionCmd::Drain { reply } => {
+                            self.cancel_current_operation().await;
+                            self.cancel_all_operations();
+                            self.cleanup_mcp_backends().await;
+                            let _ = reply.send(());
+                            break;
+                        }
+                        SessionCmd::GetActiveOperation { reply } => {
+                            let _ = reply.send(self.active_operation());
+                        }
+                        SessionCmd::GetIdleSince { reply } => {
+                            let _ = reply.send(self.idle_since());
+                        }
+                        SessionCmd::CancelOperation { op_id, force, reply } => {
+                            let result = self.cancel_operation(op_id, force).await;
+                            let _ = reply.send(result);
+                        }
                         SessionCmd::Shutdown => {
                             self.cancel_all_operations();
                             self.cleanup_mcp_backends().await;
@@ -215,6 +362,7 @@ impl SessionActor {
                 }
 
                 Some(action) = self.internal_action_rx.recv() => {
+                    self.last_activity = Instant::now();
                     if let Err(e) = self.handle_action(action).await {
                         tracing::error!(
                             session_id = %self.session_id,
@@ -225,6 +373,7 @@ impl SessionActor {
                 }
 
                 Some(UnsubscribeSignal) = self.unsubscribe_rx.recv() => {
+                    self.last_activity = Instant::now();
                     self.subscriber_count = self.subscriber_count.saturating_sub(1);
                     tracing::debug!(
                         session_id = %self.session_id,
@@ -237,6 +386,7 @@ impl SessionActor {
             }
         }
 
+        self.shutdown_token.cancel();
         tracing::debug!(session_id = %self.session_id, "Session actor stopped");
     }
 
@@ -323,7 +473,16 @@ impl SessionActor {
 
     async fn handle_effect(&mut self, effect: Effect) -> Result<(), SessionError> {
         match effect {
-            Effect::EmitEvent { event, .. } => {
+            Effect::EmitEvent { mut event, .. } => {
+                if let SessionEvent::OperationCompleted { op_id, timing } = &mut event
+                    && timing.is_none()
+                {
+                    *timing = self.operation_timers.finish(*op_id);
+                    if let Some(timing) = timing {
+                        metrics::record_operation_timing(timing);
+                    }
+                }
+                self.track_operation_lifecycle(&event);
                 let seq = match self.event_store.append(self.session_id, &event).await {
                     Ok(seq) => seq,
                     Err(e) => {
@@ -339,7 +498,7 @@ impl SessionActor {
                 };
 
                 let envelope = SessionEventEnvelope { seq, event };
-                let _ = self.event_broadcast.send(envelope);
+                self.publish(envelope);
 
                 Ok(())
             }
@@ -352,29 +511,42 @@ impl SessionActor {
                 tools,
                 ..
             } => {
+                let system_context = self.with_session_memory(system_context).await;
                 let context_window_tokens = self.interpreter.model_context_window_tokens(&model);
                 let configured_max_output_tokens = self.interpreter.model_max_output_tokens(&model);
                 let cancel_token = self.active_operations.entry(op_id).or_default().clone();
+                self.spawn_token_count(
+                    &model,
+                    &messages,
+                    system_context.as_ref(),
+                    &tools,
+                    cancel_token.child_token(),
+                );
 
                 let interpreter = self.interpreter.clone();
                 let action_tx = self.internal_action_tx.clone();
                 let session_id = self.session_id;
                 let delta_broadcast = self.delta_broadcast.clone();
                 let message_id = MessageId::new();
+                let timers = self.operation_timers.clone();
 
-                tokio::spawn(async move {
+                let task = tokio::spawn(async move {
                     let (delta_tx, mut delta_rx) = mpsc::channel::<StreamDelta>(64);
                     let delta_stream = Some(DeltaStreamContext::new(
                         delta_tx,
                         (op_id, message_id.clone()),
                     ));
 
+                    let sent = Instant::now();
                     let delta_forward_task = {
                         let delta_broadcast = delta_broadcast.clone();
                         tokio::spawn(async move {
+                            let mut first_delta = None;
                             while let Some(delta) = delta_rx.recv().await {
+                                first_delta.get_or_insert_with(Instant::now);
                                 let _ = delta_broadcast.send(delta);
                             }
+                            first_delta
                         })
                     };
 
@@ -389,13 +561,18 @@ impl SessionActor {
                         )
                         .await;
 
-                    if let Err(e) = delta_forward_task.await {
-                        tracing::debug!(
-                            session_id = %session_id,
-                            error = %e,
-                            "Delta forward task ended unexpectedly"
-                        );
-                    }
+                    let first_delta = match delta_forward_task.await {
+                        Ok(first_delta) => first_delta,
+                        Err(e) => {
+                            tracing::debug!(
+                                session_id = %session_id,
+                                error = %e,
+                                "Delta forward task ended unexpectedly"
+                            );
+                            None
+                        }
+                    };
+                    timers.record_model_call(op_id, sent, first_delta);
 
                     let action = match result {
                         Ok(CompletionResponse { content, usage }) => {
@@ -410,6 +587,13 @@ impl SessionActor {
                                 timestamp: current_timestamp(),
                             }
                         }
+                        Err(error @ ModelCallError::Refused { .. }) => {
+                            Action::ModelResponseRefused {
+                                session_id,
+                                op_id,
+                                reason: error.to_string(),
+                            }
+                        }
                         Err(error) => Action::ModelResponseError {
                             session_id,
                             op_id,
@@ -419,6 +603,56 @@ impl SessionActor {
 
                     let _ = action_tx.send(action).await;
                 });
+                self.track_task(op_id, &task);
+
+                Ok(())
+            }
+
+            Effect::RunBestOf {
+                op_id,
+                candidate_op_ids,
+                model,
+                messages,
+                system_context,
+                tools,
+                ..
+            } => {
+                let system_context = self.with_session_memory(system_context).await;
+                let cancel_token = self.active_operations.entry(op_id).or_default().clone();
+                let env = self.tool_env();
+                let session_id = self.session_id;
+
+                for (index, candidate_op_id) in (0u32..).zip(candidate_op_ids) {
+                    let interpreter = self.interpreter.clone();
+                    let action_tx = self.internal_action_tx.clone();
+                    let cancel_token = cancel_token.clone();
+                    let request = best_of::CandidateRequest {
+                        model: model.clone(),
+                        messages: messages.clone(),
+                        system_context: system_context.clone(),
+                        tools: tools.clone(),
+                        env: env.clone(),
+                    };
+
+                    tokio::spawn(async move {
+                        let draft =
+                            best_of::draft_candidate(&interpreter, request, cancel_token).await;
+                        let action = Action::BestOfCandidateFinished {
+                            session_id,
+                            op_id,
+                            candidate: BestOfCandidate {
+                                index,
+                                op_id: candidate_op_id,
+                                message_id: MessageId::new(),
+                                content: draft.content,
+                                usage: draft.usage,
+                                error: draft.error,
+                            },
+                            side_effect_tool: draft.side_effect_tool,
+                        };
+                        let _ = action_tx.send(action).await;
+                    });
+                }
 
                 Ok(())
             }
@@ -482,7 +716,10 @@ impl SessionActor {
             }
 
             Effect::ExecuteTool {
-                op_id, tool_call, ..
+                op_id,
+                tool_call,
+                approved_by,
+                ..
             } => {
                 let cancel_token = self.active_operations.entry(op_id).or_default().clone();
 
@@ -494,6 +731,20 @@ impl SessionActor {
                 let tool_name = tool_call.name.clone();
                 let tool_parameters = tool_call.parameters.clone();
                 let invoking_model = self.state.operation_models.get(&op_id).cloned();
+                let env = self.tool_env();
+                let audit = self.tool_executor.audit_log().map(|store| {
+                    PendingToolAudit::start(
+                        store.clone(),
+                        session_id,
+                        &tool_call,
+                        approved_by,
+                        self.state
+                            .session_config
+                            .as_ref()
+                            .and_then(|config| config.workspace.get_path()),
+                        &env,
+                    )
+                });
 
                 let start_action = Action::ToolExecutionStarted {
                     session_id,
@@ -502,11 +753,23 @@ impl SessionActor {
                     tool_parameters,
                 };
                 let _ = action_tx.send(start_action).await;
+                let timers = self.operation_timers.clone();
 
-                tokio::spawn(async move {
+                let task = tokio::spawn(async move {
+                    let started = Instant::now();
                     let result = interpreter
-                        .execute_tool(tool_call, invoking_model, cancel_token)
+                        .execute_tool(tool_call, invoking_model, env, cancel_token)
                         .await;
+                    timers.record(
+                        op_id,
+                        TimingSpanKind::ToolExecution {
+                            tool_name: tool_name.clone(),
+                        },
+                        started.elapsed(),
+                    );
+                    if let Some(audit) = audit {
+                        audit.finish(&result).await;
+                    }
 
                     let action = Action::ToolResult {
                         session_id,
@@ -517,6 +780,7 @@ impl SessionActor {
 
                     let _ = action_tx.send(action).await;
                 });
+                self.track_task(op_id, &task);
 
                 Ok(())
             }
@@ -526,13 +790,16 @@ impl SessionActor {
                 tool_call,
                 ..
             } => {
+                if let Some(op) = &self.state.current_operation {
+                    self.operation_timers.approval_requested(op.op_id);
+                }
                 let event = SessionEvent::ApprovalRequested {
                     request_id,
                     tool_call,
                 };
                 let seq = self.event_store.append(self.session_id, &event).await?;
                 let envelope = SessionEventEnvelope { seq, event };
-                let _ = self.event_broadcast.send(envelope);
+                self.publish(envelope);
                 Ok(())
             }
 
@@ -543,6 +810,41 @@ impl SessionActor {
                 Ok(())
             }
 
+            Effect::GenerateSessionSummary {
+                model, messages, ..
+            } => {
+                let interpreter = self.interpreter.clone();
+                let action_tx = self.internal_action_tx.clone();
+                let session_id = self.session_id;
+                let cancel_token = self.shutdown_token.child_token();
+
+                // Summaries run outside the interactive operation so they
+                // never hold it up or get cancelled with it; they stop with
+                // the actor.
+                tokio::spawn(async move {
+                    let message_count = messages.len();
+                    let action = match generate_session_summary(
+                        &interpreter,
+                        model,
+                        &messages,
+                        cancel_token,
+                    )
+                    .await
+                    {
+                        Ok(summary) => Action::SessionSummaryGenerated {
+                            session_id,
+                            summary,
+                            message_count,
+                        },
+                        Err(error) => Action::SessionSummaryGenerationFailed { session_id, error },
+                    };
+
+                    let _ = action_tx.send(action).await;
+                });
+
+                Ok(())
+            }
+
             Effect::ListWorkspaceFiles { .. } => Ok(()),
 
             Effect::ConnectMcpServer { config, .. } => {
@@ -586,8 +888,9 @@ impl SessionActor {
                     .clone()
                     .map(MessageId::from);
                 let system_context = self.state.cached_system_context.clone();
-                tokio::spawn(async move {
+                let task = tokio::spawn(async move {
                     let mut compaction_messages = messages;
+                    let attachments = summarize_attachments(&mut compaction_messages);
                     let compaction_prompt = build_compaction_message();
                     let mut dropped_tool_results = 0usize;
 
@@ -641,6 +944,18 @@ impl SessionActor {
                                 })
                                 .collect::<Vec<_>>()
                                 .join("\n");
+                            let summary_text = if attachments.is_empty() {
+                                summary_text
+                            } else {
+                                format!(
+                                    "{summary_text}\n\nFiles the user attached earlier (contents not kept after compaction):\n{}",
+                                    attachments
+                                        .iter()
+                                        .map(|summary| format!("- {summary}"))
+                                        .collect::<Vec<_>>()
+                                        .join("\n")
+                                )
+                            };
 
                             Action::CompactionComplete {
                                 session_id,
@@ -669,6 +984,7 @@ impl SessionActor {
 
                     let _ = action_tx.send(action).await;
                 });
+                self.track_task(op_id, &task);
 
                 Ok(())
             }
@@ -810,6 +1126,116 @@ impl SessionActor {
             .ok();
     }
 
+    /// Environment for tools run on behalf of this session.
+    fn tool_env(&self) -> Arc<ToolEnv> {
+        Arc::new(
+            self.state
+                .session_config
+                .as_ref()
+                .map(|config| {
+                    let denylist = CommandDenylist::new(&config.tool_config.bash_denylist)
+                        .unwrap_or_else(|error| {
+                            tracing::warn!(
+                                session_id = %self.session_id,
+                                %error,
+                                "Refusing all bash commands: the session's denylist is invalid"
+                            );
+                            CommandDenylist::refusing_all(error)
+                        });
+                    ToolEnv::resolve(&config.tool_config.env)
+                        .with_denylist(denylist)
+                        .with_network(config.tool_config.allow_network)
+                        .with_outside_workspace(config.tool_config.allow_outside_workspace)
+                })
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Count the request with the provider's tokenizer in the background,
+    /// when it can and the session's calibration for `model` has gone stale.
+    /// The result comes back as [`Action::TokenCountCalibrated`]; the count
+    /// is abandoned when `cancel_token` is cancelled with the operation.
+    fn spawn_token_count(
+        &self,
+        model: &ModelId,
+        messages: &[Message],
+        system_context: Option<&SystemContext>,
+        tools: &[steer_tools::ToolSchema],
+        cancel_token: CancellationToken,
+    ) {
+        if self
+            .token_count_in_flight
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            return;
+        }
+
+        let in_flight = self.token_count_in_flight.clone();
+        let calibration = self.state.token_calibrations.get(model).copied();
+        let interpreter = self.interpreter.clone();
+        let action_tx = self.internal_action_tx.clone();
+        let session_id = self.session_id;
+        let model = model.clone();
+        let messages = messages.to_vec();
+        let system_context = system_context.cloned();
+        let tools = tools.to_vec();
+
+        tokio::spawn(async move {
+            let action = count_request_tokens(
+                &interpreter,
+                session_id,
+                model,
+                messages,
+                system_context,
+                tools,
+                calibration,
+                cancel_token,
+            )
+            .await;
+            if let Some(action) = action {
+                let _ = action_tx.send(action).await;
+            }
+            in_flight.store(false, Ordering::Release);
+        });
+    }
+
+    /// Attach the current session memory. It lives outside the event log and
+    /// can change mid-operation, so the cached context may be stale.
+    async fn with_session_memory(
+        &self,
+        system_context: Option<SystemContext>,
+    ) -> Option<SystemContext> {
+        let memory = match self.event_store.load_memory(self.session_id).await {
+            Ok(memory) => memory,
+            Err(e) => {
+                tracing::warn!(
+                    session_id = %self.session_id,
+                    error = %e,
+                    "Failed to load session memory"
+                );
+                return system_context;
+            }
+        };
+        if memory.is_empty() {
+            return system_context;
+        }
+
+        let mut context = system_context.unwrap_or_else(|| SystemContext::new(String::new()));
+        context.memory = memory;
+        Some(context)
+    }
+
+    fn publish(&self, envelope: SessionEventEnvelope) {
+        if let Some(runtime_events) = &self.runtime_events {
+            let _ = runtime_events.send(RuntimeEvent {
+                session_id: self.session_id,
+                envelope: envelope.clone(),
+            });
+        }
+        let _ = self.event_broadcast.send(envelope);
+    }
+
     fn create_subscription(&mut self) -> SessionEventSubscription {
         self.subscriber_count += 1;
         tracing::debug!(
@@ -822,6 +1248,146 @@ impl SessionActor {
         SessionEventSubscription::new(self.session_id, rx, self.unsubscribe_tx.clone())
     }
 
+    /// Cancels the running operation through the reducer so the cancellation
+    /// is persisted like one the user asked for.
+    async fn cancel_current_operation(&mut self) {
+        let Some(op_id) = self.state.current_operation.as_ref().map(|op| op.op_id) else {
+            return;
+        };
+        if let Err(e) = self
+            .handle_action(Action::Cancel {
+                session_id: self.session_id,
+                op_id: Some(op_id),
+            })
+            .await
+        {
+            tracing::error!(
+                session_id = %self.session_id,
+                op_id = %op_id,
+                error = %e,
+                "Failed to cancel operation while draining"
+            );
+        }
+    }
+
+    fn track_task(&mut self, op_id: OpId, task: &JoinHandle<()>) {
+        self.operation_tasks
+            .entry(op_id)
+            .or_default()
+            .push(task.abort_handle());
+    }
+
+    fn track_operation_lifecycle(&mut self, event: &SessionEvent) {
+        match event {
+            SessionEvent::OperationStarted { op_id, .. } => {
+                self.operation_started_at.insert(*op_id, Utc::now());
+                self.operation_timers.started(*op_id);
+            }
+            SessionEvent::OperationCompleted { op_id, .. }
+            | SessionEvent::OperationCancelled { op_id, .. } => {
+                self.operation_started_at.remove(op_id);
+                self.operation_tasks.remove(op_id);
+                self.operation_timers.discard(*op_id);
+            }
+            SessionEvent::QueueUpdated { queue } => {
+                for item in queue {
+                    self.operation_timers.queued(item.op_id);
+                }
+            }
+            SessionEvent::ApprovalDecided { .. } => {
+                if let Some(op) = &self.state.current_operation {
+                    self.operation_timers.approval_decided(op.op_id);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn active_operation(&self) -> Option<ActiveOperation> {
+        let op = self.state.current_operation.as_ref()?;
+        let current_tool = op.pending_tool_calls.iter().next().map(|tool_call_id| {
+            self.state
+                .message_graph
+                .find_tool_name_by_id(tool_call_id.as_str())
+                .unwrap_or_else(|| tool_call_id.as_str().to_string())
+        });
+        let phase = if self.state.pending_approval.is_some() {
+            OperationPhase::AwaitingApproval
+        } else if current_tool.is_some() {
+            OperationPhase::RunningTool
+        } else {
+            match op.kind {
+                OperationKind::AgentLoop => OperationPhase::CallingModel,
+                OperationKind::Compact { .. } => OperationPhase::Compacting,
+                OperationKind::DirectBash { .. } => OperationPhase::RunningBash,
+            }
+        };
+
+        Some(ActiveOperation {
+            session_id: self.session_id,
+            op_id: op.op_id,
+            phase,
+            started_at: self.operation_started_at.get(&op.op_id).copied(),
+            current_tool,
+            model: self.state.operation_models.get(&op.op_id).cloned(),
+        })
+    }
+
+    fn idle_since(&self) -> Option<Instant> {
+        if self.subscriber_count > 0 {
+            return None;
+        }
+        let busy = self
+            .active_operation()
+            .is_some_and(|op| op.phase != OperationPhase::AwaitingApproval);
+        (!busy).then_some(self.last_activity)
+    }
+
+    /// Cancels the running operation through the reducer, exactly like a user
+    /// cancel. `force` additionally aborts the operation's tasks instead of
+    /// waiting for them to notice the cancellation token.
+    async fn cancel_operation(
+        &mut self,
+        op_id: Option<OpId>,
+        force: bool,
+    ) -> Result<bool, SessionError> {
+        let Some(target) = self
+            .state
+            .current_operation
+            .as_ref()
+            .map(|op| op.op_id)
+            .filter(|current| op_id.is_none_or(|id| id == *current))
+        else {
+            return Ok(false);
+        };
+
+        let tasks = if force {
+            self.operation_tasks.remove(&target).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        self.handle_action(Action::Cancel {
+            session_id: self.session_id,
+            op_id: Some(target),
+        })
+        .await?;
+
+        if !tasks.is_empty() {
+            tracing::warn!(
+                session_id = %self.session_id,
+                op_id = %target,
+                tasks = tasks.len(),
+                "Force-cancelled operation; aborting its tasks"
+            );
+        }
+        for task in tasks {
+            task.abort();
+        }
+
+        Ok(true)
+    }
+
     fn cancel_all_operations(&mut self) {
         for (_, token) in self.active_operations.drain() {
             token.cancel();
@@ -839,10 +1405,12 @@ pub(crate) fn spawn_session_actor(
     event_store: Arc<dyn EventStore>,
     api_client: Arc<ApiClient>,
     tool_executor: Arc<ToolExecutor>,
+    runtime_events: broadcast::Sender<RuntimeEvent>,
 ) -> SessionActorHandle {
     let (cmd_tx, cmd_rx) = mpsc::channel(32);
 
-    let actor = SessionActor::new(session_id, state, event_store, api_client, tool_executor);
+    let mut actor = SessionActor::new(session_id, state, event_store, api_client, tool_executor);
+    actor.runtime_events = Some(runtime_events);
 
     tokio::spawn(actor.run(cmd_rx));
 
@@ -856,6 +1424,149 @@ fn current_timestamp() -> u64 {
         .as_secs()
 }
 
+/// Ask `model` for a short summary of `messages`. Providers without
+/// credentials are skipped rather than called.
+pub(super) async fn generate_session_summary(
+    interpreter: &EffectInterpreter,
+    model: ModelId,
+    messages: &[Message],
+    cancel_token: CancellationToken,
+) -> Result<String, SessionSummaryError> {
+    if !interpreter.has_credentials(&model).await {
+        return Err(SessionSummaryError::NotAuthenticated {
+            provider: model.provider.to_string(),
+        });
+    }
+
+    let request = vec![Message {
+        data: MessageData::User {
+            content: vec![UserContent::Text {
+                text: session_summary_prompt(messages),
+            }],
+        },
+        timestamp: current_timestamp(),
+        id: uuid::Uuid::new_v4().to_string(),
+        parent_message_id: None,
+    }];
+    let response = interpreter
+        .call_model(model, request, None, vec![], cancel_token)
+        .await?;
+    Ok(response.extract_text().trim().to_string())
+}
+
+/// Count a model request with the provider's tokenizer, unless the provider
+/// cannot or `calibration` still matches our estimate of the request.
+#[expect(
+    clippy::too_many_arguments,
+    reason = "Takes the counted request's parts plus calibration and cancellation"
+)]
+async fn count_request_tokens(
+    interpreter: &EffectInterpreter,
+    session_id: SessionId,
+    model: ModelId,
+    messages: Vec<Message>,
+    system_context: Option<SystemContext>,
+    tools: Vec<steer_tools::ToolSchema>,
+    calibration: Option<TokenCalibration>,
+    cancel_token: CancellationToken,
+) -> Option<Action> {
+    if !interpreter.supports_token_counting(&model).await {
+        return None;
+    }
+
+    let estimated = estimate_request_tokens(
+        TokenCounter::for_model(&model),
+        &messages,
+        system_context.as_ref(),
+        &tools,
+    );
+    if calibration.is_some_and(|calibration| !calibration.needs_refresh(estimated)) {
+        return None;
+    }
+
+    match interpreter
+        .count_tokens(&model, messages, system_context, tools, cancel_token)
+        .await
+    {
+        Ok(counted) => counted.map(|counted| Action::TokenCountCalibrated {
+            session_id,
+            model,
+            counted,
+            estimated,
+        }),
+        Err(error) => {
+            tracing::debug!(
+                session_id = %session_id,
+                error = %error,
+                "Token count failed; keeping the current estimate"
+            );
+            None
+        }
+    }
+}
+
+/// Build the summarization request: an instruction followed by a plain-text
+/// transcript of the most recent messages that fit.
+fn session_summary_prompt(messages: &[Message]) -> String {
+    let mut entries = Vec::new();
+    let mut total = 0;
+    for message in messages.iter().rev() {
+        let (role, parts): (&str, Vec<String>) = match &message.data {
+            MessageData::User { content } => (
+                "User",
+                content
+                    .iter()
+                    .filter_map(|item| match item {
+                        UserContent::Text { text } => Some(text.trim().to_string()),
+                        UserContent::CommandExecution { command, .. } => {
+                            Some(format!("(ran `{}`)", command.trim()))
+                        }
+                        UserContent::Attachment { attachment } => {
+                            Some(format!("(attached {})", attachment.summary()))
+                        }
+                        UserContent::Image { .. } => None,
+                    })
+                    .collect(),
+            ),
+            MessageData::Assistant { content } => (
+                "Assistant",
+                content
+                    .iter()
+                    .filter_map(|item| match item {
+                        AssistantContent::Text { text } => Some(text.trim().to_string()),
+                        AssistantContent::ToolCall { tool_call, .. } => {
+                            Some(format!("(used {})", tool_call.name))
+                        }
+                        AssistantContent::Image { .. } | AssistantContent::Thought { .. } => None,
+                    })
+                    .collect(),
+            ),
+            MessageData::Tool { .. } => continue,
+        };
+        let text = parts
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if text.is_empty() {
+            continue;
+        }
+
+        let excerpt: String = text.chars().take(SUMMARY_MESSAGE_MAX_CHARS).collect();
+        total += excerpt.len();
+        if total > SUMMARY_TRANSCRIPT_MAX_CHARS && !entries.is_empty() {
+            break;
+        }
+        entries.push(format!("{role}: {excerpt}"));
+    }
+    entries.reverse();
+
+    format!(
+        "Summarize this conversation in one paragraph of at most five sentences for someone picking it up later: the goal, what has been done, and what remains. Return only the summary.\n\nConversation:\n{}",
+        entries.join("\n\n")
+    )
+}
+
 fn is_context_window_exceeded_error(error: &ModelCallError) -> bool {
     let normalized = error.to_string().to_ascii_lowercase();
 
@@ -881,6 +1592,27 @@ fn is_context_window_exceeded_error(error: &ModelCallError) -> bool {
                 || normalized.contains("limit")))
 }
 
+/// Replaces attached files with a one-line summary by name and size, so the
+/// compaction request stays small, and returns those summaries in order.
+fn summarize_attachments(messages: &mut [Message]) -> Vec<String> {
+    let mut summaries = Vec::new();
+    for message in messages {
+        let MessageData::User { content } = &mut message.data else {
+            continue;
+        };
+        for block in content.iter_mut() {
+            if let UserContent::Attachment { attachment } = block {
+                let summary = attachment.summary();
+                *block = UserContent::Text {
+                    text: format!("[Attached file: {summary}]"),
+                };
+                summaries.push(summary);
+            }
+        }
+    }
+    summaries
+}
+
 fn drop_earlier_tool_results(messages: &mut Vec<crate::app::conversation::Message>) -> usize {
     let dropped_stale_reads = drop_stale_read_file_results(messages);
     if dropped_stale_reads > 0 {
@@ -1164,9 +1896,26 @@ mod tests {
         release_rx: Arc<tokio::sync::Mutex<Option<tokio::sync::oneshot::Receiver<()>>>>,
     }
 
+    /// Never answers and ignores cancellation, like a wedged remote call.
     #[derive(Clone)]
-    struct ContextWindowLimitProvider {
-        max_tool_messages: usize,
+    struct HangingProvider {
+        in_flight: Arc<()>,
+    }
+
+    /// Answers every request and counts tokens as a fixed number.
+    #[derive(Clone)]
+    struct TokenCountingProvider {
+        counted: u32,
+    }
+
+    /// Answers every request but only finishes a token count once it is
+    /// cancelled.
+    #[derive(Clone)]
+    struct HangingTokenCountProvider;
+
+    #[derive(Clone)]
+    struct ContextWindowLimitProvider {
+        max_tool_messages: usize,
         observed_tool_message_counts: Arc<std::sync::Mutex<Vec<usize>>>,
     }
 
@@ -1175,6 +1924,87 @@ mod tests {
         file_path: String,
         overflow_char_limit: usize,
     }
+    #[async_trait]
+    impl Provider for HangingTokenCountProvider {
+        fn name(&self) -> &'static str {
+            "hanging-token-count"
+        }
+
+        async fn complete(
+            &self,
+            _model_id: &ModelId,
+            _messages: Vec<Message>,
+            _system: Option<SystemContext>,
+            _tools: Option<Vec<ToolSchema>>,
+            _call_options: Option<ModelParameters>,
+            _token: CancellationToken,
+        ) -> Result<CompletionResponse, ApiError> {
+            Ok(CompletionResponse {
+                content: vec![AssistantContent::Text {
+                    text: "reply".to_string(),
+                }],
+                usage: None,
+            })
+        }
+
+        fn supports_token_counting(&self) -> bool {
+            true
+        }
+
+        async fn count_tokens(
+            &self,
+            _model_id: &ModelId,
+            _messages: Vec<Message>,
+            _system: Option<SystemContext>,
+            _tools: Option<Vec<ToolSchema>>,
+            token: CancellationToken,
+        ) -> Result<u32, ApiError> {
+            token.cancelled().await;
+            Err(ApiError::Cancelled {
+                provider: self.name().to_string(),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl Provider for TokenCountingProvider {
+        fn name(&self) -> &'static str {
+            "token-counting"
+        }
+
+        async fn complete(
+            &self,
+            _model_id: &ModelId,
+            _messages: Vec<Message>,
+            _system: Option<SystemContext>,
+            _tools: Option<Vec<ToolSchema>>,
+            _call_options: Option<ModelParameters>,
+            _token: CancellationToken,
+        ) -> Result<CompletionResponse, ApiError> {
+            Ok(CompletionResponse {
+                content: vec![AssistantContent::Text {
+                    text: "reply".to_string(),
+                }],
+                usage: None,
+            })
+        }
+
+        fn supports_token_counting(&self) -> bool {
+            true
+        }
+
+        async fn count_tokens(
+            &self,
+            _model_id: &ModelId,
+            _messages: Vec<Message>,
+            _system: Option<SystemContext>,
+            _tools: Option<Vec<ToolSchema>>,
+            _token: CancellationToken,
+        ) -> Result<u32, ApiError> {
+            Ok(self.counted)
+        }
+    }
+
     #[async_trait]
     impl Provider for BlockingStreamProvider {
         fn name(&self) -> &'static str {
@@ -1231,6 +2061,26 @@ mod tests {
         }
     }
 
+    #[async_trait]
+    impl Provider for HangingProvider {
+        fn name(&self) -> &'static str {
+            "hanging"
+        }
+
+        async fn complete(
+            &self,
+            _model_id: &ModelId,
+            _messages: Vec<Message>,
+            _system: Option<SystemContext>,
+            _tools: Option<Vec<ToolSchema>>,
+            _call_options: Option<ModelParameters>,
+            _token: CancellationToken,
+        ) -> Result<CompletionResponse, ApiError> {
+            let _in_flight = self.in_flight.clone();
+            std::future::pending().await
+        }
+    }
+
     #[async_trait]
     impl Provider for StubProvider {
         fn name(&self) -> &'static str {
@@ -1407,6 +2257,7 @@ mod tests {
         let workspace =
             crate::workspace::create_workspace(&crate::workspace::WorkspaceConfig::Local {
                 path: std::env::current_dir().expect("current dir"),
+                context_limits: Default::default(),
             })
             .await
             .expect("create workspace");
@@ -1599,7 +2450,7 @@ mod tests {
                 if events.iter().any(|(_, event)| {
                     matches!(
                         event,
-                        SessionEvent::OperationCompleted { op_id: completed } if *completed == op_id
+                        SessionEvent::OperationCompleted { op_id: completed, .. } if *completed == op_id
                     )
                 }) {
                     return;
@@ -1665,6 +2516,123 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn call_model_effect_calibrates_estimates_with_provider_token_count() {
+        let session_id = SessionId::new();
+        let mut state = AppState::new(session_id);
+        seed_messages(&mut state);
+        let messages = state
+            .message_graph
+            .get_thread_messages()
+            .into_iter()
+            .cloned()
+            .collect();
+        let (event_store, api_client, tool_executor) = create_test_deps().await;
+
+        let model_id = ModelId::new(ProviderId("token-counting".to_string()), "counting-model");
+        api_client.insert_test_provider(
+            model_id.provider.clone(),
+            Arc::new(TokenCountingProvider { counted: 42 }),
+        );
+
+        let mut actor =
+            SessionActor::new(session_id, state, event_store, api_client, tool_executor);
+
+        actor
+            .handle_effect(Effect::CallModel {
+                session_id,
+                op_id: OpId::new(),
+                model: model_id.clone(),
+                messages,
+                system_context: Some(SystemContext::new("be brief".to_string())),
+                tools: vec![],
+            })
+            .await
+            .expect("call model effect should succeed");
+
+        let calibration = timeout(Duration::from_secs(2), async {
+            loop {
+                match actor.internal_action_rx.recv().await {
+                    Some(action @ Action::TokenCountCalibrated { .. }) => return action,
+                    Some(_) => {}
+                    None => panic!("internal action channel closed"),
+                }
+            }
+        })
+        .await
+        .expect("timed out waiting for token count");
+
+        match &calibration {
+            Action::TokenCountCalibrated {
+                model,
+                counted,
+                estimated,
+                ..
+            } => {
+                assert_eq!(model, &model_id);
+                assert_eq!(*counted, 42);
+                assert!(*estimated > 0);
+            }
+            other => panic!("expected TokenCountCalibrated, got {other:?}"),
+        }
+
+        actor
+            .handle_action(calibration)
+            .await
+            .expect("calibration action should reduce");
+        assert!(actor.state.token_calibrations.contains_key(&model_id));
+    }
+
+    #[tokio::test]
+    async fn token_count_is_cancelled_with_its_operation() {
+        let session_id = SessionId::new();
+        let mut state = AppState::new(session_id);
+        seed_messages(&mut state);
+        let messages = state
+            .message_graph
+            .get_thread_messages()
+            .into_iter()
+            .cloned()
+            .collect();
+        let (event_store, api_client, tool_executor) = create_test_deps().await;
+
+        let model_id = ModelId::new(ProviderId("hanging-token-count".to_string()), "model");
+        api_client.insert_test_provider(
+            model_id.provider.clone(),
+            Arc::new(HangingTokenCountProvider),
+        );
+
+        let mut actor =
+            SessionActor::new(session_id, state, event_store, api_client, tool_executor);
+        let op_id = OpId::new();
+
+        actor
+            .handle_effect(Effect::CallModel {
+                session_id,
+                op_id,
+                model: model_id,
+                messages,
+                system_context: None,
+                tools: vec![],
+            })
+            .await
+            .expect("call model effect should succeed");
+        assert!(actor.token_count_in_flight.load(Ordering::Acquire));
+
+        actor
+            .handle_effect(Effect::CancelOperation { session_id, op_id })
+            .await
+            .expect("cancel effect should succeed");
+
+        timeout(Duration::from_secs(2), async {
+            while actor.token_count_in_flight.load(Ordering::Acquire) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("token count was not cancelled with the operation");
+    }
+
     #[tokio::test]
     async fn session_title_generation_uses_operation_cancel_token() {
         let session_id = SessionId::new();
@@ -1687,6 +2655,9 @@ mod tests {
             metadata: std::collections::HashMap::new(),
             default_model: model_id.clone(),
             auto_compaction: crate::session::state::AutoCompactionConfig::default(),
+            auto_summary: crate::session::state::AutoSummaryConfig::default(),
+            isolation: crate::isolation::IsolationMode::None,
+            isolated_workspace: None,
         });
 
         let (event_store, api_client, tool_executor) = create_test_deps().await;
@@ -1735,6 +2706,141 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn session_summary_is_cancelled_when_the_actor_stops() {
+        let session_id = SessionId::new();
+        let mut state = AppState::new(session_id);
+        seed_messages(&mut state);
+        let model_id = ModelId::new(ProviderId("blocking-stream".to_string()), "blocking-model");
+
+        let (event_store, api_client, tool_executor) = create_test_deps().await;
+        let (release_tx, release_rx) = tokio::sync::oneshot::channel();
+        api_client.insert_test_provider(
+            model_id.provider.clone(),
+            Arc::new(BlockingStreamProvider {
+                release_rx: Arc::new(tokio::sync::Mutex::new(Some(release_rx))),
+            }),
+        );
+
+        let mut actor =
+            SessionActor::new(session_id, state, event_store, api_client, tool_executor);
+        let messages = actor
+            .state
+            .message_graph
+            .get_thread_messages()
+            .into_iter()
+            .cloned()
+            .collect();
+
+        actor
+            .handle_effect(Effect::GenerateSessionSummary {
+                session_id,
+                model: model_id,
+                messages,
+            })
+            .await
+            .expect("summary effect should dispatch");
+
+        actor.shutdown_token.cancel();
+        let _ = release_tx.send(());
+
+        let action = timeout(Duration::from_secs(2), actor.internal_action_rx.recv())
+            .await
+            .expect("timed out waiting for summary action")
+            .expect("expected summary action");
+
+        assert!(matches!(
+            action,
+            Action::SessionSummaryGenerationFailed { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn force_cancel_aborts_hung_operation_and_cancels_it_like_a_user() {
+        let session_id = SessionId::new();
+        let mut state = AppState::new(session_id);
+        seed_messages(&mut state);
+
+        let (event_store, api_client, tool_executor) = create_test_deps().await;
+        let provider_id = ProviderId("hanging".to_string());
+        let model_id = ModelId::new(provider_id.clone(), "hanging-model");
+        let in_flight = Arc::new(());
+        api_client.insert_test_provider(
+            provider_id,
+            Arc::new(HangingProvider {
+                in_flight: in_flight.clone(),
+            }),
+        );
+        let idle = Arc::strong_count(&in_flight);
+
+        let mut actor = SessionActor::new(
+            session_id,
+            state,
+            event_store.clone(),
+            api_client,
+            tool_executor,
+        );
+        let op_id = OpId::new();
+        actor
+            .handle_action(Action::RequestCompaction {
+                session_id,
+                op_id,
+                model: model_id,
+            })
+            .await
+            .expect("dispatch compaction");
+
+        timeout(Duration::from_secs(2), async {
+            while Arc::strong_count(&in_flight) == idle {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("model call should start");
+
+        let active = actor
+            .active_operation()
+            .expect("operation should be active");
+        assert_eq!(active.op_id, op_id);
+        assert_eq!(active.phase, OperationPhase::Compacting);
+        assert!(active.started_at.is_some());
+
+        assert!(
+            !actor
+                .cancel_operation(Some(OpId::new()), true)
+                .await
+                .expect("cancel other op")
+        );
+        assert!(
+            actor
+                .cancel_operation(Some(op_id), true)
+                .await
+                .expect("force cancel")
+        );
+        assert!(actor.active_operation().is_none());
+
+        timeout(Duration::from_secs(2), async {
+            while Arc::strong_count(&in_flight) > idle {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("hung model call should be aborted");
+
+        let events = event_store.load_events(session_id).await.expect("events");
+        assert!(events.iter().any(|(_, event)| matches!(
+            event,
+            SessionEvent::OperationCancelled { op_id: cancelled, .. } if *cancelled == op_id
+        )));
+        assert!(events.iter().any(|(_, event)| matches!(
+            event,
+            SessionEvent::CompactResult {
+                result: CompactResult::Cancelled,
+                ..
+            }
+        )));
+    }
+
     #[tokio::test]
     async fn compaction_emits_operation_completed_event() {
         let session_id = SessionId::new();
@@ -1752,6 +2858,7 @@ mod tests {
             event_store.clone(),
             api_client,
             tool_executor,
+            broadcast::channel(16).0,
         );
 
         let op_id = OpId::new();
@@ -1773,6 +2880,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn session_summary_prompt_keeps_conversation_and_skips_tool_output() {
+        let messages = vec![
+            Message {
+                data: MessageData::User {
+                    content: vec![UserContent::Text {
+                        text: "Where is the config loaded?".to_string(),
+                    }],
+                },
+                id: "u-1".to_string(),
+                parent_message_id: None,
+                timestamp: 1,
+            },
+            Message {
+                data: MessageData::Assistant {
+                    content: vec![AssistantContent::ToolCall {
+                        tool_call: ToolCall {
+                            name: "grep".to_string(),
+                            parameters: json!({"pattern": "load_config"}),
+                            id: "grep-1".to_string(),
+                        },
+                        thought_signature: None,
+                    }],
+                },
+                id: "a-1".to_string(),
+                parent_message_id: Some("u-1".to_string()),
+                timestamp: 2,
+            },
+            Message {
+                data: MessageData::Tool {
+                    tool_use_id: "grep-1".to_string(),
+                    result: crate::app::conversation::ToolResult::External(ExternalResult {
+                        tool_name: "grep".to_string(),
+                        payload: "secret tool output".to_string(),
+                    }),
+                },
+                id: "t-1".to_string(),
+                parent_message_id: Some("a-1".to_string()),
+                timestamp: 3,
+            },
+            Message {
+                data: MessageData::Assistant {
+                    content: vec![AssistantContent::Text {
+                        text: "It is loaded in session_config.rs.".to_string(),
+                    }],
+                },
+                id: "a-2".to_string(),
+                parent_message_id: Some("t-1".to_string()),
+                timestamp: 4,
+            },
+        ];
+
+        let prompt = session_summary_prompt(&messages);
+
+        let transcript = prompt
+            .split_once("Conversation:\n")
+            .map(|(_, transcript)| transcript)
+            .expect("transcript");
+        assert_eq!(
+            transcript,
+            "User: Where is the config loaded?\n\nAssistant: (used grep)\n\nAssistant: It is loaded in session_config.rs."
+        );
+    }
+
     #[test]
     fn drop_stale_read_file_results_drops_old_reads_and_keeps_latest_per_file() {
         let messages = vec![
@@ -1799,6 +2970,10 @@ mod tests {
                         file_path: "/tmp/a.rs".to_string(),
                         line_count: 50,
                         truncated: true,
+                        encoding: None,
+                        binary_kind: None,
+                        file_size: None,
+                        warnings: Vec::new(),
                     }),
                 },
                 id: "t-read-1".to_string(),
@@ -1828,6 +3003,10 @@ mod tests {
                         file_path: "/tmp/a.rs".to_string(),
                         line_count: 50,
                         truncated: false,
+                        encoding: None,
+                        binary_kind: None,
+                        file_size: None,
+                        warnings: Vec::new(),
                     }),
                 },
                 id: "t-read-2".to_string(),
@@ -1862,6 +3041,7 @@ mod tests {
                         file_created: false,
                         old_content: None,
                         new_content: None,
+                        skipped_edits: Vec::new(),
                     }),
                 },
                 id: "t-edit".to_string(),
@@ -1891,6 +3071,10 @@ mod tests {
                         file_path: "/tmp/a.rs".to_string(),
                         line_count: 200,
                         truncated: false,
+                        encoding: None,
+                        binary_kind: None,
+                        file_size: None,
+                        warnings: Vec::new(),
                     }),
                 },
                 id: "t-read-3".to_string(),
@@ -1965,6 +3149,10 @@ mod tests {
                         file_path: "/tmp/a.rs".to_string(),
                         line_count: 1,
                         truncated: false,
+                        encoding: None,
+                        binary_kind: None,
+                        file_size: None,
+                        warnings: Vec::new(),
                     }),
                 },
                 id: "tool-1".to_string(),
@@ -2013,6 +3201,7 @@ mod tests {
         let workspace =
             crate::workspace::create_workspace(&crate::workspace::WorkspaceConfig::Local {
                 path: temp_dir.path().to_path_buf(),
+                context_limits: Default::default(),
             })
             .await
             .expect("create test workspace");
@@ -2033,6 +3222,7 @@ mod tests {
             event_store.clone(),
             api_client,
             tool_executor,
+            broadcast::channel(16).0,
         );
 
         let user_op_id = OpId::new();
@@ -2093,6 +3283,7 @@ mod tests {
             event_store.clone(),
             api_client,
             tool_executor,
+            broadcast::channel(16).0,
         );
 
         let op_id = OpId::new();
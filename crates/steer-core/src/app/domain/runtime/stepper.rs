@@ -47,6 +47,7 @@ pub enum AgentInput {
     },
     ToolDenied {
         tool_call_id: ToolCallId,
+        reason: Option<String>,
     },
     ToolCompleted {
         tool_call_id: ToolCallId,
@@ -160,13 +161,17 @@ impl AgentStepper {
                     approved,
                     denied,
                 },
-                AgentInput::ToolDenied { tool_call_id },
+                AgentInput::ToolDenied {
+                    tool_call_id,
+                    reason,
+                },
             ) => Self::handle_tool_denied(
                 messages,
                 pending_approvals,
                 approved,
                 denied,
                 tool_call_id,
+                reason,
             ),
 
             (
@@ -331,6 +336,7 @@ impl AgentStepper {
         approved: Vec<ToolCall>,
         mut denied: Vec<ToolCall>,
         tool_call_id: ToolCallId,
+        reason: Option<String>,
     ) -> (AgentState, Vec<AgentOutput>) {
         let mut outputs = vec![];
 
@@ -339,12 +345,8 @@ impl AgentStepper {
             .position(|tc| tc.id == tool_call_id.0)
         {
             let tool_call = pending_approvals.remove(pos);
-            Self::emit_tool_error_message(
-                &mut messages,
-                &mut outputs,
-                &tool_call,
-                ToolError::DeniedByUser(tool_call.name.clone()),
-            );
+            let error = ToolError::approval_denied(tool_call.name.clone(), reason);
+            Self::emit_tool_error_message(&mut messages, &mut outputs, &tool_call, error);
             denied.push(tool_call);
         }
 
@@ -625,6 +627,7 @@ mod tests {
             state,
             AgentInput::ToolDenied {
                 tool_call_id: ToolCallId::from_string("tc_1"),
+                reason: None,
             },
         );
 
@@ -639,7 +642,11 @@ mod tests {
         match &tool_message.data {
             MessageData::Tool { result, .. } => match result {
                 ToolResult::Error(error) => {
-                    assert!(matches!(error, ToolError::DeniedByUser(name) if name == "test_tool"));
+                    assert!(matches!(
+                        error,
+                        ToolError::ApprovalDenied { tool_name, reason: None }
+                            if tool_name == "test_tool"
+                    ));
                 }
                 _ => panic!("expected denied tool error"),
             },
@@ -647,6 +654,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tool_denied_with_reason_reports_reason_to_model() {
+        let stepper = AgentStepper::new(test_config());
+        let tool_call = ToolCall {
+            id: "tc_1".to_string(),
+            name: "test_tool".to_string(),
+            parameters: serde_json::json!({}),
+        };
+
+        let state = AgentState::AwaitingToolApprovals {
+            messages: vec![],
+            pending_approvals: vec![tool_call],
+            approved: vec![],
+            denied: vec![],
+        };
+
+        let (_new_state, outputs) = stepper.step(
+            state,
+            AgentInput::ToolDenied {
+                tool_call_id: ToolCallId::from_string("tc_1"),
+                reason: Some("use the staging database".to_string()),
+            },
+        );
+
+        let tool_message = outputs
+            .iter()
+            .find_map(|output| match output {
+                AgentOutput::EmitMessage { message } => Some(message),
+                _ => None,
+            })
+            .expect("tool denial should emit a tool result message");
+
+        match &tool_message.data {
+            MessageData::Tool {
+                result: ToolResult::Error(error),
+                ..
+            } => {
+                assert!(matches!(
+                    error,
+                    ToolError::ApprovalDenied { tool_name, reason: Some(reason) }
+                        if tool_name == "test_tool" && reason == "use the staging database"
+                ));
+            }
+            _ => panic!("expected denied tool error"),
+        }
+    }
+
     #[test]
     fn test_cancel_emits_tool_results_for_pending_approvals() {
         let stepper = AgentStepper::new(test_config());
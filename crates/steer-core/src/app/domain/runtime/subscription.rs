@@ -9,6 +9,13 @@ pub struct SessionEventEnvelope {
     pub event: SessionEvent,
 }
 
+/// An event from any session, as seen by runtime-wide subscribers.
+#[derive(Debug, Clone)]
+pub struct RuntimeEvent {
+    pub session_id: SessionId,
+    pub envelope: SessionEventEnvelope,
+}
+
 pub struct SessionEventSubscription {
     pub session_id: SessionId,
     pub rx: broadcast::Receiver<SessionEventEnvelope>,
@@ -1,29 +1,67 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use thiserror::Error;
 use tokio::sync::{broadcast, mpsc, oneshot};
 use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 
 use crate::api::Client as ApiClient;
-use crate::app::conversation::UserContent;
-use crate::app::domain::action::Action;
+use crate::api::error::ApiError;
+use crate::app::conversation::{MessageData, UserContent};
+use crate::app::domain::action::{Action, ModelSwitchMode, SessionSummaryError};
 use crate::app::domain::delta::StreamDelta;
 use crate::app::domain::event::SessionEvent;
-use crate::app::domain::reduce::apply_event_to_state;
+use crate::app::domain::reduce::{
+    InterruptedOperation, apply_event_to_state, restore_interrupted_operation,
+};
 use crate::app::domain::session::EventStore;
 use crate::app::domain::state::AppState;
 use crate::app::domain::types::{MessageId, OpId, RequestId, SessionId};
 
+use crate::commit::{self, CommitDraft, CommitError, CommitOutcome};
 use crate::config::model::ModelId;
+use crate::isolation::{self, IsolatedWorkspace, IsolationError, IsolationMode};
+use crate::metrics;
 use crate::primary_agents::{default_primary_agent_id, resolve_effective_config};
+use crate::profiling::{ProfilePhase, time_phase};
 use crate::prompts::system_prompt_for_model;
-use crate::session::state::SessionConfig;
-use crate::tools::ToolExecutor;
+use crate::review::{self, ReviewError, ReviewReport, ReviewTarget};
+use crate::session::state::{SessionConfig, WorkspaceConfig};
+use crate::tools::model_caller_impl::DefaultModelCaller;
+use crate::tools::{CommandDenylist, ToolExecutor};
+use crate::utils::paths::AppPaths;
+use steer_tools::tools::memory::MemoryEntry;
 use tracing::warn;
 
-use super::session_actor::{SessionActorHandle, SessionError, spawn_session_actor};
-use super::subscription::SessionEventSubscription;
+use super::interpreter::EffectInterpreter;
+use super::session_actor::{
+    ActiveOperation, SessionActorHandle, SessionError, generate_session_summary,
+    spawn_session_actor,
+};
+use super::subscription::{RuntimeEvent, SessionEventSubscription};
+
+const RUNTIME_EVENT_BROADCAST_CAPACITY: usize = 1024;
+/// Upper bound on how often idle sessions are looked for.
+const MAX_IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The user turn appended to a session before asking for structured output.
+const STRUCTURED_OUTPUT_REQUEST: &str =
+    "Give your final answer to my previous request as JSON matching the required schema.";
+
+/// Limits on how many sessions keep an actor loaded.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RuntimeConfig {
+    /// Suspend a session once it has had no subscribers and no running
+    /// operation for this long. It is resumed on its next request.
+    pub idle_timeout: Option<Duration>,
+    /// Most sessions that may be loaded at once. Suspended sessions don't
+    /// count; when the limit is reached the longest-idle session is suspended
+    /// to make room, or the request fails if every session is busy.
+    pub max_concurrent_sessions: Option<usize>,
+}
 
 #[derive(Debug, Error)]
 pub enum RuntimeError {
@@ -47,6 +85,24 @@ pub enum RuntimeError {
 
     #[error("Supervisor shutting down")]
     ShuttingDown,
+
+    #[error("Isolation error: {0}")]
+    Isolation(#[from] IsolationError),
+
+    #[error("Review error: {0}")]
+    Review(#[from] ReviewError),
+
+    #[error("Commit error: {0}")]
+    Commit(#[from] CommitError),
+
+    #[error("Summary error: {0}")]
+    Summary(#[from] SessionSummaryError),
+
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+
+    #[error("Too many active sessions (limit {limit})")]
+    SessionLimitReached { limit: usize },
 }
 
 impl From<SessionError> for RuntimeError {
@@ -104,6 +160,15 @@ pub(crate) enum SupervisorCmd {
     ListActiveSessions {
         reply: oneshot::Sender<Vec<SessionId>>,
     },
+    ListActiveOperations {
+        reply: oneshot::Sender<Vec<ActiveOperation>>,
+    },
+    CancelOperation {
+        session_id: SessionId,
+        op_id: Option<OpId>,
+        force: bool,
+        reply: oneshot::Sender<Result<bool, RuntimeError>>,
+    },
     ListAllSessions {
         reply: oneshot::Sender<Result<Vec<SessionId>, RuntimeError>>,
     },
@@ -111,6 +176,53 @@ pub(crate) enum SupervisorCmd {
         session_id: SessionId,
         reply: oneshot::Sender<Result<bool, RuntimeError>>,
     },
+    GetIsolatedWorkspace {
+        session_id: SessionId,
+        reply: oneshot::Sender<Result<Option<IsolatedWorkspace>, RuntimeError>>,
+    },
+    LoadMemory {
+        session_id: SessionId,
+        reply: oneshot::Sender<Result<Vec<MemoryEntry>, RuntimeError>>,
+    },
+    Remember {
+        session_id: SessionId,
+        entry: MemoryEntry,
+        reply: oneshot::Sender<Result<Vec<MemoryEntry>, RuntimeError>>,
+    },
+    Forget {
+        session_id: SessionId,
+        key: String,
+        reply: oneshot::Sender<Result<Vec<MemoryEntry>, RuntimeError>>,
+    },
+    Review {
+        session_id: SessionId,
+        target: ReviewTarget,
+        model: ModelId,
+        reply: oneshot::Sender<Result<ReviewReport, RuntimeError>>,
+    },
+    DraftCommit {
+        session_id: SessionId,
+        model: ModelId,
+        reply: oneshot::Sender<Result<CommitDraft, RuntimeError>>,
+    },
+    CreateCommit {
+        session_id: SessionId,
+        files: Vec<String>,
+        message: String,
+        reply: oneshot::Sender<Result<CommitOutcome, RuntimeError>>,
+    },
+    StructuredOutput {
+        session_id: SessionId,
+        model: ModelId,
+        schema: serde_json::Value,
+        cancel_token: CancellationToken,
+        reply: oneshot::Sender<Result<Option<serde_json::Value>, RuntimeError>>,
+    },
+    SummarizeSession {
+        session_id: SessionId,
+        reply: oneshot::Sender<Result<String, RuntimeError>>,
+    },
+    Drain,
     Shutdown,
 }
 
@@ -119,6 +231,11 @@ struct RuntimeSupervisor {
     event_store: Arc<dyn EventStore>,
     api_client: Arc<ApiClient>,
     tool_executor: Arc<ToolExecutor>,
+    runtime_events: broadcast::Sender<RuntimeEvent>,
+    config: RuntimeConfig,
+    /// Parent of the tokens given to work the supervisor spawns on a
+    /// caller's behalf; cancelled when the supervisor stops.
+    shutdown_token: CancellationToken,
 }
 
 impl RuntimeSupervisor {
@@ -126,18 +243,35 @@ impl RuntimeSupervisor {
         event_store: Arc<dyn EventStore>,
         api_client: Arc<ApiClient>,
         tool_executor: Arc<ToolExecutor>,
+        runtime_events: broadcast::Sender<RuntimeEvent>,
+        config: RuntimeConfig,
     ) -> Self {
         Self {
             sessions: HashMap::new(),
             event_store,
             api_client,
             tool_executor,
+            runtime_events,
+            config,
+            shutdown_token: CancellationToken::new(),
         }
     }
 
     async fn run(mut self, mut cmd_rx: mpsc::Receiver<SupervisorCmd>) {
+        let idle_check_interval = self
+            .config
+            .idle_timeout
+            .map_or(MAX_IDLE_CHECK_INTERVAL, |timeout| {
+                (timeout / 2).clamp(Duration::from_millis(1), MAX_IDLE_CHECK_INTERVAL)
+            });
+        let mut idle_check = tokio::time::interval(idle_check_interval);
+        idle_check.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
         loop {
             tokio::select! {
+                _ = idle_check.tick(), if self.config.idle_timeout.is_some() => {
+                    self.suspend_idle_sessions().await;
+                }
                 Some(cmd) = cmd_rx.recv() => {
                     match cmd {
                         SupervisorCmd::CreateSession { config, reply } => {
@@ -192,6 +326,19 @@ impl RuntimeSupervisor {
                             let sessions: Vec<SessionId> = self.sessions.keys().copied().collect();
                             let _ = reply.send(sessions);
                         }
+                        SupervisorCmd::ListActiveOperations { reply } => {
+                            let operations = self.list_active_operations().await;
+                            let _ = reply.send(operations);
+                        }
+                        SupervisorCmd::CancelOperation {
+                            session_id,
+                            op_id,
+                            force,
+                            reply,
+                        } => {
+                            let result = self.cancel_operation(session_id, op_id, force).await;
+                            let _ = reply.send(result);
+                        }
                         SupervisorCmd::ListAllSessions { reply } => {
                             let result = self.event_store.list_session_ids().await
                                 .map_err(RuntimeError::from);
@@ -202,6 +349,71 @@ impl RuntimeSupervisor {
                                 .map_err(RuntimeError::from);
                             let _ = reply.send(result);
                         }
+                        SupervisorCmd::GetIsolatedWorkspace { session_id, reply } => {
+                            let result =
+                                stored_isolated_workspace(&*self.event_store, session_id).await;
+                            let _ = reply.send(result);
+                        }
+                        SupervisorCmd::LoadMemory { session_id, reply } => {
+                            let result = self.load_memory(session_id).await;
+                            let _ = reply.send(result);
+                        }
+                        SupervisorCmd::Remember {
+                            session_id,
+                            entry,
+                            reply,
+                        } => {
+                            let result = self.remember(session_id, entry).await;
+                            let _ = reply.send(result);
+                        }
+                        SupervisorCmd::Forget {
+                            session_id,
+                            key,
+                            reply,
+                        } => {
+                            let result = self.forget(session_id, &key).await;
+                            let _ = reply.send(result);
+                        }
+                        SupervisorCmd::Review {
+                            session_id,
+                            target,
+                            model,
+                            reply,
+                        } => {
+                            self.review(session_id, target, model, reply).await;
+                        }
+                        SupervisorCmd::DraftCommit {
+                            session_id,
+                            model,
+                            reply,
+                        } => {
+                            self.draft_commit(session_id, model, reply).await;
+                        }
+                        SupervisorCmd::CreateCommit {
+                            session_id,
+                            files,
+                            message,
+                            reply,
+                        } => {
+                            self.create_commit(session_id, files, message, reply).await;
+                        }
+                        SupervisorCmd::StructuredOutput {
+                            session_id,
+                            model,
+                            schema,
+                            cancel_token,
+                            reply,
+                        } => {
+                            self.structured_output(session_id, model, schema, cancel_token, reply)
+                                .await;
+                        }
+                        SupervisorCmd::SummarizeSession { session_id, reply } => {
+                            self.summarize_session(session_id, reply).await;
+                        }
+                        SupervisorCmd::Drain => {
+                            self.drain_all().await;
+                            break;
+                        }
                         SupervisorCmd::Shutdown => {
                             self.shutdown_all().await;
                             break;
@@ -212,22 +424,51 @@ impl RuntimeSupervisor {
             }
         }
 
+        self.shutdown_token.cancel();
         tracing::info!("Runtime supervisor stopped");
     }
 
     async fn create_session(&mut self, config: SessionConfig) -> Result<SessionId, RuntimeError> {
+        self.ensure_capacity().await?;
+        CommandDenylist::new(&config.tool_config.bash_denylist).map_err(|e| {
+            RuntimeError::InvalidInput {
+                message: e.to_string(),
+            }
+        })?;
         let session_id = SessionId::new();
 
-        self.event_store.create_session(session_id).await?;
-
         let mut config = config;
+        if config.isolation == IsolationMode::Worktree && config.isolated_workspace.is_none() {
+            let WorkspaceConfig::Local { path } = &config.workspace else {
+                return Err(RuntimeError::InvalidInput {
+                    message: "worktree isolation requires a local workspace".to_string(),
+                });
+            };
+            let isolated = isolation::create(&AppPaths::worktrees_root(), session_id, path).await?;
+            config.workspace = WorkspaceConfig::Local {
+                path: isolated.path.clone(),
+            };
+            config.isolated_workspace = Some(isolated);
+        }
+
+        let tool_executor = match self.session_tool_executor(&config).await {
+            Ok(executor) => executor,
+            Err(error) => {
+                if let Some(isolated) = &config.isolated_workspace {
+                    let _ = isolated.discard().await;
+                }
+                return Err(error);
+            }
+        };
+
+        self.event_store.create_session(session_id).await?;
 
         if config.primary_agent_id.is_none() {
             config.primary_agent_id = Some(default_primary_agent_id().to_string());
         }
         let mut config = resolve_effective_config(&config);
 
-        let system_context = self.resolve_system_context(&config).await;
+        let system_context = self.resolve_system_context(&config, &tool_executor).await;
         if let Some(context) = &system_context {
             config.system_prompt = Some(context.prompt.clone());
         }
@@ -252,9 +493,11 @@ impl RuntimeSupervisor {
             state,
             self.event_store.clone(),
             self.api_client.clone(),
-            self.tool_executor.clone(),
+            tool_executor,
+            self.runtime_events.clone(),
         );
         self.sessions.insert(session_id, handle);
+        metrics::set_active_sessions(self.sessions.len());
 
         tracing::info!(session_id = %session_id, "Created session");
 
@@ -271,6 +514,7 @@ impl RuntimeSupervisor {
                 session_id: session_id.to_string(),
             });
         }
+        self.ensure_capacity().await?;
 
         let events = self.event_store.load_events_for_runtime(session_id).await?;
 
@@ -279,9 +523,14 @@ impl RuntimeSupervisor {
             apply_event_to_state(&mut state, event);
         }
 
+        let tool_executor = match &state.session_config {
+            Some(config) => self.session_tool_executor(config).await?,
+            None => self.tool_executor.clone(),
+        };
+
         if let Some(config) = state.session_config.clone() {
             let mut resolved = resolve_effective_config(&config);
-            let system_context = self.resolve_system_context(&resolved).await;
+            let system_context = self.resolve_system_context(&resolved, &tool_executor).await;
             if let Some(context) = &system_context {
                 resolved.system_prompt = Some(context.prompt.clone());
             }
@@ -289,13 +538,16 @@ impl RuntimeSupervisor {
             state.cached_system_context = system_context;
         }
 
+        let interrupted = restore_interrupted_operation(&mut state, &events);
+
         let should_drain_queue = !state.has_active_operation() && !state.queued_work.is_empty();
         let handle = spawn_session_actor(
             session_id,
             state,
             self.event_store.clone(),
             self.api_client.clone(),
-            self.tool_executor.clone(),
+            tool_executor,
+            self.runtime_events.clone(),
         );
 
         if should_drain_queue {
@@ -304,7 +556,32 @@ impl RuntimeSupervisor {
                 .await?;
         }
 
+        match interrupted {
+            Some(InterruptedOperation::AwaitingApproval { op_id }) => {
+                tracing::info!(
+                    session_id = %session_id,
+                    op_id = %op_id,
+                    "Resumed operation awaiting tool approval"
+                );
+                handle
+                    .dispatch(Action::ReissuePendingApproval { session_id, op_id })
+                    .await?;
+            }
+            Some(InterruptedOperation::Abandoned { op_id }) => {
+                tracing::info!(
+                    session_id = %session_id,
+                    op_id = %op_id,
+                    "Cancelling operation interrupted before the session was resumed"
+                );
+                handle
+                    .dispatch(Action::AbandonInterruptedOperation { session_id, op_id })
+                    .await?;
+            }
+            None => {}
+        }
+
         self.sessions.insert(session_id, handle);
+        metrics::set_active_sessions(self.sessions.len());
 
         tracing::info!(
             session_id = %session_id,
@@ -315,9 +592,72 @@ impl RuntimeSupervisor {
         Ok(())
     }
 
+    /// The executor for a session's tools: the shared one when the session runs
+    /// in the shared workspace's directory, otherwise one bound to the session's
+    /// own directory (its isolated worktree, or the path it was created with).
+    /// The process working directory is never changed.
+    async fn session_tool_executor(
+        &self,
+        config: &SessionConfig,
+    ) -> Result<Arc<ToolExecutor>, RuntimeError> {
+        if let Some(isolated) = &config.isolated_workspace
+            && !isolated.exists().await
+        {
+            return Err(RuntimeError::InvalidInput {
+                message: format!(
+                    "isolated workspace {} no longer exists",
+                    isolated.root.display()
+                ),
+            });
+        }
+
+        let WorkspaceConfig::Local { path } = &config.workspace else {
+            return Ok(self.tool_executor.clone());
+        };
+        let Some(shared) = self.tool_executor.workspace() else {
+            return Ok(self.tool_executor.clone());
+        };
+        if config.isolated_workspace.is_none() && shared.working_directory() == path {
+            return Ok(self.tool_executor.clone());
+        }
+        if !tokio::fs::metadata(path)
+            .await
+            .is_ok_and(|metadata| metadata.is_dir())
+        {
+            return Err(RuntimeError::InvalidInput {
+                message: format!("working directory {} does not exist", path.display()),
+            });
+        }
+
+        let workspace =
+            crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
+                path: path.clone(),
+                context_limits: Default::default(),
+            })
+            .await
+            .map_err(|e| RuntimeError::InvalidInput {
+                message: format!("failed to open workspace {}: {e}", path.display()),
+            })?;
+        Ok(Arc::new(self.tool_executor.for_workspace(workspace)))
+    }
+
     async fn resolve_system_context(
         &self,
         config: &SessionConfig,
+        tool_executor: &ToolExecutor,
+    ) -> Option<crate::app::SystemContext> {
+        time_phase(
+            self.tool_executor.profiler(),
+            ProfilePhase::SystemPrompt,
+            || None,
+            Self::build_system_context(config, tool_executor),
+        )
+        .await
+    }
+
+    async fn build_system_context(
+        config: &SessionConfig,
+        tool_executor: &ToolExecutor,
     ) -> Option<crate::app::SystemContext> {
         let prompt = config
             .system_prompt
@@ -331,7 +671,7 @@ impl RuntimeSupervisor {
             })
             .unwrap_or_else(|| system_prompt_for_model(&config.default_model));
 
-        let workspace = match self.tool_executor.workspace() {
+        let workspace = match tool_executor.workspace() {
             Some(workspace) => workspace,
             None => return Some(crate::app::SystemContext::new(prompt)),
         };
@@ -350,18 +690,351 @@ impl RuntimeSupervisor {
         ))
     }
 
+    async fn load_memory(&self, session_id: SessionId) -> Result<Vec<MemoryEntry>, RuntimeError> {
+        if !self.event_store.session_exists(session_id).await? {
+            return Err(RuntimeError::SessionNotFound {
+                session_id: session_id.to_string(),
+            });
+        }
+        Ok(self.event_store.load_memory(session_id).await?)
+    }
+
+    async fn remember(
+        &self,
+        session_id: SessionId,
+        entry: MemoryEntry,
+    ) -> Result<Vec<MemoryEntry>, RuntimeError> {
+        let entry = MemoryEntry {
+            key: entry.key.trim().to_string(),
+            content: entry.content.trim().to_string(),
+        };
+        if entry.key.is_empty() || entry.content.is_empty() {
+            return Err(RuntimeError::InvalidInput {
+                message: "memory key and content must not be empty".to_string(),
+            });
+        }
+        self.load_memory(session_id).await?;
+        self.event_store
+            .save_memory_entry(session_id, &entry)
+            .await?;
+        self.load_memory(session_id).await
+    }
+
+    async fn forget(
+        &self,
+        session_id: SessionId,
+        key: &str,
+    ) -> Result<Vec<MemoryEntry>, RuntimeError> {
+        self.load_memory(session_id).await?;
+        if !self
+            .event_store
+            .delete_memory_entry(session_id, key.trim())
+            .await?
+        {
+            return Err(RuntimeError::InvalidInput {
+                message: format!("no memory entry with key '{}'", key.trim()),
+            });
+        }
+        self.load_memory(session_id).await
+    }
+
     async fn suspend_session(&mut self, session_id: SessionId) -> Result<(), RuntimeError> {
         if let Some(handle) = self.sessions.remove(&session_id) {
             let _ = handle.suspend().await;
             tracing::info!(session_id = %session_id, "Suspended session");
         }
+        metrics::set_active_sessions(self.sessions.len());
+        Ok(())
+    }
+
+    /// Loaded sessions that could be suspended, with when each went idle,
+    /// longest-idle first.
+    async fn idle_sessions(&self) -> Vec<(SessionId, tokio::time::Instant)> {
+        let queries = self.sessions.iter().map(|(session_id, handle)| async move {
+            match handle.idle_since().await {
+                Ok(idle_since) => idle_since.map(|since| (*session_id, since)),
+                Err(error) => {
+                    warn!(
+                        session_id = %session_id,
+                        error = %error,
+                        "Failed to query session idleness"
+                    );
+                    None
+                }
+            }
+        });
+        let mut idle: Vec<_> = futures::future::join_all(queries)
+            .await
+            .into_iter()
+            .flatten()
+            .collect();
+        idle.sort_by_key(|(_, since)| *since);
+        idle
+    }
+
+    async fn suspend_idle_sessions(&mut self) {
+        let Some(timeout) = self.config.idle_timeout else {
+            return;
+        };
+        for (session_id, since) in self.idle_sessions().await {
+            if since.elapsed() < timeout {
+                break;
+            }
+            tracing::info!(session_id = %session_id, "Suspending idle session");
+            let _ = self.suspend_session(session_id).await;
+        }
+    }
+
+    /// Makes room for one more loaded session under `max_concurrent_sessions`.
+    async fn ensure_capacity(&mut self) -> Result<(), RuntimeError> {
+        let Some(limit) = self.config.max_concurrent_sessions else {
+            return Ok(());
+        };
+        if self.sessions.len() < limit {
+            return Ok(());
+        }
+
+        let excess = self.sessions.len() + 1 - limit;
+        let idle = self.idle_sessions().await;
+        if idle.len() < excess {
+            return Err(RuntimeError::SessionLimitReached { limit });
+        }
+        for (session_id, _) in idle.into_iter().take(excess) {
+            tracing::info!(
+                session_id = %session_id,
+                "Suspending idle session to stay under the session limit"
+            );
+            self.suspend_session(session_id).await?;
+        }
         Ok(())
     }
 
+    /// Runs `work` on its own task so model calls don't stall the supervisor,
+    /// sending its result on `reply`. `token`, which `work` should observe, is
+    /// cancelled if the caller stops waiting for the reply or the supervisor
+    /// stops first.
+    fn spawn_reply<T, F>(
+        &self,
+        token: CancellationToken,
+        mut reply: oneshot::Sender<Result<T, RuntimeError>>,
+        work: F,
+    ) where
+        T: Send + 'static,
+        F: Future<Output = Result<T, RuntimeError>> + Send + 'static,
+    {
+        let shutdown = self.shutdown_token.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                result = work => {
+                    let _ = reply.send(result);
+                }
+                () = reply.closed() => token.cancel(),
+                () = shutdown.cancelled() => token.cancel(),
+            }
+        });
+    }
+
+    /// Runs the review on its own task; the result is sent on `reply` when it
+    /// finishes.
+    async fn review(
+        &self,
+        session_id: SessionId,
+        target: ReviewTarget,
+        model: ModelId,
+        reply: oneshot::Sender<Result<ReviewReport, RuntimeError>>,
+    ) {
+        let root = match session_repo_root(&*self.event_store, session_id).await {
+            Ok(root) => root,
+            Err(e) => {
+                let _ = reply.send(Err(e));
+                return;
+            }
+        };
+        let caller = DefaultModelCaller::new(self.api_client.clone());
+        let token = self.shutdown_token.child_token();
+        self.spawn_reply(token.clone(), reply, async move {
+            review::run_review(&caller, &model, &root, target, token)
+                .await
+                .map_err(RuntimeError::from)
+        });
+    }
+
+    async fn draft_commit(
+        &mut self,
+        session_id: SessionId,
+        model: ModelId,
+        reply: oneshot::Sender<Result<CommitDraft, RuntimeError>>,
+    ) {
+        let (root, touched) = match self.commit_scope(session_id).await {
+            Ok(scope) => scope,
+            Err(e) => {
+                let _ = reply.send(Err(e));
+                return;
+            }
+        };
+        let caller = DefaultModelCaller::new(self.api_client.clone());
+        let token = self.shutdown_token.child_token();
+        self.spawn_reply(token.clone(), reply, async move {
+            commit::draft_commit(&caller, &model, &root, &touched, token)
+                .await
+                .map_err(RuntimeError::from)
+        });
+    }
+
+    async fn create_commit(
+        &mut self,
+        session_id: SessionId,
+        files: Vec<String>,
+        message: String,
+        reply: oneshot::Sender<Result<CommitOutcome, RuntimeError>>,
+    ) {
+        let (root, touched) = match self.commit_scope(session_id).await {
+            Ok(scope) => scope,
+            Err(e) => {
+                let _ = reply.send(Err(e));
+                return;
+            }
+        };
+        if let Some(path) = files.iter().find(|file| !touched.contains(file)) {
+            let _ = reply.send(Err(CommitError::UntouchedFile { path: path.clone() }.into()));
+            return;
+        }
+        tokio::spawn(async move {
+            let result = commit::create_commit(&root, &files, &message)
+                .await
+                .map_err(RuntimeError::from);
+            let _ = reply.send(result);
+        });
+    }
+
+    async fn structured_output(
+        &mut self,
+        session_id: SessionId,
+        model: ModelId,
+        schema: serde_json::Value,
+        cancel_token: CancellationToken,
+        reply: oneshot::Sender<Result<Option<serde_json::Value>, RuntimeError>>,
+    ) {
+        let mut state = match self.get_session_state(session_id).await {
+            Ok(state) => state,
+            Err(e) => {
+                let _ = reply.send(Err(e));
+                return;
+            }
+        };
+        // End on a user turn so providers don't treat the previous answer
+        // as a prefill.
+        state
+            .message_graph
+            .add_message_from_data(MessageData::User {
+                content: vec![UserContent::Text {
+                    text: STRUCTURED_OUTPUT_REQUEST.to_string(),
+                }],
+            });
+        let messages: Vec<_> = state
+            .message_graph
+            .get_thread_messages()
+            .into_iter()
+            .cloned()
+            .collect();
+        let system = state.cached_system_context.clone();
+        let api_client = self.api_client.clone();
+        let token = cancel_token.child_token();
+        self.spawn_reply(token.clone(), reply, async move {
+            api_client
+                .complete_structured(&model, messages, system, &schema, token)
+                .await
+                .map_err(RuntimeError::from)
+        });
+    }
+
+    /// Refresh the session summary regardless of `auto_summary`, storing it
+    /// the same way background summaries are stored.
+    async fn summarize_session(
+        &mut self,
+        session_id: SessionId,
+        reply: oneshot::Sender<Result<String, RuntimeError>>,
+    ) {
+        let state = match self.get_session_state(session_id).await {
+            Ok(state) => state,
+            Err(e) => {
+                let _ = reply.send(Err(e));
+                return;
+            }
+        };
+        let Some(handle) = self.sessions.get(&session_id).cloned() else {
+            let _ = reply.send(Err(RuntimeError::SessionNotFound {
+                session_id: session_id.to_string(),
+            }));
+            return;
+        };
+        let Some(model) = state
+            .session_config
+            .as_ref()
+            .map(SessionConfig::summary_model)
+        else {
+            let _ = reply.send(Err(RuntimeError::InvalidInput {
+                message: "Session has no configuration".to_string(),
+            }));
+            return;
+        };
+        let messages: Vec<_> = state
+            .message_graph
+            .get_thread_messages()
+            .into_iter()
+            .cloned()
+            .collect();
+        if messages.is_empty() {
+            let _ = reply.send(Err(RuntimeError::InvalidInput {
+                message: "Session has no messages to summarize".to_string(),
+            }));
+            return;
+        }
+
+        let interpreter =
+            EffectInterpreter::new(self.api_client.clone(), self.tool_executor.clone());
+        let token = self.shutdown_token.child_token();
+        self.spawn_reply(token.clone(), reply, async move {
+            let summary = generate_session_summary(&interpreter, model, &messages, token).await?;
+            handle
+                .dispatch(Action::SessionSummaryGenerated {
+                    session_id,
+                    summary: summary.clone(),
+                    message_count: messages.len(),
+                })
+                .await
+                .map(|()| summary)
+                .map_err(RuntimeError::from)
+        });
+    }
+
+    /// The repository directory for `session_id` and the files its edit tools
+    /// wrote, relative to that directory.
+    async fn commit_scope(
+        &mut self,
+        session_id: SessionId,
+    ) -> Result<(PathBuf, Vec<String>), RuntimeError> {
+        let root = session_repo_root(&*self.event_store, session_id).await?;
+        let state = self.get_session_state(session_id).await?;
+        let touched = commit::touched_files(&state.message_graph.messages, &root);
+        Ok((root, touched))
+    }
+
     async fn delete_session(&mut self, session_id: SessionId) -> Result<(), RuntimeError> {
         if let Some(handle) = self.sessions.remove(&session_id) {
             handle.shutdown();
         }
+        metrics::set_active_sessions(self.sessions.len());
+
+        if let Some(isolated) = stored_isolated_workspace(&*self.event_store, session_id).await?
+            && let Err(error) = isolated.cleanup().await
+        {
+            warn!(
+                session_id = %session_id,
+                error = %error,
+                "Failed to clean up isolated workspace"
+            );
+        }
 
         self.event_store.delete_session(session_id).await?;
 
@@ -448,17 +1121,112 @@ impl RuntimeSupervisor {
         Ok(state)
     }
 
+    async fn list_active_operations(&self) -> Vec<ActiveOperation> {
+        let queries = self.sessions.iter().map(|(session_id, handle)| async move {
+            handle.active_operation().await.unwrap_or_else(|error| {
+                warn!(
+                    session_id = %session_id,
+                    error = %error,
+                    "Failed to query active operation"
+                );
+                None
+            })
+        });
+        futures::future::join_all(queries)
+            .await
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Only loaded sessions can have a running operation, so unlike
+    /// [`Self::dispatch_action`] this never resumes the session.
+    async fn cancel_operation(
+        &self,
+        session_id: SessionId,
+        op_id: Option<OpId>,
+        force: bool,
+    ) -> Result<bool, RuntimeError> {
+        let Some(handle) = self.sessions.get(&session_id) else {
+            return Ok(false);
+        };
+        Ok(handle.cancel_operation(op_id, force).await?)
+    }
+
+    async fn drain_all(&mut self) {
+        let drains = self
+            .sessions
+            .drain()
+            .map(|(session_id, handle)| async move {
+                if let Err(error) = handle.drain().await {
+                    warn!(
+                        session_id = %session_id,
+                        error = %error,
+                        "Failed to drain session"
+                    );
+                }
+            });
+        futures::future::join_all(drains).await;
+        metrics::set_active_sessions(0);
+    }
+
     async fn shutdown_all(&mut self) {
         for (session_id, handle) in self.sessions.drain() {
             handle.shutdown();
             tracing::debug!(session_id = %session_id, "Shutting down session");
         }
+        metrics::set_active_sessions(0);
+    }
+}
+
+/// The isolated workspace recorded when the session was created, if any.
+async fn stored_session_config(
+    event_store: &dyn EventStore,
+    session_id: SessionId,
+) -> Result<Option<SessionConfig>, RuntimeError> {
+    let events = event_store.load_events(session_id).await?;
+    Ok(events.into_iter().find_map(|(_, event)| match event {
+        SessionEvent::SessionCreated { config, .. } => Some(*config),
+        _ => None,
+    }))
+}
+
+async fn stored_isolated_workspace(
+    event_store: &dyn EventStore,
+    session_id: SessionId,
+) -> Result<Option<IsolatedWorkspace>, RuntimeError> {
+    Ok(stored_session_config(event_store, session_id)
+        .await?
+        .and_then(|config| config.isolated_workspace))
+}
+
+/// The directory git operations for `session_id` run in: the isolated
+/// worktree if the session has one, otherwise its local workspace.
+async fn session_repo_root(
+    event_store: &dyn EventStore,
+    session_id: SessionId,
+) -> Result<PathBuf, RuntimeError> {
+    let config = stored_session_config(event_store, session_id)
+        .await?
+        .ok_or_else(|| RuntimeError::SessionNotFound {
+            session_id: session_id.to_string(),
+        })?;
+    if let Some(isolated) = config.isolated_workspace {
+        return Ok(isolated.path);
+    }
+    match config.workspace {
+        WorkspaceConfig::Local { path } => Ok(path),
+        WorkspaceConfig::Remote { .. } => Err(RuntimeError::InvalidInput {
+            message: "Git operations are only supported for sessions with a local workspace"
+                .to_string(),
+        }),
     }
 }
 
 #[derive(Clone)]
 pub struct RuntimeHandle {
     tx: mpsc::Sender<SupervisorCmd>,
+    runtime_events: broadcast::Sender<RuntimeEvent>,
 }
 
 impl RuntimeHandle {
@@ -542,6 +1310,11 @@ impl RuntimeHandle {
         reply_rx.await.map_err(|_| RuntimeError::ChannelClosed)?
     }
 
+    /// Receive events from every session hosted by this runtime.
+    pub fn subscribe_all_events(&self) -> broadcast::Receiver<RuntimeEvent> {
+        self.runtime_events.subscribe()
+    }
+
     pub async fn subscribe_deltas(
         &self,
         session_id: SessionId,
@@ -557,16 +1330,15 @@ impl RuntimeHandle {
         reply_rx.await.map_err(|_| RuntimeError::ChannelClosed)?
     }
 
-    pub async fn load_events_after(
+    /// The worktree an isolated session runs in, or `None` for ordinary sessions.
+    pub async fn isolated_workspace(
         &self,
         session_id: SessionId,
-        after_seq: u64,
-    ) -> Result<Vec<(u64, SessionEvent)>, RuntimeError> {
+    ) -> Result<Option<IsolatedWorkspace>, RuntimeError> {
         let (reply_tx, reply_rx) = oneshot::channel();
         self.tx
-            .send(SupervisorCmd::LoadEventsAfter {
+            .send(SupervisorCmd::GetIsolatedWorkspace {
                 session_id,
-                after_seq,
                 reply: reply_tx,
             })
             .await
@@ -574,11 +1346,19 @@ impl RuntimeHandle {
         reply_rx.await.map_err(|_| RuntimeError::ChannelClosed)?
     }
 
-    pub async fn get_session_state(&self, session_id: SessionId) -> Result<AppState, RuntimeError> {
+    /// Review the changes selected by `target` in the session's workspace.
+    pub async fn review(
+        &self,
+        session_id: SessionId,
+        target: ReviewTarget,
+        model: ModelId,
+    ) -> Result<ReviewReport, RuntimeError> {
         let (reply_tx, reply_rx) = oneshot::channel();
         self.tx
-            .send(SupervisorCmd::GetSessionState {
+            .send(SupervisorCmd::Review {
                 session_id,
+                target,
+                model,
                 reply: reply_tx,
             })
             .await
@@ -586,10 +1366,169 @@ impl RuntimeHandle {
         reply_rx.await.map_err(|_| RuntimeError::ChannelClosed)?
     }
 
-    pub async fn is_session_active(&self, session_id: SessionId) -> Result<bool, RuntimeError> {
+    /// Draft a commit message for the uncommitted changes to files the
+    /// session edited.
+    pub async fn draft_commit(
+        &self,
+        session_id: SessionId,
+        model: ModelId,
+    ) -> Result<CommitDraft, RuntimeError> {
         let (reply_tx, reply_rx) = oneshot::channel();
         self.tx
-            .send(SupervisorCmd::IsSessionActive {
+            .send(SupervisorCmd::DraftCommit {
+                session_id,
+                model,
+                reply: reply_tx,
+            })
+            .await
+            .map_err(|_| RuntimeError::ChannelClosed)?;
+        reply_rx.await.map_err(|_| RuntimeError::ChannelClosed)?
+    }
+
+    /// Stage and commit `files`, which must all have been edited by the
+    /// session.
+    pub async fn create_commit(
+        &self,
+        session_id: SessionId,
+        files: Vec<String>,
+        message: String,
+    ) -> Result<CommitOutcome, RuntimeError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.tx
+            .send(SupervisorCmd::CreateCommit {
+                session_id,
+                files,
+                message,
+                reply: reply_tx,
+            })
+            .await
+            .map_err(|_| RuntimeError::ChannelClosed)?;
+        reply_rx.await.map_err(|_| RuntimeError::ChannelClosed)?
+    }
+
+    /// Answer the session's conversation again as JSON constrained to
+    /// `schema`, using the provider's native structured output mode. Returns
+    /// `None` when the model's provider has no such mode. The model call is
+    /// cancelled with `cancel_token`.
+    pub async fn structured_output(
+        &self,
+        session_id: SessionId,
+        model: ModelId,
+        schema: serde_json::Value,
+        cancel_token: CancellationToken,
+    ) -> Result<Option<serde_json::Value>, RuntimeError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.tx
+            .send(SupervisorCmd::StructuredOutput {
+                session_id,
+                model,
+                schema,
+                cancel_token,
+                reply: reply_tx,
+            })
+            .await
+            .map_err(|_| RuntimeError::ChannelClosed)?;
+        reply_rx.await.map_err(|_| RuntimeError::ChannelClosed)?
+    }
+
+    /// Summarize the conversation now and store the result on the session.
+    pub async fn summarize_session(&self, session_id: SessionId) -> Result<String, RuntimeError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.tx
+            .send(SupervisorCmd::SummarizeSession {
+                session_id,
+                reply: reply_tx,
+            })
+            .await
+            .map_err(|_| RuntimeError::ChannelClosed)?;
+        reply_rx.await.map_err(|_| RuntimeError::ChannelClosed)?
+    }
+
+    /// Session memory, in the order entries were first remembered.
+    pub async fn load_memory(
+        &self,
+        session_id: SessionId,
+    ) -> Result<Vec<MemoryEntry>, RuntimeError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.tx
+            .send(SupervisorCmd::LoadMemory {
+                session_id,
+                reply: reply_tx,
+            })
+            .await
+            .map_err(|_| RuntimeError::ChannelClosed)?;
+        reply_rx.await.map_err(|_| RuntimeError::ChannelClosed)?
+    }
+
+    /// Add or replace a memory entry and return the updated memory.
+    pub async fn remember(
+        &self,
+        session_id: SessionId,
+        entry: MemoryEntry,
+    ) -> Result<Vec<MemoryEntry>, RuntimeError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.tx
+            .send(SupervisorCmd::Remember {
+                session_id,
+                entry,
+                reply: reply_tx,
+            })
+            .await
+            .map_err(|_| RuntimeError::ChannelClosed)?;
+        reply_rx.await.map_err(|_| RuntimeError::ChannelClosed)?
+    }
+
+    /// Remove a memory entry and return the updated memory.
+    pub async fn forget(
+        &self,
+        session_id: SessionId,
+        key: String,
+    ) -> Result<Vec<MemoryEntry>, RuntimeError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.tx
+            .send(SupervisorCmd::Forget {
+                session_id,
+                key,
+                reply: reply_tx,
+            })
+            .await
+            .map_err(|_| RuntimeError::ChannelClosed)?;
+        reply_rx.await.map_err(|_| RuntimeError::ChannelClosed)?
+    }
+
+    pub async fn load_events_after(
+        &self,
+        session_id: SessionId,
+        after_seq: u64,
+    ) -> Result<Vec<(u64, SessionEvent)>, RuntimeError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.tx
+            .send(SupervisorCmd::LoadEventsAfter {
+                session_id,
+                after_seq,
+                reply: reply_tx,
+            })
+            .await
+            .map_err(|_| RuntimeError::ChannelClosed)?;
+        reply_rx.await.map_err(|_| RuntimeError::ChannelClosed)?
+    }
+
+    pub async fn get_session_state(&self, session_id: SessionId) -> Result<AppState, RuntimeError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.tx
+            .send(SupervisorCmd::GetSessionState {
+                session_id,
+                reply: reply_tx,
+            })
+            .await
+            .map_err(|_| RuntimeError::ChannelClosed)?;
+        reply_rx.await.map_err(|_| RuntimeError::ChannelClosed)?
+    }
+
+    pub async fn is_session_active(&self, session_id: SessionId) -> Result<bool, RuntimeError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.tx
+            .send(SupervisorCmd::IsSessionActive {
                 session_id,
                 reply: reply_tx,
             })
@@ -647,17 +1586,9 @@ impl RuntimeHandle {
         &self,
         session_id: SessionId,
         request_id: RequestId,
-        approved: bool,
+        decision: crate::app::domain::action::ApprovalDecision,
         remember: Option<crate::app::domain::action::ApprovalMemory>,
     ) -> Result<(), RuntimeError> {
-        use crate::app::domain::action::ApprovalDecision;
-
-        let decision = if approved {
-            ApprovalDecision::Approved
-        } else {
-            ApprovalDecision::Denied
-        };
-
         let action = Action::ToolApprovalDecided {
             session_id,
             request_id,
@@ -680,6 +1611,22 @@ impl RuntimeHandle {
         self.dispatch_action(session_id, action).await
     }
 
+    /// Switch the model of the session's active agent operation, if any.
+    pub async fn switch_model(
+        &self,
+        session_id: SessionId,
+        model: ModelId,
+        mode: ModelSwitchMode,
+    ) -> Result<(), RuntimeError> {
+        let action = Action::SwitchModel {
+            session_id,
+            model,
+            mode,
+            op_id: OpId::new(),
+        };
+        self.dispatch_action(session_id, action).await
+    }
+
     pub async fn cancel_operation(
         &self,
         session_id: SessionId,
@@ -689,6 +1636,38 @@ impl RuntimeHandle {
         self.dispatch_action(session_id, action).await
     }
 
+    /// Every loaded session's in-flight operation.
+    pub async fn list_active_operations(&self) -> Result<Vec<ActiveOperation>, RuntimeError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.tx
+            .send(SupervisorCmd::ListActiveOperations { reply: reply_tx })
+            .await
+            .map_err(|_| RuntimeError::ChannelClosed)?;
+        reply_rx.await.map_err(|_| RuntimeError::ChannelClosed)
+    }
+
+    /// Cancel the session's running operation the way a user cancel does. With
+    /// `force`, its model and tool tasks are aborted rather than left to notice
+    /// the cancellation. Returns whether an operation was cancelled.
+    pub async fn cancel_running_operation(
+        &self,
+        session_id: SessionId,
+        op_id: Option<OpId>,
+        force: bool,
+    ) -> Result<bool, RuntimeError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.tx
+            .send(SupervisorCmd::CancelOperation {
+                session_id,
+                op_id,
+                force,
+                reply: reply_tx,
+            })
+            .await
+            .map_err(|_| RuntimeError::ChannelClosed)?;
+        reply_rx.await.map_err(|_| RuntimeError::ChannelClosed)?
+    }
+
     pub async fn submit_edited_message(
         &self,
         session_id: SessionId,
@@ -714,6 +1693,69 @@ impl RuntimeHandle {
         Ok(op_id)
     }
 
+    pub async fn retry_last_turn(
+        &self,
+        session_id: SessionId,
+        model: ModelId,
+    ) -> Result<OpId, RuntimeError> {
+        let op_id = OpId::new();
+
+        let action = Action::RetryLastTurn {
+            session_id,
+            op_id,
+            new_message_id: MessageId::new(),
+            model,
+            timestamp: current_timestamp(),
+        };
+
+        self.dispatch_action(session_id, action).await?;
+        Ok(op_id)
+    }
+
+    /// Send `text` as a prompt and draft `candidates` answers to it in
+    /// parallel; see [`Action::StartBestOf`].
+    pub async fn start_best_of(
+        &self,
+        session_id: SessionId,
+        text: String,
+        candidates: usize,
+        model: ModelId,
+    ) -> Result<OpId, RuntimeError> {
+        if text.trim().is_empty() {
+            return Err(RuntimeError::InvalidInput {
+                message: "Input text cannot be empty".to_string(),
+            });
+        }
+
+        let op_id = OpId::new();
+        let action = Action::StartBestOf {
+            session_id,
+            content: vec![UserContent::Text { text }],
+            op_id,
+            message_id: MessageId::new(),
+            candidate_op_ids: (0..candidates).map(|_| OpId::new()).collect(),
+            model,
+            timestamp: current_timestamp(),
+        };
+
+        self.dispatch_action(session_id, action).await?;
+        Ok(op_id)
+    }
+
+    pub async fn pick_best_of_candidate(
+        &self,
+        session_id: SessionId,
+        index: u32,
+    ) -> Result<(), RuntimeError> {
+        let action = Action::PickBestOfCandidate {
+            session_id,
+            index,
+            timestamp: current_timestamp(),
+        };
+
+        self.dispatch_action(session_id, action).await
+    }
+
     pub async fn submit_dequeue_queued_item(
         &self,
         session_id: SessionId,
@@ -803,13 +1845,34 @@ impl RuntimeService {
         event_store: Arc<dyn EventStore>,
         api_client: Arc<ApiClient>,
         tool_executor: Arc<ToolExecutor>,
+    ) -> Self {
+        Self::spawn_with_config(
+            event_store,
+            api_client,
+            tool_executor,
+            RuntimeConfig::default(),
+        )
+    }
+
+    pub fn spawn_with_config(
+        event_store: Arc<dyn EventStore>,
+        api_client: Arc<ApiClient>,
+        tool_executor: Arc<ToolExecutor>,
+        config: RuntimeConfig,
     ) -> Self {
         let (tx, rx) = mpsc::channel(64);
+        let (runtime_events, _) = broadcast::channel(RUNTIME_EVENT_BROADCAST_CAPACITY);
 
-        let supervisor = RuntimeSupervisor::new(event_store, api_client, tool_executor);
+        let supervisor = RuntimeSupervisor::new(
+            event_store,
+            api_client,
+            tool_executor,
+            runtime_events.clone(),
+            config,
+        );
         let task = tokio::spawn(supervisor.run(rx));
 
-        let handle = RuntimeHandle { tx };
+        let handle = RuntimeHandle { tx, runtime_events };
 
         Self { handle, task }
     }
@@ -822,6 +1885,22 @@ impl RuntimeService {
         self.handle.shutdown();
         let _ = self.task.await;
     }
+
+    /// Cancels every running operation, persists the cancellations, and stops
+    /// the runtime. Returns false if that took longer than `timeout`, in which
+    /// case the runtime is stopped without waiting for the rest.
+    pub async fn drain(mut self, timeout: std::time::Duration) -> bool {
+        let drained = tokio::time::timeout(timeout, async {
+            let _ = self.handle.tx.send(SupervisorCmd::Drain).await;
+            let _ = (&mut self.task).await;
+        })
+        .await
+        .is_ok();
+        if !drained {
+            self.task.abort();
+        }
+        drained
+    }
 }
 
 fn current_timestamp() -> u64 {
@@ -874,6 +1953,9 @@ mod tests {
             title: None,
             metadata: std::collections::HashMap::new(),
             auto_compaction: crate::session::state::AutoCompactionConfig::default(),
+            auto_summary: crate::session::state::AutoSummaryConfig::default(),
+            isolation: crate::isolation::IsolationMode::None,
+            isolated_workspace: None,
         }
     }
 
@@ -893,6 +1975,88 @@ mod tests {
         service.shutdown().await;
     }
 
+    #[tokio::test]
+    async fn test_create_session_rejects_invalid_denylist_rules() {
+        let (event_store, api_client, tool_executor) = create_test_deps().await;
+        let service = RuntimeService::spawn(event_store, api_client, tool_executor);
+
+        let mut config = test_session_config();
+        config.tool_config.bash_denylist.deny = vec!["rm 'unterminated".to_string()];
+        let result = service.handle.create_session(config).await;
+
+        let Err(RuntimeError::InvalidInput { message }) = &result else {
+            panic!("expected an invalid input error, got {result:?}");
+        };
+        assert!(message.contains("rm 'unterminated"), "{message}");
+        assert!(
+            service
+                .handle
+                .list_active_sessions()
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        service.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_sessions_use_their_own_working_directory() {
+        let (event_store, api_client, _) = create_test_deps().await;
+        let model_registry = Arc::new(crate::model_registry::ModelRegistry::load(&[]).unwrap());
+        let shared_dir = tempfile::tempdir().unwrap();
+        let workspace =
+            crate::workspace::create_workspace(&crate::workspace::WorkspaceConfig::Local {
+                path: shared_dir.path().to_path_buf(),
+                context_limits: Default::default(),
+            })
+            .await
+            .unwrap();
+        let tool_executor = crate::tools::ToolSystemBuilder::new(
+            workspace,
+            event_store.clone(),
+            api_client.clone(),
+            model_registry,
+        )
+        .with_backend_registry(Arc::new(BackendRegistry::new()))
+        .with_validators(Arc::new(ValidatorRegistry::new()))
+        .build();
+        let service = RuntimeService::spawn(event_store, api_client, tool_executor);
+        let cwd_before = std::env::current_dir().unwrap();
+
+        let first_dir = tempfile::tempdir().unwrap();
+        let second_dir = tempfile::tempdir().unwrap();
+        let mut sessions = Vec::new();
+        for dir in [&first_dir, &second_dir] {
+            let mut config = test_session_config();
+            config.workspace = WorkspaceConfig::Local {
+                path: dir.path().to_path_buf(),
+            };
+            sessions.push(service.handle.create_session(config).await.unwrap());
+        }
+
+        for (session_id, dir) in sessions.into_iter().zip([&first_dir, &second_dir]) {
+            let state = service.handle.get_session_state(session_id).await.unwrap();
+            let environment = state
+                .cached_system_context
+                .and_then(|context| context.environment)
+                .expect("session should have environment info");
+            assert_eq!(environment.working_directory, dir.path());
+        }
+        assert_eq!(std::env::current_dir().unwrap(), cwd_before);
+
+        let mut missing = test_session_config();
+        missing.workspace = WorkspaceConfig::Local {
+            path: first_dir.path().join("missing"),
+        };
+        assert!(matches!(
+            service.handle.create_session(missing).await,
+            Err(RuntimeError::InvalidInput { .. })
+        ));
+
+        service.shutdown().await;
+    }
+
     #[tokio::test]
     async fn test_suspend_and_resume_session() {
         let (event_store, api_client, tool_executor) = create_test_deps().await;
@@ -913,6 +2077,579 @@ mod tests {
         service.shutdown().await;
     }
 
+    /// Answers every request and records the system prompt it was sent.
+    #[derive(Default)]
+    struct SystemPromptRecordingProvider {
+        prompts: std::sync::Mutex<Vec<Option<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::api::provider::Provider for SystemPromptRecordingProvider {
+        fn name(&self) -> &'static str {
+            "recording"
+        }
+
+        async fn complete(
+            &self,
+            _model_id: &ModelId,
+            _messages: Vec<crate::app::conversation::Message>,
+            system: Option<crate::app::SystemContext>,
+            _tools: Option<Vec<steer_tools::ToolSchema>>,
+            _call_options: Option<crate::config::model::ModelParameters>,
+            _token: CancellationToken,
+        ) -> Result<crate::api::provider::CompletionResponse, crate::api::ApiError> {
+            self.prompts
+                .lock()
+                .unwrap()
+                .push(system.and_then(|context| context.render()));
+            Ok(crate::api::provider::CompletionResponse {
+                content: vec![crate::app::conversation::AssistantContent::Text {
+                    text: "ok".to_string(),
+                }],
+                usage: None,
+            })
+        }
+    }
+
+    async fn wait_for_operation_completed(
+        event_store: &Arc<dyn EventStore>,
+        session_id: SessionId,
+        op_id: OpId,
+    ) {
+        for _ in 0..100 {
+            let events = event_store.load_events(session_id).await.unwrap();
+            if events.iter().any(|(_, event)| {
+                matches!(event, SessionEvent::OperationCompleted { op_id: id, .. } if *id == op_id)
+            }) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("operation {op_id} did not complete");
+    }
+
+    #[tokio::test]
+    async fn test_session_memory_survives_compaction_and_resume() {
+        let (event_store, api_client, tool_executor) = create_test_deps().await;
+        let provider_id = crate::config::provider::ProviderId("recording".to_string());
+        let model = ModelId::new(provider_id.clone(), "recording-model");
+        let provider = Arc::new(SystemPromptRecordingProvider::default());
+        api_client.insert_test_provider(provider_id, provider.clone());
+        let service = RuntimeService::spawn(event_store.clone(), api_client, tool_executor);
+
+        let mut config = test_session_config();
+        config.default_model = model.clone();
+        config.title = Some("memory".to_string());
+        let session_id = service.handle.create_session(config).await.unwrap();
+        event_store
+            .save_memory_entry(
+                session_id,
+                &steer_tools::tools::memory::MemoryEntry {
+                    key: "test-command".to_string(),
+                    content: "cargo nextest run".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+
+        let send = |text: &str| {
+            service.handle.submit_user_input(
+                session_id,
+                vec![UserContent::Text {
+                    text: text.to_string(),
+                }],
+                model.clone(),
+            )
+        };
+        for text in ["first", "second"] {
+            let (op_id, _) = send(text).await.unwrap();
+            wait_for_operation_completed(&event_store, session_id, op_id).await;
+        }
+
+        let op_id = service
+            .handle
+            .compact_session(session_id, model.clone())
+            .await
+            .unwrap();
+        wait_for_operation_completed(&event_store, session_id, op_id).await;
+        let events = event_store.load_events(session_id).await.unwrap();
+        assert!(
+            events
+                .iter()
+                .any(|(_, event)| matches!(event, SessionEvent::ConversationCompacted { .. }))
+        );
+
+        service.handle.suspend_session(session_id).await.unwrap();
+        service.handle.resume_session(session_id).await.unwrap();
+
+        let (op_id, _) = send("third").await.unwrap();
+        wait_for_operation_completed(&event_store, session_id, op_id).await;
+
+        let last_prompt = provider
+            .prompts
+            .lock()
+            .unwrap()
+            .last()
+            .cloned()
+            .flatten()
+            .expect("system prompt");
+        assert!(last_prompt.contains("<session_memory>"));
+        assert!(last_prompt.contains("- test-command: cargo nextest run"));
+
+        service.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_resume_cancels_operation_interrupted_mid_tool() {
+        use crate::app::conversation::{AssistantContent, Message, MessageData};
+        use crate::app::domain::state::OperationKind;
+        use crate::app::domain::types::{OpId, ToolCallId};
+
+        let (event_store, api_client, tool_executor) = create_test_deps().await;
+        let service = RuntimeService::spawn(event_store.clone(), api_client, tool_executor);
+
+        let session_id = service
+            .handle
+            .create_session(test_session_config())
+            .await
+            .unwrap();
+        service.handle.suspend_session(session_id).await.unwrap();
+
+        // Simulate a server that stopped while a tool was running.
+        let op_id = OpId::new();
+        let model = crate::config::model::builtin::claude_sonnet_4_5();
+        let tool_call = steer_tools::ToolCall {
+            id: "tc_1".to_string(),
+            name: "bash".to_string(),
+            parameters: serde_json::json!({ "command": "sleep 60" }),
+        };
+        for event in [
+            SessionEvent::OperationStarted {
+                op_id,
+                kind: OperationKind::AgentLoop,
+            },
+            SessionEvent::AssistantMessageAdded {
+                message: Message {
+                    data: MessageData::Assistant {
+                        content: vec![AssistantContent::ToolCall {
+                            tool_call: tool_call.clone(),
+                            thought_signature: None,
+                        }],
+                    },
+                    timestamp: 0,
+                    id: "msg_1".to_string(),
+                    parent_message_id: None,
+                },
+                model: model.clone(),
+            },
+            SessionEvent::ToolCallStarted {
+                id: ToolCallId::from_string("tc_1"),
+                name: tool_call.name.clone(),
+                parameters: tool_call.parameters.clone(),
+                model,
+            },
+        ] {
+            event_store.append(session_id, &event).await.unwrap();
+        }
+
+        service.handle.resume_session(session_id).await.unwrap();
+
+        let mut cancelled = false;
+        for _ in 0..50 {
+            let events = event_store.load_events(session_id).await.unwrap();
+            cancelled = events.iter().any(|(_, event)| {
+                matches!(event, SessionEvent::OperationCancelled { op_id: id, .. } if *id == op_id)
+            });
+            if cancelled {
+                assert!(events.iter().any(|(_, event)| matches!(
+                    event,
+                    SessionEvent::ToolMessageAdded { message }
+                        if matches!(&message.data, MessageData::Tool { tool_use_id, .. } if tool_use_id == "tc_1")
+                )));
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert!(
+            cancelled,
+            "interrupted operation should be cancelled on resume"
+        );
+
+        service.shutdown().await;
+    }
+
+    /// Never answers; the call only ends when its operation is cancelled.
+    struct HangingProvider;
+
+    #[async_trait::async_trait]
+    impl crate::api::provider::Provider for HangingProvider {
+        fn name(&self) -> &'static str {
+            "hanging"
+        }
+
+        async fn complete(
+            &self,
+            _model_id: &ModelId,
+            _messages: Vec<crate::app::conversation::Message>,
+            _system: Option<crate::app::SystemContext>,
+            _tools: Option<Vec<steer_tools::ToolSchema>>,
+            _call_options: Option<crate::config::model::ModelParameters>,
+            token: CancellationToken,
+        ) -> Result<crate::api::provider::CompletionResponse, crate::api::ApiError> {
+            token.cancelled().await;
+            Err(crate::api::ApiError::Cancelled {
+                provider: "hanging".to_string(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_drain_cancels_and_persists_running_operation() {
+        let (event_store, api_client, tool_executor) = create_test_deps().await;
+        let provider_id = crate::config::provider::ProviderId("hanging".to_string());
+        let model = ModelId::new(provider_id.clone(), "hanging-model");
+        api_client.insert_test_provider(provider_id, Arc::new(HangingProvider));
+        let service = RuntimeService::spawn(event_store.clone(), api_client, tool_executor);
+
+        let mut config = test_session_config();
+        config.default_model = model.clone();
+        config.title = Some("drain".to_string());
+        let session_id = service.handle.create_session(config).await.unwrap();
+        let (op_id, _) = service
+            .handle
+            .submit_user_input(
+                session_id,
+                vec![UserContent::Text {
+                    text: "hello".to_string(),
+                }],
+                model,
+            )
+            .await
+            .unwrap();
+
+        assert!(
+            service.drain(std::time::Duration::from_secs(5)).await,
+            "drain should finish before the timeout"
+        );
+
+        let events = event_store.load_events(session_id).await.unwrap();
+        assert!(events.iter().any(|(_, event)| {
+            matches!(event, SessionEvent::OperationCancelled { op_id: id, .. } if *id == op_id)
+        }));
+    }
+
+    /// Never answers, and reports when a call starts and when its token is
+    /// cancelled, even if the call itself is dropped first.
+    struct CancellationProbeProvider {
+        started: mpsc::UnboundedSender<()>,
+        cancelled: mpsc::UnboundedSender<()>,
+    }
+
+    impl CancellationProbeProvider {
+        fn new() -> (
+            Self,
+            mpsc::UnboundedReceiver<()>,
+            mpsc::UnboundedReceiver<()>,
+        ) {
+            let (started, started_rx) = mpsc::unbounded_channel();
+            let (cancelled, cancelled_rx) = mpsc::unbounded_channel();
+            (Self { started, cancelled }, started_rx, cancelled_rx)
+        }
+
+        fn watch(&self, token: &CancellationToken) {
+            let _ = self.started.send(());
+            let token = token.clone();
+            let cancelled = self.cancelled.clone();
+            tokio::spawn(async move {
+                token.cancelled().await;
+                let _ = cancelled.send(());
+            });
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl crate::api::provider::Provider for CancellationProbeProvider {
+        fn name(&self) -> &'static str {
+            "probe"
+        }
+
+        async fn complete(
+            &self,
+            _model_id: &ModelId,
+            _messages: Vec<crate::app::conversation::Message>,
+            _system: Option<crate::app::SystemContext>,
+            _tools: Option<Vec<steer_tools::ToolSchema>>,
+            _call_options: Option<crate::config::model::ModelParameters>,
+            token: CancellationToken,
+        ) -> Result<crate::api::provider::CompletionResponse, crate::api::ApiError> {
+            self.watch(&token);
+            token.cancelled().await;
+            Err(crate::api::ApiError::Cancelled {
+                provider: "probe".to_string(),
+            })
+        }
+
+        fn supports_structured_output(&self) -> bool {
+            true
+        }
+
+        async fn complete_structured(
+            &self,
+            _model_id: &ModelId,
+            _messages: Vec<crate::app::conversation::Message>,
+            _system: Option<crate::app::SystemContext>,
+            _schema: &serde_json::Value,
+            token: CancellationToken,
+        ) -> Result<serde_json::Value, crate::api::ApiError> {
+            self.watch(&token);
+            token.cancelled().await;
+            Err(crate::api::ApiError::Cancelled {
+                provider: "probe".to_string(),
+            })
+        }
+    }
+
+    async fn git(dir: &std::path::Path, args: &[&str]) {
+        let status = tokio::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .await
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    /// A repository with one committed file and an unstaged change to it.
+    async fn repo_with_unstaged_change() -> tempfile::TempDir {
+        let repo = tempfile::tempdir().unwrap();
+        git(repo.path(), &["init", "-q"]).await;
+        git(repo.path(), &["config", "user.email", "dev@example.com"]).await;
+        git(repo.path(), &["config", "user.name", "Dev"]).await;
+        std::fs::write(repo.path().join("lib.rs"), "fn a() {}\n").unwrap();
+        git(repo.path(), &["add", "."]).await;
+        git(repo.path(), &["commit", "-q", "-m", "init"]).await;
+        std::fs::write(repo.path().join("lib.rs"), "fn b() {}\n").unwrap();
+        repo
+    }
+
+    #[tokio::test]
+    async fn test_review_is_cancelled_when_the_caller_stops_waiting() {
+        let repo = repo_with_unstaged_change().await;
+        let (event_store, api_client, tool_executor) = create_test_deps().await;
+        let provider_id = crate::config::provider::ProviderId("probe".to_string());
+        let model = ModelId::new(provider_id.clone(), "probe-model");
+        let (provider, mut started, mut cancelled) = CancellationProbeProvider::new();
+        api_client.insert_test_provider(provider_id, Arc::new(provider));
+        let service = RuntimeService::spawn(event_store, api_client, tool_executor);
+
+        let mut config = test_session_config();
+        config.default_model = model.clone();
+        config.workspace = WorkspaceConfig::Local {
+            path: repo.path().to_path_buf(),
+        };
+        let session_id = service.handle.create_session(config).await.unwrap();
+
+        let handle = service.handle.clone();
+        let review = tokio::spawn(async move {
+            handle
+                .review(session_id, ReviewTarget::Unstaged, model)
+                .await
+        });
+        tokio::time::timeout(Duration::from_secs(5), started.recv())
+            .await
+            .expect("the review never called the model");
+        review.abort();
+        tokio::time::timeout(Duration::from_secs(5), cancelled.recv())
+            .await
+            .expect("the review's model call was not cancelled");
+
+        service.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_structured_output_is_cancelled_with_the_callers_token() {
+        let (event_store, api_client, tool_executor) = create_test_deps().await;
+        let provider_id = crate::config::provider::ProviderId("probe".to_string());
+        let model = ModelId::new(provider_id.clone(), "probe-model");
+        let (provider, mut started, mut cancelled) = CancellationProbeProvider::new();
+        api_client.insert_test_provider(provider_id, Arc::new(provider));
+        let service = RuntimeService::spawn(event_store, api_client, tool_executor);
+
+        let mut config = test_session_config();
+        config.default_model = model.clone();
+        let session_id = service.handle.create_session(config).await.unwrap();
+
+        let cancel_token = CancellationToken::new();
+        let handle = service.handle.clone();
+        let output = tokio::spawn({
+            let cancel_token = cancel_token.clone();
+            async move {
+                handle
+                    .structured_output(session_id, model, serde_json::json!({}), cancel_token)
+                    .await
+            }
+        });
+        tokio::time::timeout(Duration::from_secs(5), started.recv())
+            .await
+            .expect("structured output never called the model");
+        cancel_token.cancel();
+        tokio::time::timeout(Duration::from_secs(5), cancelled.recv())
+            .await
+            .expect("the structured output call was not cancelled");
+        assert!(output.await.unwrap().is_err());
+
+        service.shutdown().await;
+    }
+
+    async fn wait_until_suspended(handle: &RuntimeHandle, session_id: SessionId) -> bool {
+        for _ in 0..100 {
+            if !handle.is_session_active(session_id).await.unwrap() {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        false
+    }
+
+    #[tokio::test]
+    async fn test_idle_session_suspended_during_pending_approval_reissues_it_on_resume() {
+        use crate::app::conversation::{AssistantContent, Message, MessageData};
+        use crate::app::domain::action::ApprovalDecision;
+        use crate::app::domain::state::OperationKind;
+
+        let (event_store, api_client, tool_executor) = create_test_deps().await;
+        let service = RuntimeService::spawn_with_config(
+            event_store.clone(),
+            api_client,
+            tool_executor,
+            RuntimeConfig {
+                idle_timeout: Some(Duration::from_millis(50)),
+                ..RuntimeConfig::default()
+            },
+        );
+
+        let session_id = service
+            .handle
+            .create_session(test_session_config())
+            .await
+            .unwrap();
+        service.handle.suspend_session(session_id).await.unwrap();
+
+        // An agent loop stopped on an approval prompt nobody answered.
+        let op_id = OpId::new();
+        let stale_request_id = RequestId::new();
+        let tool_call = steer_tools::ToolCall {
+            id: "tc_1".to_string(),
+            name: "bash".to_string(),
+            parameters: serde_json::json!({ "command": "rm -rf build" }),
+        };
+        for event in [
+            SessionEvent::OperationStarted {
+                op_id,
+                kind: OperationKind::AgentLoop,
+            },
+            SessionEvent::AssistantMessageAdded {
+                message: Message {
+                    data: MessageData::Assistant {
+                        content: vec![AssistantContent::ToolCall {
+                            tool_call: tool_call.clone(),
+                            thought_signature: None,
+                        }],
+                    },
+                    timestamp: 0,
+                    id: "msg_1".to_string(),
+                    parent_message_id: None,
+                },
+                model: crate::config::model::builtin::claude_sonnet_4_5(),
+            },
+            SessionEvent::ApprovalRequested {
+                request_id: stale_request_id,
+                tool_call: tool_call.clone(),
+            },
+        ] {
+            event_store.append(session_id, &event).await.unwrap();
+        }
+
+        // Waiting on an approval with no one attached counts as idle.
+        service.handle.resume_session(session_id).await.unwrap();
+        assert!(wait_until_suspended(&service.handle, session_id).await);
+
+        // A decision on the prompt shown before suspension no longer resolves it.
+        service
+            .handle
+            .submit_tool_approval(
+                session_id,
+                stale_request_id,
+                ApprovalDecision::Approved,
+                None,
+            )
+            .await
+            .unwrap();
+        let state = service.handle.get_session_state(session_id).await.unwrap();
+        assert_eq!(
+            state.current_operation.as_ref().map(|op| op.op_id),
+            Some(op_id)
+        );
+        let pending = state
+            .pending_approval
+            .expect("approval should be requested again");
+        assert_ne!(pending.request_id, stale_request_id);
+        assert_eq!(pending.tool_call.id, "tc_1");
+
+        let events = event_store.load_events(session_id).await.unwrap();
+        let last_request = events.iter().rev().find_map(|(_, event)| match event {
+            SessionEvent::ApprovalRequested { request_id, .. } => Some(*request_id),
+            _ => None,
+        });
+        assert_eq!(last_request, Some(pending.request_id));
+        assert!(!events.iter().any(|(_, event)| matches!(
+            event,
+            SessionEvent::ApprovalDecided { request_id, .. } if *request_id == stale_request_id
+        )));
+
+        service.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_session_limit_applies_to_loaded_sessions_only() {
+        let (event_store, api_client, tool_executor) = create_test_deps().await;
+        let service = RuntimeService::spawn_with_config(
+            event_store,
+            api_client,
+            tool_executor,
+            RuntimeConfig {
+                max_concurrent_sessions: Some(1),
+                ..RuntimeConfig::default()
+            },
+        );
+
+        let first = service
+            .handle
+            .create_session(test_session_config())
+            .await
+            .unwrap();
+
+        // The idle first session is unloaded to make room.
+        let second = service
+            .handle
+            .create_session(test_session_config())
+            .await
+            .unwrap();
+        assert!(!service.handle.is_session_active(first).await.unwrap());
+        assert!(service.handle.is_session_active(second).await.unwrap());
+
+        // A session with a subscriber is not idle, so nothing can be unloaded.
+        let _subscription = service.handle.subscribe_events(second).await.unwrap();
+        let result = service.handle.resume_session(first).await;
+        assert!(matches!(
+            result,
+            Err(RuntimeError::SessionLimitReached { limit: 1 })
+        ));
+        assert!(service.handle.is_session_active(second).await.unwrap());
+
+        service.shutdown().await;
+    }
+
     #[tokio::test]
     async fn test_delete_session() {
         let (event_store, api_client, tool_executor) = create_test_deps().await;
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::app::domain::event::{OperationTiming, TimingSpan, TimingSpanKind};
+use crate::app::domain::types::OpId;
+
+/// Wall-clock spans recorded while a session's operations run, summarized
+/// into an [`OperationTiming`] when each one completes.
+///
+/// Cloned into the tasks that call models and run tools, which record their
+/// own spans.
+#[derive(Clone, Default)]
+pub(crate) struct OperationTimers {
+    inner: Arc<Mutex<Timers>>,
+}
+
+#[derive(Default)]
+struct Timers {
+    queued_since: HashMap<OpId, Instant>,
+    running: HashMap<OpId, RunningOperation>,
+}
+
+struct RunningOperation {
+    started: Instant,
+    spans: Vec<TimingSpan>,
+    approval_requested: Option<Instant>,
+}
+
+impl RunningOperation {
+    fn push(&mut self, kind: TimingSpanKind, duration: Duration) {
+        self.spans.push(TimingSpan {
+            kind,
+            duration_ms: millis(duration),
+        });
+    }
+}
+
+impl OperationTimers {
+    fn with<R>(&self, f: impl FnOnce(&mut Timers) -> R) -> R {
+        let mut timers = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        f(&mut timers)
+    }
+
+    fn with_running(&self, op_id: OpId, f: impl FnOnce(&mut RunningOperation)) {
+        self.with(|timers| {
+            if let Some(operation) = timers.running.get_mut(&op_id) {
+                f(operation);
+            }
+        });
+    }
+
+    /// Note that `op_id` is waiting in the queue. Repeated calls keep the
+    /// first time it was seen.
+    pub(crate) fn queued(&self, op_id: OpId) {
+        self.with(|timers| {
+            timers
+                .queued_since
+                .entry(op_id)
+                .or_insert_with(Instant::now);
+        });
+    }
+
+    pub(crate) fn started(&self, op_id: OpId) {
+        self.with(|timers| {
+            let mut operation = RunningOperation {
+                started: Instant::now(),
+                spans: Vec::new(),
+                approval_requested: None,
+            };
+            if let Some(queued_since) = timers.queued_since.remove(&op_id) {
+                operation.push(TimingSpanKind::QueueWait, queued_since.elapsed());
+            }
+            timers.running.insert(op_id, operation);
+        });
+    }
+
+    pub(crate) fn record(&self, op_id: OpId, kind: TimingSpanKind, duration: Duration) {
+        self.with_running(op_id, |operation| operation.push(kind, duration));
+    }
+
+    /// Record a model call sent at `sent`. `first_output` is when its first
+    /// streamed delta arrived, if any did.
+    pub(crate) fn record_model_call(
+        &self,
+        op_id: OpId,
+        sent: Instant,
+        first_output: Option<Instant>,
+    ) {
+        let finished = Instant::now();
+        self.with_running(op_id, |operation| match first_output {
+            Some(first_output) => {
+                operation.push(
+                    TimingSpanKind::TimeToFirstToken,
+                    first_output.saturating_duration_since(sent),
+                );
+                operation.push(
+                    TimingSpanKind::Streaming,
+                    finished.saturating_duration_since(first_output),
+                );
+            }
+            None => operation.push(
+                TimingSpanKind::TimeToFirstToken,
+                finished.saturating_duration_since(sent),
+            ),
+        });
+    }
+
+    pub(crate) fn approval_requested(&self, op_id: OpId) {
+        self.with_running(op_id, |operation| {
+            operation.approval_requested = Some(Instant::now());
+        });
+    }
+
+    pub(crate) fn approval_decided(&self, op_id: OpId) {
+        self.with_running(op_id, |operation| {
+            if let Some(requested) = operation.approval_requested.take() {
+                operation.push(TimingSpanKind::ApprovalWait, requested.elapsed());
+            }
+        });
+    }
+
+    pub(crate) fn finish(&self, op_id: OpId) -> Option<OperationTiming> {
+        let operation = self.with(|timers| timers.running.remove(&op_id))?;
+        Some(OperationTiming {
+            total_ms: millis(operation.started.elapsed()),
+            spans: operation.spans,
+        })
+    }
+
+    pub(crate) fn discard(&self, op_id: OpId) {
+        self.with(|timers| {
+            timers.queued_since.remove(&op_id);
+            timers.running.remove(&op_id);
+        });
+    }
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finish_summarizes_recorded_spans_in_order() {
+        let timers = OperationTimers::default();
+        let op_id = OpId::new();
+
+        timers.queued(op_id);
+        timers.started(op_id);
+        let sent = Instant::now();
+        timers.record_model_call(op_id, sent, Some(sent + Duration::from_millis(5)));
+        timers.approval_requested(op_id);
+        timers.approval_decided(op_id);
+        timers.record(
+            op_id,
+            TimingSpanKind::ToolExecution {
+                tool_name: "bash".to_string(),
+            },
+            Duration::from_millis(120),
+        );
+
+        let timing = timers.finish(op_id).unwrap();
+        let kinds = timing
+            .spans
+            .iter()
+            .map(|span| span.kind.phase())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            [
+                "queue_wait",
+                "time_to_first_token",
+                "streaming",
+                "approval_wait",
+                "tool_execution"
+            ]
+        );
+        assert_eq!(timing.spans[1].duration_ms, 5);
+        assert_eq!(timing.spans[4].duration_ms, 120);
+        assert!(timers.finish(op_id).is_none());
+    }
+
+    #[test]
+    fn spans_for_unknown_or_discarded_operations_are_ignored() {
+        let timers = OperationTimers::default();
+        let op_id = OpId::new();
+
+        timers.record(op_id, TimingSpanKind::Streaming, Duration::from_millis(1));
+        assert!(timers.finish(op_id).is_none());
+
+        timers.started(op_id);
+        timers.discard(op_id);
+        assert!(timers.finish(op_id).is_none());
+    }
+}
@@ -0,0 +1,493 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, warn};
+
+use crate::api::provider::TokenUsage;
+use crate::app::domain::event::SessionEvent;
+use crate::app::domain::types::{OpId, SessionId};
+
+use super::supervisor::RuntimeHandle;
+
+/// Header carrying `sha256=<hex hmac>` of the request body when a secret is set.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Steer-Signature";
+
+const DEFAULT_LINK_BASE: &str = "steer://sessions/";
+const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_SUMMARY_CHARS: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventKind {
+    OperationCompleted,
+    OperationFailed,
+    ApprovalRequested,
+    BudgetExceeded,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// Signs each payload with HMAC-SHA256 in the `X-Steer-Signature` header.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    /// Events delivered to this webhook; empty means all of them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<WebhookEventKind>,
+}
+
+impl WebhookConfig {
+    pub fn wants(&self, kind: WebhookEventKind) -> bool {
+        self.events.is_empty() || self.events.contains(&kind)
+    }
+}
+
+/// JSON body POSTed to each webhook.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    pub event: WebhookEventKind,
+    pub session_id: SessionId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub op_id: Option<OpId>,
+    pub summary: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
+    pub link: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Delivers session lifecycle notifications to configured webhooks, retrying
+/// with exponential backoff and logging undeliverable payloads to a
+/// dead-letter file.
+pub struct WebhookNotifier {
+    webhooks: Vec<WebhookConfig>,
+    client: reqwest::Client,
+    dead_letter_path: Option<PathBuf>,
+    link_base: String,
+    max_attempts: u32,
+    initial_backoff: Duration,
+}
+
+impl WebhookNotifier {
+    pub fn new(webhooks: Vec<WebhookConfig>, dead_letter_path: Option<PathBuf>) -> Self {
+        Self {
+            webhooks,
+            client: crate::http::client_builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            dead_letter_path,
+            link_base: DEFAULT_LINK_BASE.to_string(),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+        }
+    }
+
+    /// Prefix for the deep link included in payloads; the session id is appended.
+    pub fn with_link_base(mut self, link_base: impl Into<String>) -> Self {
+        self.link_base = link_base.into();
+        self
+    }
+
+    pub fn with_retry(mut self, max_attempts: u32, initial_backoff: Duration) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.webhooks.is_empty()
+    }
+
+    pub fn payload(
+        &self,
+        event: WebhookEventKind,
+        session_id: SessionId,
+        summary: impl Into<String>,
+    ) -> WebhookPayload {
+        WebhookPayload {
+            event,
+            session_id,
+            op_id: None,
+            summary: truncate_summary(summary.into()),
+            usage: None,
+            link: format!("{}{session_id}", self.link_base),
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Queue `payload` for every webhook subscribed to its event. Delivery
+    /// happens in the background.
+    pub fn notify(self: &Arc<Self>, payload: WebhookPayload) {
+        for index in 0..self.webhooks.len() {
+            if !self.webhooks[index].wants(payload.event) {
+                continue;
+            }
+            let notifier = self.clone();
+            let payload = payload.clone();
+            tokio::spawn(async move {
+                notifier.deliver(&notifier.webhooks[index], &payload).await;
+            });
+        }
+    }
+
+    /// Forward lifecycle events from every session on `runtime` until
+    /// `shutdown` is cancelled.
+    pub fn spawn(
+        self: Arc<Self>,
+        runtime: &RuntimeHandle,
+        shutdown: CancellationToken,
+    ) -> JoinHandle<()> {
+        let mut events = runtime.subscribe_all_events();
+        tokio::spawn(async move {
+            let mut tracker = OperationTracker::default();
+            loop {
+                let event = tokio::select! {
+                    () = shutdown.cancelled() => break,
+                    event = events.recv() => event,
+                };
+                match event {
+                    Ok(event) => {
+                        if let Some(notification) =
+                            tracker.observe(event.session_id, &event.envelope.event)
+                        {
+                            let mut payload = self.payload(
+                                notification.kind,
+                                event.session_id,
+                                notification.summary,
+                            );
+                            payload.op_id = notification.op_id;
+                            payload.usage = notification.usage;
+                            self.notify(payload);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!(
+                            lagged = n,
+                            "Webhook listener lagged; some events were dropped"
+                        );
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+            debug!("Webhook listener stopped");
+        })
+    }
+
+    async fn deliver(&self, webhook: &WebhookConfig, payload: &WebhookPayload) {
+        let body = match serde_json::to_vec(payload) {
+            Ok(body) => body,
+            Err(e) => {
+                warn!(error = %e, "Failed to serialize webhook payload");
+                return;
+            }
+        };
+
+        let mut backoff = self.initial_backoff;
+        let mut last_error = String::new();
+        for attempt in 1..=self.max_attempts {
+            match self.post(webhook, &body).await {
+                Ok(()) => {
+                    debug!(url = %webhook.url, event = ?payload.event, "Delivered webhook");
+                    return;
+                }
+                Err(e) => {
+                    warn!(url = %webhook.url, attempt, error = %e, "Webhook delivery failed");
+                    last_error = e;
+                }
+            }
+            if attempt < self.max_attempts {
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        }
+
+        self.dead_letter(webhook, payload, &last_error).await;
+    }
+
+    async fn post(&self, webhook: &WebhookConfig, body: &[u8]) -> Result<(), String> {
+        let mut request = self
+            .client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_vec());
+        if let Some(signature) = webhook
+            .secret
+            .as_deref()
+            .and_then(|secret| sign(secret, body))
+        {
+            request = request.header(WEBHOOK_SIGNATURE_HEADER, signature);
+        }
+
+        let response = request.send().await.map_err(|e| e.to_string())?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("server responded with {}", response.status()))
+        }
+    }
+
+    async fn dead_letter(&self, webhook: &WebhookConfig, payload: &WebhookPayload, error: &str) {
+        let Some(path) = &self.dead_letter_path else {
+            warn!(url = %webhook.url, "Dropping undeliverable webhook");
+            return;
+        };
+
+        let entry = serde_json::json!({
+            "url": webhook.url,
+            "error": error,
+            "failed_at": Utc::now(),
+            "payload": payload,
+        });
+        let result = async {
+            let mut file = tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .await?;
+            file.write_all(format!("{entry}\n").as_bytes()).await?;
+            file.flush().await
+        }
+        .await;
+
+        if let Err(e) = result {
+            warn!(path = %path.display(), error = %e, "Failed to write webhook dead letter");
+        }
+    }
+}
+
+/// `sha256=<hex>` HMAC of `body` keyed by `secret`.
+pub fn sign(secret: &str, body: &[u8]) -> Option<String> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).ok()?;
+    mac.update(body);
+    Some(format!(
+        "sha256={}",
+        hex::encode(mac.finalize().into_bytes())
+    ))
+}
+
+fn truncate_summary(summary: String) -> String {
+    match summary.char_indices().nth(MAX_SUMMARY_CHARS) {
+        Some((end, _)) => format!("{}…", &summary[..end]),
+        None => summary,
+    }
+}
+
+struct Notification {
+    kind: WebhookEventKind,
+    op_id: Option<OpId>,
+    summary: String,
+    usage: Option<TokenUsage>,
+}
+
+#[derive(Default)]
+struct OperationProgress {
+    op_id: Option<OpId>,
+    usage: Option<TokenUsage>,
+    last_reply: Option<String>,
+}
+
+/// Accumulates per-operation usage and the latest assistant reply so that
+/// completion and failure notifications can report them.
+#[derive(Default)]
+struct OperationTracker {
+    sessions: HashMap<SessionId, OperationProgress>,
+}
+
+impl OperationTracker {
+    fn observe(&mut self, session_id: SessionId, event: &SessionEvent) -> Option<Notification> {
+        match event {
+            SessionEvent::OperationStarted { op_id, .. } => {
+                self.sessions.insert(
+                    session_id,
+                    OperationProgress {
+                        op_id: Some(*op_id),
+                        ..OperationProgress::default()
+                    },
+                );
+                None
+            }
+            SessionEvent::LlmUsageUpdated { usage, .. } => {
+                let progress = self.sessions.entry(session_id).or_default();
+                progress.usage = Some(match progress.usage {
+                    Some(total) => TokenUsage::new(
+                        total.input_tokens.saturating_add(usage.input_tokens),
+                        total.output_tokens.saturating_add(usage.output_tokens),
+                        total.total_tokens.saturating_add(usage.total_tokens),
+                    ),
+                    None => *usage,
+                });
+                None
+            }
+            SessionEvent::AssistantMessageAdded { message, .. } => {
+                let text = message.extract_text();
+                if !text.trim().is_empty() {
+                    self.sessions.entry(session_id).or_default().last_reply = Some(text);
+                }
+                None
+            }
+            SessionEvent::OperationCompleted { op_id, .. } => {
+                let progress = self.sessions.remove(&session_id).unwrap_or_default();
+                Some(Notification {
+                    kind: WebhookEventKind::OperationCompleted,
+                    op_id: Some(*op_id),
+                    summary: progress
+                        .last_reply
+                        .unwrap_or_else(|| "Operation completed".to_string()),
+                    usage: progress.usage,
+                })
+            }
+            SessionEvent::OperationCancelled { .. } => {
+                self.sessions.remove(&session_id);
+                None
+            }
+            SessionEvent::Error { message } | SessionEvent::Refused { reason: message } => {
+                let progress = self.sessions.remove(&session_id).unwrap_or_default();
+                Some(Notification {
+                    kind: WebhookEventKind::OperationFailed,
+                    op_id: progress.op_id,
+                    summary: message.clone(),
+                    usage: progress.usage,
+                })
+            }
+            SessionEvent::ApprovalRequested { tool_call, .. } => {
+                let progress = self.sessions.get(&session_id);
+                Some(Notification {
+                    kind: WebhookEventKind::ApprovalRequested,
+                    op_id: progress.and_then(|progress| progress.op_id),
+                    summary: format!("Approval requested for tool '{}'", tool_call.name),
+                    usage: None,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::conversation::{AssistantContent, Message, MessageData};
+    use crate::app::domain::event::SessionEvent;
+    use crate::config::model::builtin;
+
+    #[test]
+    fn completion_reports_last_reply_and_summed_usage() {
+        let mut tracker = OperationTracker::default();
+        let session_id = SessionId::new();
+        let op_id = OpId::new();
+
+        let events = [
+            SessionEvent::OperationStarted {
+                op_id,
+                kind: crate::app::domain::event::OperationKind::AgentLoop,
+            },
+            SessionEvent::LlmUsageUpdated {
+                op_id,
+                model: builtin::claude_sonnet_4_5(),
+                usage: TokenUsage::new(100, 20, 120),
+                context_window: None,
+            },
+            SessionEvent::AssistantMessageAdded {
+                message: Message {
+                    data: MessageData::Assistant {
+                        content: vec![AssistantContent::Text {
+                            text: "All tests pass.".to_string(),
+                        }],
+                    },
+                    timestamp: 0,
+                    id: "msg_1".to_string(),
+                    parent_message_id: None,
+                },
+                model: builtin::claude_sonnet_4_5(),
+            },
+            SessionEvent::LlmUsageUpdated {
+                op_id,
+                model: builtin::claude_sonnet_4_5(),
+                usage: TokenUsage::new(50, 10, 60),
+                context_window: None,
+            },
+        ];
+        for event in &events {
+            assert!(tracker.observe(session_id, event).is_none());
+        }
+
+        let notification = tracker
+            .observe(
+                session_id,
+                &SessionEvent::OperationCompleted {
+                    op_id,
+                    timing: None,
+                },
+            )
+            .expect("completion notifies");
+        assert_eq!(notification.kind, WebhookEventKind::OperationCompleted);
+        assert_eq!(notification.op_id, Some(op_id));
+        assert_eq!(notification.summary, "All tests pass.");
+        assert_eq!(notification.usage, Some(TokenUsage::new(150, 30, 180)));
+    }
+
+    #[test]
+    fn webhook_event_filter() {
+        let webhook = WebhookConfig {
+            url: "http://localhost/hook".to_string(),
+            secret: None,
+            events: vec![WebhookEventKind::OperationFailed],
+        };
+
+        assert!(webhook.wants(WebhookEventKind::OperationFailed));
+        assert!(!webhook.wants(WebhookEventKind::OperationCompleted));
+    }
+
+    #[test]
+    fn signature_is_hex_hmac_sha256() {
+        assert_eq!(
+            sign("key", b"The quick brown fox jumps over the lazy dog"),
+            Some(
+                "sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+                    .to_string()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn undeliverable_payload_is_dead_lettered() {
+        let dir = tempfile::tempdir().unwrap();
+        let dead_letter_path = dir.path().join("dead_letters.jsonl");
+        let webhook = WebhookConfig {
+            // Nothing listens on port 9 (discard) on test hosts.
+            url: "http://127.0.0.1:9/hook".to_string(),
+            secret: Some("s3cret".to_string()),
+            events: Vec::new(),
+        };
+        let notifier = WebhookNotifier::new(vec![webhook.clone()], Some(dead_letter_path.clone()))
+            .with_retry(2, Duration::from_millis(1));
+
+        let payload = notifier.payload(
+            WebhookEventKind::OperationFailed,
+            SessionId::new(),
+            "model request failed",
+        );
+        notifier.deliver(&webhook, &payload).await;
+
+        let contents = std::fs::read_to_string(&dead_letter_path).unwrap();
+        let entry: serde_json::Value = serde_json::from_str(contents.trim()).unwrap();
+        assert_eq!(entry["url"], "http://127.0.0.1:9/hook");
+        assert_eq!(entry["payload"]["event"], "operation_failed");
+        assert_eq!(entry["payload"]["summary"], "model request failed");
+    }
+}
@@ -0,0 +1,113 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::app::domain::types::SessionId;
+
+#[derive(Debug, Error)]
+pub enum ToolAuditStoreError {
+    #[error("Database error: {message}")]
+    Database { message: String },
+
+    #[error("Serialization error: {message}")]
+    Serialization { message: String },
+}
+
+impl ToolAuditStoreError {
+    pub fn database(message: impl Into<String>) -> Self {
+        Self::Database {
+            message: message.into(),
+        }
+    }
+
+    pub fn serialization(message: impl Into<String>) -> Self {
+        Self::Serialization {
+            message: message.into(),
+        }
+    }
+}
+
+/// Who allowed a tool call to run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolApprover {
+    /// The user answered an approval prompt, or ran the command themselves.
+    User,
+    /// The session's tool policy or a remembered approval allowed it.
+    Policy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolAuditStatus {
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+/// One tool execution, as recorded in the audit log.
+///
+/// Entries are kept apart from the session's events, so they outlive
+/// compaction and session deletion until retention removes them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolAuditEntry {
+    pub session_id: SessionId,
+    pub tool_call_id: String,
+    pub tool_name: String,
+    /// Hex SHA-256 of the parameters as the model sent them, before redaction.
+    pub parameters_hash: String,
+    /// The parameters with secrets replaced by `[REDACTED]`.
+    pub parameters: Value,
+    /// Workspace path or remote address the tool ran against.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+    pub status: ToolAuditStatus,
+    pub duration_ms: u64,
+    pub approved_by: ToolApprover,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+}
+
+/// Narrows an audit log query; unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct ToolAuditFilter {
+    pub session_id: Option<SessionId>,
+    pub tool_name: Option<String>,
+    /// Only entries for executions that started at or after this time.
+    pub since: Option<DateTime<Utc>>,
+}
+
+impl ToolAuditFilter {
+    pub fn matches(&self, entry: &ToolAuditEntry) -> bool {
+        self.session_id.is_none_or(|id| entry.session_id == id)
+            && self
+                .tool_name
+                .as_deref()
+                .is_none_or(|name| entry.tool_name == name)
+            && self.since.is_none_or(|since| entry.started_at >= since)
+    }
+}
+
+/// Append-only record of the tools sessions have run.
+#[async_trait]
+pub trait ToolAuditStore: Send + Sync {
+    async fn record_tool_execution(
+        &self,
+        entry: &ToolAuditEntry,
+    ) -> Result<(), ToolAuditStoreError>;
+
+    /// Matching entries, oldest first.
+    async fn list_tool_executions(
+        &self,
+        filter: &ToolAuditFilter,
+    ) -> Result<Vec<ToolAuditEntry>, ToolAuditStoreError>;
+
+    /// Drop entries for executions that started before `cutoff`, returning
+    /// how many were removed. This is the only way entries are deleted.
+    async fn prune_tool_executions(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<u64, ToolAuditStoreError>;
+}
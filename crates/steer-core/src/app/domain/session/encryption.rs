@@ -0,0 +1,169 @@
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::Argon2;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+
+use super::event_store::EventStoreError;
+use crate::preferences::{Preferences, SessionEncryption};
+
+/// Environment variable holding the passphrase for `encryption = "passphrase"`.
+pub const SESSION_PASSPHRASE_ENV_VAR: &str = "STEER_SESSION_PASSPHRASE";
+
+const ENCRYPTED_PREFIX: &str = "enc:v1:";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+pub(super) const SALT_LEN: usize = 16;
+const KEYRING_SERVICE: &str = "steer";
+const KEYRING_USER: &str = "session-db-key";
+
+/// Where the key for an encrypted session store comes from.
+#[derive(Clone)]
+pub enum SessionKeySource {
+    Keyring,
+    Passphrase(String),
+}
+
+impl std::fmt::Debug for SessionKeySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Keyring => f.write_str("Keyring"),
+            Self::Passphrase(_) => f.write_str("Passphrase(..)"),
+        }
+    }
+}
+
+impl SessionKeySource {
+    /// The key source selected by `[storage] encryption`, if any.
+    pub fn from_preferences(preferences: &Preferences) -> Result<Option<Self>, EventStoreError> {
+        match preferences.storage.encryption {
+            SessionEncryption::Off => Ok(None),
+            SessionEncryption::Keyring => Ok(Some(Self::Keyring)),
+            SessionEncryption::Passphrase => match std::env::var(SESSION_PASSPHRASE_ENV_VAR) {
+                Ok(passphrase) if !passphrase.is_empty() => Ok(Some(Self::Passphrase(passphrase))),
+                _ => Err(EventStoreError::encryption(format!(
+                    "Session encryption is set to passphrase but {SESSION_PASSPHRASE_ENV_VAR} is not set"
+                ))),
+            },
+        }
+    }
+
+    /// The key source from the user's preferences file.
+    pub fn configured() -> Result<Option<Self>, EventStoreError> {
+        Self::from_preferences(&Preferences::load().unwrap_or_default())
+    }
+
+    /// Derive the store cipher. A keyring key is generated on first use only
+    /// when `create` is set, so an existing database never gets a fresh key.
+    pub(super) async fn cipher(
+        &self,
+        salt: &[u8],
+        create: bool,
+    ) -> Result<SessionCipher, EventStoreError> {
+        let key = match self {
+            Self::Passphrase(passphrase) => {
+                let mut key = [0u8; KEY_LEN];
+                Argon2::default()
+                    .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+                    .map_err(|e| {
+                        EventStoreError::encryption(format!("Failed to derive key: {e}"))
+                    })?;
+                key
+            }
+            Self::Keyring => tokio::task::spawn_blocking(move || keyring_key(create))
+                .await
+                .map_err(|e| EventStoreError::encryption(format!("Keyring task failed: {e}")))??,
+        };
+        Ok(SessionCipher::new(&key))
+    }
+}
+
+fn keyring_key(create: bool) -> Result<[u8; KEY_LEN], EventStoreError> {
+    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .map_err(|e| EventStoreError::encryption(format!("Failed to open keyring: {e}")))?;
+    match entry.get_password() {
+        Ok(encoded) => {
+            let bytes = BASE64.decode(encoded.trim()).map_err(|e| {
+                EventStoreError::encryption(format!("Keyring session key is corrupt: {e}"))
+            })?;
+            bytes.try_into().map_err(|_| {
+                EventStoreError::encryption("Keyring session key has the wrong length")
+            })
+        }
+        Err(keyring::Error::NoEntry) if create => {
+            let mut key = [0u8; KEY_LEN];
+            OsRng.fill_bytes(&mut key);
+            entry.set_password(&BASE64.encode(key)).map_err(|e| {
+                EventStoreError::encryption(format!("Failed to store session key in keyring: {e}"))
+            })?;
+            Ok(key)
+        }
+        Err(keyring::Error::NoEntry) => Err(EventStoreError::encryption(
+            "The session database is encrypted but its key is missing from the OS keyring",
+        )),
+        Err(e) => Err(EventStoreError::encryption(format!(
+            "Failed to read session key from keyring: {e}"
+        ))),
+    }
+}
+
+pub(super) fn random_salt() -> [u8; SALT_LEN] {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    salt
+}
+
+/// AES-256-GCM over individual stored values. Each value gets a fresh nonce
+/// and is stored as `enc:v1:<base64(nonce || ciphertext)>`.
+pub(super) struct SessionCipher {
+    cipher: Aes256Gcm,
+}
+
+impl SessionCipher {
+    fn new(key: &[u8; KEY_LEN]) -> Self {
+        Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)),
+        }
+    }
+
+    pub(super) fn encrypt(&self, plaintext: &str) -> Result<String, EventStoreError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|_| EventStoreError::encryption("Failed to encrypt session data"))?;
+
+        let mut sealed = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(format!("{ENCRYPTED_PREFIX}{}", BASE64.encode(sealed)))
+    }
+
+    pub(super) fn decrypt(&self, stored: &str) -> Result<String, EventStoreError> {
+        let encoded = stored.strip_prefix(ENCRYPTED_PREFIX).ok_or_else(|| {
+            EventStoreError::encryption("Stored value is not encrypted session data")
+        })?;
+        let sealed = BASE64
+            .decode(encoded)
+            .map_err(|e| EventStoreError::encryption(format!("Corrupt encrypted value: {e}")))?;
+        if sealed.len() < NONCE_LEN {
+            return Err(EventStoreError::encryption("Corrupt encrypted value"));
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                EventStoreError::encryption(
+                    "Failed to decrypt session data: wrong passphrase or key",
+                )
+            })?;
+        String::from_utf8(plaintext)
+            .map_err(|e| EventStoreError::encryption(format!("Decrypted value is not UTF-8: {e}")))
+    }
+}
+
+pub(super) fn is_encrypted(stored: &str) -> bool {
+    stored.starts_with(ENCRYPTED_PREFIX)
+}
@@ -5,8 +5,10 @@ use thiserror::Error;
 use crate::app::domain::event::SessionEvent;
 use crate::app::domain::types::SessionId;
 use crate::session::state::SessionConfig;
+use steer_tools::tools::memory::MemoryEntry;
 use steer_tools::tools::todo::TodoItem;
 
+use super::audit_store::{ToolAuditEntry, ToolAuditFilter, ToolAuditStore, ToolAuditStoreError};
 use super::metadata_store::{
     SessionFilter, SessionMetadataStore, SessionMetadataStoreError, SessionSummary,
 };
@@ -30,6 +32,9 @@ pub enum EventStoreError {
 
     #[error("In-memory store lock poisoned: {message}")]
     LockPoisoned { message: String },
+
+    #[error("Encryption error: {message}")]
+    Encryption { message: String },
 }
 
 impl EventStoreError {
@@ -51,6 +56,12 @@ impl EventStoreError {
         }
     }
 
+    pub fn encryption(message: impl Into<String>) -> Self {
+        Self::Encryption {
+            message: message.into(),
+        }
+    }
+
     pub fn lock_poisoned(message: impl Into<String>) -> Self {
         Self::LockPoisoned {
             message: message.into(),
@@ -104,12 +115,32 @@ pub trait EventStore: Send + Sync {
         session_id: SessionId,
         todos: &[TodoItem],
     ) -> Result<(), EventStoreError>;
+
+    /// Session memory in the order entries were first remembered.
+    async fn load_memory(&self, session_id: SessionId)
+    -> Result<Vec<MemoryEntry>, EventStoreError>;
+
+    /// Stores `entry`, replacing the content of an existing entry with the same key.
+    async fn save_memory_entry(
+        &self,
+        session_id: SessionId,
+        entry: &MemoryEntry,
+    ) -> Result<(), EventStoreError>;
+
+    /// Returns whether an entry with `key` existed.
+    async fn delete_memory_entry(
+        &self,
+        session_id: SessionId,
+        key: &str,
+    ) -> Result<bool, EventStoreError>;
 }
 
 pub struct InMemoryEventStore {
     events: std::sync::RwLock<std::collections::HashMap<SessionId, Vec<(u64, SessionEvent)>>>,
     catalog: std::sync::RwLock<std::collections::HashMap<SessionId, InMemoryCatalogEntry>>,
     todos: std::sync::RwLock<std::collections::HashMap<SessionId, Vec<TodoItem>>>,
+    memory: std::sync::RwLock<std::collections::HashMap<SessionId, Vec<MemoryEntry>>>,
+    tool_audit: std::sync::RwLock<Vec<ToolAuditEntry>>,
 }
 
 struct InMemoryCatalogEntry {
@@ -119,6 +150,7 @@ struct InMemoryCatalogEntry {
     message_count: u32,
     last_model: Option<String>,
     title: Option<String>,
+    summary: Option<String>,
 }
 
 fn catalog_update_for_event(
@@ -158,6 +190,7 @@ fn apply_catalog_update(
 
     if let Some(config) = config {
         entry.title.clone_from(&config.title);
+        entry.summary = config.summary().map(String::from);
         entry.config = config.clone();
         updated = true;
     }
@@ -183,6 +216,8 @@ impl InMemoryEventStore {
             events: std::sync::RwLock::new(std::collections::HashMap::new()),
             catalog: std::sync::RwLock::new(std::collections::HashMap::new()),
             todos: std::sync::RwLock::new(std::collections::HashMap::new()),
+            memory: std::sync::RwLock::new(std::collections::HashMap::new()),
+            tool_audit: std::sync::RwLock::new(Vec::new()),
         }
     }
 }
@@ -228,6 +263,7 @@ impl EventStore for InMemoryEventStore {
                         message_count: 0,
                         last_model: None,
                         title: config.title.clone(),
+                        summary: config.summary().map(String::from),
                     },
                 );
             }
@@ -315,6 +351,13 @@ impl EventStore for InMemoryEventStore {
             .write()
             .map_err(|_| EventStoreError::lock_poisoned("todos"))?;
         todos.remove(&session_id);
+        drop(todos);
+
+        let mut memory = self
+            .memory
+            .write()
+            .map_err(|_| EventStoreError::lock_poisoned("memory"))?;
+        memory.remove(&session_id);
         Ok(())
     }
 
@@ -349,6 +392,54 @@ impl EventStore for InMemoryEventStore {
         store.insert(session_id, todos.to_vec());
         Ok(())
     }
+
+    async fn load_memory(
+        &self,
+        session_id: SessionId,
+    ) -> Result<Vec<MemoryEntry>, EventStoreError> {
+        let memory = self
+            .memory
+            .read()
+            .map_err(|_| EventStoreError::lock_poisoned("memory"))?;
+        Ok(memory.get(&session_id).cloned().unwrap_or_default())
+    }
+
+    async fn save_memory_entry(
+        &self,
+        session_id: SessionId,
+        entry: &MemoryEntry,
+    ) -> Result<(), EventStoreError> {
+        let mut memory = self
+            .memory
+            .write()
+            .map_err(|_| EventStoreError::lock_poisoned("memory"))?;
+        let entries = memory.entry(session_id).or_default();
+        match entries
+            .iter_mut()
+            .find(|existing| existing.key == entry.key)
+        {
+            Some(existing) => existing.content.clone_from(&entry.content),
+            None => entries.push(entry.clone()),
+        }
+        Ok(())
+    }
+
+    async fn delete_memory_entry(
+        &self,
+        session_id: SessionId,
+        key: &str,
+    ) -> Result<bool, EventStoreError> {
+        let mut memory = self
+            .memory
+            .write()
+            .map_err(|_| EventStoreError::lock_poisoned("memory"))?;
+        let Some(entries) = memory.get_mut(&session_id) else {
+            return Ok(false);
+        };
+        let before = entries.len();
+        entries.retain(|entry| entry.key != key);
+        Ok(entries.len() < before)
+    }
 }
 
 #[async_trait]
@@ -379,6 +470,8 @@ impl SessionMetadataStore for InMemoryEventStore {
             message_count: e.message_count,
             last_model: e.last_model.clone(),
             title: e.title.clone(),
+            summary: e.summary.clone(),
+            workspace_path: e.config.workspace.get_path(),
         }))
     }
 
@@ -399,6 +492,8 @@ impl SessionMetadataStore for InMemoryEventStore {
                 message_count: e.message_count,
                 last_model: e.last_model.clone(),
                 title: e.title.clone(),
+                summary: e.summary.clone(),
+                workspace_path: e.config.workspace.get_path(),
             })
             .collect();
 
@@ -430,6 +525,7 @@ impl SessionMetadataStore for InMemoryEventStore {
             if let Some(cfg) = config {
                 entry.config = cfg.clone();
                 entry.title.clone_from(&cfg.title);
+                entry.summary = cfg.summary().map(String::from);
             }
             if increment_message_count {
                 entry.message_count += 1;
@@ -449,6 +545,7 @@ impl SessionMetadataStore for InMemoryEventStore {
                     message_count: u32::from(increment_message_count),
                     last_model: new_model.map(String::from),
                     title: cfg.title.clone(),
+                    summary: cfg.summary().map(String::from),
                 },
             );
         }
@@ -457,6 +554,50 @@ impl SessionMetadataStore for InMemoryEventStore {
     }
 }
 
+#[async_trait]
+impl ToolAuditStore for InMemoryEventStore {
+    async fn record_tool_execution(
+        &self,
+        entry: &ToolAuditEntry,
+    ) -> Result<(), ToolAuditStoreError> {
+        self.tool_audit
+            .write()
+            .map_err(|_| ToolAuditStoreError::database("Tool audit lock poisoned"))?
+            .push(entry.clone());
+        Ok(())
+    }
+
+    async fn list_tool_executions(
+        &self,
+        filter: &ToolAuditFilter,
+    ) -> Result<Vec<ToolAuditEntry>, ToolAuditStoreError> {
+        let entries = self
+            .tool_audit
+            .read()
+            .map_err(|_| ToolAuditStoreError::database("Tool audit lock poisoned"))?;
+        let mut matching: Vec<_> = entries
+            .iter()
+            .filter(|entry| filter.matches(entry))
+            .cloned()
+            .collect();
+        matching.sort_by_key(|entry| entry.started_at);
+        Ok(matching)
+    }
+
+    async fn prune_tool_executions(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<u64, ToolAuditStoreError> {
+        let mut entries = self
+            .tool_audit
+            .write()
+            .map_err(|_| ToolAuditStoreError::database("Tool audit lock poisoned"))?;
+        let before = entries.len();
+        entries.retain(|entry| entry.started_at >= cutoff);
+        Ok(u64::try_from(before - entries.len()).unwrap_or(u64::MAX))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -676,6 +817,54 @@ mod tests {
         assert_eq!(loaded, None);
     }
 
+    #[tokio::test]
+    async fn test_in_memory_store_memory_replaces_and_forgets_by_key() {
+        let store = InMemoryEventStore::new();
+        let session_id = SessionId::new();
+        let entry = |key: &str, content: &str| MemoryEntry {
+            key: key.to_string(),
+            content: content.to_string(),
+        };
+
+        store
+            .save_memory_entry(session_id, &entry("tests", "cargo test"))
+            .await
+            .unwrap();
+        store
+            .save_memory_entry(session_id, &entry("style", "no unwrap"))
+            .await
+            .unwrap();
+        store
+            .save_memory_entry(session_id, &entry("tests", "cargo nextest run"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.load_memory(session_id).await.unwrap(),
+            vec![
+                entry("tests", "cargo nextest run"),
+                entry("style", "no unwrap")
+            ]
+        );
+
+        assert!(
+            store
+                .delete_memory_entry(session_id, "tests")
+                .await
+                .unwrap()
+        );
+        assert!(
+            !store
+                .delete_memory_entry(session_id, "tests")
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            store.load_memory(session_id).await.unwrap(),
+            vec![entry("style", "no unwrap")]
+        );
+    }
+
     #[tokio::test]
     async fn test_in_memory_store_updates_config_on_session_config_updated() {
         let store = InMemoryEventStore::new();
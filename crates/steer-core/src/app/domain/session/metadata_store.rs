@@ -48,6 +48,9 @@ pub struct SessionSummary {
     pub message_count: u32,
     pub last_model: Option<String>,
     pub title: Option<String>,
+    pub summary: Option<String>,
+    /// Workspace the session was started in: a local path or remote address.
+    pub workspace_path: Option<String>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -1,9 +1,21 @@
+pub mod audit_store;
+pub mod encryption;
 pub mod event_store;
 pub mod metadata_store;
 pub mod sqlite_event_store;
+pub mod task_store;
 
+pub use audit_store::{
+    ToolApprover, ToolAuditEntry, ToolAuditFilter, ToolAuditStatus, ToolAuditStore,
+    ToolAuditStoreError,
+};
+pub use encryption::{SESSION_PASSPHRASE_ENV_VAR, SessionKeySource};
 pub use event_store::{EventStore, EventStoreError, InMemoryEventStore};
 pub use metadata_store::{
     SessionFilter, SessionMetadataStore, SessionMetadataStoreError, SessionSummary,
 };
 pub use sqlite_event_store::SqliteEventStore;
+pub use task_store::{
+    ScheduledRunOutcome, ScheduledTask, ScheduledTaskRun, ScheduledTaskStore,
+    ScheduledTaskStoreError,
+};
@@ -11,25 +11,64 @@ use sqlx::{
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
+use super::audit_store::{ToolAuditEntry, ToolAuditFilter, ToolAuditStore, ToolAuditStoreError};
+use super::encryption::{SALT_LEN, SessionCipher, SessionKeySource, is_encrypted, random_salt};
 use super::event_store::{EventStore, EventStoreError};
 use super::metadata_store::{
     SessionFilter, SessionMetadataStore, SessionMetadataStoreError, SessionSummary,
 };
+use super::task_store::{
+    ScheduledTask, ScheduledTaskRun, ScheduledTaskStore, ScheduledTaskStoreError,
+};
 use crate::app::conversation::{
-    AssistantContent, ImageContent, ImageSource, Message, MessageData, UserContent,
+    AssistantContent, AttachmentBody, AttachmentContent, ImageContent, ImageSource, Message,
+    MessageData, UserContent,
 };
 use crate::app::domain::event::SessionEvent;
 use crate::app::domain::types::SessionId;
+use crate::preferences::Preferences;
 use crate::session::state::SessionConfig;
+use steer_tools::tools::memory::MemoryEntry;
 use steer_tools::tools::todo::TodoItem;
 
+const ENCRYPTION_SALT_KEY: &str = "encryption_salt";
+const ENCRYPTION_CHECK_KEY: &str = "encryption_check";
+const ENCRYPTION_CHECK_PLAINTEXT: &str = "steer-session-store";
+
 pub struct SqliteEventStore {
     pool: SqlitePool,
     media_root: Option<PathBuf>,
+    cipher: Option<SessionCipher>,
 }
 
 impl SqliteEventStore {
     pub async fn new(path: &Path) -> Result<Self, EventStoreError> {
+        Self::open(path, None).await
+    }
+
+    /// Open the store with the encryption selected in the user's preferences,
+    /// dropping audit log entries older than the configured retention.
+    pub async fn open_configured(path: &Path) -> Result<Self, EventStoreError> {
+        let preferences = Preferences::load().unwrap_or_default();
+        let store = Self::open(path, SessionKeySource::from_preferences(&preferences)?).await?;
+
+        if let Some(days) = preferences.storage.audit_retention_days {
+            let cutoff = Utc::now() - chrono::Duration::days(i64::from(days));
+            store
+                .prune_tool_executions(cutoff)
+                .await
+                .map_err(|e| EventStoreError::database(e.to_string()))?;
+        }
+
+        Ok(store)
+    }
+
+    /// Open the store, encrypting session data with a key from `key_source`.
+    /// A database that was encrypted can only be opened with its key.
+    pub async fn open(
+        path: &Path,
+        key_source: Option<SessionKeySource>,
+    ) -> Result<Self, EventStoreError> {
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent).map_err(|e| {
                 EventStoreError::connection(format!("Failed to create directory: {e}"))
@@ -51,11 +90,13 @@ impl SqliteEventStore {
                 EventStoreError::connection(format!("Failed to connect to SQLite: {e}"))
             })?;
 
-        let store = Self {
+        let mut store = Self {
             pool,
             media_root: media_root_for_path(path),
+            cipher: None,
         };
         store.run_migrations().await?;
+        store.cipher = store.init_encryption(key_source).await?;
 
         Ok(store)
     }
@@ -78,6 +119,7 @@ impl SqliteEventStore {
         let store = Self {
             pool,
             media_root: None,
+            cipher: None,
         };
         store.run_migrations().await?;
 
@@ -94,7 +136,9 @@ impl SqliteEventStore {
                 config_json TEXT,
                 message_count INTEGER NOT NULL DEFAULT 0,
                 last_model TEXT,
-                title TEXT
+                title TEXT,
+                summary TEXT,
+                workspace_path TEXT
             )
             ",
         )
@@ -152,11 +196,197 @@ impl SqliteEventStore {
             message: format!("Failed to create todos table: {e}"),
         })?;
 
+        // Kept apart from `domain_events` so compaction never drops it.
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS session_memory (
+                session_id TEXT NOT NULL,
+                key TEXT NOT NULL,
+                content TEXT NOT NULL,
+                updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+                PRIMARY KEY (session_id, key),
+                FOREIGN KEY (session_id) REFERENCES domain_sessions(id) ON DELETE CASCADE
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EventStoreError::Migration {
+            message: format!("Failed to create session memory table: {e}"),
+        })?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS scheduled_tasks (
+                id TEXT PRIMARY KEY,
+                task_json TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EventStoreError::Migration {
+            message: format!("Failed to create scheduled tasks table: {e}"),
+        })?;
+
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS store_metadata (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EventStoreError::Migration {
+            message: format!("Failed to create store metadata table: {e}"),
+        })?;
+
+        // No foreign key to `domain_sessions`: entries outlive their session.
+        sqlx::query(
+            r"
+            CREATE TABLE IF NOT EXISTS tool_audit_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL,
+                tool_name TEXT NOT NULL,
+                started_at TEXT NOT NULL,
+                entry_json TEXT NOT NULL
+            )
+            ",
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EventStoreError::Migration {
+            message: format!("Failed to create tool audit log table: {e}"),
+        })?;
+
+        sqlx::query(
+            r"
+            CREATE INDEX IF NOT EXISTS idx_tool_audit_log_session_started
+            ON tool_audit_log(session_id, started_at)
+            ",
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EventStoreError::Migration {
+            message: format!("Failed to create tool audit log index: {e}"),
+        })?;
+
+        sqlx::query(
+            r"
+            CREATE TRIGGER IF NOT EXISTS tool_audit_log_append_only
+            BEFORE UPDATE ON tool_audit_log
+            BEGIN
+                SELECT RAISE(ABORT, 'tool audit log is append-only');
+            END
+            ",
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EventStoreError::Migration {
+            message: format!("Failed to create tool audit log trigger: {e}"),
+        })?;
+
         self.migrate_add_catalog_columns().await?;
 
         Ok(())
     }
 
+    async fn init_encryption(
+        &self,
+        key_source: Option<SessionKeySource>,
+    ) -> Result<Option<SessionCipher>, EventStoreError> {
+        let check = self.store_metadata(ENCRYPTION_CHECK_KEY).await?;
+
+        let Some(key_source) = key_source else {
+            return match check {
+                Some(_) => Err(EventStoreError::encryption(
+                    "The session database is encrypted; set `[storage] encryption` in preferences to open it",
+                )),
+                None => Ok(None),
+            };
+        };
+
+        if let Some(check) = check {
+            let salt = self
+                .store_metadata(ENCRYPTION_SALT_KEY)
+                .await?
+                .ok_or_else(|| EventStoreError::encryption("Encryption salt is missing"))?;
+            let salt = base64::engine::general_purpose::STANDARD
+                .decode(salt)
+                .map_err(|e| {
+                    EventStoreError::encryption(format!("Corrupt encryption salt: {e}"))
+                })?;
+            let cipher = key_source.cipher(&salt, false).await?;
+            if cipher.decrypt(&check)? != ENCRYPTION_CHECK_PLAINTEXT {
+                return Err(EventStoreError::encryption(
+                    "Failed to unlock the session database: wrong passphrase or key",
+                ));
+            }
+            return Ok(Some(cipher));
+        }
+
+        // Enabling encryption on an existing database only affects new writes;
+        // values stored earlier stay readable as plaintext.
+        let salt: [u8; SALT_LEN] = random_salt();
+        let cipher = key_source.cipher(&salt, true).await?;
+        let check = cipher.encrypt(ENCRYPTION_CHECK_PLAINTEXT)?;
+        sqlx::query("INSERT INTO store_metadata (key, value) VALUES (?1, ?2), (?3, ?4)")
+            .bind(ENCRYPTION_SALT_KEY)
+            .bind(base64::engine::general_purpose::STANDARD.encode(salt))
+            .bind(ENCRYPTION_CHECK_KEY)
+            .bind(&check)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                EventStoreError::database(format!("Failed to record encryption metadata: {e}"))
+            })?;
+
+        Ok(Some(cipher))
+    }
+
+    async fn store_metadata(&self, key: &str) -> Result<Option<String>, EventStoreError> {
+        sqlx::query_scalar("SELECT value FROM store_metadata WHERE key = ?1")
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| EventStoreError::database(format!("Failed to read store metadata: {e}")))
+    }
+
+    /// Encrypt a value for storage when the store is encrypted.
+    fn seal(&self, plaintext: String) -> Result<String, EventStoreError> {
+        match &self.cipher {
+            Some(cipher) => cipher.encrypt(&plaintext),
+            None => Ok(plaintext),
+        }
+    }
+
+    /// Decrypt a stored value. Values written before encryption was enabled
+    /// are returned unchanged.
+    fn unseal(&self, stored: String) -> Result<String, EventStoreError> {
+        if !is_encrypted(&stored) {
+            return Ok(stored);
+        }
+        match &self.cipher {
+            Some(cipher) => cipher.decrypt(&stored),
+            None => Err(EventStoreError::encryption(
+                "Stored session data is encrypted but no key was provided",
+            )),
+        }
+    }
+
+    fn unseal_catalog(
+        &self,
+        stored: Option<String>,
+    ) -> Result<Option<String>, SessionMetadataStoreError> {
+        stored
+            .map(|value| self.unseal(value))
+            .transpose()
+            .map_err(|e| SessionMetadataStoreError::serialization(e.to_string()))
+    }
+
     async fn migrate_add_catalog_columns(&self) -> Result<(), EventStoreError> {
         let has_updated_at: bool = sqlx::query_scalar(
             "SELECT COUNT(*) > 0 FROM pragma_table_info('domain_sessions') WHERE name = 'updated_at'",
@@ -213,6 +443,56 @@ impl SqliteEventStore {
                 })?;
         }
 
+        let has_summary: bool = sqlx::query_scalar(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('domain_sessions') WHERE name = 'summary'",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .unwrap_or(false);
+
+        if !has_summary {
+            sqlx::query("ALTER TABLE domain_sessions ADD COLUMN summary TEXT")
+                .execute(&self.pool)
+                .await
+                .map_err(|e| EventStoreError::Migration {
+                    message: format!("Failed to add summary column: {e}"),
+                })?;
+        }
+
+        let has_workspace_path: bool = sqlx::query_scalar(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('domain_sessions') WHERE name = 'workspace_path'",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .unwrap_or(false);
+
+        if !has_workspace_path {
+            sqlx::query("ALTER TABLE domain_sessions ADD COLUMN workspace_path TEXT")
+                .execute(&self.pool)
+                .await
+                .map_err(|e| EventStoreError::Migration {
+                    message: format!("Failed to add workspace_path column: {e}"),
+                })?;
+
+            // Backfill from the stored config so older sessions can be matched
+            // to their workspace too.
+            sqlx::query(
+                r"
+                UPDATE domain_sessions
+                SET workspace_path = COALESCE(
+                    json_extract(config_json, '$.workspace.path'),
+                    json_extract(config_json, '$.workspace.agent_address')
+                )
+                WHERE config_json IS NOT NULL AND json_valid(config_json)
+                ",
+            )
+            .execute(&self.pool)
+            .await
+            .map_err(|e| EventStoreError::Migration {
+                message: format!("Failed to backfill workspace_path column: {e}"),
+            })?;
+        }
+
         Ok(())
     }
 
@@ -268,6 +548,21 @@ impl SqliteEventStore {
                             changed |= persisted != *image;
                             updated.push(UserContent::Image { image: persisted });
                         }
+                        UserContent::Attachment { attachment } => {
+                            let mut persisted = attachment.clone();
+                            if let AttachmentBody::Image { image } = &mut persisted.body {
+                                *image = Self::persist_image_content(
+                                    session_id,
+                                    &message.id,
+                                    image,
+                                    session_root,
+                                )?;
+                            }
+                            changed |= persisted != *attachment;
+                            updated.push(UserContent::Attachment {
+                                attachment: persisted,
+                            });
+                        }
                         _ => updated.push(block.clone()),
                     }
                 }
@@ -392,8 +687,13 @@ impl SqliteEventStore {
             SessionEvent::WorkspaceChanged => "workspace_changed",
             SessionEvent::QueueUpdated { .. } => "queue_updated",
             SessionEvent::Error { .. } => "error",
+            SessionEvent::Refused { .. } => "refused",
             SessionEvent::McpServerStateChanged { .. } => "mcp_server_state_changed",
             SessionEvent::LlmUsageUpdated { .. } => "llm_usage_updated",
+            SessionEvent::ContextUsage { .. } => "context_usage",
+            SessionEvent::BestOfStarted { .. } => "best_of_started",
+            SessionEvent::BestOfCandidateReady { .. } => "best_of_candidate_ready",
+            SessionEvent::BestOfResolved { .. } => "best_of_resolved",
         }
     }
 }
@@ -454,6 +754,7 @@ impl EventStore for SqliteEventStore {
         let event_data = serde_json::to_string(&prepared_event).map_err(|e| {
             EventStoreError::serialization(format!("Failed to serialize event: {e}"))
         })?;
+        let event_data = self.seal(event_data)?;
 
         let next_seq: i64 = sqlx::query_scalar(
             "SELECT COALESCE(MAX(sequence_num), -1) + 1 FROM domain_events WHERE session_id = ?1",
@@ -519,7 +820,7 @@ impl EventStore for SqliteEventStore {
         let mut events = Vec::with_capacity(rows.len());
         for row in rows {
             let seq: i64 = row.get("sequence_num");
-            let event_data: String = row.get("event_data");
+            let event_data = self.unseal(row.get("event_data"))?;
             let event: SessionEvent = serde_json::from_str(&event_data)
                 .map_err(|e| EventStoreError::serialization(format!("Invalid event data: {e}")))?;
             events.push((seq as u64, event));
@@ -582,7 +883,7 @@ impl EventStore for SqliteEventStore {
         let mut events = Vec::with_capacity(rows.len());
         for row in rows {
             let seq: i64 = row.get("sequence_num");
-            let event_data: String = row.get("event_data");
+            let event_data = self.unseal(row.get("event_data"))?;
             let event: SessionEvent = serde_json::from_str(&event_data)
                 .map_err(|e| EventStoreError::serialization(format!("Invalid event data: {e}")))?;
             events.push((seq as u64, event));
@@ -684,6 +985,7 @@ impl EventStore for SqliteEventStore {
         let todos_json: String = row
             .try_get("todos_json")
             .map_err(|e| EventStoreError::database(format!("Failed to read todos row: {e}")))?;
+        let todos_json = self.unseal(todos_json)?;
 
         let todos: Vec<TodoItem> = serde_json::from_str(&todos_json)
             .map_err(|e| EventStoreError::serialization(format!("Invalid todos JSON: {e}")))?;
@@ -700,6 +1002,7 @@ impl EventStore for SqliteEventStore {
         let todos_json = serde_json::to_string(todos).map_err(|e| {
             EventStoreError::serialization(format!("Failed to serialize todos: {e}"))
         })?;
+        let todos_json = self.seal(todos_json)?;
 
         sqlx::query(
             r"
@@ -718,6 +1021,83 @@ impl EventStore for SqliteEventStore {
 
         Ok(())
     }
+
+    async fn load_memory(
+        &self,
+        session_id: SessionId,
+    ) -> Result<Vec<MemoryEntry>, EventStoreError> {
+        let session_id_str = session_id.0.to_string();
+
+        // Upserts keep their rowid, so this is the order keys were first remembered.
+        let rows = sqlx::query(
+            "SELECT key, content FROM session_memory WHERE session_id = ?1 ORDER BY rowid",
+        )
+        .bind(&session_id_str)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| EventStoreError::database(format!("Failed to load memory: {e}")))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let key: String = row.try_get("key").map_err(|e| {
+                    EventStoreError::database(format!("Failed to read memory row: {e}"))
+                })?;
+                let content: String = row.try_get("content").map_err(|e| {
+                    EventStoreError::database(format!("Failed to read memory row: {e}"))
+                })?;
+                Ok(MemoryEntry {
+                    key,
+                    content: self.unseal(content)?,
+                })
+            })
+            .collect()
+    }
+
+    async fn save_memory_entry(
+        &self,
+        session_id: SessionId,
+        entry: &MemoryEntry,
+    ) -> Result<(), EventStoreError> {
+        let session_id_str = session_id.0.to_string();
+        let content = self.seal(entry.content.clone())?;
+
+        sqlx::query(
+            r"
+            INSERT INTO session_memory (session_id, key, content, updated_at)
+            VALUES (?1, ?2, ?3, datetime('now'))
+            ON CONFLICT(session_id, key) DO UPDATE SET
+                content = excluded.content,
+                updated_at = datetime('now')
+            ",
+        )
+        .bind(&session_id_str)
+        .bind(&entry.key)
+        .bind(&content)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| EventStoreError::database(format!("Failed to save memory entry: {e}")))?;
+
+        Ok(())
+    }
+
+    async fn delete_memory_entry(
+        &self,
+        session_id: SessionId,
+        key: &str,
+    ) -> Result<bool, EventStoreError> {
+        let session_id_str = session_id.0.to_string();
+
+        let result = sqlx::query("DELETE FROM session_memory WHERE session_id = ?1 AND key = ?2")
+            .bind(&session_id_str)
+            .bind(key)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                EventStoreError::database(format!("Failed to delete memory entry: {e}"))
+            })?;
+
+        Ok(result.rows_affected() > 0)
+    }
 }
 
 #[async_trait]
@@ -738,7 +1118,7 @@ impl SessionMetadataStore for SqliteEventStore {
 
         match row {
             Some(row) => {
-                let config_json: Option<String> = row.get("config_json");
+                let config_json = self.unseal_catalog(row.get("config_json"))?;
                 match config_json {
                     Some(json) => {
                         let config: SessionConfig = serde_json::from_str(&json).map_err(|e| {
@@ -762,7 +1142,7 @@ impl SessionMetadataStore for SqliteEventStore {
         let session_id_str = session_id.0.to_string();
 
         let row = sqlx::query(
-            "SELECT id, created_at, updated_at, message_count, last_model, title FROM domain_sessions WHERE id = ?1",
+            "SELECT id, created_at, updated_at, message_count, last_model, title, summary, workspace_path FROM domain_sessions WHERE id = ?1",
         )
         .bind(&session_id_str)
         .fetch_optional(&self.pool)
@@ -776,7 +1156,9 @@ impl SessionMetadataStore for SqliteEventStore {
                 let updated_at_str: String = row.get("updated_at");
                 let message_count: i64 = row.get("message_count");
                 let last_model: Option<String> = row.get("last_model");
-                let title: Option<String> = row.get("title");
+                let title = self.unseal_catalog(row.get("title"))?;
+                let summary = self.unseal_catalog(row.get("summary"))?;
+                let workspace_path: Option<String> = row.get("workspace_path");
 
                 let uuid = uuid::Uuid::parse_str(&id_str).map_err(|e| {
                     SessionMetadataStoreError::serialization(format!("Invalid session ID: {e}"))
@@ -792,6 +1174,8 @@ impl SessionMetadataStore for SqliteEventStore {
                     message_count: message_count as u32,
                     last_model,
                     title,
+                    summary,
+                    workspace_path,
                 }))
             }
             None => Ok(None),
@@ -807,7 +1191,8 @@ impl SessionMetadataStore for SqliteEventStore {
 
         let rows = sqlx::query(
             r"
-            SELECT id, created_at, updated_at, message_count, last_model, title 
+            SELECT id, created_at, updated_at, message_count, last_model, title, summary,
+                workspace_path
             FROM domain_sessions 
             ORDER BY updated_at DESC
             LIMIT ?1 OFFSET ?2
@@ -828,7 +1213,9 @@ impl SessionMetadataStore for SqliteEventStore {
             let updated_at_str: String = row.get("updated_at");
             let message_count: i64 = row.get("message_count");
             let last_model: Option<String> = row.get("last_model");
-            let title: Option<String> = row.get("title");
+            let title = self.unseal_catalog(row.get("title"))?;
+            let summary = self.unseal_catalog(row.get("summary"))?;
+            let workspace_path: Option<String> = row.get("workspace_path");
 
             let uuid = uuid::Uuid::parse_str(&id_str).map_err(|e| {
                 SessionMetadataStoreError::serialization(format!("Invalid session ID: {e}"))
@@ -844,6 +1231,8 @@ impl SessionMetadataStore for SqliteEventStore {
                 message_count: message_count as u32,
                 last_model,
                 title,
+                summary,
+                workspace_path,
             });
         }
 
@@ -864,12 +1253,21 @@ impl SessionMetadataStore for SqliteEventStore {
             let config_json = serde_json::to_string(cfg).map_err(|e| {
                 SessionMetadataStoreError::serialization(format!("Failed to serialize config: {e}"))
             })?;
+            let seal = |value: &str| {
+                self.seal(value.to_string())
+                    .map_err(|e| SessionMetadataStoreError::serialization(e.to_string()))
+            };
+            let config_json = seal(&config_json)?;
+            let title = cfg.title.as_deref().map(seal).transpose()?;
+            let summary = cfg.summary().map(seal).transpose()?;
 
             sqlx::query(
-                "UPDATE domain_sessions SET config_json = ?1, title = ?2, updated_at = ?3 WHERE id = ?4",
+                "UPDATE domain_sessions SET config_json = ?1, title = ?2, summary = ?3, workspace_path = ?4, updated_at = ?5 WHERE id = ?6",
             )
             .bind(&config_json)
-            .bind(cfg.title.as_deref())
+            .bind(title)
+            .bind(summary)
+            .bind(cfg.workspace.get_path())
             .bind(&now)
             .bind(&session_id_str)
             .execute(&self.pool)
@@ -912,6 +1310,191 @@ impl SessionMetadataStore for SqliteEventStore {
     }
 }
 
+#[async_trait]
+impl ScheduledTaskStore for SqliteEventStore {
+    async fn create_task(&self, task: &ScheduledTask) -> Result<(), ScheduledTaskStoreError> {
+        let task_json = self.serialize_scheduled_task(task)?;
+
+        sqlx::query("INSERT INTO scheduled_tasks (id, task_json) VALUES (?1, ?2)")
+            .bind(&task.id)
+            .bind(&task_json)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                ScheduledTaskStoreError::database(format!("Failed to create task: {e}"))
+            })?;
+
+        Ok(())
+    }
+
+    async fn get_task(&self, id: &str) -> Result<Option<ScheduledTask>, ScheduledTaskStoreError> {
+        let row = sqlx::query("SELECT task_json FROM scheduled_tasks WHERE id = ?1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| ScheduledTaskStoreError::database(format!("Failed to get task: {e}")))?;
+
+        row.map(|row| self.parse_scheduled_task(row.get("task_json")))
+            .transpose()
+    }
+
+    async fn list_tasks(&self) -> Result<Vec<ScheduledTask>, ScheduledTaskStoreError> {
+        let rows = sqlx::query("SELECT task_json FROM scheduled_tasks ORDER BY created_at, id")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ScheduledTaskStoreError::database(format!("Failed to list tasks: {e}")))?;
+
+        rows.iter()
+            .map(|row| self.parse_scheduled_task(row.get("task_json")))
+            .collect()
+    }
+
+    async fn delete_task(&self, id: &str) -> Result<bool, ScheduledTaskStoreError> {
+        let result = sqlx::query("DELETE FROM scheduled_tasks WHERE id = ?1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                ScheduledTaskStoreError::database(format!("Failed to delete task: {e}"))
+            })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn record_run(
+        &self,
+        id: &str,
+        run: &ScheduledTaskRun,
+    ) -> Result<(), ScheduledTaskStoreError> {
+        let Some(mut task) = self.get_task(id).await? else {
+            // The task was deleted while it ran; there is nothing to update.
+            return Ok(());
+        };
+        task.last_run = Some(run.clone());
+
+        let task_json = self.serialize_scheduled_task(&task)?;
+
+        sqlx::query("UPDATE scheduled_tasks SET task_json = ?2 WHERE id = ?1")
+            .bind(id)
+            .bind(&task_json)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                ScheduledTaskStoreError::database(format!("Failed to record task run: {e}"))
+            })?;
+
+        Ok(())
+    }
+}
+
+impl SqliteEventStore {
+    fn serialize_scheduled_task(
+        &self,
+        task: &ScheduledTask,
+    ) -> Result<String, ScheduledTaskStoreError> {
+        let task_json = serde_json::to_string(task).map_err(|e| {
+            ScheduledTaskStoreError::serialization(format!("Failed to serialize task: {e}"))
+        })?;
+        self.seal(task_json)
+            .map_err(|e| ScheduledTaskStoreError::serialization(e.to_string()))
+    }
+
+    fn parse_scheduled_task(
+        &self,
+        task_json: String,
+    ) -> Result<ScheduledTask, ScheduledTaskStoreError> {
+        let task_json = self
+            .unseal(task_json)
+            .map_err(|e| ScheduledTaskStoreError::serialization(e.to_string()))?;
+        serde_json::from_str(&task_json).map_err(|e| {
+            ScheduledTaskStoreError::serialization(format!("Failed to parse task: {e}"))
+        })
+    }
+}
+
+#[async_trait]
+impl ToolAuditStore for SqliteEventStore {
+    async fn record_tool_execution(
+        &self,
+        entry: &ToolAuditEntry,
+    ) -> Result<(), ToolAuditStoreError> {
+        let entry_json = serde_json::to_string(entry).map_err(|e| {
+            ToolAuditStoreError::serialization(format!("Failed to serialize audit entry: {e}"))
+        })?;
+        let entry_json = self
+            .seal(entry_json)
+            .map_err(|e| ToolAuditStoreError::serialization(e.to_string()))?;
+
+        sqlx::query(
+            "INSERT INTO tool_audit_log (session_id, tool_name, started_at, entry_json) VALUES (?1, ?2, ?3, ?4)",
+        )
+        .bind(entry.session_id.0.to_string())
+        .bind(&entry.tool_name)
+        .bind(audit_timestamp(entry.started_at))
+        .bind(&entry_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            ToolAuditStoreError::database(format!("Failed to record audit entry: {e}"))
+        })?;
+
+        Ok(())
+    }
+
+    async fn list_tool_executions(
+        &self,
+        filter: &ToolAuditFilter,
+    ) -> Result<Vec<ToolAuditEntry>, ToolAuditStoreError> {
+        let rows = sqlx::query(
+            r"
+            SELECT entry_json FROM tool_audit_log
+            WHERE (?1 IS NULL OR session_id = ?1)
+              AND (?2 IS NULL OR tool_name = ?2)
+              AND (?3 IS NULL OR started_at >= ?3)
+            ORDER BY started_at, id
+            ",
+        )
+        .bind(filter.session_id.map(|id| id.0.to_string()))
+        .bind(filter.tool_name.as_deref())
+        .bind(filter.since.map(audit_timestamp))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| ToolAuditStoreError::database(format!("Failed to list audit entries: {e}")))?;
+
+        rows.iter()
+            .map(|row| {
+                let entry_json = self
+                    .unseal(row.get("entry_json"))
+                    .map_err(|e| ToolAuditStoreError::serialization(e.to_string()))?;
+                serde_json::from_str(&entry_json).map_err(|e| {
+                    ToolAuditStoreError::serialization(format!("Failed to parse audit entry: {e}"))
+                })
+            })
+            .collect()
+    }
+
+    async fn prune_tool_executions(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<u64, ToolAuditStoreError> {
+        let result = sqlx::query("DELETE FROM tool_audit_log WHERE started_at < ?1")
+            .bind(audit_timestamp(cutoff))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                ToolAuditStoreError::database(format!("Failed to prune audit entries: {e}"))
+            })?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+/// Fixed-width UTC timestamps, so the `started_at` column sorts and compares
+/// as text.
+fn audit_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(chrono::SecondsFormat::Micros, true)
+}
+
 fn media_root_for_path(path: &Path) -> Option<PathBuf> {
     path.parent().map(|parent| parent.join("session_media"))
 }
@@ -1075,9 +1658,18 @@ fn normalize_user_content_session_files(
     session_id: SessionId,
 ) -> Result<(), EventStoreError> {
     for block in content {
-        if let UserContent::Image { image } = block
-            && let ImageSource::SessionFile { relative_path } = &image.source
-        {
+        let image = match block {
+            UserContent::Image { image } => image,
+            UserContent::Attachment {
+                attachment:
+                    AttachmentContent {
+                        body: AttachmentBody::Image { image },
+                        ..
+                    },
+            } => image,
+            _ => continue,
+        };
+        if let ImageSource::SessionFile { relative_path } = &image.source {
             *image = image_content_from_session_file(
                 image,
                 relative_path,
@@ -1149,7 +1741,9 @@ mod tests {
     use crate::app::domain::types::{OpId, ToolCallId};
     use crate::config::model::{ModelId, builtin};
     use crate::config::provider::ProviderId;
-    use crate::session::state::{SessionConfig, ToolVisibility};
+    use crate::session::state::{
+        SUMMARY_METADATA_KEY, SessionConfig, ToolVisibility, WorkspaceConfig,
+    };
     use std::collections::{HashMap, HashSet};
     use steer_tools::error::{ToolError, ToolExecutionError, WorkspaceOpError};
     use steer_tools::result::ToolResult;
@@ -1416,6 +2010,52 @@ mod tests {
         assert_eq!(loaded, None);
     }
 
+    #[tokio::test]
+    async fn test_sqlite_store_memory_upserts_in_first_remembered_order() {
+        let store = SqliteEventStore::new_in_memory().await.unwrap();
+        let session_id = SessionId::new();
+        let entry = |key: &str, content: &str| MemoryEntry {
+            key: key.to_string(),
+            content: content.to_string(),
+        };
+
+        store.create_session(session_id).await.unwrap();
+        for (key, content) in [
+            ("tests", "cargo test"),
+            ("style", "no unwrap"),
+            ("tests", "cargo nextest run"),
+        ] {
+            store
+                .save_memory_entry(session_id, &entry(key, content))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            store.load_memory(session_id).await.unwrap(),
+            vec![
+                entry("tests", "cargo nextest run"),
+                entry("style", "no unwrap")
+            ]
+        );
+
+        assert!(
+            store
+                .delete_memory_entry(session_id, "style")
+                .await
+                .unwrap()
+        );
+        assert!(
+            !store
+                .delete_memory_entry(session_id, "style")
+                .await
+                .unwrap()
+        );
+
+        store.delete_session(session_id).await.unwrap();
+        assert!(store.load_memory(session_id).await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_sqlite_store_sequence_numbers() {
         let store = SqliteEventStore::new_in_memory().await.unwrap();
@@ -1502,6 +2142,70 @@ mod tests {
         assert!(events.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_sqlite_store_tool_audit_outlives_session_until_pruned() {
+        use crate::app::domain::session::{ToolApprover, ToolAuditStatus};
+
+        let store = SqliteEventStore::new_in_memory().await.unwrap();
+        let session_id = SessionId::new();
+        store.create_session(session_id).await.unwrap();
+
+        let started_at = Utc::now() - chrono::Duration::days(10);
+        let entry = |tool_name: &str, started_at: DateTime<Utc>| ToolAuditEntry {
+            session_id,
+            tool_call_id: format!("{tool_name}_call"),
+            tool_name: tool_name.to_string(),
+            parameters_hash: "abc".to_string(),
+            parameters: serde_json::json!({ "command": "ls" }),
+            workspace: Some("/repo".to_string()),
+            status: ToolAuditStatus::Succeeded,
+            duration_ms: 12,
+            approved_by: ToolApprover::Policy,
+            started_at,
+            finished_at: started_at,
+        };
+        let old_bash = entry("bash", started_at);
+        let new_grep = entry("grep", Utc::now());
+        store.record_tool_execution(&new_grep).await.unwrap();
+        store.record_tool_execution(&old_bash).await.unwrap();
+
+        store.delete_session(session_id).await.unwrap();
+
+        let all = store
+            .list_tool_executions(&ToolAuditFilter {
+                session_id: Some(session_id),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(all, vec![old_bash.clone(), new_grep.clone()]);
+
+        let bash_only = store
+            .list_tool_executions(&ToolAuditFilter {
+                tool_name: Some("bash".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(bash_only, vec![old_bash]);
+
+        let update = sqlx::query("UPDATE tool_audit_log SET tool_name = 'edit'")
+            .execute(&store.pool)
+            .await;
+        assert!(update.is_err());
+
+        let cutoff = Utc::now() - chrono::Duration::days(1);
+        assert_eq!(store.prune_tool_executions(cutoff).await.unwrap(), 1);
+        let recent = store
+            .list_tool_executions(&ToolAuditFilter {
+                since: Some(cutoff),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(recent, vec![new_grep]);
+    }
+
     #[tokio::test]
     async fn test_sqlite_store_list_sessions() {
         let store = SqliteEventStore::new_in_memory().await.unwrap();
@@ -1686,6 +2390,172 @@ mod tests {
         assert_eq!(listed_summary.title.as_deref(), Some("Updated Title"));
     }
 
+    #[tokio::test]
+    async fn test_sqlite_store_tracks_summary_from_config_metadata() {
+        let store = SqliteEventStore::new_in_memory().await.unwrap();
+        let session_id = SessionId::new();
+
+        store.create_session(session_id).await.unwrap();
+
+        let config = SessionConfig::read_only(builtin::claude_sonnet_4_5());
+        store
+            .append(
+                session_id,
+                &SessionEvent::SessionCreated {
+                    config: Box::new(config.clone()),
+                    metadata: HashMap::new(),
+                    parent_session_id: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let mut updated = config;
+        updated.metadata.insert(
+            SUMMARY_METADATA_KEY.to_string(),
+            "Fixing the flaky login test.".to_string(),
+        );
+        store
+            .append(
+                session_id,
+                &SessionEvent::SessionConfigUpdated {
+                    config: Box::new(updated),
+                    primary_agent_id: "normal".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+
+        let summary = store
+            .get_session_summary(session_id)
+            .await
+            .unwrap()
+            .expect("summary");
+        assert_eq!(
+            summary.summary.as_deref(),
+            Some("Fixing the flaky login test.")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_store_tracks_and_backfills_workspace_path() {
+        let store = SqliteEventStore::new_in_memory().await.unwrap();
+        let session_id = SessionId::new();
+
+        store.create_session(session_id).await.unwrap();
+
+        let mut config = SessionConfig::read_only(builtin::claude_sonnet_4_5());
+        config.workspace = WorkspaceConfig::Local {
+            path: PathBuf::from("/work/project"),
+        };
+        store
+            .append(
+                session_id,
+                &SessionEvent::SessionCreated {
+                    config: Box::new(config),
+                    metadata: HashMap::new(),
+                    parent_session_id: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let summary = store
+            .get_session_summary(session_id)
+            .await
+            .unwrap()
+            .expect("summary");
+        assert_eq!(summary.workspace_path.as_deref(), Some("/work/project"));
+
+        // Simulate a database created before the column existed.
+        sqlx::query("ALTER TABLE domain_sessions DROP COLUMN workspace_path")
+            .execute(&store.pool)
+            .await
+            .unwrap();
+        store.run_migrations().await.unwrap();
+
+        let listed = store.list_sessions(SessionFilter::default()).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].workspace_path.as_deref(), Some("/work/project"));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_store_encrypted_roundtrip_requires_key() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("events.sqlite");
+        let key = || Some(SessionKeySource::Passphrase("correct horse".to_string()));
+        let session_id = SessionId::new();
+
+        {
+            let store = SqliteEventStore::open(&db_path, key()).await.unwrap();
+            store.create_session(session_id).await.unwrap();
+
+            let mut config = SessionConfig::read_only(builtin::claude_sonnet_4_5());
+            config.title = Some("Secret project".to_string());
+            store
+                .append(
+                    session_id,
+                    &SessionEvent::SessionCreated {
+                        config: Box::new(config),
+                        metadata: HashMap::new(),
+                        parent_session_id: None,
+                    },
+                )
+                .await
+                .unwrap();
+            store
+                .append(
+                    session_id,
+                    &SessionEvent::Error {
+                        message: "api key sk-secret leaked".to_string(),
+                    },
+                )
+                .await
+                .unwrap();
+            store.save_todos(session_id, &sample_todos()).await.unwrap();
+
+            let raw: Vec<String> = sqlx::query_scalar(
+                "SELECT event_data FROM domain_events UNION ALL SELECT todos_json FROM session_todos UNION ALL SELECT config_json FROM domain_sessions",
+            )
+            .fetch_all(&store.pool)
+            .await
+            .unwrap();
+            assert!(raw.iter().all(|value| is_encrypted(value)));
+            assert!(raw.iter().all(|value| !value.contains("sk-secret")));
+        }
+
+        let store = SqliteEventStore::open(&db_path, key()).await.unwrap();
+        let events = store.load_events(session_id).await.unwrap();
+        assert!(matches!(
+            &events[1].1,
+            SessionEvent::Error { message } if message == "api key sk-secret leaked"
+        ));
+        assert_eq!(
+            store.load_todos(session_id).await.unwrap(),
+            Some(sample_todos())
+        );
+        let summary = store
+            .get_session_summary(session_id)
+            .await
+            .unwrap()
+            .expect("summary");
+        assert_eq!(summary.title.as_deref(), Some("Secret project"));
+        drop(store);
+
+        assert!(matches!(
+            SqliteEventStore::new(&db_path).await,
+            Err(EventStoreError::Encryption { .. })
+        ));
+        assert!(matches!(
+            SqliteEventStore::open(
+                &db_path,
+                Some(SessionKeySource::Passphrase("wrong".to_string()))
+            )
+            .await,
+            Err(EventStoreError::Encryption { .. })
+        ));
+    }
+
     #[tokio::test]
     async fn test_sqlite_store_serializes_tool_visibility_whitelist() {
         let store = SqliteEventStore::new_in_memory().await.unwrap();
@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::app::domain::types::SessionId;
+use crate::session::state::SessionConfig;
+
+#[derive(Debug, Error)]
+pub enum ScheduledTaskStoreError {
+    #[error("Database error: {message}")]
+    Database { message: String },
+
+    #[error("Serialization error: {message}")]
+    Serialization { message: String },
+}
+
+impl ScheduledTaskStoreError {
+    pub fn database(message: impl Into<String>) -> Self {
+        Self::Database {
+            message: message.into(),
+        }
+    }
+
+    pub fn serialization(message: impl Into<String>) -> Self {
+        Self::Serialization {
+            message: message.into(),
+        }
+    }
+}
+
+/// A prompt run headlessly on a cron schedule by `steer serve`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledTask {
+    pub id: String,
+    /// Cron expression, either five fields (minute precision) or six with seconds.
+    pub schedule: String,
+    pub prompt: String,
+    /// Config for the session created by each run.
+    pub config: SessionConfig,
+    /// Cancel a run once its model calls have used this many tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_total_tokens: Option<u64>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run: Option<ScheduledTaskRun>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledTaskRun {
+    pub session_id: SessionId,
+    pub started_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+    pub outcome: ScheduledRunOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ScheduledRunOutcome {
+    Running,
+    Succeeded,
+    Failed { message: String },
+    BudgetExceeded,
+}
+
+#[async_trait]
+pub trait ScheduledTaskStore: Send + Sync {
+    async fn create_task(&self, task: &ScheduledTask) -> Result<(), ScheduledTaskStoreError>;
+
+    async fn get_task(&self, id: &str) -> Result<Option<ScheduledTask>, ScheduledTaskStoreError>;
+
+    async fn list_tasks(&self) -> Result<Vec<ScheduledTask>, ScheduledTaskStoreError>;
+
+    /// Returns whether a task with `id` existed.
+    async fn delete_task(&self, id: &str) -> Result<bool, ScheduledTaskStoreError>;
+
+    async fn record_run(
+        &self,
+        id: &str,
+        run: &ScheduledTaskRun,
+    ) -> Result<(), ScheduledTaskStoreError>;
+}
@@ -3,13 +3,15 @@ use crate::app::SystemContext;
 use crate::app::conversation::MessageGraph;
 use crate::app::conversation::UserContent;
 use crate::app::domain::action::McpServerState;
-use crate::app::domain::event::ContextWindowUsage;
+use crate::app::domain::event::{BestOfCandidate, ContextWindowUsage};
 use crate::app::domain::types::{MessageId, OpId, RequestId, SessionId, ToolCallId};
 use crate::config::model::ModelId;
 use crate::prompts::system_prompt_for_model;
 use crate::session::state::SessionConfig;
+use crate::utils::tokens::TokenCalibration;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
+use steer_tools::tools::edit::hunks::EditHunk;
 use steer_tools::{ToolCall, ToolSchema};
 
 #[derive(Debug, Clone)]
@@ -30,6 +32,9 @@ pub struct AppState {
     pub static_bash_patterns: Vec<String>,
     pub pending_approval: Option<PendingApproval>,
     pub approval_queue: VecDeque<QueuedApproval>,
+    /// Hunks the user rejected from edit tool calls that are still executing,
+    /// reported back to the model alongside the edit result.
+    pub rejected_hunks: HashMap<ToolCallId, Vec<EditHunk>>,
     pub queued_work: VecDeque<QueuedWorkItem>,
 
     pub current_operation: Option<OperationState>,
@@ -48,10 +53,39 @@ pub struct AppState {
     pub llm_usage_by_op: HashMap<OpId, LlmUsageSnapshot>,
     pub llm_usage_totals: TokenUsage,
 
+    /// Per-model corrections to token estimates, from the provider's own
+    /// token counts. Kept in memory only.
+    pub token_calibrations: HashMap<ModelId, TokenCalibration>,
+
     pub event_sequence: u64,
 
     /// Message IDs that are compaction summaries.
     pub compaction_summary_ids: HashSet<String>,
+
+    /// Whether a conversation summary is being generated.
+    pub summary_in_flight: bool,
+
+    /// The best-of turn whose candidates are drafting or awaiting a pick.
+    pub best_of: Option<BestOfRun>,
+}
+
+/// Most candidates a single best-of turn may draft.
+pub const MAX_BEST_OF_CANDIDATES: usize = 4;
+
+#[derive(Debug, Clone)]
+pub struct BestOfRun {
+    pub op_id: OpId,
+    pub user_message_id: MessageId,
+    pub model: ModelId,
+    pub candidate_op_ids: Vec<OpId>,
+    pub candidates: Vec<BestOfCandidate>,
+}
+
+impl BestOfRun {
+    /// Whether every candidate has reported back.
+    pub fn is_complete(&self) -> bool {
+        self.candidates.len() == self.candidate_op_ids.len()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -153,6 +187,7 @@ impl AppState {
             static_bash_patterns: Vec::new(),
             pending_approval: None,
             approval_queue: VecDeque::new(),
+            rejected_hunks: HashMap::new(),
             queued_work: VecDeque::new(),
             current_operation: None,
             active_streams: HashMap::new(),
@@ -163,8 +198,11 @@ impl AppState {
             operation_messages: HashMap::new(),
             llm_usage_by_op: HashMap::new(),
             llm_usage_totals: TokenUsage::new(0, 0, 0),
+            token_calibrations: HashMap::new(),
             event_sequence: 0,
             compaction_summary_ids: HashSet::new(),
+            summary_in_flight: false,
+            best_of: None,
         }
     }
 
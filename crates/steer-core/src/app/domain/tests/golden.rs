@@ -128,9 +128,14 @@ mod tests {
                     SessionEvent::ToolCallCompleted { .. } => "ToolCallCompleted".to_string(),
                     SessionEvent::ToolCallFailed { .. } => "ToolCallFailed".to_string(),
                     SessionEvent::Error { .. } => "Error".to_string(),
+                    SessionEvent::Refused { .. } => "Refused".to_string(),
                     SessionEvent::SessionCreated { .. } => "SessionCreated".to_string(),
                     SessionEvent::SessionConfigUpdated { .. } => "SessionConfigUpdated".to_string(),
                     SessionEvent::MessageUpdated { .. } => "MessageUpdated".to_string(),
+                    SessionEvent::ContextUsage { .. } => "ContextUsage".to_string(),
+                    SessionEvent::BestOfStarted { .. } => "BestOfStarted".to_string(),
+                    SessionEvent::BestOfCandidateReady { .. } => "BestOfCandidateReady".to_string(),
+                    SessionEvent::BestOfResolved { .. } => "BestOfResolved".to_string(),
                     SessionEvent::WorkspaceChanged => "WorkspaceChanged".to_string(),
                     SessionEvent::ConversationCompacted { .. } => {
                         "ConversationCompacted".to_string()
@@ -319,6 +324,9 @@ mod tests {
                 EffectSnapshot::EmitEvent {
                     event_type: "OperationStarted".to_string(),
                 },
+                EffectSnapshot::EmitEvent {
+                    event_type: "ContextUsage".to_string(),
+                },
                 EffectSnapshot::CallModel,
                 EffectSnapshot::GenerateSessionTitle,
             ],
@@ -361,6 +369,9 @@ mod tests {
                 EffectSnapshot::EmitEvent {
                     event_type: "OperationStarted".to_string(),
                 },
+                EffectSnapshot::EmitEvent {
+                    event_type: "ContextUsage".to_string(),
+                },
                 EffectSnapshot::CallModel,
                 EffectSnapshot::GenerateSessionTitle,
                 EffectSnapshot::EmitEvent {
@@ -0,0 +1,249 @@
+//! Byte budgets for tool results sent back to the model.
+//!
+//! Oversized results are cut down in place, keeping their typed shape so the
+//! TUI formatters still apply, and carry a marker telling the model how to
+//! narrow the query.
+
+use steer_tools::result::ToolResult;
+
+/// Approximate per-item overhead (line numbers, separators) for list results.
+const LIST_ITEM_OVERHEAD: usize = 24;
+/// Room left for the native "stopped early" / "showing N of M" markers.
+const NATIVE_MARKER_RESERVE: usize = 160;
+
+/// Truncate `result` so its LLM-formatted size fits within `max_bytes`.
+///
+/// Returns `true` if anything was cut. Errors, edits and todo results are
+/// small by construction and are left untouched.
+pub fn truncate_tool_result(result: &mut ToolResult, max_bytes: usize) -> bool {
+    let original_bytes = result.llm_format().len();
+    if original_bytes <= max_bytes {
+        return false;
+    }
+
+    let marker = |hint: &str| {
+        format!(
+            "\n[Output truncated: {original_bytes} bytes exceeded the {max_bytes}-byte tool result budget. {hint}]"
+        )
+    };
+    // Bytes the variable part of the result has to shrink by, marker included.
+    let overflow = |marker: &str| original_bytes - max_bytes + marker.len();
+
+    match result {
+        ToolResult::Search(r) => {
+            keep_within(
+                &mut r.matches,
+                max_bytes.saturating_sub(NATIVE_MARKER_RESERVE),
+                |m| m.file_path.len() + m.line_content.len() + LIST_ITEM_OVERHEAD,
+            );
+            r.search_completed = false;
+        }
+        ToolResult::FileList(r) => {
+            if r.total_entries < r.entries.len() {
+                r.total_entries = r.entries.len();
+            }
+            keep_within(
+                &mut r.entries,
+                max_bytes.saturating_sub(NATIVE_MARKER_RESERVE),
+                |e| e.path.len() + LIST_ITEM_OVERHEAD,
+            );
+            r.truncated = true;
+        }
+        ToolResult::Glob(r) => {
+            let marker = marker("Use a more specific pattern.");
+            keep_within(
+                &mut r.matches,
+                max_bytes.saturating_sub(marker.len()),
+                |path| path.len() + 1,
+            );
+            r.matches.push(marker.trim_start().to_string());
+        }
+        ToolResult::FileContent(r) => {
+            let marker = marker("Read a smaller range with offset and limit.");
+            let keep = r.content.len().saturating_sub(overflow(&marker));
+            truncate_text(&mut r.content, keep, &marker);
+            r.truncated = true;
+        }
+        ToolResult::Bash(r) => {
+            let marker = marker("Narrow the output, e.g. with head, tail or grep.");
+            let available = max_bytes.saturating_sub(marker.len() + LIST_ITEM_OVERHEAD * 2);
+            let stderr_keep = r.stderr.len().min(available / 4);
+            let stdout_keep = available - stderr_keep;
+            if r.stderr.len() > stderr_keep {
+                truncate_text(&mut r.stderr, stderr_keep, "");
+            }
+            truncate_text(&mut r.stdout, stdout_keep, &marker);
+        }
+        ToolResult::Fetch(r) => {
+            let marker = marker("Fetch a more specific page.");
+            let keep = r.content.len().saturating_sub(overflow(&marker));
+            truncate_text(&mut r.content, keep, &marker);
+        }
+        ToolResult::GitStatus(r) => {
+            let marker = marker("Narrow the output with a path-limited git command via bash.");
+            let keep = r.content.len().saturating_sub(overflow(&marker));
+            truncate_text(&mut r.content, keep, &marker);
+        }
+        ToolResult::Agent(r) => {
+            let marker = marker("Ask for a narrower result.");
+            let keep = r.content.len().saturating_sub(overflow(&marker));
+            truncate_text(&mut r.content, keep, &marker);
+        }
+        ToolResult::External(r) => {
+            let marker = marker("Ask for a narrower result.");
+            let keep = r.payload.len().saturating_sub(overflow(&marker));
+            truncate_text(&mut r.payload, keep, &marker);
+        }
+        ToolResult::Edit(_)
+        | ToolResult::TodoRead(_)
+        | ToolResult::TodoWrite(_)
+        | ToolResult::Memory(_)
+        | ToolResult::Error(_) => return false,
+    }
+
+    true
+}
+
+/// Fit a turn's results within `max_turn_bytes`, cutting the largest first.
+///
+/// Results are capped at a common level chosen so the total fits; anything
+/// already under that level is kept whole. Returns the indices that were cut.
+pub fn enforce_turn_budget(results: &mut [ToolResult], max_turn_bytes: usize) -> Vec<usize> {
+    let sizes: Vec<usize> = results.iter().map(|r| r.llm_format().len()).collect();
+    if sizes.iter().sum::<usize>() <= max_turn_bytes {
+        return Vec::new();
+    }
+
+    let mut ascending = sizes.clone();
+    ascending.sort_unstable();
+    let mut remaining = max_turn_bytes;
+    let mut level = 0;
+    for (i, size) in ascending.iter().enumerate() {
+        let share = remaining / (ascending.len() - i);
+        if *size > share {
+            level = share;
+            break;
+        }
+        remaining -= size;
+    }
+
+    sizes
+        .iter()
+        .enumerate()
+        .filter(|(_, size)| **size > level)
+        .filter_map(|(index, _)| truncate_tool_result(&mut results[index], level).then_some(index))
+        .collect()
+}
+
+fn keep_within<T>(items: &mut Vec<T>, budget: usize, size: impl Fn(&T) -> usize) {
+    let mut used = 0;
+    let keep = items
+        .iter()
+        .take_while(|item| {
+            used += size(item);
+            used <= budget
+        })
+        .count();
+    items.truncate(keep);
+}
+
+fn truncate_text(text: &mut String, max_len: usize, marker: &str) {
+    let mut end = max_len.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+    text.push_str(marker);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use steer_tools::result::{BashResult, ExternalResult, SearchMatch, SearchResult};
+
+    fn external(payload_len: usize) -> ToolResult {
+        ToolResult::External(ExternalResult {
+            tool_name: "mcp__test".to_string(),
+            payload: "x".repeat(payload_len),
+        })
+    }
+
+    #[test]
+    fn small_results_are_left_alone() {
+        let mut result = external(100);
+        assert!(!truncate_tool_result(&mut result, 1_000));
+        assert_eq!(result.llm_format().len(), 100);
+    }
+
+    #[test]
+    fn oversized_text_result_is_cut_with_marker() {
+        let mut result = external(10_000);
+        assert!(truncate_tool_result(&mut result, 1_000));
+
+        let formatted = result.llm_format();
+        assert!(formatted.len() <= 1_000);
+        assert!(formatted.contains("10000 bytes exceeded the 1000-byte tool result budget"));
+    }
+
+    #[test]
+    fn oversized_search_keeps_leading_matches_and_flags_incomplete() {
+        let mut result = ToolResult::Search(SearchResult {
+            matches: (0..1_000)
+                .map(|i| SearchMatch {
+                    file_path: format!("vendor/file_{i}.rs"),
+                    line_number: i,
+                    line_content: "let matched = true;".to_string(),
+                    column_range: None,
+                })
+                .collect(),
+            total_files_searched: 1_000,
+            search_completed: true,
+            warnings: Vec::new(),
+        });
+
+        assert!(truncate_tool_result(&mut result, 2_000));
+        let ToolResult::Search(search) = &result else {
+            unreachable!("variant is preserved");
+        };
+        assert!(!search.search_completed);
+        assert!(search.matches.len() < 1_000);
+        assert!(result.llm_format().len() <= 2_000);
+        assert!(result.llm_format().contains("Narrow the pattern or path"));
+    }
+
+    #[test]
+    fn bash_output_keeps_head_of_stdout() {
+        let mut result = ToolResult::Bash(BashResult {
+            stdout: "line\n".repeat(5_000),
+            stderr: String::new(),
+            exit_code: 0,
+            command: "cat big.log".to_string(),
+            timed_out: false,
+            parsed: None,
+        });
+
+        assert!(truncate_tool_result(&mut result, 4_096));
+        let formatted = result.llm_format();
+        assert!(formatted.len() <= 4_096);
+        assert!(formatted.starts_with("line\n"));
+        assert!(formatted.contains("head, tail or grep"));
+    }
+
+    #[test]
+    fn turn_budget_cuts_largest_results_first() {
+        let mut results = vec![external(500), external(8_000), external(4_000)];
+
+        let truncated = enforce_turn_budget(&mut results, 6_000);
+
+        assert_eq!(truncated, vec![1, 2]);
+        assert_eq!(results[0].llm_format().len(), 500);
+        let total: usize = results.iter().map(|r| r.llm_format().len()).sum();
+        assert!(total <= 6_000);
+    }
+
+    #[test]
+    fn turn_budget_is_noop_when_under_cap() {
+        let mut results = vec![external(500), external(800)];
+        assert!(enforce_turn_budget(&mut results, 6_000).is_empty());
+    }
+}
@@ -5,11 +5,13 @@ use crate::error::Result;
 use crate::model_registry::ModelRegistry;
 use std::sync::Arc;
 
+pub mod context_report;
 pub mod conversation;
 pub mod domain;
 pub mod system_context;
 pub mod validation;
 
+pub use context_report::{ContextItem, ContextReport, ContextSegment, ContextSegmentKind};
 pub use conversation::{Message, MessageData, MessageGraph};
 pub use steer_workspace::EnvironmentInfo;
 pub use system_context::SystemContext;
@@ -1,9 +1,13 @@
 use crate::app::EnvironmentInfo;
+use steer_tools::tools::memory::MemoryEntry;
 
 #[derive(Debug, Clone)]
 pub struct SystemContext {
     pub prompt: String,
     pub environment: Option<EnvironmentInfo>,
+    /// Session memory, rendered after the environment. Filled in just before
+    /// each model call since it can change between turns.
+    pub memory: Vec<MemoryEntry>,
 }
 
 impl SystemContext {
@@ -11,6 +15,7 @@ impl SystemContext {
         Self {
             prompt,
             environment: None,
+            memory: Vec::new(),
         }
     }
 
@@ -18,6 +23,7 @@ impl SystemContext {
         Self {
             prompt,
             environment,
+            memory: Vec::new(),
         }
     }
 
@@ -51,6 +57,12 @@ impl SystemContext {
             }
             combined.push_str(&env_value);
         }
+        if !self.memory.is_empty() {
+            if !combined.is_empty() {
+                combined.push_str("\n\n");
+            }
+            combined.push_str(&render_memory(&self.memory));
+        }
 
         if combined.trim().is_empty() {
             None
@@ -59,3 +71,14 @@ impl SystemContext {
         }
     }
 }
+
+fn render_memory(memory: &[MemoryEntry]) -> String {
+    let mut rendered = String::from(
+        "<session_memory>\nFacts remembered for this session. Keep them in mind; use the remember and forget tools to change them.\n",
+    );
+    for entry in memory {
+        rendered.push_str(&format!("- {}: {}\n", entry.key, entry.content));
+    }
+    rendered.push_str("</session_memory>");
+    rendered
+}
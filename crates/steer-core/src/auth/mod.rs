@@ -11,7 +11,7 @@ pub use steer_auth_plugin::flow::{
     AuthFlowWrapper, AuthMethod, AuthProgress, AuthenticationFlow, DynAuthenticationFlow,
 };
 pub use steer_auth_plugin::identifiers::{ModelId, ProviderId};
-pub use steer_auth_plugin::{AnthropicAuth, AuthPlugin, OpenAiResponsesAuth};
+pub use steer_auth_plugin::{AccountInfo, AnthropicAuth, AuthPlugin, OpenAiResponsesAuth};
 pub use steer_auth_plugin::{
     ApiKeyOrigin, AuthDirective, AuthErrorAction, AuthErrorContext, AuthHeaderContext,
     AuthHeaderProvider, AuthSource, HeaderPair, InstructionPolicy, ModelVisibilityPolicy,
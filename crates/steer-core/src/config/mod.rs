@@ -217,7 +217,9 @@ impl LlmConfigProvider {
         &self,
         provider_id: &ProviderId,
     ) -> Result<ResolvedAuth> {
-        if let Some(plugin) = self.plugin_registry.get(provider_id)
+        // Plugin auth may refresh tokens over the network; offline, only API keys apply.
+        if !crate::offline::is_offline()
+            && let Some(plugin) = self.plugin_registry.get(provider_id)
             && let Some(directive) = plugin.resolve_auth(self.storage.clone()).await?
         {
             return Ok(ResolvedAuth::Plugin {
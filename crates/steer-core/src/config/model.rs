@@ -6,7 +6,7 @@ use super::provider::ProviderId;
 use super::toml_types::ModelData;
 
 // Re-export types from toml_types for public use
-pub use super::toml_types::{ModelParameters, ThinkingConfig};
+pub use super::toml_types::{ModelParameters, ModelPricing, ThinkingConfig};
 
 /// Identifier for a model (provider + model id string).
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
@@ -86,6 +86,10 @@ pub struct ModelConfig {
     /// Optional model-specific parameters.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parameters: Option<ModelParameters>,
+
+    /// Optional pricing used to estimate the cost of a run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<ModelPricing>,
 }
 
 impl ModelConfig {
@@ -121,6 +125,9 @@ impl ModelConfig {
         if other.context_window_tokens.is_some() {
             self.context_window_tokens = other.context_window_tokens;
         }
+        if other.pricing.is_some() {
+            self.pricing = other.pricing;
+        }
 
         // Merge parameters
         match (&mut self.parameters, other.parameters) {
@@ -158,6 +165,15 @@ impl ModelConfig {
     }
 }
 
+impl ModelPricing {
+    /// Estimate the cost in USD of the given token counts.
+    pub fn cost_usd(&self, input_tokens: u32, output_tokens: u32) -> f64 {
+        (f64::from(input_tokens) * self.input_per_million
+            + f64::from(output_tokens) * self.output_per_million)
+            / 1_000_000.0
+    }
+}
+
 impl From<ModelData> for ModelConfig {
     fn from(data: ModelData) -> Self {
         ModelConfig {
@@ -168,6 +184,7 @@ impl From<ModelData> for ModelConfig {
             recommended: data.recommended,
             context_window_tokens: data.context_window_tokens,
             parameters: data.parameters,
+            pricing: data.pricing,
         }
     }
 }
@@ -192,6 +209,10 @@ mod tests {
                 top_p: Some(0.9),
                 thinking_config: None,
             }),
+            pricing: Some(ModelPricing {
+                input_per_million: 15.0,
+                output_per_million: 75.0,
+            }),
         };
 
         // Serialize to TOML
@@ -275,6 +296,7 @@ mod tests {
                 top_p: None,
                 thinking_config: None,
             }),
+            pricing: None,
         };
 
         // Test with no call options
@@ -306,6 +328,7 @@ mod tests {
             recommended: false,
             context_window_tokens: Some(200_000),
             parameters: None,
+            pricing: None,
         };
 
         base.merge_with(ModelConfig {
@@ -316,6 +339,7 @@ mod tests {
             recommended: true,
             context_window_tokens: None,
             parameters: None,
+            pricing: None,
         });
         assert_eq!(base.context_window_tokens, Some(200_000));
 
@@ -327,6 +351,7 @@ mod tests {
             recommended: true,
             context_window_tokens: Some(400_000),
             parameters: None,
+            pricing: None,
         });
         assert_eq!(base.context_window_tokens, Some(400_000));
     }
@@ -341,6 +366,7 @@ mod tests {
             recommended: false,
             context_window_tokens: None,
             parameters: None,
+            pricing: None,
         };
 
         let toml_string = toml::to_string_pretty(&config).expect("Failed to serialize to TOML");
@@ -8,6 +8,8 @@ pub enum ApiFormat {
     Anthropic,
     Google,
     Xai,
+    /// Scripted responses from [`crate::api::mock`]; needs no credentials.
+    Mock,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -41,6 +43,15 @@ pub struct ModelData {
     pub context_window_tokens: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parameters: Option<ModelParameters>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<ModelPricing>,
+}
+
+/// Published per-token pricing for a model, in USD per million tokens.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Copy)]
+pub struct ModelPricing {
+    pub input_per_million: f64,
+    pub output_per_million: f64,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Copy)]
@@ -0,0 +1,492 @@
+//! Run the agent loop inside your own process, without a gRPC server.
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! use steer_core::embedded::{Agent, AgentEvent, ApprovalDecision};
+//!
+//! # async fn run() -> steer_core::error::Result<()> {
+//! let agent = Agent::builder()
+//!     .model("sonnet")
+//!     .workspace_path("/path/to/project")
+//!     .build()
+//!     .await?;
+//!
+//! let mut turn = agent.send_message("Summarize the README").await?;
+//! while let Some(event) = turn.next().await {
+//!     match event {
+//!         AgentEvent::AssistantMessage { text } => println!("{text}"),
+//!         AgentEvent::ApprovalRequested { request_id, .. } => {
+//!             agent.approve_tool(request_id, ApprovalDecision::Denied).await?;
+//!         }
+//!         _ => {}
+//!     }
+//! }
+//! agent.shutdown().await;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Each [`Agent`] owns one session. Sessions live in memory and are gone
+//! once the agent is dropped.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+use futures::stream::BoxStream;
+
+use crate::api::Client as ApiClient;
+use crate::app::AppConfig;
+use crate::app::conversation::{Message, MessageData, UserContent};
+use crate::app::domain::event::SessionEvent;
+use crate::app::domain::runtime::{RuntimeHandle, RuntimeService, SessionEventSubscription};
+use crate::app::domain::session::InMemoryEventStore;
+use crate::auth::{AuthStorage, DefaultAuthStorage};
+use crate::catalog::CatalogConfig;
+use crate::config::model::{ModelId, builtin};
+use crate::error::{Error, Result};
+use crate::isolation::IsolationMode;
+use crate::model_registry::ModelRegistry;
+use crate::session::state::{
+    AutoCompactionConfig, AutoSummaryConfig, SessionConfig, SessionPolicyOverrides,
+    SessionToolConfig, WorkspaceConfig,
+};
+use crate::tools::ToolSystemBuilder;
+
+pub use crate::app::domain::action::ApprovalDecision;
+pub use crate::app::domain::types::{OpId, RequestId, SessionId};
+pub use crate::session::ToolApprovalPolicy;
+pub use steer_tools::ToolCall;
+
+/// Something that happened while the agent worked on a message.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum AgentEvent {
+    /// The assistant finished a message that has text in it.
+    AssistantMessage {
+        text: String,
+    },
+    ToolCallStarted {
+        id: String,
+        name: String,
+        parameters: serde_json::Value,
+    },
+    /// A tool call ended. `output` is what the model sees when it
+    /// succeeded; `error` is set if it failed.
+    ToolCallFinished {
+        id: String,
+        name: String,
+        output: Option<String>,
+        error: Option<String>,
+    },
+    /// The agent wants to run a tool the policy doesn't pre-approve. The
+    /// turn waits until [`Agent::approve_tool`] is called with `request_id`.
+    ApprovalRequested {
+        request_id: RequestId,
+        tool_call: ToolCall,
+    },
+    Error {
+        message: String,
+    },
+    /// The turn is over. Always the last event.
+    Finished,
+    /// The turn was cancelled. Always the last event.
+    Cancelled,
+}
+
+/// Events of one turn; ends after [`AgentEvent::Finished`] or
+/// [`AgentEvent::Cancelled`].
+pub struct AgentTurn {
+    op_id: OpId,
+    events: BoxStream<'static, AgentEvent>,
+}
+
+impl AgentTurn {
+    pub fn op_id(&self) -> OpId {
+        self.op_id
+    }
+}
+
+impl Stream for AgentTurn {
+    type Item = AgentEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<AgentEvent>> {
+        self.events.as_mut().poll_next(cx)
+    }
+}
+
+/// Builder for [`Agent`]; see [`Agent::builder`].
+#[must_use]
+pub struct AgentBuilder {
+    model: Option<String>,
+    workspace_path: Option<PathBuf>,
+    tool_policy: ToolApprovalPolicy,
+    catalogs: Vec<String>,
+    auth_storage: Option<Arc<dyn AuthStorage>>,
+}
+
+impl AgentBuilder {
+    /// Model name or alias, e.g. `"sonnet"` or `"openai/gpt-5"`. Defaults
+    /// to the CLI's default model.
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Directory the tools work in. Defaults to the current directory.
+    pub fn workspace_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.workspace_path = Some(path.into());
+        self
+    }
+
+    /// Which tools run without asking. Defaults to the read-only tools;
+    /// anything else emits [`AgentEvent::ApprovalRequested`].
+    pub fn tool_policy(mut self, policy: ToolApprovalPolicy) -> Self {
+        self.tool_policy = policy;
+        self
+    }
+
+    /// Load an extra model catalog file.
+    pub fn catalog(mut self, path: impl Into<String>) -> Self {
+        self.catalogs.push(path.into());
+        self
+    }
+
+    /// Where credentials come from. Defaults to the OS keyring, as used by
+    /// the `steer` CLI.
+    pub fn auth_storage(mut self, storage: Arc<dyn AuthStorage>) -> Self {
+        self.auth_storage = Some(storage);
+        self
+    }
+
+    pub async fn build(self) -> Result<Agent> {
+        let auth_storage = match &self.auth_storage {
+            Some(storage) => storage.clone(),
+            None => Arc::new(DefaultAuthStorage::new()?),
+        };
+        let app_config = AppConfig::from_auth_storage_with_catalog(
+            auth_storage,
+            CatalogConfig::with_catalogs(self.catalogs.clone()),
+        )?;
+        let api_client = Arc::new(ApiClient::new_with_deps(
+            app_config.llm_config_provider,
+            app_config.provider_registry,
+            app_config.model_registry.clone(),
+        ));
+
+        self.start(api_client, app_config.model_registry).await
+    }
+
+    async fn start(
+        self,
+        api_client: Arc<ApiClient>,
+        model_registry: Arc<ModelRegistry>,
+    ) -> Result<Agent> {
+        let model = match &self.model {
+            Some(name) => model_registry
+                .resolve(name)
+                .map_err(|e| Error::Configuration(format!("Invalid model '{name}': {e}")))?,
+            None => builtin::default_model(),
+        };
+        let workspace_path = match self.workspace_path {
+            Some(path) => path,
+            None => std::env::current_dir()?,
+        };
+        let workspace = WorkspaceConfig::Local {
+            path: workspace_path,
+        };
+
+        let event_store = Arc::new(InMemoryEventStore::new());
+        let tool_executor = ToolSystemBuilder::new(
+            crate::workspace::create_workspace_from_session_config(&workspace).await?,
+            event_store.clone(),
+            api_client.clone(),
+            model_registry,
+        )
+        .build();
+        let runtime = RuntimeService::spawn(event_store, api_client, tool_executor);
+
+        let tool_config = SessionToolConfig {
+            approval_policy: self.tool_policy,
+            ..SessionToolConfig::default()
+        };
+        let config = SessionConfig {
+            workspace,
+            workspace_ref: None,
+            workspace_id: None,
+            repo_ref: None,
+            parent_session_id: None,
+            workspace_name: None,
+            tool_config,
+            system_prompt: None,
+            primary_agent_id: None,
+            policy_overrides: SessionPolicyOverrides::empty(),
+            title: None,
+            metadata: HashMap::new(),
+            default_model: model.clone(),
+            auto_compaction: AutoCompactionConfig::default(),
+            auto_summary: AutoSummaryConfig::default(),
+            isolation: IsolationMode::None,
+            isolated_workspace: None,
+        };
+        let session_id = runtime.handle.create_session(config).await?;
+
+        Ok(Agent {
+            runtime,
+            session_id,
+            model,
+        })
+    }
+}
+
+/// An agent session running in this process.
+pub struct Agent {
+    runtime: RuntimeService,
+    session_id: SessionId,
+    model: ModelId,
+}
+
+impl Agent {
+    pub fn builder() -> AgentBuilder {
+        AgentBuilder {
+            model: None,
+            workspace_path: None,
+            tool_policy: ToolApprovalPolicy::default(),
+            catalogs: Vec::new(),
+            auth_storage: None,
+        }
+    }
+
+    pub fn session_id(&self) -> SessionId {
+        self.session_id
+    }
+
+    pub fn model(&self) -> &ModelId {
+        &self.model
+    }
+
+    /// Send a user message and stream what the agent does with it. Sending
+    /// while a turn is running queues the message behind it.
+    pub async fn send_message(&self, text: impl Into<String>) -> Result<AgentTurn> {
+        let handle = self.runtime.handle();
+        let subscription = handle.subscribe_events(self.session_id).await?;
+        let (op_id, _) = handle
+            .submit_user_input(
+                self.session_id,
+                vec![UserContent::Text { text: text.into() }],
+                self.model.clone(),
+            )
+            .await?;
+
+        Ok(AgentTurn {
+            op_id,
+            events: Box::pin(turn_events(handle, subscription, op_id)),
+        })
+    }
+
+    pub async fn approve_tool(
+        &self,
+        request_id: RequestId,
+        decision: ApprovalDecision,
+    ) -> Result<()> {
+        self.runtime
+            .handle
+            .submit_tool_approval(self.session_id, request_id, decision, None)
+            .await?;
+        Ok(())
+    }
+
+    /// Cancel the running turn, if any.
+    pub async fn cancel(&self) -> Result<()> {
+        self.runtime
+            .handle
+            .cancel_operation(self.session_id, None)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn shutdown(self) {
+        self.runtime.shutdown().await;
+    }
+}
+
+fn turn_events(
+    // Keeps the runtime alive while the stream is held.
+    _handle: RuntimeHandle,
+    mut subscription: SessionEventSubscription,
+    op_id: OpId,
+) -> impl Stream<Item = AgentEvent> + Send + 'static {
+    async_stream::stream! {
+        while let Some(envelope) = subscription.recv().await {
+            match envelope.event {
+                SessionEvent::OperationCompleted { op_id: done, .. } if done == op_id => {
+                    yield AgentEvent::Finished;
+                    break;
+                }
+                SessionEvent::OperationCancelled { op_id: done, .. } if done == op_id => {
+                    yield AgentEvent::Cancelled;
+                    break;
+                }
+                event => {
+                    if let Some(event) = agent_event(event) {
+                        yield event;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn agent_event(event: SessionEvent) -> Option<AgentEvent> {
+    match event {
+        SessionEvent::AssistantMessageAdded { message, .. } => {
+            assistant_text(&message).map(|text| AgentEvent::AssistantMessage { text })
+        }
+        SessionEvent::ToolCallStarted {
+            id,
+            name,
+            parameters,
+            ..
+        } => Some(AgentEvent::ToolCallStarted {
+            id: id.0,
+            name,
+            parameters,
+        }),
+        SessionEvent::ToolCallCompleted {
+            id, name, result, ..
+        } => Some(AgentEvent::ToolCallFinished {
+            id: id.0,
+            name,
+            output: Some(result.llm_format()),
+            error: None,
+        }),
+        SessionEvent::ToolCallFailed {
+            id, name, error, ..
+        } => Some(AgentEvent::ToolCallFinished {
+            id: id.0,
+            name,
+            output: None,
+            error: Some(error),
+        }),
+        SessionEvent::ApprovalRequested {
+            request_id,
+            tool_call,
+        } => Some(AgentEvent::ApprovalRequested {
+            request_id,
+            tool_call,
+        }),
+        SessionEvent::Error { message } => Some(AgentEvent::Error { message }),
+        _ => None,
+    }
+}
+
+fn assistant_text(message: &Message) -> Option<String> {
+    if !matches!(message.data, MessageData::Assistant { .. }) {
+        return None;
+    }
+    let text = message.extract_text();
+    (!text.trim().is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{ApiError, CompletionResponse, Provider};
+    use crate::app::conversation::AssistantContent;
+    use futures::StreamExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use steer_tools::tools::BASH_TOOL_NAME;
+    use tokio_util::sync::CancellationToken;
+
+    /// Asks to run bash once, then answers with text.
+    struct BashThenText {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl Provider for BashThenText {
+        fn name(&self) -> &'static str {
+            "stub-bash-then-text"
+        }
+
+        async fn complete(
+            &self,
+            _model_id: &ModelId,
+            _messages: Vec<Message>,
+            _system: Option<crate::app::SystemContext>,
+            _tools: Option<Vec<steer_tools::ToolSchema>>,
+            _call_options: Option<crate::config::model::ModelParameters>,
+            _token: CancellationToken,
+        ) -> std::result::Result<CompletionResponse, ApiError> {
+            let content = if self.calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                AssistantContent::ToolCall {
+                    tool_call: ToolCall {
+                        id: "tc_bash".to_string(),
+                        name: BASH_TOOL_NAME.to_string(),
+                        parameters: serde_json::json!({ "command": "echo hi" }),
+                    },
+                    thought_signature: None,
+                }
+            } else {
+                AssistantContent::Text {
+                    text: "all done".to_string(),
+                }
+            };
+            Ok(CompletionResponse::new(vec![content]))
+        }
+    }
+
+    #[tokio::test]
+    async fn send_message_streams_approval_and_answer() {
+        let model_registry = Arc::new(ModelRegistry::load(&[]).unwrap());
+        let provider_registry = Arc::new(crate::auth::ProviderRegistry::load(&[]).unwrap());
+        let api_client = Arc::new(ApiClient::new_with_deps(
+            crate::test_utils::test_llm_config_provider().unwrap(),
+            provider_registry,
+            model_registry.clone(),
+        ));
+        let model = builtin::claude_sonnet_4_5();
+        api_client.insert_test_provider(
+            model.provider.clone(),
+            Arc::new(BashThenText {
+                calls: AtomicUsize::new(0),
+            }),
+        );
+        let workspace = tempfile::tempdir().unwrap();
+
+        let agent = Agent::builder()
+            .model("sonnet")
+            .workspace_path(workspace.path())
+            .start(api_client, model_registry)
+            .await
+            .unwrap();
+        assert_eq!(agent.model(), &model);
+
+        let mut turn = agent.send_message("say hi").await.unwrap();
+        let mut events = Vec::new();
+        while let Some(event) = turn.next().await {
+            if let AgentEvent::ApprovalRequested { request_id, .. } = &event {
+                agent
+                    .approve_tool(*request_id, ApprovalDecision::Denied)
+                    .await
+                    .unwrap();
+            }
+            events.push(event);
+        }
+
+        assert!(events.iter().any(|event| matches!(
+            event,
+            AgentEvent::ApprovalRequested { tool_call, .. } if tool_call.name == BASH_TOOL_NAME
+        )));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            AgentEvent::AssistantMessage { text } if text == "all done"
+        )));
+        assert!(matches!(events.last(), Some(AgentEvent::Finished)));
+
+        agent.shutdown().await;
+    }
+}
@@ -33,6 +33,12 @@ pub enum Error {
     Status(String),
     #[error("Bash command error: {0}")]
     BashCommandError(String),
+    #[error("Final answer did not match the JSON schema after {attempts} attempts: {reason}")]
+    StructuredOutput {
+        attempts: usize,
+        reason: String,
+        raw_output: String,
+    },
 }
 
 impl From<steer_tools::ToolError> for Error {
@@ -0,0 +1,622 @@
+//! Disposable copies of a session's working directory.
+//!
+//! An isolated session edits a linked git worktree (or, outside git, a copy
+//! with a baseline commit) under `~/.steer/worktrees/<session>` instead of the
+//! user's checkout. Changes are brought back as a patch applied to the source,
+//! exported as a branch, or discarded.
+
+use std::path::{Path, PathBuf};
+use std::process::{Output, Stdio};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tracing::{info, warn};
+
+use crate::app::domain::types::SessionId;
+
+const BRANCH_PREFIX: &str = "steer/";
+const BASELINE_MESSAGE: &str = "steer isolation baseline";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IsolationMode {
+    /// Tools work directly in the session's workspace.
+    #[default]
+    None,
+    /// Tools work in a disposable worktree (or copy) of the workspace.
+    Worktree,
+}
+
+#[derive(Debug, Error)]
+pub enum IsolationError {
+    #[error("IO error: {message}")]
+    Io { message: String },
+
+    #[error("git {command} failed: {message}")]
+    Git { command: String, message: String },
+
+    #[error("{message}")]
+    Unsupported { message: String },
+}
+
+impl From<std::io::Error> for IsolationError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io {
+            message: error.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum IsolationKind {
+    /// A linked worktree of the source repository on its own branch.
+    GitWorktree { repo_root: PathBuf, branch: String },
+    /// A plain copy of a directory that is not under git.
+    Copy,
+}
+
+/// Where an isolated session's changes live and how to bring them back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct IsolatedWorkspace {
+    /// Directory the session was started in.
+    pub source: PathBuf,
+    /// Root of the worktree or copy; patches are relative to it.
+    pub root: PathBuf,
+    /// Working directory for tools, matching `source` inside `root`.
+    pub path: PathBuf,
+    /// Commit the isolated copy started from.
+    pub base_commit: String,
+    pub kind: IsolationKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileChangeKind {
+    Added,
+    Modified,
+    Deleted,
+}
+
+impl FileChangeKind {
+    /// The letter `git status --short` uses for this kind of change.
+    pub fn marker(self) -> char {
+        match self {
+            FileChangeKind::Added => 'A',
+            FileChangeKind::Modified => 'M',
+            FileChangeKind::Deleted => 'D',
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileChange {
+    pub kind: FileChangeKind,
+    pub path: String,
+}
+
+/// Result of applying an isolated session's changes to its source.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeReport {
+    pub applied: Vec<String>,
+    pub conflicts: Vec<String>,
+    /// Output from `git apply` when the patch did not apply cleanly.
+    pub details: String,
+}
+
+impl MergeReport {
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Create an isolated copy of `source` for `session_id` under `worktrees_root`.
+pub async fn create(
+    worktrees_root: &Path,
+    session_id: SessionId,
+    source: &Path,
+) -> Result<IsolatedWorkspace, IsolationError> {
+    let source = tokio::fs::canonicalize(source).await?;
+    let root = worktrees_root.join(session_id.to_string());
+    if tokio::fs::try_exists(&root).await? {
+        return Err(IsolationError::Unsupported {
+            message: format!("{} already exists", root.display()),
+        });
+    }
+    tokio::fs::create_dir_all(worktrees_root).await?;
+
+    let workspace = match git_output(&source, &["rev-parse", "--show-toplevel"]).await {
+        Ok(toplevel) => {
+            let repo_root = PathBuf::from(toplevel.trim());
+            create_worktree(session_id, &source, &repo_root, root).await?
+        }
+        Err(_) => create_copy(&source, root).await?,
+    };
+
+    info!(
+        session_id = %session_id,
+        source = %workspace.source.display(),
+        path = %workspace.path.display(),
+        "Created isolated workspace"
+    );
+    Ok(workspace)
+}
+
+async fn create_worktree(
+    session_id: SessionId,
+    source: &Path,
+    repo_root: &Path,
+    root: PathBuf,
+) -> Result<IsolatedWorkspace, IsolationError> {
+    let base_commit = git_output(repo_root, &["rev-parse", "HEAD"]).await?;
+    let base_commit = base_commit.trim().to_string();
+    let branch = format!("{BRANCH_PREFIX}{session_id}");
+    let root_arg = root.to_string_lossy();
+
+    git_output(
+        repo_root,
+        &[
+            "worktree",
+            "add",
+            "-q",
+            "-b",
+            &branch,
+            &root_arg,
+            &base_commit,
+        ],
+    )
+    .await?;
+
+    let relative = source.strip_prefix(repo_root).unwrap_or(Path::new(""));
+    Ok(IsolatedWorkspace {
+        source: source.to_path_buf(),
+        path: root.join(relative),
+        root,
+        base_commit,
+        kind: IsolationKind::GitWorktree {
+            repo_root: repo_root.to_path_buf(),
+            branch,
+        },
+    })
+}
+
+async fn create_copy(source: &Path, root: PathBuf) -> Result<IsolatedWorkspace, IsolationError> {
+    let from = source.to_path_buf();
+    let to = root.clone();
+    tokio::task::spawn_blocking(move || copy_dir(&from, &to))
+        .await
+        .map_err(|e| IsolationError::Io {
+            message: e.to_string(),
+        })??;
+
+    git_output(&root, &["init", "-q"]).await?;
+    git_output(&root, &["add", "-A"]).await?;
+    git_output(
+        &root,
+        &[
+            "-c",
+            "user.name=steer",
+            "-c",
+            "user.email=steer@localhost",
+            "commit",
+            "-q",
+            "--no-verify",
+            "--allow-empty",
+            "-m",
+            BASELINE_MESSAGE,
+        ],
+    )
+    .await?;
+    let base_commit = git_output(&root, &["rev-parse", "HEAD"]).await?;
+
+    Ok(IsolatedWorkspace {
+        source: source.to_path_buf(),
+        path: root.clone(),
+        root,
+        base_commit: base_commit.trim().to_string(),
+        kind: IsolationKind::Copy,
+    })
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else if file_type.is_symlink() {
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(std::fs::read_link(entry.path())?, &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+impl IsolatedWorkspace {
+    /// Directory the patch applies to: the repository root or the copied directory.
+    fn target(&self) -> &Path {
+        match &self.kind {
+            IsolationKind::GitWorktree { repo_root, .. } => repo_root,
+            IsolationKind::Copy => &self.source,
+        }
+    }
+
+    pub fn branch(&self) -> Option<&str> {
+        match &self.kind {
+            IsolationKind::GitWorktree { branch, .. } => Some(branch),
+            IsolationKind::Copy => None,
+        }
+    }
+
+    pub async fn exists(&self) -> bool {
+        tokio::fs::try_exists(&self.root).await.unwrap_or(false)
+    }
+
+    /// Files changed since the isolated copy was created, including uncommitted
+    /// and untracked ones.
+    pub async fn changes(&self) -> Result<Vec<FileChange>, IsolationError> {
+        self.stage_all().await?;
+        let output = git_output(
+            &self.root,
+            &[
+                "diff",
+                "--cached",
+                "--name-status",
+                "--no-renames",
+                &self.base_commit,
+            ],
+        )
+        .await?;
+
+        Ok(output
+            .lines()
+            .filter_map(|line| {
+                let (status, path) = line.split_once('\t')?;
+                let kind = match status {
+                    "A" => FileChangeKind::Added,
+                    "D" => FileChangeKind::Deleted,
+                    _ => FileChangeKind::Modified,
+                };
+                Some(FileChange {
+                    kind,
+                    path: path.to_string(),
+                })
+            })
+            .collect())
+    }
+
+    /// All changes since the isolated copy was created, as a binary-safe patch.
+    pub async fn patch(&self) -> Result<String, IsolationError> {
+        self.stage_all().await?;
+        git_output(
+            &self.root,
+            &[
+                "diff",
+                "--cached",
+                "--binary",
+                "--no-color",
+                "--no-renames",
+                &self.base_commit,
+            ],
+        )
+        .await
+    }
+
+    /// Apply the session's changes to the source directory without staging or
+    /// committing them. For git sources a patch that does not apply cleanly is
+    /// merged three-way, leaving conflict markers in the conflicting files; for
+    /// copies nothing is written unless the whole patch applies.
+    pub async fn merge(&self) -> Result<MergeReport, IsolationError> {
+        let patch = self.patch().await?;
+        let changed: Vec<String> = self
+            .changes()
+            .await?
+            .into_iter()
+            .map(|change| change.path)
+            .collect();
+        if patch.trim().is_empty() {
+            return Ok(MergeReport::default());
+        }
+
+        let target = self.target();
+        let check = git_apply(target, &["--check"], &patch).await?;
+        if check.status.success() {
+            let applied = git_apply(target, &[], &patch).await?;
+            if applied.status.success() {
+                return Ok(MergeReport {
+                    applied: changed,
+                    ..MergeReport::default()
+                });
+            }
+            return Err(git_error("apply", &applied));
+        }
+
+        let check_details = String::from_utf8_lossy(&check.stderr).trim().to_string();
+        if matches!(self.kind, IsolationKind::Copy) {
+            return Ok(MergeReport {
+                applied: Vec::new(),
+                conflicts: failed_paths(&check_details),
+                details: check_details,
+            });
+        }
+
+        let three_way = git_apply(target, &["--3way"], &patch).await?;
+        let details = String::from_utf8_lossy(&three_way.stderr)
+            .trim()
+            .to_string();
+        if three_way.status.success() {
+            return Ok(MergeReport {
+                applied: changed,
+                conflicts: Vec::new(),
+                details,
+            });
+        }
+
+        let unmerged = git_output(target, &["diff", "--name-only", "--diff-filter=U"]).await?;
+        let mut conflicts: Vec<String> = unmerged.lines().map(str::to_string).collect();
+        if conflicts.is_empty() {
+            conflicts = failed_paths(&details);
+        }
+        let applied = changed
+            .into_iter()
+            .filter(|path| !conflicts.contains(path))
+            .collect();
+        Ok(MergeReport {
+            applied,
+            conflicts,
+            details,
+        })
+    }
+
+    /// Commit the session's changes on its branch and return the branch name.
+    pub async fn commit_to_branch(&self, message: &str) -> Result<String, IsolationError> {
+        let Some(branch) = self.branch() else {
+            return Err(IsolationError::Unsupported {
+                message: "the session's directory is not a git checkout; export a patch instead"
+                    .to_string(),
+            });
+        };
+
+        self.stage_all().await?;
+        let staged = git_command(&self.root, &["diff", "--cached", "--quiet"]).await?;
+        if !staged.status.success() {
+            git_output(&self.root, &["commit", "-q", "--no-verify", "-m", message]).await?;
+        }
+        Ok(branch.to_string())
+    }
+
+    /// Delete the isolated copy and, for worktrees, its branch.
+    pub async fn discard(&self) -> Result<(), IsolationError> {
+        self.remove(true).await
+    }
+
+    /// Delete the isolated copy, keeping the branch if it has commits so
+    /// exported work survives.
+    pub async fn cleanup(&self) -> Result<(), IsolationError> {
+        let keep_branch = match &self.kind {
+            IsolationKind::GitWorktree { repo_root, branch } => {
+                let range = format!("{}..{branch}", self.base_commit);
+                git_output(repo_root, &["rev-list", "--count", &range])
+                    .await
+                    .map(|count| count.trim() != "0")
+                    .unwrap_or(false)
+            }
+            IsolationKind::Copy => false,
+        };
+        self.remove(!keep_branch).await
+    }
+
+    async fn remove(&self, delete_branch: bool) -> Result<(), IsolationError> {
+        if let IsolationKind::GitWorktree { repo_root, branch } = &self.kind {
+            let root_arg = self.root.to_string_lossy();
+            if let Err(e) =
+                git_output(repo_root, &["worktree", "remove", "--force", &root_arg]).await
+            {
+                warn!(root = %self.root.display(), error = %e, "Failed to remove worktree");
+            }
+            if delete_branch && let Err(e) = git_output(repo_root, &["branch", "-D", branch]).await
+            {
+                warn!(branch = %branch, error = %e, "Failed to delete isolation branch");
+            }
+        }
+
+        if tokio::fs::try_exists(&self.root).await? {
+            tokio::fs::remove_dir_all(&self.root).await?;
+        }
+        Ok(())
+    }
+
+    async fn stage_all(&self) -> Result<(), IsolationError> {
+        git_output(&self.root, &["add", "-A"]).await.map(|_| ())
+    }
+}
+
+/// Paths named in `git apply` errors such as `error: patch failed: src/lib.rs:12`.
+fn failed_paths(details: &str) -> Vec<String> {
+    let mut paths: Vec<String> = details
+        .lines()
+        .filter_map(|line| line.strip_prefix("error: "))
+        .filter_map(|rest| {
+            if let Some(location) = rest.strip_prefix("patch failed: ") {
+                return location.rsplit_once(':').map(|(path, _)| path.to_string());
+            }
+            rest.split_once(": ").map(|(path, _)| path.to_string())
+        })
+        .collect();
+    paths.dedup();
+    paths
+}
+
+async fn git_command(dir: &Path, args: &[&str]) -> Result<Output, IsolationError> {
+    Ok(Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .output()
+        .await?)
+}
+
+async fn git_output(dir: &Path, args: &[&str]) -> Result<String, IsolationError> {
+    let output = git_command(dir, args).await?;
+    if !output.status.success() {
+        return Err(git_error(args.first().copied().unwrap_or("git"), &output));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+async fn git_apply(dir: &Path, args: &[&str], patch: &str) -> Result<Output, IsolationError> {
+    let mut child = Command::new("git")
+        .arg("apply")
+        .args(args)
+        .arg("-")
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(patch.as_bytes()).await?;
+    }
+    Ok(child.wait_with_output().await?)
+}
+
+fn git_error(command: &str, output: &Output) -> IsolationError {
+    IsolationError::Git {
+        command: command.to_string(),
+        message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    async fn init_repo(dir: &Path) {
+        for args in [
+            &["init", "-q"][..],
+            &["config", "user.name", "test"],
+            &["config", "user.email", "test@example.com"],
+        ] {
+            git_output(dir, args).await.expect("git setup");
+        }
+        tokio::fs::write(dir.join("lib.rs"), "fn a() {}\n")
+            .await
+            .unwrap();
+        git_output(dir, &["add", "-A"]).await.unwrap();
+        git_output(dir, &["commit", "-q", "-m", "init"])
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn worktree_changes_stay_out_of_source_until_merged() {
+        let source = TempDir::new().unwrap();
+        let worktrees = TempDir::new().unwrap();
+        init_repo(source.path()).await;
+
+        let isolated = create(worktrees.path(), SessionId::new(), source.path())
+            .await
+            .expect("worktree created");
+        assert!(matches!(isolated.kind, IsolationKind::GitWorktree { .. }));
+
+        tokio::fs::write(isolated.path.join("lib.rs"), "fn b() {}\n")
+            .await
+            .unwrap();
+        tokio::fs::write(isolated.path.join("new.rs"), "fn c() {}\n")
+            .await
+            .unwrap();
+        assert_eq!(
+            tokio::fs::read_to_string(source.path().join("lib.rs"))
+                .await
+                .unwrap(),
+            "fn a() {}\n"
+        );
+
+        let changes = isolated.changes().await.unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                FileChange {
+                    kind: FileChangeKind::Modified,
+                    path: "lib.rs".to_string(),
+                },
+                FileChange {
+                    kind: FileChangeKind::Added,
+                    path: "new.rs".to_string(),
+                },
+            ]
+        );
+
+        let report = isolated.merge().await.unwrap();
+        assert!(report.is_clean());
+        assert_eq!(report.applied, vec!["lib.rs", "new.rs"]);
+        assert_eq!(
+            tokio::fs::read_to_string(source.path().join("lib.rs"))
+                .await
+                .unwrap(),
+            "fn b() {}\n"
+        );
+
+        isolated.discard().await.unwrap();
+        assert!(!isolated.exists().await);
+    }
+
+    #[tokio::test]
+    async fn merge_reports_conflicts_with_source_edits() {
+        let source = TempDir::new().unwrap();
+        let worktrees = TempDir::new().unwrap();
+        init_repo(source.path()).await;
+
+        let isolated = create(worktrees.path(), SessionId::new(), source.path())
+            .await
+            .unwrap();
+        tokio::fs::write(isolated.path.join("lib.rs"), "fn from_agent() {}\n")
+            .await
+            .unwrap();
+        tokio::fs::write(source.path().join("lib.rs"), "fn from_user() {}\n")
+            .await
+            .unwrap();
+        git_output(source.path(), &["commit", "-qam", "user edit"])
+            .await
+            .unwrap();
+
+        let report = isolated.merge().await.unwrap();
+        assert_eq!(report.conflicts, vec!["lib.rs"]);
+        assert!(report.applied.is_empty());
+    }
+
+    #[tokio::test]
+    async fn copies_directories_outside_git() {
+        let source = TempDir::new().unwrap();
+        let worktrees = TempDir::new().unwrap();
+        tokio::fs::write(source.path().join("notes.txt"), "one\n")
+            .await
+            .unwrap();
+
+        let isolated = create(worktrees.path(), SessionId::new(), source.path())
+            .await
+            .unwrap();
+        assert_eq!(isolated.kind, IsolationKind::Copy);
+        assert!(isolated.commit_to_branch("msg").await.is_err());
+
+        tokio::fs::write(isolated.path.join("notes.txt"), "two\n")
+            .await
+            .unwrap();
+        let report = isolated.merge().await.unwrap();
+        assert!(report.is_clean());
+        assert_eq!(
+            tokio::fs::read_to_string(source.path().join("notes.txt"))
+                .await
+                .unwrap(),
+            "two\n"
+        );
+    }
+}
@@ -6,15 +6,25 @@ pub mod app;
 pub mod auth;
 pub mod auth_utils;
 pub mod catalog;
+pub mod commit;
 pub mod config;
+pub mod embedded;
 pub mod error;
+pub mod isolation;
+pub mod mentions;
+pub mod metrics;
 pub mod model_registry;
+pub mod offline;
 pub mod preferences;
 pub mod primary_agents;
+pub mod profiling;
 pub mod prompts;
+pub mod review;
 pub mod runners;
 pub mod session;
 pub mod test_utils;
 pub mod tools;
 pub mod utils;
 pub mod workspace;
+
+pub use steer_auth_plugin::http;
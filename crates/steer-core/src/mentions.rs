@@ -0,0 +1,287 @@
+//! Expansion of `@path` and `@path#symbol` file mentions into message context.
+//!
+//! Symbol mentions inline just the item's source range; plain mentions inline
+//! small files whole and summarize large ones as a symbol outline.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use steer_workspace::symbols::{FileSymbol, extract_symbols, find_symbol};
+use steer_workspace::{ReadFileRequest, Workspace, WorkspaceError, WorkspaceOpContext};
+use thiserror::Error;
+use tokio_util::sync::CancellationToken;
+
+/// Plain mentions of files larger than this expand to an outline.
+pub const OUTLINE_THRESHOLD_BYTES: u64 = 32 * 1024;
+
+/// Files larger than this are never parsed for symbols.
+const MAX_MENTION_FILE_BYTES: u64 = 2 * 1024 * 1024;
+
+#[derive(Debug, Error)]
+pub enum MentionError {
+    #[error("failed to read {path}: {source}")]
+    Read {
+        path: String,
+        #[source]
+        source: WorkspaceError,
+    },
+
+    #[error("{path} is a binary file")]
+    Binary { path: String },
+
+    #[error("{path} is too large to index symbols")]
+    TooLarge { path: String },
+
+    #[error("symbols are not supported for {path}")]
+    UnsupportedLanguage { path: String },
+
+    #[error("symbol '{symbol}' not found in {path}")]
+    SymbolNotFound { path: String, symbol: String },
+}
+
+struct MentionedFile {
+    content: String,
+    size: u64,
+}
+
+async fn read_mentioned_file(
+    workspace: &dyn Workspace,
+    path: &str,
+) -> Result<MentionedFile, MentionError> {
+    let ctx = WorkspaceOpContext::new("file-mention", CancellationToken::new());
+    let result = workspace
+        .read_file(
+            ReadFileRequest {
+                file_path: path.to_string(),
+                offset: None,
+                limit: None,
+                raw: Some(true),
+                max_bytes: Some(MAX_MENTION_FILE_BYTES),
+            },
+            &ctx,
+        )
+        .await
+        .map_err(|source| MentionError::Read {
+            path: path.to_string(),
+            source,
+        })?;
+
+    if result.binary_kind.is_some() {
+        return Err(MentionError::Binary {
+            path: path.to_string(),
+        });
+    }
+
+    let size = result.file_size.unwrap_or(result.content.len() as u64);
+    Ok(MentionedFile {
+        content: result.content,
+        size,
+    })
+}
+
+fn symbols_for(path: &str, file: &MentionedFile) -> Result<Vec<FileSymbol>, MentionError> {
+    if file.size > MAX_MENTION_FILE_BYTES {
+        return Err(MentionError::TooLarge {
+            path: path.to_string(),
+        });
+    }
+    extract_symbols(Path::new(path), &file.content).ok_or_else(|| {
+        MentionError::UnsupportedLanguage {
+            path: path.to_string(),
+        }
+    })
+}
+
+/// Lists the symbols defined in a workspace file, for `@file#symbol` completion.
+pub async fn list_file_symbols(
+    workspace: &dyn Workspace,
+    path: &str,
+) -> Result<Vec<FileSymbol>, MentionError> {
+    let file = read_mentioned_file(workspace, path).await?;
+    symbols_for(path, &file)
+}
+
+/// Expands a file mention into the text that is sent to the model.
+///
+/// With `symbol`, the item's source range is inlined under a header comment.
+/// Otherwise small files are inlined whole; larger files become an outline
+/// when `outline_large_files` is set, or a pointer to `read_file` when not.
+pub async fn expand_file_mention(
+    workspace: &dyn Workspace,
+    path: &str,
+    symbol: Option<&str>,
+    outline_large_files: bool,
+) -> Result<String, MentionError> {
+    let file = read_mentioned_file(workspace, path).await?;
+    let fence = fence_language(path);
+    let comment = comment_prefix(path);
+
+    if let Some(symbol_name) = symbol {
+        let symbols = symbols_for(path, &file)?;
+        let found =
+            find_symbol(&symbols, symbol_name).ok_or_else(|| MentionError::SymbolNotFound {
+                path: path.to_string(),
+                symbol: symbol_name.to_string(),
+            })?;
+        let source = file
+            .content
+            .lines()
+            .skip(found.start_line - 1)
+            .take(found.end_line + 1 - found.start_line)
+            .collect::<Vec<_>>()
+            .join("\n");
+        return Ok(format!(
+            "```{fence}\n{comment} {path}:{}-{} ({})\n{source}\n```",
+            found.start_line,
+            found.end_line,
+            found.qualified_name()
+        ));
+    }
+
+    let line_count = file.content.lines().count();
+    if file.size <= OUTLINE_THRESHOLD_BYTES {
+        return Ok(format!(
+            "```{fence}\n{comment} {path} ({line_count} lines)\n{}\n```",
+            file.content.trim_end_matches('\n')
+        ));
+    }
+
+    let read_hint = format!(
+        "Use the read_file tool with offset and limit to read the parts of {path} you need."
+    );
+    let outline = if outline_large_files {
+        symbols_for(path, &file).ok()
+    } else {
+        None
+    };
+    let Some(outline) = outline.filter(|symbols| !symbols.is_empty()) else {
+        return Ok(format!(
+            "{path} is {} bytes and was not inlined. {read_hint}",
+            file.size
+        ));
+    };
+
+    let mut text = format!(
+        "Outline of {path} ({} bytes, {line_count} lines):\n",
+        file.size
+    );
+    for symbol in &outline {
+        let _ = writeln!(
+            text,
+            "{}- {} {} (lines {}-{})",
+            "  ".repeat(symbol.depth),
+            symbol.kind,
+            symbol.name,
+            symbol.start_line,
+            symbol.end_line
+        );
+    }
+    text.push_str(&read_hint);
+    Ok(text)
+}
+
+fn extension(path: &str) -> &str {
+    Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+}
+
+fn fence_language(path: &str) -> &str {
+    match extension(path) {
+        "rs" => "rust",
+        "py" => "python",
+        "ts" | "tsx" => "typescript",
+        "js" | "jsx" | "mjs" | "cjs" => "javascript",
+        "sh" | "bash" => "bash",
+        "yml" => "yaml",
+        "md" => "markdown",
+        other => other,
+    }
+}
+
+fn comment_prefix(path: &str) -> &'static str {
+    match extension(path) {
+        "py" | "rb" | "sh" | "bash" | "toml" | "yaml" | "yml" | "r" | "pl" => "#",
+        "sql" | "lua" | "hs" => "--",
+        _ => "//",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use steer_workspace::local::LocalWorkspace;
+    use tempfile::tempdir;
+
+    const SOURCE: &str = r"/// An app.
+pub struct App;
+
+impl App {
+    pub fn spawn_agent_operation(&self) -> u32 {
+        42
+    }
+}
+";
+
+    #[tokio::test]
+    async fn symbol_mention_inlines_only_the_item() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/app.rs"), SOURCE).unwrap();
+        let workspace = LocalWorkspace::with_path(dir.path().to_path_buf())
+            .await
+            .unwrap();
+
+        let text = expand_file_mention(
+            &workspace,
+            "src/app.rs",
+            Some("spawn_agent_operation"),
+            true,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            text,
+            "```rust\n// src/app.rs:5-7 (App::spawn_agent_operation)\n    pub fn spawn_agent_operation(&self) -> u32 {\n        42\n    }\n```"
+        );
+
+        let missing = expand_file_mention(&workspace, "src/app.rs", Some("nope"), true).await;
+        assert!(matches!(missing, Err(MentionError::SymbolNotFound { .. })));
+
+        let symbols = list_file_symbols(&workspace, "src/app.rs").await.unwrap();
+        assert_eq!(symbols.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn large_plain_mention_expands_to_outline_when_enabled() {
+        let dir = tempdir().unwrap();
+        let mut content = SOURCE.to_string();
+        while (content.len() as u64) <= OUTLINE_THRESHOLD_BYTES {
+            content.push_str("// padding line for a large file\n");
+        }
+        std::fs::write(dir.path().join("big.rs"), &content).unwrap();
+        std::fs::write(dir.path().join("small.rs"), SOURCE).unwrap();
+        let workspace = LocalWorkspace::with_path(dir.path().to_path_buf())
+            .await
+            .unwrap();
+
+        let small = expand_file_mention(&workspace, "small.rs", None, true)
+            .await
+            .unwrap();
+        assert!(small.starts_with("```rust\n// small.rs (8 lines)\n/// An app."));
+
+        let outline = expand_file_mention(&workspace, "big.rs", None, true)
+            .await
+            .unwrap();
+        assert!(outline.starts_with("Outline of big.rs"));
+        assert!(outline.contains("\n  - fn spawn_agent_operation (lines 5-7)\n"));
+        assert!(outline.ends_with("you need."));
+
+        let pointer = expand_file_mention(&workspace, "big.rs", None, false)
+            .await
+            .unwrap();
+        assert!(pointer.contains("was not inlined"));
+        assert!(!pointer.contains("spawn_agent_operation"));
+    }
+}
@@ -0,0 +1,122 @@
+//! Operational metrics for tools, model requests and sessions.
+//!
+//! Everything here records through the [`metrics`](::metrics) facade, so it
+//! costs nothing until a recorder is installed (e.g. the Prometheus exporter
+//! behind `steer server --metrics-addr`).
+
+use std::future::Future;
+use std::time::Instant;
+
+use ::metrics::{
+    Unit, counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram,
+};
+
+use crate::api::provider::TokenUsage;
+use crate::app::domain::event::OperationTiming;
+use crate::config::model::ModelId;
+
+pub const TOOL_EXECUTIONS_TOTAL: &str = "steer_tool_executions_total";
+pub const TOOL_EXECUTION_DURATION_SECONDS: &str = "steer_tool_execution_duration_seconds";
+pub const TOOL_RETRIES_TOTAL: &str = "steer_tool_retries_total";
+pub const MODEL_REQUESTS_TOTAL: &str = "steer_model_requests_total";
+pub const MODEL_REQUEST_DURATION_SECONDS: &str = "steer_model_request_duration_seconds";
+pub const MODEL_TOKENS_TOTAL: &str = "steer_model_tokens_total";
+pub const ACTIVE_SESSIONS: &str = "steer_active_sessions";
+pub const OPERATION_DURATION_SECONDS: &str = "steer_operation_duration_seconds";
+pub const OPERATION_PHASE_DURATION_SECONDS: &str = "steer_operation_phase_duration_seconds";
+
+/// Register descriptions for every metric, so exporters can emit help text.
+pub fn describe() {
+    describe_counter!(TOOL_EXECUTIONS_TOTAL, "Tool executions by tool and outcome");
+    describe_histogram!(
+        TOOL_EXECUTION_DURATION_SECONDS,
+        Unit::Seconds,
+        "Tool execution latency by tool"
+    );
+    describe_counter!(
+        TOOL_RETRIES_TOTAL,
+        "Automatic retries of idempotent tools after transient failures, by tool"
+    );
+    describe_counter!(MODEL_REQUESTS_TOTAL, "Model requests by model and outcome");
+    describe_histogram!(
+        MODEL_REQUEST_DURATION_SECONDS,
+        Unit::Seconds,
+        "Model request latency by model, until the full response arrives"
+    );
+    describe_counter!(
+        MODEL_TOKENS_TOTAL,
+        Unit::Count,
+        "Tokens reported by providers, by model and kind (input or output)"
+    );
+    describe_gauge!(ACTIVE_SESSIONS, "Sessions currently loaded by the runtime");
+    describe_histogram!(
+        OPERATION_DURATION_SECONDS,
+        Unit::Seconds,
+        "Completed operation latency, from start to completion"
+    );
+    describe_histogram!(
+        OPERATION_PHASE_DURATION_SECONDS,
+        Unit::Seconds,
+        "Time completed operations spent per phase (queue wait, time to first token, streaming, tool execution, approval wait)"
+    );
+}
+
+fn outcome<T, E>(result: &Result<T, E>) -> &'static str {
+    if result.is_ok() { "success" } else { "error" }
+}
+
+/// Await a tool execution, counting it and recording its latency under `tool`.
+pub async fn time_tool<T, E, F>(tool: &str, future: F) -> Result<T, E>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    let started = Instant::now();
+    let result = future.await;
+    let tool = tool.to_string();
+    counter!(TOOL_EXECUTIONS_TOTAL, "tool" => tool.clone(), "outcome" => outcome(&result))
+        .increment(1);
+    histogram!(TOOL_EXECUTION_DURATION_SECONDS, "tool" => tool)
+        .record(started.elapsed().as_secs_f64());
+    result
+}
+
+pub fn record_tool_retry(tool: &str) {
+    counter!(TOOL_RETRIES_TOTAL, "tool" => tool.to_string()).increment(1);
+}
+
+/// Record a finished model request that started at `started`.
+pub fn record_model_request(
+    model: &ModelId,
+    started: Instant,
+    succeeded: bool,
+    usage: Option<&TokenUsage>,
+) {
+    let model = model.to_string();
+    let outcome = if succeeded { "success" } else { "error" };
+    counter!(MODEL_REQUESTS_TOTAL, "model" => model.clone(), "outcome" => outcome).increment(1);
+    histogram!(MODEL_REQUEST_DURATION_SECONDS, "model" => model.clone())
+        .record(started.elapsed().as_secs_f64());
+    if let Some(usage) = usage {
+        counter!(MODEL_TOKENS_TOTAL, "model" => model.clone(), "kind" => "input")
+            .increment(u64::from(usage.input_tokens));
+        counter!(MODEL_TOKENS_TOTAL, "model" => model, "kind" => "output")
+            .increment(u64::from(usage.output_tokens));
+    }
+}
+
+/// Record the latency breakdown of a completed operation.
+pub fn record_operation_timing(timing: &OperationTiming) {
+    histogram!(OPERATION_DURATION_SECONDS).record(seconds(timing.total_ms));
+    for span in &timing.spans {
+        histogram!(OPERATION_PHASE_DURATION_SECONDS, "phase" => span.kind.phase())
+            .record(seconds(span.duration_ms));
+    }
+}
+
+fn seconds(millis: u64) -> f64 {
+    std::time::Duration::from_millis(millis).as_secs_f64()
+}
+
+pub fn set_active_sessions(count: usize) {
+    gauge!(ACTIVE_SESSIONS).set(count as f64);
+}
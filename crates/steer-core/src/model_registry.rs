@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
 
 use tracing::debug;
 
@@ -299,6 +300,84 @@ impl ModelRegistry {
     }
 }
 
+/// What changed when a [`SharedModelRegistry`] reloaded its catalogs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CatalogReload {
+    pub added: Vec<ModelId>,
+    pub removed: Vec<ModelId>,
+    pub changed: Vec<ModelId>,
+    pub model_count: usize,
+}
+
+/// A model registry that can be replaced while the process runs.
+///
+/// Readers take a [`snapshot`](Self::snapshot); [`reload`](Self::reload)
+/// swaps in a freshly loaded registry without touching snapshots already
+/// handed out, so calls in flight finish against the registry they started
+/// with.
+#[derive(Debug, Clone)]
+pub struct SharedModelRegistry {
+    current: Arc<RwLock<Arc<ModelRegistry>>>,
+    catalog_paths: Arc<Vec<String>>,
+}
+
+impl SharedModelRegistry {
+    /// Share `registry`, reloading it from `catalog_paths` on request.
+    pub fn new(registry: Arc<ModelRegistry>, catalog_paths: Vec<String>) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(registry)),
+            catalog_paths: Arc::new(catalog_paths),
+        }
+    }
+
+    pub fn snapshot(&self) -> Arc<ModelRegistry> {
+        match self.current.read() {
+            Ok(current) => current.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Re-run [`ModelRegistry::load`] with the original catalog paths and swap
+    /// the result in. On error the current registry stays in place.
+    pub fn reload(&self) -> Result<CatalogReload, Error> {
+        let next = Arc::new(ModelRegistry::load(&self.catalog_paths)?);
+        let mut current = match self.current.write() {
+            Ok(current) => current,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        let mut reload = CatalogReload {
+            model_count: next.models.len(),
+            ..CatalogReload::default()
+        };
+        for (id, config) in &next.models {
+            match current.models.get(id) {
+                None => reload.added.push(id.clone()),
+                Some(previous) if previous != config => reload.changed.push(id.clone()),
+                Some(_) => {}
+            }
+        }
+        reload.removed = current
+            .models
+            .keys()
+            .filter(|id| !next.models.contains_key(*id))
+            .cloned()
+            .collect();
+        for ids in [&mut reload.added, &mut reload.removed, &mut reload.changed] {
+            ids.sort_by_key(ToString::to_string);
+        }
+
+        *current = next;
+        Ok(reload)
+    }
+}
+
+impl From<Arc<ModelRegistry>> for SharedModelRegistry {
+    fn from(registry: Arc<ModelRegistry>) -> Self {
+        Self::new(registry, Vec::new())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -492,6 +571,42 @@ parameters = { temperature = 0.5, max_output_tokens = 4096, top_p = 0.95 }
         assert_eq!(gemini_params.top_p, Some(0.95));
     }
 
+    #[test]
+    fn test_reload_makes_added_model_resolvable() {
+        use std::fs;
+        use tempfile::TempDir;
+
+        let dir = TempDir::new().unwrap();
+        let catalog_path = dir.path().join("catalog.toml");
+        let catalog_paths = vec![catalog_path.to_string_lossy().into_owned()];
+        let shared = SharedModelRegistry::new(
+            Arc::new(ModelRegistry::load(&catalog_paths).unwrap()),
+            catalog_paths,
+        );
+        let before = shared.snapshot();
+        assert!(before.resolve("anthropic/reloaded-model").is_err());
+
+        fs::write(
+            &catalog_path,
+            r#"
+[[models]]
+provider = "anthropic"
+id = "reloaded-model"
+aliases = ["reloaded"]
+parameters = { max_output_tokens = 4096 }
+"#,
+        )
+        .unwrap();
+        let reload = shared.reload().unwrap();
+
+        let added = ModelId::new(provider::anthropic(), "reloaded-model");
+        assert_eq!(reload.added, vec![added.clone()]);
+        assert!(reload.removed.is_empty());
+        assert_eq!(shared.snapshot().resolve("reloaded").unwrap(), added);
+        // Snapshots taken before the reload keep the old catalog.
+        assert!(before.resolve("reloaded").is_err());
+    }
+
     #[test]
     fn test_load_catalog_from_path() {
         use std::fs;
@@ -545,6 +660,7 @@ parameters = { max_output_tokens = 4096 }
             recommended: false,
             context_window_tokens: None,
             parameters: None,
+            pricing: None,
         };
         let m2 = ModelConfig {
             provider: prov.clone(),
@@ -554,6 +670,7 @@ parameters = { max_output_tokens = 4096 }
             recommended: false,
             context_window_tokens: None,
             parameters: None,
+            pricing: None,
         };
         let id1 = ModelId::new(prov.clone(), m1.id.clone());
         let id2 = ModelId::new(prov.clone(), m2.id.clone());
@@ -590,6 +707,7 @@ parameters = { max_output_tokens = 4096 }
             recommended: false,
             context_window_tokens: None,
             parameters: None,
+            pricing: None,
         };
         let m2 = ModelConfig {
             provider: prov.clone(),
@@ -599,6 +717,7 @@ parameters = { max_output_tokens = 4096 }
             recommended: false,
             context_window_tokens: None,
             parameters: None,
+            pricing: None,
         };
         let id1 = ModelId::new(prov.clone(), m1.id.clone());
         let id2 = ModelId::new(prov.clone(), m2.id.clone());
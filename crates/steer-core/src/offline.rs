@@ -0,0 +1,124 @@
+//! Offline mode.
+//!
+//! When offline, Steer skips startup work that needs the network (OAuth token
+//! refresh, startup telemetry) and refuses to build clients for cloud
+//! providers, with an error that says why. Providers whose base URL points at
+//! this machine, such as a catalog entry for Ollama on `localhost:11434`, keep
+//! working. Sessions, transcripts and local tools don't need the network and
+//! are unaffected.
+
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use url::Host;
+
+use crate::config::provider::{ApiFormat, ProviderConfig};
+
+/// Host probed by [`network_reachable`] during auto-detection.
+pub const PROBE_ADDR: &str = "api.anthropic.com:443";
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Switch offline mode on or off for the whole process.
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Whether `provider` is served from this machine, i.e. it is the scripted mock
+/// provider or its base URL is a loopback host. Other providers without a base
+/// URL talk to the vendor's cloud API.
+pub fn is_local_provider(provider: &ProviderConfig) -> bool {
+    if provider.api_format == ApiFormat::Mock {
+        return true;
+    }
+    match provider.base_url.as_ref().and_then(|url| url.host()) {
+        Some(Host::Domain(domain)) => domain.eq_ignore_ascii_case("localhost"),
+        Some(Host::Ipv4(ip)) => IpAddr::V4(ip).is_loopback(),
+        Some(Host::Ipv6(ip)) => IpAddr::V6(ip).is_loopback(),
+        None => false,
+    }
+}
+
+/// Why `provider` can't be used right now, or `None` if it can.
+pub fn unavailable_reason(provider: &ProviderConfig) -> Option<String> {
+    unavailable_reason_with(provider, is_offline())
+}
+
+fn unavailable_reason_with(provider: &ProviderConfig, offline: bool) -> Option<String> {
+    (offline && !is_local_provider(provider)).then(|| {
+        format!(
+            "{} models are unavailable offline. Use a provider running on this machine \
+             (a catalog provider with a localhost base_url, e.g. Ollama) or restart without --offline.",
+            provider.name
+        )
+    })
+}
+
+/// Whether a TCP connection to `addr` can be opened within `timeout`.
+pub async fn network_reachable(addr: &str, timeout: Duration) -> bool {
+    matches!(
+        tokio::time::timeout(timeout, tokio::net::TcpStream::connect(addr)).await,
+        Ok(Ok(_))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::provider::{ApiFormat, AuthScheme, ProviderId};
+
+    fn provider(base_url: Option<&str>) -> ProviderConfig {
+        ProviderConfig {
+            id: ProviderId("custom".to_string()),
+            name: "Custom".to_string(),
+            api_format: ApiFormat::OpenaiChat,
+            auth_schemes: vec![AuthScheme::ApiKey],
+            base_url: base_url.map(|url| url.parse().unwrap()),
+        }
+    }
+
+    #[test]
+    fn loopback_base_urls_are_local() {
+        assert!(is_local_provider(&provider(Some(
+            "http://localhost:11434/v1"
+        ))));
+        assert!(is_local_provider(&provider(Some("http://127.0.0.1:8080"))));
+        assert!(is_local_provider(&provider(Some("http://[::1]:8080"))));
+        assert!(!is_local_provider(&provider(Some(
+            "https://api.example.com"
+        ))));
+        assert!(!is_local_provider(&provider(None)));
+
+        let mock = ProviderConfig {
+            api_format: ApiFormat::Mock,
+            ..provider(None)
+        };
+        assert!(is_local_provider(&mock));
+    }
+
+    #[test]
+    fn only_cloud_providers_are_unavailable_offline() {
+        let cloud = provider(None);
+        let local = provider(Some("http://localhost:11434/v1"));
+
+        let reason = unavailable_reason_with(&cloud, true).unwrap();
+        assert!(reason.contains("Custom models are unavailable offline"));
+        assert_eq!(unavailable_reason_with(&local, true), None);
+        assert_eq!(unavailable_reason_with(&cloud, false), None);
+    }
+
+    #[tokio::test]
+    async fn network_probe_reports_closed_ports() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        assert!(network_reachable(&addr, Duration::from_secs(1)).await);
+
+        drop(listener);
+        assert!(!network_reachable(&addr, Duration::from_secs(1)).await);
+    }
+}
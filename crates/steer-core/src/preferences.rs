@@ -32,14 +32,40 @@ pub struct Preferences {
 
     #[serde(default)]
     pub telemetry: TelemetryPreferences,
+
+    #[serde(default)]
+    pub storage: StoragePreferences,
+
+    #[serde(default)]
+    pub network: NetworkPreferences,
 }
 
+/// Default cap on files loaded into the TUI file picker.
+pub const DEFAULT_FILE_LIST_LIMIT: usize = 10_000;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct UiPreferences {
     pub theme: Option<String>,
+    /// Language for CLI and TUI text, e.g. `en` or `pt-BR`. Defaults to
+    /// `LC_ALL`, `LC_MESSAGES` or `LANG`, then English.
+    pub locale: Option<String>,
     #[serde(default)]
     pub notifications: NotificationPreferences,
     pub history_limit: Option<usize>,
+    /// Maximum number of workspace files loaded for the file picker; 0 means no limit.
+    pub file_list_limit: Option<usize>,
+    /// Whether model reasoning text is shown in the chat view. Defaults to true.
+    pub show_reasoning: Option<bool>,
+    /// Whether `@file#symbol` mentions are completed and expanded. Defaults to true.
+    pub symbol_mentions: Option<bool>,
+    /// Whether plain mentions of large files expand to a symbol outline. Defaults to true.
+    pub outline_large_mentions: Option<bool>,
+    /// Whether the terminal title shows the session and what it is doing. Defaults to true.
+    pub terminal_title: Option<bool>,
+    /// Whether running operations are reported as OSC 9;4 taskbar progress (Windows
+    /// Terminal, WezTerm, ConEmu). Defaults to false: other terminals may show
+    /// OSC 9 as a desktop notification.
+    pub terminal_progress: Option<bool>,
     pub provider_priority: Option<Vec<String>>,
     #[serde(default)]
     pub editing_mode: EditingMode,
@@ -49,6 +75,15 @@ pub struct UiPreferences {
 pub struct NotificationPreferences {
     #[serde(default)]
     pub transport: NotificationTransport,
+    /// Command run when a tool approval is requested while the terminal is
+    /// unfocused, e.g. `tmux select-window -t {pane}`. It is split like a shell
+    /// command line and run without a shell; `{session_id}`, `{session_title}`
+    /// and `{pane}` (`$TMUX_PANE`) are substituted into each argument.
+    #[serde(default)]
+    pub focus_command: Option<String>,
+    /// Whether approval requests also ask iTerm2 for attention (bouncing the dock icon).
+    #[serde(default)]
+    pub iterm2_attention: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -56,6 +91,54 @@ pub struct ToolPreferences {
     pub pre_approved: Vec<String>,
 }
 
+/// How the session database is encrypted at rest.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, Display)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum SessionEncryption {
+    #[default]
+    Off,
+    /// A random key kept in the OS keyring.
+    Keyring,
+    /// A key derived from the `STEER_SESSION_PASSPHRASE` environment variable.
+    Passphrase,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct StoragePreferences {
+    #[serde(default)]
+    pub encryption: SessionEncryption,
+    /// Days to keep tool audit log entries; kept indefinitely when unset.
+    #[serde(default)]
+    pub audit_retention_days: Option<u32>,
+}
+
+/// Outbound HTTP settings applied to every request Steer makes.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct NetworkPreferences {
+    /// Proxy URL; takes precedence over `HTTPS_PROXY` and the other proxy env vars.
+    pub proxy: Option<String>,
+    /// Comma-separated hosts that bypass `proxy`.
+    pub no_proxy: Option<String>,
+    /// PEM file of extra root certificates to trust, e.g. a corporate CA.
+    pub ca_bundle: Option<PathBuf>,
+    /// Seconds before an auth request or a connection attempt fails (default 30).
+    pub request_timeout_secs: Option<u64>,
+}
+
+impl NetworkPreferences {
+    pub fn http_config(&self) -> crate::http::HttpConfig {
+        crate::http::HttpConfig {
+            proxy: self.proxy.clone(),
+            no_proxy: self.no_proxy.clone(),
+            ca_bundle: self.ca_bundle.clone(),
+            request_timeout: self
+                .request_timeout_secs
+                .map(std::time::Duration::from_secs),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TelemetryPreferences {
     #[serde(default = "default_telemetry_enabled")]
@@ -67,10 +150,38 @@ fn default_telemetry_enabled() -> bool {
     true
 }
 
+impl UiPreferences {
+    pub fn file_list_limit(&self) -> usize {
+        self.file_list_limit.unwrap_or(DEFAULT_FILE_LIST_LIMIT)
+    }
+
+    pub fn show_reasoning(&self) -> bool {
+        self.show_reasoning.unwrap_or(true)
+    }
+
+    pub fn symbol_mentions(&self) -> bool {
+        self.symbol_mentions.unwrap_or(true)
+    }
+
+    pub fn outline_large_mentions(&self) -> bool {
+        self.outline_large_mentions.unwrap_or(true)
+    }
+
+    pub fn terminal_title(&self) -> bool {
+        self.terminal_title.unwrap_or(true)
+    }
+
+    pub fn terminal_progress(&self) -> bool {
+        self.terminal_progress.unwrap_or(false)
+    }
+}
+
 impl Default for NotificationPreferences {
     fn default() -> Self {
         Self {
             transport: NotificationTransport::Auto,
+            focus_command: None,
+            iterm2_attention: false,
         }
     }
 }
@@ -144,4 +255,51 @@ mod tests {
         assert!(telemetry.enabled);
         assert_eq!(telemetry.endpoint, None);
     }
+
+    #[test]
+    fn file_list_limit_defaults_and_can_be_configured() {
+        assert_eq!(
+            UiPreferences::default().file_list_limit(),
+            DEFAULT_FILE_LIST_LIMIT
+        );
+
+        let prefs: Preferences = toml::from_str("[ui]\nfile_list_limit = 250\n").unwrap();
+        assert_eq!(prefs.ui.file_list_limit(), 250);
+    }
+
+    #[test]
+    fn terminal_title_is_on_and_progress_off_by_default() {
+        let defaults = UiPreferences::default();
+        assert!(defaults.terminal_title());
+        assert!(!defaults.terminal_progress());
+
+        let prefs: Preferences =
+            toml::from_str("[ui]\nterminal_title = false\nterminal_progress = true\n").unwrap();
+        assert!(!prefs.ui.terminal_title());
+        assert!(prefs.ui.terminal_progress());
+    }
+
+    #[test]
+    fn show_reasoning_defaults_to_true() {
+        assert!(UiPreferences::default().show_reasoning());
+
+        let prefs: Preferences = toml::from_str("[ui]\nshow_reasoning = false\n").unwrap();
+        assert!(!prefs.ui.show_reasoning());
+    }
+
+    #[test]
+    fn network_preferences_map_to_http_config() {
+        let prefs: Preferences = toml::from_str(
+            "[network]\nproxy = \"http://proxy.corp:3128\"\nca_bundle = \"/etc/ssl/corp.pem\"\nrequest_timeout_secs = 10\n",
+        )
+        .unwrap();
+        let config = prefs.network.http_config();
+        assert_eq!(config.proxy.as_deref(), Some("http://proxy.corp:3128"));
+        assert_eq!(config.no_proxy, None);
+        assert_eq!(config.ca_bundle, Some(PathBuf::from("/etc/ssl/corp.pem")));
+        assert_eq!(
+            config.request_timeout,
+            Some(std::time::Duration::from_secs(10))
+        );
+    }
 }
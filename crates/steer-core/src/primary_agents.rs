@@ -358,6 +358,9 @@ mod tests {
             metadata: HashMap::new(),
             default_model: builtin::claude_sonnet_4_5(),
             auto_compaction: crate::session::state::AutoCompactionConfig::default(),
+            auto_summary: crate::session::state::AutoSummaryConfig::default(),
+            isolation: crate::isolation::IsolationMode::None,
+            isolated_workspace: None,
         }
     }
 
@@ -0,0 +1,191 @@
+//! Lightweight wall-clock profiling for a single run.
+//!
+//! A [`Profiler`] is a cheap, cloneable handle that components record phase
+//! timings into. It is opt-in: components hold an `Option<Profiler>` and skip
+//! recording entirely when none is configured.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfilePhase {
+    CatalogLoad,
+    AuthResolution,
+    SystemPrompt,
+    ModelRequest,
+    ToolExecution,
+}
+
+impl ProfilePhase {
+    const ALL: [ProfilePhase; 5] = [
+        ProfilePhase::CatalogLoad,
+        ProfilePhase::AuthResolution,
+        ProfilePhase::SystemPrompt,
+        ProfilePhase::ModelRequest,
+        ProfilePhase::ToolExecution,
+    ];
+}
+
+/// A single recorded span, with offsets relative to the profiler's creation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhaseTiming {
+    pub phase: ProfilePhase,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub start_ms: f64,
+    pub duration_ms: f64,
+}
+
+/// Per-phase totals, so it is obvious whether time went to the model or the tools.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhaseSummary {
+    pub phase: ProfilePhase,
+    pub count: usize,
+    pub total_ms: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileReport {
+    pub total_ms: f64,
+    pub summary: Vec<PhaseSummary>,
+    pub timings: Vec<PhaseTiming>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Profiler {
+    origin: Instant,
+    timings: Arc<Mutex<Vec<PhaseTiming>>>,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+            timings: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Record a span that started at `started` and ends now.
+    pub fn record(&self, phase: ProfilePhase, label: Option<String>, started: Instant) {
+        let timing = PhaseTiming {
+            phase,
+            label,
+            start_ms: as_millis(started.saturating_duration_since(self.origin)),
+            duration_ms: as_millis(started.elapsed()),
+        };
+        self.timings
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(timing);
+    }
+
+    /// Await `future`, recording how long it took.
+    pub async fn time<F: Future>(
+        &self,
+        phase: ProfilePhase,
+        label: Option<String>,
+        future: F,
+    ) -> F::Output {
+        let started = Instant::now();
+        let output = future.await;
+        self.record(phase, label, started);
+        output
+    }
+
+    pub fn report(&self) -> ProfileReport {
+        let mut timings = self
+            .timings
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        timings.sort_by(|a, b| a.start_ms.total_cmp(&b.start_ms));
+
+        let summary = ProfilePhase::ALL
+            .iter()
+            .filter_map(|phase| {
+                let matching = timings.iter().filter(|t| t.phase == *phase);
+                let count = matching.clone().count();
+                (count > 0).then(|| PhaseSummary {
+                    phase: *phase,
+                    count,
+                    total_ms: matching.map(|t| t.duration_ms).sum(),
+                })
+            })
+            .collect();
+
+        ProfileReport {
+            total_ms: as_millis(self.origin.elapsed()),
+            summary,
+            timings,
+        }
+    }
+}
+
+/// Await `future`, recording it on `profiler` when one is configured.
+pub async fn time_phase<F: Future>(
+    profiler: Option<&Profiler>,
+    phase: ProfilePhase,
+    label: impl FnOnce() -> Option<String>,
+    future: F,
+) -> F::Output {
+    match profiler {
+        Some(profiler) => profiler.time(phase, label(), future).await,
+        None => future.await,
+    }
+}
+
+fn as_millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn report_summarizes_recorded_phases() {
+        let profiler = Profiler::new();
+
+        profiler
+            .time(ProfilePhase::CatalogLoad, None, async {})
+            .await;
+        for name in ["bash", "grep"] {
+            time_phase(
+                Some(&profiler),
+                ProfilePhase::ToolExecution,
+                || Some(name.to_string()),
+                async {},
+            )
+            .await;
+        }
+        time_phase(None, ProfilePhase::ModelRequest, || None, async {}).await;
+
+        let report = profiler.report();
+        assert_eq!(report.timings.len(), 3);
+        assert_eq!(
+            report
+                .summary
+                .iter()
+                .map(|s| (s.phase, s.count))
+                .collect::<Vec<_>>(),
+            vec![
+                (ProfilePhase::CatalogLoad, 1),
+                (ProfilePhase::ToolExecution, 2)
+            ]
+        );
+        assert_eq!(report.timings[1].label.as_deref(), Some("bash"));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["summary"][1]["phase"], "tool_execution");
+    }
+}
@@ -1,3 +1,5 @@
 pub mod one_shot_runner;
+pub mod scheduler;
 
 pub use one_shot_runner::*;
+pub use scheduler::*;
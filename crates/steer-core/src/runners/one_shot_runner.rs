@@ -3,15 +3,21 @@ use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
 
 use crate::agents::default_agent_spec_id;
+use crate::api::provider::TokenUsage;
 use crate::app::conversation::{Message, UserContent};
-use crate::app::domain::event::SessionEvent;
+use crate::app::domain::action::ApprovalDecision;
+use crate::app::domain::event::{OperationTiming, SessionEvent};
 use crate::app::domain::runtime::{RuntimeError, RuntimeHandle};
 use crate::app::domain::types::SessionId;
+use crate::commit::{CommitError, CommitOutcome};
 use crate::config::model::ModelId;
 use crate::error::{Error, Result};
+use crate::model_registry::ModelRegistry;
 use crate::session::ToolApprovalPolicy;
 use crate::session::state::SessionConfig;
-use crate::tools::{DISPATCH_AGENT_TOOL_NAME, DispatchAgentParams, DispatchAgentTarget};
+use crate::tools::{
+    CommandDenylist, DISPATCH_AGENT_TOOL_NAME, DispatchAgentParams, DispatchAgentTarget,
+};
 use steer_tools::ToolCall;
 use steer_tools::tools::BASH_TOOL_NAME;
 use steer_tools::tools::bash::BashParams;
@@ -20,8 +26,80 @@ use steer_tools::tools::bash::BashParams;
 pub struct RunOnceResult {
     pub final_message: Message,
     pub session_id: SessionId,
+    #[serde(default)]
+    pub usage: RunUsage,
+    /// Latency breakdown of the run's operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<OperationTiming>,
+    /// The final answer parsed as JSON, for runs given an output schema.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parsed_output: Option<serde_json::Value>,
 }
 
+/// Token usage summed over every model call made while handling a run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunUsage {
+    pub total: TokenUsage,
+    pub by_model: Vec<ModelUsage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelUsage {
+    pub model: ModelId,
+    pub usage: TokenUsage,
+}
+
+impl Default for RunUsage {
+    fn default() -> Self {
+        Self {
+            total: TokenUsage::new(0, 0, 0),
+            by_model: Vec::new(),
+        }
+    }
+}
+
+impl RunUsage {
+    pub fn record(&mut self, model: ModelId, usage: TokenUsage) {
+        self.total = add_usage(self.total, usage);
+        match self.by_model.iter_mut().find(|entry| entry.model == model) {
+            Some(entry) => entry.usage = add_usage(entry.usage, usage),
+            None => self.by_model.push(ModelUsage { model, usage }),
+        }
+    }
+
+    /// Estimate the cost of the run from catalog pricing. Returns `None` when
+    /// any model that was used has no pricing in the registry.
+    pub fn estimated_cost_usd(&self, registry: &ModelRegistry) -> Option<f64> {
+        self.by_model.iter().try_fold(0.0, |cost, entry| {
+            let pricing = registry.get(&entry.model)?.pricing?;
+            Some(cost + pricing.cost_usd(entry.usage.input_tokens, entry.usage.output_tokens))
+        })
+    }
+}
+
+fn add_usage(a: TokenUsage, b: TokenUsage) -> TokenUsage {
+    TokenUsage::new(
+        a.input_tokens.saturating_add(b.input_tokens),
+        a.output_tokens.saturating_add(b.output_tokens),
+        a.total_tokens.saturating_add(b.total_tokens),
+    )
+}
+
+/// What `--auto-commit` did after a headless run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum AutoCommit {
+    Committed(CommitOutcome),
+    Skipped { reason: String },
+}
+
+/// The bash command a commit is checked against in the approval policy and
+/// the denylist.
+const AUTO_COMMIT_COMMAND: &str = "git commit";
+
+/// How many times a schema-constrained run asks for a valid answer.
+pub const STRUCTURED_OUTPUT_ATTEMPTS: usize = 3;
+
 pub struct OneShotRunner;
 
 impl Default for OneShotRunner {
@@ -156,6 +234,165 @@ impl OneShotRunner {
         Self::run_in_session_with_cancel(runtime, session_id, message, model, cancel_token).await
     }
 
+    /// Run a message whose final answer must be JSON matching `schema`.
+    ///
+    /// The schema is appended to the message. After each turn the answer is
+    /// taken from the provider's native structured output mode when it has
+    /// one, and parsed from the final message otherwise. Invalid answers are
+    /// sent back to the model, up to [`STRUCTURED_OUTPUT_ATTEMPTS`] turns.
+    pub async fn run_in_session_with_schema(
+        runtime: &RuntimeHandle,
+        session_id: SessionId,
+        message: String,
+        model: ModelId,
+        schema: &serde_json::Value,
+    ) -> Result<RunOnceResult> {
+        Self::run_in_session_with_schema_and_cancel(
+            runtime,
+            session_id,
+            message,
+            model,
+            schema,
+            CancellationToken::new(),
+        )
+        .await
+    }
+
+    pub async fn run_in_session_with_schema_and_cancel(
+        runtime: &RuntimeHandle,
+        session_id: SessionId,
+        message: String,
+        model: ModelId,
+        schema: &serde_json::Value,
+        cancel_token: CancellationToken,
+    ) -> Result<RunOnceResult> {
+        let validator = jsonschema::JSONSchema::compile(schema)
+            .map_err(|e| Error::Configuration(format!("Invalid JSON schema: {e}")))?;
+        let schema_text = serde_json::to_string_pretty(schema)
+            .map_err(|e| Error::Configuration(format!("Invalid JSON schema: {e}")))?;
+
+        let mut prompt = format!(
+            "{message}\n\nYour final answer must be a single JSON value matching this JSON \
+             schema, with no other text:\n{schema_text}"
+        );
+        let mut usage = RunUsage::default();
+        let mut failure = None;
+
+        for attempt in 1..=STRUCTURED_OUTPUT_ATTEMPTS {
+            let mut result = Self::run_in_session_with_cancel(
+                runtime,
+                session_id,
+                prompt,
+                model.clone(),
+                cancel_token.clone(),
+            )
+            .await?;
+            for entry in &result.usage.by_model {
+                usage.record(entry.model.clone(), entry.usage);
+            }
+
+            let native = runtime
+                .structured_output(
+                    session_id,
+                    model.clone(),
+                    schema.clone(),
+                    cancel_token.clone(),
+                )
+                .await;
+            let (raw_output, parsed) = match native {
+                Ok(Some(value)) => (value.to_string(), Ok(value)),
+                fallback => {
+                    if let Err(e) = fallback {
+                        warn!(
+                            session_id = %session_id,
+                            error = %e,
+                            "Native structured output failed; parsing the final message"
+                        );
+                    }
+                    let text = result.final_message.extract_text();
+                    let parsed = parse_json_output(&text);
+                    (text, parsed)
+                }
+            };
+
+            match parsed.and_then(|value| validate_output(&validator, value)) {
+                Ok(value) => {
+                    result.parsed_output = Some(value);
+                    result.usage = usage;
+                    return Ok(result);
+                }
+                Err(reason) => {
+                    warn!(
+                        session_id = %session_id,
+                        attempt,
+                        %reason,
+                        "Final answer does not match the output schema"
+                    );
+                    prompt = format!(
+                        "Your final answer does not match the required JSON schema: {reason}\n\n\
+                         Reply again with only a JSON value matching the schema."
+                    );
+                    failure = Some((reason, raw_output));
+                }
+            }
+        }
+
+        let (reason, raw_output) = failure.unwrap_or_default();
+        Err(Error::StructuredOutput {
+            attempts: STRUCTURED_OUTPUT_ATTEMPTS,
+            reason,
+            raw_output,
+        })
+    }
+
+    pub async fn run_new_session_with_schema(
+        runtime: &RuntimeHandle,
+        config: SessionConfig,
+        message: String,
+        model: ModelId,
+        schema: &serde_json::Value,
+    ) -> Result<RunOnceResult> {
+        let session_id = runtime
+            .create_session(config)
+            .await
+            .map_err(|e| Error::InvalidOperation(format!("Failed to create session: {e}")))?;
+
+        info!(session_id = %session_id, "Created new session for one-shot run");
+
+        Self::run_in_session_with_schema(runtime, session_id, message, model, schema).await
+    }
+
+    /// Commit the files the session edited, if its approval policy would
+    /// run `git commit` without asking and the bash denylist allows it.
+    pub async fn auto_commit(
+        runtime: &RuntimeHandle,
+        session_id: SessionId,
+        model: ModelId,
+    ) -> Result<AutoCommit> {
+        let Some(config) = runtime.get_session_state(session_id).await?.session_config else {
+            return Ok(AutoCommit::Skipped {
+                reason: "the session has no configuration".to_string(),
+            });
+        };
+        if let Some(reason) = auto_commit_blocked(&config) {
+            return Ok(AutoCommit::Skipped { reason });
+        }
+
+        let draft = match runtime.draft_commit(session_id, model).await {
+            Ok(draft) => draft,
+            Err(RuntimeError::Commit(CommitError::NoChanges)) => {
+                return Ok(AutoCommit::Skipped {
+                    reason: CommitError::NoChanges.to_string(),
+                });
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let outcome = runtime
+            .create_commit(session_id, draft.files, draft.message)
+            .await?;
+        Ok(AutoCommit::Committed(outcome))
+    }
+
     async fn process_events(
         runtime: &RuntimeHandle,
         mut subscription: crate::app::domain::runtime::SessionEventSubscription,
@@ -164,6 +401,8 @@ impl OneShotRunner {
         mut approval_policy: ToolApprovalPolicy,
     ) -> Result<RunOnceResult> {
         let mut messages = Vec::new();
+        let mut usage = RunUsage::default();
+        let mut timing = None;
         info!(session_id = %session_id, "Starting event processing loop");
 
         while let Some(envelope) = subscription.recv().await {
@@ -186,12 +425,25 @@ impl OneShotRunner {
                     );
                 }
 
+                SessionEvent::LlmUsageUpdated {
+                    op_id: usage_op,
+                    model,
+                    usage: call_usage,
+                    ..
+                } => {
+                    if usage_op == op_id {
+                        usage.record(model, call_usage);
+                    }
+                }
+
                 SessionEvent::OperationCompleted {
                     op_id: completed_op,
+                    timing: completed_timing,
                 } => {
                     if completed_op != op_id {
                         continue;
                     }
+                    timing = completed_timing;
                     info!(
                         session_id = %session_id,
                         op_id = %completed_op,
@@ -225,18 +477,23 @@ impl OneShotRunner {
                     )));
                 }
 
+                SessionEvent::Refused { reason } => {
+                    warn!(session_id = %session_id, %reason, "Refused event");
+                    return Err(Error::InvalidOperation(reason));
+                }
+
                 SessionEvent::ApprovalRequested {
                     request_id,
                     tool_call,
                 } => {
-                    let approved = tool_is_preapproved(&tool_call, &approval_policy);
-                    if approved {
+                    let decision = if tool_is_preapproved(&tool_call, &approval_policy) {
                         info!(
                             session_id = %session_id,
                             request_id = %request_id,
                             tool = %tool_call.name,
                             "Auto-approving preapproved tool"
                         );
+                        ApprovalDecision::Approved
                     } else {
                         warn!(
                             session_id = %session_id,
@@ -244,10 +501,11 @@ impl OneShotRunner {
                             tool = %tool_call.name,
                             "Auto-denying unapproved tool"
                         );
-                    }
+                        ApprovalDecision::Denied
+                    };
 
                     runtime
-                        .submit_tool_approval(session_id, request_id, approved, None)
+                        .submit_tool_approval(session_id, request_id, decision, None)
                         .await
                         .map_err(|e| {
                             Error::InvalidOperation(format!(
@@ -274,6 +532,9 @@ impl OneShotRunner {
                 Ok(RunOnceResult {
                     final_message: final_message.clone(),
                     session_id,
+                    usage,
+                    timing,
+                    parsed_output: None,
                 })
             }
             None => Err(Error::InvalidOperation("No message received".to_string())),
@@ -312,6 +573,58 @@ fn tool_is_preapproved(tool_call: &ToolCall, policy: &ToolApprovalPolicy) -> boo
     false
 }
 
+/// Parse a final answer as JSON, allowing a surrounding markdown code fence.
+fn parse_json_output(text: &str) -> std::result::Result<serde_json::Value, String> {
+    let trimmed = text.trim();
+    let body = trimmed
+        .strip_prefix("```")
+        .and_then(|rest| rest.strip_suffix("```"))
+        .map_or(trimmed, |fenced| {
+            // Drop the info string, e.g. "json", on the opening fence line.
+            fenced.split_once('\n').map_or(fenced, |(_, code)| code)
+        });
+    serde_json::from_str(body).map_err(|e| format!("the answer is not valid JSON ({e})"))
+}
+
+fn validate_output(
+    validator: &jsonschema::JSONSchema,
+    value: serde_json::Value,
+) -> std::result::Result<serde_json::Value, String> {
+    if let Err(errors) = validator.validate(&value) {
+        let reasons: Vec<String> = errors
+            .map(|error| {
+                let path = error.instance_path.to_string();
+                if path.is_empty() {
+                    error.to_string()
+                } else {
+                    format!("{path}: {error}")
+                }
+            })
+            .collect();
+        return Err(reasons.join("; "));
+    }
+    Ok(value)
+}
+
+fn auto_commit_blocked(config: &SessionConfig) -> Option<String> {
+    let tool_call = ToolCall {
+        id: "auto_commit".to_string(),
+        name: BASH_TOOL_NAME.to_string(),
+        parameters: serde_json::json!({ "command": AUTO_COMMIT_COMMAND }),
+    };
+    if !tool_is_preapproved(&tool_call, &config.tool_config.approval_policy) {
+        return Some(format!(
+            "the session's approval policy does not pre-approve `{AUTO_COMMIT_COMMAND}`"
+        ));
+    }
+    match CommandDenylist::new(&config.tool_config.bash_denylist) {
+        Ok(denylist) => denylist
+            .check(AUTO_COMMIT_COMMAND)
+            .map(|denied| format!("`{AUTO_COMMIT_COMMAND}` is denylisted: {}", denied.reason)),
+        Err(e) => Some(format!("the session's bash denylist is invalid: {e}")),
+    }
+}
+
 impl From<RuntimeError> for Error {
     fn from(e: RuntimeError) -> Self {
         match e {
@@ -332,6 +645,12 @@ impl From<RuntimeError> for Error {
             RuntimeError::EventStore(e) => {
                 Error::InvalidOperation(format!("Event store error: {e}"))
             }
+            RuntimeError::Isolation(e) => Error::InvalidOperation(format!("Isolation error: {e}")),
+            RuntimeError::Review(e) => Error::InvalidOperation(format!("Review error: {e}")),
+            RuntimeError::Commit(e) => Error::InvalidOperation(format!("Commit error: {e}")),
+            RuntimeError::Summary(e) => Error::InvalidOperation(format!("Summary error: {e}")),
+            RuntimeError::Api(e) => Error::Api(e),
+            e @ RuntimeError::SessionLimitReached { .. } => Error::InvalidOperation(e.to_string()),
         }
     }
 }
@@ -342,7 +661,6 @@ mod tests {
     use crate::api::Client as ApiClient;
     use crate::api::{ApiError, CompletionResponse, Provider};
     use crate::app::conversation::{AssistantContent, Message, MessageData};
-    use crate::app::domain::action::ApprovalDecision;
     use crate::app::domain::runtime::RuntimeService;
     use crate::app::domain::session::event_store::InMemoryEventStore;
     use crate::app::validation::ValidatorRegistry;
@@ -439,6 +757,111 @@ mod tests {
         }
     }
 
+    #[derive(Clone)]
+    struct UsageReportingProvider {
+        usage: TokenUsage,
+    }
+
+    #[async_trait::async_trait]
+    impl Provider for UsageReportingProvider {
+        fn name(&self) -> &'static str {
+            "stub-usage"
+        }
+
+        async fn complete(
+            &self,
+            _model_id: &crate::config::model::ModelId,
+            _messages: Vec<Message>,
+            _system: Option<crate::app::SystemContext>,
+            _tools: Option<Vec<steer_tools::ToolSchema>>,
+            _call_options: Option<crate::config::model::ModelParameters>,
+            _token: CancellationToken,
+        ) -> std::result::Result<CompletionResponse, ApiError> {
+            Ok(CompletionResponse::new(vec![AssistantContent::Text {
+                text: "done".to_string(),
+            }])
+            .with_usage(self.usage))
+        }
+    }
+
+    /// Replies with each text in turn, repeating the last one.
+    #[derive(Clone)]
+    struct ScriptedTextProvider {
+        replies: Vec<String>,
+        call_count: Arc<StdMutex<usize>>,
+    }
+
+    impl ScriptedTextProvider {
+        fn new(replies: &[&str]) -> Self {
+            Self {
+                replies: replies.iter().map(|reply| (*reply).to_string()).collect(),
+                call_count: Arc::new(StdMutex::new(0)),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Provider for ScriptedTextProvider {
+        fn name(&self) -> &'static str {
+            "stub-scripted"
+        }
+
+        async fn complete(
+            &self,
+            _model_id: &crate::config::model::ModelId,
+            _messages: Vec<Message>,
+            _system: Option<crate::app::SystemContext>,
+            _tools: Option<Vec<steer_tools::ToolSchema>>,
+            _call_options: Option<crate::config::model::ModelParameters>,
+            _token: CancellationToken,
+        ) -> std::result::Result<CompletionResponse, ApiError> {
+            let call_index = {
+                let mut count = self.call_count.lock().expect("call counter lock poisoned");
+                let idx = *count;
+                *count += 1;
+                idx
+            };
+            let text = self
+                .replies
+                .get(call_index)
+                .or(self.replies.last())
+                .cloned()
+                .unwrap_or_default();
+            Ok(CompletionResponse::new(vec![AssistantContent::Text {
+                text,
+            }]))
+        }
+    }
+
+    fn create_scripted_runtime(provider: ScriptedTextProvider) -> RuntimeService {
+        let event_store = Arc::new(InMemoryEventStore::new());
+        let model_registry = Arc::new(crate::model_registry::ModelRegistry::load(&[]).unwrap());
+        let provider_registry = Arc::new(crate::auth::ProviderRegistry::load(&[]).unwrap());
+        let api_client = Arc::new(ApiClient::new_with_deps(
+            crate::test_utils::test_llm_config_provider().unwrap(),
+            provider_registry,
+            model_registry,
+        ));
+        api_client.insert_test_provider(
+            builtin::claude_sonnet_4_5().provider.clone(),
+            Arc::new(provider),
+        );
+
+        let tool_executor = Arc::new(ToolExecutor::with_components(
+            Arc::new(BackendRegistry::new()),
+            Arc::new(ValidatorRegistry::new()),
+        ));
+        RuntimeService::spawn(event_store, api_client, tool_executor)
+    }
+
+    fn answer_schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": { "answer": { "type": "integer" } },
+            "required": ["answer"],
+        })
+    }
+
     async fn create_test_runtime() -> RuntimeService {
         let event_store = Arc::new(InMemoryEventStore::new());
         let model_registry = Arc::new(crate::model_registry::ModelRegistry::load(&[]).unwrap());
@@ -473,6 +896,9 @@ mod tests {
             title: None,
             metadata: std::collections::HashMap::new(),
             auto_compaction: crate::session::state::AutoCompactionConfig::default(),
+            auto_summary: crate::session::state::AutoSummaryConfig::default(),
+            isolation: crate::isolation::IsolationMode::None,
+            isolated_workspace: None,
         }
     }
 
@@ -502,6 +928,33 @@ mod tests {
         assert!(tool_is_preapproved(&tool_call, &policy));
     }
 
+    #[test]
+    fn auto_commit_requires_preapproved_git_commit() {
+        use crate::session::state::ToolRule;
+
+        let mut config = create_test_session_config();
+        assert!(
+            auto_commit_blocked(&config)
+                .is_some_and(|reason| reason.contains("does not pre-approve"))
+        );
+
+        config
+            .tool_config
+            .approval_policy
+            .preapproved
+            .per_tool
+            .insert(
+                BASH_TOOL_NAME.to_string(),
+                ToolRule::Bash {
+                    patterns: vec!["git commit*".to_string()],
+                },
+            );
+        assert_eq!(auto_commit_blocked(&config), None);
+
+        config.tool_config.bash_denylist.deny = vec!["git commit".to_string()];
+        assert!(auto_commit_blocked(&config).is_some_and(|reason| reason.contains("denylisted")));
+    }
+
     #[test]
     fn tool_is_preapproved_allows_bash_pattern() {
         use crate::session::state::{ApprovalRules, ToolRule, UnapprovedBehavior};
@@ -657,6 +1110,239 @@ mod tests {
         runtime.shutdown().await;
     }
 
+    #[test]
+    fn parse_json_output_accepts_fenced_json() {
+        assert_eq!(
+            parse_json_output("```json\n{\"answer\": 1}\n```"),
+            Ok(json!({ "answer": 1 }))
+        );
+        assert_eq!(parse_json_output("  [1, 2]\n"), Ok(json!([1, 2])));
+        assert!(parse_json_output("The answer is 1.").is_err());
+    }
+
+    #[test]
+    fn validate_output_reports_the_failing_path() {
+        let validator = jsonschema::JSONSchema::compile(&answer_schema()).unwrap();
+
+        assert_eq!(
+            validate_output(&validator, json!({ "answer": 3 })),
+            Ok(json!({ "answer": 3 }))
+        );
+        let reason = validate_output(&validator, json!({ "answer": "three" })).unwrap_err();
+        assert!(
+            reason.starts_with("/answer: "),
+            "unexpected reason: {reason}"
+        );
+    }
+
+    #[tokio::test]
+    async fn run_with_schema_retries_until_the_answer_validates() {
+        let provider =
+            ScriptedTextProvider::new(&["The answer is 42.", "```json\n{\"answer\": 42}\n```"]);
+        let call_count = provider.call_count.clone();
+        let runtime = create_scripted_runtime(provider);
+
+        let result = OneShotRunner::run_new_session_with_schema(
+            &runtime.handle,
+            create_test_session_config(),
+            "What is the answer?".to_string(),
+            builtin::claude_sonnet_4_5(),
+            &answer_schema(),
+        )
+        .await
+        .expect("second answer should validate");
+        runtime.shutdown().await;
+
+        assert_eq!(result.parsed_output, Some(json!({ "answer": 42 })));
+        assert_eq!(*call_count.lock().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn run_with_schema_fails_with_the_last_output_after_retries() {
+        let provider = ScriptedTextProvider::new(&[r#"{"answer": "forty-two"}"#]);
+        let call_count = provider.call_count.clone();
+        let runtime = create_scripted_runtime(provider);
+
+        let error = OneShotRunner::run_new_session_with_schema(
+            &runtime.handle,
+            create_test_session_config(),
+            "What is the answer?".to_string(),
+            builtin::claude_sonnet_4_5(),
+            &answer_schema(),
+        )
+        .await
+        .expect_err("answer never matches the schema");
+        runtime.shutdown().await;
+
+        match error {
+            Error::StructuredOutput {
+                attempts,
+                reason,
+                raw_output,
+            } => {
+                assert_eq!(attempts, STRUCTURED_OUTPUT_ATTEMPTS);
+                assert!(reason.contains("/answer"), "unexpected reason: {reason}");
+                assert_eq!(raw_output, r#"{"answer": "forty-two"}"#);
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+        assert_eq!(*call_count.lock().unwrap(), STRUCTURED_OUTPUT_ATTEMPTS);
+    }
+
+    #[tokio::test]
+    async fn run_new_session_aggregates_usage_and_estimates_cost() {
+        let event_store = Arc::new(InMemoryEventStore::new());
+        let model_registry = Arc::new(crate::model_registry::ModelRegistry::load(&[]).unwrap());
+        let provider_registry = Arc::new(crate::auth::ProviderRegistry::load(&[]).unwrap());
+        let api_client = Arc::new(ApiClient::new_with_deps(
+            crate::test_utils::test_llm_config_provider().unwrap(),
+            provider_registry,
+            model_registry,
+        ));
+        api_client.insert_test_provider(
+            builtin::claude_sonnet_4_5().provider.clone(),
+            Arc::new(UsageReportingProvider {
+                usage: TokenUsage::from_input_output(2_000, 500),
+            }),
+        );
+
+        let tool_executor = Arc::new(ToolExecutor::with_components(
+            Arc::new(BackendRegistry::new()),
+            Arc::new(ValidatorRegistry::new()),
+        ));
+        let runtime = RuntimeService::spawn(event_store, api_client, tool_executor);
+
+        let model = builtin::claude_sonnet_4_5();
+        let result = OneShotRunner::run_new_session(
+            &runtime.handle,
+            create_test_session_config(),
+            "Say done".to_string(),
+            model.clone(),
+        )
+        .await
+        .expect("run_new_session should complete");
+        runtime.shutdown().await;
+
+        assert_eq!(
+            result.usage.total,
+            TokenUsage::from_input_output(2_000, 500)
+        );
+        assert_eq!(
+            result.usage.by_model,
+            vec![ModelUsage {
+                model,
+                usage: TokenUsage::from_input_output(2_000, 500),
+            }]
+        );
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let catalog_path = temp_dir.path().join("pricing.toml");
+        std::fs::write(
+            &catalog_path,
+            r#"
+[[models]]
+provider = "anthropic"
+id = "claude-sonnet-4-5"
+pricing = { input_per_million = 10.0, output_per_million = 40.0 }
+"#,
+        )
+        .unwrap();
+        let registry = ModelRegistry::load(&[catalog_path.to_string_lossy().to_string()]).unwrap();
+
+        let cost = result
+            .usage
+            .estimated_cost_usd(&registry)
+            .expect("priced model should produce a cost");
+        assert!((cost - 0.04).abs() < 1e-9, "unexpected cost {cost}");
+
+        assert_eq!(
+            result.usage.estimated_cost_usd(&ModelRegistry::empty()),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn run_new_session_replays_mock_script() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let script_path = temp_dir.path().join("script.json");
+        std::fs::write(
+            &script_path,
+            r#"[
+  {"text": "Checking.", "tool_calls": [{"name": "ls", "parameters": {"path": "."}}]},
+  {"text": "Done."}
+]"#,
+        )
+        .unwrap();
+        let catalog_path = temp_dir.path().join("mock.toml");
+        std::fs::write(
+            &catalog_path,
+            format!(
+                r#"
+[[providers]]
+id = "scripted"
+name = "Scripted"
+api_format = "mock"
+auth_schemes = []
+base_url = "{}"
+
+[[models]]
+provider = "scripted"
+id = "demo"
+parameters = {{ max_output_tokens = 1024 }}
+"#,
+                url::Url::from_file_path(&script_path).unwrap()
+            ),
+        )
+        .unwrap();
+        let catalogs = [catalog_path.to_string_lossy().to_string()];
+
+        let event_store = Arc::new(InMemoryEventStore::new());
+        let api_client = Arc::new(ApiClient::new_with_deps(
+            crate::test_utils::test_llm_config_provider().unwrap(),
+            Arc::new(crate::auth::ProviderRegistry::load(&catalogs).unwrap()),
+            Arc::new(ModelRegistry::load(&catalogs).unwrap()),
+        ));
+        let tool_executor = Arc::new(ToolExecutor::with_components(
+            Arc::new(BackendRegistry::new()),
+            Arc::new(ValidatorRegistry::new()),
+        ));
+        let runtime = RuntimeService::spawn(event_store, api_client, tool_executor);
+
+        let model = ModelId::new(
+            crate::config::provider::ProviderId("scripted".to_string()),
+            "demo",
+        );
+        let result = OneShotRunner::run_new_session(
+            &runtime.handle,
+            create_test_session_config(),
+            "What's here?".to_string(),
+            model,
+        )
+        .await
+        .expect("mock run should complete");
+
+        assert_eq!(result.final_message.extract_text(), "Done.");
+
+        let events = runtime
+            .handle
+            .load_events_after(result.session_id, 0)
+            .await
+            .expect("load events");
+        let tool_result_ids: Vec<_> = events
+            .into_iter()
+            .filter_map(|(_, event)| match event {
+                SessionEvent::ToolMessageAdded { message } => match message.data {
+                    MessageData::Tool { tool_use_id, .. } => Some(tool_use_id),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        assert_eq!(tool_result_ids, vec!["mock-0-0".to_string()]);
+
+        runtime.shutdown().await;
+    }
+
     #[tokio::test]
     async fn run_new_session_updates_approval_policy_after_agent_switch_event() {
         let event_store = Arc::new(InMemoryEventStore::new());
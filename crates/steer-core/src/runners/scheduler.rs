@@ -0,0 +1,463 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use cron::Schedule;
+use thiserror::Error;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::app::domain::event::SessionEvent;
+use crate::app::domain::runtime::{RuntimeError, RuntimeHandle, WebhookEventKind, WebhookNotifier};
+use crate::app::domain::session::{
+    ScheduledRunOutcome, ScheduledTask, ScheduledTaskRun, ScheduledTaskStore,
+    ScheduledTaskStoreError,
+};
+use crate::app::domain::types::SessionId;
+use crate::error::Error;
+use crate::session::state::SessionConfig;
+
+use super::OneShotRunner;
+
+/// Session metadata key tagging sessions created by a scheduled task.
+pub const SCHEDULED_TASK_METADATA_KEY: &str = "scheduled_task_id";
+
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Error)]
+pub enum SchedulerError {
+    #[error("Invalid schedule '{expression}': {message}")]
+    InvalidSchedule { expression: String, message: String },
+
+    #[error("Scheduled task not found: {id}")]
+    NotFound { id: String },
+
+    #[error("Scheduled task {id} is already running")]
+    AlreadyRunning { id: String },
+
+    #[error(transparent)]
+    Store(#[from] ScheduledTaskStoreError),
+
+    #[error(transparent)]
+    Runtime(#[from] RuntimeError),
+}
+
+/// Parse a cron expression. Five-field expressions are accepted and fire at
+/// second zero of each matching minute.
+pub fn parse_schedule(expression: &str) -> Result<Schedule, SchedulerError> {
+    let trimmed = expression.trim();
+    let normalized = if trimmed.split_whitespace().count() == 5 {
+        format!("0 {trimmed}")
+    } else {
+        trimmed.to_string()
+    };
+
+    Schedule::from_str(&normalized).map_err(|e| SchedulerError::InvalidSchedule {
+        expression: expression.to_string(),
+        message: e.to_string(),
+    })
+}
+
+/// Owns scheduled tasks for a server: persists them, fires them on schedule
+/// and makes sure a task never has two runs in flight.
+pub struct Scheduler {
+    store: Arc<dyn ScheduledTaskStore>,
+    runtime: RuntimeHandle,
+    running: Arc<Mutex<HashSet<String>>>,
+    notifier: Option<Arc<WebhookNotifier>>,
+}
+
+impl Scheduler {
+    pub fn new(store: Arc<dyn ScheduledTaskStore>, runtime: RuntimeHandle) -> Self {
+        Self {
+            store,
+            runtime,
+            running: Arc::new(Mutex::new(HashSet::new())),
+            notifier: None,
+        }
+    }
+
+    /// Send `budget_exceeded` webhooks when a run is cancelled for its token budget.
+    pub fn with_notifier(mut self, notifier: Arc<WebhookNotifier>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    pub async fn create_task(
+        &self,
+        schedule: String,
+        prompt: String,
+        config: SessionConfig,
+        max_total_tokens: Option<u64>,
+    ) -> Result<ScheduledTask, SchedulerError> {
+        parse_schedule(&schedule)?;
+
+        let task = ScheduledTask {
+            id: uuid::Uuid::new_v4().to_string(),
+            schedule,
+            prompt,
+            config,
+            max_total_tokens,
+            created_at: Utc::now(),
+            last_run: None,
+        };
+        self.store.create_task(&task).await?;
+
+        info!(task_id = %task.id, schedule = %task.schedule, "Created scheduled task");
+        Ok(task)
+    }
+
+    pub async fn list_tasks(&self) -> Result<Vec<ScheduledTask>, SchedulerError> {
+        Ok(self.store.list_tasks().await?)
+    }
+
+    pub async fn get_task(&self, id: &str) -> Result<ScheduledTask, SchedulerError> {
+        self.store
+            .get_task(id)
+            .await?
+            .ok_or_else(|| SchedulerError::NotFound { id: id.to_string() })
+    }
+
+    pub async fn delete_task(&self, id: &str) -> Result<(), SchedulerError> {
+        if self.store.delete_task(id).await? {
+            info!(task_id = %id, "Deleted scheduled task");
+            Ok(())
+        } else {
+            Err(SchedulerError::NotFound { id: id.to_string() })
+        }
+    }
+
+    pub fn is_running(&self, id: &str) -> bool {
+        self.running
+            .lock()
+            .is_ok_and(|running| running.contains(id))
+    }
+
+    /// Start a run of the task immediately, outside its schedule. Returns the
+    /// session created for the run; the run itself continues in the background.
+    pub async fn run_now(&self, id: &str) -> Result<SessionId, SchedulerError> {
+        let task = self.get_task(id).await?;
+        self.start_run(task).await
+    }
+
+    /// Fire tasks as their schedules come due until `shutdown` is cancelled.
+    pub fn spawn(self: Arc<Self>, shutdown: CancellationToken) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut next_runs = HashMap::new();
+            let mut interval = tokio::time::interval(TICK_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                tokio::select! {
+                    () = shutdown.cancelled() => break,
+                    _ = interval.tick() => {}
+                }
+
+                let tasks = match self.store.list_tasks().await {
+                    Ok(tasks) => tasks,
+                    Err(e) => {
+                        warn!(error = %e, "Failed to load scheduled tasks");
+                        continue;
+                    }
+                };
+
+                for task in due_tasks(&tasks, &mut next_runs, Utc::now()) {
+                    let task_id = task.id.clone();
+                    match self.start_run(task).await {
+                        Ok(session_id) => {
+                            info!(task_id = %task_id, session_id = %session_id, "Started scheduled run");
+                        }
+                        Err(SchedulerError::AlreadyRunning { .. }) => {
+                            info!(task_id = %task_id, "Skipping scheduled run; previous run still in progress");
+                        }
+                        Err(e) => {
+                            warn!(task_id = %task_id, error = %e, "Failed to start scheduled run");
+                        }
+                    }
+                }
+            }
+
+            debug!("Scheduler stopped");
+        })
+    }
+
+    async fn start_run(&self, task: ScheduledTask) -> Result<SessionId, SchedulerError> {
+        let claim = RunClaim::acquire(&self.running, &task.id)?;
+
+        let mut config = task.config.clone();
+        config
+            .metadata
+            .insert(SCHEDULED_TASK_METADATA_KEY.to_string(), task.id.clone());
+        let model = config.default_model.clone();
+
+        let session_id = self.runtime.create_session(config).await?;
+        let started_at = Utc::now();
+        self.store
+            .record_run(
+                &task.id,
+                &ScheduledTaskRun {
+                    session_id,
+                    started_at,
+                    finished_at: None,
+                    outcome: ScheduledRunOutcome::Running,
+                },
+            )
+            .await?;
+
+        let cancel_token = CancellationToken::new();
+        let budget_exceeded = Arc::new(AtomicBool::new(false));
+        let budget_watcher = match task.max_total_tokens {
+            Some(max_total_tokens) => Some(
+                self.watch_budget(
+                    session_id,
+                    max_total_tokens,
+                    cancel_token.clone(),
+                    budget_exceeded.clone(),
+                )
+                .await?,
+            ),
+            None => None,
+        };
+
+        let runtime = self.runtime.clone();
+        let store = self.store.clone();
+        let notifier = self.notifier.clone();
+        tokio::spawn(async move {
+            let _claim = claim;
+            let result = OneShotRunner::run_in_session_with_cancel(
+                &runtime,
+                session_id,
+                task.prompt.clone(),
+                model,
+                cancel_token,
+            )
+            .await;
+
+            if let Some(watcher) = budget_watcher {
+                watcher.abort();
+            }
+
+            let outcome = match result {
+                Ok(_) => ScheduledRunOutcome::Succeeded,
+                Err(Error::Cancelled) if budget_exceeded.load(Ordering::SeqCst) => {
+                    ScheduledRunOutcome::BudgetExceeded
+                }
+                Err(e) => ScheduledRunOutcome::Failed {
+                    message: e.to_string(),
+                },
+            };
+            info!(task_id = %task.id, session_id = %session_id, outcome = ?outcome, "Scheduled run finished");
+
+            if let (ScheduledRunOutcome::BudgetExceeded, Some(notifier)) = (&outcome, &notifier) {
+                let summary = format!(
+                    "Scheduled task {} exceeded its budget of {} tokens",
+                    task.id,
+                    task.max_total_tokens.unwrap_or_default()
+                );
+                notifier.notify(notifier.payload(
+                    WebhookEventKind::BudgetExceeded,
+                    session_id,
+                    summary,
+                ));
+            }
+
+            let run = ScheduledTaskRun {
+                session_id,
+                started_at,
+                finished_at: Some(Utc::now()),
+                outcome,
+            };
+            if let Err(e) = store.record_run(&task.id, &run).await {
+                warn!(task_id = %task.id, error = %e, "Failed to record scheduled run");
+            }
+        });
+
+        Ok(session_id)
+    }
+
+    async fn watch_budget(
+        &self,
+        session_id: SessionId,
+        max_total_tokens: u64,
+        cancel_token: CancellationToken,
+        budget_exceeded: Arc<AtomicBool>,
+    ) -> Result<JoinHandle<()>, SchedulerError> {
+        let mut subscription = self.runtime.subscribe_events(session_id).await?;
+
+        Ok(tokio::spawn(async move {
+            let mut used: u64 = 0;
+            while let Some(envelope) = subscription.recv().await {
+                if let SessionEvent::LlmUsageUpdated { usage, .. } = envelope.event {
+                    used += u64::from(usage.total_tokens);
+                    if used > max_total_tokens {
+                        warn!(
+                            session_id = %session_id,
+                            used,
+                            max_total_tokens,
+                            "Scheduled run exceeded its token budget; cancelling"
+                        );
+                        budget_exceeded.store(true, Ordering::SeqCst);
+                        cancel_token.cancel();
+                        break;
+                    }
+                }
+            }
+        }))
+    }
+}
+
+/// Marks a task as running for as long as it is held.
+struct RunClaim {
+    running: Arc<Mutex<HashSet<String>>>,
+    task_id: String,
+}
+
+impl RunClaim {
+    fn acquire(
+        running: &Arc<Mutex<HashSet<String>>>,
+        task_id: &str,
+    ) -> Result<Self, SchedulerError> {
+        let mut guard = running
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if !guard.insert(task_id.to_string()) {
+            return Err(SchedulerError::AlreadyRunning {
+                id: task_id.to_string(),
+            });
+        }
+
+        Ok(Self {
+            running: running.clone(),
+            task_id: task_id.to_string(),
+        })
+    }
+}
+
+impl Drop for RunClaim {
+    fn drop(&mut self) {
+        let mut guard = self
+            .running
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        guard.remove(&self.task_id);
+    }
+}
+
+/// Return the tasks whose next fire time has passed. Fire times are computed
+/// from when a task is first seen, so schedules missed while the server was
+/// down are skipped rather than replayed.
+fn due_tasks(
+    tasks: &[ScheduledTask],
+    next_runs: &mut HashMap<String, DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Vec<ScheduledTask> {
+    next_runs.retain(|id, _| tasks.iter().any(|task| &task.id == id));
+
+    let mut due = Vec::new();
+    for task in tasks {
+        let schedule = match parse_schedule(&task.schedule) {
+            Ok(schedule) => schedule,
+            Err(e) => {
+                warn!(task_id = %task.id, error = %e, "Ignoring task with invalid schedule");
+                continue;
+            }
+        };
+
+        let Some(next) = next_runs.get(&task.id).copied() else {
+            if let Some(next) = schedule.after(&now).next() {
+                next_runs.insert(task.id.clone(), next);
+            }
+            continue;
+        };
+
+        if next <= now {
+            due.push(task.clone());
+            match schedule.after(&now).next() {
+                Some(next) => next_runs.insert(task.id.clone(), next),
+                None => next_runs.remove(&task.id),
+            };
+        }
+    }
+
+    due
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::model::builtin;
+    use chrono::TimeZone;
+
+    fn task(id: &str, schedule: &str) -> ScheduledTask {
+        ScheduledTask {
+            id: id.to_string(),
+            schedule: schedule.to_string(),
+            prompt: "summarize open issues".to_string(),
+            config: SessionConfig::read_only(builtin::claude_sonnet_4_5()),
+            max_total_tokens: None,
+            created_at: Utc::now(),
+            last_run: None,
+        }
+    }
+
+    #[test]
+    fn parses_five_and_six_field_schedules() {
+        assert!(parse_schedule("*/5 * * * *").is_ok());
+        assert!(parse_schedule("30 */5 * * * *").is_ok());
+        assert!(matches!(
+            parse_schedule("every tuesday"),
+            Err(SchedulerError::InvalidSchedule { .. })
+        ));
+    }
+
+    #[test]
+    fn missed_schedules_are_not_replayed() {
+        let tasks = vec![task("hourly", "0 * * * *")];
+        let mut next_runs = HashMap::new();
+
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 10, 30, 0).unwrap();
+        assert!(due_tasks(&tasks, &mut next_runs, start).is_empty());
+        assert_eq!(
+            next_runs["hourly"],
+            Utc.with_ymd_and_hms(2026, 1, 1, 11, 0, 0).unwrap()
+        );
+
+        // Three hours pass without a tick: the task fires once, not three times.
+        let later = Utc.with_ymd_and_hms(2026, 1, 1, 13, 15, 0).unwrap();
+        assert_eq!(due_tasks(&tasks, &mut next_runs, later).len(), 1);
+        assert!(due_tasks(&tasks, &mut next_runs, later).is_empty());
+        assert_eq!(
+            next_runs["hourly"],
+            Utc.with_ymd_and_hms(2026, 1, 1, 14, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn deleted_tasks_are_forgotten() {
+        let mut next_runs = HashMap::new();
+        let now = Utc.with_ymd_and_hms(2026, 1, 1, 10, 30, 0).unwrap();
+        due_tasks(&[task("a", "0 * * * *")], &mut next_runs, now);
+        assert!(next_runs.contains_key("a"));
+
+        due_tasks(&[], &mut next_runs, now);
+        assert!(next_runs.is_empty());
+    }
+
+    #[test]
+    fn overlapping_runs_are_rejected() {
+        let running = Arc::new(Mutex::new(HashSet::new()));
+
+        let claim = RunClaim::acquire(&running, "nightly").unwrap();
+        assert!(matches!(
+            RunClaim::acquire(&running, "nightly"),
+            Err(SchedulerError::AlreadyRunning { .. })
+        ));
+
+        drop(claim);
+        assert!(RunClaim::acquire(&running, "nightly").is_ok());
+    }
+}
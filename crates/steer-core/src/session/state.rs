@@ -1,6 +1,7 @@
 use crate::config::model::ModelId;
 use crate::error::Result;
-use crate::tools::builtin_tools::READ_ONLY_TOOL_NAMES;
+use crate::isolation::{IsolatedWorkspace, IsolationMode};
+use crate::tools::builtin_tools::{NETWORK_TOOL_NAMES, READ_ONLY_TOOL_NAMES};
 use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -71,9 +72,10 @@ impl WorkspaceConfig {
     /// Convert to steer_workspace::WorkspaceConfig
     pub fn to_workspace_config(&self) -> steer_workspace::WorkspaceConfig {
         match self {
-            WorkspaceConfig::Local { path } => {
-                steer_workspace::WorkspaceConfig::Local { path: path.clone() }
-            }
+            WorkspaceConfig::Local { path } => steer_workspace::WorkspaceConfig::Local {
+                path: path.clone(),
+                context_limits: Default::default(),
+            },
             WorkspaceConfig::Remote {
                 agent_address,
                 auth,
@@ -161,6 +163,14 @@ pub struct SessionConfig {
     pub default_model: ModelId,
     #[serde(default)]
     pub auto_compaction: AutoCompactionConfig,
+    #[serde(default)]
+    pub auto_summary: AutoSummaryConfig,
+    /// Whether tools run in a disposable worktree instead of the workspace.
+    #[serde(default)]
+    pub isolation: IsolationMode,
+    /// The worktree created for an isolated session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub isolated_workspace: Option<IsolatedWorkspace>,
 }
 
 impl SessionConfig {
@@ -228,11 +238,21 @@ impl SessionConfig {
         Ok((registry, mcp_servers))
     }
 
-    /// Filter tools based on visibility settings
+    /// Filter tools based on visibility settings and the session's network
+    /// policy
     pub fn filter_tools_by_visibility(
         &self,
         tools: Vec<steer_tools::ToolSchema>,
     ) -> Vec<steer_tools::ToolSchema> {
+        let tools = if self.tool_config.allow_network {
+            tools
+        } else {
+            tools
+                .into_iter()
+                .filter(|schema| !NETWORK_TOOL_NAMES.contains(&schema.name.as_str()))
+                .collect()
+        };
+
         match &self.tool_config.visibility {
             ToolVisibility::All => tools,
             ToolVisibility::ReadOnly => {
@@ -257,6 +277,30 @@ impl SessionConfig {
         }
     }
 
+    /// The stored conversation summary, if one has been generated.
+    pub fn summary(&self) -> Option<&str> {
+        self.metadata
+            .get(SUMMARY_METADATA_KEY)
+            .map(String::as_str)
+            .filter(|summary| !summary.trim().is_empty())
+    }
+
+    /// The model that writes conversation summaries.
+    pub fn summary_model(&self) -> ModelId {
+        self.auto_summary
+            .model
+            .clone()
+            .unwrap_or_else(|| self.default_model.clone())
+    }
+
+    /// How many conversation messages the stored summary covers.
+    pub fn summary_message_count(&self) -> usize {
+        self.metadata
+            .get(SUMMARY_MESSAGE_COUNT_METADATA_KEY)
+            .and_then(|count| count.parse().ok())
+            .unwrap_or(0)
+    }
+
     /// Minimal read-only configuration
     #[cfg(test)]
     pub fn read_only(default_model: ModelId) -> Self {
@@ -277,6 +321,9 @@ impl SessionConfig {
             metadata: HashMap::new(),
             default_model,
             auto_compaction: AutoCompactionConfig::default(),
+            auto_summary: AutoSummaryConfig::default(),
+            isolation: IsolationMode::None,
+            isolated_workspace: None,
         }
     }
 }
@@ -303,6 +350,41 @@ impl AutoCompactionConfig {
     }
 }
 
+/// Metadata key holding the session's conversation summary.
+pub const SUMMARY_METADATA_KEY: &str = "summary";
+/// Metadata key holding how many messages the stored summary covers.
+pub const SUMMARY_MESSAGE_COUNT_METADATA_KEY: &str = "summary_message_count";
+
+/// Configuration for background conversation summaries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct AutoSummaryConfig {
+    pub enabled: bool,
+    /// Model used to summarize; the session's default model when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<ModelId>,
+    /// Refresh the summary once this many messages were added since it was written.
+    pub refresh_after_messages: u32,
+}
+
+impl Default for AutoSummaryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            model: None,
+            refresh_after_messages: 10,
+        }
+    }
+}
+
+impl AutoSummaryConfig {
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+}
+
 /// User-controlled policy overrides applied on top of a primary agent base policy.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SessionPolicyOverrides {
@@ -618,12 +700,93 @@ pub enum BackendConfig {
     },
 }
 
+/// Byte caps applied to tool results before they are sent to the model.
+///
+/// Unset caps are derived from the active model's context window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ToolResultBudget {
+    /// Maximum size of a single tool result.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_result_bytes: Option<usize>,
+    /// Maximum combined size of all tool results in one turn.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_turn_bytes: Option<usize>,
+}
+
+/// Bash commands refused before execution, whatever the approval state.
+///
+/// Rules are commands such as `git reset --hard`: a command matches when it runs
+/// the same program with all of the rule's arguments, in any order. Clustered
+/// short flags are split, so `rm -r /` also matches `rm -rf /`, an argument
+/// ending in `*` matches any suffix, and one ending in `\*` a literal `*`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct BashDenylistConfig {
+    /// Rules checked in addition to the built-in ones.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+    /// Commands that run even when they match a deny rule.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    /// Drop the built-in rules and only check `deny`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disable_defaults: bool,
+}
+
+/// Context window assumed when the model does not report one.
+const FALLBACK_CONTEXT_WINDOW_TOKENS: u32 = 128_000;
+
+impl ToolResultBudget {
+    /// Resolve both caps, filling unset ones from the model's context window.
+    ///
+    /// By default a turn's tool results may take up roughly a quarter of the
+    /// window (at ~4 bytes per token), and a single result half of that.
+    pub fn resolve(&self, context_window_tokens: Option<u32>) -> ResolvedToolResultBudget {
+        let window_tokens = context_window_tokens.unwrap_or(FALLBACK_CONTEXT_WINDOW_TOKENS);
+        let default_turn_bytes = window_tokens as usize;
+        let max_turn_bytes = self.max_turn_bytes.unwrap_or(default_turn_bytes);
+        let max_result_bytes = self
+            .max_result_bytes
+            .unwrap_or(max_turn_bytes / 2)
+            .min(max_turn_bytes);
+
+        ResolvedToolResultBudget {
+            max_result_bytes,
+            max_turn_bytes,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolvedToolResultBudget {
+    pub max_result_bytes: usize,
+    pub max_turn_bytes: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SessionToolConfig {
     pub backends: Vec<BackendConfig>,
     pub visibility: ToolVisibility,
     pub approval_policy: ToolApprovalPolicy,
     pub metadata: HashMap<String, String>,
+    #[serde(default)]
+    pub result_budget: ToolResultBudget,
+    /// Environment variables set for every bash command. Values may reference
+    /// the server's environment as `${env:NAME}`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+    #[serde(default)]
+    pub bash_denylist: BashDenylistConfig,
+    /// Whether tools may reach the network. When false, network tools such as
+    /// fetch are hidden and bash commands run behind an unroutable proxy.
+    #[serde(default = "default_allow_network")]
+    pub allow_network: bool,
+    /// Whether file tools may resolve paths outside the workspace root.
+    #[serde(default)]
+    pub allow_outside_workspace: bool,
+}
+
+fn default_allow_network() -> bool {
+    true
 }
 
 impl Default for SessionToolConfig {
@@ -633,6 +796,11 @@ impl Default for SessionToolConfig {
             visibility: ToolVisibility::All,
             approval_policy: ToolApprovalPolicy::default(),
             metadata: HashMap::new(),
+            result_budget: ToolResultBudget::default(),
+            env: HashMap::new(),
+            bash_denylist: BashDenylistConfig::default(),
+            allow_network: true,
+            allow_outside_workspace: false,
         }
     }
 }
@@ -644,6 +812,11 @@ impl SessionToolConfig {
             visibility: ToolVisibility::ReadOnly,
             approval_policy: ToolApprovalPolicy::default(),
             metadata: HashMap::new(),
+            result_budget: ToolResultBudget::default(),
+            env: HashMap::new(),
+            bash_denylist: BashDenylistConfig::default(),
+            allow_network: true,
+            allow_outside_workspace: false,
         }
     }
 }
@@ -931,7 +1104,7 @@ mod tests {
     use crate::config::model::builtin::claude_sonnet_4_5 as test_model;
     use crate::tools::DISPATCH_AGENT_TOOL_NAME;
     use crate::tools::builtin_tools::READ_ONLY_TOOL_NAMES;
-    use steer_tools::tools::{BASH_TOOL_NAME, EDIT_TOOL_NAME};
+    use steer_tools::tools::{BASH_TOOL_NAME, EDIT_TOOL_NAME, FETCH_TOOL_NAME};
 
     #[test]
     fn test_session_creation() {
@@ -952,6 +1125,9 @@ mod tests {
             metadata: HashMap::new(),
             default_model: test_model(),
             auto_compaction: AutoCompactionConfig::default(),
+            auto_summary: AutoSummaryConfig::default(),
+            isolation: IsolationMode::None,
+            isolated_workspace: None,
         };
         let session = Session::new("test-session".to_string(), config.clone());
 
@@ -967,6 +1143,33 @@ mod tests {
         assert_eq!(session.state.message_count(), 0);
     }
 
+    #[test]
+    fn test_network_tools_hidden_when_network_disabled() {
+        let schema = |name: &str| steer_tools::ToolSchema {
+            name: name.to_string(),
+            display_name: name.to_string(),
+            description: String::new(),
+            input_schema: steer_tools::InputSchema::new(serde_json::json!({"type": "object"})),
+        };
+        let tools = vec![schema(FETCH_TOOL_NAME), schema(BASH_TOOL_NAME)];
+
+        let mut config = SessionConfig::read_only(test_model());
+        config.tool_config.visibility = ToolVisibility::All;
+        let names = |tools: Vec<steer_tools::ToolSchema>| {
+            tools.into_iter().map(|tool| tool.name).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(config.filter_tools_by_visibility(tools.clone())),
+            vec![FETCH_TOOL_NAME, BASH_TOOL_NAME]
+        );
+
+        config.tool_config.allow_network = false;
+        assert_eq!(
+            names(config.filter_tools_by_visibility(tools)),
+            vec![BASH_TOOL_NAME]
+        );
+    }
+
     #[test]
     fn test_tool_approval_policy_prompt_unapproved() {
         let policy = ToolApprovalPolicy {
@@ -1271,6 +1474,9 @@ mod tests {
             metadata: HashMap::new(),
             default_model: test_model(),
             auto_compaction: AutoCompactionConfig::default(),
+            auto_summary: AutoSummaryConfig::default(),
+            isolation: IsolationMode::None,
+            isolated_workspace: None,
         };
 
         let (registry, _mcp_servers) = config.build_registry().await.unwrap();
@@ -8,7 +8,8 @@ use crate::auth::{AuthStorage, CredentialType};
 use crate::config::LlmConfigProvider;
 use crate::config::model::ModelId;
 use crate::session::state::{
-    AutoCompactionConfig, SessionConfig, SessionPolicyOverrides, SessionToolConfig, WorkspaceConfig,
+    AutoCompactionConfig, AutoSummaryConfig, SessionConfig, SessionPolicyOverrides,
+    SessionToolConfig, WorkspaceConfig,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -96,5 +97,97 @@ pub fn read_only_session_config(default_model: ModelId) -> SessionConfig {
         metadata: HashMap::new(),
         default_model,
         auto_compaction: AutoCompactionConfig::default(),
+        auto_summary: AutoSummaryConfig::default(),
+        isolation: crate::isolation::IsolationMode::None,
+        isolated_workspace: None,
     }
 }
+
+/// Environment variable that makes [`check_tool_schema_snapshot`] rewrite
+/// snapshots instead of comparing against them.
+pub const UPDATE_TOOL_SCHEMA_SNAPSHOTS_ENV: &str = "UPDATE_TOOL_SCHEMA_SNAPSHOTS";
+
+#[derive(Debug, thiserror::Error)]
+pub enum ToolSchemaSnapshotError {
+    #[error("no builtin tool named {0}")]
+    UnknownTool(String),
+    #[error("missing snapshot {0}; run with {UPDATE_TOOL_SCHEMA_SNAPSHOTS_ENV}=1 to create it")]
+    Missing(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// Compares a builtin tool's schema against `<snapshot_dir>/<tool_name>.json`,
+/// returning a readable diff when they differ.
+pub fn check_tool_schema_snapshot(
+    tool_name: &str,
+    snapshot_dir: &std::path::Path,
+) -> Result<Option<String>, ToolSchemaSnapshotError> {
+    let schema = crate::tools::builtin_tool_schemas()
+        .into_iter()
+        .find(|schema| schema.name == tool_name)
+        .ok_or_else(|| ToolSchemaSnapshotError::UnknownTool(tool_name.to_string()))?;
+    let actual = format!("{}\n", serde_json::to_string_pretty(&schema)?);
+    let path = snapshot_dir.join(format!("{tool_name}.json"));
+
+    if std::env::var_os(UPDATE_TOOL_SCHEMA_SNAPSHOTS_ENV).is_some() {
+        std::fs::create_dir_all(snapshot_dir)?;
+        std::fs::write(&path, &actual)?;
+        return Ok(None);
+    }
+
+    let expected = std::fs::read_to_string(&path)
+        .map_err(|_| ToolSchemaSnapshotError::Missing(path.display().to_string()))?;
+    if expected == actual {
+        return Ok(None);
+    }
+
+    let diff = similar::TextDiff::from_lines(&expected, &actual)
+        .unified_diff()
+        .header("snapshot", "current")
+        .to_string();
+    Ok(Some(format!(
+        "schema for `{tool_name}` changed; rerun with {UPDATE_TOOL_SCHEMA_SNAPSHOTS_ENV}=1 if intentional\n{diff}"
+    )))
+}
+
+/// Generates one snapshot test per builtin tool, plus a test that fails when a
+/// registered tool has no snapshot test.
+///
+/// ```ignore
+/// steer_core::tool_schema_snapshot_tests! {
+///     snapshot_dir: std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots");
+///     bash_schema => steer_tools::tools::BASH_TOOL_NAME,
+/// }
+/// ```
+#[macro_export]
+macro_rules! tool_schema_snapshot_tests {
+    (
+        snapshot_dir: $snapshot_dir:expr;
+        $($test_name:ident => $tool_name:expr),+ $(,)?
+    ) => {
+        $(
+            #[test]
+            fn $test_name() {
+                let diff = $crate::test_utils::check_tool_schema_snapshot($tool_name, &$snapshot_dir)
+                    .expect("snapshot check should run");
+                if let Some(diff) = diff {
+                    panic!("{diff}");
+                }
+            }
+        )+
+
+        #[test]
+        fn every_builtin_tool_has_a_snapshot_test() {
+            let mut covered = vec![$($tool_name),+];
+            covered.sort_unstable();
+            let all: Vec<String> = $crate::tools::builtin_tool_schemas()
+                .into_iter()
+                .map(|schema| schema.name)
+                .collect();
+            assert_eq!(covered, all);
+        }
+    };
+}
@@ -12,7 +12,8 @@ use crate::model_registry::ModelRegistry;
 use crate::runners::OneShotRunner;
 use crate::session::state::{
     ApprovalRulesOverrides, SessionConfig, SessionPolicyOverrides, SessionToolConfig,
-    ToolApprovalPolicy, ToolApprovalPolicyOverrides, ToolVisibility, WorkspaceConfig,
+    ToolApprovalPolicy, ToolApprovalPolicyOverrides, ToolResultBudget, ToolVisibility,
+    WorkspaceConfig,
 };
 use crate::tools::{ToolExecutor, ToolSystemBuilder};
 use crate::workspace::{RepoManager, Workspace, WorkspaceManager};
@@ -47,13 +48,18 @@ impl DefaultAgentSpawner {
         }
     }
 
-    fn build_tool_executor(&self, workspace: Arc<dyn Workspace>) -> Arc<ToolExecutor> {
+    fn build_tool_executor(
+        &self,
+        workspace: Arc<dyn Workspace>,
+        allow_outside_workspace: bool,
+    ) -> Arc<ToolExecutor> {
         let mut tool_builder = ToolSystemBuilder::new(
             workspace,
             self.event_store.clone(),
             self.api_client.clone(),
             self.model_registry.clone(),
-        );
+        )
+        .with_allow_outside_workspace(allow_outside_workspace);
 
         if let Some(manager) = &self.workspace_manager {
             tool_builder = tool_builder.with_workspace_manager(manager.clone());
@@ -91,6 +97,11 @@ impl AgentSpawner for DefaultAgentSpawner {
             visibility: ToolVisibility::All,
             approval_policy: ToolApprovalPolicy::default(),
             metadata: HashMap::new(),
+            result_budget: ToolResultBudget::default(),
+            env: config.env.clone(),
+            bash_denylist: config.bash_denylist.clone(),
+            allow_network: config.allow_network,
+            allow_outside_workspace: config.allow_outside_workspace,
         };
 
         let policy_overrides = SessionPolicyOverrides {
@@ -124,9 +135,12 @@ impl AgentSpawner for DefaultAgentSpawner {
             metadata: HashMap::new(),
             default_model: config.model.clone(),
             auto_compaction: crate::session::state::AutoCompactionConfig::default(),
+            auto_summary: crate::session::state::AutoSummaryConfig::disabled(),
+            isolation: crate::isolation::IsolationMode::None,
+            isolated_workspace: None,
         };
 
-        let tool_executor = self.build_tool_executor(workspace);
+        let tool_executor = self.build_tool_executor(workspace, config.allow_outside_workspace);
         let runtime = RuntimeService::spawn(
             self.event_store.clone(),
             self.api_client.clone(),
@@ -169,13 +183,13 @@ mod tests {
     use crate::auth::ProviderRegistry;
     use crate::config::model::builtin;
     use crate::model_registry::ModelRegistry;
-    use crate::session::state::ToolVisibility;
+    use crate::session::state::{BashDenylistConfig, ToolVisibility};
     use crate::test_utils::test_llm_config_provider;
     use crate::tools::builtin_tools::READ_ONLY_TOOL_NAMES;
     use crate::tools::services::AgentSpawner;
     use crate::tools::services::SubAgentConfig;
     use crate::workspace::WorkspaceConfig;
-    use std::collections::HashSet;
+    use std::collections::{HashMap, HashSet};
     use std::sync::Arc;
     use std::sync::Mutex as StdMutex;
     use steer_tools::tools::edit::multi_edit::MULTI_EDIT_TOOL_NAME;
@@ -237,6 +251,7 @@ mod tests {
         let temp_dir = TempDir::new().expect("create temp dir");
         let workspace = crate::workspace::create_workspace(&WorkspaceConfig::Local {
             path: temp_dir.path().to_path_buf(),
+            context_limits: Default::default(),
         })
         .await
         .expect("create workspace");
@@ -258,7 +273,7 @@ mod tests {
             None,
         );
 
-        let tool_executor = spawner.build_tool_executor(workspace);
+        let tool_executor = spawner.build_tool_executor(workspace, false);
         for tool_name in [
             GLOB_TOOL_NAME,
             GREP_TOOL_NAME,
@@ -281,6 +296,7 @@ mod tests {
         let temp_dir = TempDir::new().expect("create temp dir");
         let workspace = crate::workspace::create_workspace(&WorkspaceConfig::Local {
             path: temp_dir.path().to_path_buf(),
+            context_limits: Default::default(),
         })
         .await
         .expect("create workspace");
@@ -329,6 +345,10 @@ mod tests {
             workspace_name: None,
             mcp_backends: Vec::new(),
             allow_mcp_tools: true,
+            env: HashMap::new(),
+            bash_denylist: BashDenylistConfig::default(),
+            allow_network: true,
+            allow_outside_workspace: false,
         };
 
         let result = spawner
@@ -11,8 +11,13 @@ use crate::config::model::ModelId;
 use steer_tools::error::ToolExecutionError;
 use steer_tools::result::ToolResult;
 use steer_tools::{ToolSchema, ToolSpec};
+use steer_workspace::WorkspaceType;
+use steer_workspace::utils::{
+    WorkspacePathError, ensure_within_workspace, normalize_workspace_path,
+};
 
 use super::capability::Capabilities;
+use super::env::ToolEnv;
 use super::services::ToolServices;
 
 #[derive(Debug, Clone)]
@@ -22,12 +27,64 @@ pub struct BuiltinToolContext {
     pub invoking_model: Option<ModelId>,
     pub cancellation_token: CancellationToken,
     pub services: Arc<ToolServices>,
+    pub env: Arc<ToolEnv>,
 }
 
 impl BuiltinToolContext {
     pub fn is_cancelled(&self) -> bool {
         self.cancellation_token.is_cancelled()
     }
+
+    /// Resolves a path argument relative to the workspace working directory,
+    /// rejecting relative paths that traverse outside of it.
+    ///
+    /// For local workspaces the resolved path must also stay inside the root
+    /// (following symlinks). Enabling `allow_outside_workspace` for the tool
+    /// system or the session lifts both checks. Remote workspaces resolve the
+    /// path against their own root and enforce the boundary server side, under
+    /// the remote workspace's own `--allow-outside-workspace` setting.
+    pub fn resolve_workspace_path<E>(&self, path: &str) -> Result<String, BuiltinToolError<E>>
+    where
+        E: StdError + Send + Sync + 'static,
+    {
+        let workspace = &self.services.workspace;
+        let root = match workspace.metadata().workspace_type {
+            WorkspaceType::Local => Some(workspace.working_directory()),
+            WorkspaceType::Remote => None,
+        };
+        let allow_outside =
+            self.services.allow_outside_workspace() || self.env.allows_outside_workspace();
+        let resolved = match (normalize_workspace_path(root, path), root) {
+            (Ok(resolved), _) => resolved,
+            // With the boundary lifted, `..` is left for the filesystem to resolve.
+            (Err(WorkspacePathError::EscapesWorkspace { .. }), Some(root)) if allow_outside => {
+                root.join(path).to_string_lossy().into_owned()
+            }
+            (Err(WorkspacePathError::EscapesWorkspace { .. }), None) if allow_outside => {
+                path.to_string()
+            }
+            (Err(e), _) => return Err(BuiltinToolError::invalid_params(e.to_string())),
+        };
+
+        if let Some(root) = root
+            && !allow_outside
+        {
+            ensure_within_workspace(root, &resolved)
+                .map_err(|e| BuiltinToolError::invalid_params(e.to_string()))?;
+        }
+
+        Ok(resolved)
+    }
+
+    pub fn resolve_optional_workspace_path<E>(
+        &self,
+        path: Option<String>,
+    ) -> Result<Option<String>, BuiltinToolError<E>>
+    where
+        E: StdError + Send + Sync + 'static,
+    {
+        path.map(|p| self.resolve_workspace_path(&p)).transpose()
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -112,6 +169,11 @@ pub trait BuiltinTool: Send + Sync + 'static {
     const DESCRIPTION: &'static str;
     const REQUIRES_APPROVAL: bool;
     const REQUIRED_CAPABILITIES: Capabilities;
+    /// Running the tool twice with the same parameters has the same effect as
+    /// running it once, so transient failures can be retried automatically.
+    /// Tools that change files, run commands or mutate session state must
+    /// leave this `false`.
+    const IDEMPOTENT: bool = false;
 
     async fn execute(
         &self,
@@ -132,6 +194,7 @@ pub trait BuiltinToolErased: Send + Sync {
     fn name(&self) -> &'static str;
     fn requires_approval(&self) -> bool;
     fn required_capabilities(&self) -> Capabilities;
+    fn idempotent(&self) -> bool;
     fn schema(&self) -> ToolSchema;
 
     async fn execute_erased(
@@ -158,6 +221,10 @@ where
         T::REQUIRED_CAPABILITIES
     }
 
+    fn idempotent(&self) -> bool {
+        T::IDEMPOTENT
+    }
+
     fn schema(&self) -> ToolSchema {
         T::schema()
     }
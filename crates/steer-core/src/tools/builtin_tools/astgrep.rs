@@ -9,6 +9,8 @@ use steer_workspace::{AstGrepRequest, WorkspaceOpContext};
 
 pub struct AstGrepTool;
 
+register_builtin_tool!(AstGrepTool);
+
 #[async_trait]
 impl BuiltinTool for AstGrepTool {
     type Params = AstGrepParams;
@@ -31,6 +33,7 @@ Advanced patterns:
 - Use any valid code as a pattern - ast-grep understands the syntax!
 Automatically respects .gitignore files"#;
     const REQUIRES_APPROVAL: bool = false;
+    const IDEMPOTENT: bool = true;
     const REQUIRED_CAPABILITIES: Capabilities = Capabilities::WORKSPACE;
 
     async fn execute(
@@ -43,7 +46,7 @@ Automatically respects .gitignore files"#;
             lang: params.lang,
             include: params.include,
             exclude: params.exclude,
-            path: params.path,
+            path: ctx.resolve_optional_workspace_path(params.path)?,
         };
         let op_ctx =
             WorkspaceOpContext::new(ctx.tool_call_id.0.clone(), ctx.cancellation_token.clone());
@@ -1,14 +1,17 @@
 use async_trait::async_trait;
 use regex::Regex;
+use std::path::PathBuf;
 use std::process::ExitStatus;
+use std::sync::LazyLock;
 use std::time::Duration;
 use tokio::io::AsyncReadExt;
 use tokio::process::Command;
 
 use crate::tools::builtin_tool::{BuiltinTool, BuiltinToolContext, BuiltinToolError};
 use crate::tools::capability::Capabilities;
+use crate::tools::env::ToolEnv;
 use steer_tools::result::BashResult;
-use steer_tools::tools::bash::{BashError, BashParams, BashToolSpec};
+use steer_tools::tools::bash::{BashError, BashParams, BashToolSpec, ParsedCommand};
 
 const DEFAULT_TIMEOUT_MS: u64 = 180_000;
 const MAX_TIMEOUT_MS: u64 = 3_600_000;
@@ -16,6 +19,8 @@ const TIMEOUT_EXIT_CODE: i32 = 124;
 
 pub struct BashTool;
 
+register_builtin_tool!(BashTool);
+
 #[async_trait]
 impl BuiltinTool for BashTool {
     type Params = BashParams;
@@ -41,16 +46,28 @@ impl BuiltinTool for BashTool {
             }));
         }
 
+        if let Some(denied) = ctx.env.denylist().check(&params.command) {
+            return Err(BuiltinToolError::execution(BashError::DeniedCommand {
+                command: params.command,
+                rule: denied.rule,
+                reason: denied.reason,
+            }));
+        }
+
         let timeout_ms = params
             .timeout
             .unwrap_or(DEFAULT_TIMEOUT_MS)
             .min(MAX_TIMEOUT_MS);
         let timeout_duration = Duration::from_millis(timeout_ms);
-        let working_directory = ctx.services.workspace.working_directory().to_path_buf();
+        let working_directory = match ctx.resolve_optional_workspace_path(params.cwd)? {
+            Some(cwd) => std::path::PathBuf::from(cwd),
+            None => ctx.services.workspace.working_directory().to_path_buf(),
+        };
 
         run_command(
             &params.command,
             &working_directory,
+            &ctx.env,
             timeout_duration,
             ctx.cancellation_token.clone(),
         )
@@ -58,21 +75,88 @@ impl BuiltinTool for BashTool {
     }
 }
 
+/// Shell used to run commands.
+///
+/// Bash is used wherever it exists. On Windows that means Git Bash or a `bash.exe`
+/// on PATH (which includes WSL); without either, commands run under PowerShell
+/// with the same timeout and output capture.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Shell {
+    program: PathBuf,
+    args: &'static [&'static str],
+}
+
+static SHELL: LazyLock<Shell> = LazyLock::new(resolve_shell);
+
+#[cfg(not(windows))]
+fn resolve_shell() -> Shell {
+    Shell {
+        program: PathBuf::from("/bin/bash"),
+        args: &["-c"],
+    }
+}
+
+#[cfg(windows)]
+fn resolve_shell() -> Shell {
+    let git_bash = ["ProgramFiles", "ProgramFiles(x86)", "LOCALAPPDATA"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .flat_map(|dir| {
+            let dir = PathBuf::from(dir);
+            [
+                dir.join("Git").join("bin").join("bash.exe"),
+                dir.join("Programs")
+                    .join("Git")
+                    .join("bin")
+                    .join("bash.exe"),
+            ]
+        });
+    let path_bash = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|dir| dir.join("bash.exe"));
+
+    if let Some(bash) = git_bash
+        .chain(path_bash)
+        .find(|candidate| candidate.is_file())
+    {
+        return Shell {
+            program: bash,
+            args: &["-c"],
+        };
+    }
+
+    tracing::info!(
+        target: "tools::bash",
+        "No bash found; running commands with PowerShell"
+    );
+    Shell {
+        program: PathBuf::from("powershell.exe"),
+        args: &["-NoLogo", "-NoProfile", "-NonInteractive", "-Command"],
+    }
+}
+
 enum CommandCompletion {
     Completed(ExitStatus),
     TimedOut,
 }
 
+/// Run `command` with the session env applied, redacting secret env values
+/// from the captured output.
 async fn run_command(
     command: &str,
     working_directory: &std::path::Path,
+    env: &ToolEnv,
     timeout_duration: Duration,
     cancellation_token: tokio_util::sync::CancellationToken,
 ) -> Result<BashResult, BuiltinToolError<BashError>> {
-    let mut cmd = Command::new("/bin/bash");
-    cmd.arg("-c")
+    let shell = &*SHELL;
+    let mut cmd = Command::new(&shell.program);
+    cmd.args(shell.args)
         .arg(command)
         .current_dir(working_directory)
+        .envs(env.vars().iter().map(|(name, value)| (name, value)))
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped())
         .kill_on_drop(true);
@@ -148,19 +232,21 @@ async fn run_command(
         })
     })?;
 
-    let stdout = String::from_utf8_lossy(&stdout_bytes).to_string();
-    let stderr = String::from_utf8_lossy(&stderr_bytes).to_string();
+    let stdout = env.redact(&String::from_utf8_lossy(&stdout_bytes));
+    let stderr = env.redact(&String::from_utf8_lossy(&stderr_bytes));
 
     let (exit_code, timed_out) = match completion {
         CommandCompletion::Completed(status) => (status.code().unwrap_or(-1), false),
         CommandCompletion::TimedOut => (TIMEOUT_EXIT_CODE, true),
     };
 
+    let command = env.redact(command);
     Ok(BashResult {
         stdout,
         stderr,
         exit_code,
-        command: command.to_string(),
+        parsed: ParsedCommand::parse(&command),
+        command,
         timed_out,
     })
 }
@@ -195,23 +281,36 @@ static BANNED_COMMAND_REGEXES: std::sync::LazyLock<Vec<Regex>> = std::sync::Lazy
 });
 
 fn is_banned_command(command: &str) -> bool {
-    BANNED_COMMAND_REGEXES.iter().any(|re| re.is_match(command))
+    if BANNED_COMMAND_REGEXES.iter().any(|re| re.is_match(command)) {
+        return true;
+    }
+
+    // The regexes only see the start of the line; check every program in a
+    // pipeline or command list as well.
+    ParsedCommand::parse(command).is_some_and(|parsed| {
+        parsed
+            .program_names()
+            .any(|program| BANNED_COMMAND_REGEXES.iter().any(|re| re.is_match(program)))
+    })
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
     use std::path::Path;
     use std::time::Duration;
 
     use tokio_util::sync::CancellationToken;
 
     use super::{TIMEOUT_EXIT_CODE, run_command};
+    use crate::tools::env::ToolEnv;
 
     #[tokio::test]
     async fn returns_partial_output_when_command_times_out() {
         let result = run_command(
             "echo hello; sleep 1; echo world",
             Path::new("."),
+            &ToolEnv::default(),
             Duration::from_millis(100),
             CancellationToken::new(),
         )
@@ -229,6 +328,7 @@ mod tests {
         let result = run_command(
             "printf 'done'",
             Path::new("."),
+            &ToolEnv::default(),
             Duration::from_secs(5),
             CancellationToken::new(),
         )
@@ -239,4 +339,51 @@ mod tests {
         assert_eq!(result.exit_code, 0);
         assert_eq!(result.stdout, "done");
     }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn injects_session_env_and_redacts_secrets() {
+        let config = HashMap::from([
+            (
+                "STEER_TEST_API".to_string(),
+                "http://localhost:8080".to_string(),
+            ),
+            ("STEER_TEST_TOKEN".to_string(), "${env:PATH}".to_string()),
+        ]);
+        let env = ToolEnv::resolve(&config);
+
+        let result = run_command(
+            "echo \"$STEER_TEST_API $STEER_TEST_TOKEN\"",
+            Path::new("."),
+            &env,
+            Duration::from_secs(5),
+            CancellationToken::new(),
+        )
+        .await
+        .expect("command should complete");
+
+        assert_eq!(result.exit_code, 0);
+        assert_eq!(result.stdout.trim(), "http://localhost:8080 [REDACTED]");
+    }
+
+    #[test]
+    fn bans_programs_later_in_a_pipeline() {
+        assert!(super::is_banned_command("sudo rm -rf /tmp/x"));
+        assert!(super::is_banned_command("cd /tmp && sudo ls"));
+        assert!(super::is_banned_command("cat notes | /usr/bin/vim -"));
+        assert!(!super::is_banned_command("echo 'sudo is banned'"));
+        assert!(!super::is_banned_command("git status | head"));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn prefers_bash_when_git_for_windows_is_installed() {
+        let shell = super::resolve_shell();
+
+        assert_eq!(
+            shell.program.file_name().and_then(|name| name.to_str()),
+            Some("bash.exe")
+        );
+        assert_eq!(shell.args, &["-c"]);
+    }
 }
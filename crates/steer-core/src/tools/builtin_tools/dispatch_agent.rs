@@ -80,7 +80,7 @@ Usage:
 
 Reference:
 - Each invocation returns a session_id. Pass it back via `target: {{ "session": "resume", "session_id": "<uuid>" }}` to continue the conversation with the same agent.
-- When `target.session` is `resume`, the session_id must refer to a child of the current session. The `agent` and `workspace` options are ignored and the existing session config is used.
+- When `target.session` is `resume`, the session_id must refer to a child of the current session. The `agent`, `workspace`, `system_prompt`, and `tools` options are ignored and the existing session config is used.
 - The agent's outputs should generally be trusted.
 - New workspaces are preserved (not auto-deleted). Clean them up manually if needed.
 - If the agent spec omits a model, the parent session's default model is used.
@@ -98,19 +98,50 @@ New session options:
 - `target: {{ "session": "new", "workspace": {{ "location": "current" }} }}` to run in the current workspace
 - `target: {{ "session": "new", "workspace": {{ "location": "new", "name": "..." }} }}` to run in a new workspace
 - `target: {{ "session": "new", "workspace": {{ "location": "current" }}, "agent": "<id>" }}` selects an agent spec (defaults to "{default_agent}")
+- `"system_prompt": "You are a code reviewer. Report problems; do not edit files."` in a new target specializes the sub-agent's role
+- `"tools": ["{}", "{}"]` in a new target restricts the sub-agent to a subset of its agent spec's tools (MCP tools are dropped)
 
 {agent_specs_block}"#,
         READ_FILE_TOOL_NAME,
         LS_TOOL_NAME,
         GREP_TOOL_NAME,
         GREP_TOOL_NAME,
+        READ_FILE_TOOL_NAME,
+        GREP_TOOL_NAME,
         default_agent = default_agent_spec_id(),
         agent_specs_block = agent_specs_block
     )
 }
 
+const DEFAULT_SUB_AGENT_ROLE: &str = "You are an agent for a CLI-based coding tool. Given the user's prompt, you should use the tools available to you to answer the user's question.";
+
+/// Narrows an agent spec's tools to the requested subset, rejecting names the
+/// spec does not grant.
+fn restrict_tools(
+    spec_tools: &[String],
+    requested: Vec<String>,
+    agent_id: &str,
+) -> Result<Vec<String>, BuiltinToolError<DispatchAgentError>> {
+    let mut allowed = Vec::with_capacity(requested.len());
+    for name in requested {
+        let name = name.trim().to_string();
+        if !spec_tools.contains(&name) {
+            return Err(BuiltinToolError::invalid_params(format!(
+                "Tool '{name}' is not available to agent spec '{agent_id}'. Available: {}",
+                spec_tools.join(", ")
+            )));
+        }
+        if !allowed.contains(&name) {
+            allowed.push(name);
+        }
+    }
+    Ok(allowed)
+}
+
 pub struct DispatchAgentTool;
 
+register_builtin_tool!(DispatchAgentTool);
+
 #[async_trait]
 impl BuiltinTool for DispatchAgentTool {
     type Params = DispatchAgentParams;
@@ -132,14 +163,19 @@ impl BuiltinTool for DispatchAgentTool {
     ) -> Result<Self::Output, BuiltinToolError<DispatchAgentError>> {
         let DispatchAgentParams { prompt, target } = params;
 
-        let (workspace_target, agent) = match target {
+        let (workspace_target, agent, role_prompt, tool_restriction) = match target {
             DispatchAgentTarget::Resume { session_id } => {
                 let session_id = SessionId::parse(&session_id).ok_or_else(|| {
                     BuiltinToolError::invalid_params(format!("Invalid session_id '{session_id}'"))
                 })?;
                 return resume_agent_session(session_id, prompt, ctx).await;
             }
-            DispatchAgentTarget::New { workspace, agent } => (workspace, agent),
+            DispatchAgentTarget::New {
+                workspace,
+                agent,
+                system_prompt,
+                tools,
+            } => (workspace, agent, system_prompt, tools),
         };
 
         let spawner = ctx
@@ -276,8 +312,14 @@ impl BuiltinTool for DispatchAgentTool {
             BuiltinToolError::execution(DispatchAgentError::Workspace(workspace_op_error(e)))
         })?;
 
+        let role = role_prompt
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .unwrap_or(DEFAULT_SUB_AGENT_ROLE);
+
         let system_prompt = format!(
-            r#"You are an agent for a CLI-based coding tool. Given the user's prompt, you should use the tools available to you to answer the user's question.
+            r#"{role}
 
 Notes:
 1. IMPORTANT: You should be concise, direct, and to the point, since your responses will be displayed on a command line interface. Answer the user's question directly, without elaboration, explanation, or details. One word answers are best. Avoid introductions, conclusions, and explanations. You MUST avoid text before/after your response, such as "The answer is <answer>.", "Here is the content of the file..." or "Based on the information provided, the answer is..." or "Here is what I will do next...".
@@ -329,24 +371,41 @@ Notes:
             .as_ref()
             .map_or_else(default_model, |config| config.default_model.clone());
 
-        let allow_mcp_tools = agent_spec.mcp_access.allow_mcp_tools();
-        let mcp_backends = match &agent_spec.mcp_access {
-            McpAccessPolicy::None => Vec::new(),
-            McpAccessPolicy::All => parent_mcp_backends,
-            McpAccessPolicy::Allowlist(servers) => parent_mcp_backends
-                .into_iter()
-                .filter(|backend| match backend {
-                    BackendConfig::Mcp { server_name, .. } => {
-                        servers.iter().any(|allowed| allowed == server_name)
-                    }
-                })
-                .collect(),
-        };
+        // An explicit tool list narrows the spec's tools and drops MCP access.
+        let (allowed_tools, allow_mcp_tools, mcp_backends) =
+            if let Some(requested) = tool_restriction {
+                (
+                    restrict_tools(&agent_spec.tools, requested, &agent_spec.id)?,
+                    false,
+                    Vec::new(),
+                )
+            } else {
+                let mcp_backends = match &agent_spec.mcp_access {
+                    McpAccessPolicy::None => Vec::new(),
+                    McpAccessPolicy::All => parent_mcp_backends,
+                    McpAccessPolicy::Allowlist(servers) => parent_mcp_backends
+                        .into_iter()
+                        .filter(|backend| match backend {
+                            BackendConfig::Mcp { server_name, .. } => {
+                                servers.iter().any(|allowed| allowed == server_name)
+                            }
+                        })
+                        .collect(),
+                };
+                (
+                    agent_spec.tools.clone(),
+                    agent_spec.mcp_access.allow_mcp_tools(),
+                    mcp_backends,
+                )
+            };
 
+        let parent_tool_config = parent_session_config
+            .map(|config| config.tool_config)
+            .unwrap_or_default();
         let config = SubAgentConfig {
             parent_session_id: ctx.session_id,
             prompt,
-            allowed_tools: agent_spec.tools.clone(),
+            allowed_tools,
             model: agent_spec.model.clone().unwrap_or(parent_model),
             system_context: Some(crate::app::SystemContext::new(system_prompt)),
             workspace: Some(workspace),
@@ -356,6 +415,10 @@ Notes:
             workspace_name,
             mcp_backends,
             allow_mcp_tools,
+            env: parent_tool_config.env,
+            bash_denylist: parent_tool_config.bash_denylist,
+            allow_network: parent_tool_config.allow_network,
+            allow_outside_workspace: parent_tool_config.allow_outside_workspace,
         };
 
         let spawn_result = spawner.spawn(config, ctx.cancellation_token.clone()).await;
@@ -625,6 +688,29 @@ mod tests {
         ) -> Result<(), EventStoreError> {
             Ok(())
         }
+
+        async fn load_memory(
+            &self,
+            _session_id: SessionId,
+        ) -> Result<Vec<steer_tools::tools::memory::MemoryEntry>, EventStoreError> {
+            Ok(Vec::new())
+        }
+
+        async fn save_memory_entry(
+            &self,
+            _session_id: SessionId,
+            _entry: &steer_tools::tools::memory::MemoryEntry,
+        ) -> Result<(), EventStoreError> {
+            Ok(())
+        }
+
+        async fn delete_memory_entry(
+            &self,
+            _session_id: SessionId,
+            _key: &str,
+        ) -> Result<bool, EventStoreError> {
+            Ok(false)
+        }
     }
 
     #[derive(Clone)]
@@ -827,6 +913,7 @@ mod tests {
         let workspace =
             crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
                 path: std::env::current_dir().unwrap(),
+                context_limits: Default::default(),
             })
             .await
             .unwrap();
@@ -867,6 +954,7 @@ mod tests {
         let workspace =
             crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
                 path: std::env::current_dir().unwrap(),
+                context_limits: Default::default(),
             })
             .await
             .unwrap();
@@ -908,6 +996,7 @@ mod tests {
         let workspace =
             crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
                 path: std::env::current_dir().unwrap(),
+                context_limits: Default::default(),
             })
             .await
             .unwrap();
@@ -938,6 +1027,7 @@ mod tests {
             invoking_model: None,
             cancellation_token: CancellationToken::new(),
             services,
+            env: Arc::default(),
         };
 
         let result = resume_agent_session(session_id, "ping".to_string(), &ctx).await;
@@ -966,6 +1056,7 @@ mod tests {
         let workspace =
             crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
                 path: std::env::current_dir().unwrap(),
+                context_limits: Default::default(),
             })
             .await
             .unwrap();
@@ -981,6 +1072,7 @@ mod tests {
             invoking_model: None,
             cancellation_token: CancellationToken::new(),
             services,
+            env: Arc::default(),
         };
 
         let result = resume_agent_session(session_id, "ping".to_string(), &ctx).await;
@@ -1006,6 +1098,7 @@ mod tests {
         let workspace =
             crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
                 path: std::env::current_dir().unwrap(),
+                context_limits: Default::default(),
             })
             .await
             .unwrap();
@@ -1019,6 +1112,7 @@ mod tests {
             invoking_model: None,
             cancellation_token: CancellationToken::new(),
             services,
+            env: Arc::default(),
         };
 
         let result = resume_agent_session(session_id, "ping".to_string(), &ctx).await;
@@ -1047,6 +1141,7 @@ mod tests {
         let workspace =
             crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
                 path: std::env::current_dir().unwrap(),
+                context_limits: Default::default(),
             })
             .await
             .unwrap();
@@ -1081,6 +1176,7 @@ mod tests {
             invoking_model: None,
             cancellation_token: CancellationToken::new(),
             services,
+            env: Arc::default(),
         };
 
         let result = resume_agent_session(session_id, "ping".to_string(), &ctx).await;
@@ -1114,6 +1210,7 @@ mod tests {
         let workspace =
             crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
                 path: std::env::current_dir().unwrap(),
+                context_limits: Default::default(),
             })
             .await
             .unwrap();
@@ -1144,6 +1241,7 @@ mod tests {
             invoking_model: None,
             cancellation_token: CancellationToken::new(),
             services,
+            env: Arc::default(),
         };
 
         let result = resume_agent_session(session_id, "ping".to_string(), &ctx)
@@ -1172,6 +1270,7 @@ mod tests {
         let workspace =
             crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
                 path: std::env::current_dir().unwrap(),
+                context_limits: Default::default(),
             })
             .await
             .unwrap();
@@ -1203,6 +1302,7 @@ mod tests {
             invoking_model: None,
             cancellation_token: cancel_token.clone(),
             services,
+            env: Arc::default(),
         };
 
         let cancel_task = tokio::spawn(async move {
@@ -1229,6 +1329,7 @@ mod tests {
         let workspace =
             crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
                 path: std::env::current_dir().unwrap(),
+                context_limits: Default::default(),
             })
             .await
             .unwrap();
@@ -1250,6 +1351,7 @@ mod tests {
             invoking_model: None,
             cancellation_token: CancellationToken::new(),
             services,
+            env: Arc::default(),
         };
 
         let params = DispatchAgentParams {
@@ -1257,6 +1359,8 @@ mod tests {
             target: DispatchAgentTarget::New {
                 workspace: WorkspaceTarget::Current,
                 agent: None,
+                system_prompt: None,
+                tools: None,
             },
         };
 
@@ -1280,6 +1384,7 @@ mod tests {
         let workspace =
             crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
                 path: std::env::current_dir().unwrap(),
+                context_limits: Default::default(),
             })
             .await
             .unwrap();
@@ -1355,6 +1460,7 @@ mod tests {
             invoking_model: None,
             cancellation_token: CancellationToken::new(),
             services,
+            env: Arc::default(),
         };
 
         let params = DispatchAgentParams {
@@ -1362,6 +1468,8 @@ mod tests {
             target: DispatchAgentTarget::New {
                 workspace: WorkspaceTarget::Current,
                 agent: Some(agent_id),
+                system_prompt: None,
+                tools: None,
             },
         };
 
@@ -1393,6 +1501,7 @@ mod tests {
         let workspace =
             crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
                 path: std::env::current_dir().unwrap(),
+                context_limits: Default::default(),
             })
             .await
             .unwrap();
@@ -1447,6 +1556,7 @@ mod tests {
             invoking_model: None,
             cancellation_token: CancellationToken::new(),
             services,
+            env: Arc::default(),
         };
 
         let params = DispatchAgentParams {
@@ -1454,6 +1564,8 @@ mod tests {
             target: DispatchAgentTarget::New {
                 workspace: WorkspaceTarget::Current,
                 agent: Some(agent_id),
+                system_prompt: None,
+                tools: None,
             },
         };
 
@@ -1476,6 +1588,7 @@ mod tests {
         let workspace =
             crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
                 path: std::env::current_dir().unwrap(),
+                context_limits: Default::default(),
             })
             .await
             .unwrap();
@@ -1531,6 +1644,7 @@ mod tests {
             invoking_model: None,
             cancellation_token: CancellationToken::new(),
             services,
+            env: Arc::default(),
         };
 
         let params = DispatchAgentParams {
@@ -1538,6 +1652,8 @@ mod tests {
             target: DispatchAgentTarget::New {
                 workspace: WorkspaceTarget::Current,
                 agent: Some(agent_id),
+                system_prompt: None,
+                tools: None,
             },
         };
 
@@ -1547,6 +1663,104 @@ mod tests {
         assert_eq!(captured.model, spec_model);
     }
 
+    #[tokio::test]
+    async fn dispatch_agent_honors_custom_system_prompt_and_tool_restriction() {
+        let event_store = Arc::new(InMemoryEventStore::new());
+        let model_registry = Arc::new(ModelRegistry::load(&[]).unwrap());
+        let provider_registry = Arc::new(crate::auth::ProviderRegistry::load(&[]).unwrap());
+        let api_client = Arc::new(ApiClient::new_with_deps(
+            crate::test_utils::test_llm_config_provider().unwrap(),
+            provider_registry,
+            model_registry,
+        ));
+        let workspace =
+            crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
+                path: std::env::current_dir().unwrap(),
+                context_limits: Default::default(),
+            })
+            .await
+            .unwrap();
+
+        let agent_id = format!("custom_role_{}", Uuid::new_v4());
+        let spec = AgentSpec {
+            id: agent_id.clone(),
+            name: "custom role test".to_string(),
+            description: "custom role test".to_string(),
+            tools: vec![
+                READ_FILE_TOOL_NAME.to_string(),
+                GREP_TOOL_NAME.to_string(),
+                LS_TOOL_NAME.to_string(),
+            ],
+            mcp_access: McpAccessPolicy::All,
+            model: None,
+        };
+        match register_agent_spec(spec) {
+            Ok(()) => {}
+            Err(AgentSpecError::AlreadyRegistered(_)) => {}
+            Err(AgentSpecError::RegistryPoisoned) => {}
+        }
+
+        let captured = Arc::new(tokio::sync::Mutex::new(None));
+        let spawner = CapturingAgentSpawner {
+            session_id: SessionId::new(),
+            response: "ok".to_string(),
+            captured: captured.clone(),
+        };
+
+        let services = Arc::new(
+            ToolServices::new(workspace, event_store, api_client)
+                .with_agent_spawner(Arc::new(spawner)),
+        );
+
+        let ctx = BuiltinToolContext {
+            tool_call_id: ToolCallId::new(),
+            session_id: SessionId::new(),
+            invoking_model: None,
+            cancellation_token: CancellationToken::new(),
+            services,
+            env: Arc::default(),
+        };
+
+        let params = DispatchAgentParams {
+            prompt: "review the diff".to_string(),
+            target: DispatchAgentTarget::New {
+                workspace: WorkspaceTarget::Current,
+                agent: Some(agent_id.clone()),
+                system_prompt: Some("You are a code reviewer.".to_string()),
+                tools: Some(vec![
+                    READ_FILE_TOOL_NAME.to_string(),
+                    GREP_TOOL_NAME.to_string(),
+                ]),
+            },
+        };
+
+        let _ = DispatchAgentTool.execute(params, &ctx).await.unwrap();
+        let captured = captured.lock().await.clone().expect("no config captured");
+
+        let prompt = captured.system_context.expect("system context").prompt;
+        assert!(prompt.starts_with("You are a code reviewer.\n"));
+        assert!(!prompt.contains(DEFAULT_SUB_AGENT_ROLE));
+        assert_eq!(
+            captured.allowed_tools,
+            vec![READ_FILE_TOOL_NAME.to_string(), GREP_TOOL_NAME.to_string()]
+        );
+        assert!(!captured.allow_mcp_tools);
+        assert!(captured.mcp_backends.is_empty());
+
+        let params = DispatchAgentParams {
+            prompt: "review the diff".to_string(),
+            target: DispatchAgentTarget::New {
+                workspace: WorkspaceTarget::Current,
+                agent: Some(agent_id),
+                system_prompt: None,
+                tools: Some(vec!["bash".to_string()]),
+            },
+        };
+
+        let result = DispatchAgentTool.execute(params, &ctx).await;
+        assert!(matches!(result, Err(BuiltinToolError::InvalidParams(_))));
+    }
+
     #[tokio::test]
     async fn resume_session_rejects_invisible_tools_as_unknown() {
         let event_store = Arc::new(InMemoryEventStore::new());
@@ -1560,6 +1774,7 @@ mod tests {
         let workspace =
             crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
                 path: std::env::current_dir().unwrap(),
+                context_limits: Default::default(),
             })
             .await
             .unwrap();
@@ -1618,6 +1833,7 @@ mod tests {
             invoking_model: None,
             cancellation_token: CancellationToken::new(),
             services,
+            env: Arc::default(),
         };
 
         let _ = resume_agent_session(session_id, "trigger".to_string(), &ctx)
@@ -5,12 +5,14 @@ use async_trait::async_trait;
 use steer_tools::result::{EditResult, MultiEditResult};
 use steer_tools::tools::edit::multi_edit::{MultiEditError, MultiEditParams, MultiEditToolSpec};
 use steer_tools::tools::edit::{
-    EditError, EditFailure, EditMatchPreview, EditParams, EditToolSpec,
+    EditError, EditFailure, EditMatchMode, EditMatchPreview, EditParams, EditToolSpec,
 };
 use steer_workspace::{ApplyEditsRequest, EditMatchSelection, EditOperation, WorkspaceOpContext};
 
 pub struct EditTool;
 
+register_builtin_tool!(EditTool);
+
 const EDIT_DESCRIPTION: &str = r"This is a tool for editing files. For moving or renaming files, you should generally use the Bash tool with the 'mv' command instead. For larger edits, use the replace tool to overwrite files.
 
 Before using this tool:
@@ -26,8 +28,9 @@ To make a file edit, provide the following:
 3. new_string: The edited text to replace the old_string
 4. Optional match_mode: `exactly_one` (default), `first`, `all`, or `nth`
 5. Optional match_index: 1-based index used only when match_mode is `nth`
+6. Optional replace_all: set to true to replace every occurrence (same as match_mode `all`)
 
-By default (`match_mode = exactly_one`), the tool replaces ONE occurrence of old_string with new_string in the specified file.
+By default (`match_mode = exactly_one`), the tool replaces ONE occurrence of old_string with new_string in the specified file. If old_string occurs more than once, the edit fails and reports how many matches there are and where, so you can add context to old_string or set replace_all.
 
 CRITICAL REQUIREMENTS FOR USING THIS TOOL:
 
@@ -75,22 +78,23 @@ impl BuiltinTool for EditTool {
         params: Self::Params,
         ctx: &BuiltinToolContext,
     ) -> Result<Self::Output, BuiltinToolError<EditError>> {
+        let match_selection = edit_match_selection(&params).map_err(|message| {
+            BuiltinToolError::execution(EditError::EditFailure(
+                EditFailure::InvalidMatchSelection {
+                    file_path: params.file_path.clone(),
+                    edit_index: 1,
+                    message,
+                },
+            ))
+        })?;
         let request = ApplyEditsRequest {
-            file_path: params.file_path,
+            file_path: ctx.resolve_workspace_path(&params.file_path)?,
             edits: vec![EditOperation {
                 old_string: params.old_string,
                 new_string: params.new_string,
-                match_selection: params.match_mode.map(|mode| match mode {
-                    steer_tools::tools::edit::EditMatchMode::ExactlyOne => {
-                        EditMatchSelection::ExactlyOne
-                    }
-                    steer_tools::tools::edit::EditMatchMode::First => EditMatchSelection::First,
-                    steer_tools::tools::edit::EditMatchMode::All => EditMatchSelection::All,
-                    steer_tools::tools::edit::EditMatchMode::Nth => EditMatchSelection::Nth {
-                        match_index: params.match_index,
-                    },
-                }),
+                match_selection,
             }],
+            allow_partial: false,
         };
         let op_ctx =
             WorkspaceOpContext::new(ctx.tool_call_id.0.clone(), ctx.cancellation_token.clone());
@@ -104,13 +108,15 @@ impl BuiltinTool for EditTool {
 
 pub struct MultiEditTool;
 
+register_builtin_tool!(MultiEditTool);
+
 #[async_trait]
 impl BuiltinTool for MultiEditTool {
     type Params = MultiEditParams;
     type Output = MultiEditResult;
     type Spec = MultiEditToolSpec;
 
-    const DESCRIPTION: &'static str = "This is a tool for making multiple edits to a single file in one operation. Prefer this tool over the edit_file tool when you need to make multiple edits to the same file. Edits are applied sequentially in the provided order against the latest file content after prior edits. Each edit supports optional `match_mode`: `exactly_one` (default), `first`, `all`, or `nth` (requires 1-based `match_index`).";
+    const DESCRIPTION: &'static str = "This is a tool for making multiple edits to a single file in one operation. Prefer this tool over the edit_file tool when you need to make multiple edits to the same file. Edits are applied sequentially in the provided order against the latest file content after prior edits. The file is only written if every edit succeeds. Before anything is applied, edits whose targets overlap in the original file, or whose old_string matches several places one of which an earlier edit rewrites, fail with both edit indices; an edit whose old_string was changed by an earlier edit, or whose match overlaps text an earlier edit wrote, fails with its edit index. Set `allow_partial` to apply the edits that succeed and report the rest as skipped. Each edit supports optional `match_mode`: `exactly_one` (default), `first`, `all`, or `nth` (requires 1-based `match_index`).";
     const REQUIRES_APPROVAL: bool = true;
     const REQUIRED_CAPABILITIES: Capabilities = Capabilities::WORKSPACE;
 
@@ -120,25 +126,19 @@ impl BuiltinTool for MultiEditTool {
         ctx: &BuiltinToolContext,
     ) -> Result<Self::Output, BuiltinToolError<MultiEditError>> {
         let request = ApplyEditsRequest {
-            file_path: params.file_path,
+            file_path: ctx.resolve_workspace_path(&params.file_path)?,
             edits: params
                 .edits
                 .into_iter()
                 .map(|e| EditOperation {
                     old_string: e.old_string,
                     new_string: e.new_string,
-                    match_selection: e.match_mode.map(|mode| match mode {
-                        steer_tools::tools::edit::EditMatchMode::ExactlyOne => {
-                            EditMatchSelection::ExactlyOne
-                        }
-                        steer_tools::tools::edit::EditMatchMode::First => EditMatchSelection::First,
-                        steer_tools::tools::edit::EditMatchMode::All => EditMatchSelection::All,
-                        steer_tools::tools::edit::EditMatchMode::Nth => EditMatchSelection::Nth {
-                            match_index: e.match_index,
-                        },
-                    }),
+                    match_selection: e
+                        .match_mode
+                        .map(|mode| match_selection(mode, e.match_index)),
                 })
                 .collect(),
+            allow_partial: params.allow_partial.unwrap_or(false),
         };
         let op_ctx =
             WorkspaceOpContext::new(ctx.tool_call_id.0.clone(), ctx.cancellation_token.clone());
@@ -152,6 +152,30 @@ impl BuiltinTool for MultiEditTool {
     }
 }
 
+fn match_selection(mode: EditMatchMode, match_index: Option<u64>) -> EditMatchSelection {
+    match mode {
+        EditMatchMode::ExactlyOne => EditMatchSelection::ExactlyOne,
+        EditMatchMode::First => EditMatchSelection::First,
+        EditMatchMode::All => EditMatchSelection::All,
+        EditMatchMode::Nth => EditMatchSelection::Nth { match_index },
+    }
+}
+
+/// `replace_all: true` is shorthand for `match_mode: all`. Pairing it with a
+/// different match mode is rejected rather than guessing which was meant.
+fn edit_match_selection(params: &EditParams) -> Result<Option<EditMatchSelection>, String> {
+    let selection = params
+        .match_mode
+        .map(|mode| match_selection(mode, params.match_index));
+    if params.replace_all != Some(true) {
+        return Ok(selection);
+    }
+    match selection {
+        None | Some(EditMatchSelection::All) => Ok(Some(EditMatchSelection::All)),
+        Some(_) => Err("replace_all cannot be combined with a match_mode other than `all`".into()),
+    }
+}
+
 fn map_workspace_edit_error(err: steer_workspace::WorkspaceError) -> EditError {
     match err {
         steer_workspace::WorkspaceError::Edit(edit_failure) => {
@@ -214,5 +238,185 @@ fn map_edit_failure(failure: steer_workspace::error::EditFailure) -> EditFailure
                 .collect(),
             omitted_matches,
         },
+        steer_workspace::error::EditFailure::StaleOldString {
+            file_path,
+            edit_index,
+        } => EditFailure::StaleOldString {
+            file_path,
+            edit_index,
+        },
+        steer_workspace::error::EditFailure::OverlappingEdit {
+            file_path,
+            edit_index,
+            conflicting_edit_index,
+        } => EditFailure::OverlappingEdit {
+            file_path,
+            edit_index,
+            conflicting_edit_index,
+        },
+        steer_workspace::error::EditFailure::ConflictingEdits {
+            file_path,
+            edit_index,
+            conflicting_edit_index,
+            line_number,
+        } => EditFailure::ConflictingEdits {
+            file_path,
+            edit_index,
+            conflicting_edit_index,
+            line_number,
+        },
+        steer_workspace::error::EditFailure::AmbiguousEdit {
+            file_path,
+            edit_index,
+            conflicting_edit_index,
+            occurrences,
+        } => EditFailure::AmbiguousEdit {
+            file_path,
+            edit_index,
+            conflicting_edit_index,
+            occurrences,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::Client as ApiClient;
+    use crate::app::domain::session::InMemoryEventStore;
+    use crate::app::domain::types::{SessionId, ToolCallId};
+    use crate::model_registry::ModelRegistry;
+    use crate::tools::ToolEnv;
+    use crate::tools::services::ToolServices;
+    use std::collections::HashMap;
+    use std::path::Path;
+    use std::sync::Arc;
+    use tokio_util::sync::CancellationToken;
+
+    async fn test_context(root: &Path) -> BuiltinToolContext {
+        let model_registry = Arc::new(ModelRegistry::load(&[]).unwrap());
+        let provider_registry = Arc::new(crate::auth::ProviderRegistry::load(&[]).unwrap());
+        let api_client = Arc::new(ApiClient::new_with_deps(
+            crate::test_utils::test_llm_config_provider().unwrap(),
+            provider_registry,
+            model_registry,
+        ));
+        let workspace =
+            crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
+                path: root.to_path_buf(),
+                context_limits: Default::default(),
+            })
+            .await
+            .unwrap();
+        let services =
+            ToolServices::new(workspace, Arc::new(InMemoryEventStore::new()), api_client);
+
+        BuiltinToolContext {
+            tool_call_id: ToolCallId::new(),
+            session_id: SessionId::new(),
+            invoking_model: None,
+            cancellation_token: CancellationToken::new(),
+            services: Arc::new(services),
+            env: Arc::new(ToolEnv::resolve(&HashMap::new())),
+        }
+    }
+
+    fn edit_params(file_path: &Path, old_string: &str, new_string: &str) -> EditParams {
+        EditParams {
+            file_path: file_path.display().to_string(),
+            old_string: old_string.to_string(),
+            new_string: new_string.to_string(),
+            match_mode: None,
+            match_index: None,
+            replace_all: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn unique_match_is_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        std::fs::write(&file, "let a = 1;\nlet b = 2;\n").unwrap();
+        let ctx = test_context(dir.path()).await;
+
+        let result = EditTool
+            .execute(edit_params(&file, "let a = 1;", "let a = 10;"), &ctx)
+            .await
+            .expect("unique match should be replaced");
+
+        assert_eq!(result.changes_made, 1);
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            "let a = 10;\nlet b = 2;\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn non_unique_match_reports_count_and_locations() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        std::fs::write(&file, "x += 1;\ny += 1;\nx += 1;\n").unwrap();
+        let ctx = test_context(dir.path()).await;
+
+        let error = EditTool
+            .execute(edit_params(&file, "x += 1;", "x += 2;"), &ctx)
+            .await
+            .expect_err("ambiguous old_string should be rejected");
+
+        let BuiltinToolError::Execution(EditError::EditFailure(EditFailure::NonUniqueMatch {
+            occurrences,
+            ref match_previews,
+            ..
+        })) = error
+        else {
+            panic!("expected a non-unique match error, got {error:?}");
+        };
+        assert_eq!(occurrences, 2);
+        let lines: Vec<_> = match_previews.iter().map(|p| p.line_number).collect();
+        assert_eq!(lines, vec![1, 3]);
+        assert!(error.to_string().contains("found 2 matches"), "{error}");
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            "x += 1;\ny += 1;\nx += 1;\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn replace_all_replaces_every_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lib.rs");
+        std::fs::write(&file, "x += 1;\ny += 1;\nx += 1;\n").unwrap();
+        let ctx = test_context(dir.path()).await;
+
+        let mut params = edit_params(&file, "x += 1;", "x += 2;");
+        params.replace_all = Some(true);
+        let result = EditTool
+            .execute(params, &ctx)
+            .await
+            .expect("replace_all should accept repeated matches");
+
+        assert_eq!(result.changes_made, 2);
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            "x += 2;\ny += 1;\nx += 2;\n"
+        );
+    }
+
+    #[test]
+    fn replace_all_conflicting_with_match_mode_is_rejected() {
+        let mut params = edit_params(Path::new("/tmp/lib.rs"), "x", "y");
+        params.replace_all = Some(true);
+        params.match_mode = Some(EditMatchMode::First);
+        assert!(edit_match_selection(&params).is_err());
+
+        params.match_mode = Some(EditMatchMode::All);
+        assert_eq!(
+            edit_match_selection(&params),
+            Ok(Some(EditMatchSelection::All))
+        );
+
+        params.replace_all = Some(false);
+        params.match_mode = None;
+        assert_eq!(edit_match_selection(&params), Ok(None));
     }
 }
@@ -32,6 +32,8 @@ const REQUEST_TIMEOUT_SECONDS: u64 = 20;
 
 pub struct FetchTool;
 
+register_builtin_tool!(FetchTool);
+
 #[async_trait]
 impl BuiltinTool for FetchTool {
     type Params = FetchParams;
@@ -43,12 +45,18 @@ impl BuiltinTool for FetchTool {
     const REQUIRED_CAPABILITIES: Capabilities = Capabilities::from_bits_truncate(
         Capabilities::NETWORK.bits() | Capabilities::MODEL_CALLER.bits(),
     );
+    // Only issues GET requests.
+    const IDEMPOTENT: bool = true;
 
     async fn execute(
         &self,
         params: Self::Params,
         ctx: &BuiltinToolContext,
     ) -> Result<Self::Output, BuiltinToolError<FetchError>> {
+        if !ctx.env.allows_network() {
+            return Err(BuiltinToolError::execution(FetchError::NetworkDisabled));
+        }
+
         let model_caller = ctx
             .services
             .model_caller()
@@ -108,6 +116,7 @@ Provide a concise response based only on relevant facts from the content above.
         Ok(FetchResult {
             url: normalized_url.to_string(),
             content: result_content,
+            warnings: Vec::new(),
         })
     }
 }
@@ -161,7 +170,7 @@ async fn fetch_url(
     url: &url::Url,
     token: &tokio_util::sync::CancellationToken,
 ) -> Result<String, BuiltinToolError<FetchError>> {
-    let client = reqwest::Client::builder()
+    let client = crate::http::client_builder()
         .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECONDS))
         .build()
         .map_err(|e| {
@@ -232,6 +241,59 @@ async fn fetch_url(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::Client as ApiClient;
+    use crate::app::domain::session::InMemoryEventStore;
+    use crate::app::domain::types::{SessionId, ToolCallId};
+    use crate::model_registry::ModelRegistry;
+    use crate::tools::ToolEnv;
+    use crate::tools::services::ToolServices;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio_util::sync::CancellationToken;
+
+    #[tokio::test]
+    async fn fetch_errors_when_network_disabled() {
+        let model_registry = Arc::new(ModelRegistry::load(&[]).unwrap());
+        let provider_registry = Arc::new(crate::auth::ProviderRegistry::load(&[]).unwrap());
+        let api_client = Arc::new(ApiClient::new_with_deps(
+            crate::test_utils::test_llm_config_provider().unwrap(),
+            provider_registry,
+            model_registry,
+        ));
+        let workspace =
+            crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
+                path: std::env::current_dir().unwrap(),
+                context_limits: Default::default(),
+            })
+            .await
+            .unwrap();
+        let services =
+            ToolServices::new(workspace, Arc::new(InMemoryEventStore::new()), api_client)
+                .with_network();
+
+        let ctx = BuiltinToolContext {
+            tool_call_id: ToolCallId::new(),
+            session_id: SessionId::new(),
+            invoking_model: None,
+            cancellation_token: CancellationToken::new(),
+            services: Arc::new(services),
+            env: Arc::new(ToolEnv::resolve(&HashMap::new()).with_network(false)),
+        };
+        let params = FetchParams {
+            url: "https://example.com".to_string(),
+            prompt: "summarize".to_string(),
+        };
+
+        let error = FetchTool
+            .execute(params, &ctx)
+            .await
+            .expect_err("fetch should fail without network");
+        assert!(matches!(
+            error,
+            BuiltinToolError::Execution(FetchError::NetworkDisabled)
+        ));
+        assert!(error.to_string().contains("network disabled"));
+    }
 
     #[test]
     fn normalize_fetch_url_upgrades_http_to_https() {
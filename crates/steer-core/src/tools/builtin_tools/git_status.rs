@@ -0,0 +1,49 @@
+use async_trait::async_trait;
+
+use crate::tools::builtin_tool::{BuiltinTool, BuiltinToolContext, BuiltinToolError};
+use crate::tools::capability::Capabilities;
+use steer_tools::result::GitStatusResult;
+use steer_tools::tools::git_status::{GitStatusError, GitStatusParams, GitStatusToolSpec};
+use steer_workspace::LlmStatus;
+use steer_workspace::utils::GitStatusUtils;
+
+const DESCRIPTION: &str = "Returns the full git status of the workspace repository: the current branch, every modified, staged and untracked path, and the most recent commit. The system prompt only carries a short summary that is captured at the start of the conversation, so use this tool when you need the complete or current list of changes. This tool takes no parameters and does not modify the repository.";
+
+pub struct GitStatusTool;
+
+register_builtin_tool!(GitStatusTool);
+
+#[async_trait]
+impl BuiltinTool for GitStatusTool {
+    type Params = GitStatusParams;
+    type Output = GitStatusResult;
+    type Spec = GitStatusToolSpec;
+
+    const DESCRIPTION: &'static str = DESCRIPTION;
+    const REQUIRES_APPROVAL: bool = false;
+    const REQUIRED_CAPABILITIES: Capabilities = Capabilities::WORKSPACE;
+
+    async fn execute(
+        &self,
+        _params: Self::Params,
+        ctx: &BuiltinToolContext,
+    ) -> Result<Self::Output, BuiltinToolError<GitStatusError>> {
+        let root = ctx.services.workspace.working_directory().to_path_buf();
+        let status = tokio::task::spawn_blocking(move || GitStatusUtils::get_git_status(&root))
+            .await
+            .map_err(|e| {
+                BuiltinToolError::execution(GitStatusError::Status {
+                    message: e.to_string(),
+                })
+            })?
+            .map_err(|e| {
+                BuiltinToolError::execution(GitStatusError::Status {
+                    message: e.to_string(),
+                })
+            })?;
+
+        Ok(GitStatusResult {
+            content: status.as_llm_string(),
+        })
+    }
+}
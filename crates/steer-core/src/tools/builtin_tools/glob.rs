@@ -9,6 +9,8 @@ use steer_workspace::{GlobRequest, WorkspaceOpContext};
 
 pub struct GlobTool;
 
+register_builtin_tool!(GlobTool);
+
 #[async_trait]
 impl BuiltinTool for GlobTool {
     type Params = GlobParams;
@@ -17,9 +19,11 @@ impl BuiltinTool for GlobTool {
 
     const DESCRIPTION: &'static str = r#"Fast file pattern matching tool that works with any codebase size.
 - Supports glob patterns like "**/*.js" or "src/**/*.ts"
-- Returns matching file paths sorted by modification time
+- Returns matching file and directory paths sorted by path
+- Skips files excluded by .gitignore and the .git directory
 - Use this tool when you need to find files by name patterns"#;
     const REQUIRES_APPROVAL: bool = false;
+    const IDEMPOTENT: bool = true;
     const REQUIRED_CAPABILITIES: Capabilities = Capabilities::WORKSPACE;
 
     async fn execute(
@@ -29,7 +33,7 @@ impl BuiltinTool for GlobTool {
     ) -> Result<Self::Output, BuiltinToolError<GlobError>> {
         let request = GlobRequest {
             pattern: params.pattern,
-            path: params.path,
+            path: ctx.resolve_optional_workspace_path(params.path)?,
         };
         let op_ctx =
             WorkspaceOpContext::new(ctx.tool_call_id.0.clone(), ctx.cancellation_token.clone());
@@ -11,6 +11,8 @@ use steer_workspace::{GrepRequest, WorkspaceOpContext};
 
 pub struct GrepTool;
 
+register_builtin_tool!(GrepTool);
+
 #[async_trait]
 impl BuiltinTool for GrepTool {
     type Params = GrepParams;
@@ -22,9 +24,11 @@ impl BuiltinTool for GrepTool {
 - Supports regex syntax like "log.*Error", "function\\s+\\w+", etc.
 - If the pattern isn't valid regex, it automatically searches for the literal text
 - Filter files by name pattern with include parameter (e.g., "*.js", "*.{ts,tsx}")
-- Automatically respects .gitignore files
+- Automatically respects .gitignore files, including nested ones
+- Stops after 2000 matching lines; narrow the pattern, include or path if the search is incomplete
 - Returns matches as "filepath:line_number: line_content""#;
     const REQUIRES_APPROVAL: bool = false;
+    const IDEMPOTENT: bool = true;
     const REQUIRED_CAPABILITIES: Capabilities = Capabilities::WORKSPACE;
 
     async fn execute(
@@ -34,7 +38,8 @@ impl BuiltinTool for GrepTool {
     ) -> Result<Self::Output, BuiltinToolError<GrepError>> {
         const GREP_TIMEOUT: Duration = Duration::from_secs(30);
 
-        let request = validate_and_build_request(params)?;
+        let mut request = validate_and_build_request(params)?;
+        request.path = ctx.resolve_optional_workspace_path(request.path)?;
         let op_ctx =
             WorkspaceOpContext::new(ctx.tool_call_id.0.clone(), ctx.cancellation_token.clone());
 
@@ -7,16 +7,22 @@ use steer_tools::result::FileListResult;
 use steer_tools::tools::ls::{LsError, LsParams, LsToolSpec};
 use steer_workspace::{ListDirectoryRequest, WorkspaceOpContext};
 
+/// Page size used when the model does not pass `limit`.
+const DEFAULT_LS_LIMIT: usize = 1000;
+
 pub struct LsTool;
 
+register_builtin_tool!(LsTool);
+
 #[async_trait]
 impl BuiltinTool for LsTool {
     type Params = LsParams;
     type Output = FileListResult;
     type Spec = LsToolSpec;
 
-    const DESCRIPTION: &'static str = "Lists files and directories in a given path. The path parameter must be an absolute path, not a relative path. You should generally prefer the Glob and Grep tools, if you know which directories to search.";
+    const DESCRIPTION: &'static str = "Lists files and directories in a given path. The path parameter must be an absolute path, not a relative path. Entries are sorted directories first, then by name, and at most `limit` entries (default 1000) are returned. When the result says it is truncated, call again with `offset` set to the number of entries already seen to get the next page. You should generally prefer the Glob and Grep tools, if you know which directories to search.";
     const REQUIRES_APPROVAL: bool = false;
+    const IDEMPOTENT: bool = true;
     const REQUIRED_CAPABILITIES: Capabilities = Capabilities::WORKSPACE;
 
     async fn execute(
@@ -25,8 +31,11 @@ impl BuiltinTool for LsTool {
         ctx: &BuiltinToolContext,
     ) -> Result<Self::Output, BuiltinToolError<LsError>> {
         let request = ListDirectoryRequest {
-            path: params.path,
+            path: ctx.resolve_workspace_path(&params.path)?,
             ignore: params.ignore,
+            offset: params.offset,
+            limit: Some(params.limit.unwrap_or(DEFAULT_LS_LIMIT)),
+            respect_gitignore: params.respect_gitignore,
         };
         let op_ctx =
             WorkspaceOpContext::new(ctx.tool_call_id.0.clone(), ctx.cancellation_token.clone());
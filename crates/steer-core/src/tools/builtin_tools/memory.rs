@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+
+use crate::tools::builtin_tool::{BuiltinTool, BuiltinToolContext, BuiltinToolError};
+use crate::tools::capability::Capabilities;
+use steer_tools::result::MemoryResult;
+use steer_tools::tools::memory::MemoryEntry;
+use steer_tools::tools::memory::forget::{ForgetError, ForgetParams, ForgetToolSpec};
+use steer_tools::tools::memory::remember::{RememberError, RememberParams, RememberToolSpec};
+
+const REMEMBER_DESCRIPTION: &str = r"Save a fact to the session memory so it stays available for the rest of the session.
+
+Session memory is shown in the system prompt on every turn and is kept when the conversation is compacted or resumed, so use it for durable facts rather than progress notes.
+
+When to use this tool:
+- The user states a preference or convention they expect you to follow (e.g. how to run tests, code style rules)
+- You discover a non-obvious fact about the project that later work depends on
+- The user explicitly asks you to remember something
+
+When not to use this tool:
+- Task progress or plans (use the todo tools instead)
+- Facts that are easy to rediscover from the code
+- Anything secret, such as passwords or API keys
+
+Usage:
+- `key` is a short, stable name such as `test-command`; remembering an existing key replaces its content
+- `content` should make sense on its own, without the surrounding conversation
+- Returns the full session memory after the change";
+
+const FORGET_DESCRIPTION: &str = r"Remove a fact from the session memory by its key.
+
+Use this when a remembered fact is wrong or no longer applies, or when the user asks you to forget it. To correct a fact, prefer the remember tool with the same key instead.
+
+Returns the full session memory after the change.";
+
+pub struct RememberTool;
+
+register_builtin_tool!(RememberTool);
+
+#[async_trait]
+impl BuiltinTool for RememberTool {
+    type Params = RememberParams;
+    type Output = MemoryResult;
+    type Spec = RememberToolSpec;
+
+    const DESCRIPTION: &'static str = REMEMBER_DESCRIPTION;
+    const REQUIRES_APPROVAL: bool = false;
+    const REQUIRED_CAPABILITIES: Capabilities = Capabilities::WORKSPACE;
+
+    async fn execute(
+        &self,
+        params: Self::Params,
+        ctx: &BuiltinToolContext,
+    ) -> Result<Self::Output, BuiltinToolError<RememberError>> {
+        if ctx.is_cancelled() {
+            return Err(BuiltinToolError::Cancelled);
+        }
+
+        let entry = MemoryEntry {
+            key: params.key.trim().to_string(),
+            content: params.content.trim().to_string(),
+        };
+        if entry.key.is_empty() || entry.content.is_empty() {
+            return Err(BuiltinToolError::execution(RememberError::Empty));
+        }
+
+        let io_error = |e: crate::app::domain::session::EventStoreError| {
+            BuiltinToolError::execution(RememberError::Io {
+                message: e.to_string(),
+            })
+        };
+
+        let store = &ctx.services.event_store;
+        store
+            .save_memory_entry(ctx.session_id, &entry)
+            .await
+            .map_err(io_error)?;
+        let entries = store.load_memory(ctx.session_id).await.map_err(io_error)?;
+
+        Ok(MemoryResult { entries })
+    }
+}
+
+pub struct ForgetTool;
+
+register_builtin_tool!(ForgetTool);
+
+#[async_trait]
+impl BuiltinTool for ForgetTool {
+    type Params = ForgetParams;
+    type Output = MemoryResult;
+    type Spec = ForgetToolSpec;
+
+    const DESCRIPTION: &'static str = FORGET_DESCRIPTION;
+    const REQUIRES_APPROVAL: bool = false;
+    const REQUIRED_CAPABILITIES: Capabilities = Capabilities::WORKSPACE;
+
+    async fn execute(
+        &self,
+        params: Self::Params,
+        ctx: &BuiltinToolContext,
+    ) -> Result<Self::Output, BuiltinToolError<ForgetError>> {
+        if ctx.is_cancelled() {
+            return Err(BuiltinToolError::Cancelled);
+        }
+
+        let io_error = |e: crate::app::domain::session::EventStoreError| {
+            BuiltinToolError::execution(ForgetError::Io {
+                message: e.to_string(),
+            })
+        };
+
+        let key = params.key.trim();
+        let store = &ctx.services.event_store;
+        if !store
+            .delete_memory_entry(ctx.session_id, key)
+            .await
+            .map_err(io_error)?
+        {
+            return Err(BuiltinToolError::execution(ForgetError::NotFound {
+                key: key.to_string(),
+            }));
+        }
+        let entries = store.load_memory(ctx.session_id).await.map_err(io_error)?;
+
+        Ok(MemoryResult { entries })
+    }
+}
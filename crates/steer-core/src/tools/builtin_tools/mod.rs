@@ -1,11 +1,28 @@
+/// Adds a builtin tool to the static registry read by [`builtin_tool_schemas`].
+///
+/// Invoke it next to the tool's struct; the entry is collected at link time, so
+/// a tool cannot ship without its schema showing up in `steer tools schema` and
+/// the snapshot tests.
+macro_rules! register_builtin_tool {
+    ($tool:ty) => {
+        inventory::submit! {
+            $crate::tools::builtin_tools::BuiltinToolRegistration::new(
+                <$tool as $crate::tools::BuiltinTool>::schema,
+            )
+        }
+    };
+}
+
 pub mod astgrep;
 pub mod bash;
 pub mod dispatch_agent;
 pub mod edit;
 pub mod fetch;
+pub mod git_status;
 pub mod glob;
 pub mod grep;
 pub mod ls;
+pub mod memory;
 pub mod read_file;
 pub mod replace;
 pub mod todo;
@@ -15,14 +32,17 @@ pub use bash::BashTool;
 pub use dispatch_agent::DispatchAgentTool;
 pub use edit::{EditTool, MultiEditTool};
 pub use fetch::FetchTool;
+pub use git_status::GitStatusTool;
 pub use glob::GlobTool;
 pub use grep::GrepTool;
 pub use ls::LsTool;
+pub use memory::{ForgetTool, RememberTool};
 pub use read_file::ReadFileTool;
 pub use replace::ReplaceTool;
 pub use todo::{TodoReadTool, TodoWriteTool};
 
 use crate::session::state::ToolVisibility;
+use steer_tools::ToolSchema;
 
 #[cfg(test)]
 pub(crate) const ALL_BUILTIN_TOOL_NAMES: &[&str] = &[
@@ -31,16 +51,51 @@ pub(crate) const ALL_BUILTIN_TOOL_NAMES: &[&str] = &[
     steer_tools::tools::DISPATCH_AGENT_TOOL_NAME,
     steer_tools::tools::EDIT_TOOL_NAME,
     steer_tools::tools::FETCH_TOOL_NAME,
+    steer_tools::tools::GIT_STATUS_TOOL_NAME,
     steer_tools::tools::GLOB_TOOL_NAME,
     steer_tools::tools::GREP_TOOL_NAME,
     steer_tools::tools::LS_TOOL_NAME,
     steer_tools::tools::MULTI_EDIT_TOOL_NAME,
+    steer_tools::tools::REMEMBER_TOOL_NAME,
+    steer_tools::tools::FORGET_TOOL_NAME,
     steer_tools::tools::REPLACE_TOOL_NAME,
     steer_tools::tools::TODO_READ_TOOL_NAME,
     steer_tools::tools::TODO_WRITE_TOOL_NAME,
     steer_tools::tools::READ_FILE_TOOL_NAME,
 ];
 
+/// A builtin tool's entry in the static registry, submitted by
+/// `register_builtin_tool!`.
+pub struct BuiltinToolRegistration {
+    schema: fn() -> ToolSchema,
+}
+
+impl BuiltinToolRegistration {
+    pub const fn new(schema: fn() -> ToolSchema) -> Self {
+        Self { schema }
+    }
+
+    pub fn schema(&self) -> ToolSchema {
+        (self.schema)()
+    }
+}
+
+inventory::collect!(BuiltinToolRegistration);
+
+/// Schemas for every builtin tool, sorted by tool name.
+///
+/// These are the exact schemas and descriptions sent to the model, so this is the
+/// source of truth for `steer tools schema` and the schema snapshot tests. They
+/// come from the static registry, so no runtime or workspace is needed.
+pub fn builtin_tool_schemas() -> Vec<ToolSchema> {
+    let mut schemas: Vec<ToolSchema> = inventory::iter::<BuiltinToolRegistration>
+        .into_iter()
+        .map(BuiltinToolRegistration::schema)
+        .collect();
+    schemas.sort_by(|a, b| a.name.cmp(&b.name));
+    schemas
+}
+
 pub(crate) fn register_builtin_tools(registry: &mut super::ToolRegistry) {
     register_builtin_tools_for_visibility(registry, &ToolVisibility::All);
 }
@@ -81,6 +136,12 @@ pub(crate) fn register_builtin_tools_for_visibility(
             registry.register_builtin(ReadFileTool);
         },
     );
+    register_if_visible(
+        registry,
+        visibility,
+        steer_tools::tools::GIT_STATUS_TOOL_NAME,
+        |registry| registry.register_builtin(GitStatusTool),
+    );
     register_if_visible(
         registry,
         visibility,
@@ -127,6 +188,18 @@ pub(crate) fn register_builtin_tools_for_visibility(
         steer_tools::tools::TODO_WRITE_TOOL_NAME,
         |registry| registry.register_builtin(TodoWriteTool),
     );
+    register_if_visible(
+        registry,
+        visibility,
+        steer_tools::tools::REMEMBER_TOOL_NAME,
+        |registry| registry.register_builtin(RememberTool),
+    );
+    register_if_visible(
+        registry,
+        visibility,
+        steer_tools::tools::FORGET_TOOL_NAME,
+        |registry| registry.register_builtin(ForgetTool),
+    );
     register_if_visible(
         registry,
         visibility,
@@ -173,6 +246,10 @@ pub(crate) fn workspace_op_error(
         steer_workspace::WorkspaceError::NotSupported(message) => {
             WorkspaceOpError::NotSupported { message }
         }
+        error @ (steer_workspace::WorkspaceError::Transport(_)
+        | steer_workspace::WorkspaceError::Status(_)) => WorkspaceOpError::Unavailable {
+            message: error.to_string(),
+        },
         other => WorkspaceOpError::Other {
             message: other.to_string(),
         },
@@ -202,11 +279,18 @@ pub const READ_ONLY_TOOL_NAMES: &[&str] = &[
     steer_tools::tools::GLOB_TOOL_NAME,
     steer_tools::tools::LS_TOOL_NAME,
     steer_tools::tools::READ_FILE_TOOL_NAME,
+    steer_tools::tools::GIT_STATUS_TOOL_NAME,
     steer_tools::tools::TODO_READ_TOOL_NAME,
-    // This mutates only the session todo list and is intentionally auto-approved.
+    // These mutate only session state (todos, memory) and are intentionally auto-approved.
     steer_tools::tools::TODO_WRITE_TOOL_NAME,
+    steer_tools::tools::REMEMBER_TOOL_NAME,
+    steer_tools::tools::FORGET_TOOL_NAME,
 ];
 
+/// Built-in tools that reach the network and are hidden from sessions that
+/// disallow outbound network access.
+pub const NETWORK_TOOL_NAMES: &[&str] = &[steer_tools::tools::FETCH_TOOL_NAME];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -226,6 +310,31 @@ mod tests {
         assert_eq!(names, expected);
     }
 
+    #[test]
+    fn builtin_tool_schemas_are_sorted_and_complete() {
+        let schemas = builtin_tool_schemas();
+        let names: Vec<&str> = schemas.iter().map(|s| s.name.as_str()).collect();
+
+        let mut expected = ALL_BUILTIN_TOOL_NAMES.to_vec();
+        expected.sort_unstable();
+
+        assert_eq!(names, expected);
+        assert!(schemas.iter().all(|s| !s.display_name.is_empty()));
+    }
+
+    #[test]
+    fn static_registry_matches_runtime_registration() {
+        let mut registry = crate::tools::ToolRegistry::new();
+        register_builtin_tools(&mut registry);
+        let mut runtime = registry.builtin_schemas();
+        runtime.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(
+            serde_json::to_value(builtin_tool_schemas()).unwrap(),
+            serde_json::to_value(runtime).unwrap()
+        );
+    }
+
     #[test]
     fn register_builtin_tools_for_visibility_honors_whitelist() {
         let mut registry = crate::tools::ToolRegistry::new();
@@ -9,6 +9,8 @@ use steer_workspace::{ReadFileRequest, WorkspaceOpContext};
 
 pub struct ReadFileTool;
 
+register_builtin_tool!(ReadFileTool);
+
 #[async_trait]
 impl BuiltinTool for ReadFileTool {
     type Params = ReadFileParams;
@@ -19,10 +21,12 @@ impl BuiltinTool for ReadFileTool {
         "Reads a file from the local filesystem. The file_path parameter must be an absolute path, not a relative path.\n",
         "By default, it reads up to 2000 lines starting from the beginning of the file. You can optionally specify a line offset and limit\n",
         "(especially handy for long files), but it's recommended to read the whole file by not providing these parameters.\n",
-        "Any lines longer than 2000 characters will be truncated.\n",
+        "Any lines longer than 2000 characters will be truncated, and at most 50KB of content is returned (set max_bytes to return less).\n",
+        "Non-UTF-8 files are transcoded to UTF-8 and binary files are summarized instead of returned.\n",
         "Set raw=true to return unnumbered, untrimmed content without truncation for exact copy/paste."
     );
     const REQUIRES_APPROVAL: bool = false;
+    const IDEMPOTENT: bool = true;
     const REQUIRED_CAPABILITIES: Capabilities = Capabilities::WORKSPACE;
 
     async fn execute(
@@ -31,10 +35,11 @@ impl BuiltinTool for ReadFileTool {
         ctx: &BuiltinToolContext,
     ) -> Result<Self::Output, BuiltinToolError<ReadFileError>> {
         let request = ReadFileRequest {
-            file_path: params.file_path,
+            file_path: ctx.resolve_workspace_path(&params.file_path)?,
             offset: params.offset,
             limit: params.limit,
             raw: params.raw,
+            max_bytes: params.max_bytes,
         };
         let op_ctx =
             WorkspaceOpContext::new(ctx.tool_call_id.0.clone(), ctx.cancellation_token.clone());
@@ -47,3 +52,110 @@ impl BuiltinTool for ReadFileTool {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::Client as ApiClient;
+    use crate::app::domain::session::InMemoryEventStore;
+    use crate::app::domain::types::{SessionId, ToolCallId};
+    use crate::model_registry::ModelRegistry;
+    use crate::tools::ToolEnv;
+    use crate::tools::services::ToolServices;
+    use std::path::Path;
+    use std::sync::Arc;
+    use tokio_util::sync::CancellationToken;
+
+    async fn test_context(root: &Path, env: ToolEnv) -> BuiltinToolContext {
+        let model_registry = Arc::new(ModelRegistry::load(&[]).unwrap());
+        let provider_registry = Arc::new(crate::auth::ProviderRegistry::load(&[]).unwrap());
+        let api_client = Arc::new(ApiClient::new_with_deps(
+            crate::test_utils::test_llm_config_provider().unwrap(),
+            provider_registry,
+            model_registry,
+        ));
+        let workspace =
+            crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
+                path: root.to_path_buf(),
+                context_limits: Default::default(),
+            })
+            .await
+            .unwrap();
+        let services =
+            ToolServices::new(workspace, Arc::new(InMemoryEventStore::new()), api_client);
+
+        BuiltinToolContext {
+            tool_call_id: ToolCallId::new(),
+            session_id: SessionId::new(),
+            invoking_model: None,
+            cancellation_token: CancellationToken::new(),
+            services: Arc::new(services),
+            env: Arc::new(env),
+        }
+    }
+
+    fn read_params(file_path: &str) -> ReadFileParams {
+        ReadFileParams {
+            file_path: file_path.to_string(),
+            offset: None,
+            limit: None,
+            raw: Some(true),
+            max_bytes: None,
+        }
+    }
+
+    /// A workspace at `<tmp>/project` with `secret.txt` next to it.
+    fn project_with_sibling_secret() -> (tempfile::TempDir, std::path::PathBuf, String) {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("project");
+        std::fs::create_dir(&root).unwrap();
+        std::fs::write(root.join("notes.txt"), "inside\n").unwrap();
+        let secret = dir.path().join("secret.txt");
+        std::fs::write(&secret, "outside\n").unwrap();
+        (dir, root, secret.display().to_string())
+    }
+
+    #[tokio::test]
+    async fn reads_files_inside_the_workspace() {
+        let (_dir, root, _secret) = project_with_sibling_secret();
+        let ctx = test_context(&root, ToolEnv::default()).await;
+
+        let result = ReadFileTool
+            .execute(read_params("notes.txt"), &ctx)
+            .await
+            .expect("in-workspace read should succeed");
+
+        assert_eq!(result.content, "inside\n");
+    }
+
+    #[tokio::test]
+    async fn paths_outside_the_workspace_are_denied_by_default() {
+        let (_dir, root, secret) = project_with_sibling_secret();
+        let ctx = test_context(&root, ToolEnv::default()).await;
+
+        for path in [secret.as_str(), "../secret.txt"] {
+            let error = ReadFileTool
+                .execute(read_params(path), &ctx)
+                .await
+                .expect_err("out-of-workspace read should be denied");
+            assert!(
+                matches!(error, BuiltinToolError::InvalidParams(_)),
+                "unexpected error for {path}: {error:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn paths_outside_the_workspace_are_allowed_when_the_session_allows_it() {
+        let (_dir, root, secret) = project_with_sibling_secret();
+        let ctx = test_context(&root, ToolEnv::default().with_outside_workspace(true)).await;
+
+        for path in [secret.as_str(), "../secret.txt"] {
+            let result = ReadFileTool
+                .execute(read_params(path), &ctx)
+                .await
+                .expect("out-of-workspace read should be allowed");
+            assert_eq!(result.content, "outside\n");
+        }
+    }
+}
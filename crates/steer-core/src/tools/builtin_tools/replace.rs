@@ -9,6 +9,8 @@ use steer_workspace::{WorkspaceOpContext, WriteFileRequest};
 
 pub struct ReplaceTool;
 
+register_builtin_tool!(ReplaceTool);
+
 #[async_trait]
 impl BuiltinTool for ReplaceTool {
     type Params = ReplaceParams;
@@ -32,7 +34,7 @@ Before using this tool:
         ctx: &BuiltinToolContext,
     ) -> Result<Self::Output, BuiltinToolError<ReplaceError>> {
         let request = WriteFileRequest {
-            file_path: params.file_path,
+            file_path: ctx.resolve_workspace_path(&params.file_path)?,
             content: params.content,
         };
         let op_ctx =
@@ -51,6 +51,8 @@ Task management:
 
 pub struct TodoReadTool;
 
+register_builtin_tool!(TodoReadTool);
+
 #[async_trait]
 impl BuiltinTool for TodoReadTool {
     type Params = TodoReadParams;
@@ -88,6 +90,8 @@ impl BuiltinTool for TodoReadTool {
 
 pub struct TodoWriteTool;
 
+register_builtin_tool!(TodoWriteTool);
+
 #[async_trait]
 impl BuiltinTool for TodoWriteTool {
     type Params = TodoWriteParams;
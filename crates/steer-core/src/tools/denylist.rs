@@ -0,0 +1,639 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+use steer_tools::tools::bash::{ParsedCommand, SimpleCommand};
+
+use crate::session::state::BashDenylistConfig;
+
+/// Built-in rules and why each is refused.
+const DEFAULT_DENY_RULES: &[(&str, &str)] = &[
+    ("rm -r /", "recursively deletes the filesystem root"),
+    ("rm -R /", "recursively deletes the filesystem root"),
+    (
+        "rm --recursive /",
+        "recursively deletes the filesystem root",
+    ),
+    (r"rm -r '/\*'", "recursively deletes the filesystem root"),
+    (r"rm -R '/\*'", "recursively deletes the filesystem root"),
+    (
+        r"rm --recursive '/\*'",
+        "recursively deletes the filesystem root",
+    ),
+    ("rm -r ~", "recursively deletes the home directory"),
+    ("rm -R ~", "recursively deletes the home directory"),
+    ("rm --recursive ~", "recursively deletes the home directory"),
+    (r"rm -r '~/\*'", "recursively deletes the home directory"),
+    (r"rm -R '~/\*'", "recursively deletes the home directory"),
+    (
+        r"rm --recursive '~/\*'",
+        "recursively deletes the home directory",
+    ),
+    ("git reset --hard", "discards uncommitted changes"),
+    ("git checkout --", "discards uncommitted changes to files"),
+    ("dd of=/dev/*", "writes directly to a device"),
+];
+
+/// Built-in exceptions to the default rules.
+const DEFAULT_ALLOW_RULES: &[&str] = &["dd of=/dev/null"];
+
+const FORK_BOMB_RULE: &str = ":(){ :|:& };:";
+const FORK_BOMB_REASON: &str = "defines a fork bomb";
+const CUSTOM_RULE_REASON: &str = "listed in the session's bash denylist";
+const UNCHECKED_RULE: &str = "(unchecked command)";
+const UNPARSEABLE_REASON: &str = "could not be parsed to check against the denylist";
+const TOO_DEEP_REASON: &str = "nests command wrappers too deeply to check against the denylist";
+
+// Options of each wrapper that take the following word as their value.
+const SUDO_VALUE_OPTIONS: &[&str] = &[
+    "-u",
+    "-g",
+    "-C",
+    "-h",
+    "-p",
+    "-D",
+    "-r",
+    "-t",
+    "-U",
+    "-T",
+    "--user",
+    "--group",
+    "--host",
+    "--prompt",
+    "--chdir",
+    "--role",
+    "--type",
+    "--other-user",
+    "--command-timeout",
+];
+const ENV_VALUE_OPTIONS: &[&str] = &["-u", "-C", "--unset", "--chdir"];
+const NICE_VALUE_OPTIONS: &[&str] = &["-n", "--adjustment"];
+const TIMEOUT_VALUE_OPTIONS: &[&str] = &["-s", "-k", "--signal", "--kill-after"];
+const XARGS_VALUE_OPTIONS: &[&str] = &[
+    "-I",
+    "-n",
+    "-P",
+    "-L",
+    "-s",
+    "-d",
+    "-E",
+    "-a",
+    "--max-args",
+    "--max-procs",
+    "--max-lines",
+    "--max-chars",
+    "--delimiter",
+    "--eof",
+    "--arg-file",
+];
+
+/// How many wrappers (`sudo`, `sh -c`, ...) deep a command is checked before
+/// it is refused outright.
+const MAX_WRAPPER_DEPTH: usize = 8;
+
+/// A function whose body pipes the function into itself and backgrounds it.
+static FUNCTION_DEFINITION: LazyLock<Option<Regex>> = LazyLock::new(|| {
+    Regex::new(r"([A-Za-z_:][A-Za-z0-9_:]*)\s*\(\s*\)\s*\{([^}]*)\}")
+        .map_err(|err| {
+            tracing::error!(
+                target: "tools::bash",
+                error = %err,
+                "Failed to compile fork bomb regex"
+            );
+        })
+        .ok()
+});
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DenylistError {
+    #[error("bash denylist rule '{rule}' is not a command")]
+    InvalidRule { rule: String },
+}
+
+/// Why the denylist refused a command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeniedCommand {
+    pub rule: String,
+    pub reason: String,
+}
+
+/// Bash commands refused before execution, resolved from a session's
+/// [`BashDenylistConfig`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandDenylist {
+    deny: Vec<DenyRule>,
+    allow: Vec<CommandPattern>,
+    fork_bombs: bool,
+    /// Set when the session's rules could not be resolved; every command is refused.
+    invalid: Option<DenylistError>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DenyRule {
+    pattern: CommandPattern,
+    reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CommandPattern {
+    source: String,
+    program: String,
+    args: Vec<String>,
+}
+
+/// What a wrapper such as `sudo` or `sh -c` runs on the caller's behalf.
+enum Wrapped {
+    Command(SimpleCommand),
+    Script(String),
+}
+
+impl Default for CommandDenylist {
+    fn default() -> Self {
+        Self {
+            deny: builtin_deny_rules().collect(),
+            allow: builtin_allow_rules().collect(),
+            fork_bombs: true,
+            invalid: None,
+        }
+    }
+}
+
+impl CommandDenylist {
+    /// Resolves `config`, failing on any rule that is not a command.
+    pub fn new(config: &BashDenylistConfig) -> Result<Self, DenylistError> {
+        let defaults = !config.disable_defaults;
+
+        let mut deny = Vec::new();
+        if defaults {
+            deny.extend(builtin_deny_rules());
+        }
+        for rule in &config.deny {
+            deny.push(DenyRule::new(rule, CUSTOM_RULE_REASON)?);
+        }
+
+        let mut allow = Vec::new();
+        if defaults {
+            allow.extend(builtin_allow_rules());
+        }
+        for rule in &config.allow {
+            allow.push(CommandPattern::parse(rule)?);
+        }
+
+        Ok(Self {
+            deny,
+            allow,
+            fork_bombs: defaults,
+            invalid: None,
+        })
+    }
+
+    /// A denylist that refuses every command, for a session whose rules fail
+    /// to resolve, so its commands are not run unchecked.
+    pub fn refusing_all(error: DenylistError) -> Self {
+        Self {
+            deny: Vec::new(),
+            allow: Vec::new(),
+            fork_bombs: false,
+            invalid: Some(error),
+        }
+    }
+
+    /// Returns the first rule `command` violates. Each command in a pipeline or
+    /// list is checked on its own, as is whatever a wrapper such as `sudo`,
+    /// `env`, `xargs` or `sh -c` runs, and allowlisted commands are skipped.
+    /// Commands that cannot be parsed are refused, since they cannot be checked.
+    pub fn check(&self, command: &str) -> Option<DeniedCommand> {
+        if let Some(error) = &self.invalid {
+            return Some(unchecked(&format!(
+                "could not be checked against the session's denylist ({error})"
+            )));
+        }
+        if self.deny.is_empty() && !self.fork_bombs {
+            return None;
+        }
+        if self.fork_bombs && is_fork_bomb(command) {
+            return Some(DeniedCommand {
+                rule: FORK_BOMB_RULE.to_string(),
+                reason: FORK_BOMB_REASON.to_string(),
+            });
+        }
+
+        self.check_script(command, 0)
+    }
+
+    fn check_script(&self, script: &str, depth: usize) -> Option<DeniedCommand> {
+        let Some(parsed) = ParsedCommand::parse(script) else {
+            return Some(unchecked(UNPARSEABLE_REASON));
+        };
+        parsed
+            .commands
+            .iter()
+            .find_map(|simple| self.check_simple(simple, depth))
+    }
+
+    fn check_simple(&self, command: &SimpleCommand, depth: usize) -> Option<DeniedCommand> {
+        if !self.allow.iter().any(|pattern| pattern.matches(command))
+            && let Some(rule) = self.deny.iter().find(|rule| rule.pattern.matches(command))
+        {
+            return Some(DeniedCommand {
+                rule: rule.pattern.source.clone(),
+                reason: rule.reason.clone(),
+            });
+        }
+
+        let wrapped = wrapped_command(command)?;
+        if depth >= MAX_WRAPPER_DEPTH {
+            return Some(unchecked(TOO_DEEP_REASON));
+        }
+        match wrapped {
+            Wrapped::Command(inner) => self.check_simple(&inner, depth + 1),
+            Wrapped::Script(script) => self.check_script(&script, depth + 1),
+        }
+    }
+}
+
+fn builtin_deny_rules() -> impl Iterator<Item = DenyRule> {
+    DEFAULT_DENY_RULES
+        .iter()
+        .filter_map(|(rule, reason)| DenyRule::new(rule, reason).ok())
+}
+
+fn builtin_allow_rules() -> impl Iterator<Item = CommandPattern> {
+    DEFAULT_ALLOW_RULES
+        .iter()
+        .filter_map(|rule| CommandPattern::parse(rule).ok())
+}
+
+fn unchecked(reason: &str) -> DeniedCommand {
+    DeniedCommand {
+        rule: UNCHECKED_RULE.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+impl DenyRule {
+    fn new(rule: &str, reason: &str) -> Result<Self, DenylistError> {
+        Ok(Self {
+            pattern: CommandPattern::parse(rule)?,
+            reason: reason.to_string(),
+        })
+    }
+}
+
+impl CommandPattern {
+    fn parse(rule: &str) -> Result<Self, DenylistError> {
+        let parsed = ParsedCommand::parse(rule);
+        let Some(simple) = parsed.as_ref().and_then(|parsed| parsed.commands.first()) else {
+            return Err(DenylistError::InvalidRule {
+                rule: rule.to_string(),
+            });
+        };
+
+        Ok(Self {
+            source: rule.trim().to_string(),
+            program: simple.program_name().to_string(),
+            args: expand_flags(&simple.args),
+        })
+    }
+
+    fn matches(&self, command: &SimpleCommand) -> bool {
+        if command.program_name() != self.program {
+            return false;
+        }
+
+        let args = expand_flags(&command.args);
+        self.args
+            .iter()
+            .all(|expected| args.iter().any(|arg| arg_matches(expected, arg)))
+    }
+}
+
+/// A trailing `*` matches any suffix; `\*` matches a literal `*`.
+fn arg_matches(expected: &str, arg: &str) -> bool {
+    if let Some(prefix) = expected.strip_suffix(r"\*") {
+        return arg.strip_suffix('*') == Some(prefix);
+    }
+    match expected.strip_suffix('*') {
+        Some(prefix) => arg.starts_with(prefix),
+        None => expected == arg,
+    }
+}
+
+/// The command a wrapper program runs, or `None` if `command` is not a
+/// wrapper or runs nothing else.
+fn wrapped_command(command: &SimpleCommand) -> Option<Wrapped> {
+    let args = command.args.as_slice();
+    let (value_options, skips_assignments, positionals): (&[&str], bool, usize) =
+        match command.program_name() {
+            "sudo" | "doas" => (SUDO_VALUE_OPTIONS, true, 0),
+            "env" => {
+                if let Some(index) = args
+                    .iter()
+                    .position(|arg| arg == "-S" || arg == "--split-string")
+                {
+                    return args.get(index + 1).cloned().map(Wrapped::Script);
+                }
+                (ENV_VALUE_OPTIONS, true, 0)
+            }
+            "nice" => (NICE_VALUE_OPTIONS, false, 0),
+            // `timeout DURATION COMMAND`
+            "timeout" => (TIMEOUT_VALUE_OPTIONS, false, 1),
+            "xargs" => (XARGS_VALUE_OPTIONS, false, 0),
+            "command" | "exec" | "nohup" | "time" => (&[], false, 0),
+            "eval" => return (!args.is_empty()).then(|| Wrapped::Script(args.join(" "))),
+            "sh" | "bash" | "dash" | "zsh" | "ksh" => {
+                let index = args.iter().position(|arg| {
+                    arg.strip_prefix('-').is_some_and(|flags| {
+                        flags.contains('c') && flags.chars().all(|flag| flag.is_ascii_alphabetic())
+                    })
+                })?;
+                return args.get(index + 1).cloned().map(Wrapped::Script);
+            }
+            _ => return None,
+        };
+
+    let mut rest = args.iter();
+    let mut positionals = positionals;
+    while let Some(arg) = rest.next() {
+        if arg == "--" {
+            break;
+        }
+        if value_options.contains(&arg.as_str()) {
+            rest.next();
+            continue;
+        }
+        if arg.starts_with('-') || (skips_assignments && is_assignment(arg)) {
+            continue;
+        }
+        if positionals > 0 {
+            positionals -= 1;
+            continue;
+        }
+        return Some(Wrapped::Command(SimpleCommand {
+            program: arg.clone(),
+            args: rest.cloned().collect(),
+        }));
+    }
+    let program = rest.nth(positionals)?;
+    Some(Wrapped::Command(SimpleCommand {
+        program: program.clone(),
+        args: rest.cloned().collect(),
+    }))
+}
+
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+/// Split clustered short flags, so `-rf` becomes `-r` and `-f`.
+fn expand_flags(args: &[String]) -> Vec<String> {
+    let mut expanded = Vec::with_capacity(args.len());
+    for arg in args {
+        let cluster = arg.strip_prefix('-').filter(|flags| {
+            flags.len() > 1 && flags.chars().all(|flag| flag.is_ascii_alphabetic())
+        });
+        match cluster {
+            Some(flags) => expanded.extend(flags.chars().map(|flag| format!("-{flag}"))),
+            None => expanded.push(arg.clone()),
+        }
+    }
+    expanded
+}
+
+fn is_fork_bomb(command: &str) -> bool {
+    let Some(definition) = FUNCTION_DEFINITION.as_ref() else {
+        return false;
+    };
+
+    definition.captures_iter(command).any(|captures| {
+        let name = &captures[1];
+        let body: String = captures[2].split_whitespace().collect();
+        body.contains(&format!("{name}|{name}&"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn denied_rule(denylist: &CommandDenylist, command: &str) -> Option<String> {
+        denylist.check(command).map(|denied| denied.rule)
+    }
+
+    #[test]
+    fn blocks_default_dangerous_commands() {
+        let denylist = CommandDenylist::default();
+
+        for (command, rule) in [
+            ("rm -rf /", "rm -r /"),
+            ("rm -fr --no-preserve-root /", "rm -r /"),
+            ("/bin/rm -r -f ~", "rm -r ~"),
+            ("git reset --hard HEAD~1", "git reset --hard"),
+            ("git -C repo reset --hard", "git reset --hard"),
+            ("git checkout -- src/main.rs", "git checkout --"),
+            (
+                "cd build && dd if=disk.img of=/dev/sda bs=4M",
+                "dd of=/dev/*",
+            ),
+            (":(){ :|:& };:", FORK_BOMB_RULE),
+            ("bomb() { bomb | bomb & }; bomb", FORK_BOMB_RULE),
+        ] {
+            assert_eq!(
+                denied_rule(&denylist, command).as_deref(),
+                Some(rule),
+                "{command}"
+            );
+        }
+    }
+
+    #[test]
+    fn allows_similar_safe_commands() {
+        let denylist = CommandDenylist::default();
+
+        for command in [
+            "rm -rf /tmp/build",
+            "rm -rf ./target",
+            "git reset --soft HEAD~1",
+            "git reset HEAD file.rs",
+            "git checkout main",
+            "git commit -m 'git reset --hard'",
+            "echo 'rm -rf /'",
+            "dd if=/dev/zero of=disk.img count=1",
+            "dd if=/dev/urandom of=/dev/null count=1",
+            "greet() { echo hi; }; greet",
+        ] {
+            assert_eq!(denied_rule(&denylist, command), None, "{command}");
+        }
+    }
+
+    #[test]
+    fn allowlist_overrides_deny_rules() {
+        let denylist = CommandDenylist::new(&BashDenylistConfig {
+            allow: vec!["git reset --hard".to_string()],
+            ..BashDenylistConfig::default()
+        })
+        .unwrap();
+
+        assert_eq!(denied_rule(&denylist, "git reset --hard origin/main"), None);
+        assert_eq!(
+            denied_rule(&denylist, "git reset --hard && git checkout -- .").as_deref(),
+            Some("git checkout --")
+        );
+    }
+
+    #[test]
+    fn custom_rules_extend_or_replace_defaults() {
+        let extended = CommandDenylist::new(&BashDenylistConfig {
+            deny: vec![
+                "git push --force".to_string(),
+                "terraform destroy".to_string(),
+            ],
+            ..BashDenylistConfig::default()
+        })
+        .unwrap();
+        let denied = extended
+            .check("git push origin main --force")
+            .expect("force push should be denied");
+        assert_eq!(denied.rule, "git push --force");
+        assert_eq!(denied.reason, CUSTOM_RULE_REASON);
+        assert!(extended.check("git reset --hard").is_some());
+
+        let replaced = CommandDenylist::new(&BashDenylistConfig {
+            deny: vec!["terraform destroy".to_string()],
+            disable_defaults: true,
+            ..BashDenylistConfig::default()
+        })
+        .unwrap();
+        assert!(replaced.check("git reset --hard").is_none());
+        assert!(replaced.check(":(){ :|:& };:").is_none());
+        assert!(replaced.check("terraform destroy -auto-approve").is_some());
+    }
+
+    #[test]
+    fn blocks_commands_run_through_wrappers() {
+        let denylist = CommandDenylist::default();
+
+        for (command, rule) in [
+            ("sudo rm -r /", "rm -r /"),
+            ("sudo -u root -E rm -rf /", "rm -r /"),
+            ("env rm -r /", "rm -r /"),
+            ("env -i HOME=/tmp rm -r /", "rm -r /"),
+            ("env -S 'rm -r /'", "rm -r /"),
+            ("bash -c 'git reset --hard'", "git reset --hard"),
+            ("sh -ec 'cd repo && git reset --hard'", "git reset --hard"),
+            ("xargs rm -r /", "rm -r /"),
+            ("xargs -n 1 rm -r /", "rm -r /"),
+            ("command rm -r ~", "rm -r ~"),
+            ("nice -n 10 rm -r /", "rm -r /"),
+            ("timeout 5 git reset --hard", "git reset --hard"),
+            ("eval git reset --hard", "git reset --hard"),
+            (
+                "sudo env bash -c \"sudo git checkout -- .\"",
+                "git checkout --",
+            ),
+        ] {
+            assert_eq!(
+                denied_rule(&denylist, command).as_deref(),
+                Some(rule),
+                "{command}"
+            );
+        }
+    }
+
+    #[test]
+    fn blocks_recursive_deletes_of_root_contents() {
+        let denylist = CommandDenylist::default();
+
+        for (command, rule) in [
+            ("rm -rf /*", r"rm -r '/\*'"),
+            ("sudo rm --recursive --force /*", r"rm --recursive '/\*'"),
+            ("rm -Rf ~/*", r"rm -R '~/\*'"),
+        ] {
+            assert_eq!(
+                denied_rule(&denylist, command).as_deref(),
+                Some(rule),
+                "{command}"
+            );
+        }
+        assert_eq!(denied_rule(&denylist, "rm -rf /tmp/build/*"), None);
+        assert_eq!(denied_rule(&denylist, "rm -rf ./*"), None);
+    }
+
+    #[test]
+    fn allows_safe_commands_run_through_wrappers() {
+        let denylist = CommandDenylist::default();
+
+        for command in [
+            "sudo apt-get install ripgrep",
+            "env RUST_LOG=debug cargo test",
+            "bash -c 'git status'",
+            "find . -name '*.tmp' | xargs rm -f",
+            "command -v rm",
+        ] {
+            assert_eq!(denied_rule(&denylist, command), None, "{command}");
+        }
+    }
+
+    #[test]
+    fn refuses_commands_it_cannot_check() {
+        let denylist = CommandDenylist::default();
+        let deeply_wrapped = format!("{}true", "sudo ".repeat(MAX_WRAPPER_DEPTH + 2));
+
+        for command in [
+            "echo 'unterminated",
+            "bash -c \"rm -rf / 'oops\"",
+            deeply_wrapped.as_str(),
+        ] {
+            let denied = denylist
+                .check(command)
+                .unwrap_or_else(|| panic!("{command} should be refused"));
+            assert_eq!(denied.rule, UNCHECKED_RULE, "{command}");
+        }
+
+        let empty = CommandDenylist::new(&BashDenylistConfig {
+            disable_defaults: true,
+            ..BashDenylistConfig::default()
+        })
+        .unwrap();
+        assert_eq!(empty.check("echo 'unterminated"), None);
+    }
+
+    #[test]
+    fn invalid_rules_are_reported() {
+        for config in [
+            BashDenylistConfig {
+                deny: vec!["rm 'unterminated".to_string()],
+                ..BashDenylistConfig::default()
+            },
+            BashDenylistConfig {
+                allow: vec![String::new()],
+                ..BashDenylistConfig::default()
+            },
+        ] {
+            assert!(matches!(
+                CommandDenylist::new(&config),
+                Err(DenylistError::InvalidRule { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn unresolved_denylist_refuses_everything() {
+        let denylist = CommandDenylist::refusing_all(DenylistError::InvalidRule {
+            rule: "rm 'unterminated".to_string(),
+        });
+
+        let denied = denylist.check("ls").expect("ls should be refused");
+        assert_eq!(denied.rule, UNCHECKED_RULE);
+        assert!(denied.reason.contains("rm 'unterminated"));
+    }
+
+    #[test]
+    fn builtin_rules_all_parse() {
+        let denylist = CommandDenylist::default();
+
+        assert_eq!(denylist.deny.len(), DEFAULT_DENY_RULES.len());
+        assert_eq!(denylist.allow.len(), DEFAULT_ALLOW_RULES.len());
+    }
+}
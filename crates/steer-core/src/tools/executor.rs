@@ -1,19 +1,77 @@
+use crate::app::domain::session::ToolAuditStore;
 use crate::app::domain::types::{SessionId, ToolCallId};
 use crate::config::LlmConfigProvider;
 use crate::config::model::ModelId;
+use crate::metrics;
+use crate::profiling::{ProfilePhase, Profiler, time_phase};
 use crate::tools::error::Result;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio_util::sync::CancellationToken;
-use tracing::{Span, debug, error, instrument};
+use tracing::{Span, debug, error, instrument, warn};
 
 use crate::app::validation::{ValidationContext, ValidatorRegistry};
 use crate::tools::builtin_tool::{BuiltinToolContext, BuiltinToolError};
 use crate::tools::registry::ToolRegistry;
 use crate::tools::resolver::BackendResolver;
 use crate::tools::services::ToolServices;
-use crate::tools::{BackendRegistry, ExecutionContext};
+use crate::tools::{BackendRegistry, ExecutionContext, ToolEnv};
 use steer_tools::{ToolCall, ToolSchema, result::ToolResult};
 
+/// How idempotent builtin tools are retried after a transient failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToolRetryPolicy {
+    /// Retries after the first attempt; `0` disables retrying.
+    pub max_retries: u32,
+    /// Wait before the first retry, doubled for each one after it.
+    pub initial_backoff: Duration,
+}
+
+impl ToolRetryPolicy {
+    /// Two retries, after 250ms and then 500ms: enough to ride out a dropped
+    /// remote workspace connection without stalling the turn.
+    pub const fn standard() -> Self {
+        Self {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(250),
+        }
+    }
+
+    fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+    }
+
+    /// Run `attempt` until it succeeds, fails with a non-transient error, the
+    /// retries run out or `token` is cancelled. Returns the final result and
+    /// the number of retries made.
+    async fn run<T, F, Fut>(
+        &self,
+        token: &CancellationToken,
+        mut attempt: F,
+    ) -> (std::result::Result<T, steer_tools::ToolError>, u32)
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = std::result::Result<T, steer_tools::ToolError>>,
+    {
+        let mut retries = 0;
+        loop {
+            let result = attempt().await;
+            match &result {
+                Err(error) if error.is_transient() && retries < self.max_retries => {
+                    tokio::select! {
+                        () = token.cancelled() => return (result, retries),
+                        () = tokio::time::sleep(self.backoff(retries)) => {}
+                    }
+                    retries += 1;
+                }
+                _ => return (result, retries),
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct ToolExecutor {
     pub(crate) backend_registry: Arc<BackendRegistry>,
@@ -21,6 +79,9 @@ pub struct ToolExecutor {
     pub(crate) llm_config_provider: Option<LlmConfigProvider>,
     pub(crate) tool_registry: Option<Arc<ToolRegistry>>,
     pub(crate) tool_services: Option<Arc<ToolServices>>,
+    pub(crate) profiler: Option<Profiler>,
+    pub(crate) retry_policy: ToolRetryPolicy,
+    pub(crate) audit_log: Option<Arc<dyn ToolAuditStore>>,
 }
 
 impl ToolExecutor {
@@ -34,6 +95,9 @@ impl ToolExecutor {
             llm_config_provider: None,
             tool_registry: None,
             tool_services: None,
+            profiler: None,
+            retry_policy: ToolRetryPolicy::standard(),
+            audit_log: None,
         }
     }
 
@@ -48,6 +112,9 @@ impl ToolExecutor {
             llm_config_provider: Some(llm_config_provider),
             tool_registry: None,
             tool_services: None,
+            profiler: None,
+            retry_policy: ToolRetryPolicy::standard(),
+            audit_log: None,
         }
     }
 
@@ -61,6 +128,38 @@ impl ToolExecutor {
         self
     }
 
+    pub fn with_profiler(mut self, profiler: Profiler) -> Self {
+        self.profiler = Some(profiler);
+        self
+    }
+
+    pub fn with_retry_policy(mut self, policy: ToolRetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Record every tool a session runs in `store`.
+    pub fn with_audit_log(mut self, store: Arc<dyn ToolAuditStore>) -> Self {
+        self.audit_log = Some(store);
+        self
+    }
+
+    pub fn audit_log(&self) -> Option<&Arc<dyn ToolAuditStore>> {
+        self.audit_log.as_ref()
+    }
+
+    pub fn profiler(&self) -> Option<&Profiler> {
+        self.profiler.as_ref()
+    }
+
+    /// Whether `tool_name` is a builtin tool that only reads, so running it
+    /// again has no further effect. MCP and unknown tools are assumed not to be.
+    pub fn is_idempotent(&self, tool_name: &str) -> bool {
+        self.tool_registry
+            .as_ref()
+            .is_some_and(|registry| registry.idempotent(tool_name))
+    }
+
     pub async fn requires_approval(&self, tool_name: &str) -> Result<bool> {
         if let Some(registry) = &self.tool_registry
             && registry.is_builtin_tool(tool_name)
@@ -104,26 +203,28 @@ impl ToolExecutor {
         session_resolver: Option<&dyn BackendResolver>,
     ) -> Vec<ToolSchema> {
         let mut schemas = Vec::new();
-        let mut builtin_tool_names = std::collections::HashSet::new();
+        let mut seen_tool_names = std::collections::HashSet::new();
 
+        // Builtin schemas take precedence, so a backend that also exposes e.g.
+        // `fetch` never shadows the locally executed builtin.
         if let Some(registry) = &self.tool_registry {
-            let builtin_schemas = registry.available_schemas(capabilities).await;
-            for schema in &builtin_schemas {
-                builtin_tool_names.insert(schema.name.clone());
+            for schema in registry.available_schemas(capabilities).await {
+                if seen_tool_names.insert(schema.name.clone()) {
+                    schemas.push(schema);
+                }
             }
-            schemas.extend(builtin_schemas);
         }
 
         if let Some(resolver) = session_resolver {
             for schema in resolver.get_tool_schemas().await {
-                if !builtin_tool_names.contains(&schema.name) {
+                if seen_tool_names.insert(schema.name.clone()) {
                     schemas.push(schema);
                 }
             }
         }
 
         for schema in self.backend_registry.get_tool_schemas().await {
-            if !builtin_tool_names.contains(&schema.name) {
+            if seen_tool_names.insert(schema.name.clone()) {
                 schemas.push(schema);
             }
         }
@@ -154,6 +255,17 @@ impl ToolExecutor {
             .map(|services| services.workspace.clone())
     }
 
+    /// A copy of this executor whose builtin tools operate on `workspace`.
+    pub fn for_workspace(&self, workspace: Arc<dyn crate::workspace::Workspace>) -> Self {
+        Self {
+            tool_services: self
+                .tool_services
+                .as_ref()
+                .map(|services| Arc::new(services.for_workspace(workspace))),
+            ..self.clone()
+        }
+    }
+
     #[instrument(skip(self, tool_call, session_id, token), fields(tool.name = %tool_call.name, tool.id = %tool_call.id))]
     pub async fn execute_tool_with_session(
         &self,
@@ -161,16 +273,24 @@ impl ToolExecutor {
         session_id: SessionId,
         token: CancellationToken,
     ) -> std::result::Result<ToolResult, steer_tools::ToolError> {
-        self.execute_tool_with_session_resolver(tool_call, session_id, None, token, None)
-            .await
+        self.execute_tool_with_session_resolver(
+            tool_call,
+            session_id,
+            None,
+            Arc::default(),
+            token,
+            None,
+        )
+        .await
     }
 
-    #[instrument(skip(self, tool_call, session_id, invoking_model, token, session_resolver), fields(tool.name = %tool_call.name, tool.id = %tool_call.id))]
+    #[instrument(skip(self, tool_call, session_id, invoking_model, env, token, session_resolver), fields(tool.name = %tool_call.name, tool.id = %tool_call.id))]
     pub async fn execute_tool_with_session_resolver(
         &self,
         tool_call: &ToolCall,
         session_id: SessionId,
         invoking_model: Option<ModelId>,
+        env: Arc<ToolEnv>,
         token: CancellationToken,
         session_resolver: Option<&dyn BackendResolver>,
     ) -> std::result::Result<ToolResult, steer_tools::ToolError> {
@@ -180,10 +300,31 @@ impl ToolExecutor {
             self.tool_registry.as_ref().zip(self.tool_services.as_ref())
             && let Some(tool) = registry.builtin_tool(tool_name)
         {
+            // Builtin tools always run here, even for a remote workspace: file and
+            // shell tools reach it through `services.workspace`, and the rest only
+            // touch session state or the network.
             debug!(target: "tool_executor", "Executing builtin tool: {}", tool_name);
-            return self
-                .execute_builtin_tool(tool, tool_call, session_id, invoking_model, services, token)
-                .await;
+            return time_phase(
+                self.profiler.as_ref(),
+                ProfilePhase::ToolExecution,
+                || Some(tool_name.clone()),
+                metrics::time_tool(
+                    tool_name,
+                    self.execute_builtin_tool(
+                        tool,
+                        tool_call,
+                        BuiltinToolContext {
+                            tool_call_id: ToolCallId(tool_call.id.clone()),
+                            session_id,
+                            invoking_model,
+                            cancellation_token: token,
+                            services: services.clone(),
+                            env,
+                        },
+                    ),
+                ),
+            )
+            .await;
         }
 
         self.execute_tool_with_resolver(tool_call, token, session_resolver)
@@ -206,6 +347,24 @@ impl ToolExecutor {
         tool_call: &ToolCall,
         token: CancellationToken,
         session_resolver: Option<&dyn BackendResolver>,
+    ) -> std::result::Result<ToolResult, steer_tools::ToolError> {
+        time_phase(
+            self.profiler.as_ref(),
+            ProfilePhase::ToolExecution,
+            || Some(tool_call.name.clone()),
+            metrics::time_tool(
+                &tool_call.name,
+                self.execute_external_tool(tool_call, token, session_resolver),
+            ),
+        )
+        .await
+    }
+
+    async fn execute_external_tool(
+        &self,
+        tool_call: &ToolCall,
+        token: CancellationToken,
+        session_resolver: Option<&dyn BackendResolver>,
     ) -> std::result::Result<ToolResult, steer_tools::ToolError> {
         let tool_name = &tool_call.name;
         let tool_id = &tool_call.id;
@@ -274,21 +433,47 @@ impl ToolExecutor {
         &self,
         tool: &dyn super::builtin_tool::BuiltinToolErased,
         tool_call: &ToolCall,
-        session_id: SessionId,
-        invoking_model: Option<ModelId>,
-        services: &Arc<ToolServices>,
-        token: CancellationToken,
+        ctx: BuiltinToolContext,
     ) -> std::result::Result<ToolResult, steer_tools::ToolError> {
-        let ctx = BuiltinToolContext {
-            tool_call_id: ToolCallId(tool_call.id.clone()),
-            session_id,
-            invoking_model,
-            cancellation_token: token,
-            services: services.clone(),
-        };
+        if !tool.idempotent() {
+            return Self::execute_builtin_tool_once(tool, tool_call, &ctx).await;
+        }
 
+        let (mut result, retries) = self
+            .retry_policy
+            .run(&ctx.cancellation_token, || {
+                Self::execute_builtin_tool_once(tool, tool_call, &ctx)
+            })
+            .await;
+        if retries > 0 {
+            for _ in 0..retries {
+                metrics::record_tool_retry(&tool_call.name);
+            }
+            warn!(
+                target: "tool_executor",
+                tool_name = %tool_call.name,
+                tool_id = %tool_call.id,
+                retries,
+                succeeded = result.is_ok(),
+                "Retried idempotent tool after transient failure"
+            );
+            if let Ok(output) = &mut result {
+                let noun = if retries == 1 { "retry" } else { "retries" };
+                output.push_warning(format!(
+                    "succeeded after {retries} automatic {noun} following transient failures"
+                ));
+            }
+        }
+        result
+    }
+
+    async fn execute_builtin_tool_once(
+        tool: &dyn super::builtin_tool::BuiltinToolErased,
+        tool_call: &ToolCall,
+        ctx: &BuiltinToolContext,
+    ) -> std::result::Result<ToolResult, steer_tools::ToolError> {
         let output = tool
-            .execute_erased(tool_call.parameters.clone(), &ctx)
+            .execute_erased(tool_call.parameters.clone(), ctx)
             .await
             .map_err(|e| match e {
                 BuiltinToolError::InvalidParams(msg) => steer_tools::ToolError::InvalidParams {
@@ -334,7 +519,18 @@ impl ToolExecutor {
                 tool_id
             );
             return self
-                .execute_builtin_tool(tool, tool_call, SessionId::new(), None, services, token)
+                .execute_builtin_tool(
+                    tool,
+                    tool_call,
+                    BuiltinToolContext {
+                        tool_call_id: ToolCallId(tool_call.id.clone()),
+                        session_id: SessionId::new(),
+                        invoking_model: None,
+                        cancellation_token: token,
+                        services: services.clone(),
+                        env: Arc::default(),
+                    },
+                )
                 .await;
         }
 
@@ -378,3 +574,356 @@ impl ToolExecutor {
         backend.execute(tool_call, &context).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::validation::ValidatorRegistry;
+    use crate::tools::{BackendMetadata, ToolBackend};
+    use async_trait::async_trait;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use steer_tools::error::{ToolExecutionError, WorkspaceOpError};
+    use steer_tools::result::{ExternalResult, FileContentResult};
+    use steer_tools::tools::TODO_READ_TOOL_NAME;
+    use steer_tools::tools::read_file::{ReadFileError, ReadFileParams, ReadFileToolSpec};
+
+    struct EchoBackend;
+
+    #[async_trait]
+    impl ToolBackend for EchoBackend {
+        async fn execute(
+            &self,
+            tool_call: &ToolCall,
+            _context: &ExecutionContext,
+        ) -> std::result::Result<ToolResult, steer_tools::ToolError> {
+            Ok(ToolResult::External(ExternalResult {
+                tool_name: tool_call.name.clone(),
+                payload: "ok".to_string(),
+            }))
+        }
+
+        async fn supported_tools(&self) -> Vec<String> {
+            vec!["echo".to_string()]
+        }
+
+        async fn get_tool_schemas(&self) -> Vec<ToolSchema> {
+            Vec::new()
+        }
+
+        fn metadata(&self) -> BackendMetadata {
+            BackendMetadata::new("echo".to_string(), "Test".to_string())
+        }
+    }
+
+    /// A backend, like a remote workspace, that also exposes `todo_read`.
+    struct ShadowingBackend;
+
+    #[async_trait]
+    impl ToolBackend for ShadowingBackend {
+        async fn execute(
+            &self,
+            tool_call: &ToolCall,
+            _context: &ExecutionContext,
+        ) -> std::result::Result<ToolResult, steer_tools::ToolError> {
+            Ok(ToolResult::External(ExternalResult {
+                tool_name: tool_call.name.clone(),
+                payload: "from the backend".to_string(),
+            }))
+        }
+
+        async fn supported_tools(&self) -> Vec<String> {
+            vec![TODO_READ_TOOL_NAME.to_string()]
+        }
+
+        async fn get_tool_schemas(&self) -> Vec<ToolSchema> {
+            crate::tools::builtin_tool_schemas()
+                .into_iter()
+                .filter(|schema| schema.name == TODO_READ_TOOL_NAME)
+                .map(|schema| ToolSchema {
+                    description: "backend todo_read".to_string(),
+                    ..schema
+                })
+                .collect()
+        }
+
+        fn metadata(&self) -> BackendMetadata {
+            BackendMetadata::new("shadowing".to_string(), "Test".to_string())
+        }
+    }
+
+    fn unavailable() -> steer_tools::ToolError {
+        steer_tools::ToolError::Execution(ToolExecutionError::ReadFile(ReadFileError::Workspace(
+            WorkspaceOpError::Unavailable {
+                message: "connection reset".to_string(),
+            },
+        )))
+    }
+
+    const NO_BACKOFF: ToolRetryPolicy = ToolRetryPolicy {
+        max_retries: 2,
+        initial_backoff: Duration::ZERO,
+    };
+
+    #[tokio::test]
+    async fn retry_policy_retries_transient_errors_until_success() {
+        let mut attempts = 0;
+        let (result, retries) = NO_BACKOFF
+            .run(&CancellationToken::new(), || {
+                attempts += 1;
+                let attempt = attempts;
+                async move {
+                    if attempt < 3 {
+                        Err(unavailable())
+                    } else {
+                        Ok(attempt)
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(retries, 2);
+    }
+
+    #[tokio::test]
+    async fn retry_policy_gives_up_after_max_retries() {
+        let policy = ToolRetryPolicy {
+            max_retries: 1,
+            ..NO_BACKOFF
+        };
+        let mut attempts = 0;
+        let (result, retries) = policy
+            .run(&CancellationToken::new(), || {
+                attempts += 1;
+                async { Err::<(), _>(unavailable()) }
+            })
+            .await;
+
+        assert!(matches!(result, Err(steer_tools::ToolError::Execution(_))));
+        assert_eq!(retries, 1);
+        assert_eq!(attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn retry_policy_does_not_retry_permanent_errors() {
+        let mut attempts = 0;
+        let (result, retries) = NO_BACKOFF
+            .run(&CancellationToken::new(), || {
+                attempts += 1;
+                async { Err::<(), _>(steer_tools::ToolError::UnknownTool("x".to_string())) }
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(retries, 0);
+        assert_eq!(attempts, 1);
+    }
+
+    /// An idempotent tool whose first `failures` calls hit a dropped workspace.
+    struct FlakyReadTool {
+        failures: AtomicU32,
+    }
+
+    #[async_trait]
+    impl crate::tools::BuiltinTool for FlakyReadTool {
+        type Params = ReadFileParams;
+        type Output = FileContentResult;
+        type Spec = ReadFileToolSpec;
+
+        const DESCRIPTION: &'static str = "Fails transiently before succeeding";
+        const REQUIRES_APPROVAL: bool = false;
+        const REQUIRED_CAPABILITIES: crate::tools::Capabilities =
+            crate::tools::Capabilities::WORKSPACE;
+        const IDEMPOTENT: bool = true;
+
+        async fn execute(
+            &self,
+            params: Self::Params,
+            _ctx: &BuiltinToolContext,
+        ) -> std::result::Result<Self::Output, BuiltinToolError<ReadFileError>> {
+            if self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                return Err(BuiltinToolError::execution(ReadFileError::Workspace(
+                    WorkspaceOpError::Unavailable {
+                        message: "connection reset".to_string(),
+                    },
+                )));
+            }
+            Ok(FileContentResult {
+                content: "contents".to_string(),
+                file_path: params.file_path,
+                line_count: 1,
+                truncated: false,
+                encoding: None,
+                binary_kind: None,
+                file_size: None,
+                warnings: Vec::new(),
+            })
+        }
+    }
+
+    async fn builtin_context() -> BuiltinToolContext {
+        let model_registry = Arc::new(crate::model_registry::ModelRegistry::load(&[]).unwrap());
+        let provider_registry = Arc::new(crate::auth::ProviderRegistry::load(&[]).unwrap());
+        let api_client = Arc::new(crate::api::Client::new_with_deps(
+            crate::test_utils::test_llm_config_provider().unwrap(),
+            provider_registry,
+            model_registry,
+        ));
+        let workspace =
+            crate::workspace::create_workspace(&steer_workspace::WorkspaceConfig::Local {
+                path: std::env::temp_dir(),
+                context_limits: Default::default(),
+            })
+            .await
+            .unwrap();
+        let services = ToolServices::new(
+            workspace,
+            Arc::new(crate::app::domain::session::InMemoryEventStore::new()),
+            api_client,
+        );
+
+        BuiltinToolContext {
+            tool_call_id: ToolCallId::new(),
+            session_id: SessionId::new(),
+            invoking_model: None,
+            cancellation_token: CancellationToken::new(),
+            services: Arc::new(services),
+            env: Arc::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn retried_results_tell_the_model_how_many_retries_it_took() {
+        let mut executor = ToolExecutor::with_components(
+            Arc::new(BackendRegistry::new()),
+            Arc::new(ValidatorRegistry::new()),
+        );
+        executor.retry_policy = NO_BACKOFF;
+        let tool = FlakyReadTool {
+            failures: AtomicU32::new(2),
+        };
+        let call = ToolCall {
+            id: "tc_1".to_string(),
+            name: "read_file".to_string(),
+            parameters: serde_json::json!({ "file_path": "notes.txt" }),
+        };
+
+        let result = executor
+            .execute_builtin_tool(&tool, &call, builtin_context().await)
+            .await
+            .expect("third attempt should succeed");
+
+        assert_eq!(
+            result.warnings(),
+            ["succeeded after 2 automatic retries following transient failures"]
+        );
+        assert!(result.llm_format().ends_with(
+            "[Warning: succeeded after 2 automatic retries following transient failures]"
+        ));
+    }
+
+    #[tokio::test]
+    async fn builtin_tools_take_precedence_over_backends_with_the_same_name() {
+        let mut backends = BackendRegistry::new();
+        backends
+            .register("shadowing".to_string(), Arc::new(ShadowingBackend))
+            .await;
+        let mut registry = ToolRegistry::new();
+        registry.register_builtin(crate::tools::builtin_tools::TodoReadTool);
+        let executor =
+            ToolExecutor::with_components(Arc::new(backends), Arc::new(ValidatorRegistry::new()))
+                .with_builtin_tools(Arc::new(registry), builtin_context().await.services);
+
+        let todo_schemas: Vec<_> = executor
+            .get_tool_schemas()
+            .await
+            .into_iter()
+            .filter(|schema| schema.name == TODO_READ_TOOL_NAME)
+            .collect();
+        assert_eq!(todo_schemas.len(), 1);
+        assert_ne!(todo_schemas[0].description, "backend todo_read");
+
+        assert!(
+            !executor
+                .requires_approval(TODO_READ_TOOL_NAME)
+                .await
+                .unwrap()
+        );
+
+        let call = ToolCall {
+            id: "tc_1".to_string(),
+            name: TODO_READ_TOOL_NAME.to_string(),
+            parameters: serde_json::json!({}),
+        };
+        let result = executor
+            .execute_tool_with_session(&call, SessionId::new(), CancellationToken::new())
+            .await
+            .unwrap();
+        assert!(matches!(result, ToolResult::TodoRead(_)));
+    }
+
+    #[test]
+    fn only_read_only_builtin_tools_are_idempotent() {
+        use crate::tools::builtin_tool::BuiltinToolErased;
+        use crate::tools::builtin_tools::{
+            BashTool, EditTool, FetchTool, GlobTool, GrepTool, LsTool, ReadFileTool,
+        };
+
+        assert!(ReadFileTool.idempotent());
+        assert!(GrepTool.idempotent());
+        assert!(GlobTool.idempotent());
+        assert!(LsTool.idempotent());
+        assert!(FetchTool.idempotent());
+        assert!(!BashTool.idempotent());
+        assert!(!EditTool.idempotent());
+    }
+
+    #[test]
+    fn executing_a_tool_increments_its_counter() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+
+        ::metrics::with_local_recorder(&recorder, || {
+            futures::executor::block_on(async {
+                let mut backends = BackendRegistry::new();
+                backends
+                    .register("echo".to_string(), Arc::new(EchoBackend))
+                    .await;
+                let executor = ToolExecutor::with_components(
+                    Arc::new(backends),
+                    Arc::new(ValidatorRegistry::new()),
+                );
+                for id in ["tc_1", "tc_2"] {
+                    let call = ToolCall {
+                        id: id.to_string(),
+                        name: "echo".to_string(),
+                        parameters: serde_json::json!({}),
+                    };
+                    executor
+                        .execute_tool_with_cancellation(&call, CancellationToken::new())
+                        .await
+                        .unwrap();
+                }
+            });
+        });
+
+        let snapshot = snapshotter.snapshot().into_vec();
+        let count = snapshot.iter().find_map(|(key, _, _, value)| {
+            let key = key.key();
+            let is_echo = key.name() == crate::metrics::TOOL_EXECUTIONS_TOTAL
+                && key
+                    .labels()
+                    .any(|label| label.key() == "tool" && label.value() == "echo");
+            match value {
+                DebugValue::Counter(count) if is_echo => Some(*count),
+                _ => None,
+            }
+        });
+        assert_eq!(count, Some(2));
+    }
+}
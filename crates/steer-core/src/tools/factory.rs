@@ -1,9 +1,10 @@
 use std::sync::Arc;
 
 use crate::api::Client as ApiClient;
-use crate::app::domain::session::EventStore;
+use crate::app::domain::session::{EventStore, ToolAuditStore};
 use crate::app::validation::ValidatorRegistry;
 use crate::model_registry::ModelRegistry;
+use crate::profiling::Profiler;
 use crate::workspace::{RepoManager, Workspace, WorkspaceManager};
 
 use super::BackendRegistry;
@@ -23,6 +24,9 @@ pub struct ToolSystemBuilder {
     validators: Arc<ValidatorRegistry>,
     workspace_manager: Option<Arc<dyn WorkspaceManager>>,
     repo_manager: Option<Arc<dyn RepoManager>>,
+    allow_outside_workspace: bool,
+    profiler: Option<Profiler>,
+    audit_log: Option<Arc<dyn ToolAuditStore>>,
 }
 
 impl ToolSystemBuilder {
@@ -41,6 +45,9 @@ impl ToolSystemBuilder {
             validators: Arc::new(ValidatorRegistry::new()),
             workspace_manager: None,
             repo_manager: None,
+            allow_outside_workspace: false,
+            profiler: None,
+            audit_log: None,
         }
     }
 
@@ -64,8 +71,30 @@ impl ToolSystemBuilder {
         self
     }
 
+    pub fn with_allow_outside_workspace(mut self, allow: bool) -> Self {
+        self.allow_outside_workspace = allow;
+        self
+    }
+
+    pub fn with_profiler(mut self, profiler: Profiler) -> Self {
+        self.profiler = Some(profiler);
+        self
+    }
+
+    pub fn with_audit_log(mut self, store: Arc<dyn ToolAuditStore>) -> Self {
+        self.audit_log = Some(store);
+        self
+    }
+
     pub fn build(self) -> Arc<ToolExecutor> {
-        let base_executor = ToolExecutor::with_components(self.backend_registry, self.validators);
+        let mut base_executor =
+            ToolExecutor::with_components(self.backend_registry, self.validators);
+        if let Some(profiler) = self.profiler {
+            base_executor = base_executor.with_profiler(profiler);
+        }
+        if let Some(store) = self.audit_log {
+            base_executor = base_executor.with_audit_log(store);
+        }
 
         let agent_spawner = Arc::new(DefaultAgentSpawner::new(
             self.event_store.clone(),
@@ -82,7 +111,8 @@ impl ToolSystemBuilder {
             ToolServices::new(self.workspace.clone(), self.event_store, self.api_client)
                 .with_agent_spawner(agent_spawner)
                 .with_model_caller(model_caller)
-                .with_network();
+                .with_network()
+                .with_allow_outside_workspace(self.allow_outside_workspace);
         if let Some(manager) = self.workspace_manager {
             services = services.with_workspace_manager(manager);
         }
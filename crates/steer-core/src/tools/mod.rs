@@ -3,6 +3,8 @@ pub mod backend;
 pub mod builtin_tool;
 pub mod builtin_tools;
 pub mod capability;
+pub mod denylist;
+pub mod env;
 pub mod error;
 pub mod execution_context;
 pub mod executor;
@@ -16,10 +18,13 @@ pub mod services;
 pub use agent_spawner_impl::DefaultAgentSpawner;
 pub use backend::{BackendMetadata, BackendRegistry, ToolBackend};
 pub use builtin_tool::{BuiltinTool, BuiltinToolContext, BuiltinToolErased, BuiltinToolError};
+pub use builtin_tools::builtin_tool_schemas;
 pub use capability::Capabilities;
+pub use denylist::{CommandDenylist, DeniedCommand, DenylistError};
+pub use env::ToolEnv;
 pub use error::ToolError;
 pub use execution_context::ExecutionContext;
-pub use executor::ToolExecutor;
+pub use executor::{ToolExecutor, ToolRetryPolicy};
 pub use mcp::{McpBackend, McpError, McpTransport};
 pub use model_caller_impl::DefaultModelCaller;
 pub use registry::ToolRegistry;
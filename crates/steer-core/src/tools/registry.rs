@@ -60,6 +60,13 @@ impl ToolRegistry {
         self.builtin_tools.contains_key(name)
     }
 
+    pub fn builtin_schemas(&self) -> Vec<ToolSchema> {
+        self.builtin_tools
+            .values()
+            .map(|tool| tool.schema())
+            .collect()
+    }
+
     pub fn builtin_tool_names(&self) -> Vec<&str> {
         self.builtin_tools.keys().map(|s| s.as_str()).collect()
     }
@@ -71,6 +78,12 @@ impl ToolRegistry {
         true
     }
 
+    pub fn idempotent(&self, tool_name: &str) -> bool {
+        self.builtin_tools
+            .get(tool_name)
+            .is_some_and(|tool| tool.idempotent())
+    }
+
     pub fn required_capabilities(&self, tool_name: &str) -> Option<Capabilities> {
         self.builtin_tools
             .get(tool_name)
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -9,7 +10,7 @@ use crate::app::conversation::Message;
 use crate::app::domain::session::EventStore;
 use crate::app::domain::types::SessionId;
 use crate::config::model::ModelId;
-use crate::session::state::BackendConfig;
+use crate::session::state::{BackendConfig, BashDenylistConfig};
 use crate::workspace::{
     RepoManager, RepoRef, Workspace, WorkspaceId, WorkspaceManager, WorkspaceRef,
 };
@@ -40,6 +41,14 @@ pub struct SubAgentConfig {
     pub workspace_name: Option<String>,
     pub mcp_backends: Vec<BackendConfig>,
     pub allow_mcp_tools: bool,
+    /// Session environment variables inherited from the parent session.
+    pub env: HashMap<String, String>,
+    /// Bash denylist inherited from the parent session.
+    pub bash_denylist: BashDenylistConfig,
+    /// Network policy inherited from the parent session.
+    pub allow_network: bool,
+    /// Workspace boundary policy inherited from the parent session.
+    pub allow_outside_workspace: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -94,6 +103,7 @@ pub struct ToolServices {
     repo_manager: Option<Arc<dyn RepoManager>>,
 
     available_capabilities: Capabilities,
+    allow_outside_workspace: bool,
 }
 
 impl ToolServices {
@@ -111,6 +121,7 @@ impl ToolServices {
             workspace_manager: None,
             repo_manager: None,
             available_capabilities: Capabilities::WORKSPACE,
+            allow_outside_workspace: false,
         }
     }
 
@@ -141,10 +152,35 @@ impl ToolServices {
         self
     }
 
+    /// Lets file tools and bash operate on paths outside the workspace root.
+    pub fn with_allow_outside_workspace(mut self, allow: bool) -> Self {
+        self.allow_outside_workspace = allow;
+        self
+    }
+
+    /// The same services operating on a different workspace.
+    pub fn for_workspace(&self, workspace: Arc<dyn Workspace>) -> Self {
+        Self {
+            workspace,
+            event_store: self.event_store.clone(),
+            api_client: self.api_client.clone(),
+            agent_spawner: self.agent_spawner.clone(),
+            model_caller: self.model_caller.clone(),
+            workspace_manager: self.workspace_manager.clone(),
+            repo_manager: self.repo_manager.clone(),
+            available_capabilities: self.available_capabilities,
+            allow_outside_workspace: self.allow_outside_workspace,
+        }
+    }
+
     pub fn capabilities(&self) -> Capabilities {
         self.available_capabilities
     }
 
+    pub fn allow_outside_workspace(&self) -> bool {
+        self.allow_outside_workspace
+    }
+
     pub fn agent_spawner(&self) -> Option<&Arc<dyn AgentSpawner>> {
         self.agent_spawner.as_ref()
     }
@@ -166,6 +202,7 @@ impl std::fmt::Debug for ToolServices {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ToolServices")
             .field("capabilities", &self.available_capabilities)
+            .field("allow_outside_workspace", &self.allow_outside_workspace)
             .finish_non_exhaustive()
     }
 }
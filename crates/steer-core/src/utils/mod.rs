@@ -1,5 +1,6 @@
 pub mod paths;
 pub mod session;
+pub mod tokens;
 pub mod tracing;
 
 use std::path::Path;
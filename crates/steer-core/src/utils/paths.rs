@@ -43,6 +43,26 @@ impl AppPaths {
         PathBuf::from(".steer/envs/local")
     }
 
+    /// Return the root for isolated session worktrees: ~/.steer/worktrees.
+    /// Can be overridden with STEER_WORKTREES_ROOT.
+    pub fn worktrees_root() -> PathBuf {
+        if let Ok(root) = std::env::var("STEER_WORKTREES_ROOT") {
+            return PathBuf::from(root);
+        }
+        if let Some(home_dir) = dirs::home_dir() {
+            return home_dir.join(".steer").join("worktrees");
+        }
+        PathBuf::from(".steer/worktrees")
+    }
+
+    /// Return the default model response cache directory: ~/.steer/cache.
+    pub fn response_cache_dir() -> PathBuf {
+        if let Some(home_dir) = dirs::home_dir() {
+            return home_dir.join(".steer").join("cache");
+        }
+        PathBuf::from(".steer/cache")
+    }
+
     /// Return the user-level catalog path (platform-specific)
     pub fn user_catalog() -> Option<PathBuf> {
         Self::user_config_dir().map(|d| d.join("catalog.toml"))
@@ -0,0 +1,318 @@
+//! Token estimates, shared by auto-compaction, the context inspector and the
+//! context gauge so all of them report the same numbers.
+
+use std::borrow::Cow;
+
+use tiktoken_rs::tokenizer::{Tokenizer, get_tokenizer};
+
+use steer_tools::ToolSchema;
+
+use crate::app::SystemContext;
+use crate::app::conversation::{AssistantContent, Message, MessageData, UserContent};
+use crate::config::model::ModelId;
+use crate::config::provider;
+
+/// Rough characters-per-token ratio for English prose and source code.
+pub const ESTIMATED_CHARS_PER_TOKEN: f64 = 4.0;
+
+/// Estimates the tokens needed for `chars` characters, rounding up.
+pub fn estimate_tokens_for_chars(chars: usize) -> u32 {
+    ((chars as f64) / ESTIMATED_CHARS_PER_TOKEN).ceil() as u32
+}
+
+/// Estimates the tokens needed to send `text`.
+pub fn estimate_tokens(text: &str) -> u32 {
+    estimate_tokens_for_chars(text.chars().count())
+}
+
+/// Estimates the tokens a message adds to a request.
+pub fn estimate_message_tokens(message: &Message) -> u32 {
+    TokenCounter::Heuristic.count_message(message)
+}
+
+/// How text is turned into a token count for a given model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenCounter {
+    /// OpenAI's `o200k_base` encoding (GPT-4o and later).
+    O200kBase,
+    /// OpenAI's `cl100k_base` encoding (GPT-4, GPT-3.5).
+    Cl100kBase,
+    /// Characters divided by [`ESTIMATED_CHARS_PER_TOKEN`], for providers
+    /// whose tokenizers are not public.
+    Heuristic,
+}
+
+impl TokenCounter {
+    pub fn for_model(model: &ModelId) -> Self {
+        if model.provider.as_str() != provider::OPENAI_ID {
+            return Self::Heuristic;
+        }
+        match get_tokenizer(&model.id) {
+            Some(Tokenizer::Cl100kBase) => Self::Cl100kBase,
+            _ => Self::O200kBase,
+        }
+    }
+
+    pub fn count(self, text: &str) -> u32 {
+        match self {
+            Self::O200kBase => tiktoken_rs::o200k_base_singleton()
+                .encode_with_special_tokens(text)
+                .len() as u32,
+            Self::Cl100kBase => tiktoken_rs::cl100k_base_singleton()
+                .encode_with_special_tokens(text)
+                .len() as u32,
+            Self::Heuristic => estimate_tokens(text),
+        }
+    }
+
+    pub fn count_message(self, message: &Message) -> u32 {
+        let parts = message_text_parts(message);
+        match self {
+            // Round once per message so short blocks don't each cost a token.
+            Self::Heuristic => {
+                estimate_tokens_for_chars(parts.iter().map(|part| part.chars().count()).sum())
+            }
+            _ => parts
+                .iter()
+                .fold(0u32, |total, part| total.saturating_add(self.count(part))),
+        }
+    }
+}
+
+/// Estimates the input tokens of a whole request: the messages, the rendered
+/// system prompt and each tool's name, description and input schema.
+pub fn estimate_request_tokens(
+    counter: TokenCounter,
+    messages: &[Message],
+    system: Option<&SystemContext>,
+    tools: &[ToolSchema],
+) -> u32 {
+    let messages = messages.iter().fold(0u32, |total, message| {
+        total.saturating_add(counter.count_message(message))
+    });
+    let system = system
+        .and_then(SystemContext::render)
+        .map_or(0, |text| counter.count(&text));
+    let tools = tools.iter().fold(0u32, |total, tool| {
+        let schema = serde_json::to_string(&tool.input_schema).unwrap_or_default();
+        total
+            .saturating_add(counter.count(&tool.name))
+            .saturating_add(counter.count(&tool.description))
+            .saturating_add(counter.count(&schema))
+    });
+    messages.saturating_add(system).saturating_add(tools)
+}
+
+/// Smallest and largest correction a provider count may apply to estimates.
+const MIN_CALIBRATION_RATIO: f64 = 0.25;
+const MAX_CALIBRATION_RATIO: f64 = 4.0;
+
+/// How far an estimate may drift from the calibrated one, as a fraction,
+/// before the request is worth counting again.
+const CALIBRATION_REFRESH_DRIFT: f64 = 0.2;
+
+/// The ratio between a provider's exact token count for a request and our
+/// estimate of the same request, used to correct later estimates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenCalibration {
+    ratio: f64,
+    estimated: u32,
+}
+
+impl TokenCalibration {
+    /// Returns `None` when there is no estimate to compare against.
+    pub fn new(counted: u32, estimated: u32) -> Option<Self> {
+        if estimated == 0 || counted == 0 {
+            return None;
+        }
+        let ratio = (f64::from(counted) / f64::from(estimated))
+            .clamp(MIN_CALIBRATION_RATIO, MAX_CALIBRATION_RATIO);
+        Some(Self { ratio, estimated })
+    }
+
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// Scales an estimate by the calibrated ratio, rounding up.
+    pub fn apply(&self, estimate: u32) -> u32 {
+        (f64::from(estimate) * self.ratio).ceil() as u32
+    }
+
+    /// Whether the conversation has changed enough since this calibration was
+    /// taken that the ratio may no longer hold.
+    pub fn needs_refresh(&self, estimate: u32) -> bool {
+        let drift = (f64::from(estimate) - f64::from(self.estimated)).abs();
+        drift >= f64::from(self.estimated) * CALIBRATION_REFRESH_DRIFT
+    }
+}
+
+/// Applies `calibration`, if any, to an estimate.
+pub fn calibrated(estimate: u32, calibration: Option<&TokenCalibration>) -> u32 {
+    calibration.map_or(estimate, |calibration| calibration.apply(estimate))
+}
+
+/// The text of each block of `message` that is sent to the model.
+fn message_text_parts(message: &Message) -> Vec<Cow<'_, str>> {
+    match &message.data {
+        MessageData::Tool { result, .. } => vec![Cow::Owned(result.llm_format())],
+        MessageData::Assistant { content } => content
+            .iter()
+            .flat_map(|block| match block {
+                AssistantContent::Text { text } => vec![Cow::Borrowed(text.as_str())],
+                AssistantContent::Image { image } => vec![Cow::Borrowed(image.mime_type.as_str())],
+                AssistantContent::ToolCall { tool_call, .. } => vec![
+                    Cow::Borrowed(tool_call.name.as_str()),
+                    Cow::Owned(tool_call.parameters.to_string()),
+                ],
+                AssistantContent::Thought { thought } => vec![Cow::Owned(thought.display_text())],
+            })
+            .collect(),
+        MessageData::User { content } => content
+            .iter()
+            .flat_map(|block| match block {
+                UserContent::Text { text } => vec![Cow::Borrowed(text.as_str())],
+                UserContent::Image { image } => vec![Cow::Borrowed(image.mime_type.as_str())],
+                UserContent::Attachment { attachment } => {
+                    vec![Cow::Owned(attachment.prompt_text())]
+                }
+                UserContent::CommandExecution {
+                    command,
+                    stdout,
+                    stderr,
+                    ..
+                } => vec![
+                    Cow::Borrowed(command.as_str()),
+                    Cow::Borrowed(stdout.as_str()),
+                    Cow::Borrowed(stderr.as_str()),
+                ],
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_round_up_per_four_characters() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("a"), 1);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("hello world"), 3);
+        assert_eq!(estimate_tokens("fn main() {}\n"), 4);
+    }
+
+    #[test]
+    fn estimates_count_characters_not_bytes() {
+        // 5 characters, 15 bytes.
+        assert_eq!(estimate_tokens("日本語です"), 2);
+    }
+
+    #[test]
+    fn message_estimate_sums_blocks_before_rounding() {
+        let message = Message {
+            data: MessageData::User {
+                content: vec![
+                    UserContent::Text {
+                        text: "ab".to_string(),
+                    },
+                    UserContent::Text {
+                        text: "cd".to_string(),
+                    },
+                ],
+            },
+            timestamp: 0,
+            id: "user_1".to_string(),
+            parent_message_id: None,
+        };
+        assert_eq!(estimate_message_tokens(&message), 1);
+    }
+
+    #[test]
+    fn openai_models_use_their_tiktoken_encoding() {
+        let gpt4 = ModelId::new(provider::openai(), "gpt-4");
+        let gpt5 = ModelId::new(provider::openai(), "gpt-5");
+        let claude = ModelId::new(provider::anthropic(), "claude-sonnet-4-5");
+
+        assert_eq!(TokenCounter::for_model(&gpt4), TokenCounter::Cl100kBase);
+        assert_eq!(TokenCounter::for_model(&gpt5), TokenCounter::O200kBase);
+        assert_eq!(TokenCounter::for_model(&claude), TokenCounter::Heuristic);
+        assert_eq!(TokenCounter::O200kBase.count("hello world"), 2);
+    }
+
+    #[test]
+    fn calibration_scales_estimates_by_counted_ratio() {
+        let calibration = TokenCalibration::new(150, 100).expect("calibration");
+
+        assert!((calibration.ratio() - 1.5).abs() < f64::EPSILON);
+        assert_eq!(calibration.apply(10), 15);
+        assert_eq!(calibration.apply(3), 5);
+        assert_eq!(calibrated(10, Some(&calibration)), 15);
+        assert_eq!(calibrated(10, None), 10);
+    }
+
+    #[test]
+    fn calibration_clamps_outlandish_ratios() {
+        let calibration = TokenCalibration::new(10_000, 10).expect("calibration");
+        assert!((calibration.ratio() - MAX_CALIBRATION_RATIO).abs() < f64::EPSILON);
+
+        assert!(TokenCalibration::new(10, 0).is_none());
+        assert!(TokenCalibration::new(0, 10).is_none());
+    }
+
+    #[test]
+    fn calibration_refreshes_after_the_estimate_drifts() {
+        let calibration = TokenCalibration::new(120, 100).expect("calibration");
+
+        assert!(!calibration.needs_refresh(100));
+        assert!(!calibration.needs_refresh(119));
+        assert!(calibration.needs_refresh(120));
+        assert!(calibration.needs_refresh(80));
+    }
+
+    #[test]
+    fn request_estimate_includes_system_prompt_and_tools() {
+        let message = Message {
+            data: MessageData::User {
+                content: vec![UserContent::Text {
+                    text: "abcd".to_string(),
+                }],
+            },
+            timestamp: 0,
+            id: "user_1".to_string(),
+            parent_message_id: None,
+        };
+        let system = SystemContext::new("abcdefgh".to_string());
+        let tool = ToolSchema {
+            name: "abcd".to_string(),
+            display_name: String::new(),
+            description: "abcd".to_string(),
+            input_schema: steer_tools::InputSchema::empty_object(),
+        };
+
+        let messages_only = estimate_request_tokens(
+            TokenCounter::Heuristic,
+            std::slice::from_ref(&message),
+            None,
+            &[],
+        );
+        let with_system = estimate_request_tokens(
+            TokenCounter::Heuristic,
+            std::slice::from_ref(&message),
+            Some(&system),
+            &[],
+        );
+        let with_tools = estimate_request_tokens(
+            TokenCounter::Heuristic,
+            &[message],
+            Some(&system),
+            std::slice::from_ref(&tool),
+        );
+
+        assert_eq!(messages_only, 1);
+        assert_eq!(with_system, 3);
+        assert!(with_tools > with_system + 2);
+    }
+}
@@ -1,19 +1,47 @@
 use chrono::Local;
 use dirs;
+use serde::Deserialize;
 use std::io;
+use std::str::FromStr;
 use tracing_appender::rolling::{self};
 use tracing_subscriber::{
-    EnvFilter,
+    EnvFilter, Layer, Registry,
     fmt::{self, format::FmtSpan},
     prelude::*,
 };
 
+/// Format of log lines written by [`init_tracing`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "unknown log format '{other}' (expected 'text' or 'json')"
+            )),
+        }
+    }
+}
+
 /// Initialize the tracing system with either stdout or file logging.
 ///
 /// Configuration behavior:
 /// - In normal operation: Logs to file in ~/.steer directory
 /// - Logging level is controlled by the RUST_LOG environment variable
-pub fn init_tracing() -> io::Result<()> {
+/// - `format` picks plain text or JSON lines
+pub fn init_tracing(format: LogFormat) -> io::Result<()> {
     // Default log file in the user's home directory with timestamp
     let now = Local::now();
     let timestamp = now.format("%Y%m%d_%H%M%S");
@@ -33,16 +61,17 @@ pub fn init_tracing() -> io::Result<()> {
         // Create the file appender directly (synchronous writing)
         let file_appender = rolling::never(log_dir.clone(), format!("{timestamp}.log"));
 
-        let subscriber = tracing_subscriber::registry()
-            .with(
-                fmt::Layer::new()
-                    .with_writer(file_appender)
-                    .with_ansi(false)
-                    .with_span_events(FmtSpan::CLOSE)
-                    .with_file(true)
-                    .with_line_number(true),
-            )
-            .with(filter);
+        let layer = fmt::Layer::new()
+            .with_writer(file_appender)
+            .with_ansi(false)
+            .with_span_events(FmtSpan::CLOSE)
+            .with_file(true)
+            .with_line_number(true);
+        let layer: Box<dyn Layer<Registry> + Send + Sync> = match format {
+            LogFormat::Text => layer.boxed(),
+            LogFormat::Json => layer.json().boxed(),
+        };
+        let subscriber = tracing_subscriber::registry().with(layer).with(filter);
 
         tracing::subscriber::set_global_default(subscriber).map_err(io::Error::other)?;
 
@@ -53,9 +82,12 @@ pub fn init_tracing() -> io::Result<()> {
         );
     } else {
         // Fallback to stdout if home directory not available
-        let subscriber = tracing_subscriber::registry()
-            .with(fmt::Layer::default().with_ansi(true).with_target(true))
-            .with(filter);
+        let layer = fmt::Layer::default().with_ansi(true).with_target(true);
+        let layer: Box<dyn Layer<Registry> + Send + Sync> = match format {
+            LogFormat::Text => layer.boxed(),
+            LogFormat::Json => layer.json().boxed(),
+        };
+        let subscriber = tracing_subscriber::registry().with(layer).with(filter);
 
         tracing::subscriber::set_global_default(subscriber).map_err(io::Error::other)?;
 
@@ -15,10 +15,14 @@ pub async fn create_workspace(
     config: &steer_workspace::WorkspaceConfig,
 ) -> Result<Arc<dyn Workspace>> {
     match config {
-        steer_workspace::WorkspaceConfig::Local { path } => {
+        steer_workspace::WorkspaceConfig::Local {
+            path,
+            context_limits,
+        } => {
             let workspace = steer_workspace::local::LocalWorkspace::with_path(path.clone())
                 .await
-                .map_err(|e| WorkspaceError::NotSupported(e.to_string()))?;
+                .map_err(|e| WorkspaceError::NotSupported(e.to_string()))?
+                .with_context_limits(*context_limits);
             Ok(Arc::new(workspace))
         }
         steer_workspace::WorkspaceConfig::Remote { address, auth } => {
@@ -39,9 +43,10 @@ pub async fn create_workspace_from_session_config(
     use steer_workspace::WorkspaceConfig as WsConfig;
 
     let ws_config = match config {
-        crate::session::state::WorkspaceConfig::Local { path } => {
-            WsConfig::Local { path: path.clone() }
-        }
+        crate::session::state::WorkspaceConfig::Local { path } => WsConfig::Local {
+            path: path.clone(),
+            context_limits: Default::default(),
+        },
         crate::session::state::WorkspaceConfig::Remote {
             agent_address,
             auth,
@@ -355,6 +355,10 @@ async fn run_api_with_pruned_duplicate_read_file_history(
                     file_path: "/tmp/pruned-config.toml".to_string(),
                     line_count: 3,
                     truncated: false,
+                    encoding: None,
+                    binary_kind: None,
+                    file_size: None,
+                    warnings: Vec::new(),
                 }),
             },
             timestamp: ts3,
@@ -440,6 +444,7 @@ async fn run_api_with_pruned_pre_edit_read_history(
                     file_created: false,
                     old_content: None,
                     new_content: None,
+                    skipped_edits: Vec::new(),
                 }),
             },
             timestamp: ts3,
@@ -473,6 +478,10 @@ async fn run_api_with_pruned_pre_edit_read_history(
                     file_path: "/tmp/pruned-greeting.rs".to_string(),
                     line_count: 1,
                     truncated: false,
+                    encoding: None,
+                    binary_kind: None,
+                    file_size: None,
+                    warnings: Vec::new(),
                 }),
             },
             timestamp: ts5,
@@ -567,6 +576,10 @@ async fn run_api_with_pruned_assistant_text_and_tool_history(
                     file_path: "/tmp/notes.md".to_string(),
                     line_count: 1,
                     truncated: false,
+                    encoding: None,
+                    binary_kind: None,
+                    file_size: None,
+                    warnings: Vec::new(),
                 }),
             },
             timestamp: ts4,
@@ -859,7 +872,9 @@ async fn run_api_with_dispatch_agent_tool_call(
     assert_eq!(params.prompt, "find files");
 
     match params.target {
-        DispatchAgentTarget::New { workspace, agent } => {
+        DispatchAgentTarget::New {
+            workspace, agent, ..
+        } => {
             assert_eq!(workspace, WorkspaceTarget::Current);
             assert!(
                 agent == Some("explore".to_string()) || agent.is_none(),
@@ -5,13 +5,14 @@ use steer_core::config::provider;
 fn parses_default_providers() {
     let registry = ProviderRegistry::load(&[]).expect("load provider registry");
     let providers: Vec<_> = registry.all().cloned().collect();
-    assert_eq!(providers.len(), 4);
+    assert_eq!(providers.len(), 5);
 
     let ids: Vec<_> = providers.iter().map(|p| &p.id).collect::<Vec<_>>();
     assert!(ids.iter().any(|id| **id == provider::anthropic()));
     assert!(ids.iter().any(|id| **id == provider::openai()));
     assert!(ids.iter().any(|id| **id == provider::google()));
     assert!(ids.iter().any(|id| **id == provider::xai()));
+    assert!(ids.iter().any(|id| **id == provider::mock()));
 
     let anthro = providers
         .iter()
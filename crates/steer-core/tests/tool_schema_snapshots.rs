@@ -0,0 +1,26 @@
+//! Snapshot tests for builtin tool schemas.
+//!
+//! Each builtin tool's name, description, and input schema is compared against a
+//! checked-in JSON snapshot so unintended changes to what the model sees fail CI.
+//! Run with `UPDATE_TOOL_SCHEMA_SNAPSHOTS=1` to rewrite the snapshots after an
+//! intentional change.
+
+steer_core::tool_schema_snapshot_tests! {
+    snapshot_dir: std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots/tool_schemas");
+    ast_grep_schema => steer_tools::tools::AST_GREP_TOOL_NAME,
+    bash_schema => steer_tools::tools::BASH_TOOL_NAME,
+    dispatch_agent_schema => steer_tools::tools::DISPATCH_AGENT_TOOL_NAME,
+    edit_schema => steer_tools::tools::EDIT_TOOL_NAME,
+    fetch_schema => steer_tools::tools::FETCH_TOOL_NAME,
+    forget_schema => steer_tools::tools::FORGET_TOOL_NAME,
+    git_status_schema => steer_tools::tools::GIT_STATUS_TOOL_NAME,
+    glob_schema => steer_tools::tools::GLOB_TOOL_NAME,
+    grep_schema => steer_tools::tools::GREP_TOOL_NAME,
+    ls_schema => steer_tools::tools::LS_TOOL_NAME,
+    multi_edit_schema => steer_tools::tools::MULTI_EDIT_TOOL_NAME,
+    read_file_schema => steer_tools::tools::READ_FILE_TOOL_NAME,
+    remember_schema => steer_tools::tools::REMEMBER_TOOL_NAME,
+    replace_schema => steer_tools::tools::REPLACE_TOOL_NAME,
+    todo_read_schema => steer_tools::tools::TODO_READ_TOOL_NAME,
+    todo_write_schema => steer_tools::tools::TODO_WRITE_TOOL_NAME,
+}
@@ -0,0 +1,70 @@
+//! Drive a headless agent from Rust.
+//!
+//! Runs one prompt against the current directory and prints the answer:
+//!
+//! ```bash
+//! cargo run -p steer-grpc --example headless_agent -- "Summarize the README"
+//! # or against a running `steer server`
+//! cargo run -p steer-grpc --example headless_agent -- --remote http://127.0.0.1:50051 "..."
+//! ```
+//!
+//! The model may read the workspace freely; any other tool call is denied.
+
+use std::io::Write;
+
+use steer_grpc::AgentClient;
+use steer_grpc::client_api::{ApprovalDecision, CreateSessionParams, ToolCall, builtin};
+
+const READ_ONLY_TOOLS: [&str; 4] = ["read_file", "grep", "glob", "ls"];
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1).peekable();
+    let remote = if args.peek().map(String::as_str) == Some("--remote") {
+        args.next();
+        Some(args.next().ok_or("--remote needs an address")?)
+    } else {
+        None
+    };
+    let prompt = args.collect::<Vec<_>>().join(" ");
+    if prompt.is_empty() {
+        return Err("usage: headless_agent [--remote ADDR] <prompt>".into());
+    }
+
+    let model = builtin::claude_sonnet_4_5();
+    let client = match remote {
+        Some(addr) => AgentClient::connect(&addr).await?,
+        None => AgentClient::local(model.clone()).await?,
+    };
+
+    client
+        .create_session(
+            CreateSessionParams::builder(model.clone())
+                .workspace_path(std::env::current_dir()?)
+                .preapprove_tools(READ_ONLY_TOOLS)
+                .metadata("origin", "headless_agent example")
+                .build(),
+        )
+        .await?;
+    let mut events = client.event_stream().await?;
+
+    let mut stderr = std::io::stderr();
+    let mut approvals = |tool_call: &ToolCall| {
+        let _ = writeln!(stderr, "denying {}", tool_call.name);
+        ApprovalDecision::DenyWithReason("This agent only reads the workspace".to_string())
+    };
+    let outcome = client
+        .send_message_and_wait(&mut events, prompt, model, &mut approvals)
+        .await?;
+
+    let mut stdout = std::io::stdout();
+    for error in &outcome.errors {
+        writeln!(stdout, "error: {error}")?;
+    }
+    if let Some(answer) = outcome.final_text() {
+        writeln!(stdout, "{answer}")?;
+    }
+
+    client.shutdown().await;
+    Ok(())
+}
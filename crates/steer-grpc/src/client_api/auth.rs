@@ -1,9 +1,12 @@
 //! Client-facing authentication types and helpers.
 
+use std::time::Duration;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AuthMethod {
     OAuth,
     ApiKey,
+    DeviceCode,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -21,11 +24,28 @@ pub enum ApiKeyOrigin {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AuthProgress {
-    NeedInput { prompt: String },
-    InProgress { message: String },
+    NeedInput {
+        prompt: String,
+    },
+    InProgress {
+        message: String,
+    },
     Complete,
-    Error { message: String },
-    OAuthStarted { auth_url: String },
+    Error {
+        message: String,
+    },
+    OAuthStarted {
+        auth_url: String,
+    },
+    DeviceCode {
+        verification_uri: String,
+        user_code: String,
+    },
+    WaitingForCallback {
+        elapsed: Duration,
+    },
+    ExchangingCode,
+    Refreshing,
 }
 
 #[derive(Debug, Clone)]
@@ -9,6 +9,7 @@ pub enum ClientCommand {
         message_id: MessageId,
         new_content: String,
     },
+    RetryLastTurn,
     ExecuteBashCommand {
         command: String,
     },
@@ -24,7 +25,11 @@ pub enum ClientCommand {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ApprovalDecision {
     Deny,
+    DenyWithReason(String),
     Once,
     AlwaysTool,
     AlwaysBashPattern(String),
+    /// Approve only these hunks of an edit call, as numbered by
+    /// [`steer_tools::tools::edit::hunks::edit_hunks`].
+    AcceptHunks(Vec<usize>),
 }
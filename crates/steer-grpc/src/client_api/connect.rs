@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+pub use tonic::transport::{Certificate, ClientTlsConfig, Identity};
+
+/// How [`AgentClient::connect_with`](crate::AgentClient::connect_with)
+/// reaches a server. To run the server in-process instead, use
+/// [`AgentClient::local`](crate::AgentClient::local).
+#[derive(Debug, Clone, Default)]
+pub struct ConnectOptions {
+    /// TLS settings for `https://` addresses: the CA to trust, the server's
+    /// domain name, and optionally a client identity.
+    pub tls: Option<ClientTlsConfig>,
+    pub connect_timeout: Option<Duration>,
+}
+
+impl ConnectOptions {
+    #[must_use]
+    pub fn with_tls(mut self, tls: ClientTlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    #[must_use]
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+}
@@ -1,9 +1,13 @@
 use super::types::{
-    CompactResult, CompactTrigger, CompactionRecord, ContextWindowUsage, McpServerState, Message,
-    MessageId, ModelId, OpId, OperationKind, QueuedWorkItem, RequestId, SessionConfig, TokenUsage,
-    ToolCall, ToolCallDelta, ToolCallId, ToolResult,
+    BestOfCandidate, CommitDraft, CompactResult, CompactTrigger, CompactionRecord,
+    ContextWindowUsage, McpServerState, Message, MessageId, ModelId, OpId, OperationKind,
+    OperationTiming, QueuedWorkItem, RequestId, ReviewReport, SessionConfig, TokenUsage, ToolCall,
+    ToolCallDelta, ToolCallId, ToolResult,
 };
 
+/// Events of the active session, from [`AgentClient::event_stream`](crate::AgentClient::event_stream).
+pub type ClientEventStream = futures::stream::BoxStream<'static, ClientEvent>;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UsageUpdateKind {
     Unspecified,
@@ -30,6 +34,24 @@ pub enum ClientEvent {
         context_window: Option<ContextWindowUsage>,
         kind: UsageUpdateKind,
     },
+    ContextUsage {
+        used: u32,
+        limit: Option<u32>,
+    },
+    BestOfStarted {
+        op_id: OpId,
+        user_message_id: MessageId,
+        model: ModelId,
+        candidate_op_ids: Vec<OpId>,
+    },
+    BestOfCandidateReady {
+        op_id: OpId,
+        candidate: BestOfCandidate,
+    },
+    BestOfResolved {
+        op_id: OpId,
+        picked: Option<u32>,
+    },
     MessageUpdated {
         message: Message,
     },
@@ -89,6 +111,7 @@ pub enum ClientEvent {
     },
     ProcessingCompleted {
         op_id: OpId,
+        timing: Option<OperationTiming>,
     },
     OperationCancelled {
         op_id: OpId,
@@ -99,9 +122,21 @@ pub enum ClientEvent {
     WorkspaceFiles {
         files: Vec<String>,
     },
+    /// A file appeared in the workspace; directories end in `/`.
+    WorkspaceFileAdded {
+        path: String,
+    },
+    /// A file disappeared from the workspace; directories end in `/`.
+    WorkspaceFileRemoved {
+        path: String,
+    },
     Error {
         message: String,
     },
+    /// The provider declined to answer; `reason` says which filter stopped it.
+    Refused {
+        reason: String,
+    },
     McpServerStateChanged {
         server_name: String,
         state: McpServerState,
@@ -115,4 +150,21 @@ pub enum ClientEvent {
         head: Option<QueuedWorkItem>,
         count: usize,
     },
+
+    /// A review started with `AgentClient::start_review` finished.
+    ReviewCompleted {
+        report: Box<ReviewReport>,
+    },
+    ReviewFailed {
+        message: String,
+    },
+
+    /// A draft started with `AgentClient::start_commit_draft` is ready for
+    /// the user to confirm.
+    CommitDrafted {
+        draft: Box<CommitDraft>,
+    },
+    CommitDraftFailed {
+        message: String,
+    },
 }
@@ -1,11 +1,46 @@
 //! Client-facing API for Steer applications (TUI, IDE plugins, etc.).
+//!
+//! Embedding an agent takes four steps with [`AgentClient`](crate::AgentClient):
+//! connect ([`AgentClient::connect_with`](crate::AgentClient::connect_with) for
+//! a server, [`AgentClient::local`](crate::AgentClient::local) to run one
+//! in-process), create a session from [`CreateSessionParams::builder`], open
+//! the typed [`ClientEventStream`], and send prompts with
+//! [`AgentClient::send_message_and_wait`](crate::AgentClient::send_message_and_wait),
+//! which answers tool approval prompts through an [`ApprovalHandler`].
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! use steer_grpc::AgentClient;
+//! use steer_grpc::client_api::{CreateSessionParams, DenyAll, builtin};
+//!
+//! let model = builtin::claude_sonnet_4_5();
+//! let client = AgentClient::local(model.clone()).await?;
+//! client
+//!     .create_session(CreateSessionParams::builder(model.clone()).read_only().build())
+//!     .await?;
+//! let mut events = client.event_stream().await?;
+//! let outcome = client
+//!     .send_message_and_wait(&mut events, "What does this repo do?".into(), model, &mut DenyAll)
+//!     .await?;
+//! println!("{}", outcome.final_text().unwrap_or_default());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! See `examples/headless_agent.rs` for a complete program.
 
 mod auth;
 mod command;
+mod connect;
 mod event;
+mod session_builder;
+mod turn;
 mod types;
 
 pub use auth::*;
 pub use command::{ApprovalDecision, ClientCommand};
-pub use event::{ClientEvent, UsageUpdateKind};
+pub use connect::{Certificate, ClientTlsConfig, ConnectOptions, Identity};
+pub use event::{ClientEvent, ClientEventStream, UsageUpdateKind};
+pub use session_builder::CreateSessionBuilder;
+pub use turn::{ApprovalHandler, DenyAll, TurnOutcome};
 pub use types::*;
@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use super::types::{
+    AutoSummaryConfig, CreateSessionParams, IsolationMode, ModelId, SessionPolicyOverrides,
+    SessionToolConfig, ToolVisibility, UnapprovedBehavior, WorkspaceConfig,
+};
+
+impl CreateSessionParams {
+    /// Start building a session that uses `model`, works in the server's
+    /// current directory, and prompts before running tools that are not
+    /// pre-approved.
+    pub fn builder(model: ModelId) -> CreateSessionBuilder {
+        CreateSessionBuilder {
+            params: Self {
+                workspace: WorkspaceConfig::default(),
+                tool_config: SessionToolConfig::default(),
+                metadata: HashMap::new(),
+                default_model: model,
+                primary_agent_id: None,
+                policy_overrides: SessionPolicyOverrides::empty(),
+                isolation: IsolationMode::None,
+                auto_summary: AutoSummaryConfig::default(),
+            },
+        }
+    }
+}
+
+/// Builder for [`CreateSessionParams`]; see [`CreateSessionParams::builder`].
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct CreateSessionBuilder {
+    params: CreateSessionParams,
+}
+
+impl CreateSessionBuilder {
+    /// Work in `path` on the server's filesystem.
+    pub fn workspace_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.params.workspace = WorkspaceConfig::Local { path: path.into() };
+        self
+    }
+
+    pub fn workspace(mut self, workspace: WorkspaceConfig) -> Self {
+        self.params.workspace = workspace;
+        self
+    }
+
+    /// Which tools the model is shown.
+    pub fn tool_visibility(mut self, visibility: ToolVisibility) -> Self {
+        self.params.tool_config.visibility = visibility;
+        self
+    }
+
+    /// Show the model only tools that cannot change the workspace.
+    pub fn read_only(self) -> Self {
+        self.tool_visibility(ToolVisibility::ReadOnly)
+    }
+
+    /// Run these tools without asking for approval.
+    pub fn preapprove_tools<I, S>(mut self, tools: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.params
+            .tool_config
+            .approval_policy
+            .preapproved
+            .tools
+            .extend(tools.into_iter().map(Into::into));
+        self
+    }
+
+    /// What happens to tool calls that are not pre-approved: prompt (the
+    /// default, answered through `approve_tool`), deny, or allow.
+    pub fn unapproved_tools(mut self, behavior: UnapprovedBehavior) -> Self {
+        self.params.tool_config.approval_policy.default_behavior = behavior;
+        self
+    }
+
+    pub fn allow_network(mut self, allow: bool) -> Self {
+        self.params.tool_config.allow_network = allow;
+        self
+    }
+
+    pub fn allow_outside_workspace(mut self, allow: bool) -> Self {
+        self.params.tool_config.allow_outside_workspace = allow;
+        self
+    }
+
+    pub fn tool_config(mut self, tool_config: SessionToolConfig) -> Self {
+        self.params.tool_config = tool_config;
+        self
+    }
+
+    pub fn policy_overrides(mut self, overrides: SessionPolicyOverrides) -> Self {
+        self.params.policy_overrides = overrides;
+        self
+    }
+
+    pub fn primary_agent(mut self, id: impl Into<String>) -> Self {
+        self.params.primary_agent_id = Some(id.into());
+        self
+    }
+
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.params.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn isolation(mut self, isolation: IsolationMode) -> Self {
+        self.params.isolation = isolation;
+        self
+    }
+
+    pub fn auto_summary(mut self, auto_summary: AutoSummaryConfig) -> Self {
+        self.params.auto_summary = auto_summary;
+        self
+    }
+
+    pub fn build(self) -> CreateSessionParams {
+        self.params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client_api::builtin;
+
+    #[test]
+    fn builder_sets_tool_policy() {
+        let params = CreateSessionParams::builder(builtin::claude_sonnet_4_5())
+            .workspace_path("/tmp/project")
+            .read_only()
+            .preapprove_tools(["grep", "read_file"])
+            .unapproved_tools(UnapprovedBehavior::Deny)
+            .metadata("origin", "sdk")
+            .build();
+
+        assert!(matches!(
+            params.workspace,
+            WorkspaceConfig::Local { ref path } if path == &PathBuf::from("/tmp/project")
+        ));
+        assert_eq!(params.tool_config.visibility, ToolVisibility::ReadOnly);
+        let policy = &params.tool_config.approval_policy;
+        assert!(policy.preapproved.tools.contains("grep"));
+        assert!(policy.preapproved.tools.contains("read_file"));
+        assert_eq!(policy.default_behavior, UnapprovedBehavior::Deny);
+        assert_eq!(
+            params.metadata.get("origin").map(String::as_str),
+            Some("sdk")
+        );
+    }
+}
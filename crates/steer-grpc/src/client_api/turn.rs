@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+
+use super::command::ApprovalDecision;
+use super::types::{Message, MessageData, OpId, ToolCall};
+
+/// Answers tool approval prompts while
+/// [`AgentClient::send_message_and_wait`](crate::AgentClient::send_message_and_wait)
+/// drives a turn.
+///
+/// Closures of type `FnMut(&ToolCall) -> ApprovalDecision` implement this.
+#[async_trait]
+pub trait ApprovalHandler: Send {
+    async fn decide(&mut self, tool_call: &ToolCall) -> ApprovalDecision;
+}
+
+#[async_trait]
+impl<F> ApprovalHandler for F
+where
+    F: FnMut(&ToolCall) -> ApprovalDecision + Send,
+{
+    async fn decide(&mut self, tool_call: &ToolCall) -> ApprovalDecision {
+        self(tool_call)
+    }
+}
+
+/// Denies every tool call that needs approval.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DenyAll;
+
+#[async_trait]
+impl ApprovalHandler for DenyAll {
+    async fn decide(&mut self, _tool_call: &ToolCall) -> ApprovalDecision {
+        ApprovalDecision::Deny
+    }
+}
+
+/// What happened during one turn.
+#[derive(Debug, Clone)]
+pub struct TurnOutcome {
+    pub op_id: OpId,
+    /// Assistant and tool messages added during the turn, in order.
+    pub messages: Vec<Message>,
+    /// Errors the server reported while the turn ran.
+    pub errors: Vec<String>,
+    pub cancelled: bool,
+}
+
+impl TurnOutcome {
+    pub(crate) fn new(op_id: OpId) -> Self {
+        Self {
+            op_id,
+            messages: Vec::new(),
+            errors: Vec::new(),
+            cancelled: false,
+        }
+    }
+
+    /// Text of the last assistant message, which is the turn's answer.
+    pub fn final_text(&self) -> Option<String> {
+        self.messages
+            .iter()
+            .rev()
+            .find(|message| matches!(message.data, MessageData::Assistant { .. }))
+            .map(Message::extract_text)
+    }
+}
@@ -3,7 +3,8 @@
 use std::collections::HashMap;
 
 pub use steer_core::app::conversation::{
-    AssistantContent, ImageContent, ImageSource, Message, MessageData, ThoughtContent, UserContent,
+    AssistantContent, AttachmentBody, AttachmentContent, ImageContent, ImageSource, Message,
+    MessageData, ThoughtContent, UserContent,
 };
 
 pub use steer_core::app::domain::types::{
@@ -30,9 +31,14 @@ pub struct QueuedWorkItem {
 }
 
 pub use steer_core::api::provider::TokenUsage;
+pub use steer_core::app::domain::ModelSwitchMode;
 pub use steer_core::app::domain::delta::ToolCallDelta;
-pub use steer_core::app::domain::event::{CompactResult, CompactTrigger, ContextWindowUsage};
+pub use steer_core::app::domain::event::{
+    BestOfCandidate, CompactResult, CompactTrigger, ContextWindowUsage, OperationTiming,
+    TimingSpan, TimingSpanKind,
+};
 pub use steer_core::app::domain::state::OperationKind;
+pub use steer_core::app::{ContextItem, ContextReport, ContextSegment, ContextSegmentKind};
 
 pub use steer_core::agents::default_agent_spec_id;
 pub use steer_core::primary_agents::default_primary_agent_id;
@@ -48,9 +54,18 @@ pub use steer_tools::result::{
 pub use steer_tools::{ToolCall, ToolError};
 
 pub use steer_core::session::state::{
-    SessionConfig, SessionPolicyOverrides, SessionToolConfig, WorkspaceConfig,
+    AutoSummaryConfig, SessionConfig, SessionPolicyOverrides, SessionToolConfig, ToolVisibility,
+    UnapprovedBehavior, WorkspaceConfig,
+};
+
+pub use steer_core::isolation::{
+    FileChange, FileChangeKind, IsolatedWorkspace, IsolationKind, IsolationMode, MergeReport,
 };
 
+pub use steer_core::commit::{CommitDraft, CommitOutcome};
+
+pub use steer_core::review::{ReviewFinding, ReviewReport, ReviewSeverity, ReviewTarget};
+
 pub use steer_core::session::McpServerInfo;
 pub use steer_core::session::state::McpConnectionState;
 
@@ -58,10 +73,15 @@ pub use steer_core::app::domain::action::McpServerState;
 
 pub use steer_core::tools::McpTransport;
 
-pub use steer_core::preferences::{EditingMode, NotificationTransport, Preferences};
+pub use steer_core::preferences::{
+    EditingMode, NotificationPreferences, NotificationTransport, Preferences,
+};
+
+pub use steer_core::http::client_builder as http_client_builder;
 
 pub use steer_core::config::provider::ProviderId;
 
+pub use steer_workspace::symbols::FileSymbol;
 pub use steer_workspace::{LlmStatus, WorkspaceStatus};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -71,6 +91,13 @@ pub struct PrimaryAgentSpec {
     pub description: String,
 }
 
+/// An isolated session's worktree and the files changed in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IsolationStatus {
+    pub workspace: IsolatedWorkspace,
+    pub changes: Vec<FileChange>,
+}
+
 #[derive(Debug, Clone)]
 pub struct CreateSessionParams {
     pub workspace: WorkspaceConfig,
@@ -79,6 +106,8 @@ pub struct CreateSessionParams {
     pub default_model: ModelId,
     pub primary_agent_id: Option<String>,
     pub policy_overrides: SessionPolicyOverrides,
+    pub isolation: IsolationMode,
+    pub auto_summary: AutoSummaryConfig,
 }
 
 impl From<SessionConfig> for CreateSessionParams {
@@ -90,6 +119,8 @@ impl From<SessionConfig> for CreateSessionParams {
             default_model: config.default_model,
             primary_agent_id: config.primary_agent_id,
             policy_overrides: config.policy_overrides,
+            isolation: config.isolation,
+            auto_summary: config.auto_summary,
         }
     }
 }
@@ -1,33 +1,45 @@
+use futures::StreamExt;
+use futures::stream::BoxStream;
 use tokio::sync::{Mutex, mpsc};
 use tokio::task::JoinHandle;
+use tokio_stream::wrappers::ReceiverStream;
 use tonic::Request;
-use tonic::transport::Channel;
+use tonic::transport::{Channel, Endpoint};
 use tracing::{debug, error, info, warn};
 
 use crate::client_api::{
-    ClientEvent, CreateSessionParams, PrimaryAgentSpec, ProviderAuthStatus, ProviderInfo,
-    StartAuthResponse,
+    ApprovalHandler, ClientEvent, ClientEventStream, CommitDraft, CommitOutcome, ConnectOptions,
+    ContextReport, CreateSessionParams, FileSymbol, IsolationStatus, MergeReport, ModelSwitchMode,
+    OpId, PrimaryAgentSpec, ProviderAuthStatus, ProviderInfo, ReviewReport, ReviewTarget,
+    StartAuthResponse, TurnOutcome,
 };
 use crate::grpc::GRPC_MAX_MESSAGE_SIZE_BYTES;
 use crate::grpc::conversions::{
-    model_to_proto, proto_to_client_event, proto_to_mcp_server_info, proto_to_message,
+    auto_summary_config_to_proto, core_attachment_to_proto, isolation_mode_to_proto,
+    model_switch_mode_to_proto, model_to_proto, parse_op_id, proto_to_client_event,
+    proto_to_commit_draft, proto_to_commit_outcome, proto_to_context_report, proto_to_file_change,
+    proto_to_file_symbol, proto_to_isolated_workspace, proto_to_mcp_server_info, proto_to_message,
     proto_to_primary_agent_spec, proto_to_provider_auth_status, proto_to_provider_info,
-    proto_to_repo_info, proto_to_start_auth_response, proto_to_workspace_info,
-    proto_to_workspace_status, session_policy_overrides_to_proto, session_tool_config_to_proto,
-    workspace_config_to_proto,
+    proto_to_repo_info, proto_to_review_report, proto_to_start_auth_response,
+    proto_to_workspace_info, proto_to_workspace_status, session_config_to_proto,
+    session_policy_overrides_to_proto, session_tool_config_to_proto, workspace_config_to_proto,
 };
-use crate::grpc::error::GrpcError;
+use crate::grpc::error::{ConversionError, GrpcError};
 
 type GrpcResult<T> = std::result::Result<T, GrpcError>;
 
 use steer_core::app::conversation::Message;
+use steer_core::app::domain::session::ToolAuditFilter;
 use steer_core::session::McpServerInfo;
+use steer_core::session::state::SessionConfig;
 use steer_proto::agent::v1::{
     self as proto, CreateSessionRequest, DeleteSessionRequest, GetConversationRequest,
     GetDefaultModelRequest, GetMcpServersRequest, GetSessionRequest, GetWorkspaceStatusRequest,
     ListReposRequest, ListSessionsRequest, ListWorkspacesRequest, ResolveRepoRequest, SessionInfo,
     SessionState, agent_service_client::AgentServiceClient,
 };
+use steer_proto::convert::proto_to_memory_entry;
+use steer_tools::tools::memory::MemoryEntry;
 
 pub struct AgentClient {
     client: Mutex<AgentServiceClient<Channel>>,
@@ -47,22 +59,26 @@ fn configure_agent_service_client(
 
 impl AgentClient {
     pub async fn connect(addr: &str) -> GrpcResult<Self> {
+        Self::connect_with(addr, ConnectOptions::default()).await
+    }
+
+    /// Connect to `addr` (e.g. `http://127.0.0.1:50051`), using TLS and
+    /// timeouts from `options`.
+    pub async fn connect_with(addr: &str, options: ConnectOptions) -> GrpcResult<Self> {
         info!("Connecting to gRPC server at {}", addr);
 
-        let client =
-            configure_agent_service_client(AgentServiceClient::connect(addr.to_string()).await?);
+        let mut endpoint = Endpoint::from_shared(addr.to_string())?;
+        if let Some(tls) = options.tls {
+            endpoint = endpoint.tls_config(tls)?;
+        }
+        if let Some(connect_timeout) = options.connect_timeout {
+            endpoint = endpoint.connect_timeout(connect_timeout);
+        }
+        let channel = endpoint.connect().await?;
 
         info!("Successfully connected to gRPC server");
 
-        let (client_event_tx, client_event_rx) = mpsc::channel::<ClientEvent>(100);
-
-        Ok(Self {
-            client: Mutex::new(client),
-            session_id: Mutex::new(None),
-            client_event_tx,
-            client_event_rx: Mutex::new(Some(client_event_rx)),
-            stream_handle: Mutex::new(None),
-        })
+        Self::from_channel(channel).await
     }
 
     pub async fn from_channel(channel: Channel) -> GrpcResult<Self> {
@@ -100,6 +116,8 @@ impl AgentClient {
             primary_agent_id: params.primary_agent_id,
             policy_overrides: Some(session_policy_overrides_to_proto(&params.policy_overrides)),
             auto_compaction: None,
+            isolation: isolation_mode_to_proto(params.isolation).into(),
+            auto_summary: Some(auto_summary_config_to_proto(&params.auto_summary)),
         });
 
         let response = self
@@ -154,6 +172,7 @@ impl AgentClient {
         let request = Request::new(proto::SubscribeSessionEventsRequest {
             session_id: session_id.clone(),
             since_sequence: None,
+            event_kinds: Vec::new(),
         });
 
         let mut inbound_stream = self
@@ -222,6 +241,63 @@ impl AgentClient {
         content: Vec<crate::client_api::UserContent>,
         model: steer_core::config::model::ModelId,
     ) -> GrpcResult<()> {
+        self.submit_content(content, model).await.map(|_| ())
+    }
+
+    /// Send `message` and follow the turn it starts until it finishes,
+    /// answering tool approval prompts with `approvals`. `events` must come
+    /// from [`Self::event_stream`] on this client.
+    pub async fn send_message_and_wait(
+        &self,
+        events: &mut ClientEventStream,
+        message: String,
+        model: steer_core::config::model::ModelId,
+        approvals: &mut dyn ApprovalHandler,
+    ) -> GrpcResult<TurnOutcome> {
+        let op_id = self
+            .submit_content(
+                vec![crate::client_api::UserContent::Text { text: message }],
+                model,
+            )
+            .await?;
+        let mut outcome = TurnOutcome::new(op_id);
+
+        while let Some(event) = events.next().await {
+            match event {
+                ClientEvent::AssistantMessageAdded { message, .. }
+                | ClientEvent::ToolMessageAdded { message } => outcome.messages.push(message),
+                ClientEvent::ApprovalRequested {
+                    request_id,
+                    tool_call,
+                } => {
+                    let decision = approvals.decide(&tool_call).await;
+                    self.approve_tool(request_id.to_string(), decision).await?;
+                }
+                ClientEvent::Error { message } | ClientEvent::Refused { reason: message } => {
+                    outcome.errors.push(message);
+                }
+                ClientEvent::ProcessingCompleted { op_id, .. } if op_id == outcome.op_id => {
+                    return Ok(outcome);
+                }
+                ClientEvent::OperationCancelled { op_id, .. } if op_id == outcome.op_id => {
+                    outcome.cancelled = true;
+                    return Ok(outcome);
+                }
+                _ => {}
+            }
+        }
+
+        Err(GrpcError::StreamError(
+            "Event stream ended before the turn finished".to_string(),
+        ))
+    }
+
+    /// Send a message and return the id of the operation it starts.
+    async fn submit_content(
+        &self,
+        content: Vec<crate::client_api::UserContent>,
+        model: steer_core::config::model::ModelId,
+    ) -> GrpcResult<OpId> {
         let session_id = self
             .session_id
             .lock()
@@ -261,6 +337,11 @@ impl AgentClient {
                             exit_code,
                         },
                     )),
+                    crate::client_api::UserContent::Attachment { attachment } => {
+                        Some(proto::user_content::Content::Attachment(
+                            core_attachment_to_proto(&attachment),
+                        ))
+                    }
                     crate::client_api::UserContent::Image { image } => {
                         let source = match image.source {
                             crate::client_api::ImageSource::SessionFile { relative_path } => {
@@ -303,14 +384,20 @@ impl AgentClient {
             }),
         });
 
-        self.client
+        let operation = self
+            .client
             .lock()
             .await
             .send_message(request)
             .await
-            .map_err(Box::new)?;
+            .map_err(Box::new)?
+            .into_inner()
+            .operation
+            .ok_or_else(|| ConversionError::MissingField {
+                field: "operation".to_string(),
+            })?;
 
-        Ok(())
+        Ok(parse_op_id(&operation.id)?)
     }
 
     pub async fn edit_message(
@@ -358,6 +445,11 @@ impl AgentClient {
                             exit_code,
                         },
                     )),
+                    crate::client_api::UserContent::Attachment { attachment } => {
+                        Some(proto::user_content::Content::Attachment(
+                            core_attachment_to_proto(&attachment),
+                        ))
+                    }
                     crate::client_api::UserContent::Image { image } => {
                         let source = match image.source {
                             crate::client_api::ImageSource::SessionFile { relative_path } => {
@@ -411,6 +503,96 @@ impl AgentClient {
         Ok(())
     }
 
+    /// Regenerate the response to the last prompt, optionally with a
+    /// different model for this retry only.
+    pub async fn retry_last_turn(
+        &self,
+        model: steer_core::config::model::ModelId,
+    ) -> GrpcResult<()> {
+        let session_id = self
+            .session_id
+            .lock()
+            .await
+            .as_ref()
+            .cloned()
+            .ok_or_else(|| GrpcError::InvalidSessionState {
+                reason: "No active session".to_string(),
+            })?;
+
+        let request = Request::new(proto::RetryLastTurnRequest {
+            session_id,
+            model: Some(model_to_proto(model)),
+        });
+
+        self.client
+            .lock()
+            .await
+            .retry_last_turn(request)
+            .await
+            .map_err(Box::new)?;
+
+        Ok(())
+    }
+
+    /// Send `message` as a prompt and draft `candidates` answers to it,
+    /// running only read-only tools.
+    pub async fn start_best_of(
+        &self,
+        message: String,
+        candidates: u32,
+        model: steer_core::config::model::ModelId,
+    ) -> GrpcResult<()> {
+        let session_id = self
+            .session_id
+            .lock()
+            .await
+            .as_ref()
+            .cloned()
+            .ok_or_else(|| GrpcError::InvalidSessionState {
+                reason: "No active session".to_string(),
+            })?;
+
+        let request = Request::new(proto::StartBestOfRequest {
+            session_id,
+            message,
+            candidates,
+            model: Some(model_to_proto(model)),
+        });
+
+        self.client
+            .lock()
+            .await
+            .start_best_of(request)
+            .await
+            .map_err(Box::new)?;
+
+        Ok(())
+    }
+
+    /// Make best-of candidate `index` the answer to its prompt.
+    pub async fn pick_best_of_candidate(&self, index: u32) -> GrpcResult<()> {
+        let session_id = self
+            .session_id
+            .lock()
+            .await
+            .as_ref()
+            .cloned()
+            .ok_or_else(|| GrpcError::InvalidSessionState {
+                reason: "No active session".to_string(),
+            })?;
+
+        let request = Request::new(proto::PickBestOfCandidateRequest { session_id, index });
+
+        self.client
+            .lock()
+            .await
+            .pick_best_of_candidate(request)
+            .await
+            .map_err(Box::new)?;
+
+        Ok(())
+    }
+
     pub async fn approve_tool(
         &self,
         tool_call_id: String,
@@ -431,11 +613,17 @@ impl AgentClient {
 
         let decision_type = match decision {
             ApprovalDecision::Deny => DecisionType::Deny(true),
+            ApprovalDecision::DenyWithReason(reason) => DecisionType::DenyWithReason(reason),
             ApprovalDecision::Once => DecisionType::Once(true),
             ApprovalDecision::AlwaysTool => DecisionType::AlwaysTool(true),
             ApprovalDecision::AlwaysBashPattern(pattern) => {
                 DecisionType::AlwaysBashPattern(pattern)
             }
+            ApprovalDecision::AcceptHunks(indices) => {
+                DecisionType::AcceptHunks(proto::AcceptedHunks {
+                    indices: indices.into_iter().map(|index| index as u32).collect(),
+                })
+            }
         };
 
         let request = Request::new(proto::ApproveToolRequest {
@@ -482,6 +670,39 @@ impl AgentClient {
         Ok(())
     }
 
+    /// Switch the model of the active operation; `mode` decides whether the
+    /// in-flight request finishes first or the operation restarts.
+    pub async fn switch_model(
+        &self,
+        model: steer_core::config::model::ModelId,
+        mode: ModelSwitchMode,
+    ) -> GrpcResult<()> {
+        let session_id = self
+            .session_id
+            .lock()
+            .await
+            .as_ref()
+            .cloned()
+            .ok_or_else(|| GrpcError::InvalidSessionState {
+                reason: "No active session".to_string(),
+            })?;
+
+        let request = Request::new(proto::SwitchModelRequest {
+            session_id,
+            model: Some(model_to_proto(model)),
+            mode: model_switch_mode_to_proto(mode) as i32,
+        });
+
+        self.client
+            .lock()
+            .await
+            .switch_model(request)
+            .await
+            .map_err(Box::new)?;
+
+        Ok(())
+    }
+
     pub async fn cancel_operation(&self) -> GrpcResult<()> {
         let session_id = self
             .session_id
@@ -493,7 +714,11 @@ impl AgentClient {
                 reason: "No active session".to_string(),
             })?;
 
-        let request = Request::new(proto::CancelOperationRequest { session_id });
+        let request = Request::new(proto::CancelOperationRequest {
+            session_id,
+            op_id: None,
+            force: false,
+        });
 
         self.client
             .lock()
@@ -583,6 +808,15 @@ impl AgentClient {
         Ok(())
     }
 
+    /// Subscribe to the active session and return its events as a typed
+    /// stream. Like [`Self::subscribe_client_events`], this can be called
+    /// once per client.
+    pub async fn event_stream(&self) -> GrpcResult<ClientEventStream> {
+        let receiver = self.subscribe_client_events().await?;
+        self.subscribe_session_events().await?;
+        Ok(ReceiverStream::new(receiver).boxed())
+    }
+
     pub async fn subscribe_client_events(&self) -> GrpcResult<mpsc::Receiver<ClientEvent>> {
         let mut guard = self.client_event_rx.lock().await;
         if let Some(receiver) = guard.take() {
@@ -619,6 +853,155 @@ impl AgentClient {
         Ok(sessions_response.sessions)
     }
 
+    /// Summarize the session now and store the result on it.
+    pub async fn summarize_session(&self, session_id: &str) -> GrpcResult<String> {
+        let request = Request::new(proto::SummarizeSessionRequest {
+            session_id: session_id.to_string(),
+        });
+
+        let response = self
+            .client
+            .lock()
+            .await
+            .summarize_session(request)
+            .await
+            .map_err(Box::new)?
+            .into_inner();
+
+        Ok(response.summary)
+    }
+
+    /// The session memory, in the order entries were first remembered.
+    pub async fn session_memory(&self, session_id: &str) -> GrpcResult<Vec<MemoryEntry>> {
+        let response = self
+            .client
+            .lock()
+            .await
+            .get_session_memory(Request::new(proto::GetSessionMemoryRequest {
+                session_id: session_id.to_string(),
+            }))
+            .await
+            .map_err(Box::new)?
+            .into_inner();
+
+        Ok(Self::memory_entries(response))
+    }
+
+    /// Add or replace a session memory entry and return the updated memory.
+    pub async fn remember(
+        &self,
+        session_id: &str,
+        key: String,
+        content: String,
+    ) -> GrpcResult<Vec<MemoryEntry>> {
+        let response = self
+            .client
+            .lock()
+            .await
+            .remember_session_fact(Request::new(proto::RememberSessionFactRequest {
+                session_id: session_id.to_string(),
+                key,
+                content,
+            }))
+            .await
+            .map_err(Box::new)?
+            .into_inner();
+
+        Ok(Self::memory_entries(response))
+    }
+
+    /// Remove a session memory entry and return the updated memory.
+    pub async fn forget(&self, session_id: &str, key: String) -> GrpcResult<Vec<MemoryEntry>> {
+        let response = self
+            .client
+            .lock()
+            .await
+            .forget_session_fact(Request::new(proto::ForgetSessionFactRequest {
+                session_id: session_id.to_string(),
+                key,
+            }))
+            .await
+            .map_err(Box::new)?
+            .into_inner();
+
+        Ok(Self::memory_entries(response))
+    }
+
+    fn memory_entries(response: proto::SessionMemoryResponse) -> Vec<MemoryEntry> {
+        response
+            .entries
+            .into_iter()
+            .map(proto_to_memory_entry)
+            .collect()
+    }
+
+    /// Pause or resume the server's event log, or query it with `None`.
+    /// Returns whether logging is enabled afterwards.
+    pub async fn set_event_logging(&self, enabled: Option<bool>) -> GrpcResult<bool> {
+        let response = self
+            .client
+            .lock()
+            .await
+            .set_event_logging(Request::new(proto::SetEventLoggingRequest { enabled }))
+            .await
+            .map_err(Box::new)?
+            .into_inner();
+
+        Ok(response.enabled)
+    }
+
+    /// Every operation currently running on the server, across sessions.
+    pub async fn list_active_operations(&self) -> GrpcResult<Vec<proto::ActiveOperation>> {
+        let response = self
+            .client
+            .lock()
+            .await
+            .list_active_operations(Request::new(proto::ListActiveOperationsRequest {}))
+            .await
+            .map_err(Box::new)?;
+
+        Ok(response.into_inner().operations)
+    }
+
+    /// Re-read the server's model catalogs so edited models resolve without a
+    /// restart.
+    pub async fn reload_catalog(&self) -> GrpcResult<proto::ReloadCatalogResponse> {
+        let response = self
+            .client
+            .lock()
+            .await
+            .reload_catalog(Request::new(proto::ReloadCatalogRequest {}))
+            .await
+            .map_err(Box::new)?;
+
+        Ok(response.into_inner())
+    }
+
+    /// Cancel `op_id` in any session, not just the attached one. Returns
+    /// whether it was still running.
+    pub async fn cancel_session_operation(
+        &self,
+        session_id: &str,
+        op_id: &str,
+        force: bool,
+    ) -> GrpcResult<bool> {
+        let request = Request::new(proto::CancelOperationRequest {
+            session_id: session_id.to_string(),
+            op_id: Some(op_id.to_string()),
+            force,
+        });
+
+        let response = self
+            .client
+            .lock()
+            .await
+            .cancel_operation(request)
+            .await
+            .map_err(Box::new)?;
+
+        Ok(response.into_inner().cancelled)
+    }
+
     pub async fn get_session(&self, session_id: &str) -> GrpcResult<Option<SessionState>> {
         debug!("Getting session {} from gRPC server", session_id);
 
@@ -901,8 +1284,17 @@ impl AgentClient {
         Ok(statuses)
     }
 
-    pub async fn start_auth(&self, provider_id: String) -> GrpcResult<StartAuthResponse> {
-        let request = Request::new(proto::StartAuthRequest { provider_id });
+    pub async fn start_auth(
+        &self,
+        provider_id: String,
+        method: Option<crate::client_api::AuthMethod>,
+    ) -> GrpcResult<StartAuthResponse> {
+        let request = Request::new(proto::StartAuthRequest {
+            provider_id,
+            method: method
+                .as_ref()
+                .map(|method| crate::grpc::conversions::client_auth_method_to_proto(method) as i32),
+        });
         let response = self
             .client
             .lock()
@@ -986,7 +1378,12 @@ impl AgentClient {
         Ok(response.into_inner().models)
     }
 
-    pub async fn list_workspace_files(&self) -> GrpcResult<Vec<String>> {
+    /// Streams workspace file paths in the chunks the server sends them, stopping
+    /// after `max_results` paths (0 means no limit).
+    pub async fn stream_workspace_files(
+        &self,
+        max_results: usize,
+    ) -> GrpcResult<BoxStream<'static, GrpcResult<Vec<String>>>> {
         let session_id = self
             .session_id
             .lock()
@@ -1000,10 +1397,10 @@ impl AgentClient {
         let request = Request::new(proto::ListFilesRequest {
             session_id,
             query: String::new(),
-            max_results: 0,
+            max_results: u32::try_from(max_results).unwrap_or(u32::MAX),
         });
 
-        let mut stream = self
+        let stream = self
             .client
             .lock()
             .await
@@ -1012,12 +1409,311 @@ impl AgentClient {
             .map_err(Box::new)?
             .into_inner();
 
-        let mut all_files = Vec::new();
-        while let Some(response) = stream.message().await.map_err(Box::new)? {
-            all_files.extend(response.paths);
-        }
+        Ok(stream
+            .map(|response| {
+                response
+                    .map(|response| response.paths)
+                    .map_err(|status| GrpcError::from(Box::new(status)))
+            })
+            .boxed())
+    }
+
+    /// Estimates the size of each part of the session's next request. `model`
+    /// selects the context window to compare against; it defaults to the
+    /// session's default model.
+    pub async fn get_context_report(
+        &self,
+        session_id: &str,
+        model: Option<&steer_core::config::model::ModelId>,
+    ) -> GrpcResult<ContextReport> {
+        let request = Request::new(proto::GetContextReportRequest {
+            session_id: session_id.to_string(),
+            model: model.cloned().map(model_to_proto),
+        });
+
+        let response = self
+            .client
+            .lock()
+            .await
+            .get_context_report(request)
+            .await
+            .map_err(Box::new)?
+            .into_inner();
+
+        Ok(proto_to_context_report(response)?)
+    }
+
+    /// The worktree an isolated session runs in and its changed files, or
+    /// `None` when the session is not isolated.
+    pub async fn get_isolation_status(
+        &self,
+        session_id: &str,
+    ) -> GrpcResult<Option<IsolationStatus>> {
+        let request = Request::new(proto::GetIsolationStatusRequest {
+            session_id: session_id.to_string(),
+        });
+
+        let response = self
+            .client
+            .lock()
+            .await
+            .get_isolation_status(request)
+            .await
+            .map_err(Box::new)?
+            .into_inner();
+
+        Ok(response.workspace.map(|workspace| IsolationStatus {
+            workspace: proto_to_isolated_workspace(workspace),
+            changes: response
+                .changes
+                .into_iter()
+                .map(proto_to_file_change)
+                .collect(),
+        }))
+    }
+
+    /// Applies an isolated session's changes to the directory it was started in.
+    pub async fn merge_isolated_workspace(&self, session_id: &str) -> GrpcResult<MergeReport> {
+        let request = Request::new(proto::MergeIsolatedWorkspaceRequest {
+            session_id: session_id.to_string(),
+        });
+
+        let response = self
+            .client
+            .lock()
+            .await
+            .merge_isolated_workspace(request)
+            .await
+            .map_err(Box::new)?
+            .into_inner();
+
+        Ok(MergeReport {
+            applied: response.applied,
+            conflicts: response.conflicts,
+            details: response.details,
+        })
+    }
+
+    pub async fn export_isolated_patch(&self, session_id: &str) -> GrpcResult<String> {
+        let request = Request::new(proto::ExportIsolatedPatchRequest {
+            session_id: session_id.to_string(),
+        });
+
+        let response = self
+            .client
+            .lock()
+            .await
+            .export_isolated_patch(request)
+            .await
+            .map_err(Box::new)?
+            .into_inner();
 
-        Ok(all_files)
+        Ok(response.patch)
+    }
+
+    /// Commits an isolated session's changes on its branch and returns the branch name.
+    pub async fn commit_isolated_branch(
+        &self,
+        session_id: &str,
+        message: Option<String>,
+    ) -> GrpcResult<String> {
+        let request = Request::new(proto::CommitIsolatedBranchRequest {
+            session_id: session_id.to_string(),
+            message,
+        });
+
+        let response = self
+            .client
+            .lock()
+            .await
+            .commit_isolated_branch(request)
+            .await
+            .map_err(Box::new)?
+            .into_inner();
+
+        Ok(response.branch)
+    }
+
+    pub async fn discard_isolated_workspace(&self, session_id: &str) -> GrpcResult<()> {
+        let request = Request::new(proto::DiscardIsolatedWorkspaceRequest {
+            session_id: session_id.to_string(),
+        });
+
+        self.client
+            .lock()
+            .await
+            .discard_isolated_workspace(request)
+            .await
+            .map_err(Box::new)?;
+
+        Ok(())
+    }
+
+    /// Reviews the diff selected by `target` in a session's workspace. Model
+    /// calls can take a while, so this does not hold the client lock.
+    pub async fn run_review(
+        &self,
+        session_id: &str,
+        target: &ReviewTarget,
+        model: steer_core::config::model::ModelId,
+    ) -> GrpcResult<ReviewReport> {
+        let request = Request::new(proto::RunReviewRequest {
+            session_id: session_id.to_string(),
+            target: target.to_string(),
+            model: Some(model_to_proto(model)),
+        });
+
+        let mut client = self.client.lock().await.clone();
+        let response = client
+            .run_review(request)
+            .await
+            .map_err(Box::new)?
+            .into_inner();
+
+        Ok(proto_to_review_report(response))
+    }
+
+    /// Drafts a commit message for the active session's edits in the
+    /// background. The draft arrives as `ClientEvent::CommitDrafted` or
+    /// `ClientEvent::CommitDraftFailed`.
+    pub async fn start_commit_draft(
+        &self,
+        model: steer_core::config::model::ModelId,
+    ) -> GrpcResult<()> {
+        let session_id = self.active_session_id().await?;
+        let request = proto::DraftCommitRequest {
+            session_id,
+            model: Some(model_to_proto(model)),
+        };
+        let mut client = self.client.lock().await.clone();
+        let evt_tx = self.client_event_tx.clone();
+
+        tokio::spawn(async move {
+            let event = match client.draft_commit(Request::new(request)).await {
+                Ok(response) => ClientEvent::CommitDrafted {
+                    draft: Box::new(proto_to_commit_draft(response.into_inner())),
+                },
+                Err(status) => ClientEvent::CommitDraftFailed {
+                    message: status.message().to_string(),
+                },
+            };
+            let _ = evt_tx.send(event).await;
+        });
+
+        Ok(())
+    }
+
+    /// Stages and commits a confirmed draft in the active session's workspace.
+    pub async fn create_commit(&self, draft: &CommitDraft) -> GrpcResult<CommitOutcome> {
+        let session_id = self.active_session_id().await?;
+        let request = Request::new(proto::CreateCommitRequest {
+            session_id,
+            files: draft.files.clone(),
+            message: draft.message.clone(),
+        });
+
+        let response = self
+            .client
+            .lock()
+            .await
+            .create_commit(request)
+            .await
+            .map_err(Box::new)?
+            .into_inner();
+
+        Ok(proto_to_commit_outcome(response))
+    }
+
+    /// Starts a review of the active session in the background. The outcome
+    /// arrives as `ClientEvent::ReviewCompleted` or `ClientEvent::ReviewFailed`.
+    pub async fn start_review(
+        &self,
+        target: ReviewTarget,
+        model: steer_core::config::model::ModelId,
+    ) -> GrpcResult<()> {
+        let session_id = self.active_session_id().await?;
+        let request = proto::RunReviewRequest {
+            session_id,
+            target: target.to_string(),
+            model: Some(model_to_proto(model)),
+        };
+        let mut client = self.client.lock().await.clone();
+        let evt_tx = self.client_event_tx.clone();
+
+        tokio::spawn(async move {
+            let event = match client.run_review(Request::new(request)).await {
+                Ok(response) => ClientEvent::ReviewCompleted {
+                    report: Box::new(proto_to_review_report(response.into_inner())),
+                },
+                Err(status) => ClientEvent::ReviewFailed {
+                    message: status.message().to_string(),
+                },
+            };
+            let _ = evt_tx.send(event).await;
+        });
+
+        Ok(())
+    }
+
+    /// Lists the symbols defined in a workspace file, for `@file#symbol` completion.
+    pub async fn list_file_symbols(&self, path: &str) -> GrpcResult<Vec<FileSymbol>> {
+        let session_id = self.active_session_id().await?;
+        let request = Request::new(proto::ListFileSymbolsRequest {
+            session_id,
+            path: path.to_string(),
+        });
+
+        let response = self
+            .client
+            .lock()
+            .await
+            .list_file_symbols(request)
+            .await
+            .map_err(Box::new)?
+            .into_inner();
+
+        Ok(response
+            .symbols
+            .into_iter()
+            .map(proto_to_file_symbol)
+            .collect())
+    }
+
+    /// Expands an `@path` or `@path#symbol` mention into the text sent to the model.
+    pub async fn expand_file_mention(
+        &self,
+        path: &str,
+        symbol: Option<&str>,
+        outline_large_files: bool,
+    ) -> GrpcResult<String> {
+        let session_id = self.active_session_id().await?;
+        let request = Request::new(proto::ExpandFileMentionRequest {
+            session_id,
+            path: path.to_string(),
+            symbol: symbol.map(str::to_string),
+            outline_large_files,
+        });
+
+        let response = self
+            .client
+            .lock()
+            .await
+            .expand_file_mention(request)
+            .await
+            .map_err(Box::new)?
+            .into_inner();
+
+        Ok(response.text)
+    }
+
+    async fn active_session_id(&self) -> GrpcResult<String> {
+        self.session_id
+            .lock()
+            .await
+            .clone()
+            .ok_or_else(|| GrpcError::InvalidSessionState {
+                reason: "No active session".to_string(),
+            })
     }
 
     pub async fn list_workspaces(
@@ -1123,6 +1819,125 @@ impl AgentClient {
 
         Ok(proto_to_workspace_status(status)?)
     }
+
+    pub async fn create_scheduled_task(
+        &self,
+        schedule: String,
+        prompt: String,
+        config: &SessionConfig,
+        max_total_tokens: Option<u64>,
+    ) -> GrpcResult<proto::ScheduledTask> {
+        let request = Request::new(proto::CreateScheduledTaskRequest {
+            schedule,
+            prompt,
+            config: Some(session_config_to_proto(config)),
+            max_total_tokens,
+        });
+
+        let response = self
+            .client
+            .lock()
+            .await
+            .create_scheduled_task(request)
+            .await
+            .map_err(Box::new)?;
+
+        response
+            .into_inner()
+            .task
+            .ok_or_else(|| GrpcError::InvalidSessionState {
+                reason: "Scheduled task missing from response".to_string(),
+            })
+    }
+
+    pub async fn list_scheduled_tasks(&self) -> GrpcResult<Vec<proto::ScheduledTask>> {
+        let request = Request::new(proto::ListScheduledTasksRequest {});
+
+        let response = self
+            .client
+            .lock()
+            .await
+            .list_scheduled_tasks(request)
+            .await
+            .map_err(Box::new)?;
+
+        Ok(response.into_inner().tasks)
+    }
+
+    /// Stream the tool audit log entries matching `filter` as JSONL chunks.
+    pub async fn export_audit(
+        &self,
+        filter: &ToolAuditFilter,
+    ) -> GrpcResult<BoxStream<'static, GrpcResult<String>>> {
+        let request = Request::new(proto::ExportAuditRequest {
+            session_id: filter.session_id.map(|id| id.to_string()),
+            tool_name: filter.tool_name.clone(),
+            since: filter
+                .since
+                .map(|since| prost_types::Timestamp::from(std::time::SystemTime::from(since))),
+        });
+
+        let stream = self
+            .client
+            .lock()
+            .await
+            .export_audit(request)
+            .await
+            .map_err(Box::new)?
+            .into_inner();
+
+        Ok(stream
+            .map(|response| {
+                response
+                    .map(|response| response.jsonl)
+                    .map_err(|status| GrpcError::from(Box::new(status)))
+            })
+            .boxed())
+    }
+
+    pub async fn get_scheduled_task(&self, id: &str) -> GrpcResult<Option<proto::ScheduledTask>> {
+        let request = Request::new(proto::GetScheduledTaskRequest { id: id.to_string() });
+
+        match self.client.lock().await.get_scheduled_task(request).await {
+            Ok(response) => Ok(response.into_inner().task),
+            Err(status) if status.code() == tonic::Code::NotFound => Ok(None),
+            Err(e) => Err(GrpcError::from(e)),
+        }
+    }
+
+    pub async fn delete_scheduled_task(&self, id: &str) -> GrpcResult<bool> {
+        let request = Request::new(proto::DeleteScheduledTaskRequest { id: id.to_string() });
+
+        match self
+            .client
+            .lock()
+            .await
+            .delete_scheduled_task(request)
+            .await
+        {
+            Ok(_) => {
+                info!("Deleted scheduled task: {}", id);
+                Ok(true)
+            }
+            Err(status) if status.code() == tonic::Code::NotFound => Ok(false),
+            Err(e) => Err(GrpcError::from(e)),
+        }
+    }
+
+    /// Start a run of the task now; returns the id of the session it runs in.
+    pub async fn run_scheduled_task_now(&self, id: &str) -> GrpcResult<String> {
+        let request = Request::new(proto::RunScheduledTaskNowRequest { id: id.to_string() });
+
+        let response = self
+            .client
+            .lock()
+            .await
+            .run_scheduled_task_now(request)
+            .await
+            .map_err(Box::new)?;
+
+        Ok(response.into_inner().session_id)
+    }
 }
 
 #[cfg(test)]
@@ -1,7 +1,8 @@
 use super::conversions::*;
 use steer_core::session::state::{
-    ApprovalRules, BackendConfig, RemoteAuth, SessionToolConfig, ToolApprovalPolicy, ToolFilter,
-    ToolVisibility, UnapprovedBehavior, WorkspaceConfig,
+    ApprovalRules, BackendConfig, BashDenylistConfig, RemoteAuth, SessionToolConfig,
+    ToolApprovalPolicy, ToolFilter, ToolResultBudget, ToolVisibility, UnapprovedBehavior,
+    WorkspaceConfig,
 };
 use steer_core::tools::McpTransport;
 
@@ -92,6 +93,14 @@ prop_compose! {
         pre_approved_tools in prop::collection::vec("[a-z]+", 0..5),
         metadata_key in "[a-z]+",
         metadata_value in "[a-z0-9]+",
+        env in prop::collection::hash_map("[A-Z_]+", "[a-z0-9:/${}]*", 0..3),
+        max_result_bytes in prop::option::of(1usize..1_000_000),
+        max_turn_bytes in prop::option::of(1usize..1_000_000),
+        deny in prop::collection::vec("[a-z]+ [a-z -]+", 0..3),
+        allow in prop::collection::vec("[a-z]+ [a-z -]+", 0..3),
+        disable_defaults in any::<bool>(),
+        allow_network in any::<bool>(),
+        allow_outside_workspace in any::<bool>(),
     ) -> SessionToolConfig {
         let mut metadata = HashMap::new();
         metadata.insert(metadata_key, metadata_value);
@@ -107,6 +116,18 @@ prop_compose! {
             visibility,
             approval_policy,
             metadata,
+            result_budget: ToolResultBudget {
+                max_result_bytes,
+                max_turn_bytes,
+            },
+            env,
+            bash_denylist: BashDenylistConfig {
+                deny,
+                allow,
+                disable_defaults,
+            },
+            allow_network,
+            allow_outside_workspace,
         }
     }
 }
@@ -133,6 +154,11 @@ proptest! {
 
         prop_assert_eq!(config.visibility, roundtrip.visibility);
         prop_assert_eq!(config.approval_policy, roundtrip.approval_policy);
+        prop_assert_eq!(config.result_budget, roundtrip.result_budget);
+        prop_assert_eq!(config.env, roundtrip.env);
+        prop_assert_eq!(config.bash_denylist, roundtrip.bash_denylist);
+        prop_assert_eq!(config.allow_network, roundtrip.allow_network);
+        prop_assert_eq!(config.allow_outside_workspace, roundtrip.allow_outside_workspace);
 
         prop_assert_eq!(config.backends.len(), roundtrip.backends.len());
         for (b1, b2) in config.backends.iter().zip(roundtrip.backends.iter()) {
@@ -255,19 +281,75 @@ mod id_preservation_tests {
     #[test]
     fn test_op_id_preserved_in_operation_completed() {
         let op_id = OpId::from(Uuid::new_v4());
-        let event = SessionEvent::OperationCompleted { op_id };
+        let event = SessionEvent::OperationCompleted {
+            op_id,
+            timing: None,
+        };
 
         let proto_response = session_event_to_proto(event, 1).unwrap();
         let client_event = proto_to_client_event(proto_response).unwrap().unwrap();
 
         match client_event {
-            ClientEvent::ProcessingCompleted { op_id: received } => {
+            ClientEvent::ProcessingCompleted {
+                op_id: received,
+                timing,
+            } => {
                 assert_eq!(op_id, received);
+                assert!(timing.is_none());
             }
             other => panic!("Expected ProcessingCompleted, got {other:?}"),
         }
     }
 
+    #[test]
+    fn test_operation_timing_roundtrip() {
+        use steer_core::app::domain::event::{OperationTiming, TimingSpan, TimingSpanKind};
+
+        let op_id = OpId::from(Uuid::new_v4());
+        let timing = OperationTiming {
+            total_ms: 2_400,
+            spans: vec![
+                TimingSpan {
+                    kind: TimingSpanKind::QueueWait,
+                    duration_ms: 15,
+                },
+                TimingSpan {
+                    kind: TimingSpanKind::TimeToFirstToken,
+                    duration_ms: 800,
+                },
+                TimingSpan {
+                    kind: TimingSpanKind::Streaming,
+                    duration_ms: 1_200,
+                },
+                TimingSpan {
+                    kind: TimingSpanKind::ToolExecution {
+                        tool_name: "bash".to_string(),
+                    },
+                    duration_ms: 300,
+                },
+                TimingSpan {
+                    kind: TimingSpanKind::ApprovalWait,
+                    duration_ms: 85,
+                },
+            ],
+        };
+        let event = SessionEvent::OperationCompleted {
+            op_id,
+            timing: Some(timing.clone()),
+        };
+
+        let proto_response = session_event_to_proto(event, 1).unwrap();
+        let client_event = proto_to_client_event(proto_response).unwrap().unwrap();
+
+        match client_event {
+            ClientEvent::ProcessingCompleted {
+                timing: Some(received),
+                ..
+            } => assert_eq!(received, timing),
+            other => panic!("Expected ProcessingCompleted with timing, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_op_id_preserved_in_operation_cancelled() {
         let op_id = OpId::from(Uuid::new_v4());
@@ -458,6 +540,74 @@ mod event_conversion_tests {
         }
     }
 
+    #[test]
+    fn test_context_usage_event_roundtrip() {
+        let event = SessionEvent::ContextUsage {
+            used: 1_234,
+            limit: Some(200_000),
+        };
+
+        let proto = session_event_to_proto(event, 5).unwrap();
+        let client_event = proto_to_client_event(proto).unwrap().unwrap();
+
+        match client_event {
+            ClientEvent::ContextUsage { used, limit } => {
+                assert_eq!(used, 1_234);
+                assert_eq!(limit, Some(200_000));
+            }
+            other => panic!("Expected ContextUsage, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_best_of_candidate_event_roundtrip() {
+        use steer_core::app::conversation::AssistantContent;
+        use steer_core::app::domain::event::BestOfCandidate;
+        use steer_core::app::domain::types::{MessageId, OpId};
+
+        let op_id = OpId::new();
+        let candidate_op_id = OpId::new();
+        let message_id = MessageId::new();
+        let event = SessionEvent::BestOfCandidateReady {
+            op_id,
+            candidate: BestOfCandidate {
+                index: 1,
+                op_id: candidate_op_id,
+                message_id: message_id.clone(),
+                content: vec![AssistantContent::Text {
+                    text: "read_header".to_string(),
+                }],
+                usage: Some(steer_core::api::provider::TokenUsage::new(12, 3, 15)),
+                error: None,
+            },
+        };
+
+        let proto = session_event_to_proto(event, 6).unwrap();
+        let client_event = proto_to_client_event(proto).unwrap().unwrap();
+
+        match client_event {
+            ClientEvent::BestOfCandidateReady {
+                op_id: got_op,
+                candidate,
+            } => {
+                assert_eq!(got_op, op_id);
+                assert_eq!(candidate.index, 1);
+                assert_eq!(candidate.op_id, candidate_op_id);
+                assert_eq!(candidate.message_id, message_id);
+                assert!(matches!(
+                    candidate.content.as_slice(),
+                    [AssistantContent::Text { text }] if text == "read_header"
+                ));
+                assert_eq!(
+                    candidate.usage,
+                    Some(steer_core::api::provider::TokenUsage::new(12, 3, 15))
+                );
+                assert!(candidate.error.is_none());
+            }
+            other => panic!("Expected BestOfCandidateReady, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_llm_usage_event_known_kind_values_map_exhaustively() {
         let op_id = Uuid::new_v4().to_string();
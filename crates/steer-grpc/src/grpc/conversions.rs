@@ -6,18 +6,24 @@ use steer_core::app::conversation::{
     UserContent,
 };
 use steer_core::app::domain::types::SessionId;
-use steer_core::app::domain::{SessionEvent, StreamDelta, ToolCallDelta as CoreToolCallDelta};
+use steer_core::app::domain::{
+    ModelSwitchMode, SessionEvent, StreamDelta, ToolCallDelta as CoreToolCallDelta,
+};
 
+use steer_core::commit::{CommitDraft, CommitOutcome};
+use steer_core::isolation::{
+    FileChange, FileChangeKind, IsolatedWorkspace, IsolationKind, IsolationMode,
+};
+use steer_core::review::{ReviewFinding, ReviewReport, ReviewSeverity};
 use steer_core::session::state::{
-    ApprovalRules, ApprovalRulesOverrides, BackendConfig, RemoteAuth, SessionConfig,
-    SessionPolicyOverrides, SessionToolConfig, ToolApprovalPolicy, ToolApprovalPolicyOverrides,
-    ToolFilter, ToolRule, ToolRuleOverrides, ToolVisibility, UnapprovedBehavior, WorkspaceConfig,
+    ApprovalRules, ApprovalRulesOverrides, AutoSummaryConfig, BackendConfig, BashDenylistConfig,
+    RemoteAuth, SessionConfig, SessionPolicyOverrides, SessionToolConfig, ToolApprovalPolicy,
+    ToolApprovalPolicyOverrides, ToolFilter, ToolResultBudget, ToolRule, ToolRuleOverrides,
+    ToolVisibility, UnapprovedBehavior, WorkspaceConfig,
 };
 use steer_proto::agent::v1 as proto;
-use steer_proto::common::v1 as common;
 use steer_proto::remote_workspace::v1 as remote_proto;
 use steer_tools::ToolCall;
-use steer_tools::tools::todo::{TodoItem, TodoPriority, TodoStatus, TodoWriteFileOperation};
 use uuid::Uuid;
 
 use crate::client_api::{
@@ -52,320 +58,6 @@ pub fn proto_to_model(
     ))
 }
 
-fn agent_workspace_revision_to_proto(
-    revision: &steer_tools::result::AgentWorkspaceRevision,
-) -> proto::AgentWorkspaceRevision {
-    proto::AgentWorkspaceRevision {
-        vcs_kind: revision.vcs_kind.clone(),
-        revision_id: revision.revision_id.clone(),
-        summary: revision.summary.clone(),
-        change_id: revision.change_id.clone().unwrap_or_default(),
-    }
-}
-
-fn agent_workspace_info_to_proto(
-    info: &steer_tools::result::AgentWorkspaceInfo,
-) -> proto::AgentWorkspaceInfo {
-    proto::AgentWorkspaceInfo {
-        workspace_id: info.workspace_id.clone().unwrap_or_default(),
-        revision: info
-            .revision
-            .as_ref()
-            .map(agent_workspace_revision_to_proto),
-    }
-}
-/// Convert steer_tools ToolResult to protobuf
-fn steer_tools_result_to_proto(
-    result: &steer_tools::result::ToolResult,
-) -> Result<proto::ToolResult, ConversionError> {
-    use proto::tool_result::Result as ProtoResult;
-    use steer_tools::result::ToolResult as CoreResult;
-
-    let proto_result = match result {
-        CoreResult::Search(r) => ProtoResult::Search(common::SearchResult {
-            matches: r
-                .matches
-                .iter()
-                .map(|m| common::SearchMatch {
-                    file_path: m.file_path.clone(),
-                    line_number: m.line_number as u64,
-                    line_content: m.line_content.clone(),
-                    column_range: m.column_range.map(|(start, end)| common::ColumnRange {
-                        start: start as u64,
-                        end: end as u64,
-                    }),
-                })
-                .collect(),
-            total_files_searched: r.total_files_searched as u64,
-            search_completed: r.search_completed,
-        }),
-        CoreResult::FileList(r) => ProtoResult::FileList(common::FileListResult {
-            entries: r
-                .entries
-                .iter()
-                .map(|e| common::FileEntry {
-                    path: e.path.clone(),
-                    is_directory: e.is_directory,
-                    size: e.size,
-                    permissions: e.permissions.clone(),
-                })
-                .collect(),
-            base_path: r.base_path.clone(),
-        }),
-        CoreResult::FileContent(r) => ProtoResult::FileContent(common::FileContentResult {
-            content: r.content.clone(),
-            file_path: r.file_path.clone(),
-            line_count: r.line_count as u64,
-            truncated: r.truncated,
-        }),
-        CoreResult::Edit(r) => ProtoResult::Edit(common::EditResult {
-            file_path: r.file_path.clone(),
-            changes_made: r.changes_made as u64,
-            file_created: r.file_created,
-            old_content: r.old_content.clone(),
-            new_content: r.new_content.clone(),
-        }),
-        CoreResult::Bash(r) => ProtoResult::Bash(common::BashResult {
-            stdout: r.stdout.clone(),
-            stderr: r.stderr.clone(),
-            exit_code: r.exit_code,
-            command: r.command.clone(),
-            timed_out: r.timed_out,
-        }),
-        CoreResult::Glob(r) => ProtoResult::Glob(common::GlobResult {
-            matches: r.matches.clone(),
-            pattern: r.pattern.clone(),
-        }),
-        CoreResult::TodoRead(r) => ProtoResult::TodoRead(common::TodoListResult {
-            todos: r.todos.iter().map(convert_todo_item_to_proto).collect(),
-        }),
-        CoreResult::TodoWrite(r) => ProtoResult::TodoWrite(common::TodoWriteResult {
-            todos: r.todos.iter().map(convert_todo_item_to_proto).collect(),
-            operation: convert_todo_write_file_operation_to_proto(&r.operation) as i32,
-        }),
-        CoreResult::Fetch(r) => ProtoResult::Fetch(proto::FetchResult {
-            url: r.url.clone(),
-            content: r.content.clone(),
-        }),
-        CoreResult::Agent(r) => ProtoResult::Agent(proto::AgentResult {
-            content: r.content.clone(),
-            workspace: r.workspace.as_ref().map(agent_workspace_info_to_proto),
-            session_id: r.session_id.clone().unwrap_or_default(),
-        }),
-        CoreResult::External(r) => ProtoResult::External(proto::ExternalResult {
-            tool_name: r.tool_name.clone(),
-            payload: r.payload.clone(),
-        }),
-        CoreResult::Error(e) => ProtoResult::Error(tool_error_to_proto(e)),
-    };
-
-    Ok(proto::ToolResult {
-        result: Some(proto_result),
-    })
-}
-
-fn proto_to_agent_workspace_revision(
-    revision: proto::AgentWorkspaceRevision,
-) -> steer_tools::result::AgentWorkspaceRevision {
-    steer_tools::result::AgentWorkspaceRevision {
-        vcs_kind: revision.vcs_kind,
-        revision_id: revision.revision_id,
-        summary: revision.summary,
-        change_id: if revision.change_id.is_empty() {
-            None
-        } else {
-            Some(revision.change_id)
-        },
-    }
-}
-
-fn proto_to_agent_workspace_info(
-    info: proto::AgentWorkspaceInfo,
-) -> steer_tools::result::AgentWorkspaceInfo {
-    steer_tools::result::AgentWorkspaceInfo {
-        workspace_id: if info.workspace_id.is_empty() {
-            None
-        } else {
-            Some(info.workspace_id)
-        },
-        revision: info.revision.map(proto_to_agent_workspace_revision),
-    }
-}
-
-/// Convert ToolError to protobuf
-fn tool_error_to_proto(error: &steer_tools::error::ToolError) -> proto::ToolError {
-    use proto::tool_error::ErrorType;
-    use steer_tools::error::ToolError;
-
-    let error_type = match error {
-        ToolError::UnknownTool(name) => ErrorType::UnknownTool(name.clone()),
-        ToolError::InvalidParams { tool_name, message } => {
-            ErrorType::InvalidParams(proto::InvalidParamsError {
-                tool_name: tool_name.clone(),
-                message: message.clone(),
-            })
-        }
-        ToolError::Execution(error) => ErrorType::Execution(proto::ExecutionError {
-            tool_name: error.tool_name().to_string(),
-            message: error.to_string(),
-        }),
-        ToolError::Cancelled(name) => ErrorType::Cancelled(name.clone()),
-        ToolError::Timeout(name) => ErrorType::Timeout(name.clone()),
-        ToolError::DeniedByUser(name) => ErrorType::DeniedByUser(name.clone()),
-        ToolError::DeniedByPolicy(name) => ErrorType::DeniedByPolicy(name.clone()),
-        ToolError::InternalError(msg) => ErrorType::InternalError(msg.clone()),
-    };
-
-    proto::ToolError {
-        error_type: Some(error_type),
-    }
-}
-
-/// Convert protobuf ToolResult to steer_tools ToolResult
-fn proto_to_steer_tools_result(
-    proto_result: proto::ToolResult,
-) -> Result<steer_tools::result::ToolResult, ConversionError> {
-    use proto::tool_result::Result as ProtoResult;
-    use steer_tools::result::{
-        AgentResult, BashResult, EditResult, ExternalResult, FetchResult, FileContentResult,
-        FileEntry, FileListResult, GlobResult, SearchMatch, SearchResult, TodoListResult,
-        TodoWriteResult, ToolResult,
-    };
-
-    let result = proto_result
-        .result
-        .ok_or_else(|| ConversionError::MissingField {
-            field: "tool_result.result".to_string(),
-        })?;
-
-    Ok(match result {
-        ProtoResult::Search(r) => ToolResult::Search(SearchResult {
-            matches: r
-                .matches
-                .into_iter()
-                .map(|m| SearchMatch {
-                    file_path: m.file_path,
-                    line_number: m.line_number as usize,
-                    line_content: m.line_content,
-                    column_range: m
-                        .column_range
-                        .map(|cr| (cr.start as usize, cr.end as usize)),
-                })
-                .collect(),
-            total_files_searched: r.total_files_searched as usize,
-            search_completed: r.search_completed,
-        }),
-        ProtoResult::FileList(r) => ToolResult::FileList(FileListResult {
-            entries: r
-                .entries
-                .into_iter()
-                .map(|e| FileEntry {
-                    path: e.path,
-                    is_directory: e.is_directory,
-                    size: e.size,
-                    permissions: e.permissions,
-                })
-                .collect(),
-            base_path: r.base_path,
-        }),
-        ProtoResult::FileContent(r) => ToolResult::FileContent(FileContentResult {
-            content: r.content,
-            file_path: r.file_path,
-            line_count: r.line_count as usize,
-            truncated: r.truncated,
-        }),
-        ProtoResult::Edit(r) => ToolResult::Edit(EditResult {
-            file_path: r.file_path,
-            changes_made: r.changes_made as usize,
-            file_created: r.file_created,
-            old_content: r.old_content,
-            new_content: r.new_content,
-        }),
-        ProtoResult::Bash(r) => ToolResult::Bash(BashResult {
-            stdout: r.stdout,
-            stderr: r.stderr,
-            exit_code: r.exit_code,
-            command: r.command,
-            timed_out: r.timed_out,
-        }),
-        ProtoResult::Glob(r) => ToolResult::Glob(GlobResult {
-            matches: r.matches,
-            pattern: r.pattern,
-        }),
-        ProtoResult::TodoRead(r) => ToolResult::TodoRead(TodoListResult {
-            todos: r
-                .todos
-                .into_iter()
-                .map(convert_proto_to_todo_item)
-                .collect(),
-        }),
-        ProtoResult::TodoWrite(r) => ToolResult::TodoWrite(TodoWriteResult {
-            todos: r
-                .todos
-                .into_iter()
-                .map(convert_proto_to_todo_item)
-                .collect(),
-            operation: convert_proto_to_todo_write_file_operation(
-                steer_proto::common::v1::TodoWriteFileOperation::try_from(r.operation)
-                    .unwrap_or(steer_proto::common::v1::TodoWriteFileOperation::OperationUnset),
-            ),
-        }),
-        ProtoResult::Fetch(r) => ToolResult::Fetch(FetchResult {
-            url: r.url,
-            content: r.content,
-        }),
-        ProtoResult::Agent(r) => ToolResult::Agent(AgentResult {
-            content: r.content,
-            session_id: if r.session_id.is_empty() {
-                None
-            } else {
-                Some(r.session_id)
-            },
-            workspace: r.workspace.map(proto_to_agent_workspace_info),
-        }),
-        ProtoResult::External(r) => ToolResult::External(ExternalResult {
-            tool_name: r.tool_name,
-            payload: r.payload,
-        }),
-        ProtoResult::Error(e) => ToolResult::Error(proto_to_tool_error(e)?),
-    })
-}
-
-/// Convert protobuf ToolError to steer_tools ToolError
-fn proto_to_tool_error(
-    proto_error: proto::ToolError,
-) -> Result<steer_tools::error::ToolError, ConversionError> {
-    use proto::tool_error::ErrorType;
-    use steer_tools::error::{ToolError, ToolExecutionError};
-
-    let error_type = proto_error
-        .error_type
-        .ok_or_else(|| ConversionError::MissingField {
-            field: "tool_error.error_type".to_string(),
-        })?;
-
-    Ok(match error_type {
-        ErrorType::UnknownTool(name) => ToolError::UnknownTool(name),
-        ErrorType::InvalidParams(e) => ToolError::InvalidParams {
-            tool_name: e.tool_name,
-            message: e.message,
-        },
-        ErrorType::Execution(e) => ToolError::Execution(ToolExecutionError::External {
-            tool_name: e.tool_name,
-            message: e.message,
-        }),
-        ErrorType::Cancelled(name) => ToolError::Cancelled(name),
-        ErrorType::Timeout(name) => ToolError::Timeout(name),
-        ErrorType::DeniedByUser(name) => ToolError::DeniedByUser(name),
-        ErrorType::DeniedByPolicy(name) => ToolError::DeniedByPolicy(name),
-        ErrorType::InternalError(msg) => ToolError::InternalError(msg),
-        ErrorType::Io(e) => ToolError::Execution(ToolExecutionError::External {
-            tool_name: e.tool_name,
-            message: e.message,
-        }),
-    })
-}
-
 pub(crate) fn message_to_proto(
     message: ConversationMessage,
 ) -> Result<proto::Message, ConversionError> {
@@ -383,6 +75,11 @@ pub(crate) fn message_to_proto(
                                 core_image_to_proto(image),
                             )),
                         },
+                        UserContent::Attachment { attachment } => proto::UserContent {
+                            content: Some(proto::user_content::Content::Attachment(
+                                core_attachment_to_proto(attachment),
+                            )),
+                        },
                         UserContent::CommandExecution {
                             command,
                             stdout,
@@ -471,7 +168,7 @@ pub(crate) fn message_to_proto(
             result,
             ..
         } => {
-            let proto_result = steer_tools_result_to_proto(result)?;
+            let proto_result = steer_proto::convert::tool_result_to_proto(result);
             let tool_msg = proto::ToolMessage {
                 tool_use_id: tool_use_id.clone(),
                 result: Some(proto_result),
@@ -689,6 +386,18 @@ pub(crate) fn session_tool_config_to_proto(config: &SessionToolConfig) -> proto:
         metadata: config.metadata.clone(),
         visibility: Some(tool_visibility_to_proto(&config.visibility)),
         approval_policy: Some(tool_approval_policy_to_proto(&config.approval_policy)),
+        result_budget: Some(proto::ToolResultBudget {
+            max_result_bytes: config.result_budget.max_result_bytes.map(|b| b as u64),
+            max_turn_bytes: config.result_budget.max_turn_bytes.map(|b| b as u64),
+        }),
+        env: config.env.clone(),
+        bash_denylist: Some(proto::BashDenylist {
+            deny: config.bash_denylist.deny.clone(),
+            allow: config.bash_denylist.allow.clone(),
+            disable_defaults: config.bash_denylist.disable_defaults,
+        }),
+        allow_network: Some(config.allow_network),
+        allow_outside_workspace: config.allow_outside_workspace,
     }
 }
 
@@ -737,6 +446,12 @@ pub(crate) fn session_config_to_proto(config: &SessionConfig) -> proto::SessionC
             threshold_percent: config.auto_compaction.threshold_percent,
         }),
         title: config.title.clone(),
+        isolation: isolation_mode_to_proto(config.isolation).into(),
+        isolated_workspace: config
+            .isolated_workspace
+            .as_ref()
+            .map(isolated_workspace_to_proto),
+        auto_summary: Some(auto_summary_config_to_proto(&config.auto_summary)),
     }
 }
 
@@ -845,6 +560,11 @@ pub(crate) fn proto_to_session_config(
                 threshold_percent: ac.threshold_percent,
             })
             .unwrap_or_default(),
+        isolation: proto_to_isolation_mode(proto_config.isolation),
+        isolated_workspace: proto_config
+            .isolated_workspace
+            .map(proto_to_isolated_workspace),
+        auto_summary: proto_to_auto_summary_config(proto_config.auto_summary)?,
     })
 }
 
@@ -974,6 +694,93 @@ pub(crate) fn proto_to_workspace_status(
     })
 }
 
+pub(crate) fn context_report_to_proto(
+    report: steer_core::app::ContextReport,
+) -> proto::GetContextReportResponse {
+    proto::GetContextReportResponse {
+        segments: report
+            .segments
+            .into_iter()
+            .map(|segment| proto::ContextSegment {
+                kind: segment.kind.as_str().to_string(),
+                tokens: segment.tokens,
+                items: segment
+                    .items
+                    .into_iter()
+                    .map(|item| proto::ContextItem {
+                        label: item.label,
+                        tokens: item.tokens,
+                    })
+                    .collect(),
+            })
+            .collect(),
+        context_window_tokens: report.context_window_tokens,
+        max_output_tokens: report.max_output_tokens,
+    }
+}
+
+pub(crate) fn proto_to_context_report(
+    response: proto::GetContextReportResponse,
+) -> Result<steer_core::app::ContextReport, ConversionError> {
+    let segments = response
+        .segments
+        .into_iter()
+        .map(|segment| {
+            let kind =
+                steer_core::app::ContextSegmentKind::parse(&segment.kind).ok_or_else(|| {
+                    ConversionError::InvalidValue {
+                        field: "ContextSegment.kind".to_string(),
+                        value: segment.kind.clone(),
+                    }
+                })?;
+            Ok(steer_core::app::ContextSegment {
+                kind,
+                tokens: segment.tokens,
+                items: segment
+                    .items
+                    .into_iter()
+                    .map(|item| steer_core::app::ContextItem {
+                        label: item.label,
+                        tokens: item.tokens,
+                    })
+                    .collect(),
+            })
+        })
+        .collect::<Result<Vec<_>, ConversionError>>()?;
+
+    Ok(steer_core::app::ContextReport {
+        segments,
+        context_window_tokens: response.context_window_tokens,
+        max_output_tokens: response.max_output_tokens,
+    })
+}
+
+pub(crate) fn file_symbol_to_proto(
+    symbol: steer_workspace::symbols::FileSymbol,
+) -> proto::FileSymbol {
+    proto::FileSymbol {
+        name: symbol.name,
+        kind: symbol.kind,
+        parent: symbol.parent,
+        depth: symbol.depth as u32,
+        start_line: symbol.start_line as u32,
+        end_line: symbol.end_line as u32,
+    }
+}
+
+pub(crate) fn proto_to_file_symbol(
+    symbol: proto::FileSymbol,
+) -> steer_workspace::symbols::FileSymbol {
+    steer_workspace::symbols::FileSymbol {
+        name: symbol.name,
+        kind: symbol.kind,
+        parent: symbol.parent,
+        depth: symbol.depth as usize,
+        start_line: symbol.start_line as usize,
+        end_line: symbol.end_line as usize,
+    }
+}
+
 pub(crate) fn proto_to_repo_info(
     info: proto::RepoInfo,
 ) -> Result<steer_workspace::RepoInfo, ConversionError> {
@@ -1044,15 +851,15 @@ fn proto_to_vcs_info(
     };
 
     let status = match info.status {
-        Some(remote_proto::vcs_info::Status::GitStatus(status)) => {
-            steer_workspace::VcsStatus::Git(proto_to_git_status(status)?)
+        Some(remote_proto::vcs_info::Status::GitSummary(summary)) => {
+            steer_workspace::VcsStatus::Git(proto_to_git_summary(summary)?)
         }
         Some(remote_proto::vcs_info::Status::JjStatus(status)) => {
             steer_workspace::VcsStatus::Jj(proto_to_jj_status(status)?)
         }
         None => match kind {
             steer_workspace::VcsKind::Git => steer_workspace::VcsStatus::Git(
-                steer_workspace::GitStatus::unavailable("Missing git status"),
+                steer_workspace::GitSummary::unavailable("Missing git status"),
             ),
             steer_workspace::VcsKind::Jj => steer_workspace::VcsStatus::Jj(
                 steer_workspace::JjStatus::unavailable("Missing jj status"),
@@ -1067,9 +874,9 @@ fn proto_to_vcs_info(
     })
 }
 
-fn proto_to_git_status(
-    status: remote_proto::GitStatus,
-) -> Result<steer_workspace::GitStatus, ConversionError> {
+fn proto_to_git_summary(
+    status: remote_proto::GitSummary,
+) -> Result<steer_workspace::GitSummary, ConversionError> {
     let head = match status.head {
         Some(head) => {
             let kind = remote_proto::GitHeadKind::try_from(head.kind).map_err(|_| {
@@ -1136,19 +943,19 @@ fn proto_to_git_status(
         })
         .collect::<Result<Vec<_>, ConversionError>>()?;
 
-    let recent_commits = status
-        .recent_commits
-        .into_iter()
-        .map(|commit| steer_workspace::GitCommitSummary {
-            id: commit.id,
-            summary: commit.summary,
-        })
-        .collect();
+    let upstream = status
+        .upstream
+        .map(|upstream| steer_workspace::GitUpstream {
+            name: upstream.name,
+            ahead: upstream.ahead,
+            behind: upstream.behind,
+        });
 
-    Ok(steer_workspace::GitStatus {
+    Ok(steer_workspace::GitSummary {
         head,
+        upstream,
         entries,
-        recent_commits,
+        omitted_entries: status.omitted_entries,
         error: status.error,
     })
 }
@@ -1262,22 +1069,23 @@ fn vcs_info_to_proto(info: &steer_workspace::VcsInfo) -> remote_proto::VcsInfo {
                 })
                 .collect();
 
-            let recent_commits = status
-                .recent_commits
-                .iter()
-                .map(|commit| remote_proto::GitCommitSummary {
-                    id: commit.id.clone(),
-                    summary: commit.summary.clone(),
-                })
-                .collect();
+            let upstream = status
+                .upstream
+                .as_ref()
+                .map(|upstream| remote_proto::GitUpstream {
+                    name: upstream.name.clone(),
+                    ahead: upstream.ahead,
+                    behind: upstream.behind,
+                });
 
-            let git_status = remote_proto::GitStatus {
+            let git_summary = remote_proto::GitSummary {
                 head,
                 entries,
-                recent_commits,
                 error: status.error.clone(),
+                upstream,
+                omitted_entries: status.omitted_entries,
             };
-            Some(remote_proto::vcs_info::Status::GitStatus(git_status))
+            Some(remote_proto::vcs_info::Status::GitSummary(git_summary))
         }
         steer_workspace::VcsStatus::Jj(status) => {
             let changes = status
@@ -1525,6 +1333,24 @@ pub(crate) fn proto_to_tool_config(proto_config: proto::SessionToolConfig) -> Se
         approval_policy: proto_to_tool_approval_policy(proto_config.approval_policy),
         visibility: proto_to_tool_visibility(proto_config.visibility),
         metadata: proto_config.metadata,
+        result_budget: proto_config
+            .result_budget
+            .map(|budget| ToolResultBudget {
+                max_result_bytes: budget.max_result_bytes.map(|b| b as usize),
+                max_turn_bytes: budget.max_turn_bytes.map(|b| b as usize),
+            })
+            .unwrap_or_default(),
+        env: proto_config.env,
+        bash_denylist: proto_config
+            .bash_denylist
+            .map(|denylist| BashDenylistConfig {
+                deny: denylist.deny,
+                allow: denylist.allow,
+                disable_defaults: denylist.disable_defaults,
+            })
+            .unwrap_or_default(),
+        allow_network: proto_config.allow_network.unwrap_or(true),
+        allow_outside_workspace: proto_config.allow_outside_workspace,
     }
 }
 
@@ -1558,18 +1384,22 @@ pub(crate) fn proto_to_message(
                 .content
                 .into_iter()
                 .filter_map(|user_content| {
-                    user_content.content.map(|content| match content {
-                        user_content::Content::Text(text) => UserContent::Text { text },
-                        user_content::Content::Image(image) => UserContent::Image {
+                    user_content.content.and_then(|content| match content {
+                        user_content::Content::Text(text) => Some(UserContent::Text { text }),
+                        user_content::Content::Image(image) => Some(UserContent::Image {
                             image: proto_image_to_core(image),
-                        },
+                        }),
+                        user_content::Content::Attachment(attachment) => {
+                            proto_attachment_to_core(attachment)
+                                .map(|attachment| UserContent::Attachment { attachment })
+                        }
                         user_content::Content::CommandExecution(cmd) => {
-                            UserContent::CommandExecution {
+                            Some(UserContent::CommandExecution {
                                 command: cmd.command,
                                 stdout: cmd.stdout,
                                 stderr: cmd.stderr,
                                 exit_code: cmd.exit_code,
-                            }
+                            })
                         }
                     })
                 })
@@ -1636,7 +1466,7 @@ pub(crate) fn proto_to_message(
         }
         message::Message::Tool(tool_msg) => {
             if let Some(proto_result) = tool_msg.result {
-                let tool_result = proto_to_steer_tools_result(proto_result)?;
+                let tool_result = steer_proto::convert::proto_to_tool_result(proto_result)?;
                 Ok(ConversationMessage {
                     data: MessageData::Tool {
                         tool_use_id: tool_msg.tool_use_id,
@@ -1666,17 +1496,23 @@ fn proto_user_message_to_core(
         .content
         .into_iter()
         .filter_map(|user_content| {
-            user_content.content.map(|content| match content {
-                user_content::Content::Text(text) => UserContent::Text { text },
-                user_content::Content::Image(image) => UserContent::Image {
+            user_content.content.and_then(|content| match content {
+                user_content::Content::Text(text) => Some(UserContent::Text { text }),
+                user_content::Content::Image(image) => Some(UserContent::Image {
                     image: proto_image_to_core(image),
-                },
-                user_content::Content::CommandExecution(cmd) => UserContent::CommandExecution {
-                    command: cmd.command,
-                    stdout: cmd.stdout,
-                    stderr: cmd.stderr,
-                    exit_code: cmd.exit_code,
-                },
+                }),
+                user_content::Content::Attachment(attachment) => {
+                    proto_attachment_to_core(attachment)
+                        .map(|attachment| UserContent::Attachment { attachment })
+                }
+                user_content::Content::CommandExecution(cmd) => {
+                    Some(UserContent::CommandExecution {
+                        command: cmd.command,
+                        stdout: cmd.stdout,
+                        stderr: cmd.stderr,
+                        exit_code: cmd.exit_code,
+                    })
+                }
             })
         })
         .collect();
@@ -1744,6 +1580,58 @@ fn proto_assistant_message_to_core(
     })
 }
 
+fn best_of_candidate_to_proto(
+    candidate: steer_core::app::domain::event::BestOfCandidate,
+) -> Result<proto::BestOfCandidate, ConversionError> {
+    let message = message_to_proto(ConversationMessage {
+        data: MessageData::Assistant {
+            content: candidate.content,
+        },
+        timestamp: 0,
+        id: candidate.message_id.to_string(),
+        parent_message_id: None,
+    })?;
+    let content = match message.message {
+        Some(proto::message::Message::Assistant(assistant)) => assistant.content,
+        _ => Vec::new(),
+    };
+
+    Ok(proto::BestOfCandidate {
+        index: candidate.index,
+        op_id: candidate.op_id.to_string(),
+        message_id: candidate.message_id.to_string(),
+        content,
+        usage: candidate.usage.map(usage_to_proto),
+        error: candidate.error,
+    })
+}
+
+fn proto_to_best_of_candidate(
+    candidate: proto::BestOfCandidate,
+) -> Result<steer_core::app::domain::event::BestOfCandidate, ConversionError> {
+    let message = proto_assistant_message_to_core(
+        candidate.message_id.clone(),
+        proto::AssistantMessage {
+            content: candidate.content,
+            timestamp: 0,
+            parent_message_id: None,
+        },
+    )?;
+    let content = match message.data {
+        MessageData::Assistant { content } => content,
+        _ => Vec::new(),
+    };
+
+    Ok(steer_core::app::domain::event::BestOfCandidate {
+        index: candidate.index,
+        op_id: parse_op_id(&candidate.op_id)?,
+        message_id: steer_core::app::domain::types::MessageId::from_string(candidate.message_id),
+        content,
+        usage: candidate.usage.map(proto_to_usage),
+        error: candidate.error,
+    })
+}
+
 fn core_image_to_proto(image: &steer_core::app::conversation::ImageContent) -> proto::ImageContent {
     let source = match &image.source {
         ImageSource::SessionFile { relative_path } => Some(
@@ -1799,12 +1687,53 @@ fn proto_image_to_core(image: proto::ImageContent) -> steer_core::app::conversat
     }
 }
 
+pub(crate) fn core_attachment_to_proto(
+    attachment: &steer_core::app::conversation::AttachmentContent,
+) -> proto::AttachmentContent {
+    use steer_core::app::conversation::AttachmentBody;
+
+    let body = match &attachment.body {
+        AttachmentBody::Text { text } => proto::attachment_content::Body::Text(text.clone()),
+        AttachmentBody::Image { image } => {
+            proto::attachment_content::Body::Image(core_image_to_proto(image))
+        }
+    };
+
+    proto::AttachmentContent {
+        path: attachment.path.clone(),
+        mime_type: attachment.mime_type.clone(),
+        bytes: attachment.bytes,
+        body: Some(body),
+    }
+}
+
+/// Returns `None` for an attachment without a body.
+pub(crate) fn proto_attachment_to_core(
+    attachment: proto::AttachmentContent,
+) -> Option<steer_core::app::conversation::AttachmentContent> {
+    use steer_core::app::conversation::{AttachmentBody, AttachmentContent};
+
+    let body = match attachment.body? {
+        proto::attachment_content::Body::Text(text) => AttachmentBody::Text { text },
+        proto::attachment_content::Body::Image(image) => AttachmentBody::Image {
+            image: proto_image_to_core(image),
+        },
+    };
+
+    Some(AttachmentContent {
+        path: attachment.path,
+        mime_type: attachment.mime_type,
+        bytes: attachment.bytes,
+        body,
+    })
+}
+
 fn proto_tool_message_to_core(
     id: String,
     tool_msg: proto::ToolMessage,
 ) -> Result<ConversationMessage, ConversionError> {
     if let Some(proto_result) = tool_msg.result {
-        let tool_result = proto_to_steer_tools_result(proto_result)?;
+        let tool_result = steer_proto::convert::proto_to_tool_result(proto_result)?;
         Ok(ConversationMessage {
             data: MessageData::Tool {
                 tool_use_id: tool_msg.tool_use_id,
@@ -1866,6 +1795,280 @@ fn compaction_record_from_proto(
     })
 }
 
+pub(crate) fn model_switch_mode_to_proto(mode: ModelSwitchMode) -> proto::ModelSwitchMode {
+    match mode {
+        ModelSwitchMode::AfterCurrentTurn => proto::ModelSwitchMode::AfterCurrentTurn,
+        ModelSwitchMode::RestartOperation => proto::ModelSwitchMode::RestartOperation,
+    }
+}
+
+pub(crate) fn proto_to_model_switch_mode(value: i32) -> ModelSwitchMode {
+    match proto::ModelSwitchMode::try_from(value) {
+        Ok(proto::ModelSwitchMode::RestartOperation) => ModelSwitchMode::RestartOperation,
+        _ => ModelSwitchMode::AfterCurrentTurn,
+    }
+}
+
+pub(crate) fn isolation_mode_to_proto(mode: IsolationMode) -> proto::IsolationMode {
+    match mode {
+        IsolationMode::None => proto::IsolationMode::None,
+        IsolationMode::Worktree => proto::IsolationMode::Worktree,
+    }
+}
+
+pub(crate) fn proto_to_isolation_mode(value: i32) -> IsolationMode {
+    match proto::IsolationMode::try_from(value) {
+        Ok(proto::IsolationMode::Worktree) => IsolationMode::Worktree,
+        _ => IsolationMode::None,
+    }
+}
+
+pub(crate) fn auto_summary_config_to_proto(config: &AutoSummaryConfig) -> proto::AutoSummaryConfig {
+    proto::AutoSummaryConfig {
+        enabled: config.enabled,
+        model: config.model.clone().map(model_to_proto),
+        refresh_after_messages: config.refresh_after_messages,
+    }
+}
+
+pub(crate) fn proto_to_auto_summary_config(
+    config: Option<proto::AutoSummaryConfig>,
+) -> Result<AutoSummaryConfig, ConversionError> {
+    let Some(config) = config else {
+        return Ok(AutoSummaryConfig::default());
+    };
+    Ok(AutoSummaryConfig {
+        enabled: config.enabled,
+        model: config.model.as_ref().map(proto_to_model).transpose()?,
+        refresh_after_messages: config.refresh_after_messages,
+    })
+}
+
+pub(crate) fn isolated_workspace_to_proto(
+    workspace: &IsolatedWorkspace,
+) -> proto::IsolatedWorkspace {
+    let (repo_root, branch) = match &workspace.kind {
+        IsolationKind::GitWorktree { repo_root, branch } => (
+            Some(repo_root.to_string_lossy().into_owned()),
+            Some(branch.clone()),
+        ),
+        IsolationKind::Copy => (None, None),
+    };
+    proto::IsolatedWorkspace {
+        source: workspace.source.to_string_lossy().into_owned(),
+        root: workspace.root.to_string_lossy().into_owned(),
+        path: workspace.path.to_string_lossy().into_owned(),
+        base_commit: workspace.base_commit.clone(),
+        repo_root,
+        branch,
+    }
+}
+
+pub(crate) fn proto_to_isolated_workspace(
+    workspace: proto::IsolatedWorkspace,
+) -> IsolatedWorkspace {
+    let kind = match (workspace.repo_root, workspace.branch) {
+        (Some(repo_root), Some(branch)) => IsolationKind::GitWorktree {
+            repo_root: PathBuf::from(repo_root),
+            branch,
+        },
+        _ => IsolationKind::Copy,
+    };
+    IsolatedWorkspace {
+        source: PathBuf::from(workspace.source),
+        root: PathBuf::from(workspace.root),
+        path: PathBuf::from(workspace.path),
+        base_commit: workspace.base_commit,
+        kind,
+    }
+}
+
+pub(crate) fn file_change_to_proto(change: &FileChange) -> proto::IsolatedFileChange {
+    let kind = match change.kind {
+        FileChangeKind::Added => proto::IsolatedFileChangeKind::Added,
+        FileChangeKind::Modified => proto::IsolatedFileChangeKind::Modified,
+        FileChangeKind::Deleted => proto::IsolatedFileChangeKind::Deleted,
+    };
+    proto::IsolatedFileChange {
+        kind: kind.into(),
+        path: change.path.clone(),
+    }
+}
+
+pub(crate) fn proto_to_file_change(change: proto::IsolatedFileChange) -> FileChange {
+    let kind = match proto::IsolatedFileChangeKind::try_from(change.kind) {
+        Ok(proto::IsolatedFileChangeKind::Added) => FileChangeKind::Added,
+        Ok(proto::IsolatedFileChangeKind::Deleted) => FileChangeKind::Deleted,
+        _ => FileChangeKind::Modified,
+    };
+    FileChange {
+        kind,
+        path: change.path,
+    }
+}
+
+pub(crate) fn review_report_to_proto(report: &ReviewReport) -> proto::RunReviewResponse {
+    proto::RunReviewResponse {
+        target: report.target.to_string(),
+        root: report.root.to_string_lossy().into_owned(),
+        findings: report
+            .findings
+            .iter()
+            .map(review_finding_to_proto)
+            .collect(),
+        files_reviewed: u32::try_from(report.files_reviewed).unwrap_or(u32::MAX),
+        chunks: u32::try_from(report.chunks).unwrap_or(u32::MAX),
+    }
+}
+
+fn review_finding_to_proto(finding: &ReviewFinding) -> proto::ReviewFinding {
+    let severity = match finding.severity {
+        ReviewSeverity::Critical => proto::ReviewSeverity::Critical,
+        ReviewSeverity::Major => proto::ReviewSeverity::Major,
+        ReviewSeverity::Minor => proto::ReviewSeverity::Minor,
+        ReviewSeverity::Nit => proto::ReviewSeverity::Nit,
+    };
+    proto::ReviewFinding {
+        severity: severity.into(),
+        path: finding.path.clone(),
+        line: finding.line,
+        end_line: finding.end_line,
+        title: finding.title.clone(),
+        detail: finding.detail.clone(),
+    }
+}
+
+pub(crate) fn proto_to_review_report(response: proto::RunReviewResponse) -> ReviewReport {
+    ReviewReport {
+        target: response.target.parse().unwrap_or_default(),
+        root: PathBuf::from(response.root),
+        findings: response
+            .findings
+            .into_iter()
+            .map(proto_to_review_finding)
+            .collect(),
+        files_reviewed: response.files_reviewed as usize,
+        chunks: response.chunks as usize,
+    }
+}
+
+fn proto_to_review_finding(finding: proto::ReviewFinding) -> ReviewFinding {
+    let severity = match proto::ReviewSeverity::try_from(finding.severity) {
+        Ok(proto::ReviewSeverity::Critical) => ReviewSeverity::Critical,
+        Ok(proto::ReviewSeverity::Major) => ReviewSeverity::Major,
+        Ok(proto::ReviewSeverity::Nit) => ReviewSeverity::Nit,
+        _ => ReviewSeverity::Minor,
+    };
+    ReviewFinding {
+        severity,
+        path: finding.path,
+        line: finding.line,
+        end_line: finding.end_line,
+        title: finding.title,
+        detail: finding.detail,
+    }
+}
+
+pub(crate) fn commit_draft_to_proto(draft: &CommitDraft) -> proto::DraftCommitResponse {
+    proto::DraftCommitResponse {
+        root: draft.root.to_string_lossy().into_owned(),
+        files: draft.files.clone(),
+        message: draft.message.clone(),
+    }
+}
+
+pub(crate) fn proto_to_commit_draft(response: proto::DraftCommitResponse) -> CommitDraft {
+    CommitDraft {
+        root: PathBuf::from(response.root),
+        files: response.files,
+        message: response.message,
+    }
+}
+
+pub(crate) fn commit_outcome_to_proto(outcome: &CommitOutcome) -> proto::CreateCommitResponse {
+    proto::CreateCommitResponse {
+        commit: outcome.commit.clone(),
+        summary: outcome.summary.clone(),
+        files: outcome.files.clone(),
+    }
+}
+
+pub(crate) fn proto_to_commit_outcome(response: proto::CreateCommitResponse) -> CommitOutcome {
+    CommitOutcome {
+        commit: response.commit,
+        summary: response.summary,
+        files: response.files,
+    }
+}
+
+pub(crate) fn active_operation_to_proto(
+    operation: steer_core::app::domain::runtime::ActiveOperation,
+) -> proto::ActiveOperation {
+    use steer_core::app::domain::runtime::OperationPhase;
+
+    let phase = match operation.phase {
+        OperationPhase::CallingModel => proto::OperationPhase::CallingModel,
+        OperationPhase::RunningTool => proto::OperationPhase::RunningTool,
+        OperationPhase::AwaitingApproval => proto::OperationPhase::AwaitingApproval,
+        OperationPhase::Compacting => proto::OperationPhase::Compacting,
+        OperationPhase::RunningBash => proto::OperationPhase::RunningBash,
+    };
+
+    proto::ActiveOperation {
+        session_id: operation.session_id.to_string(),
+        op_id: operation.op_id.to_string(),
+        started_at: operation
+            .started_at
+            .map(|at| prost_types::Timestamp::from(std::time::SystemTime::from(at))),
+        phase: phase.into(),
+        current_tool: operation.current_tool,
+        model: operation.model.map(model_to_proto),
+    }
+}
+
+pub(crate) fn scheduled_task_to_proto(
+    task: &steer_core::app::domain::session::ScheduledTask,
+) -> proto::ScheduledTask {
+    proto::ScheduledTask {
+        id: task.id.clone(),
+        schedule: task.schedule.clone(),
+        prompt: task.prompt.clone(),
+        config: Some(session_config_to_proto(&task.config)),
+        max_total_tokens: task.max_total_tokens,
+        created_at: Some(prost_types::Timestamp::from(std::time::SystemTime::from(
+            task.created_at,
+        ))),
+        last_run: task.last_run.as_ref().map(scheduled_task_run_to_proto),
+    }
+}
+
+fn scheduled_task_run_to_proto(
+    run: &steer_core::app::domain::session::ScheduledTaskRun,
+) -> proto::ScheduledTaskRun {
+    use steer_core::app::domain::session::ScheduledRunOutcome;
+
+    let (status, error) = match &run.outcome {
+        ScheduledRunOutcome::Running => (proto::ScheduledRunStatus::Running, None),
+        ScheduledRunOutcome::Succeeded => (proto::ScheduledRunStatus::Succeeded, None),
+        ScheduledRunOutcome::Failed { message } => {
+            (proto::ScheduledRunStatus::Failed, Some(message.clone()))
+        }
+        ScheduledRunOutcome::BudgetExceeded => (proto::ScheduledRunStatus::BudgetExceeded, None),
+    };
+
+    proto::ScheduledTaskRun {
+        session_id: run.session_id.to_string(),
+        started_at: Some(prost_types::Timestamp::from(std::time::SystemTime::from(
+            run.started_at,
+        ))),
+        finished_at: run
+            .finished_at
+            .map(|at| prost_types::Timestamp::from(std::time::SystemTime::from(at))),
+        status: status.into(),
+        error,
+    }
+}
+
 fn compact_trigger_to_proto(
     trigger: steer_core::app::domain::event::CompactTrigger,
 ) -> proto::CompactTrigger {
@@ -1875,6 +2078,76 @@ fn compact_trigger_to_proto(
     }
 }
 
+fn operation_timing_to_proto(
+    timing: &steer_core::app::domain::event::OperationTiming,
+) -> proto::OperationTiming {
+    use steer_core::app::domain::event::TimingSpanKind;
+
+    let spans = timing
+        .spans
+        .iter()
+        .map(|span| {
+            let (kind, tool_name) = match &span.kind {
+                TimingSpanKind::QueueWait => (proto::TimingSpanKind::QueueWait, String::new()),
+                TimingSpanKind::TimeToFirstToken => {
+                    (proto::TimingSpanKind::TimeToFirstToken, String::new())
+                }
+                TimingSpanKind::Streaming => (proto::TimingSpanKind::Streaming, String::new()),
+                TimingSpanKind::ToolExecution { tool_name } => {
+                    (proto::TimingSpanKind::ToolExecution, tool_name.clone())
+                }
+                TimingSpanKind::ApprovalWait => {
+                    (proto::TimingSpanKind::ApprovalWait, String::new())
+                }
+            };
+            proto::TimingSpan {
+                kind: kind as i32,
+                tool_name,
+                duration_ms: span.duration_ms,
+            }
+        })
+        .collect();
+
+    proto::OperationTiming {
+        total_ms: timing.total_ms,
+        spans,
+    }
+}
+
+/// Spans of a kind this client does not know are dropped rather than failing
+/// the whole completion event.
+fn operation_timing_from_proto(
+    timing: proto::OperationTiming,
+) -> steer_core::app::domain::event::OperationTiming {
+    use steer_core::app::domain::event::{OperationTiming, TimingSpan, TimingSpanKind};
+
+    let spans = timing
+        .spans
+        .into_iter()
+        .filter_map(|span| {
+            let kind = match proto::TimingSpanKind::try_from(span.kind) {
+                Ok(proto::TimingSpanKind::QueueWait) => TimingSpanKind::QueueWait,
+                Ok(proto::TimingSpanKind::TimeToFirstToken) => TimingSpanKind::TimeToFirstToken,
+                Ok(proto::TimingSpanKind::Streaming) => TimingSpanKind::Streaming,
+                Ok(proto::TimingSpanKind::ToolExecution) => TimingSpanKind::ToolExecution {
+                    tool_name: span.tool_name,
+                },
+                Ok(proto::TimingSpanKind::ApprovalWait) => TimingSpanKind::ApprovalWait,
+                Ok(proto::TimingSpanKind::Unspecified) | Err(_) => return None,
+            };
+            Some(TimingSpan {
+                kind,
+                duration_ms: span.duration_ms,
+            })
+        })
+        .collect();
+
+    OperationTiming {
+        total_ms: timing.total_ms,
+        spans,
+    }
+}
+
 fn compact_trigger_from_proto(value: i32) -> steer_core::app::domain::event::CompactTrigger {
     match proto::CompactTrigger::try_from(value) {
         Ok(proto::CompactTrigger::Auto) => steer_core::app::domain::event::CompactTrigger::Auto,
@@ -2177,6 +2450,34 @@ pub(crate) fn session_event_to_proto(
                 kind: usage_update_kind_to_proto(UsageUpdateKind::Final),
             },
         )),
+        SessionEvent::ContextUsage { used, limit } => Some(
+            proto::session_event::Event::ContextUsage(proto::ContextUsageEvent { used, limit }),
+        ),
+        SessionEvent::BestOfStarted {
+            op_id,
+            user_message_id,
+            model,
+            candidate_op_ids,
+        } => Some(proto::session_event::Event::BestOfStarted(
+            proto::BestOfStartedEvent {
+                op_id: op_id.to_string(),
+                user_message_id: user_message_id.to_string(),
+                model: Some(model_to_proto(model)),
+                candidate_op_ids: candidate_op_ids.iter().map(ToString::to_string).collect(),
+            },
+        )),
+        SessionEvent::BestOfCandidateReady { op_id, candidate } => Some(
+            proto::session_event::Event::BestOfCandidateReady(proto::BestOfCandidateReadyEvent {
+                op_id: op_id.to_string(),
+                candidate: Some(best_of_candidate_to_proto(candidate)?),
+            }),
+        ),
+        SessionEvent::BestOfResolved { op_id, picked } => Some(
+            proto::session_event::Event::BestOfResolved(proto::BestOfResolvedEvent {
+                op_id: op_id.to_string(),
+                picked,
+            }),
+        ),
         SessionEvent::MessageUpdated { message } => {
             let proto_message = message_to_proto(message)?;
             Some(proto::session_event::Event::MessageUpdated(
@@ -2204,8 +2505,7 @@ pub(crate) fn session_event_to_proto(
             result,
             model,
         } => {
-            let proto_result = steer_tools_result_to_proto(&result)
-                .map_err(|e| ConversionError::ToolResultConversion(e.to_string()))?;
+            let proto_result = steer_proto::convert::tool_result_to_proto(&result);
             Some(proto::session_event::Event::ToolCallCompleted(
                 proto::ToolCallCompletedEvent {
                     name,
@@ -2249,9 +2549,10 @@ pub(crate) fn session_event_to_proto(
                 operation_kind: Some(operation_kind_to_proto(&kind)),
             }),
         ),
-        SessionEvent::OperationCompleted { op_id } => Some(
+        SessionEvent::OperationCompleted { op_id, timing } => Some(
             proto::session_event::Event::ProcessingCompleted(proto::ProcessingCompletedEvent {
                 op_id: op_id.to_string(),
+                timing: timing.as_ref().map(operation_timing_to_proto),
             }),
         ),
         SessionEvent::OperationCancelled { op_id, info } => Some(
@@ -2287,6 +2588,11 @@ pub(crate) fn session_event_to_proto(
                 message,
             }))
         }
+        SessionEvent::Refused { reason } => {
+            Some(proto::session_event::Event::Refused(proto::RefusedEvent {
+                reason,
+            }))
+        }
         SessionEvent::McpServerStateChanged { server_name, state } => Some(
             proto::session_event::Event::McpServerStateChanged(proto::McpServerStateChangedEvent {
                 server_name,
@@ -2382,62 +2688,27 @@ pub(crate) fn stream_delta_to_proto(
     })
 }
 
-// TODO: These todo conversion functions are pub because steer-remote-workspace uses them.
-// This is a boundary violation - should be refactored to use shared proto conversion traits.
-pub fn convert_todo_item_to_proto(item: &TodoItem) -> common::TodoItem {
-    common::TodoItem {
-        id: item.id.clone(),
-        content: item.content.clone(),
-        status: match item.status {
-            TodoStatus::Pending => common::TodoStatus::Pending as i32,
-            TodoStatus::InProgress => common::TodoStatus::InProgress as i32,
-            TodoStatus::Completed => common::TodoStatus::Completed as i32,
-        },
-        priority: match item.priority {
-            TodoPriority::High => common::TodoPriority::High as i32,
-            TodoPriority::Medium => common::TodoPriority::Medium as i32,
-            TodoPriority::Low => common::TodoPriority::Low as i32,
-        },
-    }
-}
-
-pub(crate) fn convert_proto_to_todo_item(item: common::TodoItem) -> TodoItem {
-    TodoItem {
-        id: item.id.clone(),
-        content: item.content.clone(),
-        status: match steer_proto::common::v1::TodoStatus::try_from(item.status) {
-            Ok(steer_proto::common::v1::TodoStatus::Pending) => TodoStatus::Pending,
-            Ok(steer_proto::common::v1::TodoStatus::InProgress) => TodoStatus::InProgress,
-            Ok(steer_proto::common::v1::TodoStatus::Completed) => TodoStatus::Completed,
-            Ok(steer_proto::common::v1::TodoStatus::StatusUnset) => TodoStatus::Pending,
-            Err(_) => TodoStatus::Pending,
-        },
-        priority: match steer_proto::common::v1::TodoPriority::try_from(item.priority) {
-            Ok(steer_proto::common::v1::TodoPriority::High) => TodoPriority::High,
-            Ok(steer_proto::common::v1::TodoPriority::Medium) => TodoPriority::Medium,
-            Ok(steer_proto::common::v1::TodoPriority::Low) => TodoPriority::Low,
-            Ok(steer_proto::common::v1::TodoPriority::PriorityUnset) => TodoPriority::Low,
-            Err(_) => TodoPriority::Low,
-        },
-    }
-}
-
-pub(crate) fn convert_proto_to_todo_write_file_operation(
-    operation: common::TodoWriteFileOperation,
-) -> TodoWriteFileOperation {
-    match operation {
-        common::TodoWriteFileOperation::Created => TodoWriteFileOperation::Created,
-        common::TodoWriteFileOperation::Modified => TodoWriteFileOperation::Modified,
-        common::TodoWriteFileOperation::OperationUnset => TodoWriteFileOperation::Created,
-    }
-}
+/// Wraps a live workspace file change in a session event. Like stream deltas,
+/// it carries the sequence number of the last persisted event.
+pub(crate) fn workspace_file_change_to_proto(
+    change: steer_workspace::WorkspaceFileChange,
+    sequence_num: u64,
+) -> proto::SessionEvent {
+    let event = match change {
+        steer_workspace::WorkspaceFileChange::Added(path) => {
+            proto::session_event::Event::WorkspaceFileAdded(proto::WorkspaceFileAddedEvent { path })
+        }
+        steer_workspace::WorkspaceFileChange::Removed(path) => {
+            proto::session_event::Event::WorkspaceFileRemoved(proto::WorkspaceFileRemovedEvent {
+                path,
+            })
+        }
+    };
 
-pub fn convert_todo_write_file_operation_to_proto(
-    operation: &TodoWriteFileOperation,
-) -> common::TodoWriteFileOperation {
-    match operation {
-        TodoWriteFileOperation::Created => common::TodoWriteFileOperation::Created,
-        TodoWriteFileOperation::Modified => common::TodoWriteFileOperation::Modified,
+    proto::SessionEvent {
+        sequence_num,
+        timestamp: Some(prost_types::Timestamp::from(std::time::SystemTime::now())),
+        event: Some(event),
     }
 }
 
@@ -2647,7 +2918,7 @@ fn proto_to_mcp_transport(
     })
 }
 
-fn parse_op_id(s: &str) -> Result<crate::client_api::OpId, ConversionError> {
+pub(crate) fn parse_op_id(s: &str) -> Result<crate::client_api::OpId, ConversionError> {
     uuid::Uuid::parse_str(s)
         .map(crate::client_api::OpId::from)
         .map_err(|_| ConversionError::InvalidData {
@@ -2774,6 +3045,38 @@ pub(crate) fn proto_to_client_event(
                 kind,
             }
         }
+        proto::session_event::Event::ContextUsage(e) => ClientEvent::ContextUsage {
+            used: e.used,
+            limit: e.limit,
+        },
+        proto::session_event::Event::BestOfStarted(e) => ClientEvent::BestOfStarted {
+            op_id: parse_op_id(&e.op_id)?,
+            user_message_id: MessageId::from_string(e.user_message_id),
+            model: e
+                .model
+                .ok_or_else(|| ConversionError::MissingField {
+                    field: "best_of_started.model".to_string(),
+                })
+                .and_then(|spec| proto_to_model(&spec))?,
+            candidate_op_ids: e
+                .candidate_op_ids
+                .iter()
+                .map(|id| parse_op_id(id))
+                .collect::<Result<_, _>>()?,
+        },
+        proto::session_event::Event::BestOfCandidateReady(e) => {
+            let candidate = e.candidate.ok_or_else(|| ConversionError::MissingField {
+                field: "best_of_candidate_ready.candidate".to_string(),
+            })?;
+            ClientEvent::BestOfCandidateReady {
+                op_id: parse_op_id(&e.op_id)?,
+                candidate: proto_to_best_of_candidate(candidate)?,
+            }
+        }
+        proto::session_event::Event::BestOfResolved(e) => ClientEvent::BestOfResolved {
+            op_id: parse_op_id(&e.op_id)?,
+            picked: e.picked,
+        },
         proto::session_event::Event::MessageUpdated(e) => {
             let proto_message = e.message.ok_or_else(|| ConversionError::MissingField {
                 field: "message_updated_event.message".to_string(),
@@ -2795,11 +3098,12 @@ pub(crate) fn proto_to_client_event(
             }
         }
         proto::session_event::Event::ToolCallCompleted(e) => {
-            let result = proto_to_steer_tools_result(e.result.ok_or_else(|| {
-                ConversionError::MissingField {
-                    field: "result".to_string(),
-                }
-            })?)?;
+            let result =
+                steer_proto::convert::proto_to_tool_result(e.result.ok_or_else(|| {
+                    ConversionError::MissingField {
+                        field: "result".to_string(),
+                    }
+                })?)?;
             ClientEvent::ToolCompleted {
                 id: ToolCallId::from(e.id),
                 name: e.name,
@@ -2821,7 +3125,10 @@ pub(crate) fn proto_to_client_event(
         }
         proto::session_event::Event::ProcessingCompleted(e) => {
             let op_id = parse_op_id(&e.op_id)?;
-            ClientEvent::ProcessingCompleted { op_id }
+            ClientEvent::ProcessingCompleted {
+                op_id,
+                timing: e.timing.map(operation_timing_from_proto),
+            }
         }
         proto::session_event::Event::RequestToolApproval(e) => {
             let parameters = serde_json::from_str(&e.parameters_json).map_err(|err| {
@@ -2926,7 +3233,14 @@ pub(crate) fn proto_to_client_event(
             }
         }
         proto::session_event::Event::Error(e) => ClientEvent::Error { message: e.message },
+        proto::session_event::Event::Refused(e) => ClientEvent::Refused { reason: e.reason },
         proto::session_event::Event::WorkspaceChanged(_) => ClientEvent::WorkspaceChanged,
+        proto::session_event::Event::WorkspaceFileAdded(e) => {
+            ClientEvent::WorkspaceFileAdded { path: e.path }
+        }
+        proto::session_event::Event::WorkspaceFileRemoved(e) => {
+            ClientEvent::WorkspaceFileRemoved { path: e.path }
+        }
         proto::session_event::Event::McpServerStateChanged(e) => {
             let state = e.state.ok_or_else(|| ConversionError::MissingField {
                 field: "mcp_server_state_changed.state".to_string(),
@@ -2968,6 +3282,22 @@ pub(crate) fn auth_progress_to_proto(
         steer_core::auth::AuthProgress::OAuthStarted { auth_url } => {
             State::OauthStarted(proto::AuthOAuthStarted { auth_url })
         }
+        steer_core::auth::AuthProgress::DeviceCode {
+            verification_uri,
+            user_code,
+        } => State::DeviceCode(proto::AuthDeviceCode {
+            verification_uri,
+            user_code,
+        }),
+        steer_core::auth::AuthProgress::WaitingForCallback { elapsed } => {
+            State::WaitingForCallback(proto::AuthWaitingForCallback {
+                elapsed_ms: u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+            })
+        }
+        steer_core::auth::AuthProgress::ExchangingCode => {
+            State::ExchangingCode(proto::AuthExchangingCode {})
+        }
+        steer_core::auth::AuthProgress::Refreshing => State::Refreshing(proto::AuthRefreshing {}),
     };
 
     proto::AuthProgress { state: Some(state) }
@@ -2987,10 +3317,7 @@ pub(crate) fn auth_source_to_proto(source: steer_core::auth::AuthSource) -> prot
             }
         }
         steer_core::auth::AuthSource::Plugin { method } => {
-            let method = match method {
-                steer_core::auth::AuthMethod::OAuth => proto::AuthMethod::Oauth as i32,
-                steer_core::auth::AuthMethod::ApiKey => proto::AuthMethod::ApiKey as i32,
-            };
+            let method = auth_method_to_proto(method) as i32;
             proto::AuthSource {
                 source: Some(Source::Plugin(proto::AuthSourcePlugin { method })),
             }
@@ -3001,6 +3328,33 @@ pub(crate) fn auth_source_to_proto(source: steer_core::auth::AuthSource) -> prot
     }
 }
 
+pub(crate) fn auth_method_to_proto(method: steer_core::auth::AuthMethod) -> proto::AuthMethod {
+    match method {
+        steer_core::auth::AuthMethod::OAuth => proto::AuthMethod::Oauth,
+        steer_core::auth::AuthMethod::ApiKey => proto::AuthMethod::ApiKey,
+        steer_core::auth::AuthMethod::DeviceCode => proto::AuthMethod::DeviceCode,
+    }
+}
+
+pub(crate) fn proto_to_auth_method(
+    method: proto::AuthMethod,
+) -> Option<steer_core::auth::AuthMethod> {
+    match method {
+        proto::AuthMethod::Oauth => Some(steer_core::auth::AuthMethod::OAuth),
+        proto::AuthMethod::ApiKey => Some(steer_core::auth::AuthMethod::ApiKey),
+        proto::AuthMethod::DeviceCode => Some(steer_core::auth::AuthMethod::DeviceCode),
+        proto::AuthMethod::Unspecified => None,
+    }
+}
+
+pub(crate) fn client_auth_method_to_proto(method: &ClientAuthMethod) -> proto::AuthMethod {
+    match method {
+        ClientAuthMethod::OAuth => proto::AuthMethod::Oauth,
+        ClientAuthMethod::ApiKey => proto::AuthMethod::ApiKey,
+        ClientAuthMethod::DeviceCode => proto::AuthMethod::DeviceCode,
+    }
+}
+
 pub(crate) fn proto_to_auth_source(
     source: proto::AuthSource,
 ) -> Result<ClientAuthSource, ConversionError> {
@@ -3024,6 +3378,7 @@ pub(crate) fn proto_to_auth_source(
             let method = match proto::AuthMethod::try_from(plugin.method) {
                 Ok(proto::AuthMethod::Oauth) => ClientAuthMethod::OAuth,
                 Ok(proto::AuthMethod::ApiKey) => ClientAuthMethod::ApiKey,
+                Ok(proto::AuthMethod::DeviceCode) => ClientAuthMethod::DeviceCode,
                 _ => {
                     return Err(ConversionError::InvalidData {
                         message: format!("Invalid auth method {}", plugin.method),
@@ -3060,6 +3415,17 @@ pub(crate) fn proto_to_auth_progress(
         proto::auth_progress::State::OauthStarted(oauth) => ClientAuthProgress::OAuthStarted {
             auth_url: oauth.auth_url,
         },
+        proto::auth_progress::State::DeviceCode(device) => ClientAuthProgress::DeviceCode {
+            verification_uri: device.verification_uri,
+            user_code: device.user_code,
+        },
+        proto::auth_progress::State::WaitingForCallback(waiting) => {
+            ClientAuthProgress::WaitingForCallback {
+                elapsed: std::time::Duration::from_millis(waiting.elapsed_ms),
+            }
+        }
+        proto::auth_progress::State::ExchangingCode(_) => ClientAuthProgress::ExchangingCode,
+        proto::auth_progress::State::Refreshing(_) => ClientAuthProgress::Refreshing,
     };
 
     Ok(mapped)
@@ -71,6 +71,9 @@ pub enum GrpcError {
 
     #[error("Channel receive error: {0}")]
     ChannelError(String),
+
+    #[error("HTTP gateway error: {0}")]
+    HttpGateway(#[from] std::io::Error),
 }
 
 #[derive(Error, Debug)]
@@ -100,7 +103,7 @@ pub enum ConversionError {
     InvalidData { message: String },
 
     #[error("Tool result conversion error: {0}")]
-    ToolResultConversion(String),
+    ToolResultConversion(#[from] steer_proto::convert::ToolResultConversionError),
 }
 
 impl From<GrpcError> for tonic::Status {
@@ -129,6 +132,9 @@ impl From<GrpcError> for tonic::Status {
             GrpcError::ChannelError(msg) => {
                 tonic::Status::internal(format!("Channel error: {msg}"))
             }
+            GrpcError::HttpGateway(e) => {
+                tonic::Status::internal(format!("HTTP gateway error: {e}"))
+            }
         }
     }
 }
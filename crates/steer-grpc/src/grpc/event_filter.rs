@@ -0,0 +1,121 @@
+use std::collections::HashSet;
+
+use crate::grpc::proto::{self, SessionEventKind, session_event::Event};
+
+/// Subscriber-selected set of event kinds forwarded by `SubscribeSessionEvents`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EventKindFilter {
+    kinds: Option<HashSet<SessionEventKind>>,
+}
+
+impl EventKindFilter {
+    /// Build a filter from the request's raw `event_kinds`; an empty list (or one
+    /// with only unknown values) forwards everything.
+    pub(crate) fn from_proto(event_kinds: &[i32]) -> Self {
+        let kinds: HashSet<_> = event_kinds
+            .iter()
+            .filter_map(|kind| SessionEventKind::try_from(*kind).ok())
+            .filter(|kind| *kind != SessionEventKind::Unspecified)
+            .collect();
+
+        Self {
+            kinds: (!kinds.is_empty()).then_some(kinds),
+        }
+    }
+
+    pub(crate) fn allows_kind(&self, kind: SessionEventKind) -> bool {
+        self.kinds
+            .as_ref()
+            .is_none_or(|kinds| kinds.contains(&kind))
+    }
+
+    pub(crate) fn allows(&self, event: &proto::SessionEvent) -> bool {
+        event
+            .event
+            .as_ref()
+            .is_some_and(|event| self.allows_kind(event_kind(event)))
+    }
+}
+
+fn event_kind(event: &Event) -> SessionEventKind {
+    match event {
+        Event::AssistantMessageAdded(_)
+        | Event::UserMessageAdded(_)
+        | Event::ToolMessageAdded(_)
+        | Event::MessageUpdated(_) => SessionEventKind::Messages,
+        Event::StreamDelta(_) => SessionEventKind::StreamDeltas,
+        Event::ToolCallStarted(_)
+        | Event::ToolCallCompleted(_)
+        | Event::ToolCallFailed(_)
+        | Event::RequestToolApproval(_) => SessionEventKind::Tools,
+        Event::ProcessingStarted(_)
+        | Event::ProcessingCompleted(_)
+        | Event::OperationCancelled(_)
+        | Event::QueueUpdated(_)
+        | Event::BestOfStarted(_)
+        | Event::BestOfCandidateReady(_)
+        | Event::BestOfResolved(_) => SessionEventKind::Operations,
+        Event::WorkspaceChanged(_)
+        | Event::WorkspaceFileAdded(_)
+        | Event::WorkspaceFileRemoved(_) => SessionEventKind::Workspace,
+        Event::ConversationCompacted(_) | Event::CompactResult(_) => SessionEventKind::Compaction,
+        Event::SessionConfigUpdated(_) | Event::McpServerStateChanged(_) => {
+            SessionEventKind::Config
+        }
+        Event::LlmUsageUpdated(_) | Event::ContextUsage(_) => SessionEventKind::Usage,
+        Event::Error(_) | Event::Refused(_) => SessionEventKind::Errors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grpc::conversions::{session_event_to_proto, stream_delta_to_proto};
+    use steer_core::app::domain::delta::StreamDelta;
+    use steer_core::app::domain::event::SessionEvent as CoreSessionEvent;
+    use steer_core::app::domain::types::{MessageId, OpId, ToolCallId};
+    use steer_core::config::model::builtin;
+
+    #[test]
+    fn tool_filter_excludes_message_deltas() {
+        let filter = EventKindFilter::from_proto(&[SessionEventKind::Tools as i32]);
+
+        let delta = stream_delta_to_proto(
+            StreamDelta::TextChunk {
+                op_id: OpId::new(),
+                message_id: MessageId::new(),
+                delta: "partial answer".to_string(),
+            },
+            1,
+            0,
+        )
+        .expect("delta converts");
+        let tool_started = session_event_to_proto(
+            CoreSessionEvent::ToolCallStarted {
+                id: ToolCallId::from_string("tc_1"),
+                name: "grep".to_string(),
+                parameters: serde_json::json!({ "pattern": "todo" }),
+                model: builtin::claude_sonnet_4_5(),
+            },
+            2,
+        )
+        .expect("event converts");
+
+        assert!(!filter.allows(&delta));
+        assert!(filter.allows(&tool_started));
+    }
+
+    #[test]
+    fn empty_filter_allows_everything() {
+        let filter = EventKindFilter::from_proto(&[]);
+
+        for kind in [
+            SessionEventKind::Messages,
+            SessionEventKind::StreamDeltas,
+            SessionEventKind::Tools,
+            SessionEventKind::Usage,
+        ] {
+            assert!(filter.allows_kind(kind));
+        }
+    }
+}
@@ -1,6 +1,7 @@
 pub mod client_adapter;
 pub(crate) mod conversions;
 pub mod error;
+mod event_filter;
 pub mod runtime_server;
 
 #[cfg(test)]
@@ -15,5 +16,3 @@ pub use agent as proto;
 pub use client_adapter::*;
 pub use error::*;
 pub use runtime_server::{RuntimeAgentDeps, RuntimeAgentService};
-
-pub use conversions::{convert_todo_item_to_proto, convert_todo_write_file_operation_to_proto};
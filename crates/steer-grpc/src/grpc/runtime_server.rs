@@ -1,40 +1,59 @@
 use crate::grpc::conversions::{
-    environment_descriptor_to_proto, message_to_proto, model_to_proto, proto_to_model,
+    active_operation_to_proto, commit_draft_to_proto, commit_outcome_to_proto,
+    context_report_to_proto, environment_descriptor_to_proto, file_change_to_proto,
+    file_symbol_to_proto, isolated_workspace_to_proto, message_to_proto, model_to_proto,
+    parse_op_id, proto_attachment_to_core, proto_to_auto_summary_config, proto_to_isolation_mode,
+    proto_to_model, proto_to_model_switch_mode, proto_to_session_config,
     proto_to_session_policy_overrides, proto_to_tool_config, proto_to_workspace_config,
-    repo_info_to_proto, session_event_to_proto, stream_delta_to_proto, workspace_info_to_proto,
+    repo_info_to_proto, review_report_to_proto, scheduled_task_to_proto, session_event_to_proto,
+    stream_delta_to_proto, workspace_file_change_to_proto, workspace_info_to_proto,
     workspace_status_to_proto,
 };
+use crate::grpc::event_filter::EventKindFilter;
 use std::cmp::Ordering as CmpOrdering;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 use steer_core::app::conversation::UserContent;
-use steer_core::app::domain::runtime::{RuntimeError, RuntimeHandle};
-use steer_core::app::domain::session::{SessionFilter, SessionMetadataStore};
+use steer_core::app::domain::SessionSummaryError;
+use steer_core::app::domain::runtime::{EventLogger, RuntimeError, RuntimeHandle};
+use steer_core::app::domain::session::{
+    SessionFilter, SessionMetadataStore, ToolAuditFilter, ToolAuditStore,
+};
 use steer_core::app::domain::types::SessionId;
 use steer_core::auth::api_key::ApiKeyAuthFlow;
 use steer_core::auth::{
     AuthFlowWrapper, AuthMethod, AuthSource, DynAuthenticationFlow, ModelId as AuthModelId,
     ModelVisibilityPolicy, ProviderId as AuthProviderId,
 };
+use steer_core::commit::CommitError;
+use steer_core::isolation::{IsolatedWorkspace, IsolationError};
+use steer_core::mentions::{self, MentionError};
 use steer_core::primary_agents::primary_agent_specs;
+use steer_core::review::{ReviewError, ReviewTarget};
+use steer_core::runners::{Scheduler, SchedulerError};
 use steer_core::session::state::SessionConfig;
 use steer_proto::agent::v1::{
     self as proto, ApproveToolRequest, ApproveToolResponse, CancelOperationRequest,
     CancelOperationResponse, CompactSessionRequest, CompactSessionResponse, CreateSessionRequest,
     CreateSessionResponse, DeleteSessionRequest, DeleteSessionResponse, DequeueQueuedItemRequest,
     DequeueQueuedItemResponse, EditMessageRequest, EditMessageResponse, ExecuteBashCommandRequest,
-    ExecuteBashCommandResponse, GetConversationFooter, GetConversationRequest,
-    GetConversationResponse, GetMcpServersRequest, GetMcpServersResponse, GetSessionRequest,
-    GetSessionResponse, ListFilesRequest, ListFilesResponse, ListModelsRequest, ListModelsResponse,
-    ListPrimaryAgentsRequest, ListPrimaryAgentsResponse, ListProvidersRequest,
+    ExecuteBashCommandResponse, ExpandFileMentionRequest, ExpandFileMentionResponse,
+    GetConversationFooter, GetConversationRequest, GetConversationResponse, GetMcpServersRequest,
+    GetMcpServersResponse, GetSessionRequest, GetSessionResponse, ListFileSymbolsRequest,
+    ListFileSymbolsResponse, ListFilesRequest, ListFilesResponse, ListModelsRequest,
+    ListModelsResponse, ListPrimaryAgentsRequest, ListPrimaryAgentsResponse, ListProvidersRequest,
     ListProvidersResponse, ListSessionsRequest, ListSessionsResponse, Operation, OperationStatus,
-    OperationType, SendMessageRequest, SendMessageResponse, SessionEvent, SessionInfo,
-    SessionStateFooter, SessionStateHeader, SubscribeSessionEventsRequest,
+    OperationType, PickBestOfCandidateRequest, PickBestOfCandidateResponse, RetryLastTurnRequest,
+    RetryLastTurnResponse, SendMessageRequest, SendMessageResponse, SessionEvent, SessionEventKind,
+    SessionInfo, SessionStateFooter, SessionStateHeader, StartBestOfRequest, StartBestOfResponse,
+    SubscribeSessionEventsRequest, SwitchModelRequest, SwitchModelResponse,
     SwitchPrimaryAgentRequest, SwitchPrimaryAgentResponse, agent_service_server,
     get_conversation_response, get_session_response,
 };
+use steer_proto::convert::memory_entry_to_proto;
+use steer_tools::tools::memory::MemoryEntry;
 use steer_workspace::{EnvironmentManager, RepoManager, WorkspaceManager};
 use tokio::sync::{Mutex, broadcast, mpsc};
 use tokio_stream::wrappers::ReceiverStream;
@@ -45,12 +64,15 @@ use uuid::Uuid;
 pub struct RuntimeAgentService {
     runtime: RuntimeHandle,
     catalog: Arc<dyn SessionMetadataStore>,
-    model_registry: Arc<steer_core::model_registry::ModelRegistry>,
+    model_registry: steer_core::model_registry::SharedModelRegistry,
     provider_registry: Arc<steer_core::auth::ProviderRegistry>,
     llm_config_provider: steer_core::config::LlmConfigProvider,
     environment_manager: Arc<dyn EnvironmentManager>,
     workspace_manager: Arc<dyn WorkspaceManager>,
     repo_manager: Arc<dyn RepoManager>,
+    scheduler: Option<Arc<Scheduler>>,
+    event_logger: Option<Arc<EventLogger>>,
+    audit_log: Option<Arc<dyn ToolAuditStore>>,
     auth_flow_manager: Arc<AuthFlowManager>,
 }
 
@@ -92,11 +114,17 @@ pub struct RuntimeAgentDeps {
     pub runtime: RuntimeHandle,
     pub catalog: Arc<dyn SessionMetadataStore>,
     pub llm_config_provider: steer_core::config::LlmConfigProvider,
-    pub model_registry: Arc<steer_core::model_registry::ModelRegistry>,
+    pub model_registry: steer_core::model_registry::SharedModelRegistry,
     pub provider_registry: Arc<steer_core::auth::ProviderRegistry>,
     pub environment_manager: Arc<dyn EnvironmentManager>,
     pub workspace_manager: Arc<dyn WorkspaceManager>,
     pub repo_manager: Arc<dyn RepoManager>,
+    /// Present when the server persists and fires scheduled tasks.
+    pub scheduler: Option<Arc<Scheduler>>,
+    /// Present when the server was started with `--log-events`.
+    pub event_logger: Option<Arc<EventLogger>>,
+    /// Present when the server records tool executions to an audit log.
+    pub audit_log: Option<Arc<dyn ToolAuditStore>>,
 }
 
 impl RuntimeAgentService {
@@ -110,12 +138,53 @@ impl RuntimeAgentService {
             environment_manager: deps.environment_manager,
             workspace_manager: deps.workspace_manager,
             repo_manager: deps.repo_manager,
+            scheduler: deps.scheduler,
+            event_logger: deps.event_logger,
+            audit_log: deps.audit_log,
             auth_flow_manager: Arc::new(AuthFlowManager::new()),
         }
     }
 
+    /// The runtime backing this service, for in-process front ends such as the
+    /// HTTP gateway.
+    pub(crate) fn runtime(&self) -> &RuntimeHandle {
+        &self.runtime
+    }
+
+    fn memory_response(entries: &[MemoryEntry]) -> proto::SessionMemoryResponse {
+        proto::SessionMemoryResponse {
+            entries: entries.iter().map(memory_entry_to_proto).collect(),
+        }
+    }
+
+    fn memory_status(session_id: SessionId, error: RuntimeError) -> Status {
+        match error {
+            RuntimeError::SessionNotFound { .. } => {
+                Status::not_found(format!("Session not found: {session_id}"))
+            }
+            RuntimeError::InvalidInput { message } => Status::invalid_argument(message),
+            other => Status::internal(format!("Failed to update session memory: {other}")),
+        }
+    }
+
+    async fn session_workspace(
+        &self,
+        session_id: SessionId,
+    ) -> Result<Arc<dyn steer_workspace::Workspace>, Status> {
+        let config = self
+            .catalog
+            .get_session_config(session_id)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to get session config: {e}")))?
+            .ok_or_else(|| Status::not_found(format!("Session not found: {session_id}")))?;
+
+        steer_core::workspace::create_workspace(&config.workspace.to_workspace_config())
+            .await
+            .map_err(|e| Status::internal(format!("Failed to create workspace: {e}")))
+    }
+
     #[expect(clippy::result_large_err)]
-    fn parse_session_id(session_id: &str) -> Result<SessionId, Status> {
+    pub(crate) fn parse_session_id(session_id: &str) -> Result<SessionId, Status> {
         Uuid::parse_str(session_id)
             .map(SessionId::from)
             .map_err(|_| Status::invalid_argument(format!("Invalid session ID: {session_id}")))
@@ -147,14 +216,15 @@ impl RuntimeAgentService {
 
     fn select_default_model(&self) -> steer_core::config::model::ModelId {
         let builtin_default = steer_core::config::model::builtin::default_model();
+        let model_registry = self.model_registry.snapshot();
 
-        if let Some(config) = self.model_registry.get(&builtin_default)
+        if let Some(config) = model_registry.get(&builtin_default)
             && config.recommended
         {
             return builtin_default;
         }
 
-        let mut recommended: Vec<_> = self.model_registry.recommended().collect();
+        let mut recommended: Vec<_> = model_registry.recommended().collect();
         if recommended.is_empty() {
             return builtin_default;
         }
@@ -186,6 +256,91 @@ impl RuntimeAgentService {
         }
     }
 
+    fn mention_error_to_status(err: MentionError) -> Status {
+        match err {
+            MentionError::SymbolNotFound { .. } => Status::not_found(err.to_string()),
+            MentionError::Binary { .. }
+            | MentionError::TooLarge { .. }
+            | MentionError::UnsupportedLanguage { .. } => {
+                Status::failed_precondition(err.to_string())
+            }
+            MentionError::Read { .. } => Status::invalid_argument(err.to_string()),
+        }
+    }
+
+    fn scheduler(&self) -> Result<&Scheduler, Status> {
+        self.scheduler
+            .as_deref()
+            .ok_or_else(|| Status::failed_precondition("Scheduled tasks are not enabled"))
+    }
+
+    fn scheduler_error_to_status(err: SchedulerError) -> Status {
+        match err {
+            SchedulerError::InvalidSchedule { .. } => Status::invalid_argument(err.to_string()),
+            SchedulerError::NotFound { .. } => Status::not_found(err.to_string()),
+            SchedulerError::AlreadyRunning { .. } => Status::already_exists(err.to_string()),
+            SchedulerError::Store(_) | SchedulerError::Runtime(_) => {
+                Status::internal(err.to_string())
+            }
+        }
+    }
+
+    fn audit_log(&self) -> Result<Arc<dyn ToolAuditStore>, Status> {
+        self.audit_log
+            .clone()
+            .ok_or_else(|| Status::failed_precondition("The tool audit log is not enabled"))
+    }
+
+    async fn isolated_workspace(
+        &self,
+        session_id: SessionId,
+    ) -> Result<Option<IsolatedWorkspace>, Status> {
+        match self.runtime.isolated_workspace(session_id).await {
+            Ok(workspace) => Ok(workspace),
+            Err(RuntimeError::SessionNotFound { .. }) => Err(Status::not_found(format!(
+                "Session not found: {session_id}"
+            ))),
+            Err(e) => Err(Status::internal(format!(
+                "Failed to load isolated workspace: {e}"
+            ))),
+        }
+    }
+
+    async fn require_isolated_workspace(
+        &self,
+        session_id: SessionId,
+    ) -> Result<IsolatedWorkspace, Status> {
+        self.isolated_workspace(session_id).await?.ok_or_else(|| {
+            Status::failed_precondition(format!("Session {session_id} is not isolated"))
+        })
+    }
+
+    fn isolation_error_to_status(err: &IsolationError) -> Status {
+        match err {
+            IsolationError::Unsupported { message } => Status::failed_precondition(message),
+            IsolationError::Io { .. } | IsolationError::Git { .. } => {
+                Status::internal(err.to_string())
+            }
+        }
+    }
+
+    fn commit_error_to_status(session_id: SessionId, err: RuntimeError) -> Status {
+        match err {
+            RuntimeError::SessionNotFound { .. } => {
+                Status::not_found(format!("Session not found: {session_id}"))
+            }
+            RuntimeError::InvalidInput { message } => Status::failed_precondition(message),
+            RuntimeError::Commit(CommitError::UntouchedFile { .. } | CommitError::EmptyMessage) => {
+                Status::invalid_argument(err.to_string())
+            }
+            RuntimeError::Commit(CommitError::NoChanges | CommitError::Git { .. }) => {
+                Status::failed_precondition(err.to_string())
+            }
+            RuntimeError::Commit(CommitError::Cancelled) => Status::cancelled(err.to_string()),
+            other => Status::internal(format!("Failed to commit: {other}")),
+        }
+    }
+
     fn environment_manager_error_to_status(
         err: steer_workspace::EnvironmentManagerError,
     ) -> Status {
@@ -202,9 +357,12 @@ impl RuntimeAgentService {
         }
     }
 
+    /// Pick the flow for `provider_id`, honouring `requested` when the provider
+    /// supports it. Unsupported requests fall back to the provider's default method.
     fn create_auth_flow(
         &self,
         provider_id: &steer_core::config::provider::ProviderId,
+        requested: Option<AuthMethod>,
     ) -> Result<(Arc<dyn DynAuthenticationFlow>, AuthMethod), Status> {
         let provider_cfg = self.provider_registry.get(provider_id).ok_or_else(|| {
             Status::not_found(format!("Unknown provider: {}", provider_id.as_str()))
@@ -216,7 +374,9 @@ impl RuntimeAgentService {
             && let Some(flow) = plugin.create_flow(auth_storage.clone())
         {
             let methods = flow.available_methods();
-            let method = if methods.contains(&AuthMethod::OAuth) {
+            let method = if let Some(requested) = requested.filter(|m| methods.contains(m)) {
+                requested
+            } else if methods.contains(&AuthMethod::OAuth) {
                 AuthMethod::OAuth
             } else if methods.contains(&AuthMethod::ApiKey) {
                 AuthMethod::ApiKey
@@ -242,6 +402,7 @@ impl RuntimeAgentService {
 impl agent_service_server::AgentService for RuntimeAgentService {
     type SubscribeSessionEventsStream = ReceiverStream<Result<SessionEvent, Status>>;
     type ListFilesStream = ReceiverStream<Result<ListFilesResponse, Status>>;
+    type ExportAuditStream = ReceiverStream<Result<proto::ExportAuditResponse, Status>>;
     type GetSessionStream =
         std::pin::Pin<Box<dyn futures::Stream<Item = Result<GetSessionResponse, Status>> + Send>>;
     type GetConversationStream = std::pin::Pin<
@@ -273,6 +434,25 @@ impl agent_service_server::AgentService for RuntimeAgentService {
             .await
             .map_err(|e| Status::internal(format!("Failed to subscribe to deltas: {e}")))?;
 
+        let filter = EventKindFilter::from_proto(&req.event_kinds);
+        let file_watch = if filter.allows_kind(SessionEventKind::Workspace) {
+            match self.session_workspace(session_id).await {
+                Ok(workspace) => workspace.watch_files().unwrap_or_else(|e| {
+                    warn!("Failed to watch workspace files for {}: {}", session_id, e);
+                    None
+                }),
+                Err(status) => {
+                    debug!(
+                        "No workspace to watch for session {}: {}",
+                        session_id,
+                        status.message()
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
         let (tx, rx) = mpsc::channel(100);
         let last_sequence = Arc::new(AtomicU64::new(req.since_sequence.unwrap_or(0)));
         let delta_sequence = Arc::new(AtomicU64::new(0));
@@ -293,7 +473,7 @@ impl agent_service_server::AgentService for RuntimeAgentService {
                             }
                         };
 
-                        if proto_event.event.is_none() {
+                        if !filter.allows(&proto_event) {
                             continue;
                         }
 
@@ -315,6 +495,7 @@ impl agent_service_server::AgentService for RuntimeAgentService {
         let last_sequence_events = last_sequence.clone();
         let delta_sequence_counter = delta_sequence.clone();
         let min_live_seq = min_live_seq;
+        let forward_deltas = filter.allows_kind(SessionEventKind::StreamDeltas);
         tokio::spawn(async move {
             async fn send_delta(
                 delta: steer_core::app::domain::delta::StreamDelta,
@@ -342,6 +523,7 @@ impl agent_service_server::AgentService for RuntimeAgentService {
 
             let mut subscription = subscription;
             let mut delta_rx = delta_subscription;
+            let mut file_watch = file_watch;
             let mut events_closed = false;
             let mut deltas_closed = false;
 
@@ -356,6 +538,7 @@ impl agent_service_server::AgentService for RuntimeAgentService {
                             Some(envelope) => {
                                 loop {
                                     match delta_rx.try_recv() {
+                                        Ok(_) if !forward_deltas => {}
                                         Ok(delta) => {
                                             if send_delta(
                                                 delta,
@@ -393,7 +576,8 @@ impl agent_service_server::AgentService for RuntimeAgentService {
                                     }
                                 };
 
-                                if proto_event.event.is_none() {
+                                if !filter.allows(&proto_event) {
+                                    last_sequence_events.store(envelope.seq, Ordering::Relaxed);
                                     continue;
                                 }
 
@@ -410,6 +594,7 @@ impl agent_service_server::AgentService for RuntimeAgentService {
                     }
                     delta = delta_rx.recv(), if !deltas_closed => {
                         match delta {
+                            Ok(_) if !forward_deltas => {}
                             Ok(delta) => {
                                 if send_delta(
                                     delta,
@@ -431,6 +616,21 @@ impl agent_service_server::AgentService for RuntimeAgentService {
                             }
                         }
                     }
+                    change = async { file_watch.as_mut()?.recv().await }, if file_watch.is_some() => {
+                        match change {
+                            Some(change) => {
+                                let sequence_num = last_sequence_events.load(Ordering::Relaxed);
+                                let proto_event = workspace_file_change_to_proto(change, sequence_num);
+                                if let Err(e) = event_tx.send(Ok(proto_event)).await {
+                                    warn!("Failed to send workspace file change to client: {}", e);
+                                    break;
+                                }
+                            }
+                            None => {
+                                file_watch = None;
+                            }
+                        }
+                    }
                 }
             }
             debug!("Event forwarding task ended for session: {}", session_id);
@@ -530,6 +730,10 @@ impl agent_service_server::AgentService for RuntimeAgentService {
                     threshold_percent: ac.threshold_percent,
                 })
                 .unwrap_or_default(),
+            isolation: proto_to_isolation_mode(req.isolation),
+            isolated_workspace: None,
+            auto_summary: proto_to_auto_summary_config(req.auto_summary)
+                .map_err(|e| Status::invalid_argument(format!("Invalid auto_summary: {e}")))?,
         };
 
         match self.runtime.create_session(session_config.clone()).await {
@@ -552,11 +756,18 @@ impl agent_service_server::AgentService for RuntimeAgentService {
                     status: proto::SessionStatus::Active as i32,
                     metadata: None,
                     title: session_config.title.clone(),
+                    summary: None,
+                    workspace_path: session_config.workspace.get_path(),
                 };
                 Ok(Response::new(CreateSessionResponse {
                     session: Some(session_info),
                 }))
             }
+            Err(RuntimeError::InvalidInput { message }) => Err(Status::invalid_argument(message)),
+            Err(RuntimeError::Isolation(e)) => Err(Self::isolation_error_to_status(&e)),
+            Err(e @ RuntimeError::SessionLimitReached { .. }) => {
+                Err(Status::resource_exhausted(e.to_string()))
+            }
             Err(e) => {
                 error!("Failed to create session: {}", e);
                 Err(Status::internal(format!("Failed to create session: {e}")))
@@ -585,6 +796,8 @@ impl agent_service_server::AgentService for RuntimeAgentService {
                         status: proto::SessionStatus::Active as i32,
                         metadata: None,
                         title: s.title,
+                        summary: s.summary,
+                        workspace_path: s.workspace_path,
                     })
                     .collect();
 
@@ -667,7 +880,13 @@ impl agent_service_server::AgentService for RuntimeAgentService {
                                     attachment_count: message
                                         .content
                                         .iter()
-                                        .filter(|item| matches!(item, UserContent::Image { .. }))
+                                        .filter(|item| {
+                                            matches!(
+                                                item,
+                                                UserContent::Image { .. }
+                                                    | UserContent::Attachment { .. }
+                                            )
+                                        })
                                         .count() as u32,
                                 }
                             }
@@ -789,6 +1008,10 @@ impl agent_service_server::AgentService for RuntimeAgentService {
                         exit_code: cmd.exit_code,
                     })
                 }
+                Some(proto::user_content::Content::Attachment(attachment)) => {
+                    proto_attachment_to_core(attachment)
+                        .map(|attachment| UserContent::Attachment { attachment })
+                }
                 Some(proto::user_content::Content::Image(image)) => {
                     let source = image.source.map(|source| match source {
                         proto::image_content::Source::SessionFile(file) => {
@@ -859,6 +1082,9 @@ impl agent_service_server::AgentService for RuntimeAgentService {
                 }),
             })),
             Err(RuntimeError::InvalidInput { message }) => Err(Status::invalid_argument(message)),
+            Err(e @ RuntimeError::SessionLimitReached { .. }) => {
+                Err(Status::resource_exhausted(e.to_string()))
+            }
             Err(e) => {
                 error!("Failed to send message: {}", e);
                 Err(Status::internal(format!("Failed to send message: {e}")))
@@ -899,6 +1125,10 @@ impl agent_service_server::AgentService for RuntimeAgentService {
                         exit_code: cmd.exit_code,
                     })
                 }
+                Some(proto::user_content::Content::Attachment(attachment)) => {
+                    proto_attachment_to_core(attachment)
+                        .map(|attachment| UserContent::Attachment { attachment })
+                }
                 Some(proto::user_content::Content::Image(image)) => {
                     let source = image.source.map(|source| match source {
                         proto::image_content::Source::SessionFile(file) => {
@@ -950,6 +1180,97 @@ impl agent_service_server::AgentService for RuntimeAgentService {
         Ok(Response::new(EditMessageResponse {}))
     }
 
+    async fn retry_last_turn(
+        &self,
+        request: Request<RetryLastTurnRequest>,
+    ) -> Result<Response<RetryLastTurnResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+
+        let model = if let Some(model_spec) = req.model {
+            proto_to_model(&model_spec)
+                .map_err(|e| Status::invalid_argument(format!("Invalid model spec: {e}")))?
+        } else {
+            let config = self
+                .catalog
+                .get_session_config(session_id)
+                .await
+                .map_err(|e| Status::internal(format!("Failed to get session config: {e}")))?
+                .ok_or_else(|| Status::not_found("Session config not found"))?;
+            config.default_model
+        };
+
+        self.runtime
+            .retry_last_turn(session_id, model)
+            .await
+            .map_err(|e| match e {
+                RuntimeError::InvalidInput { message } => Status::failed_precondition(message),
+                other => Status::internal(format!("Failed to retry last turn: {other}")),
+            })?;
+
+        Ok(Response::new(RetryLastTurnResponse {}))
+    }
+
+    async fn start_best_of(
+        &self,
+        request: Request<StartBestOfRequest>,
+    ) -> Result<Response<StartBestOfResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+
+        let model = if let Some(model_spec) = req.model {
+            proto_to_model(&model_spec)
+                .map_err(|e| Status::invalid_argument(format!("Invalid model spec: {e}")))?
+        } else {
+            let config = self
+                .catalog
+                .get_session_config(session_id)
+                .await
+                .map_err(|e| Status::internal(format!("Failed to get session config: {e}")))?
+                .ok_or_else(|| Status::not_found("Session config not found"))?;
+            config.default_model
+        };
+
+        let op_id = self
+            .runtime
+            .start_best_of(session_id, req.message, req.candidates as usize, model)
+            .await
+            .map_err(|e| match e {
+                RuntimeError::InvalidInput { message } => Status::failed_precondition(message),
+                other => Status::internal(format!("Failed to start best-of: {other}")),
+            })?;
+
+        Ok(Response::new(StartBestOfResponse {
+            operation: Some(Operation {
+                id: op_id.to_string(),
+                session_id: session_id.to_string(),
+                r#type: OperationType::SendMessage as i32,
+                status: OperationStatus::Running as i32,
+                created_at: Some(prost_types::Timestamp::from(std::time::SystemTime::now())),
+                completed_at: None,
+                metadata: std::collections::HashMap::new(),
+            }),
+        }))
+    }
+
+    async fn pick_best_of_candidate(
+        &self,
+        request: Request<PickBestOfCandidateRequest>,
+    ) -> Result<Response<PickBestOfCandidateResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+
+        self.runtime
+            .pick_best_of_candidate(session_id, req.index)
+            .await
+            .map_err(|e| match e {
+                RuntimeError::InvalidInput { message } => Status::failed_precondition(message),
+                other => Status::internal(format!("Failed to pick best-of candidate: {other}")),
+            })?;
+
+        Ok(Response::new(PickBestOfCandidateResponse {}))
+    }
+
     async fn dequeue_queued_item(
         &self,
         request: Request<DequeueQueuedItemRequest>,
@@ -979,17 +1300,41 @@ impl agent_service_server::AgentService for RuntimeAgentService {
             .map(steer_core::app::domain::types::RequestId::from)
             .map_err(|_| Status::invalid_argument("Invalid tool call ID"))?;
 
-        let (approved, remember) = match req.decision {
+        use steer_core::app::domain::action::{ApprovalDecision, ApprovalMemory};
+
+        let (decision, remember) = match req.decision {
             Some(decision) => match decision.decision_type {
-                Some(proto::approval_decision::DecisionType::Deny(_)) => (false, None),
-                Some(proto::approval_decision::DecisionType::Once(_)) => (true, None),
+                Some(proto::approval_decision::DecisionType::Deny(_)) => {
+                    (ApprovalDecision::Denied, None)
+                }
+                Some(proto::approval_decision::DecisionType::DenyWithReason(reason)) => {
+                    let reason = reason.trim();
+                    if reason.is_empty() {
+                        (ApprovalDecision::Denied, None)
+                    } else {
+                        (ApprovalDecision::DeniedWithReason(reason.to_string()), None)
+                    }
+                }
+                Some(proto::approval_decision::DecisionType::Once(_)) => {
+                    (ApprovalDecision::Approved, None)
+                }
                 Some(proto::approval_decision::DecisionType::AlwaysTool(_)) => (
-                    true,
-                    Some(steer_core::app::domain::action::ApprovalMemory::PendingTool),
+                    ApprovalDecision::Approved,
+                    Some(ApprovalMemory::PendingTool),
                 ),
                 Some(proto::approval_decision::DecisionType::AlwaysBashPattern(pattern)) => (
-                    true,
-                    Some(steer_core::app::domain::action::ApprovalMemory::BashPattern(pattern)),
+                    ApprovalDecision::Approved,
+                    Some(ApprovalMemory::BashPattern(pattern)),
+                ),
+                Some(proto::approval_decision::DecisionType::AcceptHunks(hunks)) => (
+                    ApprovalDecision::ApprovedHunks(
+                        hunks
+                            .indices
+                            .into_iter()
+                            .map(|index| index as usize)
+                            .collect(),
+                    ),
+                    None,
                 ),
                 None => {
                     return Err(Status::invalid_argument("Invalid approval decision"));
@@ -1002,7 +1347,7 @@ impl agent_service_server::AgentService for RuntimeAgentService {
 
         match self
             .runtime
-            .submit_tool_approval(session_id, request_id, approved, remember)
+            .submit_tool_approval(session_id, request_id, decision, remember)
             .await
         {
             Ok(()) => Ok(Response::new(ApproveToolResponse {})),
@@ -1037,15 +1382,45 @@ impl agent_service_server::AgentService for RuntimeAgentService {
         Ok(Response::new(SwitchPrimaryAgentResponse {}))
     }
 
+    async fn switch_model(
+        &self,
+        request: Request<SwitchModelRequest>,
+    ) -> Result<Response<SwitchModelResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+        let model_spec = req
+            .model
+            .ok_or_else(|| Status::invalid_argument("Missing model spec"))?;
+        let model = proto_to_model(&model_spec)
+            .map_err(|e| Status::invalid_argument(format!("Invalid model spec: {e}")))?;
+
+        self.runtime
+            .switch_model(session_id, model, proto_to_model_switch_mode(req.mode))
+            .await
+            .map_err(|e| Status::internal(format!("Failed to switch model: {e}")))?;
+
+        Ok(Response::new(SwitchModelResponse {}))
+    }
+
     async fn cancel_operation(
         &self,
         request: Request<CancelOperationRequest>,
     ) -> Result<Response<CancelOperationResponse>, Status> {
         let req = request.into_inner();
         let session_id = Self::parse_session_id(&req.session_id)?;
+        let op_id = req
+            .op_id
+            .as_deref()
+            .map(parse_op_id)
+            .transpose()
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
 
-        match self.runtime.cancel_operation(session_id, None).await {
-            Ok(()) => Ok(Response::new(CancelOperationResponse {})),
+        match self
+            .runtime
+            .cancel_running_operation(session_id, op_id, req.force)
+            .await
+        {
+            Ok(cancelled) => Ok(Response::new(CancelOperationResponse { cancelled })),
             Err(e) => {
                 error!("Failed to cancel operation: {}", e);
                 Err(Status::internal(format!("Failed to cancel operation: {e}")))
@@ -1100,17 +1475,7 @@ impl agent_service_server::AgentService for RuntimeAgentService {
 
         debug!("ListFiles called for session: {}", session_id);
 
-        let config = self
-            .catalog
-            .get_session_config(session_id)
-            .await
-            .map_err(|e| Status::internal(format!("Failed to get session config: {e}")))?
-            .ok_or_else(|| Status::not_found(format!("Session not found: {session_id}")))?;
-
-        let workspace =
-            steer_core::workspace::create_workspace(&config.workspace.to_workspace_config())
-                .await
-                .map_err(|e| Status::internal(format!("Failed to create workspace: {e}")))?;
+        let workspace = self.session_workspace(session_id).await?;
 
         let (tx, rx) = mpsc::channel(100);
 
@@ -1152,6 +1517,86 @@ impl agent_service_server::AgentService for RuntimeAgentService {
         Ok(Response::new(ReceiverStream::new(rx)))
     }
 
+    async fn list_file_symbols(
+        &self,
+        request: Request<ListFileSymbolsRequest>,
+    ) -> Result<Response<ListFileSymbolsResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+        let workspace = self.session_workspace(session_id).await?;
+
+        let symbols = mentions::list_file_symbols(workspace.as_ref(), &req.path)
+            .await
+            .map_err(Self::mention_error_to_status)?;
+
+        Ok(Response::new(ListFileSymbolsResponse {
+            symbols: symbols.into_iter().map(file_symbol_to_proto).collect(),
+        }))
+    }
+
+    async fn expand_file_mention(
+        &self,
+        request: Request<ExpandFileMentionRequest>,
+    ) -> Result<Response<ExpandFileMentionResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+        let workspace = self.session_workspace(session_id).await?;
+
+        let text = mentions::expand_file_mention(
+            workspace.as_ref(),
+            &req.path,
+            req.symbol.as_deref(),
+            req.outline_large_files,
+        )
+        .await
+        .map_err(Self::mention_error_to_status)?;
+
+        Ok(Response::new(ExpandFileMentionResponse { text }))
+    }
+
+    async fn get_context_report(
+        &self,
+        request: Request<proto::GetContextReportRequest>,
+    ) -> Result<Response<proto::GetContextReportResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+
+        if let Err(e) = self.runtime.resume_session(session_id).await
+            && matches!(e, RuntimeError::SessionNotFound { .. })
+        {
+            return Err(Status::not_found(format!(
+                "Session not found: {session_id}"
+            )));
+        }
+
+        let state = self
+            .runtime
+            .get_session_state(session_id)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to get session state: {e}")))?;
+
+        let model = match req.model {
+            Some(spec) => proto_to_model(&spec)
+                .map_err(|e| Status::invalid_argument(format!("Invalid model: {e}")))?,
+            None => state
+                .session_config
+                .as_ref()
+                .map(|config| config.default_model.clone())
+                .ok_or_else(|| Status::failed_precondition("Session has no default model"))?,
+        };
+        let model_registry = self.model_registry.snapshot();
+        let model_config = model_registry.get(&model);
+        let report = steer_core::app::ContextReport::from_state(
+            &state,
+            model_config.and_then(|config| config.context_window_tokens),
+            model_config
+                .and_then(|config| config.parameters)
+                .and_then(|parameters| parameters.max_output_tokens),
+        );
+
+        Ok(Response::new(context_report_to_proto(report)))
+    }
+
     async fn get_mcp_servers(
         &self,
         request: Request<GetMcpServersRequest>,
@@ -1244,9 +1689,11 @@ impl agent_service_server::AgentService for RuntimeAgentService {
         &self,
         _request: Request<ListProvidersRequest>,
     ) -> Result<Response<ListProvidersResponse>, Status> {
+        // Providers without auth schemes, like `mock`, have nothing to set up.
         let providers = self
             .provider_registry
             .all()
+            .filter(|p| !p.auth_schemes.is_empty())
             .map(|p| proto::ProviderInfo {
                 id: p.id.storage_key(),
                 name: p.name.clone(),
@@ -1287,7 +1734,8 @@ impl agent_service_server::AgentService for RuntimeAgentService {
 
         let mut all_models = Vec::new();
 
-        for model in self.model_registry.recommended() {
+        let model_registry = self.model_registry.snapshot();
+        for model in model_registry.recommended() {
             if let Some(ref provider_id) = req.provider_id
                 && model.provider.storage_key() != *provider_id
             {
@@ -1350,6 +1798,10 @@ impl agent_service_server::AgentService for RuntimeAgentService {
                     .is_some_and(|tc| tc.enabled),
                 aliases: model.aliases.clone(),
                 context_window_tokens: model.context_window_tokens,
+                unavailable_reason: self
+                    .provider_registry
+                    .get(&provider_id)
+                    .and_then(steer_core::offline::unavailable_reason),
             });
         }
 
@@ -1363,7 +1815,11 @@ impl agent_service_server::AgentService for RuntimeAgentService {
         let req = request.into_inner();
 
         let mut statuses = Vec::new();
-        for p in self.provider_registry.all() {
+        for p in self
+            .provider_registry
+            .all()
+            .filter(|p| !p.auth_schemes.is_empty())
+        {
             if let Some(ref filter) = req.provider_id
                 && &p.id.storage_key() != filter
             {
@@ -1392,13 +1848,31 @@ impl agent_service_server::AgentService for RuntimeAgentService {
     ) -> Result<Response<proto::StartAuthResponse>, Status> {
         self.auth_flow_manager.cleanup().await;
         let req = request.into_inner();
+        let requested = req
+            .method
+            .and_then(|method| proto::AuthMethod::try_from(method).ok())
+            .and_then(crate::grpc::conversions::proto_to_auth_method);
         let provider_id = steer_core::config::provider::ProviderId(req.provider_id);
 
-        let (flow, method) = self.create_auth_flow(&provider_id)?;
-        let state = flow
-            .start_auth(method)
-            .await
-            .map_err(|e| Status::internal(format!("auth start failed: {e}")))?;
+        let (flow, mut method) = self.create_auth_flow(&provider_id, requested)?;
+        let state = match flow.start_auth(method).await {
+            Ok(state) => state,
+            Err(e)
+                if method == AuthMethod::DeviceCode
+                    && flow.available_methods().contains(&AuthMethod::OAuth) =>
+            {
+                warn!(
+                    "Device code auth unavailable for {}, falling back to OAuth: {}",
+                    provider_id.as_str(),
+                    e
+                );
+                method = AuthMethod::OAuth;
+                flow.start_auth(method)
+                    .await
+                    .map_err(|e| Status::internal(format!("auth start failed: {e}")))?
+            }
+            Err(e) => return Err(Status::internal(format!("auth start failed: {e}"))),
+        };
         let progress = flow
             .get_initial_progress(&state, method)
             .await
@@ -1500,7 +1974,11 @@ impl agent_service_server::AgentService for RuntimeAgentService {
         let req = request.into_inner();
         let flow_id = req.flow_id;
 
-        let _ = self.auth_flow_manager.take(&flow_id).await;
+        if let Some(mut entry) = self.auth_flow_manager.take(&flow_id).await
+            && let Err(e) = entry.flow.cancel(&mut entry.state).await
+        {
+            warn!("Failed to cancel auth flow {}: {}", flow_id, e);
+        }
 
         Ok(Response::new(proto::CancelAuthResponse {}))
     }
@@ -1511,7 +1989,7 @@ impl agent_service_server::AgentService for RuntimeAgentService {
     ) -> Result<Response<proto::ResolveModelResponse>, Status> {
         let req = request.into_inner();
 
-        match self.model_registry.resolve(&req.input) {
+        match self.model_registry.snapshot().resolve(&req.input) {
             Ok(model_id) => {
                 let steer_core::config::model::ModelId { provider, id } = model_id;
                 let model_spec = proto::ModelSpec {
@@ -1729,4 +2207,469 @@ impl agent_service_server::AgentService for RuntimeAgentService {
 
         Ok(Response::new(proto::DeleteEnvironmentResponse {}))
     }
+
+    async fn create_scheduled_task(
+        &self,
+        request: Request<proto::CreateScheduledTaskRequest>,
+    ) -> Result<Response<proto::CreateScheduledTaskResponse>, Status> {
+        let req = request.into_inner();
+        let scheduler = self.scheduler()?;
+
+        let config = req
+            .config
+            .ok_or_else(|| Status::invalid_argument("Missing required config"))
+            .and_then(|config| {
+                proto_to_session_config(config)
+                    .map_err(|e| Status::invalid_argument(format!("Invalid config: {e}")))
+            })?;
+
+        let task = scheduler
+            .create_task(req.schedule, req.prompt, config, req.max_total_tokens)
+            .await
+            .map_err(Self::scheduler_error_to_status)?;
+
+        Ok(Response::new(proto::CreateScheduledTaskResponse {
+            task: Some(scheduled_task_to_proto(&task)),
+        }))
+    }
+
+    async fn list_scheduled_tasks(
+        &self,
+        _request: Request<proto::ListScheduledTasksRequest>,
+    ) -> Result<Response<proto::ListScheduledTasksResponse>, Status> {
+        let tasks = self
+            .scheduler()?
+            .list_tasks()
+            .await
+            .map_err(Self::scheduler_error_to_status)?;
+
+        Ok(Response::new(proto::ListScheduledTasksResponse {
+            tasks: tasks.iter().map(scheduled_task_to_proto).collect(),
+        }))
+    }
+
+    async fn get_scheduled_task(
+        &self,
+        request: Request<proto::GetScheduledTaskRequest>,
+    ) -> Result<Response<proto::GetScheduledTaskResponse>, Status> {
+        let req = request.into_inner();
+        let task = self
+            .scheduler()?
+            .get_task(&req.id)
+            .await
+            .map_err(Self::scheduler_error_to_status)?;
+
+        Ok(Response::new(proto::GetScheduledTaskResponse {
+            task: Some(scheduled_task_to_proto(&task)),
+        }))
+    }
+
+    async fn delete_scheduled_task(
+        &self,
+        request: Request<proto::DeleteScheduledTaskRequest>,
+    ) -> Result<Response<proto::DeleteScheduledTaskResponse>, Status> {
+        let req = request.into_inner();
+        self.scheduler()?
+            .delete_task(&req.id)
+            .await
+            .map_err(Self::scheduler_error_to_status)?;
+
+        Ok(Response::new(proto::DeleteScheduledTaskResponse {}))
+    }
+
+    async fn run_scheduled_task_now(
+        &self,
+        request: Request<proto::RunScheduledTaskNowRequest>,
+    ) -> Result<Response<proto::RunScheduledTaskNowResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = self
+            .scheduler()?
+            .run_now(&req.id)
+            .await
+            .map_err(Self::scheduler_error_to_status)?;
+
+        Ok(Response::new(proto::RunScheduledTaskNowResponse {
+            session_id: session_id.to_string(),
+        }))
+    }
+
+    async fn get_isolation_status(
+        &self,
+        request: Request<proto::GetIsolationStatusRequest>,
+    ) -> Result<Response<proto::GetIsolationStatusResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+        let Some(workspace) = self.isolated_workspace(session_id).await? else {
+            return Ok(Response::new(proto::GetIsolationStatusResponse::default()));
+        };
+
+        let changes = workspace
+            .changes()
+            .await
+            .map_err(|e| Self::isolation_error_to_status(&e))?;
+
+        Ok(Response::new(proto::GetIsolationStatusResponse {
+            workspace: Some(isolated_workspace_to_proto(&workspace)),
+            changes: changes.iter().map(file_change_to_proto).collect(),
+        }))
+    }
+
+    async fn merge_isolated_workspace(
+        &self,
+        request: Request<proto::MergeIsolatedWorkspaceRequest>,
+    ) -> Result<Response<proto::MergeIsolatedWorkspaceResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+        let workspace = self.require_isolated_workspace(session_id).await?;
+
+        let report = workspace
+            .merge()
+            .await
+            .map_err(|e| Self::isolation_error_to_status(&e))?;
+
+        Ok(Response::new(proto::MergeIsolatedWorkspaceResponse {
+            applied: report.applied,
+            conflicts: report.conflicts,
+            details: report.details,
+        }))
+    }
+
+    async fn export_audit(
+        &self,
+        request: Request<proto::ExportAuditRequest>,
+    ) -> Result<Response<Self::ExportAuditStream>, Status> {
+        let req = request.into_inner();
+        let audit_log = self.audit_log()?;
+        let filter = ToolAuditFilter {
+            session_id: req
+                .session_id
+                .as_deref()
+                .map(Self::parse_session_id)
+                .transpose()?,
+            tool_name: req.tool_name,
+            since: req
+                .since
+                .map(|since| {
+                    chrono::DateTime::from_timestamp(
+                        since.seconds,
+                        u32::try_from(since.nanos).unwrap_or(0),
+                    )
+                    .ok_or_else(|| Status::invalid_argument("Invalid since timestamp"))
+                })
+                .transpose()?,
+        };
+
+        let entries = audit_log
+            .list_tool_executions(&filter)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to read audit log: {e}")))?;
+
+        let (tx, rx) = mpsc::channel(16);
+        tokio::spawn(async move {
+            for chunk in entries.chunks(500) {
+                let mut jsonl = String::new();
+                for entry in chunk {
+                    match serde_json::to_string(entry) {
+                        Ok(line) => {
+                            jsonl.push_str(&line);
+                            jsonl.push('\n');
+                        }
+                        Err(e) => {
+                            let _ = tx
+                                .send(Err(Status::internal(format!(
+                                    "Failed to serialize audit entry: {e}"
+                                ))))
+                                .await;
+                            return;
+                        }
+                    }
+                }
+                if tx
+                    .send(Ok(proto::ExportAuditResponse { jsonl }))
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn export_isolated_patch(
+        &self,
+        request: Request<proto::ExportIsolatedPatchRequest>,
+    ) -> Result<Response<proto::ExportIsolatedPatchResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+        let workspace = self.require_isolated_workspace(session_id).await?;
+
+        let patch = workspace
+            .patch()
+            .await
+            .map_err(|e| Self::isolation_error_to_status(&e))?;
+
+        Ok(Response::new(proto::ExportIsolatedPatchResponse { patch }))
+    }
+
+    async fn commit_isolated_branch(
+        &self,
+        request: Request<proto::CommitIsolatedBranchRequest>,
+    ) -> Result<Response<proto::CommitIsolatedBranchResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+        let workspace = self.require_isolated_workspace(session_id).await?;
+
+        let message = req
+            .message
+            .filter(|message| !message.trim().is_empty())
+            .unwrap_or_else(|| format!("Changes from steer session {session_id}"));
+        let branch = workspace
+            .commit_to_branch(&message)
+            .await
+            .map_err(|e| Self::isolation_error_to_status(&e))?;
+
+        Ok(Response::new(proto::CommitIsolatedBranchResponse {
+            branch,
+        }))
+    }
+
+    async fn discard_isolated_workspace(
+        &self,
+        request: Request<proto::DiscardIsolatedWorkspaceRequest>,
+    ) -> Result<Response<proto::DiscardIsolatedWorkspaceResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+        let workspace = self.require_isolated_workspace(session_id).await?;
+
+        // The session's tools point at the worktree; stop it so it is not
+        // resumed against a missing directory without an explicit error.
+        if let Err(e) = self.runtime.suspend_session(session_id).await {
+            warn!(
+                "Failed to suspend session before discarding worktree: {}",
+                e
+            );
+        }
+        workspace
+            .discard()
+            .await
+            .map_err(|e| Self::isolation_error_to_status(&e))?;
+
+        Ok(Response::new(proto::DiscardIsolatedWorkspaceResponse {}))
+    }
+
+    async fn run_review(
+        &self,
+        request: Request<proto::RunReviewRequest>,
+    ) -> Result<Response<proto::RunReviewResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+        let target: ReviewTarget = req
+            .target
+            .parse()
+            .map_err(|e: ReviewError| Status::invalid_argument(e.to_string()))?;
+        let model_spec = req
+            .model
+            .ok_or_else(|| Status::invalid_argument("Missing model spec"))?;
+        let model = proto_to_model(&model_spec)
+            .map_err(|e| Status::invalid_argument(format!("Invalid model spec: {e}")))?;
+
+        let report = self
+            .runtime
+            .review(session_id, target, model)
+            .await
+            .map_err(|e| match e {
+                RuntimeError::SessionNotFound { .. } => {
+                    Status::not_found(format!("Session not found: {session_id}"))
+                }
+                RuntimeError::InvalidInput { message } => Status::failed_precondition(message),
+                RuntimeError::Review(ReviewError::Git { .. }) => {
+                    Status::failed_precondition(e.to_string())
+                }
+                RuntimeError::Review(ReviewError::Cancelled) => Status::cancelled(e.to_string()),
+                other => Status::internal(format!("Failed to run review: {other}")),
+            })?;
+
+        Ok(Response::new(review_report_to_proto(&report)))
+    }
+
+    async fn draft_commit(
+        &self,
+        request: Request<proto::DraftCommitRequest>,
+    ) -> Result<Response<proto::DraftCommitResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+        let model_spec = req
+            .model
+            .ok_or_else(|| Status::invalid_argument("Missing model spec"))?;
+        let model = proto_to_model(&model_spec)
+            .map_err(|e| Status::invalid_argument(format!("Invalid model spec: {e}")))?;
+
+        let draft = self
+            .runtime
+            .draft_commit(session_id, model)
+            .await
+            .map_err(|e| Self::commit_error_to_status(session_id, e))?;
+
+        Ok(Response::new(commit_draft_to_proto(&draft)))
+    }
+
+    async fn create_commit(
+        &self,
+        request: Request<proto::CreateCommitRequest>,
+    ) -> Result<Response<proto::CreateCommitResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+
+        let outcome = self
+            .runtime
+            .create_commit(session_id, req.files, req.message)
+            .await
+            .map_err(|e| Self::commit_error_to_status(session_id, e))?;
+
+        Ok(Response::new(commit_outcome_to_proto(&outcome)))
+    }
+
+    async fn summarize_session(
+        &self,
+        request: Request<proto::SummarizeSessionRequest>,
+    ) -> Result<Response<proto::SummarizeSessionResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+
+        let summary = self
+            .runtime
+            .summarize_session(session_id)
+            .await
+            .map_err(|e| match e {
+                RuntimeError::SessionNotFound { .. } => {
+                    Status::not_found(format!("Session not found: {session_id}"))
+                }
+                RuntimeError::InvalidInput { message } => Status::failed_precondition(message),
+                RuntimeError::Summary(SessionSummaryError::NotAuthenticated { .. }) => {
+                    Status::failed_precondition(e.to_string())
+                }
+                other => Status::internal(format!("Failed to summarize session: {other}")),
+            })?;
+
+        Ok(Response::new(proto::SummarizeSessionResponse { summary }))
+    }
+
+    async fn get_session_memory(
+        &self,
+        request: Request<proto::GetSessionMemoryRequest>,
+    ) -> Result<Response<proto::SessionMemoryResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+
+        let entries = self
+            .runtime
+            .load_memory(session_id)
+            .await
+            .map_err(|e| Self::memory_status(session_id, e))?;
+
+        Ok(Response::new(Self::memory_response(&entries)))
+    }
+
+    async fn remember_session_fact(
+        &self,
+        request: Request<proto::RememberSessionFactRequest>,
+    ) -> Result<Response<proto::SessionMemoryResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+
+        let entries = self
+            .runtime
+            .remember(
+                session_id,
+                MemoryEntry {
+                    key: req.key,
+                    content: req.content,
+                },
+            )
+            .await
+            .map_err(|e| Self::memory_status(session_id, e))?;
+
+        Ok(Response::new(Self::memory_response(&entries)))
+    }
+
+    async fn forget_session_fact(
+        &self,
+        request: Request<proto::ForgetSessionFactRequest>,
+    ) -> Result<Response<proto::SessionMemoryResponse>, Status> {
+        let req = request.into_inner();
+        let session_id = Self::parse_session_id(&req.session_id)?;
+
+        let entries = self
+            .runtime
+            .forget(session_id, req.key)
+            .await
+            .map_err(|e| Self::memory_status(session_id, e))?;
+
+        Ok(Response::new(Self::memory_response(&entries)))
+    }
+
+    async fn set_event_logging(
+        &self,
+        request: Request<proto::SetEventLoggingRequest>,
+    ) -> Result<Response<proto::SetEventLoggingResponse>, Status> {
+        let logger = self.event_logger.as_deref().ok_or_else(|| {
+            Status::failed_precondition(
+                "Event logging is not configured; start the server with --log-events",
+            )
+        })?;
+
+        if let Some(enabled) = request.into_inner().enabled {
+            logger.set_enabled(enabled);
+        }
+
+        Ok(Response::new(proto::SetEventLoggingResponse {
+            enabled: logger.is_enabled(),
+        }))
+    }
+
+    async fn reload_catalog(
+        &self,
+        _request: Request<proto::ReloadCatalogRequest>,
+    ) -> Result<Response<proto::ReloadCatalogResponse>, Status> {
+        let reload = self
+            .model_registry
+            .reload()
+            .map_err(|e| Status::invalid_argument(format!("Failed to reload catalogs: {e}")))?;
+        info!(
+            added = reload.added.len(),
+            removed = reload.removed.len(),
+            changed = reload.changed.len(),
+            "Reloaded model catalogs"
+        );
+
+        let to_proto = |ids: Vec<steer_core::config::model::ModelId>| {
+            ids.into_iter().map(model_to_proto).collect()
+        };
+        Ok(Response::new(proto::ReloadCatalogResponse {
+            added: to_proto(reload.added),
+            removed: to_proto(reload.removed),
+            changed: to_proto(reload.changed),
+            model_count: u32::try_from(reload.model_count).unwrap_or(u32::MAX),
+        }))
+    }
+
+    async fn list_active_operations(
+        &self,
+        _request: Request<proto::ListActiveOperationsRequest>,
+    ) -> Result<Response<proto::ListActiveOperationsResponse>, Status> {
+        let operations = self
+            .runtime
+            .list_active_operations()
+            .await
+            .map_err(|e| Status::internal(format!("Failed to list active operations: {e}")))?;
+
+        Ok(Response::new(proto::ListActiveOperationsResponse {
+            operations: operations
+                .into_iter()
+                .map(active_operation_to_proto)
+                .collect(),
+        }))
+    }
 }
@@ -0,0 +1,963 @@
+//! HTTP+JSON gateway for clients that do not speak gRPC.
+//!
+//! Every route calls the same [`RuntimeAgentService`] methods the gRPC server
+//! exposes, in-process, so validation, model fallback and workspace handling
+//! stay in one place. Sending a message answers with a `text/event-stream` of
+//! the session's events that ends when the operation it started finishes.
+//! Each SSE event is named after its field in the gRPC `SessionEvent.event`
+//! oneof (`assistant_message_added`, `stream_delta`, ...) and carries the
+//! event as JSON. Disconnecting before the turn finishes cancels it.
+//!
+//! | Method   | Path                          | Body                    |
+//! |----------|-------------------------------|-------------------------|
+//! | `POST`   | `/v1/sessions`                | [`CreateSessionBody`]   |
+//! | `GET`    | `/v1/sessions`                |                         |
+//! | `GET`    | `/v1/sessions/{id}`           |                         |
+//! | `DELETE` | `/v1/sessions/{id}`           |                         |
+//! | `POST`   | `/v1/sessions/{id}/messages`  | [`SendMessageBody`]     |
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use steer_core::app::conversation::Message;
+use steer_core::app::domain::delta::StreamDelta;
+use steer_core::app::domain::event::SessionEvent;
+use steer_core::app::domain::runtime::{RuntimeError, RuntimeHandle, SessionEventEnvelope};
+use steer_core::app::domain::types::{OpId, SessionId};
+use steer_proto::agent::v1::{
+    self as proto, CreateSessionRequest, DeleteSessionRequest, GetDefaultModelRequest,
+    GetSessionRequest, ListSessionsRequest, ResolveModelRequest, SendMessageRequest,
+    agent_service_server::AgentService, get_session_response, session_event,
+};
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+use tonic::{Code, Status};
+use tracing::{debug, warn};
+
+use crate::grpc::RuntimeAgentService;
+use crate::grpc::conversions::{parse_op_id, proto_to_message, session_event_to_proto};
+
+/// Serve the gateway on `listener` until `shutdown` is cancelled.
+pub async fn serve(
+    listener: TcpListener,
+    service: Arc<RuntimeAgentService>,
+    shutdown: CancellationToken,
+) -> std::io::Result<()> {
+    axum::serve(listener, router(service))
+        .with_graceful_shutdown(shutdown.cancelled_owned())
+        .await
+}
+
+pub fn router(service: Arc<RuntimeAgentService>) -> Router {
+    Router::new()
+        .route("/v1/sessions", post(create_session).get(list_sessions))
+        .route("/v1/sessions/{id}", get(get_session).delete(delete_session))
+        .route("/v1/sessions/{id}/messages", post(send_message))
+        .with_state(service)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSessionBody {
+    /// `provider/model` or an alias; the server default when omitted.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Local directory the session works in; the server's workspace when omitted.
+    #[serde(default)]
+    pub workspace_path: Option<PathBuf>,
+    #[serde(default)]
+    pub primary_agent_id: Option<String>,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SendMessageBody {
+    pub text: String,
+    /// `provider/model` or an alias for this turn; the session default when omitted.
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionJson {
+    pub id: String,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub title: Option<String>,
+    pub summary: Option<String>,
+    pub workspace_path: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionListJson {
+    pub sessions: Vec<SessionJson>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionStateJson {
+    pub id: String,
+    pub default_model: Option<String>,
+    pub last_event_sequence: u64,
+    pub messages: Vec<Message>,
+    pub approved_tools: Vec<String>,
+}
+
+/// A gRPC status rendered as an HTTP error with a JSON body.
+struct GatewayError(Status);
+
+impl From<Status> for GatewayError {
+    fn from(status: Status) -> Self {
+        Self(status)
+    }
+}
+
+impl IntoResponse for GatewayError {
+    fn into_response(self) -> Response {
+        let (status, code) = match self.0.code() {
+            Code::InvalidArgument | Code::OutOfRange => {
+                (StatusCode::BAD_REQUEST, "invalid_argument")
+            }
+            Code::NotFound => (StatusCode::NOT_FOUND, "not_found"),
+            Code::AlreadyExists | Code::Aborted => (StatusCode::CONFLICT, "conflict"),
+            Code::FailedPrecondition => (StatusCode::CONFLICT, "failed_precondition"),
+            Code::ResourceExhausted => (StatusCode::TOO_MANY_REQUESTS, "resource_exhausted"),
+            Code::Unauthenticated => (StatusCode::UNAUTHORIZED, "unauthenticated"),
+            Code::PermissionDenied => (StatusCode::FORBIDDEN, "permission_denied"),
+            Code::Unimplemented => (StatusCode::NOT_IMPLEMENTED, "unimplemented"),
+            Code::Unavailable => (StatusCode::SERVICE_UNAVAILABLE, "unavailable"),
+            Code::DeadlineExceeded => (StatusCode::GATEWAY_TIMEOUT, "deadline_exceeded"),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, "internal"),
+        };
+        let body = serde_json::json!({
+            "error": { "code": code, "message": self.0.message() }
+        });
+        (status, Json(body)).into_response()
+    }
+}
+
+type GatewayResult<T> = Result<T, GatewayError>;
+
+async fn create_session(
+    State(service): State<Arc<RuntimeAgentService>>,
+    Json(body): Json<CreateSessionBody>,
+) -> GatewayResult<(StatusCode, Json<SessionJson>)> {
+    let default_model = match body.model {
+        Some(model) => resolve_model(&service, model).await?,
+        None => service
+            .get_default_model(tonic::Request::new(GetDefaultModelRequest {}))
+            .await?
+            .into_inner()
+            .model
+            .ok_or_else(|| Status::internal("Server has no default model"))?,
+    };
+
+    let request = CreateSessionRequest {
+        metadata: body.metadata,
+        workspace_config: body.workspace_path.map(|path| proto::WorkspaceConfig {
+            config: Some(proto::workspace_config::Config::Local(
+                proto::LocalWorkspaceConfig {
+                    path: path.to_string_lossy().into_owned(),
+                },
+            )),
+        }),
+        default_model: Some(default_model),
+        primary_agent_id: body.primary_agent_id,
+        ..Default::default()
+    };
+    let session = service
+        .create_session(tonic::Request::new(request))
+        .await?
+        .into_inner()
+        .session
+        .ok_or_else(|| Status::internal("Session was created without details"))?;
+
+    Ok((StatusCode::CREATED, Json(session_json(session))))
+}
+
+async fn list_sessions(
+    State(service): State<Arc<RuntimeAgentService>>,
+) -> GatewayResult<Json<SessionListJson>> {
+    let sessions = service
+        .list_sessions(tonic::Request::new(ListSessionsRequest {
+            filter: None,
+            page_size: None,
+            page_token: None,
+        }))
+        .await?
+        .into_inner()
+        .sessions;
+
+    Ok(Json(SessionListJson {
+        sessions: sessions.into_iter().map(session_json).collect(),
+    }))
+}
+
+async fn get_session(
+    State(service): State<Arc<RuntimeAgentService>>,
+    Path(id): Path<String>,
+) -> GatewayResult<Json<SessionStateJson>> {
+    let mut chunks = service
+        .get_session(tonic::Request::new(GetSessionRequest {
+            session_id: id.clone(),
+        }))
+        .await?
+        .into_inner();
+
+    let mut state = SessionStateJson {
+        id,
+        default_model: None,
+        last_event_sequence: 0,
+        messages: Vec::new(),
+        approved_tools: Vec::new(),
+    };
+    while let Some(chunk) = chunks.next().await {
+        match chunk?.chunk {
+            Some(get_session_response::Chunk::Header(header)) => {
+                state.default_model = header
+                    .config
+                    .and_then(|config| config.default_model)
+                    .map(|model| model_string(&model));
+                state.last_event_sequence = header.last_event_sequence;
+            }
+            Some(get_session_response::Chunk::Message(message)) => {
+                let message = proto_to_message(message)
+                    .map_err(|e| Status::internal(format!("Failed to convert message: {e}")))?;
+                state.messages.push(message);
+            }
+            Some(get_session_response::Chunk::Footer(footer)) => {
+                state.approved_tools = footer.approved_tools;
+            }
+            None => {}
+        }
+    }
+
+    Ok(Json(state))
+}
+
+async fn delete_session(
+    State(service): State<Arc<RuntimeAgentService>>,
+    Path(id): Path<String>,
+) -> GatewayResult<StatusCode> {
+    service
+        .delete_session(tonic::Request::new(DeleteSessionRequest { session_id: id }))
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Start a turn and stream the session's events until it finishes.
+///
+/// The subscriptions are opened before the message is submitted so the
+/// stream cannot miss the operation's first events. If the client goes away
+/// before the turn finishes, the operation is cancelled.
+async fn send_message(
+    State(service): State<Arc<RuntimeAgentService>>,
+    Path(id): Path<String>,
+    Json(body): Json<SendMessageBody>,
+) -> GatewayResult<Sse<impl Stream<Item = Result<Event, Infallible>>>> {
+    let session_id = RuntimeAgentService::parse_session_id(&id)?;
+    let runtime = service.runtime();
+    if let Err(e @ RuntimeError::SessionNotFound { .. }) = runtime.resume_session(session_id).await
+    {
+        return Err(Status::not_found(e.to_string()).into());
+    }
+    let mut subscription = runtime
+        .subscribe_events(session_id)
+        .await
+        .map_err(|e| Status::internal(format!("Failed to subscribe: {e}")))?;
+    let mut deltas = runtime
+        .subscribe_deltas(session_id)
+        .await
+        .map_err(|e| Status::internal(format!("Failed to subscribe to deltas: {e}")))?;
+
+    let model = match body.model {
+        Some(model) => Some(resolve_model(&service, model).await?),
+        None => None,
+    };
+    let operation = service
+        .send_message(tonic::Request::new(SendMessageRequest {
+            session_id: id,
+            message: body.text,
+            model,
+            content: Vec::new(),
+        }))
+        .await?
+        .into_inner()
+        .operation
+        .ok_or_else(|| Status::internal("Message was sent without an operation"))?;
+    let op_id = parse_op_id(&operation.id)
+        .map_err(|e| Status::internal(format!("Invalid operation id: {e}")))?;
+    let guard = CancelOnDisconnect {
+        runtime: runtime.clone(),
+        session_id,
+        op_id: Some(op_id),
+    };
+
+    let events = async_stream::stream! {
+        // Bind the whole guard so the stream owns it; capturing only its
+        // `op_id` would drop it, and cancel the turn, when this handler returns.
+        let mut guard = guard;
+        let mut deltas_open = true;
+        loop {
+            // Deltas are published before the event that completes them, so
+            // drain them first to keep the stream in order.
+            let (event, finished) = tokio::select! {
+                biased;
+                delta = deltas.recv(), if deltas_open => match delta {
+                    Ok(delta) => (delta_sse_event(delta), false),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Delta subscription lagged by {} messages", skipped);
+                        (None, false)
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        deltas_open = false;
+                        (None, false)
+                    }
+                },
+                envelope = subscription.recv() => {
+                    let Some(envelope) = envelope else {
+                        break;
+                    };
+                    let finished = match &envelope.event {
+                        SessionEvent::OperationCompleted { op_id: done, .. }
+                        | SessionEvent::OperationCancelled { op_id: done, .. } => *done == op_id,
+                        _ => false,
+                    };
+                    (session_sse_event(envelope), finished)
+                }
+            };
+
+            if let Some(event) = event {
+                yield Ok(event);
+            }
+            if finished {
+                guard.op_id = None;
+                break;
+            }
+        }
+    };
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// Cancels the operation a message started when its event stream is dropped
+/// before the operation finishes, i.e. when the client disconnects.
+struct CancelOnDisconnect {
+    runtime: RuntimeHandle,
+    session_id: SessionId,
+    op_id: Option<OpId>,
+}
+
+impl Drop for CancelOnDisconnect {
+    fn drop(&mut self) {
+        let Some(op_id) = self.op_id.take() else {
+            return;
+        };
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let runtime = self.runtime.clone();
+        let session_id = self.session_id;
+        handle.spawn(async move {
+            debug!(
+                "HTTP client disconnected; cancelling operation {} in session {}",
+                op_id, session_id
+            );
+            if let Err(e) = runtime.cancel_operation(session_id, Some(op_id)).await {
+                warn!("Failed to cancel operation {}: {}", op_id, e);
+            }
+        });
+    }
+}
+
+/// A session event as a named SSE event. The name is the event's field in the
+/// gRPC `SessionEvent.event` oneof and the data is the event as JSON; events
+/// the gRPC stream does not carry are skipped.
+fn session_sse_event(envelope: SessionEventEnvelope) -> Option<Event> {
+    let name = match session_event_to_proto(envelope.event.clone(), envelope.seq) {
+        Ok(proto::SessionEvent {
+            event: Some(event), ..
+        }) => sse_event_name(&event),
+        Ok(_) => return None,
+        Err(e) => {
+            warn!("Failed to convert session event: {}", e);
+            return None;
+        }
+    };
+    Event::default()
+        .event(name)
+        .id(envelope.seq.to_string())
+        .json_data(&envelope.event)
+        .inspect_err(|e| warn!("Failed to serialize session event: {}", e))
+        .ok()
+}
+
+const STREAM_DELTA_EVENT: &str = "stream_delta";
+
+/// A streamed token or tool-call fragment as a `stream_delta` SSE event.
+fn delta_sse_event(delta: StreamDelta) -> Option<Event> {
+    Event::default()
+        .event(STREAM_DELTA_EVENT)
+        .json_data(&delta)
+        .inspect_err(|e| warn!("Failed to serialize stream delta: {}", e))
+        .ok()
+}
+
+fn sse_event_name(event: &session_event::Event) -> &'static str {
+    use session_event::Event;
+    match event {
+        Event::AssistantMessageAdded(_) => "assistant_message_added",
+        Event::UserMessageAdded(_) => "user_message_added",
+        Event::ToolMessageAdded(_) => "tool_message_added",
+        Event::MessageUpdated(_) => "message_updated",
+        Event::ToolCallStarted(_) => "tool_call_started",
+        Event::ToolCallCompleted(_) => "tool_call_completed",
+        Event::ToolCallFailed(_) => "tool_call_failed",
+        Event::ProcessingStarted(_) => "processing_started",
+        Event::ProcessingCompleted(_) => "processing_completed",
+        Event::RequestToolApproval(_) => "request_tool_approval",
+        Event::OperationCancelled(_) => "operation_cancelled",
+        Event::Error(_) => "error",
+        Event::WorkspaceChanged(_) => "workspace_changed",
+        Event::ConversationCompacted(_) => "conversation_compacted",
+        Event::StreamDelta(_) => STREAM_DELTA_EVENT,
+        Event::CompactResult(_) => "compact_result",
+        Event::McpServerStateChanged(_) => "mcp_server_state_changed",
+        Event::SessionConfigUpdated(_) => "session_config_updated",
+        Event::QueueUpdated(_) => "queue_updated",
+        Event::LlmUsageUpdated(_) => "llm_usage_updated",
+        Event::ContextUsage(_) => "context_usage",
+        Event::BestOfStarted(_) => "best_of_started",
+        Event::BestOfCandidateReady(_) => "best_of_candidate_ready",
+        Event::BestOfResolved(_) => "best_of_resolved",
+        Event::WorkspaceFileAdded(_) => "workspace_file_added",
+        Event::WorkspaceFileRemoved(_) => "workspace_file_removed",
+        Event::Refused(_) => "refused",
+    }
+}
+
+async fn resolve_model(
+    service: &RuntimeAgentService,
+    input: String,
+) -> Result<proto::ModelSpec, Status> {
+    service
+        .resolve_model(tonic::Request::new(ResolveModelRequest { input }))
+        .await?
+        .into_inner()
+        .model
+        .ok_or_else(|| Status::internal("Model resolved to nothing"))
+}
+
+fn model_string(model: &proto::ModelSpec) -> String {
+    format!("{}/{}", model.provider_id, model.model_id)
+}
+
+fn session_json(session: proto::SessionInfo) -> SessionJson {
+    SessionJson {
+        id: session.id,
+        created_at: session.created_at.and_then(timestamp_string),
+        updated_at: session.updated_at.and_then(timestamp_string),
+        title: session.title,
+        summary: session.summary,
+        workspace_path: session.workspace_path,
+    }
+}
+
+fn timestamp_string(timestamp: prost_types::Timestamp) -> Option<String> {
+    let nanos = u32::try_from(timestamp.nanos).ok()?;
+    chrono::DateTime::from_timestamp(timestamp.seconds, nanos).map(|time| time.to_rfc3339())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+    use steer_core::api::Client as ApiClient;
+    use steer_core::api::error::{ApiError, StreamError};
+    use steer_core::api::provider::{CompletionResponse, CompletionStream, Provider, StreamChunk};
+    use steer_core::app::conversation::{AssistantContent, MessageData, UserContent};
+    use steer_core::app::domain::runtime::RuntimeService;
+    use steer_core::app::domain::session::{EventStore, InMemoryEventStore, SessionMetadataStore};
+    use steer_core::catalog::CatalogConfig;
+    use steer_core::config::model::ModelId;
+    use steer_core::tools::ToolSystemBuilder;
+    use steer_tools::ToolCall;
+    use steer_tools::tools::LS_TOOL_NAME;
+    use steer_workspace::{LocalEnvironmentManager, LocalWorkspaceManager, RepoManager};
+    use tempfile::TempDir;
+    use tokio::time::{Duration, timeout};
+
+    const STUB_RESPONSE: &str = "hello over http";
+    /// A prompt the stub answers by listing its workspace first.
+    const LIST_FILES_PROMPT: &str = "list files";
+    /// A prompt the stub never answers; its stream ends only when cancelled.
+    const HANG_PROMPT: &str = "hang";
+
+    /// Streams [`STUB_RESPONSE`], except that it answers
+    /// [`LIST_FILES_PROMPT`] with an `ls` call of `workspace_root` and
+    /// [`HANG_PROMPT`] not at all.
+    #[derive(Clone)]
+    struct StubProvider {
+        workspace_root: String,
+    }
+
+    #[async_trait::async_trait]
+    impl Provider for StubProvider {
+        fn name(&self) -> &'static str {
+            "stub"
+        }
+
+        async fn complete(
+            &self,
+            _model_id: &ModelId,
+            messages: Vec<Message>,
+            _system: Option<steer_core::app::SystemContext>,
+            _tools: Option<Vec<steer_tools::ToolSchema>>,
+            _call_options: Option<steer_core::config::model::ModelParameters>,
+            _token: CancellationToken,
+        ) -> Result<CompletionResponse, ApiError> {
+            let content = if last_prompt_is(&messages, LIST_FILES_PROMPT) {
+                AssistantContent::ToolCall {
+                    tool_call: ToolCall {
+                        id: "call_ls".to_string(),
+                        name: LS_TOOL_NAME.to_string(),
+                        parameters: json!({ "path": self.workspace_root }),
+                    },
+                    thought_signature: None,
+                }
+            } else {
+                AssistantContent::Text {
+                    text: STUB_RESPONSE.to_string(),
+                }
+            };
+            Ok(CompletionResponse {
+                content: vec![content],
+                usage: None,
+            })
+        }
+
+        async fn stream_complete(
+            &self,
+            model_id: &ModelId,
+            messages: Vec<Message>,
+            system: Option<steer_core::app::SystemContext>,
+            tools: Option<Vec<steer_tools::ToolSchema>>,
+            call_options: Option<steer_core::config::model::ModelParameters>,
+            token: CancellationToken,
+        ) -> Result<CompletionStream, ApiError> {
+            if last_prompt_is(&messages, HANG_PROMPT) {
+                return Ok(Box::pin(futures::stream::once(async move {
+                    token.cancelled().await;
+                    StreamChunk::Error(StreamError::Cancelled)
+                })));
+            }
+            let response = self
+                .complete(model_id, messages, system, tools, call_options, token)
+                .await?;
+            let mut chunks = Vec::new();
+            let text = response.extract_text();
+            if !text.is_empty() {
+                chunks.push(StreamChunk::TextDelta(text));
+            }
+            chunks.push(StreamChunk::MessageComplete(response));
+            Ok(Box::pin(futures::stream::iter(chunks)))
+        }
+    }
+
+    /// Whether the last message is a user message with exactly `prompt`.
+    fn last_prompt_is(messages: &[Message], prompt: &str) -> bool {
+        messages.last().is_some_and(|message| {
+            matches!(
+                &message.data,
+                MessageData::User { content } if content.iter().any(|block| matches!(
+                    block,
+                    UserContent::Text { text } if text == prompt
+                ))
+            )
+        })
+    }
+
+    /// Serve the gateway on an ephemeral port over a runtime whose model is
+    /// the stub provider, returning its base URL.
+    async fn spawn_gateway(
+        model: &ModelId,
+        workspace_root: &std::path::Path,
+        shutdown: CancellationToken,
+    ) -> (String, RuntimeService) {
+        let store = Arc::new(InMemoryEventStore::new());
+        let event_store: Arc<dyn EventStore> = store.clone();
+        let catalog: Arc<dyn SessionMetadataStore> = store;
+        let catalog_paths = CatalogConfig::default().catalog_paths;
+        let model_registry = steer_core::model_registry::SharedModelRegistry::new(
+            Arc::new(
+                steer_core::model_registry::ModelRegistry::load(&catalog_paths)
+                    .expect("model registry"),
+            ),
+            catalog_paths.clone(),
+        );
+        let provider_registry = Arc::new(
+            steer_core::auth::ProviderRegistry::load(&catalog_paths).expect("provider registry"),
+        );
+        let llm_config_provider = steer_core::config::LlmConfigProvider::new(Arc::new(
+            steer_core::test_utils::InMemoryAuthStorage::new(),
+        ))
+        .expect("llm config provider");
+        let api_client = Arc::new(ApiClient::new_with_deps(
+            llm_config_provider.clone(),
+            provider_registry.clone(),
+            model_registry.clone(),
+        ));
+        api_client.insert_test_provider(
+            model.provider.clone(),
+            Arc::new(StubProvider {
+                workspace_root: workspace_root.to_string_lossy().into_owned(),
+            }),
+        );
+
+        let workspace = steer_core::workspace::create_workspace(
+            &steer_core::workspace::WorkspaceConfig::Local {
+                path: workspace_root.to_path_buf(),
+                context_limits: Default::default(),
+            },
+        )
+        .await
+        .expect("workspace");
+        let environment_root = workspace_root.join(".steer-env");
+        let workspace_manager = Arc::new(
+            LocalWorkspaceManager::new(environment_root.clone())
+                .await
+                .expect("workspace manager"),
+        );
+        let repo_manager: Arc<dyn RepoManager> = workspace_manager.clone();
+        let tool_executor = ToolSystemBuilder::new(
+            workspace,
+            event_store.clone(),
+            api_client.clone(),
+            model_registry.snapshot(),
+        )
+        .with_workspace_manager(workspace_manager.clone())
+        .with_repo_manager(repo_manager.clone())
+        .build();
+        let runtime_service = RuntimeService::spawn(event_store, api_client, tool_executor);
+
+        let service = Arc::new(RuntimeAgentService::new(crate::grpc::RuntimeAgentDeps {
+            runtime: runtime_service.handle(),
+            catalog,
+            llm_config_provider,
+            model_registry,
+            provider_registry,
+            environment_manager: Arc::new(LocalEnvironmentManager::new(environment_root)),
+            workspace_manager,
+            repo_manager,
+            scheduler: None,
+            event_logger: None,
+            audit_log: None,
+        }));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        tokio::spawn(serve(listener, service, shutdown));
+
+        (format!("http://{addr}"), runtime_service)
+    }
+
+    /// The name and JSON payload of each event in an SSE body, in order.
+    fn sse_events(body: &str) -> Vec<(String, Value)> {
+        body.split("\n\n")
+            .filter_map(|frame| {
+                let mut name = None;
+                let mut data = None;
+                for line in frame.lines() {
+                    if let Some(value) = line.strip_prefix("event:") {
+                        name = Some(value.trim().to_string());
+                    } else if let Some(value) = line.strip_prefix("data:") {
+                        data = Some(serde_json::from_str(value.trim()).expect("event JSON"));
+                    }
+                }
+                Some((name?, data?))
+            })
+            .collect()
+    }
+
+    /// Create a session over HTTP and return its id.
+    async fn create_session(
+        client: &reqwest::Client,
+        base: &str,
+        model: &ModelId,
+        workspace_root: &std::path::Path,
+    ) -> String {
+        let response = client
+            .post(format!("{base}/v1/sessions"))
+            .json(&json!({
+                "model": format!("{}/{}", model.provider.storage_key(), model.id),
+                "workspace_path": workspace_root,
+            }))
+            .send()
+            .await
+            .expect("create session");
+        assert_eq!(response.status(), reqwest::StatusCode::CREATED);
+        let created: Value = response.json().await.expect("session JSON");
+        created["id"].as_str().expect("session id").to_string()
+    }
+
+    #[tokio::test]
+    async fn test_session_lifecycle_over_http() {
+        let workspace_root = TempDir::new().expect("workspace tempdir");
+        let model = steer_core::config::model::builtin::claude_sonnet_4_5();
+        let shutdown = CancellationToken::new();
+        let (base, runtime_service) =
+            spawn_gateway(&model, workspace_root.path(), shutdown.clone()).await;
+        let client = reqwest::Client::new();
+
+        let session_id = create_session(&client, &base, &model, workspace_root.path()).await;
+
+        let listed: Value = client
+            .get(format!("{base}/v1/sessions"))
+            .send()
+            .await
+            .expect("list sessions")
+            .json()
+            .await
+            .expect("list JSON");
+        assert!(
+            listed["sessions"]
+                .as_array()
+                .expect("sessions array")
+                .iter()
+                .any(|session| session["id"] == session_id.as_str())
+        );
+
+        let response = client
+            .post(format!("{base}/v1/sessions/{session_id}/messages"))
+            .json(&json!({ "text": "hello" }))
+            .send()
+            .await
+            .expect("send message");
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        assert_eq!(
+            response.headers()[reqwest::header::CONTENT_TYPE],
+            "text/event-stream"
+        );
+        let body = timeout(Duration::from_secs(10), response.text())
+            .await
+            .expect("turn timed out")
+            .expect("event stream");
+        let events = sse_events(&body);
+        assert!(
+            events
+                .iter()
+                .any(|(name, event)| name == "assistant_message_added"
+                    && event.get("AssistantMessageAdded").is_some())
+        );
+        assert!(
+            events
+                .last()
+                .is_some_and(|(name, _)| name == "processing_completed"),
+            "stream should end with the turn's completion: {body}"
+        );
+
+        let response = client
+            .get(format!("{base}/v1/sessions/{session_id}"))
+            .send()
+            .await
+            .expect("get session");
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        let state: Value = response.json().await.expect("state JSON");
+        let messages = state["messages"].as_array().expect("messages array");
+        assert_eq!(messages.len(), 2);
+        assert!(messages[1].to_string().contains(STUB_RESPONSE));
+
+        let response = client
+            .delete(format!("{base}/v1/sessions/{session_id}"))
+            .send()
+            .await
+            .expect("delete session");
+        assert_eq!(response.status(), reqwest::StatusCode::NO_CONTENT);
+
+        let response = client
+            .get(format!("{base}/v1/sessions/{session_id}"))
+            .send()
+            .await
+            .expect("get deleted session");
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+        let response = client
+            .get(format!("{base}/v1/sessions/not-a-session"))
+            .send()
+            .await
+            .expect("get invalid session");
+        assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+        let error: Value = response.json().await.expect("error JSON");
+        assert_eq!(error["error"]["code"], "invalid_argument");
+
+        shutdown.cancel();
+        runtime_service.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_message_stream_covers_a_tool_call_turn() {
+        let workspace_root = TempDir::new().expect("workspace tempdir");
+        std::fs::write(workspace_root.path().join("notes.txt"), "hi").expect("seed file");
+        let model = steer_core::config::model::builtin::claude_sonnet_4_5();
+        let shutdown = CancellationToken::new();
+        let (base, runtime_service) =
+            spawn_gateway(&model, workspace_root.path(), shutdown.clone()).await;
+        let client = reqwest::Client::new();
+        let session_id = create_session(&client, &base, &model, workspace_root.path()).await;
+
+        let response = client
+            .post(format!("{base}/v1/sessions/{session_id}/messages"))
+            .json(&json!({ "text": LIST_FILES_PROMPT }))
+            .send()
+            .await
+            .expect("send message");
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        let body = timeout(Duration::from_secs(10), response.text())
+            .await
+            .expect("turn timed out")
+            .expect("event stream");
+        let events = sse_events(&body);
+        let names: Vec<&str> = events.iter().map(|(name, _)| name.as_str()).collect();
+        let position = |wanted: &str| {
+            names
+                .iter()
+                .position(|name| *name == wanted)
+                .unwrap_or_else(|| panic!("no {wanted} event in {names:?}"))
+        };
+
+        let started = position("tool_call_started");
+        let completed = position("tool_call_completed");
+        let tool_message = position("tool_message_added");
+        let delta = position("stream_delta");
+        assert!(position("processing_started") < started);
+        assert!(started < completed);
+        assert!(completed < delta);
+        assert_eq!(names.last(), Some(&"processing_completed"));
+
+        let (_, tool_call) = &events[started];
+        assert_eq!(tool_call["ToolCallStarted"]["name"], LS_TOOL_NAME);
+        let (_, listing) = &events[tool_message];
+        assert!(listing.to_string().contains("notes.txt"));
+        let (_, text) = &events[delta];
+        assert_eq!(text["TextChunk"]["delta"], STUB_RESPONSE);
+        assert!(events.iter().any(|(name, event)| {
+            name == "assistant_message_added" && event.to_string().contains(STUB_RESPONSE)
+        }));
+
+        shutdown.cancel();
+        runtime_service.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_dropping_the_message_stream_cancels_the_turn() {
+        let workspace_root = TempDir::new().expect("workspace tempdir");
+        let model = steer_core::config::model::builtin::claude_sonnet_4_5();
+        let shutdown = CancellationToken::new();
+        let (base, runtime_service) =
+            spawn_gateway(&model, workspace_root.path(), shutdown.clone()).await;
+        let client = reqwest::Client::new();
+        let session_id = create_session(&client, &base, &model, workspace_root.path()).await;
+        let mut subscription = runtime_service
+            .handle()
+            .subscribe_events(
+                RuntimeAgentService::parse_session_id(&session_id).expect("session id"),
+            )
+            .await
+            .expect("subscribe");
+
+        let mut response = client
+            .post(format!("{base}/v1/sessions/{session_id}/messages"))
+            .json(&json!({ "text": HANG_PROMPT }))
+            .send()
+            .await
+            .expect("send message");
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        let first = timeout(Duration::from_secs(10), response.chunk())
+            .await
+            .expect("first event timed out")
+            .expect("event stream");
+        assert!(first.is_some(), "stream ended before the turn started");
+        drop(response);
+
+        timeout(Duration::from_secs(10), async {
+            while let Some(envelope) = subscription.recv().await {
+                match envelope.event {
+                    SessionEvent::OperationCancelled { .. } => return,
+                    SessionEvent::OperationCompleted { .. } => {
+                        panic!("the turn completed instead of being cancelled")
+                    }
+                    _ => {}
+                }
+            }
+            panic!("event subscription closed before the turn was cancelled");
+        })
+        .await
+        .expect("the turn was not cancelled");
+
+        shutdown.cancel();
+        runtime_service.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_finished_message_stream_does_not_cancel_the_turn() {
+        let workspace_root = TempDir::new().expect("workspace tempdir");
+        let model = steer_core::config::model::builtin::claude_sonnet_4_5();
+        let shutdown = CancellationToken::new();
+        let (base, runtime_service) =
+            spawn_gateway(&model, workspace_root.path(), shutdown.clone()).await;
+        let client = reqwest::Client::new();
+        let session_id = create_session(&client, &base, &model, workspace_root.path()).await;
+        let mut subscription = runtime_service
+            .handle()
+            .subscribe_events(
+                RuntimeAgentService::parse_session_id(&session_id).expect("session id"),
+            )
+            .await
+            .expect("subscribe");
+
+        let response = client
+            .post(format!("{base}/v1/sessions/{session_id}/messages"))
+            .json(&json!({ "text": "hello" }))
+            .send()
+            .await
+            .expect("send message");
+        let body = timeout(Duration::from_secs(10), response.text())
+            .await
+            .expect("turn timed out")
+            .expect("event stream");
+        assert!(
+            sse_events(&body)
+                .last()
+                .is_some_and(|(name, _)| name == "processing_completed"),
+            "stream should end with the turn's completion: {body}"
+        );
+
+        let mut completed = false;
+        let _ = timeout(Duration::from_millis(500), async {
+            while let Some(envelope) = subscription.recv().await {
+                match envelope.event {
+                    SessionEvent::OperationCompleted { .. } => completed = true,
+                    SessionEvent::OperationCancelled { .. } => {
+                        panic!("a finished stream cancelled its turn")
+                    }
+                    _ => {}
+                }
+            }
+        })
+        .await;
+        assert!(completed, "the turn never completed");
+
+        shutdown.cancel();
+        runtime_service.shutdown().await;
+    }
+}
@@ -1,5 +1,6 @@
 pub mod client_api;
 pub mod grpc;
+pub mod http_gateway;
 pub mod local_server;
 pub mod service_host;
 
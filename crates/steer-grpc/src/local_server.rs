@@ -5,7 +5,7 @@ type Result<T> = std::result::Result<T, GrpcError>;
 use std::sync::Arc;
 use steer_core::api::Client as ApiClient;
 use steer_core::app::domain::runtime::RuntimeService;
-use steer_core::app::domain::session::{InMemoryEventStore, SessionMetadataStore};
+use steer_core::app::domain::session::{InMemoryEventStore, SessionMetadataStore, ToolAuditStore};
 use steer_core::catalog::CatalogConfig;
 use steer_core::config::model::ModelId;
 use steer_core::tools::ToolSystemBuilder;
@@ -17,7 +17,7 @@ use tonic::transport::{Channel, Server};
 pub async fn create_local_channel(
     runtime_service: &RuntimeService,
     catalog: Arc<dyn SessionMetadataStore>,
-    model_registry: Arc<steer_core::model_registry::ModelRegistry>,
+    model_registry: steer_core::model_registry::SharedModelRegistry,
     provider_registry: Arc<steer_core::auth::ProviderRegistry>,
     llm_config_provider: steer_core::config::LlmConfigProvider,
     environment_root: std::path::PathBuf,
@@ -43,6 +43,9 @@ pub async fn create_local_channel(
         environment_manager,
         workspace_manager,
         repo_manager,
+        scheduler: None,
+        event_logger: None,
+        audit_log: None,
     });
     let svc = AgentServiceServer::new(service)
         .max_decoding_message_size(GRPC_MAX_MESSAGE_SIZE_BYTES)
@@ -108,27 +111,36 @@ pub async fn setup_local_grpc_with_catalog(
     catalog_config: CatalogConfig,
     workspace_root: Option<std::path::PathBuf>,
 ) -> Result<LocalGrpcSetup> {
-    let (event_store, catalog): (
+    let (event_store, catalog, audit_log): (
         Arc<dyn steer_core::app::domain::session::EventStore>,
         Arc<dyn SessionMetadataStore>,
+        Arc<dyn ToolAuditStore>,
     ) = if let Some(db_path) = session_db_path {
         let sqlite_store = Arc::new(
-            steer_core::app::domain::session::SqliteEventStore::new(&db_path)
+            steer_core::app::domain::session::SqliteEventStore::open_configured(&db_path)
                 .await
                 .map_err(|e| GrpcError::InvalidSessionState {
                     reason: format!("Failed to create event store: {e}"),
                 })?,
         );
-        (sqlite_store.clone(), sqlite_store)
+        (sqlite_store.clone(), sqlite_store.clone(), sqlite_store)
     } else {
         let in_memory_store = Arc::new(InMemoryEventStore::new());
-        (in_memory_store.clone(), in_memory_store)
+        (
+            in_memory_store.clone(),
+            in_memory_store.clone(),
+            in_memory_store,
+        )
     };
 
     let model_registry = Arc::new(
         steer_core::model_registry::ModelRegistry::load(&catalog_config.catalog_paths)
             .map_err(GrpcError::CoreError)?,
     );
+    let model_registry = steer_core::model_registry::SharedModelRegistry::new(
+        model_registry,
+        catalog_config.catalog_paths.clone(),
+    );
 
     let provider_registry = Arc::new(
         steer_core::auth::ProviderRegistry::load(&catalog_config.catalog_paths)
@@ -159,6 +171,7 @@ pub async fn setup_local_grpc_with_catalog(
     let workspace =
         steer_core::workspace::create_workspace(&steer_core::workspace::WorkspaceConfig::Local {
             path: workspace_path.clone(),
+            context_limits: Default::default(),
         })
         .await
         .map_err(|e| GrpcError::InvalidSessionState {
@@ -177,10 +190,11 @@ pub async fn setup_local_grpc_with_catalog(
         workspace,
         event_store.clone(),
         api_client.clone(),
-        model_registry.clone(),
+        model_registry.snapshot(),
     )
     .with_workspace_manager(workspace_manager)
     .with_repo_manager(repo_manager)
+    .with_audit_log(audit_log)
     .build();
 
     let runtime_service = RuntimeService::spawn(event_store, api_client, tool_executor);
@@ -281,6 +295,10 @@ mod tests {
             steer_core::model_registry::ModelRegistry::load(&catalog_config.catalog_paths)
                 .map_err(GrpcError::CoreError)?,
         );
+        let model_registry = steer_core::model_registry::SharedModelRegistry::new(
+            model_registry,
+            catalog_config.catalog_paths.clone(),
+        );
         let provider_registry = Arc::new(
             steer_core::auth::ProviderRegistry::load(&catalog_config.catalog_paths)
                 .map_err(GrpcError::CoreError)?,
@@ -300,6 +318,7 @@ mod tests {
         let workspace = steer_core::workspace::create_workspace(
             &steer_core::workspace::WorkspaceConfig::Local {
                 path: workspace_root.clone(),
+                context_limits: Default::default(),
             },
         )
         .await
@@ -321,7 +340,7 @@ mod tests {
             workspace,
             event_store.clone(),
             api_client.clone(),
-            model_registry.clone(),
+            model_registry.snapshot(),
         )
         .with_workspace_manager(workspace_manager)
         .with_repo_manager(repo_manager)
@@ -356,7 +375,7 @@ mod tests {
             Arc<dyn SessionMetadataStore>,
         ) = if let Some(db_path) = session_db_path {
             let sqlite_store = Arc::new(
-                steer_core::app::domain::session::SqliteEventStore::new(&db_path)
+                steer_core::app::domain::session::SqliteEventStore::open_configured(&db_path)
                     .await
                     .map_err(|e| GrpcError::InvalidSessionState {
                         reason: format!("Failed to create event store: {e}"),
@@ -373,6 +392,10 @@ mod tests {
             steer_core::model_registry::ModelRegistry::load(&catalog_config.catalog_paths)
                 .map_err(GrpcError::CoreError)?,
         );
+        let model_registry = steer_core::model_registry::SharedModelRegistry::new(
+            model_registry,
+            catalog_config.catalog_paths.clone(),
+        );
         let provider_registry = Arc::new(
             steer_core::auth::ProviderRegistry::load(&catalog_config.catalog_paths)
                 .map_err(GrpcError::CoreError)?,
@@ -392,6 +415,7 @@ mod tests {
         let workspace = steer_core::workspace::create_workspace(
             &steer_core::workspace::WorkspaceConfig::Local {
                 path: workspace_root.clone(),
+                context_limits: Default::default(),
             },
         )
         .await
@@ -413,7 +437,7 @@ mod tests {
             workspace,
             event_store.clone(),
             api_client.clone(),
-            model_registry.clone(),
+            model_registry.snapshot(),
         )
         .with_workspace_manager(workspace_manager)
         .with_repo_manager(repo_manager)
@@ -485,7 +509,10 @@ mod tests {
             Some(Event::RequestToolApproval(_)) => "RequestToolApproval",
             Some(Event::OperationCancelled(_)) => "OperationCancelled",
             Some(Event::Error(_)) => "Error",
+            Some(Event::Refused(_)) => "Refused",
             Some(Event::WorkspaceChanged(_)) => "WorkspaceChanged",
+            Some(Event::WorkspaceFileAdded(_)) => "WorkspaceFileAdded",
+            Some(Event::WorkspaceFileRemoved(_)) => "WorkspaceFileRemoved",
             Some(Event::ConversationCompacted(_)) => "ConversationCompacted",
             Some(Event::StreamDelta(_)) => "StreamDelta",
             Some(Event::CompactResult(_)) => "CompactResult",
@@ -493,6 +520,10 @@ mod tests {
             Some(Event::SessionConfigUpdated(_)) => "SessionConfigUpdated",
             Some(Event::QueueUpdated(_)) => "QueueUpdated",
             Some(Event::LlmUsageUpdated(_)) => "LlmUsageUpdated",
+            Some(Event::ContextUsage(_)) => "ContextUsage",
+            Some(Event::BestOfStarted(_)) => "BestOfStarted",
+            Some(Event::BestOfCandidateReady(_)) => "BestOfCandidateReady",
+            Some(Event::BestOfResolved(_)) => "BestOfResolved",
             None => "None",
         }
     }
@@ -514,11 +545,16 @@ mod tests {
             SessionEvent::OperationCompleted { .. } => "OperationCompleted",
             SessionEvent::OperationCancelled { .. } => "OperationCancelled",
             SessionEvent::LlmUsageUpdated { .. } => "LlmUsageUpdated",
+            SessionEvent::ContextUsage { .. } => "ContextUsage",
+            SessionEvent::BestOfStarted { .. } => "BestOfStarted",
+            SessionEvent::BestOfCandidateReady { .. } => "BestOfCandidateReady",
+            SessionEvent::BestOfResolved { .. } => "BestOfResolved",
             SessionEvent::CompactResult { .. } => "CompactResult",
             SessionEvent::ConversationCompacted { .. } => "ConversationCompacted",
             SessionEvent::WorkspaceChanged => "WorkspaceChanged",
             SessionEvent::QueueUpdated { .. } => "QueueUpdated",
             SessionEvent::Error { .. } => "Error",
+            SessionEvent::Refused { .. } => "Refused",
             SessionEvent::McpServerStateChanged { .. } => "McpServerStateChanged",
         }
     }
@@ -563,6 +599,7 @@ mod tests {
         let request = tonic::Request::new(SubscribeSessionEventsRequest {
             session_id: session_id.to_string(),
             since_sequence: Some(0),
+            event_kinds: Vec::new(),
         });
 
         let mut stream = client
@@ -615,6 +652,7 @@ mod tests {
         let request = tonic::Request::new(SubscribeSessionEventsRequest {
             session_id: session_id.to_string(),
             since_sequence: None,
+            event_kinds: Vec::new(),
         });
 
         let mut stream = event_client
@@ -685,6 +723,46 @@ mod tests {
         assert_eq!(record.model, model.id);
     }
 
+    #[tokio::test]
+    async fn test_send_message_and_wait_returns_turn_outcome() {
+        let workspace_root = test_workspace_root();
+        let model = steer_core::config::model::builtin::claude_sonnet_4_5();
+        let setup =
+            setup_local_grpc_with_stub_provider(model.clone(), workspace_root.path().to_path_buf())
+                .await
+                .expect("local grpc setup");
+
+        let client = crate::AgentClient::from_channel(setup.channel.clone())
+            .await
+            .expect("client");
+        client
+            .create_session(
+                crate::client_api::CreateSessionParams::builder(model.clone())
+                    .workspace_path(workspace_root.path())
+                    .read_only()
+                    .build(),
+            )
+            .await
+            .expect("create session");
+        let mut events = client.event_stream().await.expect("event stream");
+
+        let outcome = timeout(
+            Duration::from_secs(10),
+            client.send_message_and_wait(
+                &mut events,
+                "hello".to_string(),
+                model,
+                &mut crate::client_api::DenyAll,
+            ),
+        )
+        .await
+        .expect("turn timed out")
+        .expect("turn");
+
+        assert!(!outcome.cancelled);
+        assert_eq!(outcome.final_text().as_deref(), Some(STUB_RESPONSE));
+    }
+
     #[tokio::test]
     async fn test_compaction_emits_processing_completed_with_sqlite_store() {
         let workspace_root = test_workspace_root();
@@ -713,6 +791,7 @@ mod tests {
         let request = tonic::Request::new(SubscribeSessionEventsRequest {
             session_id: session_id.to_string(),
             since_sequence: None,
+            event_kinds: Vec::new(),
         });
 
         let mut stream = event_client
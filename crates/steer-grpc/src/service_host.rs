@@ -3,17 +3,27 @@ use crate::grpc::error::GrpcError;
 type Result<T> = std::result::Result<T, GrpcError>;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
 use tonic::transport::Server;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use crate::grpc::RuntimeAgentService;
+use crate::http_gateway;
 use steer_core::api::Client as ApiClient;
-use steer_core::app::domain::runtime::{RuntimeHandle, RuntimeService};
-use steer_core::app::domain::session::{SessionMetadataStore, SqliteEventStore};
+use steer_core::app::domain::runtime::{
+    EventLogConfig, EventLogger, RuntimeConfig, RuntimeHandle, RuntimeService, WebhookConfig,
+    WebhookNotifier,
+};
+use steer_core::app::domain::session::{
+    ScheduledTaskStore, SessionMetadataStore, SqliteEventStore, ToolAuditStore,
+};
 use steer_core::auth::storage::AuthStorage;
 use steer_core::catalog::CatalogConfig;
+use steer_core::runners::Scheduler;
 use steer_core::tools::ToolSystemBuilder;
 use steer_proto::agent::v1::agent_service_server::AgentServiceServer;
 use steer_workspace::{LocalEnvironmentManager, LocalWorkspaceManager, RepoManager};
@@ -25,6 +35,13 @@ pub struct ServiceHostConfig {
     pub auth_storage: Arc<dyn AuthStorage>,
     pub catalog_config: CatalogConfig,
     pub workspace_root: Option<std::path::PathBuf>,
+    pub webhooks: Vec<WebhookConfig>,
+    /// Tee every session's events to a log sink (`steer server --log-events`).
+    pub event_log: Option<EventLogConfig>,
+    /// Idle suspension and loaded-session limits.
+    pub runtime: RuntimeConfig,
+    /// Also serve the HTTP+JSON gateway here (`steer server --http-port`).
+    pub http_addr: Option<SocketAddr>,
 }
 
 impl std::fmt::Debug for ServiceHostConfig {
@@ -35,6 +52,10 @@ impl std::fmt::Debug for ServiceHostConfig {
             .field("auth_storage", &"Arc<dyn AuthStorage>")
             .field("catalog_config", &self.catalog_config)
             .field("workspace_root", &self.workspace_root)
+            .field("webhooks", &self.webhooks)
+            .field("event_log", &self.event_log)
+            .field("runtime", &self.runtime)
+            .field("http_addr", &self.http_addr)
             .finish()
     }
 }
@@ -52,6 +73,10 @@ impl ServiceHostConfig {
             auth_storage,
             catalog_config: CatalogConfig::default(),
             workspace_root: None,
+            webhooks: Vec::new(),
+            event_log: None,
+            runtime: RuntimeConfig::default(),
+            http_addr: None,
         })
     }
 
@@ -71,6 +96,10 @@ impl ServiceHostConfig {
             auth_storage,
             catalog_config,
             workspace_root: None,
+            webhooks: Vec::new(),
+            event_log: None,
+            runtime: RuntimeConfig::default(),
+            http_addr: None,
         })
     }
 }
@@ -79,24 +108,34 @@ pub struct ServiceHost {
     runtime_service: RuntimeService,
     runtime_handle: RuntimeHandle,
     catalog: Arc<dyn SessionMetadataStore>,
-    model_registry: Arc<steer_core::model_registry::ModelRegistry>,
+    model_registry: steer_core::model_registry::SharedModelRegistry,
     provider_registry: Arc<steer_core::auth::ProviderRegistry>,
     llm_config_provider: steer_core::config::LlmConfigProvider,
     environment_root: std::path::PathBuf,
+    scheduler: Arc<Scheduler>,
+    event_logger: Option<Arc<EventLogger>>,
+    audit_log: Arc<dyn ToolAuditStore>,
+    background_shutdown: CancellationToken,
     server_handle: Option<JoinHandle<Result<()>>>,
+    http_handle: Option<JoinHandle<Result<()>>>,
+    http_addr: Option<SocketAddr>,
     shutdown_tx: Option<oneshot::Sender<()>>,
     config: ServiceHostConfig,
 }
 
 impl ServiceHost {
     pub async fn new(config: ServiceHostConfig) -> Result<Self> {
-        let event_store = Arc::new(SqliteEventStore::new(&config.db_path).await.map_err(|e| {
-            GrpcError::InvalidSessionState {
-                reason: format!("Failed to create event store: {e}"),
-            }
-        })?);
+        let event_store = Arc::new(
+            SqliteEventStore::open_configured(&config.db_path)
+                .await
+                .map_err(|e| GrpcError::InvalidSessionState {
+                    reason: format!("Failed to create event store: {e}"),
+                })?,
+        );
 
         let catalog: Arc<dyn SessionMetadataStore> = event_store.clone();
+        let task_store: Arc<dyn ScheduledTaskStore> = event_store.clone();
+        let audit_log: Arc<dyn ToolAuditStore> = event_store.clone();
 
         let model_registry = Arc::new(
             steer_core::model_registry::ModelRegistry::load(&config.catalog_config.catalog_paths)
@@ -104,6 +143,10 @@ impl ServiceHost {
                 reason: format!("Failed to load model registry: {e}"),
             })?,
         );
+        let model_registry = steer_core::model_registry::SharedModelRegistry::new(
+            model_registry,
+            config.catalog_config.catalog_paths.clone(),
+        );
 
         let provider_registry = Arc::new(
             steer_core::auth::ProviderRegistry::load(&config.catalog_config.catalog_paths)
@@ -129,6 +172,7 @@ impl ServiceHost {
         let workspace = steer_core::workspace::create_workspace(
             &steer_core::workspace::WorkspaceConfig::Local {
                 path: workspace_path.clone(),
+                context_limits: Default::default(),
             },
         )
         .await
@@ -148,16 +192,51 @@ impl ServiceHost {
             workspace,
             event_store.clone(),
             api_client.clone(),
-            model_registry.clone(),
+            model_registry.snapshot(),
         )
         .with_workspace_manager(workspace_manager)
         .with_repo_manager(repo_manager)
+        .with_audit_log(audit_log.clone())
         .build();
 
-        let runtime_service = RuntimeService::spawn(event_store, api_client, tool_executor);
+        let runtime_service = RuntimeService::spawn_with_config(
+            event_store,
+            api_client,
+            tool_executor,
+            config.runtime,
+        );
 
         let runtime_handle = runtime_service.handle();
 
+        let background_shutdown = CancellationToken::new();
+
+        let mut scheduler = Scheduler::new(task_store, runtime_handle.clone());
+        if !config.webhooks.is_empty() {
+            let notifier = Arc::new(WebhookNotifier::new(
+                config.webhooks.clone(),
+                Some(config.db_path.with_file_name("webhook_dead_letters.jsonl")),
+            ));
+            notifier
+                .clone()
+                .spawn(&runtime_handle, background_shutdown.clone());
+            scheduler = scheduler.with_notifier(notifier);
+            info!(
+                "Webhook notifications enabled for {} endpoint(s)",
+                config.webhooks.len()
+            );
+        }
+        let scheduler = Arc::new(scheduler);
+        scheduler.clone().spawn(background_shutdown.clone());
+
+        let event_logger = config.event_log.clone().map(|event_log| {
+            info!("Logging session events to {:?}", event_log.sink);
+            let logger = Arc::new(EventLogger::new(event_log));
+            logger
+                .clone()
+                .spawn(&runtime_handle, background_shutdown.clone());
+            logger
+        });
+
         info!(
             "ServiceHost initialized with database at {:?}",
             config.db_path
@@ -171,7 +250,13 @@ impl ServiceHost {
             provider_registry,
             llm_config_provider,
             environment_root,
+            scheduler,
+            event_logger,
+            audit_log,
+            background_shutdown,
             server_handle: None,
+            http_handle: None,
+            http_addr: None,
             shutdown_tx: None,
             config,
         })
@@ -195,7 +280,7 @@ impl ServiceHost {
         let repo_manager: Arc<dyn RepoManager> = workspace_manager.clone();
         let environment_manager = Arc::new(LocalEnvironmentManager::new(environment_root));
 
-        let service = RuntimeAgentService::new(crate::grpc::RuntimeAgentDeps {
+        let service = Arc::new(RuntimeAgentService::new(crate::grpc::RuntimeAgentDeps {
             runtime: self.runtime_handle.clone(),
             catalog: self.catalog.clone(),
             llm_config_provider: self.llm_config_provider.clone(),
@@ -204,7 +289,24 @@ impl ServiceHost {
             environment_manager,
             workspace_manager,
             repo_manager,
-        });
+            scheduler: Some(self.scheduler.clone()),
+            event_logger: self.event_logger.clone(),
+            audit_log: Some(self.audit_log.clone()),
+        }));
+
+        if let Some(http_addr) = self.config.http_addr {
+            let listener = tokio::net::TcpListener::bind(http_addr).await?;
+            let local_addr = listener.local_addr()?;
+            let service = service.clone();
+            let shutdown = self.background_shutdown.clone();
+            self.http_handle = Some(tokio::spawn(async move {
+                http_gateway::serve(listener, service, shutdown)
+                    .await
+                    .map_err(GrpcError::HttpGateway)
+            }));
+            self.http_addr = Some(local_addr);
+            info!("HTTP gateway listening on {}", local_addr);
+        }
 
         let (shutdown_tx, shutdown_rx) = oneshot::channel();
         let addr = self.config.bind_addr;
@@ -214,7 +316,7 @@ impl ServiceHost {
         let server_handle = tokio::spawn(async move {
             Server::builder()
                 .add_service(
-                    AgentServiceServer::new(service)
+                    AgentServiceServer::from_arc(service)
                         .max_decoding_message_size(GRPC_MAX_MESSAGE_SIZE_BYTES)
                         .max_encoding_message_size(GRPC_MAX_MESSAGE_SIZE_BYTES),
                 )
@@ -236,6 +338,8 @@ impl ServiceHost {
     pub async fn shutdown(mut self) -> Result<()> {
         info!("Initiating ServiceHost shutdown");
 
+        self.background_shutdown.cancel();
+
         if let Some(shutdown_tx) = self.shutdown_tx.take() {
             let _ = shutdown_tx.send(());
         }
@@ -248,12 +352,81 @@ impl ServiceHost {
             }
         }
 
+        if let Some(http_handle) = self.http_handle.take() {
+            match http_handle.await {
+                Ok(Ok(())) => info!("HTTP gateway shut down successfully"),
+                Ok(Err(e)) => error!("HTTP gateway error during shutdown: {}", e),
+                Err(e) => error!("Failed to join HTTP gateway task: {}", e),
+            }
+        }
+
         self.runtime_service.shutdown().await;
 
         info!("ServiceHost shutdown complete");
         Ok(())
     }
 
+    /// Stops accepting connections, cancels in-flight operations and records
+    /// the cancellations, then waits for open RPCs to finish. Whatever is
+    /// still running after `timeout` is aborted; returns whether everything
+    /// finished in time.
+    pub async fn drain(mut self, timeout: Duration) -> bool {
+        info!("Draining ServiceHost");
+        let deadline = Instant::now() + timeout;
+
+        self.background_shutdown.cancel();
+
+        if let Some(shutdown_tx) = self.shutdown_tx.take() {
+            let _ = shutdown_tx.send(());
+        }
+
+        let mut drained = self.runtime_service.drain(timeout).await;
+        if !drained {
+            warn!(
+                "Operations still running after {:?}; aborting them",
+                timeout
+            );
+        }
+
+        if let Some(mut server_handle) = self.server_handle.take() {
+            match tokio::time::timeout_at(deadline, &mut server_handle).await {
+                Ok(Ok(Ok(()))) => info!("gRPC server shut down successfully"),
+                Ok(Ok(Err(e))) => error!("gRPC server error during shutdown: {}", e),
+                Ok(Err(e)) => error!("Failed to join server task: {}", e),
+                Err(_) => {
+                    warn!("Open RPCs still running after {:?}; aborting them", timeout);
+                    server_handle.abort();
+                    drained = false;
+                }
+            }
+        }
+
+        if let Some(mut http_handle) = self.http_handle.take() {
+            match tokio::time::timeout_at(deadline, &mut http_handle).await {
+                Ok(Ok(Ok(()))) => info!("HTTP gateway shut down successfully"),
+                Ok(Ok(Err(e))) => error!("HTTP gateway error during shutdown: {}", e),
+                Ok(Err(e)) => error!("Failed to join HTTP gateway task: {}", e),
+                Err(_) => {
+                    warn!(
+                        "Open HTTP requests still running after {:?}; aborting them",
+                        timeout
+                    );
+                    http_handle.abort();
+                    drained = false;
+                }
+            }
+        }
+
+        info!("ServiceHost drain complete");
+        drained
+    }
+
+    /// Where the HTTP gateway is listening, once [`start`](Self::start) has
+    /// bound it.
+    pub fn http_addr(&self) -> Option<SocketAddr> {
+        self.http_addr
+    }
+
     pub fn runtime_handle(&self) -> &RuntimeHandle {
         &self.runtime_handle
     }
@@ -287,6 +460,10 @@ mod tests {
             auth_storage: Arc::new(steer_core::test_utils::InMemoryAuthStorage::new()),
             catalog_config: CatalogConfig::default(),
             workspace_root: Some(temp_dir.path().to_path_buf()),
+            webhooks: Vec::new(),
+            event_log: None,
+            runtime: Default::default(),
+            http_addr: None,
         };
 
         (config, temp_dir)
@@ -314,4 +491,14 @@ mod tests {
 
         host.shutdown().await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_service_host_drain() {
+        let (config, _temp_dir) = create_test_config();
+
+        let mut host = ServiceHost::new(config).await.unwrap();
+        host.start().await.unwrap();
+
+        assert!(host.drain(Duration::from_secs(5)).await);
+    }
 }
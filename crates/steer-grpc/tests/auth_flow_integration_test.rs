@@ -22,6 +22,7 @@ async fn test_auth_progress_polling_without_input() {
     let start = client
         .start_auth(StartAuthRequest {
             provider_id: "anthropic".to_string(),
+            method: None,
         })
         .await
         .expect("start auth")
@@ -67,6 +68,7 @@ async fn test_openai_auth_progress_polling_without_input() {
     let start = client
         .start_auth(StartAuthRequest {
             provider_id: "openai".to_string(),
+            method: None,
         })
         .await
         .expect("start auth")
@@ -5,8 +5,8 @@ use std::sync::Arc;
 use steer_core::app::domain::action::{Action, McpServerState};
 use steer_core::app::domain::types::SessionId;
 use steer_grpc::client_api::{
-    ClientEvent, CreateSessionParams, SessionPolicyOverrides, SessionToolConfig,
-    WorkspaceConfig as ClientWorkspaceConfig,
+    AutoSummaryConfig, ClientEvent, CreateSessionParams, IsolationMode, SessionPolicyOverrides,
+    SessionToolConfig, WorkspaceConfig as ClientWorkspaceConfig,
 };
 use steer_grpc::{AgentClient, ServiceHost, ServiceHostConfig};
 use steer_proto::agent::v1::{
@@ -123,6 +123,10 @@ async fn test_tui_agent_service_file_listing() {
         auth_storage: Arc::new(steer_core::test_utils::InMemoryAuthStorage::new()),
         catalog_config: steer_core::catalog::CatalogConfig::default(),
         workspace_root: Some(workspace_path.clone()),
+        webhooks: Vec::new(),
+        event_log: None,
+        runtime: Default::default(),
+        http_addr: None,
     };
 
     // Start the service host
@@ -287,6 +291,10 @@ async fn test_tui_auth_flow_poll_no_input() -> Result<()> {
         auth_storage: Arc::new(steer_core::test_utils::InMemoryAuthStorage::new()),
         catalog_config: steer_core::catalog::CatalogConfig::default(),
         workspace_root: Some(workspace_path.clone()),
+        webhooks: Vec::new(),
+        event_log: None,
+        runtime: Default::default(),
+        http_addr: None,
     };
 
     let mut service_host = ServiceHost::new(config).await.unwrap();
@@ -303,6 +311,7 @@ async fn test_tui_auth_flow_poll_no_input() -> Result<()> {
     let start = grpc_client
         .start_auth(StartAuthRequest {
             provider_id: "anthropic".to_string(),
+            method: None,
         })
         .await
         .unwrap()
@@ -376,6 +385,10 @@ async fn test_agent_client_resubscribes_events_on_session_switch() -> Result<()>
         auth_storage: Arc::new(steer_core::test_utils::InMemoryAuthStorage::new()),
         catalog_config: steer_core::catalog::CatalogConfig::default(),
         workspace_root: Some(workspace_path.clone()),
+        webhooks: Vec::new(),
+        event_log: None,
+        runtime: Default::default(),
+        http_addr: None,
     };
 
     let mut service_host = ServiceHost::new(config).await.unwrap();
@@ -397,6 +410,8 @@ async fn test_agent_client_resubscribes_events_on_session_switch() -> Result<()>
         tool_config: SessionToolConfig::default(),
         primary_agent_id: None,
         policy_overrides: SessionPolicyOverrides::empty(),
+        isolation: IsolationMode::None,
+        auto_summary: AutoSummaryConfig::default(),
         metadata: HashMap::new(),
         default_model: default_model.clone(),
     };
@@ -427,6 +442,8 @@ async fn test_agent_client_resubscribes_events_on_session_switch() -> Result<()>
         tool_config: SessionToolConfig::default(),
         primary_agent_id: None,
         policy_overrides: SessionPolicyOverrides::empty(),
+        isolation: IsolationMode::None,
+        auto_summary: AutoSummaryConfig::default(),
         metadata: HashMap::new(),
         default_model,
     };
@@ -478,6 +495,10 @@ async fn test_workspace_changed_event_flow() {
         auth_storage: Arc::new(steer_core::test_utils::InMemoryAuthStorage::new()),
         catalog_config: steer_core::catalog::CatalogConfig::default(),
         workspace_root: Some(workspace_path.clone()),
+        webhooks: Vec::new(),
+        event_log: None,
+        runtime: Default::default(),
+        http_addr: None,
     };
 
     let mut service_host = ServiceHost::new(config).await.unwrap();
@@ -520,6 +541,7 @@ async fn test_workspace_changed_event_flow() {
     let subscribe_req = SubscribeSessionEventsRequest {
         session_id: session_id.clone(),
         since_sequence: None,
+        event_kinds: Vec::new(),
     };
 
     let response = grpc_client
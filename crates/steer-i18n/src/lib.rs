@@ -0,0 +1,325 @@
+//! Localized user-facing strings for the Steer CLI and TUI.
+//!
+//! Text shown to the person at the terminal is looked up by key with [`t!`]
+//! and comes from a per-locale catalog in `locales/`. English is always loaded
+//! and fills in any key a translation has not caught up with yet.
+//!
+//! Model-facing text is out of scope. System prompts, tool descriptions, tool
+//! results and every other string a model reads stay English and are built in
+//! `steer-core`, which does not depend on this crate. Never pass a translated
+//! string into a message, prompt or tool result.
+//!
+//! # Adding a translation
+//!
+//! 1. Copy `locales/en.toml` to `locales/<tag>.toml` (for example `de.toml` or
+//!    `pt-BR.toml`) and translate the values, keeping every `{placeholder}`.
+//! 2. Add the file to [`LOCALES`].
+//! 3. Run `cargo test -p steer-i18n`, which rejects unknown keys and
+//!    placeholders that do not match the English text.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+use thiserror::Error;
+
+/// Locale tag used when nothing else matches. Its catalog defines every key.
+pub const FALLBACK_LOCALE: &str = "en";
+
+/// Bundled catalogs as `(tag, source)`, fallback first.
+pub const LOCALES: &[(&str, &str)] = &[(FALLBACK_LOCALE, include_str!("../locales/en.toml"))];
+
+/// Environment variables consulted for the locale, in POSIX precedence order.
+const LOCALE_ENV_VARS: &[&str] = &["LC_ALL", "LC_MESSAGES", "LANG"];
+
+#[derive(Debug, Error)]
+pub enum CatalogError {
+    #[error("invalid catalog: {0}")]
+    Parse(#[from] toml::de::Error),
+
+    #[error("catalog entry '{key}' must be a string")]
+    NotAString { key: String },
+}
+
+struct Catalog {
+    locale: &'static str,
+    strings: HashMap<String, String>,
+}
+
+static FALLBACK: OnceLock<HashMap<String, String>> = OnceLock::new();
+static ACTIVE: OnceLock<Catalog> = OnceLock::new();
+
+/// Select the locale for this process and return its tag.
+///
+/// `preferred` comes from the `ui.locale` preference; when it is unset or not
+/// bundled, `LC_ALL`, `LC_MESSAGES` and `LANG` are consulted, then English.
+/// Only the first call has an effect.
+pub fn init(preferred: Option<&str>) -> &'static str {
+    active_catalog_or_init(|| resolve_locale(preferred, |name| std::env::var(name).ok())).locale
+}
+
+/// The locale tag in use, or the fallback before [`init`] has run.
+pub fn current_locale() -> &'static str {
+    ACTIVE
+        .get()
+        .map_or(FALLBACK_LOCALE, |catalog| catalog.locale)
+}
+
+/// Pick the bundled locale for a preference and environment lookup.
+pub fn resolve_locale(
+    preferred: Option<&str>,
+    env: impl Fn(&str) -> Option<String>,
+) -> &'static str {
+    if let Some(tag) = preferred.and_then(bundled_locale) {
+        return tag;
+    }
+
+    LOCALE_ENV_VARS
+        .iter()
+        .find_map(|name| env(name).filter(|value| !value.trim().is_empty()))
+        .and_then(|value| bundled_locale(&value))
+        .unwrap_or(FALLBACK_LOCALE)
+}
+
+/// Match a locale name such as `pt_BR.UTF-8` or `de` against the bundled tags,
+/// falling back from region to bare language.
+fn bundled_locale(name: &str) -> Option<&'static str> {
+    let tag = name
+        .split(['.', '@'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .replace('_', "-");
+    if tag.is_empty() || tag.eq_ignore_ascii_case("C") || tag.eq_ignore_ascii_case("POSIX") {
+        return None;
+    }
+
+    let find = |wanted: &str| {
+        LOCALES
+            .iter()
+            .map(|(bundled, _)| *bundled)
+            .find(|bundled| bundled.eq_ignore_ascii_case(wanted))
+    };
+    find(&tag).or_else(|| tag.split('-').next().and_then(find))
+}
+
+/// Look up a user-facing string. Prefer the [`t!`] macro.
+///
+/// Keys missing from the active catalog fall back to English, and keys missing
+/// from English return the key itself so a typo is visible rather than blank.
+pub fn tr(key: &'static str) -> &'static str {
+    let active = ACTIVE.get().and_then(|catalog| catalog.strings.get(key));
+    active
+        .or_else(|| fallback_strings().get(key))
+        .map_or(key, String::as_str)
+}
+
+/// Look up a user-facing string and fill in its `{name}` placeholders.
+/// Prefer the [`t!`] macro.
+pub fn tr_args(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    args.iter()
+        .fold(tr(key).to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), &value.to_string())
+        })
+}
+
+/// Look up a user-facing string by key, optionally filling in placeholders.
+///
+/// ```
+/// use steer_i18n::t;
+///
+/// assert_eq!(t!("approval.keybind.deny"), "No");
+/// assert_eq!(t!("plain.finished", tool = "ls"), "Finished ls");
+/// ```
+#[macro_export]
+macro_rules! t {
+    ($key:literal) => {
+        $crate::tr($key)
+    };
+    ($key:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::tr_args(
+            $key,
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+],
+        )
+    };
+}
+
+/// Parse a catalog, flattening TOML tables into dotted keys.
+pub fn parse_catalog(source: &str) -> Result<HashMap<String, String>, CatalogError> {
+    let table: toml::Table = source.parse()?;
+    let mut strings = HashMap::new();
+    flatten_into(&mut strings, "", table)?;
+    Ok(strings)
+}
+
+fn flatten_into(
+    strings: &mut HashMap<String, String>,
+    prefix: &str,
+    table: toml::Table,
+) -> Result<(), CatalogError> {
+    for (name, value) in table {
+        let key = if prefix.is_empty() {
+            name
+        } else {
+            format!("{prefix}.{name}")
+        };
+        match value {
+            toml::Value::String(text) => {
+                strings.insert(key, text);
+            }
+            toml::Value::Table(table) => flatten_into(strings, &key, table)?,
+            _ => return Err(CatalogError::NotAString { key }),
+        }
+    }
+    Ok(())
+}
+
+fn bundled_strings(locale: &str) -> HashMap<String, String> {
+    LOCALES
+        .iter()
+        .find(|(tag, _)| *tag == locale)
+        .and_then(|(_, source)| parse_catalog(source).ok())
+        .unwrap_or_default()
+}
+
+fn fallback_strings() -> &'static HashMap<String, String> {
+    FALLBACK.get_or_init(|| bundled_strings(FALLBACK_LOCALE))
+}
+
+fn active_catalog_or_init(locale: impl FnOnce() -> &'static str) -> &'static Catalog {
+    ACTIVE.get_or_init(|| {
+        let locale = locale();
+        Catalog {
+            locale,
+            strings: if locale == FALLBACK_LOCALE {
+                HashMap::new()
+            } else {
+                bundled_strings(locale)
+            },
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use std::path::Path;
+
+    fn placeholders(text: &str) -> BTreeSet<&str> {
+        text.split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn bundled_catalogs_parse_and_fallback_comes_first() {
+        assert_eq!(LOCALES[0].0, FALLBACK_LOCALE);
+        for (tag, source) in LOCALES {
+            parse_catalog(source).unwrap_or_else(|e| panic!("{tag} catalog: {e}"));
+        }
+    }
+
+    #[test]
+    fn translations_only_use_english_keys_and_placeholders() {
+        let english = parse_catalog(LOCALES[0].1).expect("English catalog parses");
+        for (tag, source) in &LOCALES[1..] {
+            let strings = parse_catalog(source).expect("catalog parses");
+            for (key, text) in &strings {
+                let english_text = english
+                    .get(key)
+                    .unwrap_or_else(|| panic!("{tag} defines unknown key '{key}'"));
+                assert_eq!(
+                    placeholders(text),
+                    placeholders(english_text),
+                    "{tag} placeholders differ for '{key}'"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_key_used_in_the_cli_and_tui_is_defined_in_english() {
+        let english = parse_catalog(LOCALES[0].1).expect("English catalog parses");
+        let crates = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let mut missing = Vec::new();
+        for dir in ["steer/src", "steer-tui/src"] {
+            let mut pending = vec![crates.join(dir)];
+            while let Some(path) = pending.pop() {
+                if path.is_dir() {
+                    for entry in std::fs::read_dir(&path).expect("source dir is readable") {
+                        pending.push(entry.expect("dir entry").path());
+                    }
+                    continue;
+                }
+                if path.extension().is_none_or(|ext| ext != "rs") {
+                    continue;
+                }
+                let source = std::fs::read_to_string(&path).expect("source file is readable");
+                for (start, pattern) in source.match_indices("t!(") {
+                    // Skip `format!(` and other macros whose names end in `t`.
+                    let preceding = source[..start].chars().next_back();
+                    if preceding.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                        continue;
+                    }
+                    let Some(rest) = source[start + pattern.len()..]
+                        .trim_start()
+                        .strip_prefix('"')
+                    else {
+                        continue;
+                    };
+                    let key = rest.split('"').next().unwrap_or_default();
+                    if !english.contains_key(key) {
+                        missing.push(format!("{}: {key}", path.display()));
+                    }
+                }
+            }
+        }
+        assert!(
+            missing.is_empty(),
+            "keys missing from en.toml: {missing:#?}"
+        );
+    }
+
+    #[test]
+    fn locale_comes_from_preference_then_environment_then_fallback() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| (*value).to_string())
+            }
+        };
+
+        assert_eq!(resolve_locale(Some("en"), env(&[])), "en");
+        assert_eq!(resolve_locale(None, env(&[("LANG", "en_GB.UTF-8")])), "en");
+        assert_eq!(
+            resolve_locale(Some("xx"), env(&[("LC_ALL", "en_US"), ("LANG", "fr_FR")])),
+            "en"
+        );
+        assert_eq!(resolve_locale(None, env(&[("LANG", "C")])), FALLBACK_LOCALE);
+        assert_eq!(resolve_locale(None, env(&[("LANG", "")])), FALLBACK_LOCALE);
+        assert_eq!(bundled_locale("fr_FR.UTF-8"), None);
+        assert_eq!(bundled_locale("EN-us"), Some("en"));
+    }
+
+    #[test]
+    fn lookups_fill_placeholders_and_fall_back_to_the_key() {
+        assert_eq!(t!("approval.keybind.deny"), "No");
+        assert_eq!(t!("plain.finished", tool = "ls"), "Finished ls");
+        assert_eq!(t!("no.such.key"), "no.such.key");
+    }
+
+    #[test]
+    fn nested_tables_flatten_to_dotted_keys() {
+        let strings =
+            parse_catalog("top = \"a\"\n[group]\nname = \"b\"\n").expect("catalog parses");
+        assert_eq!(strings["top"], "a");
+        assert_eq!(strings["group.name"], "b");
+        assert!(matches!(
+            parse_catalog("count = 3"),
+            Err(CatalogError::NotAString { key }) if key == "count"
+        ));
+    }
+}
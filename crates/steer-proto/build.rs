@@ -1,4 +1,5 @@
 use std::path::{Path, PathBuf};
+use std::process::Command;
 
 fn path_to_str(path: &Path) -> Result<&str, std::io::Error> {
     path.to_str().ok_or_else(|| {
@@ -9,9 +10,40 @@ fn path_to_str(path: &Path) -> Result<&str, std::io::Error> {
     })
 }
 
+/// Locate a protoc binary: an explicit `PROTOC` wins, then the vendored binary
+/// (so `cargo install` works without a system compiler), then `protoc` on PATH
+/// for targets that protoc-bin-vendored does not ship.
+fn find_protoc() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-env-changed=PROTOC");
+    if let Some(protoc) = std::env::var_os("PROTOC").filter(|protoc| !protoc.is_empty()) {
+        return Ok(PathBuf::from(protoc));
+    }
+
+    let vendored_error = match protoc_bin_vendored::protoc_bin_path() {
+        Ok(protoc) => return Ok(protoc),
+        Err(e) => e,
+    };
+
+    let on_path = Command::new("protoc")
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if on_path {
+        return Ok(PathBuf::from("protoc"));
+    }
+
+    Err(format!(
+        "steer-proto needs protoc to generate its gRPC code, but none was found.\n\
+         The bundled protoc-bin-vendored binary is unavailable for this target ({vendored_error}).\n\
+         Fix this by either:\n  \
+         1. installing protoc (e.g. `apt install protobuf-compiler` or `brew install protobuf`) so it is on PATH, or\n  \
+         2. setting PROTOC=/path/to/protoc to point at an existing protoc binary."
+    )
+    .into())
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Use vendored protoc to avoid requiring users to install it
-    let protoc = protoc_bin_vendored::protoc_bin_path()?;
+    let protoc = find_protoc()?;
 
     // Get the manifest directory (where Cargo.toml is located)
     let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")?;
@@ -0,0 +1,904 @@
+//! Conversions between `steer_tools` results and `steer.common.v1` messages.
+//!
+//! Both the agent protocol (steer-grpc) and the remote workspace protocol
+//! (steer-remote-workspace / steer-workspace-client) carry these messages, so the
+//! mapping lives here once instead of being duplicated on each side of the wire.
+
+use crate::common::v1 as proto;
+use steer_tools::error::{ToolError, ToolExecutionError};
+use steer_tools::result::{
+    AgentResult, AgentWorkspaceInfo, AgentWorkspaceRevision, BashResult, EditResult,
+    ExternalResult, FetchResult, FileContentResult, FileEntry, FileListResult, GitStatusResult,
+    GlobResult, MemoryResult, SearchMatch, SearchResult, SkippedEdit, TodoListResult,
+    TodoWriteResult, ToolResult,
+};
+use steer_tools::tools::bash::{ParsedCommand, SimpleCommand};
+use steer_tools::tools::memory::MemoryEntry;
+use steer_tools::tools::todo::{TodoItem, TodoPriority, TodoStatus, TodoWriteFileOperation};
+
+/// Version stamped on every outgoing [`proto::ToolResult`].
+///
+/// Bump this whenever a `ToolResult` variant is added or changes shape.
+pub const TOOL_RESULT_VERSION: u32 = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ToolResultConversionError {
+    #[error("Missing required field: {field}")]
+    MissingField { field: String },
+
+    #[error(
+        "Tool result version {version} is newer than supported version {TOOL_RESULT_VERSION} and has an unknown variant"
+    )]
+    UnsupportedVersion { version: u32 },
+}
+
+pub fn search_result_to_proto(result: &SearchResult) -> proto::SearchResult {
+    proto::SearchResult {
+        matches: result
+            .matches
+            .iter()
+            .map(|m| proto::SearchMatch {
+                file_path: m.file_path.clone(),
+                line_number: m.line_number as u64,
+                line_content: m.line_content.clone(),
+                column_range: m.column_range.map(|(start, end)| proto::ColumnRange {
+                    start: start as u64,
+                    end: end as u64,
+                }),
+            })
+            .collect(),
+        total_files_searched: result.total_files_searched as u64,
+        search_completed: result.search_completed,
+        warnings: result.warnings.clone(),
+    }
+}
+
+pub fn proto_to_search_result(result: proto::SearchResult) -> SearchResult {
+    SearchResult {
+        matches: result
+            .matches
+            .into_iter()
+            .map(|m| SearchMatch {
+                file_path: m.file_path,
+                line_number: m.line_number as usize,
+                line_content: m.line_content,
+                column_range: m
+                    .column_range
+                    .map(|cr| (cr.start as usize, cr.end as usize)),
+            })
+            .collect(),
+        total_files_searched: result.total_files_searched as usize,
+        search_completed: result.search_completed,
+        warnings: result.warnings,
+    }
+}
+
+pub fn file_list_result_to_proto(result: &FileListResult) -> proto::FileListResult {
+    proto::FileListResult {
+        entries: result
+            .entries
+            .iter()
+            .map(|e| proto::FileEntry {
+                path: e.path.clone(),
+                is_directory: e.is_directory,
+                size: e.size,
+                permissions: e.permissions.clone(),
+            })
+            .collect(),
+        base_path: result.base_path.clone(),
+        total_entries: result.total_entries as u64,
+        truncated: result.truncated,
+        warnings: result.warnings.clone(),
+    }
+}
+
+pub fn proto_to_file_list_result(result: proto::FileListResult) -> FileListResult {
+    let entries: Vec<FileEntry> = result
+        .entries
+        .into_iter()
+        .map(|e| FileEntry {
+            path: e.path,
+            is_directory: e.is_directory,
+            size: e.size,
+            permissions: e.permissions,
+        })
+        .collect();
+    // Older senders don't page listings and leave total_entries unset.
+    let total_entries = if result.total_entries == 0 {
+        entries.len()
+    } else {
+        result.total_entries as usize
+    };
+    FileListResult {
+        entries,
+        base_path: result.base_path,
+        total_entries,
+        truncated: result.truncated,
+        warnings: result.warnings,
+    }
+}
+
+pub fn file_content_result_to_proto(result: &FileContentResult) -> proto::FileContentResult {
+    proto::FileContentResult {
+        content: result.content.clone(),
+        file_path: result.file_path.clone(),
+        line_count: result.line_count as u64,
+        truncated: result.truncated,
+        encoding: result.encoding.clone(),
+        binary_kind: result.binary_kind.clone(),
+        file_size: result.file_size,
+        warnings: result.warnings.clone(),
+    }
+}
+
+pub fn proto_to_file_content_result(result: proto::FileContentResult) -> FileContentResult {
+    FileContentResult {
+        content: result.content,
+        file_path: result.file_path,
+        line_count: result.line_count as usize,
+        truncated: result.truncated,
+        encoding: result.encoding,
+        binary_kind: result.binary_kind,
+        file_size: result.file_size,
+        warnings: result.warnings,
+    }
+}
+
+pub fn edit_result_to_proto(result: &EditResult) -> proto::EditResult {
+    proto::EditResult {
+        file_path: result.file_path.clone(),
+        changes_made: result.changes_made as u64,
+        file_created: result.file_created,
+        old_content: result.old_content.clone(),
+        new_content: result.new_content.clone(),
+        skipped_edits: result
+            .skipped_edits
+            .iter()
+            .map(|skipped| proto::SkippedEdit {
+                edit_index: skipped.edit_index as u64,
+                reason: skipped.reason.clone(),
+            })
+            .collect(),
+    }
+}
+
+pub fn proto_to_edit_result(result: proto::EditResult) -> EditResult {
+    EditResult {
+        file_path: result.file_path,
+        changes_made: result.changes_made as usize,
+        file_created: result.file_created,
+        old_content: result.old_content,
+        new_content: result.new_content,
+        skipped_edits: result
+            .skipped_edits
+            .into_iter()
+            .map(|skipped| SkippedEdit {
+                edit_index: skipped.edit_index as usize,
+                reason: skipped.reason,
+            })
+            .collect(),
+    }
+}
+
+pub fn glob_result_to_proto(result: &GlobResult) -> proto::GlobResult {
+    proto::GlobResult {
+        matches: result.matches.clone(),
+        pattern: result.pattern.clone(),
+        warnings: result.warnings.clone(),
+    }
+}
+
+pub fn proto_to_glob_result(result: proto::GlobResult) -> GlobResult {
+    GlobResult {
+        matches: result.matches,
+        pattern: result.pattern,
+        warnings: result.warnings,
+    }
+}
+
+pub fn todo_item_to_proto(item: &TodoItem) -> proto::TodoItem {
+    proto::TodoItem {
+        id: item.id.clone(),
+        content: item.content.clone(),
+        status: match item.status {
+            TodoStatus::Pending => proto::TodoStatus::Pending as i32,
+            TodoStatus::InProgress => proto::TodoStatus::InProgress as i32,
+            TodoStatus::Completed => proto::TodoStatus::Completed as i32,
+        },
+        priority: match item.priority {
+            TodoPriority::High => proto::TodoPriority::High as i32,
+            TodoPriority::Medium => proto::TodoPriority::Medium as i32,
+            TodoPriority::Low => proto::TodoPriority::Low as i32,
+        },
+    }
+}
+
+pub fn memory_entry_to_proto(entry: &MemoryEntry) -> proto::MemoryEntry {
+    proto::MemoryEntry {
+        key: entry.key.clone(),
+        content: entry.content.clone(),
+    }
+}
+
+pub fn proto_to_memory_entry(entry: proto::MemoryEntry) -> MemoryEntry {
+    MemoryEntry {
+        key: entry.key,
+        content: entry.content,
+    }
+}
+
+pub fn proto_to_todo_item(item: proto::TodoItem) -> TodoItem {
+    TodoItem {
+        id: item.id,
+        content: item.content,
+        status: match proto::TodoStatus::try_from(item.status) {
+            Ok(proto::TodoStatus::InProgress) => TodoStatus::InProgress,
+            Ok(proto::TodoStatus::Completed) => TodoStatus::Completed,
+            Ok(proto::TodoStatus::Pending | proto::TodoStatus::StatusUnset) | Err(_) => {
+                TodoStatus::Pending
+            }
+        },
+        priority: match proto::TodoPriority::try_from(item.priority) {
+            Ok(proto::TodoPriority::High) => TodoPriority::High,
+            Ok(proto::TodoPriority::Medium) => TodoPriority::Medium,
+            Ok(proto::TodoPriority::Low | proto::TodoPriority::PriorityUnset) | Err(_) => {
+                TodoPriority::Low
+            }
+        },
+    }
+}
+
+pub fn todo_write_file_operation_to_proto(
+    operation: &TodoWriteFileOperation,
+) -> proto::TodoWriteFileOperation {
+    match operation {
+        TodoWriteFileOperation::Created => proto::TodoWriteFileOperation::Created,
+        TodoWriteFileOperation::Modified => proto::TodoWriteFileOperation::Modified,
+    }
+}
+
+pub fn proto_to_todo_write_file_operation(
+    operation: proto::TodoWriteFileOperation,
+) -> TodoWriteFileOperation {
+    match operation {
+        proto::TodoWriteFileOperation::Modified => TodoWriteFileOperation::Modified,
+        proto::TodoWriteFileOperation::Created | proto::TodoWriteFileOperation::OperationUnset => {
+            TodoWriteFileOperation::Created
+        }
+    }
+}
+
+fn agent_workspace_info_to_proto(info: &AgentWorkspaceInfo) -> proto::AgentWorkspaceInfo {
+    proto::AgentWorkspaceInfo {
+        workspace_id: info.workspace_id.clone().unwrap_or_default(),
+        revision: info
+            .revision
+            .as_ref()
+            .map(|revision| proto::AgentWorkspaceRevision {
+                vcs_kind: revision.vcs_kind.clone(),
+                revision_id: revision.revision_id.clone(),
+                summary: revision.summary.clone(),
+                change_id: revision.change_id.clone().unwrap_or_default(),
+            }),
+    }
+}
+
+fn proto_to_agent_workspace_info(info: proto::AgentWorkspaceInfo) -> AgentWorkspaceInfo {
+    AgentWorkspaceInfo {
+        workspace_id: non_empty(info.workspace_id),
+        revision: info.revision.map(|revision| AgentWorkspaceRevision {
+            vcs_kind: revision.vcs_kind,
+            revision_id: revision.revision_id,
+            summary: revision.summary,
+            change_id: non_empty(revision.change_id),
+        }),
+    }
+}
+
+fn non_empty(value: String) -> Option<String> {
+    if value.is_empty() { None } else { Some(value) }
+}
+
+pub fn tool_error_to_proto(error: &ToolError) -> proto::ToolError {
+    use proto::tool_error::ErrorType;
+
+    let error_type = match error {
+        ToolError::UnknownTool(name) => ErrorType::UnknownTool(name.clone()),
+        ToolError::InvalidParams { tool_name, message } => {
+            ErrorType::InvalidParams(proto::InvalidParamsError {
+                tool_name: tool_name.clone(),
+                message: message.clone(),
+            })
+        }
+        // Send External's raw message; its Display adds a "{tool} failed: " prefix
+        // that would otherwise compound on every hop.
+        ToolError::Execution(ToolExecutionError::External { tool_name, message }) => {
+            ErrorType::Execution(proto::ExecutionError {
+                tool_name: tool_name.clone(),
+                message: message.clone(),
+            })
+        }
+        ToolError::Execution(error) => ErrorType::Execution(proto::ExecutionError {
+            tool_name: error.tool_name().to_string(),
+            message: error.to_string(),
+        }),
+        ToolError::Cancelled(name) => ErrorType::Cancelled(name.clone()),
+        ToolError::Timeout(name) => ErrorType::Timeout(name.clone()),
+        ToolError::ApprovalDenied { tool_name, reason } => {
+            ErrorType::ApprovalDenied(proto::ApprovalDeniedError {
+                tool_name: tool_name.clone(),
+                reason: reason.clone(),
+            })
+        }
+        ToolError::DeniedByUser(name) => ErrorType::DeniedByUser(name.clone()),
+        ToolError::DeniedByUserWithReason { tool_name, reason } => {
+            ErrorType::DeniedByUserWithReason(proto::DeniedWithReasonError {
+                tool_name: tool_name.clone(),
+                reason: reason.clone(),
+            })
+        }
+        ToolError::DeniedByPolicy(name) => ErrorType::DeniedByPolicy(name.clone()),
+        ToolError::InternalError(msg) => ErrorType::InternalError(msg.clone()),
+    };
+
+    proto::ToolError {
+        error_type: Some(error_type),
+    }
+}
+
+/// Typed execution errors cross the wire as `External { tool_name, message }`,
+/// since only their rendered message is transmitted.
+pub fn proto_to_tool_error(
+    error: proto::ToolError,
+) -> Result<ToolError, ToolResultConversionError> {
+    use proto::tool_error::ErrorType;
+
+    let error_type = error
+        .error_type
+        .ok_or_else(|| ToolResultConversionError::MissingField {
+            field: "tool_error.error_type".to_string(),
+        })?;
+
+    Ok(match error_type {
+        ErrorType::UnknownTool(name) => ToolError::UnknownTool(name),
+        ErrorType::InvalidParams(e) => ToolError::InvalidParams {
+            tool_name: e.tool_name,
+            message: e.message,
+        },
+        ErrorType::Execution(e) => ToolError::Execution(ToolExecutionError::External {
+            tool_name: e.tool_name,
+            message: e.message,
+        }),
+        ErrorType::Cancelled(name) => ToolError::Cancelled(name),
+        ErrorType::Timeout(name) => ToolError::Timeout(name),
+        ErrorType::ApprovalDenied(e) => ToolError::ApprovalDenied {
+            tool_name: e.tool_name,
+            reason: e.reason,
+        },
+        ErrorType::DeniedByUser(name) => ToolError::DeniedByUser(name),
+        ErrorType::DeniedByUserWithReason(e) => ToolError::DeniedByUserWithReason {
+            tool_name: e.tool_name,
+            reason: e.reason,
+        },
+        ErrorType::DeniedByPolicy(name) => ToolError::DeniedByPolicy(name),
+        ErrorType::InternalError(msg) => ToolError::InternalError(msg),
+        ErrorType::Io(e) => ToolError::Execution(ToolExecutionError::External {
+            tool_name: e.tool_name,
+            message: e.message,
+        }),
+    })
+}
+
+fn parsed_command_to_proto(parsed: &ParsedCommand) -> proto::ParsedCommand {
+    proto::ParsedCommand {
+        commands: parsed
+            .commands
+            .iter()
+            .map(|command| proto::SimpleCommand {
+                program: command.program.clone(),
+                args: command.args.clone(),
+            })
+            .collect(),
+        has_pipes: parsed.has_pipes,
+        has_redirects: parsed.has_redirects,
+    }
+}
+
+fn proto_to_parsed_command(parsed: proto::ParsedCommand) -> ParsedCommand {
+    ParsedCommand {
+        commands: parsed
+            .commands
+            .into_iter()
+            .map(|command| SimpleCommand {
+                program: command.program,
+                args: command.args,
+            })
+            .collect(),
+        has_pipes: parsed.has_pipes,
+        has_redirects: parsed.has_redirects,
+    }
+}
+
+pub fn tool_result_to_proto(result: &ToolResult) -> proto::ToolResult {
+    use proto::tool_result::Result as ProtoResult;
+
+    let proto_result = match result {
+        ToolResult::Search(r) => ProtoResult::Search(search_result_to_proto(r)),
+        ToolResult::FileList(r) => ProtoResult::FileList(file_list_result_to_proto(r)),
+        ToolResult::FileContent(r) => ProtoResult::FileContent(file_content_result_to_proto(r)),
+        ToolResult::Edit(r) => ProtoResult::Edit(edit_result_to_proto(r)),
+        ToolResult::Bash(r) => ProtoResult::Bash(proto::BashResult {
+            stdout: r.stdout.clone(),
+            stderr: r.stderr.clone(),
+            exit_code: r.exit_code,
+            command: r.command.clone(),
+            timed_out: r.timed_out,
+            parsed: r.parsed.as_ref().map(parsed_command_to_proto),
+        }),
+        ToolResult::Glob(r) => ProtoResult::Glob(glob_result_to_proto(r)),
+        ToolResult::TodoRead(r) => ProtoResult::TodoRead(proto::TodoListResult {
+            todos: r.todos.iter().map(todo_item_to_proto).collect(),
+        }),
+        ToolResult::TodoWrite(r) => ProtoResult::TodoWrite(proto::TodoWriteResult {
+            todos: r.todos.iter().map(todo_item_to_proto).collect(),
+            operation: todo_write_file_operation_to_proto(&r.operation) as i32,
+        }),
+        ToolResult::Fetch(r) => ProtoResult::Fetch(proto::FetchResult {
+            url: r.url.clone(),
+            content: r.content.clone(),
+            warnings: r.warnings.clone(),
+        }),
+        ToolResult::Agent(r) => ProtoResult::Agent(proto::AgentResult {
+            content: r.content.clone(),
+            workspace: r.workspace.as_ref().map(agent_workspace_info_to_proto),
+            session_id: r.session_id.clone().unwrap_or_default(),
+        }),
+        ToolResult::GitStatus(r) => ProtoResult::GitStatus(proto::GitStatusResult {
+            content: r.content.clone(),
+        }),
+        ToolResult::Memory(r) => ProtoResult::Memory(proto::MemoryResult {
+            entries: r.entries.iter().map(memory_entry_to_proto).collect(),
+        }),
+        ToolResult::External(r) => ProtoResult::External(proto::ExternalResult {
+            tool_name: r.tool_name.clone(),
+            payload: r.payload.clone(),
+        }),
+        ToolResult::Error(e) => ProtoResult::Error(tool_error_to_proto(e)),
+    };
+
+    proto::ToolResult {
+        version: TOOL_RESULT_VERSION,
+        result: Some(proto_result),
+    }
+}
+
+/// Converts a wire `ToolResult` back into the core type.
+///
+/// A missing variant from a newer sender is reported as
+/// [`ToolResultConversionError::UnsupportedVersion`] rather than a generic
+/// missing field, so version skew is easy to diagnose.
+pub fn proto_to_tool_result(
+    result: proto::ToolResult,
+) -> Result<ToolResult, ToolResultConversionError> {
+    use proto::tool_result::Result as ProtoResult;
+
+    let version = result.version;
+    let result = result.result.ok_or_else(|| {
+        if version > TOOL_RESULT_VERSION {
+            ToolResultConversionError::UnsupportedVersion { version }
+        } else {
+            ToolResultConversionError::MissingField {
+                field: "tool_result.result".to_string(),
+            }
+        }
+    })?;
+
+    Ok(match result {
+        ProtoResult::Search(r) => ToolResult::Search(proto_to_search_result(r)),
+        ProtoResult::FileList(r) => ToolResult::FileList(proto_to_file_list_result(r)),
+        ProtoResult::FileContent(r) => ToolResult::FileContent(proto_to_file_content_result(r)),
+        ProtoResult::Edit(r) => ToolResult::Edit(proto_to_edit_result(r)),
+        ProtoResult::Bash(r) => ToolResult::Bash(BashResult {
+            stdout: r.stdout,
+            stderr: r.stderr,
+            exit_code: r.exit_code,
+            command: r.command,
+            timed_out: r.timed_out,
+            parsed: r.parsed.map(proto_to_parsed_command),
+        }),
+        ProtoResult::Glob(r) => ToolResult::Glob(proto_to_glob_result(r)),
+        ProtoResult::TodoRead(r) => ToolResult::TodoRead(TodoListResult {
+            todos: r.todos.into_iter().map(proto_to_todo_item).collect(),
+        }),
+        ProtoResult::TodoWrite(r) => ToolResult::TodoWrite(TodoWriteResult {
+            todos: r.todos.into_iter().map(proto_to_todo_item).collect(),
+            operation: proto_to_todo_write_file_operation(
+                proto::TodoWriteFileOperation::try_from(r.operation)
+                    .unwrap_or(proto::TodoWriteFileOperation::OperationUnset),
+            ),
+        }),
+        ProtoResult::Fetch(r) => ToolResult::Fetch(FetchResult {
+            url: r.url,
+            content: r.content,
+            warnings: r.warnings,
+        }),
+        ProtoResult::Agent(r) => ToolResult::Agent(AgentResult {
+            content: r.content,
+            session_id: non_empty(r.session_id),
+            workspace: r.workspace.map(proto_to_agent_workspace_info),
+        }),
+        ProtoResult::GitStatus(r) => ToolResult::GitStatus(GitStatusResult { content: r.content }),
+        ProtoResult::Memory(r) => ToolResult::Memory(MemoryResult {
+            entries: r.entries.into_iter().map(proto_to_memory_entry).collect(),
+        }),
+        ProtoResult::External(r) => ToolResult::External(ExternalResult {
+            tool_name: r.tool_name,
+            payload: r.payload,
+        }),
+        ProtoResult::Error(e) => ToolResult::Error(proto_to_tool_error(e)?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Exhaustive on purpose: adding a `ToolResult` variant fails to compile here
+    /// until it is given an index, a sample, and a strategy below.
+    fn variant_index(result: &ToolResult) -> usize {
+        match result {
+            ToolResult::Search(_) => 0,
+            ToolResult::FileList(_) => 1,
+            ToolResult::FileContent(_) => 2,
+            ToolResult::Edit(_) => 3,
+            ToolResult::Bash(_) => 4,
+            ToolResult::Glob(_) => 5,
+            ToolResult::TodoRead(_) => 6,
+            ToolResult::TodoWrite(_) => 7,
+            ToolResult::Fetch(_) => 8,
+            ToolResult::Agent(_) => 9,
+            ToolResult::GitStatus(_) => 10,
+            ToolResult::Memory(_) => 11,
+            ToolResult::External(_) => 12,
+            ToolResult::Error(_) => 13,
+        }
+    }
+
+    const VARIANT_COUNT: usize = 14;
+
+    fn assert_round_trips(result: &ToolResult) -> Result<(), TestCaseError> {
+        let proto = tool_result_to_proto(result);
+        prop_assert_eq!(proto.version, TOOL_RESULT_VERSION);
+
+        let round_tripped = proto_to_tool_result(proto)
+            .map_err(|e| TestCaseError::fail(format!("conversion failed: {e}")))?;
+        prop_assert_eq!(variant_index(result), variant_index(&round_tripped));
+
+        let original =
+            serde_json::to_value(result).map_err(|e| TestCaseError::fail(e.to_string()))?;
+        let round_tripped =
+            serde_json::to_value(&round_tripped).map_err(|e| TestCaseError::fail(e.to_string()))?;
+        prop_assert_eq!(original, round_tripped);
+        Ok(())
+    }
+
+    fn arb_search_result() -> impl Strategy<Value = SearchResult> {
+        (
+            prop::collection::vec(
+                (
+                    ".*",
+                    any::<u32>(),
+                    ".*",
+                    prop::option::of((any::<u32>(), any::<u32>())),
+                ),
+                0..4,
+            ),
+            any::<u32>(),
+            any::<bool>(),
+        )
+            .prop_map(
+                |(matches, total_files_searched, search_completed)| SearchResult {
+                    matches: matches
+                        .into_iter()
+                        .map(|(file_path, line, line_content, range)| SearchMatch {
+                            file_path,
+                            line_number: line as usize,
+                            line_content,
+                            column_range: range.map(|(s, e)| (s as usize, e as usize)),
+                        })
+                        .collect(),
+                    total_files_searched: total_files_searched as usize,
+                    search_completed,
+                    warnings: Vec::new(),
+                },
+            )
+    }
+
+    fn arb_file_list_result() -> impl Strategy<Value = FileListResult> {
+        (
+            prop::collection::vec(
+                (
+                    ".*",
+                    any::<bool>(),
+                    prop::option::of(any::<u64>()),
+                    prop::option::of(".*"),
+                ),
+                0..4,
+            ),
+            ".*",
+            0..10_000usize,
+            any::<bool>(),
+        )
+            .prop_map(|(entries, base_path, omitted, truncated)| {
+                let entries: Vec<FileEntry> = entries
+                    .into_iter()
+                    .map(|(path, is_directory, size, permissions)| FileEntry {
+                        path,
+                        is_directory,
+                        size,
+                        permissions,
+                    })
+                    .collect();
+                let total_entries = entries.len() + omitted;
+                FileListResult {
+                    entries,
+                    base_path,
+                    total_entries,
+                    truncated,
+                    warnings: Vec::new(),
+                }
+            })
+    }
+
+    fn arb_todo_item() -> impl Strategy<Value = TodoItem> {
+        (".*", ".*", 0..3usize, 0..3usize).prop_map(|(id, content, status, priority)| TodoItem {
+            id,
+            content,
+            status: [
+                TodoStatus::Pending,
+                TodoStatus::InProgress,
+                TodoStatus::Completed,
+            ][status]
+                .clone(),
+            priority: [TodoPriority::High, TodoPriority::Medium, TodoPriority::Low][priority]
+                .clone(),
+        })
+    }
+
+    fn arb_agent_workspace() -> impl Strategy<Value = Option<AgentWorkspaceInfo>> {
+        prop::option::of(
+            (
+                prop::option::of("[a-z0-9]+"),
+                prop::option::of((".*", ".*", ".*", prop::option::of("[a-z0-9]+"))),
+            )
+                .prop_map(|(workspace_id, revision)| AgentWorkspaceInfo {
+                    workspace_id,
+                    revision: revision.map(|(vcs_kind, revision_id, summary, change_id)| {
+                        AgentWorkspaceRevision {
+                            vcs_kind,
+                            revision_id,
+                            summary,
+                            change_id,
+                        }
+                    }),
+                }),
+        )
+    }
+
+    /// Errors whose wire form is lossless; typed execution errors deliberately
+    /// come back as `External`, which is covered by `execution_errors_keep_message`.
+    fn arb_tool_error() -> impl Strategy<Value = ToolError> {
+        prop_oneof![
+            ".*".prop_map(ToolError::UnknownTool),
+            (".*", ".*")
+                .prop_map(|(tool_name, message)| ToolError::InvalidParams { tool_name, message }),
+            (".*", ".*").prop_map(|(tool_name, message)| ToolError::Execution(
+                ToolExecutionError::External { tool_name, message }
+            )),
+            ".*".prop_map(ToolError::Cancelled),
+            ".*".prop_map(ToolError::Timeout),
+            (".*", proptest::option::of(".*")).prop_map(|(tool_name, reason)| {
+                ToolError::ApprovalDenied { tool_name, reason }
+            }),
+            ".*".prop_map(ToolError::DeniedByUser),
+            (".*", ".*").prop_map(|(tool_name, reason)| ToolError::DeniedByUserWithReason {
+                tool_name,
+                reason
+            }),
+            ".*".prop_map(ToolError::DeniedByPolicy),
+            ".*".prop_map(ToolError::InternalError),
+        ]
+    }
+
+    fn arb_tool_result() -> impl Strategy<Value = ToolResult> {
+        prop_oneof![
+            arb_search_result().prop_map(ToolResult::Search),
+            arb_file_list_result().prop_map(ToolResult::FileList),
+            (
+                ".*",
+                ".*",
+                any::<u32>(),
+                any::<bool>(),
+                prop::option::of(".*"),
+                prop::option::of(".*"),
+                prop::option::of(any::<u64>())
+            )
+                .prop_map(
+                    |(
+                        content,
+                        file_path,
+                        line_count,
+                        truncated,
+                        encoding,
+                        binary_kind,
+                        file_size,
+                    )| {
+                        ToolResult::FileContent(FileContentResult {
+                            content,
+                            file_path,
+                            line_count: line_count as usize,
+                            truncated,
+                            encoding,
+                            binary_kind,
+                            file_size,
+                            warnings: Vec::new(),
+                        })
+                    }
+                ),
+            (
+                ".*",
+                any::<u32>(),
+                any::<bool>(),
+                prop::option::of(".*"),
+                prop::option::of(".*"),
+                prop::collection::vec((any::<u32>(), ".*"), 0..3)
+            )
+                .prop_map(
+                    |(file_path, changes_made, file_created, old_content, new_content, skipped)| {
+                        ToolResult::Edit(EditResult {
+                            file_path,
+                            changes_made: changes_made as usize,
+                            file_created,
+                            old_content,
+                            new_content,
+                            skipped_edits: skipped
+                                .into_iter()
+                                .map(|(edit_index, reason)| SkippedEdit {
+                                    edit_index: edit_index as usize,
+                                    reason,
+                                })
+                                .collect(),
+                        })
+                    }
+                ),
+            (".*", ".*", any::<i32>(), ".*", any::<bool>()).prop_map(
+                |(stdout, stderr, exit_code, command, timed_out)| {
+                    ToolResult::Bash(BashResult {
+                        stdout,
+                        stderr,
+                        exit_code,
+                        parsed: ParsedCommand::parse(&command),
+                        command,
+                        timed_out,
+                    })
+                }
+            ),
+            (prop::collection::vec(".*", 0..4), ".*").prop_map(|(matches, pattern)| {
+                ToolResult::Glob(GlobResult {
+                    matches,
+                    pattern,
+                    warnings: Vec::new(),
+                })
+            }),
+            prop::collection::vec(arb_todo_item(), 0..4)
+                .prop_map(|todos| ToolResult::TodoRead(TodoListResult { todos })),
+            (prop::collection::vec(arb_todo_item(), 0..4), any::<bool>()).prop_map(
+                |(todos, modified)| {
+                    ToolResult::TodoWrite(TodoWriteResult {
+                        todos,
+                        operation: if modified {
+                            TodoWriteFileOperation::Modified
+                        } else {
+                            TodoWriteFileOperation::Created
+                        },
+                    })
+                }
+            ),
+            (".*", ".*").prop_map(|(url, content)| ToolResult::Fetch(FetchResult {
+                url,
+                content,
+                warnings: Vec::new(),
+            })),
+            (".*", arb_agent_workspace(), prop::option::of("[a-z0-9]+")).prop_map(
+                |(content, workspace, session_id)| {
+                    ToolResult::Agent(AgentResult {
+                        content,
+                        workspace,
+                        session_id,
+                    })
+                }
+            ),
+            ".*".prop_map(|content| ToolResult::GitStatus(GitStatusResult { content })),
+            prop::collection::vec((".*", ".*"), 0..4).prop_map(|entries| {
+                ToolResult::Memory(MemoryResult {
+                    entries: entries
+                        .into_iter()
+                        .map(|(key, content)| MemoryEntry { key, content })
+                        .collect(),
+                })
+            }),
+            (".*", ".*").prop_map(|(tool_name, payload)| {
+                ToolResult::External(ExternalResult { tool_name, payload })
+            }),
+            arb_tool_error().prop_map(ToolResult::Error),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn prop_tool_result_roundtrip(result in arb_tool_result()) {
+            assert_round_trips(&result)?;
+        }
+    }
+
+    #[test]
+    fn strategy_covers_every_variant() {
+        use proptest::strategy::ValueTree;
+        use proptest::test_runner::TestRunner;
+
+        let strategy = arb_tool_result();
+        let mut runner = TestRunner::deterministic();
+        let mut seen = [false; VARIANT_COUNT];
+        for _ in 0..2_000 {
+            let value = strategy.new_tree(&mut runner).unwrap().current();
+            seen[variant_index(&value)] = true;
+        }
+
+        assert!(seen.iter().all(|s| *s), "unsampled variants: {seen:?}");
+    }
+
+    #[test]
+    fn execution_errors_keep_message() {
+        let error = ToolError::Execution(ToolExecutionError::Bash(
+            steer_tools::tools::bash::BashError::DisallowedCommand {
+                command: "curl".to_string(),
+            },
+        ));
+
+        let round_tripped = proto_to_tool_error(tool_error_to_proto(&error)).unwrap();
+
+        match round_tripped {
+            ToolError::Execution(ToolExecutionError::External { tool_name, message }) => {
+                assert_eq!(tool_name, "bash");
+                assert_eq!(message, "command is disallowed: curl");
+            }
+            other => panic!("expected external execution error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn missing_variant_from_newer_sender_reports_version() {
+        let err = proto_to_tool_result(proto::ToolResult {
+            version: TOOL_RESULT_VERSION + 1,
+            result: None,
+        })
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ToolResultConversionError::UnsupportedVersion {
+                version: TOOL_RESULT_VERSION + 1
+            }
+        );
+
+        let err = proto_to_tool_result(proto::ToolResult {
+            version: 0,
+            result: None,
+        })
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ToolResultConversionError::MissingField { .. }
+        ));
+    }
+}
@@ -1,6 +1,8 @@
 // Generated proto code contains #[allow] attributes that we can't change
 #![allow(clippy::allow_attributes)]
 
+pub mod convert;
+
 pub mod common {
     pub mod v1 {
         #![allow(
@@ -46,5 +48,29 @@ pub mod remote_workspace {
             unused_variables
         )]
         tonic::include_proto!("steer.remote_workspace.v1");
+
+        /// Version of the remote workspace wire protocol.
+        ///
+        /// Bump this whenever an RPC is added, removed, or changes meaning so clients
+        /// can detect version skew via `GetCapabilities`.
+        pub const PROTOCOL_VERSION: u32 = 3;
+
+        /// Workspace operations exposed by protocol version [`PROTOCOL_VERSION`].
+        pub const SUPPORTED_OPERATIONS: &[&str] = &[
+            "environment_info",
+            "list_files",
+            "search_files",
+            "read_file",
+            "list_directory",
+            "list_directory_paging",
+            "glob",
+            "grep",
+            "astgrep",
+            "apply_edits",
+            "apply_edits_partial",
+            "apply_edit_batch",
+            "preview_edit",
+            "write_file",
+        ];
     }
 }
@@ -0,0 +1,40 @@
+"""Run the Steer coding agent from Python.
+
+    import asyncio, steer
+
+    result = asyncio.run(steer.run_once_ephemeral("What does this repo do?"))
+    print(result.message.text, result.usage.total_tokens)
+
+To watch a turn as it happens, use an ``Agent``:
+
+    agent = await steer.Agent.create(model="sonnet", workspace=".")
+    async for event in await agent.send("Fix the failing test"):
+        if event.kind == "approval_requested":
+            await agent.approve(event.request_id, approved=False)
+"""
+
+from ._steer import (
+    Agent,
+    Event,
+    Message,
+    RunResult,
+    ToolCall,
+    ToolResult,
+    Turn,
+    Usage,
+    run_once_ephemeral,
+    run_once_in_session,
+)
+
+__all__ = [
+    "Agent",
+    "Event",
+    "Message",
+    "RunResult",
+    "ToolCall",
+    "ToolResult",
+    "Turn",
+    "Usage",
+    "run_once_ephemeral",
+    "run_once_in_session",
+]
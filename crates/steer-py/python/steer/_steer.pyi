@@ -0,0 +1,71 @@
+from typing import Any, AsyncIterator, Optional, Sequence
+
+class Usage:
+    input_tokens: int
+    output_tokens: int
+    total_tokens: int
+    estimated_cost_usd: Optional[float]
+
+class ToolCall:
+    id: str
+    name: str
+    arguments_json: str
+    @property
+    def arguments(self) -> Any: ...
+
+class ToolResult:
+    tool_call_id: str
+    name: str
+    output: Optional[str]
+    error: Optional[str]
+
+class Message:
+    id: str
+    role: str
+    text: str
+    tool_calls: list[ToolCall]
+
+class RunResult:
+    session_id: str
+    message: Message
+    usage: Usage
+
+class Event:
+    kind: str
+    text: Optional[str]
+    tool_call: Optional[ToolCall]
+    tool_result: Optional[ToolResult]
+    request_id: Optional[str]
+
+class Turn:
+    def __aiter__(self) -> AsyncIterator[Event]: ...
+    async def __anext__(self) -> Event: ...
+
+class Agent:
+    session_id: str
+    @staticmethod
+    async def create(
+        model: Optional[str] = None,
+        workspace: Optional[str] = None,
+        catalogs: Optional[Sequence[str]] = None,
+        preapproved_tools: Optional[Sequence[str]] = None,
+    ) -> Agent: ...
+    async def send(self, prompt: str) -> Turn: ...
+    async def approve(
+        self, request_id: str, approved: bool, reason: Optional[str] = None
+    ) -> None: ...
+    async def cancel(self) -> None: ...
+    async def shutdown(self) -> None: ...
+
+async def run_once_ephemeral(
+    prompt: str,
+    model: Optional[str] = None,
+    session_config: Optional[str] = None,
+    catalogs: Optional[Sequence[str]] = None,
+) -> RunResult: ...
+async def run_once_in_session(
+    session_id: str,
+    prompt: str,
+    model: Optional[str] = None,
+    catalogs: Optional[Sequence[str]] = None,
+) -> RunResult: ...
@@ -0,0 +1,13 @@
+//! Python bindings for running Steer headlessly.
+//!
+//! The Rust side of the `steer` Python package. Build it with `maturin`
+//! (see `pyproject.toml`); plain `cargo build` compiles only the data types
+//! and runners, without PyO3.
+
+#[cfg(feature = "python")]
+mod python;
+pub mod runs;
+pub mod types;
+
+pub use runs::{run_once_ephemeral, run_once_in_session};
+pub use types::{Event, Message, RunResult, ToolCall, ToolResult, Usage};
@@ -0,0 +1,250 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use futures::StreamExt;
+use pyo3::exceptions::{PyRuntimeError, PyStopAsyncIteration, PyValueError};
+use pyo3::prelude::*;
+use pyo3_async_runtimes::tokio::future_into_py;
+use steer_core::embedded::{self, AgentTurn, ApprovalDecision, RequestId, ToolApprovalPolicy};
+use tokio::sync::{Mutex, RwLock};
+
+use crate::types::{Event, Message, RunResult, ToolCall, ToolResult, Usage};
+
+fn runtime_error(e: impl std::fmt::Display) -> PyErr {
+    PyRuntimeError::new_err(e.to_string())
+}
+
+/// Run `prompt` in a new session and return the final answer.
+#[pyfunction]
+#[pyo3(signature = (prompt, model=None, session_config=None, catalogs=None))]
+fn run_once_ephemeral(
+    py: Python<'_>,
+    prompt: String,
+    model: Option<String>,
+    session_config: Option<PathBuf>,
+    catalogs: Option<Vec<String>>,
+) -> PyResult<Bound<'_, PyAny>> {
+    future_into_py(py, async move {
+        crate::run_once_ephemeral(prompt, model, session_config, catalogs.unwrap_or_default())
+            .await
+            .map_err(runtime_error)
+    })
+}
+
+/// Continue the stored session `session_id` with `prompt`.
+#[pyfunction]
+#[pyo3(signature = (session_id, prompt, model=None, catalogs=None))]
+fn run_once_in_session(
+    py: Python<'_>,
+    session_id: String,
+    prompt: String,
+    model: Option<String>,
+    catalogs: Option<Vec<String>>,
+) -> PyResult<Bound<'_, PyAny>> {
+    future_into_py(py, async move {
+        crate::run_once_in_session(&session_id, prompt, model, catalogs.unwrap_or_default())
+            .await
+            .map_err(runtime_error)
+    })
+}
+
+/// An in-memory agent session whose turns can be streamed event by event.
+#[pyclass(module = "steer")]
+struct Agent {
+    #[pyo3(get)]
+    session_id: String,
+    inner: Arc<RwLock<Option<embedded::Agent>>>,
+}
+
+#[pymethods]
+impl Agent {
+    #[staticmethod]
+    #[pyo3(signature = (model=None, workspace=None, catalogs=None, preapproved_tools=None))]
+    fn create(
+        py: Python<'_>,
+        model: Option<String>,
+        workspace: Option<PathBuf>,
+        catalogs: Option<Vec<String>>,
+        preapproved_tools: Option<Vec<String>>,
+    ) -> PyResult<Bound<'_, PyAny>> {
+        future_into_py(py, async move {
+            let mut builder = embedded::Agent::builder();
+            if let Some(model) = model {
+                builder = builder.model(model);
+            }
+            if let Some(workspace) = workspace {
+                builder = builder.workspace_path(workspace);
+            }
+            for catalog in catalogs.unwrap_or_default() {
+                builder = builder.catalog(catalog);
+            }
+            if let Some(tools) = preapproved_tools {
+                let mut policy = ToolApprovalPolicy::default();
+                policy.preapproved.tools.extend(tools);
+                builder = builder.tool_policy(policy);
+            }
+
+            let agent = builder.build().await.map_err(runtime_error)?;
+            Ok(Agent {
+                session_id: agent.session_id().to_string(),
+                inner: Arc::new(RwLock::new(Some(agent))),
+            })
+        })
+    }
+
+    /// Send a message; iterate the returned turn with `async for`.
+    fn send<'py>(&self, py: Python<'py>, prompt: String) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
+            let guard = inner.read().await;
+            let agent = guard.as_ref().ok_or_else(shut_down)?;
+            let turn = agent.send_message(prompt).await.map_err(runtime_error)?;
+            Ok(Turn {
+                events: Arc::new(Mutex::new(Some(turn))),
+            })
+        })
+    }
+
+    /// Answer an `approval_requested` event.
+    #[pyo3(signature = (request_id, approved, reason=None))]
+    fn approve<'py>(
+        &self,
+        py: Python<'py>,
+        request_id: &str,
+        approved: bool,
+        reason: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let request_id = uuid::Uuid::parse_str(request_id)
+            .map(RequestId::from_uuid)
+            .map_err(|e| PyValueError::new_err(format!("Invalid request id: {e}")))?;
+        let decision = match (approved, reason) {
+            (true, _) => ApprovalDecision::Approved,
+            (false, Some(reason)) => ApprovalDecision::DeniedWithReason(reason),
+            (false, None) => ApprovalDecision::Denied,
+        };
+
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
+            let guard = inner.read().await;
+            let agent = guard.as_ref().ok_or_else(shut_down)?;
+            agent
+                .approve_tool(request_id, decision)
+                .await
+                .map_err(runtime_error)
+        })
+    }
+
+    /// Cancel the running turn, if any.
+    fn cancel<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
+            let guard = inner.read().await;
+            let agent = guard.as_ref().ok_or_else(shut_down)?;
+            agent.cancel().await.map_err(runtime_error)
+        })
+    }
+
+    fn shutdown<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
+            if let Some(agent) = inner.write().await.take() {
+                agent.shutdown().await;
+            }
+            Ok(())
+        })
+    }
+}
+
+fn shut_down() -> PyErr {
+    PyRuntimeError::new_err("agent has been shut down")
+}
+
+/// Events of one turn, as an async iterator of `Event`.
+#[pyclass(module = "steer")]
+struct Turn {
+    events: Arc<Mutex<Option<AgentTurn>>>,
+}
+
+#[pymethods]
+impl Turn {
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let events = self.events.clone();
+        future_into_py(py, async move {
+            let mut guard = events.lock().await;
+            while let Some(turn) = guard.as_mut() {
+                let Some(event) = turn.next().await else {
+                    break;
+                };
+                if let Some(event) = Event::from_agent_event(event) {
+                    if event.is_last() {
+                        *guard = None;
+                    }
+                    return Ok(event);
+                }
+            }
+            *guard = None;
+            Err(PyStopAsyncIteration::new_err(()))
+        })
+    }
+}
+
+#[pymethods]
+impl ToolCall {
+    /// The arguments decoded from JSON.
+    #[getter]
+    fn arguments<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        py.import("json")?
+            .call_method1("loads", (self.arguments_json.as_str(),))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ToolCall(name={:?}, arguments={})",
+            self.name, self.arguments_json
+        )
+    }
+}
+
+#[pymethods]
+impl Message {
+    fn __repr__(&self) -> String {
+        format!("Message(role={:?}, text={:?})", self.role, self.text)
+    }
+}
+
+#[pymethods]
+impl Usage {
+    fn __repr__(&self) -> String {
+        format!(
+            "Usage(input_tokens={}, output_tokens={}, total_tokens={})",
+            self.input_tokens, self.output_tokens, self.total_tokens
+        )
+    }
+}
+
+#[pymethods]
+impl Event {
+    fn __repr__(&self) -> String {
+        format!("Event(kind={:?})", self.kind)
+    }
+}
+
+#[pymodule]
+#[pyo3(name = "_steer")]
+fn steer_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(run_once_ephemeral, m)?)?;
+    m.add_function(wrap_pyfunction!(run_once_in_session, m)?)?;
+    m.add_class::<Agent>()?;
+    m.add_class::<Turn>()?;
+    m.add_class::<Event>()?;
+    m.add_class::<Message>()?;
+    m.add_class::<RunResult>()?;
+    m.add_class::<ToolCall>()?;
+    m.add_class::<ToolResult>()?;
+    m.add_class::<Usage>()?;
+    Ok(())
+}
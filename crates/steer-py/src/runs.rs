@@ -0,0 +1,69 @@
+use std::path::PathBuf;
+
+use eyre::{Result, eyre};
+use steer::model_resolver::resolve_model_selection;
+use steer::session_config::SessionConfigLoader;
+use steer_core::app::domain::types::SessionId;
+use steer_core::model_registry::ModelRegistry;
+use steer_core::runners::RunOnceResult;
+
+use crate::types::RunResult;
+
+/// Run `prompt` in a new session, as `steer headless` does. Tools outside
+/// the session config's approval policy are denied.
+pub async fn run_once_ephemeral(
+    prompt: String,
+    model: Option<String>,
+    session_config: Option<PathBuf>,
+    catalogs: Vec<String>,
+) -> Result<RunResult> {
+    let (runtime, model) = build_runtime(model, &catalogs).await?;
+    let config = SessionConfigLoader::new(model.clone(), session_config)
+        .load()
+        .await?;
+
+    let result = steer::run_once_new_session(&runtime.handle, config, prompt, model).await;
+    runtime.shutdown().await;
+    to_run_result(&result?, &catalogs)
+}
+
+/// Continue a stored session with `prompt`.
+pub async fn run_once_in_session(
+    session_id: &str,
+    prompt: String,
+    model: Option<String>,
+    catalogs: Vec<String>,
+) -> Result<RunResult> {
+    let session_id =
+        SessionId::parse(session_id).ok_or_else(|| eyre!("Invalid session ID: {session_id}"))?;
+    let (runtime, model) = build_runtime(model, &catalogs).await?;
+
+    let result = steer::run_once_in_session(&runtime.handle, session_id, prompt, model).await;
+    runtime.shutdown().await;
+    to_run_result(&result?, &catalogs)
+}
+
+async fn build_runtime(
+    model: Option<String>,
+    catalogs: &[String],
+) -> Result<(
+    steer_core::app::domain::runtime::RuntimeService,
+    steer_core::config::model::ModelId,
+)> {
+    let model = model.unwrap_or_else(|| {
+        resolve_model_selection(None, catalogs)
+            .default_model
+            .to_string()
+    });
+    steer::RuntimeBuilder::new(model)
+        .with_catalogs(catalogs.to_vec())
+        .build()
+        .await
+}
+
+fn to_run_result(result: &RunOnceResult, catalogs: &[String]) -> Result<RunResult> {
+    let registry =
+        ModelRegistry::load(catalogs).map_err(|e| eyre!("Failed to load model catalog: {}", e))?;
+    let cost = result.usage.estimated_cost_usd(&registry);
+    Ok(RunResult::new(result, cost))
+}
@@ -0,0 +1,255 @@
+//! Plain data handed to Python. Each type becomes a frozen Python class
+//! with read-only attributes when the `python` feature is on.
+
+#[cfg(feature = "python")]
+use pyo3::pyclass;
+
+use steer_core::api::provider::TokenUsage;
+use steer_core::app::conversation::{AssistantContent, Message as CoreMessage, MessageData};
+use steer_core::embedded::AgentEvent;
+use steer_core::runners::RunOnceResult;
+
+#[cfg_attr(feature = "python", pyclass(frozen, get_all, module = "steer"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Usage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    pub total_tokens: u32,
+    /// `None` when a model used has no pricing in the catalog.
+    pub estimated_cost_usd: Option<f64>,
+}
+
+impl Usage {
+    pub fn new(usage: TokenUsage, estimated_cost_usd: Option<f64>) -> Self {
+        Self {
+            input_tokens: usage.input_tokens,
+            output_tokens: usage.output_tokens,
+            total_tokens: usage.total_tokens,
+            estimated_cost_usd,
+        }
+    }
+}
+
+#[cfg_attr(feature = "python", pyclass(frozen, get_all, module = "steer"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolCall {
+    pub id: String,
+    pub name: String,
+    /// Arguments as JSON; Python also gets them decoded as `arguments`.
+    pub arguments_json: String,
+}
+
+impl From<&steer_core::embedded::ToolCall> for ToolCall {
+    fn from(tool_call: &steer_core::embedded::ToolCall) -> Self {
+        Self {
+            id: tool_call.id.clone(),
+            name: tool_call.name.clone(),
+            arguments_json: tool_call.parameters.to_string(),
+        }
+    }
+}
+
+#[cfg_attr(feature = "python", pyclass(frozen, get_all, module = "steer"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolResult {
+    pub tool_call_id: String,
+    pub name: String,
+    /// What the model was shown, when the tool succeeded.
+    pub output: Option<String>,
+    pub error: Option<String>,
+}
+
+#[cfg_attr(feature = "python", pyclass(frozen, get_all, module = "steer"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub id: String,
+    /// `"user"`, `"assistant"` or `"tool"`.
+    pub role: String,
+    pub text: String,
+    pub tool_calls: Vec<ToolCall>,
+}
+
+impl From<&CoreMessage> for Message {
+    fn from(message: &CoreMessage) -> Self {
+        let tool_calls = match &message.data {
+            MessageData::Assistant { content } => content
+                .iter()
+                .filter_map(|item| match item {
+                    AssistantContent::ToolCall { tool_call, .. } => Some(tool_call.into()),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Self {
+            id: message.id().to_string(),
+            role: message.role().to_string().to_lowercase(),
+            text: message.extract_text(),
+            tool_calls,
+        }
+    }
+}
+
+#[cfg_attr(feature = "python", pyclass(frozen, get_all, module = "steer"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunResult {
+    pub session_id: String,
+    /// The assistant's final message.
+    pub message: Message,
+    pub usage: Usage,
+}
+
+impl RunResult {
+    pub fn new(result: &RunOnceResult, estimated_cost_usd: Option<f64>) -> Self {
+        Self {
+            session_id: result.session_id.to_string(),
+            message: Message::from(&result.final_message),
+            usage: Usage::new(result.usage.total, estimated_cost_usd),
+        }
+    }
+}
+
+/// One step of an agent turn. `kind` says which of the optional fields are
+/// set:
+///
+/// - `"assistant_message"`: `text`
+/// - `"tool_call_started"`: `tool_call`
+/// - `"tool_call_finished"`: `tool_result`
+/// - `"approval_requested"`: `request_id` and `tool_call`
+/// - `"error"`: `text`
+/// - `"finished"` / `"cancelled"`: nothing; always the last event
+#[cfg_attr(feature = "python", pyclass(frozen, get_all, module = "steer"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub kind: String,
+    pub text: Option<String>,
+    pub tool_call: Option<ToolCall>,
+    pub tool_result: Option<ToolResult>,
+    pub request_id: Option<String>,
+}
+
+impl Event {
+    fn new(kind: &str) -> Self {
+        Self {
+            kind: kind.to_string(),
+            text: None,
+            tool_call: None,
+            tool_result: None,
+            request_id: None,
+        }
+    }
+
+    /// Returns `None` for events this version doesn't know how to show.
+    pub fn from_agent_event(event: AgentEvent) -> Option<Self> {
+        let event = match event {
+            AgentEvent::AssistantMessage { text } => Self {
+                text: Some(text),
+                ..Self::new("assistant_message")
+            },
+            AgentEvent::ToolCallStarted {
+                id,
+                name,
+                parameters,
+            } => Self {
+                tool_call: Some(ToolCall {
+                    id,
+                    name,
+                    arguments_json: parameters.to_string(),
+                }),
+                ..Self::new("tool_call_started")
+            },
+            AgentEvent::ToolCallFinished {
+                id,
+                name,
+                output,
+                error,
+            } => Self {
+                tool_result: Some(ToolResult {
+                    tool_call_id: id,
+                    name,
+                    output,
+                    error,
+                }),
+                ..Self::new("tool_call_finished")
+            },
+            AgentEvent::ApprovalRequested {
+                request_id,
+                tool_call,
+            } => Self {
+                request_id: Some(request_id.to_string()),
+                tool_call: Some(ToolCall::from(&tool_call)),
+                ..Self::new("approval_requested")
+            },
+            AgentEvent::Error { message } => Self {
+                text: Some(message),
+                ..Self::new("error")
+            },
+            AgentEvent::Finished => Self::new("finished"),
+            AgentEvent::Cancelled => Self::new("cancelled"),
+            _ => return None,
+        };
+        Some(event)
+    }
+
+    pub fn is_last(&self) -> bool {
+        matches!(self.kind.as_str(), "finished" | "cancelled")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use steer_core::embedded::RequestId;
+
+    #[test]
+    fn approval_event_carries_request_and_arguments() {
+        let request_id = RequestId::new();
+        let event = Event::from_agent_event(AgentEvent::ApprovalRequested {
+            request_id,
+            tool_call: steer_core::embedded::ToolCall {
+                id: "tc_1".to_string(),
+                name: "bash".to_string(),
+                parameters: serde_json::json!({ "command": "ls" }),
+            },
+        })
+        .unwrap();
+
+        assert_eq!(event.kind, "approval_requested");
+        assert_eq!(event.request_id, Some(request_id.to_string()));
+        let tool_call = event.tool_call.unwrap();
+        assert_eq!(tool_call.name, "bash");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&tool_call.arguments_json).unwrap(),
+            serde_json::json!({ "command": "ls" })
+        );
+    }
+
+    #[test]
+    fn failed_tool_call_becomes_tool_result_with_error() {
+        let event = Event::from_agent_event(AgentEvent::ToolCallFinished {
+            id: "tc_1".to_string(),
+            name: "read_file".to_string(),
+            output: None,
+            error: Some("not found".to_string()),
+        })
+        .unwrap();
+
+        assert_eq!(event.kind, "tool_call_finished");
+        assert_eq!(
+            event.tool_result,
+            Some(ToolResult {
+                tool_call_id: "tc_1".to_string(),
+                name: "read_file".to_string(),
+                output: None,
+                error: Some("not found".to_string()),
+            })
+        );
+        assert!(!event.is_last());
+        assert!(
+            Event::from_agent_event(AgentEvent::Finished)
+                .unwrap()
+                .is_last()
+        );
+    }
+}
@@ -25,6 +25,10 @@ struct Args {
     #[arg(short, long)]
     working_dir: Option<PathBuf>,
 
+    /// Allow file operations on paths outside the working directory
+    #[arg(long)]
+    allow_outside_workspace: bool,
+
     /// Enable debug logging
     #[arg(short, long)]
     debug: bool,
@@ -55,7 +59,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Create the remote backend service
     let remote_workspace_service = RemoteWorkspaceService::new(working_dir)
         .await
-        .map_err(|e| format!("Failed to create remote backend service: {e}"))?;
+        .map_err(|e| format!("Failed to create remote backend service: {e}"))?
+        .with_allow_outside_workspace(args.allow_outside_workspace);
 
     info!(
         "Remote backend service created with {} supported tools",
@@ -5,31 +5,39 @@ use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
 
 use steer_workspace::local::LocalWorkspace;
+use steer_workspace::utils::{ensure_within_workspace, normalize_workspace_path};
 use steer_workspace::{
-    EditMatchSelection, VcsInfo, VcsKind, VcsStatus, Workspace, WorkspaceError, WorkspaceOpContext,
+    EditMatchSelection, FileEditStatus, VcsInfo, VcsKind, VcsStatus, Workspace, WorkspaceError,
+    WorkspaceOpContext,
 };
 
 use crate::proto::{
+    ApplyEditBatchRequest as GrpcApplyEditBatchRequest, ApplyEditBatchResponse,
     ApplyEditsRequest as GrpcApplyEditsRequest, AstGrepRequest as GrpcAstGrepRequest,
-    ExecuteToolRequest, ExecuteToolResponse, GetAgentInfoRequest, GetAgentInfoResponse,
-    GetToolApprovalRequirementsRequest, GetToolApprovalRequirementsResponse, GetToolSchemasRequest,
-    GetToolSchemasResponse, GlobRequest as GrpcGlobRequest, GrepRequest as GrpcGrepRequest,
-    HealthRequest, HealthResponse, HealthStatus, ListDirectoryRequest as GrpcListDirectoryRequest,
-    ListFilesRequest, ListFilesResponse, ReadFileRequest as GrpcReadFileRequest,
-    WriteFileRequest as GrpcWriteFileRequest, edit_operation::MatchSelection as GrpcMatchSelection,
+    ExecuteToolRequest, ExecuteToolResponse, FileEditNotApplied,
+    FileEditOutcome as GrpcFileEditOutcome, GetAgentInfoRequest, GetAgentInfoResponse,
+    GetCapabilitiesRequest, GetCapabilitiesResponse, GetToolApprovalRequirementsRequest,
+    GetToolApprovalRequirementsResponse, GetToolSchemasRequest, GetToolSchemasResponse,
+    GlobRequest as GrpcGlobRequest, GrepRequest as GrpcGrepRequest, HealthRequest, HealthResponse,
+    HealthStatus, ListDirectoryRequest as GrpcListDirectoryRequest, ListFilesRequest,
+    ListFilesResponse, ReadFileRequest as GrpcReadFileRequest, SearchFilesRequest,
+    SearchFilesResponse, WriteFileRequest as GrpcWriteFileRequest,
+    edit_operation::MatchSelection as GrpcMatchSelection,
+    file_edit_outcome::Status as GrpcFileEditStatus,
     remote_workspace_service_server::RemoteWorkspaceService as RemoteWorkspaceServiceServer,
 };
 use steer_proto::common::v1::{
-    ColumnRange as ProtoColumnRange, EditResult as ProtoEditResult,
-    FileContentResult as ProtoFileContentResult, FileEntry as ProtoFileEntry,
+    EditResult as ProtoEditResult, FileContentResult as ProtoFileContentResult,
     FileListResult as ProtoFileListResult, GlobResult as ProtoGlobResult,
-    SearchMatch as ProtoSearchMatch, SearchResult as ProtoSearchResult,
+    SearchResult as ProtoSearchResult,
 };
+use steer_proto::convert;
 
 /// Remote workspace service that exposes workspace operations over gRPC.
 pub struct RemoteWorkspaceService {
     workspace: Arc<LocalWorkspace>,
     version: String,
+    allow_outside_workspace: bool,
 }
 
 impl RemoteWorkspaceService {
@@ -39,14 +47,68 @@ impl RemoteWorkspaceService {
         Ok(Self {
             workspace: Arc::new(workspace),
             version: env!("CARGO_PKG_VERSION").to_string(),
+            allow_outside_workspace: false,
         })
     }
 
+    /// Let file operations reach paths outside the working directory.
+    ///
+    /// Clients cannot see this side's filesystem, so the workspace boundary for
+    /// remote sessions is enforced here rather than by the client.
+    pub fn with_allow_outside_workspace(mut self, allow: bool) -> Self {
+        self.allow_outside_workspace = allow;
+        self
+    }
+
+    /// Rejects a path argument that resolves outside the working directory,
+    /// unless the boundary has been lifted.
+    fn check_path(&self, path: &str) -> Result<(), Status> {
+        if self.allow_outside_workspace {
+            return Ok(());
+        }
+        let root = self.workspace.working_directory();
+        normalize_workspace_path(Some(root), path)
+            .and_then(|resolved| ensure_within_workspace(root, &resolved))
+            .map_err(|e| Status::permission_denied(e.to_string()))
+    }
+
     /// Get the supported tool names for legacy compatibility.
     pub fn get_supported_tools(&self) -> Vec<String> {
         Vec::new()
     }
 
+    fn convert_apply_edits_request(
+        request: GrpcApplyEditsRequest,
+    ) -> steer_workspace::ApplyEditsRequest {
+        let edits = request
+            .edits
+            .into_iter()
+            .map(|edit| {
+                let match_selection = match edit.match_selection {
+                    Some(GrpcMatchSelection::ExactlyOne(_)) => Some(EditMatchSelection::ExactlyOne),
+                    Some(GrpcMatchSelection::First(_)) => Some(EditMatchSelection::First),
+                    Some(GrpcMatchSelection::All(_)) => Some(EditMatchSelection::All),
+                    Some(GrpcMatchSelection::Nth(nth)) => Some(EditMatchSelection::Nth {
+                        match_index: Some(nth.match_index),
+                    }),
+                    None => None,
+                };
+
+                steer_workspace::EditOperation {
+                    old_string: edit.old_string,
+                    new_string: edit.new_string,
+                    match_selection,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        steer_workspace::ApplyEditsRequest {
+            file_path: request.file_path,
+            edits,
+            allow_partial: request.allow_partial,
+        }
+    }
+
     fn convert_vcs_to_proto(info: VcsInfo) -> crate::proto::VcsInfo {
         let kind = match info.kind {
             VcsKind::Git => crate::proto::VcsKind::Git,
@@ -107,20 +169,18 @@ impl RemoteWorkspaceService {
                     })
                     .collect();
 
-                let recent_commits = status
-                    .recent_commits
-                    .into_iter()
-                    .map(|commit| crate::proto::GitCommitSummary {
-                        id: commit.id,
-                        summary: commit.summary,
-                    })
-                    .collect();
+                let upstream = status.upstream.map(|upstream| crate::proto::GitUpstream {
+                    name: upstream.name,
+                    ahead: upstream.ahead,
+                    behind: upstream.behind,
+                });
 
-                crate::proto::vcs_info::Status::GitStatus(crate::proto::GitStatus {
+                crate::proto::vcs_info::Status::GitSummary(crate::proto::GitSummary {
                     head,
                     entries,
-                    recent_commits,
                     error: status.error,
+                    upstream,
+                    omitted_entries: status.omitted_entries,
                 })
             }
             VcsStatus::Jj(status) => {
@@ -177,76 +237,6 @@ impl RemoteWorkspaceService {
             status: Some(status),
         }
     }
-
-    fn search_result_to_proto(search_result: &steer_workspace::SearchResult) -> ProtoSearchResult {
-        let proto_matches = search_result
-            .matches
-            .iter()
-            .map(|m| ProtoSearchMatch {
-                file_path: m.file_path.clone(),
-                line_number: m.line_number as u64,
-                line_content: m.line_content.clone(),
-                column_range: m.column_range.map(|(start, end)| ProtoColumnRange {
-                    start: start as u64,
-                    end: end as u64,
-                }),
-            })
-            .collect();
-
-        ProtoSearchResult {
-            matches: proto_matches,
-            total_files_searched: search_result.total_files_searched as u64,
-            search_completed: search_result.search_completed,
-        }
-    }
-
-    fn file_list_result_to_proto(
-        file_list: &steer_workspace::FileListResult,
-    ) -> ProtoFileListResult {
-        let proto_entries = file_list
-            .entries
-            .iter()
-            .map(|e| ProtoFileEntry {
-                path: e.path.clone(),
-                is_directory: e.is_directory,
-                size: e.size,
-                permissions: e.permissions.clone(),
-            })
-            .collect();
-
-        ProtoFileListResult {
-            entries: proto_entries,
-            base_path: file_list.base_path.clone(),
-        }
-    }
-
-    fn file_content_result_to_proto(
-        file_content: &steer_workspace::FileContentResult,
-    ) -> ProtoFileContentResult {
-        ProtoFileContentResult {
-            content: file_content.content.clone(),
-            file_path: file_content.file_path.clone(),
-            line_count: file_content.line_count as u64,
-            truncated: file_content.truncated,
-        }
-    }
-
-    fn edit_result_to_proto(edit_result: &steer_workspace::EditResult) -> ProtoEditResult {
-        ProtoEditResult {
-            file_path: edit_result.file_path.clone(),
-            changes_made: edit_result.changes_made as u64,
-            file_created: edit_result.file_created,
-            old_content: edit_result.old_content.clone(),
-            new_content: edit_result.new_content.clone(),
-        }
-    }
-
-    fn glob_result_to_proto(glob_result: &steer_workspace::GlobResult) -> ProtoGlobResult {
-        ProtoGlobResult {
-            matches: glob_result.matches.clone(),
-            pattern: glob_result.pattern.clone(),
-        }
-    }
 }
 
 #[tonic::async_trait]
@@ -316,6 +306,21 @@ impl RemoteWorkspaceServiceServer for RemoteWorkspaceService {
         Ok(Response::new(response))
     }
 
+    /// Report protocol version and supported operations
+    async fn get_capabilities(
+        &self,
+        _request: Request<GetCapabilitiesRequest>,
+    ) -> Result<Response<GetCapabilitiesResponse>, Status> {
+        Ok(Response::new(GetCapabilitiesResponse {
+            protocol_version: crate::proto::PROTOCOL_VERSION,
+            server_version: self.version.clone(),
+            supported_operations: crate::proto::SUPPORTED_OPERATIONS
+                .iter()
+                .map(|op| (*op).to_string())
+                .collect(),
+        }))
+    }
+
     /// Get tool approval requirements
     async fn get_tool_approval_requirements(
         &self,
@@ -407,11 +412,36 @@ impl RemoteWorkspaceServiceServer for RemoteWorkspaceService {
         Ok(Response::new(ReceiverStream::new(rx)))
     }
 
+    async fn search_files(
+        &self,
+        request: Request<SearchFilesRequest>,
+    ) -> Result<Response<SearchFilesResponse>, Status> {
+        let req = request.into_inner();
+        let max_results = (req.max_results > 0).then_some(req.max_results as usize);
+
+        let matches = self
+            .workspace
+            .search_files(&req.query, max_results)
+            .await
+            .map_err(|e| Status::internal(format!("SearchFiles failed: {e}")))?;
+
+        Ok(Response::new(SearchFilesResponse {
+            matches: matches
+                .into_iter()
+                .map(|m| crate::proto::FileSearchMatch {
+                    path: m.path,
+                    score: m.score,
+                })
+                .collect(),
+        }))
+    }
+
     async fn read_file(
         &self,
         request: Request<GrpcReadFileRequest>,
     ) -> Result<Response<ProtoFileContentResult>, Status> {
         let req = request.into_inner();
+        self.check_path(&req.file_path)?;
         let cancellation_token = tokio_util::sync::CancellationToken::new();
         let _guard = cancellation_token.clone().drop_guard();
         let context = WorkspaceOpContext::new("read_file", cancellation_token);
@@ -420,6 +450,7 @@ impl RemoteWorkspaceServiceServer for RemoteWorkspaceService {
             offset: req.offset,
             limit: req.limit,
             raw: req.raw,
+            max_bytes: req.max_bytes,
         };
 
         let result = self
@@ -428,7 +459,9 @@ impl RemoteWorkspaceServiceServer for RemoteWorkspaceService {
             .await
             .map_err(|e| Status::internal(format!("ReadFile failed: {e}")))?;
 
-        Ok(Response::new(Self::file_content_result_to_proto(&result)))
+        Ok(Response::new(convert::file_content_result_to_proto(
+            &result,
+        )))
     }
 
     async fn list_directory(
@@ -436,6 +469,7 @@ impl RemoteWorkspaceServiceServer for RemoteWorkspaceService {
         request: Request<GrpcListDirectoryRequest>,
     ) -> Result<Response<ProtoFileListResult>, Status> {
         let req = request.into_inner();
+        self.check_path(&req.path)?;
         let cancellation_token = tokio_util::sync::CancellationToken::new();
         let _guard = cancellation_token.clone().drop_guard();
         let context = WorkspaceOpContext::new("ls", cancellation_token);
@@ -446,6 +480,9 @@ impl RemoteWorkspaceServiceServer for RemoteWorkspaceService {
             } else {
                 Some(req.ignore)
             },
+            offset: req.offset.map(|offset| offset as usize),
+            limit: req.limit.map(|limit| limit as usize),
+            respect_gitignore: req.respect_gitignore,
         };
 
         let result = self
@@ -454,7 +491,7 @@ impl RemoteWorkspaceServiceServer for RemoteWorkspaceService {
             .await
             .map_err(|e| Status::internal(format!("ListDirectory failed: {e}")))?;
 
-        Ok(Response::new(Self::file_list_result_to_proto(&result)))
+        Ok(Response::new(convert::file_list_result_to_proto(&result)))
     }
 
     async fn glob(
@@ -462,6 +499,9 @@ impl RemoteWorkspaceServiceServer for RemoteWorkspaceService {
         request: Request<GrpcGlobRequest>,
     ) -> Result<Response<ProtoGlobResult>, Status> {
         let req = request.into_inner();
+        if let Some(path) = &req.path {
+            self.check_path(path)?;
+        }
         let cancellation_token = tokio_util::sync::CancellationToken::new();
         let _guard = cancellation_token.clone().drop_guard();
         let context = WorkspaceOpContext::new("glob", cancellation_token);
@@ -476,7 +516,7 @@ impl RemoteWorkspaceServiceServer for RemoteWorkspaceService {
             .await
             .map_err(|e| Status::internal(format!("Glob failed: {e}")))?;
 
-        Ok(Response::new(Self::glob_result_to_proto(&result)))
+        Ok(Response::new(convert::glob_result_to_proto(&result)))
     }
 
     async fn grep(
@@ -484,6 +524,9 @@ impl RemoteWorkspaceServiceServer for RemoteWorkspaceService {
         request: Request<GrpcGrepRequest>,
     ) -> Result<Response<ProtoSearchResult>, Status> {
         let req = request.into_inner();
+        if let Some(path) = &req.path {
+            self.check_path(path)?;
+        }
         let cancellation_token = tokio_util::sync::CancellationToken::new();
         let _guard = cancellation_token.clone().drop_guard();
         let context = WorkspaceOpContext::new("grep", cancellation_token);
@@ -499,7 +542,7 @@ impl RemoteWorkspaceServiceServer for RemoteWorkspaceService {
             .await
             .map_err(|e| Status::internal(format!("Grep failed: {e}")))?;
 
-        Ok(Response::new(Self::search_result_to_proto(&result)))
+        Ok(Response::new(convert::search_result_to_proto(&result)))
     }
 
     async fn ast_grep(
@@ -507,6 +550,9 @@ impl RemoteWorkspaceServiceServer for RemoteWorkspaceService {
         request: Request<GrpcAstGrepRequest>,
     ) -> Result<Response<ProtoSearchResult>, Status> {
         let req = request.into_inner();
+        if let Some(path) = &req.path {
+            self.check_path(path)?;
+        }
         let cancellation_token = tokio_util::sync::CancellationToken::new();
         let _guard = cancellation_token.clone().drop_guard();
         let context = WorkspaceOpContext::new("astgrep", cancellation_token);
@@ -524,51 +570,95 @@ impl RemoteWorkspaceServiceServer for RemoteWorkspaceService {
             .await
             .map_err(|e| Status::internal(format!("AstGrep failed: {e}")))?;
 
-        Ok(Response::new(Self::search_result_to_proto(&result)))
+        Ok(Response::new(convert::search_result_to_proto(&result)))
     }
 
     async fn apply_edits(
         &self,
         request: Request<GrpcApplyEditsRequest>,
     ) -> Result<Response<ProtoEditResult>, Status> {
-        let req = request.into_inner();
         let cancellation_token = tokio_util::sync::CancellationToken::new();
         let _guard = cancellation_token.clone().drop_guard();
         let context = WorkspaceOpContext::new("edit", cancellation_token);
-        let edits = req
-            .edits
-            .into_iter()
-            .map(|edit| {
-                let match_selection = match edit.match_selection {
-                    Some(GrpcMatchSelection::ExactlyOne(_)) => Some(EditMatchSelection::ExactlyOne),
-                    Some(GrpcMatchSelection::First(_)) => Some(EditMatchSelection::First),
-                    Some(GrpcMatchSelection::All(_)) => Some(EditMatchSelection::All),
-                    Some(GrpcMatchSelection::Nth(nth)) => Some(EditMatchSelection::Nth {
-                        match_index: Some(nth.match_index),
-                    }),
-                    None => None,
-                };
+        let req = request.into_inner();
+        self.check_path(&req.file_path)?;
+        let params = Self::convert_apply_edits_request(req);
 
-                steer_workspace::EditOperation {
-                    old_string: edit.old_string,
-                    new_string: edit.new_string,
-                    match_selection,
-                }
-            })
-            .collect::<Vec<_>>();
+        let result = self
+            .workspace
+            .apply_edits(params, &context)
+            .await
+            .map_err(|e| Status::internal(format!("ApplyEdits failed: {e}")))?;
 
-        let params = steer_workspace::ApplyEditsRequest {
-            file_path: req.file_path,
-            edits,
+        Ok(Response::new(convert::edit_result_to_proto(&result)))
+    }
+
+    async fn preview_edit(
+        &self,
+        request: Request<GrpcApplyEditsRequest>,
+    ) -> Result<Response<ProtoEditResult>, Status> {
+        let cancellation_token = tokio_util::sync::CancellationToken::new();
+        let _guard = cancellation_token.clone().drop_guard();
+        let context = WorkspaceOpContext::new("preview_edit", cancellation_token);
+        let req = request.into_inner();
+        self.check_path(&req.file_path)?;
+        let params = Self::convert_apply_edits_request(req);
+
+        let result = self
+            .workspace
+            .preview_edit(params, &context)
+            .await
+            .map_err(|e| Status::internal(format!("PreviewEdit failed: {e}")))?;
+
+        Ok(Response::new(convert::edit_result_to_proto(&result)))
+    }
+
+    async fn apply_edit_batch(
+        &self,
+        request: Request<GrpcApplyEditBatchRequest>,
+    ) -> Result<Response<ApplyEditBatchResponse>, Status> {
+        let req = request.into_inner();
+        for file in &req.files {
+            self.check_path(&file.file_path)?;
+        }
+        let cancellation_token = tokio_util::sync::CancellationToken::new();
+        let _guard = cancellation_token.clone().drop_guard();
+        let context = WorkspaceOpContext::new("edit_batch", cancellation_token);
+        let params = steer_workspace::ApplyEditBatchRequest {
+            files: req
+                .files
+                .into_iter()
+                .map(Self::convert_apply_edits_request)
+                .collect(),
         };
 
         let result = self
             .workspace
-            .apply_edits(params, &context)
+            .apply_edit_batch(params, &context)
             .await
-            .map_err(|e| Status::internal(format!("ApplyEdits failed: {e}")))?;
+            .map_err(|e| Status::internal(format!("ApplyEditBatch failed: {e}")))?;
 
-        Ok(Response::new(Self::edit_result_to_proto(&result)))
+        let files = result
+            .files
+            .into_iter()
+            .map(|file| GrpcFileEditOutcome {
+                file_path: file.file_path,
+                status: Some(match file.status {
+                    FileEditStatus::Applied { result } => {
+                        GrpcFileEditStatus::Applied(convert::edit_result_to_proto(&result))
+                    }
+                    FileEditStatus::Failed { reason } => GrpcFileEditStatus::Failed(reason),
+                    FileEditStatus::NotApplied => {
+                        GrpcFileEditStatus::NotApplied(FileEditNotApplied {})
+                    }
+                }),
+            })
+            .collect();
+
+        Ok(Response::new(ApplyEditBatchResponse {
+            applied: result.applied,
+            files,
+        }))
     }
 
     async fn write_file(
@@ -576,6 +666,7 @@ impl RemoteWorkspaceServiceServer for RemoteWorkspaceService {
         request: Request<GrpcWriteFileRequest>,
     ) -> Result<Response<ProtoEditResult>, Status> {
         let req = request.into_inner();
+        self.check_path(&req.file_path)?;
         let cancellation_token = tokio_util::sync::CancellationToken::new();
         let _guard = cancellation_token.clone().drop_guard();
         let context = WorkspaceOpContext::new("write_file", cancellation_token);
@@ -590,6 +681,6 @@ impl RemoteWorkspaceServiceServer for RemoteWorkspaceService {
             .await
             .map_err(|e| Status::internal(format!("WriteFile failed: {e}")))?;
 
-        Ok(Response::new(Self::edit_result_to_proto(&result)))
+        Ok(Response::new(convert::edit_result_to_proto(&result)))
     }
 }
@@ -1,7 +1,8 @@
 use steer_remote_workspace::proto::{
-    ApplyEditsRequest, EditMatchAll, EditMatchExactlyOne, EditMatchNth, EditOperation,
-    ExecuteToolRequest, GetAgentInfoRequest, GetToolSchemasRequest, HealthRequest, HealthStatus,
-    ListDirectoryRequest, ReadFileRequest, WriteFileRequest, edit_operation,
+    ApplyEditBatchRequest, ApplyEditsRequest, EditMatchAll, EditMatchExactlyOne, EditMatchNth,
+    EditOperation, ExecuteToolRequest, GetAgentInfoRequest, GetCapabilitiesRequest,
+    GetToolSchemasRequest, HealthRequest, HealthStatus, ListDirectoryRequest, ReadFileRequest,
+    SearchFilesRequest, WriteFileRequest, edit_operation, file_edit_outcome,
     remote_workspace_service_server::RemoteWorkspaceService as RemoteWorkspaceServiceTrait,
 };
 use steer_remote_workspace::remote_workspace_service::RemoteWorkspaceService;
@@ -33,6 +34,55 @@ async fn test_health_check() {
     assert!(!health.message.is_empty());
 }
 
+#[tokio::test]
+async fn test_get_capabilities_reports_protocol_version() {
+    let service = RemoteWorkspaceService::new(std::env::temp_dir())
+        .await
+        .unwrap();
+
+    let capabilities = service
+        .get_capabilities(Request::new(GetCapabilitiesRequest {}))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(
+        capabilities.protocol_version,
+        steer_remote_workspace::proto::PROTOCOL_VERSION
+    );
+    assert_eq!(capabilities.server_version, env!("CARGO_PKG_VERSION"));
+    assert!(
+        capabilities
+            .supported_operations
+            .contains(&"read_file".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_search_files_ranks_matches_server_side() {
+    let temp_dir = tempdir().unwrap();
+    std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+    std::fs::write(temp_dir.path().join("src/main.rs"), "").unwrap();
+    std::fs::write(temp_dir.path().join("README.md"), "").unwrap();
+
+    let service = RemoteWorkspaceService::new(temp_dir.path().to_path_buf())
+        .await
+        .unwrap();
+
+    let response = service
+        .search_files(Request::new(SearchFilesRequest {
+            query: "main".to_string(),
+            max_results: 1,
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(response.matches.len(), 1);
+    assert_eq!(response.matches[0].path, "src/main.rs");
+    assert!(response.matches[0].score > 0);
+}
+
 #[tokio::test]
 async fn test_get_tool_schemas_empty() {
     let service = RemoteWorkspaceService::new(std::env::temp_dir())
@@ -59,6 +109,7 @@ async fn test_execute_tool_unimplemented() {
         parameters_json: "{}".to_string(),
         context_json: "{}".to_string(),
         timeout_ms: Some(5000),
+        env: Default::default(),
     });
 
     let response = service.execute_tool(request).await;
@@ -88,6 +139,7 @@ async fn test_write_and_read_file() {
         offset: None,
         limit: None,
         raw: None,
+        max_bytes: None,
     });
     let read_response = service.read_file(read_req).await;
     assert!(read_response.is_ok());
@@ -107,6 +159,9 @@ async fn test_list_directory() {
     let request = Request::new(ListDirectoryRequest {
         path: temp_dir.path().to_string_lossy().to_string(),
         ignore: Vec::new(),
+        offset: None,
+        limit: None,
+        respect_gitignore: None,
     });
 
     let response = service.list_directory(request).await;
@@ -132,6 +187,7 @@ async fn test_apply_edits_supports_match_mode_all() {
             new_string: "done".to_string(),
             match_selection: Some(edit_operation::MatchSelection::All(EditMatchAll {})),
         }],
+        allow_partial: false,
     });
 
     let edit_response = service.apply_edits(edit_request).await;
@@ -144,6 +200,7 @@ async fn test_apply_edits_supports_match_mode_all() {
         offset: None,
         limit: None,
         raw: Some(true),
+        max_bytes: None,
     });
     let read_response = service.read_file(read_request).await;
     assert!(read_response.is_ok());
@@ -151,6 +208,91 @@ async fn test_apply_edits_supports_match_mode_all() {
     assert_eq!(content.content, "done\ndone\n");
 }
 
+#[tokio::test]
+async fn test_apply_edit_batch_reports_per_file_status_over_grpc() {
+    let temp_dir = tempdir().unwrap();
+    let service = RemoteWorkspaceService::new(temp_dir.path().to_path_buf())
+        .await
+        .unwrap();
+    std::fs::write(temp_dir.path().join("a.txt"), "alpha\n").unwrap();
+    std::fs::write(temp_dir.path().join("b.txt"), "beta\n").unwrap();
+
+    let file_edit = |file_path: &str, old_string: &str| ApplyEditsRequest {
+        file_path: file_path.to_string(),
+        edits: vec![EditOperation {
+            old_string: old_string.to_string(),
+            new_string: "changed".to_string(),
+            match_selection: None,
+        }],
+        allow_partial: false,
+    };
+    let response = service
+        .apply_edit_batch(Request::new(ApplyEditBatchRequest {
+            files: vec![file_edit("a.txt", "alpha"), file_edit("b.txt", "missing")],
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+
+    assert!(!response.applied);
+    assert_eq!(response.files.len(), 2);
+    assert!(matches!(
+        response.files[0].status,
+        Some(file_edit_outcome::Status::NotApplied(_))
+    ));
+    assert!(matches!(
+        &response.files[1].status,
+        Some(file_edit_outcome::Status::Failed(reason)) if reason.contains("string not found")
+    ));
+    assert_eq!(
+        std::fs::read_to_string(temp_dir.path().join("a.txt")).unwrap(),
+        "alpha\n"
+    );
+}
+
+#[tokio::test]
+async fn test_preview_edit_matches_applied_result() {
+    let temp_dir = tempdir().unwrap();
+    let service = RemoteWorkspaceService::new(temp_dir.path().to_path_buf())
+        .await
+        .unwrap();
+    let file_path = temp_dir.path().join("preview.txt");
+    std::fs::write(&file_path, "one\ntwo\nthree\n").unwrap();
+
+    let request = || ApplyEditsRequest {
+        file_path: "preview.txt".to_string(),
+        edits: vec![EditOperation {
+            old_string: "two".to_string(),
+            new_string: "TWO".to_string(),
+            match_selection: None,
+        }],
+        allow_partial: false,
+    };
+
+    let preview = service
+        .preview_edit(Request::new(request()))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(preview.old_content.as_deref(), Some("one\ntwo\nthree\n"));
+    assert_eq!(
+        std::fs::read_to_string(&file_path).unwrap(),
+        "one\ntwo\nthree\n"
+    );
+
+    let applied = service
+        .apply_edits(Request::new(request()))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(preview.changes_made, applied.changes_made);
+    assert_eq!(preview.new_content, applied.new_content);
+    assert_eq!(
+        preview.new_content.as_deref(),
+        Some(std::fs::read_to_string(&file_path).unwrap().as_str())
+    );
+}
+
 #[tokio::test]
 async fn test_apply_edits_supports_match_mode_nth_and_match_index() {
     let temp_dir = tempdir().unwrap();
@@ -170,6 +312,7 @@ async fn test_apply_edits_supports_match_mode_nth_and_match_index() {
                 match_index: 2,
             })),
         }],
+        allow_partial: false,
     });
 
     let edit_response = service.apply_edits(edit_request).await;
@@ -182,6 +325,7 @@ async fn test_apply_edits_supports_match_mode_nth_and_match_index() {
         offset: None,
         limit: None,
         raw: Some(true),
+        max_bytes: None,
     });
     let read_response = service.read_file(read_request).await;
     assert!(read_response.is_ok());
@@ -208,6 +352,7 @@ async fn test_apply_edits_supports_match_selection_exactly_one() {
                 EditMatchExactlyOne {},
             )),
         }],
+        allow_partial: false,
     });
 
     let response = service
@@ -236,6 +381,7 @@ async fn test_apply_edits_nth_requires_match_index_over_grpc() {
                 match_index: 0,
             })),
         }],
+        allow_partial: false,
     });
 
     let err = service
@@ -265,6 +411,7 @@ async fn test_apply_edits_nth_rejects_out_of_range_match_index_over_grpc() {
                 match_index: 3,
             })),
         }],
+        allow_partial: false,
     });
 
     let err = service
@@ -289,3 +436,44 @@ async fn test_get_agent_info() {
     assert!(!info.version.is_empty());
     assert!(info.metadata.contains_key("working_directory"));
 }
+
+#[tokio::test]
+async fn test_read_outside_working_dir_is_rejected() {
+    let temp_dir = tempdir().unwrap();
+    let root = temp_dir.path().join("project");
+    std::fs::create_dir(&root).unwrap();
+    let secret = temp_dir.path().join("secret.txt");
+    std::fs::write(&secret, "secret\n").unwrap();
+    let service = RemoteWorkspaceService::new(root.clone()).await.unwrap();
+
+    let read = |file_path: String| {
+        Request::new(ReadFileRequest {
+            file_path,
+            offset: None,
+            limit: None,
+            raw: None,
+            max_bytes: None,
+        })
+    };
+    for file_path in [
+        secret.to_string_lossy().to_string(),
+        "../secret.txt".to_string(),
+    ] {
+        let err = service
+            .read_file(read(file_path.clone()))
+            .await
+            .expect_err("read outside the working dir should be rejected");
+        assert_eq!(err.code(), tonic::Code::PermissionDenied, "{file_path}");
+    }
+
+    let service = RemoteWorkspaceService::new(root)
+        .await
+        .unwrap()
+        .with_allow_outside_workspace(true);
+    let content = service
+        .read_file(read(secret.to_string_lossy().to_string()))
+        .await
+        .expect("the boundary is lifted")
+        .into_inner();
+    assert!(content.content.contains("secret"));
+}
@@ -4,12 +4,13 @@ use thiserror::Error;
 
 use crate::tools::{
     AST_GREP_TOOL_NAME, BASH_TOOL_NAME, DISPATCH_AGENT_TOOL_NAME, EDIT_TOOL_NAME, FETCH_TOOL_NAME,
-    GLOB_TOOL_NAME, GREP_TOOL_NAME, LS_TOOL_NAME, MULTI_EDIT_TOOL_NAME, READ_FILE_TOOL_NAME,
-    REPLACE_TOOL_NAME, TODO_READ_TOOL_NAME, TODO_WRITE_TOOL_NAME, astgrep::AstGrepError,
-    bash::BashError, dispatch_agent::DispatchAgentError, edit::EditError,
-    edit::multi_edit::MultiEditError, fetch::FetchError, glob::GlobError, grep::GrepError,
-    ls::LsError, read_file::ReadFileError, replace::ReplaceError, todo::read::TodoReadError,
-    todo::write::TodoWriteError,
+    FORGET_TOOL_NAME, GIT_STATUS_TOOL_NAME, GLOB_TOOL_NAME, GREP_TOOL_NAME, LS_TOOL_NAME,
+    MULTI_EDIT_TOOL_NAME, READ_FILE_TOOL_NAME, REMEMBER_TOOL_NAME, REPLACE_TOOL_NAME,
+    TODO_READ_TOOL_NAME, TODO_WRITE_TOOL_NAME, astgrep::AstGrepError, bash::BashError,
+    dispatch_agent::DispatchAgentError, edit::EditError, edit::multi_edit::MultiEditError,
+    fetch::FetchError, git_status::GitStatusError, glob::GlobError, grep::GrepError, ls::LsError,
+    memory::forget::ForgetError, memory::remember::RememberError, read_file::ReadFileError,
+    replace::ReplaceError, todo::read::TodoReadError, todo::write::TodoWriteError,
 };
 
 #[derive(Error, Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -29,9 +30,22 @@ pub enum ToolError {
     #[error("{0} timed out")]
     Timeout(String),
 
+    /// The user declined the call at the approval prompt. The message is
+    /// written for the model: the tool did not run and it should adapt.
+    #[error("{}", approval_denied_message(tool_name, reason.as_deref()))]
+    ApprovalDenied {
+        tool_name: String,
+        reason: Option<String>,
+    },
+
+    /// Recorded by sessions created before [`ToolError::ApprovalDenied`].
     #[error("{0} requires approval to run")]
     DeniedByUser(String),
 
+    /// Recorded by sessions created before [`ToolError::ApprovalDenied`].
+    #[error("{tool_name} was denied by the user: {reason}")]
+    DeniedByUserWithReason { tool_name: String, reason: String },
+
     #[error("{0} denied by approval policy")]
     DeniedByPolicy(String),
 
@@ -53,6 +67,32 @@ impl ToolError {
             message: message.into(),
         }
     }
+
+    pub fn approval_denied<T: Into<String>>(tool_name: T, reason: Option<String>) -> Self {
+        ToolError::ApprovalDenied {
+            tool_name: tool_name.into(),
+            reason: reason.filter(|reason| !reason.trim().is_empty()),
+        }
+    }
+
+    /// Whether the failure may go away if the same call is made again, e.g. a
+    /// dropped connection to a remote workspace or a failed fetch. Timeouts are
+    /// not: a local tool that hit its own limit would just hit it again.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, ToolError::Execution(error) if error.is_transient())
+    }
+}
+
+fn approval_denied_message(tool_name: &str, reason: Option<&str>) -> String {
+    let mut message =
+        format!("Approval denied: the user declined to run {tool_name}, so it was not executed.");
+    if let Some(reason) = reason {
+        message.push_str(&format!(" The user said: {reason}"));
+    }
+    message.push_str(
+        " Do not retry the same call. Propose a different approach or ask the user how to proceed.",
+    );
+    message
 }
 
 #[derive(Error, Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -69,12 +109,18 @@ pub enum ToolExecutionError {
     #[error("{0}")]
     Fetch(FetchError),
     #[error("{0}")]
+    GitStatus(GitStatusError),
+    #[error("{0}")]
     Glob(GlobError),
     #[error("{0}")]
     Grep(GrepError),
     #[error("{0}")]
     Ls(LsError),
     #[error("{0}")]
+    Remember(RememberError),
+    #[error("{0}")]
+    Forget(ForgetError),
+    #[error("{0}")]
     Replace(ReplaceError),
     #[error("{0}")]
     TodoRead(TodoReadError),
@@ -97,9 +143,12 @@ impl ToolExecutionError {
             ToolExecutionError::Edit(_) => EDIT_TOOL_NAME,
             ToolExecutionError::MultiEdit(_) => MULTI_EDIT_TOOL_NAME,
             ToolExecutionError::Fetch(_) => FETCH_TOOL_NAME,
+            ToolExecutionError::GitStatus(_) => GIT_STATUS_TOOL_NAME,
             ToolExecutionError::Glob(_) => GLOB_TOOL_NAME,
             ToolExecutionError::Grep(_) => GREP_TOOL_NAME,
             ToolExecutionError::Ls(_) => LS_TOOL_NAME,
+            ToolExecutionError::Remember(_) => REMEMBER_TOOL_NAME,
+            ToolExecutionError::Forget(_) => FORGET_TOOL_NAME,
             ToolExecutionError::Replace(_) => REPLACE_TOOL_NAME,
             ToolExecutionError::TodoRead(_) => TODO_READ_TOOL_NAME,
             ToolExecutionError::TodoWrite(_) => TODO_WRITE_TOOL_NAME,
@@ -108,6 +157,18 @@ impl ToolExecutionError {
             ToolExecutionError::External { tool_name, .. } => tool_name.as_str(),
         }
     }
+
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ToolExecutionError::ReadFile(ReadFileError::Workspace(error))
+            | ToolExecutionError::Grep(GrepError::Workspace(error))
+            | ToolExecutionError::AstGrep(AstGrepError::Workspace(error))
+            | ToolExecutionError::Glob(GlobError::Workspace(error))
+            | ToolExecutionError::Ls(LsError::Workspace(error)) => error.is_transient(),
+            ToolExecutionError::Fetch(error) => error.is_transient(),
+            _ => false,
+        }
+    }
 }
 
 #[derive(Error, Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -128,10 +189,20 @@ pub enum WorkspaceOpError {
     #[error("io error: {message}")]
     Io { message: String },
 
+    /// The workspace could not be reached, e.g. a remote workspace connection dropped.
+    #[error("workspace unavailable: {message}")]
+    Unavailable { message: String },
+
     #[error("{message}")]
     Other { message: String },
 }
 
+impl WorkspaceOpError {
+    pub fn is_transient(&self) -> bool {
+        matches!(self, WorkspaceOpError::Unavailable { .. })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,4 +248,54 @@ mod tests {
         assert_workspace_error_roundtrip(ReadFileError::Workspace(workspace_error.clone()));
         assert_workspace_error_roundtrip(DispatchAgentError::Workspace(workspace_error));
     }
+
+    #[test]
+    fn approval_denied_tells_the_model_the_tool_did_not_run() {
+        let message =
+            ToolError::approval_denied("bash", Some("use cargo check".into())).to_string();
+        assert!(message.starts_with("Approval denied: the user declined to run bash"));
+        assert!(message.contains("The user said: use cargo check"));
+
+        let blank_reason = ToolError::approval_denied("bash", Some("  ".into()));
+        assert!(matches!(
+            blank_reason,
+            ToolError::ApprovalDenied { reason: None, .. }
+        ));
+    }
+
+    #[test]
+    fn only_transient_failures_are_retryable() {
+        let unavailable = WorkspaceOpError::Unavailable {
+            message: "connection reset".to_string(),
+        };
+        assert!(
+            ToolError::Execution(ToolExecutionError::ReadFile(ReadFileError::Workspace(
+                unavailable
+            )))
+            .is_transient()
+        );
+        assert!(
+            ToolError::Execution(ToolExecutionError::Fetch(FetchError::Http {
+                status: 503,
+                url: "https://example.com".to_string(),
+            }))
+            .is_transient()
+        );
+
+        assert!(
+            !ToolError::Execution(ToolExecutionError::Fetch(FetchError::Http {
+                status: 404,
+                url: "https://example.com".to_string(),
+            }))
+            .is_transient()
+        );
+        assert!(
+            !ToolError::Execution(ToolExecutionError::ReadFile(ReadFileError::Workspace(
+                WorkspaceOpError::NotFound
+            )))
+            .is_transient()
+        );
+        assert!(!ToolError::Timeout("grep".to_string()).is_transient());
+        assert!(!ToolError::Cancelled("grep".to_string()).is_transient());
+    }
 }
@@ -1,11 +1,14 @@
 use crate::{
     error::ToolError,
+    tools::bash::ParsedCommand,
+    tools::memory::MemoryEntry,
     tools::todo::{TodoItem, TodoWriteFileOperation},
 };
 use serde::{Deserialize, Serialize};
 
 pub use steer_workspace::result::{
     EditResult, FileContentResult, FileEntry, FileListResult, GlobResult, SearchMatch, SearchResult,
+    SkippedEdit,
 };
 
 /// Core enum for all tool results
@@ -22,6 +25,8 @@ pub enum ToolResult {
     TodoWrite(TodoWriteResult),
     Fetch(FetchResult),
     Agent(AgentResult),
+    GitStatus(GitStatusResult),
+    Memory(MemoryResult),
 
     // Unknown or remote (MCP) tool payload
     External(ExternalResult),
@@ -35,6 +40,21 @@ pub enum ToolResult {
 pub struct FetchResult {
     pub url: String,
     pub content: String,
+    /// Shown to the model after the fetched content.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Result for the git_status tool: the full status rendered as text
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitStatusResult {
+    pub content: String,
+}
+
+/// Result for the remember and forget tools: the session memory after the change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryResult {
+    pub entries: Vec<MemoryEntry>,
 }
 
 /// Workspace revision metadata for dispatched agents.
@@ -80,6 +100,9 @@ pub struct BashResult {
     pub command: String,
     #[serde(default)]
     pub timed_out: bool,
+    /// `command` split into programs and arguments; `None` when it could not be parsed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parsed: Option<ParsedCommand>,
 }
 
 /// Result for todo operations
@@ -123,6 +146,8 @@ impl ToolOutput for AstGrepResult {}
 impl ToolOutput for ExternalResult {}
 impl ToolOutput for FetchResult {}
 impl ToolOutput for AgentResult {}
+impl ToolOutput for GitStatusResult {}
+impl ToolOutput for MemoryResult {}
 impl ToolOutput for ToolResult {}
 
 // Manual From implementations to support BuiltinTool::Output conversions
@@ -210,6 +235,18 @@ impl From<AgentResult> for ToolResult {
     }
 }
 
+impl From<GitStatusResult> for ToolResult {
+    fn from(r: GitStatusResult) -> Self {
+        Self::GitStatus(r)
+    }
+}
+
+impl From<MemoryResult> for ToolResult {
+    fn from(r: MemoryResult) -> Self {
+        Self::Memory(r)
+    }
+}
+
 impl From<ExternalResult> for ToolResult {
     fn from(r: ExternalResult) -> Self {
         Self::External(r)
@@ -225,6 +262,43 @@ impl From<ToolError> for ToolResult {
 impl ToolResult {
     /// Format the result for LLM consumption
     pub fn llm_format(&self) -> String {
+        let mut output = self.llm_body();
+        for warning in self.warnings() {
+            output.push_str(&format!("\n[Warning: {warning}]"));
+        }
+        output
+    }
+
+    /// Notes attached with [`Self::push_warning`].
+    pub fn warnings(&self) -> &[String] {
+        match self {
+            ToolResult::Search(r) => &r.warnings,
+            ToolResult::FileList(r) => &r.warnings,
+            ToolResult::FileContent(r) => &r.warnings,
+            ToolResult::Glob(r) => &r.warnings,
+            ToolResult::Fetch(r) => &r.warnings,
+            _ => &[],
+        }
+    }
+
+    /// Attach a note for the model about how the result was produced, e.g.
+    /// that the call was retried after a transient failure. Notes are
+    /// appended to [`Self::llm_format`]. Returns `false` if this kind of
+    /// result cannot carry warnings.
+    pub fn push_warning(&mut self, warning: impl Into<String>) -> bool {
+        let warnings = match self {
+            ToolResult::Search(r) => &mut r.warnings,
+            ToolResult::FileList(r) => &mut r.warnings,
+            ToolResult::FileContent(r) => &mut r.warnings,
+            ToolResult::Glob(r) => &mut r.warnings,
+            ToolResult::Fetch(r) => &mut r.warnings,
+            _ => return false,
+        };
+        warnings.push(warning.into());
+        true
+    }
+
+    fn llm_body(&self) -> String {
         match self {
             ToolResult::Search(r) => {
                 if r.matches.is_empty() {
@@ -246,6 +320,14 @@ impl ToolResult {
                         ));
                     }
 
+                    if !r.search_completed {
+                        output.push(format!(
+                            "\n[Search stopped early after {} matches in {} files. Narrow the pattern or path to see the rest.]",
+                            r.matches.len(),
+                            r.total_files_searched
+                        ));
+                    }
+
                     output.join("\n")
                 }
             }
@@ -259,19 +341,36 @@ impl ToolResult {
                         let size_str = entry.size.map(|s| format!(" ({s})")).unwrap_or_default();
                         lines.push(format!("{}{}{}", entry.path, type_indicator, size_str));
                     }
+                    if r.truncated {
+                        lines.push(format!(
+                            "\n[Showing {} of {} entries. Use offset to list the rest.]",
+                            r.entries.len(),
+                            r.total_entries
+                        ));
+                    }
                     lines.join("\n")
                 }
             }
-            ToolResult::FileContent(r) => r.content.clone(),
+            ToolResult::FileContent(r) => match &r.encoding {
+                Some(encoding) => format!("[Decoded from {encoding}]\n{}", r.content),
+                None => r.content.clone(),
+            },
             ToolResult::Edit(r) => {
-                if r.file_created {
+                let mut summary = if r.file_created {
                     format!("Successfully created {}", r.file_path)
                 } else {
                     format!(
                         "Successfully edited {}: {} change(s) made",
                         r.file_path, r.changes_made
                     )
+                };
+                for skipped in &r.skipped_edits {
+                    summary.push_str(&format!(
+                        "\nSkipped edit #{}: {}",
+                        skipped.edit_index, skipped.reason
+                    ));
                 }
+                summary
             }
             ToolResult::Bash(r) => {
                 // Helper to truncate long outputs
@@ -372,6 +471,18 @@ impl ToolResult {
                 || r.content.clone(),
                 |session_id| format!("{}\n\nsession_id: {}", r.content, session_id),
             ),
+            ToolResult::GitStatus(r) => r.content.clone(),
+            ToolResult::Memory(r) => {
+                if r.entries.is_empty() {
+                    "Session memory is empty.".to_string()
+                } else {
+                    r.entries
+                        .iter()
+                        .map(|entry| format!("- {}: {}", entry.key, entry.content))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                }
+            }
             ToolResult::External(r) => r.payload.clone(),
             ToolResult::Error(e) => format!("Error: {e}"),
         }
@@ -390,6 +501,8 @@ impl ToolResult {
             ToolResult::TodoWrite(_) => "TodoWrite",
             ToolResult::Fetch(_) => "Fetch",
             ToolResult::Agent(_) => "Agent",
+            ToolResult::GitStatus(_) => "GitStatus",
+            ToolResult::Memory(_) => "Memory",
             ToolResult::External(_) => "External",
             ToolResult::Error(_) => "Error",
         }
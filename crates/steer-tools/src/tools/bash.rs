@@ -29,6 +29,13 @@ pub enum BashError {
     #[error("command is disallowed: {command}")]
     DisallowedCommand { command: String },
 
+    #[error("command refused because it {reason} (denylist rule `{rule}`): {command}")]
+    DeniedCommand {
+        command: String,
+        rule: String,
+        reason: String,
+    },
+
     #[error("io error: {message}")]
     Io { message: String },
 
@@ -43,4 +50,255 @@ pub struct BashParams {
     /// Optional timeout in milliseconds (default 180000, max 3600000)
     #[schemars(range(min = 1, max = 3_600_000))]
     pub timeout: Option<u64>,
+    /// Optional working directory for the command (defaults to the workspace root)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+}
+
+/// A bash command line split into the simple commands it runs.
+///
+/// Pipelines and command lists (`|`, `&&`, `||`, `;`, `&`) produce one entry per
+/// command. Redirections and their targets are dropped from the argument lists,
+/// as are leading `NAME=value` assignments, so `program` is what actually runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ParsedCommand {
+    pub commands: Vec<SimpleCommand>,
+    #[serde(default)]
+    pub has_pipes: bool,
+    #[serde(default)]
+    pub has_redirects: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SimpleCommand {
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+impl SimpleCommand {
+    /// The program without any leading directory, e.g. `rm` for `/bin/rm`.
+    pub fn program_name(&self) -> &str {
+        self.program
+            .rsplit('/')
+            .next()
+            .unwrap_or(self.program.as_str())
+    }
+}
+
+enum Piece {
+    Words(String),
+    Separator { pipe: bool },
+    Redirect { has_target: bool },
+}
+
+impl ParsedCommand {
+    /// Parse `command` with shell-words quoting rules. Returns `None` for input
+    /// that is not valid shell syntax, such as unbalanced quotes.
+    pub fn parse(command: &str) -> Option<Self> {
+        let mut parsed = Self {
+            commands: Vec::new(),
+            has_pipes: false,
+            has_redirects: false,
+        };
+        let mut words: Vec<String> = Vec::new();
+        let mut expect_redirect_target = false;
+
+        for piece in split_pieces(command) {
+            match piece {
+                Piece::Words(text) => {
+                    let mut split = shell_words::split(&text).ok()?.into_iter();
+                    if expect_redirect_target {
+                        split.next();
+                        expect_redirect_target = false;
+                    }
+                    words.extend(split);
+                }
+                Piece::Separator { pipe } => {
+                    parsed.has_pipes |= pipe;
+                    parsed.push_command(std::mem::take(&mut words));
+                }
+                Piece::Redirect { has_target } => {
+                    parsed.has_redirects = true;
+                    expect_redirect_target = has_target;
+                }
+            }
+        }
+        parsed.push_command(words);
+
+        Some(parsed)
+    }
+
+    /// Names of every program the command runs, in order.
+    pub fn program_names(&self) -> impl Iterator<Item = &str> {
+        self.commands.iter().map(SimpleCommand::program_name)
+    }
+
+    fn push_command(&mut self, words: Vec<String>) {
+        let mut words = words.into_iter().skip_while(|word| is_assignment(word));
+        if let Some(program) = words.next() {
+            self.commands.push(SimpleCommand {
+                program,
+                args: words.collect(),
+            });
+        }
+    }
+}
+
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty()
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+/// Split `command` at unquoted control and redirection operators, leaving the
+/// text between them for shell-words to tokenize.
+fn split_pieces(command: &str) -> Vec<Piece> {
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+    let mut chars = command.chars().peekable();
+    let mut in_single = false;
+    let mut in_double = false;
+
+    let flush = |text: &mut String, pieces: &mut Vec<Piece>| {
+        if !text.trim().is_empty() {
+            pieces.push(Piece::Words(std::mem::take(text)));
+        }
+        text.clear();
+    };
+
+    while let Some(c) = chars.next() {
+        if in_single {
+            in_single = c != '\'';
+            text.push(c);
+            continue;
+        }
+        match c {
+            '\\' => {
+                text.push(c);
+                if let Some(next) = chars.next() {
+                    text.push(next);
+                }
+            }
+            '\'' if !in_double => {
+                in_single = true;
+                text.push(c);
+            }
+            '"' => {
+                in_double = !in_double;
+                text.push(c);
+            }
+            _ if in_double => text.push(c),
+            '|' | ';' | '\n' => {
+                let pipe = c == '|' && chars.next_if_eq(&'|').is_none();
+                if pipe {
+                    // `|&` pipes stderr too.
+                    chars.next_if_eq(&'&');
+                }
+                flush(&mut text, &mut pieces);
+                pieces.push(Piece::Separator { pipe });
+            }
+            '&' if chars.peek() == Some(&'>') => {
+                // `&>` / `&>>` redirect both streams.
+                chars.next();
+                chars.next_if_eq(&'>');
+                flush(&mut text, &mut pieces);
+                pieces.push(Piece::Redirect { has_target: true });
+            }
+            '&' => {
+                chars.next_if_eq(&'&');
+                flush(&mut text, &mut pieces);
+                pieces.push(Piece::Separator { pipe: false });
+            }
+            '>' | '<' => {
+                strip_fd_prefix(&mut text);
+                chars.next_if_eq(&c);
+                flush(&mut text, &mut pieces);
+                // `2>&1` duplicates a descriptor and has no file target.
+                let duplicates = chars.next_if_eq(&'&').is_some();
+                if duplicates {
+                    while chars.next_if(|d| d.is_ascii_digit() || *d == '-').is_some() {}
+                }
+                pieces.push(Piece::Redirect {
+                    has_target: !duplicates,
+                });
+            }
+            _ => text.push(c),
+        }
+    }
+    flush(&mut text, &mut pieces);
+
+    pieces
+}
+
+/// Drop a file descriptor number written directly before a redirection, as in `2>`.
+fn strip_fd_prefix(text: &mut String) {
+    let digits = text.len() - text.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    let before = &text[..text.len() - digits];
+    if digits > 0 && (before.is_empty() || before.ends_with(char::is_whitespace)) {
+        text.truncate(before.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParsedCommand;
+
+    fn programs(command: &str) -> Vec<String> {
+        ParsedCommand::parse(command)
+            .expect("command parses")
+            .program_names()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn recognizes_rm_as_program() {
+        let parsed = ParsedCommand::parse("rm -rf /").expect("command parses");
+
+        assert_eq!(parsed.commands.len(), 1);
+        assert_eq!(parsed.commands[0].program, "rm");
+        assert_eq!(parsed.commands[0].args, vec!["-rf", "/"]);
+        assert!(!parsed.has_pipes);
+        assert!(!parsed.has_redirects);
+    }
+
+    #[test]
+    fn splits_pipelines_and_lists() {
+        let parsed = ParsedCommand::parse("git log --oneline|head -5 && echo done; sleep 1 &")
+            .expect("command parses");
+
+        assert!(parsed.has_pipes);
+        assert_eq!(
+            programs("git log --oneline|head -5 && echo done; sleep 1 &"),
+            ["git", "head", "echo", "sleep"]
+        );
+    }
+
+    #[test]
+    fn drops_redirects_and_assignments() {
+        let parsed = ParsedCommand::parse("RUST_LOG=debug /usr/bin/cargo test 2>&1 >out.log")
+            .expect("command parses");
+
+        assert!(parsed.has_redirects);
+        assert_eq!(parsed.commands[0].program_name(), "cargo");
+        assert_eq!(parsed.commands[0].args, vec!["test"]);
+    }
+
+    #[test]
+    fn keeps_quoted_operators_as_arguments() {
+        let parsed = ParsedCommand::parse(r#"echo "a | b" 'c > d'"#).expect("command parses");
+
+        assert_eq!(parsed.commands.len(), 1);
+        assert_eq!(parsed.commands[0].args, vec!["a | b", "c > d"]);
+        assert!(!parsed.has_pipes);
+        assert!(!parsed.has_redirects);
+    }
+
+    #[test]
+    fn rejects_unbalanced_quotes() {
+        assert!(ParsedCommand::parse("echo 'unterminated").is_none());
+    }
 }
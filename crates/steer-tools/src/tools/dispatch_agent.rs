@@ -41,6 +41,16 @@ pub enum DispatchAgentTarget {
         workspace: WorkspaceTarget,
         #[serde(default)]
         agent: Option<String>,
+        /// Role instructions that specialize the sub-agent (for example
+        /// "You are a code reviewer. Report problems; do not edit files.").
+        /// Replaces the default role line of the sub-agent's system prompt.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        system_prompt: Option<String>,
+        /// Restricts the sub-agent to these tools. Every name must be one of
+        /// the selected agent spec's tools; MCP tools are not available when
+        /// this is set.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tools: Option<Vec<String>>,
     },
     /// Continue an existing child session by id.
     Resume { session_id: String },
@@ -8,6 +8,8 @@ use crate::result::{EditResult, MultiEditResult};
 pub use steer_workspace::EditMatchPreview;
 use steer_workspace::error::non_unique_match_preview_suffix;
 
+pub mod hunks;
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum EditMatchMode {
@@ -71,6 +73,43 @@ pub enum EditFailure {
         #[serde(default)]
         omitted_matches: usize,
     },
+
+    #[error(
+        "old_string for edit #{edit_index} in file {file_path} was changed by an earlier edit; match against the file as it will be after prior edits"
+    )]
+    StaleOldString {
+        file_path: String,
+        edit_index: usize,
+    },
+
+    #[error(
+        "edit #{edit_index} in file {file_path} overlaps text written by edit #{conflicting_edit_index}; combine them into a single edit"
+    )]
+    OverlappingEdit {
+        file_path: String,
+        edit_index: usize,
+        conflicting_edit_index: usize,
+    },
+
+    #[error(
+        "edit #{edit_index} and edit #{conflicting_edit_index} target overlapping text at line {line_number} of {file_path}; combine them into a single edit"
+    )]
+    ConflictingEdits {
+        file_path: String,
+        edit_index: usize,
+        conflicting_edit_index: usize,
+        line_number: usize,
+    },
+
+    #[error(
+        "old_string for edit #{edit_index} matches {occurrences} places in file {file_path} and edit #{conflicting_edit_index} rewrites one of them, so the result would depend on edit order; make old_string unique in the original file"
+    )]
+    AmbiguousEdit {
+        file_path: String,
+        edit_index: usize,
+        conflicting_edit_index: usize,
+        occurrences: usize,
+    },
 }
 
 #[derive(Deserialize, Serialize, Debug, JsonSchema, Clone, Error)]
@@ -107,6 +146,9 @@ pub struct EditParams {
     pub match_mode: Option<EditMatchMode>,
     /// Optional 1-based match index used when `match_mode` is `nth`.
     pub match_index: Option<u64>,
+    /// Replace every occurrence of `old_string`. Same as `match_mode: all`; defaults to false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replace_all: Option<bool>,
 }
 
 pub mod multi_edit {
@@ -148,5 +190,8 @@ pub mod multi_edit {
         pub file_path: String,
         /// A list of edit operations to apply sequentially.
         pub edits: Vec<SingleEditOperation>,
+        /// Apply the edits that succeed and report the failed ones, instead of leaving the file untouched. Defaults to false.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub allow_partial: Option<bool>,
     }
 }
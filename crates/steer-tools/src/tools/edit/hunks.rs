@@ -0,0 +1,254 @@
+//! Line-level hunks for `edit_file` and `multi_edit` calls, so an approver can
+//! accept some of a proposed edit and reject the rest.
+
+use serde::{Deserialize, Serialize};
+use similar::{DiffOp, TextDiff};
+
+use super::multi_edit::{MULTI_EDIT_TOOL_NAME, MultiEditParams};
+use super::{EDIT_TOOL_NAME, EditParams, SingleEditOperation};
+use crate::ToolCall;
+
+/// One contiguous change within an edit's `old_string` -> `new_string` replacement.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditHunk {
+    /// 0-based index of the edit this hunk belongs to (always 0 for `edit_file`).
+    pub edit_index: usize,
+    /// 1-based line in the edit's `old_string` where the hunk starts.
+    pub old_start: usize,
+    pub old_lines: Vec<String>,
+    pub new_lines: Vec<String>,
+}
+
+/// Split an edit tool call into hunks, numbered in order across all of its edits.
+///
+/// Returns an empty list for other tools, unparseable parameters, or calls with
+/// fewer than two hunks, since there is nothing to choose between.
+pub fn edit_hunks(tool_call: &ToolCall) -> Vec<EditHunk> {
+    let Some(edits) = edit_operations(tool_call) else {
+        return Vec::new();
+    };
+
+    let hunks: Vec<EditHunk> = edits
+        .iter()
+        .enumerate()
+        .flat_map(|(edit_index, (old, new))| operation_hunks(edit_index, old, new))
+        .collect();
+
+    if hunks.len() < 2 { Vec::new() } else { hunks }
+}
+
+/// Rewrite an edit tool call so it applies only the hunks in `accepted`.
+///
+/// Rejected hunks are reverted to their original lines. Edits left with no
+/// change stay in `multi_edit` calls as no-ops, so edit numbers still match
+/// the original call. Returns `None` when nothing would change, or when the
+/// call is not a splittable edit.
+pub fn accept_hunks(tool_call: &ToolCall, accepted: &[usize]) -> Option<ToolCall> {
+    let edits = edit_operations(tool_call)?;
+
+    let mut hunk_index = 0;
+    let rewritten: Vec<String> = edits
+        .iter()
+        .map(|(old, new)| {
+            let diff = TextDiff::from_lines(old.as_str(), new.as_str());
+            let old_slices = diff.old_slices();
+            let new_slices = diff.new_slices();
+            let mut text = String::with_capacity(new.len());
+            for op in diff.ops() {
+                let take_new = if let DiffOp::Equal { .. } = op {
+                    false
+                } else {
+                    let keep = accepted.contains(&hunk_index);
+                    hunk_index += 1;
+                    keep
+                };
+                let slices = if take_new {
+                    &new_slices[op.new_range()]
+                } else {
+                    &old_slices[op.old_range()]
+                };
+                text.extend(slices.iter().copied());
+            }
+            text
+        })
+        .collect();
+
+    if rewritten
+        .iter()
+        .zip(&edits)
+        .all(|(text, (old, _))| text == old)
+    {
+        return None;
+    }
+
+    let parameters = match tool_call.name.as_str() {
+        EDIT_TOOL_NAME => {
+            let mut params: EditParams =
+                serde_json::from_value(tool_call.parameters.clone()).ok()?;
+            params.new_string = rewritten.into_iter().next()?;
+            serde_json::to_value(params).ok()?
+        }
+        MULTI_EDIT_TOOL_NAME => {
+            let mut params: MultiEditParams =
+                serde_json::from_value(tool_call.parameters.clone()).ok()?;
+            params.edits = params
+                .edits
+                .into_iter()
+                .zip(rewritten)
+                .map(|(edit, new_string)| SingleEditOperation { new_string, ..edit })
+                .collect();
+            serde_json::to_value(params).ok()?
+        }
+        _ => return None,
+    };
+
+    Some(ToolCall {
+        parameters,
+        ..tool_call.clone()
+    })
+}
+
+fn edit_operations(tool_call: &ToolCall) -> Option<Vec<(String, String)>> {
+    match tool_call.name.as_str() {
+        EDIT_TOOL_NAME => {
+            let params: EditParams = serde_json::from_value(tool_call.parameters.clone()).ok()?;
+            Some(vec![(params.old_string, params.new_string)])
+        }
+        MULTI_EDIT_TOOL_NAME => {
+            let params: MultiEditParams =
+                serde_json::from_value(tool_call.parameters.clone()).ok()?;
+            Some(
+                params
+                    .edits
+                    .into_iter()
+                    .map(|edit| (edit.old_string, edit.new_string))
+                    .collect(),
+            )
+        }
+        _ => None,
+    }
+}
+
+fn operation_hunks(edit_index: usize, old: &str, new: &str) -> Vec<EditHunk> {
+    let diff = TextDiff::from_lines(old, new);
+    let old_slices = diff.old_slices();
+    let new_slices = diff.new_slices();
+    let lines = |slices: &[&str]| {
+        slices
+            .iter()
+            .map(|line| line.trim_end_matches(['\r', '\n']).to_string())
+            .collect()
+    };
+
+    diff.ops()
+        .iter()
+        .filter(|op| !matches!(op, DiffOp::Equal { .. }))
+        .map(|op| EditHunk {
+            edit_index,
+            old_start: op.old_range().start + 1,
+            old_lines: lines(&old_slices[op.old_range()]),
+            new_lines: lines(&new_slices[op.new_range()]),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn edit_call(old: &str, new: &str) -> ToolCall {
+        ToolCall {
+            name: EDIT_TOOL_NAME.to_string(),
+            parameters: json!({
+                "file_path": "/tmp/lib.rs",
+                "old_string": old,
+                "new_string": new,
+            }),
+            id: "call_1".to_string(),
+        }
+    }
+
+    #[test]
+    fn single_change_has_no_hunks_to_choose_from() {
+        let call = edit_call("a\nb\n", "a\nc\n");
+        assert!(edit_hunks(&call).is_empty());
+    }
+
+    #[test]
+    fn separate_changes_become_separate_hunks() {
+        let call = edit_call("one\ntwo\nthree\nfour\n", "ONE\ntwo\nthree\nFOUR\n");
+        let hunks = edit_hunks(&call);
+
+        assert_eq!(
+            hunks,
+            vec![
+                EditHunk {
+                    edit_index: 0,
+                    old_start: 1,
+                    old_lines: vec!["one".to_string()],
+                    new_lines: vec!["ONE".to_string()],
+                },
+                EditHunk {
+                    edit_index: 0,
+                    old_start: 4,
+                    old_lines: vec!["four".to_string()],
+                    new_lines: vec!["FOUR".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejecting_a_hunk_applies_only_the_accepted_ones() {
+        let call = edit_call("one\ntwo\nthree\nfour\n", "ONE\ntwo\nthree\nFOUR\n");
+
+        let filtered = accept_hunks(&call, &[1]).expect("one hunk was accepted");
+
+        assert_eq!(filtered.id, call.id);
+        assert_eq!(filtered.parameters["old_string"], "one\ntwo\nthree\nfour\n");
+        assert_eq!(filtered.parameters["new_string"], "one\ntwo\nthree\nFOUR\n");
+    }
+
+    #[test]
+    fn multi_edit_keeps_edits_whose_hunks_were_all_rejected_as_no_ops() {
+        let call = ToolCall {
+            name: MULTI_EDIT_TOOL_NAME.to_string(),
+            parameters: json!({
+                "file_path": "/tmp/lib.rs",
+                "edits": [
+                    { "old_string": "fn a() {}", "new_string": "fn a() { todo!() }" },
+                    { "old_string": "x\ny\nz\n", "new_string": "X\ny\nZ\n" },
+                ],
+            }),
+            id: "call_2".to_string(),
+        };
+        assert_eq!(edit_hunks(&call).len(), 3);
+
+        let filtered = accept_hunks(&call, &[2]).expect("one hunk was accepted");
+
+        assert_eq!(
+            filtered.parameters["edits"],
+            json!([
+                {
+                    "old_string": "fn a() {}",
+                    "new_string": "fn a() {}",
+                    "match_mode": null,
+                    "match_index": null,
+                },
+                {
+                    "old_string": "x\ny\nz\n",
+                    "new_string": "x\ny\nZ\n",
+                    "match_mode": null,
+                    "match_index": null,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn accepting_nothing_yields_no_call() {
+        let call = edit_call("one\ntwo\nthree\n", "ONE\ntwo\nTHREE\n");
+        assert!(accept_hunks(&call, &[]).is_none());
+    }
+}
@@ -43,6 +43,20 @@ pub enum FetchError {
 
     #[error("model call failed: {message}")]
     ModelCallFailed { message: String },
+
+    #[error("network disabled: this session does not allow outbound network access")]
+    NetworkDisabled,
+}
+
+impl FetchError {
+    /// Network failures, rate limiting and server errors; a retry may succeed.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            FetchError::RequestFailed { .. } | FetchError::ReadFailed { .. } => true,
+            FetchError::Http { status, .. } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
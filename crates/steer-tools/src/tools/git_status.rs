@@ -0,0 +1,35 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::ToolSpec;
+use crate::error::ToolExecutionError;
+use crate::result::GitStatusResult;
+
+pub const GIT_STATUS_TOOL_NAME: &str = "git_status";
+
+pub struct GitStatusToolSpec;
+
+impl ToolSpec for GitStatusToolSpec {
+    type Params = GitStatusParams;
+    type Result = GitStatusResult;
+    type Error = GitStatusError;
+
+    const NAME: &'static str = GIT_STATUS_TOOL_NAME;
+    const DISPLAY_NAME: &'static str = "Git Status";
+
+    fn execution_error(error: Self::Error) -> ToolExecutionError {
+        ToolExecutionError::GitStatus(error)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Error)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum GitStatusError {
+    #[error("failed to read git status: {message}")]
+    Status { message: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+/// This tool takes in no parameters. Leave the input blank.
+pub struct GitStatusParams {}
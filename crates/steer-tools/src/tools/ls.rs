@@ -36,4 +36,13 @@ pub struct LsParams {
     pub path: String,
     /// Optional list of glob patterns to ignore
     pub ignore: Option<Vec<String>>,
+    /// Number of entries to skip, for fetching later pages (default 0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+    /// Maximum number of entries to return (default 1000)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// Hide entries matched by .gitignore/.ignore files (default true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub respect_gitignore: Option<bool>,
 }
@@ -0,0 +1,93 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::ToolSpec;
+use crate::error::ToolExecutionError;
+use crate::result::MemoryResult;
+
+/// A fact the agent keeps for the rest of the session, outside the message log.
+#[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq, JsonSchema, Hash)]
+pub struct MemoryEntry {
+    /// Short, stable name for the fact, e.g. `test-command`
+    pub key: String,
+    /// The fact itself
+    pub content: String,
+}
+
+pub mod remember {
+    use super::{Deserialize, JsonSchema, MemoryResult, Serialize, ToolExecutionError, ToolSpec};
+    use thiserror::Error;
+
+    pub const REMEMBER_TOOL_NAME: &str = "remember";
+
+    pub struct RememberToolSpec;
+
+    impl ToolSpec for RememberToolSpec {
+        type Params = RememberParams;
+        type Result = MemoryResult;
+        type Error = RememberError;
+
+        const NAME: &'static str = REMEMBER_TOOL_NAME;
+        const DISPLAY_NAME: &'static str = "Remember";
+
+        fn execution_error(error: Self::Error) -> ToolExecutionError {
+            ToolExecutionError::Remember(error)
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Error)]
+    #[serde(tag = "code", rename_all = "snake_case")]
+    pub enum RememberError {
+        #[error("memory key and content must not be empty")]
+        Empty,
+
+        #[error("io error: {message}")]
+        Io { message: String },
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+    pub struct RememberParams {
+        /// Short, stable name for the fact. Remembering an existing key replaces its content.
+        pub key: String,
+        /// The fact to remember, written so it makes sense without the surrounding conversation
+        pub content: String,
+    }
+}
+
+pub mod forget {
+    use super::{Deserialize, JsonSchema, MemoryResult, Serialize, ToolExecutionError, ToolSpec};
+    use thiserror::Error;
+
+    pub const FORGET_TOOL_NAME: &str = "forget";
+
+    pub struct ForgetToolSpec;
+
+    impl ToolSpec for ForgetToolSpec {
+        type Params = ForgetParams;
+        type Result = MemoryResult;
+        type Error = ForgetError;
+
+        const NAME: &'static str = FORGET_TOOL_NAME;
+        const DISPLAY_NAME: &'static str = "Forget";
+
+        fn execution_error(error: Self::Error) -> ToolExecutionError {
+            ToolExecutionError::Forget(error)
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Error)]
+    #[serde(tag = "code", rename_all = "snake_case")]
+    pub enum ForgetError {
+        #[error("no memory entry with key '{key}'")]
+        NotFound { key: String },
+
+        #[error("io error: {message}")]
+        Io { message: String },
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+    pub struct ForgetParams {
+        /// Key of the memory entry to remove
+        pub key: String,
+    }
+}
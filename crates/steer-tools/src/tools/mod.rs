@@ -3,9 +3,11 @@ pub mod bash;
 pub mod dispatch_agent;
 pub mod edit;
 pub mod fetch;
+pub mod git_status;
 pub mod glob;
 pub mod grep;
 pub mod ls;
+pub mod memory;
 pub mod read_file;
 pub mod replace;
 pub mod todo;
@@ -15,9 +17,12 @@ pub use bash::BASH_TOOL_NAME;
 pub use dispatch_agent::DISPATCH_AGENT_TOOL_NAME;
 pub use edit::{EDIT_TOOL_NAME, multi_edit::MULTI_EDIT_TOOL_NAME};
 pub use fetch::FETCH_TOOL_NAME;
+pub use git_status::GIT_STATUS_TOOL_NAME;
 pub use glob::GLOB_TOOL_NAME;
 pub use grep::GREP_TOOL_NAME;
 pub use ls::LS_TOOL_NAME;
+pub use memory::forget::FORGET_TOOL_NAME;
+pub use memory::remember::REMEMBER_TOOL_NAME;
 pub use read_file::READ_FILE_TOOL_NAME;
 pub use replace::REPLACE_TOOL_NAME;
 pub use todo::read::TODO_READ_TOOL_NAME;
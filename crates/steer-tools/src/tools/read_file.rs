@@ -40,4 +40,7 @@ pub struct ReadFileParams {
     pub limit: Option<u64>,
     /// Return raw file bytes rendered as text without numbering/trimming/truncation
     pub raw: Option<bool>,
+    /// Maximum bytes of content to return; formatted reads are capped at 50KB regardless
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<u64>,
 }
@@ -2,7 +2,7 @@ use criterion::{BatchSize, BenchmarkId, Criterion, black_box, criterion_group, c
 use ratatui::{Terminal, backend::TestBackend};
 use steer_grpc::client_api::{AssistantContent, Message, MessageData, UserContent};
 use steer_tui::tui::ChatViewport;
-use steer_tui::tui::model::ChatItem;
+use steer_tui::tui::model::{ChatItem, ChatItemData};
 use steer_tui::tui::state::ChatStore;
 use steer_tui::tui::theme::Theme;
 use steer_tui::tui::widgets::chat_widgets::{MessageWidget, RowWidget};
@@ -17,6 +17,7 @@ const FLAT_CHAT_SIZES: [usize; 3] = [1_000, 5_000, 20_000];
 const RICH_CHAT_SIZES: [usize; 2] = [20, 80];
 const VARIABLE_CHAT_SIZES: [usize; 2] = [1_000, 5_000];
 const RICH_MARKDOWN_BLOCKS: usize = 40;
+const STREAM_DELTA_BYTES: [usize; 2] = [16, 128];
 
 fn long_message(lines: usize) -> Message {
     let text = (0..lines)
@@ -456,6 +457,94 @@ fn bench_chat_viewport_steady_scroll_variable_length(c: &mut Criterion) {
     group.finish();
 }
 
+/// Split `text` into deltas of roughly `delta_bytes`, as a provider stream would.
+fn stream_deltas(text: &str, delta_bytes: usize) -> Vec<&str> {
+    let mut deltas = Vec::new();
+    let mut start = 0;
+    while start < text.len() {
+        let mut end = (start + delta_bytes).min(text.len());
+        while !text.is_char_boundary(end) {
+            end += 1;
+        }
+        deltas.push(&text[start..end]);
+        start = end;
+    }
+    deltas
+}
+
+fn bench_streaming_rich_markdown(c: &mut Criterion) {
+    let mut group = c.benchmark_group("streaming_rich_markdown");
+    group.sample_size(10);
+
+    let theme = Theme::default();
+    let body = rich_markdown_body(0, RICH_MARKDOWN_BLOCKS);
+    let message_id = "streamed".to_string();
+
+    for delta_bytes in STREAM_DELTA_BYTES {
+        let deltas = stream_deltas(&body, delta_bytes);
+
+        group.bench_function(
+            BenchmarkId::new(
+                "viewport_per_delta",
+                format!("{}_bytes_{}_deltas", body.len(), deltas.len()),
+            ),
+            |b| {
+                b.iter_batched(
+                    || {
+                        let mut store = ChatStore::default();
+                        store.add_message(Message {
+                            data: MessageData::Assistant {
+                                content: vec![AssistantContent::Text {
+                                    text: String::new(),
+                                }],
+                            },
+                            id: message_id.clone(),
+                            parent_message_id: None,
+                            timestamp: 0,
+                        });
+                        store.mark_streaming(&message_id);
+                        let terminal =
+                            Terminal::new(TestBackend::new(VIEWPORT_WIDTH, VIEWPORT_HEIGHT))
+                                .expect("terminal");
+                        (store, ChatViewport::new(), terminal)
+                    },
+                    |(mut store, mut viewport, mut terminal)| {
+                        for delta in &deltas {
+                            if let Some(item) = store.get_mut_by_id(&message_id)
+                                && let ChatItemData::Message(message) = &mut item.data
+                                && let MessageData::Assistant { content, .. } = &mut message.data
+                                && let Some(AssistantContent::Text { text }) = content.first_mut()
+                            {
+                                text.push_str(delta);
+                            }
+
+                            viewport.mark_dirty();
+                            viewport.state_mut().scroll_to_bottom();
+                            terminal
+                                .draw(|f| {
+                                    let area = f.area();
+                                    viewport.rebuild_from_store(
+                                        area.width,
+                                        ViewMode::Compact,
+                                        &theme,
+                                        &store,
+                                        None,
+                                    );
+                                    viewport.render(f, area, &theme);
+                                })
+                                .expect("draw");
+                        }
+                        black_box(viewport.state().total_content_height);
+                    },
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+    }
+
+    group.finish();
+}
+
 fn bench_rowwidget_cache_hit(c: &mut Criterion) {
     let mut group = c.benchmark_group("rowwidget");
     group.sample_size(20);
@@ -500,6 +589,7 @@ criterion_group!(
     bench_chat_viewport_rebuild_rich_markdown,
     bench_chat_viewport_steady_scroll_rich_markdown,
     bench_chat_viewport_rebuild_variable_length,
-    bench_chat_viewport_steady_scroll_variable_length
+    bench_chat_viewport_steady_scroll_variable_length,
+    bench_streaming_rich_markdown
 );
 criterion_main!(benches);
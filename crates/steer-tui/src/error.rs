@@ -57,6 +57,13 @@ pub enum Error {
     #[error("Authentication error: {0}")]
     Auth(String),
 
+    /// The TUI could not render even in safe mode
+    #[error("The TUI crashed while rendering session {session_id}")]
+    RenderCrash {
+        session_id: String,
+        crash_log: Option<std::path::PathBuf>,
+    },
+
     /// gRPC errors from steer-grpc
     #[error("gRPC error: {0}")]
     Grpc(#[from] Box<steer_grpc::GrpcError>),
@@ -6,4 +6,4 @@ pub mod tui;
 pub use tui::Tui;
 
 // Expose the run functions
-pub use tui::{run_tui, run_tui_auth_setup};
+pub use tui::{run_plain, run_tui, run_tui_auth_setup};
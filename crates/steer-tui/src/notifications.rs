@@ -1,15 +1,22 @@
 //! Notification module for the TUI.
 //!
 //! Provides centralized, focus-aware notification delivery with OSC 9 as the
-//! primary transport.
+//! primary transport. Approval requests can also run a user-configured focus
+//! command (e.g. selecting the tmux window) or ask iTerm2 for attention.
 
 use ratatui::crossterm::{Command, execute};
 use std::fmt;
 use std::io::{self, stdout};
+use std::process::Stdio;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tracing::debug;
 
-use steer_grpc::client_api::{NotificationTransport, Preferences};
+use steer_grpc::client_api::{NotificationPreferences, NotificationTransport, Preferences};
+
+/// Minimum time between attention requests, so a burst of approvals runs the
+/// focus command once.
+const ATTENTION_INTERVAL: Duration = Duration::from_secs(10);
 
 /// High-level notification categories emitted by event processors.
 #[derive(Debug, Clone)]
@@ -54,6 +61,65 @@ struct NotificationState {
     transport: EffectiveTransport,
     terminal_focused: bool,
     focus_events_enabled: bool,
+    attention: AttentionHook,
+    session_id: String,
+    session_title: Option<String>,
+}
+
+/// The approval-time focus command and iTerm2 attention request.
+#[derive(Debug)]
+struct AttentionHook {
+    command: Option<Vec<String>>,
+    iterm2: bool,
+    last_request: Option<Instant>,
+}
+
+impl AttentionHook {
+    fn from_preferences(preferences: &NotificationPreferences) -> Self {
+        let command = preferences.focus_command.as_deref().and_then(|template| {
+            match shell_words::split(template) {
+                Ok(args) if !args.is_empty() => Some(args),
+                Ok(_) => None,
+                Err(err) => {
+                    debug!("Ignoring invalid focus_command {template:?}: {err}");
+                    None
+                }
+            }
+        });
+        Self {
+            command,
+            iterm2: preferences.iterm2_attention,
+            last_request: None,
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.command.is_some() || self.iterm2
+    }
+
+    /// Records a request at `now`, or returns `false` if one was made within
+    /// [`ATTENTION_INTERVAL`].
+    fn try_acquire(&mut self, now: Instant) -> bool {
+        if self
+            .last_request
+            .is_some_and(|last| now.duration_since(last) < ATTENTION_INTERVAL)
+        {
+            return false;
+        }
+        self.last_request = Some(now);
+        true
+    }
+}
+
+/// Substitutes `{name}` placeholders in each argument.
+fn expand_args(args: &[String], vars: &[(&str, &str)]) -> Vec<String> {
+    args.iter()
+        .map(|arg| {
+            vars.iter().fold(arg.clone(), |arg, (name, value)| {
+                arg.replace(&format!("{{{name}}}"), value)
+            })
+        })
+        .collect()
 }
 
 impl NotificationManager {
@@ -65,10 +131,23 @@ impl NotificationManager {
                 transport,
                 terminal_focused: true,
                 focus_events_enabled: false,
+                attention: AttentionHook::from_preferences(&preferences.ui.notifications),
+                session_id: String::new(),
+                session_title: None,
             }),
         }
     }
 
+    /// Sets the session substituted into the focus command.
+    pub fn set_session(&self, session_id: &str, title: Option<&str>) {
+        let mut state = self
+            .inner
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        state.session_id = session_id.to_string();
+        state.session_title = title.map(str::to_string);
+    }
+
     pub fn set_terminal_focused(&self, focused: bool) {
         let mut state = self
             .inner
@@ -86,8 +165,8 @@ impl NotificationManager {
     }
 
     pub fn emit(&self, event: NotificationEvent) {
-        let (should_emit, transport, title, body) = {
-            let state = self
+        let (should_emit, transport, title, body, attention) = {
+            let mut state = self
                 .inner
                 .lock()
                 .unwrap_or_else(std::sync::PoisonError::into_inner);
@@ -96,14 +175,41 @@ impl NotificationManager {
             } else {
                 true
             };
+            let attention = (should_emit
+                && matches!(event, NotificationEvent::ToolApprovalRequested { .. })
+                && state.attention.is_enabled()
+                && state.attention.try_acquire(Instant::now()))
+            .then(|| {
+                let title = state
+                    .session_title
+                    .clone()
+                    .unwrap_or_else(|| state.session_id.chars().take(8).collect());
+                let pane = std::env::var("TMUX_PANE").unwrap_or_default();
+                let command = state.attention.command.as_deref().map(|args| {
+                    expand_args(
+                        args,
+                        &[
+                            ("session_id", &state.session_id),
+                            ("session_title", &title),
+                            ("pane", &pane),
+                        ],
+                    )
+                });
+                (command, state.attention.iterm2)
+            });
             (
                 should_emit,
                 state.transport,
                 NotificationEvent::title().to_string(),
                 event.body(),
+                attention,
             )
         };
 
+        if let Some((command, iterm2)) = attention {
+            request_attention(command, iterm2);
+        }
+
         if !should_emit || transport == EffectiveTransport::Off {
             return;
         }
@@ -173,6 +279,60 @@ impl Command for PostNotification {
     }
 }
 
+/// Command that asks iTerm2 to bounce its dock icon until the window is focused.
+#[derive(Debug, Clone)]
+struct RequestAttention;
+
+impl Command for RequestAttention {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1b]1337;RequestAttention=yes\x07")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Err(std::io::Error::other(
+            "tried to execute RequestAttention using WinAPI; use ANSI instead",
+        ))
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// Runs the focus command in the background and sends the iTerm2 request.
+/// Failures are only logged: the approval prompt is shown either way.
+fn request_attention(command: Option<Vec<String>>, iterm2: bool) {
+    if iterm2 && let Err(err) = execute!(stdout(), RequestAttention) {
+        debug!("Failed to request iTerm2 attention: {err}");
+    }
+    let Some((program, args)) = command.as_deref().and_then(<[String]>::split_first) else {
+        return;
+    };
+    let spawned = tokio::process::Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    match spawned {
+        Ok(mut child) => {
+            let program = program.clone();
+            tokio::spawn(async move {
+                match child.wait().await {
+                    Ok(status) if !status.success() => {
+                        debug!("Focus command {program:?} exited with {status}");
+                    }
+                    Ok(_) => {}
+                    Err(err) => debug!("Failed to wait for focus command {program:?}: {err}"),
+                }
+            });
+        }
+        Err(err) => debug!("Failed to run focus command {program:?}: {err}"),
+    }
+}
+
 fn show_osc9_notification(title: &str, message: &str) -> io::Result<()> {
     let body = if title.is_empty() {
         message.to_string()
@@ -215,6 +375,54 @@ mod tests {
         assert_eq!(state.transport, EffectiveTransport::Off);
     }
 
+    #[test]
+    fn focus_command_placeholders_expand_per_argument() {
+        let mut prefs = Preferences::default();
+        prefs.ui.notifications.focus_command =
+            Some("tmux display-message 'approve {session_title}' -t {pane}".to_string());
+        let hook = AttentionHook::from_preferences(&prefs.ui.notifications);
+        let args = hook.command.expect("command should parse");
+
+        let expanded = expand_args(
+            &args,
+            &[
+                ("session_id", "abc"),
+                ("session_title", "Fix login; rm -rf /"),
+                ("pane", "%3"),
+            ],
+        );
+        assert_eq!(
+            expanded,
+            vec![
+                "tmux",
+                "display-message",
+                "approve Fix login; rm -rf /",
+                "-t",
+                "%3"
+            ]
+        );
+    }
+
+    #[test]
+    fn attention_requests_are_rate_limited() {
+        let mut prefs = Preferences::default();
+        prefs.ui.notifications.iterm2_attention = true;
+        let mut hook = AttentionHook::from_preferences(&prefs.ui.notifications);
+        assert!(hook.is_enabled());
+
+        let start = Instant::now();
+        assert!(hook.try_acquire(start));
+        assert!(!hook.try_acquire(start + Duration::from_secs(1)));
+        assert!(hook.try_acquire(start + ATTENTION_INTERVAL));
+    }
+
+    #[test]
+    fn invalid_focus_command_is_ignored() {
+        let mut prefs = Preferences::default();
+        prefs.ui.notifications.focus_command = Some("tmux 'unterminated".to_string());
+        assert!(!AttentionHook::from_preferences(&prefs.ui.notifications).is_enabled());
+    }
+
     #[test]
     fn event_body_formats_approval() {
         let body = NotificationEvent::ToolApprovalRequested {
@@ -6,13 +6,14 @@ use crate::tui::{
     state::chat_store::ChatStore,
     theme::{Component, Theme},
     widgets::{
-        ChatBlock, ChatListState, ChatRenderable, DynamicChatWidget, ScrollTarget, ViewMode,
-        VisibleRange,
+        ChatBlock, ChatListState, ChatRenderable, CodeWrap, DynamicChatWidget, ScrollTarget,
+        ViewMode, VisibleRange,
     },
 };
 use ratatui::{
     Frame,
     layout::Rect,
+    style::Modifier,
     text::{Line, Span},
 };
 use std::borrow::Cow;
@@ -20,6 +21,7 @@ use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use steer_grpc::client_api::{AssistantContent, Message, MessageData, UserContent};
 use steer_tools::{ToolResult, schema::ToolCall};
+use unicode_width::UnicodeWidthStr;
 
 /// Flattened item types for 1:1 widget mapping
 #[derive(Debug, Clone)]
@@ -31,6 +33,7 @@ enum FlattenedItem {
         is_edited: bool,
         is_editing: bool,
         is_compaction_summary: bool,
+        is_streaming: bool,
     },
     /// Tool call coupled with its result
     ToolInteraction {
@@ -68,12 +71,14 @@ impl FlattenedItem {
                 is_edited,
                 is_editing,
                 is_compaction_summary,
+                is_streaming,
                 ..
             } => {
                 hash_message_content(message, &mut hasher);
                 is_edited.hash(&mut hasher);
                 is_editing.hash(&mut hasher);
                 is_compaction_summary.hash(&mut hasher);
+                is_streaming.hash(&mut hasher);
             }
             FlattenedItem::ToolInteraction { call, result, .. } => {
                 call.id.hash(&mut hasher);
@@ -182,6 +187,9 @@ pub struct ChatViewport {
     last_width: u16,        // for invalidation on resize
     last_spacing: u16,      // for invalidation when theme spacing changes
     last_rebuild_mode: ViewMode, // mode used for the last segment rebuild
+    show_reasoning: bool,   // render reasoning text, or only a placeholder
+    show_previous_attempts: bool, // include branches replaced by /retry
+    code_wraps: HashMap<String, CodeWrap>, // per-item code layout, when not soft-wrapped
     dirty: bool,            // set by caller when messages change
 }
 
@@ -209,6 +217,9 @@ impl ChatViewport {
             last_width: 0,
             last_spacing: 0,
             last_rebuild_mode: ViewMode::Compact,
+            show_reasoning: true,
+            show_previous_attempts: false,
+            code_wraps: HashMap::new(),
             dirty: true,
         }
     }
@@ -218,6 +229,27 @@ impl ChatViewport {
         self.dirty = true;
     }
 
+    /// Show or hide reasoning text; widgets are recreated when this changes.
+    pub fn set_show_reasoning(&mut self, show_reasoning: bool) {
+        if self.show_reasoning != show_reasoning {
+            self.show_reasoning = show_reasoning;
+            self.items.clear();
+            self.dirty = true;
+        }
+    }
+
+    /// Show or hide earlier attempts at the last prompt alongside the active branch.
+    pub fn set_show_previous_attempts(&mut self, show: bool) {
+        if self.show_previous_attempts != show {
+            self.show_previous_attempts = show;
+            self.dirty = true;
+        }
+    }
+
+    pub fn show_previous_attempts(&self) -> bool {
+        self.show_previous_attempts
+    }
+
     /// Get mutable reference to the chat list state for key handlers
     pub fn state_mut(&mut self) -> &mut ChatListState {
         &mut self.state
@@ -228,6 +260,64 @@ impl ChatViewport {
         &self.state
     }
 
+    /// Switch the code in view between soft wrap and horizontal scrolling.
+    /// Returns the new layout, or `None` if no code in view is too wide.
+    pub fn toggle_code_wrap(&mut self) -> Option<CodeWrap> {
+        let idx = self.focused_code_item()?;
+        let wrap = match self.code_wrap_for(idx) {
+            CodeWrap::Soft => CodeWrap::Scroll { offset: 0 },
+            CodeWrap::Scroll { .. } => CodeWrap::Soft,
+        };
+        self.set_item_code_wrap(idx, wrap);
+        Some(wrap)
+    }
+
+    /// Scroll the code in view sideways by `delta` columns. Returns `false`
+    /// when the code in view is soft-wrapped rather than scrollable.
+    pub fn scroll_code(&mut self, delta: isize) -> bool {
+        let Some(idx) = self.focused_code_item() else {
+            return false;
+        };
+        let CodeWrap::Scroll { offset } = self.code_wrap_for(idx) else {
+            return false;
+        };
+        let max_offset = self.items[idx].widget.code_overflow().unwrap_or(0);
+        let offset = offset.saturating_add_signed(delta).min(max_offset);
+        self.set_item_code_wrap(idx, CodeWrap::Scroll { offset });
+        true
+    }
+
+    /// The lowest item in view whose code is wider than the view, as that is
+    /// the one the reader most recently scrolled to.
+    fn focused_code_item(&self) -> Option<usize> {
+        let range = self.state.visible_range.as_ref()?;
+        (range.first_index..=range.last_index).rev().find(|&idx| {
+            self.items
+                .get(idx)
+                .and_then(|item| item.widget.code_overflow())
+                .is_some_and(|overflow| overflow > 0)
+        })
+    }
+
+    fn code_wrap_for(&self, idx: usize) -> CodeWrap {
+        self.code_wraps
+            .get(&self.items[idx].id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn set_item_code_wrap(&mut self, idx: usize, wrap: CodeWrap) {
+        let item = &mut self.items[idx];
+        if wrap == CodeWrap::default() {
+            self.code_wraps.remove(&item.id);
+        } else {
+            self.code_wraps.insert(item.id.clone(), wrap);
+        }
+        item.widget.set_code_wrap(wrap);
+        item.cached_heights.invalidate(true, true);
+        self.dirty = true;
+    }
+
     /// Diff raw ChatItems; rebuild only when dirty / width or mode changed.
     pub fn rebuild(
         &mut self,
@@ -352,7 +442,10 @@ impl ChatViewport {
             let lineage = build_lineage_set(active_id, chat_store);
 
             // Include pre-compaction history by stitching in ancestors of compaction heads.
-            let visible_messages = build_visible_message_set(&lineage, chat_store);
+            let mut visible_messages = build_visible_message_set(&lineage, chat_store);
+            if self.show_previous_attempts {
+                visible_messages.extend(chat_store.previous_attempt_ids());
+            }
 
             // Filter items to only show those in the visible message set or attached to it.
             let filtered_items = raw_items
@@ -391,8 +484,22 @@ impl ChatViewport {
 
             if let Some(mut existing) = existing_widgets.remove(&item_id) {
                 if existing.content_hash != content_hash {
-                    existing.widget =
-                        create_widget_for_flattened_item(&flattened_item, theme, false, 0);
+                    // Streaming deltas land here; carry the text layout over so
+                    // only the changed tail of the message is laid out again.
+                    let text_layout = existing.widget.take_text_layout();
+                    existing.widget = create_widget_for_flattened_item(
+                        &flattened_item,
+                        theme,
+                        self.show_reasoning,
+                        false,
+                        0,
+                    );
+                    if let Some(text_layout) = text_layout {
+                        existing.widget.restore_text_layout(text_layout);
+                    }
+                    if let Some(&wrap) = self.code_wraps.get(&item_id) {
+                        existing.widget.set_code_wrap(wrap);
+                    }
                     existing.cached_heights.invalidate(true, true);
                 } else if width_changed || mode_changed {
                     existing
@@ -404,7 +511,16 @@ impl ChatViewport {
                 new_items.push(existing);
             } else {
                 // Create new widget
-                let widget = create_widget_for_flattened_item(&flattened_item, theme, false, 0);
+                let mut widget = create_widget_for_flattened_item(
+                    &flattened_item,
+                    theme,
+                    self.show_reasoning,
+                    false,
+                    0,
+                );
+                if let Some(&wrap) = self.code_wraps.get(&item_id) {
+                    widget.set_code_wrap(wrap);
+                }
                 let widget_item = WidgetItem {
                     id: item_id,
                     item: flattened_item,
@@ -512,6 +628,7 @@ impl ChatViewport {
                                     is_editing: false,
                                     is_compaction_summary: chat_store
                                         .is_compaction_summary(row.id()),
+                                    is_streaming: chat_store.is_streaming(row.id()),
                                 });
                             }
 
@@ -546,6 +663,7 @@ impl ChatViewport {
                                 is_edited,
                                 is_editing,
                                 is_compaction_summary: false,
+                                is_streaming: false,
                             });
                         }
                     }
@@ -614,6 +732,9 @@ impl ChatViewport {
         }
 
         self.state.offset = self.state.offset.min(max_offset);
+        if self.state.offset >= max_offset {
+            self.state.clear_unseen_output();
+        }
 
         let viewport_bottom = self.state.offset.saturating_add(viewport_height);
         if viewport_height == 0 || self.state.offset >= viewport_bottom {
@@ -765,9 +886,35 @@ impl ChatViewport {
 
             y = y.saturating_add(row_height);
         }
+
+        if self.state.has_unseen_output() {
+            render_new_output_indicator(f, area, theme);
+        }
     }
 }
 
+/// Shown at the bottom of the chat while new output sits below a scrolled-up viewport.
+const NEW_OUTPUT_INDICATOR: &str = " new output ↓ ";
+
+/// Draw the new output indicator over the right end of the chat's last row.
+fn render_new_output_indicator(f: &mut Frame, area: Rect, theme: &Theme) {
+    let width = NEW_OUTPUT_INDICATOR.width() as u16;
+    if area.height == 0 || area.width < width {
+        return;
+    }
+    let style = theme
+        .style(Component::NoticeInfo)
+        .add_modifier(Modifier::REVERSED);
+    let x = area.x + area.width - width;
+    let y = area.y + area.height - 1;
+    f.buffer_mut().set_line(
+        x,
+        y,
+        &Line::from(Span::styled(NEW_OUTPUT_INDICATOR, style)),
+        width,
+    );
+}
+
 fn hash_message_content(message: &Message, hasher: &mut impl Hasher) {
     match &message.data {
         MessageData::User { content } => {
@@ -777,6 +924,10 @@ fn hash_message_content(message: &Message, hasher: &mut impl Hasher) {
                     UserContent::Image { image } => {
                         image.mime_type.hash(hasher);
                     }
+                    UserContent::Attachment { attachment } => {
+                        attachment.path.hash(hasher);
+                        attachment.bytes.hash(hasher);
+                    }
                     UserContent::CommandExecution {
                         command,
                         stdout,
@@ -825,6 +976,7 @@ fn hash_message_content(message: &Message, hasher: &mut impl Hasher) {
 fn create_widget_for_flattened_item(
     item: &FlattenedItem,
     theme: &Theme,
+    show_reasoning: bool,
     _is_hovered: bool,
     _spinner_state: usize,
 ) -> Box<dyn ChatRenderable + Send + Sync> {
@@ -839,13 +991,16 @@ fn create_widget_for_flattened_item(
             is_edited,
             is_editing,
             is_compaction_summary,
+            is_streaming,
             ..
         } => {
             let body = Box::new(
                 crate::tui::widgets::chat_widgets::message_widget::MessageWidget::new(
                     message.clone(),
                 )
-                .with_edited_indicator(*is_edited),
+                .with_edited_indicator(*is_edited)
+                .with_show_reasoning(show_reasoning)
+                .with_streaming(*is_streaming),
             );
 
             match &message.data {
@@ -941,6 +1096,16 @@ pub fn format_command_response(resp: &CommandResponse) -> String {
             CompactResult::Cancelled => "Compact cancelled.".to_string(),
             CompactResult::InsufficientMessages => "Not enough messages to compact.".to_string(),
         },
+        CommandResponse::Review(report) => format!(
+            "Review found {} finding(s) in {} file(s).",
+            report.findings.len(),
+            report.files_reviewed
+        ),
+        CommandResponse::CommitDraft(draft) => format!(
+            "Proposed commit of {} file(s):\n\n{}",
+            draft.files.len(),
+            draft.message
+        ),
     }
 }
 
@@ -1559,6 +1724,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_new_output_indicator_only_while_scrolled_up() {
+        let mut viewport = ChatViewport::new();
+        let theme = Theme::default();
+        let messages = (0..6)
+            .map(|idx| create_assistant_message(&format!("Message {idx}"), &format!("msg{idx}")))
+            .collect::<Vec<_>>();
+        let chat_store = create_test_chat_store();
+
+        let backend = TestBackend::new(40, 3);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut draw = |viewport: &mut ChatViewport| {
+            terminal
+                .draw(|f| {
+                    let area = f.area();
+                    viewport.rebuild_for_test(
+                        &messages.iter().collect::<Vec<_>>(),
+                        area.width,
+                        ViewMode::Compact,
+                        &theme,
+                        &chat_store,
+                        None,
+                        1,
+                    );
+                    viewport.render(f, area, &theme);
+                })
+                .unwrap();
+            let buffer = terminal.backend().buffer();
+            (0..40)
+                .map(|x| buffer.cell((x, 2)).map_or(" ", |c| c.symbol()))
+                .collect::<String>()
+        };
+
+        viewport.state_mut().scroll_to_top();
+        viewport.state_mut().mark_unseen_output();
+        let last_line = draw(&mut viewport);
+        assert!(
+            last_line.contains("new output ↓"),
+            "Indicator should show while scrolled up: {last_line:?}"
+        );
+
+        viewport.state_mut().scroll_to_bottom();
+        let last_line = draw(&mut viewport);
+        assert!(!last_line.contains("new output"));
+        assert!(!viewport.state().has_unseen_output());
+    }
+
     #[test]
     fn test_gap_rows_in_measure_visible_rows() {
         let mut viewport = ChatViewport::new();
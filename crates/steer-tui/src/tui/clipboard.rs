@@ -0,0 +1,282 @@
+//! Copying text out of the chat.
+//!
+//! Copies are sent to the terminal with OSC 52, which also works over SSH
+//! (inside tmux it needs `set -g set-clipboard on`). Local sessions also set
+//! the system clipboard through `arboard`, for terminals that ignore OSC 52.
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use base64::Engine as _;
+use pulldown_cmark::{Event, Parser, Tag};
+use steer_grpc::client_api::{AssistantContent, Message, MessageData, UserContent};
+use tracing::debug;
+
+/// Tool parameters that name a file.
+const FILE_PARAMETER_KEYS: [&str; 3] = ["file_path", "path", "notebook_path"];
+
+pub(crate) struct Clipboard {
+    remote: bool,
+    system: Option<arboard::Clipboard>,
+}
+
+impl Clipboard {
+    pub(crate) fn new() -> Self {
+        let remote =
+            std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some();
+        Self {
+            remote,
+            system: None,
+        }
+    }
+
+    /// Copies `text`, failing only when neither OSC 52 nor the system
+    /// clipboard could be written.
+    pub(crate) fn copy(&mut self, text: &str) -> Result<(), String> {
+        let mut stdout = io::stdout();
+        let osc = stdout
+            .write_all(osc52_sequence(text).as_bytes())
+            .and_then(|()| stdout.flush());
+        if self.remote {
+            return osc.map_err(|e| format!("Failed to copy: {e}"));
+        }
+
+        // arboard's X11 and Wayland backends serve the clipboard for as long
+        // as the handle lives, so it is kept rather than recreated per copy.
+        if self.system.is_none() {
+            match arboard::Clipboard::new() {
+                Ok(clipboard) => self.system = Some(clipboard),
+                Err(err) => debug!(target: "tui.clipboard", "System clipboard unavailable: {err}"),
+            }
+        }
+        let system = match self.system.as_mut() {
+            Some(clipboard) => clipboard
+                .set_text(text.to_string())
+                .map_err(|e| e.to_string()),
+            None => Err("no system clipboard".to_string()),
+        };
+
+        match (osc, system) {
+            (Err(osc_err), Err(system_err)) => Err(format!(
+                "Failed to copy: {osc_err}; system clipboard: {system_err}"
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The OSC 52 sequence that sets the clipboard selection to `text`.
+fn osc52_sequence(text: &str) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    format!("\x1b]52;c;{encoded}\x07")
+}
+
+/// The text of a message as it was written, before rendering.
+pub(crate) fn message_markdown(message: &Message) -> String {
+    match &message.data {
+        MessageData::User { .. } => message.content_string(),
+        MessageData::Assistant { content } => content
+            .iter()
+            .filter_map(|block| match block {
+                AssistantContent::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        MessageData::Tool { .. } => String::new(),
+    }
+}
+
+/// The contents of each fenced or indented code block in `markdown`.
+pub(crate) fn code_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current: Option<String> = None;
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => current = Some(String::new()),
+            Event::Text(text) => {
+                if let Some(block) = current.as_mut() {
+                    block.push_str(&text);
+                }
+            }
+            Event::End(Tag::CodeBlock(_)) => {
+                if let Some(block) = current.take() {
+                    blocks.push(block);
+                }
+            }
+            _ => {}
+        }
+    }
+    blocks
+}
+
+/// Files a message refers to: paths passed to tools, `@` mentions and
+/// attached files, in order of appearance and without duplicates.
+pub(crate) fn file_references(message: &Message) -> Vec<String> {
+    let mut references: Vec<String> = Vec::new();
+    let mut push = |reference: &str| {
+        if !reference.is_empty() && !references.iter().any(|r| r == reference) {
+            references.push(reference.to_string());
+        }
+    };
+
+    match &message.data {
+        MessageData::User { content } => {
+            for block in content {
+                match block {
+                    UserContent::Text { text } => super::file_mentions(text).for_each(&mut push),
+                    UserContent::Attachment { attachment } => push(&attachment.path),
+                    _ => {}
+                }
+            }
+        }
+        MessageData::Assistant { content } => {
+            for block in content {
+                if let AssistantContent::ToolCall { tool_call, .. } = block {
+                    for key in FILE_PARAMETER_KEYS {
+                        if let Some(path) = tool_call.parameters.get(key).and_then(|v| v.as_str()) {
+                            push(path);
+                        }
+                    }
+                }
+            }
+        }
+        MessageData::Tool { .. } => {}
+    }
+    references
+}
+
+/// Resolves a file reference against the workspace root.
+pub(crate) fn absolute_path(reference: &str, root: &Path) -> PathBuf {
+    let path = Path::new(reference);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        root.join(path)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CycleKind {
+    CodeBlock,
+    FilePath,
+}
+
+/// Which code block or file path of a message was copied last, so that
+/// pressing the same key again moves on to the next one.
+#[derive(Debug, Default)]
+pub(crate) struct CopyCycle {
+    last: Option<(String, CycleKind, usize)>,
+}
+
+impl CopyCycle {
+    /// The index to copy next out of `len` items.
+    pub(crate) fn advance(&mut self, message_id: &str, kind: CycleKind, len: usize) -> usize {
+        let index = match &self.last {
+            Some((id, last_kind, index)) if id == message_id && *last_kind == kind => {
+                (index + 1) % len.max(1)
+            }
+            _ => 0,
+        };
+        self.last = Some((message_id.to_string(), kind, index));
+        index
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use steer_grpc::client_api::ThoughtContent;
+    use steer_tools::ToolCall;
+
+    fn assistant(content: Vec<AssistantContent>) -> Message {
+        Message {
+            data: MessageData::Assistant { content },
+            timestamp: 0,
+            id: "a1".to_string(),
+            parent_message_id: None,
+        }
+    }
+
+    #[test]
+    fn osc52_sequence_base64_encodes_text() {
+        assert_eq!(osc52_sequence("hi"), "\x1b]52;c;aGk=\x07");
+    }
+
+    #[test]
+    fn message_markdown_keeps_assistant_text_only() {
+        let message = assistant(vec![
+            AssistantContent::Thought {
+                thought: ThoughtContent::Simple {
+                    text: "hmm".to_string(),
+                },
+            },
+            AssistantContent::Text {
+                text: "# Title".to_string(),
+            },
+            AssistantContent::Text {
+                text: "Body".to_string(),
+            },
+        ]);
+        assert_eq!(message_markdown(&message), "# Title\n\nBody");
+    }
+
+    #[test]
+    fn code_blocks_returns_block_contents_in_order() {
+        let markdown = "Run:\n\n```sh\ncargo test\n```\n\nthen\n\n```rust\nfn main() {}\n```\n";
+        assert_eq!(
+            code_blocks(markdown),
+            vec!["cargo test\n".to_string(), "fn main() {}\n".to_string()]
+        );
+        assert!(code_blocks("no code here, just `inline`").is_empty());
+    }
+
+    #[test]
+    fn file_references_collects_tool_paths_once() {
+        let call = |name: &str, parameters: serde_json::Value| AssistantContent::ToolCall {
+            tool_call: ToolCall {
+                name: name.to_string(),
+                parameters,
+                id: name.to_string(),
+            },
+            thought_signature: None,
+        };
+        let message = assistant(vec![
+            call(
+                "read_file",
+                serde_json::json!({ "file_path": "src/lib.rs" }),
+            ),
+            call("ls", serde_json::json!({ "path": "/tmp" })),
+            call(
+                "edit_file",
+                serde_json::json!({ "file_path": "src/lib.rs" }),
+            ),
+            call("bash", serde_json::json!({ "command": "ls" })),
+        ]);
+        assert_eq!(file_references(&message), vec!["src/lib.rs", "/tmp"]);
+        assert_eq!(
+            absolute_path("src/lib.rs", Path::new("/work")),
+            PathBuf::from("/work/src/lib.rs")
+        );
+        assert_eq!(
+            absolute_path("/tmp", Path::new("/work")),
+            PathBuf::from("/tmp")
+        );
+    }
+
+    #[test]
+    fn copy_cycle_wraps_and_restarts_for_other_messages() {
+        let mut cycle = CopyCycle::default();
+        assert_eq!(cycle.advance("m1", CycleKind::CodeBlock, 2), 0);
+        assert_eq!(cycle.advance("m1", CycleKind::CodeBlock, 2), 1);
+        assert_eq!(cycle.advance("m1", CycleKind::CodeBlock, 2), 0);
+        assert_eq!(cycle.advance("m1", CycleKind::FilePath, 3), 0);
+        assert_eq!(cycle.advance("m2", CycleKind::FilePath, 3), 0);
+        cycle.reset();
+        assert_eq!(cycle.advance("m2", CycleKind::FilePath, 3), 0);
+    }
+}
@@ -23,6 +23,8 @@ pub enum TuiCommand {
     New,
     /// Reload files in the TUI
     ReloadFiles,
+    /// Re-read the server's model catalogs
+    ReloadModels,
     /// Change or list themes
     Theme(Option<String>),
     /// Launch authentication setup
@@ -35,6 +37,18 @@ pub enum TuiCommand {
     Mcp,
     /// Show workspace status
     Workspace(Option<String>),
+    /// Show the token breakdown of the next request, optionally for one segment
+    Context(Option<String>),
+    /// Show or act on the session's isolated worktree
+    Isolation(Option<String>),
+    /// Draft the answer to the next message several times and pick one
+    BestOf(Option<String>),
+    /// Show where the last operation spent its time
+    Timing,
+    /// Attach a local file to the next message
+    Attach(Option<String>),
+    /// Show or edit the session memory
+    Memory(Option<String>),
     /// Custom user-defined command
     Custom(CustomCommand),
 }
@@ -46,12 +60,19 @@ pub enum TuiCommand {
 pub enum TuiCommandType {
     New,
     ReloadFiles,
+    ReloadModels,
     Theme,
     Auth,
     Help,
     EditingMode,
     Mcp,
     Workspace,
+    Context,
+    Isolation,
+    BestOf,
+    Timing,
+    Attach,
+    Memory,
 }
 
 impl TuiCommandType {
@@ -59,12 +80,19 @@ impl TuiCommandType {
         match self {
             TuiCommandType::New => self.to_string(),
             TuiCommandType::ReloadFiles => self.to_string(),
+            TuiCommandType::ReloadModels => self.to_string(),
             TuiCommandType::Theme => self.to_string(),
             TuiCommandType::Auth => self.to_string(),
             TuiCommandType::Help => self.to_string(),
             TuiCommandType::EditingMode => self.to_string(),
             TuiCommandType::Mcp => self.to_string(),
             TuiCommandType::Workspace => self.to_string(),
+            TuiCommandType::Context => self.to_string(),
+            TuiCommandType::Isolation => self.to_string(),
+            TuiCommandType::BestOf => "bestof".to_string(),
+            TuiCommandType::Timing => self.to_string(),
+            TuiCommandType::Attach => self.to_string(),
+            TuiCommandType::Memory => self.to_string(),
         }
     }
 
@@ -72,12 +100,21 @@ impl TuiCommandType {
         match self {
             TuiCommandType::New => "Start a new conversation session",
             TuiCommandType::ReloadFiles => "Reload file cache in the TUI",
+            TuiCommandType::ReloadModels => "Re-read model catalogs on the server",
             TuiCommandType::Theme => "Change or list available themes",
             TuiCommandType::Auth => "Manage authentication settings",
             TuiCommandType::Help => "Show help information",
             TuiCommandType::EditingMode => "Switch between editing modes (simple/vim)",
             TuiCommandType::Mcp => "Show MCP server connection status",
             TuiCommandType::Workspace => "Show workspace status",
+            TuiCommandType::Context => "Show the token breakdown of the next request",
+            TuiCommandType::Isolation => "Show, merge, or discard the session's isolated worktree",
+            TuiCommandType::BestOf => {
+                "Draft several answers to the next message side by side and pick one"
+            }
+            TuiCommandType::Timing => "Show where the last operation spent its time",
+            TuiCommandType::Attach => "Attach a text file or image to the next message",
+            TuiCommandType::Memory => "Show or edit what the agent remembers for this session",
         }
     }
 
@@ -85,12 +122,27 @@ impl TuiCommandType {
         match self {
             TuiCommandType::New => format!("/{}", self.command_name()),
             TuiCommandType::ReloadFiles => format!("/{}", self.command_name()),
+            TuiCommandType::ReloadModels => format!("/{}", self.command_name()),
             TuiCommandType::Theme => format!("/{} [theme_name]", self.command_name()),
             TuiCommandType::Auth => format!("/{}", self.command_name()),
             TuiCommandType::Help => format!("/{} [command]", self.command_name()),
             TuiCommandType::EditingMode => format!("/{} [simple|vim]", self.command_name()),
             TuiCommandType::Mcp => format!("/{}", self.command_name()),
             TuiCommandType::Workspace => format!("/{} [workspace_id]", self.command_name()),
+            TuiCommandType::Context => format!(
+                "/{} [system|environment|tools|messages]",
+                self.command_name()
+            ),
+            TuiCommandType::Isolation => format!(
+                "/{} [status|merge|patch|branch|discard]",
+                self.command_name()
+            ),
+            TuiCommandType::BestOf => format!("/{} [2-4|off]", self.command_name()),
+            TuiCommandType::Timing => format!("/{}", self.command_name()),
+            TuiCommandType::Attach => format!("/{} <path>", self.command_name()),
+            TuiCommandType::Memory => {
+                format!("/{} [set <key> <fact>|forget <key>]", self.command_name())
+            }
         }
     }
 }
@@ -101,6 +153,9 @@ pub enum CoreCommandType {
     Model,
     Agent,
     Compact,
+    Retry,
+    Review,
+    Commit,
 }
 
 impl CoreCommandType {
@@ -109,6 +164,9 @@ impl CoreCommandType {
             CoreCommandType::Model => self.to_string(),
             CoreCommandType::Agent => self.to_string(),
             CoreCommandType::Compact => self.to_string(),
+            CoreCommandType::Retry => self.to_string(),
+            CoreCommandType::Review => self.to_string(),
+            CoreCommandType::Commit => self.to_string(),
         }
     }
 
@@ -117,6 +175,13 @@ impl CoreCommandType {
             CoreCommandType::Model => "Show or change the current model",
             CoreCommandType::Agent => "Show or switch the primary agent mode",
             CoreCommandType::Compact => "Summarize the current conversation",
+            CoreCommandType::Retry => {
+                "Regenerate the response to the last message, optionally with another model"
+            }
+            CoreCommandType::Review => "Review a diff and list findings by severity",
+            CoreCommandType::Commit => {
+                "Draft and create a commit from the files this session edited"
+            }
         }
     }
 
@@ -125,6 +190,12 @@ impl CoreCommandType {
             CoreCommandType::Model => format!("/{} [model_name]", self.command_name()),
             CoreCommandType::Agent => format!("/{} [mode]", self.command_name()),
             CoreCommandType::Compact => format!("/{}", self.command_name()),
+            CoreCommandType::Retry => format!("/{} [model_name]", self.command_name()),
+            CoreCommandType::Review => format!(
+                "/{} [staged|unstaged|branch:<name>|<from>..<to>]",
+                self.command_name()
+            ),
+            CoreCommandType::Commit => format!("/{}", self.command_name()),
         }
     }
 
@@ -147,6 +218,18 @@ impl CoreCommandType {
                 Some(CoreCommand::Agent { target })
             }
             CoreCommandType::Compact => Some(CoreCommand::Compact),
+            CoreCommandType::Retry => {
+                let model = if args.is_empty() {
+                    None
+                } else {
+                    Some(args.join(" "))
+                };
+                Some(CoreCommand::Retry { model })
+            }
+            CoreCommandType::Review => Some(CoreCommand::Review {
+                target: args.first().map(|target| (*target).to_string()),
+            }),
+            CoreCommandType::Commit => Some(CoreCommand::Commit),
         }
     }
 }
@@ -171,6 +254,7 @@ impl TuiCommand {
                 return match cmd_type {
                     TuiCommandType::New => Ok(TuiCommand::New),
                     TuiCommandType::ReloadFiles => Ok(TuiCommand::ReloadFiles),
+                    TuiCommandType::ReloadModels => Ok(TuiCommand::ReloadModels),
                     TuiCommandType::Theme => {
                         let theme_name = parts.get(1).map(|s| (*s).to_string());
                         Ok(TuiCommand::Theme(theme_name))
@@ -189,6 +273,33 @@ impl TuiCommand {
                         let workspace_id = parts.get(1).map(|s| (*s).to_string());
                         Ok(TuiCommand::Workspace(workspace_id))
                     }
+                    TuiCommandType::Context => {
+                        let segment = parts.get(1).map(|s| (*s).to_string());
+                        Ok(TuiCommand::Context(segment))
+                    }
+                    TuiCommandType::Isolation => {
+                        let action = parts.get(1).map(|s| (*s).to_string());
+                        Ok(TuiCommand::Isolation(action))
+                    }
+                    TuiCommandType::BestOf => {
+                        let count = parts.get(1).map(|s| (*s).to_string());
+                        Ok(TuiCommand::BestOf(count))
+                    }
+                    TuiCommandType::Timing => Ok(TuiCommand::Timing),
+                    TuiCommandType::Attach => {
+                        // Paths may contain spaces, so keep the rest of the line.
+                        let path = command[cmd_name.len()..].trim();
+                        Ok(TuiCommand::Attach(
+                            (!path.is_empty()).then(|| path.to_string()),
+                        ))
+                    }
+                    TuiCommandType::Memory => {
+                        // Facts may contain spaces, so keep the rest of the line.
+                        let args = command[cmd_name.len()..].trim();
+                        Ok(TuiCommand::Memory(
+                            (!args.is_empty()).then(|| args.to_string()),
+                        ))
+                    }
                 };
             }
         }
@@ -200,6 +311,7 @@ impl TuiCommand {
         match self {
             TuiCommand::New => TuiCommandType::New.command_name().clone(),
             TuiCommand::ReloadFiles => TuiCommandType::ReloadFiles.command_name().clone(),
+            TuiCommand::ReloadModels => TuiCommandType::ReloadModels.command_name().clone(),
             TuiCommand::Theme(None) => TuiCommandType::Theme.command_name().clone(),
             TuiCommand::Theme(Some(name)) => {
                 format!("{} {}", TuiCommandType::Theme.command_name(), name)
@@ -222,6 +334,27 @@ impl TuiCommand {
                     workspace_id
                 )
             }
+            TuiCommand::Context(None) => TuiCommandType::Context.command_name().clone(),
+            TuiCommand::Context(Some(segment)) => {
+                format!("{} {}", TuiCommandType::Context.command_name(), segment)
+            }
+            TuiCommand::Isolation(None) => TuiCommandType::Isolation.command_name().clone(),
+            TuiCommand::Isolation(Some(action)) => {
+                format!("{} {}", TuiCommandType::Isolation.command_name(), action)
+            }
+            TuiCommand::BestOf(None) => TuiCommandType::BestOf.command_name().clone(),
+            TuiCommand::BestOf(Some(count)) => {
+                format!("{} {}", TuiCommandType::BestOf.command_name(), count)
+            }
+            TuiCommand::Timing => TuiCommandType::Timing.command_name().clone(),
+            TuiCommand::Attach(None) => TuiCommandType::Attach.command_name().clone(),
+            TuiCommand::Attach(Some(path)) => {
+                format!("{} {}", TuiCommandType::Attach.command_name(), path)
+            }
+            TuiCommand::Memory(None) => TuiCommandType::Memory.command_name().clone(),
+            TuiCommand::Memory(Some(args)) => {
+                format!("{} {}", TuiCommandType::Memory.command_name(), args)
+            }
             TuiCommand::Custom(cmd) => cmd.name().to_string(),
         }
     }
@@ -325,6 +458,40 @@ mod tests {
             AppCommand::parse("/workspace").unwrap(),
             AppCommand::Tui(TuiCommand::Workspace(None))
         ));
+        assert_eq!(
+            AppCommand::parse("/context tools").unwrap(),
+            AppCommand::Tui(TuiCommand::Context(Some("tools".to_string())))
+        );
+        assert_eq!(
+            AppCommand::parse("/isolation merge").unwrap(),
+            AppCommand::Tui(TuiCommand::Isolation(Some("merge".to_string())))
+        );
+        assert_eq!(
+            AppCommand::parse("/bestof 3").unwrap(),
+            AppCommand::Tui(TuiCommand::BestOf(Some("3".to_string())))
+        );
+        assert_eq!(
+            AppCommand::parse("/memory set test-command cargo nextest run").unwrap(),
+            AppCommand::Tui(TuiCommand::Memory(Some(
+                "set test-command cargo nextest run".to_string()
+            )))
+        );
+        assert_eq!(
+            AppCommand::parse("/timing").unwrap(),
+            AppCommand::Tui(TuiCommand::Timing)
+        );
+        assert_eq!(
+            AppCommand::parse("/reload-models").unwrap(),
+            AppCommand::Tui(TuiCommand::ReloadModels)
+        );
+        assert_eq!(
+            AppCommand::parse("/attach ~/My Notes/todo.md").unwrap(),
+            AppCommand::Tui(TuiCommand::Attach(Some("~/My Notes/todo.md".to_string())))
+        );
+        assert_eq!(
+            AppCommand::parse("/attach").unwrap(),
+            AppCommand::Tui(TuiCommand::Attach(None))
+        );
     }
 
     #[test]
@@ -341,6 +508,30 @@ mod tests {
             AppCommand::parse("/compact").unwrap(),
             AppCommand::Core(CoreCommand::Compact)
         ));
+        assert!(matches!(
+            AppCommand::parse("/retry").unwrap(),
+            AppCommand::Core(CoreCommand::Retry { model: None })
+        ));
+        assert_eq!(
+            AppCommand::parse("/retry opus").unwrap(),
+            AppCommand::Core(CoreCommand::Retry {
+                model: Some("opus".to_string())
+            })
+        );
+        assert_eq!(
+            AppCommand::parse("/review branch:main").unwrap(),
+            AppCommand::Core(CoreCommand::Review {
+                target: Some("branch:main".to_string())
+            })
+        );
+        assert_eq!(
+            AppCommand::parse("/review").unwrap(),
+            AppCommand::Core(CoreCommand::Review { target: None })
+        );
+        assert_eq!(
+            AppCommand::parse("/commit").unwrap(),
+            AppCommand::Core(CoreCommand::Commit)
+        );
         assert!(matches!(
             AppCommand::parse("/agent").unwrap(),
             AppCommand::Core(CoreCommand::Agent { .. })
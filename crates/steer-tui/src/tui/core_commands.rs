@@ -2,13 +2,15 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
 
-pub use steer_grpc::client_api::CompactResult;
+pub use steer_grpc::client_api::{CommitDraft, CompactResult, ReviewReport};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "response_type", rename_all = "snake_case")]
 pub enum CommandResponse {
     Text(String),
     Compact(CompactResult),
+    Review(ReviewReport),
+    CommitDraft(CommitDraft),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -17,6 +19,9 @@ pub enum CoreCommandType {
     Model { target: Option<String> },
     Agent { target: Option<String> },
     Compact,
+    Retry { model: Option<String> },
+    Review { target: Option<String> },
+    Commit,
 }
 
 impl CoreCommandType {
@@ -49,6 +54,18 @@ impl CoreCommandType {
                 Ok(CoreCommandType::Agent { target })
             }
             "compact" => Ok(CoreCommandType::Compact),
+            "retry" => {
+                let model = if parts.len() > 1 {
+                    Some(parts[1..].join(" "))
+                } else {
+                    None
+                };
+                Ok(CoreCommandType::Retry { model })
+            }
+            "review" => Ok(CoreCommandType::Review {
+                target: parts.get(1).map(|target| (*target).to_string()),
+            }),
+            "commit" => Ok(CoreCommandType::Commit),
             cmd => Err(SlashCommandError::UnknownCommand(cmd.to_string())),
         }
     }
@@ -70,6 +87,15 @@ impl CoreCommandType {
                 }
             }
             CoreCommandType::Compact => "compact".to_string(),
+            CoreCommandType::Retry { model } => match model {
+                Some(model) => format!("retry {model}"),
+                None => "retry".to_string(),
+            },
+            CoreCommandType::Review { target } => match target {
+                Some(target) => format!("review {target}"),
+                None => "review".to_string(),
+            },
+            CoreCommandType::Commit => "commit".to_string(),
         }
     }
 }
@@ -0,0 +1,91 @@
+//! Recovery from panics while rendering a frame.
+//!
+//! A frame is rendered inside [`catch_frame`]. While a guarded frame runs, the
+//! panic hook leaves the terminal alone and records the panic with a backtrace
+//! instead, so the TUI can write a crash log and fall back to a plain renderer.
+
+use std::backtrace::Backtrace;
+use std::cell::{Cell, RefCell};
+use std::io;
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::path::{Path, PathBuf};
+
+thread_local! {
+    static IN_GUARDED_FRAME: Cell<bool> = const { Cell::new(false) };
+    static CAPTURED_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Called from the panic hook. Returns `true` when the panic happened inside
+/// [`catch_frame`] and was recorded, meaning the terminal must stay as it is.
+pub(crate) fn capture_if_guarded(info: &PanicHookInfo<'_>) -> bool {
+    if !IN_GUARDED_FRAME.with(Cell::get) {
+        return false;
+    }
+    let report = format!("{info}\n\nBacktrace:\n{}", Backtrace::force_capture());
+    CAPTURED_PANIC.with(|captured| *captured.borrow_mut() = Some(report));
+    true
+}
+
+/// Runs `f`, turning a panic into an `Err` holding the panic report.
+pub(crate) fn catch_frame<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    IN_GUARDED_FRAME.with(|guarded| guarded.set(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    IN_GUARDED_FRAME.with(|guarded| guarded.set(false));
+    result.map_err(|payload| {
+        CAPTURED_PANIC
+            .with(|captured| captured.borrow_mut().take())
+            .unwrap_or_else(|| {
+                payload
+                    .downcast_ref::<&str>()
+                    .map(|s| (*s).to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic".to_string())
+            })
+    })
+}
+
+/// The crash log directory: `~/.steer/logs`.
+pub(crate) fn logs_dir() -> Option<PathBuf> {
+    directories::BaseDirs::new().map(|dirs| dirs.home_dir().join(".steer").join("logs"))
+}
+
+/// Writes a panic report for `session_id` to a new file in `dir`.
+pub(crate) fn write_crash_log(dir: &Path, session_id: &str, report: &str) -> io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let now = chrono::Utc::now();
+    let path = dir.join(format!(
+        "tui-panic-{}.log",
+        now.format("%Y%m%dT%H%M%S%.3fZ")
+    ));
+    let contents = format!(
+        "steer {} TUI render panic at {}\nsession: {session_id}\n\n{report}\n",
+        env!("CARGO_PKG_VERSION"),
+        now.to_rfc3339()
+    );
+    std::fs::write(&path, contents)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catch_frame_returns_value_or_panic_message() {
+        assert_eq!(catch_frame(|| 7), Ok(7));
+
+        let report = catch_frame(|| -> () { panic!("frame exploded") }).unwrap_err();
+        assert!(report.contains("frame exploded"));
+        assert!(!IN_GUARDED_FRAME.with(Cell::get));
+    }
+
+    #[test]
+    fn crash_log_records_session_and_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_crash_log(&dir.path().join("logs"), "session-1", "boom").unwrap();
+
+        let contents = std::fs::read_to_string(path).unwrap();
+        assert!(contents.contains("session: session-1"));
+        assert!(contents.contains("boom"));
+    }
+}
@@ -38,6 +38,7 @@ pub struct ProcessingContext<'a> {
     pub queued_head: &'a mut Option<steer_grpc::client_api::QueuedWorkItem>,
     pub queued_count: &'a mut usize,
     pub llm_usage: &'a mut LlmUsageState,
+    pub pending_commit: &'a mut Option<steer_grpc::client_api::CommitDraft>,
 }
 
 #[async_trait]
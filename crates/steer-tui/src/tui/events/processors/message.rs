@@ -2,6 +2,11 @@
 //!
 //! Processes events that add, update, or modify messages in the conversation,
 //! including streaming message parts and message restoration.
+//!
+//! Assistant messages built from stream deltas stay "open" until the final
+//! message arrives. If the stream drops first, the next user message closes
+//! them with an `[interrupted]` marker so the partial output isn't mistaken
+//! for a complete answer.
 
 use crate::tui::events::processor::{EventProcessor, ProcessingContext, ProcessingResult};
 use crate::tui::model::ChatItemData;
@@ -11,6 +16,9 @@ use steer_grpc::client_api::{
     ToolCallDelta,
 };
 
+/// Marker appended to assistant messages whose stream ended before completion
+pub const INTERRUPTED_MARKER: &str = "[interrupted]";
+
 /// Processor for message-related events
 pub struct MessageEventProcessor;
 
@@ -48,25 +56,30 @@ impl EventProcessor for MessageEventProcessor {
         match event {
             ClientEvent::AssistantMessageAdded { message, .. }
             | ClientEvent::ToolMessageAdded { message } => {
+                ctx.chat_store.finish_streaming(&message.id);
                 Self::handle_message_added(message, ctx);
                 ProcessingResult::Handled
             }
             ClientEvent::UserMessageAdded { message } => {
                 ctx.llm_usage.clear();
+                Self::mark_interrupted_messages(ctx);
                 Self::handle_message_added(message, ctx);
                 ProcessingResult::Handled
             }
             ClientEvent::MessageUpdated { message } => {
+                ctx.chat_store.finish_streaming(&message.id);
                 Self::handle_message_updated(message, ctx);
                 ProcessingResult::Handled
             }
             ClientEvent::MessageDelta { id, delta } => {
+                Self::track_streaming(&id, ctx);
                 Self::handle_message_delta(&id, delta, ctx);
                 ProcessingResult::Handled
             }
             ClientEvent::ThinkingDelta {
                 message_id, delta, ..
             } => {
+                Self::track_streaming(&message_id, ctx);
                 Self::handle_thinking_delta(&message_id, delta, ctx);
                 ProcessingResult::Handled
             }
@@ -76,10 +89,12 @@ impl EventProcessor for MessageEventProcessor {
                 delta,
                 ..
             } => {
+                Self::track_streaming(&message_id, ctx);
                 Self::handle_tool_call_delta(&message_id, tool_call_id.as_str(), delta, ctx);
                 ProcessingResult::Handled
             }
             ClientEvent::StreamReset { message_id, .. } => {
+                Self::track_streaming(&message_id, ctx);
                 Self::handle_stream_reset(&message_id, ctx);
                 ProcessingResult::Handled
             }
@@ -93,6 +108,32 @@ impl EventProcessor for MessageEventProcessor {
 }
 
 impl MessageEventProcessor {
+    fn track_streaming(id: &MessageId, ctx: &mut ProcessingContext) {
+        ctx.chat_store.mark_streaming(id.as_str());
+    }
+
+    /// Close out assistant messages whose stream never completed.
+    fn mark_interrupted_messages(ctx: &mut ProcessingContext) {
+        for id in ctx.chat_store.take_streaming_ids() {
+            let Some(item) = ctx.chat_store.get_mut_by_id(&id) else {
+                continue;
+            };
+            if let ChatItemData::Message(message) = &mut item.data
+                && let MessageData::Assistant { content, .. } = &mut message.data
+            {
+                tracing::debug!(
+                    target: "tui.message",
+                    "Marking interrupted streaming message: {}",
+                    id
+                );
+                content.push(AssistantContent::Text {
+                    text: INTERRUPTED_MARKER.to_string(),
+                });
+                *ctx.messages_updated = true;
+            }
+        }
+    }
+
     fn handle_message_added(message: Message, ctx: &mut ProcessingContext) {
         if let MessageData::Assistant { content, .. } = &message.data {
             tracing::debug!(
@@ -370,6 +411,7 @@ mod tests {
         queued_head: Option<steer_grpc::client_api::QueuedWorkItem>,
         queued_count: usize,
         llm_usage: LlmUsageState,
+        pending_commit: Option<steer_grpc::client_api::CommitDraft>,
         _workspace_root: tempfile::TempDir,
     }
 
@@ -411,6 +453,7 @@ mod tests {
             queued_head,
             queued_count,
             llm_usage: LlmUsageState::default(),
+            pending_commit: None,
             _workspace_root: workspace_root,
         }
     }
@@ -444,6 +487,7 @@ mod tests {
             queued_head: &mut ctx.queued_head,
             queued_count: &mut ctx.queued_count,
             llm_usage: &mut ctx.llm_usage,
+            pending_commit: &mut ctx.pending_commit,
         };
 
         let message_id = MessageId::from_string("msg_stream_reset");
@@ -566,6 +610,7 @@ mod tests {
             queued_head: &mut ctx.queued_head,
             queued_count: &mut ctx.queued_count,
             llm_usage: &mut ctx.llm_usage,
+            pending_commit: &mut ctx.pending_commit,
         };
 
         // Process the Assistant message
@@ -645,6 +690,7 @@ mod tests {
             queued_head: &mut ctx.queued_head,
             queued_count: &mut ctx.queued_count,
             llm_usage: &mut ctx.llm_usage,
+            pending_commit: &mut ctx.pending_commit,
         };
 
         let result = processor
@@ -659,4 +705,126 @@ mod tests {
         assert!(matches!(result, ProcessingResult::Handled));
         assert!(processing_ctx.llm_usage.latest().is_none());
     }
+
+    #[tokio::test]
+    async fn test_dropped_stream_is_marked_interrupted_on_next_user_message() {
+        let mut processor = MessageEventProcessor::new();
+        let mut ctx = create_test_context().await;
+
+        let mut in_flight_operations = std::collections::HashSet::new();
+        let notification_manager =
+            std::sync::Arc::new(crate::notifications::NotificationManager::new(
+                &steer_grpc::client_api::Preferences::default(),
+            ));
+        let mut processing_ctx = ProcessingContext {
+            chat_store: &mut ctx.chat_store,
+            chat_list_state: &mut ctx.chat_list_state,
+            tool_registry: &mut ctx.tool_registry,
+            client: &ctx.client,
+            notification_manager: &notification_manager,
+            input_panel_state: &mut ctx.input_panel_state,
+            is_processing: &mut ctx.is_processing,
+            progress_message: &mut ctx.progress_message,
+            spinner_state: &mut ctx.spinner_state,
+            current_tool_approval: &mut ctx.current_tool_approval,
+            current_model: &mut ctx.current_model,
+            current_agent_label: &mut ctx.current_agent_label,
+            messages_updated: &mut ctx.messages_updated,
+            in_flight_operations: &mut in_flight_operations,
+            notify_on_processing_complete: &mut ctx.notify_on_processing_complete,
+            queued_head: &mut ctx.queued_head,
+            queued_count: &mut ctx.queued_count,
+            llm_usage: &mut ctx.llm_usage,
+            pending_commit: &mut ctx.pending_commit,
+        };
+
+        // A completed stream: deltas followed by the final message.
+        let completed_id = MessageId::from_string("msg_completed");
+        processor
+            .process(
+                ClientEvent::MessageDelta {
+                    id: completed_id.clone(),
+                    delta: "full ".to_string(),
+                },
+                &mut processing_ctx,
+            )
+            .await;
+        processor
+            .process(
+                ClientEvent::AssistantMessageAdded {
+                    message: Message {
+                        data: MessageData::Assistant {
+                            content: vec![AssistantContent::Text {
+                                text: "full answer".to_string(),
+                            }],
+                        },
+                        id: completed_id.as_str().to_string(),
+                        timestamp: 1_234_567_890,
+                        parent_message_id: None,
+                    },
+                    model: builtin::claude_sonnet_4_5(),
+                },
+                &mut processing_ctx,
+            )
+            .await;
+
+        // A stream that drops after a partial delta and never completes.
+        let dropped_id = MessageId::from_string("msg_dropped");
+        processor
+            .process(
+                ClientEvent::MessageDelta {
+                    id: dropped_id.clone(),
+                    delta: "partial output".to_string(),
+                },
+                &mut processing_ctx,
+            )
+            .await;
+
+        processor
+            .process(
+                ClientEvent::UserMessageAdded {
+                    message: Message {
+                        data: MessageData::User {
+                            content: vec![steer_grpc::client_api::UserContent::Text {
+                                text: "are you still there?".to_string(),
+                            }],
+                        },
+                        id: "msg_user_next".to_string(),
+                        timestamp: 1_234_567_891,
+                        parent_message_id: None,
+                    },
+                },
+                &mut processing_ctx,
+            )
+            .await;
+
+        let assistant_texts = |id: &MessageId| -> Vec<String> {
+            let stored = processing_ctx
+                .chat_store
+                .get_by_id(&id.as_str().to_string())
+                .expect("message should exist");
+            let ChatItemData::Message(message) = &stored.data else {
+                panic!("expected message item")
+            };
+            let MessageData::Assistant { content } = &message.data else {
+                panic!("expected assistant message")
+            };
+            content
+                .iter()
+                .filter_map(|block| match block {
+                    AssistantContent::Text { text } => Some(text.clone()),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        assert_eq!(
+            assistant_texts(&dropped_id),
+            vec!["partial output".to_string(), INTERRUPTED_MARKER.to_string()]
+        );
+        assert_eq!(
+            assistant_texts(&completed_id),
+            vec!["full answer".to_string()]
+        );
+    }
 }
@@ -69,7 +69,7 @@ impl EventProcessor for ProcessingStateProcessor {
 
                 ProcessingResult::Handled
             }
-            ClientEvent::ProcessingCompleted { op_id } => {
+            ClientEvent::ProcessingCompleted { op_id, .. } => {
                 let was_processing = *ctx.is_processing;
                 *ctx.is_processing = false;
                 *ctx.progress_message = None;
@@ -202,6 +202,7 @@ mod tests {
         queued_count: usize,
         notify_on_processing_complete: HashMap<OpId, bool>,
         llm_usage: LlmUsageState,
+        pending_commit: Option<steer_grpc::client_api::CommitDraft>,
         _workspace_root: tempfile::TempDir,
     }
 
@@ -234,6 +235,7 @@ mod tests {
             queued_count: 0,
             notify_on_processing_complete: HashMap::new(),
             llm_usage: LlmUsageState::default(),
+            pending_commit: None,
             _workspace_root: workspace_root,
         }
     }
@@ -279,6 +281,7 @@ mod tests {
             queued_head: &mut ctx.queued_head,
             queued_count: &mut ctx.queued_count,
             llm_usage: &mut ctx.llm_usage,
+            pending_commit: &mut ctx.pending_commit,
         };
 
         let result = processor
@@ -338,6 +341,7 @@ mod tests {
             queued_head: &mut ctx.queued_head,
             queued_count: &mut ctx.queued_count,
             llm_usage: &mut ctx.llm_usage,
+            pending_commit: &mut ctx.pending_commit,
         };
 
         let _ = processor
@@ -402,6 +406,7 @@ mod tests {
             queued_head: &mut ctx.queued_head,
             queued_count: &mut ctx.queued_count,
             llm_usage: &mut ctx.llm_usage,
+            pending_commit: &mut ctx.pending_commit,
         };
 
         let result = processor
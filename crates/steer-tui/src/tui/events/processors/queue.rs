@@ -71,6 +71,7 @@ mod tests {
         queued_head: Option<QueuedWorkItem>,
         queued_count: usize,
         llm_usage: LlmUsageState,
+        pending_commit: Option<steer_grpc::client_api::CommitDraft>,
         _workspace_root: tempfile::TempDir,
     }
 
@@ -112,6 +113,7 @@ mod tests {
             queued_head: None,
             queued_count: 0,
             llm_usage: LlmUsageState::default(),
+            pending_commit: None,
             _workspace_root: workspace_root,
         }
     }
@@ -155,6 +157,7 @@ mod tests {
             queued_head: &mut ctx.queued_head,
             queued_count: &mut ctx.queued_count,
             llm_usage: &mut ctx.llm_usage,
+            pending_commit: &mut ctx.pending_commit,
         };
 
         let result = processor
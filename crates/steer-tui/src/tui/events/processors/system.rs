@@ -1,7 +1,7 @@
 //! SystemEventProcessor - handles system and configuration events.
 //!
-//! Processes events related to command responses, compaction, and other
-//! system-level state changes.
+//! Processes events related to command responses, compaction, reviews, and
+//! other system-level state changes.
 
 use crate::notifications::{NotificationEvent, NotificationManager, NotificationManagerHandle};
 use crate::tui::core_commands::{CommandResponse, CoreCommandType};
@@ -33,9 +33,14 @@ impl EventProcessor for SystemEventProcessor {
         matches!(
             event,
             ClientEvent::Error { .. }
+                | ClientEvent::Refused { .. }
                 | ClientEvent::CompactResult { .. }
                 | ClientEvent::ConversationCompacted { .. }
                 | ClientEvent::SessionConfigUpdated { .. }
+                | ClientEvent::ReviewCompleted { .. }
+                | ClientEvent::ReviewFailed { .. }
+                | ClientEvent::CommitDrafted { .. }
+                | ClientEvent::CommitDraftFailed { .. }
         )
     }
 
@@ -64,6 +69,23 @@ impl EventProcessor for SystemEventProcessor {
 
                 ProcessingResult::Handled
             }
+            ClientEvent::Refused { reason } => {
+                let chat_item = crate::tui::model::ChatItem {
+                    parent_chat_item_id: None,
+                    data: ChatItemData::SystemNotice {
+                        id: generate_row_id(),
+                        level: NoticeLevel::Warn,
+                        text: format!(
+                            "{reason}. Try rephrasing the request or switching models with /model."
+                        ),
+                        ts: time::OffsetDateTime::now_utc(),
+                    },
+                };
+                ctx.chat_store.push(chat_item);
+                *ctx.messages_updated = true;
+
+                ProcessingResult::Handled
+            }
             ClientEvent::CompactResult { result, trigger } => {
                 if matches!(result, CompactResult::Success(_)) {
                     ctx.llm_usage.clear();
@@ -85,6 +107,66 @@ impl EventProcessor for SystemEventProcessor {
                 // Auto non-success: silent (no chat item)
                 ProcessingResult::Handled
             }
+            ClientEvent::ReviewCompleted { report } => {
+                let chat_item = crate::tui::model::ChatItem {
+                    parent_chat_item_id: None,
+                    data: ChatItemData::CoreCmdResponse {
+                        id: generate_row_id(),
+                        command: CoreCommandType::Review {
+                            target: Some(report.target.to_string()),
+                        },
+                        response: CommandResponse::Review(*report),
+                        ts: time::OffsetDateTime::now_utc(),
+                    },
+                };
+                ctx.chat_store.push(chat_item);
+                *ctx.messages_updated = true;
+                ProcessingResult::Handled
+            }
+            ClientEvent::ReviewFailed { message } => {
+                let chat_item = crate::tui::model::ChatItem {
+                    parent_chat_item_id: None,
+                    data: ChatItemData::SystemNotice {
+                        id: generate_row_id(),
+                        level: NoticeLevel::Error,
+                        text: format!("Review failed: {message}"),
+                        ts: time::OffsetDateTime::now_utc(),
+                    },
+                };
+                ctx.chat_store.push(chat_item);
+                *ctx.messages_updated = true;
+                ProcessingResult::Handled
+            }
+            ClientEvent::CommitDrafted { draft } => {
+                let chat_item = crate::tui::model::ChatItem {
+                    parent_chat_item_id: None,
+                    data: ChatItemData::CoreCmdResponse {
+                        id: generate_row_id(),
+                        command: CoreCommandType::Commit,
+                        response: CommandResponse::CommitDraft((*draft).clone()),
+                        ts: time::OffsetDateTime::now_utc(),
+                    },
+                };
+                ctx.chat_store.push(chat_item);
+                // The TUI switches to commit confirmation once this is set.
+                *ctx.pending_commit = Some(*draft);
+                *ctx.messages_updated = true;
+                ProcessingResult::Handled
+            }
+            ClientEvent::CommitDraftFailed { message } => {
+                let chat_item = crate::tui::model::ChatItem {
+                    parent_chat_item_id: None,
+                    data: ChatItemData::SystemNotice {
+                        id: generate_row_id(),
+                        level: NoticeLevel::Error,
+                        text: format!("Commit draft failed: {message}"),
+                        ts: time::OffsetDateTime::now_utc(),
+                    },
+                };
+                ctx.chat_store.push(chat_item);
+                *ctx.messages_updated = true;
+                ProcessingResult::Handled
+            }
             ClientEvent::ConversationCompacted { record } => {
                 ctx.chat_store.mark_compaction_summary_with_head(
                     record.summary_message_id.to_string(),
@@ -156,6 +238,7 @@ mod tests {
         queued_head: Option<QueuedWorkItem>,
         queued_count: usize,
         llm_usage: LlmUsageState,
+        pending_commit: Option<steer_grpc::client_api::CommitDraft>,
         _workspace_root: tempfile::TempDir,
     }
 
@@ -188,6 +271,7 @@ mod tests {
             queued_head: None,
             queued_count: 0,
             llm_usage: LlmUsageState::default(),
+            pending_commit: None,
             _workspace_root: workspace_root,
         }
     }
@@ -234,6 +318,7 @@ mod tests {
             queued_head: &mut ctx.queued_head,
             queued_count: &mut ctx.queued_count,
             llm_usage: &mut ctx.llm_usage,
+            pending_commit: &mut ctx.pending_commit,
         };
 
         let result = processor
@@ -278,6 +363,7 @@ mod tests {
             queued_head: &mut ctx.queued_head,
             queued_count: &mut ctx.queued_count,
             llm_usage: &mut ctx.llm_usage,
+            pending_commit: &mut ctx.pending_commit,
         };
 
         let result = processor
@@ -325,6 +411,7 @@ mod tests {
             queued_head: &mut ctx.queued_head,
             queued_count: &mut ctx.queued_count,
             llm_usage: &mut ctx.llm_usage,
+            pending_commit: &mut ctx.pending_commit,
         };
 
         let result = processor
@@ -378,6 +465,7 @@ mod tests {
             queued_head: &mut ctx.queued_head,
             queued_count: &mut ctx.queued_count,
             llm_usage: &mut ctx.llm_usage,
+            pending_commit: &mut ctx.pending_commit,
         };
 
         let config = steer_grpc::client_api::SessionConfig {
@@ -395,6 +483,9 @@ mod tests {
             metadata: std::collections::HashMap::new(),
             default_model: steer_grpc::client_api::builtin::claude_sonnet_4_5(),
             auto_compaction: Default::default(),
+            auto_summary: Default::default(),
+            isolation: steer_grpc::client_api::IsolationMode::None,
+            isolated_workspace: None,
         };
 
         let result = processor
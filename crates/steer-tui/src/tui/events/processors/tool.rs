@@ -223,6 +223,7 @@ mod tests {
         queued_head: Option<steer_grpc::client_api::QueuedWorkItem>,
         queued_count: usize,
         llm_usage: LlmUsageState,
+        pending_commit: Option<steer_grpc::client_api::CommitDraft>,
         _workspace_root: tempfile::TempDir,
     }
     async fn create_test_context() -> TestContext {
@@ -265,6 +266,7 @@ mod tests {
             queued_head,
             queued_count,
             llm_usage: LlmUsageState::default(),
+            pending_commit: None,
             _workspace_root: workspace_root,
         }
     }
@@ -315,6 +317,7 @@ mod tests {
                 queued_head: &mut ctx.queued_head,
                 queued_count: &mut ctx.queued_count,
                 llm_usage: &mut ctx.llm_usage,
+                pending_commit: &mut ctx.pending_commit,
             };
             let _ = msg_proc
                 .process(
@@ -347,6 +350,7 @@ mod tests {
                 queued_head: &mut ctx.queued_head,
                 queued_count: &mut ctx.queued_count,
                 llm_usage: &mut ctx.llm_usage,
+                pending_commit: &mut ctx.pending_commit,
             };
             let _ = tool_proc
                 .process(
@@ -17,7 +17,10 @@ impl EventProcessor for UsageEventProcessor {
     }
 
     fn can_handle(&self, event: &ClientEvent) -> bool {
-        matches!(event, ClientEvent::LlmUsageUpdated { .. })
+        matches!(
+            event,
+            ClientEvent::LlmUsageUpdated { .. } | ClientEvent::ContextUsage { .. }
+        )
     }
 
     async fn process(
@@ -37,6 +40,10 @@ impl EventProcessor for UsageEventProcessor {
                     .update(op_id, model, usage, context_window, kind);
                 ProcessingResult::Handled
             }
+            ClientEvent::ContextUsage { used, limit } => {
+                ctx.llm_usage.update_context_estimate(used, limit);
+                ProcessingResult::Handled
+            }
             _ => ProcessingResult::NotHandled,
         }
     }
@@ -84,6 +91,7 @@ mod tests {
         queued_count: usize,
         notify_on_processing_complete: HashMap<OpId, bool>,
         llm_usage: LlmUsageState,
+        pending_commit: Option<steer_grpc::client_api::CommitDraft>,
         _workspace_root: tempfile::TempDir,
     }
 
@@ -116,6 +124,7 @@ mod tests {
             queued_count: 0,
             notify_on_processing_complete: HashMap::new(),
             llm_usage: LlmUsageState::default(),
+            pending_commit: None,
             _workspace_root: workspace_root,
         }
     }
@@ -152,6 +161,7 @@ mod tests {
             queued_head: &mut ctx.queued_head,
             queued_count: &mut ctx.queued_count,
             llm_usage: &mut ctx.llm_usage,
+            pending_commit: &mut ctx.pending_commit,
         };
 
         let result = processor
@@ -218,6 +228,7 @@ mod tests {
             queued_head: &mut ctx.queued_head,
             queued_count: &mut ctx.queued_count,
             llm_usage: &mut ctx.llm_usage,
+            pending_commit: &mut ctx.pending_commit,
         };
 
         let _ = processor
@@ -1,10 +1,12 @@
 use crate::error::Error;
 use crate::error::Result;
 use crate::tui::Tui;
+use crate::tui::widgets::input_panel::HunkReview;
 use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use steer_grpc::client_api::ApprovalDecision;
+use steer_grpc::client_api::{ApprovalDecision, ToolCall};
 use steer_tools::tools::BASH_TOOL_NAME;
 use steer_tools::tools::bash::BashParams;
+use steer_tools::tools::edit::hunks::edit_hunks;
 use tracing::debug;
 
 fn is_cycle_agent_key(key: KeyEvent) -> bool {
@@ -19,6 +21,22 @@ impl Tui {
             return Ok(false);
         }
 
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            // Cancelling the whole operation also resolves the pending approval.
+            self.input_panel_state.denial_reason = None;
+            self.input_panel_state.hunk_review = None;
+            self.client.cancel_operation().await?;
+            return Ok(false);
+        }
+
+        if self.input_panel_state.denial_reason.is_some() {
+            return self.handle_denial_reason_input(key).await;
+        }
+
+        if self.input_panel_state.hunk_review.is_some() {
+            return self.handle_hunk_review_input(key).await;
+        }
+
         if let Some((request_id, tool_call)) = self.current_tool_approval.take() {
             match key.code {
                 KeyCode::Char('y' | 'Y') => {
@@ -28,26 +46,10 @@ impl Tui {
                     self.input_mode = self.default_input_mode();
                 }
                 KeyCode::Char('a' | 'A') => {
-                    debug!(target: "handle_approval_mode", "Approving tool call with request_id '{:?}' and name '{}'", request_id, tool_call.name);
-                    if tool_call.name == BASH_TOOL_NAME {
-                        debug!(target: "handle_approval_mode", "(Always) Approving bash command with request_id '{:?}'", request_id);
-                        let bash_params: BashParams =
-                            serde_json::from_value(tool_call.parameters.clone()).map_err(|e| {
-                                Error::CommandProcessing(format!(
-                                    "Invalid bash params for approval: {e}"
-                                ))
-                            })?;
-                        self.client
-                            .approve_tool(
-                                request_id.to_string(),
-                                ApprovalDecision::AlwaysBashPattern(bash_params.command.clone()),
-                            )
-                            .await?;
-                    } else {
-                        self.client
-                            .approve_tool(request_id.to_string(), ApprovalDecision::AlwaysTool)
-                            .await?;
-                    }
+                    debug!(target: "handle_approval_mode", "(Always) Approving tool call with request_id '{:?}' and name '{}'", request_id, tool_call.name);
+                    self.client
+                        .approve_tool(request_id.to_string(), always_decision(&tool_call)?)
+                        .await?;
                     self.input_mode = self.default_input_mode();
                 }
                 KeyCode::Char('l' | 'L') => {
@@ -66,6 +68,17 @@ impl Tui {
                         .await?;
                     self.input_mode = self.default_input_mode();
                 }
+                KeyCode::Char('r' | 'R') => {
+                    self.input_panel_state.denial_reason = Some(String::new());
+                    self.current_tool_approval = Some((request_id, tool_call));
+                }
+                KeyCode::Char('p' | 'P') => {
+                    let hunks = edit_hunks(&tool_call);
+                    if !hunks.is_empty() {
+                        self.input_panel_state.hunk_review = Some(HunkReview::new(hunks));
+                    }
+                    self.current_tool_approval = Some((request_id, tool_call));
+                }
                 _ => {
                     self.current_tool_approval = Some((request_id, tool_call));
                 }
@@ -75,12 +88,106 @@ impl Tui {
         }
         Ok(false)
     }
+
+    async fn handle_denial_reason_input(&mut self, key: KeyEvent) -> Result<bool> {
+        let Some(reason) = self.input_panel_state.denial_reason.as_mut() else {
+            return Ok(false);
+        };
+
+        match key.code {
+            KeyCode::Enter => {
+                let reason = reason.trim().to_string();
+                self.input_panel_state.denial_reason = None;
+                if let Some((request_id, _)) = self.current_tool_approval.take() {
+                    self.client
+                        .approve_tool(request_id.to_string(), denial_decision(reason))
+                        .await?;
+                }
+                self.input_mode = self.default_input_mode();
+            }
+            KeyCode::Esc => {
+                self.input_panel_state.denial_reason = None;
+            }
+            KeyCode::Backspace => {
+                reason.pop();
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                reason.push(c);
+            }
+            _ => {}
+        }
+        Ok(false)
+    }
+
+    async fn handle_hunk_review_input(&mut self, key: KeyEvent) -> Result<bool> {
+        let Some(review) = self.input_panel_state.hunk_review.as_mut() else {
+            return Ok(false);
+        };
+
+        match key.code {
+            KeyCode::Char('y' | 'Y') => review.answer(true),
+            KeyCode::Char('n' | 'N') => review.answer(false),
+            KeyCode::Char('a' | 'A') => review.answer_rest(true),
+            KeyCode::Char('d' | 'D') => review.answer_rest(false),
+            KeyCode::Esc => {
+                self.input_panel_state.hunk_review = None;
+                return Ok(false);
+            }
+            _ => return Ok(false),
+        }
+
+        if review.is_done() {
+            let decision = hunk_decision(review);
+            self.input_panel_state.hunk_review = None;
+            if let Some((request_id, _)) = self.current_tool_approval.take() {
+                self.client
+                    .approve_tool(request_id.to_string(), decision)
+                    .await?;
+            }
+            self.input_mode = self.default_input_mode();
+        }
+        Ok(false)
+    }
+}
+
+/// The answer to an approval request once every hunk has been reviewed.
+fn hunk_decision(review: &HunkReview) -> ApprovalDecision {
+    let accepted = review.accepted_indices();
+    if accepted.is_empty() {
+        ApprovalDecision::Deny
+    } else if accepted.len() == review.len() {
+        ApprovalDecision::Once
+    } else {
+        ApprovalDecision::AcceptHunks(accepted)
+    }
+}
+
+/// The "always allow" answer to an approval request: bash commands are
+/// remembered by their exact command line, other tools by name.
+pub(crate) fn always_decision(tool_call: &ToolCall) -> Result<ApprovalDecision> {
+    if tool_call.name != BASH_TOOL_NAME {
+        return Ok(ApprovalDecision::AlwaysTool);
+    }
+    let bash_params: BashParams = serde_json::from_value(tool_call.parameters.clone())
+        .map_err(|e| Error::CommandProcessing(format!("Invalid bash params for approval: {e}")))?;
+    Ok(ApprovalDecision::AlwaysBashPattern(bash_params.command))
+}
+
+pub(crate) fn denial_decision(reason: String) -> ApprovalDecision {
+    if reason.is_empty() {
+        ApprovalDecision::Deny
+    } else {
+        ApprovalDecision::DenyWithReason(reason)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::is_cycle_agent_key;
+    use super::{denial_decision, hunk_decision, is_cycle_agent_key};
+    use crate::tui::widgets::input_panel::HunkReview;
     use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use steer_grpc::client_api::ApprovalDecision;
+    use steer_tools::tools::edit::hunks::EditHunk;
 
     #[test]
     fn detects_shift_tab_cycle_key_variants() {
@@ -109,4 +216,52 @@ mod tests {
             KeyModifiers::NONE,
         )));
     }
+
+    #[test]
+    fn empty_denial_reason_falls_back_to_plain_deny() {
+        assert_eq!(denial_decision(String::new()), ApprovalDecision::Deny);
+        assert_eq!(
+            denial_decision("use rg instead".to_string()),
+            ApprovalDecision::DenyWithReason("use rg instead".to_string())
+        );
+    }
+
+    fn review_of(count: usize) -> HunkReview {
+        HunkReview::new(
+            (0..count)
+                .map(|line| EditHunk {
+                    edit_index: 0,
+                    old_start: line + 1,
+                    old_lines: vec!["old".to_string()],
+                    new_lines: vec!["new".to_string()],
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn hunk_review_sends_only_the_accepted_hunks() {
+        let mut review = review_of(3);
+        review.answer(true);
+        review.answer(false);
+        assert!(!review.is_done());
+        review.answer_rest(true);
+
+        assert!(review.is_done());
+        assert_eq!(
+            hunk_decision(&review),
+            ApprovalDecision::AcceptHunks(vec![0, 2])
+        );
+    }
+
+    #[test]
+    fn hunk_review_with_all_or_nothing_is_a_plain_answer() {
+        let mut review = review_of(2);
+        review.answer_rest(true);
+        assert_eq!(hunk_decision(&review), ApprovalDecision::Once);
+
+        let mut review = review_of(2);
+        review.answer_rest(false);
+        assert_eq!(hunk_decision(&review), ApprovalDecision::Deny);
+    }
 }
@@ -0,0 +1,86 @@
+use crate::tui::NoticeLevel;
+use crate::tui::Tui;
+use crate::tui::model::TuiCommandResponse;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Candidates drafted when best-of is armed without a count.
+const DEFAULT_BEST_OF_CANDIDATES: u32 = 2;
+/// Most candidates the server drafts for one turn.
+const MAX_BEST_OF_CANDIDATES: u32 = 4;
+
+impl Tui {
+    /// Alt+B arms best-of for the next message. While candidates are shown
+    /// and the input is empty, Left/Right choose one, Enter picks it and Esc
+    /// hides the panel. Returns true when the key was handled.
+    pub async fn handle_best_of_keys(&mut self, key: KeyEvent) -> bool {
+        if key.modifiers == KeyModifiers::ALT && key.code == KeyCode::Char('b') {
+            let armed = if self.best_of_armed.is_some() {
+                None
+            } else {
+                Some(DEFAULT_BEST_OF_CANDIDATES)
+            };
+            self.arm_best_of(armed);
+            return true;
+        }
+
+        if key.modifiers != KeyModifiers::NONE || !self.input_panel_state.content().is_empty() {
+            return false;
+        }
+        let Some(view) = self.best_of_view.as_mut() else {
+            return false;
+        };
+        match key.code {
+            KeyCode::Left => view.select_prev(),
+            KeyCode::Right => view.select_next(),
+            KeyCode::Enter if view.is_complete() => {
+                let Some(index) = view.selected_index() else {
+                    return true;
+                };
+                if let Err(e) = self.client.pick_best_of_candidate(index).await {
+                    self.push_notice(NoticeLevel::Error, Self::format_grpc_error(&e));
+                }
+            }
+            KeyCode::Esc if view.is_complete() => {
+                self.best_of_view = None;
+                self.push_notice(
+                    NoticeLevel::Info,
+                    "Candidates hidden; your next message discards them".to_string(),
+                );
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// `/bestof [2-4|off]`
+    pub(crate) fn handle_best_of_command(&mut self, command: String, count: Option<&str>) {
+        let armed = match count {
+            None => Some(DEFAULT_BEST_OF_CANDIDATES),
+            Some("off") => None,
+            Some(count) => match count.parse::<u32>() {
+                Ok(n) if (2..=MAX_BEST_OF_CANDIDATES).contains(&n) => Some(n),
+                _ => {
+                    self.push_tui_response(
+                        command,
+                        TuiCommandResponse::Text(format!(
+                            "Usage: /bestof [2-{MAX_BEST_OF_CANDIDATES}|off]"
+                        )),
+                    );
+                    return;
+                }
+            },
+        };
+        self.arm_best_of(armed);
+    }
+
+    fn arm_best_of(&mut self, armed: Option<u32>) {
+        self.best_of_armed = armed;
+        let notice = match armed {
+            Some(n) => {
+                format!("Best-of armed: your next message drafts {n} answers with read-only tools")
+            }
+            None => "Best-of off".to_string(),
+        };
+        self.push_notice(NoticeLevel::Info, notice);
+    }
+}
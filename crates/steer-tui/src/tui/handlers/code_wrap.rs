@@ -0,0 +1,32 @@
+use crate::tui::NoticeLevel;
+use crate::tui::Tui;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Columns moved per Alt+Shift+Left/Right press.
+const CODE_SCROLL_STEP: isize = 8;
+
+impl Tui {
+    /// Alt+W switches the wide code in view between soft wrap and horizontal
+    /// scrolling, and Alt+Shift+Left/Right scroll it. Returns true when the
+    /// key was handled.
+    pub fn handle_code_wrap_keys(&mut self, key: KeyEvent) -> bool {
+        match (key.code, key.modifiers) {
+            (KeyCode::Char('w'), KeyModifiers::ALT) => {
+                if self.chat_viewport.toggle_code_wrap().is_none() {
+                    self.push_notice(
+                        NoticeLevel::Info,
+                        "No code in view is wider than the screen".to_string(),
+                    );
+                }
+                true
+            }
+            (KeyCode::Left, modifiers) if modifiers == KeyModifiers::ALT | KeyModifiers::SHIFT => {
+                self.chat_viewport.scroll_code(-CODE_SCROLL_STEP)
+            }
+            (KeyCode::Right, modifiers) if modifiers == KeyModifiers::ALT | KeyModifiers::SHIFT => {
+                self.chat_viewport.scroll_code(CODE_SCROLL_STEP)
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use crate::error::Result;
+use crate::tui::Tui;
+use crate::tui::model::NoticeLevel;
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+
+impl Tui {
+    pub async fn handle_confirm_commit_mode(&mut self, key: KeyEvent) -> Result<bool> {
+        let Some(draft) = self.pending_commit.take() else {
+            self.input_mode = self.default_input_mode();
+            return Ok(false);
+        };
+
+        if let KeyCode::Char('y' | 'Y') = key.code {
+            match self.client.create_commit(&draft).await {
+                Ok(outcome) => self.push_notice(
+                    NoticeLevel::Info,
+                    format!("Committed {}: {}", outcome.commit, outcome.summary),
+                ),
+                Err(e) => self.push_notice(NoticeLevel::Error, Self::format_grpc_error(&e)),
+            }
+        } else {
+            // Any other key discards the draft without touching the index
+            self.push_notice(NoticeLevel::Info, "Commit cancelled".to_string());
+        }
+
+        self.input_mode = self.default_input_mode();
+        Ok(false)
+    }
+}
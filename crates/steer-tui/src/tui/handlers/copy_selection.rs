@@ -0,0 +1,127 @@
+use crate::error::Result;
+use crate::tui::InputMode;
+use crate::tui::NoticeLevel;
+use crate::tui::Tui;
+use crate::tui::clipboard::{self, CycleKind};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use steer_grpc::client_api::Message;
+
+impl Tui {
+    /// Alt+Y opens the copy picker. Returns true when the key was handled.
+    pub fn handle_copy_keys(&mut self, key: KeyEvent) -> bool {
+        if key.modifiers != KeyModifiers::ALT || key.code != KeyCode::Char('y') {
+            return false;
+        }
+        self.enter_copy_selection_mode();
+        true
+    }
+
+    fn enter_copy_selection_mode(&mut self) {
+        let messages = self
+            .chat_store
+            .conversation_messages_in_lineage()
+            .into_iter()
+            .filter_map(|message| {
+                let preview = Self::copy_preview(message)?;
+                Some((message.id().to_string(), preview))
+            })
+            .collect();
+        self.copy_selection_state.populate(messages);
+        self.copy_cycle.reset();
+        self.switch_mode(InputMode::CopySelection);
+    }
+
+    /// What the picker shows for a message, or `None` when there is nothing to copy.
+    fn copy_preview(message: &Message) -> Option<String> {
+        let markdown = clipboard::message_markdown(message);
+        if !markdown.trim().is_empty() {
+            return Some(markdown);
+        }
+        let files = clipboard::file_references(message);
+        (!files.is_empty()).then(|| format!("Files: {}", files.join(", ")))
+    }
+
+    pub async fn handle_copy_selection_mode(&mut self, key: KeyEvent) -> Result<bool> {
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) | (KeyCode::Char('c' | 'd'), KeyModifiers::CONTROL) => {
+                self.leave_copy_selection_mode();
+            }
+            (KeyCode::Up | KeyCode::Char('k'), _) => {
+                self.copy_selection_state.select_prev();
+                self.copy_cycle.reset();
+            }
+            (KeyCode::Down | KeyCode::Char('j'), _) => {
+                self.copy_selection_state.select_next();
+                self.copy_cycle.reset();
+            }
+            (KeyCode::Enter | KeyCode::Char('y'), _) => {
+                if let Some(message) = self.selected_copy_message() {
+                    let markdown = clipboard::message_markdown(&message);
+                    if markdown.trim().is_empty() {
+                        self.push_notice(
+                            NoticeLevel::Info,
+                            "That message has no text to copy".to_string(),
+                        );
+                    } else if self.copy_text(&markdown, "message".to_string()) {
+                        self.leave_copy_selection_mode();
+                    }
+                }
+            }
+            (KeyCode::Char('c'), _) => {
+                if let Some(message) = self.selected_copy_message() {
+                    let blocks = clipboard::code_blocks(&clipboard::message_markdown(&message));
+                    if blocks.is_empty() {
+                        self.push_notice(
+                            NoticeLevel::Info,
+                            "That message has no code blocks".to_string(),
+                        );
+                    } else {
+                        let index = self.copy_cycle.advance(
+                            message.id(),
+                            CycleKind::CodeBlock,
+                            blocks.len(),
+                        );
+                        let label = format!("code block {}/{}", index + 1, blocks.len());
+                        self.copy_text(&blocks[index], label);
+                    }
+                }
+            }
+            (KeyCode::Char('f'), _) => {
+                if let Some(message) = self.selected_copy_message() {
+                    let files = clipboard::file_references(&message);
+                    if files.is_empty() {
+                        self.push_notice(
+                            NoticeLevel::Info,
+                            "That message doesn't refer to any files".to_string(),
+                        );
+                    } else {
+                        let index =
+                            self.copy_cycle
+                                .advance(message.id(), CycleKind::FilePath, files.len());
+                        let root = self.copy_path_root();
+                        let path = clipboard::absolute_path(&files[index], &root);
+                        let text = path.display().to_string();
+                        self.copy_text(&text, text.clone());
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(false)
+    }
+
+    fn selected_copy_message(&self) -> Option<Message> {
+        let (message_id, _) = self.copy_selection_state.get_selected()?;
+        self.chat_store
+            .conversation_messages_in_lineage()
+            .into_iter()
+            .find(|message| message.id() == message_id)
+            .cloned()
+    }
+
+    fn leave_copy_selection_mode(&mut self) {
+        self.copy_selection_state.clear();
+        self.copy_cycle.reset();
+        self.input_mode = self.default_input_mode();
+    }
+}
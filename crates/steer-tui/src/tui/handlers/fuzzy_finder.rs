@@ -4,10 +4,114 @@ use crate::tui::Tui;
 use crate::tui::theme::ThemeLoader;
 use crate::tui::widgets::PickerItem;
 use crate::tui::widgets::fuzzy_finder::FuzzyFinderMode;
+use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
 use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use steer_grpc::client_api::{ModelId, ProviderId};
+use steer_grpc::client_api::{AuthSource, FileSymbol, ModelId, ProviderId};
+use steer_grpc::proto::ProviderModel;
+use tracing::warn;
 use tui_textarea::Input;
 
+/// Scores `query` against a model's `provider/name` label, ID and aliases.
+fn model_match_score(matcher: &SkimMatcherV2, model: &ProviderModel, query: &str) -> Option<i64> {
+    let prov = ProviderId(model.provider_id.clone()).storage_key();
+    let label = format!("{prov}/{}", model.display_name);
+    let alias_score = model
+        .aliases
+        .iter()
+        .filter_map(|alias| {
+            let bare = matcher.fuzzy_match(alias, query);
+            let qualified = matcher.fuzzy_match(&format!("{prov}/{alias}"), query);
+            bare.max(qualified)
+        })
+        .max();
+
+    [
+        matcher.fuzzy_match(&label, query),
+        matcher.fuzzy_match(&model.display_name, query),
+        matcher.fuzzy_match(&model.model_id, query),
+        alias_score,
+    ]
+    .into_iter()
+    .flatten()
+    .max()
+}
+
+/// Builds the `/model` picker entries matching `query`, best match first.
+fn model_picker_items(
+    models: &[ProviderModel],
+    current_model: &ModelId,
+    query: &str,
+) -> Vec<PickerItem> {
+    let matcher = SkimMatcherV2::default();
+    let mut scored: Vec<(i64, &ProviderModel)> = models
+        .iter()
+        .filter_map(|model| {
+            if query.is_empty() {
+                return Some((0, model));
+            }
+            model_match_score(&matcher, model, query).map(|score| (score, model))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+
+    scored
+        .into_iter()
+        .map(|(_, model)| {
+            let provider_id = ProviderId(model.provider_id.clone());
+            let prov = provider_id.storage_key();
+            let label = format!("{prov}/{}", model.display_name);
+            let label = if ModelId::new(provider_id, model.model_id.clone()) == *current_model {
+                format!("{label} (current)")
+            } else {
+                label
+            };
+            let label = if model.unavailable_reason.is_some() {
+                format!("{label} (unavailable)")
+            } else {
+                label
+            };
+            // Insert provider/id for lookup
+            PickerItem::new(label, format!("{prov}/{}", model.model_id))
+        })
+        .collect()
+}
+
+/// Resolves a `/model <query>` argument against the available models. An exact
+/// `provider/id`, ID or alias match wins; otherwise the best fuzzy match does.
+pub(crate) fn fuzzy_resolve_model(query: &str, models: &[ProviderModel]) -> Option<ModelId> {
+    let query = query.trim();
+    let to_id = |model: &ProviderModel| {
+        ModelId::new(
+            ProviderId(model.provider_id.clone()),
+            model.model_id.clone(),
+        )
+    };
+
+    let exact = models.iter().find(|model| {
+        let prov = ProviderId(model.provider_id.clone()).storage_key();
+        model.model_id == query
+            || format!("{prov}/{}", model.model_id) == query
+            || model
+                .aliases
+                .iter()
+                .any(|alias| alias == query || format!("{prov}/{alias}") == query)
+    });
+    if let Some(model) = exact {
+        return Some(to_id(model));
+    }
+
+    let matcher = SkimMatcherV2::default();
+    let mut best: Option<(i64, &ProviderModel)> = None;
+    for model in models {
+        if let Some(score) = model_match_score(&matcher, model, query)
+            && best.is_none_or(|(best_score, _)| score > best_score)
+        {
+            best = Some((score, model));
+        }
+    }
+    best.map(|(_, model)| to_id(model))
+}
+
 impl Tui {
     pub async fn handle_fuzzy_finder_mode(&mut self, key: KeyEvent) -> Result<bool> {
         use crate::tui::widgets::fuzzy_finder::FuzzyFinderResult;
@@ -86,35 +190,7 @@ impl Tui {
 
                         use crate::tui::widgets::fuzzy_finder::FuzzyFinderMode as FMode;
                         if is_model_cmd {
-                            self.input_panel_state
-                                .fuzzy_finder
-                                .activate(cursor_pos, FMode::Models);
-                            // Populate models from server
-                            if let Ok(models) = self.client.list_models(None).await {
-                                let current_model = self.current_model.clone();
-                                let picker_items: Vec<PickerItem> = models
-                                    .into_iter()
-                                    .map(|m| {
-                                        let provider_id = ProviderId(m.provider_id.clone());
-                                        let model_id =
-                                            ModelId::new(provider_id.clone(), m.model_id.clone());
-                                        let display_name = m.display_name;
-                                        let prov = provider_id.storage_key();
-                                        let display_full = format!("{prov}/{display_name}");
-                                        let display = if model_id == current_model {
-                                            format!("{display_full} (current)")
-                                        } else {
-                                            display_full.clone()
-                                        };
-                                        // Insert provider/id for lookup
-                                        let insert = format!("{}/{}", prov, m.model_id);
-                                        PickerItem::new(display, insert)
-                                    })
-                                    .collect();
-                                self.input_panel_state
-                                    .fuzzy_finder
-                                    .update_results(picker_items);
-                            }
+                            self.open_model_picker(cursor_pos).await;
                         } else {
                             self.input_panel_state
                                 .fuzzy_finder
@@ -147,12 +223,19 @@ impl Tui {
             return Ok(false);
         }
 
+        if mode == FuzzyFinderMode::Files && post_result.is_none() {
+            self.update_symbol_results().await;
+        }
+
         // Otherwise handle explicit results (Enter / Esc etc.)
         if let Some(result) = post_result {
             match result {
                 FuzzyFinderResult::Close => {
                     self.input_panel_state.deactivate_fuzzy();
                     self.restore_previous_mode();
+                    if let Some(draft) = self.retry_picker_draft.take() {
+                        self.input_panel_state.replace_content(&draft, None);
+                    }
                 }
                 FuzzyFinderResult::Select(selected_item) => {
                     match mode {
@@ -183,36 +266,7 @@ impl Tui {
 
                                 use crate::tui::widgets::fuzzy_finder::FuzzyFinderMode as FMode;
                                 if selected_cmd == model_cmd_name {
-                                    self.input_panel_state
-                                        .fuzzy_finder
-                                        .activate(cursor_pos, FMode::Models);
-
-                                    // Populate models from server
-                                    if let Ok(models) = self.client.list_models(None).await {
-                                        let current_model = self.current_model.clone();
-                                        let results: Vec<PickerItem> = models
-                                            .into_iter()
-                                            .map(|m| {
-                                                let provider_id = ProviderId(m.provider_id.clone());
-                                                let model_id = ModelId::new(
-                                                    provider_id.clone(),
-                                                    m.model_id.clone(),
-                                                );
-                                                let prov = provider_id.storage_key();
-                                                let display_full =
-                                                    format!("{}/{}", prov, m.display_name);
-                                                let label = if model_id == current_model {
-                                                    format!("{display_full} (current)")
-                                                } else {
-                                                    display_full.clone()
-                                                };
-                                                // Insert provider/id for lookup
-                                                let insert = format!("{}/{}", prov, m.model_id);
-                                                PickerItem::new(label, insert)
-                                            })
-                                            .collect();
-                                        self.input_panel_state.fuzzy_finder.update_results(results);
-                                    }
+                                    self.open_model_picker(cursor_pos).await;
                                 } else {
                                     self.input_panel_state
                                         .fuzzy_finder
@@ -236,6 +290,17 @@ impl Tui {
                                 self.restore_previous_mode();
                             }
                         }
+                        FuzzyFinderMode::Models if self.retry_picker_draft.is_some() => {
+                            use crate::tui::commands::CoreCommandType;
+                            let command = format!(
+                                "/{} {}",
+                                CoreCommandType::Retry.command_name(),
+                                selected_item.insert
+                            );
+                            self.send_message(command).await?;
+                            let draft = self.retry_picker_draft.take().unwrap_or_default();
+                            self.input_panel_state.replace_content(&draft, None);
+                        }
                         FuzzyFinderMode::Models => {
                             // Use the insert text (provider/model_id) for command
                             use crate::tui::commands::CoreCommandType;
@@ -244,6 +309,7 @@ impl Tui {
                                 CoreCommandType::Model.command_name(),
                                 selected_item.insert
                             );
+                            self.input_history.push(&command);
                             self.send_message(command).await?;
                             // Clear the input after sending
                             self.input_panel_state.clear();
@@ -256,6 +322,7 @@ impl Tui {
                                 TuiCommandType::Theme.command_name(),
                                 selected_item.label
                             );
+                            self.input_history.push(&command);
                             self.send_message(command).await?;
                             // Clear the input after sending
                             self.input_panel_state.clear();
@@ -315,68 +382,9 @@ impl Tui {
 
             match mode {
                 FuzzyFinderMode::Models => {
-                    // Filter models based on query from server models
-                    use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
-
-                    if let Ok(models) = self.client.list_models(None).await {
-                        let matcher = SkimMatcherV2::default();
-                        let current_model = self.current_model.clone();
-                        let mut scored_models: Vec<(i64, String, String)> = Vec::new();
-
-                        for m in models {
-                            let provider_id = ProviderId(m.provider_id.clone());
-                            let model_id = ModelId::new(provider_id.clone(), m.model_id.clone());
-                            let prov = provider_id.storage_key();
-                            let full_label = if model_id == current_model {
-                                format!("{}/{} (current)", prov, m.display_name)
-                            } else {
-                                format!("{}/{}", prov, m.display_name)
-                            };
-                            // Insert provider/id for command completion
-                            let insert = format!("{}/{}", prov, m.model_id);
-
-                            // Match against full label, display name, model id, and aliases (alias and provider/alias)
-                            let full_score = matcher.fuzzy_match(&full_label, &query);
-                            let name_score = matcher.fuzzy_match(&m.display_name, &query);
-                            let id_score = matcher.fuzzy_match(&m.model_id, &query);
-                            let alias_score: Option<i64> = m
-                                .aliases
-                                .iter()
-                                .filter_map(|a| {
-                                    let s1 = matcher.fuzzy_match(a, &query);
-                                    let s2 = matcher.fuzzy_match(&format!("{prov}/{a}"), &query);
-                                    match (s1, s2) {
-                                        (Some(x), Some(y)) => Some(x.max(y)),
-                                        (Some(x), None) => Some(x),
-                                        (None, Some(y)) => Some(y),
-                                        (None, None) => None,
-                                    }
-                                })
-                                .max();
-
-                            // Take the maximum score from all matches
-                            let best_score = [full_score, name_score, id_score, alias_score]
-                                .into_iter()
-                                .flatten()
-                                .max();
-
-                            if let Some(score) = best_score {
-                                scored_models.push((score, full_label, insert));
-                            }
-                        }
-
-                        // Sort by score (highest first)
-                        scored_models.sort_by(|a, b| b.0.cmp(&a.0));
-
-                        let results: Vec<_> = scored_models
-                            .into_iter()
-                            .map(|(_, label, insert)| {
-                                crate::tui::widgets::fuzzy_finder::PickerItem::new(label, insert)
-                            })
-                            .collect();
-
-                        self.input_panel_state.fuzzy_finder.update_results(results);
-                    }
+                    let models = self.available_models().await;
+                    let results = model_picker_items(&models, &self.current_model, &query);
+                    self.input_panel_state.fuzzy_finder.update_results(results);
                 }
                 FuzzyFinderMode::Themes => {
                     // Filter themes based on query
@@ -423,4 +431,147 @@ impl Tui {
 
         Ok(false)
     }
+
+    /// Switches the file picker to the file's symbols once the query reaches
+    /// `path#`, fetching the symbol list the first time the file is used.
+    async fn update_symbol_results(&mut self) {
+        if !self.preferences.ui.symbol_mentions() {
+            return;
+        }
+        let Some(query) = self.input_panel_state.get_current_fuzzy_query() else {
+            return;
+        };
+        let Some((path, symbol_query)) = query.split_once('#') else {
+            return;
+        };
+        if !self.input_panel_state.file_cache.contains(path).await {
+            return;
+        }
+
+        if !self.symbol_cache.contains_key(path) {
+            let symbols = match self.client.list_file_symbols(path).await {
+                Ok(symbols) => symbols,
+                Err(e) => {
+                    warn!(target: "tui.file_cache", "Failed to list symbols for {}: {}", path, e);
+                    Vec::new()
+                }
+            };
+            self.symbol_cache.insert(path.to_string(), symbols);
+        }
+        let symbols = self.symbol_cache.get(path).map_or(&[][..], Vec::as_slice);
+
+        let matcher = SkimMatcherV2::default();
+        let mut scored: Vec<(i64, &FileSymbol)> = symbols
+            .iter()
+            .filter_map(|symbol| {
+                if symbol_query.is_empty() {
+                    return Some((0, symbol));
+                }
+                matcher
+                    .fuzzy_match(&symbol.qualified_name(), symbol_query)
+                    .map(|score| (score, symbol))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+
+        let picker_items = scored
+            .into_iter()
+            .take(10)
+            .map(|(_, symbol)| {
+                let name = symbol.qualified_name();
+                PickerItem::new(
+                    format!(
+                        "{path}#{name}  {} (lines {}-{})",
+                        symbol.kind, symbol.start_line, symbol.end_line
+                    ),
+                    format!("@{path}#{name} "),
+                )
+            })
+            .collect();
+        self.input_panel_state
+            .fuzzy_finder
+            .update_results(picker_items);
+    }
+
+    /// Lists the models `/model` can switch to: those the server's visibility
+    /// policies allow, restricted to providers that are authenticated.
+    pub(crate) async fn available_models(&self) -> Vec<ProviderModel> {
+        let models = match self.client.list_models(None).await {
+            Ok(models) => models,
+            Err(e) => {
+                warn!("Failed to list models: {}", e);
+                return Vec::new();
+            }
+        };
+        let statuses = match self.client.get_provider_auth_status(None).await {
+            Ok(statuses) => statuses,
+            Err(e) => {
+                warn!("Failed to load provider auth status: {}", e);
+                return models;
+            }
+        };
+
+        models
+            .into_iter()
+            .filter(|model| {
+                statuses.iter().any(|status| {
+                    status.provider_id == model.provider_id
+                        && status
+                            .auth_source
+                            .as_ref()
+                            .is_some_and(|source| *source != AuthSource::None)
+                })
+            })
+            .collect()
+    }
+
+    /// Opens the model picker with every available model listed.
+    pub(crate) async fn open_model_picker(&mut self, cursor_pos: usize) {
+        self.input_panel_state
+            .fuzzy_finder
+            .activate(cursor_pos, FuzzyFinderMode::Models);
+        let models = self.available_models().await;
+        let items = model_picker_items(&models, &self.current_model, "");
+        self.input_panel_state.fuzzy_finder.update_results(items);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(provider: &str, id: &str, name: &str, aliases: &[&str]) -> ProviderModel {
+        ProviderModel {
+            provider_id: provider.to_string(),
+            model_id: id.to_string(),
+            display_name: name.to_string(),
+            aliases: aliases.iter().map(|alias| (*alias).to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn fuzzy_resolve_model_prefers_exact_then_best_match() {
+        let models = vec![
+            model(
+                "anthropic",
+                "claude-sonnet-4-5",
+                "Claude Sonnet 4.5",
+                &["sonnet"],
+            ),
+            model("anthropic", "claude-opus-4-1", "Claude Opus 4.1", &["opus"]),
+            model("openai", "gpt-5", "GPT-5", &["gpt5"]),
+        ];
+
+        let resolved = fuzzy_resolve_model("opus", &models).unwrap();
+        assert_eq!(resolved.id, "claude-opus-4-1");
+
+        let resolved = fuzzy_resolve_model("openai/gpt-5", &models).unwrap();
+        assert_eq!(resolved.id, "gpt-5");
+
+        let resolved = fuzzy_resolve_model("son45", &models).unwrap();
+        assert_eq!(resolved.id, "claude-sonnet-4-5");
+
+        assert!(fuzzy_resolve_model("zzzz", &models).is_none());
+    }
 }
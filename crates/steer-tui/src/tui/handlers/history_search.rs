@@ -0,0 +1,106 @@
+use crate::error::Result;
+use crate::tui::InputMode;
+use crate::tui::Tui;
+use crate::tui::state::HistorySearchState;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tui_textarea::CursorMove;
+
+impl Tui {
+    /// Begin a Ctrl+R reverse-incremental search over the input history.
+    pub fn start_history_search(&mut self) {
+        self.input_history.reset_navigation();
+        self.history_search = Some(HistorySearchState {
+            original: self.input_panel_state.content(),
+            ..HistorySearchState::default()
+        });
+        self.switch_mode(InputMode::HistorySearch);
+    }
+
+    pub fn handle_history_search_mode(&mut self, key: KeyEvent) -> Result<bool> {
+        let Some(mut search) = self.history_search.take() else {
+            self.restore_previous_mode();
+            return Ok(false);
+        };
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        if key.code == KeyCode::Esc || (ctrl && matches!(key.code, KeyCode::Char('c' | 'g'))) {
+            self.set_input_text(&search.original);
+            self.restore_previous_mode();
+            return Ok(false);
+        }
+
+        match key.code {
+            // Ctrl+R again steps to the next older match
+            KeyCode::Char('r') if ctrl => {
+                let before = search.match_index.unwrap_or(self.input_history.len());
+                if let Some(index) = self.input_history.search_before(&search.query, before) {
+                    search.match_index = Some(index);
+                }
+            }
+            KeyCode::Backspace => {
+                search.query.pop();
+                search.match_index = if search.query.is_empty() {
+                    None
+                } else {
+                    self.input_history
+                        .search_before(&search.query, self.input_history.len())
+                };
+            }
+            KeyCode::Char(ch) if !ctrl && !key.modifiers.contains(KeyModifiers::ALT) => {
+                search.query.push(ch);
+                // Keep the current match while it still matches the longer query
+                let from = search
+                    .match_index
+                    .map_or(self.input_history.len(), |index| index + 1);
+                search.match_index = self.input_history.search_before(&search.query, from);
+            }
+            // Any other key accepts the match into the input box for editing
+            _ => {
+                self.restore_previous_mode();
+                return Ok(false);
+            }
+        }
+
+        let shown = match search.match_index {
+            Some(index) => self.input_history.entries().get(index).cloned(),
+            None if search.query.is_empty() => Some(search.original.clone()),
+            None => None,
+        };
+        if let Some(text) = shown {
+            self.set_input_text(&text);
+        }
+        self.history_search = Some(search);
+        Ok(false)
+    }
+
+    /// Recall the previous history entry into the input box. Returns false
+    /// when there is nothing older to show.
+    pub fn recall_previous_input(&mut self) -> bool {
+        let current = self.input_panel_state.content();
+        let Some(entry) = self.input_history.older(&current).map(str::to_string) else {
+            return false;
+        };
+        self.set_input_text(&entry);
+        true
+    }
+
+    /// Recall the next history entry, or the original draft once past the
+    /// newest one. Returns false when not navigating history.
+    pub fn recall_next_input(&mut self) -> bool {
+        let Some(entry) = self.input_history.newer() else {
+            return false;
+        };
+        self.set_input_text(&entry);
+        true
+    }
+
+    /// Replace the input box content and put the cursor at the end.
+    fn set_input_text(&mut self, text: &str) {
+        self.input_panel_state.replace_content(text, None);
+        self.input_panel_state
+            .textarea
+            .move_cursor(CursorMove::Bottom);
+        self.input_panel_state.textarea.move_cursor(CursorMove::End);
+        self.sync_attachments_from_input_tokens();
+    }
+}
@@ -1,8 +1,14 @@
 pub mod approval;
 pub mod bash;
+pub mod best_of;
+pub mod code_wrap;
+pub mod confirm_commit;
 pub mod confirm_exit;
+pub mod copy_selection;
 pub mod edit_selection;
 pub mod fuzzy_finder;
+pub mod history_search;
+pub mod retry;
 pub mod setup;
 pub mod simple;
 pub mod text_manipulation;
@@ -11,12 +17,33 @@ pub mod vim;
 mod setup_impl;
 
 use crate::error::Result;
+use crate::tui::InputMode;
 use crate::tui::Tui;
-use ratatui::crossterm::event::KeyEvent;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use steer_grpc::client_api::EditingMode;
 
 impl Tui {
     pub async fn handle_key_event(&mut self, key: KeyEvent) -> Result<bool> {
+        // The safe renderer can't show any other mode, and quitting must not
+        // cancel the operation the server is still running.
+        if self.safe_render {
+            return Ok(matches!(
+                (key.code, key.modifiers),
+                (KeyCode::Char('c' | 'd'), KeyModifiers::CONTROL) | (KeyCode::Char('q'), _)
+            ));
+        }
+
+        if matches!(
+            self.input_mode,
+            InputMode::Simple | InputMode::VimInsert | InputMode::VimNormal
+        ) && (self.handle_retry_keys(key).await
+            || self.handle_best_of_keys(key).await
+            || self.handle_copy_keys(key)
+            || self.handle_code_wrap_keys(key))
+        {
+            return Ok(false);
+        }
+
         // Check editing mode to determine handler
         match self.preferences.ui.editing_mode {
             EditingMode::Simple => self.handle_simple_mode(key).await,
@@ -0,0 +1,52 @@
+use crate::tui::InputMode;
+use crate::tui::NoticeLevel;
+use crate::tui::Tui;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+impl Tui {
+    /// Alt+R retries the last turn with a model chosen from the picker, and
+    /// Alt+P shows or hides the attempts a retry replaced. Returns true when
+    /// the key was handled.
+    pub async fn handle_retry_keys(&mut self, key: KeyEvent) -> bool {
+        if key.modifiers != KeyModifiers::ALT {
+            return false;
+        }
+        match key.code {
+            KeyCode::Char('r') => {
+                self.open_retry_model_picker().await;
+                true
+            }
+            KeyCode::Char('p') => {
+                self.toggle_previous_attempts();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Open the model picker to retry the last turn. The picked model is used
+    /// for that retry only; the input draft is restored afterwards.
+    async fn open_retry_model_picker(&mut self) {
+        if self.is_processing {
+            self.push_notice(
+                NoticeLevel::Warn,
+                "Wait for the current response to finish before retrying".to_string(),
+            );
+            return;
+        }
+        self.retry_picker_draft = Some(self.input_panel_state.content());
+        self.input_panel_state.clear();
+        self.open_model_picker(0).await;
+        self.switch_mode(InputMode::FuzzyFinder);
+    }
+
+    fn toggle_previous_attempts(&mut self) {
+        let show = !self.chat_viewport.show_previous_attempts();
+        if show && self.chat_store.previous_attempt_ids().is_empty() {
+            self.push_notice(NoticeLevel::Info, "No previous attempt to show".to_string());
+            return;
+        }
+        self.chat_viewport.set_show_previous_attempts(show);
+        self.chat_viewport.state_mut().scroll_to_bottom();
+    }
+}
@@ -3,17 +3,25 @@ use crate::tui::InputMode;
 use crate::tui::Tui;
 use crate::tui::state::{AuthStatus, SetupState, SetupStep};
 use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use steer_grpc::client_api::{AuthProgress, AuthSource, Preferences, ProviderId};
+use steer_grpc::client_api::{AuthMethod, AuthProgress, AuthSource, Preferences, ProviderId};
 use tracing::debug;
 
 // Remove TODO comment - authentication is now handled using the generic trait
 pub struct SetupHandler;
 
+/// Over SSH a browser redirect to localhost can't reach us, so ask for a
+/// device-code flow; the server falls back to manual paste if unsupported.
+fn preferred_auth_method() -> Option<AuthMethod> {
+    let over_ssh =
+        std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some();
+    over_ssh.then_some(AuthMethod::DeviceCode)
+}
+
 impl SetupHandler {
     async fn start_auth_flow(tui: &mut Tui, provider_id: &ProviderId) -> Result<()> {
         let response = tui
             .client
-            .start_auth(provider_id.storage_key())
+            .start_auth(provider_id.storage_key(), preferred_auth_method())
             .await
             .map_err(|e| crate::error::Error::Auth(e.to_string()))?;
 
@@ -23,20 +31,34 @@ impl SetupHandler {
 
         setup_state.auth_flow_id = Some(response.flow_id.clone());
         setup_state.auth_progress = response.progress;
+        setup_state.auth_url = None;
         setup_state.auth_input.clear();
         setup_state
             .auth_providers
             .insert(provider_id.clone(), AuthStatus::InProgress);
 
-        if let Some(AuthProgress::OAuthStarted { auth_url }) = setup_state.auth_progress.as_ref()
-            && let Err(e) = open::that(auth_url)
-        {
-            setup_state.error_message = Some(format!("Failed to open browser: {e}"));
-        }
+        Self::open_new_auth_url(setup_state);
 
         Ok(())
     }
 
+    /// Opens the browser the first time an OAuth URL shows up, which may be after a
+    /// failed refresh rather than at the start of the flow.
+    fn open_new_auth_url(setup_state: &mut SetupState) {
+        let Some(AuthProgress::OAuthStarted { auth_url }) = setup_state.auth_progress.as_ref()
+        else {
+            return;
+        };
+        if setup_state.auth_url.as_ref() == Some(auth_url) {
+            return;
+        }
+
+        setup_state.auth_url = Some(auth_url.clone());
+        if let Err(e) = open::that(auth_url) {
+            setup_state.error_message = Some(format!("Failed to open browser: {e}"));
+        }
+    }
+
     async fn refresh_auth_status(tui: &mut Tui, provider_id: &ProviderId) -> Result<AuthStatus> {
         let statuses = tui
             .client
@@ -187,7 +209,11 @@ impl SetupHandler {
 
                 let expects_input = matches!(
                     progress_state,
-                    Some(AuthProgress::NeedInput { .. } | AuthProgress::OAuthStarted { .. })
+                    Some(
+                        AuthProgress::NeedInput { .. }
+                            | AuthProgress::OAuthStarted { .. }
+                            | AuthProgress::WaitingForCallback { .. }
+                    )
                 );
 
                 let flow_id = tui
@@ -261,7 +287,9 @@ impl SetupHandler {
                 let expects_input = state.auth_progress.as_ref().is_some_and(|progress| {
                     matches!(
                         progress,
-                        AuthProgress::NeedInput { .. } | AuthProgress::OAuthStarted { .. }
+                        AuthProgress::NeedInput { .. }
+                            | AuthProgress::OAuthStarted { .. }
+                            | AuthProgress::WaitingForCallback { .. }
                     )
                 });
                 if expects_input {
@@ -276,7 +304,9 @@ impl SetupHandler {
                 let expects_input = state.auth_progress.as_ref().is_some_and(|progress| {
                     matches!(
                         progress,
-                        AuthProgress::NeedInput { .. } | AuthProgress::OAuthStarted { .. }
+                        AuthProgress::NeedInput { .. }
+                            | AuthProgress::OAuthStarted { .. }
+                            | AuthProgress::WaitingForCallback { .. }
                     )
                 });
                 if expects_input {
@@ -376,7 +406,12 @@ impl SetupHandler {
             .is_some_and(|progress| {
                 matches!(
                     progress,
-                    AuthProgress::OAuthStarted { .. } | AuthProgress::InProgress { .. }
+                    AuthProgress::OAuthStarted { .. }
+                        | AuthProgress::InProgress { .. }
+                        | AuthProgress::DeviceCode { .. }
+                        | AuthProgress::WaitingForCallback { .. }
+                        | AuthProgress::ExchangingCode
+                        | AuthProgress::Refreshing
                 )
             });
 
@@ -408,6 +443,7 @@ impl SetupHandler {
                 return Ok(false);
             };
             setup_state.auth_progress = Some(progress.clone());
+            Self::open_new_auth_url(setup_state);
 
             if let Some(message) = &error_message {
                 setup_state.error_message = Some(message.clone());
@@ -13,8 +13,11 @@ impl Tui {
             InputMode::BashCommand => return self.handle_bash_mode(key).await,
             InputMode::AwaitingApproval => return self.handle_approval_mode(key).await,
             InputMode::EditMessageSelection => return self.handle_edit_selection_mode(key).await,
+            InputMode::CopySelection => return self.handle_copy_selection_mode(key).await,
             InputMode::FuzzyFinder => return self.handle_fuzzy_finder_mode(key).await,
+            InputMode::HistorySearch => return self.handle_history_search_mode(key),
             InputMode::ConfirmExit => return self.handle_confirm_exit_mode(key).await,
+            InputMode::ConfirmCommit => return self.handle_confirm_commit_mode(key).await,
             InputMode::Setup => return self.handle_setup_mode(key).await,
             InputMode::Simple | InputMode::VimInsert | InputMode::VimNormal => {}
         }
@@ -89,10 +92,11 @@ impl Tui {
                     if !self.pending_attachments.is_empty() && content.starts_with('/') {
                         self.push_notice(
                             NoticeLevel::Warn,
-                            "Image attachments are only supported for regular prompts.".to_string(),
+                            "Attachments are only supported for regular prompts.".to_string(),
                         );
                         return Ok(false);
                     }
+                    self.input_history.push(&content);
                     if content.starts_with('!') && content.len() > 1 {
                         // Execute as bash command
                         let command = content[1..].trim().to_string();
@@ -106,6 +110,7 @@ impl Tui {
                     }
                     self.input_panel_state.clear();
                     self.pending_attachments.clear();
+                    self.restore_staged_attachments();
                 }
             }
 
@@ -205,8 +210,8 @@ impl Tui {
                 }
             }
 
-            // Toggle view mode with Ctrl+R
-            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            // Toggle view mode with Ctrl+O
+            KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 self.chat_viewport.state_mut().toggle_view_mode();
                 self.chat_viewport.state_mut().scroll_to_bottom();
             }
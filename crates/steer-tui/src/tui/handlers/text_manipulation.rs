@@ -20,16 +20,35 @@ impl Tui {
                     .move_cursor(CursorMove::Forward);
                 Ok(true)
             }
+            // Up on the first line and Down on the last walk the input history
             (KeyCode::Up, KeyModifiers::NONE) => {
+                if self.input_panel_state.textarea.cursor().0 == 0 && self.recall_previous_input() {
+                    return Ok(true);
+                }
                 self.input_panel_state.textarea.move_cursor(CursorMove::Up);
                 Ok(true)
             }
             (KeyCode::Down, KeyModifiers::NONE) => {
+                let last_row = self
+                    .input_panel_state
+                    .textarea
+                    .lines()
+                    .len()
+                    .saturating_sub(1);
+                if self.input_panel_state.textarea.cursor().0 == last_row
+                    && self.recall_next_input()
+                {
+                    return Ok(true);
+                }
                 self.input_panel_state
                     .textarea
                     .move_cursor(CursorMove::Down);
                 Ok(true)
             }
+            (KeyCode::Char('r'), KeyModifiers::CONTROL) => {
+                self.start_history_search();
+                Ok(true)
+            }
 
             // Word navigation (Alt/Option + arrows)
             (KeyCode::Left, KeyModifiers::ALT) => {
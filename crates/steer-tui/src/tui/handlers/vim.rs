@@ -15,8 +15,11 @@ impl Tui {
             InputMode::BashCommand => self.handle_bash_mode(key).await,
             InputMode::AwaitingApproval => self.handle_approval_mode(key).await,
             InputMode::EditMessageSelection => self.handle_edit_selection_mode(key).await,
+            InputMode::CopySelection => self.handle_copy_selection_mode(key).await,
             InputMode::FuzzyFinder => self.handle_fuzzy_finder_mode(key).await,
+            InputMode::HistorySearch => self.handle_history_search_mode(key),
             InputMode::ConfirmExit => self.handle_confirm_exit_mode(key).await,
+            InputMode::ConfirmCommit => self.handle_confirm_commit_mode(key).await,
             InputMode::Setup => self.handle_setup_mode(key).await,
             InputMode::Simple => self.handle_simple_mode(key).await, // Fallback
         }
@@ -509,11 +512,12 @@ impl Tui {
                 } else if !self.pending_attachments.is_empty() && content.starts_with('/') {
                     self.push_notice(
                         NoticeLevel::Warn,
-                        "Image attachments are only supported for regular prompts.".to_string(),
+                        "Attachments are only supported for regular prompts.".to_string(),
                     );
                     return Ok(false);
                 } else if content.starts_with('!') && content.len() > 1 {
                     // Execute as bash command
+                    self.input_history.push(&content);
                     let command = content[1..].trim().to_string();
                     self.client.execute_bash_command(command).await?;
                     self.input_panel_state.clear();
@@ -521,11 +525,13 @@ impl Tui {
                     self.set_mode(InputMode::VimNormal);
                 } else if content.starts_with('/') {
                     // Handle as slash command
+                    self.input_history.push(&content);
                     let old_editing_mode = self.preferences.ui.editing_mode;
                     self.handle_slash_command(content).await?;
                     self.input_panel_state.clear();
                     self.sync_attachments_from_input_tokens();
                     self.pending_attachments.clear();
+                    self.restore_staged_attachments();
                     // Return to VimNormal only if we're *still* in VimInsert and the
                     // editing mode hasn’t changed (e.g. not switched into Setup).
                     if self.input_mode == InputMode::VimInsert
@@ -535,6 +541,7 @@ impl Tui {
                     }
                 } else {
                     // Send as normal message
+                    self.input_history.push(&content);
                     self.send_message(content).await?;
                     self.input_panel_state.clear();
                     self.sync_attachments_from_input_tokens();
@@ -10,15 +10,43 @@ use std::time::Duration;
 use base64::Engine as _;
 
 const IMAGE_TOKEN_LABEL_PREFIX: &str = "[Image ";
+const FILE_TOKEN_LABEL_PREFIX: &str = "[File ";
 const IMAGE_TOKEN_LABEL_SUFFIX: &str = "]";
 const FIRST_ATTACHMENT_TOKEN: u32 = 0xE000;
 
+const COPY_TOAST_DURATION: Duration = Duration::from_secs(2);
+const COPY_SELECTION_HINTS: &[(&str, &str)] = &[
+    ("[↑↓]", "navigate"),
+    ("[y]", "copy message"),
+    ("[c]", "next code block"),
+    ("[f]", "next file path"),
+    ("[Esc]", "close"),
+];
+
+/// Largest file `/attach` (or a pasted path) will read into a message.
+const MAX_ATTACHMENT_BYTES: u64 = 5 * 1024 * 1024;
+
+/// An image or file waiting in the input, shown inline as a token and label.
 #[derive(Debug, Clone)]
 struct PendingAttachment {
-    image: ImageContent,
+    content: UserContent,
     token: char,
 }
 
+impl PendingAttachment {
+    fn is_image(&self) -> bool {
+        matches!(self.content, UserContent::Image { .. })
+    }
+
+    /// Label shown after the token; images are numbered, files are named.
+    fn label(&self, image_number: usize) -> String {
+        match &self.content {
+            UserContent::Attachment { attachment } => format_inline_file_token(attachment),
+            _ => format_inline_image_token(image_number),
+        }
+    }
+}
+
 use crate::tui::update::UpdateStatus;
 
 use crate::error::{Error, Result};
@@ -33,9 +61,11 @@ use ratatui::crossterm::event::{self, Event, EventStream, KeyCode, KeyEventKind,
 use ratatui::{Frame, Terminal, layout::Rect};
 use steer_grpc::AgentClient;
 use steer_grpc::client_api::{
-    AssistantContent, ClientEvent, EditingMode, ImageContent, ImageSource, LlmStatus, Message,
-    MessageData, ModelId, OpId, Preferences, ProviderId, UserContent, WorkspaceStatus, builtin,
-    default_primary_agent_id,
+    AssistantContent, AttachmentBody, AttachmentContent, AuthSource, ClientEvent, CommitDraft,
+    ContextReport, ContextSegmentKind, EditingMode, FileSymbol, ImageContent, ImageSource,
+    IsolationKind, IsolationStatus, LlmStatus, MergeReport, Message, MessageData, ModelId,
+    ModelSwitchMode, OpId, OperationTiming, Preferences, ProviderId, UserContent, WorkspaceStatus,
+    builtin, default_primary_agent_id,
 };
 
 use crate::tui::events::processor::PendingToolApproval;
@@ -70,10 +100,19 @@ fn format_inline_image_token(n: usize) -> String {
     format!("{IMAGE_TOKEN_LABEL_PREFIX}{n}{IMAGE_TOKEN_LABEL_SUFFIX}")
 }
 
-/// Returns the byte length of an image label at the start of `s`, if one is present.
-/// Matches any label of the form `[Image ...]`.
-fn image_label_len_at(s: &str) -> Option<usize> {
-    if !s.starts_with(IMAGE_TOKEN_LABEL_PREFIX) {
+fn format_inline_file_token(attachment: &AttachmentContent) -> String {
+    let name = attachment.file_name().replace(IMAGE_TOKEN_LABEL_SUFFIX, "");
+    format!("{FILE_TOKEN_LABEL_PREFIX}{name}{IMAGE_TOKEN_LABEL_SUFFIX}")
+}
+
+fn is_attachment_label(s: &str) -> bool {
+    s.starts_with(IMAGE_TOKEN_LABEL_PREFIX) || s.starts_with(FILE_TOKEN_LABEL_PREFIX)
+}
+
+/// Returns the byte length of an attachment label at the start of `s`, if one is present.
+/// Matches any label of the form `[Image ...]` or `[File ...]`.
+fn attachment_label_len_at(s: &str) -> Option<usize> {
+    if !is_attachment_label(s) {
         return None;
     }
     let end = s.find(IMAGE_TOKEN_LABEL_SUFFIX)?;
@@ -89,7 +128,7 @@ fn attachment_spans(content: &str, attachments: &[PendingAttachment]) -> Vec<(ch
         }
 
         let mut end = start + ch.len_utf8();
-        if let Some(label_len) = image_label_len_at(&content[end..]) {
+        if let Some(label_len) = attachment_label_len_at(&content[end..]) {
             end += label_len;
         }
 
@@ -99,8 +138,110 @@ fn attachment_spans(content: &str, attachments: &[PendingAttachment]) -> Vec<(ch
     spans
 }
 
-fn parse_inline_message_content(content: &str, images: &[PendingAttachment]) -> Vec<UserContent> {
-    if images.is_empty() {
+/// Formats a token count with thousands separators.
+fn format_token_count(tokens: u32) -> String {
+    let digits = tokens.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, ch) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(ch);
+    }
+    formatted
+}
+
+const TIMING_BAR_WIDTH: u64 = 24;
+
+fn format_duration_ms(millis: u64) -> String {
+    if millis < 1_000 {
+        format!("{millis}ms")
+    } else {
+        format!(
+            "{:.1}s",
+            std::time::Duration::from_millis(millis).as_secs_f64()
+        )
+    }
+}
+
+/// Renders an operation's spans as bars scaled to the longest one.
+fn format_operation_timing(timing: &OperationTiming) -> String {
+    let mut output = format!(
+        "Last operation took {}\n",
+        format_duration_ms(timing.total_ms)
+    );
+    if timing.spans.is_empty() {
+        output.push_str("  (no spans recorded)\n");
+        return output;
+    }
+
+    let labels: Vec<String> = timing.spans.iter().map(|span| span.kind.label()).collect();
+    let label_width = labels
+        .iter()
+        .map(|label| label.chars().count())
+        .max()
+        .unwrap_or(0);
+    let longest = timing
+        .spans
+        .iter()
+        .map(|span| span.duration_ms)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    for (span, label) in timing.spans.iter().zip(&labels) {
+        let mut cells = span.duration_ms.saturating_mul(TIMING_BAR_WIDTH) / longest;
+        if span.duration_ms > 0 {
+            cells = cells.max(1);
+        }
+        let bar = "█".repeat(usize::try_from(cells).unwrap_or(0));
+        output.push_str(&format!(
+            "  {label:<label_width$}  {bar:<bar_width$}  {}\n",
+            format_duration_ms(span.duration_ms),
+            bar_width = usize::try_from(TIMING_BAR_WIDTH).unwrap_or(0),
+        ));
+    }
+    output.push_str("Spans can overlap and need not add up to the total.");
+    output
+}
+
+/// Summarizes which models a catalog reload added, removed or changed.
+fn format_catalog_reload(reload: &steer_grpc::proto::ReloadCatalogResponse) -> String {
+    let mut output = format!("Reloaded model catalogs: {} models", reload.model_count);
+    for (label, models) in [
+        ("Added", &reload.added),
+        ("Removed", &reload.removed),
+        ("Changed", &reload.changed),
+    ] {
+        if models.is_empty() {
+            continue;
+        }
+        let names: Vec<String> = models
+            .iter()
+            .map(|model| format!("{}/{}", model.provider_id, model.model_id))
+            .collect();
+        output.push_str(&format!("\n  {label}: {}", names.join(", ")));
+    }
+    if reload.added.is_empty() && reload.removed.is_empty() && reload.changed.is_empty() {
+        output.push_str("\n  No models changed.");
+    }
+    output
+}
+
+/// Returns the `@mention` tokens in a message, without the leading `@`.
+fn file_mentions(content: &str) -> impl Iterator<Item = &str> {
+    content
+        .split_whitespace()
+        .filter_map(|token| token.strip_prefix('@'))
+        .map(|mention| mention.trim_end_matches([',', '.', ';', ':', ')', '?', '!']))
+        .filter(|mention| !mention.is_empty())
+}
+
+fn parse_inline_message_content(
+    content: &str,
+    attachments: &[PendingAttachment],
+) -> Vec<UserContent> {
+    if attachments.is_empty() {
         let trimmed = content.trim().to_string();
         if trimmed.is_empty() {
             return Vec::new();
@@ -118,18 +259,16 @@ fn parse_inline_message_content(content: &str, images: &[PendingAttachment]) ->
             None => break,
         };
 
-        if let Some(attachment) = images.iter().find(|attachment| attachment.token == ch) {
+        if let Some(attachment) = attachments.iter().find(|attachment| attachment.token == ch) {
             let trimmed = text_buf.trim().to_string();
             if !trimmed.is_empty() {
                 result.push(UserContent::Text { text: trimmed });
             }
             text_buf.clear();
-            result.push(UserContent::Image {
-                image: attachment.image.clone(),
-            });
+            result.push(attachment.content.clone());
 
             cursor += ch.len_utf8();
-            if let Some(label_len) = image_label_len_at(&content[cursor..]) {
+            if let Some(label_len) = attachment_label_len_at(&content[cursor..]) {
                 cursor += label_len;
             }
             continue;
@@ -174,9 +313,7 @@ fn strip_image_token_labels(content: &str) -> String {
 
         if j < chars.len() {
             let candidate: String = chars[i..=j].iter().collect();
-            if candidate.starts_with(IMAGE_TOKEN_LABEL_PREFIX)
-                && candidate.ends_with(IMAGE_TOKEN_LABEL_SUFFIX)
-            {
+            if is_attachment_label(&candidate) && candidate.ends_with(IMAGE_TOKEN_LABEL_SUFFIX) {
                 i = j + 1;
                 continue;
             }
@@ -193,18 +330,7 @@ fn decode_pasted_image(data: &str) -> Option<ImageContent> {
     let bytes = base64::engine::general_purpose::STANDARD
         .decode(data)
         .ok()?;
-    let format = image::guess_format(&bytes).ok()?;
-
-    let mime_type = match format {
-        ImageFormat::Png => "image/png",
-        ImageFormat::Jpeg => "image/jpeg",
-        ImageFormat::Gif => "image/gif",
-        ImageFormat::WebP => "image/webp",
-        ImageFormat::Bmp => "image/bmp",
-        ImageFormat::Tiff => "image/tiff",
-        _ => return None,
-    }
-    .to_string();
+    let mime_type = image_mime_type(&bytes)?.to_string();
 
     Some(ImageContent {
         source: ImageSource::DataUrl {
@@ -218,6 +344,117 @@ fn decode_pasted_image(data: &str) -> Option<ImageContent> {
     })
 }
 
+fn image_mime_type(bytes: &[u8]) -> Option<&'static str> {
+    match image::guess_format(bytes).ok()? {
+        ImageFormat::Png => Some("image/png"),
+        ImageFormat::Jpeg => Some("image/jpeg"),
+        ImageFormat::Gif => Some("image/gif"),
+        ImageFormat::WebP => Some("image/webp"),
+        ImageFormat::Bmp => Some("image/bmp"),
+        ImageFormat::Tiff => Some("image/tiff"),
+        _ => None,
+    }
+}
+
+/// Reads a local file into an attachment: images become image blocks, UTF-8
+/// files become text. Anything else is rejected with a user-facing reason.
+fn load_file_attachment(path: &std::path::Path) -> std::result::Result<AttachmentContent, String> {
+    let display = path.display();
+    let metadata = std::fs::metadata(path).map_err(|e| format!("Cannot attach {display}: {e}"))?;
+    if !metadata.is_file() {
+        return Err(format!("Cannot attach {display}: not a regular file"));
+    }
+    if metadata.len() > MAX_ATTACHMENT_BYTES {
+        return Err(format!(
+            "Cannot attach {display}: {} bytes exceeds the {MAX_ATTACHMENT_BYTES} byte limit",
+            metadata.len()
+        ));
+    }
+
+    let bytes = std::fs::read(path).map_err(|e| format!("Cannot attach {display}: {e}"))?;
+    let size = bytes.len() as u64;
+
+    let (mime_type, body) = if let Some(mime_type) = image_mime_type(&bytes) {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&bytes);
+        let image = ImageContent {
+            source: ImageSource::DataUrl {
+                data_url: format!("data:{mime_type};base64,{encoded}"),
+            },
+            mime_type: mime_type.to_string(),
+            width: None,
+            height: None,
+            bytes: Some(size),
+            sha256: None,
+        };
+        (mime_type.to_string(), AttachmentBody::Image { image })
+    } else {
+        let text = String::from_utf8(bytes).map_err(|_| {
+            format!("Cannot attach {display}: only text files and images are supported")
+        })?;
+        ("text/plain".to_string(), AttachmentBody::Text { text })
+    };
+
+    Ok(AttachmentContent {
+        path: path.display().to_string(),
+        mime_type,
+        bytes: size,
+        body,
+    })
+}
+
+/// Recognizes a paste that is just the path of an existing file, as terminals
+/// produce when a file is dragged onto them. Handles quoting, `file://` URLs,
+/// backslash-escaped spaces and a leading `~`. Only absolute paths count, so
+/// ordinary pasted words are never mistaken for files.
+fn pasted_file_path(data: &str) -> Option<PathBuf> {
+    let trimmed = data.trim();
+    if trimmed.is_empty() || trimmed.contains('\n') {
+        return None;
+    }
+
+    let unquoted = ['\'', '"']
+        .iter()
+        .find_map(|quote| {
+            trimmed
+                .strip_prefix(*quote)
+                .and_then(|rest| rest.strip_suffix(*quote))
+        })
+        .unwrap_or(trimmed);
+
+    let raw = match unquoted.strip_prefix("file://") {
+        Some(rest) => percent_decode(rest)?,
+        None => unquoted.replace("\\ ", " "),
+    };
+
+    let path = expand_home(&raw);
+    (path.is_absolute() && path.is_file()).then_some(path)
+}
+
+/// Expands a leading `~/` to the user's home directory.
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), directories::BaseDirs::new()) {
+        (Some(rest), Some(dirs)) => dirs.home_dir().join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn percent_decode(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = s.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
 fn encode_clipboard_rgba_image(
     width: usize,
     height: usize,
@@ -290,12 +527,17 @@ use crate::tui::events::processors::tool::ToolEventProcessor;
 use crate::tui::events::processors::usage::UsageEventProcessor;
 use crate::tui::state::RemoteProviderRegistry;
 use crate::tui::state::SetupState;
-use crate::tui::state::{ChatStore, LlmUsageState, ToolCallRegistry};
+use crate::tui::state::{
+    BestOfView, ChatStore, HistorySearchState, InputHistory, LlmUsageState, ToolCallRegistry,
+};
 
 use crate::tui::terminal::{SetupGuard, cleanup};
+use crate::tui::title::{Activity, TerminalStatus};
 use crate::tui::ui_layout::UiLayout;
+use crate::tui::widgets::BestOfPanel;
 use crate::tui::widgets::EditSelectionOverlayState;
 use crate::tui::widgets::InputPanel;
+use crate::tui::widgets::StatusBar;
 use crate::tui::widgets::input_panel::InputPanelParams;
 use tracing::error as tracing_error;
 use tracing::info as tracing_info;
@@ -306,13 +548,18 @@ pub mod model;
 pub mod state;
 pub mod terminal;
 pub mod theme;
+mod title;
 pub mod widgets;
 
 mod chat_viewport;
+mod clipboard;
+mod crash;
 pub use chat_viewport::ChatViewport;
 pub mod core_commands;
 mod events;
 mod handlers;
+mod plain;
+pub use plain::run_plain;
 mod ui_layout;
 mod update;
 
@@ -339,10 +586,16 @@ pub enum InputMode {
     AwaitingApproval,
     /// Confirm exit dialog
     ConfirmExit,
+    /// Confirm a drafted commit
+    ConfirmCommit,
     /// Edit message selection mode with fuzzy filtering
     EditMessageSelection,
+    /// Alt+Y picker for copying a message, code block or file path
+    CopySelection,
     /// Fuzzy finder mode for file selection
     FuzzyFinder,
+    /// Ctrl+R reverse-incremental search over input history
+    HistorySearch,
     /// Setup mode - first run experience
     Setup,
 }
@@ -350,7 +603,10 @@ pub enum InputMode {
 fn mode_allows_mouse_chat_scroll(mode: InputMode) -> bool {
     !matches!(
         mode,
-        InputMode::Setup | InputMode::FuzzyFinder | InputMode::EditMessageSelection
+        InputMode::Setup
+            | InputMode::FuzzyFinder
+            | InputMode::EditMessageSelection
+            | InputMode::CopySelection
     )
 }
 
@@ -506,9 +762,11 @@ pub struct Tui {
     input_panel_state: crate::tui::widgets::input_panel::InputPanelState,
     /// The ID of the message being edited (if any)
     editing_message_id: Option<String>,
-    /// Pending image attachments to include on next send.
+    /// Pending image and file attachments to include on next send.
     pending_attachments: Vec<PendingAttachment>,
     next_attachment_token: u32,
+    /// Files loaded by `/attach`, added to the input once the command line is cleared.
+    staged_attachments: Vec<AttachmentContent>,
     /// Handle to send commands to the app
     client: AgentClient,
     /// Are we currently processing a request?
@@ -518,10 +776,16 @@ pub struct Tui {
     /// Animation frame for spinner
     spinner_state: usize,
     current_tool_approval: Option<PendingToolApproval>,
+    /// Commit draft awaiting confirmation
+    pending_commit: Option<CommitDraft>,
     /// Current model in use
     current_model: ModelId,
     /// Current primary agent label for status bar
     current_agent_label: Option<String>,
+    /// Git branch of the session workspace for status bar
+    workspace_branch: Option<String>,
+    /// Root of the session workspace, for resolving copied file paths
+    workspace_root: Option<PathBuf>,
     /// Event processing pipeline
     event_pipeline: EventPipeline,
     /// Chat data store
@@ -550,6 +814,20 @@ pub struct Tui {
     command_registry: CommandRegistry,
     /// User preferences
     preferences: Preferences,
+    /// Symbols per workspace file, fetched for `@file#symbol` completion
+    symbol_cache: HashMap<String, Vec<FileSymbol>>,
+    /// Submitted inputs, persisted per workspace
+    input_history: InputHistory,
+    /// In-progress Ctrl+R history search
+    history_search: Option<HistorySearchState>,
+    /// Input draft set aside while the model picker chooses a model for a retry
+    retry_picker_draft: Option<String>,
+    /// Candidate count for the next message when best-of is armed
+    best_of_armed: Option<u32>,
+    /// Candidates of the current best-of turn
+    best_of_view: Option<BestOfView>,
+    /// Latency breakdown of the most recently completed operation, for `/timing`
+    last_operation_timing: Option<OperationTiming>,
     /// Centralized notification manager
     notification_manager: NotificationManagerHandle,
     /// Double-tap tracker for key sequences
@@ -563,6 +841,21 @@ pub struct Tui {
     /// Update checker status
     update_status: UpdateStatus,
     edit_selection_state: EditSelectionOverlayState,
+    /// Messages offered by the Alt+Y copy picker
+    copy_selection_state: EditSelectionOverlayState,
+    /// Which code block or file path the copy picker copied last
+    copy_cycle: clipboard::CopyCycle,
+    clipboard: clipboard::Clipboard,
+    /// "Copied …" confirmation shown in the status bar until it expires
+    copy_toast: Option<(String, std::time::Instant)>,
+    /// Set after a frame panicked; later frames use the plain safe renderer
+    safe_render: bool,
+    /// Where the last render panic was logged
+    crash_log: Option<PathBuf>,
+    /// Terminal title and taskbar progress
+    terminal_status: TerminalStatus,
+    /// Session title shown in the terminal title, once known
+    session_title: Option<String>,
 }
 
 const MAX_MODE_DEPTH: usize = 8;
@@ -666,23 +959,49 @@ impl Tui {
         }
     }
 
-    fn add_pending_attachment(&mut self, image: ImageContent) {
+    fn pending_image_count(&self) -> usize {
+        self.pending_attachments
+            .iter()
+            .filter(|attachment| attachment.is_image())
+            .count()
+    }
+
+    fn add_pending_attachment(&mut self, content: UserContent) {
         let Some(token) = self.next_attachment_token() else {
             warn!(target: "tui.input", "Ran out of attachment token characters");
             self.push_notice(
                 NoticeLevel::Warn,
-                "Unable to attach more images in this input.".to_string(),
+                "Unable to attach more files in this input.".to_string(),
             );
             return;
         };
 
-        self.pending_attachments
-            .push(PendingAttachment { image, token });
-        let image_number = self.pending_attachments.len();
+        let attachment = PendingAttachment { content, token };
+        let label = attachment.label(self.pending_image_count() + 1);
+        self.pending_attachments.push(attachment);
         self.input_panel_state.textarea.insert_char(token);
-        self.input_panel_state
-            .textarea
-            .insert_str(format_inline_image_token(image_number));
+        self.input_panel_state.textarea.insert_str(label);
+    }
+
+    /// Reads `path` and adds it to the input as a file attachment.
+    fn attach_file(&mut self, path: &std::path::Path) -> bool {
+        match load_file_attachment(path) {
+            Ok(attachment) => {
+                self.add_pending_attachment(UserContent::Attachment { attachment });
+                true
+            }
+            Err(message) => {
+                self.push_notice(NoticeLevel::Warn, message);
+                false
+            }
+        }
+    }
+
+    /// Moves files staged by `/attach` into the (now empty) input.
+    fn restore_staged_attachments(&mut self) {
+        for attachment in std::mem::take(&mut self.staged_attachments) {
+            self.add_pending_attachment(UserContent::Attachment { attachment });
+        }
     }
 
     fn cursor_position_from_byte_offset(content: &str, byte_offset: usize) -> (u16, u16) {
@@ -742,19 +1061,21 @@ impl Tui {
                 break;
             };
 
-            if self
+            if let Some(attachment) = self
                 .pending_attachments
                 .iter()
-                .any(|attachment| attachment.token == ch)
+                .find(|attachment| attachment.token == ch)
             {
-                image_number += 1;
-                let label = format_inline_image_token(image_number);
+                if attachment.is_image() {
+                    image_number += 1;
+                }
+                let label = attachment.label(image_number);
                 retained_tokens.insert(ch);
                 normalized.push(ch);
                 normalized.push_str(&label);
 
                 cursor += ch.len_utf8();
-                if let Some(label_len) = image_label_len_at(&content[cursor..]) {
+                if let Some(label_len) = attachment_label_len_at(&content[cursor..]) {
                     cursor += label_len;
                 }
                 continue;
@@ -841,7 +1162,9 @@ impl Tui {
         if let Some(image_content) =
             encode_clipboard_rgba_image(image.width, image.height, image.bytes.as_ref())
         {
-            self.add_pending_attachment(image_content);
+            self.add_pending_attachment(UserContent::Image {
+                image: image_content,
+            });
             true
         } else {
             warn!(
@@ -892,6 +1215,10 @@ impl Tui {
         };
 
         let notification_manager = std::sync::Arc::new(NotificationManager::new(&preferences));
+        let terminal_status = TerminalStatus::new(
+            preferences.ui.terminal_title(),
+            preferences.ui.terminal_progress(),
+        );
 
         let mut tui = Self {
             terminal,
@@ -903,13 +1230,17 @@ impl Tui {
             editing_message_id: None,
             pending_attachments: Vec::new(),
             next_attachment_token: FIRST_ATTACHMENT_TOKEN,
+            staged_attachments: Vec::new(),
             client,
             is_processing: false,
             progress_message: None,
             spinner_state: 0,
             current_tool_approval: None,
+            pending_commit: None,
             current_model,
             current_agent_label: None,
+            workspace_branch: None,
+            workspace_root: None,
             event_pipeline: Self::create_event_pipeline(notification_manager.clone()),
             chat_store: ChatStore::new(),
             tool_registry: ToolCallRegistry::new(),
@@ -924,6 +1255,13 @@ impl Tui {
             llm_usage: LlmUsageState::default(),
             command_registry: CommandRegistry::new(),
             preferences,
+            symbol_cache: HashMap::new(),
+            input_history: InputHistory::default(),
+            history_search: None,
+            retry_picker_draft: None,
+            best_of_armed: None,
+            best_of_view: None,
+            last_operation_timing: None,
             notification_manager,
             double_tap_tracker: crate::tui::state::DoubleTapTracker::new(),
             vim_state: VimState::default(),
@@ -931,6 +1269,14 @@ impl Tui {
             last_revision: 0,
             update_status: UpdateStatus::Checking,
             edit_selection_state: EditSelectionOverlayState::default(),
+            copy_selection_state: EditSelectionOverlayState::default(),
+            copy_cycle: clipboard::CopyCycle::default(),
+            clipboard: clipboard::Clipboard::new(),
+            copy_toast: None,
+            safe_render: false,
+            crash_log: None,
+            terminal_status,
+            session_title: None,
         };
 
         tui.refresh_agent_label().await;
@@ -1051,6 +1397,29 @@ impl Tui {
         });
     }
 
+    /// Copies `text` and confirms with a status bar toast naming `label`.
+    /// Failures are reported as a notice. Returns whether the copy succeeded.
+    fn copy_text(&mut self, text: &str, label: String) -> bool {
+        match self.clipboard.copy(text) {
+            Ok(()) => {
+                self.copy_toast = Some((format!("Copied {label}"), std::time::Instant::now()));
+                true
+            }
+            Err(message) => {
+                self.push_notice(NoticeLevel::Error, message);
+                false
+            }
+        }
+    }
+
+    /// Directory relative file references are resolved against.
+    fn copy_path_root(&self) -> PathBuf {
+        self.workspace_root
+            .clone()
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_default()
+    }
+
     fn push_tui_response(&mut self, command: String, response: TuiCommandResponse) {
         use crate::tui::model::{ChatItem, ChatItemData, generate_row_id};
         self.chat_store.push(ChatItem {
@@ -1102,6 +1471,234 @@ impl Tui {
         output
     }
 
+    /// Renders the `/context` summary, or one segment's items when `kind` is set.
+    fn format_isolation_status(status: &IsolationStatus) -> String {
+        let workspace = &status.workspace;
+        let mut output = String::new();
+        output.push_str(&format!("Source: {}\n", workspace.source.display()));
+        output.push_str(&format!("Worktree: {}\n", workspace.path.display()));
+        match &workspace.kind {
+            IsolationKind::GitWorktree { branch, .. } => {
+                output.push_str(&format!("Branch: {branch}\n"));
+            }
+            IsolationKind::Copy => output.push_str("Kind: copy (source is not a git checkout)\n"),
+        }
+
+        if status.changes.is_empty() {
+            output.push_str("\nNo changes.");
+            return output;
+        }
+
+        output.push_str(&format!("\nChanged files ({}):\n", status.changes.len()));
+        for change in &status.changes {
+            output.push_str(&format!("  {} {}\n", change.kind.marker(), change.path));
+        }
+        output.trim_end().to_string()
+    }
+
+    fn format_merge_report(report: &MergeReport) -> String {
+        if report.applied.is_empty() && report.conflicts.is_empty() {
+            return "Nothing to merge.".to_string();
+        }
+
+        let mut output = String::new();
+        if !report.applied.is_empty() {
+            output.push_str(&format!("Applied ({}):\n", report.applied.len()));
+            for path in &report.applied {
+                output.push_str(&format!("  {path}\n"));
+            }
+        }
+        if !report.conflicts.is_empty() {
+            output.push_str(&format!("Conflicts ({}):\n", report.conflicts.len()));
+            for path in &report.conflicts {
+                output.push_str(&format!("  {path}\n"));
+            }
+            if !report.details.is_empty() {
+                output.push_str(&format!("\n{}\n", report.details));
+            }
+        }
+        output.trim_end().to_string()
+    }
+
+    async fn handle_isolation_command(
+        &mut self,
+        command: String,
+        action: Option<&str>,
+    ) -> Result<()> {
+        let response = match action.unwrap_or("status") {
+            "status" => match self.client.get_isolation_status(&self.session_id).await {
+                Ok(Some(status)) => Self::format_isolation_status(&status),
+                Ok(None) => "This session is not isolated.".to_string(),
+                Err(e) => return self.isolation_error(&e),
+            },
+            "merge" => match self.client.merge_isolated_workspace(&self.session_id).await {
+                Ok(report) => Self::format_merge_report(&report),
+                Err(e) => return self.isolation_error(&e),
+            },
+            "patch" => match self.client.export_isolated_patch(&self.session_id).await {
+                Ok(patch) if patch.is_empty() => "No changes to export.".to_string(),
+                Ok(patch) => {
+                    let path = std::path::PathBuf::from(format!("steer-{}.patch", self.session_id));
+                    std::fs::write(&path, patch)?;
+                    format!("Wrote patch to {}", path.display())
+                }
+                Err(e) => return self.isolation_error(&e),
+            },
+            "branch" => match self
+                .client
+                .commit_isolated_branch(&self.session_id, None)
+                .await
+            {
+                Ok(branch) => format!("Committed changes to branch {branch}"),
+                Err(e) => return self.isolation_error(&e),
+            },
+            "discard" => match self
+                .client
+                .discard_isolated_workspace(&self.session_id)
+                .await
+            {
+                Ok(()) => "Discarded the isolated worktree.".to_string(),
+                Err(e) => return self.isolation_error(&e),
+            },
+            other => {
+                self.push_notice(
+                    NoticeLevel::Error,
+                    format!(
+                        "Unknown isolation action '{other}'. Usage: {}",
+                        crate::tui::commands::TuiCommandType::Isolation.usage()
+                    ),
+                );
+                return Ok(());
+            }
+        };
+
+        self.push_tui_response(command, TuiCommandResponse::Text(response));
+        Ok(())
+    }
+
+    fn format_memory(entries: &[steer_tools::tools::memory::MemoryEntry]) -> String {
+        if entries.is_empty() {
+            return "Session memory is empty.".to_string();
+        }
+        entries
+            .iter()
+            .map(|entry| format!("{}: {}", entry.key, entry.content))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    async fn handle_memory_command(&mut self, command: String, args: Option<&str>) {
+        let args = args.unwrap_or("");
+        let (action, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+        let rest = rest.trim();
+
+        let result = match (action, rest.split_once(char::is_whitespace)) {
+            ("", _) => self.client.session_memory(&self.session_id).await,
+            ("set", Some((key, fact))) => {
+                self.client
+                    .remember(&self.session_id, key.to_string(), fact.trim().to_string())
+                    .await
+            }
+            ("forget", _) if !rest.is_empty() => {
+                self.client.forget(&self.session_id, rest.to_string()).await
+            }
+            _ => {
+                self.push_notice(
+                    NoticeLevel::Warn,
+                    format!(
+                        "Usage: {}",
+                        crate::tui::commands::TuiCommandType::Memory.usage()
+                    ),
+                );
+                return;
+            }
+        };
+
+        match result {
+            Ok(entries) => self.push_tui_response(
+                command,
+                TuiCommandResponse::Text(Self::format_memory(&entries)),
+            ),
+            Err(e) => self.push_notice(NoticeLevel::Error, Self::format_grpc_error(&e)),
+        }
+    }
+
+    fn isolation_error(&mut self, error: &steer_grpc::GrpcError) -> Result<()> {
+        self.push_notice(NoticeLevel::Error, Self::format_grpc_error(error));
+        Ok(())
+    }
+
+    fn format_context_report(report: &ContextReport, kind: Option<ContextSegmentKind>) -> String {
+        let total = report.total_tokens();
+        let mut output = String::new();
+
+        if let Some(kind) = kind {
+            let Some(segment) = report.segment(kind) else {
+                return format!("{} is not part of this request.", kind.label());
+            };
+            output.push_str(&format!(
+                "{}: ~{} tokens\n",
+                kind.label(),
+                format_token_count(segment.tokens)
+            ));
+            if segment.items.is_empty() {
+                output.push_str("  (empty)\n");
+            }
+            for item in &segment.items {
+                output.push_str(&format!(
+                    "  {:>8}  {}\n",
+                    format_token_count(item.tokens),
+                    item.label
+                ));
+            }
+            return output;
+        }
+
+        output.push_str("Estimated size of the next request:\n");
+        for segment in &report.segments {
+            let share = if total > 0 {
+                f64::from(segment.tokens) * 100.0 / f64::from(total)
+            } else {
+                0.0
+            };
+            output.push_str(&format!(
+                "  {:<14} {:>8}  {:>5.1}%  ({} items)\n",
+                segment.kind.label(),
+                format_token_count(segment.tokens),
+                share,
+                segment.items.len()
+            ));
+        }
+        output.push_str(&format!(
+            "  {:<14} {:>8}\n",
+            "Total",
+            format_token_count(total)
+        ));
+
+        match report.input_budget_tokens() {
+            Some(budget) if budget > 0 => {
+                let used = f64::from(total) * 100.0 / f64::from(budget);
+                output.push_str(&format!(
+                    "\n{used:.1}% of {} input tokens",
+                    format_token_count(budget)
+                ));
+                if let Some(reserved) = report.max_output_tokens.filter(|tokens| *tokens > 0) {
+                    output.push_str(&format!(
+                        " ({} reserved for output)",
+                        format_token_count(reserved)
+                    ));
+                }
+                output.push('\n');
+            }
+            _ => output.push_str("\nContext window size unknown for this model.\n"),
+        }
+        output.push_str(&format!(
+            "Drill in with {}",
+            crate::tui::commands::TuiCommandType::Context.usage()
+        ));
+        output
+    }
+
     fn format_available_primary_agents(
         agents: &[steer_grpc::client_api::PrimaryAgentSpec],
         current_agent: Option<&str>,
@@ -1181,7 +1778,8 @@ impl Tui {
     async fn start_new_session(&mut self) -> Result<()> {
         use std::collections::HashMap;
         use steer_grpc::client_api::{
-            CreateSessionParams, SessionPolicyOverrides, SessionToolConfig, WorkspaceConfig,
+            AutoSummaryConfig, CreateSessionParams, IsolationMode, SessionPolicyOverrides,
+            SessionToolConfig, WorkspaceConfig,
         };
 
         let session_params = CreateSessionParams {
@@ -1189,6 +1787,8 @@ impl Tui {
             tool_config: SessionToolConfig::default(),
             primary_agent_id: None,
             policy_overrides: SessionPolicyOverrides::empty(),
+            isolation: IsolationMode::None,
+            auto_summary: AutoSummaryConfig::default(),
             metadata: HashMap::new(),
             default_model: self.current_model.clone(),
         };
@@ -1206,6 +1806,7 @@ impl Tui {
         self.chat_viewport = ChatViewport::new();
         self.in_flight_operations.clear();
         self.notify_on_processing_complete.clear();
+        self.last_operation_timing = None;
         self.clear_ctx_utilization();
         self.input_panel_state =
             crate::tui::widgets::input_panel::InputPanelState::new(new_session_id.clone());
@@ -1221,16 +1822,67 @@ impl Tui {
         Ok(())
     }
 
+    /// Fetch the session workspace's branch for the status bar. Sessions without
+    /// a managed workspace have no status to query, so failures are only logged.
+    async fn load_workspace_branch(&mut self) {
+        let workspace_id = match self.client.get_session(&self.session_id).await {
+            Ok(Some(session)) => session.config.and_then(|config| {
+                config
+                    .workspace_id
+                    .or_else(|| config.workspace_ref.map(|reference| reference.workspace_id))
+            }),
+            Ok(None) => None,
+            Err(e) => {
+                debug!(target: "tui.workspace", "Failed to load session for workspace branch: {}", e);
+                None
+            }
+        };
+        let Some(workspace_id) = workspace_id.filter(|id| !id.is_empty()) else {
+            return;
+        };
+
+        match self.client.get_workspace_status(&workspace_id).await {
+            Ok(status) => {
+                self.workspace_root = Some(status.path.clone());
+                self.workspace_branch = status
+                    .vcs
+                    .as_ref()
+                    .and_then(|vcs| vcs.status.branch())
+                    .map(str::to_string);
+            }
+            Err(e) => {
+                debug!(target: "tui.workspace", "Failed to load workspace status: {}", e);
+            }
+        }
+    }
+
     async fn load_file_cache(&mut self) {
         info!(target: "tui.file_cache", "Requesting workspace files for session {}", self.session_id);
-        match self.client.list_workspace_files().await {
-            Ok(files) => {
-                self.input_panel_state.file_cache.update(files).await;
-            }
+        let limit = self.preferences.ui.file_list_limit();
+        let mut stream = match self.client.stream_workspace_files(limit).await {
+            Ok(stream) => stream,
             Err(e) => {
                 warn!(target: "tui.file_cache", "Failed to request workspace files: {}", e);
+                return;
             }
-        }
+        };
+
+        // Fill the cache as chunks arrive so the picker is usable before the
+        // full listing has been received.
+        let file_cache = self.input_panel_state.file_cache.clone();
+        file_cache.clear().await;
+        self.symbol_cache.clear();
+        tokio::spawn(async move {
+            while let Some(chunk) = stream.next().await {
+                match chunk {
+                    Ok(files) => file_cache.extend(files).await,
+                    Err(e) => {
+                        warn!(target: "tui.file_cache", "Workspace file stream failed: {}", e);
+                        break;
+                    }
+                }
+            }
+        });
     }
 
     pub async fn run(&mut self, event_rx: mpsc::Receiver<ClientEvent>) -> Result<()> {
@@ -1242,6 +1894,8 @@ impl Tui {
 
         // Load the initial file list
         self.load_file_cache().await;
+        self.load_workspace_branch().await;
+        self.load_session_title().await;
 
         // Spawn update checker
         let (update_tx, update_rx) = mpsc::channel::<UpdateStatus>(1);
@@ -1280,6 +1934,7 @@ impl Tui {
             // Determine if we need to redraw
             if needs_redraw {
                 self.draw()?;
+                self.update_terminal_status();
                 needs_redraw = false;
             }
 
@@ -1355,6 +2010,15 @@ impl Tui {
                         }
                     }
 
+                    if self
+                        .copy_toast
+                        .as_ref()
+                        .is_some_and(|(_, shown_at)| shown_at.elapsed() >= COPY_TOAST_DURATION)
+                    {
+                        self.copy_toast = None;
+                        needs_redraw = true;
+                    }
+
                     if self.input_mode == InputMode::Setup
                         && crate::tui::handlers::setup::SetupHandler::poll_oauth_callback(self)
                             .await?
@@ -1470,6 +2134,14 @@ impl Tui {
                         continue;
                     }
 
+                    if let Some(path) = pasted_file_path(&data) {
+                        if self.attach_file(&path) {
+                            debug!(target:"tui.run", "Attached pasted path {}", path.display());
+                        }
+                        needs_redraw = true;
+                        continue;
+                    }
+
                     let maybe_image = decode_pasted_image(&data);
                     let had_image = maybe_image.is_some();
                     let normalized_data =
@@ -1487,7 +2159,7 @@ impl Tui {
                     }
 
                     if let Some(image) = maybe_image {
-                        self.add_pending_attachment(image);
+                        self.add_pending_attachment(UserContent::Image { image });
                     }
 
                     if text_inserted || had_image {
@@ -1579,20 +2251,142 @@ impl Tui {
         }
     }
 
-    /// Draw the UI
-    fn draw(&mut self) -> Result<()> {
-        let editing_message_id = self.editing_message_id.clone();
-        let is_editing = editing_message_id.is_some();
-        let editing_preview = if is_editing {
-            self.editing_preview()
+    async fn load_session_title(&mut self) {
+        match self.client.list_sessions().await {
+            Ok(sessions) => {
+                self.session_title = sessions
+                    .into_iter()
+                    .find(|session| session.id == self.session_id)
+                    .and_then(|session| session.title);
+            }
+            Err(e) => debug!(target: "tui.title", "Failed to load session title: {e}"),
+        }
+        self.notification_manager
+            .set_session(&self.session_id, self.session_title.as_deref());
+    }
+
+    fn activity(&self) -> Activity {
+        if self.current_tool_approval.is_some() {
+            return Activity::AwaitingApproval;
+        }
+        if let Some(call) = self
+            .tool_registry
+            .active_calls()
+            .values()
+            .max_by_key(|state| state.started_at)
+        {
+            return Activity::Tool(call.call.name.clone());
+        }
+        if self.is_processing || !self.in_flight_operations.is_empty() {
+            Activity::Thinking
         } else {
-            None
-        };
+            Activity::Idle
+        }
+    }
 
-        self.terminal.draw(|f| {
-            // Check if we're in setup mode
-            if let Some(setup_state) = &self.setup_state {
-                use crate::tui::widgets::setup::{
+    fn update_terminal_status(&mut self) {
+        let activity = self.activity();
+        let session = self
+            .session_title
+            .clone()
+            .unwrap_or_else(|| self.session_id.chars().take(8).collect());
+        if let Err(e) =
+            self.terminal_status
+                .update(self.terminal.backend_mut(), &session, &activity)
+        {
+            debug!(target: "tui.title", "Failed to update terminal title: {e}");
+        }
+    }
+
+    /// Draw the UI, switching to the safe renderer if a frame panics. Fails
+    /// with [`Error::RenderCrash`] when the safe renderer panics too.
+    fn draw(&mut self) -> Result<()> {
+        if !self.safe_render {
+            match crash::catch_frame(|| self.draw_full()) {
+                Ok(result) => return result,
+                Err(report) => self.enter_safe_render(&report),
+            }
+        }
+        match crash::catch_frame(|| self.draw_safe()) {
+            Ok(result) => result,
+            Err(report) => {
+                tracing_error!(target: "tui.render", "Safe renderer panicked: {report}");
+                Err(Error::RenderCrash {
+                    session_id: self.session_id.clone(),
+                    crash_log: self.crash_log.clone(),
+                })
+            }
+        }
+    }
+
+    fn enter_safe_render(&mut self, report: &str) {
+        tracing_error!(target: "tui.render", "Render panicked, switching to safe mode: {report}");
+        self.crash_log = crash::logs_dir().and_then(|dir| {
+            crash::write_crash_log(&dir, &self.session_id, report)
+                .inspect_err(
+                    |e| tracing_error!(target: "tui.render", "Failed to write crash log: {e}"),
+                )
+                .ok()
+        });
+        self.safe_render = true;
+        // The panic may have left a half-drawn frame behind.
+        let _ = self.terminal.clear();
+    }
+
+    /// Minimal renderer used after a panic: plain text only, no widgets that
+    /// depend on chat or layout state.
+    fn draw_safe(&mut self) -> Result<()> {
+        use ratatui::text::Line;
+        use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+
+        let status = if self.is_processing || !self.in_flight_operations.is_empty() {
+            "Working; the operation continues on the server."
+        } else {
+            "Idle."
+        };
+        let mut lines = vec![
+            Line::from("The chat view hit an error and was switched to safe mode."),
+            Line::from(""),
+            Line::from(format!("Session: {}", self.session_id)),
+            Line::from(format!("Model:   {}", self.current_model)),
+            Line::from(format!("Status:  {status}")),
+        ];
+        if let Some(path) = &self.crash_log {
+            lines.push(Line::from(format!("Log:     {}", path.display())));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!(
+            "Press q or Ctrl+C to quit, then run `steer --session {}` to get the full view back.",
+            self.session_id
+        )));
+
+        self.terminal.draw(|f| {
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .title(" steer (safe mode) ");
+            f.render_widget(
+                Paragraph::new(lines)
+                    .block(block)
+                    .wrap(Wrap { trim: false }),
+                f.area(),
+            );
+        })?;
+        Ok(())
+    }
+
+    fn draw_full(&mut self) -> Result<()> {
+        let editing_message_id = self.editing_message_id.clone();
+        let is_editing = editing_message_id.is_some();
+        let editing_preview = if is_editing {
+            self.editing_preview()
+        } else {
+            None
+        };
+
+        self.terminal.draw(|f| {
+            // Check if we're in setup mode
+            if let Some(setup_state) = &self.setup_state {
+                use crate::tui::widgets::setup::{
                     authentication::AuthenticationWidget, completion::CompletionWidget,
                     provider_selection::ProviderSelectionWidget, welcome::WelcomeWidget,
                 };
@@ -1656,6 +2450,8 @@ impl Tui {
             let layout = UiLayout::compute(terminal_size, input_area_height, &self.theme);
             layout.prepare_background(f, &self.theme);
 
+            self.chat_viewport
+                .set_show_reasoning(self.preferences.ui.show_reasoning());
             self.chat_viewport.rebuild_from_store(
                 layout.chat.width,
                 self.chat_viewport.state().view_mode,
@@ -1665,6 +2461,9 @@ impl Tui {
             );
 
             self.chat_viewport.render(f, layout.chat, &self.theme);
+            if let Some(view) = &self.best_of_view {
+                f.render_widget(BestOfPanel::new(view, &self.theme), layout.chat);
+            }
 
             let input_panel = InputPanel::new(InputPanelParams {
                 input_mode,
@@ -1680,6 +2479,10 @@ impl Tui {
                     .as_ref()
                     .map_or(0, |item| item.attachment_count),
                 attachment_count: self.pending_attachments.len(),
+                history_query: self
+                    .history_search
+                    .as_ref()
+                    .map(|search| search.query.as_str()),
                 theme: &self.theme,
             });
             f.render_stateful_widget(input_panel, layout.input, &mut self.input_panel_state);
@@ -1692,23 +2495,19 @@ impl Tui {
                 }
                 _ => crate::tui::widgets::status_bar::UpdateBadge::None,
             };
-            let context_remaining_percent = self
-                .llm_usage
-                .latest()
-                .and_then(|usage| usage.context_window.as_ref())
-                .and_then(|context_window| {
-                    context_window
-                        .utilization_ratio
-                        .map(|utilization_ratio| (1.0 - utilization_ratio.clamp(0.0, 1.0)) * 100.0)
-                });
+            let context_remaining_percent = self.llm_usage.context_remaining_percent();
 
             layout.render_status_bar(
                 f,
-                &current_model_owned,
-                self.current_agent_label.as_deref(),
-                context_remaining_percent,
-                &self.theme,
-                update_badge,
+                StatusBar::new(
+                    &current_model_owned,
+                    self.current_agent_label.as_deref(),
+                    &self.theme,
+                )
+                .with_context_remaining_percent(context_remaining_percent)
+                .with_update_badge(update_badge)
+                .with_branch(self.workspace_branch.as_deref())
+                .with_toast(self.copy_toast.as_ref().map(|(text, _)| text.as_str())),
             );
 
             // Get fuzzy finder results before the render call
@@ -1745,6 +2544,16 @@ impl Tui {
                 let overlay = EditSelectionOverlay::new(&self.theme);
                 f.render_stateful_widget(overlay, terminal_size, &mut self.edit_selection_state);
             }
+
+            if input_mode == InputMode::CopySelection {
+                use crate::tui::widgets::EditSelectionOverlay;
+                let overlay = EditSelectionOverlay::new(&self.theme).with_labels(
+                    " Copy ",
+                    "No messages to copy",
+                    COPY_SELECTION_HINTS,
+                );
+                f.render_stateful_widget(overlay, terminal_size, &mut self.copy_selection_state);
+            }
         })?;
         Ok(())
     }
@@ -1916,6 +2725,42 @@ impl Tui {
             ClientEvent::WorkspaceChanged => {
                 self.load_file_cache().await;
             }
+            ClientEvent::BestOfStarted {
+                op_id,
+                model,
+                candidate_op_ids,
+                ..
+            } => {
+                self.best_of_view = Some(BestOfView::new(
+                    *op_id,
+                    model.clone(),
+                    candidate_op_ids.len(),
+                ));
+            }
+            ClientEvent::BestOfCandidateReady { op_id, candidate } => {
+                if let Some(view) = self
+                    .best_of_view
+                    .as_mut()
+                    .filter(|view| view.op_id == *op_id)
+                {
+                    view.record(candidate.clone());
+                }
+            }
+            ClientEvent::BestOfResolved { op_id, .. } => {
+                if self
+                    .best_of_view
+                    .as_ref()
+                    .is_some_and(|view| view.op_id == *op_id)
+                {
+                    self.best_of_view = None;
+                }
+            }
+            ClientEvent::ProcessingCompleted {
+                timing: Some(timing),
+                ..
+            } => {
+                self.last_operation_timing = Some(timing.clone());
+            }
             ClientEvent::WorkspaceFiles { files } => {
                 info!(target: "tui.handle_client_event", "Received workspace files event with {} files", files.len());
                 self.input_panel_state
@@ -1923,6 +2768,12 @@ impl Tui {
                     .update(files.clone())
                     .await;
             }
+            ClientEvent::WorkspaceFileAdded { path } => {
+                self.input_panel_state.file_cache.insert(path.clone()).await;
+            }
+            ClientEvent::WorkspaceFileRemoved { path } => {
+                self.input_panel_state.file_cache.remove(path).await;
+            }
             _ => {}
         }
 
@@ -1937,6 +2788,7 @@ impl Tui {
             progress_message: &mut self.progress_message,
             spinner_state: &mut self.spinner_state,
             current_tool_approval: &mut self.current_tool_approval,
+            pending_commit: &mut self.pending_commit,
             current_model: &mut self.current_model,
             current_agent_label: &mut self.current_agent_label,
             messages_updated: &mut messages_updated,
@@ -1952,17 +2804,25 @@ impl Tui {
         }
 
         if self.current_tool_approval.is_some() && self.input_mode != InputMode::AwaitingApproval {
+            self.input_panel_state.denial_reason = None;
+            self.input_panel_state.hunk_review = None;
             self.switch_mode(InputMode::AwaitingApproval);
         } else if self.current_tool_approval.is_none()
             && self.input_mode == InputMode::AwaitingApproval
         {
+            self.input_panel_state.denial_reason = None;
+            self.input_panel_state.hunk_review = None;
             self.restore_previous_mode();
+        } else if self.pending_commit.is_some() && self.input_mode != InputMode::ConfirmCommit {
+            self.switch_mode(InputMode::ConfirmCommit);
         }
 
         if messages_updated {
             self.chat_viewport.mark_dirty();
             if self.chat_viewport.state_mut().is_at_bottom() {
                 self.chat_viewport.state_mut().scroll_to_bottom();
+            } else {
+                self.chat_viewport.state_mut().mark_unseen_output();
             }
         }
     }
@@ -1980,8 +2840,9 @@ impl Tui {
                     self.push_notice(NoticeLevel::Error, Self::format_grpc_error(&e));
                 }
             } else {
-                let content_blocks =
+                let mut content_blocks =
                     parse_inline_message_content(&content, &self.pending_attachments);
+                content_blocks.extend(self.expand_file_mentions(&content).await);
                 match self
                     .client
                     .edit_message(
@@ -1999,7 +2860,12 @@ impl Tui {
             return Ok(());
         }
 
-        let content_blocks = parse_inline_message_content(&content, &self.pending_attachments);
+        if let Some(candidates) = self.best_of_armed.take() {
+            return self.send_best_of(content, candidates).await;
+        }
+
+        let mut content_blocks = parse_inline_message_content(&content, &self.pending_attachments);
+        content_blocks.extend(self.expand_file_mentions(&content).await);
 
         if let Err(e) = self
             .client
@@ -2011,6 +2877,88 @@ impl Tui {
         Ok(())
     }
 
+    /// Sends a message as a best-of turn. Candidates only see text, so
+    /// attachments are dropped with a warning.
+    async fn send_best_of(&mut self, content: String, candidates: u32) -> Result<()> {
+        if !self.pending_attachments.is_empty() {
+            self.pending_attachments.clear();
+            self.push_notice(
+                NoticeLevel::Warn,
+                "Attachments are not sent with best-of messages".to_string(),
+            );
+        }
+        let mut text = content.clone();
+        if let Some(UserContent::Text { text: expanded }) =
+            self.expand_file_mentions(&content).await
+        {
+            text.push_str("\n\n");
+            text.push_str(&expanded);
+        }
+
+        if let Err(e) = self
+            .client
+            .start_best_of(text, candidates, self.current_model.clone())
+            .await
+        {
+            self.push_notice(NoticeLevel::Error, Self::format_grpc_error(&e));
+        }
+        Ok(())
+    }
+
+    /// Expands the `@path` and `@path#symbol` mentions in a message into a text
+    /// block carrying the mentioned source, so the model does not have to read
+    /// the files itself. Mentions that fail to expand are reported and skipped.
+    async fn expand_file_mentions(&mut self, content: &str) -> Option<UserContent> {
+        let symbol_mentions = self.preferences.ui.symbol_mentions();
+        let outline_large_files = self.preferences.ui.outline_large_mentions();
+        let file_cache = self.input_panel_state.file_cache.clone();
+
+        let mut expansions = Vec::new();
+        let mut seen = HashSet::new();
+        for mention in file_mentions(content) {
+            if !seen.insert(mention) {
+                continue;
+            }
+            let (path, symbol) = if file_cache.contains(mention).await {
+                (mention, None)
+            } else {
+                match mention.split_once('#') {
+                    Some((path, symbol))
+                        if symbol_mentions
+                            && !symbol.is_empty()
+                            && file_cache.contains(path).await =>
+                    {
+                        (path, Some(symbol))
+                    }
+                    _ => continue,
+                }
+            };
+
+            match self
+                .client
+                .expand_file_mention(path, symbol, outline_large_files)
+                .await
+            {
+                Ok(text) => expansions.push(text),
+                Err(e) => self.push_notice(
+                    NoticeLevel::Warn,
+                    format!(
+                        "Could not expand @{mention}: {}",
+                        Self::format_grpc_error(&e)
+                    ),
+                ),
+            }
+        }
+
+        if expansions.is_empty() {
+            None
+        } else {
+            Some(UserContent::Text {
+                text: expansions.join("\n\n"),
+            })
+        }
+    }
+
     async fn handle_slash_command(&mut self, command_input: String) -> Result<()> {
         use crate::tui::commands::{AppCommand as TuiAppCommand, TuiCommand, TuiCommandType};
         use crate::tui::model::NoticeLevel;
@@ -2071,6 +3019,20 @@ impl Tui {
                             ),
                         );
                     }
+                    TuiCommand::ReloadModels => match self.client.reload_catalog().await {
+                        Ok(reload) => {
+                            self.push_tui_response(
+                                TuiCommandType::ReloadModels.command_name(),
+                                TuiCommandResponse::Text(format_catalog_reload(&reload)),
+                            );
+                        }
+                        Err(e) => {
+                            self.push_notice(
+                                NoticeLevel::Error,
+                                format!("Failed to reload model catalogs: {e}"),
+                            );
+                        }
+                    },
                     TuiCommand::Theme(theme_name) => {
                         if let Some(name) = theme_name {
                             // Load the specified theme
@@ -2102,29 +3064,8 @@ impl Tui {
                         }
                     }
                     TuiCommand::Help(command_name) => {
-                        // Build and show help text
-                        let help_text = if let Some(cmd_name) = command_name {
-                            // Show help for specific command
-                            if let Some(cmd_info) = self.command_registry.get(&cmd_name) {
-                                format!(
-                                    "Command: {}\n\nDescription: {}\n\nUsage: {}",
-                                    cmd_info.name, cmd_info.description, cmd_info.usage
-                                )
-                            } else {
-                                format!("Unknown command: {cmd_name}")
-                            }
-                        } else {
-                            // Show general help with all commands
-                            let mut help_lines = vec!["Available commands:".to_string()];
-                            for cmd_info in self.command_registry.all_commands() {
-                                help_lines.push(format!(
-                                    "  {:<20} - {}",
-                                    cmd_info.usage, cmd_info.description
-                                ));
-                            }
-                            help_lines.join("\n")
-                        };
-
+                        let help_text =
+                            Self::command_help(&self.command_registry, command_name.as_deref());
                         self.push_tui_response(
                             TuiCommandType::Help.command_name(),
                             TuiCommandResponse::Text(help_text),
@@ -2269,6 +3210,13 @@ impl Tui {
 
                         match self.client.get_workspace_status(&target_id).await {
                             Ok(status) => {
+                                if workspace_id.is_none() {
+                                    self.workspace_branch = status
+                                        .vcs
+                                        .as_ref()
+                                        .and_then(|vcs| vcs.status.branch())
+                                        .map(str::to_string);
+                                }
                                 let response = Self::format_workspace_status(&status);
                                 self.push_tui_response(
                                     tui_cmd.as_command_str(),
@@ -2280,6 +3228,71 @@ impl Tui {
                             }
                         }
                     }
+                    TuiCommand::Context(ref segment) => {
+                        let kind = match segment.as_deref().map(ContextSegmentKind::parse) {
+                            Some(None) => {
+                                self.push_notice(
+                                    NoticeLevel::Error,
+                                    format!(
+                                        "Unknown context segment. Usage: {}",
+                                        TuiCommandType::Context.usage()
+                                    ),
+                                );
+                                return Ok(());
+                            }
+                            Some(kind) => kind,
+                            None => None,
+                        };
+                        match self
+                            .client
+                            .get_context_report(&self.session_id, Some(&self.current_model))
+                            .await
+                        {
+                            Ok(report) => {
+                                let response = Self::format_context_report(&report, kind);
+                                self.push_tui_response(
+                                    tui_cmd.as_command_str(),
+                                    TuiCommandResponse::Text(response),
+                                );
+                            }
+                            Err(e) => {
+                                self.push_notice(NoticeLevel::Error, Self::format_grpc_error(&e));
+                            }
+                        }
+                    }
+                    TuiCommand::Timing => {
+                        let response = match &self.last_operation_timing {
+                            Some(timing) => format_operation_timing(timing),
+                            None => "No timing recorded yet. Run a turn first.".to_string(),
+                        };
+                        self.push_tui_response(
+                            tui_cmd.as_command_str(),
+                            TuiCommandResponse::Text(response),
+                        );
+                    }
+                    TuiCommand::BestOf(ref count) => {
+                        self.handle_best_of_command(tui_cmd.as_command_str(), count.as_deref());
+                    }
+                    TuiCommand::Attach(None) => {
+                        self.push_notice(
+                            NoticeLevel::Warn,
+                            format!("Usage: {}", TuiCommandType::Attach.usage()),
+                        );
+                    }
+                    TuiCommand::Attach(Some(ref path)) => {
+                        match load_file_attachment(&expand_home(path)) {
+                            Ok(attachment) => self.staged_attachments.push(attachment),
+                            Err(message) => self.push_notice(NoticeLevel::Warn, message),
+                        }
+                    }
+                    TuiCommand::Isolation(ref action) => {
+                        self.handle_isolation_command(tui_cmd.as_command_str(), action.as_deref())
+                            .await?;
+                    }
+                    TuiCommand::Memory(ref args) => {
+                        self.handle_memory_command(tui_cmd.as_command_str(), args.as_deref())
+                            .await;
+                    }
                     TuiCommand::Custom(custom_cmd) => match custom_cmd {
                         crate::tui::custom_commands::CustomCommand::Prompt { prompt, .. } => {
                             self.client
@@ -2303,6 +3316,26 @@ impl Tui {
                         Err(e) => self.push_notice(NoticeLevel::Error, Self::format_grpc_error(&e)),
                     }
                 }
+                crate::tui::core_commands::CoreCommandType::Retry { model } => {
+                    // The model override applies to this retry only; the session
+                    // model is left unchanged.
+                    let model = if let Some(query) = model {
+                        let Some(model_id) = self.resolve_model_query(&query).await else {
+                            self.push_notice(
+                                NoticeLevel::Error,
+                                format!("No available model matches '{query}'"),
+                            );
+                            return Ok(());
+                        };
+                        model_id
+                    } else {
+                        self.current_model.clone()
+                    };
+                    match self.client.retry_last_turn(model).await {
+                        Ok(()) => self.clear_ctx_utilization(),
+                        Err(e) => self.push_notice(NoticeLevel::Error, Self::format_grpc_error(&e)),
+                    }
+                }
                 crate::tui::core_commands::CoreCommandType::Agent { target } => {
                     if let Some(agent_id) = target {
                         if let Err(e) = self.client.switch_primary_agent(agent_id.clone()).await {
@@ -2327,15 +3360,53 @@ impl Tui {
                     }
                 }
                 crate::tui::core_commands::CoreCommandType::Model { target } => {
-                    if let Some(model_name) = target {
-                        match self.client.resolve_model(&model_name).await {
-                            Ok(model_id) => {
-                                self.current_model = model_id;
-                            }
-                            Err(e) => {
-                                self.push_notice(NoticeLevel::Error, Self::format_grpc_error(&e));
-                            }
+                    let Some(query) = target else {
+                        self.open_model_picker(0).await;
+                        self.switch_mode(InputMode::FuzzyFinder);
+                        return Ok(());
+                    };
+                    match self.resolve_model_query(&query).await {
+                        Some(model_id) => self.set_model(model_id).await,
+                        None => self.push_notice(
+                            NoticeLevel::Error,
+                            format!("No available model matches '{query}'"),
+                        ),
+                    }
+                }
+                crate::tui::core_commands::CoreCommandType::Review { target } => {
+                    let target = match target
+                        .as_deref()
+                        .unwrap_or_default()
+                        .parse::<steer_grpc::client_api::ReviewTarget>()
+                    {
+                        Ok(target) => target,
+                        Err(e) => {
+                            self.push_notice(NoticeLevel::Error, e.to_string());
+                            return Ok(());
+                        }
+                    };
+                    // Runs in the background; the report arrives as a client event.
+                    match self
+                        .client
+                        .start_review(target.clone(), self.current_model.clone())
+                        .await
+                    {
+                        Ok(()) => self
+                            .push_notice(NoticeLevel::Info, format!("Reviewing {target} changes…")),
+                        Err(e) => self.push_notice(NoticeLevel::Error, Self::format_grpc_error(&e)),
+                    }
+                }
+                crate::tui::core_commands::CoreCommandType::Commit => {
+                    // Runs in the background; the draft arrives as a client event.
+                    match self
+                        .client
+                        .start_commit_draft(self.current_model.clone())
+                        .await
+                    {
+                        Ok(()) => {
+                            self.push_notice(NoticeLevel::Info, "Drafting commit message…".into());
                         }
+                        Err(e) => self.push_notice(NoticeLevel::Error, Self::format_grpc_error(&e)),
                     }
                 }
             },
@@ -2344,6 +3415,110 @@ impl Tui {
         Ok(())
     }
 
+    /// `/help` text: usage for one command, or a list of all of them.
+    fn command_help(registry: &CommandRegistry, command_name: Option<&str>) -> String {
+        let Some(cmd_name) = command_name else {
+            let mut help_lines = vec!["Available commands:".to_string()];
+            for cmd_info in registry.all_commands() {
+                help_lines.push(format!(
+                    "  {:<20} - {}",
+                    cmd_info.usage, cmd_info.description
+                ));
+            }
+            return help_lines.join("\n");
+        };
+        if let Some(cmd_info) = registry.get(cmd_name) {
+            format!(
+                "Command: {}\n\nDescription: {}\n\nUsage: {}",
+                cmd_info.name, cmd_info.description, cmd_info.usage
+            )
+        } else {
+            format!("Unknown command: {cmd_name}")
+        }
+    }
+
+    /// Exact IDs and aliases resolve server-side, including models outside
+    /// the picker list; anything else is matched fuzzily.
+    async fn resolve_model_query(&self, query: &str) -> Option<ModelId> {
+        if let Ok(model_id) = self.client.resolve_model(query).await {
+            return Some(model_id);
+        }
+        let models = self.available_models().await;
+        handlers::fuzzy_finder::fuzzy_resolve_model(query, &models)
+    }
+
+    /// Switches the session model after checking its provider is authenticated
+    /// and reachable.
+    async fn set_model(&mut self, model_id: ModelId) {
+        let provider = model_id.provider.storage_key();
+        let authenticated = match self
+            .client
+            .get_provider_auth_status(Some(provider.clone()))
+            .await
+        {
+            Ok(statuses) => statuses.iter().any(|status| {
+                status
+                    .auth_source
+                    .as_ref()
+                    .is_some_and(|source| *source != AuthSource::None)
+            }),
+            Err(e) => {
+                self.push_notice(NoticeLevel::Error, Self::format_grpc_error(&e));
+                return;
+            }
+        };
+        if !authenticated {
+            self.push_notice(
+                NoticeLevel::Error,
+                format!(
+                    "Provider '{provider}' is not authenticated. Run /{} to set it up.",
+                    crate::tui::commands::TuiCommandType::Auth.command_name()
+                ),
+            );
+            return;
+        }
+
+        let unavailable_reason = match self.client.list_models(Some(provider.clone())).await {
+            Ok(models) => models
+                .into_iter()
+                .find(|model| model.model_id == model_id.id)
+                .and_then(|model| model.unavailable_reason),
+            Err(e) => {
+                warn!("Failed to list models: {}", e);
+                None
+            }
+        };
+        if let Some(reason) = unavailable_reason {
+            self.push_notice(NoticeLevel::Error, reason);
+            return;
+        }
+
+        if self.is_processing {
+            // Let the running operation finish its current turn, then continue on the new model.
+            if let Err(e) = self
+                .client
+                .switch_model(model_id.clone(), ModelSwitchMode::AfterCurrentTurn)
+                .await
+            {
+                self.push_notice(NoticeLevel::Error, Self::format_grpc_error(&e));
+                return;
+            }
+            self.push_notice(
+                NoticeLevel::Info,
+                format!(
+                    "Model set to {provider}/{}; takes effect after the current turn",
+                    model_id.id
+                ),
+            );
+        } else {
+            self.push_notice(
+                NoticeLevel::Info,
+                format!("Model set to {provider}/{}", model_id.id),
+            );
+        }
+        self.current_model = model_id;
+    }
+
     /// Enter edit mode for a specific message
     fn enter_edit_mode(&mut self, message_id: &str) {
         // Find the message in the store
@@ -2355,23 +3530,23 @@ impl Tui {
             self.pending_attachments.clear();
             self.next_attachment_token = FIRST_ATTACHMENT_TOKEN;
 
-            // Build the text content, restoring images as inline attachment tokens
+            // Build the text content, restoring images and files as inline attachment tokens
             let mut text_parts = Vec::new();
             for block in content {
                 match block {
                     UserContent::Text { text } => {
                         text_parts.push(text.clone());
                     }
-                    UserContent::Image { image } => {
+                    UserContent::Image { .. } | UserContent::Attachment { .. } => {
                         let token =
                             char::from_u32(self.next_attachment_token).unwrap_or('\u{E000}');
                         self.next_attachment_token += 1;
-                        self.pending_attachments.push(PendingAttachment {
-                            image: image.clone(),
+                        let attachment = PendingAttachment {
+                            content: block.clone(),
                             token,
-                        });
-                        let image_number = self.pending_attachments.len();
-                        let label = format_inline_image_token(image_number);
+                        };
+                        let label = attachment.label(self.pending_image_count() + 1);
+                        self.pending_attachments.push(attachment);
                         text_parts.push(format!("{token}{label}"));
                     }
                     UserContent::CommandExecution { .. } => {}
@@ -2452,6 +3627,10 @@ impl Drop for Tui {
 /// Helper to wrap terminal cleanup in panic handler
 pub fn setup_panic_hook() {
     std::panic::set_hook(Box::new(|panic_info| {
+        // Render panics are recovered from, so keep the terminal as it is
+        if crash::capture_if_guarded(panic_info) {
+            return;
+        }
         cleanup();
         // Print panic info to stderr after restoring terminal state
         tracing_error!("Application panicked:");
@@ -2460,6 +3639,56 @@ pub fn setup_panic_hook() {
 }
 
 /// High-level entry point for running the TUI
+/// Resume `session_id`, or create a session for `directory` when there is
+/// none. Returns the session ID with its messages and compaction summary IDs.
+async fn open_session(
+    client: &AgentClient,
+    session_id: Option<String>,
+    model: &ModelId,
+    directory: Option<&PathBuf>,
+) -> Result<(String, Vec<Message>, Vec<String>)> {
+    use std::collections::HashMap;
+    use steer_grpc::client_api::{
+        AutoSummaryConfig, CreateSessionParams, IsolationMode, SessionPolicyOverrides,
+        SessionToolConfig, WorkspaceConfig,
+    };
+
+    if let Some(session_id) = session_id {
+        let (messages, _approved_tools, compaction_summary_ids) =
+            client.resume_session(&session_id).await.map_err(Box::new)?;
+        info!(
+            "Resumed session: {} with {} messages",
+            session_id,
+            messages.len()
+        );
+        tracing_info!("Session ID: {session_id}");
+        Ok((session_id, messages, compaction_summary_ids))
+    } else {
+        // Create a new session
+        let workspace = if let Some(dir) = directory {
+            WorkspaceConfig::Local { path: dir.clone() }
+        } else {
+            WorkspaceConfig::default()
+        };
+        let session_params = CreateSessionParams {
+            workspace,
+            tool_config: SessionToolConfig::default(),
+            primary_agent_id: None,
+            policy_overrides: SessionPolicyOverrides::empty(),
+            isolation: IsolationMode::None,
+            auto_summary: AutoSummaryConfig::default(),
+            metadata: HashMap::new(),
+            default_model: model.clone(),
+        };
+
+        let session_id = client
+            .create_session(session_params)
+            .await
+            .map_err(Box::new)?;
+        Ok((session_id, vec![], vec![]))
+    }
+}
+
 pub async fn run_tui(
     client: steer_grpc::AgentClient,
     session_id: Option<String>,
@@ -2468,11 +3697,6 @@ pub async fn run_tui(
     theme_name: Option<String>,
     force_setup: bool,
 ) -> Result<()> {
-    use std::collections::HashMap;
-    use steer_grpc::client_api::{
-        CreateSessionParams, SessionPolicyOverrides, SessionToolConfig, WorkspaceConfig,
-    };
-
     // Load theme - use catppuccin-mocha as default if none specified
     let loader = theme::ThemeLoader::new();
     let theme = if let Some(theme_name) = theme_name {
@@ -2517,42 +3741,15 @@ pub async fn run_tui(
         }
     };
 
-    let (session_id, messages, compaction_summary_ids) = if let Some(session_id) = session_id {
-        let (messages, _approved_tools, compaction_summary_ids) =
-            client.resume_session(&session_id).await.map_err(Box::new)?;
-        info!(
-            "Resumed session: {} with {} messages",
-            session_id,
-            messages.len()
-        );
-        tracing_info!("Session ID: {session_id}");
-        (session_id, messages, compaction_summary_ids)
-    } else {
-        // Create a new session
-        let workspace = if let Some(ref dir) = directory {
-            WorkspaceConfig::Local { path: dir.clone() }
-        } else {
-            WorkspaceConfig::default()
-        };
-        let session_params = CreateSessionParams {
-            workspace,
-            tool_config: SessionToolConfig::default(),
-            primary_agent_id: None,
-            policy_overrides: SessionPolicyOverrides::empty(),
-            metadata: HashMap::new(),
-            default_model: model.clone(),
-        };
-
-        let session_id = client
-            .create_session(session_params)
-            .await
-            .map_err(Box::new)?;
-        (session_id, vec![], vec![])
-    };
+    let (session_id, messages, compaction_summary_ids) =
+        open_session(&client, session_id, &model, directory.as_ref()).await?;
 
     client.subscribe_session_events().await.map_err(Box::new)?;
     let event_rx = client.subscribe_client_events().await.map_err(Box::new)?;
     let mut tui = Tui::new(client, model.clone(), session_id.clone(), theme.clone()).await?;
+    if let Some(workspace) = directory.clone().or_else(|| std::env::current_dir().ok()) {
+        tui.input_history = InputHistory::for_workspace(&workspace);
+    }
 
     // Ensure terminal cleanup even if we error before entering the event loop
     struct TuiCleanupGuard;
@@ -3032,7 +4229,7 @@ mod tests {
 
     #[tokio::test]
     #[ignore = "Requires TTY - run with `cargo test -- --ignored` in a terminal"]
-    async fn test_ctrl_r_scrolls_to_bottom_in_simple_mode() {
+    async fn test_ctrl_o_scrolls_to_bottom_in_simple_mode() {
         let _guard = TerminalCleanupGuard;
         let workspace_root = tempdir().expect("tempdir");
         let (client, _server_handle) =
@@ -3046,8 +4243,8 @@ mod tests {
         tui.preferences.ui.editing_mode = EditingMode::Simple;
         tui.input_mode = InputMode::Simple;
 
-        let key = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL);
-        tui.handle_simple_mode(key).await.expect("handle ctrl+r");
+        let key = KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL);
+        tui.handle_simple_mode(key).await.expect("handle ctrl+o");
 
         assert_eq!(
             tui.chat_viewport.state().view_mode,
@@ -3106,6 +4303,10 @@ mod tests {
                         content: "file content here".to_string(),
                         line_count: 1,
                         truncated: false,
+                        encoding: None,
+                        binary_kind: None,
+                        file_size: None,
+                        warnings: Vec::new(),
                     },
                 ),
             },
@@ -3163,6 +4364,10 @@ mod tests {
                         content: "file content".to_string(),
                         line_count: 1,
                         truncated: false,
+                        encoding: None,
+                        binary_kind: None,
+                        file_size: None,
+                        warnings: Vec::new(),
                     },
                 ),
             },
@@ -3202,31 +4407,87 @@ mod tests {
         assert_eq!(strip_image_token_labels(&content), "hello world");
     }
 
+    #[test]
+    fn format_token_count_groups_thousands() {
+        assert_eq!(format_token_count(0), "0");
+        assert_eq!(format_token_count(999), "999");
+        assert_eq!(format_token_count(1_000), "1,000");
+        assert_eq!(format_token_count(1_234_567), "1,234,567");
+    }
+
+    #[test]
+    fn operation_timing_renders_bars_scaled_to_longest_span() {
+        use steer_grpc::client_api::{TimingSpan, TimingSpanKind};
+
+        let timing = OperationTiming {
+            total_ms: 2_450,
+            spans: vec![
+                TimingSpan {
+                    kind: TimingSpanKind::TimeToFirstToken,
+                    duration_ms: 1_200,
+                },
+                TimingSpan {
+                    kind: TimingSpanKind::ToolExecution {
+                        tool_name: "bash".to_string(),
+                    },
+                    duration_ms: 300,
+                },
+                TimingSpan {
+                    kind: TimingSpanKind::QueueWait,
+                    duration_ms: 0,
+                },
+            ],
+        };
+
+        let output = format_operation_timing(&timing);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "Last operation took 2.5s");
+        assert!(lines[1].starts_with("  model: first token  "));
+        assert_eq!(lines[1].matches('█').count(), 24);
+        assert!(lines[1].ends_with("1.2s"));
+        assert!(lines[2].starts_with("  tool: bash         "));
+        assert_eq!(lines[2].matches('█').count(), 6);
+        assert!(lines[2].ends_with("300ms"));
+        assert_eq!(lines[3].matches('█').count(), 0);
+        assert!(lines[3].ends_with("0ms"));
+    }
+
+    #[test]
+    fn file_mentions_strip_trailing_punctuation() {
+        let mentions: Vec<_> =
+            file_mentions("see @src/app.rs#App::spawn, and @README.md. email@x @").collect();
+        assert_eq!(mentions, vec!["src/app.rs#App::spawn", "README.md"]);
+    }
+
     #[test]
     fn parse_inline_message_content_preserves_text_image_order() {
         let first = PendingAttachment {
-            image: ImageContent {
-                source: ImageSource::DataUrl {
-                    data_url: "data:image/png;base64,AAAA".to_string(),
+            content: UserContent::Image {
+                image: ImageContent {
+                    source: ImageSource::DataUrl {
+                        data_url: "data:image/png;base64,AAAA".to_string(),
+                    },
+                    mime_type: "image/png".to_string(),
+                    width: Some(1),
+                    height: Some(1),
+                    bytes: Some(4),
+                    sha256: None,
                 },
-                mime_type: "image/png".to_string(),
-                width: Some(1),
-                height: Some(1),
-                bytes: Some(4),
-                sha256: None,
             },
             token: 'A',
         };
         let second = PendingAttachment {
-            image: ImageContent {
-                source: ImageSource::DataUrl {
-                    data_url: "data:image/jpeg;base64,BBBB".to_string(),
+            content: UserContent::Image {
+                image: ImageContent {
+                    source: ImageSource::DataUrl {
+                        data_url: "data:image/jpeg;base64,BBBB".to_string(),
+                    },
+                    mime_type: "image/jpeg".to_string(),
+                    width: Some(1),
+                    height: Some(1),
+                    bytes: Some(4),
+                    sha256: None,
                 },
-                mime_type: "image/jpeg".to_string(),
-                width: Some(1),
-                height: Some(1),
-                bytes: Some(4),
-                sha256: None,
             },
             token: 'B',
         };
@@ -3241,7 +4502,7 @@ mod tests {
         ));
         assert!(matches!(
             &parsed[1],
-            UserContent::Image { image } if image.mime_type == first.image.mime_type
+            UserContent::Image { image } if image.mime_type == "image/png"
         ));
         assert!(matches!(
             &parsed[2],
@@ -3249,7 +4510,7 @@ mod tests {
         ));
         assert!(matches!(
             &parsed[3],
-            UserContent::Image { image } if image.mime_type == second.image.mime_type
+            UserContent::Image { image } if image.mime_type == "image/jpeg"
         ));
         assert!(matches!(
             &parsed[4],
@@ -3260,15 +4521,17 @@ mod tests {
     #[test]
     fn parse_inline_message_content_skips_marker_labels_after_tokens() {
         let attachment = PendingAttachment {
-            image: ImageContent {
-                source: ImageSource::DataUrl {
-                    data_url: "data:image/png;base64,AAAA".to_string(),
+            content: UserContent::Image {
+                image: ImageContent {
+                    source: ImageSource::DataUrl {
+                        data_url: "data:image/png;base64,AAAA".to_string(),
+                    },
+                    mime_type: "image/png".to_string(),
+                    width: Some(1),
+                    height: Some(1),
+                    bytes: Some(4),
+                    sha256: None,
                 },
-                mime_type: "image/png".to_string(),
-                width: Some(1),
-                height: Some(1),
-                bytes: Some(4),
-                sha256: None,
             },
             token: 'A',
         };
@@ -3288,6 +4551,87 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn parse_inline_message_content_emits_file_attachments_and_skips_their_labels() {
+        let attachment = PendingAttachment {
+            content: UserContent::Attachment {
+                attachment: AttachmentContent {
+                    path: "/tmp/notes.md".to_string(),
+                    mime_type: "text/plain".to_string(),
+                    bytes: 5,
+                    body: AttachmentBody::Text {
+                        text: "notes".to_string(),
+                    },
+                },
+            },
+            token: 'A',
+        };
+
+        assert_eq!(attachment.label(1), "[File notes.md]");
+        let content = format!("see {}{} please", attachment.token, attachment.label(1));
+        let parsed = parse_inline_message_content(&content, &[attachment]);
+
+        assert_eq!(parsed.len(), 3);
+        assert!(matches!(
+            &parsed[1],
+            UserContent::Attachment { attachment } if attachment.path == "/tmp/notes.md"
+        ));
+        assert!(matches!(
+            &parsed[2],
+            UserContent::Text { text } if text == "please"
+        ));
+    }
+
+    #[test]
+    fn pasted_file_path_recognizes_dropped_files_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("my notes.txt");
+        std::fs::write(&file, "hello").unwrap();
+        let raw = file.display().to_string();
+
+        assert_eq!(pasted_file_path(&raw), Some(file.clone()));
+        assert_eq!(pasted_file_path(&format!("'{raw}'\n")), Some(file.clone()));
+        assert_eq!(
+            pasted_file_path(&raw.replace(' ', "\\ ")),
+            Some(file.clone())
+        );
+        assert_eq!(
+            pasted_file_path(&format!("file://{}", raw.replace(' ', "%20"))),
+            Some(file.clone())
+        );
+
+        assert_eq!(pasted_file_path("my notes.txt"), None);
+        assert_eq!(pasted_file_path(&format!("{raw}\n{raw}")), None);
+        assert_eq!(pasted_file_path(&dir.path().display().to_string()), None);
+    }
+
+    #[test]
+    fn load_file_attachment_reads_text_and_images_and_rejects_binary() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let text = dir.path().join("a.rs");
+        std::fs::write(&text, "fn main() {}").unwrap();
+        let attachment = load_file_attachment(&text).unwrap();
+        assert_eq!(attachment.mime_type, "text/plain");
+        assert_eq!(attachment.bytes, 12);
+        assert!(
+            matches!(attachment.body, AttachmentBody::Text { ref text } if text == "fn main() {}")
+        );
+
+        let png = dir.path().join("pixel.png");
+        let png_bytes = base64::engine::general_purpose::STANDARD
+            .decode("iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAQAAAC1HAwCAAAAC0lEQVR42mP8/x8AAwMCAO+X2N8AAAAASUVORK5CYII=")
+            .unwrap();
+        std::fs::write(&png, png_bytes).unwrap();
+        let attachment = load_file_attachment(&png).unwrap();
+        assert_eq!(attachment.mime_type, "image/png");
+        assert!(matches!(attachment.body, AttachmentBody::Image { .. }));
+
+        let binary = dir.path().join("blob.bin");
+        std::fs::write(&binary, [0xff, 0xfe, 0x00]).unwrap();
+        assert!(load_file_attachment(&binary).is_err());
+    }
+
     #[test]
     fn decode_pasted_image_recognizes_png_base64_and_sets_metadata() {
         let png_base64 = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAQAAAC1HAwCAAAAC0lEQVR42mP8/x8AAwMCAO+X2N8AAAAASUVORK5CYII=";
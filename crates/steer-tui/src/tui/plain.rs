@@ -0,0 +1,933 @@
+//! Plain, line-oriented interaction mode for screen readers.
+//!
+//! Nothing is drawn with cursor addressing and the alternate screen is never
+//! entered: replies, tool activity and notices are written to stdout as whole
+//! lines, input is read a line at a time (so the terminal's own line editing
+//! applies), and approval prompts are answered by typing `y`, `n` or `a`.
+//!
+//! Client events go through the same [`EventPipeline`] as the TUI and the chat
+//! items it produces are transcribed once they settle, so both modes show the
+//! same conversation. Slash commands are parsed with the TUI's command types
+//! and registry; the ones that need the full-screen UI are declined.
+
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use ratatui::text::Line;
+use steer_grpc::AgentClient;
+use steer_grpc::client_api::{
+    ApprovalDecision, ClientEvent, CommitDraft, ContextSegmentKind, MessageData, ModelId, OpId,
+    OperationTiming, Preferences, QueuedWorkItem, ToolCall, ToolResult, UserContent,
+};
+use steer_i18n::t;
+use steer_tools::tools::BASH_TOOL_NAME;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::mpsc;
+
+use crate::error::Result;
+use crate::notifications::{NotificationManager, NotificationManagerHandle};
+use crate::tui::Tui;
+use crate::tui::clipboard::message_markdown;
+use crate::tui::commands::registry::{CommandRegistry, CommandScope};
+use crate::tui::commands::{AppCommand, TuiCommand, TuiCommandType};
+use crate::tui::core_commands::CoreCommandType;
+use crate::tui::custom_commands::CustomCommand;
+use crate::tui::events::pipeline::EventPipeline;
+use crate::tui::events::processor::{PendingToolApproval, ProcessingContext};
+use crate::tui::handlers::approval::{always_decision, denial_decision};
+use crate::tui::model::{ChatItem, ChatItemData, NoticeLevel, TuiCommandResponse, generate_row_id};
+use crate::tui::state::{ChatStore, InputHistory, LlmUsageState, ToolCallRegistry};
+use crate::tui::theme::Theme;
+use crate::tui::widgets::chat_widgets::{CommandResponseWidget, ToolWidget, format_app_command};
+use crate::tui::widgets::formatters::get_formatter;
+use crate::tui::widgets::input_panel::InputPanelState;
+use crate::tui::widgets::{ChatListState, ChatRenderable, ViewMode};
+
+/// Width tool summaries and command responses are laid out at before their
+/// styling is dropped. The terminal wraps anything longer.
+const PLAIN_WIDTH: u16 = 100;
+
+/// Shown when the session is idle and waiting for input.
+const PROMPT: &str = "> ";
+
+/// Run a session in plain mode until stdin closes or `/exit` is entered.
+pub async fn run_plain(
+    client: AgentClient,
+    session_id: Option<String>,
+    model: ModelId,
+    directory: Option<PathBuf>,
+) -> Result<()> {
+    let (session_id, messages, _compaction_summary_ids) =
+        super::open_session(&client, session_id, &model, directory.as_ref()).await?;
+
+    client.subscribe_session_events().await.map_err(Box::new)?;
+    let event_rx = client.subscribe_client_events().await.map_err(Box::new)?;
+
+    let mut session = PlainSession::new(client, session_id, model, std::io::stdout());
+    if let Some(workspace) = directory.or_else(|| std::env::current_dir().ok()) {
+        session.input_history = InputHistory::for_workspace(&workspace);
+    }
+    session.announce_start(messages.len())?;
+    session.run(event_rx).await
+}
+
+/// A pending yes/no question and what answering it resolves.
+enum Question {
+    Approval(PendingToolApproval),
+    Commit(Box<CommitDraft>),
+}
+
+struct PlainSession<W: Write> {
+    out: W,
+    client: AgentClient,
+    session_id: String,
+    theme: Theme,
+    event_pipeline: EventPipeline,
+    command_registry: CommandRegistry,
+    input_history: InputHistory,
+
+    // State the shared event processors update.
+    chat_store: ChatStore,
+    chat_list_state: ChatListState,
+    tool_registry: ToolCallRegistry,
+    notification_manager: NotificationManagerHandle,
+    input_panel_state: InputPanelState,
+    is_processing: bool,
+    progress_message: Option<String>,
+    spinner_state: usize,
+    current_tool_approval: Option<PendingToolApproval>,
+    pending_commit: Option<CommitDraft>,
+    current_model: ModelId,
+    current_agent_label: Option<String>,
+    in_flight_operations: HashSet<OpId>,
+    notify_on_processing_complete: HashMap<OpId, bool>,
+    queued_head: Option<QueuedWorkItem>,
+    queued_count: usize,
+    llm_usage: LlmUsageState,
+    last_operation_timing: Option<OperationTiming>,
+
+    /// Chat items already written out.
+    transcribed: HashSet<String>,
+    question: Option<Question>,
+    /// Whether the input prompt is the last thing on screen.
+    prompt_shown: bool,
+}
+
+impl<W: Write> PlainSession<W> {
+    fn new(client: AgentClient, session_id: String, model: ModelId, out: W) -> Self {
+        let preferences = Preferences::load().unwrap_or_default();
+        let notification_manager = Arc::new(NotificationManager::new(&preferences));
+        Self {
+            out,
+            client,
+            input_panel_state: InputPanelState::new(session_id.clone()),
+            session_id,
+            theme: Theme::default(),
+            event_pipeline: Tui::create_event_pipeline(notification_manager.clone()),
+            command_registry: CommandRegistry::new(),
+            input_history: InputHistory::default(),
+            chat_store: ChatStore::new(),
+            chat_list_state: ChatListState::new(),
+            tool_registry: ToolCallRegistry::new(),
+            notification_manager,
+            is_processing: false,
+            progress_message: None,
+            spinner_state: 0,
+            current_tool_approval: None,
+            pending_commit: None,
+            current_model: model,
+            current_agent_label: None,
+            in_flight_operations: HashSet::new(),
+            notify_on_processing_complete: HashMap::new(),
+            queued_head: None,
+            queued_count: 0,
+            llm_usage: LlmUsageState::default(),
+            last_operation_timing: None,
+            transcribed: HashSet::new(),
+            question: None,
+            prompt_shown: false,
+        }
+    }
+
+    fn announce_start(&mut self, restored_messages: usize) -> Result<()> {
+        let model = format!(
+            "{}/{}",
+            self.current_model.provider.storage_key(),
+            self.current_model.id
+        );
+        writeln!(
+            self.out,
+            "{}",
+            t!("plain.start", session = self.session_id, model = model)
+        )?;
+        if restored_messages > 0 {
+            writeln!(
+                self.out,
+                "{}",
+                t!("plain.resumed", count = restored_messages)
+            )?;
+        }
+        writeln!(self.out, "{}", t!("plain.usage"))?;
+        Ok(())
+    }
+
+    async fn run(&mut self, mut event_rx: mpsc::Receiver<ClientEvent>) -> Result<()> {
+        let mut input = BufReader::new(tokio::io::stdin()).lines();
+        self.flush_output()?;
+
+        loop {
+            tokio::select! {
+                event = event_rx.recv() => {
+                    let Some(event) = event else {
+                        self.notice(NoticeLevel::Error, t!("plain.connection_lost").to_string());
+                        self.flush_output()?;
+                        break;
+                    };
+                    self.handle_event(event).await;
+                }
+                line = input.next_line() => {
+                    self.prompt_shown = false;
+                    match line? {
+                        Some(line) => {
+                            if self.handle_line(&line).await {
+                                break;
+                            }
+                        }
+                        None => break,
+                    }
+                }
+                _ = tokio::signal::ctrl_c() => {
+                    if !self.is_processing {
+                        break;
+                    }
+                    if let Err(e) = self.client.cancel_operation().await {
+                        self.notice(NoticeLevel::Error, Tui::format_grpc_error(&e));
+                    }
+                }
+            }
+            self.flush_output()?;
+        }
+
+        if self.prompt_shown {
+            writeln!(self.out)?;
+        }
+        self.out.flush()?;
+        Ok(())
+    }
+
+    async fn handle_event(&mut self, event: ClientEvent) {
+        if let ClientEvent::ProcessingCompleted {
+            timing: Some(timing),
+            ..
+        } = &event
+        {
+            self.last_operation_timing = Some(timing.clone());
+        }
+        let was_processing = self.is_processing;
+
+        let mut messages_updated = false;
+        let mut ctx = ProcessingContext {
+            chat_store: &mut self.chat_store,
+            chat_list_state: &mut self.chat_list_state,
+            tool_registry: &mut self.tool_registry,
+            client: &self.client,
+            notification_manager: &self.notification_manager,
+            input_panel_state: &mut self.input_panel_state,
+            is_processing: &mut self.is_processing,
+            progress_message: &mut self.progress_message,
+            spinner_state: &mut self.spinner_state,
+            current_tool_approval: &mut self.current_tool_approval,
+            pending_commit: &mut self.pending_commit,
+            current_model: &mut self.current_model,
+            current_agent_label: &mut self.current_agent_label,
+            messages_updated: &mut messages_updated,
+            in_flight_operations: &mut self.in_flight_operations,
+            notify_on_processing_complete: &mut self.notify_on_processing_complete,
+            queued_head: &mut self.queued_head,
+            queued_count: &mut self.queued_count,
+            llm_usage: &mut self.llm_usage,
+        };
+        if let Err(e) = self.event_pipeline.process_event(event, &mut ctx).await {
+            tracing::error!(target: "tui.plain", "Event processing failed: {}", e);
+        }
+
+        if self.is_processing && !was_processing {
+            self.notice(NoticeLevel::Info, t!("plain.working").to_string());
+        }
+    }
+
+    /// Handle one line of input. Returns true when the session should end.
+    async fn handle_line(&mut self, line: &str) -> bool {
+        let line = line.trim();
+        if let Some(question) = self.question.take() {
+            self.answer(question, line).await;
+            return false;
+        }
+        if line.is_empty() {
+            return false;
+        }
+        if matches!(line, "/exit" | "/quit") {
+            return true;
+        }
+
+        self.input_history.push(line);
+        if line.starts_with('/') {
+            self.handle_slash_command(line).await;
+        } else if let Some(command) = line.strip_prefix('!').filter(|c| !c.trim().is_empty()) {
+            if let Err(e) = self
+                .client
+                .execute_bash_command(command.trim().to_string())
+                .await
+            {
+                self.notice(NoticeLevel::Error, Tui::format_grpc_error(&e));
+            }
+        } else {
+            self.send_text(line.to_string()).await;
+        }
+        false
+    }
+
+    async fn answer(&mut self, question: Question, answer: &str) {
+        let (word, rest) = answer
+            .split_once(char::is_whitespace)
+            .map_or((answer, ""), |(word, rest)| (word, rest.trim()));
+        let word = word.to_ascii_lowercase();
+
+        match question {
+            Question::Approval((request_id, tool_call)) => {
+                let decision = match word.as_str() {
+                    "y" | "yes" => Ok(ApprovalDecision::Once),
+                    "a" | "always" => always_decision(&tool_call),
+                    "n" | "no" => Ok(denial_decision(rest.to_string())),
+                    _ => {
+                        self.notice(NoticeLevel::Warn, t!("plain.answer_approval").to_string());
+                        self.question = Some(Question::Approval((request_id, tool_call)));
+                        return;
+                    }
+                };
+                let result = match decision {
+                    Ok(decision) => self
+                        .client
+                        .approve_tool(request_id.to_string(), decision)
+                        .await
+                        .map_err(|e| Tui::format_grpc_error(&e)),
+                    Err(e) => Err(e.to_string()),
+                };
+                if let Err(message) = result {
+                    self.notice(NoticeLevel::Error, message);
+                }
+            }
+            Question::Commit(draft) => {
+                if matches!(word.as_str(), "y" | "yes") {
+                    match self.client.create_commit(&draft).await {
+                        Ok(outcome) => self.notice(
+                            NoticeLevel::Info,
+                            t!(
+                                "plain.committed",
+                                commit = outcome.commit,
+                                summary = outcome.summary
+                            ),
+                        ),
+                        Err(e) => self.notice(NoticeLevel::Error, Tui::format_grpc_error(&e)),
+                    }
+                } else {
+                    self.notice(NoticeLevel::Info, t!("plain.commit_cancelled").to_string());
+                }
+            }
+        }
+    }
+
+    async fn send_text(&mut self, text: String) {
+        if let Err(e) = self
+            .client
+            .send_message(text, self.current_model.clone())
+            .await
+        {
+            self.notice(NoticeLevel::Error, Tui::format_grpc_error(&e));
+        }
+    }
+
+    async fn handle_slash_command(&mut self, input: &str) {
+        let name = input.trim_start_matches('/');
+        if let Some(info) = self.command_registry.get(name)
+            && let CommandScope::Custom(CustomCommand::Prompt { prompt, .. }) = &info.scope
+        {
+            let prompt = prompt.clone();
+            self.send_text(prompt).await;
+            return;
+        }
+
+        let command = match AppCommand::parse(input) {
+            Ok(command) => command,
+            Err(e) => {
+                self.notice(NoticeLevel::Error, e.to_string());
+                return;
+            }
+        };
+
+        match command {
+            AppCommand::Tui(TuiCommand::Help(command_name)) => {
+                let help = Tui::command_help(&self.command_registry, command_name.as_deref());
+                self.respond(TuiCommandType::Help.command_name(), help);
+            }
+            AppCommand::Tui(TuiCommand::Custom(CustomCommand::Prompt { prompt, .. })) => {
+                self.send_text(prompt).await;
+            }
+            AppCommand::Tui(TuiCommand::Timing) => {
+                let response = match &self.last_operation_timing {
+                    Some(timing) => super::format_operation_timing(timing),
+                    None => t!("plain.no_timing").to_string(),
+                };
+                self.respond(TuiCommandType::Timing.command_name(), response);
+            }
+            AppCommand::Tui(TuiCommand::Context(segment)) => {
+                let kind = match segment.as_deref().map(ContextSegmentKind::parse) {
+                    Some(None) => {
+                        self.notice(
+                            NoticeLevel::Error,
+                            t!(
+                                "plain.unknown_context_segment",
+                                usage = TuiCommandType::Context.usage()
+                            ),
+                        );
+                        return;
+                    }
+                    Some(kind) => kind,
+                    None => None,
+                };
+                match self
+                    .client
+                    .get_context_report(&self.session_id, Some(&self.current_model))
+                    .await
+                {
+                    Ok(report) => self.respond(
+                        TuiCommandType::Context.command_name(),
+                        Tui::format_context_report(&report, kind),
+                    ),
+                    Err(e) => self.notice(NoticeLevel::Error, Tui::format_grpc_error(&e)),
+                }
+            }
+            AppCommand::Tui(other) => {
+                self.notice(
+                    NoticeLevel::Warn,
+                    t!(
+                        "plain.unavailable_command",
+                        command = other.as_command_str()
+                    ),
+                );
+            }
+            AppCommand::Core(command) => self.handle_core_command(command).await,
+        }
+    }
+
+    async fn handle_core_command(&mut self, command: CoreCommandType) {
+        let result = match command {
+            CoreCommandType::Compact => {
+                self.client
+                    .compact_session(self.current_model.clone())
+                    .await
+            }
+            CoreCommandType::Retry { model } => {
+                let model = match model {
+                    Some(query) => match self.client.resolve_model(&query).await {
+                        Ok(model) => model,
+                        Err(_) => {
+                            self.notice(
+                                NoticeLevel::Error,
+                                t!("plain.no_matching_model", query = query),
+                            );
+                            return;
+                        }
+                    },
+                    None => self.current_model.clone(),
+                };
+                self.client.retry_last_turn(model).await
+            }
+            CoreCommandType::Agent {
+                target: Some(agent_id),
+            } => self.client.switch_primary_agent(agent_id).await,
+            CoreCommandType::Agent { target: None } => {
+                match self.client.list_primary_agents().await {
+                    Ok(agents) => {
+                        let response = Tui::format_available_primary_agents(
+                            &agents,
+                            self.current_agent_label.as_deref(),
+                        );
+                        self.respond(
+                            crate::tui::commands::CoreCommandType::Agent.command_name(),
+                            response,
+                        );
+                        Ok(())
+                    }
+                    Err(e) => Err(e),
+                }
+            }
+            CoreCommandType::Model { target: None } => {
+                self.notice(
+                    NoticeLevel::Info,
+                    t!(
+                        "plain.current_model",
+                        model = format!(
+                            "{}/{}",
+                            self.current_model.provider.storage_key(),
+                            self.current_model.id
+                        )
+                    ),
+                );
+                return;
+            }
+            CoreCommandType::Model {
+                target: Some(query),
+            } => match self.client.resolve_model(&query).await {
+                Ok(model_id) => {
+                    self.notice(
+                        NoticeLevel::Info,
+                        t!(
+                            "plain.model_set",
+                            model = format!("{}/{}", model_id.provider.storage_key(), model_id.id)
+                        ),
+                    );
+                    self.current_model = model_id;
+                    return;
+                }
+                Err(_) => {
+                    self.notice(
+                        NoticeLevel::Error,
+                        t!("plain.no_matching_model", query = query),
+                    );
+                    return;
+                }
+            },
+            CoreCommandType::Review { target } => {
+                let target = match target
+                    .as_deref()
+                    .unwrap_or_default()
+                    .parse::<steer_grpc::client_api::ReviewTarget>()
+                {
+                    Ok(target) => target,
+                    Err(e) => {
+                        self.notice(NoticeLevel::Error, e.to_string());
+                        return;
+                    }
+                };
+                self.notice(NoticeLevel::Info, t!("plain.reviewing", target = target));
+                self.client
+                    .start_review(target, self.current_model.clone())
+                    .await
+            }
+            CoreCommandType::Commit => {
+                self.notice(NoticeLevel::Info, t!("plain.drafting_commit").to_string());
+                self.client
+                    .start_commit_draft(self.current_model.clone())
+                    .await
+            }
+        };
+        if let Err(e) = result {
+            self.notice(NoticeLevel::Error, Tui::format_grpc_error(&e));
+        }
+    }
+
+    fn notice(&mut self, level: NoticeLevel, text: String) {
+        self.chat_store.push(ChatItem {
+            parent_chat_item_id: None,
+            data: ChatItemData::SystemNotice {
+                id: generate_row_id(),
+                level,
+                text,
+                ts: time::OffsetDateTime::now_utc(),
+            },
+        });
+    }
+
+    fn respond(&mut self, command: String, text: String) {
+        self.chat_store.push(ChatItem {
+            parent_chat_item_id: None,
+            data: ChatItemData::TuiCommandResponse {
+                id: generate_row_id(),
+                command,
+                response: TuiCommandResponse::Text(text),
+                ts: time::OffsetDateTime::now_utc(),
+            },
+        });
+    }
+
+    /// Write out newly settled chat items, then the pending question or the
+    /// input prompt.
+    fn flush_output(&mut self) -> Result<()> {
+        if self.question.is_none() {
+            if let Some(approval) = self.current_tool_approval.take() {
+                self.question = Some(Question::Approval(approval));
+            } else if let Some(draft) = self.pending_commit.take() {
+                self.question = Some(Question::Commit(Box::new(draft)));
+            }
+        }
+
+        let mut entries = Vec::new();
+        for item in self.chat_store.iter() {
+            if self.transcribed.contains(item.id()) {
+                continue;
+            }
+            if let ChatItemData::Message(message) = &item.data
+                && self.chat_store.is_streaming(&message.id)
+            {
+                // Written once the final message arrives.
+                continue;
+            }
+            self.transcribed.insert(item.id().to_string());
+            if let Some(text) = transcribe(item, &self.tool_registry, &self.theme) {
+                entries.push(text);
+            }
+        }
+
+        if let Some(question) = &self.question
+            && self.transcribed.insert(question_key(question))
+        {
+            entries.push(self.question_text(question));
+        }
+
+        if !entries.is_empty() && self.prompt_shown {
+            writeln!(self.out)?;
+            self.prompt_shown = false;
+        }
+        for entry in entries {
+            writeln!(self.out, "{entry}")?;
+        }
+
+        let idle = !self.is_processing && self.question.is_none();
+        if idle && !self.prompt_shown {
+            write!(self.out, "{PROMPT}")?;
+            self.prompt_shown = true;
+        }
+        self.out.flush()?;
+        Ok(())
+    }
+
+    fn question_text(&self, question: &Question) -> String {
+        match question {
+            Question::Approval((_, tool_call)) => {
+                let always = if tool_call.name == BASH_TOOL_NAME {
+                    t!("plain.always_command")
+                } else {
+                    t!("plain.always_tool")
+                };
+                let preview = get_formatter(&tool_call.name).approval(
+                    &tool_call.parameters,
+                    PLAIN_WIDTH as usize,
+                    &self.theme,
+                );
+                t!(
+                    "plain.approval_question",
+                    tool = tool_call.name,
+                    preview = plain_text(&preview),
+                    always = always
+                )
+            }
+            Question::Commit(draft) => t!(
+                "plain.commit_question",
+                root = draft.root.display(),
+                files = draft.files.join(", "),
+                message = draft.message.trim_end()
+            ),
+        }
+    }
+}
+
+/// Questions are asked once; this key marks one as written.
+fn question_key(question: &Question) -> String {
+    match question {
+        Question::Approval((request_id, _)) => format!("approval:{request_id}"),
+        Question::Commit(draft) => format!("commit:{}", draft.message),
+    }
+}
+
+/// The text of a chat item for the transcript, or `None` for items that are
+/// only meaningful on screen or that echo what the user typed.
+fn transcribe(item: &ChatItem, tool_registry: &ToolCallRegistry, theme: &Theme) -> Option<String> {
+    match &item.data {
+        ChatItemData::Message(message) => match &message.data {
+            MessageData::Assistant { .. } => {
+                let text = message_markdown(message);
+                (!text.trim().is_empty())
+                    .then(|| format!("{}\n{}", t!("plain.assistant"), text.trim_end()))
+            }
+            MessageData::User { content, .. } => {
+                let executions = content
+                    .iter()
+                    .filter_map(|block| match block {
+                        UserContent::CommandExecution {
+                            command,
+                            stdout,
+                            stderr,
+                            exit_code,
+                        } => {
+                            let mut text = format!("$ {command}");
+                            for output in [stdout, stderr] {
+                                if !output.trim().is_empty() {
+                                    text.push('\n');
+                                    text.push_str(output.trim_end());
+                                }
+                            }
+                            if *exit_code != 0 {
+                                text.push_str(&format!("\nExit code: {exit_code}"));
+                            }
+                            Some(text)
+                        }
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                (!executions.is_empty()).then(|| executions.join("\n"))
+            }
+            MessageData::Tool {
+                tool_use_id,
+                result,
+                ..
+            } => {
+                let tool_call = tool_registry.get_tool_call(tool_use_id)?;
+                Some(t!(
+                    "plain.finished",
+                    tool = tool_text(tool_call, Some(result.clone()), theme)
+                ))
+            }
+        },
+        ChatItemData::PendingToolCall { tool_call, .. } => Some(t!(
+            "plain.running",
+            tool = tool_text(tool_call, None, theme)
+        )),
+        ChatItemData::SystemNotice { level, text, .. } => {
+            let label = match level {
+                NoticeLevel::Error => t!("notice.error"),
+                NoticeLevel::Warn => t!("notice.warning"),
+                NoticeLevel::Info => t!("notice.note"),
+            };
+            Some(format!("{label}: {text}"))
+        }
+        ChatItemData::CoreCmdResponse {
+            command, response, ..
+        } => {
+            let mut widget =
+                CommandResponseWidget::new(format_app_command(command), response.clone().into());
+            Some(plain_text(widget.lines(
+                PLAIN_WIDTH,
+                ViewMode::Compact,
+                theme,
+            )))
+        }
+        ChatItemData::TuiCommandResponse {
+            command, response, ..
+        } => {
+            let mut widget =
+                CommandResponseWidget::new(format!("/{command}"), response.clone().into());
+            Some(plain_text(widget.lines(
+                PLAIN_WIDTH,
+                ViewMode::Compact,
+                theme,
+            )))
+        }
+        ChatItemData::SlashInput { .. } | ChatItemData::InFlightOperation { .. } => None,
+    }
+}
+
+/// A tool call's compact summary, as the chat shows it, without styling.
+fn tool_text(tool_call: &ToolCall, result: Option<ToolResult>, theme: &Theme) -> String {
+    let mut widget = ToolWidget::new(tool_call.clone(), result);
+    plain_text(widget.lines(PLAIN_WIDTH, ViewMode::Compact, theme))
+}
+
+/// Rendered lines as plain text: styles dropped, trailing padding trimmed and
+/// rule lines left out.
+fn plain_text(lines: &[Line<'_>]) -> String {
+    lines
+        .iter()
+        .map(|line| {
+            let text = line
+                .spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>();
+            text.trim_end().to_string()
+        })
+        .filter(|text| text.is_empty() || !text.chars().all(|ch| ch == '─'))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::test_utils::local_client_and_server;
+    use serde_json::json;
+    use steer_grpc::client_api::{
+        AssistantContent, CreateSessionParams, Message, MessageId, RequestId, builtin,
+    };
+    use tokio::time::{Duration, timeout};
+
+    async fn plain_session() -> (PlainSession<Vec<u8>>, tempfile::TempDir) {
+        let workspace = tempfile::tempdir().unwrap();
+        let (client, _server) =
+            local_client_and_server(None, Some(workspace.path().to_path_buf())).await;
+        let session = PlainSession::new(
+            client,
+            "session".to_string(),
+            builtin::claude_sonnet_4_5(),
+            Vec::new(),
+        );
+        (session, workspace)
+    }
+
+    fn written(session: &mut PlainSession<Vec<u8>>) -> String {
+        String::from_utf8(std::mem::take(&mut session.out)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_streamed_reply_is_written_once_complete() {
+        let (mut session, _workspace) = plain_session().await;
+        session.flush_output().unwrap();
+        assert_eq!(written(&mut session), PROMPT);
+
+        session
+            .handle_event(ClientEvent::MessageDelta {
+                id: MessageId::from_string("a1"),
+                delta: "Hello".to_string(),
+            })
+            .await;
+        session.flush_output().unwrap();
+        assert_eq!(written(&mut session), "");
+
+        session
+            .handle_event(ClientEvent::AssistantMessageAdded {
+                message: Message {
+                    data: MessageData::Assistant {
+                        content: vec![AssistantContent::Text {
+                            text: "Hello, world".to_string(),
+                        }],
+                    },
+                    id: "a1".to_string(),
+                    timestamp: 0,
+                    parent_message_id: None,
+                },
+                model: builtin::claude_sonnet_4_5(),
+            })
+            .await;
+        session.flush_output().unwrap();
+        assert_eq!(
+            written(&mut session),
+            format!("\nAssistant:\nHello, world\n{PROMPT}")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_approval_is_asked_once_and_reasked_on_unclear_answer() {
+        let (mut session, _workspace) = plain_session().await;
+        session
+            .handle_event(ClientEvent::ApprovalRequested {
+                request_id: RequestId::new(),
+                tool_call: ToolCall {
+                    id: "call1".to_string(),
+                    name: BASH_TOOL_NAME.to_string(),
+                    parameters: json!({"command": "cargo test"}),
+                },
+            })
+            .await;
+        session.flush_output().unwrap();
+        let output = written(&mut session);
+        assert!(
+            output.starts_with("Tool bash needs your approval:"),
+            "{output}"
+        );
+        assert!(output.contains("cargo test"), "{output}");
+        assert!(
+            output.contains("a to always allow this command"),
+            "{output}"
+        );
+        assert!(!output.ends_with(PROMPT), "{output}");
+
+        session.flush_output().unwrap();
+        assert_eq!(written(&mut session), "");
+
+        session.handle_line("maybe").await;
+        session.flush_output().unwrap();
+        assert_eq!(written(&mut session), "Warning: Please answer y, a or n.\n");
+        assert!(matches!(session.question, Some(Question::Approval(_))));
+    }
+
+    async fn next_user_message(events: &mut mpsc::Receiver<ClientEvent>) -> Message {
+        loop {
+            let event = timeout(Duration::from_secs(5), events.recv())
+                .await
+                .expect("timed out waiting for a user message")
+                .expect("event stream closed");
+            if let ClientEvent::UserMessageAdded { message } = event {
+                return message;
+            }
+        }
+    }
+
+    async fn wait_until_idle(events: &mut mpsc::Receiver<ClientEvent>) {
+        loop {
+            let event = timeout(Duration::from_secs(5), events.recv())
+                .await
+                .expect("timed out waiting for the turn to finish")
+                .expect("event stream closed");
+            if matches!(event, ClientEvent::ProcessingCompleted { .. }) {
+                return;
+            }
+        }
+    }
+
+    fn text_of(message: &Message) -> String {
+        let MessageData::User { content } = &message.data else {
+            panic!("expected a user message, got {:?}", message.data);
+        };
+        content
+            .iter()
+            .filter_map(|block| match block {
+                UserContent::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_retry_resends_the_last_user_message() {
+        let workspace = tempfile::tempdir().unwrap();
+        let (client, _server) =
+            local_client_and_server(None, Some(workspace.path().to_path_buf())).await;
+        let model = builtin::claude_sonnet_4_5();
+        let session_id = client
+            .create_session(
+                CreateSessionParams::builder(model.clone())
+                    .workspace_path(workspace.path())
+                    .read_only()
+                    .build(),
+            )
+            .await
+            .unwrap();
+        let mut events = client.subscribe_client_events().await.unwrap();
+        client.subscribe_session_events().await.unwrap();
+        let mut session = PlainSession::new(client, session_id, model, Vec::new());
+
+        session.handle_line("Write a haiku").await;
+        let first = next_user_message(&mut events).await;
+        wait_until_idle(&mut events).await;
+
+        session.handle_line("/retry").await;
+        let retried = next_user_message(&mut events).await;
+
+        assert_eq!(text_of(&retried), "Write a haiku");
+        assert_ne!(retried.id, first.id);
+        assert_eq!(retried.parent_message_id, first.parent_message_id);
+    }
+
+    #[test]
+    fn test_plain_text_drops_styling_and_rules() {
+        let lines = vec![
+            Line::from(vec!["bash ".into(), "cargo test   ".into()]),
+            Line::from("─".repeat(10)),
+            Line::from(""),
+            Line::from("ok"),
+        ];
+        assert_eq!(plain_text(&lines), "bash cargo test\n\nok");
+    }
+}
@@ -0,0 +1,138 @@
+//! Candidates of a best-of turn while the user compares them.
+
+use steer_grpc::client_api::{AssistantContent, BestOfCandidate, ModelId, OpId};
+
+/// The candidates of one best-of run, in the order the server numbered them.
+#[derive(Debug)]
+pub struct BestOfView {
+    pub op_id: OpId,
+    pub model: ModelId,
+    slots: Vec<Option<BestOfCandidate>>,
+    selected: usize,
+}
+
+impl BestOfView {
+    pub fn new(op_id: OpId, model: ModelId, candidates: usize) -> Self {
+        Self {
+            op_id,
+            model,
+            slots: (0..candidates).map(|_| None).collect(),
+            selected: 0,
+        }
+    }
+
+    pub fn slots(&self) -> &[Option<BestOfCandidate>] {
+        &self.slots
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn record(&mut self, candidate: BestOfCandidate) {
+        let Ok(index) = usize::try_from(candidate.index) else {
+            return;
+        };
+        if let Some(slot) = self.slots.get_mut(index) {
+            *slot = Some(candidate);
+        }
+        if !self.is_pickable(self.selected)
+            && let Some(first) = (0..self.slots.len()).find(|&i| self.is_pickable(i))
+        {
+            self.selected = first;
+        }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.slots.iter().all(Option::is_some)
+    }
+
+    pub fn select_next(&mut self) {
+        let len = self.slots.len();
+        if let Some(next) = (1..len)
+            .map(|step| (self.selected + step) % len)
+            .find(|&i| self.is_pickable(i))
+        {
+            self.selected = next;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        let len = self.slots.len();
+        if let Some(prev) = (1..len)
+            .map(|step| (self.selected + len - step) % len)
+            .find(|&i| self.is_pickable(i))
+        {
+            self.selected = prev;
+        }
+    }
+
+    /// The server-side index of the selected candidate, if it can be picked.
+    pub fn selected_index(&self) -> Option<u32> {
+        self.slots
+            .get(self.selected)?
+            .as_ref()
+            .filter(|candidate| candidate.error.is_none())
+            .map(|candidate| candidate.index)
+    }
+
+    fn is_pickable(&self, index: usize) -> bool {
+        self.slots
+            .get(index)
+            .and_then(Option::as_ref)
+            .is_some_and(|candidate| candidate.error.is_none())
+    }
+}
+
+/// The text blocks of a candidate's answer, joined for display.
+pub fn candidate_text(candidate: &BestOfCandidate) -> String {
+    candidate
+        .content
+        .iter()
+        .filter_map(|block| match block {
+            AssistantContent::Text { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use steer_grpc::client_api::{MessageId, builtin};
+
+    fn candidate(index: u32, error: Option<&str>) -> BestOfCandidate {
+        BestOfCandidate {
+            index,
+            op_id: OpId::new(),
+            message_id: MessageId::new(),
+            content: vec![AssistantContent::Text {
+                text: format!("answer {index}"),
+            }],
+            usage: None,
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn selection_skips_failed_candidates() {
+        let mut view = BestOfView::new(OpId::new(), builtin::claude_sonnet_4_5(), 3);
+        view.record(candidate(0, Some("boom")));
+        assert_eq!(view.selected_index(), None);
+
+        view.record(candidate(2, None));
+        assert_eq!(view.selected_index(), Some(2));
+        assert!(!view.is_complete());
+
+        view.record(candidate(1, None));
+        assert!(view.is_complete());
+        view.select_next();
+        assert_eq!(view.selected_index(), Some(1));
+        view.select_next();
+        assert_eq!(view.selected_index(), Some(2));
+        view.select_prev();
+        assert_eq!(view.selected_index(), Some(1));
+        assert_eq!(candidate_text(&candidate(1, None)), "answer 1");
+    }
+}
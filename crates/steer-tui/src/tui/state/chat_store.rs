@@ -1,7 +1,7 @@
 //! ChatStore - storage for the new ChatItem model
 
 use crate::tui::model::{ChatItem, ChatItemData, RowId};
-use steer_grpc::client_api::{Message, MessageData};
+use steer_grpc::client_api::{Message, MessageData, UserContent};
 
 use indexmap::IndexMap;
 use std::collections::{HashMap, HashSet};
@@ -34,6 +34,8 @@ pub struct ChatStore {
     compaction_summary_ids: HashSet<String>,
     /// Mapping from compaction summary ID -> compacted head message ID.
     compaction_summary_heads: HashMap<String, String>,
+    /// Assistant messages that have received deltas but no final message yet
+    streaming_message_ids: HashSet<String>,
 }
 
 impl Default for ChatStore {
@@ -49,6 +51,7 @@ impl Default for ChatStore {
             compaction_head_key: None,
             compaction_summary_ids: HashSet::new(),
             compaction_summary_heads: HashMap::new(),
+            streaming_message_ids: HashSet::new(),
         }
     }
 }
@@ -152,6 +155,35 @@ impl ChatStore {
         self.compaction_summary_heads.get(id).map(String::as_str)
     }
 
+    /// Mark a message as receiving stream deltas.
+    pub fn mark_streaming(&mut self, id: &str) {
+        if !self.streaming_message_ids.contains(id) {
+            self.streaming_message_ids.insert(id.to_string());
+            self.revision += 1;
+        }
+    }
+
+    /// Mark a message's stream as complete.
+    pub fn finish_streaming(&mut self, id: &str) {
+        if self.streaming_message_ids.remove(id) {
+            self.revision += 1;
+        }
+    }
+
+    /// Check if a message is still being streamed.
+    pub fn is_streaming(&self, id: &str) -> bool {
+        self.streaming_message_ids.contains(id)
+    }
+
+    /// Stop tracking every open stream, returning the message IDs.
+    pub fn take_streaming_ids(&mut self) -> Vec<String> {
+        if self.streaming_message_ids.is_empty() {
+            return Vec::new();
+        }
+        self.revision += 1;
+        self.streaming_message_ids.drain().collect()
+    }
+
     /// Push a new item and return its key
     pub fn push(&mut self, mut item: ChatItem) -> ChatItemKey {
         let id = item.id().to_string();
@@ -240,6 +272,7 @@ impl ChatStore {
         self.compaction_head_key = None;
         self.compaction_summary_ids.clear();
         self.compaction_summary_heads.clear();
+        self.streaming_message_ids.clear();
         self.revision += 1; // Increment revision on mutation
     }
 
@@ -421,7 +454,74 @@ impl ChatStore {
         lineage
     }
 
+    /// Find the most recent user prompt on the active branch, skipping `!` bash
+    /// command messages. Used to regenerate the response that followed it.
+    pub fn last_user_message_in_lineage(&self) -> Option<&Message> {
+        let mut current = self.active_message_id.clone();
+        while let Some(id) = current {
+            let ChatItemData::Message(message) = &self.get_by_id(&id)?.data else {
+                return None;
+            };
+            if let MessageData::User { content } = &message.data
+                && content
+                    .iter()
+                    .any(|block| !matches!(block, UserContent::CommandExecution { .. }))
+            {
+                return (!self.is_before_compaction(message.id())).then_some(message);
+            }
+            current = message.parent_message_id().map(ToString::to_string);
+        }
+        None
+    }
+
+    /// Messages from earlier attempts at the last prompt: sibling branches
+    /// that start with the same user message, as left behind by `/retry`.
+    pub fn previous_attempt_ids(&self) -> HashSet<String> {
+        let mut attempts = HashSet::new();
+        let Some(prompt) = self.last_user_message_in_lineage() else {
+            return attempts;
+        };
+        let prompt_text = prompt.content_string();
+
+        for item in self.items.values() {
+            let ChatItemData::Message(message) = &item.data else {
+                continue;
+            };
+            let Some(parent_id) = message.parent_message_id() else {
+                continue;
+            };
+            let is_retried_prompt = message.id() != prompt.id()
+                && Some(parent_id) == prompt.parent_message_id()
+                && matches!(message.data, MessageData::User { .. })
+                && message.content_string() == prompt_text;
+            // Items are stored in arrival order, so parents precede replies.
+            if is_retried_prompt || attempts.contains(parent_id) {
+                attempts.insert(message.id().to_string());
+            }
+        }
+        attempts
+    }
+
     /// Get user messages that are in the active branch lineage
+    /// User and assistant messages on the active branch since the last compaction.
+    pub fn conversation_messages_in_lineage(&self) -> Vec<&Message> {
+        let lineage = self.build_lineage_set();
+
+        self.items
+            .values()
+            .filter_map(|item| match &item.data {
+                ChatItemData::Message(message)
+                    if !matches!(&message.data, MessageData::Tool { .. })
+                        && lineage.contains(message.id())
+                        && !self.is_before_compaction(message.id()) =>
+                {
+                    Some(message)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn user_messages_in_lineage(&self) -> Vec<(String, String)> {
         let lineage = self.build_lineage_set();
 
@@ -493,6 +593,68 @@ mod tests {
         assert_eq!(ids, vec!["msg1", "msg2", "msg3"]);
     }
 
+    #[test]
+    fn test_last_user_message_in_lineage_skips_assistant_turns() {
+        let mut store = ChatStore::new();
+        store.add_message(user_message("msg1", None, "First"));
+        store.add_message(user_message("msg2", Some("msg1"), "Second"));
+        store.add_message(Message {
+            data: MessageData::Assistant {
+                content: vec![AssistantContent::Text {
+                    text: "answer".to_string(),
+                }],
+            },
+            timestamp: 1000,
+            id: "msg3".to_string(),
+            parent_message_id: Some("msg2".to_string()),
+        });
+        store.add_message(user_message("other", Some("msg1"), "Other branch"));
+
+        store.set_active_message_id(Some("msg3".to_string()));
+        assert_eq!(
+            store.last_user_message_in_lineage().map(Message::id),
+            Some("msg2")
+        );
+
+        store.set_active_message_id(None);
+        assert!(store.last_user_message_in_lineage().is_none());
+    }
+
+    #[test]
+    fn test_previous_attempt_ids_collects_retried_branches() {
+        let assistant = |id: &str, parent: &str| Message {
+            data: MessageData::Assistant {
+                content: vec![AssistantContent::Text {
+                    text: format!("answer {id}"),
+                }],
+            },
+            timestamp: 1000,
+            id: id.to_string(),
+            parent_message_id: Some(parent.to_string()),
+        };
+        let mut store = ChatStore::new();
+        store.add_message(user_message("root", None, "Start"));
+        store.add_message(assistant("root_reply", "root"));
+        store.add_message(user_message("first", Some("root_reply"), "Write a haiku"));
+        store.add_message(assistant("first_reply", "first"));
+        store.add_message(user_message(
+            "edited",
+            Some("root_reply"),
+            "Write a limerick",
+        ));
+        store.add_message(user_message("retry", Some("root_reply"), "Write a haiku"));
+        store.add_message(assistant("retry_reply", "retry"));
+
+        store.set_active_message_id(Some("retry_reply".to_string()));
+        assert_eq!(
+            store.previous_attempt_ids(),
+            HashSet::from(["first".to_string(), "first_reply".to_string()])
+        );
+
+        store.set_active_message_id(Some("root_reply".to_string()));
+        assert!(store.previous_attempt_ids().is_empty());
+    }
+
     #[test]
     fn test_compacted_head_for_summary_round_trip() {
         let mut store = ChatStore::new();
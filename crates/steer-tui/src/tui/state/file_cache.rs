@@ -26,6 +26,30 @@ impl FileCache {
         *cache = files;
     }
 
+    /// Append file paths to the cache
+    pub async fn extend(&self, files: Vec<String>) {
+        let mut cache = self.files.write().await;
+        cache.extend(files);
+    }
+
+    /// Add a path unless it is already cached
+    pub async fn insert(&self, path: String) {
+        let mut cache = self.files.write().await;
+        if !cache.contains(&path) {
+            cache.push(path);
+        }
+    }
+
+    /// Remove a path; removing a directory (`dir/`) also drops everything under it
+    pub async fn remove(&self, path: &str) {
+        let mut cache = self.files.write().await;
+        if path.ends_with('/') {
+            cache.retain(|file| !file.starts_with(path));
+        } else {
+            cache.retain(|file| file != path);
+        }
+    }
+
     /// Clear the cache
     pub async fn clear(&self) {
         let mut cache = self.files.write().await;
@@ -44,6 +68,12 @@ impl FileCache {
         cache.len()
     }
 
+    /// Check whether a path is in the cache
+    pub async fn contains(&self, path: &str) -> bool {
+        let cache = self.files.read().await;
+        cache.iter().any(|file| file == path)
+    }
+
     /// Search files with fuzzy matching
     pub async fn fuzzy_search(&self, query: &str, max_results: Option<usize>) -> Vec<String> {
         if query.is_empty() {
@@ -121,4 +151,39 @@ mod tests {
         let results = cache.fuzzy_search("", Some(2)).await;
         assert_eq!(results.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_extend_appends_streamed_chunks() {
+        let cache = FileCache::new("session-3".to_string());
+        cache.extend(vec!["src/main.rs".to_string()]).await;
+        cache
+            .extend(vec!["src/lib.rs".to_string(), "README.md".to_string()])
+            .await;
+
+        assert_eq!(cache.len().await, 3);
+        let results = cache.fuzzy_search("lib", None).await;
+        assert!(results.iter().any(|path| path == "src/lib.rs"));
+    }
+
+    #[tokio::test]
+    async fn test_insert_and_remove_apply_incremental_changes() {
+        let cache = FileCache::new("session-4".to_string());
+        cache
+            .update(vec![
+                "src/".to_string(),
+                "src/main.rs".to_string(),
+                "README.md".to_string(),
+            ])
+            .await;
+
+        cache.insert("src/new.rs".to_string()).await;
+        cache.insert("src/new.rs".to_string()).await;
+        assert_eq!(cache.len().await, 4);
+
+        cache.remove("README.md").await;
+        assert!(!cache.contains("README.md").await);
+
+        cache.remove("src/").await;
+        assert!(cache.is_empty().await);
+    }
 }
@@ -49,10 +49,18 @@ impl LlmUsageSnapshot {
     }
 }
 
+/// Server-side estimate of the conversation size sent with the next model call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextEstimate {
+    pub used: u32,
+    pub limit: Option<u32>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct LlmUsageState {
     latest: Option<LlmUsageSnapshot>,
     by_op: HashMap<OpId, LlmUsageSnapshot>,
+    context_estimate: Option<ContextEstimate>,
 }
 
 impl LlmUsageState {
@@ -67,6 +75,38 @@ impl LlmUsageState {
         let snapshot = LlmUsageSnapshot::new(op_id, model, usage, context_window, kind);
         self.by_op.insert(snapshot.op_id, snapshot.clone());
         self.latest = Some(snapshot);
+        self.context_estimate = None;
+    }
+
+    pub fn update_context_estimate(&mut self, used: u32, limit: Option<u32>) {
+        self.context_estimate = Some(ContextEstimate { used, limit });
+    }
+
+    pub fn context_estimate(&self) -> Option<ContextEstimate> {
+        self.context_estimate
+    }
+
+    /// Percentage of the context window still free, preferring the pending
+    /// estimate over the last reported usage.
+    pub fn context_remaining_percent(&self) -> Option<f64> {
+        let utilization_ratio = match self.context_estimate {
+            Some(estimate) => {
+                let limit = estimate.limit.or_else(|| {
+                    self.latest
+                        .as_ref()
+                        .and_then(|usage| usage.max_context_tokens)
+                });
+                limit
+                    .filter(|limit| *limit > 0)
+                    .map(|limit| f64::from(estimate.used) / f64::from(limit))
+            }
+            None => self
+                .latest
+                .as_ref()
+                .and_then(|usage| usage.context_window.as_ref())
+                .and_then(|context_window| context_window.utilization_ratio),
+        }?;
+        Some((1.0 - utilization_ratio.clamp(0.0, 1.0)) * 100.0)
     }
 
     pub fn latest(&self) -> Option<&LlmUsageSnapshot> {
@@ -80,6 +120,7 @@ impl LlmUsageState {
     pub fn clear(&mut self) {
         self.latest = None;
         self.by_op.clear();
+        self.context_estimate = None;
     }
 }
 
@@ -143,4 +184,41 @@ mod tests {
         assert_eq!(latest.utilization_ratio, None);
         assert!(!latest.context_estimated);
     }
+
+    #[test]
+    fn context_estimate_drives_remaining_percent_until_usage_arrives() {
+        let mut usage_state = LlmUsageState::default();
+        let model = builtin::claude_sonnet_4_5();
+
+        usage_state.update(
+            OpId::new(),
+            model.clone(),
+            TokenUsage::from_input_output(100, 20),
+            Some(ContextWindowUsage {
+                max_context_tokens: Some(1_000),
+                remaining_tokens: Some(880),
+                utilization_ratio: Some(0.12),
+                estimated: false,
+            }),
+            UsageUpdateKind::Final,
+        );
+
+        usage_state.update_context_estimate(250, None);
+        assert_eq!(usage_state.context_remaining_percent(), Some(75.0));
+
+        usage_state.update(
+            OpId::new(),
+            model,
+            TokenUsage::from_input_output(300, 20),
+            Some(ContextWindowUsage {
+                max_context_tokens: Some(1_000),
+                remaining_tokens: Some(680),
+                utilization_ratio: Some(0.5),
+                estimated: false,
+            }),
+            UsageUpdateKind::Final,
+        );
+        assert_eq!(usage_state.context_estimate(), None);
+        assert_eq!(usage_state.context_remaining_percent(), Some(50.0));
+    }
 }
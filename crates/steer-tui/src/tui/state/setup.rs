@@ -9,6 +9,8 @@ pub struct SetupState {
     pub selected_provider: Option<ProviderId>,
     pub auth_flow_id: Option<String>,
     pub auth_progress: Option<AuthProgress>,
+    /// Authorization URL of the current OAuth flow, kept while later progress states replace it.
+    pub auth_url: Option<String>,
     pub auth_input: String,
     pub error_message: Option<String>,
     pub provider_cursor: usize,
@@ -76,6 +78,7 @@ impl SetupState {
             selected_provider: None,
             auth_flow_id: None,
             auth_progress: None,
+            auth_url: None,
             auth_input: String::new(),
             error_message: None,
             provider_cursor: 0,
@@ -18,6 +18,8 @@ pub struct TerminalState {
     pub(crate) keyboard_flags_pushed: AtomicBool,
     pub(crate) mouse_capture: AtomicBool,
     pub(crate) focus_change: AtomicBool,
+    pub(crate) title_pushed: AtomicBool,
+    pub(crate) progress_shown: AtomicBool,
 }
 
 impl Default for TerminalState {
@@ -35,6 +37,8 @@ impl TerminalState {
             keyboard_flags_pushed: AtomicBool::new(false),
             mouse_capture: AtomicBool::new(false),
             focus_change: AtomicBool::new(false),
+            title_pushed: AtomicBool::new(false),
+            progress_shown: AtomicBool::new(false),
         }
     }
 }
@@ -80,6 +84,12 @@ pub fn setup<W: Write>(w: &mut W) -> io::Result<()> {
 /// Cleanup helper that writes escape sequences to the provided writer.
 /// Uses global flags to avoid double-disabling.
 pub fn cleanup_with_writer<W: Write>(writer: &mut W) {
+    if TERMINAL_STATE.progress_shown.swap(false, Ordering::Relaxed) {
+        let _ = write!(writer, "{}", crate::tui::title::CLEAR_PROGRESS);
+    }
+    if TERMINAL_STATE.title_pushed.swap(false, Ordering::Relaxed) {
+        let _ = write!(writer, "{}", crate::tui::title::POP_TITLE);
+    }
     if TERMINAL_STATE
         .keyboard_flags_pushed
         .swap(false, Ordering::Relaxed)
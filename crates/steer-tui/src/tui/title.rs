@@ -0,0 +1,157 @@
+//! Terminal title and taskbar progress.
+//!
+//! The title (OSC 2) reads "steer – <session> – <activity>" so instances in
+//! different tmux windows or tabs can be told apart. Progress uses the OSC 9;4
+//! sequence understood by Windows Terminal, WezTerm and ConEmu. The original
+//! title is saved on the terminal's title stack and restored by
+//! [`crate::tui::terminal::cleanup`].
+
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::atomic::Ordering;
+
+use crate::tui::terminal::TERMINAL_STATE;
+
+/// Save the current window title on the terminal's title stack (XTWINOPS 22).
+pub(crate) const PUSH_TITLE: &str = "\x1b[22;0t";
+/// Restore the title saved by [`PUSH_TITLE`] (XTWINOPS 23).
+pub(crate) const POP_TITLE: &str = "\x1b[23;0t";
+pub(crate) const CLEAR_PROGRESS: &str = "\x1b]9;4;0\x07";
+
+/// What the session is doing, as shown in the title.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Activity {
+    Idle,
+    Thinking,
+    Tool(String),
+    AwaitingApproval,
+}
+
+impl fmt::Display for Activity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Activity::Idle => f.write_str("idle"),
+            Activity::Thinking => f.write_str("thinking"),
+            Activity::Tool(name) => write!(f, "tool:{name}"),
+            Activity::AwaitingApproval => f.write_str("awaiting approval"),
+        }
+    }
+}
+
+impl Activity {
+    /// The OSC 9;4 state: 0 clears progress, 3 is indeterminate and 4 is
+    /// paused, which terminals draw in a warning colour.
+    fn progress_state(&self) -> u8 {
+        match self {
+            Activity::Idle => 0,
+            Activity::Thinking | Activity::Tool(_) => 3,
+            Activity::AwaitingApproval => 4,
+        }
+    }
+}
+
+/// Writes title and progress sequences when they change.
+#[derive(Debug)]
+pub(crate) struct TerminalStatus {
+    title_enabled: bool,
+    progress_enabled: bool,
+    last_title: Option<String>,
+    last_progress: u8,
+}
+
+impl TerminalStatus {
+    pub(crate) fn new(title_enabled: bool, progress_enabled: bool) -> Self {
+        Self {
+            title_enabled,
+            progress_enabled,
+            last_title: None,
+            last_progress: 0,
+        }
+    }
+
+    pub(crate) fn update<W: Write>(
+        &mut self,
+        w: &mut W,
+        session: &str,
+        activity: &Activity,
+    ) -> io::Result<()> {
+        let mut wrote = false;
+        if self.title_enabled {
+            let title = title_text(session, activity);
+            if self.last_title.as_deref() != Some(title.as_str()) {
+                if !TERMINAL_STATE.title_pushed.swap(true, Ordering::Relaxed) {
+                    w.write_all(PUSH_TITLE.as_bytes())?;
+                }
+                write!(w, "\x1b]2;{title}\x07")?;
+                self.last_title = Some(title);
+                wrote = true;
+            }
+        }
+        if self.progress_enabled {
+            let progress = activity.progress_state();
+            if progress != self.last_progress {
+                write!(w, "\x1b]9;4;{progress}\x07")?;
+                TERMINAL_STATE
+                    .progress_shown
+                    .store(progress != 0, Ordering::Relaxed);
+                self.last_progress = progress;
+                wrote = true;
+            }
+        }
+        if wrote {
+            w.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// The window title, with control characters in the session label dropped so
+/// they can't end the escape sequence early.
+fn title_text(session: &str, activity: &Activity) -> String {
+    let session: String = session.chars().filter(|c| !c.is_control()).collect();
+    format!("steer – {session} – {activity}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn title_names_session_and_activity() {
+        assert_eq!(
+            title_text("Fix login", &Activity::Tool("bash".to_string())),
+            "steer – Fix login – tool:bash"
+        );
+        assert_eq!(
+            title_text("a\x07b", &Activity::AwaitingApproval),
+            "steer – ab – awaiting approval"
+        );
+    }
+
+    #[test]
+    fn update_writes_only_changes() {
+        let mut status = TerminalStatus::new(true, true);
+        let mut out = Vec::new();
+
+        status.update(&mut out, "s", &Activity::Thinking).unwrap();
+        let first = String::from_utf8(std::mem::take(&mut out)).unwrap();
+        assert!(first.contains("\x1b]2;steer – s – thinking\x07"));
+        assert!(first.contains("\x1b]9;4;3\x07"));
+
+        status.update(&mut out, "s", &Activity::Thinking).unwrap();
+        assert!(out.is_empty());
+
+        status.update(&mut out, "s", &Activity::Idle).unwrap();
+        let idle = String::from_utf8(out).unwrap();
+        assert!(idle.contains("steer – s – idle"));
+        assert!(idle.contains(CLEAR_PROGRESS));
+    }
+
+    #[test]
+    fn disabled_status_writes_nothing() {
+        let mut status = TerminalStatus::new(false, false);
+        let mut out = Vec::new();
+        status.update(&mut out, "s", &Activity::Thinking).unwrap();
+        assert!(out.is_empty());
+    }
+}
@@ -10,7 +10,6 @@ use ratatui::{
     style::Style,
     widgets::{Block, Clear},
 };
-use steer_grpc::client_api::ModelId;
 
 /// Computed layout areas for the UI
 pub struct UiLayout {
@@ -58,18 +57,7 @@ impl UiLayout {
     }
 
     /// Render the status bar
-    pub fn render_status_bar(
-        &self,
-        f: &mut Frame,
-        current_model: &ModelId,
-        current_agent: Option<&str>,
-        context_remaining_percent: Option<f64>,
-        theme: &Theme,
-        update_badge: crate::tui::widgets::status_bar::UpdateBadge,
-    ) {
-        let status_bar = StatusBar::new(current_model, current_agent, theme)
-            .with_context_remaining_percent(context_remaining_percent)
-            .with_update_badge(update_badge);
+    pub fn render_status_bar(&self, f: &mut Frame, status_bar: StatusBar<'_>) {
         f.render_widget(status_bar, self.status);
     }
 }
@@ -45,7 +45,7 @@ pub async fn check_latest(repo_owner: &str, repo_name: &str, current: &str) -> U
 
     let url = format!("https://api.github.com/repos/{repo_owner}/{repo_name}/releases/latest");
 
-    let client = reqwest::Client::builder()
+    let client = steer_grpc::client_api::http_client_builder()
         .user_agent(format!("steer-tui/{current}"))
         .build();
     let client = match client {
@@ -0,0 +1,90 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
+};
+
+use crate::tui::state::BestOfView;
+use crate::tui::state::best_of::candidate_text;
+use crate::tui::theme::{Component, Theme};
+
+/// Candidates of a best-of turn side by side, drawn over the chat.
+pub struct BestOfPanel<'a> {
+    view: &'a BestOfView,
+    theme: &'a Theme,
+}
+
+impl<'a> BestOfPanel<'a> {
+    pub fn new(view: &'a BestOfView, theme: &'a Theme) -> Self {
+        Self { view, theme }
+    }
+}
+
+impl Widget for BestOfPanel<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let hint = if self.view.is_complete() {
+            " [←→] choose  [Enter] pick  [Esc] hide "
+        } else {
+            " drafting… "
+        };
+        let outer_block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(
+                " Best of {} · {} ",
+                self.view.slots().len(),
+                self.view.model
+            ))
+            .title_bottom(Line::from(hint))
+            .style(self.theme.style(Component::InputPanelBorder))
+            .border_style(self.theme.style(Component::InputPanelBorderActive));
+        let inner_area = outer_block.inner(area);
+        outer_block.render(area, buf);
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(self.view.slots().iter().map(|_| Constraint::Fill(1)))
+            .split(inner_area);
+
+        for (position, (slot, column)) in self.view.slots().iter().zip(columns.iter()).enumerate() {
+            let label = position + 1;
+            let (title, body) = match slot {
+                None => (format!(" {label} · drafting… "), String::new()),
+                Some(candidate) => {
+                    if let Some(error) = &candidate.error {
+                        (format!(" {label} · failed "), error.clone())
+                    } else {
+                        let title = candidate.usage.map_or_else(
+                            || format!(" {label} "),
+                            |usage| format!(" {label} · {} tokens ", usage.total_tokens),
+                        );
+                        (title, candidate_text(candidate))
+                    }
+                }
+            };
+
+            let selected = self.view.is_complete() && position == self.view.selected();
+            let (border, title_style) = if selected {
+                (
+                    self.theme.style(Component::InputPanelBorderActive),
+                    self.theme.style(Component::InputPanelLabelActive),
+                )
+            } else {
+                (
+                    self.theme.style(Component::DimText),
+                    self.theme.style(Component::DimText),
+                )
+            };
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .title(Line::styled(title, title_style))
+                .border_style(border);
+            Paragraph::new(body)
+                .wrap(Wrap { trim: false })
+                .block(block)
+                .render(*column, buf);
+        }
+    }
+}
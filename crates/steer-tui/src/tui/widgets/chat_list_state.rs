@@ -7,6 +7,16 @@ pub enum ViewMode {
     Detailed,
 }
 
+/// How preformatted lines wider than the chat, such as code, are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CodeWrap {
+    /// Wrap onto continuation rows that keep the line's indentation
+    #[default]
+    Soft,
+    /// Keep lines whole and show them from this display column on
+    Scroll { offset: usize },
+}
+
 /// State for the ChatList widget
 #[derive(Debug)]
 pub struct ChatListState {
@@ -24,6 +34,8 @@ pub struct ChatListState {
     pub last_viewport_height: u16,
     /// Track if user has manually scrolled away from bottom
     pub user_scrolled: bool,
+    /// New content arrived below the viewport while the user was scrolled up
+    unseen_output: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -60,6 +72,7 @@ impl ChatListState {
             total_content_height: 0,
             last_viewport_height: 0,
             user_scrolled: false,
+            unseen_output: false,
         }
     }
 
@@ -67,6 +80,22 @@ impl ChatListState {
         // Resolve during render when we know total height
         self.scroll_target = Some(ScrollTarget::Bottom);
         self.user_scrolled = false;
+        self.unseen_output = false;
+    }
+
+    /// Record that content was added below a viewport the user scrolled away from.
+    pub fn mark_unseen_output(&mut self) {
+        self.unseen_output = true;
+    }
+
+    /// Whether to point the user at output added below the viewport.
+    pub fn has_unseen_output(&self) -> bool {
+        self.unseen_output
+    }
+
+    /// Forget unseen output once the bottom of the content is in view.
+    pub fn clear_unseen_output(&mut self) {
+        self.unseen_output = false;
     }
 
     pub fn scroll_up(&mut self, amount: usize) -> bool {
@@ -175,4 +204,20 @@ mod tests {
             "User scroll should be tracked when moving"
         );
     }
+
+    #[test]
+    fn test_unseen_output_cleared_by_scroll_to_bottom() {
+        let mut state = ChatListState::new();
+        state.total_content_height = 100;
+        state.last_viewport_height = 10;
+        state.offset = 40;
+        state.user_scrolled = true;
+        assert!(!state.is_at_bottom());
+
+        state.mark_unseen_output();
+        assert!(state.has_unseen_output());
+
+        state.scroll_to_bottom();
+        assert!(!state.has_unseen_output());
+    }
 }
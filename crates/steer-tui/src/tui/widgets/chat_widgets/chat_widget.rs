@@ -4,8 +4,8 @@
 //! within precise rectangular bounds, preventing buffer overlap issues.
 
 use crate::tui::theme::{Component, Theme};
-use crate::tui::widgets::chat_list_state::ViewMode;
-use crate::tui::widgets::chat_widgets::message_widget::MessageWidget;
+use crate::tui::widgets::chat_list_state::{CodeWrap, ViewMode};
+use crate::tui::widgets::chat_widgets::message_widget::{MessageWidget, TextLayoutCache};
 use ratatui::text::{Line, Span};
 use steer_grpc::client_api::{AssistantContent, Message, MessageData, ToolCall, ToolResult};
 
@@ -33,6 +33,23 @@ pub trait ChatRenderable: Send + Sync {
         let bounded_end = end.min(lines.len()).max(bounded_start);
         &lines[bounded_start..bounded_end]
     }
+
+    /// Give up layout state that a replacement widget for the same row can reuse.
+    fn take_text_layout(&mut self) -> Option<TextLayoutCache> {
+        None
+    }
+
+    /// Adopt layout state handed over by the widget this one replaces.
+    fn restore_text_layout(&mut self, _layout: TextLayoutCache) {}
+
+    /// Choose how code lines wider than the view are laid out.
+    fn set_code_wrap(&mut self, _wrap: CodeWrap) {}
+
+    /// How many columns the widest code line overflowed the view by at the
+    /// last render, or `None` if the widget shows no code.
+    fn code_overflow(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// Height cache for efficient scrolling
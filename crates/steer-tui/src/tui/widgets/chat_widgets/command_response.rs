@@ -1,9 +1,12 @@
-use crate::tui::core_commands::{CommandResponse as CoreCommandResponse, CompactResult};
+use crate::tui::core_commands::{
+    CommandResponse as CoreCommandResponse, CommitDraft, CompactResult, ReviewReport,
+};
 use crate::tui::model::{CommandResponse, TuiCommandResponse};
 use crate::tui::theme::{Component, Theme};
 use crate::tui::widgets::chat_list_state::ViewMode;
 use crate::tui::widgets::chat_widgets::chat_widget::{ChatRenderable, HeightCache};
 use ratatui::text::{Line, Span};
+use steer_grpc::client_api::ReviewSeverity;
 
 pub struct CommandResponseWidget {
     command: String,
@@ -87,6 +90,26 @@ impl ChatRenderable for CommandResponseWidget {
                                 )));
                             }
                         },
+                        CoreCommandResponse::Review(report) => {
+                            lines.push(Line::from(vec![
+                                Span::styled(
+                                    self.command.clone(),
+                                    theme.style(Component::CommandPrompt),
+                                ),
+                                Span::raw(":"),
+                            ]));
+                            lines.extend(review_lines(report, theme, wrap_width));
+                        }
+                        CoreCommandResponse::CommitDraft(draft) => {
+                            lines.push(Line::from(vec![
+                                Span::styled(
+                                    self.command.clone(),
+                                    theme.style(Component::CommandPrompt),
+                                ),
+                                Span::raw(":"),
+                            ]));
+                            lines.extend(commit_draft_lines(draft, theme, wrap_width));
+                        }
                     }
                 }
 
@@ -364,6 +387,112 @@ impl ChatRenderable for CommandResponseWidget {
     }
 }
 
+/// Findings grouped by severity. References are absolute `path:line` so
+/// terminals that detect file links make them clickable.
+fn review_lines(report: &ReviewReport, theme: &Theme, wrap_width: usize) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    if report.findings.is_empty() {
+        let text = if report.files_reviewed == 0 {
+            format!("No {} changes to review.", report.target)
+        } else {
+            format!(
+                "No findings in {} file(s) of {} changes.",
+                report.files_reviewed, report.target
+            )
+        };
+        lines.push(Line::from(vec![
+            Span::styled("✓ ", theme.style(Component::CommandSuccess)),
+            Span::styled(text, theme.style(Component::CommandText)),
+        ]));
+        return lines;
+    }
+
+    lines.push(Line::from(Span::styled(
+        format!(
+            "{} finding(s) in {} file(s) of {} changes",
+            report.findings.len(),
+            report.files_reviewed,
+            report.target
+        ),
+        theme.style(Component::CommandText),
+    )));
+
+    let detail_indent = "      ";
+    let detail_width = wrap_width.saturating_sub(detail_indent.len()).max(1);
+    for (severity, findings) in report.by_severity() {
+        let heading_style = match severity {
+            ReviewSeverity::Critical | ReviewSeverity::Major => Component::NoticeError,
+            ReviewSeverity::Minor => Component::NoticeWarn,
+            ReviewSeverity::Nit => Component::NoticeInfo,
+        };
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("{} ({})", severity.label(), findings.len()),
+            theme.style(heading_style),
+        )));
+        for finding in findings {
+            let reference = format!(
+                "{}:{}",
+                report.root.join(&finding.path).display(),
+                finding.line
+            );
+            lines.push(Line::from(vec![
+                Span::styled("  • ", theme.style(heading_style)),
+                Span::styled(reference, theme.style(Component::MarkdownLink)),
+            ]));
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(finding.title.clone(), theme.style(Component::CommandPrompt)),
+            ]));
+            for wrapped in textwrap::wrap(&finding.detail, detail_width) {
+                lines.push(Line::from(Span::styled(
+                    format!("{detail_indent}{wrapped}"),
+                    theme.style(Component::CommandText),
+                )));
+            }
+        }
+    }
+    lines
+}
+
+fn commit_draft_lines(draft: &CommitDraft, theme: &Theme, wrap_width: usize) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let indent = "  ";
+    let width = wrap_width.saturating_sub(indent.len()).max(1);
+    for line in draft.message.lines() {
+        let wrapped = textwrap::wrap(line, width);
+        if wrapped.is_empty() {
+            lines.push(Line::from(""));
+        }
+        for wrapped_line in wrapped {
+            lines.push(Line::from(Span::styled(
+                format!("{indent}{wrapped_line}"),
+                theme.style(Component::CommandText),
+            )));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("Files ({})", draft.files.len()),
+        theme.style(Component::CommandPrompt),
+    )));
+    for file in &draft.files {
+        lines.push(Line::from(vec![
+            Span::raw(indent),
+            Span::styled(
+                draft.root.join(file).display().to_string(),
+                theme.style(Component::MarkdownLink),
+            ),
+        ]));
+    }
+    lines.push(Line::from(Span::styled(
+        "Press y to commit, any other key to cancel.",
+        theme.style(Component::DimText),
+    )));
+    lines
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tui::model::TuiCommandResponse;
@@ -393,4 +522,49 @@ mod tests {
         let height = widget.lines(80, ViewMode::Compact, &theme).len();
         assert_eq!(height, 4); // Command line + 3 response lines
     }
+
+    #[test]
+    fn test_review_response_groups_by_severity() {
+        use steer_grpc::client_api::{ReviewFinding, ReviewTarget};
+
+        let finding = |severity, path: &str| ReviewFinding {
+            severity,
+            path: path.to_string(),
+            line: 7,
+            end_line: None,
+            title: "Title".to_string(),
+            detail: "Detail".to_string(),
+        };
+        let report = ReviewReport {
+            target: ReviewTarget::Staged,
+            root: std::path::PathBuf::from("/repo"),
+            findings: vec![
+                finding(ReviewSeverity::Critical, "src/a.rs"),
+                finding(ReviewSeverity::Nit, "src/b.rs"),
+            ],
+            files_reviewed: 2,
+            chunks: 1,
+        };
+        let theme = Theme::default();
+        let mut widget = CommandResponseWidget::new(
+            "/review staged".to_string(),
+            CoreCommandResponse::Review(report).into(),
+        );
+
+        let text: Vec<String> = widget
+            .lines(80, ViewMode::Compact, &theme)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect();
+
+        let critical = text.iter().position(|line| line == "Critical (1)").unwrap();
+        let nit = text.iter().position(|line| line == "Nit (1)").unwrap();
+        assert!(critical < nit);
+        assert_eq!(text[critical + 1], "  • /repo/src/a.rs:7");
+    }
 }
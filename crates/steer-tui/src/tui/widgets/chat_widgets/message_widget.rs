@@ -1,13 +1,44 @@
 use ratatui::text::{Line, Span};
-use steer_grpc::client_api::{AssistantContent, Message, MessageData, UserContent};
+use std::borrow::Cow;
+use steer_grpc::client_api::{AssistantContent, Message, MessageData, ThoughtContent, UserContent};
 
 use crate::tui::theme::{Component, Theme};
-use crate::tui::widgets::formatters::helpers::style_wrap_with_indent;
-use crate::tui::widgets::{ChatRenderable, ViewMode, markdown};
+use crate::tui::widgets::formatters::helpers::{layout_preformatted, style_wrap_with_indent};
+use crate::tui::widgets::{ChatRenderable, CodeWrap, ViewMode, markdown};
+
+/// Shown in place of reasoning that is hidden by preference or has no readable text.
+pub const HIDDEN_REASONING_PLACEHOLDER: &str = "thinking…";
+
+/// Laid-out chunks of assistant text, kept across re-renders of a message.
+///
+/// Streaming only appends, so every chunk before the tail (see
+/// [`markdown::chunk_boundaries`]) keeps its wrapped lines and only the tail
+/// is parsed and wrapped again on each delta.
+#[derive(Debug, Default, Clone)]
+pub struct TextLayoutCache {
+    width: u16,
+    theme_name: String,
+    code_wrap: CodeWrap,
+    /// Chunks rendered so far, per assistant text block.
+    blocks: Vec<Vec<LaidOutChunk>>,
+}
+
+#[derive(Debug, Clone)]
+struct LaidOutChunk {
+    source: String,
+    lines: Vec<Line<'static>>,
+    code_width: Option<usize>,
+}
 
 pub struct MessageWidget {
     message: Message,
     is_edited: bool,
+    show_reasoning: bool,
+    is_streaming: bool,
+    text_layout: TextLayoutCache,
+    code_wrap: CodeWrap,
+    /// Columns the widest code line overflowed the view by at the last render.
+    code_overflow: Option<usize>,
     rendered_lines: Option<Vec<Line<'static>>>,
     last_width: u16,
     last_mode: ViewMode,
@@ -20,6 +51,11 @@ impl MessageWidget {
         Self {
             message,
             is_edited: false,
+            show_reasoning: true,
+            is_streaming: false,
+            text_layout: TextLayoutCache::default(),
+            code_wrap: CodeWrap::default(),
+            code_overflow: None,
             rendered_lines: None,
             last_width: 0,
             last_mode: ViewMode::Compact,
@@ -33,6 +69,28 @@ impl MessageWidget {
         self
     }
 
+    pub fn with_show_reasoning(mut self, show_reasoning: bool) -> Self {
+        self.show_reasoning = show_reasoning;
+        self
+    }
+
+    /// Mark the message as still streaming, so half-written markdown at its
+    /// end is patched up instead of rendered literally.
+    pub fn with_streaming(mut self, is_streaming: bool) -> Self {
+        self.is_streaming = is_streaming;
+        self
+    }
+
+    /// Reasoning text to render, or `None` when only the placeholder should be shown.
+    fn visible_reasoning(thought: &ThoughtContent, show_reasoning: bool) -> Option<&str> {
+        let text = match thought {
+            ThoughtContent::Simple { text } | ThoughtContent::Signed { text, .. } => text,
+            // Encrypted reasoning carries no readable content.
+            ThoughtContent::Redacted { .. } => return None,
+        };
+        (show_reasoning && !text.trim().is_empty()).then_some(text.as_str())
+    }
+
     fn content_hash(message: &Message, is_edited: bool) -> u64 {
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
@@ -47,6 +105,10 @@ impl MessageWidget {
                         UserContent::Image { image } => {
                             image.mime_type.hash(&mut hasher);
                         }
+                        UserContent::Attachment { attachment } => {
+                            attachment.path.hash(&mut hasher);
+                            attachment.bytes.hash(&mut hasher);
+                        }
                         UserContent::CommandExecution {
                             command,
                             stdout,
@@ -103,6 +165,97 @@ impl MessageWidget {
         let markdown_styles = markdown::MarkdownStyles::from_theme(theme);
         markdown::from_str_with_width(text, &markdown_styles, theme, Some(max_width as u16))
     }
+
+    /// Render markdown wrapped to `max_width`, laying out code block lines
+    /// per `code_wrap`. Also returns the width of the widest code line.
+    fn wrapped_markdown(
+        text: &str,
+        theme: &Theme,
+        max_width: usize,
+        code_wrap: CodeWrap,
+    ) -> (Vec<Line<'static>>, Option<usize>) {
+        let marker_style = theme.style(Component::DimText);
+        let mut lines = Vec::new();
+        let mut code_width = None;
+        for marked_line in Self::render_as_markdown(text, theme, max_width).lines {
+            if marked_line.is_code {
+                code_width = code_width.max(Some(marked_line.line.width()));
+                lines.extend(layout_preformatted(
+                    marked_line.line,
+                    max_width,
+                    code_wrap,
+                    marker_style,
+                ));
+            } else if marked_line.no_wrap {
+                // Tables are already fitted to the width
+                lines.push(marked_line.line);
+            } else {
+                lines.extend(style_wrap_with_indent(
+                    marked_line.line,
+                    max_width as u16,
+                    marked_line.indent_level,
+                ));
+            }
+        }
+        (lines, code_width)
+    }
+
+    /// Lay out one assistant text block chunk by chunk, reusing cached chunks
+    /// whose source hasn't changed and re-rendering only from the first
+    /// difference onwards. Returns the width of the widest code line.
+    fn layout_text_block(
+        chunks: &mut Vec<LaidOutChunk>,
+        text: &str,
+        is_streaming: bool,
+        theme: &Theme,
+        max_width: usize,
+        code_wrap: CodeWrap,
+        lines: &mut Vec<Line<'static>>,
+    ) -> Option<usize> {
+        let mut code_width = None;
+        let block_start = lines.len();
+        let boundaries = markdown::chunk_boundaries(text);
+        let mut start = 0;
+        for (idx, &end) in boundaries.iter().enumerate() {
+            let source = &text[start..end];
+            if chunks.get(idx).is_none_or(|chunk| chunk.source != source) {
+                chunks.truncate(idx);
+                let (chunk_lines, chunk_code_width) =
+                    Self::wrapped_markdown(source, theme, max_width, code_wrap);
+                chunks.push(LaidOutChunk {
+                    source: source.to_string(),
+                    lines: chunk_lines,
+                    code_width: chunk_code_width,
+                });
+            }
+            code_width = code_width.max(chunks[idx].code_width);
+            Self::append_chunk(lines, block_start, &chunks[idx].lines);
+            start = end;
+        }
+        chunks.truncate(boundaries.len());
+
+        let tail = if is_streaming {
+            markdown::close_partial_markdown(&text[start..])
+        } else {
+            Cow::Borrowed(&text[start..])
+        };
+        let (tail_lines, tail_code_width) =
+            Self::wrapped_markdown(&tail, theme, max_width, code_wrap);
+        Self::append_chunk(lines, block_start, &tail_lines);
+        code_width.max(tail_code_width)
+    }
+
+    /// Append a chunk's lines, separated from the previous chunk of the same
+    /// block by one blank line as a single render of both would be.
+    fn append_chunk(lines: &mut Vec<Line<'static>>, block_start: usize, chunk: &[Line<'static>]) {
+        if chunk.is_empty() {
+            return;
+        }
+        if lines.len() > block_start && lines.last().is_some_and(|line| line.width() > 0) {
+            lines.push(Line::default());
+        }
+        lines.extend_from_slice(chunk);
+    }
 }
 
 impl ChatRenderable for MessageWidget {
@@ -120,29 +273,17 @@ impl ChatRenderable for MessageWidget {
 
         let max_width = width.saturating_sub(4) as usize; // Account for gutters
         let mut lines = Vec::new();
+        let mut code_width = None;
 
         match &self.message.data {
             MessageData::User { content, .. } => {
                 for user_content in content {
                     match user_content {
                         UserContent::Text { text } => {
-                            let marked_text = Self::render_as_markdown(text, theme, max_width);
-                            for marked_line in marked_text.lines {
-                                if marked_line.no_wrap {
-                                    // Don't wrap code block lines
-                                    lines.push(marked_line.line);
-                                } else {
-                                    // Wrap normal lines
-                                    let wrapped = style_wrap_with_indent(
-                                        marked_line.line,
-                                        max_width as u16,
-                                        marked_line.indent_level,
-                                    );
-                                    for line in wrapped {
-                                        lines.push(line);
-                                    }
-                                }
-                            }
+                            let (text_lines, text_code_width) =
+                                Self::wrapped_markdown(text, theme, max_width, self.code_wrap);
+                            lines.extend(text_lines);
+                            code_width = code_width.max(text_code_width);
                         }
                         UserContent::Image { image } => {
                             let style = theme.style(Component::DimText);
@@ -151,6 +292,13 @@ impl ChatRenderable for MessageWidget {
                                 style,
                             )));
                         }
+                        UserContent::Attachment { attachment } => {
+                            let style = theme.style(Component::DimText);
+                            lines.push(Line::from(Span::styled(
+                                format!("[File: {}]", attachment.summary()),
+                                style,
+                            )));
+                        }
                         UserContent::CommandExecution {
                             command,
                             stdout,
@@ -235,6 +383,19 @@ impl ChatRenderable for MessageWidget {
                 }
             }
             MessageData::Assistant { content, .. } => {
+                if self.text_layout.width != max_width as u16
+                    || self.text_layout.theme_name != theme.name
+                    || self.text_layout.code_wrap != self.code_wrap
+                {
+                    self.text_layout = TextLayoutCache {
+                        width: max_width as u16,
+                        theme_name: theme.name.clone(),
+                        code_wrap: self.code_wrap,
+                        blocks: Vec::new(),
+                    };
+                }
+
+                let mut text_block = 0;
                 for block in content {
                     match block {
                         AssistantContent::Text { text } => {
@@ -242,23 +403,22 @@ impl ChatRenderable for MessageWidget {
                                 continue;
                             }
 
-                            let marked_text = Self::render_as_markdown(text, theme, max_width);
-                            for marked_line in marked_text.lines {
-                                if marked_line.no_wrap {
-                                    // Don't wrap code block lines
-                                    lines.push(marked_line.line);
-                                } else {
-                                    // Wrap normal lines
-                                    let wrapped = style_wrap_with_indent(
-                                        marked_line.line,
-                                        max_width as u16,
-                                        marked_line.indent_level,
-                                    );
-                                    for line in wrapped {
-                                        lines.push(line);
-                                    }
-                                }
+                            if self.text_layout.blocks.len() <= text_block {
+                                self.text_layout
+                                    .blocks
+                                    .resize_with(text_block + 1, Vec::new);
                             }
+                            let block_code_width = Self::layout_text_block(
+                                &mut self.text_layout.blocks[text_block],
+                                text,
+                                self.is_streaming,
+                                theme,
+                                max_width,
+                                self.code_wrap,
+                                &mut lines,
+                            );
+                            code_width = code_width.max(block_code_width);
+                            text_block += 1;
                         }
                         AssistantContent::Image { image } => {
                             let style = theme.style(Component::DimText);
@@ -271,19 +431,28 @@ impl ChatRenderable for MessageWidget {
                             // Tool calls are rendered separately
                         }
                         AssistantContent::Thought { thought } => {
-                            let thought_text = thought.display_text();
                             let thought_style = theme.style(Component::ThoughtText);
+                            let Some(thought_text) =
+                                Self::visible_reasoning(thought, self.show_reasoning)
+                            else {
+                                lines.push(Line::from(Span::styled(
+                                    HIDDEN_REASONING_PLACEHOLDER,
+                                    thought_style,
+                                )));
+                                continue;
+                            };
 
                             // Parse markdown for the thought
                             let markdown_styles = markdown::MarkdownStyles::from_theme(theme);
                             let markdown_text = markdown::from_str_with_width(
-                                &thought_text,
+                                thought_text,
                                 &markdown_styles,
                                 theme,
                                 Some(max_width as u16),
                             );
 
                             // Process each line with thought styling
+                            let marker_style = theme.style(Component::DimText);
                             for marked_line in markdown_text.lines {
                                 let mut styled_spans = Vec::new();
 
@@ -297,7 +466,15 @@ impl ChatRenderable for MessageWidget {
 
                                 let thought_line = Line::from(styled_spans);
 
-                                if marked_line.no_wrap {
+                                if marked_line.is_code {
+                                    code_width = code_width.max(Some(thought_line.width()));
+                                    lines.extend(layout_preformatted(
+                                        thought_line,
+                                        max_width,
+                                        self.code_wrap,
+                                        marker_style,
+                                    ));
+                                } else if marked_line.no_wrap {
                                     lines.push(thought_line);
                                 } else {
                                     let wrapped = style_wrap_with_indent(
@@ -319,6 +496,7 @@ impl ChatRenderable for MessageWidget {
             }
         }
 
+        self.code_overflow = code_width.map(|code_width| code_width.saturating_sub(max_width));
         self.rendered_lines = Some(lines);
         self.last_width = width;
         self.last_mode = mode;
@@ -326,15 +504,37 @@ impl ChatRenderable for MessageWidget {
         self.last_content_hash = content_hash;
         self.rendered_lines.as_deref().unwrap_or(&[])
     }
+
+    fn take_text_layout(&mut self) -> Option<TextLayoutCache> {
+        Some(std::mem::take(&mut self.text_layout))
+    }
+
+    fn restore_text_layout(&mut self, layout: TextLayoutCache) {
+        self.text_layout = layout;
+    }
+
+    fn set_code_wrap(&mut self, wrap: CodeWrap) {
+        if self.code_wrap != wrap {
+            self.code_wrap = wrap;
+            self.rendered_lines = None;
+        }
+    }
+
+    fn code_overflow(&self) -> Option<usize> {
+        self.code_overflow
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::MessageWidget;
+    use super::{HIDDEN_REASONING_PLACEHOLDER, MessageWidget};
     use crate::tui::theme::Theme;
     use crate::tui::widgets::ChatRenderable;
-    use crate::tui::widgets::ViewMode;
-    use steer_grpc::client_api::{AssistantContent, Message, MessageData, UserContent};
+    use crate::tui::widgets::{CodeWrap, ViewMode};
+    use steer_grpc::client_api::{
+        AssistantContent, Message, MessageData, ThoughtContent, UserContent,
+    };
+    use unicode_width::UnicodeWidthStr;
 
     #[test]
     fn test_message_widget_user_text() {
@@ -421,6 +621,45 @@ mod tests {
         assert_eq!(height, 1); // Single line message
     }
 
+    #[test]
+    fn test_reasoning_is_replaced_by_placeholder_when_hidden_or_encrypted() {
+        let theme = Theme::default();
+        let thought_msg = |thought| Message {
+            data: MessageData::Assistant {
+                content: vec![AssistantContent::Thought { thought }],
+            },
+            timestamp: 0,
+            id: "thought-id".to_string(),
+            parent_message_id: None,
+        };
+        let rendered = |widget: &mut MessageWidget| {
+            widget
+                .lines(40, ViewMode::Compact, &theme)
+                .iter()
+                .map(|line| {
+                    line.spans
+                        .iter()
+                        .map(|span| span.content.as_ref())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+        };
+        let simple = ThoughtContent::Simple {
+            text: "weighing options".to_string(),
+        };
+
+        let mut shown = MessageWidget::new(thought_msg(simple.clone()));
+        assert_eq!(rendered(&mut shown), vec!["weighing options"]);
+
+        let mut hidden = MessageWidget::new(thought_msg(simple)).with_show_reasoning(false);
+        assert_eq!(rendered(&mut hidden), vec![HIDDEN_REASONING_PLACEHOLDER]);
+
+        let mut encrypted = MessageWidget::new(thought_msg(ThoughtContent::Redacted {
+            data: "opaque".to_string(),
+        }));
+        assert_eq!(rendered(&mut encrypted), vec![HIDDEN_REASONING_PLACEHOLDER]);
+    }
+
     #[test]
     fn test_message_widget_command_execution() {
         let theme = Theme::default();
@@ -547,4 +786,102 @@ mod tests {
             );
         }
     }
+
+    fn assistant_message(text: &str) -> Message {
+        Message {
+            data: MessageData::Assistant {
+                content: vec![AssistantContent::Text {
+                    text: text.to_string(),
+                }],
+            },
+            timestamp: 0,
+            id: "streamed".to_string(),
+            parent_message_id: None,
+        }
+    }
+
+    fn line_texts(lines: &[ratatui::text::Line<'static>]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_streamed_text_settles_to_full_render() {
+        let theme = Theme::default();
+        let text = "# Plan\n\nFirst we **parse** the input.\n\n```rust\nfn main() {\n\n    run();\n}\n```\n\n- one\n- two\n\n| a | b |\n|---|---|\n| 1 | 2 |\n\nDone.\n";
+
+        let mut widget = MessageWidget::new(assistant_message("")).with_streaming(true);
+        let mut end = 0;
+        while end < text.len() {
+            end = (end + 7).min(text.len());
+            while !text.is_char_boundary(end) {
+                end += 1;
+            }
+            let layout = widget.take_text_layout().unwrap_or_default();
+            widget = MessageWidget::new(assistant_message(&text[..end])).with_streaming(true);
+            widget.restore_text_layout(layout);
+            widget.lines(60, ViewMode::Compact, &theme);
+        }
+
+        let layout = widget.take_text_layout().unwrap_or_default();
+        let mut settled = MessageWidget::new(assistant_message(text));
+        settled.restore_text_layout(layout);
+        let mut fresh = MessageWidget::new(assistant_message(text));
+
+        assert_eq!(
+            settled.lines(60, ViewMode::Compact, &theme),
+            fresh.lines(60, ViewMode::Compact, &theme)
+        );
+    }
+
+    #[test]
+    fn test_streaming_hides_unfinished_fence_and_closes_emphasis() {
+        let theme = Theme::default();
+
+        let mut widget =
+            MessageWidget::new(assistant_message("Here it is:\n``")).with_streaming(true);
+        let rendered = line_texts(widget.lines(40, ViewMode::Compact, &theme));
+        assert_eq!(rendered, vec!["Here it is:".to_string()]);
+
+        let mut widget =
+            MessageWidget::new(assistant_message("This is **important")).with_streaming(true);
+        let rendered = line_texts(widget.lines(40, ViewMode::Compact, &theme));
+        assert_eq!(rendered, vec!["This is important".to_string()]);
+
+        // Finished messages render exactly what was written.
+        let mut widget = MessageWidget::new(assistant_message("This is **important"));
+        let rendered = line_texts(widget.lines(40, ViewMode::Compact, &theme));
+        assert_eq!(rendered, vec!["This is **important".to_string()]);
+    }
+
+    #[test]
+    fn test_wide_code_soft_wraps_until_switched_to_scrolling() {
+        let theme = Theme::default();
+        let code_line = "    let value = compute(alpha, beta, gamma);";
+        let text = format!("```\n{code_line}\n```");
+        let mut widget = MessageWidget::new(assistant_message(&text));
+
+        // 24 columns leave 20 for the text once gutters are taken off.
+        let wrapped = line_texts(widget.lines(24, ViewMode::Compact, &theme));
+        assert!(wrapped.len() > 1, "{wrapped:?}");
+        assert!(wrapped.iter().all(|line| line.width() <= 20), "{wrapped:?}");
+        assert!(wrapped[0].starts_with("    let"));
+        assert!(
+            wrapped[1..].iter().all(|line| line.starts_with("    ↪ ")),
+            "{wrapped:?}"
+        );
+        assert_eq!(widget.code_overflow(), Some(code_line.width() - 20));
+
+        widget.set_code_wrap(CodeWrap::Scroll { offset: 4 });
+        let scrolled = line_texts(widget.lines(24, ViewMode::Compact, &theme));
+        assert_eq!(scrolled.len(), 1);
+        assert_eq!(scrolled[0], code_line[4..24]);
+    }
 }
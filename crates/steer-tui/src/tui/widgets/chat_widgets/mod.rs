@@ -42,6 +42,15 @@ pub fn format_app_command(cmd: &CoreCommandType) -> String {
             }
         }
         CoreCommandType::Compact => "/compact".to_string(),
+        CoreCommandType::Retry { model } => match model {
+            Some(model) => format!("/retry {model}"),
+            None => "/retry".to_string(),
+        },
+        CoreCommandType::Review { target } => match target {
+            Some(target) => format!("/review {target}"),
+            None => "/review".to_string(),
+        },
+        CoreCommandType::Commit => "/commit".to_string(),
     }
 }
 
@@ -54,6 +63,16 @@ pub fn format_command_response(resp: &CommandResponse) -> String {
             CompactResult::Cancelled => "Compact cancelled.".to_string(),
             CompactResult::InsufficientMessages => "Not enough messages to compact.".to_string(),
         },
+        CommandResponse::Review(report) => format!(
+            "Review found {} finding(s) in {} file(s).",
+            report.findings.len(),
+            report.files_reviewed
+        ),
+        CommandResponse::CommitDraft(draft) => format!(
+            "Proposed commit of {} file(s):\n\n{}",
+            draft.files.len(),
+            draft.message
+        ),
     }
 }
 
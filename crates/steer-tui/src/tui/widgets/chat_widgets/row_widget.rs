@@ -6,7 +6,7 @@ use unicode_width::UnicodeWidthStr;
 
 use crate::tui::{
     theme::Theme,
-    widgets::{ChatRenderable, ViewMode},
+    widgets::{ChatRenderable, CodeWrap, ViewMode, chat_widgets::message_widget::TextLayoutCache},
 };
 
 const ACCENT_WIDTH: u16 = 1;
@@ -255,6 +255,23 @@ impl ChatRenderable for RowWidget {
         self.cached_lines = Some(lines);
         self.cached_lines.as_deref().unwrap_or(&[])
     }
+
+    fn take_text_layout(&mut self) -> Option<TextLayoutCache> {
+        self.body.take_text_layout()
+    }
+
+    fn restore_text_layout(&mut self, layout: TextLayoutCache) {
+        self.body.restore_text_layout(layout);
+    }
+
+    fn set_code_wrap(&mut self, wrap: CodeWrap) {
+        self.body.set_code_wrap(wrap);
+        self.cached_lines = None;
+    }
+
+    fn code_overflow(&self) -> Option<usize> {
+        self.body.code_overflow()
+    }
 }
 
 #[cfg(test)]
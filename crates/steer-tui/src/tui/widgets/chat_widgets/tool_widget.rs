@@ -279,6 +279,7 @@ mod tests {
             file_created: true,
             old_content: None,
             new_content: Some("Hello, world!".to_string()),
+            skipped_edits: Vec::new(),
         }));
 
         let mut widget = ToolWidget::new(tool_call, result);
@@ -305,6 +306,7 @@ mod tests {
             stdout: "Hello, world!\n".to_string(),
             stderr: String::new(),
             timed_out: false,
+            parsed: None,
         }));
 
         let mut widget = ToolWidget::new(tool_call, result);
@@ -352,6 +354,7 @@ mod tests {
             ],
             total_files_searched: 2,
             search_completed: true,
+            warnings: Vec::new(),
         }));
 
         let mut widget = ToolWidget::new(tool_call.clone(), result);
@@ -389,6 +392,10 @@ mod tests {
                 content,
                 line_count: 100,
                 truncated: true,
+                encoding: None,
+                binary_kind: None,
+                file_size: None,
+                warnings: Vec::new(),
             },
         ));
 
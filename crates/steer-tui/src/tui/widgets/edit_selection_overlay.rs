@@ -56,11 +56,38 @@ impl EditSelectionOverlayState {
 
 pub struct EditSelectionOverlay<'a> {
     theme: &'a Theme,
+    title: &'a str,
+    empty_message: &'a str,
+    hints: &'a [(&'a str, &'a str)],
 }
 
+const EDIT_HINTS: &[(&str, &str)] = &[
+    ("[↑↓]", "navigate"),
+    ("[Enter]", "select"),
+    ("[Esc]", "cancel"),
+];
+
 impl<'a> EditSelectionOverlay<'a> {
     pub fn new(theme: &'a Theme) -> Self {
-        Self { theme }
+        Self {
+            theme,
+            title: " Edit Message ",
+            empty_message: "No user messages to edit",
+            hints: EDIT_HINTS,
+        }
+    }
+
+    /// Reuses the picker for another action on messages.
+    pub fn with_labels(
+        mut self,
+        title: &'a str,
+        empty_message: &'a str,
+        hints: &'a [(&'a str, &'a str)],
+    ) -> Self {
+        self.title = title;
+        self.empty_message = empty_message;
+        self.hints = hints;
+        self
     }
 
     fn centered_rect(area: Rect) -> Rect {
@@ -153,7 +180,7 @@ impl StatefulWidget for EditSelectionOverlay<'_> {
 
         let outer_block = Block::default()
             .borders(Borders::ALL)
-            .title(" Edit Message ")
+            .title(self.title)
             .style(self.theme.style(Component::InputPanelBorder))
             .border_style(self.theme.style(Component::InputPanelBorderActive));
 
@@ -161,8 +188,8 @@ impl StatefulWidget for EditSelectionOverlay<'_> {
         outer_block.render(popup_area, buf);
 
         if state.is_empty() {
-            let empty_msg = Paragraph::new("No user messages to edit")
-                .style(self.theme.style(Component::DimText));
+            let empty_msg =
+                Paragraph::new(self.empty_message).style(self.theme.style(Component::DimText));
             empty_msg.render(inner_area, buf);
             return;
         }
@@ -229,17 +256,23 @@ impl StatefulWidget for EditSelectionOverlay<'_> {
             height: 1,
         };
 
-        let hint = Line::from(vec![
-            Span::styled("[↑↓]", self.theme.style(Component::InputPanelLabelActive)),
-            Span::styled(" navigate ", self.theme.style(Component::DimText)),
-            Span::styled(
-                "[Enter]",
+        let mut hint_spans = Vec::new();
+        for (index, (key, action)) in self.hints.iter().enumerate() {
+            hint_spans.push(Span::styled(
+                *key,
                 self.theme.style(Component::InputPanelLabelActive),
-            ),
-            Span::styled(" select ", self.theme.style(Component::DimText)),
-            Span::styled("[Esc]", self.theme.style(Component::InputPanelLabelActive)),
-            Span::styled(" cancel", self.theme.style(Component::DimText)),
-        ]);
+            ));
+            let separator = if index + 1 < self.hints.len() {
+                " "
+            } else {
+                ""
+            };
+            hint_spans.push(Span::styled(
+                format!(" {action}{separator}"),
+                self.theme.style(Component::DimText),
+            ));
+        }
+        let hint = Line::from(hint_spans);
 
         buf.set_line(hint_area.x, hint_area.y, &hint, hint_area.width);
     }
@@ -1,6 +1,6 @@
 use super::{
     ToolFormatter,
-    helpers::{separator_line, tool_error_user_message, truncate_lines},
+    helpers::{separator_line, tool_error_user_message, truncate_lines, wrap_preformatted_text},
 };
 use crate::tui::theme::{Component, Theme};
 use ratatui::{
@@ -153,9 +153,12 @@ impl ToolFormatter for BashFormatter {
                             truncate_lines(&bash_result.stdout, MAX_OUTPUT_LINES);
 
                         for line in output_lines {
-                            for wrapped in textwrap::wrap(line, wrap_width) {
-                                lines.push(Line::from(Span::raw(wrapped.to_string())));
-                            }
+                            lines.extend(wrap_preformatted_text(
+                                line,
+                                wrap_width,
+                                Style::default(),
+                                theme.style(Component::DimText),
+                            ));
                         }
 
                         if truncated {
@@ -186,12 +189,12 @@ impl ToolFormatter for BashFormatter {
                             truncate_lines(&bash_result.stderr, MAX_ERROR_LINES);
 
                         for line in error_lines {
-                            for wrapped in textwrap::wrap(line, wrap_width) {
-                                lines.push(Line::from(Span::styled(
-                                    wrapped.to_string(),
-                                    theme.style(Component::ErrorText),
-                                )));
-                            }
+                            lines.extend(wrap_preformatted_text(
+                                line,
+                                wrap_width,
+                                theme.style(Component::ErrorText),
+                                theme.style(Component::DimText),
+                            ));
                         }
 
                         if truncated {
@@ -15,6 +15,41 @@ use steer_tools::tools::dispatch_agent::{
 
 pub struct DispatchAgentFormatter;
 
+/// Shows the role and tool restriction a new sub-agent was given, if any.
+fn push_specialization_lines(
+    lines: &mut Vec<Line<'static>>,
+    target: &DispatchAgentTarget,
+    wrap_width: usize,
+    theme: &Theme,
+) {
+    let DispatchAgentTarget::New {
+        system_prompt,
+        tools,
+        ..
+    } = target
+    else {
+        return;
+    };
+
+    if let Some(tools) = tools {
+        lines.push(Line::from(vec![
+            Span::styled("Tools: ", theme.subtle_text()),
+            Span::styled(tools.join(", "), Style::default()),
+        ]));
+    }
+    if let Some(system_prompt) = system_prompt {
+        lines.push(Line::from(Span::styled("Role:", theme.subtle_text())));
+        for line in system_prompt.lines() {
+            for wrapped_line in textwrap::wrap(line, wrap_width) {
+                lines.push(Line::from(Span::styled(
+                    wrapped_line.to_string(),
+                    Style::default(),
+                )));
+            }
+        }
+    }
+}
+
 impl ToolFormatter for DispatchAgentFormatter {
     fn compact(
         &self,
@@ -39,7 +74,9 @@ impl ToolFormatter for DispatchAgentFormatter {
         };
 
         let (agent_id, workspace_summary, params_session_id) = match &params.target {
-            DispatchAgentTarget::New { workspace, agent } => {
+            DispatchAgentTarget::New {
+                workspace, agent, ..
+            } => {
                 let agent_id = agent
                     .as_deref()
                     .filter(|value| !value.trim().is_empty())
@@ -107,7 +144,9 @@ impl ToolFormatter for DispatchAgentFormatter {
         };
 
         let (agent_id, workspace_label, params_session_id) = match &params.target {
-            DispatchAgentTarget::New { workspace, agent } => {
+            DispatchAgentTarget::New {
+                workspace, agent, ..
+            } => {
                 let agent_id = agent
                     .as_deref()
                     .filter(|value| !value.trim().is_empty())
@@ -140,6 +179,7 @@ impl ToolFormatter for DispatchAgentFormatter {
             ]));
         }
 
+        push_specialization_lines(&mut lines, &params.target, wrap_width, theme);
         lines.push(Line::from(Span::styled("Instructions:", theme.text())));
         for line in params.prompt.lines() {
             for wrapped_line in textwrap::wrap(line, wrap_width) {
@@ -246,7 +286,9 @@ impl ToolFormatter for DispatchAgentFormatter {
         };
 
         let (agent_id, workspace_label, params_session_id) = match &params.target {
-            DispatchAgentTarget::New { workspace, agent } => {
+            DispatchAgentTarget::New {
+                workspace, agent, ..
+            } => {
                 let agent_id = agent
                     .as_deref()
                     .filter(|value| !value.trim().is_empty())
@@ -279,6 +321,7 @@ impl ToolFormatter for DispatchAgentFormatter {
             ]));
         }
 
+        push_specialization_lines(&mut lines, &params.target, wrap_width, theme);
         lines.push(Line::from(Span::styled(
             "Instructions:",
             theme.subtle_text(),
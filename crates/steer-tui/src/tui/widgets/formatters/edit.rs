@@ -54,6 +54,10 @@ impl ToolFormatter for EditFormatter {
 
             let edit_count = params.edits.len();
             let info = match result {
+                Some(ToolResult::Edit(r)) if !r.skipped_edits.is_empty() => format!(
+                    "{} of {edit_count} edits applied",
+                    edit_count.saturating_sub(r.skipped_edits.len())
+                ),
                 Some(ToolResult::Edit(_)) => {
                     if edit_count == 1 {
                         "1 edit applied".to_string()
@@ -170,6 +174,24 @@ impl ToolFormatter for EditFormatter {
             if let Some(result) = result {
                 lines.push(separator_line(wrap_width, theme.style(Component::DimText)));
                 match result {
+                    ToolResult::Edit(r) if !r.skipped_edits.is_empty() => {
+                        lines.push(Line::from(Span::styled(
+                            format!(
+                                "✓ {} of {edit_count} edits applied",
+                                edit_count.saturating_sub(r.skipped_edits.len())
+                            ),
+                            theme.style(Component::ToolSuccess),
+                        )));
+                        for skipped in &r.skipped_edits {
+                            lines.push(Line::from(Span::styled(
+                                format!(
+                                    "✗ Edit {} skipped: {}",
+                                    skipped.edit_index, skipped.reason
+                                ),
+                                theme.style(Component::ErrorText),
+                            )));
+                        }
+                    }
                     ToolResult::Edit(_) => {
                         lines.push(Line::from(Span::styled(
                             format!("✓ All {edit_count} edits applied successfully"),
@@ -4,13 +4,25 @@ use ratatui::{
 };
 use std::borrow::Cow;
 use textwrap;
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::tui::widgets::CodeWrap;
+
+/// Marks rows that continue a soft-wrapped preformatted line
+pub const CONTINUATION_MARKER: &str = "↪ ";
+
+/// Narrowest continuation row worth keeping indentation for
+const MIN_CONTINUATION_WIDTH: usize = 8;
 
 /// Extract concise user-facing message from a ToolError
 pub fn tool_error_user_message(e: &steer_tools::error::ToolError) -> Cow<'_, str> {
     match e {
         steer_tools::error::ToolError::Execution(err) => Cow::Owned(err.to_string()),
         steer_tools::error::ToolError::InvalidParams { message, .. } => Cow::Borrowed(message),
+        steer_tools::error::ToolError::ApprovalDenied { reason, .. } => match reason {
+            Some(reason) => Cow::Owned(format!("Denied by user: {reason}")),
+            None => Cow::Borrowed("Denied by user"),
+        },
         _ => Cow::Owned(e.to_string()),
     }
 }
@@ -79,6 +91,19 @@ pub fn format_size(bytes: usize) -> String {
     }
 }
 
+/// Format a count with thousands separators, e.g. `48112` -> `48,112`.
+pub fn format_count(count: usize) -> String {
+    let digits = count.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
 /// Extract exit code from error message
 pub fn extract_exit_code(error: &str) -> Option<&str> {
     error
@@ -153,3 +178,253 @@ pub fn style_wrap_with_indent(line: Line<'_>, max_width: u16, indent: usize) ->
 
     output_lines
 }
+
+/// Lay out a preformatted line (code, command output) for a view `max_width`
+/// columns wide, either soft-wrapped or scrolled horizontally.
+pub fn layout_preformatted(
+    line: Line<'_>,
+    max_width: usize,
+    wrap: CodeWrap,
+    marker_style: Style,
+) -> Vec<Line<'static>> {
+    match wrap {
+        CodeWrap::Soft => wrap_preformatted(line, max_width, marker_style),
+        CodeWrap::Scroll { offset } => vec![slice_columns(&line, offset, max_width)],
+    }
+}
+
+/// Soft-wrap preformatted text line by line; see [`wrap_preformatted`].
+/// Blank lines are kept, so empty input yields one empty row.
+pub fn wrap_preformatted_text(
+    text: &str,
+    max_width: usize,
+    style: Style,
+    marker_style: Style,
+) -> Vec<Line<'static>> {
+    if text.is_empty() {
+        return vec![Line::default()];
+    }
+    text.lines()
+        .flat_map(|line| {
+            wrap_preformatted(
+                Line::from(Span::styled(line, style)),
+                max_width,
+                marker_style,
+            )
+        })
+        .collect()
+}
+
+/// Wrap a preformatted line without losing its shape: rows break after
+/// whitespace or punctuation where one is close enough to the edge, and only
+/// split a token that is too long to move. Continuation rows repeat the
+/// line's indentation and start with [`CONTINUATION_MARKER`].
+pub fn wrap_preformatted(
+    line: Line<'_>,
+    max_width: usize,
+    marker_style: Style,
+) -> Vec<Line<'static>> {
+    if max_width == 0 || line.width() <= max_width {
+        return vec![owned_line(line)];
+    }
+
+    let cells = line
+        .spans
+        .iter()
+        .flat_map(|span| span.content.chars().map(move |ch| (ch, span.style)))
+        .collect::<Vec<_>>();
+    let marker_width = CONTINUATION_MARKER.width();
+    let use_marker = max_width > marker_width + 1;
+    let indent = cells.iter().take_while(|(ch, _)| *ch == ' ').count();
+    let mut continuation_indent = indent.min(max_width / 2);
+    let prefix_width = |indent: usize| indent + if use_marker { marker_width } else { 0 };
+    if max_width.saturating_sub(prefix_width(continuation_indent)) < MIN_CONTINUATION_WIDTH {
+        continuation_indent = 0;
+    }
+    let continuation_width = max_width
+        .saturating_sub(prefix_width(continuation_indent))
+        .max(1);
+
+    let mut rows = Vec::new();
+    let mut start = 0;
+    while start < cells.len() {
+        let budget = if rows.is_empty() {
+            max_width
+        } else {
+            continuation_width
+        };
+        let mut end = start;
+        let mut width = 0;
+        let mut last_break = None;
+        while let Some(&(ch, _)) = cells.get(end) {
+            let ch_width = ch.width().unwrap_or(0);
+            if width + ch_width > budget {
+                break;
+            }
+            width += ch_width;
+            end += 1;
+            if is_break_after(ch) {
+                last_break = Some((end, width));
+            }
+        }
+        if end == start {
+            // A character wider than the whole row still has to go somewhere.
+            end += 1;
+        } else if end < cells.len()
+            && let Some((break_end, break_width)) = last_break
+            && break_width * 2 >= budget
+        {
+            end = break_end;
+        }
+
+        let mut spans = Vec::new();
+        if !rows.is_empty() {
+            if continuation_indent > 0 {
+                spans.push(Span::raw(" ".repeat(continuation_indent)));
+            }
+            if use_marker {
+                spans.push(Span::styled(CONTINUATION_MARKER, marker_style));
+            }
+        }
+        spans.extend(cells_to_spans(&cells[start..end]));
+        rows.push(Line::from(spans).style(line.style));
+        start = end;
+    }
+
+    rows
+}
+
+/// The part of `line` between display columns `start` and `start + width`.
+/// A wide character cut by either edge is replaced by spaces so the result
+/// never draws half a glyph.
+pub fn slice_columns(line: &Line<'_>, start: usize, width: usize) -> Line<'static> {
+    let end = start.saturating_add(width);
+    let mut spans = Vec::new();
+    let mut column = 0;
+
+    for span in &line.spans {
+        if column >= end {
+            break;
+        }
+        let mut text = String::new();
+        for ch in span.content.chars() {
+            let next = column + ch.width().unwrap_or(0);
+            if column >= start && next <= end {
+                text.push(ch);
+            } else if (column < start && next > start) || (column < end && next > end) {
+                let visible = next.min(end) - column.max(start);
+                text.push_str(&" ".repeat(visible));
+            }
+            column = next;
+            if column >= end {
+                break;
+            }
+        }
+        if !text.is_empty() {
+            spans.push(Span::styled(text, span.style));
+        }
+    }
+
+    Line::from(spans).style(line.style)
+}
+
+/// Whether a preformatted row may end after `ch` without splitting a token
+fn is_break_after(ch: char) -> bool {
+    ch.is_whitespace() || matches!(ch, ',' | ';' | ')' | ']' | '}' | '>' | '/' | '-' | '.')
+}
+
+fn cells_to_spans(cells: &[(char, Style)]) -> Vec<Span<'static>> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    for &(ch, style) in cells {
+        match spans.last_mut() {
+            Some(span) if span.style == style => span.content.to_mut().push(ch),
+            _ => spans.push(Span::styled(ch.to_string(), style)),
+        }
+    }
+    spans
+}
+
+fn owned_line(line: Line<'_>) -> Line<'static> {
+    let style = line.style;
+    Line::from(
+        line.spans
+            .into_iter()
+            .map(|span| Span::styled(span.content.into_owned(), span.style))
+            .collect::<Vec<_>>(),
+    )
+    .style(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row_texts(lines: &[Line<'_>]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_wrap_preformatted_keeps_short_lines() {
+        let rows = wrap_preformatted(Line::from("let x = 1;"), 20, Style::default());
+        assert_eq!(row_texts(&rows), vec!["let x = 1;"]);
+    }
+
+    #[test]
+    fn test_wrap_preformatted_breaks_between_tokens_and_keeps_indent() {
+        let line = Line::from("    call(first_argument, second_argument);");
+        let rows = wrap_preformatted(line, 30, Style::default());
+        assert_eq!(
+            row_texts(&rows),
+            vec!["    call(first_argument, ", "    ↪ second_argument);",]
+        );
+        assert!(rows.iter().all(|row| row.width() <= 30));
+    }
+
+    #[test]
+    fn test_wrap_preformatted_splits_tokens_longer_than_a_row() {
+        let rows = wrap_preformatted(Line::from("abcdefghijklmnop"), 10, Style::default());
+        assert_eq!(row_texts(&rows), vec!["abcdefghij", "↪ klmnop"]);
+    }
+
+    #[test]
+    fn test_wrap_preformatted_never_splits_wide_characters() {
+        let line = Line::from("日本語のテキストと😀絵文字😀を含む行");
+        let rows = wrap_preformatted(line.clone(), 9, Style::default());
+        assert!(rows.iter().all(|row| row.width() <= 9), "{rows:?}");
+        let rejoined = row_texts(&rows)
+            .into_iter()
+            .map(|row| row.trim_start_matches(CONTINUATION_MARKER).to_string())
+            .collect::<String>();
+        assert_eq!(rejoined, "日本語のテキストと😀絵文字😀を含む行");
+    }
+
+    #[test]
+    fn test_slice_columns_handles_wide_characters_at_edges() {
+        let line = Line::from("ab中文😀cd");
+        // Columns: a=0 b=1 中=2-3 文=4-5 😀=6-7 c=8 d=9
+        assert_eq!(row_texts(&[slice_columns(&line, 0, 4)]), vec!["ab中"]);
+        assert_eq!(row_texts(&[slice_columns(&line, 3, 4)]), vec![" 文 "]);
+        assert_eq!(row_texts(&[slice_columns(&line, 6, 10)]), vec!["😀cd"]);
+        assert_eq!(row_texts(&[slice_columns(&line, 20, 5)]), vec![""]);
+        assert!(slice_columns(&line, 3, 4).width() == 4);
+    }
+
+    #[test]
+    fn test_slice_columns_keeps_span_styles() {
+        let bold = Style::default().add_modifier(ratatui::style::Modifier::BOLD);
+        let line = Line::from(vec![Span::raw("fn "), Span::styled("main", bold)]);
+        let sliced = slice_columns(&line, 2, 4);
+        assert_eq!(sliced.spans.len(), 2);
+        assert_eq!(sliced.spans[0].content, " ");
+        assert_eq!(sliced.spans[1].content, "mai");
+        assert_eq!(sliced.spans[1].style, bold);
+    }
+}
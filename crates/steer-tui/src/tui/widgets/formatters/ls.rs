@@ -1,6 +1,6 @@
 use super::{
     ToolFormatter,
-    helpers::{separator_line, tool_error_user_message},
+    helpers::{format_count, separator_line, tool_error_user_message},
 };
 use crate::tui::theme::Theme;
 use ratatui::{
@@ -36,6 +36,13 @@ impl ToolFormatter for LsFormatter {
             .unwrap_or(&params.path);
 
         let info = match result {
+            Some(ToolResult::FileList(file_list)) if file_list.truncated => {
+                format!(
+                    "{} of {} entries",
+                    format_count(file_list.entries.len()),
+                    format_count(file_list.total_entries)
+                )
+            }
             Some(ToolResult::FileList(file_list)) => {
                 format!("{} files", file_list.entries.len())
             }
@@ -107,6 +114,17 @@ impl ToolFormatter for LsFormatter {
                                 theme.subtle_text(),
                             )));
                         }
+
+                        if file_list.truncated {
+                            lines.push(Line::from(Span::styled(
+                                format!(
+                                    "showing {} of {} entries",
+                                    format_count(entries.len()),
+                                    format_count(file_list.total_entries)
+                                ),
+                                theme.subtle_text(),
+                            )));
+                        }
                     }
                 }
                 ToolResult::Error(error) => {
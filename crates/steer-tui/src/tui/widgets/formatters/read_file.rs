@@ -79,8 +79,14 @@ impl ToolFormatter for ReadFileFormatter {
 fn extract_read_file_info(result: &Option<ToolResult>) -> String {
     match result {
         Some(ToolResult::FileContent(file_content)) => {
+            if let Some(kind) = &file_content.binary_kind {
+                return format!("binary, {kind}");
+            }
             let line_count = file_content.content.lines().count();
-            format!("{line_count} lines")
+            match &file_content.encoding {
+                Some(encoding) => format!("{line_count} lines, {encoding}"),
+                None => format!("{line_count} lines"),
+            }
         }
         Some(ToolResult::Error(_)) => "error".to_string(),
         _ => "pending".to_string(),
@@ -6,8 +6,11 @@ use ratatui::style::Style;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 
+use steer_i18n::t;
 use steer_tools::schema::ToolCall;
+use steer_tools::tools::edit::hunks::edit_hunks;
 
+use super::HunkReview;
 use crate::tui::theme::{Component, Theme};
 
 /// Widget for displaying tool approval prompts
@@ -15,16 +18,81 @@ use crate::tui::theme::{Component, Theme};
 pub struct ApprovalWidget<'a> {
     tool_call: &'a ToolCall,
     theme: &'a Theme,
+    denial_reason: Option<&'a str>,
+    hunk_review: Option<&'a HunkReview>,
 }
 
 impl<'a> ApprovalWidget<'a> {
     /// Create a new approval widget
     pub fn new(tool_call: &'a ToolCall, theme: &'a Theme) -> Self {
-        Self { tool_call, theme }
+        Self {
+            tool_call,
+            theme,
+            denial_reason: None,
+            hunk_review: None,
+        }
+    }
+
+    /// Show the denial reason being typed, if any
+    pub fn with_denial_reason(mut self, denial_reason: Option<&'a str>) -> Self {
+        self.denial_reason = denial_reason;
+        self
+    }
+
+    /// Show the hunk being reviewed instead of the full preview, if any
+    pub fn with_hunk_review(mut self, hunk_review: Option<&'a HunkReview>) -> Self {
+        self.hunk_review = hunk_review;
+        self
+    }
+
+    /// Format the current hunk of a hunk-by-hunk review
+    fn format_hunk_text(&self, review: &HunkReview) -> Vec<Line<'static>> {
+        let Some((index, hunk)) = review.current() else {
+            return Vec::new();
+        };
+
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(
+                    t!(
+                        "approval.hunk_position",
+                        index = index + 1,
+                        count = review.len()
+                    ),
+                    self.theme.style(Component::ToolCallHeader),
+                ),
+                Span::styled(
+                    t!(
+                        "approval.hunk_location",
+                        edit = hunk.edit_index + 1,
+                        line = hunk.old_start
+                    ),
+                    self.theme.style(Component::DimText),
+                ),
+            ]),
+            Line::from(""),
+        ];
+        lines.extend(hunk.old_lines.iter().map(|line| {
+            Line::from(Span::styled(
+                format!("-{line}"),
+                self.theme.style(Component::CodeDeletion),
+            ))
+        }));
+        lines.extend(hunk.new_lines.iter().map(|line| {
+            Line::from(Span::styled(
+                format!("+{line}"),
+                self.theme.style(Component::CodeAddition),
+            ))
+        }));
+        lines
     }
 
     /// Format the approval text for the tool call
     fn format_approval_text(&self, area_width: u16) -> Vec<Line<'static>> {
+        if let Some(review) = self.hunk_review {
+            return self.format_hunk_text(review);
+        }
+
         let formatter = crate::tui::widgets::formatters::get_formatter(&self.tool_call.name);
         let preview_lines = formatter.approval(
             &self.tool_call.parameters,
@@ -34,31 +102,38 @@ impl<'a> ApprovalWidget<'a> {
 
         let is_bash_command = self.tool_call.name == "bash";
 
-        let mut approval_text = if is_bash_command {
-            vec![
-                Line::from(vec![
-                    Span::styled("Tool ", Style::default()),
-                    Span::styled(
-                        self.tool_call.name.clone(),
-                        self.theme.style(Component::ToolCallHeader),
-                    ),
-                    Span::styled(" wants to run this shell command", Style::default()),
-                ]),
-                Line::from(""),
-            ]
+        // The tool name is styled, so the sentence is split around its placeholder.
+        let sentence = if is_bash_command {
+            t!("approval.wants_to_run")
         } else {
-            vec![
+            t!("approval.needs_approval")
+        };
+        let (before, after) = sentence.split_once("{tool}").unwrap_or((sentence, ""));
+        let mut approval_text = vec![
+            Line::from(vec![
+                Span::styled(before, Style::default()),
+                Span::styled(
+                    self.tool_call.name.clone(),
+                    self.theme.style(Component::ToolCallHeader),
+                ),
+                Span::styled(after, Style::default()),
+            ]),
+            Line::from(""),
+        ];
+
+        if let Some(reason) = self.denial_reason {
+            approval_text.insert(
+                1,
                 Line::from(vec![
-                    Span::styled("Tool ", Style::default()),
                     Span::styled(
-                        self.tool_call.name.clone(),
-                        self.theme.style(Component::ToolCallHeader),
+                        t!("approval.reason"),
+                        self.theme.style(Component::ToolError),
                     ),
-                    Span::styled(" needs your approval", Style::default()),
+                    Span::raw(reason.to_string()),
+                    Span::styled("▏", self.theme.style(Component::DimText)),
                 ]),
-                Line::from(""),
-            ]
-        };
+            );
+        }
 
         approval_text.extend(preview_lines);
         approval_text
@@ -66,55 +141,173 @@ impl<'a> ApprovalWidget<'a> {
 
     /// Get the keybind options for approval
     fn get_approval_keybinds(&self) -> Vec<(Span<'static>, Span<'static>)> {
+        if self.denial_reason.is_some() {
+            return vec![
+                (
+                    Span::styled("[Enter]", self.theme.style(Component::ToolError)),
+                    Span::styled(
+                        t!("approval.keybind.deny_with_reason"),
+                        self.theme.style(Component::DimText),
+                    ),
+                ),
+                (
+                    Span::styled("[Esc]", self.theme.style(Component::DimText)),
+                    Span::styled(
+                        t!("approval.keybind.back"),
+                        self.theme.style(Component::DimText),
+                    ),
+                ),
+            ];
+        }
+
+        if self.hunk_review.is_some() {
+            return vec![
+                (
+                    Span::styled("[Y]", self.theme.style(Component::ToolSuccess)),
+                    Span::styled(
+                        t!("approval.keybind.apply_hunk"),
+                        self.theme.style(Component::DimText),
+                    ),
+                ),
+                (
+                    Span::styled("[N]", self.theme.style(Component::ToolError)),
+                    Span::styled(
+                        t!("approval.keybind.skip_hunk"),
+                        self.theme.style(Component::DimText),
+                    ),
+                ),
+                (
+                    Span::styled("[A]", self.theme.style(Component::ToolSuccess)),
+                    Span::styled(
+                        t!("approval.keybind.apply_rest"),
+                        self.theme.style(Component::DimText),
+                    ),
+                ),
+                (
+                    Span::styled("[D]", self.theme.style(Component::ToolError)),
+                    Span::styled(
+                        t!("approval.keybind.skip_rest"),
+                        self.theme.style(Component::DimText),
+                    ),
+                ),
+                (
+                    Span::styled("[Esc]", self.theme.style(Component::DimText)),
+                    Span::styled(
+                        t!("approval.keybind.back"),
+                        self.theme.style(Component::DimText),
+                    ),
+                ),
+            ];
+        }
+
         let is_bash_command = self.tool_call.name == "bash";
 
         if is_bash_command {
             vec![
                 (
                     Span::styled("[Y]", self.theme.style(Component::ToolSuccess)),
-                    Span::styled("Yes (once)", self.theme.style(Component::DimText)),
+                    Span::styled(
+                        t!("approval.keybind.once"),
+                        self.theme.style(Component::DimText),
+                    ),
                 ),
                 (
                     Span::styled("[A]", self.theme.style(Component::ToolSuccess)),
                     Span::styled(
-                        "Always (this command)",
+                        t!("approval.keybind.always_command"),
                         self.theme.style(Component::DimText),
                     ),
                 ),
                 (
                     Span::styled("[L]", self.theme.style(Component::ToolSuccess)),
                     Span::styled(
-                        "Always (all Bash commands)",
+                        t!("approval.keybind.always_bash"),
                         self.theme.style(Component::DimText),
                     ),
                 ),
                 (
                     Span::styled("[N]", self.theme.style(Component::ToolError)),
-                    Span::styled("No", self.theme.style(Component::DimText)),
+                    Span::styled(
+                        t!("approval.keybind.deny"),
+                        self.theme.style(Component::DimText),
+                    ),
+                ),
+                (
+                    Span::styled("[R]", self.theme.style(Component::ToolError)),
+                    Span::styled(
+                        t!("approval.keybind.deny_reason"),
+                        self.theme.style(Component::DimText),
+                    ),
+                ),
+                (
+                    Span::styled("[Ctrl+C]", self.theme.style(Component::DimText)),
+                    Span::styled(
+                        t!("approval.keybind.cancel_operation"),
+                        self.theme.style(Component::DimText),
+                    ),
                 ),
             ]
         } else {
-            vec![
+            let mut keybinds = vec![
                 (
                     Span::styled("[Y]", self.theme.style(Component::ToolSuccess)),
-                    Span::styled("Yes (once)", self.theme.style(Component::DimText)),
+                    Span::styled(
+                        t!("approval.keybind.once"),
+                        self.theme.style(Component::DimText),
+                    ),
                 ),
                 (
                     Span::styled("[A]", self.theme.style(Component::ToolSuccess)),
-                    Span::styled("Always", self.theme.style(Component::DimText)),
+                    Span::styled(
+                        t!("approval.keybind.always"),
+                        self.theme.style(Component::DimText),
+                    ),
                 ),
                 (
                     Span::styled("[N]", self.theme.style(Component::ToolError)),
-                    Span::styled("No", self.theme.style(Component::DimText)),
+                    Span::styled(
+                        t!("approval.keybind.deny"),
+                        self.theme.style(Component::DimText),
+                    ),
                 ),
-            ]
+                (
+                    Span::styled("[R]", self.theme.style(Component::ToolError)),
+                    Span::styled(
+                        t!("approval.keybind.deny_reason"),
+                        self.theme.style(Component::DimText),
+                    ),
+                ),
+                (
+                    Span::styled("[Ctrl+C]", self.theme.style(Component::DimText)),
+                    Span::styled(
+                        t!("approval.keybind.cancel_operation"),
+                        self.theme.style(Component::DimText),
+                    ),
+                ),
+            ];
+            if !edit_hunks(self.tool_call).is_empty() {
+                keybinds.insert(
+                    2,
+                    (
+                        Span::styled("[P]", self.theme.style(Component::ToolSuccess)),
+                        Span::styled(
+                            t!("approval.keybind.pick_hunks"),
+                            self.theme.style(Component::DimText),
+                        ),
+                    ),
+                );
+            }
+            keybinds
         }
     }
 
     /// Format the title line with keybinds
     fn format_title(&self) -> Line<'static> {
         let approval_keybinds = self.get_approval_keybinds();
-        let mut title_spans = vec![Span::raw(" Approval Required "), Span::raw("─ ")];
+        let mut title_spans = vec![
+            Span::raw(format!(" {} ", t!("approval.title"))),
+            Span::raw("─ "),
+        ];
 
         for (i, (key, desc)) in approval_keybinds.iter().enumerate() {
             if i > 0 {
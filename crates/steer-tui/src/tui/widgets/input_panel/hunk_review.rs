@@ -0,0 +1,64 @@
+//! Per-hunk answers for an edit approval
+
+use steer_tools::tools::edit::hunks::EditHunk;
+
+/// Walks through the hunks of a pending edit approval one at a time.
+#[derive(Debug, Clone)]
+pub struct HunkReview {
+    hunks: Vec<EditHunk>,
+    accepted: Vec<bool>,
+}
+
+impl HunkReview {
+    pub fn new(hunks: Vec<EditHunk>) -> Self {
+        Self {
+            hunks,
+            accepted: Vec::new(),
+        }
+    }
+
+    /// The hunk awaiting an answer, with its index.
+    pub fn current(&self) -> Option<(usize, &EditHunk)> {
+        let index = self.accepted.len();
+        self.hunks.get(index).map(|hunk| (index, hunk))
+    }
+
+    pub fn len(&self) -> usize {
+        self.hunks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hunks.is_empty()
+    }
+
+    /// Answer the current hunk.
+    pub fn answer(&mut self, accept: bool) {
+        if !self.is_done() {
+            self.accepted.push(accept);
+        }
+    }
+
+    /// Answer the current hunk and every hunk after it.
+    pub fn answer_rest(&mut self, accept: bool) {
+        self.accepted.resize(self.hunks.len(), accept);
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.accepted.len() >= self.hunks.len()
+    }
+
+    /// Indices of the hunks answered with yes.
+    pub fn accepted_indices(&self) -> Vec<usize> {
+        self.accepted
+            .iter()
+            .enumerate()
+            .filter_map(|(index, accepted)| accepted.then_some(index))
+            .collect()
+    }
+
+    /// Lines needed to show the current hunk's removed and added lines.
+    pub fn current_line_count(&self) -> usize {
+        self.current()
+            .map_or(0, |(_, hunk)| hunk.old_lines.len() + hunk.new_lines.len())
+    }
+}
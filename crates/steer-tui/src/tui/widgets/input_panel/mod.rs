@@ -4,12 +4,14 @@
 
 mod approval_prompt;
 mod fuzzy_state;
+mod hunk_review;
 mod mode_title;
 mod queued_preview;
 mod textarea;
 
 pub use approval_prompt::ApprovalWidget;
 pub use fuzzy_state::FuzzyFinderHelper;
+pub use hunk_review::HunkReview;
 pub use mode_title::{ModeTitleParams, ModeTitleWidget};
 pub use queued_preview::QueuedPreviewWidget;
 pub use textarea::TextAreaWidget;
@@ -20,6 +22,7 @@ use ratatui::prelude::{Buffer, StatefulWidget, Widget};
 use ratatui::widgets::{Block, Borders, Padding};
 use tui_textarea::{Input, TextArea};
 
+use steer_i18n::t;
 use steer_tools::schema::ToolCall;
 
 use crate::tui::InputMode;
@@ -33,6 +36,10 @@ pub struct InputPanelState {
     pub textarea: TextArea<'static>,
     pub file_cache: FileCache,
     pub fuzzy_finder: FuzzyFinder,
+    /// Reason being typed while denying the pending tool approval.
+    pub denial_reason: Option<String>,
+    /// Hunk-by-hunk answers while reviewing the pending edit approval.
+    pub hunk_review: Option<HunkReview>,
 }
 
 impl Default for InputPanelState {
@@ -46,7 +53,7 @@ impl InputPanelState {
     /// Create a new InputPanelState with the given session ID
     pub fn new(session_id: String) -> Self {
         let mut textarea = TextArea::default();
-        textarea.set_placeholder_text("Type your message here...");
+        textarea.set_placeholder_text(t!("input.placeholder"));
         textarea.set_cursor_line_style(ratatui::style::Style::default());
         textarea.set_cursor_style(
             ratatui::style::Style::default().add_modifier(ratatui::style::Modifier::REVERSED),
@@ -55,6 +62,8 @@ impl InputPanelState {
             textarea,
             file_cache: FileCache::new(session_id),
             fuzzy_finder: FuzzyFinder::new(),
+            denial_reason: None,
+            hunk_review: None,
         }
     }
 
@@ -223,8 +232,7 @@ impl InputPanelState {
     /// Clear the input
     pub fn clear(&mut self) {
         self.textarea = TextArea::default();
-        self.textarea
-            .set_placeholder_text("Type your message here...");
+        self.textarea.set_placeholder_text(t!("input.placeholder"));
         self.textarea
             .set_cursor_line_style(ratatui::style::Style::default());
         self.textarea.set_cursor_style(
@@ -276,7 +284,16 @@ impl InputPanelState {
     ) -> u16 {
         if let Some(tool_call) = current_approval {
             // If there's a pending approval, use the approval height calculation
-            return Self::required_height_for_approval(tool_call, width, max_height);
+            let height = Self::required_height_for_approval(tool_call, width, max_height);
+            return if self.denial_reason.is_some() {
+                height.saturating_add(1).min(max_height)
+            } else if let Some(review) = &self.hunk_review {
+                // 2 lines for the hunk header + hunk lines + 2 for borders + 1 for padding
+                let review_height = (2 + review.current_line_count() + 3) as u16;
+                height.max(review_height).min(max_height)
+            } else {
+                height
+            };
         }
 
         let line_count = self.textarea.lines().len().max(1);
@@ -316,6 +333,7 @@ pub struct InputPanelParams<'a> {
     pub queued_preview: Option<&'a str>,
     pub queued_attachment_count: u32,
     pub attachment_count: usize,
+    pub history_query: Option<&'a str>,
     pub theme: &'a Theme,
 }
 
@@ -332,6 +350,7 @@ pub struct InputPanel<'a> {
     pub queued_preview: Option<&'a str>,
     pub queued_attachment_count: u32,
     pub attachment_count: usize,
+    pub history_query: Option<&'a str>,
     pub theme: &'a Theme,
 }
 
@@ -348,6 +367,7 @@ impl<'a> InputPanel<'a> {
             queued_preview: params.queued_preview,
             queued_attachment_count: params.queued_attachment_count,
             attachment_count: params.attachment_count,
+            history_query: params.history_query,
             theme: params.theme,
         }
     }
@@ -358,7 +378,10 @@ impl StatefulWidget for InputPanel<'_> {
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         if let Some(tool_call) = self.current_approval {
-            ApprovalWidget::new(tool_call, self.theme).render(area, buf);
+            ApprovalWidget::new(tool_call, self.theme)
+                .with_denial_reason(state.denial_reason.as_deref())
+                .with_hunk_review(state.hunk_review.as_ref())
+                .render(area, buf);
             return;
         }
 
@@ -372,6 +395,7 @@ impl StatefulWidget for InputPanel<'_> {
             has_content: state.has_content(),
             queued_count: self.queued_count,
             attachment_count: self.attachment_count,
+            history_query: self.history_query,
         })
         .render();
 
@@ -3,6 +3,8 @@
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 
+use steer_i18n::t;
+
 use crate::tui::InputMode;
 use crate::tui::get_spinner_char;
 use crate::tui::theme::{Component, Theme};
@@ -19,6 +21,7 @@ pub struct ModeTitleWidget<'a> {
     has_content: bool,
     queued_count: usize,
     attachment_count: usize,
+    history_query: Option<&'a str>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -32,6 +35,7 @@ pub struct ModeTitleParams<'a> {
     pub has_content: bool,
     pub queued_count: usize,
     pub attachment_count: usize,
+    pub history_query: Option<&'a str>,
 }
 
 impl<'a> ModeTitleWidget<'a> {
@@ -47,6 +51,7 @@ impl<'a> ModeTitleWidget<'a> {
             has_content: params.has_content,
             queued_count: params.queued_count,
             attachment_count: params.attachment_count,
+            history_query: params.history_query,
         }
     }
 
@@ -67,7 +72,7 @@ impl<'a> ModeTitleWidget<'a> {
 
         if self.is_editing {
             spans.push(Span::styled(
-                "Editing",
+                t!("mode.editing"),
                 self.theme.style(Component::InputPanelLabelEdit),
             ));
             if let Some(preview) = self.editing_preview {
@@ -83,6 +88,13 @@ impl<'a> ModeTitleWidget<'a> {
         let formatted_mode = self.get_formatted_mode();
         if let Some(mode) = formatted_mode {
             spans.push(mode);
+            if let Some(query) = self.history_query {
+                spans.push(Span::styled(": ", self.theme.style(Component::DimText)));
+                spans.push(Span::styled(
+                    format!("`{query}`"),
+                    self.theme.style(Component::InputPanelLabel),
+                ));
+            }
             spans.push(Span::styled(" │ ", self.theme.style(Component::DimText)));
         }
 
@@ -98,22 +110,27 @@ impl<'a> ModeTitleWidget<'a> {
     fn get_formatted_mode(&self) -> Option<Span<'static>> {
         let mode_name = match self.mode {
             InputMode::Simple => return None,
-            InputMode::VimNormal => "NORMAL",
-            InputMode::VimInsert => "INSERT",
-            InputMode::BashCommand => "Bash",
-            InputMode::AwaitingApproval => "Awaiting Approval",
-            InputMode::ConfirmExit => "Confirm Exit",
-            InputMode::EditMessageSelection => "Edit Selection",
-            InputMode::FuzzyFinder => "Search",
-            InputMode::Setup => "Setup",
+            InputMode::VimNormal => t!("mode.normal"),
+            InputMode::VimInsert => t!("mode.insert"),
+            InputMode::BashCommand => t!("mode.bash"),
+            InputMode::AwaitingApproval => t!("mode.awaiting_approval"),
+            InputMode::ConfirmExit => t!("mode.confirm_exit"),
+            InputMode::ConfirmCommit => t!("mode.confirm_commit"),
+            InputMode::EditMessageSelection => t!("mode.edit_selection"),
+            InputMode::CopySelection => t!("mode.copy"),
+            InputMode::FuzzyFinder => t!("mode.search"),
+            InputMode::HistorySearch => t!("mode.history_search"),
+            InputMode::Setup => t!("mode.setup"),
         };
 
         let component = match self.mode {
             InputMode::ConfirmExit => Component::ErrorBold,
-            InputMode::BashCommand => Component::CommandPrompt,
+            InputMode::BashCommand | InputMode::ConfirmCommit => Component::CommandPrompt,
             InputMode::AwaitingApproval => Component::ErrorBold,
-            InputMode::EditMessageSelection => Component::SelectionHighlight,
-            InputMode::FuzzyFinder => Component::SelectionHighlight,
+            InputMode::EditMessageSelection | InputMode::CopySelection => {
+                Component::SelectionHighlight
+            }
+            InputMode::FuzzyFinder | InputMode::HistorySearch => Component::SelectionHighlight,
             _ => Component::ModelInfo,
         };
 
@@ -126,62 +143,97 @@ impl<'a> ModeTitleWidget<'a> {
             InputMode::Simple => {
                 if self.has_content {
                     vec![
-                        ("Enter", "send"),
-                        ("Shift+Tab", "cycle agent"),
-                        ("ESC ESC", "clear"),
+                        ("Enter", t!("mode.keybind.send")),
+                        ("Shift+Tab", t!("mode.keybind.cycle_agent")),
+                        ("ESC ESC", t!("mode.keybind.clear")),
                     ]
                 } else {
                     vec![
-                        ("Enter", "send"),
-                        ("Shift+Tab", "cycle agent"),
-                        ("ESC ESC", "edit previous"),
-                        ("!", "bash"),
-                        ("/", "command"),
-                        ("@", "file"),
+                        ("Enter", t!("mode.keybind.send")),
+                        ("Shift+Tab", t!("mode.keybind.cycle_agent")),
+                        ("ESC ESC", t!("mode.keybind.edit_previous")),
+                        ("!", t!("mode.keybind.bash")),
+                        ("/", t!("mode.keybind.command")),
+                        ("@", t!("mode.keybind.file")),
                     ]
                 }
             }
             InputMode::VimNormal => {
                 if self.has_content {
                     vec![
-                        ("i", "insert"),
-                        ("Shift+Tab", "cycle agent"),
-                        ("ESC ESC", "clear"),
-                        ("hjkl", "move"),
+                        ("i", t!("mode.keybind.insert")),
+                        ("Shift+Tab", t!("mode.keybind.cycle_agent")),
+                        ("ESC ESC", t!("mode.keybind.clear")),
+                        ("hjkl", t!("mode.keybind.move")),
                     ]
                 } else {
                     vec![
-                        ("i", "insert"),
-                        ("Shift+Tab", "cycle agent"),
-                        ("ESC ESC", "edit previous"),
-                        ("!", "bash"),
-                        ("/", "command"),
+                        ("i", t!("mode.keybind.insert")),
+                        ("Shift+Tab", t!("mode.keybind.cycle_agent")),
+                        ("ESC ESC", t!("mode.keybind.edit_previous")),
+                        ("!", t!("mode.keybind.bash")),
+                        ("/", t!("mode.keybind.command")),
                     ]
                 }
             }
             InputMode::VimInsert => {
                 vec![
-                    ("Esc", "normal"),
-                    ("Shift+Tab", "cycle agent"),
-                    ("ESC ESC", "clear"),
-                    ("Enter", "send"),
+                    ("Esc", t!("mode.keybind.normal")),
+                    ("Shift+Tab", t!("mode.keybind.cycle_agent")),
+                    ("ESC ESC", t!("mode.keybind.clear")),
+                    ("Enter", t!("mode.keybind.send")),
                 ]
             }
             InputMode::BashCommand => {
-                vec![("Enter", "execute"), ("Esc", "cancel")]
+                vec![
+                    ("Enter", t!("mode.keybind.execute")),
+                    ("Esc", t!("mode.keybind.cancel")),
+                ]
             }
             InputMode::AwaitingApproval => {
                 // No keybinds for this mode
                 vec![]
             }
             InputMode::ConfirmExit => {
-                vec![("y/Y", "confirm"), ("any other key", "cancel")]
+                vec![
+                    ("y/Y", t!("mode.keybind.confirm")),
+                    (t!("mode.keybind.any_other_key"), t!("mode.keybind.cancel")),
+                ]
+            }
+            InputMode::ConfirmCommit => {
+                vec![
+                    ("y/Y", t!("mode.keybind.commit")),
+                    (t!("mode.keybind.any_other_key"), t!("mode.keybind.cancel")),
+                ]
             }
             InputMode::EditMessageSelection => {
-                vec![("↑↓", "navigate"), ("Enter", "select"), ("Esc", "cancel")]
+                vec![
+                    ("↑↓", t!("mode.keybind.navigate")),
+                    ("Enter", t!("mode.keybind.select")),
+                    ("Esc", t!("mode.keybind.cancel")),
+                ]
+            }
+            InputMode::CopySelection => {
+                vec![
+                    ("y", t!("mode.keybind.message")),
+                    ("c", t!("mode.keybind.code_block")),
+                    ("f", t!("mode.keybind.file_path")),
+                    ("Esc", t!("mode.keybind.close")),
+                ]
             }
             InputMode::FuzzyFinder => {
-                vec![("↑↓", "navigate"), ("Enter", "select"), ("Esc", "cancel")]
+                vec![
+                    ("↑↓", t!("mode.keybind.navigate")),
+                    ("Enter", t!("mode.keybind.select")),
+                    ("Esc", t!("mode.keybind.cancel")),
+                ]
+            }
+            InputMode::HistorySearch => {
+                vec![
+                    ("Ctrl+R", t!("mode.keybind.older")),
+                    ("Enter", t!("mode.keybind.accept")),
+                    ("Esc", t!("mode.keybind.cancel")),
+                ]
             }
             InputMode::Setup => {
                 // No keybinds shown during setup mode
@@ -190,15 +242,15 @@ impl<'a> ModeTitleWidget<'a> {
         };
 
         if self.is_editing {
-            keybinds.insert(0, ("Esc", "cancel edit"));
+            keybinds.insert(0, ("Esc", t!("mode.keybind.cancel_edit")));
         }
 
         if self.queued_count > 0 {
-            keybinds.insert(0, ("Alt+Up", "edit queued"));
+            keybinds.insert(0, ("Alt+Up", t!("mode.keybind.edit_queued")));
         }
 
         if self.attachment_count > 0 {
-            keybinds.insert(0, ("Ctrl+V", "add image"));
+            keybinds.insert(0, ("Ctrl+V", t!("mode.keybind.add_image")));
         }
 
         keybinds
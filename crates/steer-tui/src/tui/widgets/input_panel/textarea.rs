@@ -144,7 +144,7 @@ fn apply_mode_styling<'a>(
             let text_style = background_style.patch(style);
             block = block.style(text_style).border_style(style);
         }
-        InputMode::EditMessageSelection => {
+        InputMode::EditMessageSelection | InputMode::CopySelection => {
             let style = theme.style(Component::InputPanelBorderCommand);
             let text_style = background_style.patch(style);
             block = block.style(text_style).border_style(style);
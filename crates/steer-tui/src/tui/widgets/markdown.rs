@@ -7,6 +7,7 @@ use itertools::{Itertools, Position};
 use pulldown_cmark::{CodeBlockKind, CowStr, Event, HeadingLevel, Options, Parser, Tag};
 use ratatui::style::{Color, Style};
 use ratatui::text::{Line, Span};
+use std::borrow::Cow;
 use syntect::easy::HighlightLines;
 use syntect::parsing::SyntaxSet;
 use syntect::util::LinesWithEndings;
@@ -35,6 +36,7 @@ pub struct MarkedLine {
     pub line: Line<'static>,
     pub no_wrap: bool,       // If true, this line should not be wrapped
     pub indent_level: usize, // Number of spaces to indent when wrapping
+    pub is_code: bool,       // Code block line, laid out by the viewer's code wrap mode
 }
 
 impl MarkedLine {
@@ -43,6 +45,7 @@ impl MarkedLine {
             line,
             no_wrap: false,
             indent_level: 0,
+            is_code: false,
         }
     }
 
@@ -51,6 +54,14 @@ impl MarkedLine {
             line,
             no_wrap: true,
             indent_level: 0,
+            is_code: false,
+        }
+    }
+
+    pub fn new_code(line: Line<'static>) -> Self {
+        Self {
+            is_code: true,
+            ..Self::new_no_wrap(line)
         }
     }
 
@@ -625,7 +636,7 @@ where
 
         // Create marked line based on current state
         let marked_line = if self.in_code_block {
-            MarkedLine::new_no_wrap(static_line)
+            MarkedLine::new_code(static_line)
         } else {
             // Apply list item indent if we're in a list
             let indent = if !self.list_indices.is_empty() && !has_prefixes {
@@ -732,7 +743,12 @@ where
         // Nothing to do here, cell is already added
     }
 
-    /// Render the accumulated table with proper alignment
+    /// Render the accumulated table, fitted to the terminal width.
+    ///
+    /// Tables get box borders and keep their natural column widths when they
+    /// fit. Otherwise the widest columns are narrowed and their cells wrapped;
+    /// past that the borders are dropped, and as a last resort trailing columns
+    /// are elided. Table lines are never re-wrapped by the caller.
     fn render_table(&mut self) {
         if self.table_rows.is_empty() {
             return;
@@ -740,129 +756,167 @@ where
 
         // Move rows out of `self` to avoid borrow conflicts during rendering
         let rows = std::mem::take(&mut self.table_rows);
-
-        // Calculate column widths
         let num_cols = self.table_alignments.len();
-        let mut col_widths = vec![0; num_cols];
 
+        let mut natural = vec![0; num_cols];
         for row in &rows {
-            for (col_idx, cell) in row.iter().enumerate() {
-                if col_idx < num_cols {
-                    let cell_width = cell
-                        .iter()
-                        .map(|span| span.content.as_ref().width())
-                        .sum::<usize>();
-                    col_widths[col_idx] = col_widths[col_idx].max(cell_width);
+            for (col_idx, cell) in row.iter().enumerate().take(num_cols) {
+                let cell_width = cell
+                    .iter()
+                    .map(|span| span.content.as_ref().width())
+                    .sum::<usize>();
+                natural[col_idx] = natural[col_idx].max(cell_width);
+            }
+        }
+
+        let budget = self
+            .terminal_width
+            .map(|width| (width as usize).saturating_sub(self.line_prefix_width()));
+        let layout = match budget {
+            None => TableLayout::fitted(TableStyle::Bordered, natural),
+            Some(budget) => TableLayout::fit(&natural, budget),
+        };
+
+        match layout.style {
+            TableStyle::Bordered => {
+                self.render_table_border(&layout.widths, '┌', '┬', '┐');
+                for (row_idx, row) in rows.iter().enumerate() {
+                    let is_header = row_idx == 0 && rows.len() > 1;
+                    self.render_table_row(row, &layout, is_header);
+                    if is_header {
+                        self.render_table_border(&layout.widths, '├', '┼', '┤');
+                    }
+                }
+                self.render_table_border(&layout.widths, '└', '┴', '┘');
+            }
+            TableStyle::Plain => {
+                for (row_idx, row) in rows.iter().enumerate() {
+                    let is_header = row_idx == 0 && rows.len() > 1;
+                    self.render_table_row(row, &layout, is_header);
+                    if is_header {
+                        self.render_plain_header_rule(&layout);
+                    }
                 }
             }
         }
 
-        // Add padding to column widths
-        for width in &mut col_widths {
-            *width += 2; // Add 1 space padding on each side
+        if layout.hidden_columns > 0 {
+            let note = format!(
+                "… {} more column{} not shown",
+                layout.hidden_columns,
+                if layout.hidden_columns == 1 { "" } else { "s" }
+            );
+            self.push_table_line(Line::from(Span::styled(note, self.styles.table_border)));
         }
+    }
 
-        // Render the table
+    /// Render one table row, wrapping cells to their column widths.
+    fn render_table_row(&mut self, row: &[Vec<Span<'a>>], layout: &TableLayout, is_header: bool) {
         let border_style = self.styles.table_border;
-        let header_style = self.styles.table_header;
-        let cell_style = self.styles.table_cell;
-
-        // Top border
-        self.render_table_border(&col_widths, '┌', '┬', '┐', border_style);
-
-        // Render rows
-        for (row_idx, row) in rows.iter().enumerate() {
-            let is_header = row_idx == 0 && rows.len() > 1;
-            let mut line_spans = vec![Span::styled("│", border_style)];
-
-            for (col_idx, cell) in row.iter().enumerate() {
-                if col_idx < num_cols {
-                    // Concatenate cell spans into a single string for alignment
-                    let cell_text: String = cell
-                        .iter()
-                        .map(|span| span.content.as_ref())
-                        .collect::<Vec<_>>()
-                        .join("");
-
-                    let padded = Self::align_text(
-                        &cell_text,
-                        col_widths[col_idx],
-                        self.table_alignments[col_idx],
-                    );
-
-                    // Apply appropriate style
-                    let style = if is_header { header_style } else { cell_style };
-                    line_spans.push(Span::styled(padded, style));
-                    line_spans.push(Span::styled("│", border_style));
-                }
+        let base_style = if is_header {
+            self.styles.table_header
+        } else {
+            self.styles.table_cell
+        };
+
+        let wrapped: Vec<Vec<Vec<Span<'a>>>> = layout
+            .widths
+            .iter()
+            .enumerate()
+            .map(|(col_idx, &width)| {
+                let cell = row.get(col_idx).map(Vec::as_slice).unwrap_or_default();
+                wrap_cell(cell, width)
+            })
+            .collect();
+        let height = wrapped.iter().map(Vec::len).max().unwrap_or(1).max(1);
+
+        for line_idx in 0..height {
+            let mut spans = Vec::new();
+            if layout.style == TableStyle::Bordered {
+                spans.push(Span::styled("│", border_style));
             }
 
-            self.push_line(Line::from(line_spans));
+            for (col_idx, cell_lines) in wrapped.iter().enumerate() {
+                let content = cell_lines.get(line_idx).cloned().unwrap_or_default();
+                let alignment = self.table_alignments[col_idx];
+                let padded = pad_cell(content, layout.widths[col_idx], alignment, base_style);
+
+                match layout.style {
+                    TableStyle::Bordered => {
+                        spans.push(Span::styled(" ", base_style));
+                        spans.extend(padded);
+                        spans.push(Span::styled(" ", base_style));
+                        spans.push(Span::styled("│", border_style));
+                    }
+                    TableStyle::Plain => {
+                        if col_idx > 0 {
+                            spans.push(Span::raw(PLAIN_COLUMN_GAP));
+                        }
+                        spans.extend(padded);
+                    }
+                }
+            }
 
-            // Add separator after header
-            if is_header {
-                self.render_table_border(&col_widths, '├', '┼', '┤', border_style);
+            if layout.hidden_columns > 0 {
+                spans.push(Span::raw(PLAIN_COLUMN_GAP));
+                spans.push(Span::styled("…", border_style));
             }
-        }
 
-        // Bottom border
-        self.render_table_border(&col_widths, '└', '┴', '┘', border_style);
+            self.push_table_line(Line::from(spans));
+        }
     }
 
     /// Render a table border line
-    fn render_table_border(
-        &mut self,
-        col_widths: &[usize],
-        left: char,
-        mid: char,
-        right: char,
-        style: Style,
-    ) {
+    fn render_table_border(&mut self, col_widths: &[usize], left: char, mid: char, right: char) {
         let mut border = String::from(left);
 
         for (idx, &width) in col_widths.iter().enumerate() {
-            border.push_str(&"─".repeat(width));
+            // One space of padding on each side of the cell
+            border.push_str(&"─".repeat(width + 2));
             if idx < col_widths.len() - 1 {
                 border.push(mid);
             }
         }
 
         border.push(right);
-        self.push_line(Line::from(Span::styled(border, style)));
+        self.push_table_line(Line::from(Span::styled(border, self.styles.table_border)));
     }
 
-    /// Align text within a given width based on alignment
-    fn align_text(text: &str, width: usize, alignment: pulldown_cmark::Alignment) -> String {
-        let text_len = text.width();
-        // Total spaces needed = width - text_len
-        // We already have 2 spaces in the format string (1 before, 1 after)
-        let total_padding = width.saturating_sub(text_len);
+    /// Underline the header of a borderless table
+    fn render_plain_header_rule(&mut self, layout: &TableLayout) {
+        let mut rule = layout
+            .widths
+            .iter()
+            .map(|&width| "─".repeat(width))
+            .join(PLAIN_COLUMN_GAP);
+        if layout.hidden_columns > 0 {
+            rule.push_str(PLAIN_COLUMN_GAP);
+            rule.push('─');
+        }
+        self.push_table_line(Line::from(Span::styled(rule, self.styles.table_border)));
+    }
 
-        match alignment {
-            pulldown_cmark::Alignment::None | pulldown_cmark::Alignment::Left => {
-                // Left align: 1 space before, remaining spaces after
-                let right_padding = total_padding.saturating_sub(1);
-                format!(" {}{}", text, " ".repeat(right_padding))
-            }
-            pulldown_cmark::Alignment::Center => {
-                // Center: distribute padding evenly
-                let left_padding = total_padding / 2;
-                let right_padding = total_padding - left_padding;
-                format!(
-                    "{}{}{}",
-                    " ".repeat(left_padding),
-                    text,
-                    " ".repeat(right_padding)
-                )
-            }
-            pulldown_cmark::Alignment::Right => {
-                // Right align: remaining spaces before, 1 space after
-                let left_padding = total_padding.saturating_sub(1);
-                format!("{}{} ", " ".repeat(left_padding), text)
-            }
+    /// Push a line that is already laid out to the terminal width
+    fn push_table_line(&mut self, line: Line<'a>) {
+        self.push_line(line);
+        if let Some(marked_line) = self.marked_text.lines.last_mut() {
+            marked_line.no_wrap = true;
         }
     }
 
+    /// Width taken by blockquote prefixes at the start of each line
+    fn line_prefix_width(&self) -> usize {
+        if self.line_prefixes.is_empty() {
+            return 0;
+        }
+        // `push_line` separates the prefixes from the content with a space
+        self.line_prefixes
+            .iter()
+            .map(|prefix| prefix.content.as_ref().width())
+            .sum::<usize>()
+            + 1
+    }
+
     /// Render a horizontal rule
     fn rule(&mut self) {
         if self.needs_newline {
@@ -921,41 +975,499 @@ where
     }
 
     /// Render a task list marker (checkbox)
+    ///
+    /// In bullet lists the checkbox replaces the bullet; numbered items keep
+    /// their number in front of it.
     fn task_list_marker(&mut self, checked: bool) {
         // If we're not inside a list, there's nothing to render – avoid underflow.
         if self.list_indices.is_empty() {
             return;
         }
 
-        // Push the list indentation and marker
         let depth = self.list_indices.len();
-        let indent_width = depth.saturating_sub(1).saturating_mul(4);
-        let indent_str = " ".repeat(indent_width);
-
-        // Use checkbox characters
-        let checkbox = if checked { "[✓] " } else { "[ ] " };
+        let indent_str = " ".repeat(depth.saturating_sub(1).saturating_mul(4));
 
-        // Apply appropriate style based on checked state
-        let style = if checked {
-            self.styles.task_checked
+        let (checkbox, style) = if checked {
+            (TASK_CHECKED_GLYPH, self.styles.task_checked)
         } else {
-            self.styles.task_unchecked
+            (TASK_UNCHECKED_GLYPH, self.styles.task_unchecked)
         };
 
-        let full_marker = format!("{indent_str}- {checkbox}");
-        let marker_width = full_marker.len();
+        let number = match self.list_indices.last_mut() {
+            Some(Some(index)) => {
+                *index += 1;
+                Some(*index - 1)
+            }
+            _ => None,
+        };
 
-        // Update the list item indent to account for the actual marker width
-        self.list_item_indent = marker_width;
+        let mut spans = vec![Span::raw(indent_str)];
+        if let Some(number) = number {
+            spans.push(Span::styled(format!("{number}. "), self.styles.list_number));
+        }
+        spans.push(Span::styled(format!("{checkbox} "), style));
 
-        let span = Span::styled(full_marker, style);
-        self.push_span(span);
+        // Align wrapped lines with the text after the checkbox
+        self.list_item_indent = spans.iter().map(|span| span.content.width()).sum();
+        if let Some(current_line) = self.marked_text.lines.last_mut() {
+            current_line.indent_level = self.list_item_indent;
+        }
+        for span in spans {
+            self.push_span(span);
+        }
 
         // Mark that we've handled the list item start
         self.in_list_item_start = false;
     }
 }
 
+/// Checkbox glyphs for GFM task list items
+const TASK_CHECKED_GLYPH: &str = "☑";
+const TASK_UNCHECKED_GLYPH: &str = "☐";
+
+/// Separator between columns of a borderless table
+const PLAIN_COLUMN_GAP: &str = "  ";
+
+/// Narrowest a bordered column is squeezed to before dropping the borders
+const MIN_BORDERED_COLUMN_WIDTH: usize = 6;
+
+/// Narrowest a borderless column is squeezed to before eliding columns
+const MIN_PLAIN_COLUMN_WIDTH: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TableStyle {
+    Bordered,
+    Plain,
+}
+
+/// Column widths chosen for a table, excluding padding and borders
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TableLayout {
+    style: TableStyle,
+    widths: Vec<usize>,
+    /// Trailing columns left out because the table is too wide to show them
+    hidden_columns: usize,
+}
+
+impl TableLayout {
+    fn fitted(style: TableStyle, widths: Vec<usize>) -> Self {
+        Self {
+            style,
+            widths,
+            hidden_columns: 0,
+        }
+    }
+
+    /// Pick the richest layout whose lines fit in `budget` columns
+    fn fit(natural: &[usize], budget: usize) -> Self {
+        let cols = natural.len();
+
+        // `│ cell │ cell │`: three characters per column plus the closing border
+        let bordered_budget = budget.saturating_sub(3 * cols + 1);
+        if let Some(widths) = shrink_columns(natural, bordered_budget, MIN_BORDERED_COLUMN_WIDTH) {
+            return Self::fitted(TableStyle::Bordered, widths);
+        }
+
+        let plain_budget = budget.saturating_sub(PLAIN_COLUMN_GAP.len() * cols.saturating_sub(1));
+        if let Some(widths) = shrink_columns(natural, plain_budget, MIN_PLAIN_COLUMN_WIDTH) {
+            return Self::fitted(TableStyle::Plain, widths);
+        }
+
+        // Keep as many leading columns as fit next to a trailing `…` column.
+        // The first column is always shown, even if it must be cut short.
+        for shown in (1..cols).rev() {
+            let overhead = PLAIN_COLUMN_GAP.len() * shown + 1;
+            let budget = budget.saturating_sub(overhead);
+            if let Some(widths) = shrink_columns(&natural[..shown], budget, MIN_PLAIN_COLUMN_WIDTH)
+            {
+                return Self {
+                    style: TableStyle::Plain,
+                    widths,
+                    hidden_columns: cols - shown,
+                };
+            }
+        }
+
+        let first = natural.first().copied().unwrap_or(0);
+        let overhead = if cols > 1 {
+            PLAIN_COLUMN_GAP.len() + 1
+        } else {
+            0
+        };
+        Self {
+            style: TableStyle::Plain,
+            widths: vec![first.min(budget.saturating_sub(overhead)).max(1)],
+            hidden_columns: cols.saturating_sub(1),
+        }
+    }
+}
+
+/// Narrow the widest columns until the widths sum to at most `budget`,
+/// without taking any column below `min_width` (or its natural width, if
+/// that is smaller). Returns `None` when that is impossible.
+fn shrink_columns(natural: &[usize], budget: usize, min_width: usize) -> Option<Vec<usize>> {
+    let floor: usize = natural.iter().map(|&width| width.min(min_width)).sum();
+    if floor > budget {
+        return None;
+    }
+
+    let mut widths = natural.to_vec();
+    let mut total: usize = widths.iter().sum();
+    while total > budget {
+        let (widest, _) = widths
+            .iter()
+            .enumerate()
+            .max_by_key(|&(idx, &width)| (width, std::cmp::Reverse(idx)))?;
+        widths[widest] -= 1;
+        total -= 1;
+    }
+    Some(widths)
+}
+
+/// Word-wrap styled cell content to `width`, breaking words that are longer
+/// than a whole line.
+fn wrap_cell<'a>(cell: &[Span<'a>], width: usize) -> Vec<Vec<Span<'a>>> {
+    let width = width.max(1);
+    let mut lines: Vec<Vec<Span<'a>>> = vec![Vec::new()];
+    let mut line_width = 0;
+
+    for span in cell {
+        for word in span.content.split_inclusive(' ') {
+            let trimmed = word.trim_end_matches(' ');
+            let has_space = trimmed.len() != word.len();
+
+            if !trimmed.is_empty() {
+                if line_width > 0 && line_width + trimmed.width() > width {
+                    lines.push(Vec::new());
+                    line_width = 0;
+                }
+
+                let mut rest = trimmed;
+                loop {
+                    let (mut head, mut tail) =
+                        split_at_width(rest, width.saturating_sub(line_width));
+                    if head.is_empty() {
+                        if line_width > 0 {
+                            lines.push(Vec::new());
+                            line_width = 0;
+                            continue;
+                        }
+                        // A single character wider than the column; let it overflow.
+                        let len = rest.chars().next().map_or(0, char::len_utf8);
+                        (head, tail) = rest.split_at(len);
+                    }
+                    line_width += head.width();
+                    if let Some(line) = lines.last_mut() {
+                        line.push(Span::styled(head.to_string(), span.style));
+                    }
+                    rest = tail;
+                    if rest.is_empty() {
+                        break;
+                    }
+                    lines.push(Vec::new());
+                    line_width = 0;
+                }
+            }
+
+            // Keep the space between words, but never at the start or end of a line
+            if has_space && line_width > 0 && line_width < width {
+                if let Some(line) = lines.last_mut() {
+                    line.push(Span::styled(" ", span.style));
+                }
+                line_width += 1;
+            }
+        }
+    }
+
+    for line in &mut lines {
+        trim_line_end(line);
+    }
+    lines
+}
+
+/// Split `text` so that the head is at most `width` columns wide.
+fn split_at_width(text: &str, width: usize) -> (&str, &str) {
+    let mut used = 0;
+    for (idx, ch) in text.char_indices() {
+        let ch_width = unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0);
+        if used + ch_width > width {
+            return text.split_at(idx);
+        }
+        used += ch_width;
+    }
+    (text, "")
+}
+
+fn trim_line_end(line: &mut Vec<Span<'_>>) {
+    while let Some(last) = line.last_mut() {
+        let trimmed = last.content.trim_end();
+        if trimmed.is_empty() {
+            line.pop();
+        } else {
+            if trimmed.len() != last.content.len() {
+                last.content = trimmed.to_string().into();
+            }
+            break;
+        }
+    }
+}
+
+/// Pad one wrapped line of a cell to `width` according to the column alignment.
+fn pad_cell<'a>(
+    content: Vec<Span<'a>>,
+    width: usize,
+    alignment: pulldown_cmark::Alignment,
+    base_style: Style,
+) -> Vec<Span<'a>> {
+    let content_width: usize = content
+        .iter()
+        .map(|span| span.content.as_ref().width())
+        .sum();
+    let padding = width.saturating_sub(content_width);
+    let (left, right) = match alignment {
+        pulldown_cmark::Alignment::None | pulldown_cmark::Alignment::Left => (0, padding),
+        pulldown_cmark::Alignment::Center => (padding / 2, padding - padding / 2),
+        pulldown_cmark::Alignment::Right => (padding, 0),
+    };
+
+    let mut spans = Vec::with_capacity(content.len() + 2);
+    if left > 0 {
+        spans.push(Span::styled(" ".repeat(left), base_style));
+    }
+    spans.extend(
+        content
+            .into_iter()
+            .map(|span| Span::styled(span.content, base_style.patch(span.style))),
+    );
+    if right > 0 {
+        spans.push(Span::styled(" ".repeat(right), base_style));
+    }
+    spans
+}
+
+/// Byte offsets where `text` can be split into independently rendered chunks.
+///
+/// A split point is the start of a complete, unindented line that follows a
+/// blank line outside any code fence and can't continue the block before it.
+/// List items, block quotes and reference definitions may tie into earlier
+/// text, so they never start a chunk. Everything before a split point renders
+/// the same whatever is appended later, which lets a streaming message keep
+/// the layout of all but its last chunk.
+pub fn chunk_boundaries(text: &str) -> Vec<usize> {
+    let mut boundaries = Vec::new();
+    let mut fence: Option<Fence> = None;
+    let mut previous_blank = false;
+    let mut seen_content = false;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let content = line.trim_end_matches(['\n', '\r']);
+
+        if let Some(open) = fence {
+            if open.is_closed_by(content) {
+                fence = None;
+            }
+            previous_blank = false;
+            continue;
+        }
+
+        let blank = content.trim().is_empty();
+        if !blank
+            && line.ends_with('\n')
+            && previous_blank
+            && seen_content
+            && starts_independent_block(content)
+        {
+            boundaries.push(start);
+        }
+        fence = Fence::open(content);
+        seen_content |= !blank;
+        previous_blank = blank;
+    }
+
+    boundaries
+}
+
+/// Patch markdown that is still streaming so half-written constructs render
+/// the way they will once complete, rather than flashing as literal text.
+///
+/// An unfinished fence line is held back until its newline arrives. In the
+/// trailing paragraph, open code spans, strong and strikethrough markers are
+/// closed, or dropped when nothing follows them yet.
+pub fn close_partial_markdown(text: &str) -> Cow<'_, str> {
+    let (body, last_line) = match text.rfind('\n') {
+        Some(idx) => text.split_at(idx + 1),
+        None => ("", text),
+    };
+    // "``" may become an opening fence and "```" a closing one; either way the
+    // line means nothing until it is complete.
+    if fence_run(last_line).is_some_and(|(_, len, rest)| len >= 3 || rest.trim().is_empty()) {
+        return close_partial_markdown(body);
+    }
+
+    let mut fence: Option<Fence> = None;
+    let mut paragraph_start = 0;
+    let mut offset = 0;
+    for line in body.split_inclusive('\n') {
+        offset += line.len();
+        let content = line.trim_end_matches(['\n', '\r']);
+        match fence {
+            Some(open) => {
+                if open.is_closed_by(content) {
+                    fence = None;
+                    paragraph_start = offset;
+                }
+            }
+            None => {
+                fence = Fence::open(content);
+                if fence.is_some() || content.trim().is_empty() {
+                    paragraph_start = offset;
+                }
+            }
+        }
+    }
+    if fence.is_some() {
+        // An unterminated code block already renders as code up to the end.
+        return Cow::Borrowed(text);
+    }
+
+    let paragraph = &text[paragraph_start..];
+    let (outside_code, open_span) = scan_code_spans(paragraph);
+    let mut end = text.len();
+    let mut closers = String::new();
+
+    if let Some((span_start, span_len)) = open_span {
+        let opener_end = paragraph_start + span_start + span_len;
+        if text[opener_end..].trim().is_empty() {
+            end = paragraph_start + span_start;
+        } else {
+            closers.push_str(&"`".repeat(span_len));
+        }
+    }
+
+    // Close the most recently opened marker first so the nesting stays valid.
+    let mut open_markers: Vec<(usize, &str)> = ["**", "~~"]
+        .into_iter()
+        .filter(|marker| outside_code.matches(marker).count() % 2 == 1)
+        .map(|marker| (outside_code.rfind(marker).unwrap_or(0), marker))
+        .collect();
+    open_markers.sort_by(|a, b| b.0.cmp(&a.0));
+    for (_, marker) in open_markers {
+        let kept = text[..end].trim_end();
+        if open_span.is_none() && closers.is_empty() && kept.ends_with(marker) {
+            end = kept.len() - marker.len();
+        } else {
+            closers.push_str(marker);
+        }
+    }
+
+    if end == text.len() && closers.is_empty() {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(format!("{}{closers}", text[..end].trim_end()))
+    }
+}
+
+/// An open fenced code block: its marker character and run length.
+#[derive(Debug, Clone, Copy)]
+struct Fence {
+    marker: char,
+    len: usize,
+}
+
+impl Fence {
+    fn open(line: &str) -> Option<Self> {
+        let (marker, len, info) = fence_run(line)?;
+        // Backtick fences can't carry backticks in their info string.
+        if len < 3 || (marker == '`' && info.contains('`')) {
+            return None;
+        }
+        Some(Self { marker, len })
+    }
+
+    fn is_closed_by(self, line: &str) -> bool {
+        fence_run(line).is_some_and(|(marker, len, rest)| {
+            marker == self.marker && len >= self.len && rest.trim().is_empty()
+        })
+    }
+}
+
+/// Leading run of backticks or tildes after at most three spaces of indent,
+/// with the rest of the line.
+fn fence_run(line: &str) -> Option<(char, usize, &str)> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let marker = trimmed.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let len = trimmed.len() - trimmed.trim_start_matches(marker).len();
+    Some((marker, len, &trimmed[len..]))
+}
+
+/// Whether a line after a blank line starts a block that can't belong to
+/// whatever came before it.
+fn starts_independent_block(line: &str) -> bool {
+    let Some(first) = line.chars().next() else {
+        return false;
+    };
+    if first.is_whitespace() || first == '>' {
+        return false;
+    }
+    if line.starts_with('[') && line.contains("]:") {
+        return false;
+    }
+    let is_bullet = matches!(first, '-' | '*' | '+')
+        && line[1..].chars().next().is_none_or(char::is_whitespace);
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let is_ordered = (1..=9).contains(&digits)
+        && line[digits..].starts_with(['.', ')'])
+        && line[digits + 1..]
+            .chars()
+            .next()
+            .is_none_or(char::is_whitespace);
+    !is_bullet && !is_ordered
+}
+
+/// Split a paragraph into its text outside code spans and the byte range of a
+/// code span that is still open at the end, if any.
+fn scan_code_spans(paragraph: &str) -> (String, Option<(usize, usize)>) {
+    let bytes = paragraph.as_bytes();
+    let mut outside = String::new();
+    let mut open: Option<(usize, usize)> = None;
+    let mut segment_start = 0;
+    let mut idx = 0;
+
+    while idx < bytes.len() {
+        if bytes[idx] != b'`' {
+            idx += 1;
+            continue;
+        }
+        let run_start = idx;
+        while idx < bytes.len() && bytes[idx] == b'`' {
+            idx += 1;
+        }
+        let run_len = idx - run_start;
+        match open {
+            None => {
+                outside.push_str(&paragraph[segment_start..run_start]);
+                open = Some((run_start, run_len));
+            }
+            Some((_, len)) if len == run_len => {
+                open = None;
+                segment_start = idx;
+            }
+            Some(_) => {}
+        }
+    }
+    if open.is_none() {
+        outside.push_str(&paragraph[segment_start..]);
+    }
+
+    (outside, open)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1231,13 +1743,13 @@ Mixed list:
             line.line
                 .spans
                 .iter()
-                .any(|span| span.content.contains("[✓]"))
+                .any(|span| span.content.contains(TASK_CHECKED_GLYPH))
         });
         let has_unchecked = rendered.lines.iter().any(|line| {
             line.line
                 .spans
                 .iter()
-                .any(|span| span.content.contains("[ ]"))
+                .any(|span| span.content.contains(TASK_UNCHECKED_GLYPH))
         });
         assert!(has_checked, "Should contain checked checkboxes");
         assert!(has_unchecked, "Should contain unchecked checkboxes");
@@ -1679,4 +2191,225 @@ Also test with other inline formatting:
             "Should have at least 3 nested bullet points"
         );
     }
+
+    fn render_lines(markdown: &str, width: Option<u16>) -> Vec<String> {
+        let theme = Theme::default();
+        let styles = MarkdownStyles::from_theme(&theme);
+        from_str_with_width(markdown, &styles, &theme, width)
+            .lines
+            .iter()
+            .map(|line| {
+                line.line
+                    .spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_task_list_checkbox_glyphs() {
+        assert_eq!(
+            render_lines("- [x] Ship it\n- [ ] Write tests", None),
+            vec!["☑ Ship it", "☐ Write tests"]
+        );
+        assert_eq!(
+            render_lines("1. [x] First\n2. [ ] Second", None),
+            vec!["1. ☑ First", "2. ☐ Second"]
+        );
+        assert_eq!(
+            render_lines("- [ ] outer\n    - [x] inner", None),
+            vec!["☐ outer", "    ☑ inner"]
+        );
+    }
+
+    #[test]
+    fn test_task_list_wrapping_aligns_after_checkbox() {
+        use crate::tui::widgets::formatters::helpers::style_wrap_with_indent;
+
+        let theme = Theme::default();
+        let styles = MarkdownStyles::from_theme(&theme);
+        let rendered = from_str("- [ ] aaaa bbbb cccc", &styles, &theme);
+        let ml = &rendered.lines[0];
+        assert_eq!(ml.indent_level, 2);
+
+        let got: Vec<String> = style_wrap_with_indent(ml.line.clone(), 8, ml.indent_level)
+            .into_iter()
+            .map(|ln| ln.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(got, vec!["☐ aaaa ", "  bbbb ", "  cccc"]);
+    }
+
+    #[test]
+    fn test_table_keeps_code_span_style_in_cells() {
+        let markdown = "| Name | Cmd |\n|------|-----|\n| test | `cargo test` |";
+        assert_eq!(
+            render_lines(markdown, Some(80)),
+            vec![
+                "┌──────┬────────────┐",
+                "│ Name │ Cmd        │",
+                "├──────┼────────────┤",
+                "│ test │ cargo test │",
+                "└──────┴────────────┘",
+            ]
+        );
+
+        let theme = Theme::default();
+        let styles = MarkdownStyles::from_theme(&theme);
+        let rendered = from_str_with_width(markdown, &styles, &theme, Some(80));
+        assert!(rendered.lines.iter().all(|line| line.no_wrap));
+        let code_span = rendered.lines[3]
+            .line
+            .spans
+            .iter()
+            .find(|span| span.content == "cargo")
+            .expect("code span should be rendered");
+        assert_eq!(code_span.style, styles.table_cell.patch(styles.code));
+    }
+
+    #[test]
+    fn test_table_wraps_cells_when_too_wide() {
+        let markdown =
+            "| Key | Description |\n|-----|-------------|\n| a | the quick brown fox jumps |";
+        assert_eq!(
+            render_lines(markdown, Some(30)),
+            vec![
+                "┌─────┬──────────────────────┐",
+                "│ Key │ Description          │",
+                "├─────┼──────────────────────┤",
+                "│ a   │ the quick brown fox  │",
+                "│     │ jumps                │",
+                "└─────┴──────────────────────┘",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_table_drops_borders_when_terminal_is_narrow() {
+        let markdown = "| A | B | C | D |\n|---|---|---|---|\n\
+                        | alpha beta | gamma delta | epsilon | zeta eta |";
+        assert_eq!(
+            render_lines(markdown, Some(30)),
+            vec![
+                "A       B       C       D     ",
+                "──────  ──────  ──────  ──────",
+                "alpha   gamma   epsilo  zeta  ",
+                "beta    delta   n       eta   ",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_very_wide_table_elides_trailing_columns() {
+        let markdown = "| c1 | c2 | c3 | c4 | c5 | c6 | c7 | c8 |\n\
+                        |----|----|----|----|----|----|----|----|\n\
+                        | x | x | x | x | x | x | x | x |";
+        assert_eq!(
+            render_lines(markdown, Some(24)),
+            vec![
+                "c1  c2  c3  c4  c5  …",
+                "──  ──  ──  ──  ──  ─",
+                "x   x   x   x   x   …",
+                "… 3 more columns not shown",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_table_layout_never_exceeds_budget() {
+        for budget in 1..60 {
+            let layout = TableLayout::fit(&[12, 30, 4, 18], budget);
+            let gaps = PLAIN_COLUMN_GAP.len() * layout.widths.len().saturating_sub(1);
+            let total = match layout.style {
+                TableStyle::Bordered => {
+                    layout.widths.iter().sum::<usize>() + 3 * layout.widths.len() + 1
+                }
+                TableStyle::Plain if layout.hidden_columns > 0 => {
+                    layout.widths.iter().sum::<usize>() + gaps + PLAIN_COLUMN_GAP.len() + 1
+                }
+                TableStyle::Plain => layout.widths.iter().sum::<usize>() + gaps,
+            };
+            assert!(
+                total <= budget || layout.widths == vec![1],
+                "budget {budget}: {layout:?} needs {total} columns"
+            );
+        }
+    }
+
+    #[test]
+    fn test_chunk_boundaries_split_after_blank_lines() {
+        let text = "# Title\n\nFirst paragraph.\n\nSecond paragraph.\n";
+        let boundaries = chunk_boundaries(text);
+        let chunks: Vec<&str> = std::iter::once(0)
+            .chain(boundaries.iter().copied())
+            .zip(
+                boundaries
+                    .iter()
+                    .copied()
+                    .chain(std::iter::once(text.len())),
+            )
+            .map(|(start, end)| &text[start..end])
+            .collect();
+        assert_eq!(
+            chunks,
+            vec!["# Title\n\n", "First paragraph.\n\n", "Second paragraph.\n"]
+        );
+    }
+
+    #[test]
+    fn test_chunk_boundaries_never_split_inside_fences_or_blocks() {
+        let fenced = "```rust\nfn a() {}\n\nfn b() {}\n```\n";
+        assert!(chunk_boundaries(fenced).is_empty());
+
+        let list = "- one\n\n- two\n\n1. three\n";
+        assert!(chunk_boundaries(list).is_empty());
+
+        let quote = "> first\n\n> second\n";
+        assert!(chunk_boundaries(quote).is_empty());
+
+        let indented = "Intro\n\n    code\n";
+        assert!(chunk_boundaries(indented).is_empty());
+
+        // The last line may still grow into a list marker.
+        let partial = "Intro\n\nNext";
+        assert!(chunk_boundaries(partial).is_empty());
+    }
+
+    #[test]
+    fn test_close_partial_markdown_holds_back_unfinished_fence_lines() {
+        assert_eq!(close_partial_markdown("Some code:\n``"), "Some code:\n");
+        assert_eq!(close_partial_markdown("Some code:\n```py"), "Some code:\n");
+        assert_eq!(
+            close_partial_markdown("```py\nprint(1)\n``"),
+            "```py\nprint(1)\n"
+        );
+        assert_eq!(
+            close_partial_markdown("```py\nprint(**kwargs"),
+            "```py\nprint(**kwargs"
+        );
+    }
+
+    #[test]
+    fn test_close_partial_markdown_closes_inline_markers() {
+        assert_eq!(close_partial_markdown("Use `cargo te"), "Use `cargo te`");
+        assert_eq!(close_partial_markdown("Use `"), "Use");
+        assert_eq!(close_partial_markdown("This is **very"), "This is **very**");
+        assert_eq!(close_partial_markdown("This is **"), "This is");
+        assert_eq!(close_partial_markdown("~~old **new"), "~~old **new**~~");
+        assert_eq!(close_partial_markdown("`a ** b` done"), "`a ** b` done");
+        assert_eq!(
+            close_partial_markdown("**Done** and dusted"),
+            "**Done** and dusted"
+        );
+    }
+
+    #[test]
+    fn test_close_partial_markdown_only_looks_at_trailing_paragraph() {
+        let text = "A stray ** marker.\n\nNext **bold";
+        assert_eq!(
+            close_partial_markdown(text),
+            "A stray ** marker.\n\nNext **bold**"
+        );
+    }
 }
@@ -1,3 +1,4 @@
+pub mod best_of_panel;
 pub mod chat_list_state;
 pub mod chat_widgets;
 pub mod clipping;
@@ -11,7 +12,8 @@ pub mod popup_list;
 pub mod setup;
 pub mod status_bar;
 
-pub use chat_list_state::{ChatListState, ScrollTarget, ViewMode, VisibleRange};
+pub use best_of_panel::BestOfPanel;
+pub use chat_list_state::{ChatListState, CodeWrap, ScrollTarget, ViewMode, VisibleRange};
 pub use chat_widgets::{
     chat_widget::{ChatBlock, ChatRenderable, DynamicChatWidget, HeightCache, ParagraphWidget},
     slash_input::SlashInputWidget,
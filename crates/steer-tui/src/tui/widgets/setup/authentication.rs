@@ -54,7 +54,14 @@ impl AuthenticationWidget {
         let mut content = vec![];
 
         match state.auth_progress.as_ref() {
-            Some(AuthProgress::OAuthStarted { auth_url }) => {
+            Some(
+                progress @ (AuthProgress::OAuthStarted { .. }
+                | AuthProgress::WaitingForCallback { .. }),
+            ) => {
+                let auth_url = match progress {
+                    AuthProgress::OAuthStarted { auth_url } => auth_url.as_str(),
+                    _ => state.auth_url.as_deref().unwrap_or_default(),
+                };
                 content.push(Line::from(""));
                 content.push(Line::from(Span::styled(
                     "OAuth Authentication",
@@ -97,6 +104,40 @@ impl AuthenticationWidget {
                         theme.style(Component::SetupInputValue),
                     ),
                 ]));
+                if let AuthProgress::WaitingForCallback { elapsed } = progress {
+                    content.push(Line::from(""));
+                    content.push(Line::from(Span::styled(
+                        format!("Waiting for OAuth callback... ({}s)", elapsed.as_secs()),
+                        theme.style(Component::SetupHint),
+                    )));
+                }
+            }
+            Some(AuthProgress::DeviceCode {
+                verification_uri,
+                user_code,
+            }) => {
+                content.push(Line::from(""));
+                content.push(Line::from(Span::styled(
+                    "Device Code Authentication",
+                    theme.style(Component::SetupHeader),
+                )));
+                content.push(Line::from(""));
+                content.push(Line::from("On any device, visit this URL:"));
+                content.push(Line::from(""));
+                content.push(Line::from(Span::styled(
+                    verification_uri,
+                    theme.style(Component::SetupUrl),
+                )));
+                content.push(Line::from(""));
+                content.push(Line::from(vec![
+                    Span::raw("and enter the code: "),
+                    Span::styled(user_code, theme.style(Component::SetupInputValue)),
+                ]));
+                content.push(Line::from(""));
+                content.push(Line::from(Span::styled(
+                    "Waiting for authorization...",
+                    theme.style(Component::SetupHint),
+                )));
             }
             Some(AuthProgress::NeedInput { prompt }) => {
                 content.push(Line::from(""));
@@ -126,6 +167,14 @@ impl AuthenticationWidget {
                 content.push(Line::from(""));
                 content.push(Line::from(message.clone()));
             }
+            Some(AuthProgress::ExchangingCode) => {
+                content.push(Line::from(""));
+                content.push(Line::from("Exchanging authorization code for tokens..."));
+            }
+            Some(AuthProgress::Refreshing) => {
+                content.push(Line::from(""));
+                content.push(Line::from("Refreshing saved credentials..."));
+            }
             Some(AuthProgress::Complete) => {
                 content.push(Line::from(""));
                 content.push(Line::from("Authentication complete."));
@@ -169,7 +218,11 @@ impl AuthenticationWidget {
 
         // Instructions
         let instructions = match state.auth_progress.as_ref() {
-            Some(AuthProgress::OAuthStarted { .. } | AuthProgress::NeedInput { .. }) => {
+            Some(
+                AuthProgress::OAuthStarted { .. }
+                | AuthProgress::WaitingForCallback { .. }
+                | AuthProgress::NeedInput { .. },
+            ) => {
                 vec![Line::from(vec![
                     Span::raw("Type or paste input, "),
                     Span::styled("Enter", theme.style(Component::SetupKeyBinding)),
@@ -24,6 +24,8 @@ pub struct StatusBar<'a> {
     theme: &'a Theme,
     update: UpdateBadge<'a>,
     context_remaining_percent: Option<f64>,
+    branch: Option<&'a str>,
+    toast: Option<&'a str>,
 }
 
 impl<'a> StatusBar<'a> {
@@ -39,6 +41,8 @@ impl<'a> StatusBar<'a> {
             theme,
             update: UpdateBadge::None,
             context_remaining_percent: None,
+            branch: None,
+            toast: None,
         }
     }
 
@@ -54,6 +58,17 @@ impl<'a> StatusBar<'a> {
         self.context_remaining_percent = context_remaining_percent;
         self
     }
+
+    pub fn with_branch(mut self, branch: Option<&'a str>) -> Self {
+        self.branch = branch;
+        self
+    }
+
+    /// A short-lived confirmation such as "Copied code block 1/2".
+    pub fn with_toast(mut self, toast: Option<&'a str>) -> Self {
+        self.toast = toast;
+        self
+    }
 }
 
 fn format_context_remaining_percent(context_remaining_percent: f64) -> String {
@@ -67,6 +82,12 @@ impl Widget for StatusBar<'_> {
 
         // Left: update badge (if any) + current agent
         let mut left_spans = Vec::new();
+        if let Some(toast) = self.toast {
+            left_spans.push(Span::styled(
+                format!(" ✓ {toast} "),
+                self.theme.style(Component::NoticeInfo),
+            ));
+        }
         if let UpdateBadge::Available { latest } = self.update {
             left_spans.push(Span::styled(
                 format!(" v{latest} available "),
@@ -78,6 +99,10 @@ impl Widget for StatusBar<'_> {
             None => " -- ".to_string(),
         };
         left_spans.push(Span::raw(agent_text));
+        if let Some(branch) = self.branch {
+            left_spans.push(Span::styled("│ ", self.theme.style(Component::DimText)));
+            left_spans.push(Span::raw(format!("{branch} ")));
+        }
         let left_line = Line::from(left_spans);
         let left_para = Paragraph::new(left_line)
             .style(style)
@@ -3,97 +3,47 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
 use tonic::transport::Channel;
+use tracing::warn;
 
+use steer_proto::convert;
 use steer_proto::remote_workspace::v1::{
+    ApplyEditBatchRequest as ProtoApplyEditBatchRequest,
     ApplyEditsRequest as ProtoApplyEditsRequest, AstGrepRequest as ProtoAstGrepRequest,
-    EditOperation as ProtoEditOperation, GetEnvironmentInfoRequest, GetEnvironmentInfoResponse,
-    GlobRequest as ProtoGlobRequest, GrepRequest as ProtoGrepRequest,
-    ListDirectoryRequest as ProtoListDirectoryRequest, ListFilesRequest,
-    ReadFileRequest as ProtoReadFileRequest, WriteFileRequest as ProtoWriteFileRequest,
+    EditOperation as ProtoEditOperation, GetCapabilitiesRequest, GetCapabilitiesResponse,
+    GetEnvironmentInfoRequest, GetEnvironmentInfoResponse, GlobRequest as ProtoGlobRequest,
+    GrepRequest as ProtoGrepRequest, ListDirectoryRequest as ProtoListDirectoryRequest,
+    ListFilesRequest, PROTOCOL_VERSION, ReadFileRequest as ProtoReadFileRequest,
+    SearchFilesRequest, WriteFileRequest as ProtoWriteFileRequest,
     edit_operation::MatchSelection as ProtoEditMatchSelection,
+    file_edit_outcome::Status as ProtoFileEditStatus,
     remote_workspace_service_client::RemoteWorkspaceServiceClient,
 };
 use steer_tools::result::{
-    EditResult, FileContentResult, FileEntry, FileListResult, GlobResult, SearchMatch, SearchResult,
+    EditResult, FileContentResult, FileListResult, GlobResult, SearchResult,
 };
 use steer_workspace::{
-    ApplyEditsRequest, AstGrepRequest, EditMatchSelection, EnvironmentInfo, GitCommitSummary,
-    GitHead, GitStatus, GitStatusEntry, GitStatusSummary, GlobRequest, GrepRequest, JjChange,
-    JjChangeType, JjCommitSummary, JjStatus, ListDirectoryRequest, ReadFileRequest, RemoteAuth,
-    Result, VcsInfo, VcsKind, VcsStatus, Workspace, WorkspaceError, WorkspaceMetadata,
-    WorkspaceOpContext, WorkspaceType, WriteFileRequest,
+    ApplyEditBatchRequest, ApplyEditsRequest, AstGrepRequest, EditBatchResult, EditMatchSelection,
+    EnvironmentInfo, FileEditOutcome, FileEditStatus, FileSearchMatch, GitHead, GitStatusEntry,
+    GitStatusSummary, GitSummary, GitUpstream, GlobRequest, GrepRequest, JjChange, JjChangeType,
+    JjCommitSummary, JjStatus, ListDirectoryRequest, ReadFileRequest, RemoteAuth, Result, VcsInfo,
+    VcsKind, VcsStatus, Workspace, WorkspaceError, WorkspaceMetadata, WorkspaceOpContext,
+    WorkspaceType, WriteFileRequest,
 };
 
 const GRPC_MAX_MESSAGE_SIZE_BYTES: usize = 32 * 1024 * 1024;
 
-fn convert_search_result(proto_result: steer_proto::common::v1::SearchResult) -> SearchResult {
-    let matches = proto_result
-        .matches
-        .into_iter()
-        .map(|m| SearchMatch {
-            file_path: m.file_path,
-            line_number: m.line_number as usize,
-            line_content: m.line_content,
-            column_range: m
-                .column_range
-                .map(|cr| (cr.start as usize, cr.end as usize)),
-        })
-        .collect();
-
-    SearchResult {
-        matches,
-        total_files_searched: proto_result.total_files_searched as usize,
-        search_completed: proto_result.search_completed,
-    }
-}
-
-fn convert_file_list_result(
-    proto_result: steer_proto::common::v1::FileListResult,
-) -> FileListResult {
-    let entries = proto_result
-        .entries
-        .into_iter()
-        .map(|e| FileEntry {
-            path: e.path,
-            is_directory: e.is_directory,
-            size: e.size,
-            permissions: e.permissions,
-        })
-        .collect();
-
-    FileListResult {
-        entries,
-        base_path: proto_result.base_path,
-    }
-}
-
-fn convert_file_content_result(
-    proto_result: steer_proto::common::v1::FileContentResult,
-) -> FileContentResult {
-    FileContentResult {
-        content: proto_result.content,
-        file_path: proto_result.file_path,
-        line_count: proto_result.line_count as usize,
-        truncated: proto_result.truncated,
-    }
-}
-
-fn convert_edit_result(proto_result: steer_proto::common::v1::EditResult) -> EditResult {
-    EditResult {
-        file_path: proto_result.file_path,
-        changes_made: proto_result.changes_made as usize,
-        file_created: proto_result.file_created,
-        old_content: proto_result.old_content,
-        new_content: proto_result.new_content,
-    }
-}
-
-fn convert_glob_result(proto_result: steer_proto::common::v1::GlobResult) -> GlobResult {
-    GlobResult {
-        matches: proto_result.matches,
-        pattern: proto_result.pattern,
-    }
-}
+/// Operations that existed before capability negotiation was added.
+const LEGACY_OPERATIONS: &[&str] = &[
+    "environment_info",
+    "list_files",
+    "read_file",
+    "list_directory",
+    "glob",
+    "grep",
+    "astgrep",
+    "apply_edits",
+    "write_file",
+];
 
 /// Cached environment information with TTL
 #[derive(Debug, Clone)]
@@ -117,10 +67,53 @@ impl CachedEnvironment {
     }
 }
 
+/// Protocol details reported by a remote workspace server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteCapabilities {
+    pub protocol_version: u32,
+    pub server_version: Option<String>,
+    pub supported_operations: Vec<String>,
+}
+
+impl RemoteCapabilities {
+    /// Capabilities assumed for servers that predate `GetCapabilities`.
+    pub fn legacy() -> Self {
+        Self {
+            protocol_version: 0,
+            server_version: None,
+            supported_operations: LEGACY_OPERATIONS
+                .iter()
+                .map(|op| (*op).to_string())
+                .collect(),
+        }
+    }
+
+    pub fn supports(&self, operation: &str) -> bool {
+        self.supported_operations.iter().any(|op| op == operation)
+    }
+
+    /// Whether the server speaks the same protocol version as this client.
+    pub fn is_compatible(&self) -> bool {
+        self.protocol_version == PROTOCOL_VERSION
+    }
+}
+
+impl From<GetCapabilitiesResponse> for RemoteCapabilities {
+    fn from(response: GetCapabilitiesResponse) -> Self {
+        Self {
+            protocol_version: response.protocol_version,
+            server_version: (!response.server_version.is_empty())
+                .then_some(response.server_version),
+            supported_operations: response.supported_operations,
+        }
+    }
+}
+
 /// Remote workspace that executes tools and collects environment info via gRPC
 pub struct RemoteWorkspace {
     client: RemoteWorkspaceServiceClient<Channel>,
     environment_cache: Arc<RwLock<Option<CachedEnvironment>>>,
+    capabilities: Arc<RwLock<Option<RemoteCapabilities>>>,
     metadata: WorkspaceMetadata,
     #[allow(dead_code)]
     auth: Option<RemoteAuth>,
@@ -141,12 +134,60 @@ impl RemoteWorkspace {
             location: address.clone(),
         };
 
-        Ok(Self {
+        let workspace = Self {
             client,
             environment_cache: Arc::new(RwLock::new(None)),
+            capabilities: Arc::new(RwLock::new(None)),
             metadata,
             auth,
-        })
+        };
+
+        // A failure here is not cached, so the next `capabilities()` call retries.
+        match workspace.capabilities().await {
+            Ok(capabilities) => warn_on_version_skew(&address, &capabilities),
+            Err(e) => warn!(
+                address = %address,
+                error = %e,
+                "Failed to fetch remote workspace capabilities"
+            ),
+        }
+
+        Ok(workspace)
+    }
+
+    /// Capabilities reported by the remote server.
+    ///
+    /// The first successful response is cached until [`Self::invalidate_capabilities`]
+    /// is called. Transport errors are returned rather than cached so callers can
+    /// retry; servers that predate the RPC report [`RemoteCapabilities::legacy`].
+    pub async fn capabilities(&self) -> Result<RemoteCapabilities> {
+        if let Some(cached) = self.capabilities.read().await.as_ref() {
+            return Ok(cached.clone());
+        }
+
+        let mut client = self.client.clone();
+        let capabilities = match client
+            .get_capabilities(tonic::Request::new(GetCapabilitiesRequest {}))
+            .await
+        {
+            Ok(response) => RemoteCapabilities::from(response.into_inner()),
+            Err(status) if status.code() == tonic::Code::Unimplemented => {
+                RemoteCapabilities::legacy()
+            }
+            Err(status) => {
+                return Err(WorkspaceError::Status(format!(
+                    "Failed to get capabilities: {status}"
+                )));
+            }
+        };
+
+        *self.capabilities.write().await = Some(capabilities.clone());
+        Ok(capabilities)
+    }
+
+    /// Drop cached capabilities so the next call re-queries the server.
+    pub async fn invalidate_capabilities(&self) {
+        *self.capabilities.write().await = None;
     }
 
     /// Collect environment information from the remote workspace
@@ -184,7 +225,7 @@ impl RemoteWorkspace {
             };
 
             let status = match vcs.status {
-                Some(vcs_info::Status::GitStatus(status)) => {
+                Some(vcs_info::Status::GitSummary(status)) => {
                     let head = status.head.and_then(|head| {
                         let kind = ProtoGitHeadKind::try_from(head.kind).ok()?;
                         match kind {
@@ -221,19 +262,17 @@ impl RemoteWorkspace {
                         })
                         .collect();
 
-                    let recent_commits = status
-                        .recent_commits
-                        .into_iter()
-                        .map(|commit| GitCommitSummary {
-                            id: commit.id,
-                            summary: commit.summary,
-                        })
-                        .collect();
+                    let upstream = status.upstream.map(|upstream| GitUpstream {
+                        name: upstream.name,
+                        ahead: upstream.ahead,
+                        behind: upstream.behind,
+                    });
 
-                    VcsStatus::Git(GitStatus {
+                    VcsStatus::Git(GitSummary {
                         head,
+                        upstream,
                         entries,
-                        recent_commits,
+                        omitted_entries: status.omitted_entries,
                         error: status.error,
                     })
                 }
@@ -281,7 +320,7 @@ impl RemoteWorkspace {
                 }
                 None => match kind {
                     VcsKind::Git => {
-                        VcsStatus::Git(GitStatus::unavailable("missing git status".to_string()))
+                        VcsStatus::Git(GitSummary::unavailable("missing git status".to_string()))
                     }
                     VcsKind::Jj => {
                         VcsStatus::Jj(JjStatus::unavailable("missing jj status".to_string()))
@@ -309,6 +348,60 @@ impl RemoteWorkspace {
     }
 }
 
+fn warn_on_version_skew(address: &str, capabilities: &RemoteCapabilities) {
+    if !capabilities.is_compatible() {
+        warn!(
+            address = %address,
+            server_protocol = capabilities.protocol_version,
+            client_protocol = PROTOCOL_VERSION,
+            server_version = capabilities.server_version.as_deref().unwrap_or("unknown"),
+            "Remote workspace protocol version differs from client; some operations may fail"
+        );
+    }
+}
+
+fn apply_edits_request_to_proto(request: ApplyEditsRequest) -> Result<ProtoApplyEditsRequest> {
+    let edits = request
+        .edits
+        .into_iter()
+        .map(|edit| {
+            let match_selection = match edit.match_selection {
+                Some(EditMatchSelection::ExactlyOne) => Some(ProtoEditMatchSelection::ExactlyOne(
+                    steer_proto::remote_workspace::v1::EditMatchExactlyOne {},
+                )),
+                Some(EditMatchSelection::First) => Some(ProtoEditMatchSelection::First(
+                    steer_proto::remote_workspace::v1::EditMatchFirst {},
+                )),
+                Some(EditMatchSelection::All) => Some(ProtoEditMatchSelection::All(
+                    steer_proto::remote_workspace::v1::EditMatchAll {},
+                )),
+                Some(EditMatchSelection::Nth { match_index }) => {
+                    let match_index = match_index.ok_or_else(|| {
+                        WorkspaceError::Status(
+                            "match_index is required when match_selection is nth".to_string(),
+                        )
+                    })?;
+                    Some(ProtoEditMatchSelection::Nth(
+                        steer_proto::remote_workspace::v1::EditMatchNth { match_index },
+                    ))
+                }
+                None => None,
+            };
+
+            Ok(ProtoEditOperation {
+                old_string: edit.old_string,
+                new_string: edit.new_string,
+                match_selection,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(ProtoApplyEditsRequest {
+        file_path: request.file_path,
+        edits,
+        allow_partial: request.allow_partial,
+    })
+}
+
 impl std::fmt::Debug for RemoteWorkspace {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RemoteWorkspace")
@@ -382,6 +475,43 @@ impl Workspace for RemoteWorkspace {
         Ok(all_files)
     }
 
+    async fn search_files(
+        &self,
+        query: &str,
+        max_results: Option<usize>,
+    ) -> Result<Vec<FileSearchMatch>> {
+        if !self.capabilities().await?.supports("search_files") {
+            // Older servers can only list; rank the full list here so scores match.
+            let files = self.list_files(None, None).await?;
+            return Ok(steer_workspace::utils::FileListingUtils::rank_paths(
+                files,
+                query,
+                max_results,
+            ));
+        }
+
+        let mut client = self.client.clone();
+        let request = tonic::Request::new(SearchFilesRequest {
+            query: query.to_string(),
+            max_results: max_results.unwrap_or(0) as u32,
+        });
+
+        let response = client
+            .search_files(request)
+            .await
+            .map_err(|e| WorkspaceError::Status(format!("Failed to search files: {e}")))?
+            .into_inner();
+
+        Ok(response
+            .matches
+            .into_iter()
+            .map(|m| FileSearchMatch {
+                path: m.path,
+                score: m.score,
+            })
+            .collect())
+    }
+
     fn working_directory(&self) -> &std::path::Path {
         // For remote workspaces, we return a placeholder path
         // The actual working directory is on the remote machine
@@ -399,13 +529,14 @@ impl Workspace for RemoteWorkspace {
             offset: request.offset,
             limit: request.limit,
             raw: request.raw,
+            max_bytes: request.max_bytes,
         });
         let response = client
             .read_file(request)
             .await
             .map_err(|e| WorkspaceError::Status(format!("Failed to read file: {e}")))?
             .into_inner();
-        Ok(convert_file_content_result(response))
+        Ok(convert::proto_to_file_content_result(response))
     }
 
     async fn list_directory(
@@ -413,17 +544,34 @@ impl Workspace for RemoteWorkspace {
         request: ListDirectoryRequest,
         _ctx: &WorkspaceOpContext,
     ) -> Result<FileListResult> {
+        let supports_paging = self.capabilities().await?.supports("list_directory_paging");
+        let (offset, limit) = (request.offset, request.limit);
+
         let mut client = self.client.clone();
-        let request = tonic::Request::new(ProtoListDirectoryRequest {
+        let proto_request = tonic::Request::new(ProtoListDirectoryRequest {
             path: request.path,
             ignore: request.ignore.unwrap_or_default(),
+            offset: offset.map(|offset| offset as u64),
+            limit: limit.map(|limit| limit as u64),
+            respect_gitignore: request.respect_gitignore,
         });
         let response = client
-            .list_directory(request)
+            .list_directory(proto_request)
             .await
             .map_err(|e| WorkspaceError::Status(format!("Failed to list directory: {e}")))?
             .into_inner();
-        Ok(convert_file_list_result(response))
+        let mut result = convert::proto_to_file_list_result(response);
+
+        if !supports_paging {
+            // Older servers return the whole (already sorted) listing; page it here.
+            let start = offset.unwrap_or(0).min(result.entries.len());
+            let end = limit.map_or(result.entries.len(), |limit| {
+                start.saturating_add(limit).min(result.entries.len())
+            });
+            result.truncated = end < result.entries.len();
+            result.entries = result.entries.drain(start..end).collect();
+        }
+        Ok(result)
     }
 
     async fn glob(&self, request: GlobRequest, _ctx: &WorkspaceOpContext) -> Result<GlobResult> {
@@ -437,7 +585,7 @@ impl Workspace for RemoteWorkspace {
             .await
             .map_err(|e| WorkspaceError::Status(format!("Failed to glob: {e}")))?
             .into_inner();
-        Ok(convert_glob_result(response))
+        Ok(convert::proto_to_glob_result(response))
     }
 
     async fn grep(&self, request: GrepRequest, _ctx: &WorkspaceOpContext) -> Result<SearchResult> {
@@ -452,7 +600,7 @@ impl Workspace for RemoteWorkspace {
             .await
             .map_err(|e| WorkspaceError::Status(format!("Failed to grep: {e}")))?
             .into_inner();
-        Ok(convert_search_result(response))
+        Ok(convert::proto_to_search_result(response))
     }
 
     async fn astgrep(
@@ -473,7 +621,7 @@ impl Workspace for RemoteWorkspace {
             .await
             .map_err(|e| WorkspaceError::Status(format!("Failed to astgrep: {e}")))?
             .into_inner();
-        Ok(convert_search_result(response))
+        Ok(convert::proto_to_search_result(response))
     }
 
     async fn apply_edits(
@@ -481,53 +629,108 @@ impl Workspace for RemoteWorkspace {
         request: ApplyEditsRequest,
         _ctx: &WorkspaceOpContext,
     ) -> Result<EditResult> {
+        if request.allow_partial && !self.capabilities().await?.supports("apply_edits_partial") {
+            return Err(WorkspaceError::NotSupported(
+                "remote workspace does not support partial edit application".to_string(),
+            ));
+        }
+
+        let mut client = self.client.clone();
+        let request = tonic::Request::new(apply_edits_request_to_proto(request)?);
+        let response = client
+            .apply_edits(request)
+            .await
+            .map_err(|e| WorkspaceError::Status(format!("Failed to apply edits: {e}")))?
+            .into_inner();
+        Ok(convert::proto_to_edit_result(response))
+    }
+
+    async fn preview_edit(
+        &self,
+        request: ApplyEditsRequest,
+        _ctx: &WorkspaceOpContext,
+    ) -> Result<EditResult> {
+        let capabilities = self.capabilities().await?;
+        if !capabilities.supports("preview_edit") {
+            return Err(WorkspaceError::NotSupported(
+                "remote workspace does not support edit previews".to_string(),
+            ));
+        }
+        if request.allow_partial && !capabilities.supports("apply_edits_partial") {
+            return Err(WorkspaceError::NotSupported(
+                "remote workspace does not support partial edit application".to_string(),
+            ));
+        }
+
+        let mut client = self.client.clone();
+        let request = tonic::Request::new(apply_edits_request_to_proto(request)?);
+        let response = client
+            .preview_edit(request)
+            .await
+            .map_err(|e| WorkspaceError::Status(format!("Failed to preview edit: {e}")))?
+            .into_inner();
+        Ok(convert::proto_to_edit_result(response))
+    }
+
+    async fn apply_edit_batch(
+        &self,
+        request: ApplyEditBatchRequest,
+        _ctx: &WorkspaceOpContext,
+    ) -> Result<EditBatchResult> {
+        let capabilities = self.capabilities().await?;
+        if !capabilities.supports("apply_edit_batch") {
+            return Err(WorkspaceError::NotSupported(
+                "remote workspace does not support batched edits".to_string(),
+            ));
+        }
+        if request.files.iter().any(|file| file.allow_partial)
+            && !capabilities.supports("apply_edits_partial")
+        {
+            return Err(WorkspaceError::NotSupported(
+                "remote workspace does not support partial edit application".to_string(),
+            ));
+        }
+
         let mut client = self.client.clone();
-        let edits = request
-            .edits
+        let files = request
+            .files
             .into_iter()
-            .map(|edit| {
-                let match_selection = match edit.match_selection {
-                    Some(EditMatchSelection::ExactlyOne) => {
-                        Some(ProtoEditMatchSelection::ExactlyOne(
-                            steer_proto::remote_workspace::v1::EditMatchExactlyOne {},
-                        ))
-                    }
-                    Some(EditMatchSelection::First) => Some(ProtoEditMatchSelection::First(
-                        steer_proto::remote_workspace::v1::EditMatchFirst {},
-                    )),
-                    Some(EditMatchSelection::All) => Some(ProtoEditMatchSelection::All(
-                        steer_proto::remote_workspace::v1::EditMatchAll {},
-                    )),
-                    Some(EditMatchSelection::Nth { match_index }) => {
-                        let match_index = match_index.ok_or_else(|| {
-                            WorkspaceError::Status(
-                                "match_index is required when match_selection is nth".to_string(),
-                            )
-                        })?;
-                        Some(ProtoEditMatchSelection::Nth(
-                            steer_proto::remote_workspace::v1::EditMatchNth { match_index },
-                        ))
+            .map(apply_edits_request_to_proto)
+            .collect::<Result<Vec<_>>>()?;
+        let response = client
+            .apply_edit_batch(tonic::Request::new(ProtoApplyEditBatchRequest { files }))
+            .await
+            .map_err(|e| WorkspaceError::Status(format!("Failed to apply edit batch: {e}")))?
+            .into_inner();
+
+        let files = response
+            .files
+            .into_iter()
+            .map(|file| {
+                let status = match file.status {
+                    Some(ProtoFileEditStatus::Applied(result)) => FileEditStatus::Applied {
+                        result: convert::proto_to_edit_result(result),
+                    },
+                    Some(ProtoFileEditStatus::Failed(reason)) => FileEditStatus::Failed { reason },
+                    Some(ProtoFileEditStatus::NotApplied(_)) => FileEditStatus::NotApplied,
+                    None => {
+                        return Err(WorkspaceError::Status(format!(
+                            "missing edit status for {}",
+                            file.file_path
+                        )));
                     }
-                    None => None,
                 };
-
-                Ok(ProtoEditOperation {
-                    old_string: edit.old_string,
-                    new_string: edit.new_string,
-                    match_selection,
+                Ok(FileEditOutcome {
+                    file_path: file.file_path,
+                    status,
                 })
             })
             .collect::<Result<Vec<_>>>()?;
-        let request = tonic::Request::new(ProtoApplyEditsRequest {
-            file_path: request.file_path,
-            edits,
-        });
-        let response = client
-            .apply_edits(request)
-            .await
-            .map_err(|e| WorkspaceError::Status(format!("Failed to apply edits: {e}")))?
-            .into_inner();
-        Ok(convert_edit_result(response))
+
+        Ok(EditBatchResult {
+            applied: response.applied,
+            files,
+        })
     }
 
     async fn write_file(
@@ -545,7 +748,7 @@ impl Workspace for RemoteWorkspace {
             .await
             .map_err(|e| WorkspaceError::Status(format!("Failed to write file: {e}")))?
             .into_inner();
-        Ok(convert_edit_result(response))
+        Ok(convert::proto_to_edit_result(response))
     }
 }
 
@@ -569,6 +772,35 @@ mod tests {
         assert_eq!(metadata.location, address);
     }
 
+    #[test]
+    fn capabilities_from_response_detects_version_skew() {
+        let current = RemoteCapabilities::from(GetCapabilitiesResponse {
+            protocol_version: PROTOCOL_VERSION,
+            server_version: "1.2.3".to_string(),
+            supported_operations: vec!["read_file".to_string()],
+        });
+        assert!(current.is_compatible());
+        assert!(current.supports("read_file"));
+        assert!(!current.supports("grep"));
+        assert_eq!(current.server_version.as_deref(), Some("1.2.3"));
+
+        let newer = RemoteCapabilities::from(GetCapabilitiesResponse {
+            protocol_version: PROTOCOL_VERSION + 1,
+            server_version: String::new(),
+            supported_operations: Vec::new(),
+        });
+        assert!(!newer.is_compatible());
+        assert_eq!(newer.server_version, None);
+    }
+
+    #[test]
+    fn legacy_capabilities_assume_current_operations() {
+        let legacy = RemoteCapabilities::legacy();
+        assert!(!legacy.is_compatible());
+        assert!(legacy.supports("apply_edits"));
+        assert!(!legacy.supports("search_files"));
+    }
+
     #[test]
     fn test_convert_environment_response() {
         use std::path::PathBuf;
@@ -579,15 +811,20 @@ mod tests {
                 kind: steer_proto::remote_workspace::v1::VcsKind::Git as i32,
                 root: "/home/user/project".to_string(),
                 status: Some(
-                    steer_proto::remote_workspace::v1::vcs_info::Status::GitStatus(
-                        steer_proto::remote_workspace::v1::GitStatus {
+                    steer_proto::remote_workspace::v1::vcs_info::Status::GitSummary(
+                        steer_proto::remote_workspace::v1::GitSummary {
                             head: Some(steer_proto::remote_workspace::v1::GitHead {
                                 kind: steer_proto::remote_workspace::v1::GitHeadKind::Branch as i32,
                                 branch: Some("main".to_string()),
                             }),
                             entries: Vec::new(),
-                            recent_commits: Vec::new(),
                             error: None,
+                            upstream: Some(steer_proto::remote_workspace::v1::GitUpstream {
+                                name: "origin/main".to_string(),
+                                ahead: 1,
+                                behind: 0,
+                            }),
+                            omitted_entries: 0,
                         },
                     ),
                 ),
@@ -618,14 +855,8 @@ mod tests {
         assert_eq!(env_info.date, "2025-06-17");
         assert_eq!(env_info.directory_structure, "project/\nsrc/\nmain.rs\n");
         assert_eq!(
-            env_info
-                .vcs
-                .as_ref()
-                .map(|vcs| vcs.status.as_llm_string()),
-            Some(
-                "Current branch: main\n\nStatus:\nWorking tree clean\n\nRecent commits:\n<no commits>\n"
-                    .to_string()
-            )
+            env_info.vcs.as_ref().map(|vcs| vcs.status.as_llm_string()),
+            Some("Branch: main (origin/main: ahead 1, behind 0)\nWorking tree clean\n".to_string())
         );
         assert_eq!(env_info.readme_content, Some("# My Project".to_string()));
         assert_eq!(env_info.memory_file_content, None);
@@ -1,3 +1,9 @@
+#![expect(
+    clippy::expect_used,
+    clippy::panic,
+    reason = "benchmark setup has no caller to report to, so fixture failures abort the run"
+)]
+
 use criterion::{Criterion, criterion_group, criterion_main};
 use std::path::Path;
 use tempfile::TempDir;
@@ -5,12 +11,15 @@ use tokio::runtime::Runtime;
 use tokio_util::sync::CancellationToken;
 
 use steer_workspace::local::LocalWorkspace;
-use steer_workspace::{GrepRequest, Workspace, WorkspaceOpContext};
+use steer_workspace::{GlobRequest, GrepRequest, Workspace, WorkspaceOpContext};
 
 const FILES_IN_HIGH_DENSITY: usize = 100;
 const FILES_IN_SPARSE: usize = 120;
 const FILES_IN_NO_MATCH: usize = 120;
 const LINES_PER_FILE: usize = 120;
+const LARGE_TREE_DIRS: usize = 40;
+const LARGE_TREE_FILES_PER_DIR: usize = 50;
+const LARGE_TREE_IGNORED_FILES: usize = 2000;
 
 struct BenchDataset {
     _root: TempDir,
@@ -145,6 +154,54 @@ fn build_include_filtered(rt: &Runtime) -> BenchDataset {
     }
 }
 
+/// A repo-shaped tree: many nested source directories, a nested `.gitignore`,
+/// and a large ignored build directory that a correct walk never enters.
+fn build_large_tree(rt: &Runtime) -> std::io::Result<BenchDataset> {
+    let root = tempfile::tempdir()?;
+
+    std::fs::write(root.path().join(".gitignore"), "target/\n")?;
+    for dir in 0..LARGE_TREE_DIRS {
+        let crate_path = root.path().join(format!("crates/crate_{dir:02}"));
+        let dir_path = crate_path.join("src");
+        std::fs::create_dir_all(&dir_path)?;
+        std::fs::write(crate_path.join(".gitignore"), "*.generated.rs\n")?;
+
+        for file in 0..LARGE_TREE_FILES_PER_DIR {
+            let mut content = String::new();
+            for line in 0..LINES_PER_FILE {
+                if line == file && file % 2 == 0 {
+                    content.push_str(&format!("// needle {dir} {file}\n"));
+                } else {
+                    content.push_str(&format!("let value_{line} = {line};\n"));
+                }
+            }
+            std::fs::write(dir_path.join(format!("module_{file:03}.rs")), &content)?;
+            std::fs::write(
+                dir_path.join(format!("module_{file:03}.generated.rs")),
+                &content,
+            )?;
+        }
+    }
+
+    let ignored_dir = root.path().join("target/debug/build");
+    std::fs::create_dir_all(&ignored_dir)?;
+    for file in 0..LARGE_TREE_IGNORED_FILES {
+        std::fs::write(
+            ignored_dir.join(format!("artifact_{file:04}.rs")),
+            "// needle in build output\n",
+        )?;
+    }
+
+    let workspace = rt
+        .block_on(LocalWorkspace::with_path(root.path().to_path_buf()))
+        .map_err(std::io::Error::other)?;
+
+    Ok(BenchDataset {
+        _root: root,
+        workspace,
+    })
+}
+
 fn run_grep(
     rt: &Runtime,
     workspace: &LocalWorkspace,
@@ -163,12 +220,30 @@ fn run_grep(
         .block_on(workspace.grep(request, &context))
         .expect("grep benchmark request should succeed");
 
+    // High-density searches legitimately stop at the match cap.
     assert!(
-        result.search_completed,
+        result.search_completed || !result.matches.is_empty(),
         "benchmark grep unexpectedly cancelled"
     );
 }
 
+fn run_glob(rt: &Runtime, workspace: &LocalWorkspace, path: &Path, pattern: &str) {
+    let request = GlobRequest {
+        pattern: pattern.to_string(),
+        path: Some(path.to_string_lossy().to_string()),
+    };
+    let context = WorkspaceOpContext::new("bench-glob", CancellationToken::new());
+
+    let result = rt
+        .block_on(workspace.glob(request, &context))
+        .expect("glob benchmark request should succeed");
+
+    assert!(
+        !result.matches.is_empty(),
+        "benchmark glob unexpectedly matched nothing"
+    );
+}
+
 fn bench_grep(c: &mut Criterion) {
     let rt = Runtime::new().expect("failed to create tokio runtime for benchmarks");
 
@@ -176,6 +251,7 @@ fn bench_grep(c: &mut Criterion) {
     let sparse = build_sparse(&rt);
     let no_match = build_no_match(&rt);
     let include_filtered = build_include_filtered(&rt);
+    let large_tree = build_large_tree(&rt).expect("failed to build large_tree dataset");
 
     let mut group = c.benchmark_group("workspace_grep");
     group.sample_size(20);
@@ -228,6 +304,29 @@ fn bench_grep(c: &mut Criterion) {
         });
     });
 
+    group.bench_function("large_tree", |b| {
+        b.iter(|| {
+            run_grep(
+                &rt,
+                &large_tree.workspace,
+                large_tree.path(),
+                "needle",
+                None,
+            );
+        });
+    });
+
+    group.finish();
+
+    let mut group = c.benchmark_group("workspace_glob");
+    group.sample_size(20);
+
+    group.bench_function("large_tree", |b| {
+        b.iter(|| {
+            run_glob(&rt, &large_tree.workspace, large_tree.path(), "**/*.rs");
+        });
+    });
+
     group.finish();
 }
 
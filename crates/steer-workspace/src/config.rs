@@ -7,6 +7,9 @@ pub enum WorkspaceConfig {
     Local {
         /// Path to the workspace directory
         path: std::path::PathBuf,
+        /// Size caps for README and memory files inlined into the environment context
+        #[serde(default)]
+        context_limits: ContextFileLimits,
     },
     /// Remote workspace accessed via gRPC
     Remote {
@@ -17,6 +20,27 @@ pub enum WorkspaceConfig {
     },
 }
 
+/// Size caps for project documents (README.md, AGENTS.md/CLAUDE.md) that are
+/// inlined into the environment context on every request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextFileLimits {
+    /// Files larger than this are truncated at heading boundaries, keeping the
+    /// leading sections and any table of contents.
+    pub truncate_bytes: usize,
+    /// Files larger than this are not inlined at all; the model is pointed at
+    /// `read_file` instead.
+    pub omit_bytes: usize,
+}
+
+impl Default for ContextFileLimits {
+    fn default() -> Self {
+        Self {
+            truncate_bytes: 16 * 1024,
+            omit_bytes: 128 * 1024,
+        }
+    }
+}
+
 /// Authentication information for remote workspaces
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum RemoteAuth {
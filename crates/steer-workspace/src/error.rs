@@ -73,6 +73,43 @@ pub enum EditFailure {
         #[serde(default)]
         omitted_matches: usize,
     },
+
+    #[error(
+        "old_string for edit #{edit_index} in file {file_path} was changed by an earlier edit; match against the file as it will be after prior edits"
+    )]
+    StaleOldString {
+        file_path: String,
+        edit_index: usize,
+    },
+
+    #[error(
+        "edit #{edit_index} in file {file_path} overlaps text written by edit #{conflicting_edit_index}; combine them into a single edit"
+    )]
+    OverlappingEdit {
+        file_path: String,
+        edit_index: usize,
+        conflicting_edit_index: usize,
+    },
+
+    #[error(
+        "edit #{edit_index} and edit #{conflicting_edit_index} target overlapping text at line {line_number} of {file_path}; combine them into a single edit"
+    )]
+    ConflictingEdits {
+        file_path: String,
+        edit_index: usize,
+        conflicting_edit_index: usize,
+        line_number: usize,
+    },
+
+    #[error(
+        "old_string for edit #{edit_index} matches {occurrences} places in file {file_path} and edit #{conflicting_edit_index} rewrites one of them, so the result would depend on edit order; make old_string unique in the original file"
+    )]
+    AmbiguousEdit {
+        file_path: String,
+        edit_index: usize,
+        conflicting_edit_index: usize,
+        occurrences: usize,
+    },
 }
 
 #[derive(Error, Debug, Clone, Serialize, Deserialize)]
@@ -4,15 +4,17 @@ pub mod local;
 pub mod manager;
 pub mod ops;
 pub mod result;
+pub mod symbols;
 pub mod utils;
 mod workspace_registry;
 
 // Re-export main types
-pub use config::{RemoteAuth, WorkspaceConfig};
+pub use config::{ContextFileLimits, RemoteAuth, WorkspaceConfig};
 pub use error::{
     EditMatchPreview, EnvironmentManagerError, EnvironmentManagerResult, Result, WorkspaceError,
     WorkspaceManagerError, WorkspaceManagerResult,
 };
+pub use local::FileWatch;
 pub use local::LocalEnvironmentManager;
 pub use local::LocalWorkspaceManager;
 pub use manager::{
@@ -21,11 +23,13 @@ pub use manager::{
     RepoManager, WorkspaceCreateStrategy, WorkspaceManager,
 };
 pub use ops::{
-    ApplyEditsRequest, AstGrepRequest, EditMatchSelection, EditOperation, GlobRequest, GrepRequest,
-    ListDirectoryRequest, ReadFileRequest, WorkspaceOpContext, WriteFileRequest,
+    ApplyEditBatchRequest, ApplyEditsRequest, AstGrepRequest, EditMatchSelection, EditOperation,
+    GlobRequest, GrepRequest, ListDirectoryRequest, ReadFileRequest, WorkspaceOpContext,
+    WriteFileRequest,
 };
 pub use result::{
-    EditResult, FileContentResult, FileEntry, FileListResult, GlobResult, SearchMatch, SearchResult,
+    EditBatchResult, EditResult, FileContentResult, FileEditOutcome, FileEditStatus, FileEntry,
+    FileListResult, FileSearchMatch, GlobResult, SearchMatch, SearchResult, SkippedEdit,
 };
 
 // Module with the trait and core types
@@ -57,6 +61,20 @@ pub trait Workspace: Send + Sync + std::fmt::Debug {
         max_results: Option<usize>,
     ) -> Result<Vec<String>>;
 
+    /// Fuzzy-search workspace files, returning workspace-relative paths with their
+    /// match scores, best first. Ranking runs where the file list lives.
+    async fn search_files(
+        &self,
+        query: &str,
+        max_results: Option<usize>,
+    ) -> Result<Vec<FileSearchMatch>>;
+
+    /// Watch for files being added to or removed from the workspace, or `None`
+    /// if this workspace can't be watched.
+    fn watch_files(&self) -> Result<Option<FileWatch>> {
+        Ok(None)
+    }
+
     /// Get the working directory for this workspace
     fn working_directory(&self) -> &std::path::Path;
 
@@ -94,6 +112,22 @@ pub trait Workspace: Send + Sync + std::fmt::Debug {
         ctx: &WorkspaceOpContext,
     ) -> Result<EditResult>;
 
+    /// Compute what [`Workspace::apply_edits`] would do to a file without
+    /// writing it. The result carries both the current and the edited content.
+    async fn preview_edit(
+        &self,
+        request: ApplyEditsRequest,
+        ctx: &WorkspaceOpContext,
+    ) -> Result<EditResult>;
+
+    /// Apply edits to several files at once, all-or-nothing, reporting the
+    /// outcome for each file.
+    async fn apply_edit_batch(
+        &self,
+        request: ApplyEditBatchRequest,
+        ctx: &WorkspaceOpContext,
+    ) -> Result<EditBatchResult>;
+
     /// Write/replace entire file content.
     async fn write_file(
         &self,
@@ -102,6 +136,14 @@ pub trait Workspace: Send + Sync + std::fmt::Debug {
     ) -> Result<EditResult>;
 }
 
+/// A file that appeared in or disappeared from a workspace, by
+/// workspace-relative path. Directory paths end in `/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkspaceFileChange {
+    Added(String),
+    Removed(String),
+}
+
 /// Metadata about a workspace
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkspaceMetadata {
@@ -327,6 +369,22 @@ pub enum GitStatusSummary {
     Conflict,
 }
 
+impl GitStatusSummary {
+    /// Two-column status code in the style of `git status --short`.
+    fn short_code(&self) -> (char, char) {
+        match self {
+            GitStatusSummary::Added => (' ', '?'),
+            GitStatusSummary::Removed => ('D', ' '),
+            GitStatusSummary::Modified => ('M', ' '),
+            GitStatusSummary::TypeChange => ('T', ' '),
+            GitStatusSummary::Renamed => ('R', ' '),
+            GitStatusSummary::Copied => ('C', ' '),
+            GitStatusSummary::IntentToAdd => ('A', ' '),
+            GitStatusSummary::Conflict => ('U', 'U'),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitStatusEntry {
     pub summary: GitStatusSummary,
@@ -399,16 +457,7 @@ impl LlmStatus for GitStatus {
             result.push_str("Working tree clean\n");
         } else {
             for entry in &self.entries {
-                let (status_char, wt_char) = match entry.summary {
-                    GitStatusSummary::Added => (' ', '?'),
-                    GitStatusSummary::Removed => ('D', ' '),
-                    GitStatusSummary::Modified => ('M', ' '),
-                    GitStatusSummary::TypeChange => ('T', ' '),
-                    GitStatusSummary::Renamed => ('R', ' '),
-                    GitStatusSummary::Copied => ('C', ' '),
-                    GitStatusSummary::IntentToAdd => ('A', ' '),
-                    GitStatusSummary::Conflict => ('U', 'U'),
-                };
+                let (status_char, wt_char) = entry.summary.short_code();
                 result.push_str(&format!("{status_char}{wt_char} {}\n", entry.path));
             }
         }
@@ -426,6 +475,114 @@ impl LlmStatus for GitStatus {
     }
 }
 
+/// Remote-tracking branch of the current branch and how far HEAD has diverged from it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitUpstream {
+    pub name: String,
+    pub ahead: u32,
+    pub behind: u32,
+}
+
+/// Compact git state for prompts and status displays: the branch, its upstream,
+/// and at most a handful of dirty paths. [`GitStatus`] carries the full listing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitSummary {
+    pub head: Option<GitHead>,
+    pub upstream: Option<GitUpstream>,
+    pub entries: Vec<GitStatusEntry>,
+    /// Number of dirty paths left out of `entries`.
+    pub omitted_entries: u32,
+    pub error: Option<String>,
+}
+
+impl GitSummary {
+    /// Summarize `entries`, keeping at most `max_entries` of them.
+    pub fn new(
+        head: GitHead,
+        upstream: Option<GitUpstream>,
+        mut entries: Vec<GitStatusEntry>,
+        max_entries: usize,
+    ) -> Self {
+        let omitted = entries.len().saturating_sub(max_entries);
+        entries.truncate(max_entries);
+        Self {
+            head: Some(head),
+            upstream,
+            entries,
+            omitted_entries: u32::try_from(omitted).unwrap_or(u32::MAX),
+            error: None,
+        }
+    }
+
+    pub fn unavailable(message: impl Into<String>) -> Self {
+        Self {
+            head: None,
+            upstream: None,
+            entries: Vec::new(),
+            omitted_entries: 0,
+            error: Some(message.into()),
+        }
+    }
+
+    /// Name of the checked-out branch, if HEAD points at one.
+    pub fn branch(&self) -> Option<&str> {
+        match &self.head {
+            Some(GitHead::Branch(branch)) => Some(branch),
+            _ => None,
+        }
+    }
+
+    /// Total number of dirty paths, including omitted ones.
+    pub fn dirty_count(&self) -> usize {
+        let omitted = usize::try_from(self.omitted_entries).unwrap_or(usize::MAX);
+        self.entries.len().saturating_add(omitted)
+    }
+}
+
+impl LlmStatus for GitSummary {
+    fn as_llm_string(&self) -> String {
+        if let Some(error) = &self.error {
+            return format!("Status unavailable: {error}");
+        }
+        let Some(head) = &self.head else {
+            return "Status unavailable: missing git head".to_string();
+        };
+
+        let mut result = match head {
+            GitHead::Branch(branch) => format!("Branch: {branch}"),
+            GitHead::Detached => "Branch: HEAD (detached)".to_string(),
+            GitHead::Unborn => "Branch: <unborn>".to_string(),
+        };
+        if let Some(upstream) = &self.upstream {
+            if upstream.ahead == 0 && upstream.behind == 0 {
+                result.push_str(&format!(" ({}: up to date)", upstream.name));
+            } else {
+                result.push_str(&format!(
+                    " ({}: ahead {}, behind {})",
+                    upstream.name, upstream.ahead, upstream.behind
+                ));
+            }
+        }
+        result.push('\n');
+
+        if self.entries.is_empty() {
+            result.push_str("Working tree clean\n");
+            return result;
+        }
+
+        result.push_str(&format!("Dirty files: {}\n", self.dirty_count()));
+        for entry in &self.entries {
+            let (status_char, wt_char) = entry.summary.short_code();
+            result.push_str(&format!("{status_char}{wt_char} {}\n", entry.path));
+        }
+        if self.omitted_entries > 0 {
+            result.push_str(&format!("+{} more\n", self.omitted_entries));
+        }
+
+        result
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum JjChangeType {
     Added,
@@ -530,10 +687,20 @@ impl LlmStatus for JjStatus {
 /// VCS-specific status data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum VcsStatus {
-    Git(GitStatus),
+    Git(GitSummary),
     Jj(JjStatus),
 }
 
+impl VcsStatus {
+    /// Name of the checked-out git branch; `None` for jj or a detached HEAD.
+    pub fn branch(&self) -> Option<&str> {
+        match self {
+            VcsStatus::Git(summary) => summary.branch(),
+            VcsStatus::Jj(_) => None,
+        }
+    }
+}
+
 impl LlmStatus for VcsStatus {
     fn as_llm_string(&self) -> String {
         match self {
@@ -564,6 +731,12 @@ pub struct EnvironmentInfo {
     pub memory_file_content: Option<String>,
 }
 
+/// Per-directory file, in gitignore syntax, listing paths to hide from the agent.
+///
+/// Applied on top of `.gitignore` to file listings, directory listings, glob,
+/// grep and the directory snapshot in the environment context.
+pub const STEERIGNORE_FILE_NAME: &str = ".steerignore";
+
 /// Default maximum depth for directory structure traversal
 pub const MAX_DIRECTORY_DEPTH: usize = 3;
 
@@ -573,6 +746,15 @@ pub const MAX_DIRECTORY_ITEMS: usize = 1000;
 impl EnvironmentInfo {
     /// Collect environment information for a given path
     pub fn collect_for_path(path: &std::path::Path) -> Result<Self> {
+        Self::collect_for_path_with_limits(path, &ContextFileLimits::default())
+    }
+
+    /// Collect environment information, fitting README and memory files into
+    /// the given size caps.
+    pub fn collect_for_path_with_limits(
+        path: &std::path::Path,
+        limits: &ContextFileLimits,
+    ) -> Result<Self> {
         use crate::utils::{DirectoryStructureUtils, EnvironmentUtils, VcsUtils};
 
         let platform = EnvironmentUtils::get_platform().to_string();
@@ -585,10 +767,14 @@ impl EnvironmentInfo {
         )?;
         debug!("directory_structure: {}", directory_structure);
 
-        let readme_content = EnvironmentUtils::read_readme(path);
+        let readme_content = EnvironmentUtils::read_readme(path)
+            .map(|content| EnvironmentUtils::fit_markdown("README.md", content, limits));
         let (memory_file_name, memory_file_content) = match EnvironmentUtils::read_memory_file(path)
         {
-            Some((name, content)) => (Some(name), Some(content)),
+            Some((name, content)) => {
+                let content = EnvironmentUtils::fit_markdown(&name, content, limits);
+                (Some(name), Some(content))
+            }
             None => (None, None),
         };
 
@@ -623,11 +809,13 @@ impl EnvironmentInfo {
         }
 
         if let Some(ref vcs) = self.vcs {
+            let full_status_hint = match vcs.kind {
+                VcsKind::Git => " Use the git_status tool for the full status and recent commits.",
+                VcsKind::Jj => "",
+            };
             context.push_str(&format!(
-                "\n<vcs_status>\nThis is the VCS status at the start of the conversation. Note that this status is a snapshot in time, and will not update during the conversation.\n\nVCS: {}\nRoot: {}\n\n{}\n</vcs_status>",
-                vcs.kind.as_str(),
-                vcs.root.display(),
-                vcs.status.as_llm_string()
+                "\n<vcs_status>\nThis is a snapshot of the VCS status at the start of the conversation and will not update.{full_status_hint}\n\n{}\n</vcs_status>",
+                vcs.status.as_llm_string().trim_end()
             ));
         }
 
@@ -0,0 +1,238 @@
+//! Helpers for turning file bytes into model-readable text: binary sniffing,
+//! encoding detection and line/byte-bounded collection of decoded text.
+
+use chardetng::{EncodingDetector, Iso2022JpDetection, Utf8Detection};
+use encoding_rs::{Encoding, UTF_8};
+
+/// Bytes inspected at the start of a file for binary and encoding detection.
+pub(super) const SNIFF_BYTES: usize = 8 * 1024;
+
+const LINE_TRUNCATION_MARKER: &str = "... [line truncated]";
+
+/// Well-known signatures of binary formats, checked before the NUL-byte heuristic.
+const MAGIC_SIGNATURES: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "PNG image"),
+    (b"\xff\xd8\xff", "JPEG image"),
+    (b"GIF87a", "GIF image"),
+    (b"GIF89a", "GIF image"),
+    (b"%PDF-", "PDF document"),
+    (b"PK\x03\x04", "ZIP archive"),
+    (b"\x1f\x8b", "gzip archive"),
+    (b"\xfd7zXZ\x00", "xz archive"),
+    (b"(\xb5/\xfd", "zstd archive"),
+    (b"7z\xbc\xaf\x27\x1c", "7z archive"),
+    (b"\x7fELF", "ELF executable"),
+    (b"\xcf\xfa\xed\xfe", "Mach-O executable"),
+    (b"\xce\xfa\xed\xfe", "Mach-O executable"),
+    (b"\x00asm", "WebAssembly module"),
+    (b"SQLite format 3\x00", "SQLite database"),
+];
+
+/// Returns a short description of the file type if `sniff` looks like binary data.
+pub(super) fn binary_kind(sniff: &[u8]) -> Option<&'static str> {
+    // UTF-16 text is full of NUL bytes but is still text.
+    if Encoding::for_bom(sniff).is_some() {
+        return None;
+    }
+    if let Some((_, kind)) = MAGIC_SIGNATURES
+        .iter()
+        .find(|(magic, _)| sniff.starts_with(magic))
+    {
+        return Some(kind);
+    }
+    sniff.contains(&0).then_some("binary data")
+}
+
+/// Picks the encoding used to decode a file from its leading bytes.
+///
+/// A byte-order mark wins; otherwise anything that is valid UTF-8 (allowing a
+/// multi-byte sequence cut off by the sniff window) is UTF-8, and only then is
+/// the encoding guessed.
+pub(super) fn detect_encoding(sniff: &[u8], is_whole_file: bool) -> &'static Encoding {
+    if let Some((encoding, _)) = Encoding::for_bom(sniff) {
+        return encoding;
+    }
+    match std::str::from_utf8(sniff) {
+        Ok(_) => return UTF_8,
+        Err(e) if e.error_len().is_none() && !is_whole_file => return UTF_8,
+        Err(_) => {}
+    }
+    let mut detector = EncodingDetector::new(Iso2022JpDetection::Deny);
+    detector.feed(sniff, is_whole_file);
+    detector.guess(None, Utf8Detection::Allow)
+}
+
+/// Collects decoded text into the lines selected by an offset/limit window,
+/// stopping once a byte budget is spent.
+pub(super) struct LineCollector {
+    start_line: usize,
+    line_limit: Option<usize>,
+    /// Remaining bytes of in-window text that may still be consumed.
+    remaining_bytes: usize,
+    /// Per-line cap for formatted output; `None` keeps lines verbatim (raw mode).
+    max_line_length: Option<usize>,
+    current_line: usize,
+    pending: String,
+    pending_overflowed: bool,
+    lines: Vec<String>,
+    truncated: bool,
+    done: bool,
+}
+
+impl LineCollector {
+    pub(super) fn new(
+        start_line: usize,
+        line_limit: Option<usize>,
+        max_bytes: usize,
+        max_line_length: Option<usize>,
+    ) -> Self {
+        Self {
+            start_line,
+            line_limit,
+            remaining_bytes: max_bytes,
+            max_line_length,
+            current_line: 1,
+            pending: String::new(),
+            pending_overflowed: false,
+            lines: Vec::new(),
+            truncated: false,
+            done: false,
+        }
+    }
+
+    /// Whether no further input can change the result.
+    pub(super) fn is_done(&self) -> bool {
+        self.done
+    }
+
+    pub(super) fn push_str(&mut self, text: &str) {
+        for segment in text.split_inclusive('\n') {
+            if self.done {
+                return;
+            }
+            let ends_line = segment.ends_with('\n');
+            if self.current_line < self.start_line {
+                if ends_line {
+                    self.current_line += 1;
+                }
+                continue;
+            }
+
+            let budget_hit = segment.len() > self.remaining_bytes;
+            let segment = if budget_hit {
+                &segment[..segment.floor_char_boundary(self.remaining_bytes)]
+            } else {
+                segment
+            };
+            self.remaining_bytes -= segment.len();
+            self.append_pending(segment);
+
+            if budget_hit {
+                if !self.pending.is_empty() {
+                    self.finish_line();
+                }
+                self.truncated = true;
+                self.done = true;
+            } else if ends_line {
+                self.finish_line();
+            }
+        }
+    }
+
+    /// Flushes a final line that has no trailing newline.
+    pub(super) fn finish(&mut self) {
+        if !self.done && !self.pending.is_empty() {
+            self.finish_line();
+        }
+    }
+
+    /// Returns the collected lines and whether the window was cut short.
+    pub(super) fn into_parts(self) -> (Vec<String>, bool) {
+        (self.lines, self.truncated)
+    }
+
+    fn append_pending(&mut self, segment: &str) {
+        let Some(max_len) = self.max_line_length else {
+            self.pending.push_str(segment);
+            return;
+        };
+        if self.pending_overflowed {
+            return;
+        }
+        let room = max_len.saturating_sub(self.pending.len());
+        if segment.len() > room {
+            self.pending
+                .push_str(&segment[..segment.floor_char_boundary(room)]);
+            self.pending_overflowed = true;
+        } else {
+            self.pending.push_str(segment);
+        }
+    }
+
+    fn finish_line(&mut self) {
+        let mut line = std::mem::take(&mut self.pending);
+        if self.max_line_length.is_some() {
+            line.truncate(line.trim_end().len());
+            if std::mem::take(&mut self.pending_overflowed) {
+                line.push_str(LINE_TRUNCATION_MARKER);
+            }
+        }
+        self.lines.push(line);
+        self.current_line += 1;
+        if self
+            .line_limit
+            .is_some_and(|limit| self.lines.len() >= limit)
+        {
+            self.truncated = true;
+            self.done = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_binary_formats_but_not_utf16_text() {
+        assert_eq!(binary_kind(b"\x89PNG\r\n\x1a\n\0\0"), Some("PNG image"));
+        assert_eq!(binary_kind(b"hello\0world"), Some("binary data"));
+        assert_eq!(binary_kind(b"plain text\n"), None);
+        assert_eq!(binary_kind(b"\xff\xfeh\0i\0"), None);
+    }
+
+    #[test]
+    fn detects_encodings() {
+        assert_eq!(detect_encoding("héllo".as_bytes(), true), UTF_8);
+        // A multi-byte character cut off by the sniff window is still UTF-8.
+        assert_eq!(detect_encoding(&"é".as_bytes()[..1], false), UTF_8);
+        assert_eq!(
+            detect_encoding(b"\xff\xfeh\0i\0", true),
+            encoding_rs::UTF_16LE
+        );
+        assert_eq!(
+            detect_encoding(b"caf\xe9 cr\xe8me br\xfbl\xe9e", true),
+            encoding_rs::WINDOWS_1252
+        );
+    }
+
+    #[test]
+    fn line_collector_applies_window_and_byte_budget() {
+        let mut collector = LineCollector::new(2, Some(2), usize::MAX, Some(2000));
+        collector.push_str("one\ntwo  \nthree\nfour\n");
+        collector.finish();
+        assert_eq!(
+            collector.into_parts(),
+            (vec!["two".to_string(), "three".to_string()], true)
+        );
+
+        let mut collector = LineCollector::new(1, None, 6, None);
+        collector.push_str("abc\n");
+        collector.push_str("défg\n");
+        assert!(collector.is_done());
+        assert_eq!(
+            collector.into_parts(),
+            (vec!["abc\n".to_string(), "d".to_string()], true)
+        );
+    }
+}
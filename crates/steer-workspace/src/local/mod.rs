@@ -1,10 +1,13 @@
 mod environment;
+mod file_content;
 pub(crate) mod git;
 pub(crate) mod jj;
 mod layout;
 mod manager;
+mod watch;
 mod workspace;
 
 pub use environment::LocalEnvironmentManager;
 pub use manager::LocalWorkspaceManager;
+pub use watch::FileWatch;
 pub use workspace::LocalWorkspace;
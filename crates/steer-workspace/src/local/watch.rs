@@ -0,0 +1,289 @@
+use std::path::{Component, Path};
+use std::sync::Arc;
+use std::time::Duration;
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use notify_debouncer_full::notify::event::{ModifyKind, RemoveKind, RenameMode};
+use notify_debouncer_full::notify::{Event, EventKind, RecommendedWatcher, RecursiveMode};
+use notify_debouncer_full::{DebounceEventResult, Debouncer, RecommendedCache, new_debouncer};
+use tokio::sync::{RwLock, mpsc};
+use tracing::{debug, warn};
+
+use crate::error::{Result, WorkspaceError};
+use crate::{CachedEnvironment, STEERIGNORE_FILE_NAME, WorkspaceFileChange};
+
+/// Quiet period after the last filesystem event before the cache is dropped.
+pub(crate) const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Watches a workspace directory and clears its cached environment when the
+/// git HEAD moves or files are added, removed or renamed.
+///
+/// Watching stops when this value is dropped.
+pub(crate) struct EnvironmentWatcher {
+    _debouncer: Debouncer<RecommendedWatcher, RecommendedCache>,
+}
+
+impl EnvironmentWatcher {
+    pub(crate) fn spawn(
+        root: &Path,
+        cache: Arc<RwLock<Option<CachedEnvironment>>>,
+        debounce: Duration,
+    ) -> Result<Self> {
+        let watch_root = root.to_path_buf();
+        let mut debouncer = new_debouncer(debounce, None, move |result: DebounceEventResult| {
+            match result {
+                Ok(events) => {
+                    if events
+                        .iter()
+                        .any(|event| is_relevant(&watch_root, &event.event))
+                    {
+                        debug!(target: "workspace.watch", "Workspace changed, invalidating environment cache: {:?}", watch_root);
+                        // Runs on the debouncer's own thread, outside the async runtime.
+                        *cache.blocking_write() = None;
+                    }
+                }
+                Err(errors) => {
+                    for error in errors {
+                        warn!(target: "workspace.watch", "File watch error: {error}");
+                    }
+                }
+            }
+        })
+        .map_err(|e| WorkspaceError::Io(format!("failed to start file watcher: {e}")))?;
+
+        debouncer
+            .watch(root, RecursiveMode::Recursive)
+            .map_err(|e| WorkspaceError::Io(format!("failed to watch {}: {e}", root.display())))?;
+
+        Ok(Self {
+            _debouncer: debouncer,
+        })
+    }
+}
+
+/// Reports files added to or removed from a workspace, as workspace-relative
+/// paths in the same form `list_files` returns (directories end in `/`).
+///
+/// Paths under `.git` or `.jj`, or matched by the root `.gitignore` or
+/// `.steerignore`, are not reported. Watching stops when this value is dropped.
+pub struct FileWatch {
+    _debouncer: Debouncer<RecommendedWatcher, RecommendedCache>,
+    changes: mpsc::UnboundedReceiver<WorkspaceFileChange>,
+}
+
+impl FileWatch {
+    pub(crate) fn spawn(root: &Path, debounce: Duration) -> Result<Self> {
+        let (tx, changes) = mpsc::unbounded_channel();
+        let watch_root = root.to_path_buf();
+        let ignore = root_ignore(root);
+        let mut debouncer =
+            new_debouncer(
+                debounce,
+                None,
+                move |result: DebounceEventResult| match result {
+                    Ok(events) => {
+                        for event in events {
+                            for change in file_changes(&watch_root, &ignore, &event.event) {
+                                if tx.send(change).is_err() {
+                                    return;
+                                }
+                            }
+                        }
+                    }
+                    Err(errors) => {
+                        for error in errors {
+                            warn!(target: "workspace.watch", "File watch error: {error}");
+                        }
+                    }
+                },
+            )
+            .map_err(|e| WorkspaceError::Io(format!("failed to start file watcher: {e}")))?;
+
+        debouncer
+            .watch(root, RecursiveMode::Recursive)
+            .map_err(|e| WorkspaceError::Io(format!("failed to watch {}: {e}", root.display())))?;
+
+        Ok(Self {
+            _debouncer: debouncer,
+            changes,
+        })
+    }
+
+    /// The next change, or `None` once the watcher has shut down.
+    pub async fn recv(&mut self) -> Option<WorkspaceFileChange> {
+        self.changes.recv().await
+    }
+}
+
+impl std::fmt::Debug for FileWatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileWatch").finish_non_exhaustive()
+    }
+}
+
+fn root_ignore(root: &Path) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(root);
+    for name in [".gitignore", STEERIGNORE_FILE_NAME] {
+        let path = root.join(name);
+        if path.is_file()
+            && let Some(error) = builder.add(&path)
+        {
+            warn!(target: "workspace.watch", "Failed to read {}: {error}", path.display());
+        }
+    }
+    builder.build().unwrap_or_else(|_| Gitignore::empty())
+}
+
+fn file_changes(root: &Path, ignore: &Gitignore, event: &Event) -> Vec<WorkspaceFileChange> {
+    let added = |path: &Path| {
+        let is_dir = path.is_dir();
+        listed_path(root, ignore, path, is_dir).map(WorkspaceFileChange::Added)
+    };
+    let removed = |path: &Path, is_dir: bool| {
+        listed_path(root, ignore, path, is_dir).map(WorkspaceFileChange::Removed)
+    };
+
+    match event.kind {
+        EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(RenameMode::To)) => event
+            .paths
+            .iter()
+            .filter(|path| path.exists())
+            .filter_map(|path| added(path))
+            .collect(),
+        EventKind::Remove(kind) => event
+            .paths
+            .iter()
+            .filter_map(|path| removed(path, kind == RemoveKind::Folder))
+            .collect(),
+        EventKind::Modify(ModifyKind::Name(RenameMode::From)) => event
+            .paths
+            .iter()
+            .filter_map(|path| removed(path, false))
+            .collect(),
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => match event.paths.as_slice() {
+            [from, to] => {
+                let is_dir = to.is_dir();
+                removed(from, is_dir).into_iter().chain(added(to)).collect()
+            }
+            _ => Vec::new(),
+        },
+        EventKind::Modify(ModifyKind::Name(_)) => event
+            .paths
+            .iter()
+            .filter_map(|path| {
+                if path.exists() {
+                    added(path)
+                } else {
+                    removed(path, false)
+                }
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn listed_path(root: &Path, ignore: &Gitignore, path: &Path, is_dir: bool) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    if relative.as_os_str().is_empty()
+        || relative.components().any(|component| {
+            component == Component::Normal(".git".as_ref())
+                || component == Component::Normal(".jj".as_ref())
+        })
+        || ignore
+            .matched_path_or_any_parents(relative, is_dir)
+            .is_ignore()
+    {
+        return None;
+    }
+
+    let relative = relative.to_str()?;
+    Some(if is_dir {
+        format!("{relative}/")
+    } else {
+        relative.to_string()
+    })
+}
+
+fn is_relevant(root: &Path, event: &Event) -> bool {
+    if matches!(event.kind, EventKind::Access(_)) {
+        return false;
+    }
+
+    event.paths.iter().any(|path| {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        let mut components = relative.components();
+        if components.next() == Some(Component::Normal(".git".as_ref())) {
+            // Branch switches, commits and resets rewrite HEAD or a ref.
+            return match components.next() {
+                Some(Component::Normal(name)) => name == "HEAD" || name == "refs",
+                _ => false,
+            };
+        }
+
+        matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(_))
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify_debouncer_full::notify::event::{AccessKind, CreateKind, DataChange};
+    use std::path::PathBuf;
+
+    fn event(kind: EventKind, path: &str) -> Event {
+        Event::new(kind).add_path(PathBuf::from("/repo").join(path))
+    }
+
+    #[test]
+    fn test_file_changes_skip_vcs_and_ignored_paths() {
+        let root = Path::new("/repo");
+        let mut builder = GitignoreBuilder::new(root);
+        builder.add_line(None, "target/").unwrap();
+        let ignore = builder.build().unwrap();
+        let removed = |path| {
+            file_changes(
+                root,
+                &ignore,
+                &event(EventKind::Remove(RemoveKind::File), path),
+            )
+        };
+
+        assert_eq!(
+            removed("src/old.rs"),
+            vec![WorkspaceFileChange::Removed("src/old.rs".to_string())]
+        );
+        assert!(removed(".git/index").is_empty());
+        assert!(removed("target/debug/app").is_empty());
+        assert!(
+            file_changes(
+                root,
+                &ignore,
+                &event(
+                    EventKind::Modify(ModifyKind::Data(DataChange::Content)),
+                    "src/lib.rs"
+                )
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_relevant_events() {
+        let root = Path::new("/repo");
+        let create = EventKind::Create(CreateKind::File);
+        let write = EventKind::Modify(ModifyKind::Data(DataChange::Content));
+
+        assert!(is_relevant(root, &event(create, "src/new.rs")));
+        assert!(is_relevant(root, &event(write, ".git/HEAD")));
+        assert!(is_relevant(root, &event(create, ".git/refs/heads/main")));
+        assert!(!is_relevant(root, &event(write, "src/lib.rs")));
+        assert!(!is_relevant(root, &event(create, ".git/objects/ab/cdef")));
+        assert!(!is_relevant(
+            root,
+            &event(EventKind::Access(AccessKind::Any), "src/lib.rs")
+        ));
+    }
+}
@@ -1,25 +1,30 @@
 use async_trait::async_trait;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::time::Duration;
 use thiserror::Error;
-use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::io::AsyncReadExt;
 use tokio::sync::{Mutex, RwLock};
 use tokio::task;
 use tokio_util::sync::CancellationToken;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::error::{EditFailure, EditMatchPreview, Result as WorkspaceResult, WorkspaceError};
 use crate::ops::{
-    ApplyEditsRequest, AstGrepRequest, EditMatchSelection, GlobRequest, GrepRequest,
-    ListDirectoryRequest, ReadFileRequest, WorkspaceOpContext, WriteFileRequest,
+    ApplyEditBatchRequest, ApplyEditsRequest, AstGrepRequest, EditMatchSelection, GlobRequest,
+    GrepRequest, ListDirectoryRequest, ReadFileRequest, WorkspaceOpContext, WriteFileRequest,
 };
 use crate::result::{
-    EditResult, FileContentResult, FileEntry, FileListResult, GlobResult, SearchMatch, SearchResult,
+    EditBatchResult, EditResult, FileContentResult, FileEditOutcome, FileEditStatus, FileEntry,
+    FileListResult, FileSearchMatch, GlobResult, SearchMatch, SearchResult, SkippedEdit,
+};
+use crate::{
+    CachedEnvironment, ContextFileLimits, EnvironmentInfo, STEERIGNORE_FILE_NAME, Workspace,
+    WorkspaceMetadata, WorkspaceType,
 };
-use crate::{CachedEnvironment, EnvironmentInfo, Workspace, WorkspaceMetadata, WorkspaceType};
 
 use ast_grep_core::tree_sitter::StrDoc;
 use ast_grep_core::{AstGrep, Pattern};
@@ -27,17 +32,28 @@ use ast_grep_language::{LanguageExt, SupportLang};
 use grep_regex::RegexMatcherBuilder;
 use grep_searcher::sinks::UTF8;
 use grep_searcher::{BinaryDetection, SearcherBuilder, SinkError};
-use ignore::WalkBuilder;
+use ignore::overrides::OverrideBuilder;
+use ignore::{WalkBuilder, WalkState};
+
+use super::file_content::{LineCollector, SNIFF_BYTES, binary_kind, detect_encoding};
+use super::watch::{DEFAULT_DEBOUNCE, EnvironmentWatcher, FileWatch};
+use encoding_rs::UTF_8;
 
 /// Local filesystem workspace
 pub struct LocalWorkspace {
     path: PathBuf,
     environment_cache: Arc<RwLock<Option<CachedEnvironment>>>,
     metadata: WorkspaceMetadata,
+    context_limits: ContextFileLimits,
+    watcher: Option<EnvironmentWatcher>,
 }
 
 const MAX_READ_BYTES: usize = 50 * 1024;
 const MAX_LINE_LENGTH: usize = 2000;
+/// Files larger than this are skipped by content searches.
+const MAX_SEARCH_FILE_SIZE: u64 = 10 * 1024 * 1024;
+/// Grep stops early, reporting an incomplete search, once this many lines match.
+const MAX_GREP_MATCHES: usize = 2000;
 
 static FILE_LOCKS: std::sync::LazyLock<Mutex<HashMap<String, Arc<Mutex<()>>>>> =
     std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
@@ -74,11 +90,6 @@ enum ReadFileError {
     },
     #[error("File read cancelled")]
     Cancelled,
-    #[error("Error reading file line by line: {source}")]
-    ReadLine {
-        #[source]
-        source: std::io::Error,
-    },
     #[error("Error reading file: {source}")]
     Read {
         #[source]
@@ -90,6 +101,8 @@ enum ReadFileError {
 enum LsError {
     #[error("Path is not a directory: {path}")]
     NotADirectory { path: String },
+    #[error("Invalid ignore pattern: {message}")]
+    InvalidIgnorePattern { message: String },
     #[error("Operation was cancelled")]
     Cancelled,
     #[error("Task join error: {source}")]
@@ -102,9 +115,7 @@ enum LsError {
 
 async fn read_file_internal(
     file_path: &Path,
-    offset: Option<u64>,
-    limit: Option<u64>,
-    raw: Option<bool>,
+    request: &ReadFileRequest,
     cancellation_token: &CancellationToken,
 ) -> std::result::Result<FileContentResult, ReadFileError> {
     let mut file = tokio::fs::File::open(file_path)
@@ -123,127 +134,116 @@ async fn read_file_internal(
         })?
         .len();
 
-    let start_line = offset.unwrap_or(1).max(1) as usize;
-    let line_limit = limit.map(|v| v.max(1) as usize);
-    let is_raw = raw.unwrap_or(false);
-
-    let (content, total_lines, truncated) = if start_line > 1 || line_limit.is_some() {
-        let mut reader = BufReader::new(file);
-        let mut current_line_num = 1usize;
-        let mut lines_read = 0usize;
-        let mut lines = Vec::new();
-
-        loop {
-            if cancellation_token.is_cancelled() {
-                return Err(ReadFileError::Cancelled);
-            }
+    let start_line = request.offset.unwrap_or(1).max(1) as usize;
+    let line_limit = request.limit.map(|v| v.max(1) as usize);
+    let is_raw = request.raw.unwrap_or(false);
+    let requested_bytes = request.max_bytes.map(|v| v as usize);
+    let max_bytes = if is_raw {
+        requested_bytes.unwrap_or(usize::MAX)
+    } else {
+        requested_bytes.map_or(MAX_READ_BYTES, |v| v.min(MAX_READ_BYTES))
+    };
 
-            let mut line = String::new();
-            match reader.read_line(&mut line).await {
-                Ok(0) => break,
-                Ok(_) => {
-                    if current_line_num >= start_line {
-                        if is_raw {
-                            lines.push(line);
-                        } else {
-                            if line.len() > MAX_LINE_LENGTH {
-                                line.truncate(MAX_LINE_LENGTH);
-                                line.push_str("... [line truncated]");
-                            }
-                            lines.push(line.trim_end().to_string());
-                        }
-                        lines_read += 1;
-                        if line_limit.is_some_and(|l| lines_read >= l) {
-                            break;
-                        }
-                    }
-                    current_line_num += 1;
-                }
-                Err(e) => return Err(ReadFileError::ReadLine { source: e }),
-            }
+    // Fill the first chunk completely so detection sees a full sniff window.
+    let mut chunk = vec![0u8; SNIFF_BYTES];
+    let mut chunk_len = 0usize;
+    while chunk_len < chunk.len() {
+        if cancellation_token.is_cancelled() {
+            return Err(ReadFileError::Cancelled);
         }
-
-        let total_lines = lines.len();
-        let truncated = line_limit.is_some_and(|l| lines_read >= l);
-        let content = if is_raw {
-            lines.concat()
-        } else {
-            let numbered_lines: Vec<String> = lines
-                .into_iter()
-                .enumerate()
-                .map(|(i, line)| format!("{:5}\t{}", start_line + i, line))
-                .collect();
-            numbered_lines.join("\n")
-        };
-
-        (content, total_lines, truncated)
-    } else if is_raw {
-        let mut buffer = Vec::new();
-        let mut chunk = [0u8; 8192];
-
-        loop {
-            if cancellation_token.is_cancelled() {
-                return Err(ReadFileError::Cancelled);
-            }
-
-            let n = file
-                .read(&mut chunk)
-                .await
-                .map_err(|e| ReadFileError::Read { source: e })?;
-            if n == 0 {
-                break;
-            }
-            buffer.extend_from_slice(&chunk[..n]);
+        let n = file
+            .read(&mut chunk[chunk_len..])
+            .await
+            .map_err(|e| ReadFileError::Read { source: e })?;
+        if n == 0 {
+            break;
         }
+        chunk_len += n;
+    }
+    let sniff = &chunk[..chunk_len];
+
+    if let Some(kind) = binary_kind(sniff) {
+        return Ok(FileContentResult {
+            content: format!("Binary file ({kind}, {file_size} bytes); contents not shown."),
+            file_path: file_path.display().to_string(),
+            line_count: 0,
+            truncated: false,
+            encoding: None,
+            binary_kind: Some(kind.to_string()),
+            file_size: Some(file_size),
+            warnings: Vec::new(),
+        });
+    }
 
-        let content = String::from_utf8_lossy(&buffer).into_owned();
-        let total_lines = content.lines().count();
-
-        (content, total_lines, false)
+    let encoding = detect_encoding(sniff, chunk_len as u64 >= file_size);
+    // Keep UTF-8 files byte-exact (including any BOM) so raw reads round-trip.
+    let mut decoder = if encoding == UTF_8 {
+        encoding.new_decoder_without_bom_handling()
     } else {
-        let read_size = std::cmp::min(file_size as usize, MAX_READ_BYTES);
-        let mut buffer = vec![0u8; read_size];
-        let mut bytes_read = 0usize;
+        encoding.new_decoder_with_bom_removal()
+    };
+    let mut collector = LineCollector::new(
+        start_line,
+        line_limit,
+        max_bytes,
+        (!is_raw).then_some(MAX_LINE_LENGTH),
+    );
+    let mut decoded = String::new();
+
+    loop {
+        let last = chunk_len == 0;
+        decoded.clear();
+        decoded.reserve(
+            decoder
+                .max_utf8_buffer_length(chunk_len)
+                .unwrap_or(chunk_len * 3 + 16),
+        );
+        let _ = decoder.decode_to_string(&chunk[..chunk_len], &mut decoded, last);
+        collector.push_str(&decoded);
+        if last || collector.is_done() {
+            break;
+        }
 
-        while bytes_read < read_size {
-            if cancellation_token.is_cancelled() {
-                return Err(ReadFileError::Cancelled);
-            }
-            let n = file
-                .read(&mut buffer[bytes_read..])
-                .await
-                .map_err(|e| ReadFileError::Read { source: e })?;
-            if n == 0 {
-                break;
-            }
-            bytes_read += n;
+        if cancellation_token.is_cancelled() {
+            return Err(ReadFileError::Cancelled);
         }
+        chunk_len = file
+            .read(&mut chunk)
+            .await
+            .map_err(|e| ReadFileError::Read { source: e })?;
+    }
+    collector.finish();
 
-        buffer.truncate(bytes_read);
-        let content = String::from_utf8_lossy(&buffer);
-        let lines: Vec<&str> = content.lines().collect();
-        let total_lines = lines.len();
-        let truncated = file_size as usize > MAX_READ_BYTES;
-        let numbered_lines: Vec<String> = lines
+    let (lines, truncated) = collector.into_parts();
+    let line_count = lines.len();
+    let content = if is_raw {
+        lines.concat()
+    } else {
+        lines
             .into_iter()
             .enumerate()
-            .map(|(i, line)| format!("{:5}\t{}", i + 1, line))
-            .collect();
-
-        (numbered_lines.join("\n"), total_lines, truncated)
+            .map(|(i, line)| format!("{:5}\t{}", start_line + i, line))
+            .collect::<Vec<_>>()
+            .join("\n")
     };
 
     Ok(FileContentResult {
         content,
         file_path: file_path.display().to_string(),
-        line_count: total_lines,
+        line_count,
         truncated,
+        encoding: (encoding != UTF_8).then(|| encoding.name().to_string()),
+        binary_kind: None,
+        file_size: Some(file_size),
+        warnings: Vec::new(),
     })
 }
 
+/// Lists a single directory level, directories first and then by name, so that
+/// offset/limit paging is stable across calls.
 fn list_directory_internal(
     path_str: &str,
-    ignore_patterns: &[String],
+    request: &ListDirectoryRequest,
     cancellation_token: &CancellationToken,
 ) -> std::result::Result<FileListResult, LsError> {
     let path = Path::new(path_str);
@@ -257,14 +257,31 @@ fn list_directory_internal(
         return Err(LsError::Cancelled);
     }
 
+    let respect_gitignore = request.respect_gitignore.unwrap_or(true);
     let mut walk_builder = WalkBuilder::new(path);
     walk_builder.max_depth(Some(1));
-    walk_builder.git_ignore(true);
-    walk_builder.ignore(true);
+    walk_builder.git_ignore(respect_gitignore);
+    walk_builder.git_global(respect_gitignore);
+    walk_builder.git_exclude(respect_gitignore);
+    walk_builder.ignore(respect_gitignore);
+    walk_builder.add_custom_ignore_filename(STEERIGNORE_FILE_NAME);
     walk_builder.hidden(false);
 
-    for pattern in ignore_patterns {
-        walk_builder.add_ignore(pattern);
+    if let Some(patterns) = request.ignore.as_ref().filter(|p| !p.is_empty()) {
+        let mut overrides = OverrideBuilder::new(path);
+        for pattern in patterns {
+            overrides
+                .add(&format!("!{pattern}"))
+                .map_err(|e| LsError::InvalidIgnorePattern {
+                    message: e.to_string(),
+                })?;
+        }
+        let overrides = overrides
+            .build()
+            .map_err(|e| LsError::InvalidIgnorePattern {
+                message: e.to_string(),
+            })?;
+        walk_builder.overrides(overrides);
     }
 
     let walker = walk_builder.build();
@@ -305,12 +322,41 @@ fn list_directory_internal(
         _ => a.path.cmp(&b.path),
     });
 
+    let total_entries = entries.len();
+    let offset = request.offset.unwrap_or(0).min(total_entries);
+    let end = request.limit.map_or(total_entries, |limit| {
+        offset.saturating_add(limit).min(total_entries)
+    });
+    let entries: Vec<FileEntry> = entries.drain(offset..end).collect();
+
     Ok(FileListResult {
         entries,
         base_path: path_str.to_string(),
+        total_entries,
+        truncated: end < total_entries,
+        warnings: Vec::new(),
     })
 }
 
+/// Builds the directory walker shared by grep, astgrep and glob.
+///
+/// Hidden files are searched but VCS metadata is not, and `.gitignore` and
+/// `.steerignore` files at every level are honored whether or not the tree is a
+/// git checkout.
+fn search_walker(root: &Path, max_filesize: Option<u64>) -> WalkBuilder {
+    let mut walker = WalkBuilder::new(root);
+    walker
+        .hidden(false)
+        .add_custom_ignore_filename(STEERIGNORE_FILE_NAME)
+        .git_ignore(true)
+        .git_global(true)
+        .git_exclude(true)
+        .require_git(false)
+        .max_filesize(max_filesize)
+        .filter_entry(|entry| entry.depth() == 0 || entry.file_name() != ".git");
+    walker
+}
+
 fn grep_search_internal(
     pattern: &str,
     include: Option<&str>,
@@ -326,150 +372,145 @@ fn grep_search_internal(
         return Err(format!("Path does not exist: {}", base_path.display()));
     }
 
-    let matcher_pattern = if RegexMatcherBuilder::new()
+    let matcher = RegexMatcherBuilder::new()
         .line_terminator(Some(b'\n'))
         .build(pattern)
-        .is_ok()
-    {
-        pattern.to_string()
-    } else {
-        let escaped = regex::escape(pattern);
-        RegexMatcherBuilder::new()
-            .line_terminator(Some(b'\n'))
-            .build(&escaped)
-            .map_err(|e| format!("Failed to create matcher: {e}"))?;
-        escaped
-    };
-
-    let include_glob = include.map(ToOwned::to_owned);
-    if let Some(include_pattern) = include_glob.as_deref() {
-        glob::Pattern::new(include_pattern).map_err(|e| format!("Invalid glob pattern: {e}"))?;
-    }
-
-    let mut walker = WalkBuilder::new(base_path);
-    walker.hidden(false);
-    walker.git_ignore(true);
-    walker.git_global(true);
-    walker.git_exclude(true);
+        .or_else(|_| {
+            RegexMatcherBuilder::new()
+                .line_terminator(Some(b'\n'))
+                .build(&regex::escape(pattern))
+        })
+        .map_err(|e| format!("Failed to create matcher: {e}"))?;
 
     let include_pattern = include
         .map(|p| glob::Pattern::new(p).map_err(|e| format!("Invalid glob pattern: {e}")))
         .transpose()?;
 
-    let mut file_buckets: BTreeMap<String, FileMatchBucket> = BTreeMap::new();
-    let mut files_searched = 0usize;
-
-    for result in walker.build() {
-        if cancellation_token.is_cancelled() {
-            break;
-        }
-
-        let entry = match result {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-
-        let path = entry.path();
-        if !path.is_file() {
-            continue;
-        }
-
-        if let Some(ref pattern) = include_pattern
-            && !path_matches_glob(path, pattern, base_path)
-        {
-            continue;
-        }
+    let file_buckets: std::sync::Mutex<BTreeMap<String, FileMatchBucket>> =
+        std::sync::Mutex::new(BTreeMap::new());
+    let files_searched = AtomicUsize::new(0);
+    let total_matches = AtomicUsize::new(0);
+    let limit_reached = AtomicBool::new(false);
+
+    search_walker(base_path, Some(MAX_SEARCH_FILE_SIZE))
+        .build_parallel()
+        .run(|| {
+            let matcher = matcher.clone();
+            let include_pattern = include_pattern.as_ref();
+            let file_buckets = &file_buckets;
+            let files_searched = &files_searched;
+            let total_matches = &total_matches;
+            let limit_reached = &limit_reached;
+            let mut searcher = SearcherBuilder::new()
+                .binary_detection(BinaryDetection::quit(b'\x00'))
+                .line_number(true)
+                .build();
+
+            Box::new(move |result| {
+                if cancellation_token.is_cancelled() || limit_reached.load(Ordering::Relaxed) {
+                    return WalkState::Quit;
+                }
 
-        files_searched += 1;
+                let Ok(entry) = result else {
+                    return WalkState::Continue;
+                };
+                let path = entry.path();
+                if !path.is_file() {
+                    return WalkState::Continue;
+                }
 
-        let display_path = match path.canonicalize() {
-            Ok(canonical) => canonical.display().to_string(),
-            Err(_) => path.display().to_string(),
-        };
-        let file_mtime = path
-            .metadata()
-            .and_then(|m| m.modified())
-            .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-
-        let mut lines_in_file = Vec::new();
-        let matcher = RegexMatcherBuilder::new()
-            .line_terminator(Some(b'\n'))
-            .build(&matcher_pattern)
-            .map_err(|e| format!("Failed to create matcher: {e}"))?;
-        let mut searcher = SearcherBuilder::new()
-            .binary_detection(BinaryDetection::quit(b'\x00'))
-            .line_number(true)
-            .build();
-
-        let search_result = searcher.search_path(
-            &matcher,
-            path,
-            UTF8(|line_num, line| {
-                if cancellation_token.is_cancelled() {
-                    return Err(SinkError::error_message("Operation cancelled".to_string()));
+                if let Some(pattern) = include_pattern
+                    && !path_matches_glob(path, pattern, base_path)
+                {
+                    return WalkState::Continue;
                 }
 
-                lines_in_file.push((line_num as usize, line.trim_end().to_string()));
-                Ok(true)
-            }),
-        );
+                files_searched.fetch_add(1, Ordering::Relaxed);
+
+                let mut lines_in_file = Vec::new();
+                let search_result = searcher.search_path(
+                    &matcher,
+                    path,
+                    UTF8(|line_num, line| {
+                        if cancellation_token.is_cancelled() {
+                            return Err(SinkError::error_message(
+                                "Operation cancelled".to_string(),
+                            ));
+                        }
+
+                        lines_in_file.push((line_num as usize, line.trim_end().to_string()));
+                        Ok(true)
+                    }),
+                );
 
-        let append_file_matches =
-            |buckets: &mut BTreeMap<String, FileMatchBucket>,
-             file_matches: Vec<(usize, String)>| {
-                if file_matches.is_empty() {
-                    return;
+                let cancelled = match search_result {
+                    Err(err) if err.kind() == std::io::ErrorKind::InvalidData => {
+                        return WalkState::Continue;
+                    }
+                    Err(_) => cancellation_token.is_cancelled(),
+                    Ok(()) => false,
+                };
+                if lines_in_file.is_empty() {
+                    return if cancelled {
+                        WalkState::Quit
+                    } else {
+                        WalkState::Continue
+                    };
                 }
 
-                let bucket =
-                    buckets
-                        .entry(display_path.clone())
+                let display_path = match path.canonicalize() {
+                    Ok(canonical) => canonical.display().to_string(),
+                    Err(_) => path.display().to_string(),
+                };
+                let file_mtime = entry
+                    .metadata()
+                    .ok()
+                    .and_then(|m| m.modified().ok())
+                    .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+
+                let found = lines_in_file.len();
+                {
+                    let mut buckets = file_buckets
+                        .lock()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner);
+                    let bucket = buckets
+                        .entry(display_path)
                         .or_insert_with(|| FileMatchBucket {
                             mtime: file_mtime,
                             matches: Vec::new(),
                         });
-                if file_mtime > bucket.mtime {
-                    bucket.mtime = file_mtime;
+                    if file_mtime > bucket.mtime {
+                        bucket.mtime = file_mtime;
+                    }
+                    bucket.matches.extend(lines_in_file);
                 }
-                bucket.matches.extend(file_matches);
-            };
-
-        match search_result {
-            Err(err)
-                if cancellation_token.is_cancelled()
-                    && err.to_string().contains("Operation cancelled") =>
-            {
-                append_file_matches(&mut file_buckets, lines_in_file);
-                break;
-            }
-            Err(err) if err.kind() == std::io::ErrorKind::InvalidData => {}
-            Err(_) | Ok(()) => {
-                append_file_matches(&mut file_buckets, lines_in_file);
-            }
-        }
-    }
 
-    let search_completed = !cancellation_token.is_cancelled();
-    if file_buckets.is_empty() {
-        return Ok(SearchResult {
-            matches: Vec::new(),
-            total_files_searched: files_searched,
-            search_completed,
+                if total_matches.fetch_add(found, Ordering::Relaxed) + found > MAX_GREP_MATCHES {
+                    limit_reached.store(true, Ordering::Relaxed);
+                    return WalkState::Quit;
+                }
+                if cancelled {
+                    WalkState::Quit
+                } else {
+                    WalkState::Continue
+                }
+            })
         });
-    }
+
+    let limit_reached = limit_reached.into_inner();
+    let search_completed = !cancellation_token.is_cancelled() && !limit_reached;
+    let files_searched = files_searched.into_inner();
+    let file_buckets = file_buckets
+        .into_inner()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
 
     let mut sorted_files: Vec<(String, FileMatchBucket)> = file_buckets.into_iter().collect();
-    if sorted_files.len() > 1 {
-        sorted_files.sort_by(|a, b| b.1.mtime.cmp(&a.1.mtime).then_with(|| a.0.cmp(&b.0)));
-    }
+    sorted_files.sort_by(|a, b| b.1.mtime.cmp(&a.1.mtime).then_with(|| a.0.cmp(&b.0)));
 
-    let total_matches = sorted_files
-        .iter()
-        .map(|(_, bucket)| bucket.matches.len())
-        .sum();
-    let mut matches = Vec::with_capacity(total_matches);
+    let mut matches = Vec::with_capacity(total_matches.into_inner().min(MAX_GREP_MATCHES));
     for (file_path, mut bucket) in sorted_files {
-        for (line_number, line_content) in bucket.matches.drain(..) {
+        bucket.matches.sort_by_key(|(line_number, _)| *line_number);
+        for (line_number, line_content) in bucket.matches {
             matches.push(SearchMatch {
                 file_path: file_path.clone(),
                 line_number,
@@ -478,14 +519,96 @@ fn grep_search_internal(
             });
         }
     }
+    matches.truncate(MAX_GREP_MATCHES);
 
     Ok(SearchResult {
         matches,
         total_files_searched: files_searched,
         search_completed,
+        warnings: Vec::new(),
     })
 }
 
+/// Splits a glob pattern into its literal leading directories and the
+/// remaining pattern, so the walk can start as deep as possible.
+fn split_glob_root(base_path: &Path, pattern: &str) -> (PathBuf, String) {
+    let mut root = base_path.to_path_buf();
+    let mut components = pattern.split('/').peekable();
+    if pattern.starts_with('/') {
+        root = PathBuf::from("/");
+        components.next();
+    }
+    while let Some(component) = components.peek() {
+        if component.contains(['*', '?', '[']) {
+            break;
+        }
+        root.push(component);
+        components.next();
+    }
+    (root, components.collect::<Vec<_>>().join("/"))
+}
+
+fn glob_search_internal(
+    pattern: &str,
+    base_path: &Path,
+    cancellation_token: &CancellationToken,
+) -> std::result::Result<Vec<String>, String> {
+    let (root, remainder) = split_glob_root(base_path, pattern);
+    if remainder.is_empty() {
+        return Ok(if root.exists() {
+            vec![root.display().to_string()]
+        } else {
+            Vec::new()
+        });
+    }
+    if !root.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let matcher = glob::Pattern::new(&remainder)
+        .map_err(|e| format!("Invalid glob pattern '{pattern}': {e}"))?;
+    let match_options = glob::MatchOptions {
+        case_sensitive: true,
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+    };
+
+    let results = std::sync::Mutex::new(Vec::new());
+    search_walker(&root, None).build_parallel().run(|| {
+        let results = &results;
+        let matcher = &matcher;
+        let root = &root;
+        Box::new(move |entry| {
+            if cancellation_token.is_cancelled() {
+                return WalkState::Quit;
+            }
+            let Ok(entry) = entry else {
+                return WalkState::Continue;
+            };
+            if let Ok(relative) = entry.path().strip_prefix(root)
+                && !relative.as_os_str().is_empty()
+                && matcher.matches_path_with(relative, match_options)
+            {
+                results
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+                    .push(entry.path().display().to_string());
+            }
+            WalkState::Continue
+        })
+    });
+
+    if cancellation_token.is_cancelled() {
+        return Err("Operation cancelled".to_string());
+    }
+
+    let mut results = results
+        .into_inner()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    results.sort();
+    Ok(results)
+}
+
 fn astgrep_search_internal(
     pattern: &str,
     lang: Option<&str>,
@@ -498,11 +621,7 @@ fn astgrep_search_internal(
         return Err(format!("Path does not exist: {}", base_path.display()));
     }
 
-    let mut walker = WalkBuilder::new(base_path);
-    walker.hidden(false);
-    walker.git_ignore(true);
-    walker.git_global(true);
-    walker.git_exclude(true);
+    let walker = search_walker(base_path, Some(MAX_SEARCH_FILE_SIZE));
 
     let include_pattern = include
         .map(|p| glob::Pattern::new(p).map_err(|e| format!("Invalid include glob pattern: {e}")))
@@ -521,6 +640,7 @@ fn astgrep_search_internal(
                 matches: all_matches,
                 total_files_searched: files_searched,
                 search_completed: false,
+                warnings: Vec::new(),
             });
         }
 
@@ -552,15 +672,7 @@ fn astgrep_search_internal(
                 Err(_) => continue,
             }
         } else {
-            SupportLang::from_extension(path).or_else(|| {
-                path.extension()
-                    .and_then(|ext| ext.to_str())
-                    .and_then(|ext| match ext {
-                        "jsx" => Some(SupportLang::JavaScript),
-                        "mjs" => Some(SupportLang::JavaScript),
-                        _ => None,
-                    })
-            })
+            crate::symbols::detect_language(path)
         };
 
         let Some(language) = detected_lang else {
@@ -602,6 +714,7 @@ fn astgrep_search_internal(
         matches: all_matches,
         total_files_searched: files_searched,
         search_completed: true,
+        warnings: Vec::new(),
     })
 }
 
@@ -668,16 +781,6 @@ fn path_matches_glob(path: &Path, pattern: &glob::Pattern, base_path: &Path) ->
     false
 }
 
-trait LanguageHelpers {
-    fn from_extension(path: &Path) -> Option<SupportLang>;
-}
-
-impl LanguageHelpers for SupportLang {
-    fn from_extension(path: &Path) -> Option<SupportLang> {
-        ast_grep_language::Language::from_path(path)
-    }
-}
-
 const MAX_NON_UNIQUE_MATCH_PREVIEWS: usize = 5;
 const MAX_MATCH_PREVIEW_SNIPPET_CHARS: usize = 120;
 
@@ -866,26 +969,252 @@ fn apply_selected_replacements(
     updated_content
 }
 
+/// Text in the working copy that an earlier edit wrote, so later edits that
+/// depend on it can be reported instead of silently chaining.
+struct WrittenSpan {
+    start: usize,
+    end: usize,
+    edit_index: usize,
+}
+
+impl WrittenSpan {
+    fn overlaps(&self, location: &EditMatchLocation) -> bool {
+        if self.start == self.end {
+            // A deletion point only matters if a match spans across it.
+            location.start < self.start && self.start < location.end
+        } else {
+            location.start < self.end && self.start < location.end
+        }
+    }
+}
+
+/// Applies one edit to the working copy, returning the new content, the
+/// written spans remapped to it and the number of replacements made.
+fn apply_edit_to_working_copy(
+    file_path: &Path,
+    original_content: &str,
+    current_content: &str,
+    written: &[WrittenSpan],
+    edit_op: &crate::ops::EditOperation,
+    edit_index: usize,
+) -> WorkspaceResult<(String, Vec<WrittenSpan>, usize)> {
+    if edit_op.old_string.is_empty() {
+        return Err(WorkspaceError::Edit(EditFailure::EmptyOldString {
+            edit_index,
+        }));
+    }
+
+    let match_locations = find_match_locations(current_content, &edit_op.old_string);
+    if match_locations.is_empty() {
+        let file_path = file_path.display().to_string();
+        return Err(WorkspaceError::Edit(
+            if original_content.contains(&edit_op.old_string) {
+                EditFailure::StaleOldString {
+                    file_path,
+                    edit_index,
+                }
+            } else {
+                EditFailure::StringNotFound {
+                    file_path,
+                    edit_index,
+                }
+            },
+        ));
+    }
+
+    let match_selection = edit_op
+        .match_selection
+        .clone()
+        .unwrap_or(EditMatchSelection::ExactlyOne);
+    let mut selected_indices = select_match_indices(
+        match_selection,
+        &match_locations,
+        current_content,
+        file_path,
+        edit_index,
+    )?;
+    selected_indices.sort_unstable();
+    let selected = selected_indices
+        .iter()
+        .filter_map(|&index| match_locations.get(index))
+        .collect::<Vec<_>>();
+
+    if let Some(span) = written
+        .iter()
+        .find(|span| selected.iter().any(|location| span.overlaps(location)))
+    {
+        return Err(WorkspaceError::Edit(EditFailure::OverlappingEdit {
+            file_path: file_path.display().to_string(),
+            edit_index,
+            conflicting_edit_index: span.edit_index,
+        }));
+    }
+
+    let replacement_len = edit_op.new_string.len();
+    // Maps a position that no selected match covers into the updated content.
+    let remap = |position: usize| {
+        let (removed, inserted) = selected
+            .iter()
+            .filter(|location| location.end <= position)
+            .fold((0, 0), |(removed, inserted), location| {
+                (
+                    removed + (location.end - location.start),
+                    inserted + replacement_len,
+                )
+            });
+        position - removed + inserted
+    };
+
+    let mut spans = written
+        .iter()
+        .map(|span| WrittenSpan {
+            start: remap(span.start),
+            end: remap(span.end),
+            edit_index: span.edit_index,
+        })
+        .collect::<Vec<_>>();
+    spans.extend(selected.iter().map(|location| {
+        let start = remap(location.start);
+        WrittenSpan {
+            start,
+            end: start + replacement_len,
+            edit_index,
+        }
+    }));
+
+    let updated_content = apply_selected_replacements(
+        current_content,
+        &match_locations,
+        &selected_indices,
+        &edit_op.new_string,
+    );
+    Ok((updated_content, spans, selected.len()))
+}
+
+impl EditMatchLocation {
+    fn overlaps(&self, other: &EditMatchLocation) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+}
+
+/// Checks every edit against the file as originally read, before any edit is
+/// applied. An edit conflicts when its target overlaps text an earlier edit
+/// targets, or when an earlier edit rewrites one of several places its
+/// old_string matches, so which place it lands on depends on edit order.
+/// Edits whose old_string isn't in the original file build on earlier edits
+/// and are checked as they are applied instead.
+fn find_original_conflicts(
+    file_path: &Path,
+    original_content: &str,
+    operations: &[crate::ops::EditOperation],
+) -> Vec<Option<EditFailure>> {
+    let mut conflicts = vec![None; operations.len()];
+    let mut targets: Vec<(usize, Vec<EditMatchLocation>)> = Vec::new();
+
+    for (index, edit_op) in operations.iter().enumerate() {
+        let edit_index = index + 1;
+        if edit_op.old_string.is_empty() {
+            continue;
+        }
+        let match_locations = find_match_locations(original_content, &edit_op.old_string);
+        if match_locations.is_empty() {
+            continue;
+        }
+
+        let match_selection = edit_op
+            .match_selection
+            .clone()
+            .unwrap_or(EditMatchSelection::ExactlyOne);
+        // An ambiguous exactly_one edit selects nothing up front: every match
+        // competes, and without an overlap it fails as non-unique later on.
+        let selected = if matches!(match_selection, EditMatchSelection::ExactlyOne)
+            && match_locations.len() > 1
+        {
+            Vec::new()
+        } else {
+            match select_match_indices(
+                match_selection,
+                &match_locations,
+                original_content,
+                file_path,
+                edit_index,
+            ) {
+                Ok(indices) => indices
+                    .into_iter()
+                    .filter_map(|index| match_locations.get(index).copied())
+                    .collect(),
+                Err(_) => continue,
+            }
+        };
+
+        let overlap = targets.iter().find_map(|(earlier_index, earlier)| {
+            match_locations
+                .iter()
+                .find(|location| earlier.iter().any(|target| target.overlaps(location)))
+                .map(|location| (*earlier_index, *location))
+        });
+        if let Some((conflicting_edit_index, location)) = overlap {
+            let file_path = file_path.display().to_string();
+            conflicts[index] = Some(
+                if selected.iter().any(|target| target.start == location.start) {
+                    EditFailure::ConflictingEdits {
+                        file_path,
+                        edit_index,
+                        conflicting_edit_index,
+                        line_number: line_number_for_index(original_content, location.start),
+                    }
+                } else {
+                    EditFailure::AmbiguousEdit {
+                        file_path,
+                        edit_index,
+                        conflicting_edit_index,
+                        occurrences: match_locations.len(),
+                    }
+                },
+            );
+            continue;
+        }
+
+        targets.push((edit_index, selected));
+    }
+
+    conflicts
+}
+
+/// The outcome of applying edits to a file's content in memory.
+struct PlannedEdit {
+    original: String,
+    content: String,
+    changes: usize,
+    skipped_edits: Vec<SkippedEdit>,
+}
+
+/// Applies edits in order to an in-memory copy of the file. Without
+/// `allow_partial` the first failing edit aborts the whole request, so the
+/// caller never writes a half-edited file.
 async fn perform_edit_operations(
     file_path: &Path,
     operations: &[crate::ops::EditOperation],
+    allow_partial: bool,
     token: Option<&CancellationToken>,
-) -> WorkspaceResult<(String, usize)> {
+) -> WorkspaceResult<PlannedEdit> {
     if token.is_some_and(|t| t.is_cancelled()) {
         return Err(WorkspaceError::ToolExecution(
             "Operation cancelled".to_string(),
         ));
     }
 
-    for (index, edit_op) in operations.iter().enumerate() {
-        if edit_op.old_string.is_empty() {
-            return Err(WorkspaceError::Edit(EditFailure::EmptyOldString {
-                edit_index: index + 1,
-            }));
+    if !allow_partial {
+        for (index, edit_op) in operations.iter().enumerate() {
+            if edit_op.old_string.is_empty() {
+                return Err(WorkspaceError::Edit(EditFailure::EmptyOldString {
+                    edit_index: index + 1,
+                }));
+            }
         }
     }
 
-    let mut current_content = tokio::fs::read_to_string(file_path)
+    let original_content = tokio::fs::read_to_string(file_path)
         .await
         .map_err(|error| {
             if error.kind() == std::io::ErrorKind::NotFound {
@@ -900,11 +1229,11 @@ async fn perform_edit_operations(
             }
         })?;
 
-    if operations.is_empty() {
-        return Ok((current_content, 0));
-    }
-
+    let mut conflicts = find_original_conflicts(file_path, &original_content, operations);
+    let mut current_content = original_content.clone();
+    let mut written = Vec::new();
     let mut edits_applied_count = 0usize;
+    let mut skipped_edits = Vec::new();
     for (index, edit_op) in operations.iter().enumerate() {
         if token.is_some_and(|t| t.is_cancelled()) {
             return Err(WorkspaceError::ToolExecution(
@@ -913,36 +1242,39 @@ async fn perform_edit_operations(
         }
 
         let edit_index = index + 1;
-        let match_locations = find_match_locations(&current_content, &edit_op.old_string);
-        if match_locations.is_empty() {
-            return Err(WorkspaceError::Edit(EditFailure::StringNotFound {
-                file_path: file_path.display().to_string(),
+        let outcome = match conflicts[index].take() {
+            Some(conflict) => Err(WorkspaceError::Edit(conflict)),
+            None => apply_edit_to_working_copy(
+                file_path,
+                &original_content,
+                &current_content,
+                &written,
+                edit_op,
                 edit_index,
-            }));
+            ),
+        };
+        match outcome {
+            Ok((updated_content, updated_spans, replacements)) => {
+                current_content = updated_content;
+                written = updated_spans;
+                edits_applied_count += replacements;
+            }
+            Err(WorkspaceError::Edit(failure)) if allow_partial => {
+                skipped_edits.push(SkippedEdit {
+                    edit_index,
+                    reason: failure.to_string(),
+                });
+            }
+            Err(error) => return Err(error),
         }
-
-        let match_selection = edit_op
-            .match_selection
-            .clone()
-            .unwrap_or(EditMatchSelection::ExactlyOne);
-        let selected_indices = select_match_indices(
-            match_selection,
-            &match_locations,
-            &current_content,
-            file_path,
-            edit_index,
-        )?;
-
-        current_content = apply_selected_replacements(
-            &current_content,
-            &match_locations,
-            &selected_indices,
-            &edit_op.new_string,
-        );
-        edits_applied_count += selected_indices.len();
     }
 
-    Ok((current_content, edits_applied_count))
+    Ok(PlannedEdit {
+        original: original_content,
+        content: current_content,
+        changes: edits_applied_count,
+        skipped_edits,
+    })
 }
 
 impl LocalWorkspace {
@@ -957,12 +1289,38 @@ impl LocalWorkspace {
             path,
             environment_cache: Arc::new(RwLock::new(None)),
             metadata,
+            context_limits: ContextFileLimits::default(),
+            watcher: None,
         })
     }
 
+    /// Set the size caps for README and memory files in the environment context.
+    #[must_use]
+    pub fn with_context_limits(mut self, limits: ContextFileLimits) -> Self {
+        self.context_limits = limits;
+        self
+    }
+
+    /// Watch the workspace directory and invalidate the cached environment as
+    /// soon as the git HEAD moves or files are added or removed, instead of
+    /// waiting for the cache TTL to expire.
+    pub fn with_file_watch(self) -> WorkspaceResult<Self> {
+        self.with_file_watch_debounce(DEFAULT_DEBOUNCE)
+    }
+
+    /// Like [`Self::with_file_watch`], with a custom debounce interval.
+    pub fn with_file_watch_debounce(mut self, debounce: Duration) -> WorkspaceResult<Self> {
+        self.watcher = Some(EnvironmentWatcher::spawn(
+            &self.path,
+            self.environment_cache.clone(),
+            debounce,
+        )?);
+        Ok(self)
+    }
+
     /// Collect environment information for the local workspace
     async fn collect_environment(&self) -> WorkspaceResult<EnvironmentInfo> {
-        EnvironmentInfo::collect_for_path(&self.path)
+        EnvironmentInfo::collect_for_path_with_limits(&self.path, &self.context_limits)
     }
 }
 
@@ -971,6 +1329,7 @@ impl std::fmt::Debug for LocalWorkspace {
         f.debug_struct("LocalWorkspace")
             .field("path", &self.path)
             .field("metadata", &self.metadata)
+            .field("watching", &self.watcher.is_some())
             .finish_non_exhaustive()
     }
 }
@@ -1020,7 +1379,21 @@ impl Workspace for LocalWorkspace {
         FileListingUtils::list_files(&self.path, query, max_results).map_err(WorkspaceError::from)
     }
 
-    fn working_directory(&self) -> &std::path::Path {
+    async fn search_files(
+        &self,
+        query: &str,
+        max_results: Option<usize>,
+    ) -> WorkspaceResult<Vec<FileSearchMatch>> {
+        use crate::utils::FileListingUtils;
+
+        FileListingUtils::search_files(&self.path, query, max_results).map_err(WorkspaceError::from)
+    }
+
+    fn watch_files(&self) -> WorkspaceResult<Option<FileWatch>> {
+        FileWatch::spawn(&self.path, DEFAULT_DEBOUNCE).map(Some)
+    }
+
+    fn working_directory(&self) -> &std::path::Path {
         &self.path
     }
 
@@ -1030,15 +1403,9 @@ impl Workspace for LocalWorkspace {
         ctx: &WorkspaceOpContext,
     ) -> WorkspaceResult<FileContentResult> {
         let abs_path = resolve_path(&self.path, &request.file_path);
-        read_file_internal(
-            &abs_path,
-            request.offset,
-            request.limit,
-            request.raw,
-            &ctx.cancellation_token,
-        )
-        .await
-        .map_err(|e| WorkspaceError::Io(e.to_string()))
+        read_file_internal(&abs_path, &request, &ctx.cancellation_token)
+            .await
+            .map_err(|e| WorkspaceError::Io(e.to_string()))
     }
 
     async fn list_directory(
@@ -1048,11 +1415,10 @@ impl Workspace for LocalWorkspace {
     ) -> WorkspaceResult<FileListResult> {
         let target_path = resolve_path(&self.path, &request.path);
         let target_path_str = target_path.to_string_lossy().to_string();
-        let ignore_patterns = request.ignore.unwrap_or_default();
         let cancellation_token = ctx.cancellation_token.clone();
 
         let result = task::spawn_blocking(move || {
-            list_directory_internal(&target_path_str, &ignore_patterns, &cancellation_token)
+            list_directory_internal(&target_path_str, &request, &cancellation_token)
         })
         .await;
 
@@ -1076,39 +1442,27 @@ impl Workspace for LocalWorkspace {
         let search_path = request.path.as_deref().unwrap_or(".");
         let base_path = resolve_path(&self.path, search_path);
 
-        let glob_pattern = format!("{}/{}", base_path.display(), request.pattern);
+        let pattern = request.pattern.clone();
+        let cancellation_token = ctx.cancellation_token.clone();
 
-        let mut results = Vec::new();
-        match glob::glob(&glob_pattern) {
-            Ok(paths) => {
-                for entry in paths {
-                    if ctx.cancellation_token.is_cancelled() {
-                        return Err(WorkspaceError::ToolExecution(
-                            "Operation cancelled".to_string(),
-                        ));
-                    }
+        let result = task::spawn_blocking(move || {
+            glob_search_internal(&pattern, &base_path, &cancellation_token)
+        })
+        .await;
 
-                    match entry {
-                        Ok(path) => results.push(path.display().to_string()),
-                        Err(e) => {
-                            return Err(WorkspaceError::ToolExecution(format!(
-                                "Error matching glob pattern '{glob_pattern}': {e}"
-                            )));
-                        }
-                    }
-                }
-            }
+        let results = match result {
+            Ok(glob_result) => glob_result.map_err(WorkspaceError::ToolExecution)?,
             Err(e) => {
                 return Err(WorkspaceError::ToolExecution(format!(
-                    "Invalid glob pattern '{glob_pattern}': {e}"
+                    "Task join error: {e}"
                 )));
             }
-        }
+        };
 
-        results.sort();
         Ok(GlobResult {
             matches: results,
             pattern: request.pattern,
+            warnings: Vec::new(),
         })
     }
 
@@ -1186,17 +1540,21 @@ impl Workspace for LocalWorkspace {
         let file_lock = get_file_lock(&abs_path_str).await;
         let _lock_guard = file_lock.lock().await;
 
-        let (final_content, num_ops) =
-            perform_edit_operations(&abs_path, &request.edits, Some(&ctx.cancellation_token))
-                .await?;
+        let planned = perform_edit_operations(
+            &abs_path,
+            &request.edits,
+            request.allow_partial,
+            Some(&ctx.cancellation_token),
+        )
+        .await?;
 
-        if num_ops > 0 {
+        if planned.changes > 0 {
             if ctx.cancellation_token.is_cancelled() {
                 return Err(WorkspaceError::ToolExecution(
                     "Operation cancelled".to_string(),
                 ));
             }
-            tokio::fs::write(&abs_path, &final_content)
+            tokio::fs::write(&abs_path, &planned.content)
                 .await
                 .map_err(|e| {
                     WorkspaceError::Io(format!(
@@ -1208,10 +1566,11 @@ impl Workspace for LocalWorkspace {
 
             Ok(EditResult {
                 file_path: abs_path_str,
-                changes_made: num_ops,
+                changes_made: planned.changes,
                 file_created: false,
                 old_content: None,
-                new_content: Some(final_content),
+                new_content: Some(planned.content),
+                skipped_edits: planned.skipped_edits,
             })
         } else {
             Ok(EditResult {
@@ -1220,10 +1579,180 @@ impl Workspace for LocalWorkspace {
                 file_created: false,
                 old_content: None,
                 new_content: None,
+                skipped_edits: planned.skipped_edits,
             })
         }
     }
 
+    async fn preview_edit(
+        &self,
+        request: ApplyEditsRequest,
+        ctx: &WorkspaceOpContext,
+    ) -> WorkspaceResult<EditResult> {
+        let abs_path = resolve_path(&self.path, &request.file_path);
+        let planned = perform_edit_operations(
+            &abs_path,
+            &request.edits,
+            request.allow_partial,
+            Some(&ctx.cancellation_token),
+        )
+        .await?;
+
+        Ok(EditResult {
+            file_path: abs_path.display().to_string(),
+            changes_made: planned.changes,
+            file_created: false,
+            old_content: Some(planned.original),
+            new_content: Some(planned.content),
+            skipped_edits: planned.skipped_edits,
+        })
+    }
+
+    async fn apply_edit_batch(
+        &self,
+        request: ApplyEditBatchRequest,
+        ctx: &WorkspaceOpContext,
+    ) -> WorkspaceResult<EditBatchResult> {
+        let targets = request
+            .files
+            .iter()
+            .map(|file| resolve_path(&self.path, &file.file_path))
+            .collect::<Vec<_>>();
+        let mut seen = HashSet::new();
+        for target in &targets {
+            if !seen.insert(target) {
+                return Err(WorkspaceError::ToolExecution(format!(
+                    "{} appears more than once in the edit batch",
+                    target.display()
+                )));
+            }
+        }
+
+        // Lock every file in a stable order so overlapping batches cannot deadlock.
+        let mut lock_keys = targets
+            .iter()
+            .map(|target| target.display().to_string())
+            .collect::<Vec<_>>();
+        lock_keys.sort();
+        let mut lock_guards = Vec::with_capacity(lock_keys.len());
+        for key in &lock_keys {
+            lock_guards.push(get_file_lock(key).await.lock_owned().await);
+        }
+
+        let mut planned = Vec::with_capacity(targets.len());
+        for (file, target) in request.files.iter().zip(&targets) {
+            planned.push(
+                perform_edit_operations(
+                    target,
+                    &file.edits,
+                    file.allow_partial,
+                    Some(&ctx.cancellation_token),
+                )
+                .await,
+            );
+        }
+
+        if ctx.cancellation_token.is_cancelled() {
+            return Err(WorkspaceError::ToolExecution(
+                "Operation cancelled".to_string(),
+            ));
+        }
+
+        if planned.iter().any(Result::is_err) {
+            let files = targets
+                .iter()
+                .zip(planned)
+                .map(|(target, plan)| FileEditOutcome {
+                    file_path: target.display().to_string(),
+                    status: match plan {
+                        Ok(_) => FileEditStatus::NotApplied,
+                        Err(error) => FileEditStatus::Failed {
+                            reason: error.to_string(),
+                        },
+                    },
+                })
+                .collect();
+            return Ok(EditBatchResult {
+                applied: false,
+                files,
+            });
+        }
+
+        let planned = planned.into_iter().flatten().collect::<Vec<_>>();
+        let mut originals: Vec<(&Path, Vec<u8>)> = Vec::new();
+        for (index, (target, plan)) in targets.iter().zip(&planned).enumerate() {
+            if plan.changes == 0 {
+                continue;
+            }
+            let written = match tokio::fs::read(target).await {
+                Ok(original) => tokio::fs::write(target, &plan.content)
+                    .await
+                    .map(|()| original),
+                Err(e) => Err(e),
+            };
+            match written {
+                Ok(original) => originals.push((target, original)),
+                Err(e) => {
+                    for (path, original) in originals {
+                        if let Err(restore_error) = tokio::fs::write(path, original).await {
+                            warn!(
+                                target: "workspace.apply_edit_batch",
+                                "Failed to roll back {}: {restore_error}",
+                                path.display()
+                            );
+                        }
+                    }
+                    let files = targets
+                        .iter()
+                        .enumerate()
+                        .map(|(i, target)| FileEditOutcome {
+                            file_path: target.display().to_string(),
+                            status: if i == index {
+                                FileEditStatus::Failed {
+                                    reason: format!(
+                                        "Failed to write file {}: {e}",
+                                        target.display()
+                                    ),
+                                }
+                            } else {
+                                FileEditStatus::NotApplied
+                            },
+                        })
+                        .collect();
+                    return Ok(EditBatchResult {
+                        applied: false,
+                        files,
+                    });
+                }
+            }
+        }
+
+        let files = targets
+            .iter()
+            .zip(planned)
+            .map(|(target, plan)| {
+                let file_path = target.display().to_string();
+                FileEditOutcome {
+                    file_path: file_path.clone(),
+                    status: FileEditStatus::Applied {
+                        result: EditResult {
+                            file_path,
+                            changes_made: plan.changes,
+                            file_created: false,
+                            old_content: None,
+                            new_content: (plan.changes > 0).then_some(plan.content),
+                            skipped_edits: plan.skipped_edits,
+                        },
+                    },
+                }
+            })
+            .collect();
+        Ok(EditBatchResult {
+            applied: true,
+            files,
+        })
+    }
+
     async fn write_file(
         &self,
         request: WriteFileRequest,
@@ -1264,6 +1793,7 @@ impl Workspace for LocalWorkspace {
             file_created: !file_existed,
             old_content: None,
             new_content: Some(request.content),
+            skipped_edits: Vec::new(),
         })
     }
 }
@@ -1271,6 +1801,7 @@ impl Workspace for LocalWorkspace {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::WorkspaceFileChange;
     use tempfile::tempdir;
     use tokio_util::sync::CancellationToken;
 
@@ -1340,6 +1871,52 @@ mod tests {
         assert!(!env.working_directory.as_os_str().is_empty());
     }
 
+    #[tokio::test]
+    async fn test_file_watch_invalidates_cache_on_file_creation() {
+        let temp_dir = tempdir().unwrap();
+        let workspace = LocalWorkspace::with_path(temp_dir.path().to_path_buf())
+            .await
+            .unwrap()
+            .with_file_watch_debounce(Duration::from_millis(50))
+            .unwrap();
+
+        let _ = workspace.environment().await.unwrap();
+        assert!(workspace.environment_cache.read().await.is_some());
+
+        std::fs::write(temp_dir.path().join("new_file.txt"), "hello").unwrap();
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(10);
+        while workspace.environment_cache.read().await.is_some() {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "file creation did not invalidate the environment cache"
+            );
+            tokio::time::sleep(Duration::from_millis(25)).await;
+        }
+
+        let env = workspace.environment().await.unwrap();
+        assert!(env.directory_structure.contains("new_file.txt"));
+    }
+
+    #[tokio::test]
+    async fn test_watch_files_reports_created_file() {
+        let temp_dir = tempdir().unwrap();
+        let workspace = LocalWorkspace::with_path(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        let mut watch = workspace.watch_files().unwrap().unwrap();
+
+        std::fs::write(temp_dir.path().join("new_file.txt"), "hello").unwrap();
+
+        let change = tokio::time::timeout(Duration::from_secs(10), watch.recv())
+            .await
+            .expect("file creation was not reported");
+        assert_eq!(
+            change,
+            Some(WorkspaceFileChange::Added("new_file.txt".to_string()))
+        );
+    }
+
     #[tokio::test]
     async fn test_environment_collection() {
         let temp_dir = tempdir().unwrap();
@@ -1398,6 +1975,104 @@ mod tests {
         assert_eq!(files.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_list_directory_pages_in_dirs_first_name_order() {
+        let temp_dir = tempdir().unwrap();
+        let workspace = LocalWorkspace::with_path(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+
+        std::fs::create_dir(temp_dir.path().join("zdir")).unwrap();
+        std::fs::create_dir(temp_dir.path().join("adir")).unwrap();
+        for name in ["c.txt", "a.txt", "b.txt"] {
+            std::fs::write(temp_dir.path().join(name), "").unwrap();
+        }
+
+        let list = |offset, limit| ListDirectoryRequest {
+            path: ".".to_string(),
+            ignore: None,
+            offset,
+            limit,
+            respect_gitignore: None,
+        };
+        let ctx = WorkspaceOpContext::new("ls", CancellationToken::new());
+
+        let first = workspace
+            .list_directory(list(None, Some(3)), &ctx)
+            .await
+            .unwrap();
+        let names: Vec<_> = first.entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(names, vec!["adir", "zdir", "a.txt"]);
+        assert_eq!(first.total_entries, 5);
+        assert!(first.truncated);
+
+        let rest = workspace
+            .list_directory(list(Some(3), Some(3)), &ctx)
+            .await
+            .unwrap();
+        let names: Vec<_> = rest.entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(names, vec!["b.txt", "c.txt"]);
+        assert!(!rest.truncated);
+
+        let past_end = workspace
+            .list_directory(list(Some(10), None), &ctx)
+            .await
+            .unwrap();
+        assert!(past_end.entries.is_empty());
+        assert_eq!(past_end.total_entries, 5);
+    }
+
+    #[tokio::test]
+    async fn test_list_directory_ignore_patterns_and_ignore_files() {
+        let temp_dir = tempdir().unwrap();
+        let workspace = LocalWorkspace::with_path(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+
+        std::fs::write(temp_dir.path().join(".ignore"), "generated.rs\n").unwrap();
+        std::fs::write(temp_dir.path().join("generated.rs"), "").unwrap();
+        std::fs::write(temp_dir.path().join("main.rs"), "").unwrap();
+        std::fs::write(temp_dir.path().join("debug.log"), "").unwrap();
+
+        let ctx = WorkspaceOpContext::new("ls", CancellationToken::new());
+        let names = |result: FileListResult| -> Vec<String> {
+            result.entries.into_iter().map(|e| e.path).collect()
+        };
+
+        let filtered = workspace
+            .list_directory(
+                ListDirectoryRequest {
+                    path: ".".to_string(),
+                    ignore: Some(vec!["*.log".to_string()]),
+                    offset: None,
+                    limit: None,
+                    respect_gitignore: None,
+                },
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert_eq!(names(filtered), vec![".ignore", "main.rs"]);
+
+        let unfiltered = workspace
+            .list_directory(
+                ListDirectoryRequest {
+                    path: ".".to_string(),
+                    ignore: None,
+                    offset: None,
+                    limit: None,
+                    respect_gitignore: Some(false),
+                },
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            names(unfiltered),
+            vec![".ignore", "debug.log", "generated.rs", "main.rs"]
+        );
+    }
+
     #[tokio::test]
     async fn test_list_files_includes_dotfiles() {
         let temp_dir = tempdir().unwrap();
@@ -1440,6 +2115,7 @@ mod tests {
                     offset: Some(1),
                     limit: Some(2),
                     raw: Some(true),
+                    max_bytes: None,
                 },
                 &context,
             )
@@ -1458,6 +2134,7 @@ mod tests {
                     offset: Some(1),
                     limit: Some(2),
                     raw: None,
+                    max_bytes: None,
                 },
                 &context,
             )
@@ -1487,6 +2164,7 @@ mod tests {
                     offset: Some(1),
                     limit: Some(1),
                     raw: None,
+                    max_bytes: None,
                 },
                 &context,
             )
@@ -1501,6 +2179,7 @@ mod tests {
                     offset: Some(1),
                     limit: Some(1),
                     raw: Some(true),
+                    max_bytes: None,
                 },
                 &context,
             )
@@ -1531,6 +2210,7 @@ mod tests {
                     offset: None,
                     limit: None,
                     raw: None,
+                    max_bytes: None,
                 },
                 &context,
             )
@@ -1545,6 +2225,7 @@ mod tests {
                     offset: None,
                     limit: None,
                     raw: Some(true),
+                    max_bytes: None,
                 },
                 &context,
             )
@@ -1556,6 +2237,113 @@ mod tests {
         assert_eq!(raw_result.line_count, 1);
     }
 
+    #[tokio::test]
+    async fn test_read_file_transcodes_non_utf8_and_summarizes_binary() {
+        let temp_dir = tempdir().unwrap();
+        let workspace = LocalWorkspace::with_path(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        let context = WorkspaceOpContext::new("test-read-file-encoding", CancellationToken::new());
+        let read = |name: &str| ReadFileRequest {
+            file_path: temp_dir.path().join(name).to_string_lossy().to_string(),
+            offset: None,
+            limit: None,
+            raw: None,
+            max_bytes: None,
+        };
+
+        std::fs::write(
+            temp_dir.path().join("latin1.log"),
+            b"caf\xe9 cr\xe8me br\xfbl\xe9e\n",
+        )
+        .unwrap();
+        let latin1 = workspace
+            .read_file(read("latin1.log"), &context)
+            .await
+            .unwrap();
+        assert_eq!(latin1.content, "    1\tcafé crème brûlée");
+        assert_eq!(latin1.encoding.as_deref(), Some("windows-1252"));
+
+        let utf16: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("hi\r\nthere".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        std::fs::write(temp_dir.path().join("windows.txt"), utf16).unwrap();
+        let utf16 = workspace
+            .read_file(read("windows.txt"), &context)
+            .await
+            .unwrap();
+        assert_eq!(utf16.content, "    1\thi\n    2\tthere");
+        assert_eq!(utf16.encoding.as_deref(), Some("UTF-16LE"));
+
+        std::fs::write(
+            temp_dir.path().join("image.png"),
+            b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR",
+        )
+        .unwrap();
+        let binary = workspace
+            .read_file(read("image.png"), &context)
+            .await
+            .unwrap();
+        assert_eq!(binary.binary_kind.as_deref(), Some("PNG image"));
+        assert_eq!(binary.file_size, Some(16));
+        assert_eq!(binary.line_count, 0);
+        assert!(!binary.content.contains("IHDR"));
+    }
+
+    #[tokio::test]
+    async fn test_read_file_line_range_respects_max_bytes() {
+        let temp_dir = tempdir().unwrap();
+        let workspace = LocalWorkspace::with_path(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+
+        let file_path = temp_dir.path().join("big.log");
+        let mut file_content = String::new();
+        for i in 1..=20_000 {
+            use std::fmt::Write as _;
+            let _ = writeln!(file_content, "line {i}");
+        }
+        std::fs::write(&file_path, &file_content).unwrap();
+
+        let context = WorkspaceOpContext::new("test-read-file-max-bytes", CancellationToken::new());
+        let result = workspace
+            .read_file(
+                ReadFileRequest {
+                    file_path: file_path.to_string_lossy().to_string(),
+                    offset: Some(10),
+                    limit: Some(1_000_000),
+                    raw: Some(true),
+                    max_bytes: Some(30),
+                },
+                &context,
+            )
+            .await
+            .unwrap();
+
+        assert!(result.truncated);
+        assert_eq!(result.content, "line 10\nline 11\nline 12\nline 1");
+        assert_eq!(result.line_count, 4);
+        assert_eq!(result.file_size, Some(file_content.len() as u64));
+
+        // Formatted reads never exceed the built-in cap, even with a larger request.
+        let formatted = workspace
+            .read_file(
+                ReadFileRequest {
+                    file_path: file_path.to_string_lossy().to_string(),
+                    offset: Some(1),
+                    limit: Some(1_000_000),
+                    raw: None,
+                    max_bytes: Some(u64::MAX),
+                },
+                &context,
+            )
+            .await
+            .unwrap();
+        assert!(formatted.truncated);
+        assert!(formatted.line_count < 20_000);
+    }
+
     #[tokio::test]
     async fn test_apply_edits_rejects_empty_old_string_with_typed_error() {
         let temp_dir = tempdir().unwrap();
@@ -1575,6 +2363,7 @@ mod tests {
                         new_string: "replacement".to_string(),
                         match_selection: None,
                     }],
+                    allow_partial: false,
                 },
                 &context,
             )
@@ -1587,6 +2376,358 @@ mod tests {
         ));
     }
 
+    fn edit(old_string: &str, new_string: &str) -> crate::EditOperation {
+        crate::EditOperation {
+            old_string: old_string.to_string(),
+            new_string: new_string.to_string(),
+            match_selection: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_apply_edits_rejects_edits_targeting_same_text_and_leaves_file_untouched() {
+        let temp_dir = tempdir().unwrap();
+        let workspace = LocalWorkspace::with_path(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        let file_path = temp_dir.path().join("sample.txt");
+        let original = "fn alpha() {}\nfn beta() {}\n";
+        std::fs::write(&file_path, original).unwrap();
+
+        let context = WorkspaceOpContext::new("test-edit-stale", CancellationToken::new());
+        let err = workspace
+            .apply_edits(
+                ApplyEditsRequest {
+                    file_path: file_path.display().to_string(),
+                    edits: vec![
+                        edit("fn beta() {}", "fn gamma() {}"),
+                        edit("fn alpha() {}", "fn delta() {}"),
+                        edit("fn beta() {}", "fn epsilon() {}"),
+                    ],
+                    allow_partial: false,
+                },
+                &context,
+            )
+            .await
+            .expect_err("order-dependent edit should fail");
+
+        assert!(matches!(
+            err,
+            WorkspaceError::Edit(EditFailure::ConflictingEdits {
+                edit_index: 3,
+                conflicting_edit_index: 1,
+                line_number: 2,
+                ..
+            })
+        ));
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), original);
+    }
+
+    #[tokio::test]
+    async fn test_apply_edits_rejects_edit_overlapping_earlier_replacement() {
+        let temp_dir = tempdir().unwrap();
+        let workspace = LocalWorkspace::with_path(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        let file_path = temp_dir.path().join("sample.txt");
+        let original = "let total = price * qty;\nlet tax = 0;\n";
+        std::fs::write(&file_path, original).unwrap();
+
+        let context = WorkspaceOpContext::new("test-edit-overlap", CancellationToken::new());
+        let err = workspace
+            .apply_edits(
+                ApplyEditsRequest {
+                    file_path: file_path.display().to_string(),
+                    edits: vec![
+                        edit("let tax = 0;", "let tax = 1;"),
+                        edit("price * qty", "price * quantity"),
+                        edit("total = price * quantity", "subtotal = price * quantity"),
+                    ],
+                    allow_partial: false,
+                },
+                &context,
+            )
+            .await
+            .expect_err("overlapping edit should fail");
+
+        assert!(matches!(
+            err,
+            WorkspaceError::Edit(EditFailure::OverlappingEdit {
+                edit_index: 3,
+                conflicting_edit_index: 2,
+                ..
+            })
+        ));
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), original);
+    }
+
+    #[tokio::test]
+    async fn test_apply_edits_rejects_overlapping_ranges_before_applying() {
+        let temp_dir = tempdir().unwrap();
+        let workspace = LocalWorkspace::with_path(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        let file_path = temp_dir.path().join("sample.txt");
+        let original = "let tax = 0;\nlet total = price * qty;\n";
+        std::fs::write(&file_path, original).unwrap();
+
+        let context = WorkspaceOpContext::new("test-edit-conflict", CancellationToken::new());
+        let err = workspace
+            .apply_edits(
+                ApplyEditsRequest {
+                    file_path: file_path.display().to_string(),
+                    edits: vec![
+                        edit("total = price", "sum = price"),
+                        edit("let tax = 0;", "let tax = 1;"),
+                        edit("price * qty", "price * quantity"),
+                    ],
+                    allow_partial: false,
+                },
+                &context,
+            )
+            .await
+            .expect_err("overlapping edits should fail");
+
+        match err {
+            WorkspaceError::Edit(failure @ EditFailure::ConflictingEdits { .. }) => {
+                assert!(matches!(
+                    failure,
+                    EditFailure::ConflictingEdits {
+                        edit_index: 3,
+                        conflicting_edit_index: 1,
+                        line_number: 2,
+                        ..
+                    }
+                ));
+                assert!(failure.to_string().contains("edit #3 and edit #1"));
+            }
+            other => panic!("expected conflicting edits, got {other:?}"),
+        }
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), original);
+    }
+
+    #[tokio::test]
+    async fn test_apply_edits_rejects_match_made_unique_by_earlier_edit() {
+        let temp_dir = tempdir().unwrap();
+        let workspace = LocalWorkspace::with_path(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        let file_path = temp_dir.path().join("sample.txt");
+        let original = "let a = retry(3);\nlet b = retry(3);\n";
+        std::fs::write(&file_path, original).unwrap();
+
+        let context = WorkspaceOpContext::new("test-edit-ambiguous", CancellationToken::new());
+        let err = workspace
+            .apply_edits(
+                ApplyEditsRequest {
+                    file_path: file_path.display().to_string(),
+                    edits: vec![
+                        edit("let a = retry(3);", "let a = retry(5);"),
+                        edit("retry(3)", "retry(4)"),
+                    ],
+                    allow_partial: false,
+                },
+                &context,
+            )
+            .await
+            .expect_err("ambiguous edit should fail");
+
+        assert!(matches!(
+            err,
+            WorkspaceError::Edit(EditFailure::AmbiguousEdit {
+                edit_index: 2,
+                conflicting_edit_index: 1,
+                occurrences: 2,
+                ..
+            })
+        ));
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), original);
+    }
+
+    #[tokio::test]
+    async fn test_apply_edits_allow_partial_applies_successful_edits() {
+        let temp_dir = tempdir().unwrap();
+        let workspace = LocalWorkspace::with_path(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        let file_path = temp_dir.path().join("sample.txt");
+        std::fs::write(&file_path, "one\ntwo\nthree\n").unwrap();
+
+        let context = WorkspaceOpContext::new("test-edit-partial", CancellationToken::new());
+        let result = workspace
+            .apply_edits(
+                ApplyEditsRequest {
+                    file_path: file_path.display().to_string(),
+                    edits: vec![edit("one", "1"), edit("missing", "x"), edit("three", "3")],
+                    allow_partial: true,
+                },
+                &context,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(result.changes_made, 2);
+        assert_eq!(result.skipped_edits.len(), 1);
+        assert_eq!(result.skipped_edits[0].edit_index, 2);
+        assert!(result.skipped_edits[0].reason.contains("string not found"));
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "1\ntwo\n3\n");
+    }
+
+    #[tokio::test]
+    async fn test_preview_edit_leaves_file_untouched() {
+        let temp_dir = tempdir().unwrap();
+        let workspace = LocalWorkspace::with_path(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        let file_path = temp_dir.path().join("a.txt");
+        std::fs::write(&file_path, "alpha\nbeta\n").unwrap();
+
+        let context = WorkspaceOpContext::new("test-preview", CancellationToken::new());
+        let preview = workspace
+            .preview_edit(
+                ApplyEditsRequest {
+                    file_path: "a.txt".to_string(),
+                    edits: vec![edit("beta", "BETA"), edit("missing", "x")],
+                    allow_partial: true,
+                },
+                &context,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(preview.changes_made, 1);
+        assert_eq!(preview.old_content.as_deref(), Some("alpha\nbeta\n"));
+        assert_eq!(preview.new_content.as_deref(), Some("alpha\nBETA\n"));
+        assert_eq!(preview.skipped_edits.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(&file_path).unwrap(),
+            "alpha\nbeta\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_edit_batch_applies_all_files() {
+        let temp_dir = tempdir().unwrap();
+        let workspace = LocalWorkspace::with_path(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "alpha\n").unwrap();
+        std::fs::write(temp_dir.path().join("b.txt"), "beta\n").unwrap();
+
+        let context = WorkspaceOpContext::new("test-edit-batch", CancellationToken::new());
+        let result = workspace
+            .apply_edit_batch(
+                ApplyEditBatchRequest {
+                    files: vec![
+                        ApplyEditsRequest {
+                            file_path: "a.txt".to_string(),
+                            edits: vec![edit("alpha", "ALPHA")],
+                            allow_partial: false,
+                        },
+                        ApplyEditsRequest {
+                            file_path: "b.txt".to_string(),
+                            edits: vec![edit("beta", "BETA")],
+                            allow_partial: false,
+                        },
+                    ],
+                },
+                &context,
+            )
+            .await
+            .unwrap();
+
+        assert!(result.applied);
+        assert_eq!(result.files.len(), 2);
+        assert!(result.files.iter().all(|file| matches!(
+            file.status,
+            FileEditStatus::Applied { ref result } if result.changes_made == 1
+        )));
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("a.txt")).unwrap(),
+            "ALPHA\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("b.txt")).unwrap(),
+            "BETA\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_edit_batch_reports_per_file_status_on_failure() {
+        let temp_dir = tempdir().unwrap();
+        let workspace = LocalWorkspace::with_path(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "alpha\n").unwrap();
+        std::fs::write(temp_dir.path().join("b.txt"), "beta\n").unwrap();
+
+        let context = WorkspaceOpContext::new("test-edit-batch", CancellationToken::new());
+        let result = workspace
+            .apply_edit_batch(
+                ApplyEditBatchRequest {
+                    files: vec![
+                        ApplyEditsRequest {
+                            file_path: "a.txt".to_string(),
+                            edits: vec![edit("alpha", "ALPHA")],
+                            allow_partial: false,
+                        },
+                        ApplyEditsRequest {
+                            file_path: "b.txt".to_string(),
+                            edits: vec![edit("missing", "x")],
+                            allow_partial: false,
+                        },
+                    ],
+                },
+                &context,
+            )
+            .await
+            .unwrap();
+
+        assert!(!result.applied);
+        assert!(result.files[0].file_path.ends_with("a.txt"));
+        assert!(matches!(result.files[0].status, FileEditStatus::NotApplied));
+        assert!(result.files[1].file_path.ends_with("b.txt"));
+        match &result.files[1].status {
+            FileEditStatus::Failed { reason } => assert!(reason.contains("string not found")),
+            other => panic!("expected b.txt to fail, got {other:?}"),
+        }
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("a.txt")).unwrap(),
+            "alpha\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("b.txt")).unwrap(),
+            "beta\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_edit_batch_rejects_duplicate_files() {
+        let temp_dir = tempdir().unwrap();
+        let workspace = LocalWorkspace::with_path(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "alpha\n").unwrap();
+
+        let request = |old: &str, new: &str| ApplyEditsRequest {
+            file_path: "a.txt".to_string(),
+            edits: vec![edit(old, new)],
+            allow_partial: false,
+        };
+        let context = WorkspaceOpContext::new("test-edit-batch", CancellationToken::new());
+        let error = workspace
+            .apply_edit_batch(
+                ApplyEditBatchRequest {
+                    files: vec![request("alpha", "beta"), request("beta", "gamma")],
+                },
+                &context,
+            )
+            .await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("more than once"));
+    }
+
     #[tokio::test]
     async fn test_apply_edits_returns_typed_string_not_found_error() {
         let temp_dir = tempdir().unwrap();
@@ -1607,6 +2748,7 @@ mod tests {
                         new_string: "replacement".to_string(),
                         match_selection: None,
                     }],
+                    allow_partial: false,
                 },
                 &context,
             )
@@ -1638,6 +2780,7 @@ mod tests {
                         new_string: "done".to_string(),
                         match_selection: None,
                     }],
+                    allow_partial: false,
                 },
                 &context,
             )
@@ -1681,6 +2824,7 @@ mod tests {
                         new_string: "done".to_string(),
                         match_selection: Some(EditMatchSelection::First),
                     }],
+                    allow_partial: false,
                 },
                 &context,
             )
@@ -1711,6 +2855,7 @@ mod tests {
                         new_string: "done".to_string(),
                         match_selection: Some(EditMatchSelection::All),
                     }],
+                    allow_partial: false,
                 },
                 &context,
             )
@@ -1743,6 +2888,7 @@ mod tests {
                             match_index: Some(2),
                         }),
                     }],
+                    allow_partial: false,
                 },
                 &context,
             )
@@ -1774,6 +2920,7 @@ mod tests {
                         new_string: "done".to_string(),
                         match_selection: Some(EditMatchSelection::ExactlyOne),
                     }],
+                    allow_partial: false,
                 },
                 &context,
             )
@@ -1810,6 +2957,7 @@ mod tests {
                         new_string: "done".to_string(),
                         match_selection: Some(EditMatchSelection::Nth { match_index: None }),
                     }],
+                    allow_partial: false,
                 },
                 &context,
             )
@@ -1848,6 +2996,7 @@ mod tests {
                             match_index: Some(3),
                         }),
                     }],
+                    allow_partial: false,
                 },
                 &context,
             )
@@ -1981,4 +3130,168 @@ mod tests {
         assert_eq!(result.total_files_searched, 0);
         assert!(result.matches.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_grep_honors_nested_gitignore_without_git_repo() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+
+        std::fs::create_dir_all(root.join("pkg/generated")).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(root.join("pkg/.gitignore"), "generated/\n").unwrap();
+        std::fs::write(root.join("pkg/lib.rs"), "needle\n").unwrap();
+        std::fs::write(root.join("pkg/generated/out.rs"), "needle\n").unwrap();
+        std::fs::write(root.join(".git/config"), "needle\n").unwrap();
+        std::fs::write(root.join(".hidden.rs"), "needle\n").unwrap();
+
+        let workspace = LocalWorkspace::with_path(root.to_path_buf()).await.unwrap();
+        let context = WorkspaceOpContext::new("test-grep-ignore", CancellationToken::new());
+        let result = workspace
+            .grep(
+                GrepRequest {
+                    pattern: "needle".to_string(),
+                    include: None,
+                    path: Some(".".to_string()),
+                },
+                &context,
+            )
+            .await
+            .unwrap();
+
+        let mut file_names: Vec<_> = result
+            .matches
+            .iter()
+            .map(|m| {
+                std::path::Path::new(&m.file_path)
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        file_names.sort();
+        assert_eq!(file_names, vec![".hidden.rs", "lib.rs"]);
+    }
+
+    #[tokio::test]
+    async fn test_steerignore_hides_files_from_listing_and_search() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+
+        std::fs::create_dir_all(root.join("data")).unwrap();
+        std::fs::write(root.join(".steerignore"), "secrets.env\ndata/\n").unwrap();
+        std::fs::write(root.join("secrets.env"), "needle\n").unwrap();
+        std::fs::write(root.join("data/dump.csv"), "needle\n").unwrap();
+        std::fs::write(root.join("main.rs"), "needle\n").unwrap();
+
+        let workspace = LocalWorkspace::with_path(root.to_path_buf()).await.unwrap();
+
+        let files = workspace.list_files(None, None).await.unwrap();
+        assert!(files.contains(&"main.rs".to_string()));
+        assert!(!files.iter().any(|file| file.starts_with("secrets.env")));
+        assert!(!files.iter().any(|file| file.starts_with("data")));
+
+        let context = WorkspaceOpContext::new("test-steerignore", CancellationToken::new());
+        let result = workspace
+            .grep(
+                GrepRequest {
+                    pattern: "needle".to_string(),
+                    include: None,
+                    path: Some(".".to_string()),
+                },
+                &context,
+            )
+            .await
+            .unwrap();
+        let matched: Vec<_> = result
+            .matches
+            .iter()
+            .map(|m| {
+                std::path::Path::new(&m.file_path)
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(matched, vec!["main.rs"]);
+
+        let globbed = workspace
+            .glob(
+                GlobRequest {
+                    pattern: "**/*".to_string(),
+                    path: None,
+                },
+                &context,
+            )
+            .await
+            .unwrap();
+        assert!(
+            globbed
+                .matches
+                .iter()
+                .all(|path| !path.contains("secrets.env") && !path.contains("dump.csv"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_grep_stops_at_match_cap() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+
+        std::fs::write(
+            root.join("many.txt"),
+            "needle\n".repeat(MAX_GREP_MATCHES + 10),
+        )
+        .unwrap();
+
+        let workspace = LocalWorkspace::with_path(root.to_path_buf()).await.unwrap();
+        let context = WorkspaceOpContext::new("test-grep-cap", CancellationToken::new());
+        let result = workspace
+            .grep(
+                GrepRequest {
+                    pattern: "needle".to_string(),
+                    include: None,
+                    path: Some(".".to_string()),
+                },
+                &context,
+            )
+            .await
+            .unwrap();
+
+        assert!(!result.search_completed);
+        assert_eq!(result.matches.len(), MAX_GREP_MATCHES);
+        assert_eq!(result.matches[0].line_number, 1);
+    }
+
+    #[tokio::test]
+    async fn test_glob_matches_per_component_and_skips_ignored_files() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+
+        std::fs::create_dir_all(root.join("src/nested")).unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        std::fs::write(root.join("src/lib.rs"), "").unwrap();
+        std::fs::write(root.join("src/nested/mod.rs"), "").unwrap();
+        std::fs::write(root.join("target/build.rs"), "").unwrap();
+
+        let workspace = LocalWorkspace::with_path(root.to_path_buf()).await.unwrap();
+        let context = WorkspaceOpContext::new("test-glob", CancellationToken::new());
+        let glob = |pattern: &str| GlobRequest {
+            pattern: pattern.to_string(),
+            path: None,
+        };
+
+        let shallow = workspace.glob(glob("src/*.rs"), &context).await.unwrap();
+        assert_eq!(shallow.matches.len(), 1);
+        assert!(shallow.matches[0].ends_with("src/lib.rs"));
+
+        let deep = workspace.glob(glob("**/*.rs"), &context).await.unwrap();
+        assert_eq!(deep.matches.len(), 2);
+        assert!(deep.matches.iter().all(|m| !m.contains("target")));
+
+        let literal = workspace.glob(glob("src/lib.rs"), &context).await.unwrap();
+        assert_eq!(literal.matches.len(), 1);
+    }
 }
@@ -22,12 +22,21 @@ pub struct ReadFileRequest {
     pub offset: Option<u64>,
     pub limit: Option<u64>,
     pub raw: Option<bool>,
+    /// Maximum bytes of file content to read past `offset`. Formatted reads are
+    /// always capped; raw reads are unbounded unless this is set.
+    pub max_bytes: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListDirectoryRequest {
     pub path: String,
     pub ignore: Option<Vec<String>>,
+    /// Number of sorted entries to skip before returning results.
+    pub offset: Option<usize>,
+    /// Maximum number of entries to return; `None` returns everything.
+    pub limit: Option<usize>,
+    /// Whether .gitignore/.ignore rules hide entries (defaults to true).
+    pub respect_gitignore: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -73,6 +82,20 @@ pub struct EditOperation {
 pub struct ApplyEditsRequest {
     pub file_path: String,
     pub edits: Vec<EditOperation>,
+    /// Apply the edits that succeed and report the rest as skipped, instead of
+    /// leaving the file untouched when any edit fails.
+    #[serde(default)]
+    pub allow_partial: bool,
+}
+
+/// Edits to several files, applied together in one request.
+///
+/// Either every file is written or none is: if any file's edits fail, the
+/// batch is not applied.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplyEditBatchRequest {
+    /// Per-file edits; each file may appear at most once.
+    pub files: Vec<ApplyEditsRequest>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
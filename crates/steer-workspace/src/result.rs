@@ -6,6 +6,9 @@ pub struct SearchResult {
     pub matches: Vec<SearchMatch>,
     pub total_files_searched: usize,
     pub search_completed: bool,
+    /// Shown to the model after the matches.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,11 +19,27 @@ pub struct SearchMatch {
     pub column_range: Option<(usize, usize)>,
 }
 
+/// A fuzzy file search hit; higher scores are better matches.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileSearchMatch {
+    pub path: String,
+    pub score: i64,
+}
+
 /// Result for file listing operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileListResult {
     pub entries: Vec<FileEntry>,
     pub base_path: String,
+    /// Number of entries in the directory before offset/limit were applied.
+    #[serde(default)]
+    pub total_entries: usize,
+    /// Whether entries past the returned page were left out.
+    #[serde(default)]
+    pub truncated: bool,
+    /// Shown to the model after the listing.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +57,18 @@ pub struct FileContentResult {
     pub file_path: String,
     pub line_count: usize,
     pub truncated: bool,
+    /// Source encoding when the file was transcoded from something other than UTF-8.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+    /// Detected file type when the file is binary; `content` then holds a summary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary_kind: Option<String>,
+    /// Size of the file on disk in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_size: Option<u64>,
+    /// Shown to the model after the file contents.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
 /// Result for edit operations
@@ -48,6 +79,43 @@ pub struct EditResult {
     pub file_created: bool,
     pub old_content: Option<String>,
     pub new_content: Option<String>,
+    /// Edits that failed and were skipped because partial application was allowed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_edits: Vec<SkippedEdit>,
+}
+
+/// An edit left out of a partially applied edit request
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SkippedEdit {
+    /// 1-based position of the edit in the request
+    pub edit_index: usize,
+    pub reason: String,
+}
+
+/// Result of a batched edit request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditBatchResult {
+    /// Whether the batch was written. When false, no file was changed.
+    pub applied: bool,
+    /// One outcome per requested file, in request order.
+    pub files: Vec<FileEditOutcome>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileEditOutcome {
+    pub file_path: String,
+    pub status: FileEditStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum FileEditStatus {
+    /// The file's edits were written.
+    Applied { result: EditResult },
+    /// The file's edits failed, so the batch was not applied.
+    Failed { reason: String },
+    /// The file's edits were valid but not written because another file failed.
+    NotApplied,
 }
 
 /// Result for glob pattern matching
@@ -55,4 +123,7 @@ pub struct EditResult {
 pub struct GlobResult {
     pub matches: Vec<String>,
     pub pattern: String,
+    /// Shown to the model after the matched paths.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
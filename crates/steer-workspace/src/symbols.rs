@@ -0,0 +1,213 @@
+//! Tree-sitter symbol outlines for source files, built on the same ast-grep
+//! language setup that powers the astgrep operation.
+
+use std::path::Path;
+
+use ast_grep_core::Node;
+use ast_grep_core::tree_sitter::StrDoc;
+use ast_grep_language::{Language, LanguageExt, SupportLang};
+use serde::{Deserialize, Serialize};
+
+/// A named item in a source file, such as a function, type or module.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileSymbol {
+    pub name: String,
+    /// Short item kind, e.g. `fn`, `struct`, `class`, `method`.
+    pub kind: String,
+    /// Name of the enclosing item (impl, class, module...), if any.
+    pub parent: Option<String>,
+    /// Nesting depth; top-level items are 0.
+    pub depth: usize,
+    /// 1-based first line, including leading doc comments and attributes.
+    pub start_line: usize,
+    /// 1-based last line.
+    pub end_line: usize,
+}
+
+impl FileSymbol {
+    /// `Parent::name` for nested items, otherwise just the name.
+    pub fn qualified_name(&self) -> String {
+        match &self.parent {
+            Some(parent) => format!("{parent}::{}", self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// Tree-sitter node kinds that define symbols, with the kind reported for them
+/// and whether their children can define nested symbols.
+const SYMBOL_NODE_KINDS: &[(&str, &str, bool)] = &[
+    // Rust
+    ("function_item", "fn", false),
+    ("function_signature_item", "fn", false),
+    ("struct_item", "struct", false),
+    ("enum_item", "enum", false),
+    ("union_item", "union", false),
+    ("trait_item", "trait", true),
+    ("impl_item", "impl", true),
+    ("mod_item", "mod", true),
+    ("const_item", "const", false),
+    ("static_item", "static", false),
+    ("type_item", "type", false),
+    ("macro_definition", "macro", false),
+    // Python
+    ("function_definition", "function", false),
+    ("class_definition", "class", true),
+    // JavaScript / TypeScript / Go / Java
+    ("function_declaration", "function", false),
+    ("generator_function_declaration", "function", false),
+    ("class_declaration", "class", true),
+    ("abstract_class_declaration", "class", true),
+    ("interface_declaration", "interface", true),
+    ("enum_declaration", "enum", false),
+    ("type_alias_declaration", "type", false),
+    ("method_definition", "method", false),
+    ("method_declaration", "method", false),
+    ("type_spec", "type", false),
+];
+
+/// Detects the ast-grep language for a file from its extension.
+pub fn detect_language(path: &Path) -> Option<SupportLang> {
+    SupportLang::from_path(path).or_else(|| {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| match ext {
+                "jsx" | "mjs" | "cjs" => Some(SupportLang::JavaScript),
+                _ => None,
+            })
+    })
+}
+
+/// Extracts the symbol outline of `content`, or `None` if the file's language
+/// is not supported.
+pub fn extract_symbols(path: &Path, content: &str) -> Option<Vec<FileSymbol>> {
+    let language = detect_language(path)?;
+    let root = language.ast_grep(content);
+    let mut symbols = Vec::new();
+    collect_symbols(&root.root(), None, 0, &mut symbols);
+    Some(symbols)
+}
+
+/// Finds a symbol by plain (`spawn`) or qualified (`App::spawn`) name,
+/// preferring top-level items when a plain name is ambiguous.
+pub fn find_symbol<'a>(symbols: &'a [FileSymbol], name: &str) -> Option<&'a FileSymbol> {
+    symbols
+        .iter()
+        .find(|symbol| symbol.qualified_name() == name)
+        .or_else(|| {
+            symbols
+                .iter()
+                .filter(|symbol| symbol.name == name)
+                .min_by_key(|symbol| symbol.depth)
+        })
+}
+
+fn collect_symbols(
+    node: &Node<'_, StrDoc<SupportLang>>,
+    parent: Option<&str>,
+    depth: usize,
+    symbols: &mut Vec<FileSymbol>,
+) {
+    for child in node.children() {
+        let kind = child.kind();
+        let Some(&(_, symbol_kind, is_container)) = SYMBOL_NODE_KINDS
+            .iter()
+            .find(|(node_kind, _, _)| kind == *node_kind)
+        else {
+            collect_symbols(&child, parent, depth, symbols);
+            continue;
+        };
+
+        // Impl blocks are named after the type they implement.
+        let name_field = if kind == "impl_item" { "type" } else { "name" };
+        let Some(name) = child.field(name_field).map(|name| name.text().to_string()) else {
+            collect_symbols(&child, parent, depth, symbols);
+            continue;
+        };
+
+        symbols.push(FileSymbol {
+            name: name.clone(),
+            kind: symbol_kind.to_string(),
+            parent: parent.map(str::to_string),
+            depth,
+            start_line: leading_trivia_start(&child) + 1,
+            end_line: child.end_pos().line() + 1,
+        });
+
+        if is_container {
+            collect_symbols(&child, Some(&name), depth + 1, symbols);
+        }
+    }
+}
+
+/// Returns the 0-based line where the item's doc comments and attributes begin.
+fn leading_trivia_start(node: &Node<'_, StrDoc<SupportLang>>) -> usize {
+    let mut start = node.start_pos().line();
+    let mut current = node.prev();
+    while let Some(prev) = current {
+        let kind = prev.kind();
+        let is_trivia = kind.contains("comment") || kind == "attribute_item" || kind == "decorator";
+        if !is_trivia || prev.end_pos().line() + 1 < start {
+            break;
+        }
+        start = prev.start_pos().line();
+        current = prev.prev();
+    }
+    start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_nested_rust_symbols_with_doc_comments() {
+        let source = r"use std::fmt;
+
+/// An app.
+#[derive(Debug)]
+pub struct App;
+
+impl App {
+    /// Spawns work.
+    pub fn spawn_agent_operation(&self) {
+        let _ = 1;
+    }
+}
+
+fn helper() {}
+";
+        let symbols = extract_symbols(Path::new("src/app.rs"), source).unwrap();
+        let names: Vec<_> = symbols.iter().map(FileSymbol::qualified_name).collect();
+        assert_eq!(
+            names,
+            vec!["App", "App", "App::spawn_agent_operation", "helper"]
+        );
+
+        let app = &symbols[0];
+        assert_eq!(
+            (app.kind.as_str(), app.start_line, app.end_line),
+            ("struct", 3, 5)
+        );
+
+        let method = find_symbol(&symbols, "spawn_agent_operation").unwrap();
+        assert_eq!(
+            (method.start_line, method.end_line, method.depth),
+            (8, 11, 1)
+        );
+    }
+
+    #[test]
+    fn extracts_python_classes_and_methods() {
+        let source =
+            "class Greeter:\n    def greet(self):\n        return 1\n\ndef main():\n    pass\n";
+        let symbols = extract_symbols(Path::new("app.py"), source).unwrap();
+        let names: Vec<_> = symbols.iter().map(FileSymbol::qualified_name).collect();
+        assert_eq!(names, vec!["Greeter", "Greeter::greet", "main"]);
+    }
+
+    #[test]
+    fn unsupported_language_has_no_outline() {
+        assert!(extract_symbols(Path::new("notes.txt"), "hello").is_none());
+    }
+}
@@ -1,4 +1,6 @@
 use ignore::WalkBuilder;
+
+use crate::STEERIGNORE_FILE_NAME;
 use std::path::Path;
 
 /// Common directory structure functionality for workspaces
@@ -46,6 +48,7 @@ impl DirectoryStructureUtils {
         let walker = WalkBuilder::new(root_path)
             .max_depth(Some(max_depth))
             .hidden(true) // Exclude hidden files/dirs from traversal
+            .add_custom_ignore_filename(STEERIGNORE_FILE_NAME)
             .build();
 
         for entry in walker {
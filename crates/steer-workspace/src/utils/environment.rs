@@ -1,5 +1,10 @@
 use std::path::Path;
 
+use crate::config::ContextFileLimits;
+
+/// Room reserved for elision markers when truncating to the soft limit.
+const MARKER_RESERVE_BYTES: usize = 256;
+
 /// Common environment utilities for workspaces
 pub struct EnvironmentUtils;
 
@@ -54,6 +59,152 @@ impl EnvironmentUtils {
     pub fn read_claude_md(path: &Path) -> Option<String> {
         Self::read_memory_file(path).map(|(_, content)| content)
     }
+
+    /// Fit a markdown document into the environment context.
+    ///
+    /// Documents over `limits.omit_bytes` are replaced by a one-line pointer to
+    /// `read_file`. Documents over `limits.truncate_bytes` keep their leading
+    /// sections and table of contents, with the rest elided behind markers.
+    pub fn fit_markdown(file_name: &str, content: String, limits: &ContextFileLimits) -> String {
+        let size = content.len();
+        if size > limits.omit_bytes {
+            return format!(
+                "[{file_name} is {} KB, too large to include here. Use read_file to read it.]",
+                size.div_ceil(1024)
+            );
+        }
+        if size <= limits.truncate_bytes {
+            return content;
+        }
+        truncate_markdown(file_name, &content, limits.truncate_bytes)
+    }
+}
+
+fn truncate_markdown(file_name: &str, content: &str, max_bytes: usize) -> String {
+    let sections = split_sections(content);
+    let budget = max_bytes.saturating_sub(MARKER_RESERVE_BYTES);
+    let mut keep = vec![false; sections.len()];
+    let mut used = 0;
+
+    // The table of contents is the map to everything that gets elided.
+    if let Some(toc) = sections.iter().position(|section| is_toc(section))
+        && sections[toc].len() <= budget
+    {
+        keep[toc] = true;
+        used += sections[toc].len();
+    }
+
+    for (index, section) in sections.iter().enumerate() {
+        if keep[index] {
+            continue;
+        }
+        if used + section.len() > budget {
+            break;
+        }
+        keep[index] = true;
+        used += section.len();
+    }
+
+    let mut output = String::new();
+    let mut elided_sections = 0;
+    let mut elided_bytes = 0;
+    for (index, section) in sections.iter().enumerate() {
+        if keep[index] {
+            push_elision_marker(&mut output, file_name, elided_sections, elided_bytes);
+            elided_sections = 0;
+            elided_bytes = 0;
+            output.push_str(section);
+        } else if index == 0 {
+            // An oversized preamble still contributes whatever fits, cut at a line.
+            let prefix = line_bounded_prefix(section, budget.saturating_sub(used));
+            output.push_str(prefix);
+            elided_bytes += section.len() - prefix.len();
+            elided_sections += usize::from(prefix.is_empty());
+        } else {
+            elided_sections += 1;
+            elided_bytes += section.len();
+        }
+    }
+    push_elision_marker(&mut output, file_name, elided_sections, elided_bytes);
+    output
+}
+
+fn push_elision_marker(output: &mut String, file_name: &str, sections: usize, bytes: usize) {
+    if bytes == 0 {
+        return;
+    }
+    if !output.is_empty() && !output.ends_with('\n') {
+        output.push('\n');
+    }
+    let what = match sections {
+        0 => "the rest of this section".to_string(),
+        1 => "1 section".to_string(),
+        n => format!("{n} sections"),
+    };
+    output.push_str(&format!(
+        "\n[... {what} ({} KB) omitted; use read_file on {file_name} to see it ...]\n\n",
+        bytes.div_ceil(1024)
+    ));
+}
+
+/// Split markdown into sections, each starting at an ATX heading. Text before
+/// the first heading forms its own leading section. Headings inside fenced
+/// code blocks are ignored.
+fn split_sections(content: &str) -> Vec<&str> {
+    let mut starts = vec![0];
+    let mut offset = 0;
+    let mut in_fence = false;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        } else if !in_fence && offset > 0 && heading_text(line).is_some() {
+            starts.push(offset);
+        }
+        offset += line.len();
+    }
+    starts.push(content.len());
+    starts
+        .windows(2)
+        .map(|bounds| &content[bounds[0]..bounds[1]])
+        .filter(|section| !section.is_empty())
+        .collect()
+}
+
+fn heading_text(line: &str) -> Option<&str> {
+    let hashes = line.bytes().take_while(|&b| b == b'#').count();
+    if !(1..=6).contains(&hashes) {
+        return None;
+    }
+    let rest = &line[hashes..];
+    if rest.trim().is_empty() || rest.starts_with([' ', '\t']) {
+        Some(rest.trim())
+    } else {
+        None
+    }
+}
+
+fn is_toc(section: &str) -> bool {
+    section
+        .lines()
+        .next()
+        .and_then(heading_text)
+        .map(str::to_ascii_lowercase)
+        .is_some_and(|title| matches!(title.as_str(), "table of contents" | "contents" | "toc"))
+}
+
+fn line_bounded_prefix(text: &str, max_bytes: usize) -> &str {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    match text[..end].rfind('\n') {
+        Some(newline) => &text[..=newline],
+        None => "",
+    }
 }
 
 #[cfg(test)]
@@ -76,6 +227,110 @@ mod tests {
         assert_eq!(date.chars().nth(7), Some('-'));
     }
 
+    fn synthetic_readme(sections: usize, section_bytes: usize) -> String {
+        let mut readme = String::from("# Project\n\nIntro paragraph.\n\n");
+        readme.push_str("## Table of Contents\n\n");
+        for i in 0..sections {
+            readme.push_str(&format!("- [Section {i}](#section-{i})\n"));
+        }
+        readme.push('\n');
+        for i in 0..sections {
+            readme.push_str(&format!("## Section {i}\n\n"));
+            readme.push_str("```sh\n# not a heading\n```\n");
+            readme.push_str(&"lorem ipsum\n".repeat(section_bytes / 12));
+        }
+        readme
+    }
+
+    #[test]
+    fn test_fit_markdown_keeps_small_files() {
+        let content = "# Small\n\nhello\n".to_string();
+        let fitted = EnvironmentUtils::fit_markdown(
+            "README.md",
+            content.clone(),
+            &ContextFileLimits::default(),
+        );
+        assert_eq!(fitted, content);
+    }
+
+    #[test]
+    fn test_fit_markdown_truncates_at_headings() {
+        let readme = synthetic_readme(40, 1024);
+        let limits = ContextFileLimits {
+            truncate_bytes: 4 * 1024,
+            omit_bytes: 128 * 1024,
+        };
+        assert!(readme.len() > limits.truncate_bytes);
+
+        let fitted = EnvironmentUtils::fit_markdown("README.md", readme, &limits);
+
+        assert!(
+            fitted.len() <= limits.truncate_bytes,
+            "{} bytes",
+            fitted.len()
+        );
+        assert!(fitted.starts_with("# Project\n\nIntro paragraph."));
+        assert!(fitted.contains("## Table of Contents"));
+        assert!(fitted.contains("- [Section 39](#section-39)"));
+        assert!(fitted.contains("## Section 0\n"));
+        assert!(!fitted.contains("## Section 39\n"));
+        assert!(fitted.contains("sections ("));
+        assert!(fitted.contains("omitted; use read_file on README.md to see it ...]"));
+        // Every kept section is whole: no section is cut mid-way.
+        for kept in fitted.split("## Section ").skip(1) {
+            assert!(kept.contains("```\n"), "section was cut: {kept:?}");
+        }
+    }
+
+    #[test]
+    fn test_fit_markdown_keeps_late_table_of_contents() {
+        let mut readme = String::new();
+        for i in 0..20 {
+            readme.push_str(&format!("## Part {i}\n\n{}\n", "text ".repeat(100)));
+        }
+        readme.push_str("## Contents\n\n- Part 0\n- Part 19\n");
+        let limits = ContextFileLimits {
+            truncate_bytes: 2 * 1024,
+            omit_bytes: 128 * 1024,
+        };
+
+        let fitted = EnvironmentUtils::fit_markdown("AGENTS.md", readme, &limits);
+
+        assert!(fitted.starts_with("## Part 0\n"));
+        assert!(fitted.ends_with("## Contents\n\n- Part 0\n- Part 19\n"));
+        assert!(fitted.contains("omitted; use read_file on AGENTS.md to see it ...]"));
+    }
+
+    #[test]
+    fn test_fit_markdown_truncates_headingless_file_at_line() {
+        let content = "line of text\n".repeat(1000);
+        let limits = ContextFileLimits {
+            truncate_bytes: 1024,
+            omit_bytes: 128 * 1024,
+        };
+
+        let fitted = EnvironmentUtils::fit_markdown("CLAUDE.md", content, &limits);
+
+        assert!(fitted.starts_with("line of text\n"));
+        assert!(fitted.contains("\n\n[... the rest of this section ("));
+        assert!(fitted.len() <= limits.truncate_bytes);
+    }
+
+    #[test]
+    fn test_fit_markdown_omits_huge_files() {
+        let readme = synthetic_readme(200, 1024);
+        let limits = ContextFileLimits {
+            truncate_bytes: 4 * 1024,
+            omit_bytes: 64 * 1024,
+        };
+
+        let fitted = EnvironmentUtils::fit_markdown("README.md", readme, &limits);
+
+        assert!(fitted.starts_with("[README.md is "));
+        assert!(fitted.ends_with("KB, too large to include here. Use read_file to read it.]"));
+        assert_eq!(fitted.lines().count(), 1);
+    }
+
     #[test]
     fn test_git_repo_detection() {
         let temp_dir = tempdir().unwrap();
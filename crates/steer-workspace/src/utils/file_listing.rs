@@ -3,6 +3,9 @@ use fuzzy_matcher::skim::SkimMatcherV2;
 use ignore::WalkBuilder;
 use std::path::Path;
 
+use crate::STEERIGNORE_FILE_NAME;
+use crate::result::FileSearchMatch;
+
 /// Common file listing functionality for workspaces
 pub struct FileListingUtils;
 
@@ -13,11 +16,59 @@ impl FileListingUtils {
         query: Option<&str>,
         max_results: Option<usize>,
     ) -> Result<Vec<String>, std::io::Error> {
+        let limit = max_results.filter(|max| *max > 0);
+        let mut files = match query {
+            Some(query) if !query.is_empty() => {
+                Self::rank_paths(Self::walk_files(root_path, None), query, None)
+                    .into_iter()
+                    .map(|m| m.path)
+                    .collect()
+            }
+            // Without a query there is nothing to rank, so stop walking at the cap
+            // instead of enumerating the whole tree.
+            _ => Self::walk_files(root_path, limit),
+        };
+
+        // Apply max_results limit if specified
+        if let Some(max) = max_results
+            && max > 0
+            && files.len() > max
+        {
+            files.truncate(max);
+        }
+
+        Ok(files)
+    }
+
+    /// Fuzzy-match `query` against every file in the workspace, best match first.
+    ///
+    /// An empty query matches everything with a score of zero, in walk order.
+    pub fn search_files(
+        root_path: &Path,
+        query: &str,
+        max_results: Option<usize>,
+    ) -> Result<Vec<FileSearchMatch>, std::io::Error> {
+        let files = Self::walk_files(root_path, None);
+        if query.is_empty() {
+            let limit = max_results.filter(|max| *max > 0).unwrap_or(usize::MAX);
+            return Ok(files
+                .into_iter()
+                .take(limit)
+                .map(|path| FileSearchMatch { path, score: 0 })
+                .collect());
+        }
+
+        Ok(Self::rank_paths(files, query, max_results))
+    }
+
+    fn walk_files(root_path: &Path, limit: Option<usize>) -> Vec<String> {
         let mut files = Vec::new();
 
-        // Walk the directory, respecting .gitignore but including hidden files (except VCS dirs)
+        // Walk the directory, respecting .gitignore and .steerignore but including hidden
+        // files (except VCS dirs)
         let walker = WalkBuilder::new(root_path)
             .hidden(false) // Include hidden files
+            .add_custom_ignore_filename(STEERIGNORE_FILE_NAME)
             .filter_entry(|entry| {
                 // Skip VCS directories
                 entry.file_name() != ".git" && entry.file_name() != ".jj"
@@ -25,6 +76,10 @@ impl FileListingUtils {
             .build();
 
         for entry in walker {
+            if limit.is_some_and(|limit| files.len() >= limit) {
+                break;
+            }
+
             let entry = match entry {
                 Ok(e) => e,
                 Err(_) => continue, // Skip files we don't have access to
@@ -49,35 +104,36 @@ impl FileListingUtils {
             }
         }
 
-        // Apply fuzzy filter if query is provided
-        let mut filtered_files = if let Some(query) = query {
-            if query.is_empty() {
-                files
-            } else {
-                let matcher = SkimMatcherV2::default();
-                let mut scored_files: Vec<(i64, String)> = files
-                    .into_iter()
-                    .filter_map(|file| matcher.fuzzy_match(&file, query).map(|score| (score, file)))
-                    .collect();
+        files
+    }
 
-                // Sort by score (highest first)
-                scored_files.sort_by(|a, b| b.0.cmp(&a.0));
+    /// Rank already-listed paths against `query`; used directly by clients whose
+    /// server cannot search on its own.
+    pub fn rank_paths(
+        files: Vec<String>,
+        query: &str,
+        max_results: Option<usize>,
+    ) -> Vec<FileSearchMatch> {
+        let matcher = SkimMatcherV2::default();
+        let mut matches: Vec<FileSearchMatch> = files
+            .into_iter()
+            .filter_map(|path| {
+                matcher
+                    .fuzzy_match(&path, query)
+                    .map(|score| FileSearchMatch { path, score })
+            })
+            .collect();
 
-                scored_files.into_iter().map(|(_, file)| file).collect()
-            }
-        } else {
-            files
-        };
+        // Sort by score (highest first); ties keep walk order
+        matches.sort_by(|a, b| b.score.cmp(&a.score));
 
-        // Apply max_results limit if specified
         if let Some(max) = max_results
             && max > 0
-            && filtered_files.len() > max
         {
-            filtered_files.truncate(max);
+            matches.truncate(max);
         }
 
-        Ok(filtered_files)
+        matches
     }
 }
 
@@ -122,6 +178,55 @@ mod tests {
         assert_eq!(files[0], "test.rs");
     }
 
+    #[test]
+    fn test_list_files_stops_at_max_results() {
+        let temp_dir = tempdir().unwrap();
+        for i in 0..20 {
+            std::fs::write(temp_dir.path().join(format!("file_{i}.rs")), "").unwrap();
+        }
+
+        let files = FileListingUtils::list_files(temp_dir.path(), None, Some(5)).unwrap();
+        assert_eq!(files.len(), 5);
+
+        let files = FileListingUtils::list_files(temp_dir.path(), None, Some(0)).unwrap();
+        assert_eq!(files.len(), 20);
+    }
+
+    #[test]
+    fn test_search_files_ranks_closer_matches_higher() {
+        let temp_dir = tempdir().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("src/my_app")).unwrap();
+        std::fs::write(temp_dir.path().join("src/main.rs"), "").unwrap();
+        std::fs::write(temp_dir.path().join("src/my_app/index.rs"), "").unwrap();
+        std::fs::write(temp_dir.path().join("src/lib.rs"), "").unwrap();
+
+        let matches = FileListingUtils::search_files(temp_dir.path(), "main", None).unwrap();
+
+        assert_eq!(matches[0].path, "src/main.rs");
+        assert!(matches.windows(2).all(|w| w[0].score >= w[1].score));
+        let scattered = matches
+            .iter()
+            .find(|m| m.path == "src/my_app/index.rs")
+            .unwrap();
+        assert!(matches[0].score > scattered.score);
+        assert!(!matches.iter().any(|m| m.path == "src/lib.rs"));
+    }
+
+    #[test]
+    fn test_search_files_respects_max_results() {
+        let temp_dir = tempdir().unwrap();
+        for name in ["a.rs", "ab.rs", "abc.rs"] {
+            std::fs::write(temp_dir.path().join(name), "").unwrap();
+        }
+
+        let matches = FileListingUtils::search_files(temp_dir.path(), "a", Some(2)).unwrap();
+        assert_eq!(matches.len(), 2);
+
+        let everything = FileListingUtils::search_files(temp_dir.path(), "", Some(0)).unwrap();
+        assert_eq!(everything.len(), 3);
+        assert!(everything.iter().all(|m| m.score == 0));
+    }
+
     #[test]
     #[cfg(unix)]
     fn test_list_files_skips_inaccessible() {
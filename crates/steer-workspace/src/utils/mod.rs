@@ -2,8 +2,10 @@ pub mod directory_structure;
 pub mod environment;
 pub mod file_listing;
 pub mod vcs;
+pub mod workspace_path;
 
 pub use directory_structure::DirectoryStructureUtils;
 pub use environment::EnvironmentUtils;
 pub use file_listing::FileListingUtils;
 pub use vcs::{GitStatusUtils, VcsUtils};
+pub use workspace_path::{WorkspacePathError, ensure_within_workspace, normalize_workspace_path};
@@ -1,32 +1,83 @@
 use std::path::Path;
 
+/// Maximum number of dirty paths listed in a [`crate::GitSummary`].
+pub const MAX_SUMMARY_ENTRIES: usize = 10;
+
 /// Common git status functionality for workspaces
 pub struct GitStatusUtils;
 
 impl GitStatusUtils {
-    /// Get git status information for a repository
+    /// Get the full git status for a repository, listing every dirty path
     pub fn get_git_status(repo_path: &Path) -> Result<crate::GitStatus, std::io::Error> {
-        let repo = gix::discover(repo_path)
-            .map_err(|e| std::io::Error::other(format!("Failed to open git repository: {e}")))?;
+        let repo = Self::open(repo_path)?;
+        let head = Self::read_head(&repo)?;
+        let entries = Self::read_entries(&repo)?;
+
+        // Get recent commits
+        let mut recent_commits = Vec::new();
+        if let Ok(head_id) = repo.head_id() {
+            let oid = head_id.detach();
+            if let Ok(object) = repo.find_object(oid)
+                && let Ok(commit) = object.try_into_commit()
+            {
+                use gix::bstr::ByteSlice;
+                // Just show the HEAD commit for now, as rev_walk API changed
+                let summary_bytes = commit.message_raw_sloppy();
+                let summary = summary_bytes
+                    .lines()
+                    .next()
+                    .and_then(|line| std::str::from_utf8(line).ok())
+                    .unwrap_or("<no summary>");
+                let short_id = oid.to_hex().to_string();
+                let short_id = &short_id[..7.min(short_id.len())];
+                recent_commits.push(crate::GitCommitSummary {
+                    id: short_id.to_string(),
+                    summary: summary.to_string(),
+                });
+            }
+        }
+
+        Ok(crate::GitStatus::new(head, entries, recent_commits))
+    }
+
+    /// Get a compact summary of a repository: branch, upstream divergence and
+    /// at most `max_entries` dirty paths
+    pub fn get_git_summary(
+        repo_path: &Path,
+        max_entries: usize,
+    ) -> Result<crate::GitSummary, std::io::Error> {
+        let repo = Self::open(repo_path)?;
+        let head = Self::read_head(&repo)?;
+        let entries = Self::read_entries(&repo)?;
+        let upstream = Self::read_upstream(&repo);
+
+        Ok(crate::GitSummary::new(head, upstream, entries, max_entries))
+    }
+
+    fn open(repo_path: &Path) -> Result<gix::Repository, std::io::Error> {
+        gix::discover(repo_path)
+            .map_err(|e| std::io::Error::other(format!("Failed to open git repository: {e}")))
+    }
 
-        // Get current branch
-        let head = match repo.head_name() {
+    fn read_head(repo: &gix::Repository) -> Result<crate::GitHead, std::io::Error> {
+        match repo.head_name() {
             Ok(Some(name)) => {
                 let branch = name.as_bstr().to_string();
                 let branch = branch.strip_prefix("refs/heads/").unwrap_or(&branch);
-                crate::GitHead::Branch(branch.to_string())
+                Ok(crate::GitHead::Branch(branch.to_string()))
             }
-            Ok(None) => crate::GitHead::Detached,
+            Ok(None) => Ok(crate::GitHead::Detached),
             Err(e) => {
                 if e.to_string().contains("does not exist") {
-                    crate::GitHead::Unborn
+                    Ok(crate::GitHead::Unborn)
                 } else {
-                    return Err(std::io::Error::other(format!("Failed to get HEAD: {e}")));
+                    Err(std::io::Error::other(format!("Failed to get HEAD: {e}")))
                 }
             }
-        };
+        }
+    }
 
-        // Get status
+    fn read_entries(repo: &gix::Repository) -> Result<Vec<crate::GitStatusEntry>, std::io::Error> {
         let iter = repo
             .status(gix::progress::Discard)
             .map_err(|e| std::io::Error::other(format!("Failed to get git status: {e}")))?
@@ -56,31 +107,40 @@ impl GitStatusUtils {
                 });
             }
         }
+        Ok(entries)
+    }
 
-        // Get recent commits
-        let mut recent_commits = Vec::new();
-        if let Ok(head_id) = repo.head_id() {
-            let oid = head_id.detach();
-            if let Ok(object) = repo.find_object(oid)
-                && let Ok(commit) = object.try_into_commit()
-            {
-                // Just show the HEAD commit for now, as rev_walk API changed
-                let summary_bytes = commit.message_raw_sloppy();
-                let summary = summary_bytes
-                    .lines()
-                    .next()
-                    .and_then(|line| std::str::from_utf8(line).ok())
-                    .unwrap_or("<no summary>");
-                let short_id = oid.to_hex().to_string();
-                let short_id = &short_id[..7.min(short_id.len())];
-                recent_commits.push(crate::GitCommitSummary {
-                    id: short_id.to_string(),
-                    summary: summary.to_string(),
-                });
-            }
-        }
+    /// Resolve the fetch upstream of the current branch. Returns `None` when HEAD
+    /// is not on a branch, no upstream is configured, or it has not been fetched.
+    fn read_upstream(repo: &gix::Repository) -> Option<crate::GitUpstream> {
+        let head_name = repo.head_name().ok()??;
+        let tracking = repo
+            .branch_remote_tracking_ref_name(head_name.as_ref(), gix::remote::Direction::Fetch)?
+            .ok()?;
+        let upstream_id = repo
+            .find_reference(tracking.as_ref())
+            .ok()?
+            .peel_to_id_in_place()
+            .ok()?
+            .detach();
+        let head_id = repo.head_id().ok()?.detach();
+
+        Some(crate::GitUpstream {
+            name: tracking.shorten().to_string(),
+            ahead: Self::count_exclusive_commits(repo, head_id, upstream_id)?,
+            behind: Self::count_exclusive_commits(repo, upstream_id, head_id)?,
+        })
+    }
 
-        Ok(crate::GitStatus::new(head, entries, recent_commits))
+    /// Count commits reachable from `tip` but not from `hidden`.
+    fn count_exclusive_commits(
+        repo: &gix::Repository,
+        tip: gix::ObjectId,
+        hidden: gix::ObjectId,
+    ) -> Option<u32> {
+        let walk = repo.rev_walk([tip]).with_hidden([hidden]).all().ok()?;
+        let count = walk.flatten().count();
+        Some(u32::try_from(count).unwrap_or(u32::MAX))
     }
 }
 
@@ -104,7 +164,7 @@ impl VcsProvider for GitProvider {
     }
 
     fn status(&self) -> Result<crate::VcsStatus, std::io::Error> {
-        GitStatusUtils::get_git_status(&self.root).map(crate::VcsStatus::Git)
+        GitStatusUtils::get_git_summary(&self.root, MAX_SUMMARY_ENTRIES).map(crate::VcsStatus::Git)
     }
 }
 
@@ -136,7 +196,7 @@ impl VcsUtils {
             Ok(status) => status,
             Err(err) => match provider.kind() {
                 crate::VcsKind::Git => {
-                    crate::VcsStatus::Git(crate::GitStatus::unavailable(err.to_string()))
+                    crate::VcsStatus::Git(crate::GitSummary::unavailable(err.to_string()))
                 }
                 crate::VcsKind::Jj => {
                     crate::VcsStatus::Jj(crate::JjStatus::unavailable(err.to_string()))
@@ -392,6 +452,71 @@ mod tests {
         assert!(matches!(provider.kind(), crate::VcsKind::Git));
     }
 
+    fn git(dir: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "Test User")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "Test User")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn test_git_summary_reports_upstream_and_caps_dirty_paths() {
+        let temp_dir = tempdir().unwrap();
+        let repo = temp_dir.path().join("repo");
+        git(
+            temp_dir.path(),
+            &["init", "--bare", "-b", "main", "remote.git"],
+        );
+        git(temp_dir.path(), &["init", "-b", "main", "repo"]);
+        git(&repo, &["commit", "--allow-empty", "-m", "initial"]);
+        git(&repo, &["remote", "add", "origin", "../remote.git"]);
+        git(&repo, &["push", "-q", "-u", "origin", "main"]);
+        git(&repo, &["commit", "--allow-empty", "-m", "second"]);
+        git(&repo, &["commit", "--allow-empty", "-m", "third"]);
+        for name in ["a.txt", "b.txt", "c.txt", "d.txt"] {
+            std::fs::write(repo.join(name), "content").unwrap();
+        }
+
+        let summary = GitStatusUtils::get_git_summary(&repo, 2).unwrap();
+
+        assert_eq!(summary.branch(), Some("main"));
+        let upstream = summary.upstream.as_ref().unwrap();
+        assert_eq!(upstream.name, "origin/main");
+        assert_eq!((upstream.ahead, upstream.behind), (2, 0));
+        assert_eq!(summary.entries.len(), 2);
+        assert_eq!(summary.omitted_entries, 2);
+        let rendered = summary.as_llm_string();
+        assert!(
+            rendered.starts_with("Branch: main (origin/main: ahead 2, behind 0)\nDirty files: 4\n"),
+            "{rendered}"
+        );
+        assert!(rendered.ends_with("+2 more\n"), "{rendered}");
+    }
+
+    #[test]
+    fn test_git_summary_without_upstream() {
+        let temp_dir = tempdir().unwrap();
+        git(temp_dir.path(), &["init", "-q", "-b", "main"]);
+        git(
+            temp_dir.path(),
+            &["commit", "--allow-empty", "-m", "initial"],
+        );
+
+        let summary = GitStatusUtils::get_git_summary(temp_dir.path(), 2).unwrap();
+
+        assert!(summary.upstream.is_none());
+        assert_eq!(
+            summary.as_llm_string(),
+            "Branch: main\nWorking tree clean\n"
+        );
+    }
+
     fn jj_settings() -> jj_lib::settings::UserSettings {
         let mut config = jj_lib::config::StackedConfig::with_defaults();
         let overrides = jj_lib::config::ConfigLayer::parse(
@@ -0,0 +1,251 @@
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum WorkspacePathError {
+    #[error("path '{path}' escapes the workspace root {root}")]
+    EscapesWorkspace { path: String, root: String },
+
+    #[error(
+        "path '{path}' is outside the workspace root {root}; tool file operations are restricted to the workspace unless allow_outside_workspace is enabled"
+    )]
+    OutsideWorkspace { path: String, root: String },
+}
+
+/// Resolves a tool path argument against the workspace root.
+///
+/// Relative paths are lexically normalized and, when `root` is known, joined onto
+/// it; a relative path whose `..` components climb above the root is rejected.
+/// Remote workspaces pass `None` since their root lives on the other side of the
+/// wire, which leaves the normalized path relative. Absolute paths are returned
+/// unchanged.
+pub fn normalize_workspace_path(
+    root: Option<&Path>,
+    path: &str,
+) -> Result<String, WorkspacePathError> {
+    let candidate = Path::new(path);
+    if path.is_empty() || candidate.is_absolute() {
+        return Ok(path.to_string());
+    }
+
+    let mut resolved = root.map(Path::to_path_buf).unwrap_or_default();
+    let mut depth = 0usize;
+    for component in candidate.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if depth == 0 {
+                    return Err(WorkspacePathError::EscapesWorkspace {
+                        path: path.to_string(),
+                        root: root.map_or_else(
+                            || "(remote)".to_string(),
+                            |root| root.display().to_string(),
+                        ),
+                    });
+                }
+                resolved.pop();
+                depth -= 1;
+            }
+            Component::Normal(part) => {
+                resolved.push(part);
+                depth += 1;
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                return Ok(path.to_string());
+            }
+        }
+    }
+
+    if resolved.as_os_str().is_empty() {
+        resolved = PathBuf::from(".");
+    }
+
+    Ok(resolved.to_string_lossy().into_owned())
+}
+
+/// Checks that an absolute `path` lives inside `root` once symlinks are resolved.
+///
+/// Paths that do not exist yet (e.g. a file about to be written) are checked via
+/// their nearest existing ancestor, so a symlinked parent directory cannot be used
+/// to write outside the root either.
+pub fn ensure_within_workspace(root: &Path, path: &str) -> Result<(), WorkspacePathError> {
+    let outside = || WorkspacePathError::OutsideWorkspace {
+        path: path.to_string(),
+        root: root.display().to_string(),
+    };
+
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let candidate = Path::new(path);
+    let candidate = if candidate.is_absolute() {
+        candidate.to_path_buf()
+    } else {
+        root.join(candidate)
+    };
+
+    let resolved = canonicalize_existing_prefix(&candidate).ok_or_else(outside)?;
+    if resolved.starts_with(&root) {
+        Ok(())
+    } else {
+        Err(outside())
+    }
+}
+
+/// Canonicalizes the longest existing prefix of `path` and re-appends the rest.
+///
+/// Returns `None` if the non-existent remainder contains `..`, since it cannot be
+/// resolved without knowing what those components will point at.
+fn canonicalize_existing_prefix(path: &Path) -> Option<PathBuf> {
+    let mut existing = path;
+    let mut remainder = Vec::new();
+    loop {
+        if let Ok(canonical) = existing.canonicalize() {
+            let mut resolved = canonical;
+            for component in remainder.iter().rev() {
+                match component {
+                    Component::Normal(part) => resolved.push(part),
+                    Component::CurDir => {}
+                    _ => return None,
+                }
+            }
+            return Some(resolved);
+        }
+
+        let mut components = existing.components();
+        remainder.push(components.next_back()?);
+        existing = components.as_path();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn relative_paths_resolve_against_root() {
+        let root = Some(Path::new("/work/project"));
+
+        assert_eq!(
+            normalize_workspace_path(root, "src/main.rs").unwrap(),
+            "/work/project/src/main.rs"
+        );
+        assert_eq!(
+            normalize_workspace_path(root, "./src/../Cargo.toml").unwrap(),
+            "/work/project/Cargo.toml"
+        );
+        assert_eq!(
+            normalize_workspace_path(root, ".").unwrap(),
+            "/work/project"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn absolute_paths_are_unchanged() {
+        let root = Some(Path::new("/work/project"));
+
+        assert_eq!(
+            normalize_workspace_path(root, "/tmp/notes.txt").unwrap(),
+            "/tmp/notes.txt"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn traversal_outside_root_is_rejected() {
+        let root = Some(Path::new("/work/project"));
+
+        let err = normalize_workspace_path(root, "src/../../secrets.txt").unwrap_err();
+        assert_eq!(
+            err,
+            WorkspacePathError::EscapesWorkspace {
+                path: "src/../../secrets.txt".to_string(),
+                root: "/work/project".to_string(),
+            }
+        );
+        assert!(normalize_workspace_path(root, "../sibling").is_err());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn windows_relative_paths_resolve_against_root() {
+        let root = Some(Path::new(r"C:\work\project"));
+
+        assert_eq!(
+            normalize_workspace_path(root, r"src\main.rs").unwrap(),
+            r"C:\work\project\src\main.rs"
+        );
+        assert_eq!(
+            normalize_workspace_path(root, "src/../Cargo.toml").unwrap(),
+            r"C:\work\project\Cargo.toml"
+        );
+        assert!(normalize_workspace_path(root, r"src\..\..\secrets.txt").is_err());
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn windows_absolute_and_rooted_paths_are_unchanged() {
+        let root = Some(Path::new(r"C:\work\project"));
+
+        assert_eq!(
+            normalize_workspace_path(root, r"D:\notes.txt").unwrap(),
+            r"D:\notes.txt"
+        );
+        assert_eq!(
+            normalize_workspace_path(root, r"\Windows\win.ini").unwrap(),
+            r"\Windows\win.ini"
+        );
+    }
+
+    #[test]
+    fn remote_paths_stay_relative_but_are_checked() {
+        assert_eq!(
+            normalize_workspace_path(None, "./src/../lib.rs").unwrap(),
+            "lib.rs"
+        );
+        assert_eq!(normalize_workspace_path(None, "src/..").unwrap(), ".");
+        assert!(normalize_workspace_path(None, "../outside").is_err());
+    }
+
+    #[test]
+    fn paths_inside_root_are_allowed() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir(root.join("src")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "").unwrap();
+
+        let existing = root.join("src/lib.rs");
+        let new_file = root.join("src/new/module.rs");
+        assert!(ensure_within_workspace(root, existing.to_str().unwrap()).is_ok());
+        assert!(ensure_within_workspace(root, new_file.to_str().unwrap()).is_ok());
+        assert!(ensure_within_workspace(root, root.to_str().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn paths_outside_root_are_rejected() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().join("project");
+        std::fs::create_dir(&root).unwrap();
+        let sibling = temp_dir.path().join("secrets.txt");
+        std::fs::write(&sibling, "").unwrap();
+
+        let err = ensure_within_workspace(&root, sibling.to_str().unwrap()).unwrap_err();
+        assert!(matches!(err, WorkspacePathError::OutsideWorkspace { .. }));
+        assert!(ensure_within_workspace(&root, "/etc/passwd").is_err());
+        let climbing = root.join("missing/../../secrets.txt");
+        assert!(ensure_within_workspace(&root, climbing.to_str().unwrap()).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_escaping_root_are_rejected() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root = temp_dir.path().join("project");
+        let outside = temp_dir.path().join("outside");
+        std::fs::create_dir(&root).unwrap();
+        std::fs::create_dir(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("link")).unwrap();
+
+        let through_link = root.join("link/file.txt");
+        assert!(ensure_within_workspace(&root, through_link.to_str().unwrap()).is_err());
+    }
+}
@@ -1,15 +1,31 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum, ValueHint};
+use std::net::SocketAddr;
+use std::num::{NonZeroU64, NonZeroUsize};
 use std::path::PathBuf;
 
 /// An AI-powered agent and CLI tool that assists with software engineering tasks.
 #[derive(Parser)]
 #[command(version, about, long_about = None, author)]
 pub struct Cli {
+    /// Config file with defaults for the flags below (defaults to ~/.steer/config.toml).
+    /// Environment variables override it and flags override both
+    #[arg(long, env = "STEER_CONFIG", value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
     /// Resume an existing session instead of starting a new one (local or remote modes)
     #[arg(long)]
     pub session: Option<String>,
+    /// Continue the most recent session started in this directory, or start a new one
+    #[arg(short = 'c', long = "continue", conflicts_with_all = ["session", "resume"])]
+    pub continue_session: bool,
+    /// Choose which session started in this directory to resume
+    #[arg(short = 'r', long, conflicts_with = "session")]
+    pub resume: bool,
+    /// Resume the most recently updated session from any directory (same as `--session latest`)
+    #[arg(long, conflicts_with_all = ["session", "continue_session", "resume"])]
+    pub resume_latest: bool,
     /// Optional directory to work in
-    #[arg(short, long)]
+    #[arg(short, long, value_hint = ValueHint::DirPath)]
     pub directory: Option<std::path::PathBuf>,
 
     /// Model to use (e.g., 'codex', 'opus', 'sonnet', 'gemini', 'grok', 'openai/custom-model')
@@ -28,14 +44,42 @@ pub struct Cli {
     #[arg(long)]
     pub session_config: Option<PathBuf>,
 
+    /// Run new sessions in a disposable git worktree (or copy) under ~/.steer/worktrees
+    #[arg(long)]
+    pub isolate: bool,
+
+    /// Allow tools to reach the network in new sessions; `false` hides fetch and
+    /// points bash proxy variables at localhost:0
+    #[arg(long, value_name = "BOOL")]
+    pub allow_network: Option<bool>,
+
+    /// Let file tools read and write paths outside the workspace root in new sessions
+    #[arg(long)]
+    pub allow_outside_workspace: bool,
+
+    /// Start without the network: skip token refresh and telemetry, and only allow
+    /// providers on this machine. Turned on automatically (with a warning) when the
+    /// network is unreachable at startup
+    #[arg(long, env = "STEER_OFFLINE")]
+    pub offline: bool,
+
     /// Theme to use for the TUI (defaults to "default")
     #[arg(long)]
     pub theme: Option<String>,
 
+    /// Line-by-line interface without full-screen drawing, for screen readers
+    /// and other assistive technology
+    #[arg(long, env = "STEER_PLAIN")]
+    pub plain: bool,
+
     /// Additional catalog files to load (repeatable)
     #[arg(long = "catalog", value_name = "PATH")]
     pub catalogs: Vec<PathBuf>,
 
+    /// Log file format: text or json
+    #[arg(long, value_name = "FORMAT")]
+    pub log_format: Option<steer_core::utils::tracing::LogFormat>,
+
     /// Force the welcome/setup flow to run (for testing)
     #[arg(long, hide = true)]
     pub force_setup: bool,
@@ -95,6 +139,33 @@ pub enum Commands {
         /// Additional catalog files to load (repeatable)
         #[arg(long = "catalog", value_name = "PATH")]
         catalogs: Vec<PathBuf>,
+
+        /// Print a token usage and estimated cost summary to stderr when the run finishes
+        #[arg(long)]
+        show_usage: bool,
+
+        /// Emit per-phase timing (catalog, auth, system prompt, model requests, tools) as JSON to stderr
+        #[arg(long)]
+        profile: bool,
+
+        /// Commit the files the run edited with a model-drafted message. Requires `git commit`
+        /// to be pre-approved for the bash tool and not denylisted.
+        #[arg(long)]
+        auto_commit: bool,
+
+        /// JSON schema file the final answer must match. The parsed answer is printed as
+        /// `parsed_output`; the run fails if no valid answer is produced after retries.
+        #[arg(long, value_name = "PATH")]
+        json_schema: Option<PathBuf>,
+
+        /// Reuse stored model responses for identical requests, and store new ones. Requests
+        /// made after a tool with side effects ran in the same turn always go to the provider.
+        #[arg(long, env = "STEER_RESPONSE_CACHE")]
+        response_cache: bool,
+
+        /// Directory for cached responses (defaults to ~/.steer/cache)
+        #[arg(long, env = "STEER_RESPONSE_CACHE_DIR", value_name = "DIR")]
+        response_cache_dir: Option<PathBuf>,
     },
     /// Start the gRPC server
     Server {
@@ -106,9 +177,55 @@ pub enum Commands {
         #[arg(long, default_value = "127.0.0.1")]
         bind: String,
 
+        /// Also serve an HTTP+JSON gateway on this port, on the same bind address
+        #[arg(long, value_name = "PORT")]
+        http_port: Option<u16>,
+
         /// Additional catalog files to load (repeatable)
         #[arg(long = "catalog", value_name = "PATH")]
         catalogs: Vec<PathBuf>,
+
+        /// TOML file of `[[webhooks]]` to notify on session lifecycle events
+        #[arg(long, value_name = "PATH")]
+        webhooks: Option<PathBuf>,
+
+        /// Serve Prometheus metrics over HTTP at this address (e.g. 127.0.0.1:9090)
+        #[arg(long, value_name = "ADDR")]
+        metrics_addr: Option<SocketAddr>,
+        /// Seconds to wait on shutdown for running operations to be cancelled and saved
+        /// before exiting anyway
+        #[arg(long, value_name = "SECS", default_value = "30")]
+        drain_timeout: u64,
+
+        /// Unload sessions that have had no connected clients and no running operation
+        /// for this many seconds; they are reloaded on their next request
+        #[arg(long, value_name = "SECS")]
+        idle_session_timeout: Option<NonZeroU64>,
+
+        /// Most sessions kept loaded at once; unloaded sessions don't count
+        #[arg(long, value_name = "N")]
+        max_concurrent_sessions: Option<NonZeroUsize>,
+
+        /// Tee every session's events, with secrets redacted: `stdout` (JSONL), `log`
+        /// (structured log lines) or a JSONL file path
+        #[arg(long, value_name = "DEST")]
+        log_events: Option<String>,
+
+        /// Only log sessions whose id matches this glob
+        #[arg(long, value_name = "GLOB", requires = "log_events")]
+        log_events_session: Option<String>,
+
+        /// Only log these event kinds, e.g. `tool_call_started` (repeatable)
+        #[arg(long = "log-events-kind", value_name = "KIND", requires = "log_events")]
+        log_events_kinds: Vec<String>,
+
+        /// Rotate the event log file once it exceeds this many bytes
+        #[arg(long, value_name = "BYTES", requires = "log_events")]
+        log_events_max_bytes: Option<u64>,
+
+        /// Rotated event log files to keep
+        #[arg(long, value_name = "N", default_value = "5", requires = "log_events")]
+        log_events_max_files: usize,
     },
     /// Session management commands
     Session {
@@ -120,6 +237,87 @@ pub enum Commands {
         #[command(subcommand)]
         workspace_command: WorkspaceCommands,
     },
+    /// Manage scheduled headless runs on a `steer server`
+    Task {
+        #[command(subcommand)]
+        task_command: TaskCommands,
+    },
+    /// Review a diff and print findings for CI
+    Review {
+        /// Review commits on HEAD since it diverged from this branch
+        #[arg(long, conflicts_with = "target")]
+        base: Option<String>,
+        /// What to review: staged, unstaged, branch:<name> or <from>..<to> (defaults to unstaged)
+        #[arg(long)]
+        target: Option<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ReviewOutput::Md)]
+        output: ReviewOutput,
+        /// Write the results to this file instead of stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Model to use (overrides global --model)
+        #[arg(long)]
+        model: Option<String>,
+        /// Path to session configuration file (TOML format)
+        #[arg(long)]
+        session_config: Option<PathBuf>,
+        /// Additional catalog files to load (repeatable)
+        #[arg(long = "catalog", value_name = "PATH")]
+        catalogs: Vec<PathBuf>,
+    },
+    /// Inspect builtin tools
+    Tools {
+        #[command(subcommand)]
+        tools_command: ToolsCommands,
+    },
+    /// Scaffold, extend and check model catalog files
+    Catalog {
+        #[command(subcommand)]
+        catalog_command: CatalogCommands,
+    },
+    /// Inspect stored provider logins
+    Auth {
+        #[command(subcommand)]
+        auth_command: AuthCommands,
+    },
+    /// Query the log of tools sessions have run
+    Audit {
+        #[command(subcommand)]
+        audit_command: AuditCommands,
+    },
+    /// Inspect or empty the model response cache used by `headless --response-cache`
+    Cache {
+        /// Cache directory (defaults to ~/.steer/cache)
+        #[arg(
+            long,
+            env = "STEER_RESPONSE_CACHE_DIR",
+            value_name = "DIR",
+            global = true
+        )]
+        dir: Option<PathBuf>,
+        #[command(subcommand)]
+        cache_command: CacheCommands,
+    },
+    /// Update steer to the latest GitHub release
+    SelfUpdate {
+        /// Only check whether a newer release is available
+        #[arg(long)]
+        check: bool,
+    },
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate completions for
+        shell: clap_complete::Shell,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReviewOutput {
+    /// Markdown grouped by severity
+    Md,
+    /// SARIF 2.1.0 for code scanning annotations
+    Sarif,
 }
 
 #[derive(Subcommand, Clone)]
@@ -142,6 +340,9 @@ pub enum SessionCommands {
         /// Limit number of sessions to show
         #[arg(long, default_value = "20")]
         limit: u32,
+        /// Print each session's conversation summary under its row
+        #[arg(long)]
+        summaries: bool,
     },
     /// Create a new session
     Create {
@@ -167,9 +368,60 @@ pub enum SessionCommands {
     Show {
         /// Session ID to show
         session_id: String,
+        /// Refresh the conversation summary before showing it
+        #[arg(long)]
+        summarize: bool,
+    },
+    /// Show the estimated token breakdown of the session's next request
+    Context {
+        /// Session ID to inspect
+        session_id: String,
+        /// Model whose context window to compare against (defaults to the session's model)
+        #[arg(short, long)]
+        model: Option<String>,
+        /// Only show one segment: system, environment, tools or messages
+        #[arg(long)]
+        segment: Option<String>,
+    },
+    /// Inspect or bring back changes from an isolated session's worktree
+    Isolation {
+        /// What to do with the worktree
+        #[arg(value_enum)]
+        action: IsolationAction,
+        /// Session ID of an isolated session
+        session_id: String,
+        /// Write the patch to this file instead of stdout (patch only)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// Commit message for the session branch (branch only)
+        #[arg(long)]
+        message: Option<String>,
+    },
+    /// List operations running on a server, or cancel one
+    Ops {
+        /// Operation ID to cancel
+        #[arg(long, value_name = "OP_ID")]
+        cancel: Option<String>,
+        /// Abort the operation's model and tool calls instead of waiting for them to stop
+        #[arg(long, requires = "cancel")]
+        force: bool,
     },
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum IsolationAction {
+    /// Show the worktree and its changed files
+    Status,
+    /// Apply the changes to the directory the session started in
+    Merge,
+    /// Print the changes as a patch
+    Patch,
+    /// Commit the changes on the session's branch
+    Branch,
+    /// Delete the worktree and its branch
+    Discard,
+}
+
 #[derive(Subcommand, Clone)]
 pub enum WorkspaceCommands {
     /// List workspaces
@@ -188,3 +440,128 @@ pub enum WorkspaceCommands {
         session_id: Option<String>,
     },
 }
+
+#[derive(Subcommand, Clone)]
+pub enum TaskCommands {
+    /// Schedule a prompt to run headlessly on a cron schedule
+    Create {
+        /// Cron expression: five fields (min hour day month weekday) or six with seconds
+        #[arg(long)]
+        schedule: String,
+        /// Prompt sent to each run's new session
+        #[arg(long)]
+        prompt: String,
+        /// Path to session configuration file (TOML format)
+        #[arg(long)]
+        session_config: Option<PathBuf>,
+        /// Model to run with (e.g., 'codex', 'opus', 'sonnet')
+        #[arg(short, long)]
+        model: Option<String>,
+        /// Cancel a run once it has used this many tokens
+        #[arg(long)]
+        max_tokens: Option<u64>,
+    },
+    /// List scheduled tasks
+    List,
+    /// Show a scheduled task and its last run
+    Show {
+        /// Task ID to show
+        task_id: String,
+    },
+    /// Start a run of a task immediately
+    RunNow {
+        /// Task ID to run
+        task_id: String,
+    },
+    /// Delete a scheduled task
+    Delete {
+        /// Task ID to delete
+        task_id: String,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum AuditCommands {
+    /// List recorded tool executions, oldest first
+    List {
+        /// Only executions from this session
+        #[arg(long)]
+        session: Option<String>,
+        /// Only executions of this tool (e.g. `bash`)
+        #[arg(long)]
+        tool: Option<String>,
+        /// Only executions since an RFC 3339 time or a relative age such as `30m`, `12h` or `7d`
+        #[arg(long)]
+        since: Option<String>,
+        /// Print each entry as a JSON line instead of a table
+        #[arg(long)]
+        jsonl: bool,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum CacheCommands {
+    /// Show how many responses are cached and their total size
+    Stats,
+    /// Delete every cached response
+    Clear,
+}
+
+#[derive(Subcommand, Clone)]
+pub enum AuthCommands {
+    /// Show which account each logged-in provider is using
+    Whoami,
+}
+
+#[derive(Subcommand, Clone)]
+pub enum ToolsCommands {
+    /// Print the schema and description the model sees for each builtin tool
+    Schema {
+        /// Emit JSON (default)
+        #[arg(long, conflicts_with = "markdown")]
+        json: bool,
+        /// Emit Markdown documentation
+        #[arg(long)]
+        markdown: bool,
+    },
+}
+
+#[derive(Subcommand, Clone)]
+pub enum CatalogCommands {
+    /// Write a starter catalog with commented examples for each provider API format
+    Init {
+        /// Where to write the catalog
+        path: PathBuf,
+        /// Overwrite the file if it already exists
+        #[arg(long)]
+        force: bool,
+    },
+    /// Append a model to a catalog, prompting for anything not given as a flag
+    AddModel {
+        /// Catalog file to append to
+        path: PathBuf,
+        /// Provider id the model belongs to
+        #[arg(long)]
+        provider: Option<String>,
+        /// Model id as sent to the provider's API
+        #[arg(long)]
+        id: Option<String>,
+        /// Name shown in model pickers
+        #[arg(long)]
+        display_name: Option<String>,
+        /// Alias usable with --model (repeatable)
+        #[arg(long = "alias")]
+        aliases: Vec<String>,
+        /// Context window size in tokens
+        #[arg(long)]
+        context_window: Option<u32>,
+        /// Maximum output tokens per request
+        #[arg(long)]
+        max_output_tokens: Option<u32>,
+    },
+    /// Check that a catalog parses and loads alongside the built-in catalog
+    Validate {
+        /// Catalog file to check
+        path: PathBuf,
+    },
+}
@@ -1,7 +1,228 @@
 use dotenvy::dotenv;
-use eyre::Result;
+use eyre::{Result, WrapErr, eyre};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use steer_core::utils::tracing::LogFormat;
+
+use super::Cli;
 
 pub fn load_env() -> Result<()> {
     dotenv().ok();
     Ok(())
 }
+
+/// Defaults for the top-level flags, read from `--config <PATH>` or
+/// `~/.steer/config.toml`.
+///
+/// Each setting is resolved in this order, later sources winning:
+///
+/// 1. preferences (`default_model`) and built-in defaults
+/// 2. the config file
+/// 3. environment variables (`STEER_MODEL`, `STEER_THEME`, `STEER_SESSION_CONFIG`,
+///    `STEER_CATALOGS`, `STEER_LOG_FORMAT`)
+/// 4. command-line flags
+///
+/// Tool policy (approvals, visibility, MCP backends) lives in a session config
+/// file; `session_config` points new sessions at one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub model: Option<String>,
+    pub theme: Option<String>,
+    pub session_config: Option<PathBuf>,
+    /// Replaces, rather than extends, the catalogs of lower layers
+    pub catalogs: Option<Vec<PathBuf>>,
+    pub log_format: Option<LogFormat>,
+}
+
+impl Config {
+    /// The user-level config file: `~/.steer/config.toml`.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".steer").join("config.toml"))
+    }
+
+    /// Loads `path`, or the default file when `path` is `None`. Only an
+    /// explicitly named file has to exist.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let (path, required) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match Self::default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Self::default()),
+            },
+        };
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if !required && e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self::default());
+            }
+            Err(e) => return Err(eyre!("Failed to read config {}: {e}", path.display())),
+        };
+        let base_dir = path.parent().unwrap_or(Path::new("."));
+        Self::from_toml(&contents, base_dir)
+            .wrap_err_with(|| format!("Invalid config {}", path.display()))
+    }
+
+    /// Parses a config file. Relative paths are resolved against `base_dir`,
+    /// the directory holding the file.
+    pub fn from_toml(contents: &str, base_dir: &Path) -> Result<Self> {
+        let mut config: Self = toml::from_str(contents)?;
+        if let Some(path) = &mut config.session_config {
+            *path = base_dir.join(&*path);
+        }
+        for path in config.catalogs.iter_mut().flatten() {
+            *path = base_dir.join(&*path);
+        }
+        Ok(config)
+    }
+
+    /// Reads the `STEER_*` overrides through `var`.
+    pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let log_format = var("STEER_LOG_FORMAT")
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|e| eyre!("Invalid STEER_LOG_FORMAT: {e}"))
+            })
+            .transpose()?;
+        Ok(Self {
+            model: var("STEER_MODEL"),
+            theme: var("STEER_THEME"),
+            session_config: var("STEER_SESSION_CONFIG").map(PathBuf::from),
+            catalogs: var("STEER_CATALOGS").map(|paths| std::env::split_paths(&paths).collect()),
+            log_format,
+        })
+    }
+
+    /// The settings given as flags on the command line.
+    pub fn from_cli(cli: &Cli) -> Self {
+        Self {
+            model: cli.model.clone(),
+            theme: cli.theme.clone(),
+            session_config: cli.session_config.clone(),
+            catalogs: (!cli.catalogs.is_empty()).then(|| cli.catalogs.clone()),
+            log_format: cli.log_format,
+        }
+    }
+
+    /// Layers `over` on top of `self`; settings present in `over` win.
+    pub fn merge(self, over: Self) -> Self {
+        Self {
+            model: over.model.or(self.model),
+            theme: over.theme.or(self.theme),
+            session_config: over.session_config.or(self.session_config),
+            catalogs: over.catalogs.or(self.catalogs),
+            log_format: over.log_format.or(self.log_format),
+        }
+    }
+
+    /// Resolves the config file, environment and flags for `cli`, and writes
+    /// the result back into its top-level flags.
+    pub fn resolve(cli: &mut Cli) -> Result<Self> {
+        let config = Self::load(cli.config.as_deref())?
+            .merge(Self::from_env(|name| std::env::var(name).ok())?)
+            .merge(Self::from_cli(cli));
+        cli.model.clone_from(&config.model);
+        cli.theme.clone_from(&config.theme);
+        cli.session_config.clone_from(&config.session_config);
+        cli.catalogs = config.catalogs.clone().unwrap_or_default();
+        cli.log_format = config.log_format;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> Config {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect();
+        Config::from_env(|name| vars.get(name).cloned()).unwrap()
+    }
+
+    #[test]
+    fn relative_paths_resolve_against_config_dir() {
+        let config = Config::from_toml(
+            r#"
+model = "opus"
+session_config = "session.toml"
+catalogs = ["catalog.toml", "/abs/catalog.toml"]
+log_format = "json"
+"#,
+            Path::new("/home/me/.steer"),
+        )
+        .unwrap();
+
+        assert_eq!(config.model.as_deref(), Some("opus"));
+        assert_eq!(
+            config.session_config,
+            Some(PathBuf::from("/home/me/.steer/session.toml"))
+        );
+        assert_eq!(
+            config.catalogs,
+            Some(vec![
+                PathBuf::from("/home/me/.steer/catalog.toml"),
+                PathBuf::from("/abs/catalog.toml"),
+            ])
+        );
+        assert_eq!(config.log_format, Some(LogFormat::Json));
+        assert!(Config::from_toml("unknown = 1", Path::new(".")).is_err());
+    }
+
+    #[test]
+    fn env_overrides_file_and_flags_override_env() {
+        let file = Config::from_toml(
+            r#"
+model = "opus"
+theme = "gruvbox"
+catalogs = ["file.toml"]
+log_format = "json"
+"#,
+            Path::new("/cfg"),
+        )
+        .unwrap();
+        let env = env(&[("STEER_MODEL", "sonnet"), ("STEER_LOG_FORMAT", "text")]);
+        let cli =
+            Cli::try_parse_from(["steer", "--model", "gpt-5", "--catalog", "flag.toml"]).unwrap();
+
+        let from_env = file.clone().merge(env.clone());
+        assert_eq!(from_env.model.as_deref(), Some("sonnet"));
+        assert_eq!(from_env.theme.as_deref(), Some("gruvbox"));
+        assert_eq!(from_env.log_format, Some(LogFormat::Text));
+        assert_eq!(
+            from_env.catalogs,
+            Some(vec![PathBuf::from("/cfg/file.toml")])
+        );
+
+        let merged = file.merge(env).merge(Config::from_cli(&cli));
+        assert_eq!(merged.model.as_deref(), Some("gpt-5"));
+        assert_eq!(merged.theme.as_deref(), Some("gruvbox"));
+        assert_eq!(merged.catalogs, Some(vec![PathBuf::from("flag.toml")]));
+        assert_eq!(merged.log_format, Some(LogFormat::Text));
+    }
+
+    #[test]
+    fn env_rejects_unknown_log_format() {
+        assert!(
+            Config::from_env(|name| (name == "STEER_LOG_FORMAT").then(|| "xml".into())).is_err()
+        );
+    }
+
+    #[test]
+    fn missing_default_config_is_empty_but_explicit_path_is_required() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(Config::load(Some(&dir.path().join("missing.toml"))).is_err());
+
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "theme = \"light\"").unwrap();
+        assert_eq!(
+            Config::load(Some(&path)).unwrap().theme.as_deref(),
+            Some("light")
+        );
+    }
+}
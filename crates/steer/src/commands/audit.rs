@@ -0,0 +1,196 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Local, Utc};
+use eyre::{Result, eyre};
+use futures::StreamExt;
+use std::io::Write;
+use std::path::PathBuf;
+
+use steer_core::app::domain::session::{
+    SqliteEventStore, ToolAuditEntry, ToolAuditFilter, ToolAuditStore,
+};
+use steer_core::app::domain::types::SessionId;
+use steer_grpc::AgentClient;
+
+use super::Command;
+use crate::cli::AuditCommands;
+
+pub struct AuditCommand {
+    pub command: AuditCommands,
+    pub remote: Option<String>,
+    pub session_db: Option<PathBuf>,
+}
+
+#[async_trait]
+impl Command for AuditCommand {
+    async fn execute(&self) -> Result<()> {
+        match &self.command {
+            AuditCommands::List {
+                session,
+                tool,
+                since,
+                jsonl,
+            } => {
+                let filter = ToolAuditFilter {
+                    session_id: session
+                        .as_deref()
+                        .map(|id| {
+                            uuid::Uuid::parse_str(id)
+                                .map(SessionId)
+                                .map_err(|_| eyre!("Invalid session ID: {id}"))
+                        })
+                        .transpose()?,
+                    tool_name: tool.clone(),
+                    since: since
+                        .as_deref()
+                        .map(|since| parse_since(since, Utc::now()))
+                        .transpose()?,
+                };
+
+                let lines = match &self.remote {
+                    Some(remote) => remote_entries(remote, &filter).await?,
+                    None => self.local_entries(&filter).await?,
+                };
+
+                let mut stdout = std::io::stdout();
+                if *jsonl {
+                    for line in &lines {
+                        writeln!(stdout, "{line}")?;
+                    }
+                    return Ok(());
+                }
+
+                let entries = lines
+                    .iter()
+                    .map(|line| serde_json::from_str::<ToolAuditEntry>(line))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| eyre!("Failed to parse audit entry: {e}"))?;
+                print_table(&mut stdout, &entries)
+            }
+        }
+    }
+}
+
+impl AuditCommand {
+    async fn local_entries(&self, filter: &ToolAuditFilter) -> Result<Vec<String>> {
+        let db_path = match &self.session_db {
+            Some(path) => path.clone(),
+            None => steer_core::utils::session::create_session_store_path()?,
+        };
+
+        let store = SqliteEventStore::open_configured(&db_path)
+            .await
+            .map_err(|e| eyre!("Failed to open session database: {e}"))?;
+
+        store
+            .list_tool_executions(filter)
+            .await
+            .map_err(|e| eyre!("Failed to read audit log: {e}"))?
+            .iter()
+            .map(|entry| serde_json::to_string(entry).map_err(Into::into))
+            .collect()
+    }
+}
+
+async fn remote_entries(remote: &str, filter: &ToolAuditFilter) -> Result<Vec<String>> {
+    let client = AgentClient::connect(remote)
+        .await
+        .map_err(|e| eyre!("Failed to connect to server at {remote}: {e}"))?;
+
+    let mut stream = client
+        .export_audit(filter)
+        .await
+        .map_err(|e| eyre!("Failed to export audit log: {e}"))?;
+
+    let mut lines = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| eyre!("Failed to export audit log: {e}"))?;
+        lines.extend(chunk.lines().map(String::from));
+    }
+    Ok(lines)
+}
+
+fn print_table(out: &mut impl Write, entries: &[ToolAuditEntry]) -> Result<()> {
+    if entries.is_empty() {
+        writeln!(out, "No tool executions recorded.")?;
+        return Ok(());
+    }
+
+    writeln!(
+        out,
+        "{:<20} {:<36} {:<16} {:<10} {:>9} {:<8} Parameters",
+        "Started", "Session", "Tool", "Status", "Duration", "Approved"
+    )?;
+    writeln!(out, "{}", "-".repeat(130))?;
+    for entry in entries {
+        writeln!(
+            out,
+            "{:<20} {:<36} {:<16} {:<10} {:>7}ms {:<8} {}",
+            entry
+                .started_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M:%S"),
+            entry.session_id,
+            entry.tool_name,
+            serde_plain(&entry.status)?,
+            entry.duration_ms,
+            serde_plain(&entry.approved_by)?,
+            entry.parameters,
+        )?;
+    }
+    Ok(())
+}
+
+/// The snake_case name a unit enum variant serializes to.
+fn serde_plain(value: &impl serde::Serialize) -> Result<String> {
+    match serde_json::to_value(value)? {
+        serde_json::Value::String(name) => Ok(name),
+        other => Ok(other.to_string()),
+    }
+}
+
+/// Parse an RFC 3339 timestamp, or an age such as `30m`, `12h` or `7d`
+/// counted back from `now`.
+fn parse_since(input: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(input) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+
+    let invalid =
+        || eyre!("Invalid --since '{input}': expected an RFC 3339 time or an age like 12h");
+    let unit_start = input
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(invalid)?;
+    let (amount, unit) = input.split_at(unit_start);
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    let age = match unit {
+        "s" => Duration::seconds(amount),
+        "m" => Duration::minutes(amount),
+        "h" => Duration::hours(amount),
+        "d" => Duration::days(amount),
+        "w" => Duration::weeks(amount),
+        _ => return Err(invalid()),
+    };
+    Ok(now - age)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_since_as_timestamp_or_age() {
+        let now = DateTime::parse_from_rfc3339("2025-06-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            parse_since("2025-06-01T08:30:00+02:00", now).unwrap(),
+            DateTime::parse_from_rfc3339("2025-06-01T06:30:00Z").unwrap()
+        );
+        assert_eq!(parse_since("12h", now).unwrap(), now - Duration::hours(12));
+        assert_eq!(parse_since("7d", now).unwrap(), now - Duration::days(7));
+        assert!(parse_since("12", now).is_err());
+        assert!(parse_since("h", now).is_err());
+        assert!(parse_since("3y", now).is_err());
+    }
+}
@@ -1,2 +1,59 @@
-// Authentication commands have been consolidated into the TUI.
-// Run `steer auth setup` to launch the interactive authentication setup.
\ No newline at end of file
+use async_trait::async_trait;
+use eyre::{Result, eyre};
+use std::io::Write;
+use std::sync::Arc;
+use steer_core::auth::{AuthError, AuthPluginRegistry, AuthStorage, DefaultAuthStorage};
+
+use super::Command;
+use crate::cli::AuthCommands;
+
+pub struct AuthCommand {
+    pub command: AuthCommands,
+}
+
+#[async_trait]
+impl Command for AuthCommand {
+    async fn execute(&self) -> Result<()> {
+        match &self.command {
+            AuthCommands::Whoami => whoami().await,
+        }
+    }
+}
+
+async fn whoami() -> Result<()> {
+    let storage: Arc<dyn AuthStorage> = Arc::new(
+        DefaultAuthStorage::new().map_err(|e| eyre!("Failed to create auth storage: {}", e))?,
+    );
+    let registry = AuthPluginRegistry::with_defaults()?;
+    let mut plugins: Vec<_> = registry.all().collect();
+    plugins.sort_by_key(|plugin| plugin.provider_id().0);
+
+    let mut stdout = std::io::stdout();
+    for plugin in plugins {
+        let provider = plugin.provider_id().0;
+        match plugin.account_info(storage.clone()).await {
+            Ok(Some(info)) => {
+                writeln!(stdout, "{provider}: logged in")?;
+                if let Some(email) = &info.email {
+                    writeln!(stdout, "  email:        {email}")?;
+                }
+                if let Some(organization) = &info.organization {
+                    writeln!(stdout, "  organization: {organization}")?;
+                }
+                if let Some(account_id) = &info.account_id {
+                    writeln!(stdout, "  account:      {account_id}")?;
+                }
+            }
+            Ok(None) => writeln!(stdout, "{provider}: not logged in")?,
+            Err(AuthError::ReauthRequired) => {
+                writeln!(
+                    stdout,
+                    "{provider}: login expired, run `steer` to log in again"
+                )?;
+            }
+            Err(e) => writeln!(stdout, "{provider}: could not read account ({e})")?,
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+use eyre::{Result, eyre};
+use std::io::Write;
+use std::path::PathBuf;
+use steer_core::api::ResponseCache;
+
+use super::Command;
+use crate::cli::CacheCommands;
+
+pub struct CacheCommand {
+    pub command: CacheCommands,
+    pub dir: PathBuf,
+}
+
+#[async_trait]
+impl Command for CacheCommand {
+    async fn execute(&self) -> Result<()> {
+        let cache = ResponseCache::new(self.dir.clone());
+        let mut stdout = std::io::stdout();
+        match &self.command {
+            CacheCommands::Stats => {
+                let stats = cache
+                    .stats()
+                    .await
+                    .map_err(|e| eyre!("Failed to read {}: {e}", self.dir.display()))?;
+                writeln!(stdout, "Directory: {}", self.dir.display())?;
+                writeln!(stdout, "Responses: {}", stats.entries)?;
+                writeln!(stdout, "Size:      {} bytes", stats.bytes)?;
+            }
+            CacheCommands::Clear => {
+                let removed = cache
+                    .clear()
+                    .await
+                    .map_err(|e| eyre!("Failed to clear {}: {e}", self.dir.display()))?;
+                writeln!(
+                    stdout,
+                    "Removed {removed} cached responses from {}",
+                    self.dir.display()
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,318 @@
+use async_trait::async_trait;
+use eyre::{Result, bail, eyre};
+use serde::Serialize;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::Path;
+use steer_core::catalog::{CatalogConfig, load_registries};
+use steer_core::config::toml_types::{Catalog, ModelData, ModelParameters};
+
+use super::Command;
+use crate::cli::CatalogCommands;
+
+const STARTER_CATALOG: &str = include_str!("catalog_template.toml");
+
+pub struct CatalogCommand {
+    pub command: CatalogCommands,
+}
+
+#[async_trait]
+impl Command for CatalogCommand {
+    async fn execute(&self) -> Result<()> {
+        let mut stdout = std::io::stdout();
+        match &self.command {
+            CatalogCommands::Init { path, force } => {
+                init_catalog(path, *force)?;
+                writeln!(stdout, "Wrote starter catalog to {}", path.display())?;
+                writeln!(stdout, "Use it with `steer --catalog {}`", path.display())?;
+            }
+            CatalogCommands::AddModel {
+                path,
+                provider,
+                id,
+                display_name,
+                aliases,
+                context_window,
+                max_output_tokens,
+            } => {
+                let model = NewModel {
+                    provider: provider.clone(),
+                    id: id.clone(),
+                    display_name: display_name.clone(),
+                    aliases: aliases.clone(),
+                    context_window: *context_window,
+                    max_output_tokens: *max_output_tokens,
+                };
+                let model = if std::io::stdin().is_terminal() {
+                    prompt_for_missing(model, path)?
+                } else {
+                    model
+                };
+                let added = add_model(path, model)?;
+                writeln!(
+                    stdout,
+                    "Added {}/{} to {}",
+                    added.provider,
+                    added.id,
+                    path.display()
+                )?;
+            }
+            CatalogCommands::Validate { path } => {
+                let summary = validate_catalog(path)?;
+                writeln!(
+                    stdout,
+                    "{} is valid: {} provider(s), {} model(s)",
+                    path.display(),
+                    summary.providers,
+                    summary.models
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Write the commented starter catalog to `path`.
+pub fn init_catalog(path: &Path, force: bool) -> Result<()> {
+    if path.exists() && !force {
+        bail!(
+            "{} already exists; pass --force to overwrite it",
+            path.display()
+        );
+    }
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, STARTER_CATALOG)?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CatalogSummary {
+    pub providers: usize,
+    pub models: usize,
+}
+
+/// Parse `path` and load it over the built-in and discovered catalogs, the
+/// same way `--catalog` does, so every registry check applies.
+pub fn validate_catalog(path: &Path) -> Result<CatalogSummary> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| eyre!("Failed to read {}: {e}", path.display()))?;
+    let catalog: Catalog =
+        toml::from_str(&contents).map_err(|e| eyre!("Failed to parse {}: {e}", path.display()))?;
+
+    load_registries(&CatalogConfig::with_catalogs(vec![
+        path.to_string_lossy().into_owned(),
+    ]))
+    .map_err(|e| eyre!("{} is invalid: {e}", path.display()))?;
+
+    Ok(CatalogSummary {
+        providers: catalog.providers.len(),
+        models: catalog.models.len(),
+    })
+}
+
+/// A model entry collected from flags and prompts.
+#[derive(Debug, Clone, Default)]
+pub struct NewModel {
+    pub provider: Option<String>,
+    pub id: Option<String>,
+    pub display_name: Option<String>,
+    pub aliases: Vec<String>,
+    pub context_window: Option<u32>,
+    pub max_output_tokens: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct ModelsBlock<'a> {
+    models: [&'a ModelData; 1],
+}
+
+/// Append a `[[models]]` entry to `path`. The file is left untouched when
+/// the result would not validate.
+pub fn add_model(path: &Path, model: NewModel) -> Result<ModelData> {
+    let provider = model
+        .provider
+        .filter(|p| !p.trim().is_empty())
+        .ok_or_else(|| eyre!("--provider is required"))?;
+    let id = model
+        .id
+        .filter(|id| !id.trim().is_empty())
+        .ok_or_else(|| eyre!("--id is required"))?;
+    let max_output_tokens = model
+        .max_output_tokens
+        .ok_or_else(|| eyre!("--max-output-tokens is required"))?;
+
+    let data = ModelData {
+        provider: provider.trim().to_string(),
+        id: id.trim().to_string(),
+        display_name: model.display_name.filter(|name| !name.trim().is_empty()),
+        aliases: model.aliases,
+        recommended: false,
+        context_window_tokens: model.context_window,
+        parameters: Some(ModelParameters {
+            max_output_tokens: Some(max_output_tokens),
+            ..ModelParameters::default()
+        }),
+        pricing: None,
+    };
+
+    let original = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(eyre!("Failed to read {}: {e}", path.display())),
+    };
+    let block = toml::to_string(&ModelsBlock { models: [&data] })?;
+    let separator = if original.is_empty() || original.ends_with("\n\n") {
+        ""
+    } else if original.ends_with('\n') {
+        "\n"
+    } else {
+        "\n\n"
+    };
+    std::fs::write(path, format!("{original}{separator}{block}"))?;
+
+    if let Err(e) = validate_catalog(path) {
+        std::fs::write(path, original)?;
+        return Err(e.wrap_err("Model was not added"));
+    }
+    Ok(data)
+}
+
+/// Ask for any required field, and offer the optional ones, that were not
+/// given as flags.
+fn prompt_for_missing(mut model: NewModel, path: &Path) -> Result<NewModel> {
+    let mut stdout = std::io::stdout();
+    if model.provider.is_none() {
+        let providers = known_provider_ids(path);
+        if !providers.is_empty() {
+            writeln!(stdout, "Known providers: {}", providers.join(", "))?;
+        }
+        model.provider = prompt("Provider id")?;
+    }
+    if model.id.is_none() {
+        model.id = prompt("Model id (as sent to the API)")?;
+    }
+    if model.display_name.is_none() {
+        model.display_name = prompt("Display name (optional)")?;
+    }
+    if model.aliases.is_empty()
+        && let Some(aliases) = prompt("Aliases, comma-separated (optional)")?
+    {
+        model.aliases = aliases
+            .split(',')
+            .map(str::trim)
+            .filter(|alias| !alias.is_empty())
+            .map(String::from)
+            .collect();
+    }
+    if model.context_window.is_none() {
+        model.context_window = prompt_number("Context window tokens (optional)")?;
+    }
+    if model.max_output_tokens.is_none() {
+        model.max_output_tokens = prompt_number("Max output tokens")?;
+    }
+    Ok(model)
+}
+
+fn known_provider_ids(path: &Path) -> Vec<String> {
+    let catalogs = if path.exists() {
+        vec![path.to_string_lossy().into_owned()]
+    } else {
+        Vec::new()
+    };
+    let Ok((_, providers)) = load_registries(&CatalogConfig::with_catalogs(catalogs)) else {
+        return Vec::new();
+    };
+    let mut ids: Vec<String> = providers.all().map(|p| p.id.storage_key()).collect();
+    ids.sort();
+    ids
+}
+
+fn prompt(label: &str) -> Result<Option<String>> {
+    let mut stdout = std::io::stdout();
+    write!(stdout, "{label}: ")?;
+    stdout.flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    let answer = answer.trim();
+    Ok((!answer.is_empty()).then(|| answer.to_string()))
+}
+
+fn prompt_number(label: &str) -> Result<Option<u32>> {
+    loop {
+        let Some(answer) = prompt(label)? else {
+            return Ok(None);
+        };
+        match answer.replace('_', "").parse() {
+            Ok(value) => return Ok(Some(value)),
+            Err(_) => writeln!(std::io::stdout(), "Please enter a whole number")?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn starter_catalog_passes_validation() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("nested").join("catalog.toml");
+
+        init_catalog(&path, false).unwrap();
+        let summary = validate_catalog(&path).unwrap();
+        assert_eq!(
+            summary,
+            CatalogSummary {
+                providers: 1,
+                models: 1
+            }
+        );
+
+        assert!(init_catalog(&path, false).is_err());
+        init_catalog(&path, true).unwrap();
+    }
+
+    #[test]
+    fn add_model_appends_a_valid_entry_and_rejects_bad_ones() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("catalog.toml");
+        init_catalog(&path, false).unwrap();
+
+        add_model(
+            &path,
+            NewModel {
+                provider: Some("local".to_string()),
+                id: Some("qwen2.5-coder:32b".to_string()),
+                display_name: Some("Qwen 2.5 Coder".to_string()),
+                aliases: vec!["qwen-coder".to_string()],
+                context_window: Some(32_768),
+                max_output_tokens: Some(8192),
+            },
+        )
+        .unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with(STARTER_CATALOG));
+        assert_eq!(
+            validate_catalog(&path).unwrap(),
+            CatalogSummary {
+                providers: 1,
+                models: 2
+            }
+        );
+
+        let unknown_provider = add_model(
+            &path,
+            NewModel {
+                provider: Some("nowhere".to_string()),
+                id: Some("ghost".to_string()),
+                max_output_tokens: Some(1024),
+                ..NewModel::default()
+            },
+        );
+        assert!(unknown_provider.is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), contents);
+    }
+}
@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use clap::CommandFactory;
+use clap_complete::Shell;
+use eyre::Result;
+use std::io::Write;
+
+use super::Command;
+use crate::cli::Cli;
+
+pub struct CompletionsCommand {
+    pub shell: Shell,
+}
+
+#[async_trait]
+impl Command for CompletionsCommand {
+    async fn execute(&self) -> Result<()> {
+        let mut stdout = std::io::stdout();
+        write_completions(self.shell, &mut stdout);
+        stdout.flush()?;
+        Ok(())
+    }
+}
+
+fn write_completions(shell: Shell, out: &mut dyn Write) {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, out);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ValueEnum;
+
+    #[test]
+    fn every_shell_gets_a_script_with_subcommands() {
+        for shell in Shell::value_variants() {
+            let mut script = Vec::new();
+            write_completions(*shell, &mut script);
+            let script = String::from_utf8(script).unwrap();
+            assert!(!script.is_empty(), "{shell} script is empty");
+            assert!(
+                script.contains("self-update"),
+                "{shell} script lacks subcommands"
+            );
+        }
+    }
+}
@@ -3,6 +3,7 @@ use eyre::{Result, eyre};
 use std::fs;
 use std::io::{self, Read, Write};
 use std::path::PathBuf;
+use steer_core::api::ResponseCache;
 use steer_core::app::domain::types::SessionId;
 use steer_core::tools::{DISPATCH_AGENT_TOOL_NAME, FETCH_TOOL_NAME};
 use steer_tools::tools::{
@@ -15,6 +16,9 @@ use super::Command;
 use crate::session_config::{SessionConfigLoader, SessionConfigOverrides};
 use steer_core::app::MessageData;
 use steer_core::app::conversation::{Message, UserContent};
+use steer_core::model_registry::ModelRegistry;
+use steer_core::profiling::Profiler;
+use steer_core::runners::{AutoCommit, OneShotRunner, RunUsage};
 use steer_core::session::{ApprovalRulesOverrides, ToolApprovalPolicyOverrides};
 
 pub struct HeadlessCommand {
@@ -25,28 +29,86 @@ pub struct HeadlessCommand {
     pub session_config: Option<PathBuf>,
     pub remote: Option<String>,
     pub directory: Option<PathBuf>,
+    pub allow_network: Option<bool>,
+    pub allow_outside_workspace: bool,
     pub catalogs: Vec<PathBuf>,
+    pub show_usage: bool,
+    pub profile: bool,
+    pub auto_commit: bool,
+    /// JSON schema file the final answer must match.
+    pub json_schema: Option<PathBuf>,
+    /// Directory of the model response cache, when caching is enabled.
+    pub response_cache: Option<PathBuf>,
 }
 
 #[async_trait]
 impl Command for HeadlessCommand {
     async fn execute(&self) -> Result<()> {
         let message = self.extract_message()?;
+        let schema = self.load_json_schema()?;
         let model_to_use = self.model.as_ref().unwrap_or(&self.global_model);
         let normalized_catalogs = self.normalize_catalog_paths();
 
-        let (runtime, model) =
-            crate::create_runtime_with_catalogs(model_to_use.clone(), normalized_catalogs.clone())
-                .await?;
+        let profiler = self.profile.then(Profiler::new);
+        let mut builder = crate::RuntimeBuilder::new(model_to_use.clone())
+            .with_catalogs(normalized_catalogs.clone());
+        if let Some(profiler) = &profiler {
+            builder = builder.with_profiler(profiler.clone());
+        }
+        if let Some(dir) = &self.response_cache {
+            builder = builder.with_response_cache(ResponseCache::new(dir.clone()));
+        }
+        let (runtime, model) = builder.build().await?;
 
         let result = if let Some(session_id_str) = &self.session {
             let session_id = SessionId::parse(session_id_str)
                 .ok_or_else(|| eyre!("Invalid session ID: {}", session_id_str))?;
 
-            crate::run_once_in_session(&runtime.handle, session_id, message, model).await?
+            match &schema {
+                Some(schema) => {
+                    crate::run_once_in_session_with_schema(
+                        &runtime.handle,
+                        session_id,
+                        message,
+                        model.clone(),
+                        schema,
+                    )
+                    .await?
+                }
+                None => {
+                    crate::run_once_in_session(&runtime.handle, session_id, message, model.clone())
+                        .await?
+                }
+            }
         } else {
             let session_config = self.build_session_config(model.clone()).await?;
-            crate::run_once_new_session(&runtime.handle, session_config, message, model).await?
+            match &schema {
+                Some(schema) => {
+                    crate::run_once_new_session_with_schema(
+                        &runtime.handle,
+                        session_config,
+                        message,
+                        model.clone(),
+                        schema,
+                    )
+                    .await?
+                }
+                None => {
+                    crate::run_once_new_session(
+                        &runtime.handle,
+                        session_config,
+                        message,
+                        model.clone(),
+                    )
+                    .await?
+                }
+            }
+        };
+
+        let auto_commit = if self.auto_commit {
+            Some(OneShotRunner::auto_commit(&runtime.handle, result.session_id, model).await)
+        } else {
+            None
         };
 
         runtime.shutdown().await;
@@ -56,10 +118,58 @@ impl Command for HeadlessCommand {
 
         let mut stdout = io::stdout();
         writeln!(stdout, "{json_output}")?;
+
+        if self.show_usage {
+            let registry = ModelRegistry::load(&normalized_catalogs)
+                .map_err(|e| eyre!("Failed to load model catalog: {}", e))?;
+            let cost = result.usage.estimated_cost_usd(&registry);
+            let mut stderr = io::stderr();
+            write!(stderr, "{}", format_usage_summary(&result.usage, cost))?;
+        }
+
+        match auto_commit {
+            Some(Ok(AutoCommit::Committed(outcome))) => writeln!(
+                io::stderr(),
+                "Committed {} ({} file(s)): {}",
+                outcome.commit,
+                outcome.files.len(),
+                outcome.summary
+            )?,
+            Some(Ok(AutoCommit::Skipped { reason })) => {
+                writeln!(io::stderr(), "Auto-commit skipped: {reason}")?;
+            }
+            Some(Err(e)) => return Err(eyre!("Auto-commit failed: {}", e)),
+            None => {}
+        }
+
+        if let Some(profiler) = profiler {
+            let profile_json = serde_json::to_string(&profiler.report())
+                .map_err(|e| eyre!("Failed to serialize profile to JSON: {}", e))?;
+            writeln!(io::stderr(), "{profile_json}")?;
+        }
         Ok(())
     }
 }
 
+fn format_usage_summary(usage: &RunUsage, estimated_cost_usd: Option<f64>) -> String {
+    let mut summary = String::from("Usage summary:\n");
+    for entry in &usage.by_model {
+        summary.push_str(&format!(
+            "  {}: {} input, {} output tokens\n",
+            entry.model, entry.usage.input_tokens, entry.usage.output_tokens
+        ));
+    }
+    summary.push_str(&format!(
+        "  Total: {} input, {} output tokens\n",
+        usage.total.input_tokens, usage.total.output_tokens
+    ));
+    match estimated_cost_usd {
+        Some(cost) => summary.push_str(&format!("  Estimated cost: ${cost:.4}\n")),
+        None => summary.push_str("  Estimated cost: unavailable (no catalog pricing)\n"),
+    }
+    summary
+}
+
 impl HeadlessCommand {
     fn extract_message(&self) -> Result<String> {
         if let Some(json_path) = &self.messages_json {
@@ -78,7 +188,9 @@ impl HeadlessCommand {
                     .iter()
                     .find_map(|c| match c {
                         UserContent::Text { text } => Some(text.clone()),
-                        UserContent::Image { .. } | UserContent::CommandExecution { .. } => None,
+                        UserContent::Image { .. }
+                        | UserContent::Attachment { .. }
+                        | UserContent::CommandExecution { .. } => None,
                     })
                     .ok_or_else(|| eyre!("Last message must contain text content")),
                 _ => Err(eyre!("Last message must be from User")),
@@ -97,6 +209,17 @@ impl HeadlessCommand {
         }
     }
 
+    fn load_json_schema(&self) -> Result<Option<serde_json::Value>> {
+        let Some(path) = &self.json_schema else {
+            return Ok(None);
+        };
+        let content = fs::read_to_string(path)
+            .map_err(|e| eyre!("Failed to read JSON schema {}: {}", path.display(), e))?;
+        let schema = serde_json::from_str(&content)
+            .map_err(|e| eyre!("Failed to parse JSON schema {}: {}", path.display(), e))?;
+        Ok(Some(schema))
+    }
+
     fn normalize_catalog_paths(&self) -> Vec<String> {
         self.catalogs
             .iter()
@@ -120,6 +243,8 @@ impl HeadlessCommand {
     ) -> Result<steer_core::session::state::SessionConfig> {
         let overrides = SessionConfigOverrides {
             default_model: self.model.as_ref().map(|_| default_model.clone()),
+            allow_network: self.allow_network,
+            allow_outside_workspace: self.allow_outside_workspace.then_some(true),
             ..Default::default()
         };
 
@@ -166,3 +291,30 @@ impl HeadlessCommand {
         Ok(config)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use steer_core::api::provider::TokenUsage;
+    use steer_core::config::model::builtin;
+
+    #[test]
+    fn usage_summary_reports_totals_and_cost() {
+        let mut usage = RunUsage::default();
+        usage.record(
+            builtin::claude_sonnet_4_5(),
+            TokenUsage::from_input_output(1_000, 200),
+        );
+        usage.record(
+            builtin::claude_sonnet_4_5(),
+            TokenUsage::from_input_output(3_000, 800),
+        );
+
+        let summary = format_usage_summary(&usage, Some(0.027));
+
+        assert!(summary.contains("anthropic/claude-sonnet-4-5: 4000 input, 1000 output tokens"));
+        assert!(summary.contains("Total: 4000 input, 1000 output tokens"));
+        assert!(summary.contains("Estimated cost: $0.0270"));
+        assert!(format_usage_summary(&usage, None).contains("Estimated cost: unavailable"));
+    }
+}
@@ -1,10 +1,19 @@
 use async_trait::async_trait;
 use eyre::Result;
 
+pub mod audit;
+pub mod auth;
+pub mod cache;
+pub mod catalog;
+pub mod completions;
 pub mod headless;
 pub mod preferences;
+pub mod review;
+pub mod self_update;
 pub mod serve;
 pub mod session;
+pub mod task;
+pub mod tools;
 pub mod workspace;
 
 #[async_trait]
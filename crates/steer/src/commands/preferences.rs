@@ -6,6 +6,7 @@ use shell_words::split;
 use std::io::Write;
 use std::process::Command as ProcessCommand;
 use steer_core::preferences::Preferences;
+use steer_i18n::t;
 
 pub struct PreferencesCommand {
     pub action: PreferencesAction,
@@ -34,7 +35,7 @@ impl PreferencesCommand {
         let path = Preferences::config_path()?;
 
         let mut stdout = std::io::stdout();
-        writeln!(stdout, "Preferences file: {}", path.display())?;
+        writeln!(stdout, "{}", t!("preferences.file", path = path.display()))?;
         writeln!(stdout, "\n{}", toml::to_string_pretty(&prefs)?)?;
         Ok(())
     }
@@ -111,10 +112,10 @@ impl PreferencesCommand {
         if path.exists() {
             std::fs::remove_file(&path)?;
             let mut stdout = std::io::stdout();
-            writeln!(stdout, "Preferences reset to defaults")?;
+            writeln!(stdout, "{}", t!("preferences.reset"))?;
         } else {
             let mut stdout = std::io::stdout();
-            writeln!(stdout, "No preferences file found")?;
+            writeln!(stdout, "{}", t!("preferences.not_found"))?;
         }
         Ok(())
     }
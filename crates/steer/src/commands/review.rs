@@ -0,0 +1,248 @@
+use async_trait::async_trait;
+use eyre::{Result, eyre};
+use serde_json::json;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use steer_core::review::{ReviewReport, ReviewSeverity, ReviewTarget};
+use steer_core::session::state::WorkspaceConfig;
+
+use super::Command;
+use crate::cli::ReviewOutput;
+use crate::session_config::{SessionConfigLoader, SessionConfigOverrides};
+
+pub struct ReviewCommand {
+    pub target: ReviewTarget,
+    pub output: ReviewOutput,
+    pub out: Option<PathBuf>,
+    pub model: String,
+    pub session_config: Option<PathBuf>,
+    pub directory: Option<PathBuf>,
+    pub catalogs: Vec<String>,
+}
+
+#[async_trait]
+impl Command for ReviewCommand {
+    async fn execute(&self) -> Result<()> {
+        let (runtime, model) = crate::RuntimeBuilder::new(self.model.clone())
+            .with_catalogs(self.catalogs.clone())
+            .build()
+            .await?;
+
+        let result = self.review_in_new_session(&runtime.handle, model).await;
+        runtime.shutdown().await;
+        let report = result?;
+
+        let rendered = match self.output {
+            ReviewOutput::Md => render_markdown(&report),
+            ReviewOutput::Sarif => serde_json::to_string_pretty(&render_sarif(&report))
+                .map_err(|e| eyre!("Failed to serialize SARIF: {}", e))?,
+        };
+        match &self.out {
+            Some(path) => std::fs::write(path, format!("{rendered}\n"))?,
+            None => writeln!(io::stdout(), "{rendered}")?,
+        }
+        Ok(())
+    }
+}
+
+impl ReviewCommand {
+    async fn review_in_new_session(
+        &self,
+        runtime: &steer_core::app::domain::runtime::RuntimeHandle,
+        model: steer_core::config::model::ModelId,
+    ) -> Result<ReviewReport> {
+        let mut config = SessionConfigLoader::new(model.clone(), self.session_config.clone())
+            .with_overrides(SessionConfigOverrides::default())
+            .load()
+            .await?;
+        if let Some(directory) = &self.directory {
+            config.workspace = WorkspaceConfig::Local {
+                path: directory.canonicalize()?,
+            };
+        }
+        config
+            .metadata
+            .insert("mode".to_string(), "review".to_string());
+
+        let session_id = runtime
+            .create_session(config)
+            .await
+            .map_err(|e| eyre!("Failed to create session: {}", e))?;
+        let result = runtime
+            .review(session_id, self.target.clone(), model)
+            .await
+            .map_err(|e| eyre!("Review failed: {}", e));
+        if let Err(e) = runtime.delete_session(session_id).await {
+            tracing::warn!("Failed to delete review session {session_id}: {e}");
+        }
+        result
+    }
+}
+
+pub fn render_markdown(report: &ReviewReport) -> String {
+    let mut out = format!("# Review of `{}` changes\n\n", report.target);
+    if report.findings.is_empty() {
+        out.push_str(&format!(
+            "No findings in {} file(s).\n",
+            report.files_reviewed
+        ));
+        return out;
+    }
+
+    out.push_str(&format!(
+        "{} finding(s) in {} file(s).\n",
+        report.findings.len(),
+        report.files_reviewed
+    ));
+    for (severity, findings) in report.by_severity() {
+        out.push_str(&format!(
+            "\n## {} ({})\n\n",
+            severity.label(),
+            findings.len()
+        ));
+        for finding in findings {
+            out.push_str(&format!(
+                "- **`{}`** {}\n",
+                finding.location(),
+                finding.title
+            ));
+            if !finding.detail.is_empty() {
+                out.push_str(&format!("\n  {}\n\n", finding.detail.replace('\n', "\n  ")));
+            }
+        }
+    }
+    out
+}
+
+/// Renders the report as a SARIF 2.1.0 log. Locations are relative to the
+/// review root so CI annotations resolve against the checkout.
+pub fn render_sarif(report: &ReviewReport) -> serde_json::Value {
+    let results: Vec<_> = report
+        .findings
+        .iter()
+        .map(|finding| {
+            let mut region = json!({ "startLine": finding.line });
+            if let Some(end_line) = finding.end_line {
+                region["endLine"] = json!(end_line);
+            }
+            let message = if finding.detail.is_empty() {
+                finding.title.clone()
+            } else {
+                format!("{}\n\n{}", finding.title, finding.detail)
+            };
+            json!({
+                "ruleId": rule_id(finding.severity),
+                "level": sarif_level(finding.severity),
+                "message": { "text": message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": finding.path },
+                        "region": region,
+                    }
+                }],
+            })
+        })
+        .collect();
+
+    let rules: Vec<_> = ReviewSeverity::ALL
+        .into_iter()
+        .map(|severity| {
+            json!({
+                "id": rule_id(severity),
+                "name": severity.label(),
+                "defaultConfiguration": { "level": sarif_level(severity) },
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "steer",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    })
+}
+
+fn rule_id(severity: ReviewSeverity) -> String {
+    format!("review/{}", severity.label().to_ascii_lowercase())
+}
+
+fn sarif_level(severity: ReviewSeverity) -> &'static str {
+    match severity {
+        ReviewSeverity::Critical | ReviewSeverity::Major => "error",
+        ReviewSeverity::Minor => "warning",
+        ReviewSeverity::Nit => "note",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use steer_core::review::ReviewFinding;
+
+    fn report() -> ReviewReport {
+        ReviewReport {
+            target: ReviewTarget::Branch("main".to_string()),
+            root: PathBuf::from("/repo"),
+            findings: vec![
+                ReviewFinding {
+                    severity: ReviewSeverity::Major,
+                    path: "src/lib.rs".to_string(),
+                    line: 12,
+                    end_line: Some(14),
+                    title: "Unchecked index".to_string(),
+                    detail: "Panics on empty input.".to_string(),
+                },
+                ReviewFinding {
+                    severity: ReviewSeverity::Nit,
+                    path: "src/main.rs".to_string(),
+                    line: 3,
+                    end_line: None,
+                    title: "Typo in comment".to_string(),
+                    detail: String::new(),
+                },
+            ],
+            files_reviewed: 2,
+            chunks: 1,
+        }
+    }
+
+    #[test]
+    fn markdown_groups_findings_by_severity() {
+        let markdown = render_markdown(&report());
+
+        assert!(markdown.starts_with("# Review of `branch:main` changes"));
+        let major = markdown.find("## Major (1)").unwrap();
+        let nit = markdown.find("## Nit (1)").unwrap();
+        assert!(major < nit);
+        assert!(markdown.contains("- **`src/lib.rs:12`** Unchecked index"));
+    }
+
+    #[test]
+    fn sarif_maps_severity_to_level_and_region() {
+        let sarif = render_sarif(&report());
+        let results = &sarif["runs"][0]["results"];
+
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(results[0]["ruleId"], "review/major");
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/lib.rs");
+        assert_eq!(location["region"]["startLine"], 12);
+        assert_eq!(location["region"]["endLine"], 14);
+        assert_eq!(results[1]["level"], "note");
+        assert!(
+            results[1]["locations"][0]["physicalLocation"]["region"]
+                .get("endLine")
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,344 @@
+use async_trait::async_trait;
+use eyre::{Result, WrapErr, bail, eyre};
+use semver::Version;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+
+use super::Command;
+
+const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/BrendanGraham14/steer/releases/latest";
+
+pub struct SelfUpdateCommand {
+    /// Only report whether an update is available.
+    pub check: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+/// How this executable was installed, when something other than us owns it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PackageManager {
+    Cargo,
+    Homebrew,
+    Npm,
+}
+
+impl PackageManager {
+    fn update_hint(self) -> &'static str {
+        match self {
+            PackageManager::Cargo => "cargo install steer",
+            PackageManager::Homebrew => "brew upgrade steer",
+            PackageManager::Npm => "npm update -g @brendangraham/steer",
+        }
+    }
+}
+
+#[async_trait]
+impl Command for SelfUpdateCommand {
+    async fn execute(&self) -> Result<()> {
+        let mut stdout = std::io::stdout();
+        let exe = std::env::current_exe()
+            .and_then(|path| path.canonicalize())
+            .wrap_err("Failed to locate the running executable")?;
+        if let Some(manager) = package_manager(&exe) {
+            bail!(
+                "{} is managed by {manager:?}; update it with `{}` instead",
+                exe.display(),
+                manager.update_hint()
+            );
+        }
+
+        let current = Version::parse(env!("CARGO_PKG_VERSION"))?;
+        let client = steer_core::http::client_builder()
+            .user_agent(format!("steer/{current}"))
+            .build()?;
+        let release: GithubRelease = client
+            .get(LATEST_RELEASE_URL)
+            .header("Accept", "application/vnd.github+json")
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .wrap_err("Failed to query the latest release")?
+            .json()
+            .await
+            .wrap_err("Failed to parse the latest release")?;
+        let latest = parse_tag(&release.tag_name)
+            .ok_or_else(|| eyre!("Unrecognized release tag {}", release.tag_name))?;
+
+        if latest <= current {
+            writeln!(stdout, "steer {current} is up to date")?;
+            return Ok(());
+        }
+
+        writeln!(stdout, "steer {latest} is available (current: {current})")?;
+        if let Some(notes) = release.body.as_deref().map(changelog_section)
+            && !notes.is_empty()
+        {
+            writeln!(stdout, "\n{notes}\n")?;
+        }
+        if self.check {
+            return Ok(());
+        }
+
+        let target = release_target().ok_or_else(|| {
+            eyre!(
+                "No prebuilt release for {}-{}",
+                std::env::consts::ARCH,
+                std::env::consts::OS
+            )
+        })?;
+        let archive_name = format!("steer-{target}.tar.xz");
+        let checksum_name = format!("{archive_name}.sha256");
+        let asset_url = |name: &str| {
+            release
+                .assets
+                .iter()
+                .find(|asset| asset.name == name)
+                .map(|asset| asset.browser_download_url.clone())
+                .ok_or_else(|| eyre!("Release {} has no {name} asset", release.tag_name))
+        };
+        let archive_url = asset_url(&archive_name)?;
+        let checksum_url = asset_url(&checksum_name)?;
+
+        writeln!(stdout, "Downloading {archive_name}...")?;
+        let download = |url: String| {
+            let client = client.clone();
+            async move {
+                client
+                    .get(&url)
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)?
+                    .bytes()
+                    .await
+            }
+        };
+        let checksum = download(checksum_url)
+            .await
+            .wrap_err_with(|| format!("Failed to download {checksum_name}"))?;
+        let archive = download(archive_url)
+            .await
+            .wrap_err_with(|| format!("Failed to download {archive_name}"))?;
+
+        let expected = parse_checksum(&String::from_utf8_lossy(&checksum))
+            .ok_or_else(|| eyre!("{checksum_name} does not contain a sha256 digest"))?;
+        let actual = hex::encode(Sha256::digest(&archive));
+        if actual != expected {
+            bail!("Checksum mismatch for {archive_name}: expected {expected}, got {actual}");
+        }
+
+        let staging = tempfile::tempdir()?;
+        let new_exe = extract_binary(&archive, staging.path())
+            .wrap_err_with(|| format!("Failed to unpack {archive_name}"))?;
+        self_replace::self_replace(&new_exe)
+            .wrap_err_with(|| format!("Failed to replace {}", exe.display()))?;
+
+        writeln!(stdout, "Updated steer {current} -> {latest}")?;
+        Ok(())
+    }
+}
+
+fn parse_tag(tag: &str) -> Option<Version> {
+    let tag = tag.trim();
+    let version = tag
+        .strip_prefix("steer-v")
+        .or_else(|| tag.strip_prefix('v'))
+        .unwrap_or(tag);
+    Version::parse(version).ok()
+}
+
+/// The release target this build should download, for the targets we publish.
+fn release_target() -> Option<&'static str> {
+    match (std::env::consts::ARCH, std::env::consts::OS) {
+        ("aarch64", "macos") => Some("aarch64-apple-darwin"),
+        ("x86_64", "macos") => Some("x86_64-apple-darwin"),
+        ("aarch64", "linux") => Some("aarch64-unknown-linux-gnu"),
+        ("x86_64", "linux") => Some("x86_64-unknown-linux-gnu"),
+        _ => None,
+    }
+}
+
+/// Release notes without the install and download sections dist appends.
+fn changelog_section(body: &str) -> &str {
+    let end = ["## Install ", "## Download "]
+        .iter()
+        .filter_map(|heading| body.find(heading))
+        .min()
+        .unwrap_or(body.len());
+    body[..end].trim()
+}
+
+/// Reads the digest from a `sha256sum`-style line.
+fn parse_checksum(contents: &str) -> Option<String> {
+    let digest = contents.split_whitespace().next()?.to_ascii_lowercase();
+    (digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit())).then_some(digest)
+}
+
+/// Unpacks the `steer` executable from a release archive into `dir`.
+fn extract_binary(archive: &[u8], dir: &Path) -> Result<PathBuf> {
+    let mut tar_bytes = Vec::new();
+    lzma_rs::xz_decompress(&mut Cursor::new(archive), &mut tar_bytes)
+        .map_err(|e| eyre!("Invalid xz data: {e}"))?;
+    let binary_name = format!("steer{}", std::env::consts::EXE_SUFFIX);
+    let mut tar = tar::Archive::new(Cursor::new(tar_bytes));
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        if entry.path()?.file_name() == Some(binary_name.as_ref()) {
+            let dest = dir.join(&binary_name);
+            entry.unpack(&dest)?;
+            return Ok(dest);
+        }
+    }
+    bail!("Archive does not contain {binary_name}")
+}
+
+fn package_manager(exe: &Path) -> Option<PackageManager> {
+    let cargo_home = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")));
+    let crates2 = cargo_home
+        .as_ref()
+        .and_then(|home| std::fs::read_to_string(home.join(".crates2.json")).ok());
+    detect_package_manager(exe, cargo_home.as_deref(), crates2.as_deref())
+}
+
+/// Classifies `exe` by its install path. The release installer also puts the
+/// binary in `$CARGO_HOME/bin`, so cargo only owns it when its install
+/// metadata (`.crates2.json`) lists the steer package.
+fn detect_package_manager(
+    exe: &Path,
+    cargo_home: Option<&Path>,
+    crates2: Option<&str>,
+) -> Option<PackageManager> {
+    let path = exe.to_string_lossy();
+    if path.contains("/Cellar/") || path.contains("/homebrew/") || path.contains("/linuxbrew/") {
+        return Some(PackageManager::Homebrew);
+    }
+    if path.contains("node_modules") {
+        return Some(PackageManager::Npm);
+    }
+    let in_cargo_bin = cargo_home.is_some_and(|home| exe.parent() == Some(&home.join("bin")));
+    (in_cargo_bin && crates2.is_some_and(cargo_installed_steer)).then_some(PackageManager::Cargo)
+}
+
+fn cargo_installed_steer(crates2: &str) -> bool {
+    #[derive(Deserialize)]
+    struct Crates2 {
+        installs: std::collections::BTreeMap<String, serde_json::Value>,
+    }
+    serde_json::from_str::<Crates2>(crates2)
+        .is_ok_and(|c| c.installs.keys().any(|key| key.starts_with("steer ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tag_accepts_release_prefixes() {
+        assert_eq!(parse_tag("steer-v0.19.0"), Version::parse("0.19.0").ok());
+        assert_eq!(parse_tag("v0.19.0"), Version::parse("0.19.0").ok());
+        assert_eq!(parse_tag("0.19.0"), Version::parse("0.19.0").ok());
+        assert_eq!(parse_tag("steer-tui-v0.19.0"), None);
+    }
+
+    #[test]
+    fn changelog_section_drops_install_instructions() {
+        let body = "### Added\n\n- gpt-5.4 support\n\n## Install steer 0.19.0\n\n```sh\ncurl ...\n```\n\n## Download steer 0.19.0\n";
+        assert_eq!(changelog_section(body), "### Added\n\n- gpt-5.4 support");
+        assert_eq!(changelog_section("- fix\n"), "- fix");
+    }
+
+    #[test]
+    fn parse_checksum_reads_sha256sum_lines() {
+        let digest = "a".repeat(64);
+        assert_eq!(
+            parse_checksum(&format!(
+                "{digest}  steer-x86_64-unknown-linux-gnu.tar.xz\n"
+            )),
+            Some(digest.clone())
+        );
+        assert_eq!(parse_checksum(&digest.to_uppercase()), Some(digest));
+        assert_eq!(parse_checksum("not-a-digest"), None);
+        assert_eq!(parse_checksum(""), None);
+    }
+
+    #[test]
+    fn detects_package_managers_from_install_path() {
+        let cargo_home = Path::new("/home/me/.cargo");
+        let exe = Path::new("/home/me/.cargo/bin/steer");
+        let crates2 = r#"{"installs":{"steer 0.19.0 (registry+https://github.com/rust-lang/crates.io-index)":{}}}"#;
+
+        assert_eq!(
+            detect_package_manager(exe, Some(cargo_home), Some(crates2)),
+            Some(PackageManager::Cargo)
+        );
+        // The release installer also uses $CARGO_HOME/bin.
+        assert_eq!(
+            detect_package_manager(exe, Some(cargo_home), Some(r#"{"installs":{}}"#)),
+            None
+        );
+        assert_eq!(
+            detect_package_manager(
+                Path::new("/opt/homebrew/Cellar/steer/0.19.0/bin/steer"),
+                Some(cargo_home),
+                None
+            ),
+            Some(PackageManager::Homebrew)
+        );
+        assert_eq!(
+            detect_package_manager(
+                Path::new(
+                    "/usr/lib/node_modules/@brendangraham/steer/node_modules/.bin_real/steer"
+                ),
+                None,
+                None
+            ),
+            Some(PackageManager::Npm)
+        );
+        assert_eq!(
+            detect_package_manager(Path::new("/usr/local/bin/steer"), Some(cargo_home), None),
+            None
+        );
+    }
+
+    #[test]
+    fn extract_binary_finds_executable_in_archive() {
+        let binary_name = format!("steer{}", std::env::consts::EXE_SUFFIX);
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, contents) in [
+            ("steer-target/README.md".to_string(), b"readme".as_slice()),
+            (format!("steer-target/{binary_name}"), b"binary".as_slice()),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder.append_data(&mut header, path, contents).unwrap();
+        }
+        let tar_bytes = builder.into_inner().unwrap();
+        let mut archive = Vec::new();
+        lzma_rs::xz_compress(&mut Cursor::new(tar_bytes), &mut archive).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let extracted = extract_binary(&archive, dir.path()).unwrap();
+        assert_eq!(extracted, dir.path().join(&binary_name));
+        assert_eq!(std::fs::read(extracted).unwrap(), b"binary");
+    }
+}
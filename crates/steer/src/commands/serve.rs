@@ -1,17 +1,122 @@
 use async_trait::async_trait;
 use eyre::{Result, eyre};
 use std::io::Write;
-use tracing::info;
+use tracing::{error, info};
 
 use super::Command;
-use std::path::PathBuf;
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder};
+use serde::Deserialize;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use steer_core::app::domain::runtime::{
+    EventLogConfig, EventLogSink, RuntimeConfig, WebhookConfig,
+};
 use steer_core::catalog::CatalogConfig;
 
 pub struct ServeCommand {
     pub port: u16,
     pub bind: String,
+    /// Port for the HTTP+JSON gateway; not served when `None`.
+    pub http_port: Option<u16>,
     pub session_db: Option<std::path::PathBuf>,
     pub catalogs: Vec<PathBuf>,
+    pub webhooks: Option<PathBuf>,
+    pub metrics_addr: Option<SocketAddr>,
+    /// How long shutdown waits for running operations before exiting anyway.
+    pub drain_timeout: Duration,
+    pub runtime: RuntimeConfig,
+    pub event_log: EventLogOptions,
+}
+
+/// `--log-events*` flags.
+#[derive(Debug, Clone, Default)]
+pub struct EventLogOptions {
+    /// `stdout`, `log`, or a file path; `None` disables event logging.
+    pub destination: Option<String>,
+    pub session_glob: Option<String>,
+    pub kinds: Vec<String>,
+    pub max_bytes: Option<u64>,
+    pub max_files: usize,
+}
+
+impl EventLogOptions {
+    fn to_config(&self) -> Result<Option<EventLogConfig>> {
+        let Some(destination) = &self.destination else {
+            return Ok(None);
+        };
+        let sink = match destination.as_str() {
+            "stdout" | "-" => EventLogSink::Stdout,
+            "log" => EventLogSink::Tracing,
+            path => EventLogSink::File {
+                path: PathBuf::from(path),
+                max_bytes: self.max_bytes,
+                max_files: self.max_files,
+            },
+        };
+        let sessions = self
+            .session_glob
+            .as_deref()
+            .map(glob::Pattern::new)
+            .transpose()
+            .map_err(|e| eyre!("Invalid --log-events-session glob: {}", e))?;
+
+        Ok(Some(EventLogConfig {
+            sessions,
+            kinds: self.kinds.clone(),
+            ..EventLogConfig::new(sink)
+        }))
+    }
+}
+
+/// Latency buckets, in seconds, spanning quick tool calls to long model turns.
+const DURATION_BUCKETS: [f64; 12] = [
+    0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 300.0,
+];
+
+fn install_metrics_exporter(addr: SocketAddr) -> Result<()> {
+    PrometheusBuilder::new()
+        .with_http_listener(addr)
+        .set_buckets_for_metric(
+            Matcher::Suffix("_duration_seconds".to_string()),
+            &DURATION_BUCKETS,
+        )
+        .map_err(|e| eyre!("Invalid metrics buckets: {}", e))?
+        .install()
+        .map_err(|e| eyre!("Failed to start metrics exporter on {}: {}", addr, e))?;
+    steer_core::metrics::describe();
+    steer_core::metrics::set_active_sessions(0);
+    Ok(())
+}
+
+/// Resolves on Ctrl+C or, on Unix, SIGTERM.
+async fn shutdown_signal() -> Result<()> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        let mut sigterm = signal(SignalKind::terminate())?;
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => result?,
+            _ = sigterm.recv() => {}
+        }
+    }
+    #[cfg(not(unix))]
+    tokio::signal::ctrl_c().await?;
+    Ok(())
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct WebhooksFile {
+    #[serde(default)]
+    webhooks: Vec<WebhookConfig>,
+}
+
+fn load_webhooks(path: &Path) -> Result<Vec<WebhookConfig>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| eyre!("Failed to read webhooks file {}: {}", path.display(), e))?;
+    let file: WebhooksFile = toml::from_str(&contents)
+        .map_err(|e| eyre!("Invalid webhooks file {}: {}", path.display(), e))?;
+    Ok(file.webhooks)
 }
 
 #[async_trait]
@@ -35,8 +140,25 @@ impl Command for ServeCommand {
                 .collect(),
         );
 
-        let config = steer_grpc::ServiceHostConfig::with_catalog(db_path, addr, catalog_config)
+        let mut config = steer_grpc::ServiceHostConfig::with_catalog(db_path, addr, catalog_config)
             .map_err(|e| eyre!("Failed to create service config: {}", e))?;
+        if let Some(path) = &self.webhooks {
+            config.webhooks = load_webhooks(path)?;
+        }
+        config.event_log = self.event_log.to_config()?;
+        config.runtime = self.runtime;
+        config.http_addr = self
+            .http_port
+            .map(|port| format!("{}:{}", self.bind, port).parse())
+            .transpose()
+            .map_err(|e| eyre!("Invalid HTTP gateway address: {}", e))?;
+        if let Some(metrics_addr) = self.metrics_addr {
+            install_metrics_exporter(metrics_addr)?;
+            info!(
+                "Serving Prometheus metrics on http://{}/metrics",
+                metrics_addr
+            );
+        }
 
         let mut host = steer_grpc::ServiceHost::new(config)
             .await
@@ -48,15 +170,25 @@ impl Command for ServeCommand {
         info!("gRPC server started on {}", addr);
         let mut stdout = std::io::stdout();
         writeln!(stdout, "Server listening on {addr}")?;
+        if let Some(http_addr) = host.http_addr() {
+            writeln!(stdout, "HTTP gateway listening on http://{http_addr}")?;
+        }
         writeln!(stdout, "Press Ctrl+C to shutdown")?;
 
-        // Wait for shutdown signal
-        tokio::signal::ctrl_c().await?;
-        info!("Shutdown signal received");
+        shutdown_signal().await?;
+        info!("Shutdown signal received; draining");
+        writeln!(
+            stdout,
+            "Shutting down; cancelling running operations (up to {}s)",
+            self.drain_timeout.as_secs()
+        )?;
 
-        host.shutdown()
-            .await
-            .map_err(|e| eyre!("Failed to shutdown server: {}", e))?;
+        if !host.drain(self.drain_timeout).await {
+            error!("Drain timed out after {:?}; exiting", self.drain_timeout);
+            // Blocking tool calls may still be running and would keep the
+            // runtime from shutting down.
+            std::process::exit(1);
+        }
         info!("Server shutdown complete");
 
         Ok(())
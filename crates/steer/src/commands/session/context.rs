@@ -0,0 +1,122 @@
+use async_trait::async_trait;
+use eyre::{Result, eyre};
+use std::io::Write;
+
+use super::super::Command;
+
+use steer_core::catalog::CatalogConfig;
+use steer_grpc::AgentClient;
+use steer_grpc::client_api::{ContextReport, ContextSegmentKind};
+
+pub struct ContextSessionCommand {
+    pub session_id: String,
+    pub model: Option<String>,
+    pub segment: Option<String>,
+    pub remote: Option<String>,
+    pub session_db: Option<std::path::PathBuf>,
+    pub catalogs: Vec<std::path::PathBuf>,
+}
+
+#[async_trait]
+impl Command for ContextSessionCommand {
+    async fn execute(&self) -> Result<()> {
+        let segment = match self.segment.as_deref() {
+            Some(value) => Some(ContextSegmentKind::parse(value).ok_or_else(|| {
+                eyre!(
+                    "Unknown segment '{}'. Expected one of: system, environment, tools, messages",
+                    value
+                )
+            })?),
+            None => None,
+        };
+
+        let mut local_grpc_setup = None;
+        let client = if let Some(remote_addr) = &self.remote {
+            AgentClient::connect(remote_addr)
+                .await
+                .map_err(|e| eyre!("Failed to connect to remote server: {}", e))?
+        } else {
+            let db_path = match &self.session_db {
+                Some(path) => path.clone(),
+                None => steer_core::utils::session::create_session_store_path()?,
+            };
+            let catalog_paths = self
+                .catalogs
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect();
+
+            let setup = steer_grpc::local_server::setup_local_grpc_with_catalog(
+                steer_core::config::model::builtin::default_model(),
+                Some(db_path),
+                CatalogConfig::with_catalogs(catalog_paths),
+                None,
+            )
+            .await
+            .map_err(|e| eyre!("Failed to setup local gRPC: {}", e))?;
+
+            let client = AgentClient::from_channel(setup.channel.clone())
+                .await
+                .map_err(|e| eyre!("Failed to create gRPC client: {}", e))?;
+            local_grpc_setup = Some(setup);
+            client
+        };
+
+        let model = match &self.model {
+            Some(input) => Some(
+                client
+                    .resolve_model(input)
+                    .await
+                    .map_err(|e| eyre!("Failed to resolve model '{}': {}", input, e))?,
+            ),
+            None => None,
+        };
+        let report = client
+            .get_context_report(&self.session_id, model.as_ref())
+            .await
+            .map_err(|e| eyre!("Failed to get context report: {}", e));
+
+        if let Some(setup) = local_grpc_setup {
+            setup.server_handle.abort();
+            setup.runtime_service.shutdown().await;
+        }
+
+        print_report(&report?, segment)
+    }
+}
+
+fn print_report(report: &ContextReport, only: Option<ContextSegmentKind>) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    let total = report.total_tokens();
+
+    for segment in &report.segments {
+        if only.is_some_and(|kind| kind != segment.kind) {
+            continue;
+        }
+        writeln!(
+            stdout,
+            "{:<14} {:>9} tokens",
+            segment.kind.label(),
+            segment.tokens
+        )?;
+        for item in &segment.items {
+            writeln!(stdout, "  {:>9}  {}", item.tokens, item.label)?;
+        }
+    }
+
+    if only.is_none() {
+        writeln!(stdout, "{:<14} {:>9} tokens", "Total", total)?;
+        match report.input_budget_tokens() {
+            Some(budget) if budget > 0 => writeln!(
+                stdout,
+                "Context window: {:.1}% of {} input tokens ({} reserved for output)",
+                f64::from(total) * 100.0 / f64::from(budget),
+                budget,
+                report.max_output_tokens.unwrap_or(0)
+            )?,
+            _ => writeln!(stdout, "Context window: unknown for this model")?,
+        }
+    }
+
+    Ok(())
+}
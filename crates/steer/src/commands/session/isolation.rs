@@ -0,0 +1,224 @@
+use async_trait::async_trait;
+use eyre::{Result, eyre};
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::PathBuf;
+
+use super::super::Command;
+use crate::cli::IsolationAction;
+
+use steer_core::catalog::CatalogConfig;
+use steer_grpc::AgentClient;
+use steer_grpc::client_api::{IsolationKind, IsolationStatus, MergeReport};
+
+pub struct IsolationSessionCommand {
+    pub action: IsolationAction,
+    pub session_id: String,
+    pub output: Option<PathBuf>,
+    pub message: Option<String>,
+    pub remote: Option<String>,
+    pub session_db: Option<PathBuf>,
+    pub catalogs: Vec<PathBuf>,
+}
+
+#[async_trait]
+impl Command for IsolationSessionCommand {
+    async fn execute(&self) -> Result<()> {
+        let mut local_grpc_setup = None;
+        let client = if let Some(remote_addr) = &self.remote {
+            AgentClient::connect(remote_addr)
+                .await
+                .map_err(|e| eyre!("Failed to connect to remote server: {}", e))?
+        } else {
+            let db_path = match &self.session_db {
+                Some(path) => path.clone(),
+                None => steer_core::utils::session::create_session_store_path()?,
+            };
+            let catalog_paths = self
+                .catalogs
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect();
+
+            let setup = steer_grpc::local_server::setup_local_grpc_with_catalog(
+                steer_core::config::model::builtin::default_model(),
+                Some(db_path),
+                CatalogConfig::with_catalogs(catalog_paths),
+                None,
+            )
+            .await
+            .map_err(|e| eyre!("Failed to setup local gRPC: {}", e))?;
+
+            let client = AgentClient::from_channel(setup.channel.clone())
+                .await
+                .map_err(|e| eyre!("Failed to create gRPC client: {}", e))?;
+            local_grpc_setup = Some(setup);
+            client
+        };
+
+        let result = self.run(&client).await;
+
+        if let Some(setup) = local_grpc_setup {
+            setup.server_handle.abort();
+            setup.runtime_service.shutdown().await;
+        }
+
+        result
+    }
+}
+
+impl IsolationSessionCommand {
+    async fn run(&self, client: &AgentClient) -> Result<()> {
+        let mut stdout = std::io::stdout();
+        match self.action {
+            IsolationAction::Status => {
+                match client
+                    .get_isolation_status(&self.session_id)
+                    .await
+                    .map_err(|e| eyre!("Failed to get isolation status: {}", e))?
+                {
+                    Some(status) => print_status(&status)?,
+                    None => writeln!(stdout, "Session {} is not isolated.", self.session_id)?,
+                }
+            }
+            IsolationAction::Merge => {
+                let report = client
+                    .merge_isolated_workspace(&self.session_id)
+                    .await
+                    .map_err(|e| eyre!("Failed to merge isolated worktree: {}", e))?;
+                print_merge_report(&report)?;
+                if !report.is_clean() {
+                    return Err(eyre!(
+                        "{} file(s) conflicted; resolve them in the source directory",
+                        report.conflicts.len()
+                    ));
+                }
+            }
+            IsolationAction::Patch => {
+                let patch = client
+                    .export_isolated_patch(&self.session_id)
+                    .await
+                    .map_err(|e| eyre!("Failed to export patch: {}", e))?;
+                match &self.output {
+                    Some(path) => {
+                        std::fs::write(path, patch)?;
+                        writeln!(stdout, "Wrote patch to {}", path.display())?;
+                    }
+                    None => write!(stdout, "{patch}")?,
+                }
+            }
+            IsolationAction::Branch => {
+                let branch = client
+                    .commit_isolated_branch(&self.session_id, self.message.clone())
+                    .await
+                    .map_err(|e| eyre!("Failed to commit session branch: {}", e))?;
+                writeln!(stdout, "Committed changes to branch {branch}")?;
+            }
+            IsolationAction::Discard => {
+                client
+                    .discard_isolated_workspace(&self.session_id)
+                    .await
+                    .map_err(|e| eyre!("Failed to discard isolated worktree: {}", e))?;
+                writeln!(stdout, "Discarded the isolated worktree.")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// After an interactive session ends, ask what to do with its isolated
+/// worktree. Does nothing for sessions that are not isolated or when stdin is
+/// not a terminal.
+pub async fn offer_isolation_actions(client: &AgentClient, session_id: &str) -> Result<()> {
+    if !std::io::stdin().is_terminal() {
+        return Ok(());
+    }
+    let Some(status) = client
+        .get_isolation_status(session_id)
+        .await
+        .map_err(|e| eyre!("Failed to get isolation status: {}", e))?
+    else {
+        return Ok(());
+    };
+
+    let mut stdout = std::io::stdout();
+    writeln!(stdout)?;
+    print_status(&status)?;
+    let branch_choice = match status.workspace.kind {
+        IsolationKind::GitWorktree { .. } => " / [b]ranch",
+        IsolationKind::Copy => "",
+    };
+    write!(
+        stdout,
+        "\nBring the changes back? [m]erge / [p]atch{branch_choice} / [d]iscard / [k]eep (default: keep): "
+    )?;
+    stdout.flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    let action = match answer.trim().to_ascii_lowercase().as_str() {
+        "m" | "merge" => IsolationAction::Merge,
+        "p" | "patch" => IsolationAction::Patch,
+        "b" | "branch" if !branch_choice.is_empty() => IsolationAction::Branch,
+        "d" | "discard" => IsolationAction::Discard,
+        _ => {
+            writeln!(
+                stdout,
+                "Kept {}. Run `steer session isolation --help` to act on it later.",
+                status.workspace.path.display()
+            )?;
+            return Ok(());
+        }
+    };
+
+    let output = (action == IsolationAction::Patch)
+        .then(|| PathBuf::from(format!("steer-{session_id}.patch")));
+    IsolationSessionCommand {
+        action,
+        session_id: session_id.to_string(),
+        output,
+        message: None,
+        remote: None,
+        session_db: None,
+        catalogs: Vec::new(),
+    }
+    .run(client)
+    .await
+}
+
+fn print_status(status: &IsolationStatus) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    let workspace = &status.workspace;
+    writeln!(stdout, "Source:   {}", workspace.source.display())?;
+    writeln!(stdout, "Worktree: {}", workspace.path.display())?;
+    if let Some(branch) = workspace.branch() {
+        writeln!(stdout, "Branch:   {branch}")?;
+    }
+
+    if status.changes.is_empty() {
+        writeln!(stdout, "No changes.")?;
+        return Ok(());
+    }
+    writeln!(stdout, "Changed files ({}):", status.changes.len())?;
+    for change in &status.changes {
+        writeln!(stdout, "  {} {}", change.kind.marker(), change.path)?;
+    }
+    Ok(())
+}
+
+fn print_merge_report(report: &MergeReport) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    if report.applied.is_empty() && report.conflicts.is_empty() {
+        writeln!(stdout, "Nothing to merge.")?;
+        return Ok(());
+    }
+    for path in &report.applied {
+        writeln!(stdout, "applied   {path}")?;
+    }
+    for path in &report.conflicts {
+        writeln!(stdout, "conflict  {path}")?;
+    }
+    if !report.conflicts.is_empty() && !report.details.is_empty() {
+        writeln!(stdout, "\n{}", report.details)?;
+    }
+    Ok(())
+}
@@ -10,6 +10,7 @@ use steer_core::app::domain::session::{SessionFilter, SessionMetadataStore, Sqli
 pub struct ListSessionCommand {
     pub active: bool,
     pub limit: Option<u32>,
+    pub summaries: bool,
     pub remote: Option<String>,
     pub session_db: Option<std::path::PathBuf>,
 }
@@ -75,6 +76,11 @@ impl Command for ListSessionCommand {
                 model_str,
                 title_str,
             )?;
+            if self.summaries
+                && let Some(summary) = &session.summary
+            {
+                writeln!(stdout, "    {summary}")?;
+            }
         }
 
         Ok(())
@@ -165,6 +171,11 @@ impl ListSessionCommand {
                 "{:<36} {:<20} {:<20} {:<10} {:<30}",
                 session.id, created_str, updated_str, status_str, title_str,
             )?;
+            if self.summaries
+                && let Some(summary) = &session.summary
+            {
+                writeln!(stdout, "    {summary}")?;
+            }
         }
 
         Ok(())
@@ -4,14 +4,20 @@ use eyre::Result;
 use super::Command;
 use crate::cli::SessionCommands;
 
+mod context;
 mod create;
 mod delete;
+mod isolation;
 mod list;
+mod ops;
 mod show;
 
+pub use context::ContextSessionCommand;
 pub use create::CreateSessionCommand;
 pub use delete::DeleteSessionCommand;
+pub use isolation::{IsolationSessionCommand, offer_isolation_actions};
 pub use list::ListSessionCommand;
+pub use ops::OpsSessionCommand;
 pub use show::ShowSessionCommand;
 
 pub struct SessionCommand {
@@ -27,10 +33,15 @@ impl Command for SessionCommand {
     async fn execute(&self) -> Result<()> {
         // Dispatch to appropriate subcommand
         match &self.command {
-            SessionCommands::List { active, limit } => {
+            SessionCommands::List {
+                active,
+                limit,
+                summaries,
+            } => {
                 let cmd = ListSessionCommand {
                     active: *active,
                     limit: Some(*limit),
+                    summaries: *summaries,
                     remote: self.remote.clone(),
                     session_db: self.session_db.clone(),
                 };
@@ -61,11 +72,56 @@ impl Command for SessionCommand {
                 };
                 cmd.execute().await
             }
-            SessionCommands::Show { session_id } => {
+            SessionCommands::Context {
+                session_id,
+                model,
+                segment,
+            } => {
+                let cmd = ContextSessionCommand {
+                    session_id: session_id.clone(),
+                    model: model.clone(),
+                    segment: segment.clone(),
+                    remote: self.remote.clone(),
+                    session_db: self.session_db.clone(),
+                    catalogs: self.catalogs.clone(),
+                };
+                cmd.execute().await
+            }
+            SessionCommands::Isolation {
+                action,
+                session_id,
+                output,
+                message,
+            } => {
+                let cmd = IsolationSessionCommand {
+                    action: *action,
+                    session_id: session_id.clone(),
+                    output: output.clone(),
+                    message: message.clone(),
+                    remote: self.remote.clone(),
+                    session_db: self.session_db.clone(),
+                    catalogs: self.catalogs.clone(),
+                };
+                cmd.execute().await
+            }
+            SessionCommands::Ops { cancel, force } => {
+                let cmd = OpsSessionCommand {
+                    cancel: cancel.clone(),
+                    force: *force,
+                    remote: self.remote.clone(),
+                };
+                cmd.execute().await
+            }
+            SessionCommands::Show {
+                session_id,
+                summarize,
+            } => {
                 let cmd = ShowSessionCommand {
                     session_id: session_id.clone(),
+                    summarize: *summarize,
                     remote: self.remote.clone(),
                     session_db: self.session_db.clone(),
+                    catalogs: self.catalogs.clone(),
                 };
                 cmd.execute().await
             }
@@ -0,0 +1,119 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use eyre::{Result, eyre};
+use std::io::Write;
+
+use super::super::Command;
+use crate::commands::task::{DEFAULT_SERVER_ADDR, connect_client};
+
+use steer_grpc::proto::{ActiveOperation, OperationPhase};
+
+/// Operations only exist on a running `steer server`, so like `steer task`
+/// this talks to the default server address when `--remote` is not given.
+pub struct OpsSessionCommand {
+    pub cancel: Option<String>,
+    pub force: bool,
+    pub remote: Option<String>,
+}
+
+#[async_trait]
+impl Command for OpsSessionCommand {
+    async fn execute(&self) -> Result<()> {
+        let remote = self.remote.as_deref().unwrap_or(DEFAULT_SERVER_ADDR);
+        let client = connect_client(remote).await?;
+
+        let operations = client
+            .list_active_operations()
+            .await
+            .map_err(|e| eyre!("Failed to list active operations: {e}"))?;
+
+        let mut stdout = std::io::stdout();
+
+        if let Some(op_id) = &self.cancel {
+            let operation = operations
+                .iter()
+                .find(|operation| &operation.op_id == op_id)
+                .ok_or_else(|| eyre!("Operation {op_id} is not running"))?;
+            let cancelled = client
+                .cancel_session_operation(&operation.session_id, op_id, self.force)
+                .await
+                .map_err(|e| eyre!("Failed to cancel operation: {e}"))?;
+
+            if cancelled {
+                let how = if self.force {
+                    "Force-cancelled"
+                } else {
+                    "Cancelled"
+                };
+                writeln!(
+                    stdout,
+                    "{how} operation {op_id} in session {}",
+                    operation.session_id
+                )?;
+            } else {
+                writeln!(
+                    stdout,
+                    "Operation {op_id} finished before it could be cancelled"
+                )?;
+            }
+            return Ok(());
+        }
+
+        if operations.is_empty() {
+            writeln!(stdout, "No active operations.")?;
+            return Ok(());
+        }
+
+        writeln!(
+            stdout,
+            "{:<38} {:<38} {:<18} {:<10} {:<16} Model",
+            "Session", "Operation", "Phase", "Running", "Tool"
+        )?;
+        writeln!(stdout, "{}", "-".repeat(140))?;
+        for operation in &operations {
+            writeln!(
+                stdout,
+                "{:<38} {:<38} {:<18} {:<10} {:<16} {}",
+                operation.session_id,
+                operation.op_id,
+                phase_label(operation),
+                running_for(operation),
+                operation.current_tool.as_deref().unwrap_or("-"),
+                operation.model.as_ref().map_or_else(
+                    || "-".to_string(),
+                    |model| format!("{}/{}", model.provider_id, model.model_id)
+                ),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn phase_label(operation: &ActiveOperation) -> &'static str {
+    match OperationPhase::try_from(operation.phase) {
+        Ok(OperationPhase::CallingModel) => "calling model",
+        Ok(OperationPhase::RunningTool) => "running tool",
+        Ok(OperationPhase::AwaitingApproval) => "awaiting approval",
+        Ok(OperationPhase::Compacting) => "compacting",
+        Ok(OperationPhase::RunningBash) => "running bash",
+        _ => "unknown",
+    }
+}
+
+fn running_for(operation: &ActiveOperation) -> String {
+    let Some(started_at) = operation
+        .started_at
+        .as_ref()
+        .and_then(|ts| DateTime::<Utc>::from_timestamp(ts.seconds, 0))
+    else {
+        return "-".to_string();
+    };
+
+    let secs = (Utc::now() - started_at).num_seconds().max(0);
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60),
+    }
+}
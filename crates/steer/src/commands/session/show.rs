@@ -8,18 +8,29 @@ use super::super::Command;
 
 use steer_core::app::domain::session::{SessionMetadataStore, SqliteEventStore};
 use steer_core::app::domain::types::SessionId;
+use steer_core::catalog::CatalogConfig;
+use steer_core::session::state::SUMMARY_METADATA_KEY;
+use steer_grpc::AgentClient;
 
 pub struct ShowSessionCommand {
     pub session_id: String,
+    pub summarize: bool,
     pub remote: Option<String>,
     pub session_db: Option<std::path::PathBuf>,
+    pub catalogs: Vec<std::path::PathBuf>,
 }
 
 #[async_trait]
 impl Command for ShowSessionCommand {
     async fn execute(&self) -> Result<()> {
+        let refreshed = if self.summarize {
+            Some(self.refresh_summary().await?)
+        } else {
+            None
+        };
+
         if let Some(remote_addr) = &self.remote {
-            return self.handle_remote(remote_addr).await;
+            return self.handle_remote(remote_addr, refreshed).await;
         }
 
         let db_path = match &self.session_db {
@@ -70,6 +81,13 @@ impl Command for ShowSessionCommand {
                     "Title: {}",
                     info.title.unwrap_or_else(|| "N/A".to_string())
                 )?;
+                writeln!(
+                    stdout,
+                    "Summary: {}",
+                    refreshed
+                        .or(info.summary)
+                        .unwrap_or_else(|| "N/A".to_string())
+                )?;
             }
             None => {
                 return Err(eyre!("Session not found: {}", self.session_id));
@@ -81,9 +99,51 @@ impl Command for ShowSessionCommand {
 }
 
 impl ShowSessionCommand {
-    async fn handle_remote(&self, remote_addr: &str) -> Result<()> {
-        use steer_grpc::AgentClient;
+    /// Summarize the session through the remote server, or a local one over
+    /// the session database.
+    async fn refresh_summary(&self) -> Result<String> {
+        if let Some(remote_addr) = &self.remote {
+            let client = AgentClient::connect(remote_addr)
+                .await
+                .map_err(|e| eyre!("Failed to connect to remote server: {}", e))?;
+            return client
+                .summarize_session(&self.session_id)
+                .await
+                .map_err(|e| eyre!("Failed to summarize session: {}", e));
+        }
+
+        let db_path = match &self.session_db {
+            Some(path) => path.clone(),
+            None => steer_core::utils::session::create_session_store_path()?,
+        };
+        let catalog_paths = self
+            .catalogs
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        let setup = steer_grpc::local_server::setup_local_grpc_with_catalog(
+            steer_core::config::model::builtin::default_model(),
+            Some(db_path),
+            CatalogConfig::with_catalogs(catalog_paths),
+            None,
+        )
+        .await
+        .map_err(|e| eyre!("Failed to setup local gRPC: {}", e))?;
+
+        let summary = match AgentClient::from_channel(setup.channel.clone()).await {
+            Ok(client) => client
+                .summarize_session(&self.session_id)
+                .await
+                .map_err(|e| eyre!("Failed to summarize session: {}", e)),
+            Err(e) => Err(eyre!("Failed to create gRPC client: {}", e)),
+        };
+
+        setup.server_handle.abort();
+        setup.runtime_service.shutdown().await;
+        summary
+    }
 
+    async fn handle_remote(&self, remote_addr: &str, refreshed: Option<String>) -> Result<()> {
         let client = AgentClient::connect(remote_addr).await.map_err(|e| {
             eyre!(
                 "Failed to connect to remote server at {}: {}",
@@ -145,6 +205,16 @@ impl ShowSessionCommand {
                         .and_then(|config| config.title.clone())
                         .unwrap_or_else(|| "N/A".to_string())
                 )?;
+                writeln!(
+                    stdout,
+                    "Summary: {}",
+                    refreshed
+                        .or_else(|| state
+                            .config
+                            .as_ref()
+                            .and_then(|config| config.metadata.get(SUMMARY_METADATA_KEY).cloned()))
+                        .unwrap_or_else(|| "N/A".to_string())
+                )?;
 
                 if !state.metadata.is_empty() {
                     writeln!(stdout, "Metadata:")?;
@@ -0,0 +1,57 @@
+use async_trait::async_trait;
+use eyre::{Result, eyre};
+use std::io::Write;
+
+use super::super::Command;
+use super::connect_client;
+use crate::session_config::{SessionConfigLoader, SessionConfigOverrides};
+
+pub struct CreateTaskCommand {
+    pub schedule: String,
+    pub prompt: String,
+    pub session_config: Option<std::path::PathBuf>,
+    pub model: Option<String>,
+    pub max_tokens: Option<u64>,
+    pub remote: String,
+}
+
+#[async_trait]
+impl Command for CreateTaskCommand {
+    async fn execute(&self) -> Result<()> {
+        let client = connect_client(&self.remote).await?;
+
+        let default_model = match &self.model {
+            Some(input) => client
+                .resolve_model(input)
+                .await
+                .map_err(|e| eyre!("Failed to resolve model '{input}': {e}"))?,
+            None => client
+                .get_default_model()
+                .await
+                .map_err(|e| eyre!("Failed to fetch server default model: {e}"))?,
+        };
+
+        let overrides = SessionConfigOverrides {
+            default_model: self.model.as_ref().map(|_| default_model.clone()),
+            ..Default::default()
+        };
+        let session_config = SessionConfigLoader::new(default_model, self.session_config.clone())
+            .with_overrides(overrides)
+            .load()
+            .await?;
+
+        let task = client
+            .create_scheduled_task(
+                self.schedule.clone(),
+                self.prompt.clone(),
+                &session_config,
+                self.max_tokens,
+            )
+            .await
+            .map_err(|e| eyre!("Failed to create scheduled task: {e}"))?;
+
+        let mut stdout = std::io::stdout();
+        writeln!(stdout, "Created scheduled task: {}", task.id)?;
+        Ok(())
+    }
+}
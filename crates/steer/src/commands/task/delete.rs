@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use eyre::{Result, eyre};
+use std::io::Write;
+
+use super::super::Command;
+use super::connect_client;
+
+pub struct DeleteTaskCommand {
+    pub task_id: String,
+    pub remote: String,
+}
+
+#[async_trait]
+impl Command for DeleteTaskCommand {
+    async fn execute(&self) -> Result<()> {
+        let client = connect_client(&self.remote).await?;
+
+        let deleted = client
+            .delete_scheduled_task(&self.task_id)
+            .await
+            .map_err(|e| eyre!("Failed to delete scheduled task: {e}"))?;
+
+        if !deleted {
+            return Err(eyre!("Scheduled task not found: {}", self.task_id));
+        }
+
+        let mut stdout = std::io::stdout();
+        writeln!(stdout, "Scheduled task {} deleted.", self.task_id)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use eyre::{Result, eyre};
+use std::io::Write;
+
+use super::super::Command;
+use super::{connect_client, last_run_summary};
+
+pub struct ListTaskCommand {
+    pub remote: String,
+}
+
+#[async_trait]
+impl Command for ListTaskCommand {
+    async fn execute(&self) -> Result<()> {
+        let client = connect_client(&self.remote).await?;
+
+        let tasks = client
+            .list_scheduled_tasks()
+            .await
+            .map_err(|e| eyre!("Failed to list scheduled tasks: {e}"))?;
+
+        let mut stdout = std::io::stdout();
+        if tasks.is_empty() {
+            writeln!(stdout, "No scheduled tasks found.")?;
+            return Ok(());
+        }
+
+        writeln!(stdout, "Scheduled Tasks:")?;
+        writeln!(stdout, "{:<38} {:<20} Last Run", "ID", "Schedule")?;
+        writeln!(stdout, "{}", "-".repeat(90))?;
+        for task in &tasks {
+            writeln!(
+                stdout,
+                "{:<38} {:<20} {}",
+                task.id,
+                task.schedule,
+                last_run_summary(task)
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Local, Utc};
+use eyre::{Result, eyre};
+
+use super::Command;
+use crate::cli::TaskCommands;
+
+use steer_grpc::AgentClient;
+use steer_grpc::proto::{ScheduledRunStatus, ScheduledTask};
+
+mod create;
+mod delete;
+mod list;
+mod run_now;
+mod show;
+
+pub use create::CreateTaskCommand;
+pub use delete::DeleteTaskCommand;
+pub use list::ListTaskCommand;
+pub use run_now::RunTaskNowCommand;
+pub use show::ShowTaskCommand;
+
+/// Scheduled tasks live on a `steer server`; without `--remote` we talk to one
+/// started with default settings.
+pub(crate) const DEFAULT_SERVER_ADDR: &str = "http://127.0.0.1:50051";
+
+pub struct TaskCommand {
+    pub command: TaskCommands,
+    pub remote: Option<String>,
+    pub preferred_model: Option<String>,
+}
+
+#[async_trait]
+impl Command for TaskCommand {
+    async fn execute(&self) -> Result<()> {
+        let remote = self
+            .remote
+            .clone()
+            .unwrap_or_else(|| DEFAULT_SERVER_ADDR.to_string());
+
+        match &self.command {
+            TaskCommands::Create {
+                schedule,
+                prompt,
+                session_config,
+                model,
+                max_tokens,
+            } => {
+                let cmd = CreateTaskCommand {
+                    schedule: schedule.clone(),
+                    prompt: prompt.clone(),
+                    session_config: session_config.clone(),
+                    model: model.clone().or_else(|| self.preferred_model.clone()),
+                    max_tokens: *max_tokens,
+                    remote,
+                };
+                cmd.execute().await
+            }
+            TaskCommands::List => ListTaskCommand { remote }.execute().await,
+            TaskCommands::Show { task_id } => {
+                let cmd = ShowTaskCommand {
+                    task_id: task_id.clone(),
+                    remote,
+                };
+                cmd.execute().await
+            }
+            TaskCommands::RunNow { task_id } => {
+                let cmd = RunTaskNowCommand {
+                    task_id: task_id.clone(),
+                    remote,
+                };
+                cmd.execute().await
+            }
+            TaskCommands::Delete { task_id } => {
+                let cmd = DeleteTaskCommand {
+                    task_id: task_id.clone(),
+                    remote,
+                };
+                cmd.execute().await
+            }
+        }
+    }
+}
+
+pub(crate) async fn connect_client(remote: &str) -> Result<AgentClient> {
+    AgentClient::connect(remote)
+        .await
+        .map_err(|e| eyre!("Failed to connect to server at {remote}: {e}"))
+}
+
+fn format_timestamp(timestamp: Option<&prost_types::Timestamp>) -> String {
+    timestamp
+        .and_then(|ts| DateTime::<Utc>::from_timestamp(ts.seconds, ts.nanos as u32))
+        .map_or_else(
+            || "N/A".to_string(),
+            |dt| {
+                dt.with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            },
+        )
+}
+
+fn last_run_summary(task: &ScheduledTask) -> String {
+    let Some(run) = &task.last_run else {
+        return "never".to_string();
+    };
+
+    let status = match ScheduledRunStatus::try_from(run.status) {
+        Ok(ScheduledRunStatus::Running) => "running",
+        Ok(ScheduledRunStatus::Succeeded) => "succeeded",
+        Ok(ScheduledRunStatus::Failed) => "failed",
+        Ok(ScheduledRunStatus::BudgetExceeded) => "budget exceeded",
+        _ => "unknown",
+    };
+    format!("{status} at {}", format_timestamp(run.started_at.as_ref()))
+}
@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use eyre::{Result, eyre};
+use std::io::Write;
+
+use super::super::Command;
+use super::connect_client;
+
+pub struct RunTaskNowCommand {
+    pub task_id: String,
+    pub remote: String,
+}
+
+#[async_trait]
+impl Command for RunTaskNowCommand {
+    async fn execute(&self) -> Result<()> {
+        let client = connect_client(&self.remote).await?;
+
+        let session_id = client
+            .run_scheduled_task_now(&self.task_id)
+            .await
+            .map_err(|e| eyre!("Failed to run scheduled task: {e}"))?;
+
+        let mut stdout = std::io::stdout();
+        writeln!(
+            stdout,
+            "Started task {} in session {session_id}",
+            self.task_id
+        )?;
+        Ok(())
+    }
+}
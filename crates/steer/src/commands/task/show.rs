@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use eyre::{Result, eyre};
+use std::io::Write;
+
+use super::super::Command;
+use super::{connect_client, format_timestamp, last_run_summary};
+
+pub struct ShowTaskCommand {
+    pub task_id: String,
+    pub remote: String,
+}
+
+#[async_trait]
+impl Command for ShowTaskCommand {
+    async fn execute(&self) -> Result<()> {
+        let client = connect_client(&self.remote).await?;
+
+        let task = client
+            .get_scheduled_task(&self.task_id)
+            .await
+            .map_err(|e| eyre!("Failed to get scheduled task: {e}"))?
+            .ok_or_else(|| eyre!("Scheduled task not found: {}", self.task_id))?;
+
+        let mut stdout = std::io::stdout();
+        writeln!(stdout, "Scheduled Task Details:")?;
+        writeln!(stdout, "ID: {}", task.id)?;
+        writeln!(stdout, "Schedule: {}", task.schedule)?;
+        writeln!(stdout, "Prompt: {}", task.prompt)?;
+        if let Some(model) = task.config.as_ref().and_then(|c| c.default_model.as_ref()) {
+            writeln!(stdout, "Model: {}/{}", model.provider_id, model.model_id)?;
+        }
+        writeln!(
+            stdout,
+            "Token Budget: {}",
+            task.max_total_tokens
+                .map_or_else(|| "none".to_string(), |max| max.to_string())
+        )?;
+        writeln!(
+            stdout,
+            "Created: {}",
+            format_timestamp(task.created_at.as_ref())
+        )?;
+        writeln!(stdout, "Last Run: {}", last_run_summary(&task))?;
+
+        if let Some(run) = &task.last_run {
+            writeln!(stdout, "Last Run Session: {}", run.session_id)?;
+            if let Some(finished_at) = &run.finished_at {
+                writeln!(
+                    stdout,
+                    "Last Run Finished: {}",
+                    format_timestamp(Some(finished_at))
+                )?;
+            }
+            if let Some(error) = &run.error {
+                writeln!(stdout, "Last Run Error: {error}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use eyre::Result;
+use std::io::Write;
+use steer_core::tools::{ToolSchema, builtin_tool_schemas};
+
+use super::Command;
+use crate::cli::ToolsCommands;
+
+pub struct ToolsCommand {
+    pub command: ToolsCommands,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaFormat {
+    Json,
+    Markdown,
+}
+
+#[async_trait]
+impl Command for ToolsCommand {
+    async fn execute(&self) -> Result<()> {
+        match &self.command {
+            ToolsCommands::Schema { markdown, .. } => {
+                let format = if *markdown {
+                    SchemaFormat::Markdown
+                } else {
+                    SchemaFormat::Json
+                };
+                let output = render_schemas(&builtin_tool_schemas(), format)?;
+                let mut stdout = std::io::stdout();
+                writeln!(stdout, "{output}")?;
+                Ok(())
+            }
+        }
+    }
+}
+
+pub fn render_schemas(schemas: &[ToolSchema], format: SchemaFormat) -> Result<String> {
+    match format {
+        SchemaFormat::Json => Ok(serde_json::to_string_pretty(schemas)?),
+        SchemaFormat::Markdown => render_markdown(schemas),
+    }
+}
+
+fn render_markdown(schemas: &[ToolSchema]) -> Result<String> {
+    let mut out = String::from("# Builtin Tools\n");
+
+    for schema in schemas {
+        let summary = schema.input_schema.summary();
+        out.push_str(&format!(
+            "\n## {} (`{}`)\n\n{}\n",
+            schema.display_name,
+            schema.name,
+            schema.description.trim()
+        ));
+
+        if !summary.properties.is_empty() {
+            out.push_str("\n| Parameter | Type | Required | Description |\n");
+            out.push_str("|---|---|---|---|\n");
+            for (name, property) in &summary.properties {
+                let ty = property.get("type").map_or_else(
+                    || "-".to_string(),
+                    |t| match t {
+                        serde_json::Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    },
+                );
+                let description = property
+                    .get("description")
+                    .and_then(|d| d.as_str())
+                    .unwrap_or("")
+                    .replace('\n', " ")
+                    .replace('|', "\\|");
+                let required = if summary.required.contains(name) {
+                    "yes"
+                } else {
+                    "no"
+                };
+                out.push_str(&format!(
+                    "| `{name}` | {} | {required} | {description} |\n",
+                    ty.replace('|', "\\|")
+                ));
+            }
+        }
+
+        out.push_str(&format!(
+            "\n<details><summary>Input schema</summary>\n\n```json\n{}\n```\n\n</details>\n",
+            serde_json::to_string_pretty(schema.input_schema.as_value())?
+        ));
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_lists_every_builtin_tool() {
+        let schemas = builtin_tool_schemas();
+        let markdown = render_schemas(&schemas, SchemaFormat::Markdown).unwrap();
+
+        for schema in &schemas {
+            assert!(
+                markdown.contains(&format!("(`{}`)", schema.name)),
+                "missing section for {}",
+                schema.name
+            );
+        }
+        assert!(markdown.contains("| `file_path` |"));
+    }
+
+    #[test]
+    fn json_round_trips_schemas() {
+        let schemas = builtin_tool_schemas();
+        let json = render_schemas(&schemas, SchemaFormat::Json).unwrap();
+        let parsed: Vec<ToolSchema> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.len(), schemas.len());
+    }
+}
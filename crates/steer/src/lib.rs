@@ -3,17 +3,20 @@ pub mod commands;
 pub mod error;
 pub mod model_resolver;
 pub mod session_config;
+pub mod session_resume;
 pub mod telemetry;
 
 pub use steer_core::{api, app, config, runners, session, tools, utils, workspace};
 
 use eyre::Result;
 use std::sync::Arc;
-use steer_core::api::Client as ApiClient;
+use std::time::Instant;
+use steer_core::api::{Client as ApiClient, ResponseCache};
 use steer_core::app::domain::runtime::{RuntimeHandle, RuntimeService};
 use steer_core::app::domain::session::SqliteEventStore;
 use steer_core::app::domain::types::SessionId;
 use steer_core::config::model::ModelId;
+use steer_core::profiling::{ProfilePhase, Profiler};
 use steer_core::runners::{OneShotRunner, RunOnceResult};
 use steer_core::session::state::SessionConfig;
 use steer_core::tools::ToolSystemBuilder;
@@ -43,9 +46,50 @@ pub async fn run_once_new_session(
         .map_err(|e| eyre::eyre!("Failed to run new session: {}", e))
 }
 
+/// Like [`run_once_in_session`], but the final answer must be JSON matching
+/// `schema`. See [`OneShotRunner::run_in_session_with_schema`].
+pub async fn run_once_in_session_with_schema(
+    runtime: &RuntimeHandle,
+    session_id: SessionId,
+    message: String,
+    model: ModelId,
+    schema: &serde_json::Value,
+) -> Result<RunOnceResult> {
+    OneShotRunner::run_in_session_with_schema(runtime, session_id, message, model, schema)
+        .await
+        .map_err(|e| schema_run_error("Failed to run in session", e))
+}
+
+/// Like [`run_once_new_session`], but the final answer must be JSON matching
+/// `schema`. See [`OneShotRunner::run_in_session_with_schema`].
+pub async fn run_once_new_session_with_schema(
+    runtime: &RuntimeHandle,
+    config: SessionConfig,
+    message: String,
+    model: ModelId,
+    schema: &serde_json::Value,
+) -> Result<RunOnceResult> {
+    OneShotRunner::run_new_session_with_schema(runtime, config, message, model, schema)
+        .await
+        .map_err(|e| schema_run_error("Failed to run new session", e))
+}
+
+/// Include the model's last answer when it never matched the schema, so
+/// callers can see what was rejected.
+fn schema_run_error(context: &str, error: steer_core::error::Error) -> eyre::Report {
+    match &error {
+        steer_core::error::Error::StructuredOutput { raw_output, .. } => {
+            eyre::eyre!("{context}: {error}\n\nLast output:\n{raw_output}")
+        }
+        _ => eyre::eyre!("{context}: {error}"),
+    }
+}
+
 pub struct RuntimeBuilder {
     default_model: String,
     catalog_paths: Vec<String>,
+    profiler: Option<Profiler>,
+    response_cache: Option<ResponseCache>,
 }
 
 impl RuntimeBuilder {
@@ -53,6 +97,8 @@ impl RuntimeBuilder {
         Self {
             default_model,
             catalog_paths: Vec::new(),
+            profiler: None,
+            response_cache: None,
         }
     }
 
@@ -61,30 +107,59 @@ impl RuntimeBuilder {
         self
     }
 
+    pub fn with_profiler(mut self, profiler: Profiler) -> Self {
+        self.profiler = Some(profiler);
+        self
+    }
+
+    pub fn with_response_cache(mut self, cache: ResponseCache) -> Self {
+        self.response_cache = Some(cache);
+        self
+    }
+
     pub async fn build(self) -> Result<(RuntimeService, ModelId)> {
         let event_store = create_event_store().await?;
 
+        let started = Instant::now();
         let auth_storage = Arc::new(
             steer_core::auth::DefaultAuthStorage::new()
                 .map_err(|e| eyre::eyre!("Failed to create auth storage: {}", e))?,
         );
+        if let Some(profiler) = &self.profiler {
+            profiler.record(
+                ProfilePhase::AuthResolution,
+                Some("auth_storage".to_string()),
+                started,
+            );
+        }
 
+        let started = Instant::now();
         let app_config = steer_core::app::AppConfig::from_auth_storage_with_catalog(
             auth_storage,
             steer_core::catalog::CatalogConfig::with_catalogs(self.catalog_paths),
         )
         .map_err(|e| eyre::eyre!("Failed to create app config: {}", e))?;
+        if let Some(profiler) = &self.profiler {
+            profiler.record(ProfilePhase::CatalogLoad, None, started);
+        }
 
         let model_id = app_config
             .model_registry
             .resolve(&self.default_model)
             .map_err(|e| eyre::eyre!("Invalid model '{}': {}", self.default_model, e))?;
 
-        let api_client = Arc::new(ApiClient::new_with_deps(
+        let mut api_client = ApiClient::new_with_deps(
             app_config.llm_config_provider.clone(),
             app_config.provider_registry.clone(),
             app_config.model_registry.clone(),
-        ));
+        );
+        if let Some(profiler) = &self.profiler {
+            api_client = api_client.with_profiler(profiler.clone());
+        }
+        if let Some(cache) = self.response_cache {
+            api_client = api_client.with_response_cache(cache);
+        }
+        let api_client = Arc::new(api_client);
 
         let workspace_root = std::env::current_dir()
             .map_err(|e| eyre::eyre!("Failed to get current directory: {}", e))?;
@@ -104,15 +179,18 @@ impl RuntimeBuilder {
         );
         let repo_manager: Arc<dyn RepoManager> = workspace_manager.clone();
 
-        let tool_executor = ToolSystemBuilder::new(
+        let mut tool_system = ToolSystemBuilder::new(
             workspace,
             event_store.clone(),
             api_client.clone(),
             app_config.model_registry.clone(),
         )
         .with_workspace_manager(workspace_manager)
-        .with_repo_manager(repo_manager)
-        .build();
+        .with_repo_manager(repo_manager);
+        if let Some(profiler) = self.profiler {
+            tool_system = tool_system.with_profiler(profiler);
+        }
+        let tool_executor = tool_system.build();
 
         let service = RuntimeService::spawn(event_store, api_client, tool_executor);
 
@@ -129,7 +207,7 @@ async fn create_event_store() -> Result<Arc<SqliteEventStore>> {
 
     let db_path = data_dir.join("events.db");
 
-    let store = SqliteEventStore::new(&db_path)
+    let store = SqliteEventStore::open_configured(&db_path)
         .await
         .map_err(|e| eyre::eyre!("Failed to create event store: {}", e))?;
 
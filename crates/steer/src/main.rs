@@ -2,42 +2,70 @@ use clap::Parser;
 use eyre::Result;
 
 use std::io::Write;
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
+use std::time::Duration;
 use steer::cli::{Cli, Commands};
 use steer::commands::{
-    Command, headless::HeadlessCommand, serve::ServeCommand, session::SessionCommand,
+    Command,
+    audit::AuditCommand,
+    auth::AuthCommand,
+    cache::CacheCommand,
+    catalog::CatalogCommand,
+    completions::CompletionsCommand,
+    headless::HeadlessCommand,
+    review::ReviewCommand,
+    self_update::SelfUpdateCommand,
+    serve::{EventLogOptions, ServeCommand},
+    session::SessionCommand,
+    task::TaskCommand,
+    tools::ToolsCommand,
     workspace::WorkspaceCommand,
 };
 use steer::model_resolver::resolve_model_selection;
 use steer::session_config::{SessionConfigLoader, SessionConfigOverrides};
+use steer::session_resume::ResumeMode;
 use steer::telemetry::{StartupCommand as TelemetryStartupCommand, StartupTelemetryContext};
+use steer_core::app::domain::runtime::RuntimeConfig;
+use steer_core::review::ReviewTarget;
+use steer_core::utils::paths::AppPaths;
 use tracing::{debug, warn};
 use uuid::Uuid;
 
 /// Parameters for running the TUI
 struct TuiParams {
     session_id: Option<String>,
+    resume: ResumeMode,
     model: Option<String>,
     model_override: Option<String>,
     directory: Option<PathBuf>,
     session_db: Option<PathBuf>,
     session_config_path: Option<PathBuf>,
+    isolate: bool,
+    allow_network: Option<bool>,
+    allow_outside_workspace: bool,
     theme: Option<String>,
     catalogs: Vec<PathBuf>,
     force_setup: bool,
+    plain: bool,
 }
 
 /// Parameters for running the TUI with a remote server
 struct RemoteTuiParams {
     remote_addr: String,
     session_id: Option<String>,
+    resume: ResumeMode,
     model: Option<String>,
     model_override: Option<String>,
     directory: Option<PathBuf>,
     session_config_path: Option<PathBuf>,
+    isolate: bool,
+    allow_network: Option<bool>,
+    allow_outside_workspace: bool,
     theme: Option<String>,
     catalogs: Vec<PathBuf>,
     force_setup: bool,
+    plain: bool,
 }
 
 #[cfg(feature = "ui")]
@@ -48,16 +76,24 @@ async fn main() -> Result<()> {
     // Install color-eyre for better error reports
     color_eyre::install()?;
 
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
 
     // Load .env file if it exists
     steer::cli::config::load_env()?;
 
+    // Layer the config file and STEER_* env vars under the flags
+    let config = steer::cli::config::Config::resolve(&mut cli)?;
+
     // Initialize tracing (level configured via RUST_LOG env var)
-    steer_core::utils::tracing::init_tracing()?;
+    steer_core::utils::tracing::init_tracing(config.log_format.unwrap_or_default())?;
 
     // Load preferences to get default model
     let preferences = steer_core::preferences::Preferences::load().unwrap_or_default();
+    steer_i18n::init(preferences.ui.locale.as_deref());
+    steer_core::http::configure(&preferences.network.http_config())?;
+
+    let offline = cli.offline || detect_offline(&cli, &preferences).await;
+    steer_core::offline::set_offline(offline);
 
     // Determine preferred model source:
     // 1. CLI argument (if provided)
@@ -73,13 +109,16 @@ async fn main() -> Result<()> {
         model: preferred_model.clone(),
     };
     let telemetry_preferences = preferences.telemetry.clone();
-    tokio::spawn(async move {
-        steer::telemetry::emit_startup_event(telemetry_context, telemetry_preferences).await;
-    });
+    if !offline {
+        tokio::spawn(async move {
+            steer::telemetry::emit_startup_event(telemetry_context, telemetry_preferences).await;
+        });
+    }
 
-    // Set up signal handlers for terminal cleanup if using TUI
+    // Set up signal handlers for terminal cleanup if using TUI. Plain mode never
+    // touches the terminal and handles Ctrl+C itself.
     #[cfg(feature = "ui")]
-    if cli.command.is_none() || matches!(cli.command, Some(Commands::Tui { .. })) {
+    if !cli.plain && (cli.command.is_none() || matches!(cli.command, Some(Commands::Tui { .. }))) {
         setup_signal_handlers().await;
     }
 
@@ -108,8 +147,12 @@ async fn main() -> Result<()> {
                 let session_config_path = session_config.or(cli.session_config.clone());
                 // Use subcommand theme if provided, otherwise fall back to global
                 let theme_name = theme.or(cli.theme.clone());
+                let resume =
+                    ResumeMode::from_flags(cli.resume_latest, cli.continue_session, cli.resume);
                 // Set panic hook for terminal cleanup
-                setup_panic_hook();
+                if !cli.plain {
+                    setup_panic_hook();
+                }
 
                 // Launch TUI with appropriate backend
                 if let Some(addr) = remote_addr {
@@ -124,13 +167,18 @@ async fn main() -> Result<()> {
                     run_tui_remote(RemoteTuiParams {
                         remote_addr: addr,
                         session_id: cli.session,
+                        resume,
                         model: preferred_model.clone(),
                         model_override: cli_model.clone(),
                         directory: cli.directory,
                         session_config_path,
+                        isolate: cli.isolate,
+                        allow_network: cli.allow_network,
+                        allow_outside_workspace: cli.allow_outside_workspace,
                         theme: theme_name.clone(),
                         catalogs,
                         force_setup,
+                        plain: cli.plain,
                     })
                     .await
                 } else {
@@ -147,14 +195,19 @@ async fn main() -> Result<()> {
                     // Launch with in-process server
                     run_tui_local(TuiParams {
                         session_id: cli.session,
+                        resume,
                         model: preferred_model.clone(),
                         model_override: cli_model.clone(),
                         directory: cli.directory,
                         session_db: cli.session_db,
                         session_config_path,
+                        isolate: cli.isolate,
+                        allow_network: cli.allow_network,
+                        allow_outside_workspace: cli.allow_outside_workspace,
                         theme: theme_name,
                         catalogs: catalogs.iter().map(PathBuf::from).collect(),
                         force_setup,
+                        plain: cli.plain,
                     })
                     .await
                 }
@@ -185,6 +238,12 @@ async fn main() -> Result<()> {
             session_config,
             remote,
             catalogs,
+            show_usage,
+            profile,
+            auto_commit,
+            json_schema,
+            response_cache,
+            response_cache_dir,
         } => {
             let remote_addr = remote.or(cli.remote.clone());
             let catalog_paths: Vec<String> = catalogs
@@ -207,14 +266,33 @@ async fn main() -> Result<()> {
                 session_config,
                 remote: remote_addr,
                 directory: cli.directory,
+                allow_network: cli.allow_network,
+                allow_outside_workspace: cli.allow_outside_workspace,
                 catalogs,
+                show_usage,
+                profile,
+                auto_commit,
+                json_schema,
+                response_cache: response_cache
+                    .then(|| response_cache_dir.unwrap_or_else(AppPaths::response_cache_dir)),
             };
             command.execute().await
         }
         Commands::Server {
             port,
             bind,
+            http_port,
             catalogs: server_catalogs,
+            webhooks,
+            metrics_addr,
+            drain_timeout,
+            idle_session_timeout,
+            max_concurrent_sessions,
+            log_events,
+            log_events_session,
+            log_events_kinds,
+            log_events_max_bytes,
+            log_events_max_files,
         } => {
             // Merge catalogs: prefer subcommand if provided, else use global
             let catalogs = if server_catalogs.is_empty() {
@@ -227,8 +305,23 @@ async fn main() -> Result<()> {
             let command = ServeCommand {
                 port,
                 bind,
+                http_port,
                 session_db: cli.session_db.clone(),
                 catalogs: catalogs.iter().map(PathBuf::from).collect(),
+                webhooks,
+                metrics_addr,
+                drain_timeout: Duration::from_secs(drain_timeout),
+                runtime: RuntimeConfig {
+                    idle_timeout: idle_session_timeout.map(|secs| Duration::from_secs(secs.get())),
+                    max_concurrent_sessions: max_concurrent_sessions.map(NonZeroUsize::get),
+                },
+                event_log: EventLogOptions {
+                    destination: log_events,
+                    session_glob: log_events_session,
+                    kinds: log_events_kinds,
+                    max_bytes: log_events_max_bytes,
+                    max_files: log_events_max_files,
+                },
             };
             command.execute().await
         }
@@ -250,16 +343,97 @@ async fn main() -> Result<()> {
             };
             command.execute().await
         }
+        Commands::Task { task_command } => {
+            let command = TaskCommand {
+                command: task_command,
+                remote: cli.remote.clone(),
+                preferred_model: preferred_model.clone(),
+            };
+            command.execute().await
+        }
+        Commands::Review {
+            base,
+            target,
+            output,
+            out,
+            model: review_model,
+            session_config,
+            catalogs,
+        } => {
+            let target = match base {
+                Some(base) => ReviewTarget::Branch(base),
+                None => target.as_deref().unwrap_or_default().parse()?,
+            };
+            let catalogs = if catalogs.is_empty() {
+                cli.catalogs.clone()
+            } else {
+                catalogs
+            };
+            let catalogs = normalize_catalogs(&catalogs);
+            let model = match review_model.or(cli_model.clone()) {
+                Some(model) => model,
+                None => resolve_model_selection(preference_model.as_deref(), &catalogs)
+                    .default_model
+                    .to_string(),
+            };
+
+            let command = ReviewCommand {
+                target,
+                output,
+                out,
+                model,
+                session_config: session_config.or(cli.session_config.clone()),
+                directory: cli.directory.clone(),
+                catalogs,
+            };
+            command.execute().await
+        }
+        Commands::Tools { tools_command } => {
+            let command = ToolsCommand {
+                command: tools_command,
+            };
+            command.execute().await
+        }
+        Commands::Catalog { catalog_command } => {
+            let command = CatalogCommand {
+                command: catalog_command,
+            };
+            command.execute().await
+        }
+        Commands::Auth { auth_command } => {
+            let command = AuthCommand {
+                command: auth_command,
+            };
+            command.execute().await
+        }
+        Commands::Audit { audit_command } => {
+            let command = AuditCommand {
+                command: audit_command,
+                remote: cli.remote.clone(),
+                session_db: cli.session_db.clone(),
+            };
+            command.execute().await
+        }
+        Commands::Cache { dir, cache_command } => {
+            let command = CacheCommand {
+                command: cache_command,
+                dir: dir.unwrap_or_else(AppPaths::response_cache_dir),
+            };
+            command.execute().await
+        }
+        Commands::SelfUpdate { check } => SelfUpdateCommand { check }.execute().await,
+        Commands::Completions { shell } => CompletionsCommand { shell }.execute().await,
     }
 }
 
 #[cfg(feature = "ui")]
 async fn run_tui_local(params: TuiParams) -> Result<()> {
+    use steer::commands::session::offer_isolation_actions;
+    use steer::session_resume::resolve_session;
+    use steer_core::isolation::IsolationMode;
     use steer_grpc::client_api::CreateSessionParams;
     use steer_grpc::local_server;
 
-    let mut session_id = params.session_id;
-
     // Set working directory if specified
     if let Some(dir) = &params.directory {
         std::env::set_current_dir(dir)?;
@@ -284,7 +458,7 @@ async fn run_tui_local(params: TuiParams) -> Result<()> {
     let channel = local_grpc_setup.channel;
 
     // Create gRPC client
-    let client = steer_grpc::AgentClient::from_channel(channel)
+    let client = steer_grpc::AgentClient::from_channel(channel.clone())
         .await
         .map_err(|e| eyre::eyre!("Failed to create gRPC client: {}", e))?;
 
@@ -320,31 +494,23 @@ async fn run_tui_local(params: TuiParams) -> Result<()> {
         server_default.clone()
     };
 
-    // Resolve "latest" alias
-    if matches!(session_id.as_deref(), Some("latest")) {
-        let mut sessions = client
-            .list_sessions()
-            .await
-            .map_err(|e| eyre::eyre!("Failed to list sessions: {}", e))?;
-        // Sort sessions by updated_at descending, fallback to created_at
-        sessions.sort_by(|a, b| {
-            let ts_to_tuple = |ts: &Option<prost_types::Timestamp>| {
-                ts.as_ref().map_or((0, 0), |t| (t.seconds, t.nanos))
-            };
-            ts_to_tuple(&b.updated_at).cmp(&ts_to_tuple(&a.updated_at))
-        });
-        if let Some(latest) = sessions.first() {
-            session_id = Some(latest.id.clone());
-        } else {
-            eyre::bail!("No sessions found to resume");
-        }
-    }
+    // Resolve "latest" alias and --resume-latest/--continue/--resume
+    let mut session_id = resolve_session(
+        &client,
+        params.session_id,
+        params.resume,
+        &std::env::current_dir()?,
+    )
+    .await?;
 
     // If no session_id, we need to create a new session
     if session_id.is_none() {
         // Load session config (explicit path if provided, else auto-discovery or defaults)
         let overrides = SessionConfigOverrides {
             default_model: model_override.clone(),
+            isolation: params.isolate.then_some(IsolationMode::Worktree),
+            allow_network: params.allow_network,
+            allow_outside_workspace: params.allow_outside_workspace.then_some(true),
             ..Default::default()
         };
 
@@ -366,25 +532,44 @@ async fn run_tui_local(params: TuiParams) -> Result<()> {
     }
 
     // Run TUI with the client
-    tui::run_tui(
-        client,
-        session_id,
-        model_id,
-        params.directory.clone(),
-        params.theme.clone(),
-        params.force_setup,
-    )
-    .await
-    .map_err(|e| eyre::eyre!("TUI error: {}", e))
+    if params.plain {
+        tui::run_plain(
+            client,
+            session_id.clone(),
+            model_id,
+            params.directory.clone(),
+        )
+        .await
+    } else {
+        tui::run_tui(
+            client,
+            session_id.clone(),
+            model_id,
+            params.directory.clone(),
+            params.theme.clone(),
+            params.force_setup,
+        )
+        .await
+    }
+    .map_err(|e| tui_error(e, None))?;
+
+    if let Some(session_id) = session_id {
+        let client = steer_grpc::AgentClient::from_channel(channel)
+            .await
+            .map_err(|e| eyre::eyre!("Failed to create gRPC client: {}", e))?;
+        offer_isolation_actions(&client, &session_id).await?;
+    }
+    Ok(())
 }
 
 #[cfg(feature = "ui")]
 async fn run_tui_remote(params: RemoteTuiParams) -> Result<()> {
+    use steer::commands::session::offer_isolation_actions;
+    use steer::session_resume::resolve_session;
+    use steer_core::isolation::IsolationMode;
     use steer_grpc::AgentClient;
     use steer_grpc::client_api::CreateSessionParams;
 
-    let mut session_id = params.session_id;
-
     // Connect to remote server
     let client = AgentClient::connect(&params.remote_addr)
         .await
@@ -394,24 +579,13 @@ async fn run_tui_remote(params: RemoteTuiParams) -> Result<()> {
         warn!("Ignoring --catalog for remote TUI");
     }
 
-    // Resolve "latest" alias
-    if matches!(session_id.as_deref(), Some("latest")) {
-        let mut sessions = client
-            .list_sessions()
-            .await
-            .map_err(|e| eyre::eyre!("Failed to list sessions: {}", e))?;
-        sessions.sort_by(|a, b| {
-            let ts_to_tuple = |ts: &Option<prost_types::Timestamp>| {
-                ts.as_ref().map_or((0, 0), |t| (t.seconds, t.nanos))
-            };
-            ts_to_tuple(&b.updated_at).cmp(&ts_to_tuple(&a.updated_at))
-        });
-        if let Some(latest) = sessions.first() {
-            session_id = Some(latest.id.clone());
-        } else {
-            eyre::bail!("No sessions found to resume");
-        }
-    }
+    // Resolve "latest" alias and --resume-latest/--continue/--resume
+    let workspace = match &params.directory {
+        Some(dir) => dir.clone(),
+        None => std::env::current_dir()?,
+    };
+    let mut session_id =
+        resolve_session(&client, params.session_id, params.resume, &workspace).await?;
 
     let server_default = client
         .get_default_model()
@@ -450,6 +624,9 @@ async fn run_tui_remote(params: RemoteTuiParams) -> Result<()> {
         // Load session config (explicit path if provided, else auto-discovery or defaults)
         let overrides = SessionConfigOverrides {
             default_model: model_override.clone(),
+            isolation: params.isolate.then_some(IsolationMode::Worktree),
+            allow_network: params.allow_network,
+            allow_outside_workspace: params.allow_outside_workspace.then_some(true),
             ..Default::default()
         };
 
@@ -471,16 +648,58 @@ async fn run_tui_remote(params: RemoteTuiParams) -> Result<()> {
     }
 
     // Run TUI with the client
-    tui::run_tui(
-        client,
+    if params.plain {
+        tui::run_plain(
+            client,
+            session_id.clone(),
+            model_id,
+            params.directory.clone(),
+        )
+        .await
+    } else {
+        tui::run_tui(
+            client,
+            session_id.clone(),
+            model_id,
+            params.directory.clone(),
+            params.theme.clone(),
+            params.force_setup,
+        )
+        .await
+    }
+    .map_err(|e| tui_error(e, Some(&params.remote_addr)))?;
+
+    if let Some(session_id) = session_id {
+        let client = AgentClient::connect(&params.remote_addr)
+            .await
+            .map_err(|e| eyre::eyre!("Failed to reconnect to remote server: {}", e))?;
+        offer_isolation_actions(&client, &session_id).await?;
+    }
+    Ok(())
+}
+
+/// Converts a TUI failure into a report. After a render crash the terminal has
+/// been restored by now, so tell the user how to get back into the session.
+#[cfg(feature = "ui")]
+fn tui_error(err: steer_tui::error::Error, remote_addr: Option<&str>) -> eyre::Report {
+    if let steer_tui::error::Error::RenderCrash {
         session_id,
-        model_id,
-        params.directory.clone(),
-        params.theme.clone(),
-        params.force_setup,
-    )
-    .await
-    .map_err(|e| eyre::eyre!("TUI error: {}", e))
+        crash_log,
+    } = &err
+    {
+        let remote = remote_addr
+            .map(|addr| format!(" --remote {addr}"))
+            .unwrap_or_default();
+        let mut stderr = std::io::stderr();
+        let _ = writeln!(
+            stderr,
+            "The TUI crashed. Session {session_id} is saved; resume it with:\n\n    steer --session {session_id}{remote}\n"
+        );
+        if let Some(path) = crash_log {
+            let _ = writeln!(stderr, "Crash log: {}", path.display());
+        }
+    }
+    eyre::eyre!("TUI error: {}", err)
 }
 
 #[cfg(feature = "ui")]
@@ -539,11 +758,59 @@ fn map_startup_command(command: Option<&Commands>) -> TelemetryStartupCommand {
         Some(Commands::Headless { .. }) => TelemetryStartupCommand::Headless,
         Some(Commands::Server { .. }) => TelemetryStartupCommand::Server,
         Some(
-            Commands::Preferences { .. } | Commands::Session { .. } | Commands::Workspace { .. },
+            Commands::Preferences { .. }
+            | Commands::Session { .. }
+            | Commands::Workspace { .. }
+            | Commands::Task { .. }
+            | Commands::Review { .. }
+            | Commands::Tools { .. }
+            | Commands::Catalog { .. }
+            | Commands::Auth { .. }
+            | Commands::Audit { .. }
+            | Commands::Cache { .. }
+            | Commands::SelfUpdate { .. }
+            | Commands::Completions { .. },
         ) => TelemetryStartupCommand::Unknown,
     }
 }
 
+/// Probes the network when the command would call a model from this process,
+/// and falls back to offline mode with a warning if it is unreachable. Skipped
+/// behind a proxy, where direct connections may be blocked even though requests
+/// get through.
+async fn detect_offline(cli: &Cli, preferences: &steer_core::preferences::Preferences) -> bool {
+    let needs_network = match &cli.command {
+        None => cli.remote.is_none(),
+        Some(Commands::Tui { remote, .. } | Commands::Headless { remote, .. }) => {
+            remote.is_none() && cli.remote.is_none()
+        }
+        Some(Commands::Server { .. }) => true,
+        Some(_) => false,
+    };
+    let proxied = preferences.network.proxy.is_some()
+        || ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]
+            .iter()
+            .any(|var| std::env::var_os(var).is_some());
+    if !needs_network || proxied {
+        return false;
+    }
+
+    if steer_core::offline::network_reachable(
+        steer_core::offline::PROBE_ADDR,
+        Duration::from_secs(2),
+    )
+    .await
+    {
+        return false;
+    }
+    warn!("Network unreachable; starting in offline mode");
+    let _ = writeln!(
+        std::io::stderr(),
+        "Warning: network unreachable, starting in offline mode. Only providers on this machine can be used; pass --offline to skip this check."
+    );
+    true
+}
+
 fn parse_session_uuid(session: Option<&str>) -> Option<Uuid> {
     session.and_then(|value| Uuid::parse_str(value).ok())
 }
@@ -4,10 +4,11 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use steer_core::config::model::ModelId;
+use steer_core::isolation::IsolationMode;
 use steer_core::session::{
-    ApprovalRulesOverrides, BackendConfig, RemoteAuth, SessionConfig, SessionPolicyOverrides,
-    SessionToolConfig, ToolApprovalPolicy, ToolApprovalPolicyOverrides, ToolRuleOverrides,
-    ToolVisibility, WorkspaceConfig,
+    ApprovalRulesOverrides, BackendConfig, BashDenylistConfig, RemoteAuth, SessionConfig,
+    SessionPolicyOverrides, SessionToolConfig, ToolApprovalPolicy, ToolApprovalPolicyOverrides,
+    ToolResultBudget, ToolRuleOverrides, ToolVisibility, WorkspaceConfig,
 };
 use thiserror::Error;
 use tokio::fs;
@@ -40,9 +41,15 @@ pub enum SessionConfigError {
     #[error("auto_compaction.threshold_percent must be between 1 and 100")]
     InvalidThresholdPercent,
 
+    #[error("auto_summary.refresh_after_messages must be at least 1")]
+    InvalidSummaryRefresh,
+
     #[error("system_prompt is no longer supported in session config or CLI overrides")]
     SystemPromptUnsupported,
 
+    #[error(transparent)]
+    InvalidBashDenylist(#[from] steer_core::tools::DenylistError),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -62,6 +69,8 @@ pub struct PartialSessionConfig {
     pub system_prompt: Option<String>,
     pub metadata: Option<HashMap<String, String>>,
     pub auto_compaction: Option<PartialAutoCompactionConfig>,
+    pub auto_summary: Option<PartialAutoSummaryConfig>,
+    pub isolation: Option<IsolationMode>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
@@ -83,6 +92,11 @@ pub struct PartialToolConfig {
     pub backends: Option<Vec<BackendConfig>>,
     pub visibility: Option<ToolVisibilityConfig>,
     pub approvals: Option<PartialApprovalConfig>,
+    pub result_budget: Option<ToolResultBudget>,
+    pub env: Option<HashMap<String, String>>,
+    pub bash_denylist: Option<BashDenylistConfig>,
+    pub allow_network: Option<bool>,
+    pub allow_outside_workspace: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Default, JsonSchema)]
@@ -125,12 +139,23 @@ pub struct PartialAutoCompactionConfig {
     pub threshold_percent: Option<u32>,
 }
 
+/// Partial auto-summary configuration (all fields optional for TOML merging).
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct PartialAutoSummaryConfig {
+    pub enabled: Option<bool>,
+    pub model: Option<ModelId>,
+    pub refresh_after_messages: Option<u32>,
+}
+
 /// Overrides that can be applied from CLI arguments
 #[derive(Debug, Default)]
 pub struct SessionConfigOverrides {
     pub system_prompt: Option<String>,
     pub metadata: Option<String>,
     pub default_model: Option<ModelId>,
+    pub isolation: Option<IsolationMode>,
+    pub allow_network: Option<bool>,
+    pub allow_outside_workspace: Option<bool>,
 }
 
 /// Loads session configuration from files and applies overrides
@@ -196,6 +221,9 @@ impl SessionConfigLoader {
                 title: None,
                 metadata: HashMap::new(),
                 auto_compaction: steer_core::session::state::AutoCompactionConfig::default(),
+                auto_summary: steer_core::session::state::AutoSummaryConfig::default(),
+                isolation: IsolationMode::None,
+                isolated_workspace: None,
             })
         };
 
@@ -288,6 +316,13 @@ impl SessionConfigLoader {
                 visibility: ToolVisibility::default(),
                 approval_policy: ToolApprovalPolicy::default(),
                 metadata: HashMap::new(),
+                result_budget: partial_tool_config.result_budget.unwrap_or_default(),
+                env: partial_tool_config.env.unwrap_or_default(),
+                bash_denylist: partial_tool_config.bash_denylist.unwrap_or_default(),
+                allow_network: partial_tool_config.allow_network.unwrap_or(true),
+                allow_outside_workspace: partial_tool_config
+                    .allow_outside_workspace
+                    .unwrap_or(false),
             }
         } else {
             SessionToolConfig::default()
@@ -321,6 +356,21 @@ impl SessionConfigLoader {
                     }
                 })
                 .unwrap_or_default(),
+            auto_summary: partial
+                .auto_summary
+                .map(|p| {
+                    let defaults = steer_core::session::state::AutoSummaryConfig::default();
+                    steer_core::session::state::AutoSummaryConfig {
+                        enabled: p.enabled.unwrap_or(defaults.enabled),
+                        model: p.model.or(defaults.model),
+                        refresh_after_messages: p
+                            .refresh_after_messages
+                            .unwrap_or(defaults.refresh_after_messages),
+                    }
+                })
+                .unwrap_or_default(),
+            isolation: partial.isolation.unwrap_or_default(),
+            isolated_workspace: None,
         })
     }
 
@@ -339,6 +389,18 @@ impl SessionConfigLoader {
             config.metadata.extend(metadata);
         }
 
+        if let Some(isolation) = self.overrides.isolation {
+            config.isolation = isolation;
+        }
+
+        if let Some(allow_network) = self.overrides.allow_network {
+            config.tool_config.allow_network = allow_network;
+        }
+
+        if let Some(allow_outside_workspace) = self.overrides.allow_outside_workspace {
+            config.tool_config.allow_outside_workspace = allow_outside_workspace;
+        }
+
         Ok(())
     }
 
@@ -402,6 +464,12 @@ impl SessionConfigLoader {
             return Err(SessionConfigError::InvalidThresholdPercent);
         }
 
+        if config.auto_summary.refresh_after_messages == 0 {
+            return Err(SessionConfigError::InvalidSummaryRefresh);
+        }
+
+        steer_core::tools::CommandDenylist::new(&config.tool_config.bash_denylist)?;
+
         Ok(())
     }
 }
@@ -644,6 +712,193 @@ default_behavior = "invalid_value"
         );
     }
 
+    #[tokio::test]
+    async fn test_tool_result_budget_loaded_from_toml() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(
+            temp_file,
+            r"
+[tool_config.result_budget]
+max_result_bytes = 32768
+"
+        )
+        .unwrap();
+
+        let loader = SessionConfigLoader::new(test_model(), Some(temp_file.path().to_path_buf()));
+        let config = loader.load().await.unwrap();
+
+        assert_eq!(
+            config.tool_config.result_budget,
+            ToolResultBudget {
+                max_result_bytes: Some(32_768),
+                max_turn_bytes: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tool_env_loaded_from_toml() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(
+            temp_file,
+            r#"
+[tool_config.env]
+API_URL = "http://localhost:8080"
+DATABASE_URL = "postgres://app:${{env:DB_PASSWORD}}@localhost/app"
+"#
+        )
+        .unwrap();
+
+        let loader = SessionConfigLoader::new(test_model(), Some(temp_file.path().to_path_buf()));
+        let config = loader.load().await.unwrap();
+
+        assert_eq!(
+            config.tool_config.env,
+            HashMap::from([
+                ("API_URL".to_string(), "http://localhost:8080".to_string()),
+                (
+                    "DATABASE_URL".to_string(),
+                    "postgres://app:${env:DB_PASSWORD}@localhost/app".to_string()
+                ),
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bash_denylist_loaded_from_toml() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(
+            temp_file,
+            r#"
+[tool_config.bash_denylist]
+deny = ["git push --force"]
+allow = ["git reset --hard"]
+"#
+        )
+        .unwrap();
+
+        let loader = SessionConfigLoader::new(test_model(), Some(temp_file.path().to_path_buf()));
+        let config = loader.load().await.unwrap();
+
+        assert_eq!(
+            config.tool_config.bash_denylist,
+            BashDenylistConfig {
+                deny: vec!["git push --force".to_string()],
+                allow: vec!["git reset --hard".to_string()],
+                disable_defaults: false,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bash_denylist_rejects_invalid_rules() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(
+            temp_file,
+            r#"
+[tool_config.bash_denylist]
+deny = ["rm 'unterminated"]
+"#
+        )
+        .unwrap();
+
+        let err = SessionConfigLoader::new(test_model(), Some(temp_file.path().to_path_buf()))
+            .load()
+            .await
+            .expect_err("an unparseable denylist rule should fail validation");
+        assert!(
+            err.to_string().contains("rm 'unterminated"),
+            "Error should name the rule, got: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_isolation_from_toml_and_overrides() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, r#"isolation = "worktree""#).unwrap();
+
+        let loader = SessionConfigLoader::new(test_model(), Some(temp_file.path().to_path_buf()));
+        let config = loader.load().await.unwrap();
+        assert_eq!(config.isolation, IsolationMode::Worktree);
+
+        let overrides = SessionConfigOverrides {
+            isolation: Some(IsolationMode::Worktree),
+            ..Default::default()
+        };
+        let loader = SessionConfigLoader::new(test_model(), None).with_overrides(overrides);
+        let config = loader.load().await.unwrap();
+        assert_eq!(config.isolation, IsolationMode::Worktree);
+    }
+
+    #[tokio::test]
+    async fn test_allow_network_from_toml_and_overrides() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let config = SessionConfigLoader::new(test_model(), None)
+            .load()
+            .await
+            .unwrap();
+        assert!(config.tool_config.allow_network);
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, "[tool_config]\nallow_network = false").unwrap();
+        let loader = SessionConfigLoader::new(test_model(), Some(temp_file.path().to_path_buf()));
+        let config = loader.load().await.unwrap();
+        assert!(!config.tool_config.allow_network);
+
+        let overrides = SessionConfigOverrides {
+            allow_network: Some(true),
+            ..Default::default()
+        };
+        let loader = SessionConfigLoader::new(test_model(), Some(temp_file.path().to_path_buf()))
+            .with_overrides(overrides);
+        let config = loader.load().await.unwrap();
+        assert!(config.tool_config.allow_network);
+    }
+
+    #[tokio::test]
+    async fn test_allow_outside_workspace_from_toml_and_overrides() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let config = SessionConfigLoader::new(test_model(), None)
+            .load()
+            .await
+            .unwrap();
+        assert!(!config.tool_config.allow_outside_workspace);
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, "[tool_config]\nallow_outside_workspace = true").unwrap();
+        let loader = SessionConfigLoader::new(test_model(), Some(temp_file.path().to_path_buf()));
+        let config = loader.load().await.unwrap();
+        assert!(config.tool_config.allow_outside_workspace);
+
+        let overrides = SessionConfigOverrides {
+            allow_outside_workspace: Some(false),
+            ..Default::default()
+        };
+        let loader = SessionConfigLoader::new(test_model(), Some(temp_file.path().to_path_buf()))
+            .with_overrides(overrides);
+        let config = loader.load().await.unwrap();
+        assert!(!config.tool_config.allow_outside_workspace);
+    }
+
     #[tokio::test]
     async fn test_mcp_backend_validation_empty_server_name() {
         use std::io::Write;
@@ -1236,4 +1491,34 @@ threshold_percent = 101
             err
         );
     }
+
+    #[tokio::test]
+    async fn test_auto_summary_partial_overrides() {
+        use std::io::Write;
+        use tempfile::NamedTempFile;
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(
+            temp_file,
+            r#"
+[auto_summary]
+enabled = false
+model = {{ provider = "anthropic", id = "claude-haiku-4-5" }}
+"#
+        )
+        .unwrap();
+
+        let loader = SessionConfigLoader::new(test_model(), Some(temp_file.path().to_path_buf()));
+        let config = loader.load().await.unwrap();
+
+        assert!(!config.auto_summary.enabled);
+        assert_eq!(
+            config.auto_summary.model,
+            Some(ModelId::new(
+                ProviderId("anthropic".to_string()),
+                "claude-haiku-4-5"
+            ))
+        );
+        assert_eq!(config.auto_summary.refresh_after_messages, 10);
+    }
 }
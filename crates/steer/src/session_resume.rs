@@ -0,0 +1,256 @@
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use eyre::Result;
+use steer_grpc::AgentClient;
+use steer_grpc::proto::SessionInfo;
+
+/// How the TUI picks an existing session when `--session` is not given.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResumeMode {
+    /// Always start a new session.
+    #[default]
+    New,
+    /// Resume the most recently updated session in any workspace.
+    Latest,
+    /// Resume the latest session for the workspace, or start a new one.
+    ContinueLatest,
+    /// Let the user pick among the workspace's sessions.
+    Pick,
+}
+
+impl ResumeMode {
+    pub fn from_flags(resume_latest: bool, continue_latest: bool, pick: bool) -> Self {
+        if resume_latest {
+            Self::Latest
+        } else if continue_latest {
+            Self::ContinueLatest
+        } else if pick {
+            Self::Pick
+        } else {
+            Self::New
+        }
+    }
+}
+
+/// Resolve the session the TUI should open. `None` means a new session should
+/// be created.
+pub async fn resolve_session(
+    client: &AgentClient,
+    requested: Option<String>,
+    mode: ResumeMode,
+    workspace: &Path,
+) -> Result<Option<String>> {
+    if requested.is_none() && mode == ResumeMode::New {
+        return Ok(None);
+    }
+    if requested.as_deref().is_some_and(|id| id != "latest") {
+        return Ok(requested);
+    }
+
+    let sessions = client
+        .list_sessions()
+        .await
+        .map_err(|e| eyre::eyre!("Failed to list sessions: {}", e))?;
+
+    if requested.is_some() {
+        return latest_session_id(sessions).map(Some);
+    }
+
+    match mode {
+        ResumeMode::Latest => latest_session_id(sessions).map(Some),
+        ResumeMode::ContinueLatest => {
+            Ok(latest_session_for_workspace(sessions, workspace).map(|session| session.id))
+        }
+        ResumeMode::Pick => prompt_for_session(&sessions_for_workspace(sessions, workspace)),
+        ResumeMode::New => Ok(None),
+    }
+}
+
+fn timestamp_key(ts: Option<&prost_types::Timestamp>) -> (i64, i32) {
+    ts.map_or((0, 0), |t| (t.seconds, t.nanos))
+}
+
+/// Sort sessions most recently updated first, falling back to creation time.
+pub fn sort_by_recency(sessions: &mut [SessionInfo]) {
+    sessions.sort_by(|a, b| {
+        let key = |s: &SessionInfo| {
+            (
+                timestamp_key(s.updated_at.as_ref()),
+                timestamp_key(s.created_at.as_ref()),
+            )
+        };
+        key(b).cmp(&key(a))
+    });
+}
+
+/// The most recently updated session, regardless of workspace.
+pub fn latest_session(mut sessions: Vec<SessionInfo>) -> Option<SessionInfo> {
+    sort_by_recency(&mut sessions);
+    sessions.into_iter().next()
+}
+
+/// The session `--session latest` and `--resume-latest` open.
+fn latest_session_id(sessions: Vec<SessionInfo>) -> Result<String> {
+    match latest_session(sessions) {
+        Some(latest) => Ok(latest.id),
+        None => eyre::bail!("No sessions found to resume"),
+    }
+}
+
+/// Sessions recorded against `workspace`, most recent first.
+pub fn sessions_for_workspace(sessions: Vec<SessionInfo>, workspace: &Path) -> Vec<SessionInfo> {
+    let workspace = normalize(workspace);
+    let mut matching: Vec<SessionInfo> = sessions
+        .into_iter()
+        .filter(|session| {
+            session
+                .workspace_path
+                .as_deref()
+                .is_some_and(|path| normalize(Path::new(path)) == workspace)
+        })
+        .collect();
+    sort_by_recency(&mut matching);
+    matching
+}
+
+/// The most recently updated session recorded against `workspace`.
+pub fn latest_session_for_workspace(
+    sessions: Vec<SessionInfo>,
+    workspace: &Path,
+) -> Option<SessionInfo> {
+    sessions_for_workspace(sessions, workspace)
+        .into_iter()
+        .next()
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Ask the user to pick one of `sessions` on the terminal. Returns `None` when
+/// they choose to start a new session instead.
+pub fn prompt_for_session(sessions: &[SessionInfo]) -> Result<Option<String>> {
+    if sessions.is_empty() || !std::io::stdin().is_terminal() {
+        return Ok(None);
+    }
+
+    let mut stdout = std::io::stdout();
+    writeln!(stdout, "Sessions in this workspace:")?;
+    for (index, session) in sessions.iter().enumerate() {
+        let label = session
+            .title
+            .as_deref()
+            .or(session.summary.as_deref())
+            .unwrap_or("(untitled)");
+        writeln!(stdout, "  {:>2}) {}  {label}", index + 1, session.id)?;
+    }
+    write!(
+        stdout,
+        "Resume which session? [1-{}, Enter for new]: ",
+        sessions.len()
+    )?;
+    stdout.flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    Ok(parse_choice(&answer, sessions.len())
+        .and_then(|index| sessions.get(index))
+        .map(|session| session.id.clone()))
+}
+
+fn parse_choice(answer: &str, count: usize) -> Option<usize> {
+    let choice: usize = answer.trim().parse().ok()?;
+    (1..=count).contains(&choice).then(|| choice - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(id: &str, updated: i64, workspace: Option<&str>) -> SessionInfo {
+        SessionInfo {
+            id: id.to_string(),
+            created_at: Some(prost_types::Timestamp {
+                seconds: 0,
+                nanos: 0,
+            }),
+            updated_at: Some(prost_types::Timestamp {
+                seconds: updated,
+                nanos: 0,
+            }),
+            workspace_path: workspace.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sort_by_recency_puts_latest_update_first() {
+        let mut sessions = vec![
+            session("old", 10, None),
+            SessionInfo {
+                updated_at: None,
+                ..session("never-updated", 0, None)
+            },
+            session("new", 30, None),
+            session("mid", 20, None),
+        ];
+
+        sort_by_recency(&mut sessions);
+
+        let ids: Vec<&str> = sessions.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["new", "mid", "old", "never-updated"]);
+    }
+
+    #[test]
+    fn latest_session_for_workspace_ignores_other_projects() {
+        let sessions = vec![
+            session("here-old", 10, Some("/work/here")),
+            session("elsewhere-new", 50, Some("/work/elsewhere")),
+            session("here-new", 20, Some("/work/here")),
+            session("unknown", 60, None),
+        ];
+
+        assert_eq!(
+            latest_session(sessions.clone()).map(|s| s.id).as_deref(),
+            Some("unknown")
+        );
+        assert_eq!(
+            latest_session_for_workspace(sessions.clone(), Path::new("/work/here"))
+                .map(|s| s.id)
+                .as_deref(),
+            Some("here-new")
+        );
+        assert!(latest_session_for_workspace(sessions, Path::new("/work/none")).is_none());
+    }
+
+    #[test]
+    fn resume_latest_flag_resolves_to_most_recently_updated_session() {
+        use crate::cli::Cli;
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(["steer", "--resume-latest"]).unwrap();
+        let mode = ResumeMode::from_flags(cli.resume_latest, cli.continue_session, cli.resume);
+        assert_eq!(mode, ResumeMode::Latest);
+
+        let sessions = vec![
+            session("here-old", 10, Some("/work/here")),
+            session("elsewhere-new", 50, Some("/work/elsewhere")),
+            session("here-new", 20, Some("/work/here")),
+        ];
+        assert_eq!(latest_session_id(sessions).unwrap(), "elsewhere-new");
+        assert!(latest_session_id(Vec::new()).is_err());
+
+        assert!(Cli::try_parse_from(["steer", "--resume-latest", "--continue"]).is_err());
+        assert!(Cli::try_parse_from(["steer", "--resume-latest", "--session", "abc"]).is_err());
+    }
+
+    #[test]
+    fn parse_choice_accepts_only_listed_entries() {
+        assert_eq!(parse_choice("2\n", 3), Some(1));
+        assert_eq!(parse_choice("", 3), None);
+        assert_eq!(parse_choice("0", 3), None);
+        assert_eq!(parse_choice("4", 3), None);
+        assert_eq!(parse_choice("abc", 3), None);
+    }
+}
@@ -110,7 +110,10 @@ pub async fn emit_startup_event(context: StartupTelemetryContext, prefs: Telemet
         metadata: HashMap::new(),
     };
 
-    let client = match Client::builder().timeout(Duration::from_secs(2)).build() {
+    let client = match steer_core::http::client_builder()
+        .timeout(Duration::from_secs(2))
+        .build()
+    {
         Ok(client) => client,
         Err(err) => {
             tracing::debug!(target: "steer::telemetry", error = %err, "skipping startup telemetry: request client unavailable");